@@ -0,0 +1,83 @@
+//! Benchmarks for `GameStateCell` lock hold time with a large state.
+//!
+//! Run with: cargo bench --bench game_state_cell
+//!
+//! A background thread saves a 4 MB `Vec<u8>` state in a loop while the
+//! measured thread reads the cell's frame, so the reported time is dominated
+//! by how long the reader waits for the writer's lock. Writing the state
+//! inside the lock (through `data()`) is compared with staging it through
+//! `begin_save` and committing only the swap.
+
+// Allow benchmark-specific patterns
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use fortress_rollback::{Frame, GameStateCell};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const STATE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Rewrites the saved state in place while holding the cell's lock.
+fn write_inside_lock(cell: &GameStateCell<Vec<u8>>, source: &[u8]) {
+    if let Some(mut accessor) = cell.data() {
+        if let Some(state) = accessor.as_mut_dangerous() {
+            state.copy_from_slice(source);
+        }
+    }
+}
+
+/// Copies the state outside the lock and commits only the swap.
+fn stage_and_commit(cell: &GameStateCell<Vec<u8>>, source: &[u8]) {
+    let mut guard = cell.begin_save(Frame::new(1));
+    guard.write(source.to_vec());
+    black_box(guard.commit(None));
+}
+
+fn bench_reader_under_save(
+    c: &mut Criterion,
+    name: &str,
+    writer: fn(&GameStateCell<Vec<u8>>, &[u8]),
+) {
+    let cell = GameStateCell::<Vec<u8>>::default();
+    cell.save(Frame::new(1), Some(vec![0; STATE_BYTES]), None);
+    let stop = Arc::new(AtomicBool::new(false));
+    let handle = {
+        let cell = cell.clone();
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let source = vec![7; STATE_BYTES];
+            while !stop.load(Ordering::Relaxed) {
+                writer(&cell, &source);
+            }
+        })
+    };
+
+    c.bench_function(name, |b| b.iter(|| black_box(cell.frame())));
+
+    stop.store(true, Ordering::Relaxed);
+    handle.join().unwrap();
+}
+
+fn bench_game_state_cell_4mb(c: &mut Criterion) {
+    bench_reader_under_save(
+        c,
+        "GameStateCell/4MB/reader_frame_while_writing_inside_lock",
+        write_inside_lock,
+    );
+    bench_reader_under_save(
+        c,
+        "GameStateCell/4MB/reader_frame_while_begin_save_commit",
+        stage_and_commit,
+    );
+}
+
+criterion_group!(benches, bench_game_state_cell_4mb);
+criterion_main!(benches);
@@ -117,7 +117,7 @@ fn handle_requests(requests: impl IntoIterator<Item = FortressRequest<BenchConfi
             FortressRequest::LoadGameState { cell, .. } => {
                 black_box(cell.load());
             },
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 black_box(inputs);
             },
         }
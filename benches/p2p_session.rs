@@ -104,7 +104,7 @@ fn bench_advance_frame_no_rollback(c: &mut Criterion) {
                     // Process requests (minimal work)
                     for request in requests {
                         match request {
-                            FortressRequest::AdvanceFrame { inputs } => {
+                            FortressRequest::AdvanceFrame { inputs, .. } => {
                                 black_box(inputs);
                             },
                             FortressRequest::SaveGameState { cell, frame } => {
@@ -85,7 +85,7 @@ pub fn handle_requests(&mut self, requests: RequestVec<FortressConfig>, in_locks
                     }
                     self.save_game_state(cell, frame);
                 },
-                FortressRequest::AdvanceFrame { inputs } => self.advance_frame(inputs),
+                FortressRequest::AdvanceFrame { inputs, .. } => self.advance_frame(inputs),
             }
         }
     }
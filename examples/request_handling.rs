@@ -148,7 +148,7 @@ fn handle_requests_manual(requests: RequestVec<GameConfig>, game_state: &mut Gam
                 }
             },
 
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 // Apply inputs to advance the game
                 game_state.update(inputs.as_slice());
                 println!("Advanced to frame {}", game_state.frame);
@@ -216,7 +216,7 @@ fn basic_sync_test() -> Result<(), FortressError> {
                         game_state = loaded_state;
                     }
                 },
-                FortressRequest::AdvanceFrame { inputs } => {
+                FortressRequest::AdvanceFrame { inputs, .. } => {
                     // Advance game state with the provided inputs
                     game_state.advance(&inputs);
                 },
@@ -297,7 +297,7 @@ struct BadState {
                                 game_state = loaded;
                             }
                         },
-                        FortressRequest::AdvanceFrame { inputs } => {
+                        FortressRequest::AdvanceFrame { inputs, .. } => {
                             game_state.advance(&inputs);
                         },
                     }
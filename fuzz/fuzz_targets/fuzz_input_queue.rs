@@ -123,7 +123,7 @@ impl Config for TestConfig {
                                     current_state = state;
                                 }
                             },
-                            FortressRequest::AdvanceFrame { inputs } => {
+                            FortressRequest::AdvanceFrame { inputs, .. } => {
                                 // Update state based on inputs
                                 for (input, _status) in inputs {
                                     current_state.push(input);
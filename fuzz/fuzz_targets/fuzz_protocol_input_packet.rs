@@ -3,7 +3,8 @@
 //! This target feeds arbitrary frame numbers, ack frames, status vectors, and
 //! compressed payload bytes into the same internal `UdpProtocol::on_input` path
 //! used for received input packets. The safety contract is no panic, bounded
-//! decode/history growth, and clean rejection of malformed packets.
+//! decode/history growth, clean rejection of malformed packets, and no ack
+//! releasing a pending frame beyond the sent prefix.
 
 #![no_main]
 
@@ -24,6 +25,7 @@ struct ProtocolInputPacket {
     peer_connect_status: Vec<StatusInput>,
     bytes: Vec<u8>,
     pending_frames: Vec<i32>,
+    sent_frames: u8,
 }
 
 fuzz_target!(|packet: ProtocolInputPacket| {
@@ -42,5 +44,6 @@ struct ProtocolInputPacket {
         &statuses,
         &packet.bytes,
         &packet.pending_frames,
+        usize::from(packet.sent_frames),
     );
 });
@@ -37,7 +37,7 @@ struct FuzzConfig {
     // Test InputQueueConfig with arbitrary queue_length
     // Clamp to valid range to avoid expected panics during init-time validation
     let queue_length = (config.queue_length as usize).clamp(2, 1024);
-    let queue_config = InputQueueConfig { queue_length };
+    let queue_config = InputQueueConfig { queue_length, ..InputQueueConfig::default() };
 
     // Test validation - should return Ok/Err without panicking
     let _validation_result = queue_config.validate();
@@ -186,3 +186,97 @@ fn test_concurrent_access_bounded() {
         );
     });
 }
+
+/// Test a staged save committing while another thread loads.
+///
+/// The reader must see either the previous state with its frame and checksum
+/// or the committed one, never a mix: the commit swaps all three under a
+/// single lock acquisition.
+#[test]
+fn test_begin_save_commit_with_concurrent_load() {
+    loom::model(|| {
+        let cell: Arc<GameStateCell<u64>> = Arc::new(GameStateCell::default());
+        cell.save(Frame::new(1), Some(10), Some(1));
+        let cell_writer = cell.clone();
+        let cell_reader = cell.clone();
+
+        let writer = thread::spawn(move || {
+            let mut guard = cell_writer.begin_save(Frame::new(2));
+            guard.write(20);
+            assert!(guard.commit(Some(2)));
+        });
+
+        let reader = thread::spawn(move || {
+            let loaded = cell_reader.load();
+            let frame = cell_reader.frame();
+            (loaded, frame)
+        });
+
+        writer.join().unwrap();
+        let (loaded, frame) = reader.join().unwrap();
+
+        assert!(loaded == Some(10) || loaded == Some(20), "got {:?}", loaded);
+        // The frame is read after the state, so it can never lag behind it.
+        if loaded == Some(20) {
+            assert_eq!(frame, Frame::new(2));
+        }
+        assert_eq!(cell.load(), Some(20));
+        assert_eq!(cell.checksum(), Some(2));
+    });
+}
+
+/// Test two staged saves racing to commit.
+#[test]
+fn test_concurrent_begin_save_commits() {
+    loom::model(|| {
+        let cell: Arc<GameStateCell<u64>> = Arc::new(GameStateCell::default());
+        let cell1 = cell.clone();
+        let cell2 = cell.clone();
+
+        let t1 = thread::spawn(move || {
+            let mut guard = cell1.begin_save(Frame::new(1));
+            guard.write(100);
+            guard.commit(Some(1))
+        });
+        let t2 = thread::spawn(move || {
+            let mut guard = cell2.begin_save(Frame::new(2));
+            guard.write(200);
+            guard.commit(Some(2))
+        });
+
+        assert!(t1.join().unwrap());
+        assert!(t2.join().unwrap());
+
+        let pair = (cell.frame(), cell.load(), cell.checksum());
+        assert!(
+            pair == (Frame::new(1), Some(100), Some(1))
+                || pair == (Frame::new(2), Some(200), Some(2)),
+            "torn commit: {:?}",
+            pair
+        );
+    });
+}
+
+/// Test that a shared load survives a concurrent overwrite.
+///
+/// The handle from `load_shared` keeps the state it was taken from, whichever
+/// save lands first.
+#[test]
+fn test_load_shared_with_concurrent_save() {
+    loom::model(|| {
+        let cell: Arc<GameStateCell<u64>> = Arc::new(GameStateCell::default());
+        cell.save(Frame::new(1), Some(10), None);
+        let cell_writer = cell.clone();
+        let cell_reader = cell.clone();
+
+        let writer = thread::spawn(move || {
+            cell_writer.save(Frame::new(2), Some(20), None);
+        });
+        let reader = thread::spawn(move || cell_reader.load_shared());
+
+        writer.join().unwrap();
+        let shared = reader.join().unwrap().expect("a state is always saved");
+        assert!(*shared == 10 || *shared == 20, "got {}", shared);
+        assert_eq!(*cell.load_shared().unwrap(), 20);
+    });
+}
@@ -414,6 +414,9 @@ TIER1_PROOFS=(
     "proof_frame_to_option"
     "proof_frame_from_option"
     "proof_frame_ordering"
+    "proof_frame_from_usize_total"
+    "proof_frame_try_from_u64_total"
+    "proof_frame_saturating_from_usize_total"
     # Varint proofs (src/rle.rs)
     "proof_varint_encoded_len_correct"
     "proof_varint_encode_single_byte"
@@ -295,11 +295,13 @@ ResetPrediction ==
 (***************************************************************************)
 (* Action: Set frame delay                                                  *)
 (*                                                                         *)
-(* Initial setup may choose any valid delay. Mid-session decreases are      *)
-(* rejected as no-ops. Mid-session increases are modeled one frame at a     *)
-(* time, filling the new gap with the most recent confirmed input so frame  *)
-(* sequence remains contiguous. Repeating this action models larger         *)
-(* production delta increases.                                              *)
+(* Initial setup may choose any valid delay. Mid-session increases are      *)
+(* modeled one frame at a time, filling the new gap with the most recent    *)
+(* confirmed input so frame sequence remains contiguous. Mid-session        *)
+(* decreases are modeled one frame at a time too, dropping the newest       *)
+(* input while it is unread and not the only one queued; otherwise they are *)
+(* rejected as no-ops. Repeating this action models larger production      *)
+(* deltas.                                                                  *)
 (***************************************************************************)
 SetFrameDelay(newDelay) ==
     /\ newDelay \in 0..(QUEUE_LENGTH - 1)
@@ -333,8 +335,27 @@ SetFrameDelay(newDelay) ==
           /\ newDelay > frameDelay
           /\ length = QUEUE_LENGTH
           /\ UNCHANGED vars
+       \/ /\ ~frozen
+          /\ lastAddedFrame # NULL_FRAME
+          /\ newDelay = frameDelay - 1
+          /\ length > 1
+          /\ \/ lastRequestedFrame = NULL_FRAME
+             \/ lastRequestedFrame < lastAddedFrame
+          /\ inputs' = [inputs EXCEPT ![PrevIndex(head)] = BlankInput]
+          /\ lastConfirmedInput' = inputs[PrevIndex(PrevIndex(head))]
+          /\ head' = PrevIndex(head)
+          /\ length' = length - 1
+          /\ tail' = tail
+          /\ lastAddedFrame' = lastAddedFrame - 1
+          /\ frameDelay' = newDelay
+          /\ UNCHANGED <<lastRequestedFrame, firstIncorrectFrame, frozen,
+                         freezeFrame>>
        \/ /\ lastAddedFrame # NULL_FRAME
           /\ newDelay < frameDelay
+          /\ \/ newDelay # frameDelay - 1
+             \/ length <= 1
+             \/ /\ lastRequestedFrame # NULL_FRAME
+                /\ lastRequestedFrame >= lastAddedFrame
           /\ UNCHANGED vars
 
 (***************************************************************************)
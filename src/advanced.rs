@@ -0,0 +1,224 @@
+//! Read-only views of session internals for engine integrators.
+//!
+//! Enabled by the `advanced` cargo feature. The views borrow a running
+//! session's input queues, sync layer and time synchronization and expose a
+//! small set of accessors for tooling such as prediction visualizers. None of
+//! them can change session state.
+//!
+//! # Stability
+//!
+//! Unlike the hidden `__internal` module, everything here follows semver: a
+//! breaking change to a view's name, signature or documented meaning waits for
+//! a major release. The behavior each accessor documents is pinned by the
+//! `advanced_views` integration tests. The internals behind the views may
+//! change freely; the views are what stays.
+//!
+//! # Example
+//!
+//! ```
+//! # use fortress_rollback::{Config, PlayerHandle, SessionBuilder};
+//! # struct GameConfig;
+//! # impl Config for GameConfig {
+//! #     type Input = u8;
+//! #     type State = u8;
+//! #     type Address = std::net::SocketAddr;
+//! # }
+//! let session = SessionBuilder::<GameConfig>::new()
+//!     .start_synctest_session()?;
+//! let layer = session.sync_layer_view();
+//! assert_eq!(layer.num_players(), 2);
+//! let queue = layer.input_queue(PlayerHandle::new(0)).expect("player 0 exists");
+//! assert_eq!(queue.frames(), None, "no input added yet");
+//! # Ok::<(), fortress_rollback::FortressError>(())
+//! ```
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::input_queue::InputQueue;
+use crate::sync_layer::SyncLayer;
+use crate::time_sync::TimeSync;
+use crate::{Config, Frame, PlayerHandle};
+
+/// A read-only view of one player's input queue.
+///
+/// Obtained from [`SyncLayerView::input_queue`]. The queue only holds inputs
+/// the session has received or added; predictions are never stored in it.
+pub struct InputQueueView<'a, T: Config> {
+    queue: &'a InputQueue<T>,
+}
+
+impl<T: Config> Clone for InputQueueView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Config> Copy for InputQueueView<'_, T> {}
+
+impl<T: Config> fmt::Debug for InputQueueView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputQueueView")
+            .field("frames", &self.frames())
+            .field("first_incorrect_frame", &self.first_incorrect_frame())
+            .field("frame_delay", &self.frame_delay())
+            .finish()
+    }
+}
+
+impl<T: Config> InputQueueView<'_, T> {
+    /// The contiguous range of frames whose inputs the queue still holds,
+    /// oldest first, or `None` before the first input arrives.
+    ///
+    /// # Stability
+    ///
+    /// The range always ends at [`last_confirmed_frame`](Self::last_confirmed_frame)
+    /// and never spans more frames than the queue length the session was built
+    /// with. How early it starts depends on how far the session has confirmed
+    /// and may change between releases.
+    #[must_use]
+    pub fn frames(&self) -> Option<RangeInclusive<Frame>> {
+        self.queue
+            .retained_input_range()
+            .map(|range| range.first..=range.last)
+    }
+
+    /// The newest frame whose input the queue holds, or `None` before the
+    /// first input arrives. For a remote player this is the newest confirmed
+    /// input; for a local player it includes the input delay.
+    ///
+    /// # Stability
+    ///
+    /// Never decreases while the player stays connected.
+    #[must_use]
+    pub fn last_confirmed_frame(&self) -> Option<Frame> {
+        Some(self.queue.last_added_frame()).filter(|frame| frame.is_valid())
+    }
+
+    /// The earliest frame at which a received input contradicted the
+    /// prediction the session simulated, or `None` when no misprediction is
+    /// pending.
+    ///
+    /// # Stability
+    ///
+    /// A misprediction stays pending until the session's next
+    /// `advance_frame` rolls back to it, so outside `advance_frame` this is
+    /// set only between receiving the input and the next advance.
+    #[must_use]
+    pub fn first_incorrect_frame(&self) -> Option<Frame> {
+        Some(self.queue.first_incorrect_frame()).filter(|frame| !frame.is_null())
+    }
+
+    /// The input delay, in frames, applied to inputs added to this queue.
+    /// Always zero for remote players.
+    #[must_use]
+    pub fn frame_delay(&self) -> usize {
+        self.queue.frame_delay()
+    }
+}
+
+/// A read-only view of a session's sync layer: its frame counters and
+/// per-player input queues.
+///
+/// Obtained from `P2PSession::sync_layer_view` or
+/// `SyncTestSession::sync_layer_view`.
+pub struct SyncLayerView<'a, T: Config> {
+    layer: &'a SyncLayer<T>,
+}
+
+impl<T: Config> Clone for SyncLayerView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Config> Copy for SyncLayerView<'_, T> {}
+
+impl<T: Config> fmt::Debug for SyncLayerView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncLayerView")
+            .field("current_frame", &self.current_frame())
+            .field("last_confirmed_frame", &self.last_confirmed_frame())
+            .field("last_saved_frame", &self.last_saved_frame())
+            .field("num_players", &self.num_players())
+            .finish()
+    }
+}
+
+impl<'a, T: Config> SyncLayerView<'a, T> {
+    pub(crate) fn new(layer: &'a SyncLayer<T>) -> Self {
+        Self { layer }
+    }
+
+    /// The frame the session simulates next; equal to the session's
+    /// `current_frame`.
+    #[must_use]
+    pub fn current_frame(&self) -> Frame {
+        self.layer.current_frame()
+    }
+
+    /// The newest frame for which every player's input is confirmed, or
+    /// [`Frame::NULL`] before the first one.
+    ///
+    /// # Stability
+    ///
+    /// Never exceeds [`current_frame`](Self::current_frame).
+    #[must_use]
+    pub fn last_confirmed_frame(&self) -> Frame {
+        self.layer.last_confirmed_frame()
+    }
+
+    /// The newest frame whose game state the session has saved, or
+    /// [`Frame::NULL`] before the first save.
+    ///
+    /// # Stability
+    ///
+    /// Never exceeds [`current_frame`](Self::current_frame).
+    #[must_use]
+    pub fn last_saved_frame(&self) -> Frame {
+        self.layer.last_saved_frame()
+    }
+
+    /// The number of player input queues, one per player slot.
+    #[must_use]
+    pub fn num_players(&self) -> usize {
+        self.layer.num_players()
+    }
+
+    /// The input queue of the player at `handle`, or `None` if `handle` is not
+    /// a player slot of this session (spectator handles included).
+    #[must_use]
+    pub fn input_queue(&self, handle: PlayerHandle) -> Option<InputQueueView<'a, T>> {
+        self.layer
+            .input_queue(handle)
+            .map(|queue| InputQueueView { queue })
+    }
+}
+
+/// A read-only view of the time synchronization with one remote endpoint.
+///
+/// Obtained from `P2PSession::time_sync_view`.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeSyncView<'a> {
+    time_sync: &'a TimeSync,
+}
+
+impl<'a> TimeSyncView<'a> {
+    pub(crate) fn new(time_sync: &'a TimeSync) -> Self {
+        Self { time_sync }
+    }
+
+    /// The average number of frames this session runs ahead of the remote
+    /// endpoint over the recent window; negative when it runs behind.
+    ///
+    /// # Stability
+    ///
+    /// Zero until frame advantages have been measured, and always computed
+    /// with integer arithmetic, so two builds given the same measurements
+    /// report the same value. The window length follows the session's
+    /// `TimeSyncConfig`.
+    #[must_use]
+    pub fn average_frame_advantage(&self) -> i32 {
+        self.time_sync.average_frame_advantage()
+    }
+}
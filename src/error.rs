@@ -118,6 +118,30 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// An integer could not be converted into a [`Frame`] without wrapping.
+///
+/// Returned by [`Frame::try_from_u64`] and the `TryFrom<u64>` impl for
+/// [`Frame`]. Converts into [`FortressError::FrameValueTooLarge`], so `?`
+/// works in functions returning [`FortressError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameConversionError {
+    /// The value that exceeds `i32::MAX`.
+    pub value: u64,
+}
+
+impl Display for FrameConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame value {} exceeds i32::MAX ({})",
+            self.value,
+            i32::MAX
+        )
+    }
+}
+
+impl Error for FrameConversionError {}
+
 /// Represents why a frame was invalid.
 ///
 /// Using an enum instead of String allows for zero-allocation error construction
@@ -176,6 +200,14 @@ pub enum InvalidFrameReason {
         /// The last frame available in the replay.
         last_frame: Frame,
     },
+    /// [`SyncTestSession::step_back`] was asked to go further back than the
+    /// session keeps saved states and inputs for.
+    ///
+    /// [`SyncTestSession::step_back`]: crate::SyncTestSession::step_back
+    BeforeOldestSavedFrame {
+        /// The oldest frame the session can step back to.
+        oldest_frame: Frame,
+    },
     /// Custom reason (fallback for API compatibility).
     Custom(&'static str),
 }
@@ -218,11 +250,58 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             Self::ReplayExhausted { last_frame } => {
                 write!(f, "replay exhausted (last frame: {})", last_frame)
             },
+            Self::BeforeOldestSavedFrame { oldest_frame } => {
+                write!(
+                    f,
+                    "frame is before the oldest saved frame (oldest: {})",
+                    oldest_frame
+                )
+            },
             Self::Custom(s) => write!(f, "{}", s),
         }
     }
 }
 
+/// Represents why a session rejected a local input.
+///
+/// Returned in [`FortressError::LocalInputRejected`]. The input was never queued,
+/// so the local player's input for that frame would otherwise be a prediction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalInputRejectReason {
+    /// The input was stamped for a different frame than the one the session is at.
+    FrameMismatch {
+        /// The frame the session is at.
+        current_frame: Frame,
+    },
+    /// The player's input queue did not accept the input, because it would not
+    /// directly follow the last queued input or the queue is out of capacity.
+    /// The violation observer receives the details.
+    QueueRejected {
+        /// The last frame queued for the player, or [`Frame::NULL`] if none.
+        last_added_frame: Frame,
+    },
+    /// The session has no input queue for the player.
+    MissingQueue,
+}
+
+impl Display for LocalInputRejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FrameMismatch { current_frame } => {
+                write!(f, "the session is at frame {}", current_frame)
+            },
+            Self::QueueRejected { last_added_frame } => {
+                write!(
+                    f,
+                    "the input queue refused it (last queued frame: {})",
+                    last_added_frame
+                )
+            },
+            Self::MissingQueue => write!(f, "the player has no input queue"),
+        }
+    }
+}
+
 /// Represents why an RLE decode operation failed.
 ///
 /// Using an enum instead of String allows for zero-allocation error construction
@@ -348,6 +427,21 @@ pub enum DeltaDecodeReason {
         /// Maximum allowed number of decoded frame buffers.
         max: usize,
     },
+    /// The reference length is not a non-zero multiple of the slot count, so
+    /// keyframe-format inputs cannot be split into per-player slots.
+    SlotWidthMismatch {
+        /// The length of the reference buffer.
+        reference_len: usize,
+        /// The number of per-player slots in each input.
+        slots: usize,
+    },
+    /// A keyframe-format slot mask sets a bit for a slot that does not exist.
+    SlotMaskOutOfRange {
+        /// The slot index named by the mask.
+        slot: usize,
+        /// The number of per-player slots in each input.
+        slots: usize,
+    },
     /// The decoder could not reserve memory for decoded delta output.
     AllocationFailed {
         /// Static context for the allocation site.
@@ -393,6 +487,19 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     frame_count, max
                 )
             },
+            Self::SlotWidthMismatch {
+                reference_len,
+                slots,
+            } => {
+                write!(
+                    f,
+                    "reference length {} cannot be split into {} slots",
+                    reference_len, slots
+                )
+            },
+            Self::SlotMaskOutOfRange { slot, slots } => {
+                write!(f, "slot mask names slot {} of {}", slot, slots)
+            },
             Self::AllocationFailed {
                 context,
                 requested_elements,
@@ -467,6 +574,14 @@ pub enum InternalErrorKind {
         /// Serialized length of the reference frame.
         reference_len: usize,
     },
+    /// Keyframe encode operation failed because the reference frame could not
+    /// be split into equally sized per-player slots.
+    KeyframeEncodeSlotWidthMismatch {
+        /// Serialized length of the reference frame.
+        reference_len: usize,
+        /// Number of per-player slots requested.
+        slots: usize,
+    },
     /// Input serialization produced a per-player byte width different from
     /// `Config::Input::default()`.
     InputEncodeLengthMismatch {
@@ -544,6 +659,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     input_len, reference_len
                 )
             },
+            Self::KeyframeEncodeSlotWidthMismatch {
+                reference_len,
+                slots,
+            } => {
+                write!(
+                    f,
+                    "keyframe encode failed: reference length {} cannot be split into {} slots",
+                    reference_len, slots
+                )
+            },
             Self::InputEncodeLengthMismatch {
                 player,
                 input_len,
@@ -579,6 +704,11 @@ pub enum InvalidRequestKind {
         /// The handle that is already in use.
         handle: PlayerHandle,
     },
+    /// The stable player id is already assigned to another handle.
+    PlayerIdInUse {
+        /// The handle that already carries the id.
+        handle: PlayerHandle,
+    },
     /// The player handle does not refer to a local player.
     NotLocalPlayer {
         /// The handle that is not a local player.
@@ -683,11 +813,12 @@ pub enum InvalidRequestKind {
         /// The maximum allowed delay.
         max_delay: usize,
     },
-    /// Decreasing input delay mid-session is not supported.
+    /// Decreasing input delay mid-session is not supported here.
     ///
-    /// Lowering the delay would require dropping inputs that have already been
-    /// queued (and possibly already sent to remote peers). Mid-session delay
-    /// changes may only increase the delay, not decrease it.
+    /// Lowering the delay drops the newest queued inputs. That is refused when
+    /// they may already have been sent to remote peers or spectators, when
+    /// one of them was already read, or when no older input would be left
+    /// queued.
     InputDelayDecreaseUnsupported {
         /// The current input delay.
         current: usize,
@@ -736,6 +867,15 @@ pub enum InvalidRequestKind {
         /// The requested length.
         length: usize,
     },
+    /// Input queue length cannot hold `max_prediction + input_delay` frames.
+    ///
+    /// See [`InputQueueConfig::min_queue_length`](crate::InputQueueConfig::min_queue_length).
+    QueueLengthBelowMinimum {
+        /// The configured length.
+        length: usize,
+        /// The smallest length the other settings allow.
+        minimum: usize,
+    },
     /// Event queue size is too small (minimum is 10).
     EventQueueSizeTooSmall {
         /// The requested size.
@@ -778,6 +918,21 @@ pub enum InvalidRequestKind {
         /// The maximum prediction window.
         max_prediction: usize,
     },
+    /// A local input delay is not below the prediction window.
+    ///
+    /// With a delay of `max_prediction` frames or more, every local input is
+    /// applied further ahead than any remote input is ever predicted, so the
+    /// session hides more latency with delay than it could roll back. This is
+    /// almost always a mix-up of the two settings. Lockstep sessions
+    /// (`max_prediction == 0`) are exempt, and
+    /// [`crate::SessionBuilder::with_input_delay_beyond_prediction`] opts in
+    /// deliberately.
+    InputDelayBeyondPrediction {
+        /// The requested input delay.
+        delay: usize,
+        /// The maximum prediction window.
+        max_prediction: usize,
+    },
     /// Max frames behind is invalid.
     MaxFramesBehindInvalid {
         /// The requested value.
@@ -835,6 +990,90 @@ pub enum InvalidRequestKind {
         operation: &'static str,
     },
 
+    // Match pause errors
+    /// A match pause or resume negotiation is already open on this peer.
+    MatchPauseInProgress,
+    /// [`crate::P2PSession::request_match_pause`] was called while the match is
+    /// already paused (or a pause is committed but not yet reached).
+    MatchAlreadyPaused,
+    /// [`crate::P2PSession::request_match_resume`] was called before the match
+    /// reached an agreed pause.
+    MatchNotPaused,
+
+    // Spectator promotion errors
+    /// `P2PSession::promote_spectator` (hot-join feature) was given a handle
+    /// that is not a registered spectator.
+    NotSpectator {
+        /// The handle that is not a spectator.
+        handle: PlayerHandle,
+    },
+    /// `P2PSession::promote_spectator` (hot-join feature) targeted a remote
+    /// player slot that is still connected.
+    PlayerNotDisconnected {
+        /// The connected player handle.
+        handle: PlayerHandle,
+    },
+
+    // Session lifetime errors
+    /// The session reached the frame limit set with
+    /// [`crate::SessionBuilder::with_max_session_frames`] and simulates no
+    /// further frames.
+    SessionFrameLimitReached {
+        /// The configured limit; the session's final frame.
+        frame: Frame,
+    },
+
+    // Frame tag errors
+    /// [`crate::P2PSession::send_frame_tag`] was called without
+    /// [`crate::ProtocolConfig::frame_tags`] enabled.
+    FrameTagsDisabled,
+    /// [`crate::P2PSession::send_frame_tag`] was given a frame that does not
+    /// follow the previously tagged frame.
+    FrameTagNotIncreasing {
+        /// The frame that was passed.
+        frame: Frame,
+        /// The most recently tagged frame.
+        last_tagged: Frame,
+    },
+
+    // Reliable channel errors
+    /// [`crate::P2PSession::send_reliable`] was given a payload larger than
+    /// [`crate::ProtocolConfig::max_reliable_payload`].
+    ReliablePayloadTooLarge {
+        /// The payload's length in bytes.
+        len: usize,
+        /// The configured maximum.
+        max: usize,
+    },
+    /// [`crate::P2PSession::send_reliable`] was called while
+    /// [`crate::ProtocolConfig::reliable_window`] messages to the peer were
+    /// still unacknowledged.
+    ReliableWindowFull {
+        /// The configured window.
+        window: usize,
+    },
+
+    // Pre-established connection errors
+    /// [`crate::SessionBuilder::with_pre_established`] was given a connection
+    /// ID that is `0` or has its low 16 bits all zero, which the wire format
+    /// reserves.
+    InvalidConnectionId {
+        /// The rejected connection ID.
+        conn_id: u32,
+    },
+
+    // State slot errors
+    /// `propose_state_restore` was called on a
+    /// [`crate::P2PSession`] with remote players or spectators; a save-state
+    /// can only be restored while every player is local.
+    StateRestoreRequiresLocalSession,
+    /// `propose_state_restore` was given a [`crate::StateSlot`] written in a
+    /// format this build does not restore.
+    UnsupportedStateSlotVersion {
+        /// The slot's format version.
+        version: u16,
+    },
+
     /// Custom error (fallback for API compatibility).
     Custom(&'static str),
 }
@@ -845,6 +1084,13 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             Self::PlayerHandleInUse { handle } => {
                 write!(f, "player handle {} is already in use", handle.as_usize())
             },
+            Self::PlayerIdInUse { handle } => {
+                write!(
+                    f,
+                    "player id is already assigned to player handle {}",
+                    handle.as_usize()
+                )
+            },
             Self::NotLocalPlayer { handle } => {
                 write!(
                     f,
@@ -1000,6 +1246,13 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     length
                 )
             },
+            Self::QueueLengthBelowMinimum { length, minimum } => {
+                write!(
+                    f,
+                    "input queue length {} is below the minimum {} for max_prediction + input_delay",
+                    length, minimum
+                )
+            },
             Self::EventQueueSizeTooSmall { size } => {
                 write!(f, "event queue size {} is too small (minimum is 10)", size)
             },
@@ -1031,6 +1284,17 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     check_dist, max_prediction
                 )
             },
+            Self::InputDelayBeyondPrediction {
+                delay,
+                max_prediction,
+            } => {
+                write!(
+                    f,
+                    "input delay {} is not below prediction window {}; lower the delay, \
+                     raise the window, or opt in with with_input_delay_beyond_prediction",
+                    delay, max_prediction
+                )
+            },
             Self::MaxFramesBehindInvalid { value, buffer_size } => {
                 write!(
                     f,
@@ -1085,6 +1349,60 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     operation
                 )
             },
+            Self::MatchPauseInProgress => {
+                write!(
+                    f,
+                    "a match pause or resume negotiation is already in progress"
+                )
+            },
+            Self::MatchAlreadyPaused => write!(f, "the match is already paused"),
+            Self::MatchNotPaused => write!(f, "the match is not paused"),
+            Self::NotSpectator { handle } => {
+                write!(f, "handle {} is not a spectator", handle.as_usize())
+            },
+            Self::PlayerNotDisconnected { handle } => {
+                write!(f, "player {} is not disconnected", handle.as_usize())
+            },
+            Self::SessionFrameLimitReached { frame } => {
+                write!(f, "the session reached its frame limit {}", frame.as_i32())
+            },
+            Self::FrameTagsDisabled => write!(f, "frame tags are not enabled"),
+            Self::FrameTagNotIncreasing { frame, last_tagged } => {
+                write!(
+                    f,
+                    "frame tag for frame {} does not follow the last tagged frame {}",
+                    frame.as_i32(),
+                    last_tagged.as_i32()
+                )
+            },
+            Self::ReliablePayloadTooLarge { len, max } => {
+                write!(
+                    f,
+                    "reliable message payload of {} bytes exceeds the maximum of {}",
+                    len, max
+                )
+            },
+            Self::ReliableWindowFull { window } => {
+                write!(
+                    f,
+                    "all {} reliable messages in the window are unacknowledged",
+                    window
+                )
+            },
+            Self::InvalidConnectionId { conn_id } => {
+                write!(
+                    f,
+                    "connection ID {:#010x} is reserved (zero, or zero low 16 bits)",
+                    conn_id
+                )
+            },
+            Self::StateRestoreRequiresLocalSession => write!(
+                f,
+                "state slots can only be restored while every player is local"
+            ),
+            Self::UnsupportedStateSlotVersion { version } => {
+                write!(f, "state slot format version {} is not supported", version)
+            },
             Self::Custom(s) => write!(f, "{}", s),
         }
     }
@@ -1111,6 +1429,10 @@ pub enum SerializationErrorKind {
         /// Maximum decodable byte length.
         max: usize,
     },
+    /// `Config::Input::default()` does not decode back to itself, or
+    /// re-encodes to different bytes. Both peers start their input delta
+    /// chain from the encoded default, so such a type would desync.
+    DefaultInputRoundTripMismatch,
     /// Custom error (fallback for API compatibility).
     Custom(&'static str),
 }
@@ -1137,6 +1459,12 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     frame_len, max
                 )
             },
+            Self::DefaultInputRoundTripMismatch => {
+                write!(
+                    f,
+                    "Config::Input::default() does not round-trip through its serialized bytes"
+                )
+            },
             Self::Custom(s) => write!(f, "{}", s),
         }
     }
@@ -1160,6 +1488,13 @@ pub enum SocketErrorKind {
         /// The number of attempts made.
         attempts: u8,
     },
+    /// The socket could not send a message, as reported by
+    /// [`NonBlockingSocket::try_send_to`](crate::NonBlockingSocket::try_send_to).
+    SendFailed {
+        /// The I/O error category, such as `NetworkUnreachable` after the
+        /// network interface went away.
+        kind: std::io::ErrorKind,
+    },
     /// Custom error (fallback for API compatibility).
     Custom(&'static str),
 }
@@ -1177,6 +1512,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     port, attempts
                 )
             },
+            Self::SendFailed { kind } => write!(f, "failed to send message: {}", kind),
             Self::Custom(s) => write!(f, "{}", s),
         }
     }
@@ -1333,11 +1669,199 @@ pub enum FortressError {
     /// Frame value is too large to represent.
     ///
     /// This error is returned when converting a `usize` to a `Frame` and the
-    /// value exceeds `i32::MAX`.
+    /// value exceeds `i32::MAX`, or when propagating a
+    /// [`FrameConversionError`] with `?`.
     FrameValueTooLarge {
         /// The value that was too large.
         value: usize,
     },
+    /// A local input was rejected instead of being queued, so it would never
+    /// have reached the simulation.
+    LocalInputRejected {
+        /// The frame the input was for.
+        frame: Frame,
+        /// Why the input was rejected.
+        reason: LocalInputRejectReason,
+    },
+    /// The local session ran further ahead of its slowest connected peer than
+    /// [`TimeSyncConfig::max_frames_ahead`](crate::TimeSyncConfig::max_frames_ahead)
+    /// allows, so no frame was simulated.
+    ///
+    /// Unlike [`FortressError::PredictionThreshold`], no input is missing: the
+    /// peer is simply slower. The network was still polled; call
+    /// `advance_frame` again once the peer catches up.
+    AheadOfRemotes {
+        /// How many frames ahead of the slowest peer the session is.
+        frames_ahead: u32,
+        /// The configured limit.
+        limit: u32,
+    },
+}
+
+/// The category of a [`FortressError`], independent of its payload.
+///
+/// Mirrors the variants of [`FortressError`] one-to-one so errors can be
+/// counted, deduplicated, or labeled without matching on their payload fields.
+/// Obtain one with [`FortressError::kind`].
+///
+/// Legacy and structured forms of the same error (for example `InvalidFrame`
+/// and `InvalidFrameStructured`) are separate categories, like the variants
+/// they mirror.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorKind {
+    /// [`FortressError::PredictionThreshold`].
+    PredictionThreshold,
+    /// [`FortressError::InvalidRequest`].
+    InvalidRequest,
+    /// [`FortressError::MismatchedChecksum`].
+    MismatchedChecksum,
+    /// [`FortressError::NotSynchronized`].
+    NotSynchronized,
+    /// [`FortressError::SpectatorTooFarBehind`].
+    SpectatorTooFarBehind,
+    /// [`FortressError::SpectatorDivergence`].
+    SpectatorDivergence,
+    /// [`FortressError::InvalidFrame`].
+    InvalidFrame,
+    /// [`FortressError::InvalidFrameStructured`].
+    InvalidFrameStructured,
+    /// [`FortressError::InvalidPlayerHandle`].
+    InvalidPlayerHandle,
+    /// [`FortressError::MissingInput`].
+    MissingInput,
+    /// [`FortressError::SerializationError`].
+    SerializationError,
+    /// [`FortressError::InternalError`].
+    InternalError,
+    /// [`FortressError::InternalErrorStructured`].
+    InternalErrorStructured,
+    /// [`FortressError::SocketError`].
+    SocketError,
+    /// [`FortressError::InvalidRequestStructured`].
+    InvalidRequestStructured,
+    /// [`FortressError::SerializationErrorStructured`].
+    SerializationErrorStructured,
+    /// [`FortressError::SocketErrorStructured`].
+    SocketErrorStructured,
+    /// [`FortressError::FrameArithmeticOverflow`].
+    FrameArithmeticOverflow,
+    /// [`FortressError::FrameValueTooLarge`].
+    FrameValueTooLarge,
+    /// [`FortressError::LocalInputRejected`].
+    LocalInputRejected,
+    /// [`FortressError::AheadOfRemotes`].
+    AheadOfRemotes,
+}
+
+impl ErrorKind {
+    /// The number of error categories.
+    pub const COUNT: usize = 21;
+
+    /// Every category, in declaration order. Its length is [`Self::COUNT`].
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::PredictionThreshold,
+        Self::InvalidRequest,
+        Self::MismatchedChecksum,
+        Self::NotSynchronized,
+        Self::SpectatorTooFarBehind,
+        Self::SpectatorDivergence,
+        Self::InvalidFrame,
+        Self::InvalidFrameStructured,
+        Self::InvalidPlayerHandle,
+        Self::MissingInput,
+        Self::SerializationError,
+        Self::InternalError,
+        Self::InternalErrorStructured,
+        Self::SocketError,
+        Self::InvalidRequestStructured,
+        Self::SerializationErrorStructured,
+        Self::SocketErrorStructured,
+        Self::FrameArithmeticOverflow,
+        Self::FrameValueTooLarge,
+        Self::LocalInputRejected,
+        Self::AheadOfRemotes,
+    ];
+
+    /// A stable snake_case label for this category, suitable for logging or as
+    /// a metrics key.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PredictionThreshold => "prediction_threshold",
+            Self::InvalidRequest => "invalid_request",
+            Self::MismatchedChecksum => "mismatched_checksum",
+            Self::NotSynchronized => "not_synchronized",
+            Self::SpectatorTooFarBehind => "spectator_too_far_behind",
+            Self::SpectatorDivergence => "spectator_divergence",
+            Self::InvalidFrame => "invalid_frame",
+            Self::InvalidFrameStructured => "invalid_frame_structured",
+            Self::InvalidPlayerHandle => "invalid_player_handle",
+            Self::MissingInput => "missing_input",
+            Self::SerializationError => "serialization_error",
+            Self::InternalError => "internal_error",
+            Self::InternalErrorStructured => "internal_error_structured",
+            Self::SocketError => "socket_error",
+            Self::InvalidRequestStructured => "invalid_request_structured",
+            Self::SerializationErrorStructured => "serialization_error_structured",
+            Self::SocketErrorStructured => "socket_error_structured",
+            Self::FrameArithmeticOverflow => "frame_arithmetic_overflow",
+            Self::FrameValueTooLarge => "frame_value_too_large",
+            Self::LocalInputRejected => "local_input_rejected",
+            Self::AheadOfRemotes => "ahead_of_remotes",
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FortressError {
+    /// The [`ErrorKind`] category of this error, independent of its payload.
+    ///
+    /// Useful for deduplicating, counting, or labeling errors without matching
+    /// on every variant's payload fields:
+    ///
+    /// ```
+    /// use fortress_rollback::{ErrorKind, FortressError};
+    ///
+    /// let mut last_reported: Option<ErrorKind> = None;
+    /// for error in [FortressError::NotSynchronized, FortressError::NotSynchronized] {
+    ///     if last_reported != Some(error.kind()) {
+    ///         last_reported = Some(error.kind());
+    ///         // show `error` to the player once
+    ///     }
+    /// }
+    /// assert_eq!(last_reported, Some(ErrorKind::NotSynchronized));
+    /// ```
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::PredictionThreshold => ErrorKind::PredictionThreshold,
+            Self::InvalidRequest { .. } => ErrorKind::InvalidRequest,
+            Self::MismatchedChecksum { .. } => ErrorKind::MismatchedChecksum,
+            Self::NotSynchronized => ErrorKind::NotSynchronized,
+            Self::SpectatorTooFarBehind => ErrorKind::SpectatorTooFarBehind,
+            Self::SpectatorDivergence { .. } => ErrorKind::SpectatorDivergence,
+            Self::InvalidFrame { .. } => ErrorKind::InvalidFrame,
+            Self::InvalidFrameStructured { .. } => ErrorKind::InvalidFrameStructured,
+            Self::InvalidPlayerHandle { .. } => ErrorKind::InvalidPlayerHandle,
+            Self::MissingInput { .. } => ErrorKind::MissingInput,
+            Self::SerializationError { .. } => ErrorKind::SerializationError,
+            Self::InternalError { .. } => ErrorKind::InternalError,
+            Self::InternalErrorStructured { .. } => ErrorKind::InternalErrorStructured,
+            Self::SocketError { .. } => ErrorKind::SocketError,
+            Self::InvalidRequestStructured { .. } => ErrorKind::InvalidRequestStructured,
+            Self::SerializationErrorStructured { .. } => ErrorKind::SerializationErrorStructured,
+            Self::SocketErrorStructured { .. } => ErrorKind::SocketErrorStructured,
+            Self::FrameArithmeticOverflow { .. } => ErrorKind::FrameArithmeticOverflow,
+            Self::FrameValueTooLarge { .. } => ErrorKind::FrameValueTooLarge,
+            Self::LocalInputRejected { .. } => ErrorKind::LocalInputRejected,
+            Self::AheadOfRemotes { .. } => ErrorKind::AheadOfRemotes,
+        }
+    }
 }
 
 impl Display for FortressError {
@@ -1445,6 +1969,19 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     i32::MAX
                 )
             },
+            Self::LocalInputRejected { frame, reason } => {
+                write!(f, "Local input for frame {} rejected: {}", frame, reason)
+            },
+            Self::AheadOfRemotes {
+                frames_ahead,
+                limit,
+            } => {
+                write!(
+                    f,
+                    "Session is {} frames ahead of the slowest peer, beyond the limit of {}; waiting for it to catch up.",
+                    frames_ahead, limit
+                )
+            },
         }
     }
 }
@@ -1467,6 +2004,16 @@ fn source(&self) -> Option<&(dyn Error + 'static)> {
     }
 }
 
+impl From<FrameConversionError> for FortressError {
+    fn from(err: FrameConversionError) -> Self {
+        // On targets where `usize` is narrower than the value, report the
+        // largest `usize`; it still exceeds `i32::MAX`.
+        Self::FrameValueTooLarge {
+            value: usize::try_from(err.value).unwrap_or(usize::MAX),
+        }
+    }
+}
+
 impl From<InvalidRequestKind> for FortressError {
     fn from(kind: InvalidRequestKind) -> Self {
         Self::InvalidRequestStructured { kind }
@@ -1675,6 +2222,30 @@ fn test_spectator_divergence_display() {
         assert!(display.contains("PlayerHandle(1)"));
     }
 
+    #[test]
+    fn test_local_input_rejected_display() {
+        let err = FortressError::LocalInputRejected {
+            frame: Frame::new(7),
+            reason: LocalInputRejectReason::QueueRejected {
+                last_added_frame: Frame::new(3),
+            },
+        };
+        let display = format!("{}", err);
+        assert!(display.contains("Local input for frame 7 rejected"));
+        assert!(display.contains("last queued frame: 3"));
+    }
+
+    #[test]
+    fn test_ahead_of_remotes_display() {
+        let err = FortressError::AheadOfRemotes {
+            frames_ahead: 9,
+            limit: 8,
+        };
+        let display = format!("{}", err);
+        assert!(display.contains("9 frames ahead of the slowest peer"));
+        assert!(display.contains("limit of 8"));
+    }
+
     #[test]
     fn test_invalid_frame_display() {
         let err = FortressError::InvalidFrame {
@@ -1749,6 +2320,132 @@ fn test_error_debug() {
         assert!(debug.contains("PredictionThreshold"));
     }
 
+    /// One error of every variant, in declaration order, so the position of
+    /// each matches its category in [`ErrorKind::ALL`].
+    fn one_of_each_variant() -> [FortressError; ErrorKind::COUNT] {
+        [
+            FortressError::PredictionThreshold,
+            FortressError::InvalidRequest {
+                info: "info".to_string(),
+            },
+            FortressError::MismatchedChecksum {
+                current_frame: Frame::new(10),
+                mismatched_frames: vec![Frame::new(8), Frame::new(9)],
+            },
+            FortressError::NotSynchronized,
+            FortressError::SpectatorTooFarBehind,
+            FortressError::SpectatorDivergence {
+                frame: Frame::new(3),
+                player: PlayerHandle::new(1),
+            },
+            FortressError::InvalidFrame {
+                frame: Frame::new(-5),
+                reason: "negative".to_string(),
+            },
+            FortressError::InvalidFrameStructured {
+                frame: Frame::NULL,
+                reason: InvalidFrameReason::NullFrame,
+            },
+            FortressError::InvalidPlayerHandle {
+                handle: PlayerHandle::new(4),
+                max_handle: PlayerHandle::new(1),
+            },
+            FortressError::MissingInput {
+                player_handle: PlayerHandle::new(0),
+                frame: Frame::new(7),
+            },
+            FortressError::SerializationError {
+                context: "context".to_string(),
+            },
+            FortressError::InternalError {
+                context: "context".to_string(),
+            },
+            FortressError::InternalErrorStructured {
+                kind: InternalErrorKind::IndexOutOfBounds(IndexOutOfBounds {
+                    name: "inputs",
+                    index: 10,
+                    length: 5,
+                }),
+            },
+            FortressError::SocketError {
+                context: "context".to_string(),
+            },
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ZeroPlayers,
+            },
+            FortressError::SerializationErrorStructured {
+                kind: SerializationErrorKind::InputSerializedSizeZero,
+            },
+            FortressError::SocketErrorStructured {
+                kind: SocketErrorKind::BindFailed { port: 7000 },
+            },
+            FortressError::FrameArithmeticOverflow {
+                frame: Frame::new(i32::MAX),
+                operand: 1,
+                operation: "add",
+            },
+            FortressError::FrameValueTooLarge { value: usize::MAX },
+            FortressError::LocalInputRejected {
+                frame: Frame::new(2),
+                reason: LocalInputRejectReason::MissingQueue,
+            },
+            FortressError::AheadOfRemotes {
+                frames_ahead: 9,
+                limit: 8,
+            },
+        ]
+    }
+
+    #[test]
+    #[allow(clippy::redundant_clone)] // Testing Clone trait implementation
+    fn every_variant_round_trips_clone_and_eq() {
+        for err in one_of_each_variant() {
+            let cloned = err.clone();
+            assert_eq!(cloned, err);
+            assert_eq!(cloned.kind(), err.kind());
+        }
+    }
+
+    #[test]
+    fn distinct_variants_are_unequal() {
+        let errors = one_of_each_variant();
+        for (i, a) in errors.iter().enumerate() {
+            for (j, b) in errors.iter().enumerate() {
+                assert_eq!(a == b, i == j, "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn kind_maps_every_variant_to_its_own_category() {
+        let kinds = one_of_each_variant().map(|err| err.kind());
+        assert_eq!(kinds, ErrorKind::ALL);
+    }
+
+    #[test]
+    fn kind_ignores_payload() {
+        let a = FortressError::InvalidRequest {
+            info: "first".to_string(),
+        };
+        let b = FortressError::InvalidRequest {
+            info: "second".to_string(),
+        };
+        assert_ne!(a, b);
+        assert_eq!(a.kind(), b.kind());
+    }
+
+    #[test]
+    fn error_kind_labels_are_unique_snake_case() {
+        let mut labels: Vec<&str> = ErrorKind::ALL.iter().map(|kind| kind.as_str()).collect();
+        for (kind, label) in ErrorKind::ALL.iter().zip(&labels) {
+            assert_eq!(kind.to_string(), *label);
+            assert!(label.chars().all(|c| c.is_ascii_lowercase() || c == '_'));
+        }
+        labels.sort_unstable();
+        labels.dedup();
+        assert_eq!(labels.len(), ErrorKind::COUNT);
+    }
+
     #[test]
     #[allow(clippy::redundant_clone)] // Testing Clone trait implementation
     fn test_error_clone() {
@@ -1865,6 +2562,17 @@ fn invalid_frame_reason_replay_exhausted_display() {
         assert!(display.contains("99"), "Expected '99' in: {display}");
     }
 
+    #[test]
+    fn invalid_frame_reason_before_oldest_saved_frame_display() {
+        let reason = InvalidFrameReason::BeforeOldestSavedFrame {
+            oldest_frame: Frame::new(41),
+        };
+        assert_eq!(
+            reason.to_string(),
+            "frame is before the oldest saved frame (oldest: 41)"
+        );
+    }
+
     #[test]
     fn test_internal_error_kind_index_out_of_bounds() {
         let kind = InternalErrorKind::IndexOutOfBounds(IndexOutOfBounds {
@@ -2082,6 +2790,18 @@ fn test_internal_error_kind_delta_encode_input_length_mismatch() {
         assert!(display.contains("reference length 4"));
     }
 
+    #[test]
+    fn test_internal_error_kind_keyframe_encode_slot_width_mismatch() {
+        let kind = InternalErrorKind::KeyframeEncodeSlotWidthMismatch {
+            reference_len: 7,
+            slots: 2,
+        };
+        let display = format!("{}", kind);
+        assert!(display.contains("keyframe encode failed"));
+        assert!(display.contains("reference length 7"));
+        assert!(display.contains("2 slots"));
+    }
+
     #[test]
     fn test_internal_error_kind_input_encode_length_mismatch() {
         let kind = InternalErrorKind::InputEncodeLengthMismatch {
@@ -2129,6 +2849,16 @@ fn test_invalid_request_kind_player_handle_in_use() {
         assert!(display.contains("already in use"));
     }
 
+    #[test]
+    fn test_invalid_request_kind_player_id_in_use() {
+        let kind = InvalidRequestKind::PlayerIdInUse {
+            handle: PlayerHandle(2),
+        };
+        let display = format!("{}", kind);
+        assert!(display.contains("player id"));
+        assert!(display.contains("player handle 2"));
+    }
+
     #[test]
     fn test_invalid_request_kind_not_local_player() {
         let kind = InvalidRequestKind::NotLocalPlayer {
@@ -2203,6 +2933,17 @@ fn test_invalid_request_kind_queue_length_too_small() {
         assert!(display.contains("minimum is 2"));
     }
 
+    #[test]
+    fn test_invalid_request_kind_queue_length_below_minimum() {
+        let kind = InvalidRequestKind::QueueLengthBelowMinimum {
+            length: 32,
+            minimum: 33,
+        };
+        let display = format!("{}", kind);
+        assert!(display.contains("queue length 32"));
+        assert!(display.contains("minimum 33"));
+    }
+
     #[test]
     fn test_invalid_request_kind_event_queue_size_too_small() {
         let kind = InvalidRequestKind::EventQueueSizeTooSmall { size: 5 };
@@ -2260,6 +3001,18 @@ fn test_invalid_request_kind_check_distance_too_large() {
         assert!(display.contains("10"));
     }
 
+    #[test]
+    fn test_invalid_request_kind_input_delay_beyond_prediction() {
+        let kind = InvalidRequestKind::InputDelayBeyondPrediction {
+            delay: 10,
+            max_prediction: 8,
+        };
+        let display = format!("{}", kind);
+        assert!(display.contains("input delay 10"));
+        assert!(display.contains("prediction window 8"));
+        assert!(display.contains("with_input_delay_beyond_prediction"));
+    }
+
     #[test]
     fn test_invalid_request_kind_max_frames_behind_invalid() {
         let kind = InvalidRequestKind::MaxFramesBehindInvalid {
@@ -2352,6 +3105,87 @@ fn test_invalid_request_kind_not_supported_converts_to_error() {
         }
     }
 
+    #[test]
+    fn test_invalid_request_kind_match_pause_states() {
+        assert!(format!("{}", InvalidRequestKind::MatchPauseInProgress).contains("in progress"));
+        assert!(format!("{}", InvalidRequestKind::MatchAlreadyPaused).contains("already paused"));
+        assert!(format!("{}", InvalidRequestKind::MatchNotPaused).contains("not paused"));
+    }
+
+    #[test]
+    fn test_invalid_request_kind_spectator_promotion() {
+        let kind = InvalidRequestKind::NotSpectator {
+            handle: PlayerHandle::new(3),
+        };
+        assert_eq!(kind.to_string(), "handle 3 is not a spectator");
+        let kind = InvalidRequestKind::PlayerNotDisconnected {
+            handle: PlayerHandle::new(1),
+        };
+        assert_eq!(kind.to_string(), "player 1 is not disconnected");
+    }
+
+    #[test]
+    fn test_invalid_request_kind_session_frame_limit_reached() {
+        let kind = InvalidRequestKind::SessionFrameLimitReached {
+            frame: Frame::new(600),
+        };
+        assert_eq!(kind.to_string(), "the session reached its frame limit 600");
+    }
+
+    #[test]
+    fn test_invalid_request_kind_frame_tags() {
+        assert_eq!(
+            InvalidRequestKind::FrameTagsDisabled.to_string(),
+            "frame tags are not enabled"
+        );
+        let kind = InvalidRequestKind::FrameTagNotIncreasing {
+            frame: Frame::new(7),
+            last_tagged: Frame::new(9),
+        };
+        assert_eq!(
+            kind.to_string(),
+            "frame tag for frame 7 does not follow the last tagged frame 9"
+        );
+    }
+
+    #[test]
+    fn test_invalid_request_kind_reliable_channel() {
+        let kind = InvalidRequestKind::ReliablePayloadTooLarge { len: 300, max: 256 };
+        assert_eq!(
+            kind.to_string(),
+            "reliable message payload of 300 bytes exceeds the maximum of 256"
+        );
+        let kind = InvalidRequestKind::ReliableWindowFull { window: 16 };
+        assert_eq!(
+            kind.to_string(),
+            "all 16 reliable messages in the window are unacknowledged"
+        );
+    }
+
+    #[test]
+    fn test_invalid_request_kind_invalid_connection_id() {
+        let kind = InvalidRequestKind::InvalidConnectionId {
+            conn_id: 0x0001_0000,
+        };
+        assert_eq!(
+            kind.to_string(),
+            "connection ID 0x00010000 is reserved (zero, or zero low 16 bits)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_request_kind_state_slots() {
+        assert_eq!(
+            InvalidRequestKind::StateRestoreRequiresLocalSession.to_string(),
+            "state slots can only be restored while every player is local"
+        );
+        let kind = InvalidRequestKind::UnsupportedStateSlotVersion { version: 9 };
+        assert_eq!(
+            kind.to_string(),
+            "state slot format version 9 is not supported"
+        );
+    }
+
     #[test]
     fn test_invalid_request_kind_custom() {
         let kind = InvalidRequestKind::Custom("custom error message");
@@ -2409,6 +3243,14 @@ fn test_serialization_error_kind_input_serialized_size_zero() {
         assert!(display.contains("at least one byte"));
     }
 
+    #[test]
+    fn test_serialization_error_kind_default_input_round_trip_mismatch() {
+        let kind = SerializationErrorKind::DefaultInputRoundTripMismatch;
+        let display = format!("{}", kind);
+        assert!(display.contains("Config::Input::default()"));
+        assert!(display.contains("round-trip"));
+    }
+
     #[test]
     fn test_serialization_error_kind_input_serialized_frame_too_large() {
         let kind = SerializationErrorKind::InputSerializedFrameTooLarge {
@@ -2460,6 +3302,20 @@ fn test_socket_error_kind_bind_failed_after_retries() {
         assert!(display.contains("attempts"));
     }
 
+    #[test]
+    fn test_socket_error_kind_send_failed() {
+        let kind = SocketErrorKind::SendFailed {
+            kind: std::io::ErrorKind::NetworkUnreachable,
+        };
+        assert_eq!(
+            kind.to_string(),
+            format!(
+                "failed to send message: {}",
+                std::io::ErrorKind::NetworkUnreachable
+            )
+        );
+    }
+
     #[test]
     fn test_socket_error_kind_custom() {
         let kind = SocketErrorKind::Custom("custom socket error");
@@ -2703,6 +3559,26 @@ fn test_frame_value_too_large_display() {
         assert!(display.contains(&i32::MAX.to_string()));
     }
 
+    #[test]
+    fn test_frame_conversion_error_display() {
+        let too_large = u64::from(i32::MAX.unsigned_abs()) + 1;
+        let display = FrameConversionError { value: too_large }.to_string();
+        assert!(display.contains(&too_large.to_string()));
+        assert!(display.contains(&i32::MAX.to_string()));
+    }
+
+    #[test]
+    fn test_frame_conversion_error_into_fortress_error() {
+        let too_large = u64::from(i32::MAX.unsigned_abs()) + 1;
+        let err: FortressError = FrameConversionError { value: too_large }.into();
+        assert_eq!(
+            err,
+            FortressError::FrameValueTooLarge {
+                value: usize::try_from(too_large).unwrap()
+            }
+        );
+    }
+
     #[test]
     fn test_frame_arithmetic_overflow_debug() {
         let err = FortressError::FrameArithmeticOverflow {
@@ -0,0 +1,563 @@
+//! Deterministic fixed-point arithmetic.
+//!
+//! Floating-point results can differ between platforms, compilers, and
+//! optimization levels, so game state and input predictions should not use
+//! them. [`I16F16`] is a signed Q16.16 number: an `i32` with 16 integer and 16
+//! fractional bits. Every operation is integer arithmetic, so results are
+//! bit-exact everywhere, and serialization and hashing see only the raw bits.
+//!
+//! # Rounding and Overflow
+//!
+//! - Multiplication rounds toward negative infinity; division rounds toward
+//!   zero.
+//! - The operators (`+`, `-`, `*`, `/`, unary `-`) saturate at
+//!   [`I16F16::MIN`] and [`I16F16::MAX`] instead of wrapping or panicking, so
+//!   debug and release builds compute the same values. The `checked_*`
+//!   methods report overflow instead.
+//! - Dividing by zero saturates toward the sign of the dividend (zero divided
+//!   by zero is zero).
+//!
+//! # Usage
+//!
+//! ```rust
+//! use fortress_rollback::fixed::I16F16;
+//!
+//! // Constants are converted from float literals at compile time.
+//! const FRICTION: I16F16 = I16F16::from_f64(0.875);
+//!
+//! let velocity = I16F16::from_int(3);
+//! let slowed = velocity * FRICTION;
+//! assert_eq!(slowed, I16F16::from_ratio(21, 8));
+//! assert_eq!(slowed.to_int(), 2);
+//! ```
+//!
+//! [`I16F16`]: crate::fixed::I16F16
+//! [`I16F16::MIN`]: crate::fixed::I16F16::MIN
+//! [`I16F16::MAX`]: crate::fixed::I16F16::MAX
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A signed Q16.16 fixed-point number: 16 integer bits and 16 fractional bits.
+///
+/// Ranges from `-32768` to just under `32768` in steps of `1/65536`
+/// ([`DELTA`](Self::DELTA)). Ordering, equality, and hashing follow the
+/// numeric value, and it serializes as its raw `i32` bits.
+///
+/// See the [module documentation](self) for rounding and overflow rules.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct I16F16(i32);
+
+impl I16F16 {
+    /// Number of fractional bits.
+    pub const FRAC_BITS: u32 = 16;
+    /// Zero.
+    pub const ZERO: Self = Self(0);
+    /// One.
+    pub const ONE: Self = Self(1 << Self::FRAC_BITS);
+    /// Negative one.
+    pub const NEG_ONE: Self = Self(-(1 << Self::FRAC_BITS));
+    /// One half.
+    pub const HALF: Self = Self(1 << (Self::FRAC_BITS - 1));
+    /// The smallest positive value, `1/65536`.
+    pub const DELTA: Self = Self(1);
+    /// The smallest value, `-32768`.
+    pub const MIN: Self = Self(i32::MIN);
+    /// The largest value, just under `32768`.
+    pub const MAX: Self = Self(i32::MAX);
+
+    /// Creates a value from its raw bits, the value times `65536`.
+    #[must_use]
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits, the value times `65536`.
+    #[must_use]
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    /// Converts an integer, saturating outside `-32768..=32767`.
+    #[must_use]
+    pub const fn from_int(n: i32) -> Self {
+        Self::saturate(n as i64 * Self::ONE.0 as i64)
+    }
+
+    /// Converts an integer, or returns `None` outside `-32768..=32767`.
+    #[must_use]
+    pub const fn checked_from_int(n: i32) -> Option<Self> {
+        Self::checked(n as i64 * Self::ONE.0 as i64)
+    }
+
+    /// Returns `num / den`, rounded toward zero and saturating like
+    /// [`saturating_div`](Self::saturating_div).
+    #[must_use]
+    pub const fn from_ratio(num: i32, den: i32) -> Self {
+        Self::from_int(num).saturating_div(Self::from_int(den))
+    }
+
+    /// Converts a float, rounding to the nearest value (ties away from zero)
+    /// and saturating outside the range. NaN converts to zero.
+    ///
+    /// Meant for constants, such as `const DRAG: I16F16 = I16F16::from_f64(0.9);`,
+    /// which the compiler evaluates at build time. Simulation code should
+    /// stay in fixed point rather than convert floats at run time.
+    #[must_use]
+    pub const fn from_f64(x: f64) -> Self {
+        if x.is_nan() {
+            return Self::ZERO;
+        }
+        let scaled = x * Self::ONE.0 as f64;
+        let rounded = if scaled >= 0.0 {
+            scaled + 0.5
+        } else {
+            scaled - 0.5
+        };
+        // `as` saturates at the `i64` range.
+        Self::saturate(rounded as i64)
+    }
+
+    /// Converts to a float, exactly. For display and debugging; simulation
+    /// code should stay in fixed point.
+    #[must_use]
+    pub const fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::ONE.0 as f64
+    }
+
+    /// Returns the integer part, rounded toward negative infinity.
+    #[must_use]
+    pub const fn to_int(self) -> i32 {
+        self.0 >> Self::FRAC_BITS
+    }
+
+    /// Returns the absolute value, saturating [`MIN`](Self::MIN) to
+    /// [`MAX`](Self::MAX).
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Returns `self + rhs`, or `None` on overflow.
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(bits) => Some(Self(bits)),
+            None => None,
+        }
+    }
+
+    /// Returns `self - rhs`, or `None` on overflow.
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(bits) => Some(Self(bits)),
+            None => None,
+        }
+    }
+
+    /// Returns `self * rhs` rounded toward negative infinity, or `None` on
+    /// overflow.
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::checked(Self::wide_mul(self, rhs))
+    }
+
+    /// Returns `self / rhs` rounded toward zero, or `None` on overflow or
+    /// division by zero.
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        Self::checked(Self::wide_div(self, rhs))
+    }
+
+    /// Returns `self + rhs`, saturating at the range.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Returns `self - rhs`, saturating at the range.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Returns `self * rhs` rounded toward negative infinity, saturating at
+    /// the range.
+    #[must_use]
+    pub const fn saturating_mul(self, rhs: Self) -> Self {
+        Self::saturate(Self::wide_mul(self, rhs))
+    }
+
+    /// Returns `self / rhs` rounded toward zero, saturating at the range.
+    /// Division by zero saturates toward the sign of `self`; zero divided by
+    /// zero is zero.
+    #[must_use]
+    pub const fn saturating_div(self, rhs: Self) -> Self {
+        if rhs.0 == 0 {
+            return if self.0 > 0 {
+                Self::MAX
+            } else if self.0 < 0 {
+                Self::MIN
+            } else {
+                Self::ZERO
+            };
+        }
+        Self::saturate(Self::wide_div(self, rhs))
+    }
+
+    /// Interpolates linearly from `self` (at `t == 0`) to `other` (at
+    /// `t == 1`), saturating at the range. `t` is not clamped, so values
+    /// outside `0..=1` extrapolate.
+    #[must_use]
+    pub const fn lerp(self, other: Self, t: Self) -> Self {
+        let delta = other.0 as i64 - self.0 as i64;
+        let step = (delta * t.0 as i64) >> Self::FRAC_BITS;
+        Self::saturate(self.0 as i64 + step)
+    }
+
+    /// The exact product, scaled back to Q16.16 and rounded toward negative
+    /// infinity. Fits `i64`: both factors are below `2^31` in magnitude.
+    const fn wide_mul(lhs: Self, rhs: Self) -> i64 {
+        (lhs.0 as i64 * rhs.0 as i64) >> Self::FRAC_BITS
+    }
+
+    /// The quotient in Q16.16, rounded toward zero. `rhs` must be nonzero.
+    /// Fits `i64`: the shifted dividend is below `2^47` in magnitude.
+    const fn wide_div(lhs: Self, rhs: Self) -> i64 {
+        ((lhs.0 as i64) << Self::FRAC_BITS) / rhs.0 as i64
+    }
+
+    const fn checked(bits: i64) -> Option<Self> {
+        if bits < i32::MIN as i64 || bits > i32::MAX as i64 {
+            None
+        } else {
+            Some(Self(bits as i32))
+        }
+    }
+
+    const fn saturate(bits: i64) -> Self {
+        if bits < i32::MIN as i64 {
+            Self::MIN
+        } else if bits > i32::MAX as i64 {
+            Self::MAX
+        } else {
+            Self(bits as i32)
+        }
+    }
+}
+
+impl fmt::Display for I16F16 {
+    /// Writes the exact decimal value, such as `1.5` or `-0.25`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl fmt::Debug for I16F16 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "I16F16({self})")
+    }
+}
+
+impl Add for I16F16 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.saturating_add(rhs)
+    }
+}
+
+impl AddAssign for I16F16 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.saturating_add(rhs);
+    }
+}
+
+impl Sub for I16F16 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.saturating_sub(rhs)
+    }
+}
+
+impl SubAssign for I16F16 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.saturating_sub(rhs);
+    }
+}
+
+impl Mul for I16F16 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.saturating_mul(rhs)
+    }
+}
+
+impl MulAssign for I16F16 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.saturating_mul(rhs);
+    }
+}
+
+impl Div for I16F16 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.saturating_div(rhs)
+    }
+}
+
+impl DivAssign for I16F16 {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.saturating_div(rhs);
+    }
+}
+
+impl Neg for I16F16 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(self.0.saturating_neg())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::I16F16;
+
+    fn fx(x: f64) -> I16F16 {
+        I16F16::from_f64(x)
+    }
+
+    /// Golden bit patterns: any change here changes simulation results.
+    #[test]
+    fn golden_values() {
+        assert_eq!(I16F16::ONE.to_bits(), 65_536);
+        assert_eq!(I16F16::HALF.to_bits(), 32_768);
+        assert_eq!(fx(1.5).to_bits(), 98_304);
+        assert_eq!(fx(-2.25).to_bits(), -147_456);
+        assert_eq!(fx(0.1).to_bits(), 6_554);
+        assert_eq!(fx(-0.1).to_bits(), -6_554);
+        assert_eq!(I16F16::from_ratio(1, 3).to_bits(), 21_845);
+        assert_eq!(I16F16::from_ratio(-1, 3).to_bits(), -21_845);
+        assert_eq!((fx(1.5) * fx(-2.25)).to_bits(), -221_184);
+        assert_eq!((fx(0.1) * fx(0.1)).to_bits(), 655);
+        assert_eq!((fx(-0.1) * fx(0.1)).to_bits(), -656);
+        assert_eq!((I16F16::ONE / I16F16::from_int(3)).to_bits(), 21_845);
+        assert_eq!((fx(-7.5) / fx(2.0)).to_bits(), -245_760);
+        assert_eq!(fx(2.0).lerp(fx(4.0), fx(0.25)).to_bits(), 163_840);
+        assert_eq!(fx(10.0).lerp(fx(0.0), fx(0.1)).to_bits(), 589_820);
+    }
+
+    #[test]
+    fn float_literal_conversion_rounds_to_nearest() {
+        assert_eq!(fx(0.5 / 65_536.0), I16F16::DELTA);
+        assert_eq!(fx(0.49 / 65_536.0), I16F16::ZERO);
+        assert_eq!(fx(-0.5 / 65_536.0), -I16F16::DELTA);
+        assert_eq!(fx(f64::NAN), I16F16::ZERO);
+        assert_eq!(fx(1e9), I16F16::MAX);
+        assert_eq!(fx(f64::NEG_INFINITY), I16F16::MIN);
+        const QUARTER: I16F16 = I16F16::from_f64(0.25);
+        assert_eq!(QUARTER, I16F16::from_ratio(1, 4));
+    }
+
+    #[test]
+    fn integer_conversion() {
+        assert_eq!(I16F16::from_int(-3).to_int(), -3);
+        assert_eq!(fx(2.75).to_int(), 2);
+        assert_eq!(fx(-1.5).to_int(), -2);
+        assert_eq!(I16F16::from_int(40_000), I16F16::MAX);
+        assert_eq!(I16F16::from_int(-40_000), I16F16::MIN);
+        assert_eq!(I16F16::checked_from_int(32_767), Some(fx(32_767.0)));
+        assert_eq!(I16F16::checked_from_int(32_768), None);
+        assert_eq!(I16F16::checked_from_int(-32_768), Some(I16F16::MIN));
+        assert_eq!(I16F16::from_int(5).to_f64().to_bits(), 5.0f64.to_bits());
+    }
+
+    #[test]
+    fn operators_saturate() {
+        assert_eq!(I16F16::MAX + I16F16::ONE, I16F16::MAX);
+        assert_eq!(I16F16::MIN - I16F16::ONE, I16F16::MIN);
+        assert_eq!(I16F16::MAX * fx(2.0), I16F16::MAX);
+        assert_eq!(I16F16::MIN * fx(2.0), I16F16::MIN);
+        assert_eq!(I16F16::MAX / fx(0.5), I16F16::MAX);
+        assert_eq!(-I16F16::MIN, I16F16::MAX);
+        assert_eq!(I16F16::MIN.abs(), I16F16::MAX);
+        assert_eq!(I16F16::ONE / I16F16::ZERO, I16F16::MAX);
+        assert_eq!(I16F16::NEG_ONE / I16F16::ZERO, I16F16::MIN);
+        assert_eq!(I16F16::ZERO / I16F16::ZERO, I16F16::ZERO);
+        assert_eq!(I16F16::MAX.checked_add(I16F16::DELTA), None);
+        assert_eq!(I16F16::MIN.checked_sub(I16F16::DELTA), None);
+        assert_eq!(I16F16::MAX.checked_mul(fx(2.0)), None);
+        assert_eq!(I16F16::ONE.checked_div(I16F16::ZERO), None);
+
+        let mut x = fx(1.5);
+        x += fx(0.5);
+        x *= fx(3.0);
+        x -= I16F16::ONE;
+        x /= fx(2.0);
+        assert_eq!(x, fx(2.5));
+    }
+
+    #[test]
+    fn formatting_shows_the_exact_value() {
+        assert_eq!(fx(1.5).to_string(), "1.5");
+        assert_eq!(fx(-0.25).to_string(), "-0.25");
+        assert_eq!(I16F16::DELTA.to_string(), "0.0000152587890625");
+        assert_eq!(format!("{:?}", fx(3.0)), "I16F16(3)");
+    }
+
+    #[test]
+    fn serializes_as_raw_bits() {
+        assert_eq!(serde_json::to_string(&fx(1.5)).unwrap(), "98304");
+        let back: I16F16 = serde_json::from_str("-147456").unwrap();
+        assert_eq!(back, fx(-2.25));
+        let config = bincode::config::standard();
+        let bytes = bincode::serde::encode_to_vec(fx(0.1), config).unwrap();
+        let (decoded, _): (I16F16, usize) =
+            bincode::serde::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, fx(0.1));
+    }
+}
+
+// =============================================================================
+// Property-Based Tests
+// =============================================================================
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod property_tests {
+    use super::I16F16;
+    use crate::test_config::miri_case_count;
+    use proptest::prelude::*;
+
+    fn any_fixed() -> impl Strategy<Value = I16F16> {
+        any::<i32>().prop_map(I16F16::from_bits)
+    }
+
+    /// Values small enough that products of two stay in range.
+    fn small_fixed() -> impl Strategy<Value = I16F16> {
+        (-(100 << 16)..=(100 << 16)).prop_map(I16F16::from_bits)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig {
+            cases: miri_case_count(),
+            ..ProptestConfig::default()
+        })]
+
+        /// Property: addition is commutative, and associative wherever it
+        /// does not overflow.
+        #[test]
+        fn prop_addition_commutes_and_associates(
+            a in any_fixed(),
+            b in any_fixed(),
+            c in any_fixed(),
+        ) {
+            prop_assert_eq!(a + b, b + a);
+            let left = a.checked_add(b).and_then(|ab| ab.checked_add(c));
+            let right = b.checked_add(c).and_then(|bc| a.checked_add(bc));
+            if let (Some(left), Some(right)) = (left, right) {
+                prop_assert_eq!(left, right);
+            }
+        }
+
+        /// Property: zero and one are identities, and subtraction undoes
+        /// addition wherever it does not overflow.
+        #[test]
+        fn prop_identities(a in any_fixed(), b in any_fixed()) {
+            prop_assert_eq!(a + I16F16::ZERO, a);
+            prop_assert_eq!(a * I16F16::ONE, a);
+            prop_assert_eq!(a * I16F16::ZERO, I16F16::ZERO);
+            prop_assert_eq!(a / I16F16::ONE, a);
+            if let Some(sum) = a.checked_add(b) {
+                prop_assert_eq!(sum - b, a);
+            }
+        }
+
+        /// Property: multiplication commutes, and the saturating result
+        /// equals the checked one whenever that exists.
+        #[test]
+        fn prop_multiplication_commutes(a in any_fixed(), b in any_fixed()) {
+            prop_assert_eq!(a * b, b * a);
+            if let Some(product) = a.checked_mul(b) {
+                prop_assert_eq!(product, a.saturating_mul(b));
+            } else {
+                prop_assert!(a.saturating_mul(b) == I16F16::MAX || a.saturating_mul(b) == I16F16::MIN);
+            }
+        }
+
+        /// Property: multiplying back a quotient recovers the dividend to
+        /// within the rounding of both operations.
+        #[test]
+        fn prop_division_inverts_multiplication(a in small_fixed(), b in small_fixed()) {
+            prop_assume!(b.abs() >= I16F16::ONE);
+            let back = (a / b) * b;
+            let error = (back - a).abs();
+            prop_assert!(error <= b.abs() * I16F16::from_bits(2), "{a} / {b} * {b} = {back}");
+        }
+
+        /// Property: ordering follows the numeric value.
+        #[test]
+        fn prop_ordering_matches_value(a in any_fixed(), b in any_fixed()) {
+            prop_assert_eq!(a.cmp(&b), a.to_f64().partial_cmp(&b.to_f64()).unwrap());
+        }
+
+        /// Property: integers in range round-trip, and `to_int` floors.
+        #[test]
+        fn prop_integer_round_trip(n in -32_768i32..=32_767, a in any_fixed()) {
+            prop_assert_eq!(I16F16::from_int(n).to_int(), n);
+            prop_assert!(I16F16::from_int(a.to_int()) <= a);
+            prop_assert!(a.to_f64() - f64::from(a.to_int()) < 1.0);
+        }
+
+        /// Property: float conversion is exact for representable values.
+        #[test]
+        fn prop_float_round_trip(a in any_fixed()) {
+            prop_assert_eq!(I16F16::from_f64(a.to_f64()), a);
+        }
+
+        /// Property: `lerp` hits both endpoints and stays between them for
+        /// `t` in `0..=1`.
+        #[test]
+        fn prop_lerp_stays_between_endpoints(
+            a in any_fixed(),
+            b in any_fixed(),
+            t in 0..=(1i32 << 16),
+        ) {
+            let t = I16F16::from_bits(t);
+            prop_assert_eq!(a.lerp(b, I16F16::ZERO), a);
+            prop_assert_eq!(a.lerp(b, I16F16::ONE), b);
+            let mid = a.lerp(b, t);
+            prop_assert!(mid >= a.min(b) && mid <= a.max(b), "lerp({a}, {b}, {t}) = {mid}");
+        }
+    }
+}
@@ -11,10 +11,13 @@
 //! - [`PredictionStrategy`] - Trait for custom prediction strategies
 //! - [`RepeatLastConfirmed`] - Default strategy (repeats last confirmed input)
 //! - [`BlankPrediction`] - Strategy that returns default input
+//! - [`DecayToNeutral`] - Strategy that fades analog inputs toward neutral
 
 mod prediction;
 
-pub use prediction::{BlankPrediction, PredictionStrategy, RepeatLastConfirmed};
+pub use prediction::{
+    BlankPrediction, DecayInput, DecayToNeutral, PredictionStrategy, RepeatLastConfirmed,
+};
 
 use crate::frame_info::PlayerInput;
 use crate::proof_vec::ProofVec;
@@ -324,6 +327,18 @@ pub fn first_incorrect_frame(&self) -> Frame {
         self.first_incorrect_frame
     }
 
+    /// Returns the predicted and the confirmed input at
+    /// [`first_incorrect_frame`](Self::first_incorrect_frame), or `None` when no
+    /// misprediction is pending. Only meaningful before
+    /// [`reset_prediction`](Self::reset_prediction).
+    pub(crate) fn misprediction(&self) -> Option<(T::Input, T::Input)> {
+        if self.first_incorrect_frame.is_null() {
+            return None;
+        }
+        let confirmed = self.confirmed_input(self.first_incorrect_frame).ok()?;
+        Some((self.prediction.input, confirmed.input))
+    }
+
     /// Sets the frame delay for this input queue.
     ///
     /// # Behavior
@@ -334,29 +349,36 @@ pub fn first_incorrect_frame(&self) -> Frame {
     ///   inputs have already been added, the gap created by the larger delay is filled
     ///   by replicating the most recent input. This preserves the sequential invariant
     ///   `input.frame + frame_delay == last_added_frame + 1` for the next user input.
-    /// - **Mid-session decrease:** Decreasing the delay mid-session is **not
-    ///   supported**: it would require dropping already-queued (and potentially
-    ///   already-sent) inputs. Returns
-    ///   [`InvalidRequestKind::InputDelayDecreaseUnsupported`] in that case.
+    /// - **Mid-session decrease:** If `delay` is smaller than the current delay and
+    ///   inputs have already been added, the newest queued inputs for the frames
+    ///   the smaller delay overlaps are dropped, so the next inputs added take
+    ///   those frames instead.
+    ///
+    /// Either way, the queue holds exactly one input per frame afterward.
     ///
     /// # Mid-session delay change
     ///
-    /// Increasing the delay replicates the most recently added input across the new
-    /// gap. This matches the strategy used by [`advance_queue_head`] for initial
-    /// delay setup, and is consistent with what the network protocol expects (the
-    /// remote peer must observe the same input sequence on both sides). The
-    /// trade-off is that "held" inputs (e.g., an attack button) will continue for
-    /// the gap frames; applications that need different gap-fill semantics should
-    /// call this method only when no input is held.
+    /// Increasing the delay by `N` replicates the most recently added input across
+    /// the `N` new frames. This matches the strategy used by [`advance_queue_head`]
+    /// for initial delay setup, and is consistent with what the network protocol
+    /// expects (the remote peer must observe the same input sequence on both
+    /// sides). The trade-off is that "held" inputs (e.g., an attack button) will
+    /// continue for the gap frames; applications that need different gap-fill
+    /// semantics should call this method only when no input is held. The
+    /// duplication is logged at info level.
     ///
-    /// Decreasing the delay mid-session is rejected because it would require
-    /// discarding already-queued inputs. Set the desired delay before adding any
-    /// inputs (typically via the session builder).
+    /// Decreasing the delay by `N` would make the next `N` inputs land on frames
+    /// that already hold one. The newest input wins: the `N` most recently added
+    /// inputs are dropped and their frames are filled by the inputs added next.
+    /// This is reported as a [`ViolationSeverity::Warning`]. The dropped frames
+    /// must not have been read yet; a queue that already sent them elsewhere,
+    /// such as a P2P session with remote peers, must not decrease its delay.
     ///
     /// # Errors
     /// - Returns [`InvalidRequestKind::FrameDelayTooLarge`] if `delay > max_frame_delay()`.
     /// - Returns [`InvalidRequestKind::InputDelayDecreaseUnsupported`] if `delay` is less
-    ///   than the current delay and inputs have already been added.
+    ///   than the current delay and an input it would drop was already requested
+    ///   (or is the only one queued).
     /// - Returns [`InternalErrorKind::InputQueueGapFillFailed`] if gap-fill replication
     ///   fails (indicates an internal invariant violation).
     ///
@@ -404,14 +426,8 @@ fn set_frame_delay_with_reclaim_floor(
             return Ok(());
         }
 
-        // Decreasing delay mid-session is unsupported: it would require dropping
-        // already-queued (and potentially already-sent) inputs.
         if delay < self.frame_delay {
-            return Err(InvalidRequestKind::InputDelayDecreaseUnsupported {
-                current: self.frame_delay,
-                requested: delay,
-            }
-            .into());
+            return self.drop_overlapped_inputs(delay);
         }
 
         // Increasing delay mid-session: replicate the most-recent input to fill the
@@ -490,6 +506,74 @@ fn set_frame_delay_with_reclaim_floor(
             );
         }
 
+        self.frame_delay = delay;
+        tracing::info!(
+            "input delay of player {} raised to {}: input for frame {} repeated {} times",
+            self.player_index,
+            delay,
+            snapshot_last_added_frame,
+            delta
+        );
+        Ok(())
+    }
+
+    /// Lowers the frame delay mid-session to `delay`, dropping the newest
+    /// queued inputs for the frames the smaller delay overlaps.
+    fn drop_overlapped_inputs(&mut self, delay: usize) -> Result<(), FortressError> {
+        let unsupported = InvalidRequestKind::InputDelayDecreaseUnsupported {
+            current: self.frame_delay,
+            requested: delay,
+        };
+        let delta = self.frame_delay - delay;
+        // Keep at least one input, so the slot behind `head` still holds
+        // `last_added_frame` for the next sequential add.
+        if delta >= self.length {
+            return Err(unsupported.into());
+        }
+        let Ok(frames) = i32::try_from(delta) else {
+            return Err(unsupported.into());
+        };
+        let new_last_added = safe_frame_sub!(
+            self.last_added_frame,
+            frames,
+            "InputQueue::drop_overlapped_inputs"
+        );
+        // A dropped input that was already read could have been simulated.
+        if !self.last_requested_frame.is_null() && self.last_requested_frame > new_last_added {
+            return Err(unsupported.into());
+        }
+
+        for _ in 0..delta {
+            self.head = match self.head {
+                0 => self.queue_length - 1,
+                _ => self.head - 1,
+            };
+            if let Some(slot) = self.inputs.get_mut(self.head) {
+                *slot = PlayerInput::blank_input(Frame::NULL);
+            }
+        }
+        self.length -= delta;
+        let previous_position = match self.head {
+            0 => self.queue_length - 1,
+            _ => self.head - 1,
+        };
+        if let Some(newest) = self.inputs.get(previous_position) {
+            self.last_confirmed_input = Some(newest.input);
+        }
+        if self.first_incorrect_frame > new_last_added {
+            self.first_incorrect_frame = Frame::NULL;
+        }
+        let dropped_from = safe_frame_add!(new_last_added, 1, "InputQueue::drop_overlapped_inputs");
+        report_violation!(
+            ViolationSeverity::Warning,
+            ViolationKind::InputQueue,
+            "input delay of player {} lowered to {}: dropped the inputs queued for frames {} to {}",
+            self.player_index,
+            delay,
+            dropped_from,
+            self.last_added_frame
+        );
+        self.last_added_frame = new_last_added;
         self.frame_delay = delay;
         Ok(())
     }
@@ -798,6 +882,10 @@ pub(crate) fn freeze_at_prevalidated_cut(&mut self, cut: Frame) {
     ///
     /// Note: After calling `discard_confirmed_frames(5)`, frames 0-4 are discarded
     /// and frame 5 becomes the new tail (oldest frame in queue).
+    ///
+    /// Inputs from [`first_incorrect_frame`](Self::first_incorrect_frame) on
+    /// are kept until [`reset_prediction`](Self::reset_prediction), since the
+    /// pending rollback replays them.
     pub fn discard_confirmed_frames(&mut self, mut frame: Frame) {
         // An EMPTY queue holds nothing to discard — return quietly (chunk-N5
         // noise downgrade, S34 residual 5). A hot-join reactivated queue
@@ -824,6 +912,11 @@ pub fn discard_confirmed_frames(&mut self, mut frame: Frame) {
         if !self.last_requested_frame.is_null() {
             frame = cmp::min(frame, self.last_requested_frame);
         }
+        // a detected misprediction resimulates from `first_incorrect_frame`, so every input from
+        // there on stays until the rollback resets the prediction
+        if !self.first_incorrect_frame.is_null() {
+            frame = cmp::min(frame, self.first_incorrect_frame);
+        }
         if self
             .reclaimed_floor_input
             .is_some_and(|input| input.frame < frame)
@@ -1322,10 +1415,6 @@ pub fn unfreeze(&mut self) {
     /// `frame` must be non-negative. On a negative or [`Frame::NULL`] frame this
     /// reports a violation and leaves the queue **unchanged** (it does not
     /// panic).
-    // dead_code: consumed by chunk 5's session orchestration; the public session
-    // API that drives reactivation/seek lands there.
-    #[cfg(feature = "hot-join")]
-    #[allow(dead_code)]
     pub(crate) fn reset_to_frame(&mut self, frame: Frame) {
         if frame.as_i32() < 0 {
             report_violation!(
@@ -1449,6 +1538,25 @@ pub(crate) fn reset_to_frame(&mut self, frame: Frame) {
         }
     }
 
+    /// Repositions the queue for a user-driven state restore at `frame`: the
+    /// next accepted input is for game frame `frame`, as after
+    /// [`Self::reset_to_frame`], and the `frame_delay` frames from `frame` on
+    /// hold the default input, as at the start of a session. Nothing from
+    /// before the restore is kept, not even the prediction base.
+    ///
+    /// # Errors
+    /// Returns the error of [`Self::set_frame_delay`] if re-applying the delay
+    /// fails (an internal invariant violation).
+    pub(crate) fn restart_at_frame(&mut self, frame: Frame) -> Result<(), FortressError> {
+        let delay = self.frame_delay;
+        self.frame_delay = 0;
+        self.last_confirmed_input = None;
+        self.reset_to_frame(frame);
+        // Raising the delay again fills its frames like a mid-session delay
+        // increase, replicating the default input `reset_to_frame` left behind.
+        self.set_frame_delay(delay)
+    }
+
     /// Adds an input frame to the queue. Will consider the set frame delay.
     ///
     /// If the queue has been frozen via [`Self::freeze`], this method is a
@@ -1874,7 +1982,8 @@ impl<T: Config> InvariantChecker for InputQueue<T> {
     /// 1. `length` must not exceed `queue_length`
     /// 2. `head` and `tail` must be valid indices (< queue_length)
     /// 3. `length` must be consistent with head/tail positions (accounting for full queue)
-    /// 4. If `length > 0`, the frames in the queue should be consecutive
+    /// 4. If `length > 0`, the queue holds exactly one input per frame, up to
+    ///    `last_added_frame`, also after a mid-session frame-delay change
     /// 5. `frame_delay` must be within reasonable bounds
     /// 6. `first_incorrect_frame` should be NULL_FRAME or >= 0
     fn check_invariants(&self) -> Result<(), InvariantViolation> {
@@ -1986,6 +2095,36 @@ fn check_invariants(&self) -> Result<(), InvariantViolation> {
             );
         }
 
+        // Invariant 4 (listed above): one input per frame, from the tail up to
+        // last_added_frame
+        let mut expected = None;
+        for offset in 0..self.length {
+            let position = (self.tail + offset) % self.queue_length;
+            let Some(input) = self.inputs.get(position) else {
+                break;
+            };
+            if expected.is_some_and(|frame| input.frame != frame) {
+                return Err(InvariantViolation::new(
+                    "InputQueue",
+                    "queued inputs do not hold exactly one input per frame",
+                )
+                .with_details(format!(
+                    "slot {} holds frame {}, expected {:?}",
+                    position, input.frame, expected
+                )));
+            }
+            expected = Some(input.frame + 1);
+        }
+        if let Some(next) = expected {
+            if next != self.last_added_frame + 1 {
+                return Err(InvariantViolation::new(
+                    "InputQueue",
+                    "newest queued input is not last_added_frame",
+                )
+                .with_field_value("last_added_frame", self.last_added_frame));
+            }
+        }
+
         Ok(())
     }
 }
@@ -2522,6 +2661,25 @@ fn test_first_incorrect_frame_detection() {
         assert_eq!(queue.first_incorrect_frame(), Frame::new(1));
     }
 
+    #[test]
+    fn misprediction_reports_predicted_and_confirmed_input_until_reset() {
+        let mut queue = test_queue(0);
+        queue.add_input(PlayerInput::new(Frame::new(0), TestInput { inp: 10 }));
+        assert_eq!(queue.misprediction(), None);
+
+        // Frames 1 and 2 are predicted as 10; frame 1 arrives as 99, frame 2 as 7.
+        let _ = queue.input(Frame::new(2)).expect("input");
+        queue.add_input(PlayerInput::new(Frame::new(1), TestInput { inp: 99 }));
+        queue.add_input(PlayerInput::new(Frame::new(2), TestInput { inp: 7 }));
+        assert_eq!(
+            queue.misprediction(),
+            Some((TestInput { inp: 10 }, TestInput { inp: 99 }))
+        );
+
+        queue.reset_prediction();
+        assert_eq!(queue.misprediction(), None);
+    }
+
     #[test]
     fn test_first_incorrect_frame_correct_prediction() {
         let mut queue = test_queue(0);
@@ -2654,21 +2812,48 @@ fn test_frame_delay_increase_mid_session_replicates_last_input() {
         assert_eq!(frame5.input.inp, 99);
     }
 
-    /// Decreasing frame delay mid-session is unsupported and must return a
-    /// `InputDelayDecreaseUnsupported` error.
+    /// Decreasing frame delay mid-session drops the newest queued inputs the
+    /// smaller delay overlaps; the next inputs added take their frames.
     #[test]
-    fn test_frame_delay_decrease_mid_session_returns_error() {
+    fn test_frame_delay_decrease_mid_session_drops_newest_inputs() {
         let mut queue = test_queue(0);
         queue.set_frame_delay(2).expect("initial delay 2");
+        for i in 0..3i32 {
+            let input = PlayerInput::new(Frame::new(i), TestInput { inp: 10 + i as u8 });
+            assert_eq!(queue.add_input(input), Frame::new(i + 2));
+        }
+
+        queue.set_frame_delay(1).expect("decrease mid-session ok");
+        assert_eq!(queue.frame_delay(), 1);
+        assert_eq!(queue.last_added_frame, Frame::new(3));
+        assert!(queue.confirmed_input(Frame::new(4)).is_err());
+        queue
+            .check_invariants()
+            .expect("decrease preserves invariants");
 
-        // Add a single input so we are mid-session.
+        // Game frame 3 with delay 1 takes frame 4, the dropped one.
+        let input = PlayerInput::new(Frame::new(3), TestInput { inp: 99 });
+        assert_eq!(queue.add_input(input), Frame::new(4));
+        assert_eq!(queue.confirmed_input(Frame::new(3)).unwrap().input.inp, 11);
+        assert_eq!(queue.confirmed_input(Frame::new(4)).unwrap().input.inp, 99);
+        queue.check_invariants().expect("one input per frame");
+    }
+
+    /// A decrease that would drop an input a simulation already read, or the
+    /// only queued input, returns `InputDelayDecreaseUnsupported` and leaves
+    /// the queue unchanged.
+    #[test]
+    fn test_frame_delay_decrease_of_read_inputs_returns_error() {
+        let mut queue = test_queue(0);
+        queue.set_frame_delay(2).expect("initial delay 2");
         let input = PlayerInput::new(Frame::new(0), TestInput { inp: 1 });
         queue.add_input(input);
+        queue.input(Frame::new(2)).expect("frame 2 is queued");
         let before = queue.clone();
 
         let err = queue
             .set_frame_delay(1)
-            .expect_err("decrease should be rejected");
+            .expect_err("frame 2 was already read");
         match err {
             FortressError::InvalidRequestStructured {
                 kind: InvalidRequestKind::InputDelayDecreaseUnsupported { current, requested },
@@ -2679,6 +2864,102 @@ fn test_frame_delay_decrease_mid_session_returns_error() {
             other => panic!("unexpected error variant: {other:?}"),
         }
         assert_queue_unchanged(&queue, &before);
+
+        let mut queue = test_queue(0);
+        queue.set_frame_delay(2).expect("initial delay 2");
+        queue.add_input(PlayerInput::new(Frame::new(0), TestInput { inp: 1 }));
+        queue.discard_confirmed_frames(Frame::new(2));
+        assert_eq!(queue.length, 1);
+        let before = queue.clone();
+        assert!(queue.set_frame_delay(0).is_err());
+        assert_queue_unchanged(&queue, &before);
+    }
+
+    /// Drives a queue like a session would: each game frame adds its input,
+    /// reads the frame it simulates and discards the frames before it. The
+    /// delay changes from `from` to `to` after `frames_before` frames.
+    /// Checks the queue against a model holding one input per frame.
+    fn run_delay_transition(queue_length: usize, from: usize, to: usize, frames_before: i32) {
+        let mut queue =
+            InputQueue::<TestConfig>::with_queue_length(0, queue_length).expect("valid length");
+        queue.set_frame_delay(from).expect("initial delay");
+        let mut model: std::collections::BTreeMap<i32, u8> = std::collections::BTreeMap::new();
+        let mut delay = from;
+        let context = format!("length {queue_length}, {from} -> {to} after {frames_before}");
+
+        for game_frame in 0..frames_before + 12 {
+            if game_frame == frames_before {
+                queue.set_frame_delay(to).expect(&context);
+                let last = *model.keys().next_back().unwrap();
+                if to > delay {
+                    let held = model[&last];
+                    for frame in last + 1..=last + (to - delay) as i32 {
+                        model.insert(frame, held);
+                    }
+                } else {
+                    model.retain(|&frame, _| frame <= last - (delay - to) as i32);
+                }
+                delay = to;
+                queue.check_invariants().expect(&context);
+            }
+
+            let inp = (game_frame % 200) as u8 + 1;
+            let target =
+                queue.add_input(PlayerInput::new(Frame::new(game_frame), TestInput { inp }));
+            let expected = game_frame + delay as i32;
+            assert_eq!(target, Frame::new(expected), "{context}");
+            if model.is_empty() {
+                // the initial delay frames hold the default input
+                for frame in 0..expected {
+                    model.insert(frame, 0);
+                }
+            }
+            model.insert(expected, inp);
+            queue.check_invariants().expect(&context);
+
+            let (simulated, status) = queue.input(Frame::new(game_frame)).expect(&context);
+            assert_eq!(status, InputStatus::Confirmed, "{context}");
+            assert_eq!(
+                simulated.inp, model[&game_frame],
+                "{context}, frame {game_frame}"
+            );
+            if game_frame > 0 {
+                queue.discard_confirmed_frames(Frame::new(game_frame - 1));
+            }
+        }
+
+        let last = queue.last_added_frame.as_i32();
+        assert_eq!(last, *model.keys().next_back().unwrap(), "{context}");
+        for frame in (last - delay as i32)..=last {
+            let queued = queue.confirmed_input(Frame::new(frame)).expect(&context);
+            assert_eq!(queued.frame, Frame::new(frame), "{context}");
+            assert_eq!(queued.input.inp, model[&frame], "{context}, frame {frame}");
+        }
+    }
+
+    /// Delay changes of ±1 and ±3 at the start of a session, mid-session and
+    /// with the queue's head on either side of the circular wrap boundary.
+    #[test]
+    fn test_frame_delay_transitions_keep_one_input_per_frame() {
+        let queue_length = 8;
+        for (from, to) in [
+            (2, 3),
+            (3, 2),
+            (1, 4),
+            (4, 1),
+            (0, 3),
+            (3, 0),
+            (0, 1),
+            (1, 0),
+        ] {
+            for frames_before in [1, 2, 3, 4, 5, 6, 7, 8, 9, 15, 16, 17] {
+                run_delay_transition(queue_length, from, to, frames_before);
+            }
+        }
+        for frames_before in [126, 127, 128, 129] {
+            run_delay_transition(INPUT_QUEUE_LENGTH, 3, 0, frames_before);
+            run_delay_transition(INPUT_QUEUE_LENGTH, 0, 3, frames_before);
+        }
     }
 
     /// Increasing frame delay mid-session must be transactional: if the queue
@@ -3901,6 +4182,7 @@ fn freeze_at_with_null_frame_leaves_value_unchanged() {
     /// thread-local capturing subscriber for the duration of `f`. Thread-local
     /// (`tracing::subscriber::with_default`), so tests running in parallel never
     /// observe each other's events.
+    #[cfg_attr(not(feature = "telemetry"), allow(dead_code, unused_imports))]
     mod violation_capture {
         use std::sync::{Arc, Mutex};
 
@@ -3992,6 +4274,7 @@ fn freeze_at_with_null_frame_emits_no_violation() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn freeze_at_with_missing_nonnull_frame_still_warns() {
         // A *non-NULL* freeze frame with no confirmed input in the ring (evicted,
@@ -13,6 +13,8 @@
 //!
 //! - [`RepeatLastConfirmed`]: Repeats the last confirmed input (default)
 //! - [`BlankPrediction`]: Always returns the default (blank) input
+//! - [`DecayToNeutral`]: Fades the last confirmed input toward neutral, for
+//!   analog inputs that implement [`DecayInput`]
 //!
 //! # Custom Strategies
 //!
@@ -32,6 +34,7 @@
 //! }
 //! ```
 
+use crate::fixed::I16F16;
 use crate::Frame;
 
 /// Defines the strategy used to predict inputs when we haven't received the actual input yet.
@@ -121,6 +124,126 @@ fn predict(&self, _frame: Frame, _last_confirmed: Option<I>, _player_index: usiz
     }
 }
 
+/// An input that [`DecayToNeutral`] can scale toward its neutral value.
+///
+/// `scale` must compute with integer or [`I16F16`] arithmetic only, so every
+/// peer gets the same bits.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::fixed::I16F16;
+/// use fortress_rollback::DecayInput;
+///
+/// #[derive(Clone, Copy, Default)]
+/// struct Stick {
+///     x: I16F16,
+///     y: I16F16,
+///     jump: bool,
+/// }
+///
+/// impl DecayInput for Stick {
+///     fn scale(self, factor: I16F16) -> Self {
+///         // Buttons are not analog: predict them released.
+///         Stick { x: self.x * factor, y: self.y * factor, jump: false }
+///     }
+/// }
+/// ```
+pub trait DecayInput: Copy + Default {
+    /// Returns this input with its analog components multiplied by `factor`,
+    /// which lies in `0..=1`. A factor of zero should give the neutral input.
+    #[must_use]
+    fn scale(self, factor: I16F16) -> Self;
+}
+
+/// A prediction strategy that fades the last confirmed input toward neutral.
+///
+/// Holding a stick at full tilt is likely for a frame or two, less so for
+/// twenty. This strategy halves the analog components of the input every
+/// `half_life_frames` frames, interpolating linearly between halvings, so a
+/// long prediction gap drifts to neutral instead of repeating a full input.
+/// All arithmetic is [`I16F16`], so peers predict identical bits.
+///
+/// # Frames of Decay
+///
+/// [`PredictionStrategy::predict`] is not told how long the input has been
+/// missing, so it applies one frame of decay. The session's input queue
+/// predicts with [`RepeatLastConfirmed`] and reuses one prediction for a whole
+/// gap; to fade over the gap, call [`decay`](Self::decay) from the simulation
+/// with the number of frames since the player's last confirmed input.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::fixed::I16F16;
+/// use fortress_rollback::DecayToNeutral;
+///
+/// let decay = DecayToNeutral::new(4);
+/// assert_eq!(decay.factor(0), I16F16::ONE);
+/// assert_eq!(decay.factor(4), I16F16::HALF);
+/// assert_eq!(decay.factor(8), I16F16::from_ratio(1, 4));
+/// assert_eq!(decay.factor(2), I16F16::from_ratio(3, 4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecayToNeutral {
+    /// Frames over which the input halves. Zero predicts neutral immediately.
+    pub half_life_frames: u32,
+}
+
+impl DecayToNeutral {
+    /// Creates a strategy that halves the input every `half_life_frames`
+    /// frames.
+    #[must_use]
+    pub const fn new(half_life_frames: u32) -> Self {
+        Self { half_life_frames }
+    }
+
+    /// Returns the factor after `frames` frames of decay: [`I16F16::ONE`] at
+    /// zero frames, halving every `half_life_frames` frames, and linear in
+    /// between. Reaches [`I16F16::ZERO`] after 17 half-lives.
+    #[must_use]
+    pub const fn factor(&self, frames: u32) -> I16F16 {
+        if frames == 0 {
+            return I16F16::ONE;
+        }
+        if self.half_life_frames == 0 {
+            return I16F16::ZERO;
+        }
+        let halvings = frames / self.half_life_frames;
+        if halvings > I16F16::FRAC_BITS {
+            return I16F16::ZERO;
+        }
+        let remainder = frames % self.half_life_frames;
+        let start = I16F16::from_bits(I16F16::ONE.to_bits() >> halvings);
+        let end = I16F16::from_bits(start.to_bits() >> 1);
+        // `remainder < half_life_frames`, so the fraction is below one.
+        let t = ((remainder as i64) << I16F16::FRAC_BITS) / self.half_life_frames as i64;
+        start.lerp(end, I16F16::from_bits(t as i32))
+    }
+
+    /// Returns `input` after `frames` frames of decay.
+    #[must_use]
+    pub fn decay<I: DecayInput>(&self, input: I, frames: u32) -> I {
+        input.scale(self.factor(frames))
+    }
+}
+
+impl std::fmt::Display for DecayToNeutral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DecayToNeutral {{ half_life_frames: {} }}",
+            self.half_life_frames
+        )
+    }
+}
+
+impl<I: DecayInput> PredictionStrategy<I> for DecayToNeutral {
+    fn predict(&self, _frame: Frame, last_confirmed_input: Option<I>, _player_index: usize) -> I {
+        last_confirmed_input.map_or_else(I::default, |input| self.decay(input, 1))
+    }
+}
+
 #[cfg(test)]
 #[allow(
     clippy::panic,
@@ -222,4 +345,79 @@ fn test_blank_prediction_copy() {
         let debug_b = format!("{:?}", b);
         assert_eq!(debug_a, debug_b);
     }
+
+    #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+    struct AxisInput {
+        axis: I16F16,
+        button: bool,
+    }
+
+    impl DecayInput for AxisInput {
+        fn scale(self, factor: I16F16) -> Self {
+            Self {
+                axis: self.axis * factor,
+                button: false,
+            }
+        }
+    }
+
+    /// Golden factors: any change here changes predictions on the wire.
+    #[test]
+    fn test_decay_to_neutral_factor_golden_values() {
+        let decay = DecayToNeutral::new(3);
+        let bits: Vec<i32> = (0..8)
+            .map(|frames| decay.factor(frames).to_bits())
+            .collect();
+        assert_eq!(
+            bits,
+            [65_536, 54_613, 43_691, 32_768, 27_306, 21_845, 16_384, 13_653]
+        );
+        assert_eq!(DecayToNeutral::new(1).factor(16), I16F16::DELTA);
+        assert_eq!(DecayToNeutral::new(1).factor(17), I16F16::ZERO);
+        assert_eq!(DecayToNeutral::new(1).factor(u32::MAX), I16F16::ZERO);
+        assert_eq!(DecayToNeutral::new(0).factor(0), I16F16::ONE);
+        assert_eq!(DecayToNeutral::new(0).factor(1), I16F16::ZERO);
+        assert_eq!(
+            DecayToNeutral::new(u32::MAX).factor(u32::MAX - 1),
+            I16F16::HALF
+        );
+    }
+
+    #[test]
+    fn test_decay_to_neutral_factor_never_increases() {
+        for half_life in [1, 2, 5, 60] {
+            let decay = DecayToNeutral::new(half_life);
+            for frames in 0..20 * half_life {
+                assert!(
+                    decay.factor(frames + 1) <= decay.factor(frames),
+                    "half-life {half_life}, frame {frames}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decay_to_neutral_strategy() {
+        let strategy = DecayToNeutral::new(2);
+        let held = AxisInput {
+            axis: I16F16::from_f64(-0.8),
+            button: true,
+        };
+        let result: AxisInput = strategy.predict(Frame::new(5), Some(held), 0);
+        assert_eq!(result, strategy.decay(held, 1));
+        assert_eq!(result.axis.to_bits(), -39_322);
+        assert!(!result.button);
+
+        let result: AxisInput = strategy.predict(Frame::new(0), None, 0);
+        assert_eq!(result, AxisInput::default());
+        assert_eq!(strategy.decay(held, 40).axis, I16F16::ZERO);
+    }
+
+    #[test]
+    fn test_decay_to_neutral_display() {
+        assert_eq!(
+            DecayToNeutral::new(6).to_string(),
+            "DecayToNeutral { half_life_frames: 6 }"
+        );
+    }
 }
@@ -51,8 +51,9 @@
 use std::{fmt::Debug, hash::Hash};
 
 pub use error::{
-    DeltaDecodeReason, FortressError, IndexOutOfBounds, InternalErrorKind, InvalidFrameReason,
-    InvalidRequestKind, RleDecodeReason, SerializationErrorKind, SocketErrorKind,
+    DeltaDecodeReason, ErrorKind, FortressError, FrameConversionError, IndexOutOfBounds,
+    InternalErrorKind, InvalidFrameReason, InvalidRequestKind, LocalInputRejectReason,
+    RleDecodeReason, SerializationErrorKind, SocketErrorKind,
 };
 
 /// A specialized `Result` type for Fortress Rollback operations.
@@ -107,35 +108,59 @@
 #[cfg(feature = "hot-join")]
 pub use metrics::HotJoinMetrics;
 pub use metrics::{
-    EventKind, EventKindCounts, MessageKind, MessageKindCounts, PeerMetrics,
-    RollbackDepthHistogram, SessionMetrics,
+    CompressionStats, DecodeFailure, DecodeFailureCounts, EventKind, EventKindCounts,
+    InputRejection, InputRejectionCounts, MemoryReport, MemoryUsage, MessageKind,
+    MessageKindCounts, PeerMetrics, RollbackDepthHistogram, SessionMetrics,
+};
+pub use network::chaos_socket::{
+    ChaosConfig, ChaosConfigBuilder, ChaosPhase, ChaosPhaseDuration, ChaosSocket, ChaosStats,
 };
-pub use network::chaos_socket::{ChaosConfig, ChaosConfigBuilder, ChaosSocket, ChaosStats};
 pub use network::messages::Message;
 pub use network::network_stats::NetworkStats;
+pub use network::packet_trace::{
+    PacketDirection, PacketRecord, PacketTrace, PacketTraceHandle, ReplaySocket, TracingSocket,
+};
 pub use network::udp_socket::UdpNonBlockingSocket;
 pub use replay::{Replay, ReplayDecodeConfig, ReplayMetadata};
 use serde::{de::DeserializeOwned, Serialize};
-pub use sessions::builder::SessionBuilder;
+pub use sessions::builder::{SessionBuilder, MAX_FPS, MAX_SESSION_FRAMES};
 pub use sessions::config::{
     ClockFn, DisconnectBehavior, InputQueueConfig, ProtocolConfig, SaveMode, SpectatorConfig,
     SyncConfig,
 };
-pub use sessions::event_drain::EventDrain;
+pub use sessions::confirmation_latency::{ConfirmationLatencySample, LatencyStats};
+pub use sessions::desync_bisector::{DesyncBisection, DesyncBisector};
+pub use sessions::event_drain::{EventDrain, TimestampedEvent, TimestampedEventDrain};
+pub use sessions::frame_progress::FrameProgress;
+pub use sessions::frame_timeline::FrameFlags;
 pub use sessions::p2p_session::P2PSession;
+pub use sessions::p2p_session_core::{P2PSessionCore, TickOutput};
 pub use sessions::p2p_spectator_session::SpectatorSession;
 pub use sessions::player_registry::PlayerRegistry;
+pub use sessions::player_roster::{PlayerId, PlayerRoster, RosterEntry};
 pub use sessions::replay_session::ReplaySession;
+pub use sessions::rollback_cause::RollbackCause;
+pub use sessions::rollback_context::RollbackContext;
+pub use sessions::session_health::{SessionHealth, SpectatorHealth};
+pub use sessions::session_preset::{PresetSettings, SessionPreset};
+pub use sessions::session_set::SessionSet;
 pub use sessions::session_trait::Session;
+pub use sessions::state_slot::StateSlot;
+pub use sessions::stats_publisher::{StatsSnapshot, StatsSubscription};
 pub use sessions::sync_health::SyncHealth;
-pub use sessions::sync_test_session::SyncTestSession;
+pub use sessions::sync_test_session::{
+    SyncTestMismatch, SyncTestMismatchKind, SyncTestSession, SyncTestSummary,
+};
+pub use sessions::sync_wait::{EndpointSyncProgress, SyncWaitError};
 // Re-export smallvec for users who need to work with SmallVec-backed types directly
 pub use smallvec::SmallVec;
-pub use sync_layer::{GameStateAccessor, GameStateCell};
+pub use sync_layer::{GameStateAccessor, GameStateCell, SaveGuard, SavedFrameInfo, StateDiffCodec};
 pub use time_sync::TimeSyncConfig;
 
 // Re-export prediction strategies
-pub use crate::input_queue::{BlankPrediction, PredictionStrategy, RepeatLastConfirmed};
+pub use crate::input_queue::{
+    BlankPrediction, DecayInput, DecayToNeutral, PredictionStrategy, RepeatLastConfirmed,
+};
 
 // Re-export checksum utilities for easy access
 pub use checksum::{compute_checksum, compute_checksum_fletcher16, fletcher16, hash_bytes_fnv1a};
@@ -207,6 +232,12 @@ pub mod tokio_socket {
 /// See module documentation for detailed usage and performance considerations.
 pub mod checksum;
 
+/// Read-only views of session internals for engine integrators.
+///
+/// See the [`advanced`] module documentation for the stability policy.
+#[cfg(feature = "advanced")]
+pub mod advanced;
+
 /// Convenient re-exports for common usage.
 ///
 /// This module provides a "prelude" that re-exports the most commonly used types
@@ -219,6 +250,9 @@ pub mod tokio_socket {
 // Internal modules - made pub for re-export in __internal, but doc(hidden) for API cleanliness
 #[doc(hidden)]
 pub mod error;
+/// Deterministic Q16.16 fixed-point arithmetic ([`fixed::I16F16`]) for game
+/// state and input predictions that must match bit for bit across platforms.
+pub mod fixed;
 #[doc(hidden)]
 pub mod frame_info;
 pub mod hash;
@@ -254,6 +288,9 @@ pub mod tokio_socket {
 pub(crate) mod test_config;
 #[doc(hidden)]
 pub mod time_sync;
+/// Internal ordered map backed by a sorted `Vec`, used for the per-frame maps
+/// on the session's hot path.
+pub(crate) mod vec_map;
 #[doc(hidden)]
 pub mod sessions {
     #[doc(hidden)]
@@ -262,26 +299,62 @@ pub mod sessions {
     #[doc(hidden)]
     pub mod config;
     #[doc(hidden)]
+    pub mod confirmation_latency;
+    /// Binary search for the first divergent frame of a recorded desync.
+    pub mod desync_bisector;
+    #[doc(hidden)]
     pub mod event_drain;
+    #[doc(hidden)]
+    pub mod frame_progress;
+    #[doc(hidden)]
+    pub mod frame_timeline;
     /// Hot-join snapshot serialization and capture/apply helpers.
     #[cfg(feature = "hot-join")]
     pub mod hot_join;
+    /// Match pause/resume negotiation state for `P2PSession`.
+    pub(crate) mod match_pause;
     #[doc(hidden)]
     pub mod p2p_session;
     #[doc(hidden)]
+    pub mod p2p_session_core;
+    #[doc(hidden)]
     pub mod p2p_spectator_session;
     #[doc(hidden)]
     pub mod player_registry;
+    #[doc(hidden)]
+    pub mod player_roster;
+    /// Previous-frame inputs carried by `AdvanceFrame` requests.
+    pub(crate) mod previous_inputs;
     /// Replay playback session for deterministic match replay.
     pub mod replay_session;
     #[doc(hidden)]
+    pub mod rollback_cause;
+    #[doc(hidden)]
+    pub mod rollback_context;
+    #[doc(hidden)]
+    pub mod session_health;
+    #[doc(hidden)]
+    pub mod session_preset;
+    #[doc(hidden)]
+    pub mod session_set;
+    #[doc(hidden)]
     pub mod session_trait;
     #[doc(hidden)]
+    pub mod state_slot;
+    #[doc(hidden)]
+    pub mod stats_publisher;
+    #[doc(hidden)]
     pub mod sync_health;
     #[doc(hidden)]
     pub mod sync_test_session;
+    #[doc(hidden)]
+    pub mod sync_wait;
+    /// Chrome trace-event recording for `P2PSession`.
+    pub(crate) mod trace_recorder;
 }
-#[doc(hidden)]
+/// Networking internals. Only [`codec`](network::codec) and
+/// [`wire`](network::wire) are supported public modules; sockets are
+/// re-exported at the crate root.
 pub mod network {
     pub(crate) const MAX_RECEIVE_MESSAGES_PER_POLL: usize = 256;
     pub(crate) const WIRE_SENTINEL: [u8; 2] = [0xF5, 0x52];
@@ -303,11 +376,13 @@ pub(crate) const fn next_conn_id(conn_id: u32) -> u32 {
 
     /// Shared fail-closed allocation for socket receive/send buffers.
     mod buffer;
+    #[doc(hidden)]
     pub mod chaos_socket;
     /// Binary codec for network message serialization.
     ///
     /// Provides centralized, zero-allocation-where-possible encoding and decoding
-    /// of network messages using bincode.
+    /// of network messages using bincode, and a versioned record format for
+    /// storing them.
     pub mod codec;
     /// Recursion-depth-limited serde wrapper for peer-controlled decodes
     /// (closes the recursive-`Config::State` stack-overflow surface, B-codec).
@@ -322,12 +397,16 @@ pub(crate) const fn next_conn_id(conn_id: u32) -> u32 {
     #[doc(hidden)]
     pub mod network_stats;
     #[doc(hidden)]
+    pub mod packet_trace;
+    #[doc(hidden)]
     pub mod protocol;
     mod socket_receive;
     #[cfg(feature = "tokio")]
+    #[doc(hidden)]
     pub mod tokio_socket;
     #[doc(hidden)]
     pub mod udp_socket;
+    pub mod wire;
 }
 
 /// Internal module exposing implementation details for testing, fuzzing, and formal verification.
@@ -407,7 +486,8 @@ pub mod __internal {
     pub use crate::rle::{decode as rle_decode, encode as rle_encode};
 
     // Session internals
-    pub use crate::sessions::player_registry::PlayerRegistry;
+    pub use crate::sessions::player_registry::{EndpointMap, PlayerRegistry};
+    pub use crate::sessions::stats_publisher::StatsPublisher;
 
     /// Returns the exact encoded byte length and stable kind of one protocol
     /// message.
@@ -448,6 +528,27 @@ pub fn mutate_input_gossip_frame(
         Some((before, after))
     }
 
+    /// Marks `Input.peer_connect_status[target]` disconnected, keeping its
+    /// frame.
+    ///
+    /// Returns `false` when the message is not an `Input`, the target is
+    /// absent, or the slot is already marked disconnected. This unstable hook
+    /// exists solely for deterministic hostile-message integration tests.
+    #[doc(hidden)]
+    pub fn mark_input_gossip_disconnected(message: &mut crate::Message, target: usize) -> bool {
+        let crate::network::messages::MessageBody::Input(input) = &mut message.body else {
+            return false;
+        };
+        let Some(status) = input.peer_connect_status.get_mut(target) else {
+            return false;
+        };
+        if status.disconnected {
+            return false;
+        }
+        status.disconnected = true;
+        true
+    }
+
     /// Rewrites one valid `FloorReply.floors[target]` entry by a signed delta,
     /// clamped to the public non-negative frame domain.
     ///
@@ -497,12 +598,23 @@ pub fn p2p_container_lengths<T: crate::Config>(
 /// The exact network protocol version emitted and accepted by this crate.
 ///
 /// Any change to bytes that a protocol message can produce or accept requires a
-/// version bump. A new tail variant may reuse a version only when it is optional
-/// for correctness and its sender is gated by an explicitly negotiated feature.
-/// Protocol v2 deliberately rejects both released v1 packets and legacy
+/// version bump. A new tail variant or header flag may reuse a version only when
+/// it is optional for correctness and its sender is gated by an explicitly
+/// negotiated feature.
+/// Protocol v12 deliberately rejects released v1 through v11 packets and legacy
 /// unversioned packets. Membership-generation semantics changed at the v2
-/// boundary, so mixed v1/v2 sessions must fail closed instead of handshaking.
-pub const PROTOCOL_VERSION: u8 = 2;
+/// boundary, v3 adds the match pause/resume negotiation that every peer must
+/// take part in, v4 adds the host's end-of-stream notice to spectators, v5
+/// adds the reliable message channel, v6 adds the closed-link notice that
+/// lets both ends of a timed-out link agree on its disconnect frame, v7
+/// adds the one-off checksum request a rejoining peer verifies its state
+/// with, v8 adds the advance-stall hint on quality reports, v9 adds the
+/// digest of each peer's claimed player handles to the sync handshake, v10
+/// carries the newest input acknowledgement on keepalives, v11 adds the
+/// spectator access proof to sync requests, and v12 adds the sender's connect
+/// status to keepalives, so mixed-version sessions fail closed instead of
+/// handshaking.
+pub const PROTOCOL_VERSION: u8 = 12;
 
 /// Internally, -1 represents no frame / invalid frame.
 ///
@@ -1137,6 +1249,63 @@ pub fn try_from_usize(value: usize) -> Result<Self, FortressError> {
         Self::from_usize(value).ok_or(FortressError::FrameValueTooLarge { value })
     }
 
+    /// Creates a `Frame` from a `u64`, returning an error if it exceeds `i32::MAX`.
+    ///
+    /// Also available as `Frame::try_from(value)`. The error converts into
+    /// [`FortressError::FrameValueTooLarge`], so `?` works in functions
+    /// returning [`FortressError`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameConversionError`] if the value exceeds `i32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fortress_rollback::{Frame, FrameConversionError};
+    ///
+    /// assert_eq!(Frame::try_from_u64(42), Ok(Frame::new(42)));
+    ///
+    /// // Values exceeding i32::MAX return error
+    /// let too_large = u64::from(i32::MAX.unsigned_abs()) + 1;
+    /// assert_eq!(
+    ///     Frame::try_from_u64(too_large),
+    ///     Err(FrameConversionError { value: too_large })
+    /// );
+    /// ```
+    #[inline]
+    pub const fn try_from_u64(value: u64) -> Result<Self, FrameConversionError> {
+        if value <= i32::MAX as u64 {
+            Ok(Self(value as i32))
+        } else {
+            Err(FrameConversionError { value })
+        }
+    }
+
+    /// Creates a `Frame` from a `usize`, clamping values above `i32::MAX` to
+    /// `Frame::new(i32::MAX)`.
+    ///
+    /// Never wraps into a negative frame. Prefer [`from_usize`](Self::from_usize)
+    /// or [`try_from_usize`](Self::try_from_usize) unless a terminal frame is
+    /// the right answer for an out-of-range value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fortress_rollback::Frame;
+    ///
+    /// assert_eq!(Frame::saturating_from_usize(42), Frame::new(42));
+    /// assert_eq!(Frame::saturating_from_usize(usize::MAX), Frame::new(i32::MAX));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn saturating_from_usize(value: usize) -> Self {
+        match Self::from_usize(value) {
+            Some(frame) => frame,
+            None => Self(i32::MAX),
+        }
+    }
+
     // === Distance and Range Methods ===
 
     /// Returns the signed distance from `self` to `other` (`other - self`).
@@ -1281,13 +1450,32 @@ fn from(frame: Frame) -> Self {
     }
 }
 
+/// Converts a `u64` to a `Frame`, failing for values above `i32::MAX`.
+///
+/// Equivalent to [`Frame::try_from_u64()`].
+impl TryFrom<u64> for Frame {
+    type Error = FrameConversionError;
+
+    #[inline]
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::try_from_u64(value)
+    }
+}
+
 /// Converts a `usize` to a `Frame`.
 ///
-/// # ⚠️ Discouraged
+/// # ⚠️ Deprecated
 ///
-/// **Soft-deprecated**: This conversion silently truncates values larger
-/// than `i32::MAX`. For safe conversion with overflow detection, use
-/// [`Frame::from_usize()`] or [`Frame::try_from_usize()`] instead.
+/// **Soft-deprecated** and scheduled for removal in the next breaking
+/// release. Values larger than `i32::MAX` saturate to `Frame::new(i32::MAX)`
+/// (see [`Frame::saturating_from_usize()`]) instead of wrapping into a
+/// negative frame, and trip a `debug_assert!` in debug builds. For
+/// conversion with overflow detection, use [`Frame::from_usize()`] or
+/// [`Frame::try_from_usize()`] instead.
+///
+/// This impl blocks a `TryFrom<usize>` impl, which the standard library
+/// derives from every `From` impl; [`Frame::try_from_usize()`] fills that
+/// role until this impl is removed.
 ///
 /// This impl cannot use `#[deprecated]` because Rust doesn't support that attribute
 /// on trait impl blocks — no compiler warning will be emitted. Consider using the
@@ -1295,7 +1483,11 @@ fn from(frame: Frame) -> Self {
 impl From<usize> for Frame {
     #[inline]
     fn from(value: usize) -> Self {
-        Self(value as i32)
+        debug_assert!(
+            value <= i32::MAX as usize,
+            "Frame::from({value}) exceeds i32::MAX; use Frame::try_from_usize"
+        );
+        Self::saturating_from_usize(value)
     }
 }
 
@@ -1457,6 +1649,16 @@ pub enum DesyncDetection {
     ///
     /// The interval controls how often checksums are compared. An interval of 1 means
     /// every frame, 10 means every 10th frame (6 times per second at 60hz), etc.
+    /// Every peer reports the checksum of each confirmed frame that is a multiple of
+    /// the interval, so peers always compare the same frames.
+    ///
+    /// Under [`SaveMode::Sparse`] most of those frames are never saved, so a sparse peer
+    /// instead reports every `interval`-th frame it saved once that frame is confirmed,
+    /// labeled with the saved frame. Reports therefore arrive once per `interval` sparse
+    /// saves rather than once per `interval` frames. Peers compare such off-grid reports
+    /// against their own saved cell for the frame while they still hold it, so sparse and
+    /// every-frame peers can be mixed; a report whose frame a peer no longer holds is not
+    /// compared.
     On {
         /// Interval rate for checksum comparison. At 60hz, an interval of 1 means
         /// checksums are compared every frame, 10 means 6 times per second, etc.
@@ -1582,7 +1784,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 /// like a regular slice:
 ///
 /// ```ignore
-/// let FortressRequest::AdvanceFrame { inputs } = request else { return };
+/// let FortressRequest::AdvanceFrame { inputs, .. } = request else { return };
 /// for (input, status) in inputs.iter() {
 ///     // Process each player's input
 /// }
@@ -1727,6 +1929,45 @@ pub enum IncompatibleSessionReason {
         /// The remote canonical configuration digest.
         theirs: u64,
     },
+    /// The peer claims to own a different set of player handles than this
+    /// session registered for its address.
+    PlayerHandles {
+        /// Digest of the handles registered locally for the peer.
+        ours: u64,
+        /// Digest of the handles the peer claims to own.
+        theirs: u64,
+    },
+    /// The peer claims a player handle this session registered to another
+    /// endpoint or to itself, so two machines would send inputs for it.
+    DuplicateHandle {
+        /// The lowest handle claimed by both.
+        handle: PlayerHandle,
+    },
+}
+
+impl IncompatibleSessionReason {
+    /// The name of the configuration field the peers disagree on.
+    ///
+    /// ```
+    /// use fortress_rollback::IncompatibleSessionReason;
+    ///
+    /// let reason = IncompatibleSessionReason::NumPlayers { ours: 2, theirs: 3 };
+    /// assert_eq!(reason.field(), "num_players");
+    /// ```
+    #[must_use]
+    pub const fn field(&self) -> &'static str {
+        match self {
+            Self::ProtocolVersion { .. } => "protocol_version",
+            Self::NumPlayers { .. } => "num_players",
+            Self::InputWidth { .. } => "input_bytes_per_player",
+            Self::Fps { .. } => "fps",
+            Self::MaxPrediction { .. } => "max_prediction",
+            Self::DesyncInterval { .. } => "desync_interval",
+            Self::Features { .. } => "features",
+            Self::ConfigDigest { .. } => "config_digest",
+            Self::PlayerHandles { .. } | Self::DuplicateHandle { .. } => "player_handles",
+        }
+    }
 }
 
 impl std::fmt::Display for IncompatibleSessionReason {
@@ -1757,6 +1998,19 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     "configuration digest (ours=0x{ours:016x}, theirs=0x{theirs:016x})"
                 )
             },
+            Self::PlayerHandles { ours, theirs } => {
+                write!(
+                    f,
+                    "player handles (ours=0x{ours:016x}, theirs=0x{theirs:016x})"
+                )
+            },
+            Self::DuplicateHandle { handle } => {
+                write!(
+                    f,
+                    "player handle {} claimed by more than one endpoint",
+                    handle.as_usize()
+                )
+            },
         }
     }
 }
@@ -1774,7 +2028,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 ///     // ... handle all other variants
 /// }
 /// ```
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FortressEvent<T>
 where
     T: Config,
@@ -1817,6 +2071,10 @@ pub enum FortressEvent<T>
         addr: T::Address,
     },
     /// Sent out if Fortress Rollback recommends skipping a few frames to let clients catch up. If you receive this, consider waiting `skip_frames` number of frames.
+    ///
+    /// [`P2PSession::frame_advantages`] names the players being waited for.
+    /// Not emitted by sessions built with
+    /// [`SessionBuilder::with_auto_frame_skip`], which skip the frames themselves.
     WaitRecommendation {
         /// Amount of frames recommended to be skipped in order to let other clients catch up.
         skip_frames: u32,
@@ -1910,6 +2168,171 @@ pub enum FortressEvent<T>
         /// Address of the removed player.
         addr: T::Address,
     },
+    /// Every peer agreed to pause the match at `frame` (see
+    /// [`crate::P2PSession::request_match_pause`]), and this session has
+    /// reached it. Until the match resumes, `advance_frame` emits no
+    /// [`FortressRequest::AdvanceFrame`] past `frame`; every peer reports the
+    /// same `frame`.
+    MatchPaused {
+        /// The frame the match is paused at.
+        frame: Frame,
+    },
+    /// Every peer agreed to resume a paused match (see
+    /// [`crate::P2PSession::request_match_resume`]). The simulation continues
+    /// from `frame`, the frame it was paused at, on every peer.
+    MatchResumed {
+        /// The frame the match resumes from.
+        frame: Frame,
+    },
+    /// A pause or resume negotiation closed without an agreement: a peer
+    /// declined it, it timed out, or a peer disconnected before every peer
+    /// accepted. Emitted by the proposer and by every peer that had accepted;
+    /// the match keeps its previous running or paused state.
+    MatchPauseCancelled {
+        /// The frame the negotiation proposed.
+        frame: Frame,
+        /// `true` for a cancelled resume, `false` for a cancelled pause.
+        resume: bool,
+    },
+    /// The session reached the frame limit set with
+    /// [`SessionBuilder::with_max_session_frames`] and every frame before it is
+    /// confirmed. Terminal: every later `advance_frame` returns
+    /// [`InvalidRequestKind::SessionFrameLimitReached`]. Every peer reports the
+    /// same `frame` and holds the same state at it.
+    SessionFrameLimitReached {
+        /// The configured limit; the session's final frame.
+        frame: Frame,
+    },
+    /// The host ended its spectator stream (see
+    /// [`crate::P2PSession::end_spectator_stream`]) and this spectator has
+    /// simulated every frame up to `final_frame`. Unlike
+    /// [`FortressEvent::Disconnected`], which reports a host that stopped
+    /// responding, this marks a match that ended cleanly; a host that
+    /// disconnects afterwards is not reported.
+    StreamEnded {
+        /// The last frame of the match the host streamed; [`Frame::NULL`] if
+        /// the stream ended before any frame.
+        final_frame: Frame,
+    },
+    /// A remote peer tagged `frame` with
+    /// [`P2PSession::send_frame_tag`](crate::P2PSession::send_frame_tag).
+    /// Emitted exactly once per tag, no earlier than the remote inputs the tag
+    /// was sent with. Requires [`ProtocolConfig::frame_tags`] on both peers.
+    FrameTag {
+        /// The first player handle of the peer that sent the tag.
+        handle: PlayerHandle,
+        /// The frame the sender tagged.
+        frame: Frame,
+        /// The tag's payload.
+        data: [u8; 8],
+    },
+    /// A message sent with
+    /// [`P2PSession::send_reliable`](crate::P2PSession::send_reliable)
+    /// arrived. Every message is emitted exactly once, in the order the peer
+    /// sent it.
+    ReliableMessage {
+        /// Address of the peer that sent the message.
+        addr: T::Address,
+        /// The message's payload.
+        payload: Vec<u8>,
+    },
+    /// The remote peers hold a different state at `frame` than the one set
+    /// with [`SessionBuilder::with_expected_state_checksum`]. Emitted once; the
+    /// session stays [`SessionState::Synchronizing`] and never advances,
+    /// because resuming from that state would desync at once.
+    RejoinStateMismatch {
+        /// The frame the expected checksum describes.
+        frame: Frame,
+        /// The checksum set with
+        /// [`SessionBuilder::with_expected_state_checksum`].
+        expected: u128,
+        /// The checksum a remote peer reported for `frame`.
+        actual: u128,
+    },
+    /// The session has been [`SessionState::Running`] without advancing a
+    /// frame for longer than the threshold set with
+    /// [`SessionBuilder::with_advance_stall_warning`], while still being
+    /// polled. Emitted once per stall; the remote players are told, and see
+    /// [`RemoteAdvanceStalled`](Self::RemoteAdvanceStalled).
+    LocalAdvanceStalled {
+        /// Milliseconds since the last advanced frame.
+        since_ms: u128,
+    },
+    /// Sent only after a `LocalAdvanceStalled` event, once the session advances
+    /// a frame again.
+    LocalAdvanceResumed {
+        /// Milliseconds the session went without advancing.
+        stalled_ms: u128,
+    },
+    /// The remote peer at `addr` keeps polling but stopped advancing frames;
+    /// see [`LocalAdvanceStalled`](Self::LocalAdvanceStalled). Only peers
+    /// with [`SessionBuilder::with_advance_stall_warning`] report stalls.
+    RemoteAdvanceStalled {
+        /// Address of the stalled peer.
+        addr: T::Address,
+    },
+    /// Sent only after a `RemoteAdvanceStalled` event, once the peer advances
+    /// again.
+    RemoteAdvanceResumed {
+        /// Address of the peer.
+        addr: T::Address,
+    },
+    /// A spectator has waited [`SpectatorConfig::input_gap_timeout`] for the
+    /// confirmed inputs of `first_missing` while a host has already sent
+    /// later frames. The later frames stay buffered and the spectator resumes
+    /// as soon as the missing ones arrive; until then `advance_frame` cannot
+    /// pass `first_missing`. Emitted once per missing frame.
+    ///
+    /// [`SpectatorConfig::input_gap_timeout`]: crate::SpectatorConfig::input_gap_timeout
+    SpectatorInputGap {
+        /// The first frame whose confirmed inputs have not arrived.
+        first_missing: Frame,
+    },
+    /// The socket refused `consecutive_failures` sends to `addr` in a row,
+    /// as reported by [`NonBlockingSocket::try_send_to`]. The session keeps
+    /// running and keeps sending; if the link stays down, the usual
+    /// [`NetworkInterrupted`](Self::NetworkInterrupted) and disconnect follow.
+    /// Emitted once per run of failures, when it reaches
+    /// [`ProtocolConfig::transport_error_threshold`]; a successful send starts
+    /// a new run. A game may use it to tell the player early that their
+    /// network changed, for example after a VPN was toggled.
+    ///
+    /// [`ProtocolConfig::transport_error_threshold`]: crate::ProtocolConfig::transport_error_threshold
+    TransportError {
+        /// The address the sends failed for.
+        addr: T::Address,
+        /// Failed sends in a row so far.
+        consecutive_failures: u32,
+    },
+    /// A spectator verifying the match with
+    /// [`SpectatorConfig::checksum_authority`] found hosts whose checksum of
+    /// `frame` differs from the majority. The majority counts every host's
+    /// report and the spectator's own checksum, which also breaks ties, so
+    /// unlike [`DesyncDetected`](Self::DesyncDetected) the verdict names the
+    /// peers that diverged. Emitted once per frame with a mismatch.
+    ///
+    /// [`SpectatorConfig::checksum_authority`]: crate::SpectatorConfig::checksum_authority
+    AuthoritativeDesyncVerdict {
+        /// The frame the checksums describe.
+        frame: Frame,
+        /// Addresses of the hosts whose checksum differs from the majority,
+        /// in host order.
+        mismatching: Vec<T::Address>,
+    },
+    /// A corrected input would have required rolling back deeper than
+    /// [`SessionBuilder::with_max_rollback_depth`] allows, so the session
+    /// skipped the rollback and accepted the inputs without correcting the
+    /// frames already simulated. The local state now diverges from the
+    /// peers'; the game is expected to resynchronize it. Emitted once per
+    /// skipped rollback.
+    ///
+    /// [`SessionBuilder::with_max_rollback_depth`]: crate::SessionBuilder::with_max_rollback_depth
+    RollbackTooDeep {
+        /// Frames the rollback would have re-simulated.
+        required_depth: usize,
+        /// The configured maximum rollback depth.
+        cap: usize,
+    },
     /// A peer is requesting to hot-join the session by filling a reserved or
     /// previously-dropped player slot `handle`. Informational: the host serves
     /// a state snapshot automatically; applications may observe this to log or
@@ -1966,6 +2389,22 @@ pub const fn kind(&self) -> crate::metrics::EventKind {
             Self::SpectatorDivergence { .. } => EventKind::SpectatorDivergence,
             Self::InputDelayRecommendation { .. } => EventKind::InputDelayRecommendation,
             Self::PeerDropped { .. } => EventKind::PeerDropped,
+            Self::MatchPaused { .. } => EventKind::MatchPaused,
+            Self::MatchResumed { .. } => EventKind::MatchResumed,
+            Self::MatchPauseCancelled { .. } => EventKind::MatchPauseCancelled,
+            Self::SessionFrameLimitReached { .. } => EventKind::SessionFrameLimitReached,
+            Self::StreamEnded { .. } => EventKind::StreamEnded,
+            Self::FrameTag { .. } => EventKind::FrameTag,
+            Self::ReliableMessage { .. } => EventKind::ReliableMessage,
+            Self::RejoinStateMismatch { .. } => EventKind::RejoinStateMismatch,
+            Self::LocalAdvanceStalled { .. } => EventKind::LocalAdvanceStalled,
+            Self::LocalAdvanceResumed { .. } => EventKind::LocalAdvanceResumed,
+            Self::RemoteAdvanceStalled { .. } => EventKind::RemoteAdvanceStalled,
+            Self::RemoteAdvanceResumed { .. } => EventKind::RemoteAdvanceResumed,
+            Self::SpectatorInputGap { .. } => EventKind::SpectatorInputGap,
+            Self::TransportError { .. } => EventKind::TransportError,
+            Self::AuthoritativeDesyncVerdict { .. } => EventKind::AuthoritativeDesyncVerdict,
+            Self::RollbackTooDeep { .. } => EventKind::RollbackTooDeep,
             #[cfg(feature = "hot-join")]
             Self::JoinRequested { .. } => EventKind::JoinRequested,
             #[cfg(feature = "hot-join")]
@@ -2060,6 +2499,94 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Self::PeerDropped { handle, addr } => {
                 write!(f, "PeerDropped(handle={}, addr={})", handle, addr)
             },
+            Self::MatchPaused { frame } => write!(f, "MatchPaused(frame={})", frame.as_i32()),
+            Self::MatchResumed { frame } => write!(f, "MatchResumed(frame={})", frame.as_i32()),
+            Self::MatchPauseCancelled { frame, resume } => write!(
+                f,
+                "MatchPauseCancelled(frame={}, resume={})",
+                frame.as_i32(),
+                resume
+            ),
+            Self::SessionFrameLimitReached { frame } => {
+                write!(f, "SessionFrameLimitReached(frame={})", frame.as_i32())
+            },
+            Self::StreamEnded { final_frame } => {
+                write!(f, "StreamEnded(final_frame={})", final_frame.as_i32())
+            },
+            Self::FrameTag {
+                handle,
+                frame,
+                data,
+            } => write!(
+                f,
+                "FrameTag(handle={}, frame={}, data=0x{:016x})",
+                handle,
+                frame.as_i32(),
+                u64::from_be_bytes(*data)
+            ),
+            Self::ReliableMessage { addr, payload } => {
+                write!(f, "ReliableMessage(addr={}, len={})", addr, payload.len())
+            },
+            Self::RejoinStateMismatch {
+                frame,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "RejoinStateMismatch(frame={}, expected={:#x}, actual={:#x})",
+                frame.as_i32(),
+                expected,
+                actual
+            ),
+            Self::LocalAdvanceStalled { since_ms } => {
+                write!(f, "LocalAdvanceStalled(since={}ms)", since_ms)
+            },
+            Self::LocalAdvanceResumed { stalled_ms } => {
+                write!(f, "LocalAdvanceResumed(stalled={}ms)", stalled_ms)
+            },
+            Self::RemoteAdvanceStalled { addr } => {
+                write!(f, "RemoteAdvanceStalled(addr={})", addr)
+            },
+            Self::RemoteAdvanceResumed { addr } => {
+                write!(f, "RemoteAdvanceResumed(addr={})", addr)
+            },
+            Self::SpectatorInputGap { first_missing } => {
+                write!(
+                    f,
+                    "SpectatorInputGap(first_missing={})",
+                    first_missing.as_i32()
+                )
+            },
+            Self::TransportError {
+                addr,
+                consecutive_failures,
+            } => write!(
+                f,
+                "TransportError(addr={}, consecutive_failures={})",
+                addr, consecutive_failures
+            ),
+            Self::AuthoritativeDesyncVerdict { frame, mismatching } => {
+                write!(
+                    f,
+                    "AuthoritativeDesyncVerdict(frame={}, mismatching=[",
+                    frame.as_i32()
+                )?;
+                for (index, addr) in mismatching.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", addr)?;
+                }
+                f.write_str("])")
+            },
+            Self::RollbackTooDeep {
+                required_depth,
+                cap,
+            } => write!(
+                f,
+                "RollbackTooDeep(required_depth={}, cap={})",
+                required_depth, cap
+            ),
             #[cfg(feature = "hot-join")]
             Self::JoinRequested { handle, addr } => {
                 write!(f, "JoinRequested(handle={}, addr={})", handle, addr)
@@ -2123,7 +2650,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 ///                 game_state = state;
 ///             }
 ///         }
-///         FortressRequest::AdvanceFrame { inputs } => {
+///         FortressRequest::AdvanceFrame { inputs, .. } => {
 ///             game_state.update(&inputs);
 ///         }
 ///     }
@@ -2157,6 +2684,16 @@ pub enum FortressRequest<T>
         /// For 1-4 players, inputs are stack-allocated (no heap allocation).
         /// The collection implements `Deref<Target = [T]>`, so `.iter()` and indexing work normally.
         inputs: InputVec<T::Input>,
+        /// The `inputs` this session provided for the previous frame in the pass
+        /// that produced the state being advanced, so a resimulated frame sees
+        /// the corrected previous inputs rather than the mispredicted ones.
+        ///
+        /// Only filled when
+        /// [`SessionBuilder::with_previous_inputs_in_requests`] is enabled.
+        /// Empty otherwise, on the first frame, and whenever the previous
+        /// frame was not simulated by this session (e.g. straight after a
+        /// hot-join snapshot load).
+        previous_inputs: InputVec<T::Input>,
     },
 }
 
@@ -2169,7 +2706,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Self::LoadGameState { frame, .. } => {
                 write!(f, "LoadGameState(frame={})", frame.as_i32())
             },
-            Self::AdvanceFrame { inputs } => {
+            Self::AdvanceFrame { inputs, .. } => {
                 write!(f, "AdvanceFrame(inputs={})", inputs.len())
             },
         }
@@ -2301,7 +2838,7 @@ macro_rules! handle_requests {
                     #[allow(clippy::redundant_closure_call)]
                     ($load)(cell, frame);
                 },
-                $crate::FortressRequest::AdvanceFrame { inputs } => {
+                $crate::FortressRequest::AdvanceFrame { inputs, .. } => {
                     #[allow(clippy::redundant_closure_call)]
                     ($advance)(inputs);
                 },
@@ -2461,6 +2998,25 @@ pub trait NonBlockingSocket<A>: Send + Sync
     /// Takes a [`Message`] and sends it to the given address.
     fn send_to(&mut self, msg: &Message, addr: &A);
 
+    /// Sends like [`send_to`](Self::send_to), but reports a send the
+    /// transport refused.
+    ///
+    /// Sessions send through this method and count consecutive failures per
+    /// peer; once [`ProtocolConfig::transport_error_threshold`] is reached,
+    /// they emit [`FortressEvent::TransportError`], long before the peer's
+    /// disconnect timeout. A failure never ends the session: the protocol
+    /// resends unacknowledged inputs anyway.
+    ///
+    /// Return an error only when the local transport rejected the message,
+    /// not for the losses best-effort delivery allows. The default calls
+    /// `send_to` and always returns `Ok`.
+    ///
+    /// [`ProtocolConfig::transport_error_threshold`]: crate::ProtocolConfig::transport_error_threshold
+    fn try_send_to(&mut self, msg: &Message, addr: &A) -> Result<(), SocketErrorKind> {
+        self.send_to(msg, addr);
+        Ok(())
+    }
+
     /// This method should return all messages received since the last time this method was called.
     /// The pairs `(A, Message)` indicate from which address each packet was received.
     ///
@@ -2470,6 +3026,16 @@ pub trait NonBlockingSocket<A>: Send + Sync
     /// redesign should add bounded draining so socket adapters can stream packets
     /// without building an unbounded collection first.
     fn receive_all_messages(&mut self) -> Vec<(A, Message)>;
+
+    /// Counts of received datagrams this socket dropped because they failed
+    /// to decode, by [`DecodeFailure`] category. Sessions report them in
+    /// [`SessionMetrics::decode_failures`](crate::SessionMetrics::decode_failures).
+    ///
+    /// The default reports none; sockets that decode raw bytes with
+    /// [`crate::network::codec::decode_message`] should override it.
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        DecodeFailureCounts::default()
+    }
 }
 
 /// Compile time parameterization for sessions.
@@ -2569,6 +3135,25 @@ pub trait NonBlockingSocket<A>
     /// Takes a [`Message`] and sends it to the given address.
     fn send_to(&mut self, msg: &Message, addr: &A);
 
+    /// Sends like [`send_to`](Self::send_to), but reports a send the
+    /// transport refused.
+    ///
+    /// Sessions send through this method and count consecutive failures per
+    /// peer; once [`ProtocolConfig::transport_error_threshold`] is reached,
+    /// they emit [`FortressEvent::TransportError`], long before the peer's
+    /// disconnect timeout. A failure never ends the session: the protocol
+    /// resends unacknowledged inputs anyway.
+    ///
+    /// Return an error only when the local transport rejected the message,
+    /// not for the losses best-effort delivery allows. The default calls
+    /// `send_to` and always returns `Ok`.
+    ///
+    /// [`ProtocolConfig::transport_error_threshold`]: crate::ProtocolConfig::transport_error_threshold
+    fn try_send_to(&mut self, msg: &Message, addr: &A) -> Result<(), SocketErrorKind> {
+        self.send_to(msg, addr);
+        Ok(())
+    }
+
     /// This method should return all messages received since the last time this method was called.
     /// The pairs `(A, Message)` indicate from which address each packet was received.
     ///
@@ -2578,6 +3163,16 @@ pub trait NonBlockingSocket<A>
     /// redesign should add bounded draining so socket adapters can stream packets
     /// without building an unbounded collection first.
     fn receive_all_messages(&mut self) -> Vec<(A, Message)>;
+
+    /// Counts of received datagrams this socket dropped because they failed
+    /// to decode, by [`DecodeFailure`] category. Sessions report them in
+    /// [`SessionMetrics::decode_failures`](crate::SessionMetrics::decode_failures).
+    ///
+    /// The default reports none; sockets that decode raw bytes with
+    /// [`crate::network::codec::decode_message`] should override it.
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        DecodeFailureCounts::default()
+    }
 }
 
 // ###################
@@ -2850,7 +3445,7 @@ fn fortress_event_clone() {
         let event: FortressEvent<TestConfig> = FortressEvent::Synchronized {
             addr: test_addr(8080),
         };
-        let cloned = event;
+        let cloned = event.clone();
         assert_eq!(event, cloned);
     }
 
@@ -2980,6 +3575,98 @@ fn check_event_display(event: &FortressEvent<TestConfig>) {
                 format!("handle={handle}"),
                 format!("addr={addr}"),
             ],
+            FortressEvent::MatchPaused { frame } => {
+                vec![
+                    "MatchPaused(".to_string(),
+                    format!("frame={}", frame.as_i32()),
+                ]
+            },
+            FortressEvent::MatchResumed { frame } => {
+                vec![
+                    "MatchResumed(".to_string(),
+                    format!("frame={}", frame.as_i32()),
+                ]
+            },
+            FortressEvent::MatchPauseCancelled { frame, resume } => vec![
+                "MatchPauseCancelled(".to_string(),
+                format!("frame={}", frame.as_i32()),
+                format!("resume={resume}"),
+            ],
+            FortressEvent::SessionFrameLimitReached { frame } => vec![
+                "SessionFrameLimitReached(".to_string(),
+                format!("frame={}", frame.as_i32()),
+            ],
+            FortressEvent::StreamEnded { final_frame } => vec![
+                "StreamEnded(".to_string(),
+                format!("final_frame={}", final_frame.as_i32()),
+            ],
+            FortressEvent::FrameTag {
+                handle,
+                frame,
+                data,
+            } => vec![
+                "FrameTag(".to_string(),
+                format!("handle={handle}"),
+                format!("frame={}", frame.as_i32()),
+                format!("data=0x{:016x}", u64::from_be_bytes(*data)),
+            ],
+            FortressEvent::ReliableMessage { addr, payload } => vec![
+                "ReliableMessage(".to_string(),
+                format!("addr={addr}"),
+                format!("len={}", payload.len()),
+            ],
+            FortressEvent::RejoinStateMismatch {
+                frame,
+                expected,
+                actual,
+            } => vec![
+                "RejoinStateMismatch(".to_string(),
+                format!("frame={}", frame.as_i32()),
+                format!("expected={expected:#x}"),
+                format!("actual={actual:#x}"),
+            ],
+            FortressEvent::LocalAdvanceStalled { since_ms } => vec![
+                "LocalAdvanceStalled(".to_string(),
+                format!("since={since_ms}ms"),
+            ],
+            FortressEvent::LocalAdvanceResumed { stalled_ms } => vec![
+                "LocalAdvanceResumed(".to_string(),
+                format!("stalled={stalled_ms}ms"),
+            ],
+            FortressEvent::RemoteAdvanceStalled { addr } => {
+                vec!["RemoteAdvanceStalled(".to_string(), format!("addr={addr}")]
+            },
+            FortressEvent::RemoteAdvanceResumed { addr } => {
+                vec!["RemoteAdvanceResumed(".to_string(), format!("addr={addr}")]
+            },
+            FortressEvent::SpectatorInputGap { first_missing } => vec![
+                "SpectatorInputGap(".to_string(),
+                format!("first_missing={}", first_missing.as_i32()),
+            ],
+            FortressEvent::TransportError {
+                addr,
+                consecutive_failures,
+            } => vec![
+                "TransportError(".to_string(),
+                format!("addr={addr}"),
+                format!("consecutive_failures={consecutive_failures}"),
+            ],
+            FortressEvent::AuthoritativeDesyncVerdict { frame, mismatching } => {
+                let mut expected = vec![
+                    "AuthoritativeDesyncVerdict(".to_string(),
+                    format!("frame={}", frame.as_i32()),
+                ];
+                expected.extend(mismatching.iter().map(ToString::to_string));
+                expected
+            },
+            FortressEvent::RollbackTooDeep {
+                required_depth,
+                cap,
+            } => vec![
+                "RollbackTooDeep(".to_string(),
+                format!("required_depth={required_depth}"),
+                format!("cap={cap}"),
+            ],
             #[cfg(feature = "hot-join")]
             FortressEvent::JoinRequested { handle, addr } => vec![
                 "JoinRequested(".to_string(),
@@ -3076,6 +3763,59 @@ fn fortress_event_display_renders_every_variant() {
                 handle: PlayerHandle::new(4),
                 addr: test_addr(7002),
             },
+            FortressEvent::MatchPaused {
+                frame: Frame::new(120),
+            },
+            FortressEvent::MatchResumed {
+                frame: Frame::new(120),
+            },
+            FortressEvent::MatchPauseCancelled {
+                frame: Frame::new(136),
+                resume: true,
+            },
+            FortressEvent::SessionFrameLimitReached {
+                frame: Frame::new(600),
+            },
+            FortressEvent::StreamEnded {
+                final_frame: Frame::new(599),
+            },
+            FortressEvent::FrameTag {
+                handle: PlayerHandle::new(1),
+                frame: Frame::new(42),
+                data: *b"gameover",
+            },
+            FortressEvent::ReliableMessage {
+                addr: test_addr(7005),
+                payload: b"ready".to_vec(),
+            },
+            FortressEvent::RejoinStateMismatch {
+                frame: Frame::new(0),
+                expected: 0x1234,
+                actual: 0x5678,
+            },
+            FortressEvent::LocalAdvanceStalled { since_ms: 2000 },
+            FortressEvent::LocalAdvanceResumed { stalled_ms: 2500 },
+            FortressEvent::RemoteAdvanceStalled {
+                addr: test_addr(7006),
+            },
+            FortressEvent::RemoteAdvanceResumed {
+                addr: test_addr(7007),
+            },
+            FortressEvent::SpectatorInputGap {
+                first_missing: Frame::new(12),
+            },
+            FortressEvent::TransportError {
+                addr: test_addr(7008),
+                consecutive_failures: 10,
+            },
+            FortressEvent::AuthoritativeDesyncVerdict {
+                frame: Frame::new(120),
+                mismatching: vec![test_addr(7009), test_addr(7010)],
+            },
+            FortressEvent::RollbackTooDeep {
+                required_depth: 9,
+                cap: 6,
+            },
             #[cfg(feature = "hot-join")]
             FortressEvent::JoinRequested {
                 handle: PlayerHandle::new(5),
@@ -3457,7 +4197,10 @@ fn fortress_request_display_advance_frame() {
             (1_u8, InputStatus::Confirmed),
             (2_u8, InputStatus::Predicted),
         ];
-        let request: FortressRequest<TestConfig> = FortressRequest::AdvanceFrame { inputs };
+        let request: FortressRequest<TestConfig> = FortressRequest::AdvanceFrame {
+            inputs,
+            previous_inputs: InputVec::new(),
+        };
         let display = format!("{}", request);
         assert_eq!(display, "AdvanceFrame(inputs=2)");
     }
@@ -3466,7 +4209,10 @@ fn fortress_request_display_advance_frame() {
     fn fortress_request_display_advance_frame_empty() {
         use crate::InputVec;
         let inputs: InputVec<u8> = smallvec::smallvec![];
-        let request: FortressRequest<TestConfig> = FortressRequest::AdvanceFrame { inputs };
+        let request: FortressRequest<TestConfig> = FortressRequest::AdvanceFrame {
+            inputs,
+            previous_inputs: InputVec::new(),
+        };
         let display = format!("{}", request);
         assert_eq!(display, "AdvanceFrame(inputs=0)");
     }
@@ -3920,6 +4666,57 @@ fn frame_try_from_usize_too_large() {
         ));
     }
 
+    #[test]
+    fn frame_try_from_u64_boundaries() {
+        let max = u64::from(i32::MAX.unsigned_abs());
+        assert_eq!(Frame::try_from_u64(0), Ok(Frame::new(0)));
+        assert_eq!(Frame::try_from_u64(max), Ok(Frame::new(i32::MAX)));
+        assert_eq!(
+            Frame::try_from_u64(max + 1),
+            Err(FrameConversionError { value: max + 1 })
+        );
+        assert_eq!(
+            Frame::try_from_u64(u64::MAX),
+            Err(FrameConversionError { value: u64::MAX })
+        );
+        assert_eq!(Frame::try_from(max), Ok(Frame::new(i32::MAX)));
+        assert_eq!(
+            Frame::try_from(max + 1),
+            Err(FrameConversionError { value: max + 1 })
+        );
+    }
+
+    #[test]
+    fn frame_saturating_from_usize_boundaries() {
+        let max = i32::MAX as usize;
+        assert_eq!(Frame::saturating_from_usize(0), Frame::new(0));
+        assert_eq!(Frame::saturating_from_usize(max), Frame::new(i32::MAX));
+        assert_eq!(Frame::saturating_from_usize(max + 1), Frame::new(i32::MAX));
+        assert_eq!(
+            Frame::saturating_from_usize(usize::MAX),
+            Frame::new(i32::MAX)
+        );
+    }
+
+    #[test]
+    fn frame_from_usize_impl_at_i32_max() {
+        assert_eq!(Frame::from(i32::MAX as usize), Frame::new(i32::MAX));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "exceeds i32::MAX")]
+    fn frame_from_usize_impl_asserts_instead_of_wrapping() {
+        let _ = Frame::from(i32::MAX as usize + 1);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn frame_from_usize_impl_saturates_instead_of_wrapping() {
+        assert_eq!(Frame::from(i32::MAX as usize + 1), Frame::new(i32::MAX));
+        assert_eq!(Frame::from(usize::MAX), Frame::new(i32::MAX));
+    }
+
     #[test]
     fn frame_distance_to_basic() {
         let a = Frame::new(100);
@@ -4243,6 +5040,99 @@ fn proof_frame_sub_assign_consistent() {
         kani::assert(result1 == frame2, "SubAssign should be consistent with Sub");
     }
 
+    /// Proof: Frame::from_usize and Frame::try_from_usize never wrap.
+    ///
+    /// - Tier: 1 (Fast, <30s)
+    /// - Verifies: Every usize up to i32::MAX converts losslessly; every
+    ///   larger one is rejected, carrying the rejected value
+    /// - Related: proof_frame_try_from_u64_total, proof_frame_saturating_from_usize_total
+    // kani::no-unwind-needed: scalar comparison and cast, no loops
+    #[kani::proof]
+    fn proof_frame_from_usize_total() {
+        let value: usize = kani::any();
+
+        match Frame::from_usize(value) {
+            Some(frame) => {
+                kani::assert(value <= i32::MAX as usize, "Only in-range values convert");
+                kani::assert(frame.as_i32() >= 0, "Converted frame is never negative");
+                kani::assert(
+                    frame.as_i32() as usize == value,
+                    "Conversion preserves the value",
+                );
+                kani::assert(
+                    Frame::try_from_usize(value) == Ok(frame),
+                    "try_from_usize agrees with from_usize",
+                );
+            },
+            None => {
+                kani::assert(value > i32::MAX as usize, "Only out-of-range values fail");
+                kani::assert(
+                    Frame::try_from_usize(value)
+                        == Err(FortressError::FrameValueTooLarge { value }),
+                    "try_from_usize reports the rejected value",
+                );
+            },
+        }
+    }
+
+    /// Proof: Frame::try_from_u64 never wraps.
+    ///
+    /// - Tier: 1 (Fast, <30s)
+    /// - Verifies: Every u64 up to i32::MAX converts losslessly; every larger
+    ///   one is rejected, and the TryFrom impl agrees
+    /// - Related: proof_frame_from_usize_total
+    // kani::no-unwind-needed: scalar comparison and cast, no loops
+    #[kani::proof]
+    fn proof_frame_try_from_u64_total() {
+        let value: u64 = kani::any();
+
+        let result = Frame::try_from_u64(value);
+        kani::assert(
+            Frame::try_from(value) == result,
+            "TryFrom<u64> agrees with try_from_u64",
+        );
+        match result {
+            Ok(frame) => {
+                kani::assert(value <= i32::MAX as u64, "Only in-range values convert");
+                kani::assert(frame.as_i32() >= 0, "Converted frame is never negative");
+                kani::assert(
+                    frame.as_i32() as u64 == value,
+                    "Conversion preserves the value",
+                );
+            },
+            Err(err) => {
+                kani::assert(value > i32::MAX as u64, "Only out-of-range values fail");
+                kani::assert(err.value == value, "Error carries the rejected value");
+            },
+        }
+    }
+
+    /// Proof: Frame::saturating_from_usize clamps instead of wrapping.
+    ///
+    /// - Tier: 1 (Fast, <30s)
+    /// - Verifies: The result is never negative, equals from_usize in range,
+    ///   and is i32::MAX above it
+    /// - Related: proof_frame_from_usize_total
+    // kani::no-unwind-needed: scalar comparison and cast, no loops
+    #[kani::proof]
+    fn proof_frame_saturating_from_usize_total() {
+        let value: usize = kani::any();
+
+        let frame = Frame::saturating_from_usize(value);
+        kani::assert(frame.as_i32() >= 0, "Saturated frame is never negative");
+        if value <= i32::MAX as usize {
+            kani::assert(
+                Frame::from_usize(value) == Some(frame),
+                "In range, saturation is the identity",
+            );
+        } else {
+            kani::assert(
+                frame.as_i32() == i32::MAX,
+                "Out of range clamps to i32::MAX",
+            );
+        }
+    }
+
     /// Proof: PlayerHandle validity check is correct.
     ///
     /// - Tier: 2 (Medium, 30s-2min)
@@ -68,6 +68,38 @@ pub enum EventKind {
     InputDelayRecommendation,
     /// [`FortressEvent::PeerDropped`](crate::FortressEvent::PeerDropped).
     PeerDropped,
+    /// [`FortressEvent::MatchPaused`](crate::FortressEvent::MatchPaused).
+    MatchPaused,
+    /// [`FortressEvent::MatchResumed`](crate::FortressEvent::MatchResumed).
+    MatchResumed,
+    /// [`FortressEvent::MatchPauseCancelled`](crate::FortressEvent::MatchPauseCancelled).
+    MatchPauseCancelled,
+    /// [`FortressEvent::SessionFrameLimitReached`](crate::FortressEvent::SessionFrameLimitReached).
+    SessionFrameLimitReached,
+    /// [`FortressEvent::StreamEnded`](crate::FortressEvent::StreamEnded).
+    StreamEnded,
+    /// [`FortressEvent::FrameTag`](crate::FortressEvent::FrameTag).
+    FrameTag,
+    /// [`FortressEvent::ReliableMessage`](crate::FortressEvent::ReliableMessage).
+    ReliableMessage,
+    /// [`FortressEvent::RejoinStateMismatch`](crate::FortressEvent::RejoinStateMismatch).
+    RejoinStateMismatch,
+    /// [`FortressEvent::LocalAdvanceStalled`](crate::FortressEvent::LocalAdvanceStalled).
+    LocalAdvanceStalled,
+    /// [`FortressEvent::LocalAdvanceResumed`](crate::FortressEvent::LocalAdvanceResumed).
+    LocalAdvanceResumed,
+    /// [`FortressEvent::RemoteAdvanceStalled`](crate::FortressEvent::RemoteAdvanceStalled).
+    RemoteAdvanceStalled,
+    /// [`FortressEvent::RemoteAdvanceResumed`](crate::FortressEvent::RemoteAdvanceResumed).
+    RemoteAdvanceResumed,
+    /// [`FortressEvent::SpectatorInputGap`](crate::FortressEvent::SpectatorInputGap).
+    SpectatorInputGap,
+    /// [`FortressEvent::TransportError`](crate::FortressEvent::TransportError).
+    TransportError,
+    /// [`FortressEvent::AuthoritativeDesyncVerdict`](crate::FortressEvent::AuthoritativeDesyncVerdict).
+    AuthoritativeDesyncVerdict,
+    /// [`FortressEvent::RollbackTooDeep`](crate::FortressEvent::RollbackTooDeep).
+    RollbackTooDeep,
     /// [`FortressEvent::JoinRequested`](crate::FortressEvent::JoinRequested).
     #[cfg(feature = "hot-join")]
     JoinRequested,
@@ -82,13 +114,13 @@ impl EventKind {
     /// Varies with enabled features: two additional categories exist when the
     /// `hot-join` feature is on.
     #[cfg(not(feature = "hot-join"))]
-    pub const COUNT: usize = 13;
+    pub const COUNT: usize = 29;
     /// The number of event categories.
     ///
     /// Varies with enabled features: two additional categories exist when the
     /// `hot-join` feature is on.
     #[cfg(feature = "hot-join")]
-    pub const COUNT: usize = 15;
+    pub const COUNT: usize = 31;
 
     /// Every category, in declaration order. Its length is [`Self::COUNT`].
     #[cfg(not(feature = "hot-join"))]
@@ -106,6 +138,22 @@ impl EventKind {
         Self::SpectatorDivergence,
         Self::InputDelayRecommendation,
         Self::PeerDropped,
+        Self::MatchPaused,
+        Self::MatchResumed,
+        Self::MatchPauseCancelled,
+        Self::SessionFrameLimitReached,
+        Self::StreamEnded,
+        Self::FrameTag,
+        Self::ReliableMessage,
+        Self::RejoinStateMismatch,
+        Self::LocalAdvanceStalled,
+        Self::LocalAdvanceResumed,
+        Self::RemoteAdvanceStalled,
+        Self::RemoteAdvanceResumed,
+        Self::SpectatorInputGap,
+        Self::TransportError,
+        Self::AuthoritativeDesyncVerdict,
+        Self::RollbackTooDeep,
     ];
     /// Every category, in declaration order. Its length is [`Self::COUNT`].
     #[cfg(feature = "hot-join")]
@@ -123,6 +171,22 @@ impl EventKind {
         Self::SpectatorDivergence,
         Self::InputDelayRecommendation,
         Self::PeerDropped,
+        Self::MatchPaused,
+        Self::MatchResumed,
+        Self::MatchPauseCancelled,
+        Self::SessionFrameLimitReached,
+        Self::StreamEnded,
+        Self::FrameTag,
+        Self::ReliableMessage,
+        Self::RejoinStateMismatch,
+        Self::LocalAdvanceStalled,
+        Self::LocalAdvanceResumed,
+        Self::RemoteAdvanceStalled,
+        Self::RemoteAdvanceResumed,
+        Self::SpectatorInputGap,
+        Self::TransportError,
+        Self::AuthoritativeDesyncVerdict,
+        Self::RollbackTooDeep,
         Self::JoinRequested,
         Self::PeerJoined,
     ];
@@ -145,6 +209,22 @@ pub const fn as_str(self) -> &'static str {
             Self::SpectatorDivergence => "spectator_divergence",
             Self::InputDelayRecommendation => "input_delay_recommendation",
             Self::PeerDropped => "peer_dropped",
+            Self::MatchPaused => "match_paused",
+            Self::MatchResumed => "match_resumed",
+            Self::MatchPauseCancelled => "match_pause_cancelled",
+            Self::SessionFrameLimitReached => "session_frame_limit_reached",
+            Self::StreamEnded => "stream_ended",
+            Self::FrameTag => "frame_tag",
+            Self::ReliableMessage => "reliable_message",
+            Self::RejoinStateMismatch => "rejoin_state_mismatch",
+            Self::LocalAdvanceStalled => "local_advance_stalled",
+            Self::LocalAdvanceResumed => "local_advance_resumed",
+            Self::RemoteAdvanceStalled => "remote_advance_stalled",
+            Self::RemoteAdvanceResumed => "remote_advance_resumed",
+            Self::SpectatorInputGap => "spectator_input_gap",
+            Self::TransportError => "transport_error",
+            Self::AuthoritativeDesyncVerdict => "authoritative_desync_verdict",
+            Self::RollbackTooDeep => "rollback_too_deep",
             #[cfg(feature = "hot-join")]
             Self::JoinRequested => "join_requested",
             #[cfg(feature = "hot-join")]
@@ -169,10 +249,26 @@ const fn index(self) -> usize {
             Self::SpectatorDivergence => 10,
             Self::InputDelayRecommendation => 11,
             Self::PeerDropped => 12,
+            Self::MatchPaused => 13,
+            Self::MatchResumed => 14,
+            Self::MatchPauseCancelled => 15,
+            Self::SessionFrameLimitReached => 16,
+            Self::StreamEnded => 17,
+            Self::FrameTag => 18,
+            Self::ReliableMessage => 19,
+            Self::RejoinStateMismatch => 20,
+            Self::LocalAdvanceStalled => 21,
+            Self::LocalAdvanceResumed => 22,
+            Self::RemoteAdvanceStalled => 23,
+            Self::RemoteAdvanceResumed => 24,
+            Self::SpectatorInputGap => 25,
+            Self::TransportError => 26,
+            Self::AuthoritativeDesyncVerdict => 27,
+            Self::RollbackTooDeep => 28,
             #[cfg(feature = "hot-join")]
-            Self::JoinRequested => 13,
+            Self::JoinRequested => 29,
             #[cfg(feature = "hot-join")]
-            Self::PeerJoined => 14,
+            Self::PeerJoined => 30,
         }
     }
 }
@@ -391,10 +487,13 @@ pub struct SessionMetrics {
     /// count means the local simulation is being throttled by the network.
     pub stall_count: u64,
 
-    /// The number of [`FortressEvent::WaitRecommendation`] events emitted (the
-    /// session asked the application to slow down to let a peer catch up).
+    /// The number of wait recommendations made (the session asked the
+    /// application to slow down to let a peer catch up). Each is emitted as a
+    /// [`FortressEvent::WaitRecommendation`], or honored by the session itself
+    /// under [`SessionBuilder::with_auto_frame_skip`].
     ///
     /// [`FortressEvent::WaitRecommendation`]: crate::FortressEvent::WaitRecommendation
+    /// [`SessionBuilder::with_auto_frame_skip`]: crate::SessionBuilder::with_auto_frame_skip
     pub wait_recommendations: u64,
 
     /// The most recently sampled confirmation lag: how many frames ahead of the
@@ -463,10 +562,42 @@ pub struct SessionMetrics {
     /// a peer whose NAT mapping changed during the session. Such packets are
     /// ignored because an address is the peer identity at this protocol layer.
     /// Malformed datagrams rejected by a socket before it yields a decoded
-    /// [`Message`](crate::Message) are outside this counter's boundary. Replay
-    /// and sync-test sessions have no receive socket, so this remains zero for
-    /// them.
+    /// [`Message`](crate::Message) are outside this counter's boundary; see
+    /// [`decode_failures`](Self::decode_failures). Replay and sync-test
+    /// sessions have no receive socket, so this remains zero for them.
     pub unknown_source_packets: u64,
+
+    /// Number of times a remote or spectator endpoint moved to a new source
+    /// address, with
+    /// [`SessionBuilder::with_address_migration`](crate::SessionBuilder::with_address_migration)
+    /// enabled. The packet that moved it is not counted in
+    /// [`unknown_source_packets`](Self::unknown_source_packets).
+    pub peer_address_migrations: u64,
+
+    /// Number of remote inputs dropped because they arrived from an endpoint
+    /// for a player handle that endpoint was not registered to send.
+    ///
+    /// The handshake refuses peers that claim another endpoint's handles, so
+    /// a non-zero value points at a protocol bug rather than a misconfigured
+    /// lobby. Such inputs are never merged into the simulation.
+    pub undeclared_handle_inputs: u64,
+
+    /// Number of times a peer claimed another player disconnected while this
+    /// session was still hearing from that player directly.
+    ///
+    /// Such a claim is only a hint: it is counted once each time a peer starts
+    /// making it, and acted on only once this session loses the player itself.
+    pub uncorroborated_disconnect_claims: u64,
+
+    /// Datagrams the session's socket dropped because they failed to decode,
+    /// by [`DecodeFailure`] category, as reported by
+    /// [`NonBlockingSocket::decode_failures`](crate::NonBlockingSocket::decode_failures).
+    ///
+    /// Truncation points at a transport that cuts packets, an unsupported
+    /// version at a peer running another release, and malformed bytes at
+    /// corruption or foreign traffic. Custom sockets that do not override
+    /// the trait method, and sessions without a socket, report zero.
+    pub decode_failures: DecodeFailureCounts,
 }
 
 impl SessionMetrics {
@@ -487,6 +618,23 @@ pub(crate) fn record_unknown_source_packet(&mut self) {
         self.unknown_source_packets = self.unknown_source_packets.saturating_add(1);
     }
 
+    /// Records one endpoint moving to a new source address.
+    pub(crate) fn record_peer_address_migration(&mut self) {
+        self.peer_address_migrations = self.peer_address_migrations.saturating_add(1);
+    }
+
+    /// Records one remote input dropped for a handle its endpoint does not own.
+    pub(crate) fn record_undeclared_handle_input(&mut self) {
+        self.undeclared_handle_inputs = self.undeclared_handle_inputs.saturating_add(1);
+    }
+
+    /// Records one peer's disconnect claim about another player that this
+    /// session could not corroborate.
+    pub(crate) fn record_uncorroborated_disconnect_claim(&mut self) {
+        self.uncorroborated_disconnect_claims =
+            self.uncorroborated_disconnect_claims.saturating_add(1);
+    }
+
     /// Records one forward frame advance (a rendered/visual frame) and samples
     /// the confirmation lag at that advance.
     pub(crate) fn record_forward_advance(&mut self, confirmation_lag: u64) {
@@ -526,7 +674,8 @@ pub(crate) fn record_stall(&mut self) {
         self.stall_count = self.stall_count.saturating_add(1);
     }
 
-    /// Records one emitted [`FortressEvent::WaitRecommendation`].
+    /// Records one wait recommendation, emitted as a
+    /// [`FortressEvent::WaitRecommendation`] or honored automatically.
     ///
     /// [`FortressEvent::WaitRecommendation`]: crate::FortressEvent::WaitRecommendation
     pub(crate) fn record_wait_recommendation(&mut self) {
@@ -693,12 +842,26 @@ pub enum MessageKind {
     DropCommit,
     /// A coordinated graceful-drop abort notification.
     DropAbort,
+    /// A match pause/resume proposal, commit, or cancellation.
+    MatchPause,
+    /// A peer's answer to a match pause/resume message.
+    MatchPauseAck,
+    /// A host's notice to a spectator that the match ended.
+    StreamEnd,
+    /// An application message on the reliable channel.
+    ReliableMessage,
+    /// An acknowledgement of reliable-channel messages.
+    ReliableAck,
+    /// A peer's notice that it closed the link after a local timeout.
+    LinkClosed,
+    /// A one-off request for the peer's checksum of one frame.
+    ChecksumRequest,
 }
 
 impl MessageKind {
     /// The number of message categories.
     ///
-    pub const COUNT: usize = 23;
+    pub const COUNT: usize = 30;
 
     /// Every category, in declaration (wire-discriminant) order. Its length is
     /// [`Self::COUNT`].
@@ -726,6 +889,13 @@ impl MessageKind {
         Self::DropBackfill,
         Self::DropCommit,
         Self::DropAbort,
+        Self::MatchPause,
+        Self::MatchPauseAck,
+        Self::StreamEnd,
+        Self::ReliableMessage,
+        Self::ReliableAck,
+        Self::LinkClosed,
+        Self::ChecksumRequest,
     ];
 
     /// A stable snake_case label for this category, suitable for logging or as a
@@ -756,6 +926,13 @@ pub const fn as_str(self) -> &'static str {
             Self::DropBackfill => "drop_backfill",
             Self::DropCommit => "drop_commit",
             Self::DropAbort => "drop_abort",
+            Self::MatchPause => "match_pause",
+            Self::MatchPauseAck => "match_pause_ack",
+            Self::StreamEnd => "stream_end",
+            Self::ReliableMessage => "reliable_message",
+            Self::ReliableAck => "reliable_ack",
+            Self::LinkClosed => "link_closed",
+            Self::ChecksumRequest => "checksum_request",
         }
     }
 
@@ -786,6 +963,13 @@ const fn index(self) -> usize {
             Self::DropBackfill => 20,
             Self::DropCommit => 21,
             Self::DropAbort => 22,
+            Self::MatchPause => 23,
+            Self::MatchPauseAck => 24,
+            Self::StreamEnd => 25,
+            Self::ReliableMessage => 26,
+            Self::ReliableAck => 27,
+            Self::LinkClosed => 28,
+            Self::ChecksumRequest => 29,
         }
     }
 }
@@ -850,6 +1034,229 @@ fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Err
     }
 }
 
+/// Why an incoming `Input` or `InputAck` message was rejected.
+///
+/// Counted per endpoint in [`PeerMetrics::input_messages_rejected`]. A rejected
+/// message is dropped whole: none of its inputs are staged and its ack does not
+/// trim the local send queue. Duplicated, reordered, and stale packets are not
+/// rejections; they are accepted and their already-known frames skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputRejection {
+    /// The ack frame is newer than the newest input frame sent to the peer.
+    /// Honoring it would drop unsent inputs from the send queue.
+    FutureAck,
+    /// The start frame is negative, including `Frame::NULL`: a sender only
+    /// queues an `Input` once it has a frame to send.
+    InvalidStartFrame,
+    /// The frames the message implies (`start_frame` plus the decoded input
+    /// count) exceed `pending_output_limit` frames, or leave less than
+    /// `max_prediction` frames of headroom below `i32::MAX`.
+    FrameRangeOutOfBounds,
+    /// The message carries a connection-status entry count other than the
+    /// `num_players` both peers agreed on in the handshake.
+    ConnectStatusLength,
+}
+
+impl InputRejection {
+    /// The number of rejection reasons.
+    pub const COUNT: usize = 4;
+
+    /// Every reason, in declaration order. Its length is [`Self::COUNT`].
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::FutureAck,
+        Self::InvalidStartFrame,
+        Self::FrameRangeOutOfBounds,
+        Self::ConnectStatusLength,
+    ];
+
+    /// A stable snake_case label for this reason, suitable for logging or as a
+    /// metrics key. Matches the JSON key produced by serialization.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FutureAck => "future_ack",
+            Self::InvalidStartFrame => "invalid_start_frame",
+            Self::FrameRangeOutOfBounds => "frame_range_out_of_bounds",
+            Self::ConnectStatusLength => "connect_status_length",
+        }
+    }
+
+    /// The array index this reason occupies in [`InputRejectionCounts`]. Always
+    /// less than [`Self::COUNT`].
+    const fn index(self) -> usize {
+        match self {
+            Self::FutureAck => 0,
+            Self::InvalidStartFrame => 1,
+            Self::FrameRangeOutOfBounds => 2,
+            Self::ConnectStatusLength => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for InputRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Per-[`InputRejection`] counters, keyed by reason.
+///
+/// Serializes as a JSON object keyed by each reason's
+/// [`InputRejection::as_str`] label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputRejectionCounts([u64; InputRejection::COUNT]);
+
+impl Default for InputRejectionCounts {
+    fn default() -> Self {
+        Self([0; InputRejection::COUNT])
+    }
+}
+
+impl InputRejectionCounts {
+    /// The count recorded for `reason`.
+    #[must_use]
+    pub fn get(&self, reason: InputRejection) -> u64 {
+        self.0.get(reason.index()).copied().unwrap_or(0)
+    }
+
+    /// The total number of rejected messages across every reason.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.0.iter().copied().fold(0u64, u64::saturating_add)
+    }
+
+    /// Increments the counter for `reason` by one, saturating at [`u64::MAX`].
+    pub(crate) fn record(&mut self, reason: InputRejection) {
+        if let Some(slot) = self.0.get_mut(reason.index()) {
+            *slot = slot.saturating_add(1);
+        }
+    }
+}
+
+impl Serialize for InputRejectionCounts {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(InputRejection::COUNT))?;
+        for reason in InputRejection::ALL {
+            map.serialize_entry(reason.as_str(), &self.get(reason))?;
+        }
+        map.end()
+    }
+}
+
+/// Why a received datagram failed to decode.
+///
+/// Counted per socket in [`DecodeFailureCounts`], read with
+/// [`UdpNonBlockingSocket::decode_failures`](crate::UdpNonBlockingSocket::decode_failures).
+/// Each variant matches one [`CodecError`] returned by
+/// [`decode_message`]; see [`CodecError::decode_failure`].
+///
+/// [`CodecError`]: crate::network::codec::CodecError
+/// [`CodecError::decode_failure`]: crate::network::codec::CodecError::decode_failure
+/// [`decode_message`]: crate::network::codec::decode_message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecodeFailure {
+    /// The datagram ended before the message did. Usually the transport cut
+    /// it, for example a receive buffer smaller than the peer's packet.
+    Truncated,
+    /// Bytes followed a complete message.
+    TrailingBytes,
+    /// The datagram held a value the message schema does not allow, or was
+    /// not a Fortress message at all.
+    Malformed,
+    /// The datagram came from a peer speaking another protocol version.
+    UnsupportedVersion,
+}
+
+impl DecodeFailure {
+    /// The number of failure categories.
+    pub const COUNT: usize = 4;
+
+    /// Every category, in declaration order. Its length is [`Self::COUNT`].
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::Truncated,
+        Self::TrailingBytes,
+        Self::Malformed,
+        Self::UnsupportedVersion,
+    ];
+
+    /// A stable snake_case label for this category, suitable for logging or as
+    /// a metrics key. Matches the JSON key produced by serialization.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Truncated => "truncated",
+            Self::TrailingBytes => "trailing_bytes",
+            Self::Malformed => "malformed",
+            Self::UnsupportedVersion => "unsupported_version",
+        }
+    }
+
+    /// The array index this category occupies in [`DecodeFailureCounts`].
+    /// Always less than [`Self::COUNT`].
+    const fn index(self) -> usize {
+        match self {
+            Self::Truncated => 0,
+            Self::TrailingBytes => 1,
+            Self::Malformed => 2,
+            Self::UnsupportedVersion => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for DecodeFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Per-[`DecodeFailure`] counters, keyed by category.
+///
+/// Serializes as a JSON object keyed by each category's
+/// [`DecodeFailure::as_str`] label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeFailureCounts([u64; DecodeFailure::COUNT]);
+
+impl Default for DecodeFailureCounts {
+    fn default() -> Self {
+        Self([0; DecodeFailure::COUNT])
+    }
+}
+
+impl DecodeFailureCounts {
+    /// The count recorded for `failure`.
+    #[must_use]
+    pub fn get(&self, failure: DecodeFailure) -> u64 {
+        self.0.get(failure.index()).copied().unwrap_or(0)
+    }
+
+    /// The total number of datagrams that failed to decode.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.0.iter().copied().fold(0u64, u64::saturating_add)
+    }
+
+    /// Increments the counter for `failure` by one, saturating at [`u64::MAX`].
+    pub(crate) fn record(&mut self, failure: DecodeFailure) {
+        if let Some(slot) = self.0.get_mut(failure.index()) {
+            *slot = slot.saturating_add(1);
+        }
+    }
+}
+
+impl Serialize for DecodeFailureCounts {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(DecodeFailure::COUNT))?;
+        for failure in DecodeFailure::ALL {
+            map.serialize_entry(failure.as_str(), &self.get(failure))?;
+        }
+        map.end()
+    }
+}
+
 /// A per-peer snapshot of protocol-level traffic and connection metrics for one
 /// remote endpoint.
 ///
@@ -859,8 +1266,9 @@ fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Err
 ///
 /// # Counters vs gauges
 ///
-/// The byte, packet, message-kind, and input-compression fields are **cumulative
-/// counters**, monotonic for the life of the endpoint. The trailing five fields —
+/// The byte, packet, message-kind, input-compression, and rejection fields are
+/// **cumulative counters**, monotonic for the life of the endpoint. The trailing
+/// five fields —
 /// [`pending_output_len`](Self::pending_output_len),
 /// [`pending_checksums_len`](Self::pending_checksums_len),
 /// [`ping_ms`](Self::ping_ms),
@@ -943,6 +1351,24 @@ pub struct PeerMetrics {
     /// **after** delta/RLE compression.
     pub input_bytes_post_compression: u64,
 
+    /// Cumulative already-acknowledged input frames resent ahead of the
+    /// unacknowledged ones, per
+    /// [`ProtocolConfig::redundant_input_frames`](crate::ProtocolConfig::redundant_input_frames).
+    /// Stays 0 at the default of one frame.
+    pub redundant_input_frames_sent: u64,
+
+    /// Raw input bytes of the
+    /// [`redundant_input_frames_sent`](Self::redundant_input_frames_sent),
+    /// included in [`input_bytes_pre_compression`](Self::input_bytes_pre_compression):
+    /// the bandwidth cost of redundancy before compression.
+    pub redundant_input_bytes_pre_compression: u64,
+
+    /// Cumulative `SyncRequest`s from this spectator address ignored because
+    /// they lacked a valid proof of
+    /// [`SpectatorConfig::access_token`](crate::SpectatorConfig::access_token).
+    /// Stays 0 without an access token.
+    pub unauthorized_sync_requests: u64,
+
     /// Cumulative logical messages whose complete Fortress wire payload was at
     /// least 1200 bytes when queued for this endpoint. This conservative budget
     /// is portable across common UDP, WebRTC, QUIC-datagram, and tunneled paths;
@@ -956,6 +1382,26 @@ pub struct PeerMetrics {
     /// smaller sizes. This counter is diagnostic, not path-MTU discovery.
     pub fragmentation_risk_messages_sent: u64,
 
+    /// Per-[`InputRejection`] count of `Input` and `InputAck` messages from
+    /// this peer that were dropped as malformed or adversarial.
+    pub input_messages_rejected: InputRejectionCounts,
+
+    /// Cumulative `Input` packets sent to this peer in the self-contained
+    /// resync encoding because the unacknowledged backlog exceeded
+    /// [`ProtocolConfig::input_resync_threshold`]. Stays 0 unless both peers
+    /// enabled input resync.
+    ///
+    /// [`ProtocolConfig::input_resync_threshold`]: crate::ProtocolConfig::input_resync_threshold
+    pub input_resyncs_sent: u64,
+
+    /// Cumulative frame tags queued for this peer and dropped before it
+    /// acknowledged them, because they fell more than the prediction window
+    /// behind a newer tag. Stays 0 unless both peers enabled
+    /// [`ProtocolConfig::frame_tags`].
+    ///
+    /// [`ProtocolConfig::frame_tags`]: crate::ProtocolConfig::frame_tags
+    pub frame_tags_dropped: u64,
+
     /// **Gauge.** The number of input frames queued for (re)transmission that the
     /// peer has not yet acknowledged — the connection-backpressure signal also
     /// reported as
@@ -972,11 +1418,11 @@ pub struct PeerMetrics {
     pub ping_ms: u128,
 
     /// **Gauge.** The peer's most recently reported frame-advantage value — the
-    /// same quantity [`NetworkStats::remote_frames_behind`] surfaces (the remote
+    /// same quantity [`NetworkStats::remote_frame_advantage`] surfaces (the remote
     /// player's own estimate of the local↔remote frame gap; see that field for
     /// the sign convention).
     ///
-    /// [`NetworkStats::remote_frames_behind`]: crate::NetworkStats::remote_frames_behind
+    /// [`NetworkStats::remote_frame_advantage`]: crate::NetworkStats::remote_frame_advantage
     pub remote_frame_advantage: i32,
 
     /// **Gauge.** The rolling average of the local/remote frame-advantage
@@ -1008,6 +1454,308 @@ pub fn to_json_pretty(&self) -> Option<String> {
     }
 }
 
+/// How well the delta/RLE input encoder compresses the inputs sent to one
+/// remote peer or spectator, returned by
+/// [`P2PSession::compression_stats`].
+///
+/// Besides the packet-size averages, [`byte_change_counts`] records, for each
+/// byte offset of the serialized local input, how many consecutive frames
+/// changed that byte. The encoder compresses unchanged bytes almost for free,
+/// so offsets with high counts are the volatile fields: grouping them together
+/// and keeping stable fields apart shortens the runs the encoder has to spell
+/// out. Each pair of consecutive frames is counted once, however often it is
+/// resent.
+///
+/// Unlike [`PeerMetrics`], these statistics can be cleared with
+/// [`P2PSession::reset_compression_stats`], for example to measure one input
+/// layout after another.
+///
+/// This type is `#[non_exhaustive]`: future library versions may add fields
+/// without a breaking change, so match with `..`.
+///
+/// [`P2PSession::compression_stats`]: crate::P2PSession::compression_stats
+/// [`P2PSession::reset_compression_stats`]: crate::P2PSession::reset_compression_stats
+/// [`byte_change_counts`]: Self::byte_change_counts
+///
+/// # Example
+///
+/// ```
+/// # use fortress_rollback::metrics::CompressionStats;
+/// let stats = CompressionStats::default();
+/// assert_eq!(stats.input_packets, 0);
+/// assert_eq!(stats.compression_ratio(), None);
+/// assert_eq!(stats.change_frequency(3), 0.0);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[must_use = "CompressionStats should be inspected after being queried"]
+pub struct CompressionStats {
+    /// `Input` packets sent, each carrying one encoded batch of frames.
+    pub input_packets: u64,
+
+    /// Raw input bytes batched into those packets before compression.
+    pub raw_bytes: u64,
+
+    /// Encoded input bytes placed in those packets after compression.
+    pub encoded_bytes: u64,
+
+    /// Pairs of consecutive frames compared for
+    /// [`byte_change_counts`](Self::byte_change_counts).
+    pub frames_compared: u64,
+
+    /// For each byte offset of the serialized local input, how many of the
+    /// [`frames_compared`](Self::frames_compared) pairs changed it. As long as
+    /// the longest input sent.
+    pub byte_change_counts: Vec<u64>,
+}
+
+impl CompressionStats {
+    /// Average encoded input bytes per `Input` packet, or 0 before the first.
+    #[must_use]
+    pub fn average_encoded_bytes(&self) -> f64 {
+        if self.input_packets == 0 {
+            return 0.0;
+        }
+        self.encoded_bytes as f64 / self.input_packets as f64
+    }
+
+    /// Average raw input bytes per `Input` packet, or 0 before the first.
+    #[must_use]
+    pub fn average_raw_bytes(&self) -> f64 {
+        if self.input_packets == 0 {
+            return 0.0;
+        }
+        self.raw_bytes as f64 / self.input_packets as f64
+    }
+
+    /// Encoded bytes per raw byte: below 1 the encoder saves space. `None`
+    /// before any input was sent.
+    #[must_use]
+    pub fn compression_ratio(&self) -> Option<f64> {
+        if self.raw_bytes == 0 {
+            return None;
+        }
+        Some(self.encoded_bytes as f64 / self.raw_bytes as f64)
+    }
+
+    /// The share of consecutive frames, from 0 to 1, that changed the byte at
+    /// `offset`. 0 for offsets past the input and before any comparison.
+    #[must_use]
+    pub fn change_frequency(&self, offset: usize) -> f64 {
+        if self.frames_compared == 0 {
+            return 0.0;
+        }
+        let changes = self.byte_change_counts.get(offset).copied().unwrap_or(0);
+        changes as f64 / self.frames_compared as f64
+    }
+
+    /// Counts the byte offsets that differ between two consecutive frames.
+    /// Offsets present in only one of them count as changed.
+    pub(crate) fn record_change(&mut self, previous: &[u8], current: &[u8]) {
+        let len = previous.len().max(current.len());
+        if self.byte_change_counts.len() < len {
+            // alloc-bound: the length of a locally serialized input frame.
+            self.byte_change_counts.resize(len, 0);
+        }
+        for (offset, count) in self.byte_change_counts.iter_mut().take(len).enumerate() {
+            if previous.get(offset) != current.get(offset) {
+                *count = count.saturating_add(1);
+            }
+        }
+        self.frames_compared = self.frames_compared.saturating_add(1);
+    }
+}
+
+/// The entries one component of a [`MemoryReport`] holds and their
+/// estimated size.
+///
+/// # Example
+///
+/// ```
+/// # use fortress_rollback::metrics::MemoryUsage;
+/// let usage = MemoryUsage::default();
+/// assert_eq!(usage.count, 0);
+/// assert_eq!(usage.bytes, Some(0));
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MemoryUsage {
+    /// How many entries the component holds: saved states, input slots,
+    /// messages, checksums or events.
+    pub count: u64,
+    /// The estimated bytes the entries occupy, or `None` when that depends on
+    /// a size the crate cannot know (see
+    /// [`MemoryReport::saved_states`]).
+    pub bytes: Option<u64>,
+}
+
+impl Default for MemoryUsage {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            bytes: Some(0),
+        }
+    }
+}
+
+impl MemoryUsage {
+    /// `count` entries of `entry_bytes` bytes each.
+    pub(crate) fn of(count: usize, entry_bytes: usize) -> Self {
+        Self {
+            count: u64::try_from(count).unwrap_or(u64::MAX),
+            bytes: Some(u64::try_from(count.saturating_mul(entry_bytes)).unwrap_or(u64::MAX)),
+        }
+    }
+
+    /// `count` entries of `entry_bytes` bytes each, or just the count when
+    /// the entry size is unknown.
+    pub(crate) fn of_hint(count: usize, entry_bytes: Option<usize>) -> Self {
+        match entry_bytes {
+            Some(entry_bytes) => Self::of(count, entry_bytes),
+            None => Self {
+                count: u64::try_from(count).unwrap_or(u64::MAX),
+                bytes: None,
+            },
+        }
+    }
+
+    /// `count` entries occupying `bytes` bytes together.
+    pub(crate) fn measured(count: usize, bytes: usize) -> Self {
+        Self {
+            count: u64::try_from(count).unwrap_or(u64::MAX),
+            bytes: Some(u64::try_from(bytes).unwrap_or(u64::MAX)),
+        }
+    }
+
+    /// Adds another component's entries and bytes to this one. The sum has
+    /// no size if either side has none.
+    pub(crate) fn add(&mut self, other: Self) {
+        self.count = self.count.saturating_add(other.count);
+        self.bytes = self
+            .bytes
+            .zip(other.bytes)
+            .map(|(bytes, other)| bytes.saturating_add(other));
+    }
+}
+
+/// An estimate of the memory a session's buffers hold, per component,
+/// returned by [`P2PSession::memory_report`] and
+/// [`SpectatorSession::memory_report`].
+///
+/// Sizes the crate can measure are exact: input queue slots, checksums and
+/// events by their in-memory size, and buffered protocol messages by their
+/// encoded payload bytes. Only the size of a saved game state depends on the
+/// game; give it with
+/// [`SessionBuilder::with_state_size_hint`](crate::SessionBuilder::with_state_size_hint),
+/// or [`saved_states`](Self::saved_states) reports just the count. Heap data
+/// behind event payloads and the containers' own bookkeeping are not counted.
+///
+/// The protocol components sum every remote peer, spectator or host endpoint;
+/// read [`PeerMetrics`] for per-peer queue lengths.
+///
+/// This type is `#[non_exhaustive]`: future library versions may add
+/// components without a breaking change, so match with `..`.
+///
+/// [`P2PSession::memory_report`]: crate::P2PSession::memory_report
+/// [`SpectatorSession::memory_report`]: crate::SpectatorSession::memory_report
+///
+/// # Example
+///
+/// ```
+/// # use fortress_rollback::metrics::MemoryReport;
+/// let report = MemoryReport::default();
+/// assert_eq!(report.saved_states.count, 0);
+/// assert_eq!(report.total_bytes(), 0);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[must_use = "MemoryReport should be inspected after being queried"]
+pub struct MemoryReport {
+    /// Saved-state cells that hold a state, each counted at the state size
+    /// hint. `bytes` is `None` without a hint. With a
+    /// [`StateDiffCodec`](crate::StateDiffCodec), cells holding a diff count
+    /// too, so the estimate is an upper bound.
+    pub saved_states: MemoryUsage,
+
+    /// Input queue slots across all players, allocated up front at the
+    /// configured queue length, at the in-memory size of one input slot.
+    /// Spectators count their input buffer.
+    pub input_queues: MemoryUsage,
+
+    /// Inputs received from peers and kept for decoding the next ones, by
+    /// serialized input bytes.
+    pub recv_inputs: MemoryUsage,
+
+    /// Local inputs sent but not yet acknowledged, plus the acknowledged ones
+    /// kept for redundant sends, by serialized input bytes.
+    pub pending_output: MemoryUsage,
+
+    /// Messages queued for the socket, by encoded bytes.
+    pub send_queue: MemoryUsage,
+
+    /// Peers' confirmed-frame checksums awaiting comparison.
+    pub pending_checksums: MemoryUsage,
+
+    /// Local confirmed-frame checksums kept for desync detection. Always
+    /// empty for spectators.
+    pub checksum_history: MemoryUsage,
+
+    /// Events waiting to be drained, at the in-memory size of one event.
+    pub event_queue: MemoryUsage,
+}
+
+impl MemoryReport {
+    /// The sum of every component's bytes. Components without a size (saved
+    /// states without a hint) add nothing.
+    #[must_use]
+    pub fn total_bytes(&self) -> u64 {
+        // Destructure to ensure all fields are included when new fields are added.
+        let Self {
+            saved_states,
+            input_queues,
+            recv_inputs,
+            pending_output,
+            send_queue,
+            pending_checksums,
+            checksum_history,
+            event_queue,
+        } = self;
+        [
+            saved_states,
+            input_queues,
+            recv_inputs,
+            pending_output,
+            send_queue,
+            pending_checksums,
+            checksum_history,
+            event_queue,
+        ]
+        .iter()
+        .filter_map(|usage| usage.bytes)
+        .fold(0, u64::saturating_add)
+    }
+
+    /// Serializes this report to a compact JSON string.
+    ///
+    /// Returns `None` if serialization fails — not expected for a few
+    /// integers, but possible (for example, an allocation failure inside
+    /// `serde_json`).
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn to_json(&self) -> Option<String> {
+        serde_json::to_string(self).ok()
+    }
+
+    /// Serializes this report to a pretty-printed JSON string.
+    ///
+    /// Like [`to_json`](Self::to_json), but indented for readability.
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn to_json_pretty(&self) -> Option<String> {
+        serde_json::to_string_pretty(self).ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1091,6 +1839,17 @@ fn session_metrics_record_event_discard_bumps_total_and_kind() {
         );
     }
 
+    #[test]
+    fn session_metrics_peer_address_migration_counter_saturates() {
+        let mut metrics = SessionMetrics::new();
+        metrics.record_peer_address_migration();
+        assert_eq!(metrics.peer_address_migrations, 1);
+
+        metrics.peer_address_migrations = u64::MAX;
+        metrics.record_peer_address_migration();
+        assert_eq!(metrics.peer_address_migrations, u64::MAX);
+    }
+
     #[test]
     fn session_metrics_unknown_source_counter_saturates() {
         let mut metrics = SessionMetrics::new();
@@ -1384,6 +2143,24 @@ fn message_kind_counts_record_get_and_total_saturate() {
         assert_eq!(counts.total(), 3);
     }
 
+    #[test]
+    fn input_rejection_index_and_labels_cover_all() {
+        let mut seen = std::collections::BTreeSet::new();
+        for (i, reason) in InputRejection::ALL.into_iter().enumerate() {
+            assert_eq!(reason.index(), i, "index of {reason:?}");
+            assert!(seen.insert(reason.as_str()), "duplicate label {reason}");
+        }
+        assert_eq!(seen.len(), InputRejection::COUNT);
+
+        let mut counts = InputRejectionCounts::default();
+        counts.record(InputRejection::FutureAck);
+        counts.record(InputRejection::FutureAck);
+        counts.record(InputRejection::FrameRangeOutOfBounds);
+        assert_eq!(counts.get(InputRejection::FutureAck), 2);
+        assert_eq!(counts.get(InputRejection::InvalidStartFrame), 0);
+        assert_eq!(counts.total(), 3);
+    }
+
     #[test]
     fn peer_metrics_default_is_zero() {
         let m = PeerMetrics::default();
@@ -1395,8 +2172,14 @@ fn peer_metrics_default_is_zero() {
         assert_eq!(m.messages_received_by_kind.total(), 0);
         assert_eq!(m.input_bytes_pre_compression, 0);
         assert_eq!(m.input_bytes_post_compression, 0);
+        assert_eq!(m.redundant_input_frames_sent, 0);
+        assert_eq!(m.redundant_input_bytes_pre_compression, 0);
+        assert_eq!(m.unauthorized_sync_requests, 0);
         assert_eq!(m.portability_risk_messages_sent, 0);
         assert_eq!(m.fragmentation_risk_messages_sent, 0);
+        assert_eq!(m.input_messages_rejected.total(), 0);
+        assert_eq!(m.input_resyncs_sent, 0);
+        assert_eq!(m.frame_tags_dropped, 0);
         assert_eq!(m.pending_output_len, 0);
         assert_eq!(m.pending_checksums_len, 0);
         assert_eq!(m.ping_ms, 0);
@@ -1430,4 +2213,25 @@ fn peer_metrics_serializes_kind_breakdown_as_labeled_map() {
         assert!(json.contains(r#""input":1"#), "{json}");
         assert!(json.contains(r#""keep_alive":0"#), "{json}");
     }
+
+    #[test]
+    fn memory_usage_sum_has_no_size_if_either_side_has_none() {
+        let mut usage = MemoryUsage::of(3, 8);
+        assert_eq!((usage.count, usage.bytes), (3, Some(24)));
+        usage.add(MemoryUsage::measured(2, 5));
+        assert_eq!((usage.count, usage.bytes), (5, Some(29)));
+        usage.add(MemoryUsage::of_hint(1, None));
+        assert_eq!((usage.count, usage.bytes), (6, None));
+    }
+
+    #[test]
+    fn memory_report_total_skips_unsized_components() {
+        let report = MemoryReport {
+            saved_states: MemoryUsage::of_hint(5, None),
+            input_queues: MemoryUsage::of(256, 8),
+            event_queue: MemoryUsage::of(2, 100),
+            ..MemoryReport::default()
+        };
+        assert_eq!(report.total_bytes(), 256 * 8 + 200);
+    }
 }
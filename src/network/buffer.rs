@@ -21,8 +21,15 @@
 use crate::network::messages::Message;
 use crate::report_violation;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
+use crate::SocketErrorKind;
 use std::io::{Error, ErrorKind};
 
+/// What the socket adapters' `try_send_to` returns for a message that could
+/// not be encoded.
+pub(crate) const ENCODE_FAILED: SocketErrorKind = SocketErrorKind::SendFailed {
+    kind: ErrorKind::InvalidData,
+};
+
 /// Reports a reused send buffer that was too small for `message`.
 pub(crate) fn report_send_buffer_too_small(message: &Message, provided: usize) {
     report_violation!(
@@ -69,9 +76,10 @@ pub(crate) fn zeroed_buffer(size: usize, name: &'static str) -> Result<Vec<u8>,
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+#[cfg_attr(not(feature = "telemetry"), allow(dead_code, unused_imports))]
 mod tests {
     use super::*;
-    use crate::network::messages::{MessageBody, MessageHeader};
+    use crate::network::messages::{KeepAlive, MessageBody, MessageHeader};
     use crate::telemetry::{
         push_violation_observer, CollectingObserver, ViolationObserver, ViolationSeverity,
     };
@@ -98,11 +106,12 @@ fn zeroed_buffer_one_byte_is_allowed() {
         assert_eq!(buffer.len(), 1);
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn send_buffer_warning_reports_required_encoded_length_and_capacity() {
         let message = Message {
             header: MessageHeader::new(1),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let observer = Arc::new(CollectingObserver::new());
         let _guard = push_violation_observer(Arc::clone(&observer) as Arc<dyn ViolationObserver>);
@@ -32,6 +32,7 @@
 //! - **Duplication**: Randomly duplicate packets
 //! - **Reordering**: Shuffle packet delivery order
 //! - **Asymmetric Conditions**: Different settings for send vs receive
+//! - **Scenarios**: Time-varying conditions as a script of [`ChaosPhase`]s
 //! - **Deterministic**: Seeded RNG for reproducible test scenarios
 
 use std::collections::VecDeque;
@@ -47,7 +48,7 @@
 use crate::report_violation;
 use crate::rng::{Pcg32, Rng, SeedableRng};
 use crate::telemetry::{ViolationKind, ViolationSeverity};
-use crate::NonBlockingSocket;
+use crate::{DecodeFailureCounts, NonBlockingSocket, SocketErrorKind};
 
 /// Configuration for network chaos simulation.
 ///
@@ -88,6 +89,47 @@ pub struct ChaosConfig {
 
     /// Random seed for deterministic behavior (default: random)
     pub seed: Option<u64>,
+
+    /// Scenario script of time-varying conditions (default: empty)
+    ///
+    /// While a phase is active, its config's chaos parameters replace the ones
+    /// above; its `seed`, `phases`, and `repeat_phases` are ignored. The script
+    /// starts the first time the socket sends or polls, and all phases draw from
+    /// the socket's single seeded RNG. Once a script that does not repeat ends,
+    /// the parameters above apply again.
+    pub phases: Vec<ChaosPhase>,
+
+    /// Whether the phase script restarts after its last phase (default: false)
+    pub repeat_phases: bool,
+}
+
+/// How long a [`ChaosPhase`] lasts.
+///
+/// Durations are measured on the socket's clock (see
+/// [`ChaosSocket::with_clock`]), so a test clock makes them deterministic.
+/// Converts from [`Duration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChaosPhaseDuration {
+    /// The phase ends once this much time has passed since it started.
+    Time(Duration),
+    /// The phase ends after this many packets: every packet passed to
+    /// `send_to` and every packet received from the inner socket counts.
+    Packets(u64),
+}
+
+impl From<Duration> for ChaosPhaseDuration {
+    fn from(duration: Duration) -> Self {
+        Self::Time(duration)
+    }
+}
+
+/// One phase of a [`ChaosConfig`] scenario script.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosPhase {
+    /// How long the phase lasts.
+    pub duration: ChaosPhaseDuration,
+    /// The conditions while the phase is active.
+    pub config: ChaosConfig,
 }
 
 impl Default for ChaosConfig {
@@ -103,6 +145,8 @@ fn default() -> Self {
             burst_loss_probability: 0.0,
             burst_loss_length: 0,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 }
@@ -121,6 +165,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             burst_loss_probability,
             burst_loss_length,
             seed,
+            phases,
+            repeat_phases,
         } = self;
 
         write!(
@@ -136,7 +182,16 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             burst_loss_probability * 100.0,
             burst_loss_length,
             seed.map_or_else(|| "None".to_string(), |s| s.to_string()),
-        )
+        )?;
+        if !phases.is_empty() {
+            write!(
+                f,
+                " + {} phase(s){}",
+                phases.len(),
+                if *repeat_phases { ", repeating" } else { "" }
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -165,6 +220,8 @@ pub fn high_latency(latency_ms: u64) -> Self {
             burst_loss_probability: 0.0,
             burst_loss_length: 0,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 
@@ -182,6 +239,8 @@ pub fn lossy(loss_rate: f64) -> Self {
             burst_loss_probability: 0.0,
             burst_loss_length: 0,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 
@@ -199,6 +258,8 @@ pub fn poor_network() -> Self {
             burst_loss_probability: 0.0,
             burst_loss_length: 0,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 
@@ -216,6 +277,8 @@ pub fn terrible_network() -> Self {
             burst_loss_probability: 0.0,
             burst_loss_length: 0,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 
@@ -244,6 +307,8 @@ pub fn mobile_network() -> Self {
             burst_loss_probability: 0.02,
             burst_loss_length: 4,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 
@@ -271,6 +336,8 @@ pub fn wifi_interference() -> Self {
             burst_loss_probability: 0.05,
             burst_loss_length: 3,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 
@@ -296,6 +363,8 @@ pub fn intercontinental() -> Self {
             burst_loss_probability: 0.0,
             burst_loss_length: 0,
             seed: None,
+            phases: Vec::new(),
+            repeat_phases: false,
         }
     }
 }
@@ -390,6 +459,41 @@ pub fn seed(mut self, seed: u64) -> Self {
         self
     }
 
+    /// Appends a scenario phase lasting `duration`, with conditions set by
+    /// `configure` on a fresh builder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::ChaosConfig;
+    /// use std::time::Duration;
+    ///
+    /// // Clean for a second, then 30% loss for a second, then clean again.
+    /// let config = ChaosConfig::builder()
+    ///     .phase(Duration::from_secs(1), |b| b)
+    ///     .phase(Duration::from_secs(1), |b| b.packet_loss_rate(0.3))
+    ///     .seed(7)
+    ///     .build();
+    /// assert_eq!(config.phases.len(), 2);
+    /// ```
+    pub fn phase(
+        mut self,
+        duration: impl Into<ChaosPhaseDuration>,
+        configure: impl FnOnce(Self) -> Self,
+    ) -> Self {
+        self.config.phases.push(ChaosPhase {
+            duration: duration.into(),
+            config: configure(Self::new()).build(),
+        });
+        self
+    }
+
+    /// Sets whether the phase script restarts after its last phase.
+    pub fn repeat_phases(mut self, repeat: bool) -> Self {
+        self.config.repeat_phases = repeat;
+        self
+    }
+
     /// Builds the configuration.
     pub fn build(self) -> ChaosConfig {
         self.config
@@ -411,18 +515,18 @@ struct InFlightPacket<A> {
 ///
 /// # Type Parameters
 ///
-/// - `A`: The address type (must match the inner socket)
+/// - `A`: The address type (must match the inner socket). Any address the
+///   inner socket accepts works, not just `SocketAddr`; chaos decisions never
+///   depend on the address, so a seeded socket behaves the same for every
+///   address type.
 /// - `S`: The inner socket type implementing [`NonBlockingSocket<A>`]
 ///
 /// # Thread Safety
 ///
 /// When the `sync-send` feature is enabled, `ChaosSocket` implements
-/// `Send + Sync` if the inner socket does.
-pub struct ChaosSocket<A, S>
-where
-    A: Clone + PartialEq + Eq + Hash + Send + Sync,
-    S: NonBlockingSocket<A>,
-{
+/// `Send + Sync` if the inner socket does. Without it, the address type need
+/// not be `Send` or `Sync`.
+pub struct ChaosSocket<A, S> {
     inner: S,
     config: ChaosConfig,
     rng: Pcg32,
@@ -436,6 +540,18 @@ pub struct ChaosSocket<A, S>
     /// Remaining packets to drop in current burst loss event
     burst_loss_remaining: usize,
 
+    /// Index of the active scenario phase in `config.phases`
+    phase_index: usize,
+
+    /// When the active phase started; `None` until the socket is first used
+    phase_started_at: Option<Instant>,
+
+    /// Packets handled during the active phase
+    phase_packets: u64,
+
+    /// Whether a non-repeating phase script has run past its last phase
+    phases_finished: bool,
+
     /// Statistics tracking
     stats: ChaosStats,
 
@@ -466,6 +582,9 @@ pub struct ChaosStats {
     pub burst_loss_events: u64,
     /// Packets dropped due to burst loss
     pub packets_dropped_burst: u64,
+    /// Index of the active scenario phase, or `None` without a phase script,
+    /// before it starts, and after a non-repeating script ends
+    pub active_phase: Option<usize>,
 }
 
 impl std::fmt::Display for ChaosStats {
@@ -480,10 +599,11 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             packets_reordered,
             burst_loss_events,
             packets_dropped_burst,
+            active_phase,
         } = self;
         write!(
             f,
-            "ChaosStats {{ sent: {}, dropped_send: {}, dup: {}, recv: {}, dropped_recv: {}, reordered: {}, bursts: {}, dropped_burst: {} }}",
+            "ChaosStats {{ sent: {}, dropped_send: {}, dup: {}, recv: {}, dropped_recv: {}, reordered: {}, bursts: {}, dropped_burst: {}",
             packets_sent,
             packets_dropped_send,
             packets_duplicated,
@@ -492,10 +612,15 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             packets_reordered,
             burst_loss_events,
             packets_dropped_burst
-        )
+        )?;
+        if let Some(phase) = active_phase {
+            write!(f, ", phase: {}", phase)?;
+        }
+        write!(f, " }}")
     }
 }
 
+#[cfg(feature = "sync-send")]
 impl<A, S> ChaosSocket<A, S>
 where
     A: Clone + PartialEq + Eq + Hash + Send + Sync,
@@ -503,6 +628,24 @@ impl<A, S> ChaosSocket<A, S>
 {
     /// Creates a new chaos socket wrapping the given inner socket.
     pub fn new(inner: S, config: ChaosConfig) -> Self {
+        Self::wrap(inner, config)
+    }
+}
+
+#[cfg(not(feature = "sync-send"))]
+impl<A, S> ChaosSocket<A, S>
+where
+    A: Clone + PartialEq + Eq + Hash,
+    S: NonBlockingSocket<A>,
+{
+    /// Creates a new chaos socket wrapping the given inner socket.
+    pub fn new(inner: S, config: ChaosConfig) -> Self {
+        Self::wrap(inner, config)
+    }
+}
+
+impl<A, S> ChaosSocket<A, S> {
+    fn wrap(inner: S, config: ChaosConfig) -> Self {
         let rng = match config.seed {
             Some(seed) => Pcg32::seed_from_u64(seed),
             None => Pcg32::from_entropy(),
@@ -515,6 +658,10 @@ pub fn new(inner: S, config: ChaosConfig) -> Self {
             in_flight: VecDeque::new(),
             reorder_buffer: Vec::new(),
             burst_loss_remaining: 0,
+            phase_index: 0,
+            phase_started_at: None,
+            phase_packets: 0,
+            phases_finished: false,
             stats: ChaosStats::default(),
             clock_fn: None,
         }
@@ -541,8 +688,15 @@ pub fn config(&self) -> &ChaosConfig {
     }
 
     /// Updates the chaos configuration.
+    ///
+    /// The new config's phase script, if any, starts over from its first phase.
     pub fn set_config(&mut self, config: ChaosConfig) {
         self.config = config;
+        self.phase_index = 0;
+        self.phase_started_at = None;
+        self.phase_packets = 0;
+        self.phases_finished = false;
+        self.stats.active_phase = None;
     }
 
     /// Returns statistics about chaos behavior.
@@ -550,9 +704,12 @@ pub fn stats(&self) -> &ChaosStats {
         &self.stats
     }
 
-    /// Resets statistics counters.
+    /// Resets statistics counters. The active phase is kept.
     pub fn reset_stats(&mut self) {
-        self.stats = ChaosStats::default();
+        self.stats = ChaosStats {
+            active_phase: self.stats.active_phase,
+            ..ChaosStats::default()
+        };
     }
 
     /// Returns the number of packets currently in flight (delayed).
@@ -606,12 +763,69 @@ fn now(&self) -> Instant {
         }
     }
 
+    /// Returns the conditions in effect: the active phase's config, or the
+    /// top-level config without an active phase.
+    fn conditions(&self) -> &ChaosConfig {
+        if self.phases_finished || self.phase_started_at.is_none() {
+            return &self.config;
+        }
+        self.config
+            .phases
+            .get(self.phase_index)
+            .map_or(&self.config, |phase| &phase.config)
+    }
+
+    /// Starts the phase script on first use and moves past every phase that
+    /// has ended by now.
+    fn advance_phase(&mut self) {
+        if self.config.phases.is_empty() || self.phases_finished {
+            return;
+        }
+        let now = self.now();
+        let mut started_at = *self.phase_started_at.get_or_insert(now);
+        // Each iteration ends one phase, so a script of zero-length phases
+        // cannot spin forever.
+        for _ in 0..self.config.phases.len() {
+            let Some(phase) = self.config.phases.get(self.phase_index) else {
+                break;
+            };
+            let next_start = match phase.duration {
+                ChaosPhaseDuration::Time(duration) => match started_at.checked_add(duration) {
+                    Some(end) if end <= now => end,
+                    _ => break,
+                },
+                ChaosPhaseDuration::Packets(count) if self.phase_packets >= count => now,
+                ChaosPhaseDuration::Packets(_) => break,
+            };
+            started_at = next_start;
+            self.phase_packets = 0;
+            self.phase_index += 1;
+            if self.phase_index >= self.config.phases.len() {
+                if !self.config.repeat_phases {
+                    self.phases_finished = true;
+                    break;
+                }
+                self.phase_index = 0;
+            }
+        }
+        self.phase_started_at = Some(started_at);
+        self.stats.active_phase = (!self.phases_finished).then_some(self.phase_index);
+    }
+
+    /// Counts a packet towards the active phase, after moving past any phase
+    /// that has ended.
+    fn count_phase_packet(&mut self) {
+        self.advance_phase();
+        self.phase_packets = self.phase_packets.saturating_add(1);
+    }
+
     /// Calculates the delivery time for a packet with latency and jitter.
     fn calculate_delivery_time(&mut self) -> Instant {
         let now = self.now();
-        let base_latency = self.config.latency;
-        let jitter = if self.config.jitter > Duration::ZERO {
-            let jitter_range = self.config.jitter.as_nanos() as i64;
+        let base_latency = self.conditions().latency;
+        let jitter_limit = self.conditions().jitter;
+        let jitter = if jitter_limit > Duration::ZERO {
+            let jitter_range = jitter_limit.as_nanos() as i64;
             let jitter_offset = self
                 .rng
                 .gen_range_i64_inclusive(-jitter_range..=jitter_range);
@@ -643,7 +857,7 @@ fn should_drop(&mut self, rate: f64) -> bool {
 
     /// Determines if a packet should be duplicated.
     fn should_duplicate(&mut self) -> bool {
-        self.should_drop(self.config.duplication_rate)
+        self.should_drop(self.conditions().duplication_rate)
     }
 
     /// Determines if a packet should be dropped due to burst loss.
@@ -657,11 +871,11 @@ fn should_drop_burst(&mut self) -> bool {
         }
 
         // Check if we should start a new burst
-        if self.config.burst_loss_length > 0 && self.should_drop(self.config.burst_loss_probability)
-        {
+        let burst_loss_length = self.conditions().burst_loss_length;
+        if burst_loss_length > 0 && self.should_drop(self.conditions().burst_loss_probability) {
             self.stats.burst_loss_events += 1;
             // Drop this packet and set up remaining burst
-            self.burst_loss_remaining = self.config.burst_loss_length.saturating_sub(1);
+            self.burst_loss_remaining = burst_loss_length.saturating_sub(1);
             self.stats.packets_dropped_burst += 1;
             return true;
         }
@@ -724,7 +938,7 @@ fn deliver_ready_packets(&mut self) -> Vec<(A, Message)> {
     /// prevent indefinite holding - this simulates the real-world behavior where
     /// delayed packets eventually arrive.
     fn apply_reordering(&mut self, messages: &mut Vec<(A, Message)>) {
-        if self.config.reorder_buffer_size == 0 || self.config.reorder_rate <= 0.0 {
+        if self.conditions().reorder_buffer_size == 0 || self.conditions().reorder_rate <= 0.0 {
             return;
         }
 
@@ -758,7 +972,7 @@ fn apply_reordering(&mut self, messages: &mut Vec<(A, Message)>) {
         // 1. Buffer is full enough for meaningful reordering, OR
         // 2. No new messages arrived and buffer has packets (prevent indefinite holding)
         let reorder_threshold = self
-            .config
+            .conditions()
             .reorder_buffer_size
             .min(MAX_RECEIVE_MESSAGES_PER_POLL);
         let should_release = self.reorder_buffer.len() >= reorder_threshold
@@ -785,12 +999,13 @@ fn apply_reordering(&mut self, messages: &mut Vec<(A, Message)>) {
 
     /// Determines if a packet swap should occur based on reorder_rate.
     fn should_reorder(&mut self) -> bool {
-        if self.config.reorder_rate <= 0.0 {
+        let reorder_rate = self.conditions().reorder_rate;
+        if reorder_rate <= 0.0 {
             false
-        } else if self.config.reorder_rate >= 1.0 {
+        } else if reorder_rate >= 1.0 {
             true
         } else {
-            self.rng.gen::<f64>() < self.config.reorder_rate
+            self.rng.gen::<f64>() < reorder_rate
         }
     }
 
@@ -815,8 +1030,10 @@ fn queue_new_messages(&mut self, new_messages: Vec<(A, Message)>) {
                 continue;
             }
 
+            self.count_phase_packet();
+
             // Apply receive-side packet loss before queueing.
-            if self.should_drop(self.config.receive_loss_rate) {
+            if self.should_drop(self.conditions().receive_loss_rate) {
                 self.stats.packets_dropped_receive += 1;
                 continue;
             }
@@ -853,12 +1070,14 @@ fn queue_new_messages(&mut self, new_messages: Vec<(A, Message)>) {
         }
     }
 
-    fn receive_all_messages_impl(&mut self) -> Vec<(A, Message)> {
-        let new_messages = self.inner.receive_all_messages();
+    /// Runs freshly received messages through the receive-side chaos and
+    /// returns the packets due now.
+    fn receive_all_messages_impl(&mut self, new_messages: Vec<(A, Message)>) -> Vec<(A, Message)> {
+        self.advance_phase();
         self.queue_new_messages(new_messages);
 
         // Sort by delivery time to maintain order (unless reordering is enabled).
-        if self.config.reorder_rate <= 0.0 {
+        if self.conditions().reorder_rate <= 0.0 {
             self.in_flight
                 .make_contiguous()
                 .sort_by_key(|p| p.deliver_at);
@@ -883,31 +1102,45 @@ impl<A, S> NonBlockingSocket<A> for ChaosSocket<A, S>
     S: NonBlockingSocket<A> + Send + Sync,
 {
     fn send_to(&mut self, msg: &Message, addr: &A) {
+        // The inner socket reports its own failures.
+        let _ = self.try_send_to(msg, addr);
+    }
+
+    /// Simulated losses succeed, as they would on a real network; failures
+    /// of the inner socket are passed through.
+    fn try_send_to(&mut self, msg: &Message, addr: &A) -> Result<(), SocketErrorKind> {
         self.stats.packets_sent += 1;
+        self.count_phase_packet();
 
         // Check for burst loss first (takes priority)
         if self.should_drop_burst() {
-            return;
+            return Ok(());
         }
 
         // Check for packet loss on send
-        if self.should_drop(self.config.send_loss_rate) {
+        if self.should_drop(self.conditions().send_loss_rate) {
             self.stats.packets_dropped_send += 1;
-            return;
+            return Ok(());
         }
 
         // Send immediately to inner socket
-        self.inner.send_to(msg, addr);
+        self.inner.try_send_to(msg, addr)?;
 
         // Check for duplication - send additional copy
         if self.should_duplicate() {
             self.stats.packets_duplicated += 1;
-            self.inner.send_to(msg, addr);
+            self.inner.try_send_to(msg, addr)?;
         }
+        Ok(())
     }
 
     fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
-        self.receive_all_messages_impl()
+        let new_messages = self.inner.receive_all_messages();
+        self.receive_all_messages_impl(new_messages)
+    }
+
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        self.inner.decode_failures()
     }
 }
 
@@ -915,49 +1148,60 @@ fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
 #[cfg(not(feature = "sync-send"))]
 impl<A, S> NonBlockingSocket<A> for ChaosSocket<A, S>
 where
-    A: Clone + PartialEq + Eq + Hash + Send + Sync,
+    A: Clone + PartialEq + Eq + Hash,
     S: NonBlockingSocket<A>,
 {
     fn send_to(&mut self, msg: &Message, addr: &A) {
+        // The inner socket reports its own failures.
+        let _ = self.try_send_to(msg, addr);
+    }
+
+    /// Simulated losses succeed, as they would on a real network; failures
+    /// of the inner socket are passed through.
+    fn try_send_to(&mut self, msg: &Message, addr: &A) -> Result<(), SocketErrorKind> {
         self.stats.packets_sent += 1;
+        self.count_phase_packet();
 
         // Check for burst loss first (takes priority)
         if self.should_drop_burst() {
-            return;
+            return Ok(());
         }
 
         // Check for packet loss on send
-        if self.should_drop(self.config.send_loss_rate) {
+        if self.should_drop(self.conditions().send_loss_rate) {
             self.stats.packets_dropped_send += 1;
-            return;
+            return Ok(());
         }
 
         // Send immediately to inner socket
-        self.inner.send_to(msg, addr);
+        self.inner.try_send_to(msg, addr)?;
 
         // Check for duplication - send additional copy
         if self.should_duplicate() {
             self.stats.packets_duplicated += 1;
-            self.inner.send_to(msg, addr);
+            self.inner.try_send_to(msg, addr)?;
         }
+        Ok(())
     }
 
     fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
-        self.receive_all_messages_impl()
+        let new_messages = self.inner.receive_all_messages();
+        self.receive_all_messages_impl(new_messages)
+    }
+
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        self.inner.decode_failures()
     }
 }
 
-impl<A, S> fmt::Debug for ChaosSocket<A, S>
-where
-    A: Clone + PartialEq + Eq + Hash + Send + Sync,
-    S: NonBlockingSocket<A>,
-{
+impl<A, S> fmt::Debug for ChaosSocket<A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ChaosSocket")
             .field("config", &self.config)
             .field("stats", &self.stats)
             .field("packets_in_flight", &self.in_flight.len())
             .field("burst_loss_remaining", &self.burst_loss_remaining)
+            .field("phase_index", &self.phase_index)
             .field("has_custom_clock", &self.clock_fn.is_some())
             .finish_non_exhaustive()
     }
@@ -981,11 +1225,22 @@ mod tests {
     struct TestSocket {
         sent: Vec<(SocketAddr, Message)>,
         to_receive: Vec<(SocketAddr, Message)>,
+        refuse_sends: bool,
     }
 
     impl NonBlockingSocket<SocketAddr> for TestSocket {
         fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+            let _ = self.try_send_to(msg, addr);
+        }
+
+        fn try_send_to(&mut self, msg: &Message, addr: &SocketAddr) -> Result<(), SocketErrorKind> {
+            if self.refuse_sends {
+                return Err(SocketErrorKind::SendFailed {
+                    kind: std::io::ErrorKind::ConnectionRefused,
+                });
+            }
             self.sent.push((*addr, msg.clone()));
+            Ok(())
         }
 
         fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
@@ -1028,11 +1283,11 @@ fn test_addr() -> SocketAddr {
     }
 
     fn test_message() -> Message {
-        use crate::network::messages::{MessageBody, MessageHeader};
+        use crate::network::messages::{KeepAlive, MessageBody, MessageHeader};
 
         Message {
             header: MessageHeader::new(0),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         }
     }
 
@@ -1051,6 +1306,38 @@ fn test_passthrough_mode() {
         assert_eq!(socket.inner().sent.len(), 1);
     }
 
+    #[test]
+    fn test_try_send_to_reports_inner_failures_but_not_simulated_loss() {
+        let inner = TestSocket {
+            refuse_sends: true,
+            ..TestSocket::default()
+        };
+        let mut socket = ChaosSocket::new(inner, ChaosConfig::passthrough());
+        let addr = test_addr();
+        let msg = test_message();
+
+        assert_eq!(
+            socket.try_send_to(&msg, &addr),
+            Err(SocketErrorKind::SendFailed {
+                kind: std::io::ErrorKind::ConnectionRefused,
+            })
+        );
+
+        // A packet the chaos layer drops never reaches the inner socket, so
+        // it is not a send failure.
+        let inner = TestSocket {
+            refuse_sends: true,
+            ..TestSocket::default()
+        };
+        let config = ChaosConfig::builder()
+            .packet_loss_rate(1.0)
+            .seed(42)
+            .build();
+        let mut socket = ChaosSocket::new(inner, config);
+        assert_eq!(socket.try_send_to(&msg, &addr), Ok(()));
+        assert_eq!(socket.stats().packets_dropped_send, 1);
+    }
+
     #[test]
     fn test_packet_loss_100_percent() {
         let inner = TestSocket::default();
@@ -1914,10 +2201,10 @@ fn test_latency_fifo_ordering_without_jitter() {
         // Create distinct messages to track order
         let messages: Vec<Message> = (0..5)
             .map(|i| {
-                use crate::network::messages::{MessageBody, MessageHeader};
+                use crate::network::messages::{KeepAlive, MessageBody, MessageHeader};
                 Message {
                     header: MessageHeader::new(i),
-                    body: MessageBody::KeepAlive,
+                    body: MessageBody::KeepAlive(KeepAlive::default()),
                 }
             })
             .collect();
@@ -2339,6 +2626,197 @@ fn test_reorder_stats_accuracy() {
         );
     }
 
+    /// Two-phase script: clean for 1s, then 30% send loss for 1s.
+    fn clean_then_lossy() -> ChaosConfig {
+        ChaosConfig::builder()
+            .phase(Duration::from_secs(1), |b| b)
+            .phase(Duration::from_secs(1), |b| b.send_loss_rate(0.3))
+            .seed(42)
+            .build()
+    }
+
+    /// Sends one packet every 10ms for `packets` packets and returns whether
+    /// each one reached the inner socket, and the active phase when sent.
+    fn send_paced(
+        socket: &mut ChaosSocket<SocketAddr, TestSocket>,
+        clock: &TestClock,
+        packets: usize,
+    ) -> Vec<(bool, Option<usize>)> {
+        (0..packets)
+            .map(|_| {
+                let before = socket.inner().sent.len();
+                socket.send_to(&test_message(), &test_addr());
+                let delivered = socket.inner().sent.len() > before;
+                let phase = socket.stats().active_phase;
+                clock.advance(Duration::from_millis(10));
+                (delivered, phase)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_phases_loss_only_in_lossy_phase() {
+        let clock = TestClock::new();
+        let mut socket = ChaosSocket::new(TestSocket::default(), clean_then_lossy())
+            .with_clock(clock.as_clock_fn());
+
+        let sent = send_paced(&mut socket, &clock, 300);
+
+        // 100 packets per second: [0, 100) clean, [100, 200) lossy, then the
+        // script ends and the (clean) top-level config applies again.
+        assert!(sent[..100].iter().all(|&(delivered, _)| delivered));
+        assert!(sent[..100].iter().all(|&(_, phase)| phase == Some(0)));
+        let lost = sent[100..200]
+            .iter()
+            .filter(|&&(delivered, _)| !delivered)
+            .count();
+        assert!(
+            (10..=50).contains(&lost),
+            "expected roughly 30 of 100 packets lost in phase 1, got {lost}"
+        );
+        assert!(sent[100..200].iter().all(|&(_, phase)| phase == Some(1)));
+        assert!(sent[200..].iter().all(|&(delivered, _)| delivered));
+        assert!(sent[200..].iter().all(|&(_, phase)| phase.is_none()));
+        assert_eq!(socket.stats().packets_dropped_send, lost as u64);
+    }
+
+    #[test]
+    fn test_phases_are_deterministic_with_seed() {
+        let run = || {
+            let clock = TestClock::new();
+            let mut socket = ChaosSocket::new(TestSocket::default(), clean_then_lossy())
+                .with_clock(clock.as_clock_fn());
+            send_paced(&mut socket, &clock, 250)
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_phases_repeat() {
+        let clock = TestClock::new();
+        let config = ChaosConfig::builder()
+            .phase(Duration::from_secs(1), |b| b)
+            .phase(Duration::from_secs(1), |b| b.send_loss_rate(1.0))
+            .repeat_phases(true)
+            .build();
+        let mut socket =
+            ChaosSocket::new(TestSocket::default(), config).with_clock(clock.as_clock_fn());
+
+        let sent = send_paced(&mut socket, &clock, 400);
+
+        for (index, &(delivered, phase)) in sent.iter().enumerate() {
+            let lossy = (index / 100) % 2 == 1;
+            assert_eq!(delivered, !lossy, "packet {index}");
+            assert_eq!(phase, Some(usize::from(lossy)), "packet {index}");
+        }
+    }
+
+    #[test]
+    fn test_phases_by_packet_count() {
+        let config = ChaosConfig::builder()
+            .phase(ChaosPhaseDuration::Packets(3), |b| b.send_loss_rate(1.0))
+            .phase(ChaosPhaseDuration::Packets(2), |b| b)
+            .build();
+        let mut socket = ChaosSocket::new(TestSocket::default(), config);
+
+        for _ in 0..3 {
+            socket.send_to(&test_message(), &test_addr());
+        }
+        assert_eq!(socket.inner().sent.len(), 0);
+        assert_eq!(socket.stats().active_phase, Some(0));
+
+        for _ in 0..2 {
+            socket.send_to(&test_message(), &test_addr());
+        }
+        assert_eq!(socket.inner().sent.len(), 2);
+        assert_eq!(socket.stats().active_phase, Some(1));
+
+        // The script has ended: the top-level (passthrough) config applies.
+        socket.send_to(&test_message(), &test_addr());
+        assert_eq!(socket.inner().sent.len(), 3);
+        assert_eq!(socket.stats().active_phase, None);
+    }
+
+    #[test]
+    fn test_phases_count_received_packets() {
+        let mut inner = TestSocket::default();
+        for _ in 0..4 {
+            inner.to_receive.push((test_addr(), test_message()));
+        }
+        let config = ChaosConfig::builder()
+            .phase(ChaosPhaseDuration::Packets(2), |b| b.receive_loss_rate(1.0))
+            .build();
+        let mut socket = ChaosSocket::new(inner, config);
+
+        let received = socket.receive_all_messages();
+
+        assert_eq!(received.len(), 2);
+        assert_eq!(socket.stats().packets_dropped_receive, 2);
+    }
+
+    #[test]
+    fn test_set_config_restarts_phases_and_reset_stats_keeps_phase() {
+        let config = ChaosConfig::builder()
+            .phase(ChaosPhaseDuration::Packets(1), |b| b)
+            .phase(ChaosPhaseDuration::Packets(1), |b| b)
+            .build();
+        let mut socket = ChaosSocket::new(TestSocket::default(), config.clone());
+        socket.send_to(&test_message(), &test_addr());
+        socket.send_to(&test_message(), &test_addr());
+        assert_eq!(socket.stats().active_phase, Some(1));
+
+        socket.reset_stats();
+        assert_eq!(socket.stats().packets_sent, 0);
+        assert_eq!(socket.stats().active_phase, Some(1));
+
+        socket.set_config(config);
+        assert_eq!(socket.stats().active_phase, None);
+        socket.send_to(&test_message(), &test_addr());
+        assert_eq!(socket.stats().active_phase, Some(0));
+    }
+
+    #[test]
+    fn test_zero_length_repeating_phases_terminate() {
+        let config = ChaosConfig::builder()
+            .phase(Duration::ZERO, |b| b)
+            .phase(ChaosPhaseDuration::Packets(0), |b| b)
+            .repeat_phases(true)
+            .build();
+        let mut socket = ChaosSocket::new(TestSocket::default(), config);
+
+        socket.send_to(&test_message(), &test_addr());
+
+        assert_eq!(socket.inner().sent.len(), 1);
+        assert!(socket.stats().active_phase.is_some());
+    }
+
+    /// Without `sync-send`, the address type need not be `Send` or `Sync`.
+    #[cfg(not(feature = "sync-send"))]
+    #[test]
+    fn test_wraps_socket_with_non_send_address() {
+        use std::rc::Rc;
+
+        #[derive(Default)]
+        struct RcSocket {
+            sent: Vec<Rc<str>>,
+        }
+
+        impl NonBlockingSocket<Rc<str>> for RcSocket {
+            fn send_to(&mut self, _msg: &Message, addr: &Rc<str>) {
+                self.sent.push(Rc::clone(addr));
+            }
+
+            fn receive_all_messages(&mut self) -> Vec<(Rc<str>, Message)> {
+                vec![(Rc::from("peer"), test_message())]
+            }
+        }
+
+        let mut socket = ChaosSocket::new(RcSocket::default(), ChaosConfig::passthrough());
+        socket.send_to(&test_message(), &Rc::from("peer"));
+        assert_eq!(socket.receive_all_messages().len(), 1);
+        assert_eq!(socket.into_inner().sent, vec![Rc::from("peer")]);
+    }
+
     /// Tests for Display implementations
     mod display_tests {
         use super::*;
@@ -2367,6 +2845,7 @@ fn test_chaos_stats_display_populated() {
                 packets_reordered: 10,
                 burst_loss_events: 1,
                 packets_dropped_burst: 4,
+                active_phase: None,
             };
             let display = stats.to_string();
 
@@ -2388,6 +2867,7 @@ fn test_chaos_stats_display_format() {
                 packets_reordered: 4,
                 burst_loss_events: 5,
                 packets_dropped_burst: 6,
+                active_phase: Some(7),
             };
             let display = stats.to_string();
 
@@ -2402,6 +2882,7 @@ fn test_chaos_stats_display_format() {
             assert!(display.contains("reordered: 4"));
             assert!(display.contains("bursts: 5"));
             assert!(display.contains("dropped_burst: 6"));
+            assert!(display.contains("phase: 7"));
         }
     }
 }
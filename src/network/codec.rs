@@ -36,16 +36,33 @@
 //! assert!(len <= buffer.len());
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! # Versioned Records
+//!
+//! [`encode_versioned()`](crate::network::codec::encode_versioned) and
+//! [`decode_versioned()`](crate::network::codec::decode_versioned) store
+//! [`Message`](crate::Message)s outside a live session, for example to analyze
+//! captured traffic offline. A record is one
+//! [`CODEC_FORMAT_VERSION`](crate::network::codec::CODEC_FORMAT_VERSION) byte
+//! followed by the message's wire encoding. Records of the current format
+//! version keep decoding to the same messages across patch releases; the
+//! current protocol's golden byte fixtures pin this. A record of any other
+//! version is refused with
+//! [`CodecError::UnsupportedFormatVersion`](crate::network::codec::CodecError::UnsupportedFormatVersion)
+//! rather than a generic decode error.
 
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt;
 use std::io::{self, Write};
 
+use crate::metrics::DecodeFailure;
 use crate::network::messages::{
-    ChecksumReport, ConnectionStatus, DropAbort, DropAbortReason, DropBackfill, DropCommit,
-    DropOperationId, DropPrepare, DropReceipt, DropReport, DropReportStage, DropTarget, FloorReply,
-    FloorRequest, Goodbye, Input, InputAck, Message, MessageBody, MessageHeader, QualityReply,
-    QualityReport, SessionConfigBlock, SyncReply, SyncRequest,
+    ChecksumReport, ChecksumRequest, ConnectionStatus, DropAbort, DropAbortReason, DropBackfill,
+    DropCommit, DropOperationId, DropPrepare, DropReceipt, DropReport, DropReportStage, DropTarget,
+    FloorReply, FloorRequest, Goodbye, Input, InputAck, KeepAlive, LinkClosed, MatchPause,
+    MatchPauseAck, MatchPauseKind, MatchPauseStage, Message, MessageBody, MessageHeader,
+    QualityReply, QualityReport, ReliableAck, ReliableMessage, SessionConfigBlock, StreamEnd,
+    SyncReply, SyncRequest,
 };
 #[cfg(feature = "hot-join")]
 use crate::network::messages::{
@@ -107,10 +124,10 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 /// Classifies bytes that [`decode_message`] rejected.
 ///
 /// This is a diagnostic helper, not a validator: because [`WireRejectKind`] has
-/// no accepted variant, valid v2 bytes also fall through to
-/// [`WireRejectKind::Malformed`]. Released v1 bytes classify as
+/// no accepted variant, valid v12 bytes also fall through to
+/// [`WireRejectKind::Malformed`]. Released v1, v2, and v3 bytes classify as
 /// [`WireRejectKind::UnsupportedVersion`]. The legacy test is intentionally heuristic and
-/// may classify a malformed v2 packet as legacy; valid v2 connection IDs make
+/// may classify a malformed v12 packet as legacy; valid v12 connection IDs make
 /// the layouts unambiguous.
 #[must_use]
 pub fn classify_wire_bytes(bytes: &[u8]) -> WireRejectKind {
@@ -140,7 +157,7 @@ pub fn classify_wire_bytes(bytes: &[u8]) -> WireRejectKind {
     let Some(flags) = bytes.get(3).copied() else {
         return WireRejectKind::Malformed;
     };
-    if flags != 0 {
+    if flags & !MessageHeader::KNOWN_FLAGS != 0 {
         return WireRejectKind::UnknownFlags { seen: flags };
     }
 
@@ -290,6 +307,47 @@ pub enum CodecError {
         /// The actual buffer size provided.
         provided: usize,
     },
+    /// A received message ends before a field it needs (see
+    /// [`decode_message`]).
+    ///
+    /// Common on transports that cut datagrams, such as a receive buffer
+    /// smaller than the peer's packet.
+    Truncated {
+        /// The fewest bytes the message needs to hold the field that was cut
+        /// off, counted from its start.
+        expected_at_least: usize,
+        /// The bytes actually received.
+        got: usize,
+    },
+    /// A received message decoded completely but bytes follow it (see
+    /// [`decode_message`]).
+    TrailingBytes {
+        /// The number of unread bytes after the message.
+        extra: usize,
+    },
+    /// A received message holds a value its schema does not allow: a bad
+    /// sentinel, unknown flags or variant, an invalid connection ID, boolean,
+    /// or frame (see [`decode_message`]).
+    Malformed {
+        /// What was wrong, for logs.
+        ///
+        /// A `String` for the same reason as [`DecodeError::message`](Self::DecodeError::message).
+        detail: String,
+    },
+    /// A received message declares a protocol version this build does not
+    /// accept (see [`decode_message`]).
+    UnsupportedVersion {
+        /// The protocol version byte of the message.
+        found: u8,
+    },
+    /// A versioned record starts with a format version this build cannot
+    /// read (see [`decode_versioned`]).
+    UnsupportedFormatVersion {
+        /// The format version byte the record starts with.
+        version: u8,
+        /// The only format version this build reads, [`CODEC_FORMAT_VERSION`].
+        supported: u8,
+    },
 }
 
 impl CodecError {
@@ -308,6 +366,24 @@ pub fn decode(message: impl Into<String>, operation: CodecOperation) -> Self {
             operation,
         }
     }
+
+    /// The [`DecodeFailure`] category a socket counts this error under when a
+    /// received datagram fails to decode, or `None` for encoding errors.
+    ///
+    /// A generic [`DecodeError`](Self::DecodeError) counts as
+    /// [`DecodeFailure::Malformed`].
+    #[must_use]
+    pub fn decode_failure(&self) -> Option<DecodeFailure> {
+        match self {
+            Self::Truncated { .. } => Some(DecodeFailure::Truncated),
+            Self::TrailingBytes { .. } => Some(DecodeFailure::TrailingBytes),
+            Self::Malformed { .. } | Self::DecodeError { .. } => Some(DecodeFailure::Malformed),
+            Self::UnsupportedVersion { .. } | Self::UnsupportedFormatVersion { .. } => {
+                Some(DecodeFailure::UnsupportedVersion)
+            },
+            Self::EncodeError { .. } | Self::BufferTooSmall { .. } => None,
+        }
+    }
 }
 
 impl fmt::Display for CodecError {
@@ -329,6 +405,24 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     write!(f, "buffer too small: only {provided} bytes provided")
                 }
             },
+            Self::Truncated {
+                expected_at_least,
+                got,
+            } => write!(
+                f,
+                "truncated message: needed at least {expected_at_least} bytes, got {got}"
+            ),
+            Self::TrailingBytes { extra } => {
+                write!(f, "message has {extra} trailing byte(s)")
+            },
+            Self::Malformed { detail } => write!(f, "malformed message: {detail}"),
+            Self::UnsupportedVersion { found } => {
+                write!(f, "unsupported protocol version {found}")
+            },
+            Self::UnsupportedFormatVersion { version, supported } => write!(
+                f,
+                "unsupported codec format version {version} (this build reads version {supported})"
+            ),
         }
     }
 }
@@ -346,8 +440,10 @@ impl std::error::Error for CodecError {}
 /// a peer-controlled stream length from driving an unbounded allocation.
 pub const DEFAULT_MAX_FRAME_LEN: usize = crate::rle::DEFAULT_MAX_DECODED_LEN;
 
-fn decode_message_error(message: impl Into<String>) -> CodecError {
-    CodecError::decode(message, CodecOperation::DecodeMessage)
+fn decode_message_error(detail: impl Into<String>) -> CodecError {
+    CodecError::Malformed {
+        detail: detail.into(),
+    }
 }
 
 fn take_bytes<'a>(
@@ -359,9 +455,10 @@ fn take_bytes<'a>(
     let end = cursor
         .checked_add(len)
         .ok_or_else(|| decode_message_error(format!("{} offset overflow", field)))?;
-    let slice = bytes
-        .get(*cursor..end)
-        .ok_or_else(|| decode_message_error(format!("truncated {}", field)))?;
+    let slice = bytes.get(*cursor..end).ok_or(CodecError::Truncated {
+        expected_at_least: end,
+        got: bytes.len(),
+    })?;
     *cursor = end;
     Ok(slice)
 }
@@ -489,9 +586,10 @@ pub(crate) fn ensure_length_within_remaining(
         decode_message_error(format!("{field} length {len} overflows the byte bound"))
     })?;
     if min_bytes > remaining {
-        return Err(decode_message_error(format!(
-            "{field} length {len} exceeds the {remaining} remaining byte(s)"
-        )));
+        return Err(CodecError::Truncated {
+            expected_at_least: cursor.saturating_add(min_bytes),
+            got: bytes.len(),
+        });
     }
     Ok(())
 }
@@ -529,6 +627,8 @@ fn decode_sync_request(bytes: &[u8], cursor: &mut usize) -> CodecResult<SyncRequ
             ],
         )?,
         config_digest: read_u64(bytes, cursor, "sync_request.config_digest")?,
+        handles_digest: read_u64(bytes, cursor, "sync_request.handles_digest")?,
+        access_proof: read_u64(bytes, cursor, "sync_request.access_proof")?,
     })
 }
 
@@ -549,31 +649,46 @@ fn decode_sync_reply(bytes: &[u8], cursor: &mut usize) -> CodecResult<SyncReply>
             ],
         )?,
         config_digest: read_u64(bytes, cursor, "sync_reply.config_digest")?,
+        handles_digest: read_u64(bytes, cursor, "sync_reply.handles_digest")?,
     })
 }
 
-fn decode_input(bytes: &[u8], cursor: &mut usize) -> CodecResult<Input> {
-    let status_len = read_usize(bytes, cursor, "input.peer_connect_status.len")?;
+/// Decodes a length-prefixed `Vec<ConnectionStatus>`; `fields` names the
+/// length prefix and the list in decode errors.
+fn decode_connection_statuses(
+    bytes: &[u8],
+    cursor: &mut usize,
+    fields: [&'static str; 2],
+) -> CodecResult<Vec<ConnectionStatus>> {
+    let [len_field, field] = fields;
+    let status_len = read_usize(bytes, cursor, len_field)?;
     ensure_length_within_remaining(
         bytes,
         *cursor,
         status_len,
         CONNECTION_STATUS_WIRE_LEN,
-        "input.peer_connect_status",
+        field,
     )?;
 
-    let mut peer_connect_status = Vec::new();
-    peer_connect_status
-        .try_reserve_exact(status_len)
-        .map_err(|_err| {
-            decode_message_error(format!(
-                "failed to reserve {} connection status entries",
-                status_len
-            ))
-        })?;
+    let mut statuses = Vec::new();
+    statuses.try_reserve_exact(status_len).map_err(|_err| {
+        decode_message_error(format!(
+            "failed to reserve {} connection status entries",
+            status_len
+        ))
+    })?;
     for _ in 0..status_len {
-        peer_connect_status.push(decode_connection_status(bytes, cursor)?);
+        statuses.push(decode_connection_status(bytes, cursor)?);
     }
+    Ok(statuses)
+}
+
+pub(crate) fn decode_input(bytes: &[u8], cursor: &mut usize) -> CodecResult<Input> {
+    let peer_connect_status = decode_connection_statuses(
+        bytes,
+        cursor,
+        ["input.peer_connect_status.len", "input.peer_connect_status"],
+    )?;
 
     let start_frame = Frame::new(read_i32(bytes, cursor, "input.start_frame")?);
     let ack_frame = Frame::new(read_i32(bytes, cursor, "input.ack_frame")?);
@@ -787,6 +902,21 @@ fn decode_drop_backfill(bytes: &[u8], cursor: &mut usize) -> CodecResult<DropBac
     })
 }
 
+fn decode_reliable_message(bytes: &[u8], cursor: &mut usize) -> CodecResult<ReliableMessage> {
+    let sequence = read_u32(bytes, cursor, "reliable_message.sequence")?;
+    let payload_len = read_usize(bytes, cursor, "reliable_message.payload.len")?;
+    let source = take_bytes(bytes, cursor, payload_len, "reliable_message.payload")?;
+    // alloc-bound: `payload_len` has already been proven to fit in the unread packet.
+    let mut payload = Vec::new();
+    payload.try_reserve_exact(payload_len).map_err(|_err| {
+        decode_message_error(format!(
+            "failed to reserve {payload_len} reliable payload bytes"
+        ))
+    })?;
+    payload.extend_from_slice(source);
+    Ok(ReliableMessage { sequence, payload })
+}
+
 fn decode_drop_abort_reason(bytes: &[u8], cursor: &mut usize) -> CodecResult<DropAbortReason> {
     match read_u32(bytes, cursor, "drop_abort.reason")? {
         0 => Ok(DropAbortReason::Superseded),
@@ -802,6 +932,54 @@ fn decode_drop_abort_reason(bytes: &[u8], cursor: &mut usize) -> CodecResult<Dro
     }
 }
 
+fn decode_match_pause_stage(
+    bytes: &[u8],
+    cursor: &mut usize,
+    field: &'static str,
+) -> CodecResult<MatchPauseStage> {
+    match read_u32(bytes, cursor, field)? {
+        0 => Ok(MatchPauseStage::Propose),
+        1 => Ok(MatchPauseStage::Commit),
+        2 => Ok(MatchPauseStage::Cancel),
+        other => Err(decode_message_error(format!(
+            "invalid match pause stage {other}"
+        ))),
+    }
+}
+
+fn decode_match_pause(bytes: &[u8], cursor: &mut usize) -> CodecResult<MatchPause> {
+    let proposer = read_u16(bytes, cursor, "match_pause.proposer")?;
+    let sequence = read_u32(bytes, cursor, "match_pause.sequence")?;
+    let kind = match read_u32(bytes, cursor, "match_pause.kind")? {
+        0 => MatchPauseKind::Pause,
+        1 => MatchPauseKind::Resume,
+        other => {
+            return Err(decode_message_error(format!(
+                "invalid match pause kind {other}"
+            )))
+        },
+    };
+    let stage = decode_match_pause_stage(bytes, cursor, "match_pause.stage")?;
+    let frame = read_frame(bytes, cursor, "match_pause.frame", false)?;
+    Ok(MatchPause {
+        proposer,
+        sequence,
+        kind,
+        stage,
+        frame,
+    })
+}
+
+fn decode_match_pause_ack(bytes: &[u8], cursor: &mut usize) -> CodecResult<MatchPauseAck> {
+    Ok(MatchPauseAck {
+        proposer: read_u16(bytes, cursor, "match_pause_ack.proposer")?,
+        sequence: read_u32(bytes, cursor, "match_pause_ack.sequence")?,
+        stage: decode_match_pause_stage(bytes, cursor, "match_pause_ack.stage")?,
+        accepted: read_bool(bytes, cursor, "match_pause_ack.accepted")?,
+        frame: read_frame(bytes, cursor, "match_pause_ack.frame", true)?,
+    })
+}
+
 /// Reads a bincode `Option<u128>` encoded under fixed-int config: a one-byte
 /// tag (0 = `None`, 1 = `Some`) followed by a 16-byte little-endian `u128` when
 /// the tag is 1.
@@ -1001,6 +1179,90 @@ fn encode_framed_with_max_len(message: &Message, max_frame_len: usize) -> CodecR
     Ok(framed)
 }
 
+/// Format version written by [`encode_versioned`] and read by
+/// [`decode_versioned`].
+///
+/// It follows [`PROTOCOL_VERSION`](crate::PROTOCOL_VERSION), because the
+/// record body is the message's wire encoding. It only changes when the
+/// protocol version does, which never happens in a patch release.
+pub const CODEC_FORMAT_VERSION: u8 = crate::PROTOCOL_VERSION;
+
+/// Encodes one network [`Message`] as a self-describing record for storage.
+///
+/// The record is one [`CODEC_FORMAT_VERSION`] byte followed by exactly the
+/// bytes produced by [`encode`] for `message`. Records carry no length
+/// prefix; wrap them in one of your own to store several in a stream. Nothing
+/// sent to peers uses this format.
+///
+/// # Errors
+///
+/// Returns [`CodecError::EncodeError`] if the message cannot be encoded or
+/// the output allocation cannot be reserved.
+///
+/// # Examples
+///
+/// ```
+/// use fortress_rollback::network::codec::{decode_versioned, encode_versioned, CODEC_FORMAT_VERSION};
+/// use fortress_rollback::Message;
+///
+/// fn roundtrip(message: &Message) -> Result<Message, fortress_rollback::network::codec::CodecError> {
+///     let record = encode_versioned(message)?;
+///     assert_eq!(record.first(), Some(&CODEC_FORMAT_VERSION));
+///     let (_version, decoded) = decode_versioned(&record)?;
+///     Ok(decoded)
+/// }
+/// # let _ = roundtrip;
+/// ```
+pub fn encode_versioned(message: &Message) -> CodecResult<Vec<u8>> {
+    let payload_len = encoded_len(message)?;
+    let record_len = payload_len.checked_add(1).ok_or_else(|| {
+        CodecError::encode(
+            "versioned record length overflow",
+            CodecOperation::EncodeMessage,
+        )
+    })?;
+    let mut record = Vec::new();
+    record.try_reserve_exact(record_len).map_err(|_err| {
+        CodecError::encode(
+            format!("failed to reserve {record_len} versioned record bytes"),
+            CodecOperation::EncodeMessage,
+        )
+    })?;
+    record.push(CODEC_FORMAT_VERSION);
+    encode_append(message, &mut record)?;
+    Ok(record)
+}
+
+/// Decodes one record written by [`encode_versioned`], returning its format
+/// version and message.
+///
+/// The message is decoded with [`decode_message`], so records are as safe to
+/// read from untrusted files as datagrams are from peers. Records of the
+/// current format version decode to the same messages across patch releases.
+///
+/// # Errors
+///
+/// Returns [`CodecError::UnsupportedFormatVersion`] if the record starts with
+/// any version other than [`CODEC_FORMAT_VERSION`], including versions newer
+/// than this build. Returns [`CodecError::Truncated`] if the record is empty,
+/// and any error of [`decode_message`] for the message.
+pub fn decode_versioned(bytes: &[u8]) -> CodecResult<(u8, Message)> {
+    let Some((&version, payload)) = bytes.split_first() else {
+        return Err(CodecError::Truncated {
+            expected_at_least: 1,
+            got: 0,
+        });
+    };
+    if version != CODEC_FORMAT_VERSION {
+        return Err(CodecError::UnsupportedFormatVersion {
+            version,
+            supported: CODEC_FORMAT_VERSION,
+        });
+    }
+    let (message, _consumed) = decode_message(payload)?;
+    Ok((version, message))
+}
+
 /// Incremental decoder for length-prefixed network messages on byte streams.
 ///
 /// Each frame starts with a four-byte little-endian `u32` payload length that
@@ -1368,10 +1630,14 @@ pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> CodecResult<(T, usize)> {
 ///
 /// # Errors
 ///
-/// Returns [`CodecError::DecodeError`] when the message is truncated, contains an
-/// invalid variant or boolean, contains an invalid connection-status,
-/// floor-gossip, or checksum-report frame value, has trailing bytes, or declares
-/// a length that cannot fit in the remaining packet.
+/// - [`CodecError::Truncated`] when the message ends early or declares a
+///   length that cannot fit in the remaining packet.
+/// - [`CodecError::TrailingBytes`] when bytes follow a complete message.
+/// - [`CodecError::UnsupportedVersion`] when the header declares a protocol
+///   version this build does not accept.
+/// - [`CodecError::Malformed`] for any other invalid value: sentinel, flags,
+///   connection ID, variant, boolean, or a connection-status, floor-gossip, or
+///   checksum-report frame.
 pub fn decode_message(bytes: &[u8]) -> CodecResult<(Message, usize)> {
     let mut cursor = 0;
     let sentinel = read_array(bytes, &mut cursor, "message.header.sentinel")?;
@@ -1383,12 +1649,12 @@ pub fn decode_message(bytes: &[u8]) -> CodecResult<(Message, usize)> {
     if protocol_version < super::MIN_SUPPORTED_PROTOCOL_VERSION
         || protocol_version > crate::PROTOCOL_VERSION
     {
-        return Err(decode_message_error(format!(
-            "unsupported protocol version {protocol_version}"
-        )));
+        return Err(CodecError::UnsupportedVersion {
+            found: protocol_version,
+        });
     }
     let flags = read_array::<1>(bytes, &mut cursor, "message.header.flags")?[0];
-    if flags != 0 {
+    if flags & !MessageHeader::KNOWN_FLAGS != 0 {
         return Err(decode_message_error(format!(
             "unknown protocol flags 0x{flags:02x}"
         )));
@@ -1399,11 +1665,17 @@ pub fn decode_message(bytes: &[u8]) -> CodecResult<(Message, usize)> {
             "invalid connection ID 0x{conn_id:08x}"
         )));
     }
+    let sequence = if flags & MessageHeader::FLAG_SEQUENCE != 0 {
+        read_u32(bytes, &mut cursor, "message.header.sequence")?
+    } else {
+        0
+    };
     let header = MessageHeader {
         sentinel,
         protocol_version,
         flags,
         conn_id,
+        sequence,
     };
     let variant = read_u32(bytes, &mut cursor, "message.body.variant")?;
     let body = match variant {
@@ -1416,6 +1688,7 @@ pub fn decode_message(bytes: &[u8]) -> CodecResult<(Message, usize)> {
         4 => MessageBody::QualityReport(QualityReport {
             frame_advantage: read_i16(bytes, &mut cursor, "quality_report.frame_advantage")?,
             ping: read_u128(bytes, &mut cursor, "quality_report.ping")?,
+            stalled: read_bool(bytes, &mut cursor, "quality_report.stalled")?,
         }),
         5 => MessageBody::QualityReply(QualityReply {
             pong: read_u128(bytes, &mut cursor, "quality_reply.pong")?,
@@ -1424,7 +1697,17 @@ pub fn decode_message(bytes: &[u8]) -> CodecResult<(Message, usize)> {
             checksum: read_u128(bytes, &mut cursor, "checksum_report.checksum")?,
             frame: read_frame(bytes, &mut cursor, "checksum_report.frame", false)?,
         }),
-        7 => MessageBody::KeepAlive,
+        7 => MessageBody::KeepAlive(KeepAlive {
+            ack_frame: Frame::new(read_i32(bytes, &mut cursor, "keep_alive.ack_frame")?),
+            peer_connect_status: decode_connection_statuses(
+                bytes,
+                &mut cursor,
+                [
+                    "keep_alive.peer_connect_status.len",
+                    "keep_alive.peer_connect_status",
+                ],
+            )?,
+        }),
         // Floor-round variants (double-failure-relay connected-relay reorder fix,
         // S55), appended after the original core block — see the `MessageBody`
         // enum comment. Hot-join variants occupy discriminants 10..=16 in every
@@ -1484,6 +1767,26 @@ pub fn decode_message(bytes: &[u8]) -> CodecResult<(Message, usize)> {
             operation: decode_drop_operation_id(bytes, &mut cursor, "drop_abort")?,
             reason: decode_drop_abort_reason(bytes, &mut cursor)?,
         }),
+        23 => MessageBody::MatchPause(decode_match_pause(bytes, &mut cursor)?),
+        24 => MessageBody::MatchPauseAck(decode_match_pause_ack(bytes, &mut cursor)?),
+        25 => MessageBody::StreamEnd(StreamEnd {
+            final_frame: read_frame(bytes, &mut cursor, "stream_end.final_frame", true)?,
+        }),
+        26 => MessageBody::ReliableMessage(decode_reliable_message(bytes, &mut cursor)?),
+        27 => MessageBody::ReliableAck(ReliableAck {
+            next_sequence: read_u32(bytes, &mut cursor, "reliable_ack.next_sequence")?,
+        }),
+        28 => MessageBody::LinkClosed(LinkClosed {
+            last_received_frame: read_frame(
+                bytes,
+                &mut cursor,
+                "link_closed.last_received_frame",
+                true,
+            )?,
+        }),
+        29 => MessageBody::ChecksumRequest(ChecksumRequest {
+            frame: read_frame(bytes, &mut cursor, "checksum_request.frame", false)?,
+        }),
         other => {
             return Err(decode_message_error(format!(
                 "unknown message body variant {}",
@@ -1493,10 +1796,9 @@ pub fn decode_message(bytes: &[u8]) -> CodecResult<(Message, usize)> {
     };
 
     if cursor != bytes.len() {
-        return Err(decode_message_error(format!(
-            "message has {} trailing byte(s)",
-            bytes.len() - cursor
-        )));
+        return Err(CodecError::TrailingBytes {
+            extra: bytes.len() - cursor,
+        });
     }
 
     Ok((Message { header, body }, cursor))
@@ -1717,16 +2019,47 @@ fn assert_wire_golden_suite(
 }
 
 #[cfg(test)]
-#[path = "wire_golden_v2.rs"]
-mod wire_golden_v2;
+#[path = "wire_golden_v12.rs"]
+mod wire_golden_v12;
 
-// Compile the released v1 literals as a rejection suite without presenting it
-// as the active golden registration. The immutable legacy-0.9 fixture module
-// imports this historical name for its opposite-direction framing checks.
+// Compile the released v1 through v11 literals as rejection suites without
+// presenting them as the active golden registration. The immutable legacy-0.9
+// fixture module imports the historical v1 name for its opposite-direction
+// framing checks.
 #[cfg(test)]
 #[path = "wire_golden_v1.rs"]
 mod released_wire_golden_v1;
 #[cfg(test)]
+#[path = "wire_golden_v10.rs"]
+mod released_wire_golden_v10;
+#[cfg(test)]
+#[path = "wire_golden_v11.rs"]
+mod released_wire_golden_v11;
+#[cfg(test)]
+#[path = "wire_golden_v2.rs"]
+mod released_wire_golden_v2;
+#[cfg(test)]
+#[path = "wire_golden_v3.rs"]
+mod released_wire_golden_v3;
+#[cfg(test)]
+#[path = "wire_golden_v4.rs"]
+mod released_wire_golden_v4;
+#[cfg(test)]
+#[path = "wire_golden_v5.rs"]
+mod released_wire_golden_v5;
+#[cfg(test)]
+#[path = "wire_golden_v6.rs"]
+mod released_wire_golden_v6;
+#[cfg(test)]
+#[path = "wire_golden_v7.rs"]
+mod released_wire_golden_v7;
+#[cfg(test)]
+#[path = "wire_golden_v8.rs"]
+mod released_wire_golden_v8;
+#[cfg(test)]
+#[path = "wire_golden_v9.rs"]
+mod released_wire_golden_v9;
+#[cfg(test)]
 use self::released_wire_golden_v1 as wire_golden_v1;
 
 #[cfg(test)]
@@ -1743,9 +2076,9 @@ fn assert_wire_golden_suite(
 mod tests {
     use super::*;
     use crate::network::messages::{
-        ChecksumReport, ConnectionStatus, FloorReply, FloorRequest, Input, InputAck, Message,
-        MessageBody, MessageHeader, QualityReply, QualityReport, SessionConfigBlock, SyncReply,
-        SyncRequest,
+        ChecksumReport, ConnectionStatus, FloorReply, FloorRequest, Input, InputAck, KeepAlive,
+        Message, MessageBody, MessageHeader, QualityReply, QualityReport, SessionConfigBlock,
+        SyncReply, SyncRequest,
     };
 
     fn wire_prefix(conn_id: u32, variant: u32) -> Vec<u8> {
@@ -1757,16 +2090,16 @@ fn wire_prefix(conn_id: u32, variant: u32) -> Vec<u8> {
     fn keep_alive(conn_id: u32) -> Message {
         Message {
             header: MessageHeader::new(conn_id),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         }
     }
 
     #[test]
-    fn shared_wire_golden_harness_accepts_current_v2_suite() {
+    fn shared_wire_golden_harness_accepts_current_v12_suite() {
         assert_wire_golden_suite(
-            super::wire_golden_v2::WIRE_GOLDEN_VERSION,
-            super::wire_golden_v2::fixtures(),
-            super::wire_golden_v2::expected,
+            super::wire_golden_v12::WIRE_GOLDEN_VERSION,
+            super::wire_golden_v12::fixtures(),
+            super::wire_golden_v12::expected,
         );
     }
 
@@ -1959,7 +2292,7 @@ fn frame_decoder_finish_distinguishes_partial_prefix_and_payload() {
         );
         let error = decoder.finish().unwrap_err();
         assert!(error.to_string().contains("incomplete frame payload"));
-        assert!(error.to_string().contains("11 of 12"));
+        assert!(error.to_string().contains("23 of 24"));
     }
 
     #[test]
@@ -1999,7 +2332,7 @@ fn test_encode_decode_roundtrip_message() {
     fn codec_wire_format_uses_fixed_little_endian_bytes() {
         assert_eq!(
             crate::PROTOCOL_VERSION,
-            2,
+            12,
             "wire bytes changed without a version bump"
         );
         let cases = [
@@ -2019,10 +2352,12 @@ fn codec_wire_format_uses_fixed_little_endian_bytes() {
                             desync_interval: 60,
                         },
                         config_digest: 0x5082_C060_858A_E1C8,
+                        handles_digest: 0x1020_3040_5060_7080,
+                        access_proof: 0x0807_0605_0403_0201,
                     }),
                 },
                 vec![
-                    0xF5, 0x52, 0x02, 0x00, // sentinel, version, flags
+                    0xF5, 0x52, 0x0C, 0x00, // sentinel, version, flags
                     0xCD, 0xAB, 0x00, 0x00, // conn_id
                     0x00, 0x00, 0x00, 0x00, // MessageBody::SyncRequest tag
                     0xE7, 0x03, 0x00, 0x00, // random_request
@@ -2034,6 +2369,8 @@ fn codec_wire_format_uses_fixed_little_endian_bytes() {
                     0x08, 0x00, // config.max_prediction
                     0x3C, 0x00, 0x00, 0x00, // config.desync_interval
                     0xC8, 0xE1, 0x8A, 0x85, 0x60, 0xC0, 0x82, 0x50, // config_digest
+                    0x80, 0x70, 0x60, 0x50, 0x40, 0x30, 0x20, 0x10, // handles_digest
+                    0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // access_proof
                 ],
             ),
             (
@@ -2043,15 +2380,17 @@ fn codec_wire_format_uses_fixed_little_endian_bytes() {
                     body: MessageBody::QualityReport(QualityReport {
                         frame_advantage: -2,
                         ping: 0x0102_0304_0506_0708_090A_0B0C_0D0E_0F10,
+                        stalled: true,
                     }),
                 },
                 vec![
-                    0xF5, 0x52, 0x02, 0x00, // sentinel, version, flags
+                    0xF5, 0x52, 0x0C, 0x00, // sentinel, version, flags
                     0x34, 0x12, 0x00, 0x00, // MessageHeader::conn_id
                     0x04, 0x00, 0x00, 0x00, // MessageBody::QualityReport tag
                     0xFE, 0xFF, // frame_advantage: i16 -2
                     0x10, 0x0F, 0x0E, 0x0D, 0x0C, 0x0B, 0x0A, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04,
                     0x03, 0x02, 0x01, // ping: u128
+                    0x01, // stalled
                 ],
             ),
             (
@@ -2061,12 +2400,36 @@ fn codec_wire_format_uses_fixed_little_endian_bytes() {
                     body: MessageBody::Goodbye(Goodbye { reason: 7 }),
                 },
                 vec![
-                    0xF5, 0x52, 0x02, 0x00, // sentinel, version, flags
+                    0xF5, 0x52, 0x0C, 0x00, // sentinel, version, flags
                     0x34, 0x12, 0x00, 0x00, // MessageHeader::conn_id
                     0x11, 0x00, 0x00, 0x00, // MessageBody::Goodbye tag 17
                     0x07, // reason
                 ],
             ),
+            (
+                "keep_alive",
+                Message {
+                    header: MessageHeader::new(0x1234),
+                    body: MessageBody::KeepAlive(KeepAlive {
+                        ack_frame: Frame::new(0x4D),
+                        peer_connect_status: vec![ConnectionStatus {
+                            disconnected: true,
+                            last_frame: Frame::new(0x4A),
+                            epoch: 3,
+                        }],
+                    }),
+                },
+                vec![
+                    0xF5, 0x52, 0x0C, 0x00, // sentinel, version, flags
+                    0x34, 0x12, 0x00, 0x00, // MessageHeader::conn_id
+                    0x07, 0x00, 0x00, 0x00, // MessageBody::KeepAlive tag 7
+                    0x4D, 0x00, 0x00, 0x00, // ack_frame
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // peer_connect_status len
+                    0x01, // peer_connect_status[0].disconnected
+                    0x4A, 0x00, 0x00, 0x00, // peer_connect_status[0].last_frame
+                    0x03, 0x00, // peer_connect_status[0].epoch
+                ],
+            ),
         ];
 
         for (name, original, expected) in cases {
@@ -2081,6 +2444,40 @@ fn codec_wire_format_uses_fixed_little_endian_bytes() {
         }
     }
 
+    #[test]
+    fn sequenced_header_encodes_sequence_after_conn_id() {
+        let message = Message {
+            header: MessageHeader::new(0x1234).with_sequence(0xA1B2_C3D4),
+            body: MessageBody::KeepAlive(KeepAlive {
+                ack_frame: Frame::new(0x4D),
+                peer_connect_status: Vec::new(),
+            }),
+        };
+        let expected = [
+            0xF5, 0x52, 0x0C, 0x01, // sentinel, version, flags
+            0x34, 0x12, 0x00, 0x00, // MessageHeader::conn_id
+            0xD4, 0xC3, 0xB2, 0xA1, // MessageHeader::sequence
+            0x07, 0x00, 0x00, 0x00, // MessageBody::KeepAlive tag 7
+            0x4D, 0x00, 0x00, 0x00, // ack_frame
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // peer_connect_status len
+        ];
+
+        let bytes = encode(&message).unwrap();
+        assert_eq!(bytes, expected);
+        assert_eq!(message.encoded_len(), bytes.len());
+        let generic: Message = decode_value(&bytes).unwrap();
+        assert_eq!(generic, message);
+        assert_eq!(decode_message(&bytes), Ok((message, bytes.len())));
+        assert_eq!(generic.header.sequence(), Some(0xA1B2_C3D4));
+        for len in 0..bytes.len() {
+            assert!(
+                decode_message(&bytes[..len]).is_err(),
+                "truncated sequenced prefix of {len} bytes must be rejected"
+            );
+        }
+        assert_eq!(MessageHeader::new(0x1234).sequence(), None);
+    }
+
     #[test]
     fn decode_message_rejects_every_truncated_handshake_field() {
         let message = Message {
@@ -2097,10 +2494,12 @@ fn decode_message_rejects_every_truncated_handshake_field() {
                     desync_interval: 60,
                 },
                 config_digest: 0x5082_C060_858A_E1C8,
+                handles_digest: 0x1020_3040_5060_7080,
+                access_proof: 0x0807_0605_0403_0201,
             }),
         };
         let bytes = encode(&message).unwrap();
-        assert_eq!(bytes.len(), 43);
+        assert_eq!(bytes.len(), 59);
 
         for len in 0..bytes.len() {
             assert!(
@@ -2155,8 +2554,8 @@ fn classify_wire_bytes_uses_stable_reject_precedence() {
     }
 
     #[test]
-    fn decode_message_rejects_every_invalid_v2_header_before_body_decode() {
-        let valid = wire_prefix(1, 7);
+    fn decode_message_rejects_every_invalid_v12_header_before_body_decode() {
+        let valid = encode(&keep_alive(1)).unwrap();
         for len in 0..valid.len() {
             assert!(
                 decode_message(&valid[..len]).is_err(),
@@ -2171,11 +2570,13 @@ fn decode_message_rejects_every_invalid_v2_header_before_body_decode() {
         let mut unsupported = valid.clone();
         unsupported[2] = crate::PROTOCOL_VERSION.saturating_add(1);
         invalid_headers.push(unsupported);
-        let mut released_v1 = valid.clone();
-        released_v1[2] = 1;
-        invalid_headers.push(released_v1);
+        for released in [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11] {
+            let mut released_header = valid.clone();
+            released_header[2] = released;
+            invalid_headers.push(released_header);
+        }
         let mut flags = valid;
-        flags[3] = 1;
+        flags[3] = 0x02;
         invalid_headers.push(flags);
         invalid_headers.push(wire_prefix(0, 7));
         invalid_headers.push(wire_prefix(0x1234_0000, 7));
@@ -2188,16 +2589,12 @@ fn decode_message_rejects_every_invalid_v2_header_before_body_decode() {
         }
 
         for conn_id in [1, u32::MAX] {
-            let bytes = wire_prefix(conn_id, 7);
+            let mut bytes = wire_prefix(conn_id, 7);
+            bytes.extend_from_slice(&Frame::NULL.as_i32().to_le_bytes());
+            bytes.extend_from_slice(&0u64.to_le_bytes());
             assert_eq!(
                 decode_message(&bytes),
-                Ok((
-                    Message {
-                        header: MessageHeader::new(conn_id),
-                        body: MessageBody::KeepAlive,
-                    },
-                    bytes.len(),
-                ))
+                Ok((keep_alive(conn_id), bytes.len()))
             );
         }
     }
@@ -2268,6 +2665,7 @@ fn decode_message_roundtrips_every_body_variant() {
                 body: MessageBody::QualityReport(QualityReport {
                     frame_advantage: -2,
                     ping: 1_000,
+                    stalled: true,
                 }),
             },
             Message {
@@ -2283,7 +2681,7 @@ fn decode_message_roundtrips_every_body_variant() {
             },
             Message {
                 header: MessageHeader::new(0xABCD),
-                body: MessageBody::KeepAlive,
+                body: MessageBody::KeepAlive(KeepAlive::default()),
             },
             Message {
                 header: MessageHeader::new(0xABCD),
@@ -2300,6 +2698,55 @@ fn decode_message_roundtrips_every_body_variant() {
                 header: MessageHeader::new(0xABCD),
                 body: MessageBody::Goodbye(Goodbye { reason: 3 }),
             },
+            Message {
+                header: MessageHeader::new(0xABCD),
+                body: MessageBody::MatchPause(MatchPause {
+                    proposer: 1,
+                    sequence: 7,
+                    kind: MatchPauseKind::Pause,
+                    stage: MatchPauseStage::Cancel,
+                    frame: Frame::new(120),
+                }),
+            },
+            Message {
+                header: MessageHeader::new(0xABCD),
+                body: MessageBody::MatchPauseAck(MatchPauseAck {
+                    proposer: 1,
+                    sequence: 7,
+                    stage: MatchPauseStage::Propose,
+                    accepted: false,
+                    frame: Frame::NULL,
+                }),
+            },
+            Message {
+                header: MessageHeader::new(0xABCD),
+                body: MessageBody::StreamEnd(StreamEnd {
+                    final_frame: Frame::new(599),
+                }),
+            },
+            Message {
+                header: MessageHeader::new(0xABCD),
+                body: MessageBody::ReliableMessage(ReliableMessage {
+                    sequence: u32::MAX,
+                    payload: b"ready".to_vec(),
+                }),
+            },
+            Message {
+                header: MessageHeader::new(0xABCD),
+                body: MessageBody::ReliableAck(ReliableAck { next_sequence: 0 }),
+            },
+            Message {
+                header: MessageHeader::new(0xABCD),
+                body: MessageBody::LinkClosed(LinkClosed {
+                    last_received_frame: Frame::NULL,
+                }),
+            },
+            Message {
+                header: MessageHeader::new(0xABCD),
+                body: MessageBody::ChecksumRequest(ChecksumRequest {
+                    frame: Frame::new(0),
+                }),
+            },
         ];
 
         for original in messages {
@@ -2313,6 +2760,14 @@ fn decode_message_roundtrips_every_body_variant() {
         }
     }
 
+    fn match_pause_stage(stage: u8) -> MatchPauseStage {
+        match stage {
+            0 => MatchPauseStage::Propose,
+            1 => MatchPauseStage::Commit,
+            _ => MatchPauseStage::Cancel,
+        }
+    }
+
     fn drop_operation() -> DropOperationId {
         DropOperationId {
             coordinator: 2,
@@ -2394,7 +2849,7 @@ fn drop_bodies() -> Vec<(u32, MessageBody)> {
     }
 
     #[test]
-    fn coordinated_drop_v2_goldens_roundtrip_with_manual_generic_parity() {
+    fn coordinated_drop_v12_goldens_roundtrip_with_manual_generic_parity() {
         for (tag, body) in drop_bodies() {
             let original = Message {
                 header: MessageHeader::new(0x1234),
@@ -2403,14 +2858,14 @@ fn coordinated_drop_v2_goldens_roundtrip_with_manual_generic_parity() {
             let bytes = encode(&original).unwrap();
             let expected: &[u8] = match tag {
                 18 => &[
-                    0xF5, 0x52, 0x02, 0x00, 0x34, 0x12, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x02,
+                    0xF5, 0x52, 0x0C, 0x00, 0x34, 0x12, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x02,
                     0x00, 0x07, 0x00, 0x40, 0x30, 0x20, 0x10, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
                     0x02, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x09,
                     0x00, 0x05, 0x00, 0x09, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
                     0x00, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00,
                 ],
                 19 => &[
-                    0xF5, 0x52, 0x02, 0x00, 0x34, 0x12, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00, 0x02,
+                    0xF5, 0x52, 0x0C, 0x00, 0x34, 0x12, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00, 0x02,
                     0x00, 0x07, 0x00, 0x40, 0x30, 0x20, 0x10, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
                     0x02, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x00, 0xFF,
                     0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00,
@@ -2418,19 +2873,19 @@ fn coordinated_drop_v2_goldens_roundtrip_with_manual_generic_parity() {
                     0x00, 0x00, 0x00, 0x05, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00,
                 ],
                 20 => &[
-                    0xF5, 0x52, 0x02, 0x00, 0x34, 0x12, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x02,
+                    0xF5, 0x52, 0x0C, 0x00, 0x34, 0x12, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x02,
                     0x00, 0x07, 0x00, 0x40, 0x30, 0x20, 0x10, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
                     0x02, 0x01, 0x01, 0x00, 0x03, 0x00, 0x18, 0x00, 0x00, 0x00, 0x02, 0x00, 0x04,
                     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xAA, 0xBB, 0xCC, 0xDD,
                 ],
                 21 => &[
-                    0xF5, 0x52, 0x02, 0x00, 0x34, 0x12, 0x00, 0x00, 0x15, 0x00, 0x00, 0x00, 0x02,
+                    0xF5, 0x52, 0x0C, 0x00, 0x34, 0x12, 0x00, 0x00, 0x15, 0x00, 0x00, 0x00, 0x02,
                     0x00, 0x07, 0x00, 0x40, 0x30, 0x20, 0x10, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
                     0x02, 0x01, 0x1F, 0x00, 0x00, 0x00, 0x18, 0x17, 0x16, 0x15, 0x14, 0x13, 0x12,
                     0x11,
                 ],
                 22 => &[
-                    0xF5, 0x52, 0x02, 0x00, 0x34, 0x12, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x02,
+                    0xF5, 0x52, 0x0C, 0x00, 0x34, 0x12, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x02,
                     0x00, 0x07, 0x00, 0x40, 0x30, 0x20, 0x10, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
                     0x02, 0x01, 0x02, 0x00, 0x00, 0x00,
                 ],
@@ -2438,7 +2893,7 @@ fn coordinated_drop_v2_goldens_roundtrip_with_manual_generic_parity() {
             };
             assert_eq!(
                 bytes, expected,
-                "immutable protocol-v2 golden for tag {tag}"
+                "immutable protocol-v12 golden for tag {tag}"
             );
             assert_eq!(bytes.get(8..12), Some(tag.to_le_bytes().as_slice()));
             assert_eq!(original.encoded_len(), bytes.len());
@@ -2543,8 +2998,129 @@ fn coordinated_drop_decoder_rejects_unrepresentable_vector_lengths_before_alloca
         );
     }
 
+    #[test]
+    fn match_pause_decoder_rejects_invalid_discriminants_and_frames() {
+        let pause = Message {
+            header: MessageHeader::new(1),
+            body: MessageBody::MatchPause(MatchPause {
+                proposer: 0,
+                sequence: 1,
+                kind: MatchPauseKind::Pause,
+                stage: MatchPauseStage::Propose,
+                frame: Frame::new(30),
+            }),
+        };
+        let bytes = encode(&pause).unwrap();
+        let mut bad_kind = bytes.clone();
+        bad_kind[18..22].copy_from_slice(&2_u32.to_le_bytes());
+        assert!(decode_message(&bad_kind)
+            .unwrap_err()
+            .to_string()
+            .contains("invalid match pause kind 2"));
+        let mut bad_stage = bytes.clone();
+        bad_stage[22..26].copy_from_slice(&3_u32.to_le_bytes());
+        assert!(decode_message(&bad_stage)
+            .unwrap_err()
+            .to_string()
+            .contains("invalid match pause stage 3"));
+        let mut null_frame = bytes;
+        null_frame[26..30].copy_from_slice(&(-1_i32).to_le_bytes());
+        assert!(decode_message(&null_frame).is_err());
+
+        let ack = Message {
+            header: MessageHeader::new(1),
+            body: MessageBody::MatchPauseAck(MatchPauseAck {
+                proposer: 0,
+                sequence: 1,
+                stage: MatchPauseStage::Commit,
+                accepted: true,
+                frame: Frame::new(30),
+            }),
+        };
+        let mut bad_accepted = encode(&ack).unwrap();
+        bad_accepted[22] = 2;
+        assert!(decode_message(&bad_accepted)
+            .unwrap_err()
+            .to_string()
+            .contains("match_pause_ack.accepted"));
+    }
+
+    #[test]
+    fn stream_end_decoder_accepts_null_and_rejects_other_negative_frames() {
+        let mut bytes = encode(&Message {
+            header: MessageHeader::new(1),
+            body: MessageBody::StreamEnd(StreamEnd {
+                final_frame: Frame::NULL,
+            }),
+        })
+        .unwrap();
+        assert!(decode_message(&bytes).is_ok());
+        bytes[12..16].copy_from_slice(&(-2_i32).to_le_bytes());
+        assert!(decode_message(&bytes)
+            .unwrap_err()
+            .to_string()
+            .contains("stream_end.final_frame"));
+    }
+
+    #[test]
+    fn link_closed_decoder_accepts_null_and_rejects_other_negative_frames() {
+        let mut bytes = encode(&Message {
+            header: MessageHeader::new(1),
+            body: MessageBody::LinkClosed(LinkClosed {
+                last_received_frame: Frame::NULL,
+            }),
+        })
+        .unwrap();
+        assert!(decode_message(&bytes).is_ok());
+        bytes[12..16].copy_from_slice(&(-2_i32).to_le_bytes());
+        assert!(decode_message(&bytes)
+            .unwrap_err()
+            .to_string()
+            .contains("link_closed.last_received_frame"));
+    }
+
+    #[test]
+    fn checksum_request_decoder_rejects_null_and_negative_frames() {
+        let mut bytes = encode(&Message {
+            header: MessageHeader::new(1),
+            body: MessageBody::ChecksumRequest(ChecksumRequest {
+                frame: Frame::new(0),
+            }),
+        })
+        .unwrap();
+        assert!(decode_message(&bytes).is_ok());
+        for frame in [-1_i32, -2] {
+            bytes[12..16].copy_from_slice(&frame.to_le_bytes());
+            assert!(decode_message(&bytes)
+                .unwrap_err()
+                .to_string()
+                .contains("checksum_request.frame"));
+        }
+    }
+
+    #[test]
+    fn reliable_message_decoder_rejects_a_payload_longer_than_the_packet() {
+        let mut bytes = encode(&Message {
+            header: MessageHeader::new(1),
+            body: MessageBody::ReliableMessage(ReliableMessage {
+                sequence: 3,
+                payload: vec![1, 2, 3],
+            }),
+        })
+        .unwrap();
+        assert!(decode_message(&bytes).is_ok());
+        bytes[16..24].copy_from_slice(&4_u64.to_le_bytes());
+        assert_eq!(
+            decode_message(&bytes),
+            Err(CodecError::Truncated {
+                expected_at_least: 28,
+                got: 27,
+            })
+        );
+    }
+
     /// A `ConnectionStatus` with arbitrary field values (used by the wire-size
-    /// property strategies for both `Input` and `StateSnapshot`).
+    /// property strategies for `Input`, `KeepAlive`, and `StateSnapshot`).
     fn arb_connection_status() -> impl proptest::strategy::Strategy<Value = ConnectionStatus> {
         use proptest::prelude::*;
         (any::<bool>(), any::<i32>(), any::<u16>()).prop_map(|(disconnected, frame, epoch)| {
@@ -2578,6 +3154,8 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                 any::<u16>(),
                 any::<u32>(),
                 any::<u64>(),
+                any::<u64>(),
+                any::<u64>(),
             )
                 .prop_map(
                     |(
@@ -2590,6 +3168,8 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                         max_prediction,
                         desync_interval,
                         config_digest,
+                        handles_digest,
+                        access_proof,
                     )| {
                         MessageBody::SyncRequest(SyncRequest {
                             random_request,
@@ -2603,6 +3183,8 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                                 desync_interval,
                             },
                             config_digest,
+                            handles_digest,
+                            access_proof,
                         })
                     },
                 )
@@ -2617,6 +3199,7 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                 any::<u16>(),
                 any::<u32>(),
                 any::<u64>(),
+                any::<u64>(),
             )
                 .prop_map(
                     |(
@@ -2629,6 +3212,7 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                         max_prediction,
                         desync_interval,
                         config_digest,
+                        handles_digest,
                     )| {
                         MessageBody::SyncReply(SyncReply {
                             random_reply,
@@ -2642,6 +3226,7 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                                 desync_interval,
                             },
                             config_digest,
+                            handles_digest,
                         })
                     },
                 )
@@ -2668,11 +3253,12 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                     })
                 })
                 .boxed(),
-            (any::<i16>(), any::<u128>())
-                .prop_map(|(frame_advantage, ping)| {
+            (any::<i16>(), any::<u128>(), any::<bool>())
+                .prop_map(|(frame_advantage, ping, stalled)| {
                     MessageBody::QualityReport(QualityReport {
                         frame_advantage,
                         ping,
+                        stalled,
                     })
                 })
                 .boxed(),
@@ -2687,7 +3273,14 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                     })
                 })
                 .boxed(),
-            Just(MessageBody::KeepAlive).boxed(),
+            (any::<i32>(), pvec(arb_connection_status(), 0..8))
+                .prop_map(|(f, peer_connect_status)| {
+                    MessageBody::KeepAlive(KeepAlive {
+                        ack_frame: Frame::new(f),
+                        peer_connect_status,
+                    })
+                })
+                .boxed(),
             any::<u32>()
                 .prop_map(|round_seq| MessageBody::FloorRequest(FloorRequest { round_seq }))
                 .boxed(),
@@ -2793,6 +3386,73 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
                     })
                 })
                 .boxed(),
+            (
+                any::<u16>(),
+                any::<u32>(),
+                any::<bool>(),
+                0_u8..3,
+                0..=i32::MAX,
+            )
+                .prop_map(|(proposer, sequence, resume, stage, frame)| {
+                    MessageBody::MatchPause(MatchPause {
+                        proposer,
+                        sequence,
+                        kind: if resume {
+                            MatchPauseKind::Resume
+                        } else {
+                            MatchPauseKind::Pause
+                        },
+                        stage: match_pause_stage(stage),
+                        frame: Frame::new(frame),
+                    })
+                })
+                .boxed(),
+            (
+                any::<u16>(),
+                any::<u32>(),
+                0_u8..3,
+                any::<bool>(),
+                -1..=i32::MAX,
+            )
+                .prop_map(|(proposer, sequence, stage, accepted, frame)| {
+                    MessageBody::MatchPauseAck(MatchPauseAck {
+                        proposer,
+                        sequence,
+                        stage: match_pause_stage(stage),
+                        accepted,
+                        frame: Frame::new(frame),
+                    })
+                })
+                .boxed(),
+            (-1..=i32::MAX)
+                .prop_map(|frame| {
+                    MessageBody::StreamEnd(StreamEnd {
+                        final_frame: Frame::new(frame),
+                    })
+                })
+                .boxed(),
+            (any::<u32>(), prop::collection::vec(any::<u8>(), 0..64))
+                .prop_map(|(sequence, payload)| {
+                    MessageBody::ReliableMessage(ReliableMessage { sequence, payload })
+                })
+                .boxed(),
+            any::<u32>()
+                .prop_map(|next_sequence| MessageBody::ReliableAck(ReliableAck { next_sequence }))
+                .boxed(),
+            (-1..=i32::MAX)
+                .prop_map(|frame| {
+                    MessageBody::LinkClosed(LinkClosed {
+                        last_received_frame: Frame::new(frame),
+                    })
+                })
+                .boxed(),
+            (0..=i32::MAX)
+                .prop_map(|frame| {
+                    MessageBody::ChecksumRequest(ChecksumRequest {
+                        frame: Frame::new(frame),
+                    })
+                })
+                .boxed(),
         ];
 
         #[cfg(feature = "hot-join")]
@@ -2893,11 +3553,15 @@ fn arb_message() -> impl proptest::strategy::Strategy<Value = Message> {
             any::<u32>().prop_filter("valid connection ID", |id| {
                 super::super::is_valid_conn_id(*id)
             }),
+            proptest::option::of(any::<u32>()),
             Union::new(bodies),
         )
-            .prop_map(|(conn_id, body)| Message {
-                header: MessageHeader::new(conn_id),
-                body,
+            .prop_map(|(conn_id, sequence, body)| {
+                let header = MessageHeader::new(conn_id);
+                Message {
+                    header: sequence.map_or(header, |sequence| header.with_sequence(sequence)),
+                    body,
+                }
             })
     }
 
@@ -2919,7 +3583,7 @@ fn encoded_len_matches_exact_wire_bytes(msg in arb_message()) {
         }
 
         /// Stream framing is an envelope only: it must preserve the exact
-        /// protocol-v2 bytes for every body variant.
+        /// protocol-v12 bytes for every body variant.
         #[cfg_attr(miri, ignore)] // arbitrary-message proptest takes ~8 minutes on Windows Miri
         #[test]
         fn encode_framed_wraps_exact_arbitrary_message_bytes(msg in arb_message()) {
@@ -2993,7 +3657,7 @@ fn frame_decoder_preserves_arbitrary_concatenated_frame_order(
     fn size_of_val_is_constant_while_wire_size_is_not_d1() {
         let tiny = Message {
             header: MessageHeader::new(0),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let heavy = Message {
             header: MessageHeader::new(0),
@@ -3061,7 +3725,8 @@ fn decode_message_rejects_vec_length_that_exceeds_packet_bytes() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        // The length overflows the byte bound itself: corrupt, not cut off.
+        assert!(matches!(result, Err(CodecError::Malformed { .. })));
     }
 
     #[test]
@@ -3074,7 +3739,8 @@ fn decode_message_rejects_input_bytes_length_that_exceeds_packet_bytes() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        // The end offset overflows `usize`: corrupt, not cut off.
+        assert!(matches!(result, Err(CodecError::Malformed { .. })));
     }
 
     #[test]
@@ -3092,7 +3758,7 @@ fn decode_message_rejects_negative_connection_status_frame() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Malformed { .. })));
     }
 
     #[test]
@@ -3173,7 +3839,7 @@ fn decode_message_rejects_negative_floor_reply_frame() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Malformed { .. })));
     }
 
     #[test]
@@ -3187,7 +3853,7 @@ fn decode_message_rejects_negative_checksum_frame() {
 
             let result = decode_message(&bytes);
 
-            assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+            assert!(matches!(result, Err(CodecError::Malformed { .. })));
         }
     }
 
@@ -3198,22 +3864,149 @@ fn take_bytes_rejects_offset_overflow_and_preserves_cursor() {
 
         let result = take_bytes(&bytes, &mut cursor, 1, "overflowing.field");
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Malformed { .. })));
         assert_eq!(cursor, usize::MAX);
     }
 
+    #[test]
+    fn versioned_records_of_current_golden_fixtures_are_stable() {
+        assert_eq!(
+            CODEC_FORMAT_VERSION,
+            super::wire_golden_v12::WIRE_GOLDEN_VERSION
+        );
+        // A literal record, so the layout itself is pinned too.
+        assert_eq!(
+            encode_versioned(&keep_alive(0x1234)).unwrap(),
+            [
+                0x0C, 0xF5, 0x52, 0x0C, 0x00, 0x34, 0x12, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0xFF,
+                0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+            ]
+        );
+        for (variant, message) in super::wire_golden_v12::fixtures() {
+            let mut record = vec![CODEC_FORMAT_VERSION];
+            record.extend_from_slice(super::wire_golden_v12::expected(&message.body));
+            assert_eq!(
+                encode_versioned(&message).unwrap(),
+                record,
+                "record for {variant}"
+            );
+            let hot_join = matches!(
+                &message.body,
+                MessageBody::JoinRequest(_)
+                    | MessageBody::StateSnapshot(_)
+                    | MessageBody::StateSnapshotAck(_)
+                    | MessageBody::ReactivateSlot(_)
+                    | MessageBody::ReactivateSlotAck(_)
+                    | MessageBody::JoinCommitted(_)
+                    | MessageBody::JoinAborted(_)
+            );
+            if hot_join && !cfg!(feature = "hot-join") {
+                continue;
+            }
+            assert_eq!(
+                decode_versioned(&record).unwrap(),
+                (CODEC_FORMAT_VERSION, message),
+                "decode of {variant}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_versioned_rejects_other_format_versions_distinctly() {
+        let record = encode_versioned(&keep_alive(0xABCD)).unwrap();
+        for version in [
+            0,
+            CODEC_FORMAT_VERSION - 1,
+            CODEC_FORMAT_VERSION + 1,
+            u8::MAX,
+        ] {
+            let mut other = record.clone();
+            other[0] = version;
+            assert_eq!(
+                decode_versioned(&other),
+                Err(CodecError::UnsupportedFormatVersion {
+                    version,
+                    supported: CODEC_FORMAT_VERSION,
+                })
+            );
+        }
+        assert_eq!(
+            decode_versioned(&[]),
+            Err(CodecError::Truncated {
+                expected_at_least: 1,
+                got: 0,
+            })
+        );
+        let mut trailing = record;
+        trailing.push(0);
+        assert_eq!(
+            decode_versioned(&trailing),
+            Err(CodecError::TrailingBytes { extra: 1 })
+        );
+        assert_eq!(
+            decode_versioned(&[CODEC_FORMAT_VERSION]),
+            Err(CodecError::Truncated {
+                expected_at_least: 2,
+                got: 0,
+            })
+        );
+    }
+
     #[test]
     fn decode_message_rejects_trailing_bytes() {
         let original = Message {
             header: MessageHeader::new(0xABCD),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let mut bytes = encode(&original).unwrap();
         bytes.push(0);
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert_eq!(result, Err(CodecError::TrailingBytes { extra: 1 }));
+    }
+
+    #[test]
+    fn decode_message_reports_each_failure_category() {
+        let mut trailing = encode(&keep_alive(1)).unwrap();
+        trailing.extend_from_slice(&[0, 0]);
+        let mut bad_sentinel = wire_prefix(1, 7);
+        bad_sentinel[0] = 0;
+        let mut unsupported = wire_prefix(1, 7);
+        unsupported[2] = crate::PROTOCOL_VERSION.saturating_add(1);
+
+        let cases = [
+            (vec![0xF5], DecodeFailure::Truncated),
+            (trailing, DecodeFailure::TrailingBytes),
+            (bad_sentinel, DecodeFailure::Malformed),
+            (unsupported, DecodeFailure::UnsupportedVersion),
+        ];
+        for (bytes, expected) in &cases {
+            let err = decode_message(bytes).unwrap_err();
+            assert_eq!(err.decode_failure(), Some(*expected), "{err}");
+        }
+
+        assert_eq!(
+            decode_message(&[0xF5]),
+            Err(CodecError::Truncated {
+                expected_at_least: 2,
+                got: 1,
+            })
+        );
+        assert_eq!(
+            decode_message(&cases[3].0),
+            Err(CodecError::UnsupportedVersion {
+                found: crate::PROTOCOL_VERSION.saturating_add(1),
+            })
+        );
+        assert_eq!(
+            CodecError::BufferTooSmall {
+                required: 2,
+                provided: 1
+            }
+            .decode_failure(),
+            None
+        );
     }
 
     #[test]
@@ -3287,6 +4080,15 @@ fn test_codec_error_display() {
         assert!(msg.contains("buffer too small"));
         assert!(msg.contains("100"));
         assert!(msg.contains("10"));
+
+        let err = CodecError::UnsupportedFormatVersion {
+            version: 9,
+            supported: 5,
+        };
+        assert_eq!(
+            err.to_string(),
+            "unsupported codec format version 9 (this build reads version 5)"
+        );
     }
 
     #[test]
@@ -3331,7 +4133,7 @@ fn test_codec_operation_is_copy() {
     fn test_encoding_is_deterministic() {
         let msg = Message {
             header: MessageHeader::new(0x1234),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let bytes1 = encode(&msg).unwrap();
         let bytes2 = encode(&msg).unwrap();
@@ -3345,7 +4147,7 @@ fn test_encoding_is_deterministic() {
     fn test_encode_into_message() {
         let msg = Message {
             header: MessageHeader::new(0x1234),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let mut buffer = [0u8; 256];
         let len = encode_into(&msg, &mut buffer).unwrap();
@@ -3503,7 +4305,7 @@ fn decode_message_rejects_state_bytes_length_that_exceeds_packet_bytes() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A `state_bytes` length that fits in `usize` but exceeds the remaining bytes
@@ -3518,7 +4320,7 @@ fn decode_message_rejects_state_bytes_length_larger_than_remaining() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A `checksum` option tag other than 0/1 is invalid under bincode's encoding.
@@ -3534,7 +4336,7 @@ fn decode_message_rejects_invalid_checksum_option_tag() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Malformed { .. })));
     }
 
     /// Hand-crafts a `StateSnapshot` wire buffer whose `bridge_inputs` length
@@ -3552,7 +4354,7 @@ fn decode_message_rejects_bridge_inputs_length_that_exceeds_packet_bytes() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A `bridge_inputs` length that fits in `usize` but exceeds the remaining
@@ -3569,7 +4371,7 @@ fn decode_message_rejects_bridge_inputs_length_larger_than_remaining() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A snapshot buffer truncated mid-`bridge_inputs` payload (the length
@@ -3584,7 +4386,7 @@ fn decode_message_rejects_snapshot_truncated_before_bridge_inputs() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// Hand-crafts a `StateSnapshot` wire buffer whose `bridge_statuses`
@@ -3603,7 +4405,7 @@ fn decode_message_rejects_bridge_statuses_length_that_exceeds_packet_bytes() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Malformed { .. })));
     }
 
     /// A `bridge_statuses` length that fits in `usize` but whose minimum wire
@@ -3621,7 +4423,7 @@ fn decode_message_rejects_bridge_statuses_length_larger_than_remaining() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A snapshot buffer truncated before the `bridge_statuses` length prefix
@@ -3637,7 +4439,7 @@ fn decode_message_rejects_snapshot_truncated_before_bridge_statuses() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A snapshot buffer truncated mid-`bridge_statuses` payload (the prefix
@@ -3657,7 +4459,7 @@ fn decode_message_rejects_snapshot_truncated_inside_bridge_statuses() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A valid snapshot buffer with extra trailing bytes must be rejected.
@@ -3679,7 +4481,7 @@ fn decode_message_rejects_trailing_bytes_after_snapshot() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::TrailingBytes { .. })));
     }
 
     #[test]
@@ -3715,7 +4517,7 @@ fn decode_message_rejects_truncated_reactivate_slot() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A `ReactivateSlotAck` buffer truncated mid-`frame` must likewise be rejected.
@@ -3727,7 +4529,7 @@ fn decode_message_rejects_truncated_reactivate_slot_ack() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A valid `ReactivateSlot` buffer with an extra trailing byte must be rejected
@@ -3746,7 +4548,7 @@ fn decode_message_rejects_trailing_bytes_after_reactivate_slot() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::TrailingBytes { .. })));
     }
 
     /// A valid `ReactivateSlotAck` buffer with an extra trailing byte must be
@@ -3765,7 +4567,7 @@ fn decode_message_rejects_trailing_bytes_after_reactivate_slot_ack() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::TrailingBytes { .. })));
     }
 
     #[test]
@@ -3801,7 +4603,7 @@ fn decode_message_rejects_truncated_join_committed() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A `JoinAborted` buffer truncated mid-`frame` must likewise be rejected.
@@ -3813,7 +4615,7 @@ fn decode_message_rejects_truncated_join_aborted() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::Truncated { .. })));
     }
 
     /// A valid `JoinCommitted` buffer with an extra trailing byte must be rejected
@@ -3832,7 +4634,7 @@ fn decode_message_rejects_trailing_bytes_after_join_committed() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::TrailingBytes { .. })));
     }
 
     /// A valid `JoinAborted` buffer with an extra trailing byte must be rejected
@@ -3851,6 +4653,6 @@ fn decode_message_rejects_trailing_bytes_after_join_aborted() {
 
         let result = decode_message(&bytes);
 
-        assert!(matches!(result, Err(CodecError::DecodeError { .. })));
+        assert!(matches!(result, Err(CodecError::TrailingBytes { .. })));
     }
 }
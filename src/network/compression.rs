@@ -103,10 +103,27 @@ pub(crate) fn try_encode<'a>(
     reference: &[u8],
     pending_input: impl Iterator<Item = &'a Vec<u8>>,
 ) -> Result<Vec<u8>, FortressError> {
+    let mut scratch = Vec::new();
+    let mut encoded = Vec::new();
+    try_encode_into(reference, pending_input, &mut scratch, &mut encoded)?;
+    Ok(encoded)
+}
+
+/// [`try_encode`] into caller-owned buffers: `scratch` holds the XOR delta
+/// (its previous contents are discarded) and the encoding is appended to
+/// `out`. Both keep their capacity, so reusing them avoids per-call
+/// allocation.
+pub(crate) fn try_encode_into<'a>(
+    reference: &[u8],
+    pending_input: impl Iterator<Item = &'a Vec<u8>>,
+    scratch: &mut Vec<u8>,
+    out: &mut Vec<u8>,
+) -> Result<(), FortressError> {
     // first, do a XOR encoding to the reference input (will probably lead to a lot of same bits in sequence)
-    let buf = try_delta_encode(reference, pending_input)?;
+    scratch.clear();
+    try_delta_encode_into(reference, pending_input, scratch)?;
     // then, RLE encode the buffer (making use of the property mentioned above)
-    rle::try_encode(buf)
+    rle::try_encode_into(scratch, out)
 }
 
 /// Performs XOR delta encoding against a reference.
@@ -133,6 +150,17 @@ pub(crate) fn try_delta_encode<'a>(
     ref_bytes: &[u8],
     pending_input: impl Iterator<Item = &'a Vec<u8>>,
 ) -> Result<Vec<u8>, FortressError> {
+    let mut bytes = Vec::new();
+    try_delta_encode_into(ref_bytes, pending_input, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// [`try_delta_encode`], appending the delta to `bytes`.
+fn try_delta_encode_into<'a>(
+    ref_bytes: &[u8],
+    pending_input: impl Iterator<Item = &'a Vec<u8>>,
+    bytes: &mut Vec<u8>,
+) -> Result<(), FortressError> {
     if ref_bytes.is_empty() {
         report_violation!(
             ViolationSeverity::Error,
@@ -144,8 +172,6 @@ pub(crate) fn try_delta_encode<'a>(
         });
     }
 
-    let mut bytes = Vec::new();
-
     // Bind the iterator before reading its size hint (`size_hint` borrows it).
     // Each ACCEPTED input contributes exactly `ref_bytes.len()` bytes, so the
     // common case is a single allocation of `upper * ref_bytes.len()`. The hint
@@ -155,7 +181,7 @@ pub(crate) fn try_delta_encode<'a>(
     // can never make the output differ from pure incremental growth for any
     // iterator (honest or adversarial) -- see `try_reserve_hint`'s docs.
     let (_lower, upper) = pending_input.size_hint();
-    try_reserve_hint(&mut bytes, upper, ref_bytes.len());
+    try_reserve_hint(bytes, upper, ref_bytes.len());
 
     for input in pending_input {
         let input_bytes = input;
@@ -191,7 +217,7 @@ pub(crate) fn try_delta_encode<'a>(
             bytes.push(reference_byte ^ input_byte);
         }
     }
-    Ok(bytes)
+    Ok(())
 }
 
 /// Maps an RLE error to a `CompressionError`.
@@ -249,15 +275,46 @@ pub fn decode_with_max_len(
     delta_decode(reference, &buf)
 }
 
-/// Decodes XOR delta-encoded data against a reference.
+/// [`decode_with_max_len`] into caller-owned buffers: the RLE stage decodes
+/// into `scratch` (its previous contents are discarded) and the decoded frames
+/// are appended to `out` back to back, `reference.len()` bytes each. Both keep
+/// their capacity, so reusing them avoids per-packet allocation.
 ///
 /// # Errors
 ///
-/// Returns a `CompressionError::DeltaDecode` if:
-/// - The reference bytes are empty
-/// - The data length is not a multiple of the reference length
-/// - An index is out of bounds during decoding
-pub fn delta_decode(ref_bytes: &[u8], data: &[u8]) -> Result<Vec<Vec<u8>>, CompressionError> {
+/// The same as [`decode_with_max_len`]. On error `out` may hold a partially
+/// decoded tail past its original length.
+pub(crate) fn decode_into(
+    reference: &[u8],
+    data: &[u8],
+    max_decoded_len: usize,
+    scratch: &mut Vec<u8>,
+    out: &mut Vec<u8>,
+) -> Result<(), CompressionError> {
+    scratch.clear();
+    rle::decode_into(data, max_decoded_len, scratch).map_err(map_rle_error)?;
+    let frames = delta_decoded_frame_count(reference, scratch)?;
+    out.try_reserve(scratch.len())
+        .map_err(|_err| CompressionError::DeltaDecode {
+            reason: DeltaDecodeReason::AllocationFailed {
+                context: "compression.delta_decode.output",
+                requested_elements: frames,
+            },
+        })?;
+    for frame in scratch.chunks_exact(reference.len()) {
+        out.extend(
+            frame
+                .iter()
+                .zip(reference)
+                .map(|(data_byte, ref_byte)| ref_byte ^ data_byte),
+        );
+    }
+    Ok(())
+}
+
+/// Validates a delta-encoded buffer against its reference and returns how
+/// many frames it holds.
+fn delta_decoded_frame_count(ref_bytes: &[u8], data: &[u8]) -> Result<usize, CompressionError> {
     // Validate preconditions - return error instead of panicking
     if ref_bytes.is_empty() {
         report_violation!(
@@ -302,6 +359,19 @@ pub fn delta_decode(ref_bytes: &[u8], data: &[u8]) -> Result<Vec<Vec<u8>>, Compr
             },
         });
     }
+    Ok(out_size)
+}
+
+/// Decodes XOR delta-encoded data against a reference.
+///
+/// # Errors
+///
+/// Returns a `CompressionError::DeltaDecode` if:
+/// - The reference bytes are empty
+/// - The data length is not a multiple of the reference length
+/// - An index is out of bounds during decoding
+pub fn delta_decode(ref_bytes: &[u8], data: &[u8]) -> Result<Vec<Vec<u8>>, CompressionError> {
+    let out_size = delta_decoded_frame_count(ref_bytes, data)?;
 
     let mut output = Vec::new();
     output
@@ -353,6 +423,315 @@ pub fn delta_decode(ref_bytes: &[u8], data: &[u8]) -> Result<Vec<Vec<u8>>, Compr
     }
 
     Ok(output)
+}
+
+// =============================================================================
+// Keyframe Slot Encoding
+// =============================================================================
+
+/// Number of mask bytes that prefix every frame of the keyframe format.
+pub(crate) const fn keyframe_mask_len(slots: usize) -> usize {
+    slots.div_ceil(8)
+}
+
+/// Size of the widest frame (mask plus every slot) the keyframe format can
+/// produce for `reference_len`-byte inputs split into `slots` slots.
+pub(crate) const fn keyframe_frame_len(reference_len: usize, slots: usize) -> Option<usize> {
+    reference_len.checked_add(keyframe_mask_len(slots))
+}
+
+/// Returns the per-slot width, or `None` if `reference_len` is not a non-zero
+/// multiple of `slots`.
+fn keyframe_slot_width(reference_len: usize, slots: usize) -> Option<usize> {
+    if reference_len == 0 || slots == 0 || reference_len % slots != 0 {
+        return None;
+    }
+    Some(reference_len / slots)
+}
+
+/// Encodes inputs in the keyframe slot format followed by RLE compression.
+///
+/// Every input is `slots` equally sized per-player slots. Each frame is written
+/// as a slot mask ([`keyframe_mask_len`] bytes, bit `i % 8` of byte `i / 8` for
+/// slot `i`) followed by the bytes of every slot whose bit is set. A frame
+/// flagged as a keyframe sets every bit; any other frame sets only the slots
+/// whose bytes differ from the previous frame (`reference` for the first
+/// frame), so an idle frame costs just its mask.
+#[cfg(test)]
+pub(crate) fn try_keyframe_encode<'a>(
+    reference: &[u8],
+    slots: usize,
+    pending_input: impl Iterator<Item = (bool, &'a Vec<u8>)>,
+) -> Result<Vec<u8>, FortressError> {
+    let mut scratch = Vec::new();
+    let mut encoded = Vec::new();
+    try_keyframe_encode_into(reference, slots, pending_input, &mut scratch, &mut encoded)?;
+    Ok(encoded)
+}
+
+/// `try_keyframe_encode` into caller-owned buffers, like [`try_encode_into`].
+pub(crate) fn try_keyframe_encode_into<'a>(
+    reference: &[u8],
+    slots: usize,
+    pending_input: impl Iterator<Item = (bool, &'a Vec<u8>)>,
+    bytes: &mut Vec<u8>,
+    out: &mut Vec<u8>,
+) -> Result<(), FortressError> {
+    let Some(slot_width) = keyframe_slot_width(reference.len(), slots) else {
+        report_violation!(
+            ViolationSeverity::Error,
+            ViolationKind::NetworkProtocol,
+            "keyframe_encode: reference length {} cannot be split into {} slots",
+            reference.len(),
+            slots
+        );
+        return Err(FortressError::InternalErrorStructured {
+            kind: InternalErrorKind::KeyframeEncodeSlotWidthMismatch {
+                reference_len: reference.len(),
+                slots,
+            },
+        });
+    };
+    let mask_len = keyframe_mask_len(slots);
+
+    bytes.clear();
+    let (_lower, upper) = pending_input.size_hint();
+    try_reserve_hint(bytes, upper, mask_len.saturating_add(reference.len()));
+
+    let mut previous = reference;
+    for (keyframe, input) in pending_input {
+        if input.len() != reference.len() {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "keyframe_encode: input length {} does not match reference length {}",
+                input.len(),
+                reference.len()
+            );
+            return Err(FortressError::InternalErrorStructured {
+                kind: InternalErrorKind::DeltaEncodeInputLengthMismatch {
+                    input_len: input.len(),
+                    reference_len: reference.len(),
+                },
+            });
+        }
+
+        let requested = bytes
+            .len()
+            .saturating_add(mask_len)
+            .saturating_add(input.len());
+        // reserve-in-loop: fallible guard that only fires when an untrusted size_hint under-reported (or was absent), keeping growth panic-free.
+        let reserved = bytes.try_reserve(mask_len.saturating_add(input.len()));
+        reserved.map_err(|_err| allocation_failed("compression.keyframe_encode", requested))?;
+
+        let mask_start = bytes.len();
+        // alloc-bound: within the `try_reserve` just above.
+        bytes.resize(mask_start.saturating_add(mask_len), 0);
+        let slot_pairs = input
+            .chunks_exact(slot_width)
+            .zip(previous.chunks_exact(slot_width));
+        for (slot, (current, prior)) in slot_pairs.enumerate() {
+            if keyframe || current != prior {
+                if let Some(mask_byte) = bytes.get_mut(mask_start + slot / 8) {
+                    *mask_byte |= 1 << (slot % 8);
+                }
+                bytes.extend_from_slice(current);
+            }
+        }
+        previous = input;
+    }
+
+    rle::try_encode_into(bytes, out)
+}
+
+/// Decodes RLE-compressed keyframe slot data produced by [`try_keyframe_encode`].
+///
+/// Slots whose mask bit is clear are carried over from the previous decoded
+/// frame (`reference` for the first frame). `max_decoded_len` caps the RLE
+/// stage exactly as in [`decode_with_max_len`].
+///
+/// # Errors
+///
+/// Returns a `CompressionError` if:
+/// - RLE decoding fails
+/// - `reference` cannot be split into `slots` equally sized slots
+/// - a mask names a slot past `slots`, or the stream ends inside a frame
+/// - the stream holds more than [`MAX_DELTA_DECODED_FRAMES`] frames
+#[cfg(test)]
+pub(crate) fn keyframe_decode_with_max_len(
+    reference: &[u8],
+    slots: usize,
+    data: &[u8],
+    max_decoded_len: usize,
+) -> Result<Vec<Vec<u8>>, CompressionError> {
+    let mut scratch = Vec::new();
+    let mut frames = Vec::new();
+    keyframe_decode_into(
+        reference,
+        slots,
+        data,
+        max_decoded_len,
+        &mut scratch,
+        &mut frames,
+    )?;
+
+    let mut output: Vec<Vec<u8>> = Vec::new();
+    let frame_count = frames.len() / reference.len();
+    output
+        .try_reserve_exact(frame_count)
+        .map_err(|_err| CompressionError::DeltaDecode {
+            reason: DeltaDecodeReason::AllocationFailed {
+                context: "compression.keyframe_decode.output",
+                requested_elements: frame_count,
+            },
+        })?;
+    for frame in frames.chunks_exact(reference.len()) {
+        let mut buffer = Vec::new();
+        // reserve-in-loop: one decoded-input buffer per frame, reserved once to its exact bounded size (`reference.len()`).
+        buffer
+            .try_reserve_exact(frame.len())
+            .map_err(|_err| CompressionError::DeltaDecode {
+                reason: DeltaDecodeReason::AllocationFailed {
+                    context: "compression.keyframe_decode.buffer",
+                    requested_elements: frame.len(),
+                },
+            })?;
+        buffer.extend_from_slice(frame);
+        output.push(buffer);
+    }
+    Ok(output)
+}
+
+/// `keyframe_decode_with_max_len` into caller-owned buffers, like
+/// [`decode_into`]: the RLE stage decodes into `scratch` and the frames are
+/// appended to `out` back to back, `reference.len()` bytes each.
+pub(crate) fn keyframe_decode_into(
+    reference: &[u8],
+    slots: usize,
+    data: &[u8],
+    max_decoded_len: usize,
+    scratch: &mut Vec<u8>,
+    out: &mut Vec<u8>,
+) -> Result<(), CompressionError> {
+    let Some(slot_width) = keyframe_slot_width(reference.len(), slots) else {
+        report_violation!(
+            ViolationSeverity::Error,
+            ViolationKind::NetworkProtocol,
+            "keyframe_decode: reference length {} cannot be split into {} slots",
+            reference.len(),
+            slots
+        );
+        return Err(CompressionError::DeltaDecode {
+            reason: DeltaDecodeReason::SlotWidthMismatch {
+                reference_len: reference.len(),
+                slots,
+            },
+        });
+    };
+    let mask_len = keyframe_mask_len(slots);
+    scratch.clear();
+    rle::decode_into(data, max_decoded_len, scratch).map_err(map_rle_error)?;
+    let buf = scratch.as_slice();
+
+    let out_of_bounds = |index: usize| CompressionError::DeltaDecode {
+        reason: DeltaDecodeReason::DataIndexOutOfBounds {
+            index,
+            length: buf.len(),
+        },
+    };
+
+    let mut frame_count = 0;
+    let mut pos = 0;
+    while pos < buf.len() {
+        if frame_count >= MAX_DELTA_DECODED_FRAMES {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "keyframe_decode: decoded frame count exceeds maximum {}",
+                MAX_DELTA_DECODED_FRAMES
+            );
+            return Err(CompressionError::DeltaDecode {
+                reason: DeltaDecodeReason::DecodedFrameCountExceedsMaximum {
+                    frame_count: frame_count + 1,
+                    max: MAX_DELTA_DECODED_FRAMES,
+                },
+            });
+        }
+        let mask_end = pos.saturating_add(mask_len);
+        let mask = buf
+            .get(pos..mask_end)
+            .ok_or_else(|| out_of_bounds(buf.len()))?;
+        pos = mask_end;
+
+        // Carried-over slots come from the previous decoded frame, which sits
+        // just before this one in `out`, or from `reference` for the first.
+        let frame_start = out.len();
+        let previous_start = (frame_count > 0).then(|| frame_start.saturating_sub(reference.len()));
+        // reserve-in-loop: one decoded frame per iteration, bounded by MAX_DELTA_DECODED_FRAMES (checked above).
+        out.try_reserve(reference.len())
+            .map_err(|_err| CompressionError::DeltaDecode {
+                reason: DeltaDecodeReason::AllocationFailed {
+                    context: "compression.keyframe_decode.buffer",
+                    requested_elements: reference.len(),
+                },
+            })?;
+        for (byte_index, &mask_byte) in mask.iter().enumerate() {
+            for bit in 0..8 {
+                let slot = byte_index * 8 + bit;
+                let present = mask_byte & (1 << bit) != 0;
+                if slot >= slots {
+                    if present {
+                        report_violation!(
+                            ViolationSeverity::Error,
+                            ViolationKind::NetworkProtocol,
+                            "keyframe_decode: mask names slot {} of {}",
+                            slot,
+                            slots
+                        );
+                        return Err(CompressionError::DeltaDecode {
+                            reason: DeltaDecodeReason::SlotMaskOutOfRange { slot, slots },
+                        });
+                    }
+                    continue;
+                }
+                if present {
+                    let slot_end = pos.saturating_add(slot_width);
+                    let fresh = buf
+                        .get(pos..slot_end)
+                        .ok_or_else(|| out_of_bounds(buf.len()))?;
+                    pos = slot_end;
+                    out.extend_from_slice(fresh);
+                    continue;
+                }
+                let start = slot * slot_width;
+                let carried_out_of_bounds = CompressionError::DeltaDecode {
+                    reason: DeltaDecodeReason::ReferenceIndexOutOfBounds {
+                        index: start,
+                        length: reference.len(),
+                    },
+                };
+                match previous_start {
+                    Some(previous) => {
+                        let carried = previous.saturating_add(start)
+                            ..previous.saturating_add(start).saturating_add(slot_width);
+                        if carried.end > frame_start {
+                            return Err(carried_out_of_bounds);
+                        }
+                        out.extend_from_within(carried);
+                    },
+                    None => {
+                        let carried = reference
+                            .get(start..start + slot_width)
+                            .ok_or(carried_out_of_bounds)?;
+                        out.extend_from_slice(carried);
+                    },
+                }
+            }
+        }
+        frame_count += 1;
+    }
+
+    Ok(())
 } // #########
   // # TESTS #
   // #########
@@ -1059,6 +1438,145 @@ fn try_delta_encode_empty_reference_is_rejected() {
             }
         ));
     }
+
+    /// Decodes a keyframe batch with the default RLE cap.
+    fn keyframe_decode(
+        reference: &[u8],
+        slots: usize,
+        data: &[u8],
+    ) -> Result<Vec<Vec<u8>>, CompressionError> {
+        keyframe_decode_with_max_len(reference, slots, data, rle::DEFAULT_MAX_DECODED_LEN)
+    }
+
+    #[test]
+    fn keyframe_encode_decode_roundtrip() {
+        // Three 2-byte slots; frame 2 is a keyframe.
+        let reference = vec![0, 0, 5, 5, 9, 9];
+        let inputs = vec![
+            vec![0, 0, 5, 5, 9, 9],
+            vec![1, 0, 5, 5, 9, 9],
+            vec![1, 0, 5, 5, 9, 9],
+            vec![1, 0, 6, 6, 0, 0],
+        ];
+        let keyframes = [false, false, true, false];
+
+        let encoded =
+            try_keyframe_encode(&reference, 3, keyframes.iter().copied().zip(inputs.iter()))
+                .unwrap();
+        let decoded = keyframe_decode(&reference, 3, &encoded).unwrap();
+
+        assert_eq!(decoded, inputs);
+    }
+
+    #[test]
+    fn keyframe_encode_sends_only_changed_slots() {
+        let reference = vec![0; 8];
+        let mut changed = reference.clone();
+        changed[5] = 7;
+        let inputs = [changed];
+
+        let encoded =
+            try_keyframe_encode(&reference, 4, std::iter::once((false, &inputs[0]))).unwrap();
+        let raw = rle::decode(&encoded).unwrap();
+        assert_eq!(raw, vec![0b0100, 0, 7], "mask plus slot 2 only");
+
+        let encoded =
+            try_keyframe_encode(&reference, 4, std::iter::once((true, &inputs[0]))).unwrap();
+        let raw = rle::decode(&encoded).unwrap();
+        assert_eq!(raw.len(), 1 + 8, "a keyframe carries every slot");
+        assert_eq!(raw[0], 0b1111);
+    }
+
+    #[test]
+    fn keyframe_encode_beats_delta_encode_on_held_inputs() {
+        // Eight players, one of which changes and then holds its input: the
+        // delta format repeats the change against the batch reference on every
+        // frame, the keyframe format sends it once.
+        let reference = vec![0; 32];
+        let mut held = reference.clone();
+        held[4..8].copy_from_slice(&[0x12, 0x34, 0x56, 0x78]);
+        let inputs = vec![held; 12];
+
+        let delta = try_encode(&reference, inputs.iter()).unwrap();
+        let keyframe =
+            try_keyframe_encode(&reference, 8, inputs.iter().map(|input| (false, input))).unwrap();
+
+        assert!(
+            keyframe.len() * 2 < delta.len(),
+            "keyframe {} bytes vs delta {} bytes",
+            keyframe.len(),
+            delta.len()
+        );
+        assert_eq!(keyframe_decode(&reference, 8, &keyframe).unwrap(), inputs);
+    }
+
+    #[test]
+    fn keyframe_encode_rejects_uneven_slots() {
+        let reference = vec![0; 5];
+        let err = try_keyframe_encode(&reference, 2, std::iter::empty()).unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InternalErrorStructured {
+                kind: InternalErrorKind::KeyframeEncodeSlotWidthMismatch {
+                    reference_len: 5,
+                    slots: 2
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn keyframe_decode_rejects_uneven_slots() {
+        let err = keyframe_decode(&[0; 5], 2, &rle::encode([0u8])).unwrap_err();
+        assert_eq!(
+            err,
+            CompressionError::DeltaDecode {
+                reason: DeltaDecodeReason::SlotWidthMismatch {
+                    reference_len: 5,
+                    slots: 2
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn keyframe_decode_rejects_mask_bit_past_last_slot() {
+        // Three slots use bits 0..=2 of the single mask byte.
+        let data = rle::encode([0b1000u8]);
+        let err = keyframe_decode(&[0; 3], 3, &data).unwrap_err();
+        assert_eq!(
+            err,
+            CompressionError::DeltaDecode {
+                reason: DeltaDecodeReason::SlotMaskOutOfRange { slot: 3, slots: 3 }
+            }
+        );
+    }
+
+    #[test]
+    fn keyframe_decode_rejects_truncated_slot() {
+        // Slot 1 (2 bytes wide) is announced but only one byte follows.
+        let data = rle::encode([0b10u8, 7]);
+        let err = keyframe_decode(&[0; 4], 2, &data).unwrap_err();
+        assert!(matches!(
+            err,
+            CompressionError::DeltaDecode {
+                reason: DeltaDecodeReason::DataIndexOutOfBounds { .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn keyframe_decode_rejects_too_many_frames() {
+        // Every empty mask byte is one idle frame.
+        let data = rle::encode(vec![0u8; MAX_DELTA_DECODED_FRAMES + 1]);
+        let err = keyframe_decode(&[0; 4], 4, &data).unwrap_err();
+        assert!(matches!(
+            err,
+            CompressionError::DeltaDecode {
+                reason: DeltaDecodeReason::DecodedFrameCountExceedsMaximum { .. }
+            }
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -7,8 +7,9 @@
 ///
 /// # Note
 ///
-/// This type is re-exported in [`__internal`](crate::__internal) for testing and fuzzing.
-/// It is not part of the stable public API.
+/// This type is re-exported in [`__internal`](crate::__internal) for testing and fuzzing,
+/// and in [`network::wire`](crate::network::wire) as part of the `Input` body encoding.
+/// Only the latter is a stable public API.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConnectionStatus {
     /// Whether this peer has disconnected.
@@ -101,6 +102,11 @@ pub(crate) struct SyncRequest {
     pub features: u32,
     pub config: SessionConfigBlock,
     pub config_digest: u64,
+    /// Digest of the player handles the sender owns locally.
+    pub handles_digest: u64,
+    /// Keyed MAC of `random_request` proving the sender holds the host's
+    /// spectator access token; `0` when the sender has none.
+    pub access_proof: u64,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -110,6 +116,8 @@ pub(crate) struct SyncReply {
     pub features: u32,
     pub config: SessionConfigBlock,
     pub config_digest: u64,
+    /// Digest of the player handles the sender owns locally.
+    pub handles_digest: u64,
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -174,6 +182,31 @@ fn default() -> Self {
     }
 }
 
+/// Sent when the link has been quiet for a keepalive interval.
+///
+/// Carries the sender's newest input acknowledgement, so acks keep reaching a
+/// peer whose inputs flow in one direction only, and its connect status, so
+/// the status keeps reaching a peer after those acks drained every `Input`
+/// that would otherwise have carried it. The ack is applied like an
+/// [`InputAck`] and the status merged like an [`Input`]'s.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct KeepAlive {
+    /// Same as [`InputAck::ack_frame`]; `Frame::NULL` acknowledges nothing.
+    pub ack_frame: Frame,
+    /// The sender's connect status for every player, as carried by an
+    /// [`Input`]; empty when the sender shares none.
+    pub peer_connect_status: Vec<ConnectionStatus>,
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self {
+            ack_frame: Frame::NULL,
+            peer_connect_status: Vec::new(),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub(crate) struct QualityReport {
     /// Frame advantage of other player.
@@ -190,6 +223,9 @@ pub(crate) struct QualityReport {
     /// far ahead/behind that clamping the value to an i16 won't matter for any practical purpose.
     pub frame_advantage: i16,
     pub ping: u128,
+    /// The sender keeps polling but has not advanced a frame for longer than
+    /// its advance-stall threshold.
+    pub stalled: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -268,22 +304,120 @@ pub(crate) struct FloorReply {
     pub floors: Vec<Frame>,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// The fixed header every message starts with.
+///
+/// When [`flags`](Self::flags) has [`FLAG_SEQUENCE`](Self::FLAG_SEQUENCE)
+/// set, a wrapping per-endpoint [`sequence`](Self::sequence) number follows
+/// the connection ID on the wire; otherwise the field is not encoded and reads
+/// as zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) struct MessageHeader {
     pub sentinel: [u8; 2],
     pub protocol_version: u8,
     pub flags: u8,
     pub conn_id: u32,
+    pub sequence: u32,
 }
 
 impl MessageHeader {
+    /// Header flag: a `u32` sequence number follows the connection ID.
+    pub(crate) const FLAG_SEQUENCE: u8 = 1 << 0;
+    /// Every header flag this protocol version defines.
+    pub(crate) const KNOWN_FLAGS: u8 = Self::FLAG_SEQUENCE;
+
     pub(crate) const fn new(conn_id: u32) -> Self {
         Self {
             sentinel: super::WIRE_SENTINEL,
             protocol_version: crate::PROTOCOL_VERSION,
             flags: 0,
             conn_id,
+            sequence: 0,
+        }
+    }
+
+    /// This header with `sequence` attached.
+    pub(crate) const fn with_sequence(mut self, sequence: u32) -> Self {
+        self.flags |= Self::FLAG_SEQUENCE;
+        self.sequence = sequence;
+        self
+    }
+
+    /// The sequence number, if the header carries one.
+    pub(crate) const fn sequence(&self) -> Option<u32> {
+        if self.flags & Self::FLAG_SEQUENCE != 0 {
+            Some(self.sequence)
+        } else {
+            None
+        }
+    }
+
+    /// Encoded size of this header: 8 bytes, plus 4 with a sequence number.
+    pub(crate) const fn encoded_len(&self) -> usize {
+        if self.flags & Self::FLAG_SEQUENCE != 0 {
+            12
+        } else {
+            8
+        }
+    }
+}
+
+// The sequence number is only encoded when its flag is set, so the header
+// serializes as a tuple of four or five fields rather than a derived struct.
+impl Serialize for MessageHeader {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+
+        let sequenced = self.sequence().is_some();
+        let mut tuple = serializer.serialize_tuple(if sequenced { 5 } else { 4 })?;
+        tuple.serialize_element(&self.sentinel)?;
+        tuple.serialize_element(&self.protocol_version)?;
+        tuple.serialize_element(&self.flags)?;
+        tuple.serialize_element(&self.conn_id)?;
+        if sequenced {
+            tuple.serialize_element(&self.sequence)?;
         }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageHeader {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HeaderVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for HeaderVisitor {
+            type Value = MessageHeader;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("a message header")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                use serde::de::Error;
+
+                let missing = |index| A::Error::invalid_length(index, &self);
+                let sentinel = seq.next_element()?.ok_or_else(|| missing(0))?;
+                let protocol_version = seq.next_element()?.ok_or_else(|| missing(1))?;
+                let flags: u8 = seq.next_element()?.ok_or_else(|| missing(2))?;
+                let conn_id = seq.next_element()?.ok_or_else(|| missing(3))?;
+                let sequence = if flags & MessageHeader::FLAG_SEQUENCE != 0 {
+                    seq.next_element()?.ok_or_else(|| missing(4))?
+                } else {
+                    0
+                };
+                Ok(MessageHeader {
+                    sentinel,
+                    protocol_version,
+                    flags,
+                    conn_id,
+                    sequence,
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple(5, HeaderVisitor)
     }
 }
 
@@ -528,6 +662,115 @@ pub(crate) struct DropAbort {
     pub reason: DropAbortReason,
 }
 
+/// Whether a match-pause negotiation stops or restarts the simulation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) enum MatchPauseKind {
+    /// Stop advancing at the negotiated frame.
+    #[default]
+    Pause,
+    /// Start advancing again from the frame the match is paused at.
+    Resume,
+}
+
+/// Step of a match-pause negotiation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) enum MatchPauseStage {
+    /// The proposer asks every peer to hold at `frame`.
+    #[default]
+    Propose,
+    /// Every peer accepted; `frame` is the agreed frame.
+    Commit,
+    /// The proposer closed the negotiation without an agreement.
+    Cancel,
+}
+
+/// Proposer-side match pause/resume control message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) struct MatchPause {
+    /// Lowest player handle of the proposing peer.
+    pub proposer: u16,
+    /// Proposer-local negotiation counter.
+    pub sequence: u32,
+    pub kind: MatchPauseKind,
+    pub stage: MatchPauseStage,
+    /// Proposed frame for `Propose`, agreed frame for `Commit`.
+    pub frame: Frame,
+}
+
+/// Responder-side answer to one [`MatchPause`] stage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) struct MatchPauseAck {
+    pub proposer: u16,
+    pub sequence: u32,
+    pub stage: MatchPauseStage,
+    /// For `Propose`: whether the responder is now holding for the proposal.
+    pub accepted: bool,
+    /// For an accepted `Propose`: the frame the responder is holding at,
+    /// which is never below the proposed frame.
+    pub frame: Frame,
+}
+
+/// Host-to-spectator notice that the match ended at `final_frame`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct StreamEnd {
+    /// Last frame whose inputs the host forwards; `Frame::NULL` if none.
+    pub final_frame: Frame,
+}
+
+impl Default for StreamEnd {
+    fn default() -> Self {
+        Self {
+            final_frame: Frame::NULL,
+        }
+    }
+}
+
+/// Notice that the sender closed this link after a local timeout, resent
+/// until its endpoint shuts down.
+///
+/// Carries the last frame of the receiver's input the sender got, so both
+/// sides of a dropped link can agree on the last frame they share even when
+/// each timed the other out on its own.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LinkClosed {
+    /// Newest input frame received from the peer; `Frame::NULL` if none.
+    pub last_received_frame: Frame,
+}
+
+impl Default for LinkClosed {
+    fn default() -> Self {
+        Self {
+            last_received_frame: Frame::NULL,
+        }
+    }
+}
+
+/// One-off request for the peer's [`ChecksumReport`] of `frame`, resent until
+/// the report arrives.
+///
+/// A rejoining peer uses it to check the state it was handed out of band
+/// against the peer's own state at that frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) struct ChecksumRequest {
+    /// The frame whose state checksum is requested; never `Frame::NULL`.
+    pub frame: Frame,
+}
+
+/// One application message on the reliable channel, resent until acked.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) struct ReliableMessage {
+    /// Per-endpoint send counter, wrapping at `u32::MAX`.
+    pub sequence: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Cumulative acknowledgement of the reliable channel.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) struct ReliableAck {
+    /// Every sequence before this one was delivered.
+    pub next_sequence: u32,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum MessageBody {
     SyncRequest(SyncRequest),
@@ -537,7 +780,7 @@ pub(crate) enum MessageBody {
     QualityReport(QualityReport),
     QualityReply(QualityReply),
     ChecksumReport(ChecksumReport),
-    KeepAlive,
+    KeepAlive(KeepAlive),
     // Floor-round messages (double-failure-relay connected-relay reorder fix,
     // S55) are appended AFTER the original core variants so the existing core
     // discriminants (0..=7) stay stable. The wire vocabulary below is compiled
@@ -561,6 +804,18 @@ pub(crate) enum MessageBody {
     DropBackfill(DropBackfill),
     DropCommit(DropCommit),
     DropAbort(DropAbort),
+    // Protocol-v3 match pause/resume negotiation, tags 23..=24.
+    MatchPause(MatchPause),
+    MatchPauseAck(MatchPauseAck),
+    // Protocol-v4 spectator end-of-stream notice, tag 25.
+    StreamEnd(StreamEnd),
+    // Protocol-v5 reliable channel, tags 26..=27.
+    ReliableMessage(ReliableMessage),
+    ReliableAck(ReliableAck),
+    // Protocol-v6 closed-link notice, tag 28.
+    LinkClosed(LinkClosed),
+    // Protocol-v7 one-off checksum request, tag 29.
+    ChecksumRequest(ChecksumRequest),
 }
 
 /// A messages that [`NonBlockingSocket`] sends and receives. When implementing [`NonBlockingSocket`],
@@ -591,11 +846,18 @@ pub(crate) fn encoded_len(&self) -> usize {
 
         let payload = match self {
             Self::SyncRequest(_) | Self::SyncReply(_) => {
+                let access_proof = if matches!(self, Self::SyncRequest(_)) {
+                    8 // access_proof: u64
+                } else {
+                    0
+                };
                 4 // random token: u32
                     + 1 // min_compat_version: u8
                     + 4 // features: u32
                     + 14 // SessionConfigBlock
                     + 8 // config_digest: u64
+                    + 8 // handles_digest: u64
+                    + access_proof
             },
             Self::Input(input) => {
                 LEN_PREFIX
@@ -606,10 +868,14 @@ pub(crate) fn encoded_len(&self) -> usize {
                     + input.bytes.len() // bytes: Vec<u8>
             },
             Self::InputAck(_) => FRAME,            // ack_frame
-            Self::QualityReport(_) => 2 + 16,      // frame_advantage: i16, ping: u128
+            Self::QualityReport(_) => 2 + 16 + 1,  // frame_advantage: i16, ping: u128, stalled
             Self::QualityReply(_) => 16,           // pong: u128
             Self::ChecksumReport(_) => 16 + FRAME, // checksum: u128, frame
-            Self::KeepAlive => 0,
+            Self::KeepAlive(keep_alive) => {
+                FRAME // ack_frame
+                    + LEN_PREFIX
+                    + keep_alive.peer_connect_status.len() * ConnectionStatus::WIRE_LEN
+            },
             Self::FloorRequest(_) => 4, // round_seq: u32
             Self::FloorReply(reply) => {
                 4 // round_seq: u32
@@ -663,6 +929,29 @@ pub(crate) fn encoded_len(&self) -> usize {
             },
             Self::DropCommit(_) => 16 + FRAME + 8,
             Self::DropAbort(_) => 16 + 4, // operation + DropAbortReason discriminant
+            Self::MatchPause(_) => {
+                2 // proposer
+                    + 4 // sequence
+                    + 4 // MatchPauseKind discriminant
+                    + 4 // MatchPauseStage discriminant
+                    + FRAME // frame
+            },
+            Self::MatchPauseAck(_) => {
+                2 // proposer
+                    + 4 // sequence
+                    + 4 // MatchPauseStage discriminant
+                    + 1 // accepted: bool
+                    + FRAME // frame
+            },
+            Self::StreamEnd(_) => FRAME, // final_frame
+            Self::ReliableMessage(message) => {
+                4 // sequence: u32
+                    + LEN_PREFIX
+                    + message.payload.len()
+            },
+            Self::ReliableAck(_) => 4,         // next_sequence: u32
+            Self::LinkClosed(_) => FRAME,      // last_received_frame
+            Self::ChecksumRequest(_) => FRAME, // frame
         };
 
         DISCRIMINANT + payload
@@ -682,7 +971,7 @@ pub(crate) fn kind(&self) -> MessageKind {
             Self::QualityReport(_) => MessageKind::QualityReport,
             Self::QualityReply(_) => MessageKind::QualityReply,
             Self::ChecksumReport(_) => MessageKind::ChecksumReport,
-            Self::KeepAlive => MessageKind::KeepAlive,
+            Self::KeepAlive(_) => MessageKind::KeepAlive,
             Self::FloorRequest(_) => MessageKind::FloorRequest,
             Self::FloorReply(_) => MessageKind::FloorReply,
             Self::JoinRequest(_) => MessageKind::JoinRequest,
@@ -698,22 +987,98 @@ pub(crate) fn kind(&self) -> MessageKind {
             Self::DropBackfill(_) => MessageKind::DropBackfill,
             Self::DropCommit(_) => MessageKind::DropCommit,
             Self::DropAbort(_) => MessageKind::DropAbort,
+            Self::MatchPause(_) => MessageKind::MatchPause,
+            Self::MatchPauseAck(_) => MessageKind::MatchPauseAck,
+            Self::StreamEnd(_) => MessageKind::StreamEnd,
+            Self::ReliableMessage(_) => MessageKind::ReliableMessage,
+            Self::ReliableAck(_) => MessageKind::ReliableAck,
+            Self::LinkClosed(_) => MessageKind::LinkClosed,
+            Self::ChecksumRequest(_) => MessageKind::ChecksumRequest,
+        }
+    }
+
+    /// The class [`UdpProtocol::send_all_messages`] drains this body in.
+    ///
+    /// Wildcard-free like [`Self::kind`], so a new variant must pick a class.
+    ///
+    /// [`UdpProtocol::send_all_messages`]: crate::network::protocol::UdpProtocol::send_all_messages
+    pub(crate) fn send_priority(&self) -> SendPriority {
+        match self {
+            Self::SyncRequest(_) | Self::SyncReply(_) => SendPriority::Handshake,
+            Self::KeepAlive(_) | Self::QualityReport(_) | Self::QualityReply(_) => {
+                SendPriority::Liveness
+            },
+            Self::InputAck(_) => SendPriority::InputAck,
+            Self::Input(_)
+            | Self::ChecksumReport(_)
+            | Self::FloorRequest(_)
+            | Self::FloorReply(_)
+            | Self::JoinRequest(_)
+            | Self::StateSnapshot(_)
+            | Self::StateSnapshotAck(_)
+            | Self::ReactivateSlot(_)
+            | Self::ReactivateSlotAck(_)
+            | Self::JoinCommitted(_)
+            | Self::JoinAborted(_)
+            | Self::Goodbye(_)
+            | Self::DropPrepare(_)
+            | Self::DropReport(_)
+            | Self::DropBackfill(_)
+            | Self::DropCommit(_)
+            | Self::DropAbort(_)
+            | Self::MatchPause(_)
+            | Self::MatchPauseAck(_)
+            | Self::StreamEnd(_)
+            | Self::LinkClosed(_)
+            | Self::ChecksumRequest(_) => SendPriority::Ordered,
+            Self::ReliableMessage(_) | Self::ReliableAck(_) => SendPriority::Reliable,
         }
     }
 }
 
+/// Send order of a queued message, highest first.
+///
+/// Only the small, self-contained messages are promoted. Inputs and every
+/// control message whose handlers may assume they arrive after the inputs
+/// queued before them share [`Self::Ordered`] and keep their queued order.
+/// The application's reliable channel carries its own sequence numbers and
+/// goes last, so it never delays an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum SendPriority {
+    /// `SyncRequest` / `SyncReply`.
+    Handshake,
+    /// `KeepAlive`, `QualityReport`, and `QualityReply`.
+    Liveness,
+    /// `InputAck`.
+    InputAck,
+    /// Inputs and all other control messages.
+    Ordered,
+    /// `ReliableMessage` and `ReliableAck`.
+    Reliable,
+}
+
+impl SendPriority {
+    /// Every class in send order.
+    pub(crate) const ALL: [Self; 5] = [
+        Self::Handshake,
+        Self::Liveness,
+        Self::InputAck,
+        Self::Ordered,
+        Self::Reliable,
+    ];
+}
+
 impl Message {
     /// The exact number of bytes this message serializes to on the wire under the
-    /// crate's bincode configuration: the 8-byte [`MessageHeader`] plus the
-    /// [`MessageBody`] ([`MessageBody::encoded_len`]).
+    /// crate's bincode configuration: the [`MessageHeader`] (8 bytes, or 12 with
+    /// a sequence number) plus the [`MessageBody`] ([`MessageBody::encoded_len`]).
     ///
     /// This is the true payload size a [`NonBlockingSocket`](crate::NonBlockingSocket)
     /// transmits, used for bandwidth accounting. It is computed arithmetically
     /// (alloc-free) and kept wire-exact by a property test against
     /// [`codec::encode`](crate::network::codec::encode).
     pub(crate) fn encoded_len(&self) -> usize {
-        const HEADER: usize = 8;
-        HEADER + self.body.encoded_len()
+        self.header.encoded_len() + self.body.encoded_len()
     }
 
     /// The [`MessageKind`] category of this message's body.
@@ -896,8 +1261,8 @@ fn test_message_body_variants() {
         });
         assert!(matches!(floor_reply, MessageBody::FloorReply(_)));
 
-        let keep_alive = MessageBody::KeepAlive;
-        assert!(matches!(keep_alive, MessageBody::KeepAlive));
+        let keep_alive = MessageBody::KeepAlive(KeepAlive::default());
+        assert!(matches!(keep_alive, MessageBody::KeepAlive(_)));
     }
 
     #[test]
@@ -944,7 +1309,7 @@ fn test_floor_round_serialization() {
     fn test_message_clone_eq() {
         let msg = Message {
             header: MessageHeader::new(0x1234),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let cloned = msg.clone();
         assert_eq!(msg, cloned);
@@ -1051,7 +1416,10 @@ fn message_body_kind_maps_every_variant() {
                 MessageBody::ChecksumReport(ChecksumReport::default()),
                 MessageKind::ChecksumReport,
             ),
-            (MessageBody::KeepAlive, MessageKind::KeepAlive),
+            (
+                MessageBody::KeepAlive(KeepAlive::default()),
+                MessageKind::KeepAlive,
+            ),
             (
                 MessageBody::FloorRequest(FloorRequest::default()),
                 MessageKind::FloorRequest,
@@ -1106,6 +1474,34 @@ fn message_body_kind_maps_every_variant() {
                 }),
                 MessageKind::DropAbort,
             ),
+            (
+                MessageBody::MatchPause(MatchPause::default()),
+                MessageKind::MatchPause,
+            ),
+            (
+                MessageBody::MatchPauseAck(MatchPauseAck::default()),
+                MessageKind::MatchPauseAck,
+            ),
+            (
+                MessageBody::StreamEnd(StreamEnd::default()),
+                MessageKind::StreamEnd,
+            ),
+            (
+                MessageBody::ReliableMessage(ReliableMessage::default()),
+                MessageKind::ReliableMessage,
+            ),
+            (
+                MessageBody::ReliableAck(ReliableAck::default()),
+                MessageKind::ReliableAck,
+            ),
+            (
+                MessageBody::LinkClosed(LinkClosed::default()),
+                MessageKind::LinkClosed,
+            ),
+            (
+                MessageBody::ChecksumRequest(ChecksumRequest::default()),
+                MessageKind::ChecksumRequest,
+            ),
         ];
         for (body, expected) in cases {
             assert_eq!(body.kind(), *expected, "body.kind() for {body:?}");
@@ -20,12 +20,68 @@ pub struct NetworkStats {
     /// synchronization. This is offered demand, not socket-adapter acceptance or
     /// observed network throughput.
     pub kbps_sent: usize,
+    /// The number of messages queued for this peer that have not been handed
+    /// to the socket yet. Outside a poll this is nonzero only while the send
+    /// limits ([`ProtocolConfig::max_packets_per_poll`] and
+    /// [`ProtocolConfig::max_bytes_per_second`]) hold messages back.
+    ///
+    /// [`ProtocolConfig::max_packets_per_poll`]: crate::ProtocolConfig::max_packets_per_poll
+    /// [`ProtocolConfig::max_bytes_per_second`]: crate::ProtocolConfig::max_bytes_per_second
+    pub send_backlog_len: usize,
+    /// The encoded size, in bytes, of the messages counted by
+    /// [`send_backlog_len`](Self::send_backlog_len).
+    pub send_backlog_bytes: usize,
 
-    /// The number of frames Fortress Rollback calculates that the local client is behind the remote client at this instant in time.
-    /// For example, if at this instant the current game client is running frame 1002 and the remote game client is running frame 1009,
-    /// this value will mostly likely roughly equal 7.
+    /// How many frames the remote client is estimated to be ahead of the local
+    /// one: the remote client's frame minus the local client's frame.
+    ///
+    /// Positive means the local client is behind. That is an *advantage* for
+    /// the local client, since the remote one predicts its inputs further
+    /// ahead; the session slows down the client with the smaller advantage
+    /// until both meet. The remote frame is estimated from the newest input
+    /// received from it plus half the round trip, and the value is the mean
+    /// over the [`TimeSyncConfig::window_size`] most recent frames, so it
+    /// follows every frame rather than only the quality reports.
+    ///
+    /// For example, if the local client runs frame 1002 while the remote one
+    /// runs frame 1009, this is about `7` and
+    /// [`remote_frame_advantage`](Self::remote_frame_advantage) about `-7`.
+    ///
+    /// [`TimeSyncConfig::window_size`]: crate::TimeSyncConfig::window_size
+    pub local_frame_advantage: i32,
+    /// The same as [`local_frame_advantage`](Self::local_frame_advantage), but
+    /// measured by the remote client and sent in its quality reports: its
+    /// estimate of the local client's frame minus its own frame. Positive means
+    /// the remote client is behind. Averaged over the same window.
+    pub remote_frame_advantage: i32,
+    /// How long ago, in milliseconds, the older of the round-trip time and the
+    /// remote client's frame advantage report was last updated. Both refresh
+    /// through quality reports, every
+    /// [`ProtocolConfig::quality_report_interval`](crate::ProtocolConfig::quality_report_interval)
+    /// (200 ms by default); a much larger age means reports from the peer are
+    /// being lost or delayed.
+    pub stats_age_ms: u128,
+    /// The estimated offset of the remote client's clock from the local one,
+    /// in milliseconds: positive when the remote clock is ahead. `None` until
+    /// the first quality report from the remote client arrives.
+    ///
+    /// Each quality report carries the sender's timestamp, from which the
+    /// offset follows the way NTP derives it, assuming the report took half
+    /// the round trip to arrive; the estimate is averaged over recent reports.
+    /// It is only accurate to within half of [`ping`](Self::ping), and worse
+    /// when the two directions of the link have different latencies. Under an
+    /// injected [`ProtocolConfig::clock`](crate::ProtocolConfig::clock) both
+    /// clocks count from endpoint construction instead of the Unix epoch.
+    ///
+    /// Use it to align logs or telemetry from both machines after a match.
+    /// It differs between peers and over time, so it must never feed into the
+    /// simulation.
+    pub estimated_clock_offset_ms: Option<i64>,
+    /// The same as [`local_frame_advantage`](Self::local_frame_advantage).
+    #[deprecated(since = "0.12.0", note = "Use `local_frame_advantage` instead")]
     pub local_frames_behind: i32,
-    /// The same as [`local_frames_behind`](Self::local_frames_behind), but calculated from the perspective of the remote player.
+    /// The same as [`remote_frame_advantage`](Self::remote_frame_advantage).
+    #[deprecated(since = "0.12.0", note = "Use `remote_frame_advantage` instead")]
     pub remote_frames_behind: i32,
 
     // === Checksum/Desync Detection Fields ===
@@ -59,6 +115,25 @@ pub struct NetworkStats {
     /// * `None` - No comparison available yet
     ///
     pub checksums_match: Option<bool>,
+
+    // === Sequence Number Fields ===
+    /// Messages from this peer dropped because they repeated a sequence number
+    /// already received, such as datagrams duplicated by the network.
+    ///
+    /// Always `0` unless [`ProtocolConfig::sequence_numbers`] is negotiated
+    /// with the peer.
+    ///
+    /// [`ProtocolConfig::sequence_numbers`]: crate::ProtocolConfig::sequence_numbers
+    pub duplicates_dropped: u64,
+
+    /// Messages from this peer dropped because their sequence number was too
+    /// far behind the newest one received to tell whether it is a duplicate.
+    ///
+    /// Always `0` unless [`ProtocolConfig::sequence_numbers`] is negotiated
+    /// with the peer.
+    ///
+    /// [`ProtocolConfig::sequence_numbers`]: crate::ProtocolConfig::sequence_numbers
+    pub stale_dropped: u64,
 }
 
 impl NetworkStats {
@@ -71,24 +146,45 @@ pub fn new() -> Self {
 impl std::fmt::Display for NetworkStats {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Destructure to ensure all fields are included when new fields are added.
+        #[allow(deprecated)]
         let Self {
             send_queue_len,
             ping,
             kbps_sent,
-            local_frames_behind,
-            remote_frames_behind,
+            send_backlog_len,
+            send_backlog_bytes,
+            local_frame_advantage,
+            remote_frame_advantage,
+            stats_age_ms,
+            estimated_clock_offset_ms,
+            // Deprecated aliases of the two advantages.
+            local_frames_behind: _local_alias,
+            remote_frames_behind: _remote_alias,
             last_compared_frame,
             local_checksum,
             remote_checksum,
             checksums_match,
+            duplicates_dropped,
+            stale_dropped,
         } = self;
 
         write!(
             f,
-            "NetworkStats {{ ping: {}ms, queue: {}, kbps: {}, local_behind: {}, remote_behind: {}",
-            ping, send_queue_len, kbps_sent, local_frames_behind, remote_frames_behind
+            "NetworkStats {{ ping: {}ms, queue: {}, kbps: {}, backlog: {} ({} bytes), local_advantage: {}, remote_advantage: {}, age: {}ms",
+            ping,
+            send_queue_len,
+            kbps_sent,
+            send_backlog_len,
+            send_backlog_bytes,
+            local_frame_advantage,
+            remote_frame_advantage,
+            stats_age_ms
         )?;
 
+        if let Some(offset) = estimated_clock_offset_ms {
+            write!(f, ", clock_offset: {}ms", offset)?;
+        }
+
         // Include checksum fields if any checksum data is available
         if last_compared_frame.is_some()
             || local_checksum.is_some()
@@ -121,6 +217,15 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             }
         }
 
+        // Include sequence counters once any message was dropped
+        if *duplicates_dropped > 0 || *stale_dropped > 0 {
+            write!(
+                f,
+                ", duplicates_dropped: {}, stale_dropped: {}",
+                duplicates_dropped, stale_dropped
+            )?;
+        }
+
         write!(f, " }}")
     }
 }
@@ -132,6 +237,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     clippy::expect_used,
     clippy::indexing_slicing
 )]
+#[allow(deprecated)]
 mod tests {
     use super::*;
 
@@ -141,12 +247,17 @@ fn test_network_stats_default() {
         assert_eq!(stats.send_queue_len, 0);
         assert_eq!(stats.ping, 0);
         assert_eq!(stats.kbps_sent, 0);
-        assert_eq!(stats.local_frames_behind, 0);
-        assert_eq!(stats.remote_frames_behind, 0);
+        assert_eq!(stats.send_backlog_len, 0);
+        assert_eq!(stats.send_backlog_bytes, 0);
+        assert_eq!(stats.local_frame_advantage, 0);
+        assert_eq!(stats.remote_frame_advantage, 0);
+        assert_eq!(stats.stats_age_ms, 0);
         assert_eq!(stats.last_compared_frame, None);
         assert_eq!(stats.local_checksum, None);
         assert_eq!(stats.remote_checksum, None);
         assert_eq!(stats.checksums_match, None);
+        assert_eq!(stats.duplicates_dropped, 0);
+        assert_eq!(stats.stale_dropped, 0);
     }
 
     #[test]
@@ -155,12 +266,17 @@ fn test_network_stats_new() {
         assert_eq!(stats.send_queue_len, 0);
         assert_eq!(stats.ping, 0);
         assert_eq!(stats.kbps_sent, 0);
-        assert_eq!(stats.local_frames_behind, 0);
-        assert_eq!(stats.remote_frames_behind, 0);
+        assert_eq!(stats.send_backlog_len, 0);
+        assert_eq!(stats.send_backlog_bytes, 0);
+        assert_eq!(stats.local_frame_advantage, 0);
+        assert_eq!(stats.remote_frame_advantage, 0);
+        assert_eq!(stats.stats_age_ms, 0);
         assert_eq!(stats.last_compared_frame, None);
         assert_eq!(stats.local_checksum, None);
         assert_eq!(stats.remote_checksum, None);
         assert_eq!(stats.checksums_match, None);
+        assert_eq!(stats.duplicates_dropped, 0);
+        assert_eq!(stats.stale_dropped, 0);
     }
 
     #[test]
@@ -169,12 +285,20 @@ fn test_network_stats_debug() {
             send_queue_len: 5,
             ping: 100,
             kbps_sent: 50,
+            send_backlog_len: 0,
+            send_backlog_bytes: 0,
+            local_frame_advantage: 2,
+            remote_frame_advantage: -1,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: 2,
             remote_frames_behind: -1,
             last_compared_frame: None,
             local_checksum: None,
             remote_checksum: None,
             checksums_match: None,
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
         let debug = format!("{:?}", stats);
         assert!(debug.contains("NetworkStats"));
@@ -189,19 +313,27 @@ fn test_network_stats_clone() {
             send_queue_len: 10,
             ping: 50,
             kbps_sent: 100,
+            send_backlog_len: 0,
+            send_backlog_bytes: 0,
+            local_frame_advantage: 3,
+            remote_frame_advantage: -2,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: 3,
             remote_frames_behind: -2,
             last_compared_frame: Some(Frame::new(42)),
             local_checksum: Some(12345),
             remote_checksum: Some(12345),
             checksums_match: Some(true),
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
         let cloned = stats;
         assert_eq!(cloned.send_queue_len, 10);
         assert_eq!(cloned.ping, 50);
         assert_eq!(cloned.kbps_sent, 100);
-        assert_eq!(cloned.local_frames_behind, 3);
-        assert_eq!(cloned.remote_frames_behind, -2);
+        assert_eq!(cloned.local_frame_advantage, 3);
+        assert_eq!(cloned.remote_frame_advantage, -2);
         assert_eq!(cloned.last_compared_frame, Some(Frame::new(42)));
         assert_eq!(cloned.local_checksum, Some(12345));
         assert_eq!(cloned.remote_checksum, Some(12345));
@@ -209,20 +341,28 @@ fn test_network_stats_clone() {
     }
 
     #[test]
-    fn test_network_stats_negative_frames_behind() {
+    fn test_network_stats_negative_frame_advantage() {
         let stats = NetworkStats {
             send_queue_len: 0,
             ping: 0,
             kbps_sent: 0,
+            send_backlog_len: 0,
+            send_backlog_bytes: 0,
+            local_frame_advantage: -5,
+            remote_frame_advantage: 5,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: -5,
             remote_frames_behind: 5,
             last_compared_frame: None,
             local_checksum: None,
             remote_checksum: None,
             checksums_match: None,
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
-        assert_eq!(stats.local_frames_behind, -5);
-        assert_eq!(stats.remote_frames_behind, 5);
+        assert_eq!(stats.local_frame_advantage, -5);
+        assert_eq!(stats.remote_frame_advantage, 5);
     }
 
     #[test]
@@ -231,12 +371,20 @@ fn test_network_stats_checksum_fields() {
             send_queue_len: 0,
             ping: 0,
             kbps_sent: 0,
+            send_backlog_len: 0,
+            send_backlog_bytes: 0,
+            local_frame_advantage: 0,
+            remote_frame_advantage: 0,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: 0,
             remote_frames_behind: 0,
             last_compared_frame: Some(Frame::new(100)),
             local_checksum: Some(0xDEAD_BEEF),
             remote_checksum: Some(0xCAFE_BABE),
             checksums_match: Some(false),
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
         assert_eq!(stats.last_compared_frame, Some(Frame::new(100)));
         assert_eq!(stats.local_checksum, Some(0xDEAD_BEEF));
@@ -254,22 +402,56 @@ fn test_network_stats_display_without_checksum() {
             send_queue_len: 5,
             ping: 100,
             kbps_sent: 50,
+            send_backlog_len: 2,
+            send_backlog_bytes: 80,
+            local_frame_advantage: 2,
+            remote_frame_advantage: -1,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: 2,
             remote_frames_behind: -1,
             last_compared_frame: None,
             local_checksum: None,
             remote_checksum: None,
             checksums_match: None,
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
         let display = format!("{}", stats);
         assert!(display.starts_with("NetworkStats {"));
         assert!(display.contains("ping: 100ms"));
         assert!(display.contains("queue: 5"));
         assert!(display.contains("kbps: 50"));
-        assert!(display.contains("local_behind: 2"));
-        assert!(display.contains("remote_behind: -1"));
+        assert!(display.contains("backlog: 2 (80 bytes)"));
+        assert!(display.contains("local_advantage: 2"));
+        assert!(display.contains("remote_advantage: -1"));
+        assert!(display.contains("age: 0ms"));
         // Should not include checksum fields when all are None
         assert!(!display.contains("local_checksum"));
+        assert!(!display.contains("duplicates_dropped"));
+        assert!(!display.contains("clock_offset"));
+    }
+
+    #[test]
+    fn test_network_stats_display_with_clock_offset() {
+        let stats = NetworkStats {
+            estimated_clock_offset_ms: Some(-250),
+            ..NetworkStats::default()
+        };
+        let display = format!("{}", stats);
+        assert!(display.contains("age: 0ms, clock_offset: -250ms"));
+    }
+
+    #[test]
+    fn test_network_stats_display_with_dropped_messages() {
+        let stats = NetworkStats {
+            duplicates_dropped: 7,
+            stale_dropped: 1,
+            ..NetworkStats::default()
+        };
+        let display = format!("{}", stats);
+        assert!(display.contains("duplicates_dropped: 7, stale_dropped: 1"));
+        assert!(!display.contains("local_checksum"));
     }
 
     #[test]
@@ -278,12 +460,20 @@ fn test_network_stats_display_with_checksum() {
             send_queue_len: 3,
             ping: 50,
             kbps_sent: 100,
+            send_backlog_len: 0,
+            send_backlog_bytes: 0,
+            local_frame_advantage: 0,
+            remote_frame_advantage: 0,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: 0,
             remote_frames_behind: 0,
             last_compared_frame: Some(Frame::new(42)),
             local_checksum: Some(0xDEAD_BEEF_CAFE_BABE),
             remote_checksum: Some(0x1234_5678_9ABC_DEF0),
             checksums_match: Some(true),
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
         let display = format!("{}", stats);
         assert!(display.contains("ping: 50ms"));
@@ -299,12 +489,20 @@ fn test_network_stats_display_checksum_mismatch() {
             send_queue_len: 0,
             ping: 0,
             kbps_sent: 0,
+            send_backlog_len: 0,
+            send_backlog_bytes: 0,
+            local_frame_advantage: 0,
+            remote_frame_advantage: 0,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: 0,
             remote_frames_behind: 0,
             last_compared_frame: Some(Frame::new(100)),
             local_checksum: Some(0xAAAA),
             remote_checksum: Some(0xBBBB),
             checksums_match: Some(false),
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
         let display = format!("{}", stats);
         assert!(display.contains("checksums_match: false"));
@@ -317,12 +515,20 @@ fn test_network_stats_display_partial_checksum() {
             send_queue_len: 0,
             ping: 0,
             kbps_sent: 0,
+            send_backlog_len: 0,
+            send_backlog_bytes: 0,
+            local_frame_advantage: 0,
+            remote_frame_advantage: 0,
+            stats_age_ms: 0,
+            estimated_clock_offset_ms: None,
             local_frames_behind: 0,
             remote_frames_behind: 0,
             last_compared_frame: Some(Frame::new(50)),
             local_checksum: None,
             remote_checksum: None,
             checksums_match: None,
+            duplicates_dropped: 0,
+            stale_dropped: 0,
         };
         let display = format!("{}", stats);
         // Should still include checksum section because last_compared_frame is Some
@@ -0,0 +1,680 @@
+//! Packet trace capture and replay for reproducing timing-dependent bugs.
+//!
+//! [`TracingSocket`] wraps any [`NonBlockingSocket`] and records every packet
+//! it sends and receives, with the time since the trace started, into a
+//! [`PacketTrace`]. The trace serializes with [`PacketTrace::to_bytes`], so it
+//! can be captured in the field and checked into a test.
+//!
+//! The session owns its socket, so the trace is read through a
+//! [`PacketTraceHandle`] taken before the socket is handed over.
+//!
+//! [`ReplaySocket`] plays a trace back: it delivers the recorded received
+//! packets once its clock reaches their recorded times, and records what the
+//! session sends instead of sending it. With an injected clock (see
+//! [`ReplaySocket::with_clock`]) a replay runs as fast as the test can poll.
+//!
+//! A replay reproduces the original run only if the session does too: build it
+//! with the same configuration, a fixed
+//! [`ProtocolConfig::protocol_rng_seed`](crate::ProtocolConfig::protocol_rng_seed),
+//! the same clock, and the same inputs and poll schedule. Comparing the
+//! packets the replay sent ([`ReplaySocket::sent`]) with the original trace's
+//! shows where a replay diverged.
+//!
+//! # Example
+//!
+//! ```rust
+//! use fortress_rollback::{Message, NonBlockingSocket, PacketTrace, ReplaySocket, TracingSocket};
+//! use std::net::SocketAddr;
+//!
+//! # struct QuietSocket;
+//! # impl NonBlockingSocket<SocketAddr> for QuietSocket {
+//! #     fn send_to(&mut self, _msg: &Message, _addr: &SocketAddr) {}
+//! #     fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> { vec![] }
+//! # }
+//! // Capture: wrap the real socket and keep a handle before handing it over.
+//! let mut socket = TracingSocket::new(QuietSocket);
+//! let trace = socket.trace();
+//! let _ = socket.receive_all_messages();
+//! let bytes = trace.take().to_bytes()?;
+//!
+//! // Replay: feed the trace to a fresh session in a test.
+//! let trace = PacketTrace::<SocketAddr>::from_bytes(&bytes)?;
+//! let replay = ReplaySocket::new(&trace)?;
+//! assert!(replay.is_finished());
+//! # Ok::<(), fortress_rollback::network::codec::CodecError>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::Hash;
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use web_time::{Duration, Instant};
+
+use crate::network::codec::{self, CodecResult};
+use crate::network::messages::Message;
+use crate::network::MAX_RECEIVE_MESSAGES_PER_POLL;
+use crate::sessions::config::ClockFn;
+use crate::{DecodeFailureCounts, NonBlockingSocket, SocketErrorKind};
+
+/// Whether a [`PacketRecord`] was sent or received by the traced socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PacketDirection {
+    /// The session sent the packet.
+    Sent,
+    /// The socket delivered the packet to the session.
+    Received,
+}
+
+/// One packet in a [`PacketTrace`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketRecord<A> {
+    /// Whether the packet was sent or received.
+    pub direction: PacketDirection,
+    /// Time since the trace started, on the traced socket's clock.
+    pub at: Duration,
+    /// The destination of a sent packet, or the source of a received one.
+    pub addr: A,
+    /// The message, encoded with
+    /// [`encode_versioned`](crate::network::codec::encode_versioned).
+    pub bytes: Vec<u8>,
+}
+
+impl<A> PacketRecord<A> {
+    /// Decodes the recorded message.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CodecError`](crate::network::codec::CodecError) if the
+    /// bytes are malformed or were recorded by a release with another wire
+    /// format version.
+    pub fn message(&self) -> CodecResult<Message> {
+        codec::decode_versioned(&self.bytes).map(|(_version, message)| message)
+    }
+}
+
+/// The packets a [`TracingSocket`] sent and received, in the order it saw
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketTrace<A> {
+    /// Every packet, oldest first.
+    pub records: Vec<PacketRecord<A>>,
+}
+
+impl<A> Default for PacketTrace<A> {
+    fn default() -> Self {
+        Self {
+            records: Vec::new(),
+        }
+    }
+}
+
+impl<A> PacketTrace<A> {
+    /// Creates an empty trace.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The sent packets, oldest first.
+    pub fn sent(&self) -> impl Iterator<Item = &PacketRecord<A>> {
+        self.records
+            .iter()
+            .filter(|record| record.direction == PacketDirection::Sent)
+    }
+
+    /// The received packets, oldest first.
+    pub fn received(&self) -> impl Iterator<Item = &PacketRecord<A>> {
+        self.records
+            .iter()
+            .filter(|record| record.direction == PacketDirection::Received)
+    }
+}
+
+impl<A: Serialize> PacketTrace<A> {
+    /// Serializes this trace with the deterministic bincode codec.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CodecError`](crate::network::codec::CodecError) if
+    /// serialization fails.
+    pub fn to_bytes(&self) -> CodecResult<Vec<u8>> {
+        codec::encode(self)
+    }
+}
+
+impl<A: DeserializeOwned> PacketTrace<A> {
+    /// Deserializes a trace written by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CodecError`](crate::network::codec::CodecError) if the
+    /// bytes are malformed.
+    pub fn from_bytes(bytes: &[u8]) -> CodecResult<Self> {
+        codec::decode_value(bytes)
+    }
+}
+
+/// A shared view of the [`PacketTrace`] a socket is recording, which stays
+/// readable after the socket is handed to a session. Clones share the trace.
+pub struct PacketTraceHandle<A> {
+    trace: Arc<Mutex<PacketTrace<A>>>,
+}
+
+impl<A> Clone for PacketTraceHandle<A> {
+    fn clone(&self) -> Self {
+        Self {
+            trace: Arc::clone(&self.trace),
+        }
+    }
+}
+
+impl<A> PacketTraceHandle<A> {
+    fn new() -> Self {
+        Self {
+            trace: Arc::new(Mutex::new(PacketTrace::new())),
+        }
+    }
+
+    /// The number of packets recorded so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.trace.lock().records.len()
+    }
+
+    /// Whether no packet has been recorded yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the packets recorded so far and starts an empty trace. The
+    /// new trace keeps measuring time from the same start.
+    #[must_use]
+    pub fn take(&self) -> PacketTrace<A> {
+        std::mem::take(&mut *self.trace.lock())
+    }
+
+    /// Appends a record of `msg`. A message that fails to encode is left out.
+    fn record(&self, direction: PacketDirection, at: Duration, addr: &A, msg: &Message)
+    where
+        A: Clone,
+    {
+        if let Ok(bytes) = codec::encode_versioned(msg) {
+            // alloc-bound: one record per packet; the trace grows until taken.
+            self.trace.lock().records.push(PacketRecord {
+                direction,
+                at,
+                addr: addr.clone(),
+                bytes,
+            });
+        }
+    }
+}
+
+impl<A: Clone> PacketTraceHandle<A> {
+    /// Returns a copy of the packets recorded so far.
+    #[must_use]
+    pub fn snapshot(&self) -> PacketTrace<A> {
+        self.trace.lock().clone()
+    }
+}
+
+impl<A> fmt::Debug for PacketTraceHandle<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PacketTraceHandle")
+            .field("records", &self.len())
+            .finish()
+    }
+}
+
+/// The time a trace started and the clock it is measured on.
+struct TraceClock {
+    clock_fn: Option<ClockFn>,
+    started_at: Instant,
+}
+
+impl TraceClock {
+    fn new() -> Self {
+        Self {
+            clock_fn: None,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Switches to `clock_fn` and restarts the trace at its current time.
+    fn set(&mut self, clock_fn: ClockFn) {
+        self.started_at = clock_fn();
+        self.clock_fn = Some(clock_fn);
+    }
+
+    fn elapsed(&self) -> Duration {
+        let now = match &self.clock_fn {
+            Some(clock_fn) => clock_fn(),
+            None => Instant::now(),
+        };
+        now.saturating_duration_since(self.started_at)
+    }
+}
+
+/// A socket wrapper recording every packet into a [`PacketTrace`].
+///
+/// Packets pass through unchanged. Timestamps are measured from when the
+/// socket was created, or from [`with_clock`](Self::with_clock). The trace
+/// grows with every packet; read it through [`trace`](Self::trace), and take
+/// it with [`PacketTraceHandle::take`] to start a new one.
+pub struct TracingSocket<A, S> {
+    inner: S,
+    clock: TraceClock,
+    trace: PacketTraceHandle<A>,
+}
+
+impl<A, S> TracingSocket<A, S> {
+    /// Wraps `inner`, starting an empty trace.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            clock: TraceClock::new(),
+            trace: PacketTraceHandle::new(),
+        }
+    }
+
+    /// Measures timestamps on `clock_fn` instead of the system clock, and
+    /// restarts the trace's time at its current reading, like
+    /// [`ChaosSocket::with_clock`](crate::ChaosSocket::with_clock).
+    #[must_use]
+    pub fn with_clock(mut self, clock_fn: ClockFn) -> Self {
+        self.clock.set(clock_fn);
+        self
+    }
+
+    /// A handle to the trace, readable after the socket is handed to a
+    /// session.
+    #[must_use]
+    pub fn trace(&self) -> PacketTraceHandle<A> {
+        self.trace.clone()
+    }
+
+    /// Returns a reference to the inner socket.
+    #[must_use]
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner socket.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Returns the inner socket and the packets recorded so far.
+    pub fn into_parts(self) -> (S, PacketTrace<A>) {
+        let trace = self.trace.take();
+        (self.inner, trace)
+    }
+}
+
+impl<A, S> TracingSocket<A, S>
+where
+    A: Clone,
+{
+    fn record(&self, direction: PacketDirection, addr: &A, msg: &Message) {
+        self.trace
+            .record(direction, self.clock.elapsed(), addr, msg);
+    }
+}
+
+// Implementation for sync-send feature
+#[cfg(feature = "sync-send")]
+impl<A, S> NonBlockingSocket<A> for TracingSocket<A, S>
+where
+    A: Clone + PartialEq + Eq + Hash + Send + Sync,
+    S: NonBlockingSocket<A> + Send + Sync,
+{
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        // The inner socket reports its own failures.
+        let _ = self.try_send_to(msg, addr);
+    }
+
+    /// Records the packet whether or not the inner socket accepts it.
+    fn try_send_to(&mut self, msg: &Message, addr: &A) -> Result<(), SocketErrorKind> {
+        self.record(PacketDirection::Sent, addr, msg);
+        self.inner.try_send_to(msg, addr)
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        let received = self.inner.receive_all_messages();
+        for (addr, msg) in &received {
+            self.record(PacketDirection::Received, addr, msg);
+        }
+        received
+    }
+
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        self.inner.decode_failures()
+    }
+}
+
+// Implementation for non sync-send feature
+#[cfg(not(feature = "sync-send"))]
+impl<A, S> NonBlockingSocket<A> for TracingSocket<A, S>
+where
+    A: Clone + PartialEq + Eq + Hash,
+    S: NonBlockingSocket<A>,
+{
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        // The inner socket reports its own failures.
+        let _ = self.try_send_to(msg, addr);
+    }
+
+    /// Records the packet whether or not the inner socket accepts it.
+    fn try_send_to(&mut self, msg: &Message, addr: &A) -> Result<(), SocketErrorKind> {
+        self.record(PacketDirection::Sent, addr, msg);
+        self.inner.try_send_to(msg, addr)
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        let received = self.inner.receive_all_messages();
+        for (addr, msg) in &received {
+            self.record(PacketDirection::Received, addr, msg);
+        }
+        received
+    }
+
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        self.inner.decode_failures()
+    }
+}
+
+impl<A, S> fmt::Debug for TracingSocket<A, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TracingSocket")
+            .field("records", &self.trace.len())
+            .field("has_custom_clock", &self.clock.clock_fn.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A socket playing back the received packets of a [`PacketTrace`].
+///
+/// Each received packet is delivered by the first
+/// [`receive_all_messages`](NonBlockingSocket::receive_all_messages) call at
+/// or after its recorded time, measured from when the socket was created or
+/// from [`with_clock`](Self::with_clock); at most 256 per call, like the
+/// built-in sockets. Nothing is sent: sent packets are recorded instead, and
+/// can be compared with the original trace through [`sent`](Self::sent).
+pub struct ReplaySocket<A> {
+    clock: TraceClock,
+    /// Received packets not yet delivered, oldest first.
+    pending: VecDeque<(Duration, A, Message)>,
+    sent: PacketTraceHandle<A>,
+}
+
+impl<A: Clone> ReplaySocket<A> {
+    /// Creates a socket playing back the received packets of `trace`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CodecError`](crate::network::codec::CodecError) if a
+    /// received packet fails to decode, for example because it was recorded
+    /// by a release with another wire format version.
+    pub fn new(trace: &PacketTrace<A>) -> CodecResult<Self> {
+        let mut pending = VecDeque::new();
+        for record in trace.received() {
+            // alloc-bound: one entry per record of a trace the caller holds.
+            pending.push_back((record.at, record.addr.clone(), record.message()?));
+        }
+        Ok(Self {
+            clock: TraceClock::new(),
+            pending,
+            sent: PacketTraceHandle::new(),
+        })
+    }
+
+    /// Measures the recorded times on `clock_fn` instead of the system clock,
+    /// starting at its current reading, like
+    /// [`ChaosSocket::with_clock`](crate::ChaosSocket::with_clock).
+    #[must_use]
+    pub fn with_clock(mut self, clock_fn: ClockFn) -> Self {
+        self.clock.set(clock_fn);
+        self
+    }
+
+    /// A handle to the packets the session sent, timed like a
+    /// [`TracingSocket`] would, readable after the socket is handed to a
+    /// session.
+    #[must_use]
+    pub fn sent(&self) -> PacketTraceHandle<A> {
+        self.sent.clone()
+    }
+
+    /// Whether every received packet of the trace has been delivered.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    fn record_send(&self, msg: &Message, addr: &A) {
+        self.sent
+            .record(PacketDirection::Sent, self.clock.elapsed(), addr, msg);
+    }
+
+    fn deliver_due(&mut self) -> Vec<(A, Message)> {
+        let now = self.clock.elapsed();
+        let mut delivered = Vec::new();
+        while delivered.len() < MAX_RECEIVE_MESSAGES_PER_POLL {
+            match self.pending.front() {
+                Some((at, ..)) if *at <= now => {},
+                _ => break,
+            }
+            if let Some((_, addr, msg)) = self.pending.pop_front() {
+                // alloc-bound: at most MAX_RECEIVE_MESSAGES_PER_POLL per call.
+                delivered.push((addr, msg));
+            }
+        }
+        delivered
+    }
+}
+
+// Implementation for sync-send feature
+#[cfg(feature = "sync-send")]
+impl<A> NonBlockingSocket<A> for ReplaySocket<A>
+where
+    A: Clone + PartialEq + Eq + Hash + Send + Sync,
+{
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        self.record_send(msg, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        self.deliver_due()
+    }
+}
+
+// Implementation for non sync-send feature
+#[cfg(not(feature = "sync-send"))]
+impl<A> NonBlockingSocket<A> for ReplaySocket<A>
+where
+    A: Clone + PartialEq + Eq + Hash,
+{
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        self.record_send(msg, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        self.deliver_due()
+    }
+}
+
+impl<A> fmt::Debug for ReplaySocket<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplaySocket")
+            .field("pending", &self.pending.len())
+            .field("sent", &self.sent.len())
+            .field("has_custom_clock", &self.clock.clock_fn.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+    use crate::network::messages::{KeepAlive, MessageBody, MessageHeader};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    /// A socket that delivers what it is given and drops what is sent.
+    #[derive(Default)]
+    struct ScriptedSocket {
+        inbox: Vec<(u32, Message)>,
+        sent: usize,
+    }
+
+    impl NonBlockingSocket<u32> for ScriptedSocket {
+        fn send_to(&mut self, _msg: &Message, _addr: &u32) {
+            self.sent += 1;
+        }
+
+        fn receive_all_messages(&mut self) -> Vec<(u32, Message)> {
+            std::mem::take(&mut self.inbox)
+        }
+    }
+
+    fn keep_alive(conn_id: u32) -> Message {
+        Message {
+            header: MessageHeader::new(conn_id),
+            body: MessageBody::KeepAlive(KeepAlive::default()),
+        }
+    }
+
+    /// A clock advanced by hand, in milliseconds.
+    fn manual_clock() -> (ClockFn, Arc<AtomicU64>) {
+        let base = Instant::now();
+        let offset = Arc::new(AtomicU64::new(0));
+        let reader = Arc::clone(&offset);
+        let clock: ClockFn =
+            Arc::new(move || base + Duration::from_millis(reader.load(Ordering::Relaxed)));
+        (clock, offset)
+    }
+
+    #[test]
+    fn tracing_socket_records_both_directions_with_timestamps() {
+        let (clock, offset) = manual_clock();
+        let mut socket = TracingSocket::new(ScriptedSocket::default()).with_clock(clock);
+        let handle = socket.trace();
+        socket.send_to(&keep_alive(1), &7);
+        offset.store(16, Ordering::Relaxed);
+        socket.inner_mut().inbox.push((7, keep_alive(2)));
+        assert_eq!(socket.receive_all_messages().len(), 1);
+
+        assert_eq!(handle.len(), 2);
+        assert_eq!(handle.snapshot(), socket.trace().snapshot());
+        let trace = handle.take();
+        assert_eq!(socket.inner().sent, 1);
+        assert!(socket.trace().is_empty());
+        let directions: Vec<_> = trace
+            .records
+            .iter()
+            .map(|record| (record.direction, record.at, record.addr))
+            .collect();
+        assert_eq!(
+            directions,
+            vec![
+                (PacketDirection::Sent, Duration::ZERO, 7),
+                (PacketDirection::Received, Duration::from_millis(16), 7),
+            ]
+        );
+        assert_eq!(trace.records[1].message().unwrap(), keep_alive(2));
+        assert_eq!(trace.sent().count(), 1);
+        assert_eq!(trace.received().count(), 1);
+    }
+
+    #[test]
+    fn trace_round_trips_through_bytes() {
+        let mut socket = TracingSocket::new(ScriptedSocket::default());
+        socket.send_to(&keep_alive(1), &3);
+        socket.inner_mut().inbox.push((4, keep_alive(2)));
+        let _ = socket.receive_all_messages();
+        let (_inner, trace) = socket.into_parts();
+
+        let bytes = trace.to_bytes().unwrap();
+        assert_eq!(PacketTrace::<u32>::from_bytes(&bytes).unwrap(), trace);
+    }
+
+    #[test]
+    fn replay_socket_delivers_received_packets_at_their_recorded_times() {
+        let recorder = PacketTraceHandle::new();
+        for (direction, at, conn_id) in [
+            (PacketDirection::Received, 0, 1),
+            (PacketDirection::Sent, 5, 2),
+            (PacketDirection::Received, 20, 3),
+        ] {
+            recorder.record(
+                direction,
+                Duration::from_millis(at),
+                &9,
+                &keep_alive(conn_id),
+            );
+        }
+        let trace = recorder.take();
+        let (clock, offset) = manual_clock();
+        let mut socket = ReplaySocket::new(&trace).unwrap().with_clock(clock);
+
+        assert_eq!(socket.receive_all_messages(), vec![(9, keep_alive(1))]);
+        offset.store(19, Ordering::Relaxed);
+        assert!(socket.receive_all_messages().is_empty());
+        assert!(!socket.is_finished());
+        offset.store(20, Ordering::Relaxed);
+        assert_eq!(socket.receive_all_messages(), vec![(9, keep_alive(3))]);
+        assert!(socket.is_finished());
+
+        socket.send_to(&keep_alive(4), &9);
+        let sent: Vec<_> = socket
+            .sent()
+            .snapshot()
+            .sent()
+            .map(|record| record.at)
+            .collect();
+        assert_eq!(sent, vec![Duration::from_millis(20)]);
+    }
+
+    #[test]
+    fn replay_socket_caps_each_poll() {
+        let recorder = PacketTraceHandle::new();
+        for _ in 0..MAX_RECEIVE_MESSAGES_PER_POLL + 1 {
+            recorder.record(
+                PacketDirection::Received,
+                Duration::ZERO,
+                &1,
+                &keep_alive(1),
+            );
+        }
+        let mut socket = ReplaySocket::new(&recorder.take()).unwrap();
+        assert_eq!(
+            socket.receive_all_messages().len(),
+            MAX_RECEIVE_MESSAGES_PER_POLL
+        );
+        assert_eq!(socket.receive_all_messages().len(), 1);
+    }
+
+    #[test]
+    fn replay_socket_rejects_undecodable_records() {
+        let trace = PacketTrace {
+            records: vec![PacketRecord {
+                direction: PacketDirection::Received,
+                at: Duration::ZERO,
+                addr: 1_u32,
+                bytes: vec![0xFF],
+            }],
+        };
+        assert!(ReplaySocket::new(&trace).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+//! Spectator access proofs.
+//!
+//! A spectator configured with
+//! [`SessionBuilder::with_spectator_token`](crate::SessionBuilder::with_spectator_token)
+//! never sends its token. Each `SyncRequest` instead carries a SipHash-2-4 MAC,
+//! keyed by the token, of the request's random nonce. A host that set
+//! [`SpectatorConfig::access_token`](crate::SpectatorConfig::access_token)
+//! recomputes it and ignores the request on a mismatch. SipHash is a keyed
+//! pseudorandom function built for exactly this short-input case, so a peer
+//! without the token cannot forge a proof for a nonce of its choosing.
+
+/// Domain separator mixed into every proof, ahead of the nonce.
+const ACCESS_PROOF_DOMAIN: &[u8; 8] = b"FRv11-sp";
+
+/// The `access_proof` of a `SyncRequest` carrying `random_request`, proved
+/// with `token`.
+pub(super) fn access_proof(token: &[u8; 16], random_request: u32) -> u64 {
+    let mut message = [0; 12];
+    message[..8].copy_from_slice(ACCESS_PROOF_DOMAIN);
+    message[8..].copy_from_slice(&random_request.to_le_bytes());
+    sip_hash_2_4(token, &message)
+}
+
+/// SipHash-2-4 of `message` under `key`.
+fn sip_hash_2_4(key: &[u8; 16], message: &[u8]) -> u64 {
+    let mut k0 = [0; 8];
+    let mut k1 = [0; 8];
+    k0.copy_from_slice(&key[..8]);
+    k1.copy_from_slice(&key[8..]);
+    let k0 = u64::from_le_bytes(k0);
+    let k1 = u64::from_le_bytes(k1);
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+
+    let mut words = message.chunks_exact(8);
+    for word in &mut words {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(word);
+        compress(&mut v, u64::from_le_bytes(bytes));
+    }
+    // The last word holds the remaining bytes and the message length.
+    let mut last = [0; 8];
+    for (byte, rest) in last.iter_mut().zip(words.remainder()) {
+        *byte = *rest;
+    }
+    last[7] = message.len() as u8;
+    compress(&mut v, u64::from_le_bytes(last));
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v);
+    }
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+fn compress(v: &mut [u64; 4], word: u64) {
+    v[3] ^= word;
+    sip_round(v);
+    sip_round(v);
+    v[0] ^= word;
+}
+
+fn sip_round(v: &mut [u64; 4]) {
+    let [v0, v1, v2, v3] = v;
+    *v0 = v0.wrapping_add(*v1);
+    *v1 = v1.rotate_left(13) ^ *v0;
+    *v0 = v0.rotate_left(32);
+    *v2 = v2.wrapping_add(*v3);
+    *v3 = v3.rotate_left(16) ^ *v2;
+    *v0 = v0.wrapping_add(*v3);
+    *v3 = v3.rotate_left(21) ^ *v0;
+    *v2 = v2.wrapping_add(*v1);
+    *v1 = v1.rotate_left(17) ^ *v2;
+    *v2 = v2.rotate_left(32);
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    /// The key of the SipHash reference test vectors: bytes `0x00..=0x0f`.
+    fn reference_key() -> [u8; 16] {
+        std::array::from_fn(|i| i as u8)
+    }
+
+    #[test]
+    fn sip_hash_matches_the_reference_vectors() {
+        let key = reference_key();
+        let message: Vec<u8> = (0..64).collect();
+        assert_eq!(sip_hash_2_4(&key, &message[..0]), 0x726f_db47_dd0e_0e31);
+        assert_eq!(sip_hash_2_4(&key, &message[..8]), 0x93f5_f579_9a93_2462);
+        assert_eq!(sip_hash_2_4(&key, &message[..15]), 0xa129_ca61_49be_45e5);
+        assert_eq!(sip_hash_2_4(&key, &message[..63]), 0x958a_324c_eb06_4572);
+    }
+
+    #[test]
+    fn proof_depends_on_the_token_and_the_nonce() {
+        let token = reference_key();
+        let mut other_token = token;
+        other_token[15] ^= 1;
+        let proof = access_proof(&token, 7);
+        assert_eq!(proof, access_proof(&token, 7));
+        assert_ne!(proof, access_proof(&token, 8));
+        assert_ne!(proof, access_proof(&other_token, 7));
+    }
+}
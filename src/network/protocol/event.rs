@@ -2,9 +2,12 @@
 //!
 //! This module contains the event types emitted by the UDP protocol layer.
 
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
 use crate::frame_info::PlayerInput;
 use crate::network::messages::ConnectionStatus;
-use crate::{Config, IncompatibleSessionReason, PlayerHandle};
+use crate::{Config, Frame, IncompatibleSessionReason, PlayerHandle};
 
 /// Events generated by the UDP protocol layer.
 ///
@@ -37,8 +40,9 @@ pub enum Event<T>
     Input {
         input: PlayerInput<T::Input>,
         player: PlayerHandle,
-        /// Connection status snapshot from the packet that carried this input.
-        peer_connect_status: Vec<ConnectionStatus>,
+        /// Connection status snapshot from the packet that carried this input,
+        /// shared by every input of that packet.
+        peer_connect_status: Arc<[ConnectionStatus]>,
     },
     /// The remote client has disconnected.
     Disconnected,
@@ -46,6 +50,12 @@ pub enum Event<T>
     NetworkInterrupted { disconnect_timeout: u128 },
     /// Sent only after a `NetworkInterrupted` event, if communication has resumed.
     NetworkResumed,
+    /// The remote client reports that it keeps polling but stopped advancing
+    /// frames.
+    AdvanceStalled,
+    /// Sent only after an `AdvanceStalled` event, once the remote client
+    /// advances again.
+    AdvanceResumed,
     /// Synchronization has timed out. This is only emitted if a sync timeout was configured.
     /// The session will continue trying to sync, but the user may choose to abort.
     SyncTimeout {
@@ -57,6 +67,40 @@ pub enum Event<T>
         /// The first mismatching field in stable protocol order.
         reason: IncompatibleSessionReason,
     },
+    /// The host announced the end of its spectator stream.
+    StreamEnded {
+        /// The last frame whose inputs the host forwards.
+        final_frame: Frame,
+    },
+    /// The remote client tagged a frame. Emitted once per tag.
+    FrameTag {
+        /// The tagged frame.
+        frame: Frame,
+        /// The tag's payload.
+        data: [u8; 8],
+    },
+    /// The remote client sent a message on the reliable channel. Emitted once
+    /// per message, in the order they were sent.
+    ReliableMessage {
+        /// The message's payload.
+        payload: Vec<u8>,
+    },
+    /// The socket refused this many sends in a row. Emitted once per run of
+    /// failures, when it reaches `ProtocolConfig::transport_error_threshold`.
+    TransportError {
+        /// Failed sends in a row so far.
+        consecutive_failures: u32,
+    },
+}
+
+/// Source of [`next_event_stamp`]; shared by every endpoint in the process.
+static NEXT_EVENT_STAMP: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the next stamp in the process-wide order protocol events are
+/// generated in. Endpoints stamp each event as they queue it, so a session
+/// polling several endpoints can hand their events on in that order.
+pub(crate) fn next_event_stamp() -> u64 {
+    NEXT_EVENT_STAMP.fetch_add(1, Ordering::Relaxed)
 }
 
 impl<T: Config> std::fmt::Display for Event<T> {
@@ -86,10 +130,31 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 write!(f, "NetworkInterrupted(timeout={}ms)", disconnect_timeout)
             },
             Self::NetworkResumed => write!(f, "NetworkResumed"),
+            Self::AdvanceStalled => write!(f, "AdvanceStalled"),
+            Self::AdvanceResumed => write!(f, "AdvanceResumed"),
             Self::SyncTimeout { elapsed_ms } => {
                 write!(f, "SyncTimeout(elapsed={}ms)", elapsed_ms)
             },
             Self::Incompatible { reason } => write!(f, "Incompatible(reason={reason})"),
+            Self::StreamEnded { final_frame } => {
+                write!(f, "StreamEnded(final_frame={})", final_frame.as_i32())
+            },
+            Self::FrameTag { frame, data } => write!(
+                f,
+                "FrameTag(frame={}, data=0x{:016x})",
+                frame.as_i32(),
+                u64::from_be_bytes(*data)
+            ),
+            Self::ReliableMessage { payload } => {
+                write!(f, "ReliableMessage(len={})", payload.len())
+            },
+            Self::TransportError {
+                consecutive_failures,
+            } => write!(
+                f,
+                "TransportError(consecutive_failures={})",
+                consecutive_failures
+            ),
         }
     }
 }
@@ -166,7 +231,7 @@ fn event_input_construction() {
         let event: Event<TestConfig> = Event::Input {
             input,
             player,
-            peer_connect_status: Vec::new(),
+            peer_connect_status: Arc::from([]),
         };
 
         match event {
@@ -249,7 +314,7 @@ fn event_clone_input() {
         let event: Event<TestConfig> = Event::Input {
             input,
             player,
-            peer_connect_status: Vec::new(),
+            peer_connect_status: Arc::from([]),
         };
         let cloned = event.clone();
         assert_eq!(event, cloned);
@@ -405,7 +470,7 @@ fn event_display_input() {
         let event: Event<TestConfig> = Event::Input {
             input,
             player,
-            peer_connect_status: Vec::new(),
+            peer_connect_status: Arc::from([]),
         };
         let display = format!("{}", event);
         assert_eq!(display, "Input(player=PlayerHandle(1), frame=42)");
@@ -434,6 +499,14 @@ fn event_display_network_resumed() {
         assert_eq!(display, "NetworkResumed");
     }
 
+    #[test]
+    fn event_display_advance_stalled_and_resumed() {
+        let stalled: Event<TestConfig> = Event::AdvanceStalled;
+        let resumed: Event<TestConfig> = Event::AdvanceResumed;
+        assert_eq!(format!("{}", stalled), "AdvanceStalled");
+        assert_eq!(format!("{}", resumed), "AdvanceResumed");
+    }
+
     #[test]
     fn event_display_sync_timeout() {
         let event: Event<TestConfig> = Event::SyncTimeout { elapsed_ms: 10000 };
@@ -441,6 +514,45 @@ fn event_display_sync_timeout() {
         assert_eq!(display, "SyncTimeout(elapsed=10000ms)");
     }
 
+    #[test]
+    fn event_display_transport_error() {
+        let event: Event<TestConfig> = Event::TransportError {
+            consecutive_failures: 10,
+        };
+        assert_eq!(
+            format!("{}", event),
+            "TransportError(consecutive_failures=10)"
+        );
+    }
+
+    #[test]
+    fn event_display_stream_ended() {
+        let event: Event<TestConfig> = Event::StreamEnded {
+            final_frame: Frame::new(599),
+        };
+        assert_eq!(format!("{}", event), "StreamEnded(final_frame=599)");
+    }
+
+    #[test]
+    fn event_display_frame_tag() {
+        let event: Event<TestConfig> = Event::FrameTag {
+            frame: Frame::new(42),
+            data: [0xde, 0xad, 0xbe, 0xef, 0, 0, 0, 1],
+        };
+        assert_eq!(
+            format!("{}", event),
+            "FrameTag(frame=42, data=0xdeadbeef00000001)"
+        );
+    }
+
+    #[test]
+    fn event_display_reliable_message() {
+        let event: Event<TestConfig> = Event::ReliableMessage {
+            payload: b"ready".to_vec(),
+        };
+        assert_eq!(format!("{}", event), "ReliableMessage(len=5)");
+    }
+
     // ==========================================================================
     // Edge Case Tests
     // ==========================================================================
@@ -520,7 +632,7 @@ fn event_input_with_null_frame() {
         let event: Event<TestConfig> = Event::Input {
             input,
             player,
-            peer_connect_status: Vec::new(),
+            peer_connect_status: Arc::from([]),
         };
 
         match event {
@@ -794,7 +906,7 @@ fn proof_all_variants_distinct() {
         let input: Event<TestConfig> = Event::Input {
             input: PlayerInput::new(Frame::new(0), TestInput { value: 0 }),
             player: PlayerHandle::new(0),
-            peer_connect_status: Vec::new(),
+            peer_connect_status: Arc::from([]),
         };
         let disconnected: Event<TestConfig> = Event::Disconnected;
         let interrupted: Event<TestConfig> = Event::NetworkInterrupted {
@@ -940,7 +1052,7 @@ fn proof_input_event_preserves_data() {
         let event: Event<TestConfig> = Event::Input {
             input,
             player,
-            peer_connect_status: Vec::new(),
+            peer_connect_status: Arc::from([]),
         };
 
         match event {
@@ -983,7 +1095,7 @@ fn proof_input_event_clone() {
         let event: Event<TestConfig> = Event::Input {
             input,
             player,
-            peer_connect_status: Vec::new(),
+            peer_connect_status: Arc::from([]),
         };
         let cloned = event.clone();
 
@@ -3,12 +3,11 @@
 //! This module contains the internal `InputBytes` type used for serializing
 //! and deserializing player inputs for network transmission.
 
-use std::collections::BTreeMap;
-
 use crate::frame_info::PlayerInput;
 use crate::network::codec;
 use crate::report_violation;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
+use crate::vec_map::VecMap;
 use crate::{
     Config, FortressError, Frame, InternalErrorKind, PlayerHandle, SerializationErrorKind,
 };
@@ -101,35 +100,20 @@ fn player_byte_range(
         Ok(start..end)
     }
 
-    /// Creates a zeroed InputBytes for the given number of players.
+    /// Creates the blank InputBytes for the given number of players: every
+    /// player's slot holds the serialized `Config::Input::default()`.
+    ///
+    /// This is the reference both sides of a link start their input delta
+    /// chain from, so it must be derived the same way everywhere (see
+    /// [`blank_frame_bytes`](Self::blank_frame_bytes)). It is all zero bytes
+    /// only when the default input serializes to zeros.
     ///
     /// # Returns
     /// Returns `None` if serialization of the default Input type fails, which indicates
     /// a fundamental issue with the Config::Input type's serialization implementation.
     pub fn zeroed<T: Config>(num_players: usize) -> Option<Self> {
-        // Measure once to get the size of the default input without allocating
-        // an intermediate serialized buffer.
-        match codec::encoded_len(&T::Input::default()) {
-            Ok(input_size) => {
-                // saturating_mul matches the sibling `from_inputs` and avoids an
-                // overflow panic under release `overflow-checks`.
-                let size = input_size.saturating_mul(num_players);
-                let mut bytes = Vec::new();
-                if bytes.try_reserve_exact(size).is_err() {
-                    report_violation!(
-                        ViolationSeverity::Error,
-                        ViolationKind::NetworkProtocol,
-                        "Failed to reserve {} bytes for zeroed input buffer",
-                        size
-                    );
-                    return None;
-                }
-                bytes.extend(std::iter::repeat_n(0, size));
-                Some(Self {
-                    frame: Frame::NULL,
-                    bytes,
-                })
-            },
+        let input_size = match codec::encoded_len(&T::Input::default()) {
+            Ok(input_size) => input_size,
             Err(e) => {
                 report_violation!(
                     ViolationSeverity::Critical,
@@ -137,16 +121,67 @@ pub fn zeroed<T: Config>(num_players: usize) -> Option<Self> {
                     "Failed to serialize default input type: {}",
                     e
                 );
+                return None;
+            },
+        };
+        // saturating_mul matches the sibling `from_inputs` and avoids an
+        // overflow panic under release `overflow-checks`.
+        let size = input_size.saturating_mul(num_players);
+        match Self::blank_frame_bytes::<T>(size) {
+            Ok(bytes) => Some(Self {
+                frame: Frame::NULL,
+                bytes,
+            }),
+            Err(e) => {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::NetworkProtocol,
+                    "Failed to build {} blank input bytes: {}",
+                    size,
+                    e
+                );
                 None
             },
         }
     }
 
+    /// `len` bytes of back-to-back serialized `Config::Input::default()`
+    /// values: the blank frame for `len / input_size` players.
+    pub fn blank_frame_bytes<T: Config>(len: usize) -> Result<Vec<u8>, FortressError> {
+        let default = codec::encode(&T::Input::default()).map_err(|err| {
+            report_violation!(
+                ViolationSeverity::Critical,
+                ViolationKind::InternalError,
+                "Failed to serialize default input type: {}",
+                err
+            );
+            SerializationErrorKind::EndpointCreationFailed
+        })?;
+        let mut bytes = Vec::new();
+        bytes
+            .try_reserve_exact(len)
+            .map_err(|_err| crate::error::allocation_failed("input_bytes.blank_frame", len))?;
+        if !default.is_empty() {
+            bytes.extend(default.iter().copied().cycle().take(len));
+        }
+        Ok(bytes)
+    }
+
     /// Creates an InputBytes from the given inputs, rejecting per-player values
     /// whose serialized length differs from `Config::Input::default()`.
     pub fn try_from_inputs<T: Config>(
         num_players: usize,
-        inputs: &BTreeMap<PlayerHandle, PlayerInput<T::Input>>,
+        inputs: &VecMap<PlayerHandle, PlayerInput<T::Input>>,
+    ) -> Result<Self, FortressError> {
+        Self::try_from_inputs_into::<T>(num_players, inputs, Vec::new())
+    }
+
+    /// [`try_from_inputs`](Self::try_from_inputs), serializing into `bytes`
+    /// (cleared first) so a recycled buffer's capacity is reused.
+    pub fn try_from_inputs_into<T: Config>(
+        num_players: usize,
+        inputs: &VecMap<PlayerHandle, PlayerInput<T::Input>>,
+        mut bytes: Vec<u8>,
     ) -> Result<Self, FortressError> {
         let input_size = codec::encoded_len(&T::Input::default()).map_err(|err| {
             report_violation!(
@@ -173,7 +208,7 @@ pub fn try_from_inputs<T: Config>(
                 },
             }
         })?;
-        let mut bytes = Vec::new();
+        bytes.clear();
         bytes.try_reserve_exact(estimated_size).map_err(|_err| {
             crate::error::allocation_failed("input_bytes.from_inputs", estimated_size)
         })?;
@@ -251,7 +286,7 @@ pub fn try_from_inputs<T: Config>(
     #[cfg(test)]
     pub fn from_inputs<T: Config>(
         num_players: usize,
-        inputs: &BTreeMap<PlayerHandle, PlayerInput<T::Input>>,
+        inputs: &VecMap<PlayerHandle, PlayerInput<T::Input>>,
     ) -> Self {
         match Self::try_from_inputs::<T>(num_players, inputs) {
             Ok(input_bytes) => input_bytes,
@@ -272,13 +307,26 @@ pub fn from_inputs<T: Config>(
 
     /// Converts InputBytes to a vector of PlayerInput, rejecting malformed data
     /// without returning partial results.
+    #[cfg(test)]
     pub fn try_to_player_inputs_exact<T: Config>(
         &self,
         num_players: usize,
     ) -> Result<Vec<PlayerInput<T::Input>>, InputBytesDecodeError> {
+        let mut player_inputs = Vec::new();
+        self.try_append_player_inputs::<T>(num_players, &mut player_inputs)?;
+        Ok(player_inputs)
+    }
+
+    /// Decodes InputBytes into one PlayerInput per player, rejecting malformed
+    /// data, and appends them to `player_inputs`. On error some of this
+    /// frame's inputs may already have been appended.
+    pub fn try_append_player_inputs<T: Config>(
+        &self,
+        num_players: usize,
+        player_inputs: &mut Vec<PlayerInput<T::Input>>,
+    ) -> Result<(), InputBytesDecodeError> {
         let size = Self::player_input_byte_partition_size(self.bytes.len(), num_players)?;
 
-        let mut player_inputs = Vec::new();
         if player_inputs.try_reserve(num_players).is_err() {
             return Err(InputBytesDecodeError::AllocationFailed {
                 requested_players: num_players,
@@ -313,7 +361,7 @@ pub fn try_to_player_inputs_exact<T: Config>(
                 },
             }
         }
-        Ok(player_inputs)
+        Ok(())
     }
 
     /// Converts InputBytes to a vector of PlayerInput.
@@ -516,6 +564,26 @@ fn zeroed_with_zero_players_creates_empty_bytes() {
         assert!(input_bytes.bytes.is_empty());
     }
 
+    #[test]
+    fn zeroed_repeats_the_encoded_default_input() {
+        let default = codec::encode(&BalancedVariableInput::default()).unwrap();
+        assert!(
+            default.iter().any(|&b| b != 0),
+            "default must not encode to zeros"
+        );
+
+        let input_bytes = InputBytes::zeroed::<BalancedVariableInputConfig>(3).unwrap();
+        assert_eq!(input_bytes.bytes.len(), default.len() * 3);
+        for chunk in input_bytes.bytes.chunks(default.len()) {
+            assert_eq!(chunk, default.as_slice());
+        }
+        assert_eq!(
+            InputBytes::blank_frame_bytes::<BalancedVariableInputConfig>(default.len() * 3)
+                .unwrap(),
+            input_bytes.bytes
+        );
+    }
+
     // ==========================================
     // from_inputs Tests
     // ==========================================
@@ -523,7 +591,7 @@ fn zeroed_with_zero_players_creates_empty_bytes() {
     #[test]
     fn from_inputs_creates_correct_bytes() {
         let frame = Frame::new(42);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(frame, TestInput { inp: 12345 }),
@@ -537,7 +605,7 @@ fn from_inputs_creates_correct_bytes() {
     #[test]
     fn from_inputs_multiple_players() {
         let frame = Frame::new(10);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(frame, TestInput { inp: 100 }),
@@ -554,7 +622,7 @@ fn from_inputs_multiple_players() {
 
     #[test]
     fn from_inputs_empty_map_creates_empty_bytes() {
-        let inputs = BTreeMap::new();
+        let inputs = VecMap::new();
         let input_bytes = InputBytes::from_inputs::<TestConfig>(0, &inputs);
         assert_eq!(input_bytes.frame, Frame::NULL);
         assert!(input_bytes.bytes.is_empty());
@@ -564,7 +632,7 @@ fn from_inputs_empty_map_creates_empty_bytes() {
     fn from_inputs_uses_first_non_null_frame() {
         let frame1 = Frame::NULL;
         let frame2 = Frame::new(100);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(frame1, TestInput { inp: 1 }),
@@ -583,7 +651,7 @@ fn from_inputs_uses_first_non_null_frame() {
     fn from_inputs_partial_players() {
         // Only player 0 has input, but we have 2 players
         let frame = Frame::new(50);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(frame, TestInput { inp: 42 }),
@@ -605,7 +673,7 @@ fn try_from_inputs_rejects_variable_width_per_player_input() {
             "test fixture must keep the aggregate width balanced"
         );
 
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(Frame::new(50), BalancedVariableInput::Short),
@@ -635,7 +703,7 @@ fn try_from_inputs_rejects_variable_width_per_player_input() {
     #[test]
     fn to_player_inputs_roundtrip_single_player() {
         let frame = Frame::new(99);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(frame, TestInput { inp: 0xDEAD_BEEF }),
@@ -652,7 +720,7 @@ fn to_player_inputs_roundtrip_single_player() {
     #[test]
     fn to_player_inputs_roundtrip_multiple_players() {
         let frame = Frame::new(50);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(frame, TestInput { inp: 111 }),
@@ -838,7 +906,7 @@ fn large_player_count() {
     #[test]
     fn max_frame_value() {
         let max_frame = Frame::new(i32::MAX);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(max_frame, TestInput { inp: 42 }),
@@ -853,7 +921,7 @@ fn max_frame_value() {
     #[test]
     fn max_input_value() {
         let frame = Frame::new(1);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(frame, TestInput { inp: u32::MAX }),
@@ -868,7 +936,7 @@ fn max_input_value() {
     #[test]
     fn preserves_player_order() {
         let frame = Frame::new(5);
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         // Insert in reverse order to verify ordering is maintained
         inputs.insert(
             PlayerHandle::new(3),
@@ -931,7 +999,7 @@ fn complex_input_roundtrip() {
             flags: 0xFF,
         };
 
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(PlayerHandle::new(0), PlayerInput::new(frame, complex_input));
 
         let input_bytes = InputBytes::from_inputs::<ComplexConfig>(1, &inputs);
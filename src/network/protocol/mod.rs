@@ -3,12 +3,16 @@
 //! This module contains the UDP protocol handler for managing network communication
 //! between peers in a rollback networking session.
 
+mod access_proof;
 mod event;
 #[cfg(feature = "trace-validation")]
 mod handshake_trace;
 mod input_bytes;
+mod sequence_window;
 mod state;
 
+use access_proof::access_proof;
+use event::next_event_stamp;
 pub use event::Event;
 #[cfg(feature = "trace-validation")]
 use handshake_trace::HandshakeTraceRecorder;
@@ -18,18 +22,27 @@
     HandshakeTraceAction, HandshakeTraceConfig, HandshakeTraceEvent, HandshakeTraceOverflow,
 };
 use input_bytes::{log_input_decode_error, InputBytes};
+use sequence_window::{SequenceVerdict, SequenceWindow};
 pub use state::ProtocolState;
 
 use crate::error::{allocation_failed, SerializationErrorKind};
 use crate::frame_info::PlayerInput;
 use crate::hash::DeterministicHasher;
-use crate::metrics::{MessageKindCounts, PeerMetrics};
+use crate::metrics::{
+    CompressionStats, InputRejection, InputRejectionCounts, MemoryReport, MemoryUsage,
+    MessageKindCounts, PeerMetrics,
+};
 use crate::network::codec;
-use crate::network::compression::{decode_with_max_len, try_encode};
+use crate::network::compression::{
+    decode_into, keyframe_decode_into, keyframe_frame_len, try_encode_into,
+    try_keyframe_encode_into,
+};
 use crate::network::messages::{
-    ChecksumReport, ConnectionStatus, DropAbort, DropBackfill, DropCommit, DropPrepare, DropReport,
-    FloorReply, FloorRequest, Goodbye, Input, InputAck, Message, MessageBody, MessageHeader,
-    QualityReply, QualityReport, SessionConfigBlock, SyncReply, SyncRequest,
+    ChecksumReport, ChecksumRequest, ConnectionStatus, DropAbort, DropBackfill, DropCommit,
+    DropPrepare, DropReport, FloorReply, FloorRequest, Goodbye, Input, InputAck, KeepAlive,
+    LinkClosed, MatchPause, MatchPauseAck, Message, MessageBody, MessageHeader, QualityReply,
+    QualityReport, ReliableAck, ReliableMessage, SendPriority, SessionConfigBlock, StreamEnd,
+    SyncReply, SyncRequest,
 };
 #[cfg(feature = "hot-join")]
 use crate::network::messages::{
@@ -39,8 +52,10 @@
 use crate::rle;
 use crate::rng::{random, Pcg32, Rng, SeedableRng};
 use crate::sessions::config::{ProtocolConfig, SyncConfig};
+use crate::sessions::sync_wait::EndpointSyncProgress;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
-use crate::time_sync::{TimeSync, TimeSyncConfig};
+use crate::time_sync::{duration_to_frames, duration_to_frames_ceil, TimeSync, TimeSyncConfig};
+use crate::vec_map::VecMap;
 use crate::{report_violation, safe_frame_add, safe_frame_sub};
 use crate::{
     Config, DesyncDetection, FortressError, Frame, IncompatibleSessionReason, InvalidRequestKind,
@@ -49,12 +64,12 @@
 use tracing::trace;
 
 use std::collections::vec_deque::Drain;
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::collections::{BTreeSet, VecDeque};
 use std::convert::TryFrom;
 use std::hash::Hasher;
 use std::ops::Add;
 use std::sync::Arc;
-use web_time::{Duration, Instant};
+use web_time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use super::network_stats::NetworkStats;
 
@@ -62,11 +77,51 @@
 /// Conservative payload budget shared by common datagram transports.
 const PORTABLE_DATAGRAM_PAYLOAD_THRESHOLD: usize = 1200;
 /// Common IPv4/UDP payload ceiling under a 1500-byte path MTU.
-const IPV4_UDP_PAYLOAD_FRAGMENTATION_THRESHOLD: usize = 1472;
+const IPV4_UDP_PAYLOAD_FRAGMENTATION_THRESHOLD: usize =
+    ProtocolConfig::MAX_UNFRAGMENTED_PACKET_BYTES;
 const CONFIG_DIGEST_DOMAIN: &[u8; 8] = b"FRv1-cfg";
+const HANDLES_DIGEST_DOMAIN: &[u8; 8] = b"FRv9-hnd";
+/// Largest player count for which a mismatched handle claim is searched for
+/// the handles it names; every subset of handles is hashed, so this bounds a
+/// failed handshake at 2^16 digests.
+const MAX_RESOLVED_CLAIM_PLAYERS: usize = 16;
 const HOT_JOIN_FEATURE: u32 = 1 << 0;
+/// Keyframe input compression for the host-to-spectator stream
+/// (`SpectatorConfig::keyframe_interval`).
+const SPECTATOR_KEYFRAME_FEATURE: u32 = 1 << 1;
+/// History-free resync encoding for `Input` batches once the unacknowledged
+/// backlog grows (`ProtocolConfig::input_resync_threshold`).
+const INPUT_RESYNC_FEATURE: u32 = 1 << 2;
+/// Frame tags carried in `Input` bodies (`ProtocolConfig::frame_tags`).
+const FRAME_TAG_FEATURE: u32 = 1 << 3;
+/// Sequence numbers in message headers (`ProtocolConfig::sequence_numbers`).
+const SEQUENCE_FEATURE: u32 = 1 << 4;
+/// Checksum reports sent to a verifying spectator
+/// (`SpectatorConfig::checksum_authority`).
+const CHECKSUM_AUTHORITY_FEATURE: u32 = 1 << 5;
+/// Feature bits that are negotiated rather than required to match: peers may
+/// disagree on them without failing the handshake, and they are left out of
+/// the config digest.
+const NEGOTIATED_FEATURES: u32 = SPECTATOR_KEYFRAME_FEATURE
+    | INPUT_RESYNC_FEATURE
+    | FRAME_TAG_FEATURE
+    | SEQUENCE_FEATURE
+    | CHECKSUM_AUTHORITY_FEATURE;
+/// Leading byte of an `Input` body on a link with input resync negotiated:
+/// the batch is encoded against the frame before `start_frame`.
+const INPUT_BODY_DELTA: u8 = 0;
+/// Leading byte of an `Input` body on a link with input resync negotiated:
+/// the batch is encoded against a blank frame, so it decodes without history.
+const INPUT_BODY_RESYNC: u8 = 1;
+/// Encoded size of one frame tag in an `Input` body: the carrier frame, the
+/// tagged frame, and the payload.
+const FRAME_TAG_WIRE_LEN: usize = 4 + 4 + FRAME_TAG_LEN;
 /// Per-endpoint D14 carrier mailbox bound, aligned with the raw receive-poll cap.
 const MAX_RECEIVED_DROP_MESSAGES: usize = crate::network::MAX_RECEIVE_MESSAGES_PER_POLL;
+/// Messages [`UdpProtocol::preallocate_buffers`] makes room for in the send
+/// queue, and `Input` bodies it keeps for reuse. One poll queues a handful of
+/// messages, so this is headroom, not a bound.
+const PREALLOCATED_SEND_QUEUE_LEN: usize = 32;
 
 /// One coordinated graceful-drop control message carried by a running endpoint.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -91,12 +146,59 @@ fn into_body(self) -> MessageBody {
     }
 }
 
+/// Per-endpoint match pause/resume mailbox bound, aligned with the raw
+/// receive-poll cap.
+const MAX_RECEIVED_MATCH_PAUSE_MESSAGES: usize = crate::network::MAX_RECEIVE_MESSAGES_PER_POLL;
+
+/// One match pause/resume negotiation message carried by a running endpoint.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum MatchPauseMessage {
+    Control(MatchPause),
+    Ack(MatchPauseAck),
+}
+
+impl MatchPauseMessage {
+    fn into_body(self) -> MessageBody {
+        match self {
+            Self::Control(body) => MessageBody::MatchPause(body),
+            Self::Ack(body) => MessageBody::MatchPauseAck(body),
+        }
+    }
+}
+
+/// Payload bytes of one frame tag.
+const FRAME_TAG_LEN: usize = 8;
+
+/// A frame tag queued for a peer (see `ProtocolConfig::frame_tags`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PendingFrameTag {
+    /// The frame the application tagged.
+    frame: Frame,
+    data: [u8; FRAME_TAG_LEN],
+    /// The local input frame the tag travels with, [`Frame::NULL`] until the
+    /// next input is queued. Every `Input` that carries this frame also
+    /// carries the tag, so the tag is acknowledged together with it.
+    carrier: Frame,
+}
+
+/// An `Input` body split by [`UdpProtocol::split_input_body`].
+struct InputBodyParts<'a> {
+    /// Whether the batch is encoded against a blank frame.
+    resync: bool,
+    /// Frame tag entries, [`FRAME_TAG_WIRE_LEN`] bytes each.
+    frame_tags: &'a [u8],
+    /// The encoded input batch.
+    encoded: &'a [u8],
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct HandshakeConfig {
     min_compat_version: u8,
     features: u32,
     config: SessionConfigBlock,
     config_digest: u64,
+    /// Digest of the player handles the sending endpoint owns locally.
+    handles_digest: u64,
 }
 
 impl HandshakeConfig {
@@ -112,6 +214,7 @@ fn new(config: SessionConfigBlock) -> Self {
             features,
             config,
             config_digest,
+            handles_digest: handles_digest(&[]),
         }
     }
 
@@ -121,6 +224,7 @@ const fn from_request(request: SyncRequest) -> Self {
             features: request.features,
             config: request.config,
             config_digest: request.config_digest,
+            handles_digest: request.handles_digest,
         }
     }
 
@@ -130,6 +234,7 @@ const fn from_reply(reply: SyncReply) -> Self {
             features: reply.features,
             config: reply.config,
             config_digest: reply.config_digest,
+            handles_digest: reply.handles_digest,
         }
     }
 
@@ -140,6 +245,8 @@ const fn request(self, random_request: u32) -> SyncRequest {
             features: self.features,
             config: self.config,
             config_digest: self.config_digest,
+            handles_digest: self.handles_digest,
+            access_proof: 0,
         }
     }
 
@@ -150,6 +257,7 @@ const fn reply(self, random_reply: u32) -> SyncReply {
             features: self.features,
             config: self.config,
             config_digest: self.config_digest,
+            handles_digest: self.handles_digest,
         }
     }
 
@@ -190,7 +298,7 @@ const fn first_mismatch(self, theirs: Self) -> Option<IncompatibleSessionReason>
                 theirs: theirs.config.desync_interval,
             });
         }
-        if self.features != theirs.features {
+        if self.features & !NEGOTIATED_FEATURES != theirs.features & !NEGOTIATED_FEATURES {
             return Some(IncompatibleSessionReason::Features {
                 ours: self.features,
                 theirs: theirs.features,
@@ -206,6 +314,38 @@ const fn first_mismatch(self, theirs: Self) -> Option<IncompatibleSessionReason>
     }
 }
 
+/// Digest of a sorted set of player handles, exchanged in the handshake so each
+/// endpoint can check that the peer owns exactly the handles registered for it.
+fn handles_digest(handles: &[PlayerHandle]) -> u64 {
+    let mut hasher = DeterministicHasher::new();
+    hasher.write(HANDLES_DIGEST_DOMAIN);
+    for handle in handles {
+        hasher.write(&(handle.as_usize() as u64).to_le_bytes());
+    }
+    hasher.finish()
+}
+
+/// The sorted handles below `num_players` whose [`handles_digest`] is
+/// `digest`, or `None` if none match or there are too many players to search.
+fn resolve_handles_digest(digest: u64, num_players: usize) -> Option<Vec<PlayerHandle>> {
+    if num_players > MAX_RESOLVED_CLAIM_PLAYERS {
+        return None;
+    }
+    let mut handles = Vec::with_capacity(num_players); // alloc-bound: at most MAX_RESOLVED_CLAIM_PLAYERS handles.
+    for mask in 0..(1_u32 << num_players) {
+        handles.clear();
+        handles.extend(
+            (0..num_players)
+                .filter(|bit| mask & (1 << bit) != 0)
+                .map(PlayerHandle::new),
+        );
+        if handles_digest(&handles) == digest {
+            return Some(handles);
+        }
+    }
+    None
+}
+
 fn config_digest(config: SessionConfigBlock, features: u32) -> u64 {
     let mut hasher = DeterministicHasher::new();
     hasher.write(CONFIG_DIGEST_DOMAIN);
@@ -255,18 +395,50 @@ pub struct UdpProtocol<T>
     num_players: usize,
     /// Number of local players this endpoint serializes inputs for (the width of
     /// [`last_acked_input`](Self::last_acked_input)). Retained so the endpoint can
-    /// rebuild itself for a hot-join rejoin via [`rearm_for_rejoin`](Self::rearm_for_rejoin);
-    /// only read under that feature, so it is gated to keep the default build's
-    /// dead-code lint clean.
-    #[cfg(feature = "hot-join")]
+    /// rebuild itself for a restarted peer or a hot-join rejoin; see
+    /// [`restart_for_peer`](Self::restart_for_peer).
     local_players: usize,
+    /// Handles of other peers' players whose inputs this endpoint sends along
+    /// with the local players' (sorted); see [`relay_for`](Self::relay_for).
+    relayed_handles: Vec<PlayerHandle>,
     handles: Arc<[PlayerHandle]>,
     send_queue: VecDeque<Message>,
-    event_queue: VecDeque<Event<T>>,
+    /// Queued events, each with its [`next_event_stamp`] so the session can
+    /// merge several endpoints' events in the order they were generated.
+    event_queue: VecDeque<(u64, Event<T>)>,
     /// Bounded running-state mailbox drained by session-level D14 orchestration.
     received_drop_messages: VecDeque<DropControlMessage>,
     /// Rate-limits a full-mailbox diagnostic to once per endpoint era.
     drop_mailbox_warning_sent: bool,
+    /// Bounded running-state mailbox drained by the session's match pause
+    /// negotiation.
+    received_match_pause_messages: VecDeque<MatchPauseMessage>,
+    /// Rate-limits a full-mailbox diagnostic to once per drain.
+    match_pause_mailbox_warning_sent: bool,
+    /// Final frame of the spectator stream this host endpoint ended, if any.
+    /// The `StreamEnd` notice is resent on the keepalive cadence while running.
+    stream_end: Option<Frame>,
+    /// Last time a `StreamEnd` notice was sent.
+    last_stream_end_time: Instant,
+    /// Whether a received `StreamEnd` was already reported (once per era).
+    stream_end_received: bool,
+    /// Newest frame of our input the peer reported receiving in a
+    /// `LinkClosed` notice; `Frame::NULL` until one arrives.
+    peer_closed_frame: Frame,
+    /// Last time a `LinkClosed` notice was sent. While disconnected the
+    /// notice is resent on the keepalive cadence until shutdown.
+    last_link_closed_time: Instant,
+    /// Frame of the one-off `ChecksumRequest` this endpoint resends on the
+    /// keepalive cadence until the peer's report for it arrives.
+    checksum_request: Option<Frame>,
+    /// Last time a `ChecksumRequest` was sent.
+    last_checksum_request_time: Instant,
+    /// The peer's answer to `checksum_request`. Kept apart from
+    /// `pending_checksums` so desync detection does not consume it.
+    requested_checksum: Option<(Frame, u128)>,
+    /// Newest frame the peer requested our checksum of, until the session
+    /// answers it.
+    pending_checksum_request: Option<Frame>,
     /// Opt-in bounded runtime-refinement trace. Absent from normal builds.
     #[cfg(feature = "trace-validation")]
     handshake_trace: Option<HandshakeTraceRecorder>,
@@ -283,10 +455,18 @@ pub struct UdpProtocol<T>
     sync_duration_warning_sent: bool,
     /// Whether we've emitted a sync timeout event (emit only once per timeout period).
     sync_timeout_event_sent: bool,
+    /// The peer's out-of-band connection ID while the single-roundtrip handshake
+    /// is still unconfirmed; see [`pre_establish`](Self::pre_establish).
+    pre_established_remote_conn_id: Option<u32>,
+    /// Random ID and send time of the latest sync request, to seed the RTT
+    /// when a pre-established handshake concludes.
+    last_sync_request: Option<(u32, Instant)>,
     running_last_quality_report: Instant,
     running_last_input_recv: Instant,
     disconnect_notify_sent: bool,
     disconnect_event_sent: bool,
+    /// Sends the socket refused in a row; reset by the next successful send.
+    consecutive_send_failures: u32,
 
     // constants
     disconnect_timeout: Duration,
@@ -294,19 +474,53 @@ pub struct UdpProtocol<T>
     shutdown_timeout: Instant,
     fps: usize,
     conn_id: u32,
+    /// Whether `conn_id` was chosen by the application (see
+    /// [`pin_conn_id`](Self::pin_conn_id)) and must not be re-rolled.
+    conn_id_pinned: bool,
+    /// Whether a `SyncRequest` from a restarted peer restarts the handshake
+    /// of a disconnected endpoint; see
+    /// [`enable_peer_restart`](Self::enable_peer_restart).
+    accept_peer_restart: bool,
+    /// Whether packets carrying the peer's connection ID are accepted from a
+    /// new source address; see
+    /// [`enable_address_migration`](Self::enable_address_migration).
+    accept_address_migration: bool,
 
     // sync configuration
     sync_config: SyncConfig,
     local_handshake: HandshakeConfig,
     handshake_failed: Option<IncompatibleSessionReason>,
+    /// Digest of the handles the peer must claim in its handshake; `None`
+    /// skips the check. Set by [`claim_handles`](Self::claim_handles).
+    expected_handles_digest: Option<u64>,
+    /// The sorted handles behind
+    /// [`expected_handles_digest`](Self::expected_handles_digest), used to name
+    /// a doubly-claimed handle when the check fails.
+    expected_handles: Vec<PlayerHandle>,
+    /// Token proved in every `SyncRequest` we send; set by
+    /// [`prove_spectator_access`](Self::prove_spectator_access).
+    spectator_token: Option<[u8; 16]>,
+    /// Token the peer must prove before this endpoint answers or sends
+    /// anything; set by [`require_spectator_access`](Self::require_spectator_access).
+    required_spectator_token: Option<[u8; 16]>,
+    /// Whether the peer has proved [`required_spectator_token`](Self::required_spectator_token).
+    spectator_access_granted: bool,
 
     // protocol configuration
     protocol_config: ProtocolConfig,
 
     // the other client
     peer_addr: T::Address,
+    /// Where outgoing packets go instead of `peer_addr` (a relay), if set.
+    route: Option<T::Address>,
     remote_conn_id: u32,
     peer_connect_status: Vec<ConnectionStatus>,
+    /// Per slot the peer does not own, its pending claim that the slot
+    /// disconnected, as the lowest freeze frame claimed so far. Held out of
+    /// [`Self::peer_connect_status`] until the session corroborates it (see
+    /// [`Self::accept_disconnect_claim`]); the claimed frame still counts as
+    /// the peer's connected progress meanwhile.
+    disconnect_claims: Vec<Option<Frame>>,
 
     // ---- floor-round (double-failure-relay connected-relay reorder fix, S55) ----
     // A relay reports its per-slot pessimistic floor (the `min` over its own
@@ -382,8 +596,125 @@ pub struct UdpProtocol<T>
     // input compression
     pending_output: VecDeque<InputBytes>,
     last_acked_input: InputBytes,
+    /// Acknowledged inputs before `last_acked_input`, oldest first and
+    /// consecutive, kept so a packet can resend up to
+    /// `ProtocolConfig::redundant_input_frames` frames; the oldest is only
+    /// ever the delta reference.
+    acked_output: VecDeque<InputBytes>,
+    /// Newest frame carried by a queued `Input`, or `Frame::NULL` before the
+    /// first one. `pending_output` can run ahead of it (suppressed frames and
+    /// the byte-capped batch tail), so a peer's ack past it is forged.
+    newest_sent_input_frame: Frame,
     max_prediction: usize,
-    recv_inputs: BTreeMap<Frame, InputBytes>,
+    recv_inputs: VecMap<Frame, InputBytes>,
+    /// `Input` / `InputAck` messages dropped by validation, per reason.
+    input_messages_rejected: InputRejectionCounts,
+
+    // buffer reuse (see `preallocate_buffers`)
+    /// Input byte buffers released by acknowledged `pending_output` entries
+    /// and pruned `recv_inputs` history, handed back out to new frames. Only
+    /// refilled up to its reserved capacity, which stays zero unless the
+    /// session preallocated its buffers.
+    spare_input_bytes: Vec<Vec<u8>>,
+    /// Sent `Input` bodies, recycled like `spare_input_bytes`.
+    spare_input_bodies: Vec<Input>,
+    /// Connect-status lists of sent `KeepAlive`s, recycled like
+    /// `spare_input_bodies`.
+    spare_keep_alive_statuses: Vec<Vec<ConnectionStatus>>,
+    /// Delta scratch when encoding inputs, RLE scratch when decoding them.
+    codec_scratch: Vec<u8>,
+    /// Decoded frames of the `Input` being received, back to back.
+    decoded_inputs: Vec<u8>,
+    /// Frames of the `Input` being received, all validated before any is
+    /// applied.
+    staged_inputs: Vec<InputBytes>,
+    /// Per-player inputs of `staged_inputs`, `handles.len()` per frame.
+    staged_player_inputs: Vec<PlayerInput<T::Input>>,
+    /// Connect-status snapshot shared by the `Event::Input`s of one packet.
+    /// Rewritten in place once the session has consumed the previous
+    /// packet's events.
+    input_connect_status: Arc<[ConnectionStatus]>,
+
+    // spectator keyframe compression (see `SpectatorConfig::keyframe_interval`)
+    /// Keyframe interval on the host side of a spectator link; on the
+    /// spectator side any non-zero value only opts in. `0` disables the mode.
+    keyframe_interval: usize,
+    /// Whether this endpoint advertises the keyframe feature unprompted (the
+    /// spectator side). The host side only echoes a peer that advertised it.
+    keyframe_initiator: bool,
+    /// Whether the peer advertised the keyframe feature in its handshake.
+    peer_keyframes: bool,
+
+    // spectator checksum authority (see `SpectatorConfig::checksum_authority`)
+    /// Whether this endpoint asks the peer for its checksum reports (the
+    /// spectator side) and keeps them without desync detection.
+    checksum_authority: bool,
+    /// Whether the peer advertised the checksum authority feature in its
+    /// handshake.
+    peer_checksum_authority: bool,
+
+    // out-of-order input buffering (spectator side of a spectator link)
+    /// `Input` packets that start past the next expected frame, oldest
+    /// arrival first, replayed once the frames before them arrive.
+    deferred_inputs: VecDeque<Input>,
+    /// Most packets `deferred_inputs` holds; `0` drops such packets instead.
+    deferred_input_capacity: usize,
+
+    // input resync (see `ProtocolConfig::input_resync_threshold`)
+    /// Whether the peer advertised the input resync feature in its handshake.
+    peer_input_resync: bool,
+    /// `Input` packets sent in the resync encoding.
+    input_resyncs_sent: u64,
+
+    // frame tags (see `ProtocolConfig::frame_tags`)
+    /// Whether the peer advertised the frame tag feature in its handshake.
+    peer_frame_tags: bool,
+    /// Tags sent to the peer and not yet acknowledged, oldest first.
+    frame_tags: VecDeque<PendingFrameTag>,
+    /// Tags dropped unacknowledged once they fell `max_prediction` frames
+    /// behind the newest tag.
+    frame_tags_dropped: u64,
+
+    // sequence numbers (see `ProtocolConfig::sequence_numbers`)
+    /// Whether the peer advertised the sequence number feature in its handshake.
+    peer_sequence_numbers: bool,
+    /// Sequence number of the next message `queue_message` queues once the
+    /// feature is negotiated.
+    next_send_sequence: u32,
+    /// Sequence numbers received from the peer.
+    sequence_window: SequenceWindow,
+    /// Received messages dropped because their sequence number was seen before.
+    duplicates_dropped: u64,
+    /// Received messages dropped because their sequence number fell behind
+    /// the window.
+    stale_dropped: u64,
+
+    // reliable channel (see `ProtocolConfig::reliable_window`)
+    /// Sequence number of the next message `queue_reliable` accepts.
+    reliable_next_sequence: u32,
+    /// Messages sent to the peer and not yet acknowledged, oldest first. At
+    /// most `ProtocolConfig::reliable_window`.
+    reliable_in_flight: VecDeque<ReliableMessage>,
+    /// Last time every message in `reliable_in_flight` was resent.
+    last_reliable_resend_time: Instant,
+    /// Sequence number of the next message delivered to the session.
+    reliable_next_delivery: u32,
+    /// Messages received ahead of a missing one: slot `i` holds sequence
+    /// `reliable_next_delivery + i`. Shorter than
+    /// `ProtocolConfig::reliable_window`.
+    reliable_received: VecDeque<Option<Vec<u8>>>,
+
+    // redundant-input suppression (see `ProtocolConfig::suppress_redundant_inputs`)
+    /// Consecutive frames whose immediate send `send_input` skipped because
+    /// their bytes matched the previous frame. Reset by every queued `Input`
+    /// (fresh, forced, or retransmitted), so it bounds the unsent tail of
+    /// `pending_output` by `ProtocolConfig::max_suppressed_frames`.
+    suppressed_input_frames: usize,
+    /// Connection status carried by the most recently queued `Input`. A
+    /// suppressed frame must not hide a status change from the peer, so any
+    /// difference forces an immediate send. Length `num_players` once the
+    /// first `Input` is queued.
+    last_sent_input_connect_status: Vec<ConnectionStatus>,
 
     // connect-status nudge (see `send_connect_status_nudge`)
     /// When `true` (set by the session each poll via
@@ -408,16 +739,31 @@ pub struct UdpProtocol<T>
     /// in-flight Input packets would have decided). Tracked separately from
     /// `last_send_time`, which control traffic also refreshes.
     last_input_send_time: Instant,
+    /// Last time a message carrying both our newest input acknowledgement and
+    /// our connect status (`Input`, or a `KeepAlive` with a valid `ack_frame`)
+    /// was queued; a bare `InputAck` does not count. With
+    /// [`ProtocolConfig::keep_alive_acks`] a keepalive fires once this is a
+    /// keepalive interval old, even while other traffic keeps `last_send_time`
+    /// fresh, so a peer whose input stream is one-sided still drains its
+    /// `pending_output` and keeps hearing our connect status after it has.
+    last_ack_send_time: Instant,
 
     // time sync
     time_sync_layer: TimeSync,
-    /// Retained so the endpoint can rebuild its `TimeSync` for a hot-join rejoin
-    /// via [`rearm_for_rejoin`](Self::rearm_for_rejoin); only read under that
-    /// feature, so it is gated to keep the default build's dead-code lint clean.
-    #[cfg(feature = "hot-join")]
+    /// Retained so the endpoint can rebuild its `TimeSync` for a restarted peer
+    /// or a hot-join rejoin; see [`restart_for_peer`](Self::restart_for_peer).
     time_sync_config: TimeSyncConfig,
     local_frame_advantage: i32,
     remote_frame_advantage: i32,
+    /// When `round_trip_time` was last measured.
+    rtt_updated_at: Instant,
+    /// When `remote_frame_advantage` was last reported by the peer.
+    remote_advantage_updated_at: Instant,
+    /// Set by the session's advance-stall watchdog; sent with every quality
+    /// report.
+    advance_stalled: bool,
+    /// The stall hint of the peer's latest quality report.
+    remote_advance_stalled: bool,
 
     // network
     /// The instant when synchronization started, used for elapsed time calculations.
@@ -443,6 +789,16 @@ pub struct UdpProtocol<T>
     // batched into `Input` packets, for realized-compression accounting.
     input_bytes_pre_compression: u64,
     input_bytes_post_compression: u64,
+    // Acknowledged frames resent for `ProtocolConfig::redundant_input_frames`,
+    // and their raw bytes (included in `input_bytes_pre_compression`).
+    redundant_input_frames_sent: u64,
+    redundant_input_bytes_pre_compression: u64,
+    // `SyncRequest`s ignored for a missing or wrong spectator access proof.
+    unauthorized_sync_requests: u64,
+    // Resettable compression statistics, and the newest frame already compared
+    // with its predecessor for the byte-change histogram.
+    compression_stats: CompressionStats,
+    compression_profiled_frame: Frame,
     // Logical messages at or above the conservative 1200-byte cross-transport
     // budget and the 1472-byte IPv4 fragmentation boundary. Count every
     // occurrence but diagnose each threshold once per endpoint era.
@@ -460,16 +816,39 @@ pub struct UdpProtocol<T>
     /// the wall-clock adjustments (NTP steps, VM snapshot restores) that could
     /// corrupt RTT when this was derived from `SystemTime`.
     ping_epoch_base: Instant,
+    /// Added to every quality-report `ping` timestamp: the Unix time in
+    /// milliseconds at endpoint construction, or `0` under an injected
+    /// [`ProtocolConfig::clock`] so simulated sessions stay deterministic.
+    /// Read once, so RTT stays on the monotonic clock, while the peer can
+    /// compare the timestamps against its own to estimate the clock offset.
+    ping_wall_origin: u128,
+    /// Smoothed estimate of the peer's ping clock minus this endpoint's, in
+    /// microseconds so the average does not stall on rounding; `None` until
+    /// the first quality report arrives. See [`Self::observe_clock_offset`].
+    clock_offset_micros: Option<i64>,
     last_send_time: Instant,
     last_recv_time: Instant,
+    /// Messages this endpoint may still send before the next
+    /// [`poll`](Self::poll) (`ProtocolConfig::max_packets_per_poll`).
+    send_packets_left: usize,
+    /// Outbound byte budget under `ProtocolConfig::max_bytes_per_second`. A
+    /// message larger than what is left drives it negative, and nothing more is
+    /// sent until the refill repays the debt.
+    send_byte_budget: i128,
+    /// When [`send_byte_budget`](Self::send_byte_budget) was last refilled.
+    send_budget_refilled_at: Instant,
 
     // debug desync
-    pub(crate) pending_checksums: BTreeMap<Frame, u128>,
+    pub(crate) pending_checksums: VecMap<Frame, u128>,
     /// Highest frame at which a checksum this peer sent matched our local
     /// checksum history. Per-peer so that verification against one remote does
     /// not leak into another remote's sync verdict (an N>=3 logical error if it
     /// were session-global). `None` until the first matching checksum.
     pub(crate) last_verified_frame: Option<Frame>,
+    /// Highest frame at which this peer's checksum was compared against our
+    /// local checksum history, whether or not it matched. `None` until the
+    /// first comparison. Backs `P2PSession::desync_detection_lag`.
+    pub(crate) last_compared_checksum_frame: Option<Frame>,
     /// Number of confirmed frames at which this peer's checksum did NOT match
     /// our local history (the per-peer persistence signal behind B3 trust
     /// downgrade). Monotonic (saturating), per-peer (never leaks across
@@ -571,6 +950,23 @@ fn eq(&self, other: &Self) -> bool {
     }
 }
 
+/// Connection IDs both peers agreed on out of band for a link the application
+/// has already verified; see [`UdpProtocol::pre_establish`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PreEstablished {
+    /// The connection ID this endpoint sends with.
+    pub(crate) local_conn_id: u32,
+    /// The connection ID the peer is expected to send with.
+    pub(crate) remote_conn_id: u32,
+}
+
+/// A relay [`UdpProtocol::prepare_relay_for`] checked, ready for
+/// [`UdpProtocol::relay_for`] to apply without failing.
+pub(crate) struct PreparedRelay {
+    handle: PlayerHandle,
+    last_acked_input: InputBytes,
+}
+
 fn draw_valid_conn_id(mut draw: impl FnMut() -> u32) -> u32 {
     loop {
         let conn_id = draw();
@@ -591,6 +987,7 @@ pub fn fuzz_protocol_input_packet(
     peer_connect_status: &[(bool, i32)],
     bytes: &[u8],
     pending_frames: &[i32],
+    sent_frames: usize,
 ) {
     #[derive(Debug, Clone)]
     struct FuzzConfig;
@@ -642,6 +1039,13 @@ impl Config for FuzzConfig {
             bytes: pending_bytes,
         });
     }
+    // Treat a prefix of the pending frames as already sent.
+    if let Some(sent) = sent_frames
+        .checked_sub(1)
+        .and_then(|last| protocol.pending_output.get(last))
+    {
+        protocol.newest_sent_input_frame = sent.frame;
+    }
 
     let mut status = Vec::new();
     if status
@@ -688,20 +1092,19 @@ impl Config for FuzzConfig {
         protocol.pending_output.len() <= protocol.protocol_config.pending_output_limit,
         "protocol pending output exceeded configured bound"
     );
-}
-
-struct StagedInputFrame<I>
-where
-    I: Copy + Clone + PartialEq + Eq,
-{
-    input_data: InputBytes,
-    player_inputs: Vec<PlayerInput<I>>,
+    assert!(
+        !protocol.last_acked_input.frame.is_valid()
+            || protocol.last_acked_input.frame <= protocol.newest_sent_input_frame,
+        "an ack released pending output that was never sent"
+    );
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum AckDisposition {
     Apply,
     Ignore,
+    /// Acks a frame never sent; the whole carrying message is dropped.
+    Reject,
 }
 
 fn input_batch_decoded_byte_limit_with_cap(
@@ -762,9 +1165,24 @@ fn validate_default_input_wire_size<T: Config>() -> Result<usize, FortressError>
     if input_size == 0 {
         return Err(SerializationErrorKind::InputSerializedSizeZero.into());
     }
+    validate_default_input_round_trip::<T>(input_size)?;
     Ok(input_size)
 }
 
+/// Both sides of a link start their delta chain from the encoded default
+/// input ([`InputBytes::zeroed`]), and blank predictions and disconnected
+/// players use the default value itself, so the two must agree: the default
+/// has to decode back to itself from exactly `input_size` bytes.
+fn validate_default_input_round_trip<T: Config>(input_size: usize) -> Result<(), FortressError> {
+    let blank = InputBytes::blank_frame_bytes::<T>(input_size)?;
+    match codec::decode::<T::Input>(&blank) {
+        Ok((decoded, consumed)) if consumed == input_size && decoded == T::Input::default() => {
+            Ok(())
+        },
+        _ => Err(SerializationErrorKind::DefaultInputRoundTripMismatch.into()),
+    }
+}
+
 fn validate_input_frame_wire_size(
     input_size: usize,
     player_count: usize,
@@ -825,6 +1243,12 @@ pub(crate) fn new(
             Some(clock_fn) => clock_fn(),
             None => Instant::now(),
         };
+        let ping_wall_origin = match protocol_config.clock {
+            Some(_) => 0,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_millis()),
+        };
 
         handles.sort_unstable();
         let recv_player_num = handles.len();
@@ -871,6 +1295,13 @@ pub(crate) fn new(
             peer_connect_status.push(ConnectionStatus::default());
         }
 
+        let mut disconnect_claims = Vec::new();
+        disconnect_claims
+            .try_reserve_exact(num_players)
+            .map_err(|_err| allocation_failed("protocol.disconnect_claims", num_players))?;
+        // alloc-bound: `num_players` is validated at session construction (mirrors `peer_connect_status`).
+        disconnect_claims.resize(num_players, None);
+
         // floor-round reply cache (double-failure-relay connected-relay reorder
         // fix), parallel to `peer_connect_status`. Seeded to `Frame::NULL` ("no
         // reply yet"), which the session fold reads as "no floor known"; the
@@ -885,7 +1316,7 @@ pub(crate) fn new(
         }
 
         // received input history - may fail if serialization is broken
-        let mut recv_inputs = BTreeMap::new();
+        let mut recv_inputs = VecMap::new();
         recv_inputs.insert(
             Frame::NULL,
             InputBytes::zeroed::<T>(recv_player_num)
@@ -897,16 +1328,29 @@ pub(crate) fn new(
             .ok_or(SerializationErrorKind::EndpointCreationFailed)?;
 
         let time_sync_layer = TimeSync::try_with_config(time_sync_config)?;
+        let send_packets_left = protocol_config.max_packets_per_poll;
+        let send_byte_budget = protocol_config.max_bytes_per_second.map_or(0, i128::from);
 
         Ok(Self {
             num_players,
-            #[cfg(feature = "hot-join")]
             local_players,
+            relayed_handles: Vec::new(),
             handles,
             send_queue: VecDeque::new(),
             event_queue: VecDeque::new(),
             received_drop_messages: VecDeque::new(),
             drop_mailbox_warning_sent: false,
+            received_match_pause_messages: VecDeque::new(),
+            match_pause_mailbox_warning_sent: false,
+            stream_end: None,
+            last_stream_end_time: now,
+            stream_end_received: false,
+            peer_closed_frame: Frame::NULL,
+            last_link_closed_time: now,
+            checksum_request: None,
+            last_checksum_request_time: now,
+            requested_checksum: None,
+            pending_checksum_request: None,
             #[cfg(feature = "trace-validation")]
             handshake_trace: None,
 
@@ -918,10 +1362,13 @@ pub(crate) fn new(
             sync_retry_warning_sent: false,
             sync_duration_warning_sent: false,
             sync_timeout_event_sent: false,
+            pre_established_remote_conn_id: None,
+            last_sync_request: None,
             running_last_quality_report: now,
             running_last_input_recv: now,
             disconnect_notify_sent: false,
             disconnect_event_sent: false,
+            consecutive_send_failures: 0,
 
             // constants
             disconnect_timeout,
@@ -929,21 +1376,31 @@ pub(crate) fn new(
             shutdown_timeout: now,
             fps,
             conn_id,
+            conn_id_pinned: false,
+            accept_peer_restart: false,
+            accept_address_migration: false,
 
             // sync configuration
             sync_config,
             local_handshake,
             handshake_failed: None,
+            expected_handles_digest: None,
+            expected_handles: Vec::new(),
+            spectator_token: None,
+            required_spectator_token: None,
+            spectator_access_granted: false,
 
             // protocol configuration
             protocol_config,
 
             // the other client
             peer_addr,
+            route: None,
             remote_conn_id: 0,
             peer_connect_status,
 
             // floor-round (double-failure-relay connected-relay reorder fix)
+            disconnect_claims,
             round_floor,
             floor_request_seq: 0,
             floor_reply_seq: 0,
@@ -956,20 +1413,75 @@ pub(crate) fn new(
             // input compression
             pending_output: VecDeque::new(),
             last_acked_input,
+            acked_output: VecDeque::new(),
+            newest_sent_input_frame: Frame::NULL,
             max_prediction,
             recv_inputs,
+            input_messages_rejected: InputRejectionCounts::default(),
+
+            // buffer reuse
+            spare_input_bytes: Vec::new(),
+            spare_input_bodies: Vec::new(),
+            spare_keep_alive_statuses: Vec::new(),
+            codec_scratch: Vec::new(),
+            decoded_inputs: Vec::new(),
+            staged_inputs: Vec::new(),
+            staged_player_inputs: Vec::new(),
+            input_connect_status: Arc::from([]),
+
+            // spectator keyframe compression
+            keyframe_interval: 0,
+            keyframe_initiator: false,
+            peer_keyframes: false,
+
+            // spectator checksum authority
+            checksum_authority: false,
+            peer_checksum_authority: false,
+
+            // out-of-order input buffering
+            deferred_inputs: VecDeque::new(),
+            deferred_input_capacity: 0,
+
+            // input resync
+            peer_input_resync: false,
+            input_resyncs_sent: 0,
+            peer_frame_tags: false,
+            frame_tags: VecDeque::new(),
+            frame_tags_dropped: 0,
+
+            // sequence numbers
+            peer_sequence_numbers: false,
+            next_send_sequence: 0,
+            sequence_window: SequenceWindow::default(),
+            duplicates_dropped: 0,
+            stale_dropped: 0,
+
+            // reliable channel
+            reliable_next_sequence: 0,
+            reliable_in_flight: VecDeque::new(),
+            last_reliable_resend_time: now,
+            reliable_next_delivery: 0,
+            reliable_received: VecDeque::new(),
+
+            // redundant-input suppression
+            suppressed_input_frames: 0,
+            last_sent_input_connect_status: Vec::new(),
 
             // connect-status nudge
             connect_status_nudge: false,
             last_nudge_time: now,
             last_input_send_time: now,
+            last_ack_send_time: now,
 
             // time sync
             time_sync_layer,
-            #[cfg(feature = "hot-join")]
             time_sync_config,
             local_frame_advantage: 0,
             remote_frame_advantage: 0,
+            rtt_updated_at: now,
+            remote_advantage_updated_at: now,
+            advance_stalled: false,
+            remote_advance_stalled: false,
 
             // network
             stats_start_time: now,
@@ -981,18 +1493,29 @@ pub(crate) fn new(
             messages_received_by_kind: MessageKindCounts::default(),
             input_bytes_pre_compression: 0,
             input_bytes_post_compression: 0,
+            redundant_input_frames_sent: 0,
+            redundant_input_bytes_pre_compression: 0,
+            unauthorized_sync_requests: 0,
+            compression_stats: CompressionStats::default(),
+            compression_profiled_frame: Frame::NULL,
             portability_risk_messages_sent: 0,
             portability_warning_sent: false,
             fragmentation_risk_messages_sent: 0,
             fragmentation_alarm_sent: false,
             round_trip_time: 0,
             ping_epoch_base: now,
+            ping_wall_origin,
+            clock_offset_micros: None,
             last_send_time: now,
             last_recv_time: now,
+            send_packets_left,
+            send_byte_budget,
+            send_budget_refilled_at: now,
 
             // debug desync
-            pending_checksums: BTreeMap::new(),
+            pending_checksums: VecMap::new(),
             last_verified_frame: None,
+            last_compared_checksum_frame: None,
             checksum_mismatch_count: 0,
             desync_detection,
 
@@ -1032,15 +1555,180 @@ fn now(&self) -> Instant {
     }
 
     /// Returns milliseconds elapsed on the protocol clock since this endpoint
-    /// was constructed — the timestamp basis for quality-report pings (see
-    /// [`Self::ping_epoch_base`]).
+    /// was constructed, plus [`Self::ping_wall_origin`] — the timestamp basis
+    /// for quality-report pings (see [`Self::ping_epoch_base`]).
     ///
-    /// Saturates to zero if the clock reads earlier than the construction
+    /// Saturates to the origin if the clock reads earlier than the construction
     /// instant (impossible for a monotonic clock; defensive for injected ones).
     fn ping_millis(&self) -> u128 {
         self.now()
             .saturating_duration_since(self.ping_epoch_base)
             .as_millis()
+            .saturating_add(self.ping_wall_origin)
+    }
+
+    /// Reserves every buffer the running input path uses up to its configured
+    /// bound, and fills the spare pools those buffers are recycled through,
+    /// so that once synchronized this endpoint sends and receives inputs
+    /// without allocating. See `SessionBuilder::with_preallocated_buffers`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::AllocationFailed`] if a reservation fails.
+    pub(crate) fn preallocate_buffers(&mut self) -> Result<(), FortressError> {
+        let pending_limit = self.protocol_config.pending_output_limit;
+        // The received history window, plus one batch decoded on top of it
+        // before the window is pruned.
+        let recv_limit = self
+            .protocol_config
+            .input_history_multiplier
+            .saturating_mul(self.max_prediction)
+            .saturating_add(pending_limit)
+            .saturating_add(1);
+        let recv_len = self
+            .recv_inputs
+            .first_key_value()
+            .map_or(0, |(_, input)| input.bytes.len());
+        let frame_len = recv_len.max(self.last_acked_input.bytes.len());
+        // Keyframes are negotiated during the handshake, so size for the wider
+        // keyframe format either way.
+        let wire_frame_len = keyframe_frame_len(frame_len, self.num_players).unwrap_or(frame_len);
+        let batch_bytes = wire_frame_len.saturating_mul(pending_limit);
+        let players = self.handles.len();
+        let kept_acked = self
+            .protocol_config
+            .redundant_input_frames
+            .saturating_sub(1);
+        // Every frame buffer alive at once: the pending output, its acked
+        // reference and the acked inputs kept for redundant sends, the
+        // received history, and one staged batch.
+        let frame_buffers = pending_limit
+            .saturating_add(1)
+            .saturating_add(kept_acked)
+            .saturating_add(recv_limit)
+            .saturating_add(pending_limit);
+
+        let reserve_failed = |context, requested| move |_err| allocation_failed(context, requested);
+        self.pending_output
+            .try_reserve(pending_limit)
+            .map_err(reserve_failed("protocol.pending_output", pending_limit))?;
+        // alloc-bound: `redundant_input_frames` is validated to at most `pending_output_limit`.
+        self.acked_output
+            .try_reserve(kept_acked)
+            .map_err(reserve_failed("protocol.acked_output", kept_acked))?;
+        self.recv_inputs
+            .try_reserve(recv_limit)
+            .map_err(reserve_failed("protocol.recv_inputs", recv_limit))?;
+        self.staged_inputs
+            .try_reserve(pending_limit)
+            .map_err(reserve_failed("protocol.staged_inputs", pending_limit))?;
+        let staged_players = pending_limit.saturating_mul(players);
+        self.staged_player_inputs
+            .try_reserve(staged_players)
+            .map_err(reserve_failed(
+                "protocol.staged_player_inputs",
+                staged_players,
+            ))?;
+        self.decoded_inputs
+            .try_reserve(batch_bytes)
+            .map_err(reserve_failed("protocol.decoded_inputs", batch_bytes))?;
+        self.codec_scratch
+            .try_reserve(batch_bytes)
+            .map_err(reserve_failed("protocol.codec_scratch", batch_bytes))?;
+        self.event_queue
+            .try_reserve(staged_players)
+            .map_err(reserve_failed("protocol.event_queue", staged_players))?;
+        self.send_queue
+            .try_reserve(PREALLOCATED_SEND_QUEUE_LEN)
+            .map_err(reserve_failed(
+                "protocol.send_queue",
+                PREALLOCATED_SEND_QUEUE_LEN,
+            ))?;
+        self.last_sent_input_connect_status
+            .try_reserve(self.num_players)
+            .map_err(reserve_failed(
+                "protocol.last_sent_input_connect_status",
+                self.num_players,
+            ))?;
+        // Pruned before each insert, so it peaks one above the cap.
+        let checksums = self.protocol_config.max_checksum_history.saturating_add(1);
+        self.pending_checksums
+            .try_reserve(checksums)
+            .map_err(reserve_failed("protocol.pending_checksums", checksums))?;
+        // Tags span at most `max_prediction + 1` consecutive frames.
+        let frame_tags = self.max_prediction.saturating_add(1);
+        self.frame_tags
+            .try_reserve(frame_tags)
+            .map_err(reserve_failed("protocol.frame_tags", frame_tags))?;
+        self.compression_stats
+            .byte_change_counts
+            .try_reserve(frame_len)
+            .map_err(reserve_failed("protocol.compression_stats", frame_len))?;
+
+        self.spare_input_bytes
+            .try_reserve_exact(frame_buffers)
+            .map_err(reserve_failed("protocol.spare_input_bytes", frame_buffers))?;
+        while self.spare_input_bytes.len() < frame_buffers {
+            let mut bytes = Vec::new();
+            // reserve-in-loop: one frame buffer per pool slot, bounded by `frame_buffers` (reserved above).
+            let reserved = bytes.try_reserve_exact(frame_len);
+            reserved.map_err(reserve_failed("protocol.spare_input_bytes", frame_len))?;
+            self.spare_input_bytes.push(bytes);
+        }
+        self.spare_input_bodies
+            .try_reserve_exact(PREALLOCATED_SEND_QUEUE_LEN)
+            .map_err(reserve_failed(
+                "protocol.spare_input_bodies",
+                PREALLOCATED_SEND_QUEUE_LEN,
+            ))?;
+        // An encoded body carries the resync tag and rarely exceeds the raw
+        // batch; a larger one grows its buffer once and keeps it.
+        let body_bytes = batch_bytes.saturating_add(1);
+        while self.spare_input_bodies.len() < PREALLOCATED_SEND_QUEUE_LEN {
+            let mut body = Input::default();
+            // reserve-in-loop: one body per pool slot, bounded by PREALLOCATED_SEND_QUEUE_LEN (reserved above).
+            let reserved = body.bytes.try_reserve_exact(body_bytes);
+            reserved.map_err(reserve_failed("protocol.spare_input_bodies", body_bytes))?;
+            // reserve-in-loop: same pool slot, `num_players` statuses.
+            let reserved = body.peer_connect_status.try_reserve_exact(self.num_players);
+            reserved.map_err(reserve_failed(
+                "protocol.spare_input_bodies",
+                self.num_players,
+            ))?;
+            self.spare_input_bodies.push(body);
+        }
+        self.spare_keep_alive_statuses
+            .try_reserve_exact(PREALLOCATED_SEND_QUEUE_LEN)
+            .map_err(reserve_failed(
+                "protocol.spare_keep_alive_statuses",
+                PREALLOCATED_SEND_QUEUE_LEN,
+            ))?;
+        while self.spare_keep_alive_statuses.len() < PREALLOCATED_SEND_QUEUE_LEN {
+            let mut status = Vec::new();
+            // reserve-in-loop: one list per pool slot, bounded by PREALLOCATED_SEND_QUEUE_LEN (reserved above).
+            let reserved = status.try_reserve_exact(self.num_players);
+            reserved.map_err(reserve_failed(
+                "protocol.spare_keep_alive_statuses",
+                self.num_players,
+            ))?;
+            self.spare_keep_alive_statuses.push(status);
+        }
+
+        if self.input_connect_status.len() != self.num_players {
+            let mut status = Vec::new();
+            status
+                .try_reserve_exact(self.num_players)
+                .map_err(reserve_failed(
+                    "protocol.input_connect_status",
+                    self.num_players,
+                ))?;
+            status.extend(std::iter::repeat_n(
+                ConnectionStatus::default(),
+                self.num_players,
+            ));
+            self.input_connect_status = Arc::from(status);
+        }
+        Ok(())
     }
 
     pub(crate) fn update_local_frame_advantage(&mut self, local_frame: Frame) {
@@ -1059,15 +1747,13 @@ pub(crate) fn update_local_frame_advantage(&mut self, local_frame: Frame) {
         }
 
         // Estimate which frame the other client is on by looking at the last frame they gave us
-        // plus some delta for the packet roundtrip time. RTT is peer-influenced, so every step
-        // uses checked or saturating arithmetic before narrowing back to frame units.
-        let remote_frame_delta = self
-            .round_trip_time
-            .checked_div(2)
-            .and_then(|half_rtt| half_rtt.checked_mul(self.fps as u128))
-            .map(|frame_ms| frame_ms / 1000)
-            .and_then(|frames| i32::try_from(frames).ok())
-            .unwrap_or(i32::MAX);
+        // plus some delta for the packet roundtrip time. RTT is peer-influenced, so the
+        // conversion saturates before narrowing back to frame units. Half the RTT is rounded to
+        // the nearest frame: truncating it made a low fps systematically underestimate the
+        // remote frame.
+        let remote_frame_delta =
+            i32::try_from(duration_to_frames(self.round_trip_duration() / 2, self.fps))
+                .unwrap_or(i32::MAX);
         let remote_frame = safe_frame_add!(
             last_recv_frame,
             remote_frame_delta,
@@ -1078,6 +1764,38 @@ pub(crate) fn update_local_frame_advantage(&mut self, local_frame: Frame) {
         self.local_frame_advantage = remote_frame.as_i32().saturating_sub(local_frame.as_i32());
     }
 
+    /// The newest input frame the peer has probably sent by now: the newest one
+    /// received (`-1` before any arrives), aged by half the round trip.
+    pub(crate) fn estimated_remote_input_frame(&self) -> i32 {
+        let remote_frame_delta =
+            i32::try_from(duration_to_frames(self.round_trip_duration() / 2, self.fps))
+                .unwrap_or(i32::MAX);
+        self.last_recv_frame()
+            .as_i32()
+            .max(-1)
+            .saturating_add(remote_frame_delta)
+    }
+
+    /// The measured round trip as a duration, saturating at `u64::MAX` ms.
+    fn round_trip_duration(&self) -> Duration {
+        Duration::from_millis(u64::try_from(self.round_trip_time).unwrap_or(u64::MAX))
+    }
+
+    /// How many frames the session may run ahead of this endpoint's confirmed
+    /// input: `max_prediction`, or with
+    /// [`ProtocolConfig::per_peer_prediction_limit`] set, its base plus the
+    /// frames in the measured round trip (rounded up), capped at
+    /// `max_prediction`.
+    pub(crate) fn prediction_allowance(&self, max_prediction: usize) -> usize {
+        let Some(base) = self.protocol_config.per_peer_prediction_limit else {
+            return max_prediction;
+        };
+        // RTT is peer-influenced; the conversion saturates rather than overflows.
+        let rtt_frames = duration_to_frames_ceil(self.round_trip_duration(), self.fps);
+        let rtt_frames = usize::try_from(rtt_frames).unwrap_or(usize::MAX);
+        base.saturating_add(rtt_frames).min(max_prediction)
+    }
+
     pub(crate) fn network_stats(&self) -> Result<NetworkStats, FortressError> {
         if self.state != ProtocolState::Synchronizing && self.state != ProtocolState::Running {
             return Err(FortressError::NotSynchronized);
@@ -1106,19 +1824,34 @@ pub(crate) fn network_stats(&self) -> Result<NetworkStats, FortressError> {
         // `usize` range.
         let kbps_sent = usize::try_from(total_bytes_sent.saturating_mul(8) / seconds / 1000)
             .unwrap_or(usize::MAX);
+        let local_frame_advantage = self.time_sync_layer.local_average();
+        let remote_frame_advantage = self.time_sync_layer.remote_average();
+        let stats_updated_at = self.rtt_updated_at.min(self.remote_advantage_updated_at);
 
+        #[allow(deprecated)]
         Ok(NetworkStats {
             ping: self.round_trip_time,
             send_queue_len: self.pending_output.len(),
             kbps_sent,
-            local_frames_behind: self.local_frame_advantage,
-            remote_frames_behind: self.remote_frame_advantage,
+            send_backlog_len: self.send_queue.len(),
+            send_backlog_bytes: self.send_queue.iter().map(Message::encoded_len).sum(),
+            local_frame_advantage,
+            remote_frame_advantage,
+            stats_age_ms: self
+                .now()
+                .saturating_duration_since(stats_updated_at)
+                .as_millis(),
+            estimated_clock_offset_ms: self.clock_offset_ms(),
+            local_frames_behind: local_frame_advantage,
+            remote_frames_behind: remote_frame_advantage,
             // Checksum fields are populated by P2PSession::network_stats()
             // which has access to both local and remote checksum histories
             last_compared_frame: None,
             local_checksum: None,
             remote_checksum: None,
             checksums_match: None,
+            duplicates_dropped: self.duplicates_dropped,
+            stale_dropped: self.stale_dropped,
         })
     }
 
@@ -1138,8 +1871,14 @@ pub(crate) fn peer_metrics(&self) -> PeerMetrics {
             messages_received_by_kind: self.messages_received_by_kind,
             input_bytes_pre_compression: self.input_bytes_pre_compression,
             input_bytes_post_compression: self.input_bytes_post_compression,
+            redundant_input_frames_sent: self.redundant_input_frames_sent,
+            redundant_input_bytes_pre_compression: self.redundant_input_bytes_pre_compression,
+            unauthorized_sync_requests: self.unauthorized_sync_requests,
             portability_risk_messages_sent: self.portability_risk_messages_sent,
             fragmentation_risk_messages_sent: self.fragmentation_risk_messages_sent,
+            input_messages_rejected: self.input_messages_rejected,
+            input_resyncs_sent: self.input_resyncs_sent,
+            frame_tags_dropped: self.frame_tags_dropped,
             pending_output_len: u64::try_from(self.pending_output.len()).unwrap_or(u64::MAX),
             pending_checksums_len: u64::try_from(self.pending_checksums.len()).unwrap_or(u64::MAX),
             ping_ms: self.round_trip_time,
@@ -1148,6 +1887,46 @@ pub(crate) fn peer_metrics(&self) -> PeerMetrics {
         }
     }
 
+    /// Adds this endpoint's buffers to the protocol components of `report`.
+    pub(crate) fn add_memory_usage(&self, report: &mut MemoryReport) {
+        report.recv_inputs.add(MemoryUsage::measured(
+            self.recv_inputs.len(),
+            self.recv_inputs
+                .iter()
+                .map(|(_, input)| input.bytes.len())
+                .sum(),
+        ));
+        report.pending_output.add(MemoryUsage::measured(
+            self.pending_output
+                .len()
+                .saturating_add(self.acked_output.len()),
+            self.pending_output
+                .iter()
+                .chain(&self.acked_output)
+                .map(|input| input.bytes.len())
+                .sum(),
+        ));
+        report.send_queue.add(MemoryUsage::measured(
+            self.send_queue.len(),
+            self.send_queue.iter().map(Message::encoded_len).sum(),
+        ));
+        report.pending_checksums.add(MemoryUsage::of(
+            self.pending_checksums.len(),
+            std::mem::size_of::<(Frame, u128)>(),
+        ));
+    }
+
+    /// The resettable [`CompressionStats`] of the inputs sent to this endpoint.
+    pub(crate) fn compression_stats(&self) -> &CompressionStats {
+        &self.compression_stats
+    }
+
+    /// Clears the [`CompressionStats`]. Frame pairs already compared are not
+    /// compared again.
+    pub(crate) fn reset_compression_stats(&mut self) {
+        self.compression_stats = CompressionStats::default();
+    }
+
     pub(crate) fn handles(&self) -> Arc<[PlayerHandle]> {
         Arc::clone(&self.handles)
     }
@@ -1162,6 +1941,81 @@ pub(crate) fn is_running(&self) -> bool {
         self.state == ProtocolState::Running
     }
 
+    /// Last frame for which both ends of this link had each other's inputs.
+    ///
+    /// The peer's side is the later of its last ack and any closed-link
+    /// notice, so both ends converge on the same value once the notices
+    /// arrive. `Frame::NULL` if either side received nothing.
+    pub(crate) fn disconnect_frame(&self) -> Frame {
+        let peer_received = std::cmp::max(self.last_acked_input.frame, self.peer_closed_frame);
+        std::cmp::min(self.last_recv_frame(), peer_received)
+    }
+
+    pub(crate) fn is_disconnected(&self) -> bool {
+        matches!(
+            self.state,
+            ProtocolState::Disconnected | ProtocolState::Shutdown
+        )
+    }
+
+    /// The peer's pending claim that `handle`, a slot it does not own,
+    /// disconnected: the lowest freeze frame it claimed.
+    pub(crate) fn disconnect_claim(&self, handle: PlayerHandle) -> Option<Frame> {
+        self.disconnect_claims
+            .get(handle.as_usize())
+            .copied()
+            .flatten()
+    }
+
+    /// How long this endpoint goes without hearing from its peer before it
+    /// reports the peer disconnected.
+    pub(crate) fn disconnect_timeout(&self) -> Duration {
+        self.disconnect_timeout
+    }
+
+    /// Takes the pending disconnect claim for `handle` into the connection
+    /// status this endpoint reports, as if the claim had just arrived from the
+    /// slot's owner.
+    pub(crate) fn accept_disconnect_claim(&mut self, handle: PlayerHandle) {
+        let Some(frame) = self
+            .disconnect_claims
+            .get_mut(handle.as_usize())
+            .and_then(Option::take)
+        else {
+            return;
+        };
+        if let Some(status) = self.peer_connect_status.get_mut(handle.as_usize()) {
+            if !status.disconnected {
+                status.disconnected = true;
+                status.last_frame = frame;
+            }
+        }
+    }
+
+    /// Whether the peer went silent long enough to raise
+    /// [`Event::NetworkInterrupted`] and has not been heard from since.
+    pub(crate) fn is_interrupted(&self) -> bool {
+        self.disconnect_notify_sent
+    }
+
+    /// Snapshot of this endpoint's synchronization handshake, for
+    /// [`SyncWaitError`](crate::sessions::sync_wait::SyncWaitError).
+    pub(crate) fn sync_progress(&self) -> EndpointSyncProgress<T::Address> {
+        let required = self.sync_roundtrips_required();
+        EndpointSyncProgress {
+            addr: self.peer_addr.clone(),
+            synchronized: self.is_synchronized(),
+            roundtrips_done: if self.is_synchronized() {
+                required
+            } else {
+                required.saturating_sub(self.sync_remaining_roundtrips)
+            },
+            roundtrips_required: required,
+            requests_sent: self.sync_requests_sent,
+            elapsed: self.now().saturating_duration_since(self.stats_start_time),
+        }
+    }
+
     /// Activates the bounded raw handshake trace before synchronization begins.
     ///
     /// Re-activating while still initializing replaces the unused recorder. The
@@ -1322,8 +2176,38 @@ pub(crate) fn seed_frame_advantage_for_tests(&mut self, target: i32) {
         self.time_sync_layer.seed_average_for_tests(target);
     }
 
-    pub(crate) fn is_handling_message(&self, addr: &T::Address) -> bool {
-        self.peer_addr == *addr
+    /// Whether a packet from `addr` belongs to this endpoint.
+    ///
+    /// Packets from the peer's own address always do. While a route is set,
+    /// packets arriving from the route address are matched by connection id
+    /// instead, since one relay address may carry several peers; before the
+    /// handshake has bound the peer's id, any id matches, as in
+    /// [`handle_message`](Self::handle_message).
+    pub(crate) fn is_handling_message(&self, addr: &T::Address, msg: &Message) -> bool {
+        if self.peer_addr == *addr {
+            return true;
+        }
+        self.route.as_ref() == Some(addr)
+            && (self.remote_conn_id == 0 || msg.header.conn_id == self.remote_conn_id)
+    }
+
+    /// Sends all outgoing packets to `via` instead of the peer's own address.
+    /// `None`, or the peer's own address, restores direct delivery.
+    ///
+    /// Only the send address changes; the endpoint keeps its identity
+    /// (`peer_addr`), handshake, and input state, so this is safe at any time.
+    pub(crate) fn set_route(&mut self, via: Option<T::Address>) {
+        self.route = via.filter(|via| *via != self.peer_addr);
+    }
+
+    /// The relay address outgoing packets are sent to, if one is set.
+    pub(crate) fn route(&self) -> Option<&T::Address> {
+        self.route.as_ref()
+    }
+
+    /// The address outgoing packets are sent to.
+    fn send_addr(&self) -> &T::Address {
+        self.route.as_ref().unwrap_or(&self.peer_addr)
     }
 
     pub(crate) fn peer_connect_status(&self, handle: PlayerHandle) -> ConnectionStatus {
@@ -1385,8 +2269,178 @@ pub(crate) fn send_goodbye_now(
         self.queue_goodbye(reason);
         while self.send_queue.len() > queued_before {
             if let Some(message) = self.send_queue.pop_back() {
-                socket.send_to(&message, &self.peer_addr);
+                socket.send_to(&message, self.send_addr());
+            }
+        }
+    }
+
+    /// Adopts connection IDs agreed out of band, so the handshake concludes
+    /// after a single roundtrip. Must be called before
+    /// [`synchronize`](Self::synchronize).
+    ///
+    /// The roundtrip still validates the channel and seeds the timing state.
+    /// A reply carrying a different connection ID (the peer was not told, or
+    /// was told other IDs) falls back to the full
+    /// [`num_sync_packets`](SyncConfig::num_sync_packets) handshake. A rebuilt
+    /// hot-join era draws a fresh connection ID and runs the full handshake.
+    pub(crate) fn pre_establish(&mut self, ids: PreEstablished) {
+        self.conn_id = ids.local_conn_id;
+        self.conn_id_pinned = true;
+        self.pre_established_remote_conn_id = Some(ids.remote_conn_id);
+    }
+
+    /// Sends with `conn_id` instead of a random connection ID, so a peer that
+    /// still holds it as its learned remote ID keeps accepting this endpoint
+    /// after the application restarts. A pinned ID is never re-rolled on a
+    /// collision. Must be called before [`synchronize`](Self::synchronize).
+    pub(crate) fn pin_conn_id(&mut self, conn_id: u32) {
+        self.conn_id = conn_id;
+        self.conn_id_pinned = true;
+    }
+
+    /// Lets a `SyncRequest` carrying a new connection ID restart the
+    /// handshake while this endpoint is `Disconnected`: the peer at this
+    /// address restarted and drew a fresh ID. See
+    /// [`restart_for_peer`](Self::restart_for_peer).
+    pub(crate) fn enable_peer_restart(&mut self) {
+        self.accept_peer_restart = true;
+    }
+
+    /// Lets packets carrying the peer's connection ID move this endpoint to a
+    /// new source address. See [`is_migrating_to`](Self::is_migrating_to).
+    pub(crate) fn enable_address_migration(&mut self) {
+        self.accept_address_migration = true;
+    }
+
+    /// Whether `msg`, from an address no endpoint is registered under, is
+    /// the peer continuing from a new address: the endpoint is opted in and
+    /// running, and `msg` carries the connection ID its handshake bound.
+    pub(crate) fn is_migrating_to(&self, msg: &Message) -> bool {
+        self.accept_address_migration
+            && self.state == ProtocolState::Running
+            && self.remote_conn_id != 0
+            && msg.header.conn_id == self.remote_conn_id
+    }
+
+    /// Moves this endpoint to the peer's new address. Only the address
+    /// changes; the handshake, input, and send state carry over. A route to
+    /// the new address is cleared, as in [`set_route`](Self::set_route).
+    pub(crate) fn migrate_to(&mut self, addr: T::Address) {
+        if self.route.as_ref() == Some(&addr) {
+            self.route = None;
+        }
+        self.peer_addr = addr;
+    }
+
+    /// Queues `count` keep-alives, so that the next
+    /// [`send_all_messages`](Self::send_all_messages) reaches the peer from a
+    /// new socket even if some are lost. Coalesced like any keep-alive while
+    /// the send limits hold traffic back.
+    pub(crate) fn queue_keep_alive_burst(&mut self, count: usize) {
+        for _ in 0..count {
+            self.send_keep_alive(&[]);
+        }
+    }
+
+    /// The connection ID this endpoint sends with.
+    pub(crate) fn conn_id(&self) -> u32 {
+        self.conn_id
+    }
+
+    /// The peer's connection ID, or `None` until a handshake reply binds it.
+    pub(crate) fn remote_conn_id(&self) -> Option<u32> {
+        (self.remote_conn_id != 0).then_some(self.remote_conn_id)
+    }
+
+    /// Whether `msg` is a restarted peer's first handshake: a `SyncRequest`
+    /// from a different, valid connection ID while this endpoint is
+    /// `Disconnected` and opted in.
+    fn is_peer_restart(&self, msg: &Message) -> bool {
+        self.accept_peer_restart
+            && self.state == ProtocolState::Disconnected
+            && matches!(msg.body, MessageBody::SyncRequest(_))
+            && msg.header.conn_id != self.remote_conn_id
+            && super::is_valid_conn_id(msg.header.conn_id)
+    }
+
+    /// Rebuilds this endpoint for a restarted peer and re-enters
+    /// synchronization, keeping the local connection ID and every setting
+    /// applied after construction.
+    ///
+    /// Like [`rearm_for_rejoin`](Self::rearm_for_rejoin), the rebuild goes
+    /// through [`new`](Self::new) so nothing from the previous connection
+    /// leaks into the new one. The local connection ID is kept: the old peer
+    /// is gone, and the restarted one learns it afresh. A rebuild failure
+    /// leaves `self` untouched.
+    fn restart_for_peer(&mut self) -> Result<(), FortressError> {
+        let mut rebuilt = Self::new(
+            self.handles.to_vec(),
+            self.peer_addr.clone(),
+            self.num_players,
+            self.local_players,
+            self.max_prediction,
+            self.disconnect_timeout,
+            self.disconnect_notify_start,
+            self.fps,
+            self.desync_detection,
+            self.sync_config,
+            self.protocol_config.clone(),
+            self.time_sync_config,
+        )?;
+        if self.protocol_rng.is_some() {
+            rebuilt.protocol_rng = self.protocol_rng.take();
+        }
+        #[cfg(feature = "trace-validation")]
+        {
+            rebuilt.handshake_trace = self.handshake_trace.take();
+        }
+        rebuilt.conn_id = self.conn_id;
+        rebuilt.conn_id_pinned = self.conn_id_pinned;
+        rebuilt.accept_peer_restart = true;
+        rebuilt.accept_address_migration = self.accept_address_migration;
+        rebuilt.local_handshake = self.local_handshake;
+        rebuilt.expected_handles_digest = self.expected_handles_digest;
+        rebuilt.expected_handles.clone_from(&self.expected_handles);
+        rebuilt.relayed_handles.clone_from(&self.relayed_handles);
+        rebuilt.spectator_token = self.spectator_token;
+        rebuilt.required_spectator_token = self.required_spectator_token;
+        rebuilt.route = self.route.take();
+        rebuilt.keyframe_interval = self.keyframe_interval;
+        rebuilt.keyframe_initiator = self.keyframe_initiator;
+        rebuilt.checksum_authority = self.checksum_authority;
+        rebuilt.deferred_input_capacity = self.deferred_input_capacity;
+
+        *self = rebuilt;
+        self.synchronize()
+    }
+
+    /// Draws a new connection ID after the peer answered with ours, and
+    /// forgets every outstanding sync request so a reply still carrying the
+    /// old ID can never bind.
+    fn reroll_conn_id(&mut self) {
+        let old_conn_id = self.conn_id;
+        let protocol_rng = &mut self.protocol_rng;
+        self.conn_id = draw_valid_conn_id(|| {
+            let conn_id = match protocol_rng {
+                Some(rng) => rng.gen(),
+                None => random(),
+            };
+            if conn_id == old_conn_id {
+                0
+            } else {
+                conn_id
             }
+        });
+        self.sync_random_requests.clear();
+    }
+
+    /// Roundtrips the current handshake needs: one while a pre-established
+    /// connection is unconfirmed, otherwise `num_sync_packets`.
+    fn sync_roundtrips_required(&self) -> u32 {
+        if self.pre_established_remote_conn_id.is_some() {
+            1
+        } else {
+            self.sync_config.num_sync_packets
         }
     }
 
@@ -1405,7 +2459,7 @@ pub(crate) fn synchronize(&mut self) -> Result<(), FortressError> {
             .into());
         }
         self.state = ProtocolState::Synchronizing;
-        self.sync_remaining_roundtrips = self.sync_config.num_sync_packets;
+        self.sync_remaining_roundtrips = self.sync_roundtrips_required();
         self.stats_start_time = self.now();
         #[cfg(feature = "trace-validation")]
         self.record_handshake_trace(HandshakeTraceAction::BeginSynchronization, None);
@@ -1466,19 +2520,56 @@ pub(crate) fn synchronize(&mut self) -> Result<(), FortressError> {
     /// rebuild; a rebuild failure leaves `self` untouched.
     #[cfg(feature = "hot-join")]
     pub(crate) fn rearm_for_rejoin(&mut self) -> Result<(), FortressError> {
+        self.rearm_with(
+            self.handles.to_vec(),
+            self.local_players,
+            self.desync_detection,
+            self.keyframe_interval,
+            self.keyframe_initiator,
+            self.checksum_authority,
+        )
+    }
+
+    /// Rebinds a spectator endpoint as the player endpoint for `handles` and
+    /// re-arms it like [`rearm_for_rejoin`](Self::rearm_for_rejoin).
+    ///
+    /// A spectator endpoint receives every player's inputs and sends none, so
+    /// its input layout (`local_players`), desync detection, keyframe, and
+    /// checksum authority settings are replaced with a player endpoint's. The
+    /// address, RNG stream, and conn_id era fence carry over. Errors as `rearm_for_rejoin`.
+    #[cfg(feature = "hot-join")]
+    pub(crate) fn rearm_as_player(
+        &mut self,
+        handles: Vec<PlayerHandle>,
+        local_players: usize,
+        desync_detection: DesyncDetection,
+    ) -> Result<(), FortressError> {
+        self.rearm_with(handles, local_players, desync_detection, 0, false, false)
+    }
+
+    #[cfg(feature = "hot-join")]
+    fn rearm_with(
+        &mut self,
+        handles: Vec<PlayerHandle>,
+        local_players: usize,
+        desync_detection: DesyncDetection,
+        keyframe_interval: usize,
+        keyframe_initiator: bool,
+        checksum_authority: bool,
+    ) -> Result<(), FortressError> {
         // Construct the replacement BEFORE mutating `self`: if `new` fails (the
         // should-never-happen serialization path) the existing endpoint is left
         // untouched rather than half-reset.
         let mut rebuilt = Self::new(
-            self.handles.to_vec(),
+            handles,
             self.peer_addr.clone(),
             self.num_players,
-            self.local_players,
+            local_players,
             self.max_prediction,
             self.disconnect_timeout,
             self.disconnect_notify_start,
             self.fps,
-            self.desync_detection,
+            desync_detection,
             self.sync_config,
             self.protocol_config.clone(),
             self.time_sync_config,
@@ -1502,6 +2593,12 @@ pub(crate) fn rearm_for_rejoin(&mut self) -> Result<(), FortressError> {
             rebuilt.handshake_trace = self.handshake_trace.take();
         }
         rebuilt.conn_id = super::next_conn_id(old_conn_id);
+        rebuilt.accept_peer_restart = self.accept_peer_restart;
+        rebuilt.accept_address_migration = self.accept_address_migration;
+        rebuilt.route = self.route.take();
+        rebuilt.keyframe_interval = keyframe_interval;
+        rebuilt.keyframe_initiator = keyframe_initiator;
+        rebuilt.checksum_authority = checksum_authority;
 
         *self = rebuilt;
         self.synchronize()
@@ -1511,12 +2608,28 @@ pub(crate) fn average_frame_advantage(&self) -> i32 {
         self.time_sync_layer.average_frame_advantage()
     }
 
+    #[cfg(feature = "advanced")]
+    pub(crate) fn time_sync(&self) -> &TimeSync {
+        &self.time_sync_layer
+    }
+
     pub(crate) fn peer_addr(&self) -> T::Address {
         self.peer_addr.clone()
     }
 
-    pub(crate) fn poll(&mut self, connect_status: &[ConnectionStatus]) -> Drain<'_, Event<T>> {
+    /// Queues `event` with the next process-wide stamp.
+    fn push_event(&mut self, event: Event<T>) {
+        self.event_queue.push_back((next_event_stamp(), event));
+    }
+
+    /// Runs the timers and drains the queued events, each with the stamp it
+    /// was queued with; see [`next_event_stamp`].
+    pub(crate) fn poll(
+        &mut self,
+        connect_status: &[ConnectionStatus],
+    ) -> Drain<'_, (u64, Event<T>)> {
         let now = self.now();
+        self.send_packets_left = self.protocol_config.max_packets_per_poll;
         match self.state {
             ProtocolState::Synchronizing => {
                 // An incompatible handshake is terminal. Keep the protocol in
@@ -1530,7 +2643,7 @@ pub(crate) fn poll(&mut self, connect_status: &[ConnectionStatus]) -> Drain<'_,
                         let elapsed = now - self.stats_start_time;
                         if elapsed > timeout && !self.sync_timeout_event_sent {
                             self.sync_timeout_event_sent = true;
-                            self.event_queue.push_back(Event::SyncTimeout {
+                            self.push_event(Event::SyncTimeout {
                                 elapsed_ms: elapsed.as_millis(),
                             });
                             #[cfg(feature = "trace-validation")]
@@ -1607,6 +2720,29 @@ pub(crate) fn poll(&mut self, connect_status: &[ConnectionStatus]) -> Drain<'_,
                     self.last_floor_request_time = now;
                 }
 
+                // Resend every reliable message the peer has not acknowledged.
+                if !self.reliable_in_flight.is_empty()
+                    && self.last_reliable_resend_time + self.sync_config.running_retry_interval
+                        < now
+                {
+                    self.resend_reliable_messages();
+                }
+
+                // An ended spectator stream keeps announcing its final frame,
+                // so a lost `StreamEnd` does not leave the spectator to time out.
+                if self.stream_end.is_some()
+                    && self.last_stream_end_time + self.sync_config.keepalive_interval < now
+                {
+                    self.send_stream_end();
+                }
+
+                // A one-off checksum request is resent until answered.
+                if self.checksum_request.is_some()
+                    && self.last_checksum_request_time + self.sync_config.keepalive_interval < now
+                {
+                    self.send_checksum_request();
+                }
+
                 // periodically send a quality report
                 if self.running_last_quality_report + self.protocol_config.quality_report_interval
                     < now
@@ -1614,9 +2750,14 @@ pub(crate) fn poll(&mut self, connect_status: &[ConnectionStatus]) -> Drain<'_,
                     self.send_quality_report();
                 }
 
-                // send keep alive packet if we didn't send a packet for some time
-                if self.last_send_time + self.sync_config.keepalive_interval < now {
-                    self.send_keep_alive();
+                // send keep alive packet if we didn't send a packet for some time,
+                // or if none of our recent packets carried the input ack and
+                // our connect status
+                let ack_overdue = self.protocol_config.keep_alive_acks
+                    && self.last_recv_frame().is_valid()
+                    && self.last_ack_send_time + self.sync_config.keepalive_interval < now;
+                if ack_overdue || self.last_send_time + self.sync_config.keepalive_interval < now {
+                    self.send_keep_alive(connect_status);
                 }
 
                 // trigger a NetworkInterrupted event if we didn't receive a packet for some time
@@ -1624,7 +2765,7 @@ pub(crate) fn poll(&mut self, connect_status: &[ConnectionStatus]) -> Drain<'_,
                     && self.last_recv_time + self.disconnect_notify_start < now
                 {
                     let duration: Duration = self.disconnect_timeout - self.disconnect_notify_start;
-                    self.event_queue.push_back(Event::NetworkInterrupted {
+                    self.push_event(Event::NetworkInterrupted {
                         disconnect_timeout: Duration::as_millis(&duration),
                     });
                     self.disconnect_notify_sent = true;
@@ -1634,13 +2775,18 @@ pub(crate) fn poll(&mut self, connect_status: &[ConnectionStatus]) -> Drain<'_,
                 if !self.disconnect_event_sent
                     && self.last_recv_time + self.disconnect_timeout < now
                 {
-                    self.event_queue.push_back(Event::Disconnected);
+                    self.push_event(Event::Disconnected);
                     self.disconnect_event_sent = true;
                 }
             },
             ProtocolState::Disconnected => {
                 if self.shutdown_timeout < now {
                     self.state = ProtocolState::Shutdown;
+                } else if self.last_link_closed_time + self.sync_config.keepalive_interval < now {
+                    // Keep telling the peer how much of its input we got, so a
+                    // link that heals before shutdown still converges on one
+                    // disconnect frame at both ends.
+                    self.send_link_closed();
                 }
             },
             ProtocolState::Initializing | ProtocolState::Shutdown => (),
@@ -1672,23 +2818,26 @@ fn classify_ack_frame(&self, ack_frame: Frame) -> AckDisposition {
             return AckDisposition::Ignore;
         }
 
-        let Some(newest_pending) = self.pending_output.back().map(|input| input.frame) else {
+        // `pending_output` may hold frames that never went out, so the bound is
+        // what was sent, not what is queued.
+        if !self.newest_sent_input_frame.is_valid() || ack_frame > self.newest_sent_input_frame {
             report_violation!(
                 ViolationSeverity::Warning,
                 ViolationKind::NetworkProtocol,
-                "Ignoring ack frame {} with no pending output",
-                ack_frame
+                "Rejecting input message ({}): ack frame {} but newest sent frame {}",
+                InputRejection::FutureAck,
+                ack_frame,
+                self.newest_sent_input_frame
             );
-            return AckDisposition::Ignore;
-        };
+            return AckDisposition::Reject;
+        }
 
-        if ack_frame > newest_pending {
+        if self.pending_output.is_empty() {
             report_violation!(
                 ViolationSeverity::Warning,
                 ViolationKind::NetworkProtocol,
-                "Ignoring future ack frame {} (newest pending frame {})",
-                ack_frame,
-                newest_pending
+                "Ignoring ack frame {} with no pending output",
+                ack_frame
             );
             return AckDisposition::Ignore;
         }
@@ -1697,8 +2846,14 @@ fn classify_ack_frame(&self, ack_frame: Frame) -> AckDisposition {
     }
 
     fn apply_ack_frame(&mut self, ack_frame: Frame) {
-        if self.classify_ack_frame(ack_frame) != AckDisposition::Apply {
-            return;
+        match self.classify_ack_frame(ack_frame) {
+            AckDisposition::Apply => {},
+            AckDisposition::Ignore => return,
+            AckDisposition::Reject => {
+                self.input_messages_rejected
+                    .record(InputRejection::FutureAck);
+                return;
+            },
         }
 
         while !self.pending_output.is_empty() {
@@ -1706,47 +2861,334 @@ fn apply_ack_frame(&mut self, ack_frame: Frame) {
                 if input.frame <= ack_frame {
                     // This should always succeed since we just checked front() and is_empty()
                     if let Some(popped) = self.pending_output.pop_front() {
-                        self.last_acked_input = popped;
+                        let acked = std::mem::replace(&mut self.last_acked_input, popped);
+                        self.keep_acked_output(acked);
                     }
                 } else {
                     break;
                 }
             }
         }
+        // Carriers rise with the queue, so acknowledged tags sit at the front.
+        while self
+            .frame_tags
+            .front()
+            .is_some_and(|tag| tag.carrier.is_valid() && tag.carrier <= ack_frame)
+        {
+            self.frame_tags.pop_front();
+        }
+    }
+
+    /// Keeps an input that was just replaced as `last_acked_input` for
+    /// redundant sends, dropping the oldest one beyond the
+    /// `redundant_input_frames - 1` that can be resent or serve as reference.
+    fn keep_acked_output(&mut self, acked: InputBytes) {
+        let keep = self
+            .protocol_config
+            .redundant_input_frames
+            .saturating_sub(1);
+        let consecutive = self.acked_output.back().is_none_or(|newest| {
+            newest.frame.is_valid()
+                && safe_frame_add!(newest.frame, 1, "UdpProtocol::keep_acked_output") == acked.frame
+        });
+        if keep == 0 || !acked.frame.is_valid() || !consecutive {
+            while let Some(dropped) = self.acked_output.pop_front() {
+                self.recycle_input_bytes(dropped.bytes);
+            }
+            if keep == 0 || !acked.frame.is_valid() {
+                self.recycle_input_bytes(acked.bytes);
+                return;
+            }
+        }
+        self.acked_output.push_back(acked);
+        while self.acked_output.len() > keep {
+            if let Some(dropped) = self.acked_output.pop_front() {
+                self.recycle_input_bytes(dropped.bytes);
+            }
+        }
+    }
+
+    /// How many acknowledged frames to resend ahead of a batch of `batch_len`
+    /// unacknowledged ones (see `ProtocolConfig::redundant_input_frames`).
+    ///
+    /// The peer decodes the batch against the frame before its first one, so
+    /// one kept input always stays behind as the reference. That reference
+    /// must still be in the peer's input history even if it has received
+    /// every pending frame, so the batch never reaches further back than that.
+    fn redundant_frame_count(&self, batch_len: usize, decoded_byte_cap: usize) -> usize {
+        let wanted = self
+            .protocol_config
+            .redundant_input_frames
+            .saturating_sub(batch_len);
+        if wanted == 0 {
+            return 0;
+        }
+        let peer_history = self
+            .protocol_config
+            .input_history_multiplier
+            .saturating_mul(self.max_prediction);
+        let count = wanted
+            .min(self.acked_output.len())
+            .min(peer_history.saturating_sub(self.pending_output.len()));
+        // The whole batch must stay within the decoded size limits.
+        let fits = self
+            .input_wire_frame_len(self.last_acked_input.bytes.len())
+            .and_then(|frame_len| {
+                input_batch_len_for_limits(
+                    batch_len.saturating_add(count),
+                    frame_len,
+                    self.protocol_config.pending_output_limit,
+                    decoded_byte_cap,
+                )
+            })
+            .unwrap_or(batch_len);
+        count.min(fits.saturating_sub(batch_len))
     }
 
     /*
      *  SENDING MESSAGES
      */
 
+    /// Hands the queued messages to the socket and returns how many went out.
+    ///
+    /// Messages beyond the send limits (`ProtocolConfig::max_packets_per_poll`
+    /// and `max_bytes_per_second`) stay queued for the next call, in their
+    /// queued order.
     pub(crate) fn send_all_messages(
         &mut self,
         socket: &mut Box<dyn NonBlockingSocket<T::Address>>,
-    ) {
+    ) -> usize {
         if self.state == ProtocolState::Shutdown {
             trace!(
                 "Protocol is shutting down; dropping {} messages",
                 self.send_queue.len()
             );
             self.send_queue.drain(..);
-            return;
+            return 0;
         }
 
         if self.send_queue.is_empty() {
             // avoid log spam if there's nothing to send
-            return;
+            return 0;
         }
 
+        // Drain by class so a socket that drops whatever it cannot take this
+        // poll loses inputs before the handshake, liveness, and ack messages.
+        // The send limits hold back messages in the same order.
+        // This is safe because UDP already delivers in any order:
+        // - inputs are keyed by frame and resent until acked, so an input
+        //   overtaken by anything else is simply applied later;
+        // - acks are monotone (an older ack never undoes a newer one), so an
+        //   ack overtaking an input changes nothing the input relies on;
+        // - handshake and liveness messages carry their own tokens and
+        //   timestamps and do not refer to other queued messages.
+        // Messages within a class keep their queued order, and every control
+        // message stays in the input class, so any ordering between them holds.
         trace!("Sending {} messages over socket", self.send_queue.len());
-        for msg in self.send_queue.drain(..) {
-            socket.send_to(&msg, &self.peer_addr);
+        if self.protocol_config.limits_sends() {
+            self.piggyback_input_acks();
         }
-    }
-
-    pub(crate) fn send_input(
-        &mut self,
-        inputs: &BTreeMap<PlayerHandle, PlayerInput<T::Input>>,
-        connect_status: &[ConnectionStatus],
+        self.refill_send_byte_budget();
+        let limit_bytes = self.protocol_config.max_bytes_per_second.is_some();
+        let failure_threshold = self.protocol_config.transport_error_threshold;
+        // The failure count when it reached the threshold during this send.
+        let mut transport_error = None;
+        let mut sent = 0;
+        // The first class that did not fit, and how many of its messages did.
+        let mut cutoff = None;
+        'send: for priority in SendPriority::ALL {
+            let mut sent_in_class = 0;
+            for msg in &self.send_queue {
+                if msg.body.send_priority() != priority {
+                    continue;
+                }
+                if self.send_packets_left == 0 || (limit_bytes && self.send_byte_budget <= 0) {
+                    cutoff = Some((priority, sent_in_class));
+                    break 'send;
+                }
+                // A refused send still leaves the queue: inputs are resent
+                // until acked, and the rest is as lossy as UDP itself.
+                if let Err(kind) = socket.try_send_to(msg, self.send_addr()) {
+                    trace!("Socket refused a send: {}", kind);
+                    self.consecutive_send_failures =
+                        self.consecutive_send_failures.saturating_add(1);
+                    if Some(self.consecutive_send_failures) == failure_threshold {
+                        transport_error = failure_threshold;
+                    }
+                } else {
+                    self.consecutive_send_failures = 0;
+                }
+                self.send_packets_left -= 1;
+                if limit_bytes {
+                    self.send_byte_budget = self
+                        .send_byte_budget
+                        .saturating_sub(i128::try_from(msg.encoded_len()).unwrap_or(i128::MAX));
+                }
+                sent_in_class += 1;
+                sent += 1;
+            }
+        }
+
+        let mut queue = std::mem::take(&mut self.send_queue);
+        let mut seen_in_cutoff_class = 0;
+        for _ in 0..queue.len() {
+            let Some(msg) = queue.pop_front() else {
+                break;
+            };
+            let was_sent = match cutoff {
+                None => true,
+                Some((cutoff_priority, sent_in_class)) => {
+                    let priority = msg.body.send_priority();
+                    if priority == cutoff_priority {
+                        seen_in_cutoff_class += 1;
+                        seen_in_cutoff_class <= sent_in_class
+                    } else {
+                        priority < cutoff_priority
+                    }
+                },
+            };
+            if was_sent {
+                self.recycle_message(msg);
+            } else {
+                queue.push_back(msg);
+            }
+        }
+        if cutoff.is_some() {
+            self.drop_superseded_messages(&mut queue);
+        }
+        self.send_queue = queue;
+        if let Some(consecutive_failures) = transport_error {
+            self.push_event(Event::TransportError {
+                consecutive_failures,
+            });
+        }
+        sent
+    }
+
+    /// Under the send limits a standalone `InputAck` would take the budget
+    /// ahead of the inputs, so a peer that keeps receiving inputs would only
+    /// ever send acks. Instead every queued `Input` carries the newest ack, and
+    /// standalone acks are dropped while an `Input` is queued.
+    fn piggyback_input_acks(&mut self) {
+        let ack_frame = self.last_recv_frame();
+        let mut input_queued = false;
+        for msg in &mut self.send_queue {
+            if let MessageBody::Input(body) = &mut msg.body {
+                body.ack_frame = ack_frame;
+                input_queued = true;
+            }
+        }
+        if input_queued {
+            self.send_queue
+                .retain(|msg| !matches!(msg.body, MessageBody::InputAck(_)));
+        }
+    }
+
+    /// Refills [`send_byte_budget`](Self::send_byte_budget) for the time since
+    /// the last refill, up to one second of budget.
+    fn refill_send_byte_budget(&mut self) {
+        let Some(rate) = self.protocol_config.max_bytes_per_second else {
+            return;
+        };
+        let now = self.now();
+        let elapsed = now.saturating_duration_since(self.send_budget_refilled_at);
+        let refill = elapsed.as_micros().saturating_mul(u128::from(rate)) / 1_000_000;
+        // Leave the clock alone until a whole byte accrues, so frequent sends
+        // under a tiny rate still refill.
+        if refill > 0 {
+            self.send_byte_budget = self
+                .send_byte_budget
+                .saturating_add(i128::try_from(refill).unwrap_or(i128::MAX))
+                .min(i128::from(rate));
+            self.send_budget_refilled_at = now;
+        }
+    }
+
+    /// Drops held messages that a later held message makes redundant, so the
+    /// backlog stays bounded while the send limits hold traffic back:
+    /// - an `Input` followed by another `Input` with no other input-class
+    ///   message in between, since each carries every unacknowledged frame;
+    /// - every `InputAck` but the newest, since acks are monotone;
+    /// - every `KeepAlive` but the newest.
+    fn drop_superseded_messages(&mut self, queue: &mut VecDeque<Message>) {
+        let mut next_ordered_is_input = false;
+        let mut newer_ack = false;
+        let mut newer_keepalive = false;
+        for _ in 0..queue.len() {
+            let Some(msg) = queue.pop_back() else {
+                break;
+            };
+            let superseded = match &msg.body {
+                MessageBody::Input(_) => std::mem::replace(&mut next_ordered_is_input, true),
+                MessageBody::InputAck(_) => std::mem::replace(&mut newer_ack, true),
+                MessageBody::KeepAlive(_) => std::mem::replace(&mut newer_keepalive, true),
+                body => {
+                    if body.send_priority() == SendPriority::Ordered {
+                        next_ordered_is_input = false;
+                    }
+                    false
+                },
+            };
+            if superseded {
+                self.recycle_message(msg);
+            } else {
+                queue.push_front(msg);
+            }
+        }
+    }
+
+    /// Returns a sent or dropped message's `Input` body or `KeepAlive`
+    /// connect status to its pool.
+    fn recycle_message(&mut self, msg: Message) {
+        match msg.body {
+            MessageBody::Input(body)
+                if self.spare_input_bodies.len() < self.spare_input_bodies.capacity() =>
+            {
+                self.spare_input_bodies.push(body);
+            },
+            MessageBody::KeepAlive(body)
+                if body.peer_connect_status.capacity() > 0
+                    && self.spare_keep_alive_statuses.len()
+                        < self.spare_keep_alive_statuses.capacity() =>
+            {
+                let mut status = body.peer_connect_status;
+                status.clear();
+                self.spare_keep_alive_statuses.push(status);
+            },
+            _ => {},
+        }
+    }
+
+    /// Tallies one `Input` packet into the [`CompressionStats`] and compares
+    /// each batched frame not compared yet with the frame before it. The
+    /// first frame's predecessor is the last acknowledged one; the blank
+    /// reference before any acknowledgement is not a frame and is skipped.
+    fn record_compression_stats(
+        &mut self,
+        batch_len: usize,
+        raw_bytes: usize,
+        encoded_bytes: usize,
+    ) {
+        let stats = &mut self.compression_stats;
+        stats.input_packets = stats.input_packets.saturating_add(1);
+        stats.raw_bytes = stats.raw_bytes.saturating_add(raw_bytes as u64);
+        stats.encoded_bytes = stats.encoded_bytes.saturating_add(encoded_bytes as u64);
+        let mut previous = &self.last_acked_input;
+        for current in self.pending_output.iter().take(batch_len) {
+            if current.frame > self.compression_profiled_frame {
+                if !previous.frame.is_null() {
+                    stats.record_change(&previous.bytes, &current.bytes);
+                }
+                self.compression_profiled_frame = current.frame;
+            }
+            previous = current;
+        }
+    }
+
+    pub(crate) fn send_input(
+        &mut self,
+        inputs: &VecMap<PlayerHandle, PlayerInput<T::Input>>,
+        connect_status: &[ConnectionStatus],
     ) {
         if self.state != ProtocolState::Running {
             return;
@@ -1756,22 +3198,24 @@ pub(crate) fn send_input(
         // they are no longer acking our input, disconnect before mutating the
         // local send sequence.
         if self.pending_output.len() >= self.protocol_config.pending_output_limit {
-            self.event_queue.push_back(Event::Disconnected);
+            self.push_event(Event::Disconnected);
             return;
         }
 
-        let endpoint_data = match InputBytes::try_from_inputs::<T>(self.num_players, inputs) {
-            Ok(endpoint_data) => endpoint_data,
-            Err(err) => {
-                report_violation!(
-                    ViolationSeverity::Error,
-                    ViolationKind::NetworkProtocol,
-                    "send_input failed to serialize input bytes: {:?}",
-                    err
-                );
-                return;
-            },
-        };
+        let bytes = self.take_spare_input_bytes();
+        let endpoint_data =
+            match InputBytes::try_from_inputs_into::<T>(self.num_players, inputs, bytes) {
+                Ok(endpoint_data) => endpoint_data,
+                Err(err) => {
+                    report_violation!(
+                        ViolationSeverity::Error,
+                        ViolationKind::NetworkProtocol,
+                        "send_input failed to serialize input bytes: {:?}",
+                        err
+                    );
+                    return;
+                },
+            };
         if !self.pending_input_matches_reference_len(&endpoint_data, "send_input") {
             return;
         }
@@ -1783,11 +3227,137 @@ pub(crate) fn send_input(
             self.remote_frame_advantage,
         );
 
+        // Tags queued since the previous input travel with this one.
+        for tag in self
+            .frame_tags
+            .iter_mut()
+            .rev()
+            .take_while(|tag| tag.carrier.is_null())
+        {
+            tag.carrier = endpoint_data.frame;
+        }
+
+        let suppress = self.should_suppress_input_send(&endpoint_data, connect_status);
         self.pending_output.push_back(endpoint_data);
 
+        if suppress {
+            self.suppressed_input_frames = self.suppressed_input_frames.saturating_add(1);
+            trace!(
+                "Suppressed redundant input send ({} consecutive)",
+                self.suppressed_input_frames
+            );
+            return;
+        }
         self.send_pending_output(connect_status);
     }
 
+    /// Queues a frame tag for the peer. It travels with the next input
+    /// [`send_input`](Self::send_input) queues and is resent with that input
+    /// until the peer acknowledges it. Tags still unacknowledged more than
+    /// `max_prediction` frames before `frame` are dropped and counted in
+    /// `PeerMetrics::frame_tags_dropped`.
+    pub(crate) fn queue_frame_tag(&mut self, frame: Frame, data: [u8; FRAME_TAG_LEN]) {
+        let max_prediction = i32::try_from(self.max_prediction).unwrap_or(i32::MAX);
+        let oldest_kept = frame.saturating_sub(max_prediction);
+        while let Some(stale) = self
+            .frame_tags
+            .front()
+            .filter(|tag| tag.frame < oldest_kept)
+            .copied()
+        {
+            self.frame_tags.pop_front();
+            self.frame_tags_dropped = self.frame_tags_dropped.saturating_add(1);
+            report_violation!(
+                ViolationSeverity::Warning,
+                ViolationKind::NetworkProtocol,
+                "Dropping the unacknowledged frame tag for frame {}: frame {} is more than {} frames ahead",
+                stale.frame,
+                frame,
+                self.max_prediction
+            );
+        }
+        if self.frame_tags.try_reserve(1).is_err() {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "Failed to reserve a frame tag for frame {}",
+                frame
+            );
+            return;
+        }
+        self.frame_tags.push_back(PendingFrameTag {
+            frame,
+            data,
+            carrier: Frame::NULL,
+        });
+    }
+
+    /// The per-frame send of an observer (a session with no local player):
+    /// an `Input` without input bytes that carries `connect_status` and the
+    /// ack of the peer's inputs.
+    ///
+    /// `frame` advances the time sync layer the way a sent input frame would.
+    /// Nothing is kept in `pending_output`, so a lost status is simply
+    /// superseded by the next frame's.
+    pub(crate) fn send_observer_status(
+        &mut self,
+        frame: Frame,
+        connect_status: &[ConnectionStatus],
+    ) {
+        if self.state != ProtocolState::Running {
+            return;
+        }
+        self.time_sync_layer.advance_frame(
+            frame,
+            self.local_frame_advantage,
+            self.remote_frame_advantage,
+        );
+        let mut body = Input {
+            peer_connect_status: Vec::new(),
+            start_frame: frame,
+            ack_frame: self.last_recv_frame(),
+            bytes: Vec::new(),
+        };
+        connect_status.clone_into(&mut body.peer_connect_status);
+        self.queue_message(MessageBody::Input(body));
+    }
+
+    /// Decides whether `send_input` may leave `input` in `pending_output`
+    /// without sending it right away.
+    ///
+    /// Only an input whose bytes equal the immediately preceding frame's is a
+    /// candidate. The suppression budget (`max_suppressed_frames`) forces a
+    /// send periodically so acknowledgements keep flowing, and a connect-status
+    /// transition (a slot's `disconnected` flag or `epoch` changing) is never
+    /// held back. Plain `last_frame` progress is not a transition: it rides
+    /// the next send like the suppressed inputs themselves. Everything suppressed here still reaches the
+    /// peer: it rides the next queued `Input`, the running retry interval, or
+    /// the forced send.
+    fn should_suppress_input_send(
+        &self,
+        input: &InputBytes,
+        connect_status: &[ConnectionStatus],
+    ) -> bool {
+        if !self.protocol_config.suppress_redundant_inputs
+            || self.suppressed_input_frames >= self.protocol_config.max_suppressed_frames
+            || connect_status.len() != self.last_sent_input_connect_status.len()
+            || connect_status
+                .iter()
+                .zip(&self.last_sent_input_connect_status)
+                .any(|(now, sent)| now.disconnected != sent.disconnected || now.epoch != sent.epoch)
+        {
+            return false;
+        }
+        let previous = self.pending_output.back().unwrap_or(&self.last_acked_input);
+        previous.frame.is_valid() && previous.bytes == input.bytes
+    }
+
+    /// Number of consecutive frames whose send is currently suppressed.
+    #[cfg(test)]
+    pub(crate) fn suppressed_input_frames_for_tests(&self) -> usize {
+        self.suppressed_input_frames
+    }
+
     /// Pushes a replicated input frame onto `pending_output` without advancing
     /// the time-sync layer or sending. Used to bridge the gap created by a
     /// mid-session input-delay increase: the input queue back-fills the gap
@@ -1809,7 +3379,7 @@ pub(crate) fn send_input(
     /// [`pending_output_capacity_remaining`]: Self::pending_output_capacity_remaining
     pub(crate) fn enqueue_replicated_input(
         &mut self,
-        inputs: &BTreeMap<PlayerHandle, PlayerInput<T::Input>>,
+        inputs: &VecMap<PlayerHandle, PlayerInput<T::Input>>,
     ) {
         if self.state != ProtocolState::Running {
             // Pre-running protocols have no remote yet — there is nothing to
@@ -1906,12 +3476,412 @@ pub(crate) fn flush_pending_output(&mut self, connect_status: &[ConnectionStatus
     fn pending_output_batch_len_with_cap(&self, decoded_byte_cap: usize) -> Option<usize> {
         input_batch_len_for_limits(
             self.pending_output.len(),
-            self.last_acked_input.bytes.len(),
+            self.input_wire_frame_len(self.last_acked_input.bytes.len())?,
             self.protocol_config.pending_output_limit,
             decoded_byte_cap,
         )
     }
 
+    /// Enables keyframe input compression on a spectator link.
+    ///
+    /// The spectator side (`initiator`) advertises the feature in every
+    /// handshake message, so it must enable the mode before
+    /// [`synchronize`](Self::synchronize) sends the first `SyncRequest`. The
+    /// host side only echoes the feature to a spectator that advertised it, so
+    /// a spectator without this mode never sees an unknown feature bit. The format is used once the peer's advertisement arrives,
+    /// which always precedes `Running`. A zero interval leaves the mode off.
+    pub(crate) fn enable_spectator_keyframes(&mut self, interval: usize, initiator: bool) {
+        self.keyframe_interval = interval;
+        self.keyframe_initiator = initiator;
+    }
+
+    /// Asks the host of a spectator link for its checksum reports and keeps
+    /// them in [`pending_checksums`](Self::pending_checksums) even though the
+    /// link runs without desync detection. Like
+    /// [`enable_spectator_keyframes`](Self::enable_spectator_keyframes), the
+    /// spectator side must enable this before [`synchronize`](Self::synchronize).
+    pub(crate) fn enable_checksum_authority(&mut self) {
+        self.checksum_authority = true;
+    }
+
+    /// Whether the peer is a verifying spectator that asked for this
+    /// endpoint's checksum reports.
+    pub(crate) fn wants_checksum_reports(&self) -> bool {
+        self.peer_checksum_authority
+    }
+
+    /// Buffers up to `capacity` `Input` packets that start past the next
+    /// expected frame instead of dropping them, and replays each one as soon
+    /// as the frames before it arrive. When the buffer is full, the packet
+    /// that has waited longest is evicted; the peer's retransmission still
+    /// covers its frames. A zero capacity keeps dropping such packets.
+    pub(crate) fn enable_input_reordering(&mut self, capacity: usize) {
+        self.deferred_input_capacity = capacity;
+        self.deferred_inputs.clear();
+    }
+
+    /// The start frame of the newest buffered out-of-order `Input` packet.
+    pub(crate) fn newest_deferred_input_frame(&self) -> Option<Frame> {
+        self.deferred_inputs
+            .iter()
+            .map(|body| body.start_frame)
+            .max()
+    }
+
+    /// Buffers an `Input` packet whose reference frame has not arrived yet.
+    fn defer_input(&mut self, body: &Input) {
+        // A retransmission of a buffered packet replaces it.
+        self.deferred_inputs
+            .retain(|deferred| deferred.start_frame != body.start_frame);
+        if self.deferred_inputs.len() >= self.deferred_input_capacity {
+            if let Some(evicted) = self.deferred_inputs.pop_front() {
+                report_violation!(
+                    ViolationSeverity::Warning,
+                    ViolationKind::NetworkProtocol,
+                    "Out-of-order input buffer full ({} packets): evicting the packet starting at frame {}",
+                    self.deferred_input_capacity,
+                    evicted.start_frame
+                );
+            }
+        }
+        self.deferred_inputs.push_back(body.clone());
+    }
+
+    /// Applies every buffered `Input` packet the received frames now reach.
+    /// Each replay removes its packet, so the loop ends.
+    fn replay_deferred_inputs(&mut self) {
+        loop {
+            let last_recv_frame = self.last_recv_frame();
+            if last_recv_frame == Frame::NULL {
+                return;
+            }
+            let next_expected = safe_frame_add!(
+                last_recv_frame,
+                1,
+                "UdpProtocol::replay_deferred_inputs next_expected"
+            );
+            let Some(index) = self
+                .deferred_inputs
+                .iter()
+                .position(|body| body.start_frame <= next_expected)
+            else {
+                return;
+            };
+            let Some(body) = self.deferred_inputs.remove(index) else {
+                return;
+            };
+            self.on_input(&body);
+        }
+    }
+
+    /// Advertises `local` as the handles this session owns, and, with
+    /// `expected` set, fails the handshake unless the peer claims exactly
+    /// those handles. Must be called before [`synchronize`](Self::synchronize)
+    /// sends the first `SyncRequest`.
+    pub(crate) fn claim_handles(
+        &mut self,
+        local: &[PlayerHandle],
+        expected: Option<&[PlayerHandle]>,
+    ) {
+        let mut local = local.to_vec();
+        local.sort_unstable();
+        self.local_handshake.handles_digest = handles_digest(&local);
+        self.expected_handles = expected.map(<[_]>::to_vec).unwrap_or_default();
+        self.expected_handles.sort_unstable();
+        self.expected_handles_digest = expected.map(|_| handles_digest(&self.expected_handles));
+    }
+
+    /// Checks that this endpoint can send `handle`'s inputs to the peer along
+    /// with the local players' (see [`relay_for`](Self::relay_for)) without
+    /// changing anything, so a relay between two endpoints can check both
+    /// before applying either. Returns `None` if `handle` is already relayed.
+    pub(crate) fn prepare_relay_for(
+        &self,
+        handle: PlayerHandle,
+    ) -> Result<Option<PreparedRelay>, FortressError> {
+        if !self.handshake_unsent() {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "relaying inputs after the handshake was sent",
+            }
+            .into());
+        }
+        if self.relayed_handles.contains(&handle) {
+            return Ok(None);
+        }
+        let local_players = self.local_players + 1;
+        validate_protocol_input_wire_sizes::<T>(self.handles.len(), local_players)?;
+        let last_acked_input = InputBytes::zeroed::<T>(local_players)
+            .ok_or(SerializationErrorKind::EndpointCreationFailed)?;
+        Ok(Some(PreparedRelay {
+            handle,
+            last_acked_input,
+        }))
+    }
+
+    /// Sends the prepared handle's inputs to the peer along with those of
+    /// `local`, the handles this session owns: every outgoing frame grows by
+    /// one player, and the handshake claims the handle too. The peer checks
+    /// the claim against the first `SyncRequest` it receives, so this is only
+    /// possible while that request is still queued (see
+    /// [`handshake_unsent`](Self::handshake_unsent)).
+    pub(crate) fn relay_for(&mut self, prepared: PreparedRelay, local: &[PlayerHandle]) {
+        self.last_acked_input = prepared.last_acked_input;
+        self.local_players += 1;
+        self.relayed_handles.push(prepared.handle);
+        self.relayed_handles.sort_unstable();
+
+        let mut claimed: Vec<PlayerHandle> =
+            local.iter().chain(&self.relayed_handles).copied().collect();
+        claimed.sort_unstable();
+        let digest = handles_digest(&claimed);
+        self.local_handshake.handles_digest = digest;
+        for msg in &mut self.send_queue {
+            if let MessageBody::SyncRequest(body) = &mut msg.body {
+                body.handles_digest = digest;
+            }
+        }
+    }
+
+    /// Whether the first `SyncRequest` is still queued, so nothing has reached
+    /// the peer yet.
+    pub(crate) fn handshake_unsent(&self) -> bool {
+        self.state == ProtocolState::Synchronizing
+            && self.sync_requests_sent == 1
+            && self
+                .send_queue
+                .iter()
+                .any(|msg| matches!(msg.body, MessageBody::SyncRequest(_)))
+    }
+
+    /// The handles whose inputs this endpoint relays (see
+    /// [`relay_for`](Self::relay_for)).
+    pub(crate) fn relayed_handles(&self) -> &[PlayerHandle] {
+        &self.relayed_handles
+    }
+
+    /// The frame after the newest input queued for or acknowledged by the
+    /// peer, where a relaying session's next outgoing frame starts.
+    pub(crate) fn next_input_frame(&self) -> Frame {
+        let newest = self
+            .pending_output
+            .back()
+            .unwrap_or(&self.last_acked_input)
+            .frame;
+        if newest.is_null() {
+            Frame::new(0)
+        } else {
+            newest + 1
+        }
+    }
+
+    /// Why the peer's claim of the handles behind `claimed` fails the
+    /// handshake: the lowest claimed handle registered to another endpoint
+    /// (or to this session) if the claim can be resolved, the digests
+    /// otherwise.
+    fn handle_claim_mismatch(&self, expected: u64, claimed: u64) -> IncompatibleSessionReason {
+        resolve_handles_digest(claimed, self.num_players)
+            .and_then(|claimed| {
+                claimed
+                    .into_iter()
+                    .find(|handle| self.expected_handles.binary_search(handle).is_err())
+            })
+            .map_or(
+                IncompatibleSessionReason::PlayerHandles {
+                    ours: expected,
+                    theirs: claimed,
+                },
+                |handle| IncompatibleSessionReason::DuplicateHandle { handle },
+            )
+    }
+
+    /// Proves `token` in every `SyncRequest` this spectator endpoint sends,
+    /// for a host that requires it. Must be called before
+    /// [`synchronize`](Self::synchronize) sends the first `SyncRequest`.
+    pub(crate) fn prove_spectator_access(&mut self, token: [u8; 16]) {
+        self.spectator_token = Some(token);
+    }
+
+    /// Keeps this host-side spectator endpoint silent until the peer sends a
+    /// `SyncRequest` proving `token`: requests without a valid proof are
+    /// ignored and counted, and no `SyncRequest` of ours goes out before one
+    /// arrives. A peer without the token therefore never hears from us.
+    pub(crate) fn require_spectator_access(&mut self, token: [u8; 16]) {
+        self.required_spectator_token = Some(token);
+    }
+
+    /// Whether this endpoint is still waiting for the peer to prove the
+    /// required spectator access token.
+    fn awaiting_spectator_access(&self) -> bool {
+        self.required_spectator_token.is_some() && !self.spectator_access_granted
+    }
+
+    /// Whether inputs travel in the keyframe format on this link.
+    fn keyframes_active(&self) -> bool {
+        self.keyframe_interval > 0 && self.peer_keyframes
+    }
+
+    /// Whether `Input` bodies on this link carry the resync tag byte.
+    fn input_resync_active(&self) -> bool {
+        self.protocol_config.input_resync_threshold.is_some() && self.peer_input_resync
+    }
+
+    /// Whether `Input` bodies on this link carry a frame tag section.
+    fn frame_tags_active(&self) -> bool {
+        self.protocol_config.frame_tags && self.peer_frame_tags
+    }
+
+    /// Whether message headers on this link carry sequence numbers.
+    fn sequence_numbers_active(&self) -> bool {
+        self.protocol_config.sequence_numbers && self.peer_sequence_numbers
+    }
+
+    /// Negotiated feature bits this endpoint adds to its handshake messages.
+    /// `peer_offered` reports whether the message being answered advertised
+    /// the keyframe feature. Input resync and frame tags are advertised
+    /// whenever they are enabled locally, as are sequence numbers; each only
+    /// takes effect once the peer advertises it too.
+    fn offered_features(&self, peer_offered: bool) -> u32 {
+        let mut features = 0;
+        if self.keyframe_interval > 0 && (self.keyframe_initiator || peer_offered) {
+            features |= SPECTATOR_KEYFRAME_FEATURE;
+        }
+        if self.protocol_config.input_resync_threshold.is_some() {
+            features |= INPUT_RESYNC_FEATURE;
+        }
+        if self.protocol_config.frame_tags {
+            features |= FRAME_TAG_FEATURE;
+        }
+        if self.protocol_config.sequence_numbers {
+            features |= SEQUENCE_FEATURE;
+        }
+        if self.checksum_authority {
+            features |= CHECKSUM_AUTHORITY_FEATURE;
+        }
+        features
+    }
+
+    /// Decoded bytes one input frame may occupy in the negotiated wire format:
+    /// the reference length, plus the slot mask in the keyframe format.
+    fn input_wire_frame_len(&self, reference_len: usize) -> Option<usize> {
+        if self.keyframes_active() {
+            keyframe_frame_len(reference_len, self.num_players)
+        } else {
+            Some(reference_len)
+        }
+    }
+
+    /// Encodes `inputs` against `reference` in the negotiated wire format,
+    /// appending to `out`; `scratch` is working space.
+    fn encode_inputs<'a>(
+        &self,
+        reference: &[u8],
+        inputs: impl Iterator<Item = &'a InputBytes>,
+        scratch: &mut Vec<u8>,
+        out: &mut Vec<u8>,
+    ) -> Result<(), FortressError> {
+        if self.keyframes_active() {
+            let interval = self.keyframe_interval;
+            try_keyframe_encode_into(
+                reference,
+                self.num_players,
+                inputs.map(|input| {
+                    let keyframe = usize::try_from(input.frame.as_i32())
+                        .is_ok_and(|frame| frame % interval == 0);
+                    (keyframe, &input.bytes)
+                }),
+                scratch,
+                out,
+            )
+        } else {
+            try_encode_into(reference, inputs.map(|input| &input.bytes), scratch, out)
+        }
+    }
+
+    /// Encodes `inputs` into an `Input` body, appending to `out`. With input
+    /// resync negotiated the body starts with a tag byte: a `resync` body is
+    /// encoded against a blank frame instead of `reference`, so the peer can
+    /// decode it even after pruning `reference` from its history. With frame
+    /// tags negotiated the frame tag section follows, before the batch.
+    fn encode_input_body<'a>(
+        &self,
+        reference: &[u8],
+        inputs: impl Iterator<Item = &'a InputBytes>,
+        resync: bool,
+        scratch: &mut Vec<u8>,
+        out: &mut Vec<u8>,
+    ) -> Result<(), FortressError> {
+        let resync = resync && self.input_resync_active();
+        if self.input_resync_active() {
+            out.try_reserve(1)
+                .map_err(|_err| allocation_failed("protocol.input_body", out.len()))?;
+            out.push(if resync {
+                INPUT_BODY_RESYNC
+            } else {
+                INPUT_BODY_DELTA
+            });
+        }
+        if self.frame_tags_active() {
+            self.encode_frame_tags(out)?;
+        }
+        if resync {
+            // The same blank frame the receiver starts its delta chain from.
+            let blank = InputBytes::blank_frame_bytes::<T>(reference.len())?;
+            self.encode_inputs(&blank, inputs, scratch, out)
+        } else {
+            self.encode_inputs(reference, inputs, scratch, out)
+        }
+    }
+
+    /// Appends the frame tag section of an `Input` body to `out`: a
+    /// little-endian `u16` count, then the carrier frame, tagged frame, and
+    /// payload of every tag that has a carrier.
+    fn encode_frame_tags(&self, out: &mut Vec<u8>) -> Result<(), FortressError> {
+        let carried = self
+            .frame_tags
+            .iter()
+            .take_while(|tag| tag.carrier.is_valid())
+            .take(usize::from(u16::MAX));
+        let count = carried.clone().count();
+        let len = count.saturating_mul(FRAME_TAG_WIRE_LEN).saturating_add(2);
+        out.try_reserve(len)
+            .map_err(|_err| allocation_failed("protocol.input_body.frame_tags", len))?;
+        out.extend_from_slice(&u16::try_from(count).unwrap_or(u16::MAX).to_le_bytes());
+        for tag in carried {
+            out.extend_from_slice(&tag.carrier.as_i32().to_le_bytes());
+            out.extend_from_slice(&tag.frame.as_i32().to_le_bytes());
+            out.extend_from_slice(&tag.data);
+        }
+        Ok(())
+    }
+
+    /// Splits an `Input` body into its resync flag, frame tag entries, and
+    /// encoded batch. Without input resync negotiated there is no tag byte,
+    /// and without frame tags negotiated no tag section; `None` marks a body
+    /// with a missing or unknown tag byte or a truncated tag section.
+    fn split_input_body<'a>(&self, bytes: &'a [u8]) -> Option<InputBodyParts<'a>> {
+        let (resync, rest) = if self.input_resync_active() {
+            match bytes.split_first() {
+                Some((&INPUT_BODY_DELTA, rest)) => (false, rest),
+                Some((&INPUT_BODY_RESYNC, rest)) => (true, rest),
+                _ => return None,
+            }
+        } else {
+            (false, bytes)
+        };
+        let (frame_tags, encoded) = if self.frame_tags_active() {
+            let (count, rest) = rest.split_first_chunk::<2>()?;
+            let len = usize::from(u16::from_le_bytes(*count)).checked_mul(FRAME_TAG_WIRE_LEN)?;
+            rest.split_at_checked(len)?
+        } else {
+            (&[][..], rest)
+        };
+        Some(InputBodyParts {
+            resync,
+            frame_tags,
+            encoded,
+        })
+    }
+
     /// Re-sends the pending-output batch.
     fn send_pending_output(&mut self, connect_status: &[ConnectionStatus]) {
         self.send_pending_output_with_decoded_byte_cap(
@@ -1925,7 +3895,8 @@ fn send_pending_output_with_decoded_byte_cap(
         connect_status: &[ConnectionStatus],
         decoded_byte_cap: usize,
     ) {
-        let mut body = Input::default();
+        let mut body = self.spare_input_bodies.pop().unwrap_or_default();
+        body.bytes.clear();
 
         if let Some(input) = self.pending_output.front() {
             // Verify input frames are sequential relative to last acked
@@ -1971,54 +3942,116 @@ fn send_pending_output_with_decoded_byte_cap(
                 return;
             }
 
-            // encode all pending inputs to a byte buffer
-            body.bytes = match try_encode(
-                &self.last_acked_input.bytes,
-                self.pending_output
-                    .iter()
-                    .take(batch_len)
-                    .map(|gi| &gi.bytes),
-            ) {
-                Ok(bytes) => bytes,
-                Err(err) => {
-                    report_violation!(
-                        ViolationSeverity::Error,
-                        ViolationKind::NetworkProtocol,
-                        "Failed to encode pending inputs: {:?}",
-                        err
-                    );
-                    return;
-                },
+            // Past the resync threshold the peer may have pruned our delta
+            // reference while its acks were lost, so send a batch it can
+            // decode without history until an ack trims the backlog.
+            let resync = self.input_resync_active()
+                && self
+                    .protocol_config
+                    .input_resync_threshold
+                    .is_some_and(|threshold| self.pending_output.len() > threshold);
+
+            // Resend the newest acknowledged frames ahead of the batch (see
+            // `ProtocolConfig::redundant_input_frames`), delta-encoded against
+            // the kept input before them. A resync batch needs no reference.
+            let redundant = if resync {
+                0
+            } else {
+                self.redundant_frame_count(batch_len, decoded_byte_cap)
+            };
+            // `acked_output` ends with the frame just before `last_acked_input`,
+            // so the reference sits `redundant` places from its end.
+            let kept = self.acked_output.len();
+            let reference = match redundant {
+                0 => &self.last_acked_input,
+                _ => self
+                    .acked_output
+                    .get(kept.saturating_sub(redundant))
+                    .unwrap_or(&self.last_acked_input),
             };
+            let resent = self
+                .acked_output
+                .range(kept.saturating_sub(redundant).saturating_add(1).min(kept)..)
+                .chain(std::iter::once(&self.last_acked_input))
+                .take(redundant);
+            body.start_frame = safe_frame_sub!(
+                input.frame,
+                redundant as i32,
+                "UdpProtocol::send_pending_output redundant start"
+            );
+
+            // encode the redundant and pending inputs to a byte buffer
+            let mut scratch = std::mem::take(&mut self.codec_scratch);
+            let encoded = self.encode_input_body(
+                &reference.bytes,
+                resent
+                    .clone()
+                    .chain(self.pending_output.iter().take(batch_len)),
+                resync,
+                &mut scratch,
+                &mut body.bytes,
+            );
+            self.codec_scratch = scratch;
+            if let Err(err) = encoded {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::NetworkProtocol,
+                    "Failed to encode pending inputs: {:?}",
+                    err
+                );
+                return;
+            }
+            if resync {
+                self.input_resyncs_sent = self.input_resyncs_sent.saturating_add(1);
+            }
             // Input-compression accounting (always-on): the pre-compression size
             // is the sum of the raw per-frame input bytes batched into this send;
             // the post size is the delta/RLE-encoded `body.bytes`. Their ratio is
             // the realized compression, surfaced via `peer_metrics()`.
+            let redundant_bytes: usize = resent.map(|gi| gi.bytes.len()).sum();
             let pre_compression_bytes: usize = self
                 .pending_output
                 .iter()
                 .take(batch_len)
                 .map(|gi| gi.bytes.len())
-                .sum();
+                .sum::<usize>()
+                .saturating_add(redundant_bytes);
             self.input_bytes_pre_compression = self
                 .input_bytes_pre_compression
                 .saturating_add(pre_compression_bytes as u64);
             self.input_bytes_post_compression = self
                 .input_bytes_post_compression
                 .saturating_add(body.bytes.len() as u64);
+            self.redundant_input_frames_sent = self
+                .redundant_input_frames_sent
+                .saturating_add(redundant as u64);
+            self.redundant_input_bytes_pre_compression = self
+                .redundant_input_bytes_pre_compression
+                .saturating_add(redundant_bytes as u64);
+            self.record_compression_stats(batch_len, pre_compression_bytes, body.bytes.len());
             trace!(
-                "Encoded {pre_compression_bytes} bytes from {batch_len} of {} pending output(s) into {} bytes",
+                "Encoded {pre_compression_bytes} bytes from {redundant} acknowledged and {batch_len} of {} pending output(s) into {} bytes",
                 self.pending_output.len(),
                 body.bytes.len()
             );
 
             body.ack_frame = self.last_recv_frame();
             connect_status.clone_into(&mut body.peer_connect_status);
+            connect_status.clone_into(&mut self.last_sent_input_connect_status);
 
             self.queue_message(MessageBody::Input(body));
+            if let Some(newest) = batch_len
+                .checked_sub(1)
+                .and_then(|last| self.pending_output.get(last))
+            {
+                self.newest_sent_input_frame = self.newest_sent_input_frame.max(newest.frame);
+            }
             // Real input traffic went out: the connect-status nudge (an
             // input-idle substitute) stays silent for the next interval.
             self.last_input_send_time = self.now();
+            // Every pending frame up to the batch limit is on the wire again,
+            // so the redundant-input suppression budget starts over.
+            self.suppressed_input_frames = 0;
         }
     }
 
@@ -2030,8 +4063,23 @@ fn send_input_ack(&mut self) {
         self.queue_message(MessageBody::InputAck(body));
     }
 
-    fn send_keep_alive(&mut self) {
-        self.queue_message(MessageBody::KeepAlive);
+    /// Queues a keepalive. With [`ProtocolConfig::keep_alive_acks`] it carries
+    /// our newest input ack and `connect_status`: once those acks drain
+    /// `pending_output`, no retransmitted `Input` carries our connect status
+    /// any more, so a stalled peer that has not yet agreed on a drop would
+    /// otherwise hear our view only after the next fresh input.
+    fn send_keep_alive(&mut self, connect_status: &[ConnectionStatus]) {
+        let body = if self.protocol_config.keep_alive_acks {
+            let mut peer_connect_status = self.spare_keep_alive_statuses.pop().unwrap_or_default();
+            connect_status.clone_into(&mut peer_connect_status);
+            KeepAlive {
+                ack_frame: self.last_recv_frame(),
+                peer_connect_status,
+            }
+        } else {
+            KeepAlive::default()
+        };
+        self.queue_message(MessageBody::KeepAlive(body));
     }
 
     /// Enables/disables the connect-status nudge for this endpoint. Set by the
@@ -2105,7 +4153,9 @@ pub(crate) fn connect_status_nudge_for_tests(&self) -> bool {
     /// view travels ONLY in its real `Input` traffic: the next fresh send and,
     /// crucially, the `running_retry_interval` retransmission of any
     /// still-unacked `pending_output` (a capped post-agreement survivor may
-    /// send nothing else status-bearing for an unbounded time). That is why
+    /// send nothing else status-bearing for an unbounded time), plus, with
+    /// [`ProtocolConfig::keep_alive_acks`], the keepalive that fires once no
+    /// `Input` has gone out for a keepalive interval. That is why
     /// the retry pacer `running_last_input_recv` must never be reset by
     /// progress-free packets such as this nudge: `on_input` refreshes it only
     /// when a packet stages at least one NEW frame, otherwise a still-nudging
@@ -2120,34 +4170,35 @@ fn send_connect_status_nudge(&mut self, connect_status: &[ConnectionStatus]) ->
         if !self.last_acked_input.frame.is_valid() {
             return false;
         }
-        let bytes = match try_encode(
+        let mut body = self.spare_input_bodies.pop().unwrap_or_default();
+        body.bytes.clear();
+        let mut scratch = std::mem::take(&mut self.codec_scratch);
+        let encoded = self.encode_input_body(
             &self.last_acked_input.bytes,
-            std::iter::once(&self.last_acked_input.bytes),
-        ) {
-            Ok(bytes) => bytes,
-            Err(err) => {
-                report_violation!(
-                    ViolationSeverity::Error,
-                    ViolationKind::NetworkProtocol,
-                    "Failed to encode connect-status nudge: {:?}",
-                    err
-                );
-                return false;
-            },
-        };
-        let mut body = Input {
-            peer_connect_status: Vec::new(),
-            start_frame: self.last_acked_input.frame,
-            ack_frame: self.last_recv_frame(),
-            bytes,
-        };
+            std::iter::once(&self.last_acked_input),
+            false,
+            &mut scratch,
+            &mut body.bytes,
+        );
+        self.codec_scratch = scratch;
+        if let Err(err) = encoded {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "Failed to encode connect-status nudge: {:?}",
+                err
+            );
+            return false;
+        }
+        body.start_frame = self.last_acked_input.frame;
+        body.ack_frame = self.last_recv_frame();
         connect_status.clone_into(&mut body.peer_connect_status);
         self.queue_message(MessageBody::Input(body));
         true
     }
 
     fn send_sync_request(&mut self) {
-        if self.handshake_failed.is_some() {
+        if self.handshake_failed.is_some() || self.awaiting_spectator_access() {
             return;
         }
         self.sync_requests_sent += 1;
@@ -2194,7 +4245,12 @@ fn send_sync_request(&mut self) {
         };
         #[cfg(not(feature = "trace-validation"))]
         self.sync_random_requests.insert(random_number);
-        let body = self.local_handshake.request(random_number);
+        self.last_sync_request = Some((random_number, self.now()));
+        let mut body = self.local_handshake.request(random_number);
+        body.features |= self.offered_features(self.peer_keyframes);
+        if let Some(token) = &self.spectator_token {
+            body.access_proof = access_proof(token, random_number);
+        }
         self.queue_message(MessageBody::SyncRequest(body));
         #[cfg(feature = "trace-validation")]
         self.record_handshake_trace(
@@ -2223,8 +4279,21 @@ fn send_quality_report(&mut self) {
         let body = QualityReport {
             frame_advantage,
             ping: ping_timestamp,
+            stalled: self.advance_stalled,
         };
 
+        // A report still queued is stale: refresh it in place rather than send two.
+        if let Some(queued) = self
+            .send_queue
+            .iter_mut()
+            .find_map(|msg| match &mut msg.body {
+                MessageBody::QualityReport(report) => Some(report),
+                _ => None,
+            })
+        {
+            *queued = body;
+            return;
+        }
         self.queue_message(MessageBody::QualityReport(body));
     }
 
@@ -2232,7 +4301,11 @@ fn queue_message(&mut self, body: MessageBody) {
         trace!("Queuing message to {:?}: {:?}", self.peer_addr, body);
 
         // set the header
-        let header = MessageHeader::new(self.conn_id);
+        let mut header = MessageHeader::new(self.conn_id);
+        if self.sequence_numbers_active() {
+            header = header.with_sequence(self.next_send_sequence);
+            self.next_send_sequence = self.next_send_sequence.wrapping_add(1);
+        }
         let msg = Message { header, body };
         let encoded_len = msg.encoded_len();
 
@@ -2272,6 +4345,16 @@ fn queue_message(&mut self, body: MessageBody) {
 
         self.packets_sent = self.packets_sent.saturating_add(1);
         self.last_send_time = self.now();
+        // A bare `InputAck` carries no connect status, so it does not hold
+        // off the status-bearing keepalive.
+        let carries_ack = match &msg.body {
+            MessageBody::Input(_) => true,
+            MessageBody::KeepAlive(body) => body.ack_frame.is_valid(),
+            _ => false,
+        };
+        if carries_ack {
+            self.last_ack_send_time = self.last_send_time;
+        }
         // Exact encoded payload bytes (D1 fix): the previous `size_of_val(&msg)`
         // measured the constant in-memory `Message` enum size, not what the
         // socket serializes, so `kbps_sent` was fiction. `Message::encoded_len`
@@ -2294,6 +4377,19 @@ fn queue_message(&mut self, body: MessageBody) {
     pub(crate) fn handle_message(&mut self, msg: &Message) {
         trace!("Handling message from {:?}: {:?}", self.peer_addr, msg);
 
+        if self.is_peer_restart(msg) {
+            trace!("Peer restarted with a new conn_id; restarting the handshake");
+            if let Err(err) = self.restart_for_peer() {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::InternalError,
+                    "Failed to restart the handshake for a restarted peer: {}",
+                    err
+                );
+                return;
+            }
+        }
+
         // Per-peer receive accounting (always-on, exact encoded Fortress
         // payload size). Counted for every message delivered to this endpoint
         // *before* any protocol-state filter below, so `bytes_received` reflects
@@ -2317,6 +4413,26 @@ pub(crate) fn handle_message(&mut self, msg: &Message) {
             return;
         }
 
+        // Drop network-level duplicates and messages too old for the window
+        // before they reach any handler.
+        if let Some(sequence) = msg.header.sequence() {
+            if self.sequence_numbers_active() {
+                match self.sequence_window.accept(sequence) {
+                    SequenceVerdict::Fresh => {},
+                    SequenceVerdict::Duplicate => {
+                        trace!("Dropping duplicate message {sequence}");
+                        self.duplicates_dropped = self.duplicates_dropped.saturating_add(1);
+                        return;
+                    },
+                    SequenceVerdict::Stale => {
+                        trace!("Dropping stale message {sequence}");
+                        self.stale_dropped = self.stale_dropped.saturating_add(1);
+                        return;
+                    },
+                }
+            }
+        }
+
         if !self.message_allowed_in_current_state(&msg.body) {
             trace!(
                 "Dropping {:?} while protocol is in {:?}",
@@ -2335,28 +4451,42 @@ pub(crate) fn handle_message(&mut self, msg: &Message) {
             return;
         }
 
-        // update time when we last received packages
-        self.last_recv_time = self.now();
-
-        // if the connection has been marked as interrupted, send an event to signal we are receiving again
-        if self.disconnect_notify_sent && self.state == ProtocolState::Running {
-            trace!("Received message on interrupted protocol; sending NetworkResumed event");
-            self.disconnect_notify_sent = false;
-            self.event_queue.push_back(Event::NetworkResumed);
+        // A closed-link notice is the peer's verdict about us, not evidence the
+        // link is healthy, so it must not hold off our own timeout.
+        if !matches!(msg.body, MessageBody::LinkClosed(_)) {
+            // update time when we last received packages
+            self.last_recv_time = self.now();
+
+            // if the connection has been marked as interrupted, send an event to signal we are receiving again
+            if self.disconnect_notify_sent && self.state == ProtocolState::Running {
+                trace!("Received message on interrupted protocol; sending NetworkResumed event");
+                self.disconnect_notify_sent = false;
+                self.push_event(Event::NetworkResumed);
+            }
         }
 
         // handle the message
         match &msg.body {
             MessageBody::SyncRequest(body) => self.on_sync_request(*body),
             MessageBody::SyncReply(body) => self.on_sync_reply(msg.header, *body),
-            MessageBody::Input(body) => self.on_input(body),
+            // Once disconnected, only the ack still matters: it reports how
+            // much of our input the peer received.
+            MessageBody::Input(body) if self.state == ProtocolState::Disconnected => {
+                self.apply_ack_frame(body.ack_frame);
+            },
+            MessageBody::Input(body) => {
+                self.on_input(body);
+                if !self.deferred_inputs.is_empty() {
+                    self.replay_deferred_inputs();
+                }
+            },
             MessageBody::InputAck(body) => self.on_input_ack(*body),
             MessageBody::QualityReport(body) => self.on_quality_report(body),
             MessageBody::QualityReply(body) => self.on_quality_reply(body),
             MessageBody::ChecksumReport(body) => self.on_checksum_report(body),
             MessageBody::FloorRequest(body) => self.on_floor_request(body),
             MessageBody::FloorReply(body) => self.on_floor_reply(body),
-            MessageBody::KeepAlive => (),
+            MessageBody::KeepAlive(body) => self.on_keep_alive(body),
             MessageBody::Goodbye(body) => self.on_goodbye(*body),
             MessageBody::DropPrepare(body) => {
                 self.on_drop_control_message(DropControlMessage::Prepare(body.clone()));
@@ -2373,6 +4503,17 @@ pub(crate) fn handle_message(&mut self, msg: &Message) {
             MessageBody::DropAbort(body) => {
                 self.on_drop_control_message(DropControlMessage::Abort(*body));
             },
+            MessageBody::MatchPause(body) => {
+                self.on_match_pause_message(MatchPauseMessage::Control(*body));
+            },
+            MessageBody::MatchPauseAck(body) => {
+                self.on_match_pause_message(MatchPauseMessage::Ack(*body));
+            },
+            MessageBody::StreamEnd(body) => self.on_stream_end(*body),
+            MessageBody::ReliableMessage(body) => self.on_reliable_message(body),
+            MessageBody::ReliableAck(body) => self.on_reliable_ack(*body),
+            MessageBody::LinkClosed(body) => self.on_link_closed(*body),
+            MessageBody::ChecksumRequest(body) => self.on_checksum_request(*body),
             #[cfg(feature = "hot-join")]
             MessageBody::JoinRequest(body) => self.on_join_request(body),
             #[cfg(feature = "hot-join")]
@@ -2409,19 +4550,41 @@ fn message_allowed_in_current_state(&self, body: &MessageBody) -> bool {
                 )
             },
             ProtocolState::Running => true,
-            ProtocolState::Disconnected => matches!(body, MessageBody::SyncRequest(_)),
+            ProtocolState::Disconnected => matches!(
+                body,
+                MessageBody::SyncRequest(_)
+                    | MessageBody::Input(_)
+                    | MessageBody::InputAck(_)
+                    | MessageBody::KeepAlive(_)
+                    | MessageBody::LinkClosed(_)
+            ),
             ProtocolState::Shutdown => false,
         }
     }
 
     /// Upon receiving a `SyncRequest`, answer with a `SyncReply` with the proper data
     fn on_sync_request(&mut self, body: SyncRequest) {
+        if let Some(token) = &self.required_spectator_token {
+            if body.access_proof != access_proof(token, body.random_request) {
+                self.unauthorized_sync_requests += 1;
+                return;
+            }
+            if !self.spectator_access_granted {
+                self.spectator_access_granted = true;
+                // Our own handshake was held back until now.
+                if self.state == ProtocolState::Synchronizing {
+                    self.send_sync_request();
+                }
+            }
+        }
         #[cfg(feature = "trace-validation")]
         let already_incompatible = self.handshake_failed.is_some();
         // Always answer with our own configuration, including after our local
         // handshake has failed, so the requester can independently diagnose
         // the same incompatibility with its own ours/theirs orientation.
-        let reply_body = self.local_handshake.reply(body.random_request);
+        let mut reply_body = self.local_handshake.reply(body.random_request);
+        reply_body.features |=
+            self.offered_features(body.features & SPECTATOR_KEYFRAME_FEATURE != 0);
         self.queue_message(MessageBody::SyncReply(reply_body));
 
         if self.state == ProtocolState::Synchronizing {
@@ -2451,11 +4614,138 @@ fn on_sync_request(&mut self, body: SyncRequest) {
 
     fn on_goodbye(&mut self, _body: Goodbye) {
         if !self.disconnect_event_sent {
-            self.event_queue.push_back(Event::Disconnected);
+            self.push_event(Event::Disconnected);
             self.disconnect_event_sent = true;
         }
     }
 
+    /// Reports the first `StreamEnd` of this era; the host's resends are
+    /// duplicates.
+    fn on_stream_end(&mut self, body: StreamEnd) {
+        if !self.stream_end_received {
+            self.stream_end_received = true;
+            self.push_event(Event::StreamEnded {
+                final_frame: body.final_frame,
+            });
+        }
+    }
+
+    /// Records the newest frame the peer asked for our checksum of; the
+    /// session answers once it holds the state at that frame.
+    fn on_checksum_request(&mut self, body: ChecksumRequest) {
+        if self
+            .pending_checksum_request
+            .is_none_or(|pending| body.frame > pending)
+        {
+            self.pending_checksum_request = Some(body.frame);
+        }
+    }
+
+    /// Records how much of our input a peer that closed the link received.
+    /// A frame beyond anything we sent is ignored.
+    fn on_link_closed(&mut self, body: LinkClosed) {
+        let frame = body.last_received_frame;
+        if frame.is_null() {
+            return;
+        }
+        if !self.newest_sent_input_frame.is_valid() || frame > self.newest_sent_input_frame {
+            report_violation!(
+                ViolationSeverity::Warning,
+                ViolationKind::NetworkProtocol,
+                "Ignoring closed-link notice for frame {} beyond newest sent frame {}",
+                frame,
+                self.newest_sent_input_frame
+            );
+            return;
+        }
+        self.peer_closed_frame = std::cmp::max(self.peer_closed_frame, frame);
+    }
+
+    /// Buffers a reliable message within the window, delivers every message
+    /// that is now contiguous, and acknowledges. Duplicates and messages
+    /// beyond the window are only acknowledged: the peer resends the latter.
+    fn on_reliable_message(&mut self, body: &ReliableMessage) {
+        let ahead = usize::try_from(body.sequence.wrapping_sub(self.reliable_next_delivery))
+            .unwrap_or(usize::MAX);
+        if ahead < self.protocol_config.reliable_window {
+            self.buffer_reliable_message(ahead, &body.payload);
+            while matches!(self.reliable_received.front(), Some(Some(_))) {
+                if let Some(Some(payload)) = self.reliable_received.pop_front() {
+                    self.push_event(Event::ReliableMessage { payload });
+                }
+                self.reliable_next_delivery = self.reliable_next_delivery.wrapping_add(1);
+            }
+        }
+
+        // One cumulative ack answers every message of a burst.
+        let next_sequence = self.reliable_next_delivery;
+        for msg in &mut self.send_queue {
+            if let MessageBody::ReliableAck(ack) = &mut msg.body {
+                ack.next_sequence = next_sequence;
+                return;
+            }
+        }
+        self.queue_message(MessageBody::ReliableAck(ReliableAck { next_sequence }));
+    }
+
+    /// Stores a copy of `payload` in slot `ahead` of `reliable_received`
+    /// unless it already holds one.
+    fn buffer_reliable_message(&mut self, ahead: usize, payload: &[u8]) {
+        if self
+            .reliable_received
+            .get(ahead)
+            .is_some_and(Option::is_some)
+        {
+            return;
+        }
+        let slots = (ahead + 1).saturating_sub(self.reliable_received.len());
+        let mut copy = Vec::new();
+        // alloc-bound: `ahead` is below `ProtocolConfig::reliable_window` and the payload was decoded from one packet.
+        if self.reliable_received.try_reserve(slots).is_err()
+            || copy.try_reserve_exact(payload.len()).is_err()
+        {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "Failed to buffer a {}-byte reliable message; the peer will resend it",
+                payload.len()
+            );
+            return;
+        }
+        copy.extend_from_slice(payload);
+        while self.reliable_received.len() <= ahead {
+            self.reliable_received.push_back(None);
+        }
+        if let Some(slot) = self.reliable_received.get_mut(ahead) {
+            *slot = Some(copy);
+        }
+    }
+
+    /// Releases every reliable message before the ack's sequence number.
+    fn on_reliable_ack(&mut self, body: ReliableAck) {
+        let Some(oldest) = self.reliable_in_flight.front().map(|msg| msg.sequence) else {
+            return;
+        };
+        let acked = body.next_sequence.wrapping_sub(oldest);
+        // Acks are cumulative, so one from before `oldest` is stale.
+        if acked > u32::MAX / 2 {
+            return;
+        }
+        let acked = usize::try_from(acked).unwrap_or(usize::MAX);
+        if acked > self.reliable_in_flight.len() {
+            report_violation!(
+                ViolationSeverity::Warning,
+                ViolationKind::NetworkProtocol,
+                "Ignoring a reliable ack for sequence {} with only {} messages in flight from {}",
+                body.next_sequence,
+                self.reliable_in_flight.len(),
+                oldest
+            );
+            return;
+        }
+        self.reliable_in_flight.drain(..acked);
+    }
+
     /// Stages one running-state D14 control message for the session layer.
     fn on_drop_control_message(&mut self, message: DropControlMessage) {
         if self.received_drop_messages.len() >= MAX_RECEIVED_DROP_MESSAGES {
@@ -2473,6 +4763,22 @@ fn on_drop_control_message(&mut self, message: DropControlMessage) {
         self.received_drop_messages.push_back(message);
     }
 
+    fn on_match_pause_message(&mut self, message: MatchPauseMessage) {
+        if self.received_match_pause_messages.len() >= MAX_RECEIVED_MATCH_PAUSE_MESSAGES {
+            if !self.match_pause_mailbox_warning_sent {
+                self.match_pause_mailbox_warning_sent = true;
+                report_violation!(
+                    ViolationSeverity::Warning,
+                    ViolationKind::NetworkProtocol,
+                    "match pause mailbox reached its {}-message bound; dropping further pause messages until the session drains it",
+                    MAX_RECEIVED_MATCH_PAUSE_MESSAGES
+                );
+            }
+            return;
+        }
+        self.received_match_pause_messages.push_back(message);
+    }
+
     /// Upon receiving a `SyncReply`, check validity and either continue the synchronization process or conclude synchronization.
     fn on_sync_reply(&mut self, header: MessageHeader, body: SyncReply) {
         // ignore sync replies when not syncing
@@ -2536,6 +4842,27 @@ fn on_sync_reply(&mut self, header: MessageHeader, body: SyncReply) {
             );
             return;
         }
+        // The peer answered with our own connection ID. Binding it would let
+        // our own packets, reflected back, pass the filter: re-roll (unless
+        // the ID is pinned) and wait for a reply carrying another ID. A
+        // pre-established peer ID is the agreed one, never a collision.
+        if self.remote_conn_id == 0
+            && header.conn_id == self.conn_id
+            && self.pre_established_remote_conn_id != Some(header.conn_id)
+        {
+            report_violation!(
+                ViolationSeverity::Warning,
+                ViolationKind::Synchronization,
+                "Peer {:?} answered with our own connection ID {:#010x}",
+                self.peer_addr,
+                header.conn_id
+            );
+            if !self.conn_id_pinned {
+                self.reroll_conn_id();
+            }
+            self.send_sync_request();
+            return;
+        }
         // A correct random echo binds the header connection ID. Lock it
         // on the first successful roundtrip so later synchronized-state
         // Goodbyes can be accepted without opening the pre-binding
@@ -2543,6 +4870,22 @@ fn on_sync_reply(&mut self, header: MessageHeader, body: SyncReply) {
         if self.remote_conn_id == 0 {
             self.remote_conn_id = header.conn_id;
         }
+        if let Some(expected) = self.pre_established_remote_conn_id.take() {
+            if header.conn_id == expected {
+                self.seed_round_trip_time(body.random_reply);
+            } else {
+                // The peer does not share our out-of-band IDs: the channel works,
+                // so count this roundtrip toward the full handshake.
+                report_violation!(
+                    ViolationSeverity::Warning,
+                    ViolationKind::Synchronization,
+                    "Pre-established connection ID mismatch (expected {:#010x}, got {:#010x}); falling back to the full handshake",
+                    expected,
+                    header.conn_id
+                );
+                self.sync_remaining_roundtrips = self.sync_config.num_sync_packets;
+            }
+        }
         // the sync reply is good, so we send a sync request again until we have finished the required roundtrips. Then, we can conclude the syncing process.
         self.sync_remaining_roundtrips -= 1;
         let elapsed_ms = (self.now() - self.stats_start_time).as_millis();
@@ -2554,7 +4897,7 @@ fn on_sync_reply(&mut self, header: MessageHeader, body: SyncReply) {
                 total_requests_sent: self.sync_requests_sent,
                 elapsed_ms,
             };
-            self.event_queue.push_back(evt);
+            self.push_event(evt);
             #[cfg(feature = "trace-validation")]
             self.record_handshake_trace(
                 HandshakeTraceAction::HandleReply {
@@ -2569,7 +4912,7 @@ fn on_sync_reply(&mut self, header: MessageHeader, body: SyncReply) {
             // switch to running state
             self.state = ProtocolState::Running;
             // register an event
-            self.event_queue.push_back(Event::Synchronized);
+            self.push_event(Event::Synchronized);
             #[cfg(feature = "trace-validation")]
             self.record_handshake_trace(
                 HandshakeTraceAction::HandleReply {
@@ -2581,13 +4924,49 @@ fn on_sync_reply(&mut self, header: MessageHeader, body: SyncReply) {
         }
     }
 
+    /// Seeds the RTT from the sync roundtrip answered by `random_reply`, if it
+    /// answers the latest request; quality reports refine it afterwards.
+    fn seed_round_trip_time(&mut self, random_reply: u32) {
+        if let Some((random, sent_at)) = self.last_sync_request {
+            if random == random_reply {
+                let now = self.now();
+                self.round_trip_time = now.saturating_duration_since(sent_at).as_millis();
+                self.rtt_updated_at = now;
+            }
+        }
+    }
+
     fn observe_handshake(&mut self, theirs: HandshakeConfig) {
         if self.handshake_failed.is_some() {
             return;
         }
-        if let Some(reason) = self.local_handshake.first_mismatch(theirs) {
+        let handles_mismatch = self
+            .expected_handles_digest
+            .filter(|&expected| expected != theirs.handles_digest)
+            .map(|expected| self.handle_claim_mismatch(expected, theirs.handles_digest));
+        if let Some(reason) = self
+            .local_handshake
+            .first_mismatch(theirs)
+            .or(handles_mismatch)
+        {
             self.handshake_failed = Some(reason);
-            self.event_queue.push_back(Event::Incompatible { reason });
+            self.push_event(Event::Incompatible { reason });
+            return;
+        }
+        if theirs.features & SPECTATOR_KEYFRAME_FEATURE != 0 {
+            self.peer_keyframes = true;
+        }
+        if theirs.features & INPUT_RESYNC_FEATURE != 0 {
+            self.peer_input_resync = true;
+        }
+        if theirs.features & FRAME_TAG_FEATURE != 0 {
+            self.peer_frame_tags = true;
+        }
+        if theirs.features & SEQUENCE_FEATURE != 0 {
+            self.peer_sequence_numbers = true;
+        }
+        if theirs.features & CHECKSUM_AUTHORITY_FEATURE != 0 {
+            self.peer_checksum_authority = true;
         }
     }
 
@@ -2613,16 +4992,31 @@ fn observe_handshake(&mut self, theirs: HandshakeConfig) {
     /// decodable ones, narrowing the N>=3 disconnect-convergence window under
     /// asymmetric loss.
     ///
-    /// Callers MUST validate `body.peer_connect_status.len() == num_players`
-    /// first; a mismatched length is silently ignored here (the zipped iterator
-    /// stops at the shorter side) but should already have been rejected upstream.
-    fn merge_peer_connect_status(&mut self, body: &Input) {
+    /// `start_frame` is the first frame of the carrying `Input`, or
+    /// `Frame::NULL` for a `KeepAlive`. Callers MUST validate
+    /// `peer_connect_status.len() == num_players` first; a mismatched length is
+    /// silently ignored here (the zipped iterator stops at the shorter side) but
+    /// should already have been rejected upstream.
+    fn merge_peer_connect_status(
+        &mut self,
+        start_frame: Frame,
+        peer_connect_status: &[ConnectionStatus],
+    ) {
+        // A peer can only have received another player's inputs up to the
+        // prediction window past the newest input of its own it sent, as that
+        // player cannot run further ahead of it. Connected progress claimed
+        // beyond that is not taken at its word.
+        let max_prediction = i32::try_from(self.max_prediction).unwrap_or(i32::MAX);
+        let progress_ceiling =
+            std::cmp::max(self.last_recv_frame(), start_frame).saturating_add(max_prediction);
         #[cfg(feature = "hot-join")]
         let floors = &self.reactivation_floor;
+        let handles = &self.handles;
+        let claims = &mut self.disconnect_claims;
         for (slot, (local, remote)) in self
             .peer_connect_status
             .iter_mut()
-            .zip(body.peer_connect_status.iter())
+            .zip(peer_connect_status.iter())
             .enumerate()
         {
             // Reactivation floor (N-peer hot-join): ignore stale DISCONNECTED
@@ -2641,8 +5035,22 @@ fn merge_peer_connect_status(&mut self, body: &Input) {
             {
                 continue;
             }
-            #[cfg(not(feature = "hot-join"))]
-            let _ = slot;
+            // Only the slot's owner is taken at its word that the slot
+            // disconnected. Anyone else's claim waits for the session to
+            // corroborate it, see `accept_disconnect_claim`.
+            if remote.disconnected
+                && !local.disconnected
+                && !handles.iter().any(|handle| handle.as_usize() == slot)
+            {
+                if let Some(claim) = claims.get_mut(slot) {
+                    *claim = Some(claim.map_or(remote.last_frame, |claimed| {
+                        std::cmp::min(claimed, remote.last_frame)
+                    }));
+                }
+                let claimed = std::cmp::min(remote.last_frame, progress_ceiling);
+                local.last_frame = std::cmp::max(local.last_frame, claimed);
+                continue;
+            }
             if remote.disconnected || local.disconnected {
                 if local.disconnected && remote.disconnected {
                     // Both views are freeze frames: take the lower so a relayed
@@ -2665,8 +5073,10 @@ fn merge_peer_connect_status(&mut self, body: &Input) {
                 // clobber) and do NOT resurrect the slot below.
                 local.disconnected = true;
             } else {
-                // Both connected: monotone forward progress.
-                local.last_frame = std::cmp::max(local.last_frame, remote.last_frame);
+                // Both connected: monotone forward progress, bounded by the
+                // frames the peer itself has sent.
+                let claimed = std::cmp::min(remote.last_frame, progress_ceiling);
+                local.last_frame = std::cmp::max(local.last_frame, claimed);
             }
         }
     }
@@ -2681,15 +5091,23 @@ fn on_input(&mut self, body: &Input) {
         }
 
         let ack_disposition = self.classify_ack_frame(body.ack_frame);
+        if ack_disposition == AckDisposition::Reject {
+            self.input_messages_rejected
+                .record(InputRejection::FutureAck);
+            return;
+        }
 
         if body.peer_connect_status.len() != self.num_players {
             report_violation!(
                 ViolationSeverity::Error,
                 ViolationKind::NetworkProtocol,
-                "Received input with {} connection-status entries, expected {}",
+                "Rejecting input message ({}): {} connection-status entries, expected {}",
+                InputRejection::ConnectStatusLength,
                 body.peer_connect_status.len(),
                 self.num_players
             );
+            self.input_messages_rejected
+                .record(InputRejection::ConnectStatusLength);
             return;
         }
 
@@ -2697,9 +5115,28 @@ fn on_input(&mut self, body: &Input) {
             report_violation!(
                 ViolationSeverity::Error,
                 ViolationKind::NetworkProtocol,
-                "Received input with invalid start frame {}",
+                "Rejecting input message ({}): start frame {}",
+                InputRejection::InvalidStartFrame,
                 body.start_frame
             );
+            self.input_messages_rejected
+                .record(InputRejection::InvalidStartFrame);
+            return;
+        }
+
+        // A batch decodes to at most `pending_output_limit` frames, and every
+        // frame it stages must leave `max_prediction` frames of headroom for the
+        // session's frame arithmetic. Checked before the gossip merge so a
+        // message that fails it is dropped whole.
+        let max_batch_end = i32::try_from(
+            self.protocol_config
+                .pending_output_limit
+                .saturating_add(self.max_prediction),
+        )
+        .ok()
+        .and_then(|span| body.start_frame.checked_add(span));
+        if max_batch_end.is_none() {
+            self.reject_frame_range(body.start_frame, self.protocol_config.pending_output_limit);
             return;
         }
 
@@ -2712,7 +5149,31 @@ fn on_input(&mut self, body: &Input) {
         // are dropped — narrowing the convergence window under asymmetric loss.
         // Length/validity are already checked above; ack/input-staging/event
         // ordering and the recv-time bump intentionally remain gated on decode.
-        self.merge_peer_connect_status(body);
+        self.merge_peer_connect_status(body.start_frame, &body.peer_connect_status);
+
+        // An observer owns no player slot: its `Input` carries only its connect
+        // status and the ack of our inputs, never input bytes.
+        if self.handles.is_empty() {
+            if ack_disposition == AckDisposition::Apply {
+                self.apply_ack_frame(body.ack_frame);
+            }
+            return;
+        }
+
+        let Some(InputBodyParts {
+            resync,
+            frame_tags,
+            encoded,
+        }) = self.split_input_body(&body.bytes)
+        else {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "Received input body without a valid resync tag or frame tag section ({} bytes)",
+                body.bytes.len()
+            );
+            return;
+        };
 
         // Validate that received inputs are in a recoverable order.
         // If we receive an input for a frame that's too far ahead, we can't decode it
@@ -2722,6 +5183,10 @@ fn on_input(&mut self, body: &Input) {
         let next_expected =
             safe_frame_add!(last_recv_frame, 1, "UdpProtocol::on_input next_expected");
         if last_recv_frame != Frame::NULL && next_expected < body.start_frame {
+            if self.deferred_input_capacity > 0 {
+                self.defer_input(body);
+                return;
+            }
             report_violation!(
                 ViolationSeverity::Warning,
                 ViolationKind::NetworkProtocol,
@@ -2740,31 +5205,72 @@ fn on_input(&mut self, body: &Input) {
             safe_frame_sub!(body.start_frame, 1, "UdpProtocol::on_input decode_frame")
         };
 
+        // A resync batch is encoded against a blank frame and needs no
+        // history; any other batch needs the input before its start frame.
+        let blank_input;
+        let reference = if resync {
+            blank_input = InputBytes::zeroed::<T>(self.handles.len());
+            blank_input.as_ref().map(|blank| &blank.bytes)
+        } else {
+            self.recv_inputs
+                .get(&decode_frame)
+                .map(|input| &input.bytes)
+        };
+
         // if we have the necessary input saved, we decode
-        if let Some(decode_inp) = self.recv_inputs.get(&decode_frame) {
-            let max_decoded_input_bytes = match input_batch_decoded_byte_limit(
-                decode_inp.bytes.len(),
-                self.protocol_config.pending_output_limit,
-            ) {
-                Some(max) => max,
-                None => {
-                    report_violation!(
+        if let Some(reference) = reference {
+            let max_decoded_input_bytes =
+                match self
+                    .input_wire_frame_len(reference.len())
+                    .and_then(|frame_len| {
+                        input_batch_decoded_byte_limit(
+                            frame_len,
+                            self.protocol_config.pending_output_limit,
+                        )
+                    }) {
+                    Some(max) => max,
+                    None => {
+                        report_violation!(
                         ViolationSeverity::Error,
                         ViolationKind::NetworkProtocol,
                         "Input decode limit overflow: reference bytes {} * pending output limit {}",
-                        decode_inp.bytes.len(),
+                        reference.len(),
                         self.protocol_config.pending_output_limit
                     );
-                    return;
-                },
-            };
+                        return;
+                    },
+                };
 
-            let recv_inputs = match decode_with_max_len(
-                &decode_inp.bytes,
-                &body.bytes,
-                max_decoded_input_bytes,
-            ) {
-                Ok(inputs) => inputs,
+            let frame_len = reference.len();
+            let mut scratch = std::mem::take(&mut self.codec_scratch);
+            let mut decoded = std::mem::take(&mut self.decoded_inputs);
+            decoded.clear();
+            let result = if self.keyframes_active() {
+                keyframe_decode_into(
+                    reference,
+                    self.num_players,
+                    encoded,
+                    max_decoded_input_bytes,
+                    &mut scratch,
+                    &mut decoded,
+                )
+            } else {
+                decode_into(
+                    reference,
+                    encoded,
+                    max_decoded_input_bytes,
+                    &mut scratch,
+                    &mut decoded,
+                )
+            };
+            self.codec_scratch = scratch;
+            let staged = match result {
+                Ok(()) => self.stage_decoded_inputs(
+                    body.start_frame,
+                    last_recv_frame,
+                    &decoded,
+                    frame_len,
+                ),
                 Err(e) => {
                     report_violation!(
                         ViolationSeverity::Error,
@@ -2772,64 +5278,14 @@ fn on_input(&mut self, body: &Input) {
                         "Failed to decode input packet: {:?}. Packet may be corrupted.",
                         e
                     );
-                    return;
+                    false
                 },
             };
-
-            let mut staged_frames = Vec::new();
-            if staged_frames.try_reserve(recv_inputs.len()).is_err() {
-                report_violation!(
-                    ViolationSeverity::Error,
-                    ViolationKind::NetworkProtocol,
-                    "Failed to reserve {} decoded input frame(s)",
-                    recv_inputs.len()
-                );
+            self.decoded_inputs = decoded;
+            if !staged {
                 return;
             }
 
-            for (i, inp) in recv_inputs.into_iter().enumerate() {
-                let Ok(frame_offset) = i32::try_from(i) else {
-                    report_violation!(
-                        ViolationSeverity::Error,
-                        ViolationKind::NetworkProtocol,
-                        "Decoded input batch has too many frames to represent as i32 offsets"
-                    );
-                    return;
-                };
-                let Some(inp_frame) = body.start_frame.checked_add(frame_offset) else {
-                    report_violation!(
-                        ViolationSeverity::Error,
-                        ViolationKind::NetworkProtocol,
-                        "Decoded input frame overflow from start frame {} and offset {}",
-                        body.start_frame,
-                        frame_offset
-                    );
-                    return;
-                };
-                // skip inputs that we don't need
-                if inp_frame <= last_recv_frame {
-                    continue;
-                }
-
-                let input_data = InputBytes {
-                    frame: inp_frame,
-                    bytes: inp,
-                };
-                let player_inputs =
-                    match input_data.try_to_player_inputs_exact::<T>(self.handles.len()) {
-                        Ok(player_inputs) => player_inputs,
-                        Err(err) => {
-                            log_input_decode_error(err);
-                            return;
-                        },
-                    };
-
-                staged_frames.push(StagedInputFrame {
-                    input_data,
-                    player_inputs,
-                });
-            }
-
             if ack_disposition == AckDisposition::Apply {
                 self.apply_ack_frame(body.ack_frame);
             }
@@ -2855,23 +5311,38 @@ fn on_input(&mut self, body: &Input) {
             // Trade-off: duplicate-heavy legitimate traffic (retransmissions
             // under loss) now lets our retry fire on its normal interval —
             // at most one extra resend per `running_retry_interval`, benign.
-            if !staged_frames.is_empty() {
+            if !self.staged_inputs.is_empty() {
                 self.running_last_input_recv = self.now();
-            }
-            for staged in staged_frames {
-                let peer_connect_status = body.peer_connect_status.clone();
-                self.recv_inputs
-                    .insert(staged.input_data.frame, staged.input_data);
-
-                for (player_input, &player_handle) in
-                    staged.player_inputs.into_iter().zip(self.handles.iter())
-                {
-                    self.event_queue.push_back(Event::Input {
-                        input: player_input,
-                        player: player_handle,
-                        peer_connect_status: peer_connect_status.clone(),
-                    });
+                let peer_connect_status =
+                    self.share_input_connect_status(&body.peer_connect_status);
+                let mut staged_inputs = std::mem::take(&mut self.staged_inputs);
+                let mut staged_player_inputs = std::mem::take(&mut self.staged_player_inputs);
+                let mut player_inputs = staged_player_inputs.iter();
+                // `drain` rather than `into_iter` keeps the buffer's capacity.
+                #[allow(clippy::iter_with_drain)]
+                for input_data in staged_inputs.drain(..) {
+                    if let Some(replaced) = self.recv_inputs.insert(input_data.frame, input_data) {
+                        self.recycle_input_bytes(replaced.bytes);
+                    }
+                    // Handles first: `zip` pulls from its left side before
+                    // finding the right one exhausted.
+                    for (&player_handle, player_input) in
+                        self.handles.iter().zip(player_inputs.by_ref())
+                    {
+                        self.event_queue.push_back((
+                            next_event_stamp(),
+                            Event::Input {
+                                input: *player_input,
+                                player: player_handle,
+                                peer_connect_status: Arc::clone(&peer_connect_status),
+                            },
+                        ));
+                    }
                 }
+                staged_player_inputs.clear();
+                self.staged_inputs = staged_inputs;
+                self.staged_player_inputs = staged_player_inputs;
+                self.deliver_frame_tags(frame_tags, last_recv_frame);
             }
 
             // send an input ack
@@ -2885,17 +5356,24 @@ fn on_input(&mut self, body: &Input) {
                 .checked_mul(self.max_prediction)
                 .and_then(|frames| i32::try_from(frames).ok())
                 .unwrap_or(i32::MAX);
-            self.recv_inputs.retain(|&k, _| {
-                k >= safe_frame_sub!(
-                    last_recv_frame,
-                    history_frames,
-                    "UdpProtocol::on_input history prune"
-                )
-            });
-        } else {
-            // A stale retransmission can outlive its delta reference after the
-            // receiver prunes old input history. The frames are already known,
-            // so decoding/staging is unnecessary, but silence here strands the
+            let oldest_kept = safe_frame_sub!(
+                last_recv_frame,
+                history_frames,
+                "UdpProtocol::on_input history prune"
+            );
+            while self
+                .recv_inputs
+                .first_key_value()
+                .is_some_and(|(&frame, _)| frame < oldest_kept)
+            {
+                if let Some((_, pruned)) = self.recv_inputs.pop_first() {
+                    self.recycle_input_bytes(pruned.bytes);
+                }
+            }
+        } else {
+            // A stale retransmission can outlive its delta reference after the
+            // receiver prunes old input history. The frames are already known,
+            // so decoding/staging is unnecessary, but silence here strands the
             // sender if our earlier cumulative InputAck was lost: it retains
             // the old front forever, keeps `pending_output` nonempty, and both
             // peers can exhaust their prediction windows. Re-ack the receiver's
@@ -2908,18 +5386,248 @@ fn on_input(&mut self, body: &Input) {
         }
     }
 
+    /// Emits the tags of a received frame tag section whose carrier frames
+    /// this packet delivered: those after `last_recv_frame`, the newest frame
+    /// received before it, up to the newest frame received now. Every packet
+    /// carrying a frame also carries the tags sent with it, so each tag is
+    /// emitted exactly once, with the first packet to deliver its carrier.
+    fn deliver_frame_tags(&mut self, frame_tags: &[u8], last_recv_frame: Frame) {
+        let newest_recv_frame = self.last_recv_frame();
+        for entry in frame_tags.chunks_exact(FRAME_TAG_WIRE_LEN) {
+            let Some((carrier, rest)) = entry.split_first_chunk::<4>() else {
+                continue;
+            };
+            let Some((frame, data)) = rest.split_first_chunk::<4>() else {
+                continue;
+            };
+            let Ok(data) = <[u8; FRAME_TAG_LEN]>::try_from(data) else {
+                continue;
+            };
+            let carrier = Frame::new(i32::from_le_bytes(*carrier));
+            let frame = Frame::new(i32::from_le_bytes(*frame));
+            if !frame.is_valid() {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::NetworkProtocol,
+                    "Ignoring a frame tag for invalid frame {}",
+                    frame
+                );
+                continue;
+            }
+            if carrier > last_recv_frame && carrier <= newest_recv_frame {
+                self.push_event(Event::FrameTag { frame, data });
+            }
+        }
+    }
+
+    /// Stages the frames of a decoded `Input` batch (`frame_len` bytes each,
+    /// starting at `start_frame`) that are newer than `last_recv_frame` into
+    /// `staged_inputs` / `staged_player_inputs`. Either every new frame is
+    /// staged or, on a malformed batch, none is and `false` is returned.
+    fn stage_decoded_inputs(
+        &mut self,
+        start_frame: Frame,
+        last_recv_frame: Frame,
+        decoded: &[u8],
+        frame_len: usize,
+    ) -> bool {
+        let Some(frame_count) = decoded.len().checked_div(frame_len) else {
+            return false;
+        };
+        if frame_count > self.protocol_config.pending_output_limit {
+            self.reject_frame_range(start_frame, frame_count);
+            return false;
+        }
+        if self.staged_inputs.try_reserve(frame_count).is_err() {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "Failed to reserve {} decoded input frame(s)",
+                frame_count
+            );
+            return false;
+        }
+
+        let mut staged = true;
+        for (i, frame_bytes) in decoded.chunks_exact(frame_len).enumerate() {
+            let Ok(frame_offset) = i32::try_from(i) else {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::NetworkProtocol,
+                    "Decoded input batch has too many frames to represent as i32 offsets"
+                );
+                staged = false;
+                break;
+            };
+            let Some(inp_frame) = start_frame.checked_add(frame_offset) else {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::NetworkProtocol,
+                    "Decoded input frame overflow from start frame {} and offset {}",
+                    start_frame,
+                    frame_offset
+                );
+                staged = false;
+                break;
+            };
+            // skip inputs that we don't need
+            if inp_frame <= last_recv_frame {
+                continue;
+            }
+
+            let mut bytes = self.take_spare_input_bytes();
+            bytes.clear();
+            // reserve-in-loop: one frame buffer per staged frame, bounded by `pending_output_limit` (checked above); a no-op for a recycled buffer.
+            if bytes.try_reserve_exact(frame_len).is_err() {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::NetworkProtocol,
+                    "Failed to reserve {} byte(s) for decoded input frame {}",
+                    frame_len,
+                    inp_frame
+                );
+                staged = false;
+                break;
+            }
+            bytes.extend_from_slice(frame_bytes);
+            let input_data = InputBytes {
+                frame: inp_frame,
+                bytes,
+            };
+            if let Err(err) = input_data
+                .try_append_player_inputs::<T>(self.handles.len(), &mut self.staged_player_inputs)
+            {
+                log_input_decode_error(err);
+                self.recycle_input_bytes(input_data.bytes);
+                staged = false;
+                break;
+            }
+            self.staged_inputs.push(input_data);
+        }
+
+        if !staged {
+            self.staged_player_inputs.clear();
+            let mut staged_inputs = std::mem::take(&mut self.staged_inputs);
+            #[allow(clippy::iter_with_drain)]
+            for input_data in staged_inputs.drain(..) {
+                self.recycle_input_bytes(input_data.bytes);
+            }
+            self.staged_inputs = staged_inputs;
+        }
+        staged
+    }
+
+    /// Shares `status` with the `Event::Input`s of one packet, reusing the
+    /// previous packet's snapshot when none of its events is still alive.
+    fn share_input_connect_status(
+        &mut self,
+        status: &[ConnectionStatus],
+    ) -> Arc<[ConnectionStatus]> {
+        match Arc::get_mut(&mut self.input_connect_status) {
+            Some(shared) if shared.len() == status.len() => shared.copy_from_slice(status),
+            _ => self.input_connect_status = Arc::from(status),
+        }
+        Arc::clone(&self.input_connect_status)
+    }
+
+    /// A recycled input byte buffer, or a new empty one.
+    fn take_spare_input_bytes(&mut self) -> Vec<u8> {
+        self.spare_input_bytes.pop().unwrap_or_default()
+    }
+
+    /// Keeps `bytes` for reuse if the spare pool has reserved room for it,
+    /// otherwise frees it.
+    fn recycle_input_bytes(&mut self, bytes: Vec<u8>) {
+        if self.spare_input_bytes.len() < self.spare_input_bytes.capacity() {
+            self.spare_input_bytes.push(bytes);
+        }
+    }
+
+    /// Counts and reports an `Input` whose implied frame range is out of
+    /// bounds; see [`InputRejection::FrameRangeOutOfBounds`].
+    fn reject_frame_range(&mut self, start_frame: Frame, frames: usize) {
+        report_violation!(
+            ViolationSeverity::Error,
+            ViolationKind::NetworkProtocol,
+            "Rejecting input message ({}): {} frame(s) from start frame {} (pending output limit {}, max prediction {})",
+            InputRejection::FrameRangeOutOfBounds,
+            frames,
+            start_frame,
+            self.protocol_config.pending_output_limit,
+            self.max_prediction
+        );
+        self.input_messages_rejected
+            .record(InputRejection::FrameRangeOutOfBounds);
+    }
+
     /// Upon receiving a `InputAck`, discard the oldest buffered input including the acked input.
     fn on_input_ack(&mut self, body: InputAck) {
         self.apply_ack_frame(body.ack_frame);
     }
 
+    /// Upon receiving a `KeepAlive`, apply its piggybacked input ack like an
+    /// `InputAck` and merge its connect status like an `Input`'s.
+    fn on_keep_alive(&mut self, body: &KeepAlive) {
+        self.apply_ack_frame(body.ack_frame);
+        if body.peer_connect_status.is_empty() {
+            return;
+        }
+        if body.peer_connect_status.len() != self.num_players {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "Ignoring keepalive connect status: {} connection-status entries, expected {}",
+                body.peer_connect_status.len(),
+                self.num_players
+            );
+            return;
+        }
+        self.merge_peer_connect_status(Frame::NULL, &body.peer_connect_status);
+    }
+
     /// Upon receiving a `QualityReport`, update network stats and reply with a `QualityReply`.
     fn on_quality_report(&mut self, body: &QualityReport) {
         self.remote_frame_advantage = body.frame_advantage as i32;
+        self.remote_advantage_updated_at = self.now();
+        if body.stalled != self.remote_advance_stalled {
+            self.remote_advance_stalled = body.stalled;
+            self.push_event(if body.stalled {
+                Event::AdvanceStalled
+            } else {
+                Event::AdvanceResumed
+            });
+        }
+        self.observe_clock_offset(body.ping);
         let reply_body = QualityReply { pong: body.ping };
         self.queue_message(MessageBody::QualityReply(reply_body));
     }
 
+    /// Folds the peer's `ping` timestamp into the clock-offset estimate.
+    ///
+    /// The peer read its clock half a round trip ago on average, so a sample
+    /// is `ping + rtt / 2 - now`. The one-way delay lies anywhere between zero
+    /// and the round trip, which bounds a sample's error by half of it; an
+    /// exponential moving average over eight reports smooths out the jitter.
+    fn observe_clock_offset(&mut self, remote_ping: u128) {
+        let remote = i128::try_from(remote_ping).unwrap_or(i128::MAX);
+        let half_rtt = i128::try_from(self.round_trip_time / 2).unwrap_or(i128::MAX);
+        let local = i128::try_from(self.ping_millis()).unwrap_or(i128::MAX);
+        let sample = remote.saturating_add(half_rtt).saturating_sub(local);
+        let sample = sample.saturating_mul(1_000);
+        let sample = i64::try_from(sample).unwrap_or(if sample < 0 { i64::MIN } else { i64::MAX });
+        self.clock_offset_micros = Some(match self.clock_offset_micros {
+            Some(estimate) => estimate.saturating_add(sample.saturating_sub(estimate) / 8),
+            None => sample,
+        });
+    }
+
+    /// The smoothed estimate of the peer's clock minus this endpoint's, in
+    /// milliseconds, once a quality report has arrived.
+    pub(crate) fn clock_offset_ms(&self) -> Option<i64> {
+        self.clock_offset_micros
+            .map(|micros| micros.saturating_add(500).div_euclid(1_000))
+    }
+
     /// Upon receiving a `QualityReply`, update network stats.
     fn on_quality_reply(&mut self, body: &QualityReply) {
         let millis = self.ping_millis();
@@ -2928,6 +5636,20 @@ fn on_quality_reply(&mut self, body: &QualityReply) {
         // endpoint era could carry an arbitrary value. A 0 RTT is harmless -
         // it will be corrected on the next quality report.
         self.round_trip_time = millis.saturating_sub(body.pong);
+        self.rtt_updated_at = self.now();
+    }
+
+    /// Pushed by the session's advance-stall watchdog. A change is reported to
+    /// the peer at once with a fresh quality report, rather than on the next
+    /// report interval.
+    pub(crate) fn set_advance_stalled(&mut self, stalled: bool) {
+        if self.advance_stalled == stalled {
+            return;
+        }
+        self.advance_stalled = stalled;
+        if self.is_running() {
+            self.send_quality_report();
+        }
     }
 
     // ---- floor-round (double-failure-relay connected-relay reorder fix) ----
@@ -3152,6 +5874,28 @@ fn debug_assert_floor_round_invariants(&self) {
 
     /// Upon receiving a `ChecksumReport`, add it to the checksum history
     fn on_checksum_report(&mut self, body: &ChecksumReport) {
+        if self.checksum_request == Some(body.frame) {
+            self.checksum_request = None;
+            self.requested_checksum = Some((body.frame, body.checksum));
+            // The answer to our own request is expected without desync detection.
+            if !matches!(self.desync_detection, DesyncDetection::On { .. }) {
+                return;
+            }
+        }
+
+        // A verifying spectator arbitrates the reports itself: keep the
+        // newest `max_checksum_history` of them whatever their spacing.
+        if self.checksum_authority {
+            let max_history = self.protocol_config.max_checksum_history.max(1);
+            while self.pending_checksums.len() >= max_history
+                && !self.pending_checksums.contains_key(&body.frame)
+            {
+                self.pending_checksums.pop_first();
+            }
+            self.pending_checksums.insert(body.frame, body.checksum);
+            return;
+        }
+
         let interval = if let DesyncDetection::On { interval } = self.desync_detection {
             interval
         } else {
@@ -3243,6 +5987,48 @@ pub(crate) fn send_checksum_report(&mut self, frame_to_send: Frame, checksum: u1
         self.queue_message(MessageBody::ChecksumReport(body));
     }
 
+    /// Asks the peer once for its [`ChecksumReport`] of `frame`, resending on
+    /// the keepalive cadence until it arrives. No-op unless the endpoint is
+    /// running, and while the same request is outstanding or answered.
+    pub(crate) fn request_checksum(&mut self, frame: Frame) {
+        if self.state != ProtocolState::Running
+            || self.checksum_request == Some(frame)
+            || self.requested_checksum(frame).is_some()
+        {
+            return;
+        }
+        self.checksum_request = Some(frame);
+        self.send_checksum_request();
+    }
+
+    /// The peer's answer to [`request_checksum`](Self::request_checksum) for
+    /// `frame`, once it arrived.
+    pub(crate) fn requested_checksum(&self, frame: Frame) -> Option<u128> {
+        self.requested_checksum
+            .filter(|&(answered, _)| answered == frame)
+            .map(|(_, checksum)| checksum)
+    }
+
+    /// The frame the peer asked for our checksum of, until
+    /// [`answer_checksum_request`](Self::answer_checksum_request) answers it.
+    pub(crate) fn pending_checksum_request(&self) -> Option<Frame> {
+        self.pending_checksum_request
+    }
+
+    /// Answers the pending checksum request with our `checksum` of its frame.
+    pub(crate) fn answer_checksum_request(&mut self, checksum: u128) {
+        if let Some(frame) = self.pending_checksum_request.take() {
+            self.send_checksum_report(frame, checksum);
+        }
+    }
+
+    fn send_checksum_request(&mut self) {
+        if let Some(frame) = self.checksum_request {
+            self.last_checksum_request_time = self.now();
+            self.queue_message(MessageBody::ChecksumRequest(ChecksumRequest { frame }));
+        }
+    }
+
     /// Queues one coordinated graceful-drop control message. No-op unless the
     /// endpoint is running; synchronization and terminal states cannot carry a
     /// lifecycle operation from an unbound or closed connection era.
@@ -3258,11 +6044,124 @@ pub(crate) fn send_drop_control_message(&mut self, message: DropControlMessage)
     /// previous drain. The endpoint mailbox itself is bounded by
     /// [`MAX_RECEIVED_DROP_MESSAGES`].
     #[allow(dead_code)] // consumed by session orchestration in the next D14 layer
+    /// Number of D14 carrier messages waiting in the mailbox.
+    pub(crate) fn received_drop_message_count(&self) -> usize {
+        self.received_drop_messages.len()
+    }
+
     pub(crate) fn take_received_drop_messages(&mut self) -> Drain<'_, DropControlMessage> {
         self.drop_mailbox_warning_sent = false;
         self.received_drop_messages.drain(..)
     }
 
+    /// Queues one match pause/resume negotiation message. No-op unless the
+    /// endpoint is running.
+    pub(crate) fn send_match_pause_message(&mut self, message: MatchPauseMessage) {
+        if self.state != ProtocolState::Running {
+            return;
+        }
+        self.queue_message(message.into_body());
+    }
+
+    /// Ends this spectator stream at `final_frame`: queues a `StreamEnd` now
+    /// and resends it on the keepalive cadence while the endpoint runs. No-op
+    /// unless the endpoint is running, and after the first call.
+    pub(crate) fn end_stream(&mut self, final_frame: Frame) {
+        if self.state != ProtocolState::Running || self.stream_end.is_some() {
+            return;
+        }
+        self.stream_end = Some(final_frame);
+        self.send_stream_end();
+    }
+
+    fn send_link_closed(&mut self) {
+        self.last_link_closed_time = self.now();
+        let last_received_frame = self.last_recv_frame();
+        self.queue_message(MessageBody::LinkClosed(LinkClosed {
+            last_received_frame,
+        }));
+    }
+
+    fn send_stream_end(&mut self) {
+        if let Some(final_frame) = self.stream_end {
+            self.last_stream_end_time = self.now();
+            self.queue_message(MessageBody::StreamEnd(StreamEnd { final_frame }));
+        }
+    }
+
+    /// Queues `payload` on the reliable channel. It is sent right away once
+    /// the endpoint is running and resent every
+    /// `SyncConfig::running_retry_interval` until the peer acknowledges it.
+    pub(crate) fn queue_reliable(&mut self, payload: Vec<u8>) -> Result<(), FortressError> {
+        let max = self.protocol_config.max_reliable_payload;
+        if payload.len() > max {
+            return Err(InvalidRequestKind::ReliablePayloadTooLarge {
+                len: payload.len(),
+                max,
+            }
+            .into());
+        }
+        let window = self.protocol_config.reliable_window;
+        if self.reliable_in_flight.len() >= window {
+            return Err(InvalidRequestKind::ReliableWindowFull { window }.into());
+        }
+        self.reliable_in_flight
+            .try_reserve(1)
+            .map_err(|_err| allocation_failed("protocol.reliable_in_flight", 1))?;
+        self.reliable_in_flight.push_back(ReliableMessage {
+            sequence: self.reliable_next_sequence,
+            payload,
+        });
+        self.reliable_next_sequence = self.reliable_next_sequence.wrapping_add(1);
+        if self.state == ProtocolState::Running {
+            self.send_reliable_message(self.reliable_in_flight.len() - 1);
+        }
+        Ok(())
+    }
+
+    /// Resends every unacknowledged reliable message. Copies still held back
+    /// by the send limits are replaced rather than doubled.
+    fn resend_reliable_messages(&mut self) {
+        self.last_reliable_resend_time = self.now();
+        self.send_queue
+            .retain(|msg| !matches!(msg.body, MessageBody::ReliableMessage(_)));
+        for index in 0..self.reliable_in_flight.len() {
+            self.send_reliable_message(index);
+        }
+    }
+
+    /// Queues a copy of `reliable_in_flight[index]`.
+    fn send_reliable_message(&mut self, index: usize) {
+        let Some(message) = self.reliable_in_flight.get(index) else {
+            return;
+        };
+        let sequence = message.sequence;
+        let mut payload = Vec::new();
+        // alloc-bound: the payload was capped at `ProtocolConfig::max_reliable_payload` when queued.
+        if payload.try_reserve_exact(message.payload.len()).is_err() {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::NetworkProtocol,
+                "Failed to copy reliable message {} for sending; retrying on the next resend",
+                sequence
+            );
+            return;
+        }
+        payload.extend_from_slice(&message.payload);
+        self.queue_message(MessageBody::ReliableMessage(ReliableMessage {
+            sequence,
+            payload,
+        }));
+    }
+
+    /// Drains every match pause/resume message staged since the previous
+    /// drain. The endpoint mailbox itself is bounded by
+    /// [`MAX_RECEIVED_MATCH_PAUSE_MESSAGES`].
+    pub(crate) fn take_received_match_pause_messages(&mut self) -> Drain<'_, MatchPauseMessage> {
+        self.match_pause_mailbox_warning_sent = false;
+        self.received_match_pause_messages.drain(..)
+    }
+
     /// Queues a `JoinRequest` for the slot `player_handle`. No-op unless `Running`.
     // dead_code: consumed by chunk 5's session orchestration; only the message +
     // protocol layer lands in this chunk.
@@ -3472,6 +6371,9 @@ pub(crate) fn seed_peer_connect_status_for_reactivation(
         handle: PlayerHandle,
         last_frame: Frame,
     ) {
+        if let Some(claim) = self.disconnect_claims.get_mut(handle.as_usize()) {
+            *claim = None;
+        }
         if let Some(status) = self.peer_connect_status.get_mut(handle.as_usize()) {
             *status = ConnectionStatus {
                 disconnected: false,
@@ -3584,6 +6486,7 @@ pub(crate) fn reactivation_floor_for_test(&self, handle: PlayerHandle) -> Frame
     #[allow(dead_code)]
     pub(crate) fn clear_pending_output(&mut self) {
         self.pending_output.clear();
+        self.suppressed_input_frames = 0;
     }
 }
 
@@ -3597,6 +6500,7 @@ pub(crate) fn clear_pending_output(&mut self) {
 )]
 mod tests {
     use super::*;
+    use crate::network::compression::try_encode;
     use serde::{Deserialize, Serialize};
     use std::net::SocketAddr;
     use std::sync::Mutex;
@@ -3628,6 +6532,26 @@ impl Config for BoolConfig {
         type Address = SocketAddr;
     }
 
+    /// Decodes every value one higher than it was encoded, so its default
+    /// does not round-trip.
+    #[derive(Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Debug)]
+    #[serde(from = "u8")]
+    struct ShiftedInput(u8);
+
+    impl From<u8> for ShiftedInput {
+        fn from(value: u8) -> Self {
+            Self(value.wrapping_add(1))
+        }
+    }
+
+    struct ShiftedInputConfig;
+
+    impl Config for ShiftedInputConfig {
+        type Input = ShiftedInput;
+        type State = TestState;
+        type Address = SocketAddr;
+    }
+
     #[derive(Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Debug)]
     struct UnitInput;
 
@@ -3843,60 +6767,305 @@ fn complete_sync_transitions_to_running() {
     }
 
     #[test]
-    fn sync_reply_with_wrong_random_is_ignored() {
+    fn pre_established_sync_concludes_after_one_roundtrip_and_seeds_rtt() {
+        let (config, offset) = injected_clock_config();
+        let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            config,
+        );
+        protocol.pre_establish(PreEstablished {
+            local_conn_id: 0x0101_0101,
+            remote_conn_id: 0x0202_0202,
+        });
+        protocol.synchronize().unwrap();
+        assert_eq!(protocol.conn_id, 0x0101_0101);
+        assert_eq!(protocol.sync_progress().roundtrips_required, 1);
+
+        offset.store(40, std::sync::atomic::Ordering::Relaxed);
+        let random = *protocol.sync_random_requests.iter().next().unwrap();
+        let reply = matching_sync_reply(&protocol, random);
+        protocol.on_sync_reply(MessageHeader::new(0x0202_0202), reply);
+
+        assert!(protocol.is_running());
+        assert_eq!(protocol.round_trip_time, 40);
+        assert_eq!(protocol.remote_conn_id, 0x0202_0202);
+        assert!(matches!(
+            protocol.event_queue.pop_front().map(|(_, event)| event),
+            Some(Event::Synchronized)
+        ));
+    }
+
+    #[test]
+    fn pre_established_sync_falls_back_on_connection_id_mismatch() {
         let mut protocol: UdpProtocol<TestConfig> =
             create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.pre_establish(PreEstablished {
+            local_conn_id: 0x0101_0101,
+            remote_conn_id: 0x0202_0202,
+        });
         protocol.synchronize().unwrap();
 
-        let initial_remaining = protocol.sync_remaining_roundtrips;
-
-        // Send a reply with the wrong random value
-        let header = MessageHeader::new(999);
-        let reply = matching_sync_reply(&protocol, 99_999_999);
-        protocol.on_sync_reply(header, reply);
+        // The first reply still counts, toward the full handshake.
+        for _ in 0..TEST_NUM_SYNC_PACKETS {
+            assert!(!protocol.is_running());
+            let random = *protocol.sync_random_requests.iter().next().unwrap();
+            let reply = matching_sync_reply(&protocol, random);
+            protocol.on_sync_reply(MessageHeader::new(0x0303_0303), reply);
+        }
 
-        // Should still have same number of remaining roundtrips
-        assert_eq!(protocol.sync_remaining_roundtrips, initial_remaining);
+        assert!(protocol.is_running());
+        assert_eq!(protocol.remote_conn_id, 0x0303_0303);
+        assert_eq!(protocol.round_trip_time, 0);
+        assert_eq!(
+            protocol.sync_progress().roundtrips_required,
+            TEST_NUM_SYNC_PACKETS
+        );
     }
 
     #[test]
-    fn sync_reply_when_not_synchronizing_is_ignored() {
+    fn pinned_conn_id_is_sent_and_reported() {
         let mut protocol: UdpProtocol<TestConfig> =
             create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.pin_conn_id(0x0bad_cafe);
+        protocol.synchronize().unwrap();
 
-        // Protocol is in Initializing state, not Synchronizing
-        let header = MessageHeader::new(999);
-        let reply = matching_sync_reply(&protocol, 123);
-        protocol.on_sync_reply(header, reply);
+        assert_eq!(protocol.conn_id(), 0x0bad_cafe);
+        assert_eq!(protocol.remote_conn_id(), None);
+        let request = protocol.send_queue.front().unwrap();
+        assert_eq!(request.header.conn_id, 0x0bad_cafe);
 
-        // Should still be in initializing
-        assert!(!protocol.is_synchronized());
+        complete_test_sync(&mut protocol);
+        assert_eq!(protocol.remote_conn_id(), Some(999));
     }
 
-    #[cfg(feature = "trace-validation")]
     #[test]
-    fn handshake_trace_records_exact_handler_and_duplicate_order() {
-        let mut protocol = create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
-        protocol.activate_handshake_trace(16).unwrap();
+    fn sync_reply_carrying_our_conn_id_rerolls_it() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
         protocol.synchronize().unwrap();
-        let first_request = *protocol.sync_random_requests.first().unwrap();
+        let colliding = protocol.conn_id;
+        let stale = *protocol.sync_random_requests.iter().next().unwrap();
+        let reply = matching_sync_reply(&protocol, stale);
+        protocol.on_sync_reply(MessageHeader::new(colliding), reply);
+
+        assert_ne!(protocol.conn_id, colliding);
+        assert!(crate::network::is_valid_conn_id(protocol.conn_id));
+        assert_eq!(protocol.remote_conn_id(), None);
+        assert_eq!(protocol.sync_remaining_roundtrips, TEST_NUM_SYNC_PACKETS);
+        // Only the request sent under the new ID is outstanding.
+        assert_eq!(protocol.sync_random_requests.len(), 1);
+        let request = protocol.send_queue.back().unwrap();
+        assert_eq!(request.header.conn_id, protocol.conn_id);
 
-        protocol.on_sync_request(matching_sync_request(&protocol, 77));
-        protocol.on_sync_reply(
-            MessageHeader::new(999),
-            matching_sync_reply(&protocol, first_request),
-        );
-        protocol.on_sync_reply(
-            MessageHeader::new(999),
-            matching_sync_reply(&protocol, first_request),
-        );
+        complete_test_sync(&mut protocol);
+        assert!(protocol.is_running());
+    }
 
-        let trace = protocol.handshake_trace().unwrap().unwrap();
-        assert_eq!(trace.len(), 7);
-        assert_eq!(trace[0].action, HandshakeTraceAction::Activated);
-        assert_eq!(trace[1].action, HandshakeTraceAction::BeginSynchronization);
-        assert_eq!(
-            trace[2].action,
+    #[test]
+    fn sync_reply_carrying_a_pinned_conn_id_is_not_bound() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.pin_conn_id(999);
+        protocol.synchronize().unwrap();
+        let random = *protocol.sync_random_requests.iter().next().unwrap();
+        let reply = matching_sync_reply(&protocol, random);
+        protocol.on_sync_reply(MessageHeader::new(999), reply);
+
+        assert_eq!(protocol.conn_id, 999);
+        assert_eq!(protocol.remote_conn_id(), None);
+        assert_eq!(protocol.sync_remaining_roundtrips, TEST_NUM_SYNC_PACKETS);
+    }
+
+    /// A running endpoint bound to conn_id 999 whose peer then timed out.
+    fn disconnected_protocol(peer_restart: bool) -> UdpProtocol<TestConfig> {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        if peer_restart {
+            protocol.enable_peer_restart();
+        }
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        protocol.disconnect_remote();
+        assert_eq!(protocol.state, ProtocolState::Disconnected);
+        protocol.send_queue.clear();
+        protocol.event_queue.clear();
+        protocol
+    }
+
+    #[test]
+    fn restarted_peer_sync_request_restarts_the_handshake_when_enabled() {
+        let mut protocol = disconnected_protocol(true);
+        let conn_id = protocol.conn_id;
+        let request = Message {
+            header: MessageHeader::new(0x0123_4567),
+            body: MessageBody::SyncRequest(matching_sync_request(&protocol, 42)),
+        };
+        protocol.handle_message(&request);
+
+        assert_eq!(protocol.state, ProtocolState::Synchronizing);
+        assert_eq!(protocol.conn_id, conn_id);
+        assert_eq!(protocol.remote_conn_id(), None);
+        assert!(protocol.accept_peer_restart);
+        let replied = protocol.send_queue.iter().any(
+            |msg| matches!(msg.body, MessageBody::SyncReply(reply) if reply.random_reply == 42),
+        );
+        assert!(replied, "the restarted peer's request must be answered");
+
+        for _ in 0..TEST_NUM_SYNC_PACKETS {
+            let random = *protocol.sync_random_requests.iter().next().unwrap();
+            let reply = matching_sync_reply(&protocol, random);
+            protocol.handle_message(&Message {
+                header: MessageHeader::new(0x0123_4567),
+                body: MessageBody::SyncReply(reply),
+            });
+        }
+        assert!(protocol.is_running());
+        assert_eq!(protocol.remote_conn_id(), Some(0x0123_4567));
+    }
+
+    fn keep_alive_from(conn_id: u32) -> Message {
+        Message {
+            header: MessageHeader::new(conn_id),
+            body: MessageBody::KeepAlive(KeepAlive::default()),
+        }
+    }
+
+    #[test]
+    fn address_migration_needs_opt_in_a_running_endpoint_and_the_bound_conn_id() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        assert!(!protocol.is_migrating_to(&keep_alive_from(999)));
+
+        protocol.enable_address_migration();
+        assert!(protocol.is_migrating_to(&keep_alive_from(999)));
+        assert!(!protocol.is_migrating_to(&keep_alive_from(998)));
+
+        protocol.disconnect_remote();
+        assert!(!protocol.is_migrating_to(&keep_alive_from(999)));
+    }
+
+    #[test]
+    fn migrate_to_moves_the_endpoint_and_keeps_its_connection() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        let new_addr: SocketAddr = "127.0.0.1:9999".parse().unwrap();
+        protocol.set_route(Some(new_addr));
+
+        protocol.migrate_to(new_addr);
+        assert_eq!(protocol.peer_addr(), new_addr);
+        assert_eq!(protocol.route(), None);
+        assert!(protocol.is_running());
+        assert_eq!(protocol.remote_conn_id(), Some(999));
+        assert!(protocol.is_handling_message(&new_addr, &keep_alive_from(999)));
+    }
+
+    #[test]
+    fn keep_alive_burst_queues_the_requested_count() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+
+        protocol.queue_keep_alive_burst(3);
+        let keep_alives = protocol
+            .send_queue
+            .iter()
+            .filter(|msg| matches!(msg.body, MessageBody::KeepAlive(_)))
+            .count();
+        assert_eq!(keep_alives, 3);
+    }
+
+    #[test]
+    fn restarted_peer_sync_request_is_ignored_by_default() {
+        let mut protocol = disconnected_protocol(false);
+        let request = Message {
+            header: MessageHeader::new(0x0123_4567),
+            body: MessageBody::SyncRequest(matching_sync_request(&protocol, 42)),
+        };
+        protocol.handle_message(&request);
+
+        assert_eq!(protocol.state, ProtocolState::Disconnected);
+        assert_eq!(protocol.remote_conn_id(), Some(999));
+        assert!(protocol.send_queue.is_empty());
+    }
+
+    #[test]
+    fn sync_request_from_the_old_peer_does_not_restart_the_handshake() {
+        let mut protocol = disconnected_protocol(true);
+        let request = Message {
+            header: MessageHeader::new(999),
+            body: MessageBody::SyncRequest(matching_sync_request(&protocol, 42)),
+        };
+        protocol.handle_message(&request);
+
+        assert_eq!(protocol.state, ProtocolState::Disconnected);
+        assert_eq!(protocol.remote_conn_id(), Some(999));
+    }
+
+    #[test]
+    fn sync_reply_with_wrong_random_is_ignored() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+
+        let initial_remaining = protocol.sync_remaining_roundtrips;
+
+        // Send a reply with the wrong random value
+        let header = MessageHeader::new(999);
+        let reply = matching_sync_reply(&protocol, 99_999_999);
+        protocol.on_sync_reply(header, reply);
+
+        // Should still have same number of remaining roundtrips
+        assert_eq!(protocol.sync_remaining_roundtrips, initial_remaining);
+    }
+
+    #[test]
+    fn sync_reply_when_not_synchronizing_is_ignored() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+
+        // Protocol is in Initializing state, not Synchronizing
+        let header = MessageHeader::new(999);
+        let reply = matching_sync_reply(&protocol, 123);
+        protocol.on_sync_reply(header, reply);
+
+        // Should still be in initializing
+        assert!(!protocol.is_synchronized());
+    }
+
+    #[cfg(feature = "trace-validation")]
+    #[test]
+    fn handshake_trace_records_exact_handler_and_duplicate_order() {
+        let mut protocol = create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.activate_handshake_trace(16).unwrap();
+        protocol.synchronize().unwrap();
+        let first_request = *protocol.sync_random_requests.first().unwrap();
+
+        protocol.on_sync_request(matching_sync_request(&protocol, 77));
+        protocol.on_sync_reply(
+            MessageHeader::new(999),
+            matching_sync_reply(&protocol, first_request),
+        );
+        protocol.on_sync_reply(
+            MessageHeader::new(999),
+            matching_sync_reply(&protocol, first_request),
+        );
+
+        let trace = protocol.handshake_trace().unwrap().unwrap();
+        assert_eq!(trace.len(), 7);
+        assert_eq!(trace[0].action, HandshakeTraceAction::Activated);
+        assert_eq!(trace[1].action, HandshakeTraceAction::BeginSynchronization);
+        assert_eq!(
+            trace[2].action,
             HandshakeTraceAction::SendRequest {
                 request_id: first_request,
                 disposition: HandshakeRequestIdDisposition::Fresh,
@@ -4247,7 +7416,776 @@ fn handshake_mismatch_reports_each_field_in_locked_precedence_order() {
                 theirs: theirs.config_digest,
             })
         );
-        assert_eq!(ours.first_mismatch(ours), None);
+        assert_eq!(ours.first_mismatch(ours), None);
+    }
+
+    #[test]
+    fn negotiated_feature_bits_do_not_fail_the_handshake() {
+        let ours = HandshakeConfig::new(SessionConfigBlock {
+            num_players: 2,
+            input_bytes_per_player: 4,
+            fps: 60,
+            max_prediction: 8,
+            desync_interval: 60,
+        });
+        let mut theirs = ours;
+        theirs.features |= SPECTATOR_KEYFRAME_FEATURE | INPUT_RESYNC_FEATURE;
+        assert_eq!(ours.first_mismatch(theirs), None);
+        assert_eq!(theirs.first_mismatch(ours), None);
+    }
+
+    #[test]
+    fn spectator_keyframes_are_echoed_only_to_an_offering_peer() {
+        let mut host: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0), PlayerHandle::new(1)], 2, 2, 8);
+        host.enable_spectator_keyframes(4, false);
+        host.synchronize().unwrap();
+        let MessageBody::SyncRequest(request) = &host.send_queue.back().unwrap().body else {
+            panic!("synchronize should queue a sync request");
+        };
+        assert_eq!(
+            request.features & SPECTATOR_KEYFRAME_FEATURE,
+            0,
+            "the host must not offer unprompted"
+        );
+        host.send_queue.clear();
+
+        let plain = host.local_handshake;
+        host.on_sync_request(plain.request(1));
+        let MessageBody::SyncReply(reply) = &host.send_queue.back().unwrap().body else {
+            panic!("a sync request should be answered");
+        };
+        assert_eq!(reply.features & SPECTATOR_KEYFRAME_FEATURE, 0);
+        assert!(!host.keyframes_active());
+
+        let mut offering = plain;
+        offering.features |= SPECTATOR_KEYFRAME_FEATURE;
+        host.on_sync_request(offering.request(2));
+        let MessageBody::SyncReply(reply) = &host.send_queue.back().unwrap().body else {
+            panic!("a sync request should be answered");
+        };
+        assert_ne!(reply.features & SPECTATOR_KEYFRAME_FEATURE, 0);
+        assert!(host.handshake_failed.is_none());
+        assert!(host.keyframes_active());
+    }
+
+    #[test]
+    fn spectator_offers_keyframes_in_every_handshake_message() {
+        let mut spectator: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0), PlayerHandle::new(1)], 2, 1, 8);
+        spectator.enable_spectator_keyframes(1, true);
+        spectator.synchronize().unwrap();
+        let MessageBody::SyncRequest(request) = &spectator.send_queue.back().unwrap().body else {
+            panic!("synchronize should queue a sync request");
+        };
+        assert_ne!(request.features & SPECTATOR_KEYFRAME_FEATURE, 0);
+
+        let plain = spectator.local_handshake;
+        spectator.on_sync_request(plain.request(1));
+        let MessageBody::SyncReply(reply) = &spectator.send_queue.back().unwrap().body else {
+            panic!("a sync request should be answered");
+        };
+        assert_ne!(reply.features & SPECTATOR_KEYFRAME_FEATURE, 0);
+        assert!(
+            !spectator.keyframes_active(),
+            "a host that never echoed keeps the current format"
+        );
+    }
+
+    #[test]
+    fn keyframe_input_stream_roundtrips_from_host_to_spectator() {
+        let handles = vec![PlayerHandle::new(0), PlayerHandle::new(1)];
+        let mut host: UdpProtocol<TestConfig> = create_protocol(handles.clone(), 2, 2, 8);
+        host.enable_spectator_keyframes(4, false);
+        host.synchronize().unwrap();
+        let mut offering = host.local_handshake;
+        offering.features |= SPECTATOR_KEYFRAME_FEATURE;
+        host.on_sync_request(offering.request(1));
+        complete_test_sync(&mut host);
+        host.send_queue.clear();
+        assert!(host.keyframes_active());
+
+        let mut spectator: UdpProtocol<TestConfig> = create_protocol(handles, 2, 1, 8);
+        spectator.enable_spectator_keyframes(1, true);
+        spectator.synchronize().unwrap();
+        let mut echoing = spectator.local_handshake;
+        echoing.features |= SPECTATOR_KEYFRAME_FEATURE;
+        spectator.observe_handshake(echoing);
+        assert!(spectator.keyframes_active());
+
+        // Player 1 changes once and holds; frame 4 is a keyframe.
+        let expected: Vec<Vec<u8>> = (0..7u32)
+            .map(|frame| {
+                let held = if frame >= 2 { 9u32 } else { 0 };
+                [0u32.to_le_bytes(), held.to_le_bytes()].concat()
+            })
+            .collect();
+        for (frame, bytes) in expected.iter().enumerate() {
+            host.pending_output.push_back(InputBytes {
+                frame: Frame::new(i32::try_from(frame).unwrap()),
+                bytes: bytes.clone(),
+            });
+        }
+        host.send_pending_output(&[ConnectionStatus::default(); 2]);
+        let body = queued_input_body(&host).clone();
+        assert_eq!(
+            rle::decode(&body.bytes).unwrap()[0],
+            0b11,
+            "frame 0 is a keyframe: every slot is present"
+        );
+
+        spectator.on_input(&body);
+
+        let received: Vec<Vec<u8>> = (0..7)
+            .map(|frame| spectator.recv_inputs[&Frame::new(frame)].bytes.clone())
+            .collect();
+        assert_eq!(received, expected);
+    }
+
+    /// A running endpoint with input resync enabled at `threshold`, whose peer
+    /// advertised the feature.
+    fn input_resync_protocol(
+        handles: Vec<PlayerHandle>,
+        threshold: usize,
+    ) -> UdpProtocol<TestConfig> {
+        let mut protocol = create_protocol_with_config(
+            handles,
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                input_resync_threshold: Some(threshold),
+                ..ProtocolConfig::default()
+            },
+        );
+        protocol.synchronize().unwrap();
+        let mut offering = protocol.local_handshake;
+        offering.features |= INPUT_RESYNC_FEATURE;
+        protocol.observe_handshake(offering);
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+        protocol
+    }
+
+    #[test]
+    fn input_resync_is_active_only_when_both_peers_enable_it() {
+        let config = ProtocolConfig {
+            input_resync_threshold: Some(16),
+            ..ProtocolConfig::default()
+        };
+        let mut enabled: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(1)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            config,
+        );
+        enabled.synchronize().unwrap();
+        let MessageBody::SyncRequest(request) = &enabled.send_queue.back().unwrap().body else {
+            panic!("synchronize should queue a sync request");
+        };
+        assert_ne!(request.features & INPUT_RESYNC_FEATURE, 0);
+        let plain = enabled.local_handshake;
+        enabled.on_sync_request(plain.request(1));
+        let MessageBody::SyncReply(reply) = &enabled.send_queue.back().unwrap().body else {
+            panic!("a sync request should be answered");
+        };
+        assert_ne!(reply.features & INPUT_RESYNC_FEATURE, 0);
+        assert!(
+            !enabled.input_resync_active(),
+            "a peer that never advertised keeps the untagged format"
+        );
+
+        let mut disabled: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(1)], 2, 1, 8);
+        disabled.synchronize().unwrap();
+        let MessageBody::SyncRequest(request) = &disabled.send_queue.back().unwrap().body else {
+            panic!("synchronize should queue a sync request");
+        };
+        assert_eq!(request.features & INPUT_RESYNC_FEATURE, 0);
+        let mut offering = disabled.local_handshake;
+        offering.features |= INPUT_RESYNC_FEATURE;
+        disabled.on_sync_request(offering.request(2));
+        assert!(disabled.handshake_failed.is_none());
+        assert!(!disabled.input_resync_active());
+
+        enabled.on_sync_request(offering.request(3));
+        assert!(enabled.handshake_failed.is_none());
+        assert!(enabled.input_resync_active());
+    }
+
+    #[test]
+    fn resync_batch_decodes_after_the_receiver_pruned_its_reference() {
+        let bytes = |frame: i32| u32::try_from(frame).unwrap().to_le_bytes().to_vec();
+        let input = |frame: i32| InputBytes {
+            frame: Frame::new(frame),
+            bytes: bytes(frame),
+        };
+        let mut sender = input_resync_protocol(vec![PlayerHandle::new(1)], 16);
+        sender.last_acked_input = input(49);
+        for frame in 50..=70 {
+            sender.pending_output.push_back(input(frame));
+        }
+        sender.send_pending_output(&[ConnectionStatus::default(); 2]);
+        let body = queued_input_body(&sender).clone();
+        assert_eq!(body.start_frame, Frame::new(50));
+        assert_eq!(body.bytes[0], INPUT_BODY_RESYNC);
+        assert_eq!(sender.peer_metrics().input_resyncs_sent, 1);
+
+        // Acks from the receiver were lost: it already holds frames up to 60 and
+        // has pruned frame 49, the sender's delta reference.
+        let mut receiver = input_resync_protocol(vec![PlayerHandle::new(0)], 16);
+        receiver.recv_inputs.insert(Frame::new(60), input(60));
+        receiver.on_input(&body);
+        for frame in 61..=70 {
+            assert_eq!(receiver.recv_inputs[&Frame::new(frame)].bytes, bytes(frame));
+        }
+        let ack = match &receiver.send_queue.back().expect("ack queued").body {
+            MessageBody::InputAck(ack) => *ack,
+            other => panic!("expected InputAck, got {other:?}"),
+        };
+        assert_eq!(ack.ack_frame, Frame::new(70));
+
+        // The ack moves the sender's reference forward; the next batch is a
+        // plain delta against it again.
+        sender.on_input_ack(ack);
+        assert!(sender.pending_output.is_empty());
+        assert_eq!(sender.last_acked_input.frame, Frame::new(70));
+        sender.send_queue.clear();
+        sender.pending_output.push_back(input(71));
+        sender.send_pending_output(&[ConnectionStatus::default(); 2]);
+        let body = queued_input_body(&sender).clone();
+        assert_eq!(body.bytes[0], INPUT_BODY_DELTA);
+        assert_eq!(sender.peer_metrics().input_resyncs_sent, 1);
+        receiver.on_input(&body);
+        assert_eq!(receiver.recv_inputs[&Frame::new(71)].bytes, bytes(71));
+    }
+
+    /// A running endpoint that resends `redundant_input_frames` frames.
+    fn redundant_input_protocol(
+        handles: Vec<PlayerHandle>,
+        redundant_input_frames: usize,
+    ) -> UdpProtocol<TestConfig> {
+        let mut protocol = create_protocol_with_config(
+            handles,
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                redundant_input_frames,
+                ..ProtocolConfig::default()
+            },
+        );
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+        protocol
+    }
+
+    /// Sends frames `0..frames` one at a time, each delivered and acked, and
+    /// returns the start frame of every `Input` packet.
+    fn send_acked_frames(
+        sender: &mut UdpProtocol<TestConfig>,
+        receiver: &mut UdpProtocol<TestConfig>,
+        frames: i32,
+    ) -> Vec<Frame> {
+        let mut start_frames = Vec::new();
+        for frame in 0..frames {
+            sender.pending_output.push_back(InputBytes {
+                frame: Frame::new(frame),
+                bytes: u32::try_from(frame).unwrap().to_le_bytes().to_vec(),
+            });
+            sender.send_pending_output(&[ConnectionStatus::default(); 2]);
+            let body = queued_input_body(sender).clone();
+            sender.send_queue.clear();
+            start_frames.push(body.start_frame);
+            receiver.on_input(&body);
+            let ack = match &receiver.send_queue.back().expect("ack queued").body {
+                MessageBody::InputAck(ack) => *ack,
+                other => panic!("expected InputAck, got {other:?}"),
+            };
+            receiver.send_queue.clear();
+            sender.on_input_ack(ack);
+        }
+        start_frames
+    }
+
+    #[test]
+    fn redundant_inputs_resend_acknowledged_frames() {
+        let mut sender = redundant_input_protocol(vec![PlayerHandle::new(1)], 3);
+        let mut receiver = redundant_input_protocol(vec![PlayerHandle::new(0)], 3);
+        let start_frames = send_acked_frames(&mut sender, &mut receiver, 10);
+
+        // Each acked frame is kept until two newer ones are; one kept frame
+        // always stays behind as the delta reference.
+        let expected: Vec<_> = [0, 1, 1, 1, 2, 3, 4, 5, 6, 7]
+            .into_iter()
+            .map(Frame::new)
+            .collect();
+        assert_eq!(start_frames, expected);
+        for frame in 0..10 {
+            assert_eq!(
+                receiver.recv_inputs[&Frame::new(frame)].bytes,
+                u32::try_from(frame).unwrap().to_le_bytes()
+            );
+        }
+        assert!(sender.pending_output.is_empty());
+        assert!(sender.acked_output.len() <= 2);
+
+        let m = sender.peer_metrics();
+        assert_eq!(m.redundant_input_frames_sent, 15);
+        assert_eq!(m.redundant_input_bytes_pre_compression, 15 * 4);
+        assert_eq!(m.input_bytes_pre_compression, (10 + 15) * 4);
+    }
+
+    #[test]
+    fn single_redundant_frame_sends_only_unacknowledged_input() {
+        let mut sender = redundant_input_protocol(vec![PlayerHandle::new(1)], 1);
+        let mut receiver = redundant_input_protocol(vec![PlayerHandle::new(0)], 1);
+        let start_frames = send_acked_frames(&mut sender, &mut receiver, 10);
+
+        let expected: Vec<_> = (0..10).map(Frame::new).collect();
+        assert_eq!(start_frames, expected);
+        assert!(sender.acked_output.is_empty());
+        let m = sender.peer_metrics();
+        assert_eq!(m.redundant_input_frames_sent, 0);
+        assert_eq!(m.redundant_input_bytes_pre_compression, 0);
+        assert_eq!(m.input_bytes_pre_compression, 10 * 4);
+    }
+
+    #[test]
+    fn redundant_frames_fill_the_batch_up_to_the_configured_count() {
+        let mut sender = redundant_input_protocol(vec![PlayerHandle::new(1)], 4);
+        let mut receiver = redundant_input_protocol(vec![PlayerHandle::new(0)], 4);
+        send_acked_frames(&mut sender, &mut receiver, 6);
+        let before = sender.peer_metrics().redundant_input_frames_sent;
+
+        // Two unacknowledged frames leave room for two acknowledged ones.
+        for frame in 6..8 {
+            sender.pending_output.push_back(InputBytes {
+                frame: Frame::new(frame),
+                bytes: u32::try_from(frame).unwrap().to_le_bytes().to_vec(),
+            });
+        }
+        sender.send_pending_output(&[ConnectionStatus::default(); 2]);
+        let body = queued_input_body(&sender).clone();
+        assert_eq!(body.start_frame, Frame::new(4));
+        assert_eq!(
+            sender.peer_metrics().redundant_input_frames_sent,
+            before + 2
+        );
+
+        // A receiver that missed frame 6 fills the gap from this one packet.
+        receiver.on_input(&body);
+        assert_eq!(
+            receiver.recv_inputs[&Frame::new(7)].bytes,
+            7u32.to_le_bytes()
+        );
+    }
+
+    /// A running endpoint with frame tags enabled, whose peer advertised them.
+    fn frame_tag_protocol(handles: Vec<PlayerHandle>) -> UdpProtocol<TestConfig> {
+        let mut protocol = create_protocol_with_config(
+            handles,
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                frame_tags: true,
+                ..ProtocolConfig::default()
+            },
+        );
+        protocol.synchronize().unwrap();
+        let mut offering = protocol.local_handshake;
+        offering.features |= FRAME_TAG_FEATURE;
+        protocol.observe_handshake(offering);
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+        protocol
+    }
+
+    /// Sends local player 0's input for `frame` and returns the queued `Input`.
+    fn send_tagged_input(protocol: &mut UdpProtocol<TestConfig>, frame: i32) -> Input {
+        let mut inputs: VecMap<PlayerHandle, PlayerInput<TestInput>> = VecMap::new();
+        inputs.insert(
+            PlayerHandle::new(0),
+            PlayerInput::new(Frame::new(frame), TestInput { inp: 7 }),
+        );
+        protocol.send_input(&inputs, &[ConnectionStatus::default(); 2]);
+        let body = queued_input_body(protocol).clone();
+        protocol.send_queue.clear();
+        body
+    }
+
+    /// Bytes of the frame tag section `protocol` reads from `body`.
+    fn frame_tags_len(protocol: &UdpProtocol<TestConfig>, body: &Input) -> usize {
+        protocol
+            .split_input_body(&body.bytes)
+            .expect("well-formed input body")
+            .frame_tags
+            .len()
+    }
+
+    fn received_frame_tags(protocol: &UdpProtocol<TestConfig>) -> Vec<(Frame, [u8; 8])> {
+        protocol
+            .event_queue
+            .iter()
+            .map(|(_, event)| event)
+            .filter_map(|event| match event {
+                Event::FrameTag { frame, data } => Some((*frame, *data)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frame_tags_ride_every_input_until_acked_and_arrive_once() {
+        let mut sender = frame_tag_protocol(vec![PlayerHandle::new(1)]);
+        let mut receiver = frame_tag_protocol(vec![PlayerHandle::new(0)]);
+        sender.queue_frame_tag(Frame::new(3), *b"round-up");
+        assert_eq!(sender.frame_tags[0].carrier, Frame::NULL);
+
+        // The first packet carrying the tag is lost; the resend carries it too.
+        let lost = send_tagged_input(&mut sender, 0);
+        assert_eq!(sender.frame_tags[0].carrier, Frame::new(0));
+        let resent = send_tagged_input(&mut sender, 1);
+        assert_eq!(frame_tags_len(&receiver, &lost), FRAME_TAG_WIRE_LEN);
+        assert_eq!(frame_tags_len(&receiver, &resent), FRAME_TAG_WIRE_LEN);
+
+        receiver.on_input(&resent);
+        receiver.on_input(&resent);
+        receiver.on_input(&lost);
+        assert_eq!(
+            received_frame_tags(&receiver),
+            vec![(Frame::new(3), *b"round-up")]
+        );
+
+        // Acknowledging the carrier frame retires the tag.
+        sender.on_input_ack(InputAck {
+            ack_frame: Frame::new(0),
+        });
+        assert!(sender.frame_tags.is_empty());
+        let untagged = send_tagged_input(&mut sender, 2);
+        assert_eq!(frame_tags_len(&receiver, &untagged), 0);
+        receiver.on_input(&untagged);
+        assert_eq!(received_frame_tags(&receiver).len(), 1);
+    }
+
+    #[test]
+    fn frame_tags_travel_only_when_both_peers_enable_them() {
+        let mut sender: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(1)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                frame_tags: true,
+                ..ProtocolConfig::default()
+            },
+        );
+        sender.synchronize().unwrap();
+        let MessageBody::SyncRequest(request) = &sender.send_queue.back().unwrap().body else {
+            panic!("synchronize should queue a sync request");
+        };
+        assert_ne!(request.features & FRAME_TAG_FEATURE, 0);
+        complete_test_sync(&mut sender);
+        sender.send_queue.clear();
+        assert!(!sender.frame_tags_active());
+
+        // Without the peer's advertisement the body keeps the untagged format.
+        sender.queue_frame_tag(Frame::new(0), [1; 8]);
+        let body = send_tagged_input(&mut sender, 0);
+        let mut receiver: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        receiver.synchronize().unwrap();
+        complete_test_sync(&mut receiver);
+        receiver.on_input(&body);
+        assert!(receiver.recv_inputs.contains_key(&Frame::new(0)));
+        assert!(received_frame_tags(&receiver).is_empty());
+    }
+
+    #[test]
+    fn unacknowledged_frame_tags_are_dropped_beyond_the_prediction_window() {
+        let mut sender = frame_tag_protocol(vec![PlayerHandle::new(1)]);
+        for frame in 0..=8 {
+            sender.queue_frame_tag(Frame::new(frame), [0; 8]);
+        }
+        assert_eq!(sender.frame_tags.len(), 9);
+        assert_eq!(sender.peer_metrics().frame_tags_dropped, 0);
+
+        sender.queue_frame_tag(Frame::new(10), [0; 8]);
+        assert_eq!(sender.peer_metrics().frame_tags_dropped, 2);
+        let frames: Vec<i32> = sender
+            .frame_tags
+            .iter()
+            .map(|tag| tag.frame.as_i32())
+            .collect();
+        assert_eq!(frames, (2..=8).chain([10]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn checksum_authority_is_advertised_by_the_spectator_and_keeps_reports() {
+        let mut spectator: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0), PlayerHandle::new(1)], 2, 1, 8);
+        spectator.enable_checksum_authority();
+        spectator.synchronize().unwrap();
+        let MessageBody::SyncRequest(request) = &spectator.send_queue.back().unwrap().body else {
+            panic!("synchronize should queue a sync request");
+        };
+        assert_ne!(request.features & CHECKSUM_AUTHORITY_FEATURE, 0);
+
+        let mut host: UdpProtocol<TestConfig> = create_protocol(vec![], 2, 2, 8);
+        host.synchronize().unwrap();
+        assert!(!host.wants_checksum_reports());
+        let mut offering = host.local_handshake;
+        offering.features |= CHECKSUM_AUTHORITY_FEATURE;
+        host.observe_handshake(offering);
+        assert!(host.wants_checksum_reports());
+        assert_eq!(host.offered_features(false) & CHECKSUM_AUTHORITY_FEATURE, 0);
+
+        // Without desync detection the reports are kept; the oldest one leaves
+        // the bounded history first.
+        let max_history = spectator.protocol_config.max_checksum_history;
+        for frame in 0..=max_history {
+            spectator.on_checksum_report(&ChecksumReport {
+                frame: Frame::new(i32::try_from(frame).unwrap()),
+                checksum: 7,
+            });
+        }
+        assert_eq!(spectator.pending_checksums.len(), max_history);
+        assert!(!spectator.pending_checksums.contains_key(&Frame::new(0)));
+    }
+
+    /// A running endpoint with sequence numbers enabled; `peer_offers` sets
+    /// whether the peer advertised them too.
+    fn sequenced_protocol(peer_offers: bool) -> UdpProtocol<TestConfig> {
+        let mut protocol = create_protocol_with_config(
+            vec![PlayerHandle::new(1)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                sequence_numbers: true,
+                ..ProtocolConfig::default()
+            },
+        );
+        protocol.synchronize().unwrap();
+        let MessageBody::SyncRequest(request) = &protocol.send_queue.back().unwrap().body else {
+            panic!("synchronize should queue a sync request");
+        };
+        assert_ne!(request.features & SEQUENCE_FEATURE, 0);
+        let mut offering = protocol.local_handshake;
+        if peer_offers {
+            offering.features |= SEQUENCE_FEATURE;
+        }
+        protocol.observe_handshake(offering);
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+        protocol
+    }
+
+    fn queued_sequences(protocol: &UdpProtocol<TestConfig>) -> Vec<Option<u32>> {
+        protocol
+            .send_queue
+            .iter()
+            .map(|msg| msg.header.sequence())
+            .collect()
+    }
+
+    #[test]
+    fn messages_are_numbered_only_when_both_peers_enable_sequence_numbers() {
+        let mut unnumbered = sequenced_protocol(false);
+        unnumbered.send_quality_report();
+        assert_eq!(queued_sequences(&unnumbered), vec![None]);
+
+        let mut numbered = sequenced_protocol(true);
+        numbered.next_send_sequence = u32::MAX;
+        numbered.send_quality_report();
+        numbered.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+        assert_eq!(queued_sequences(&numbered), vec![Some(u32::MAX), Some(0)]);
+        assert_eq!(
+            numbered.send_queue[0].encoded_len(),
+            unnumbered.send_queue[0].encoded_len() + 4
+        );
+    }
+
+    #[test]
+    fn duplicate_and_stale_sequences_are_dropped_before_handling() {
+        let mut receiver = sequenced_protocol(true);
+        let report = |sequence| Message {
+            header: MessageHeader::new(receiver.remote_conn_id).with_sequence(sequence),
+            body: MessageBody::QualityReport(QualityReport::default()),
+        };
+        let replies = |protocol: &UdpProtocol<TestConfig>| {
+            protocol
+                .send_queue
+                .iter()
+                .filter(|msg| matches!(msg.body, MessageBody::QualityReply(_)))
+                .count()
+        };
+        let (first, newest, stale) = (
+            report(5),
+            report(5 + sequence_window::SEQUENCE_WINDOW),
+            report(5),
+        );
+
+        receiver.handle_message(&first);
+        receiver.handle_message(&first);
+        assert_eq!(replies(&receiver), 1);
+        assert_eq!(receiver.duplicates_dropped, 1);
+
+        receiver.handle_message(&newest);
+        receiver.handle_message(&stale);
+        assert_eq!(replies(&receiver), 2);
+        assert_eq!(receiver.stale_dropped, 1);
+        // Dropped messages still count as received traffic.
+        assert_eq!(receiver.packets_received, 4);
+    }
+
+    #[test]
+    fn sequences_are_ignored_unless_negotiated() {
+        let mut receiver = sequenced_protocol(false);
+        let report = Message {
+            header: MessageHeader::new(receiver.remote_conn_id).with_sequence(3),
+            body: MessageBody::QualityReport(QualityReport::default()),
+        };
+        receiver.handle_message(&report);
+        receiver.handle_message(&report);
+        assert_eq!(receiver.send_queue.len(), 2);
+        assert_eq!(receiver.duplicates_dropped, 0);
+    }
+
+    fn queued_reliable_messages(protocol: &mut UdpProtocol<TestConfig>) -> Vec<ReliableMessage> {
+        let messages = protocol
+            .send_queue
+            .iter()
+            .filter_map(|msg| match &msg.body {
+                MessageBody::ReliableMessage(body) => Some(body.clone()),
+                _ => None,
+            })
+            .collect();
+        protocol.send_queue.clear();
+        messages
+    }
+
+    fn queued_reliable_acks(protocol: &UdpProtocol<TestConfig>) -> Vec<u32> {
+        protocol
+            .send_queue
+            .iter()
+            .filter_map(|msg| match &msg.body {
+                MessageBody::ReliableAck(body) => Some(body.next_sequence),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn received_reliable_messages(protocol: &UdpProtocol<TestConfig>) -> Vec<Vec<u8>> {
+        protocol
+            .event_queue
+            .iter()
+            .map(|(_, event)| event)
+            .filter_map(|event| match event {
+                Event::ReliableMessage { payload } => Some(payload.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reliable_messages_arrive_in_order_once_and_cumulative_acks_release_them() {
+        let mut sender: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(1)], 2, 1, 8);
+        let mut receiver: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        for protocol in [&mut sender, &mut receiver] {
+            protocol.synchronize().unwrap();
+            complete_test_sync(protocol);
+            protocol.send_queue.clear();
+        }
+        for payload in [b"a", b"b", b"c"] {
+            sender.queue_reliable(payload.to_vec()).unwrap();
+        }
+        let sent = queued_reliable_messages(&mut sender);
+        let sequences: Vec<u32> = sent.iter().map(|msg| msg.sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2]);
+
+        // Later messages wait for the missing first one, and the burst is
+        // answered by one ack that moves as messages are delivered.
+        receiver.on_reliable_message(&sent[2]);
+        receiver.on_reliable_message(&sent[1]);
+        assert!(received_reliable_messages(&receiver).is_empty());
+        assert_eq!(queued_reliable_acks(&receiver), vec![0]);
+        receiver.on_reliable_message(&sent[0]);
+        receiver.on_reliable_message(&sent[1]);
+        assert_eq!(
+            received_reliable_messages(&receiver),
+            vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]
+        );
+        assert_eq!(queued_reliable_acks(&receiver), vec![3]);
+
+        sender.on_reliable_ack(ReliableAck { next_sequence: 2 });
+        assert_eq!(sender.reliable_in_flight.len(), 1);
+        // A reordered older ack and one beyond the sent messages change nothing.
+        sender.on_reliable_ack(ReliableAck { next_sequence: 1 });
+        sender.on_reliable_ack(ReliableAck { next_sequence: 9 });
+        assert_eq!(sender.reliable_in_flight.len(), 1);
+        sender.on_reliable_ack(ReliableAck { next_sequence: 3 });
+        assert!(sender.reliable_in_flight.is_empty());
+    }
+
+    #[test]
+    fn reliable_channel_enforces_its_limits_and_resends_until_acked() {
+        let (mut config, clock) = mutable_clock_config();
+        config.reliable_window = 2;
+        config.max_reliable_payload = 4;
+        let sync_config = SyncConfig::default();
+        let mut protocol =
+            create_protocol_with_config(vec![PlayerHandle::new(1)], 2, 1, 8, sync_config, config);
+
+        // Messages queued before the handshake wait for it.
+        protocol.queue_reliable(b"one".to_vec()).unwrap();
+        assert_eq!(
+            protocol.queue_reliable(b"large".to_vec()),
+            Err(InvalidRequestKind::ReliablePayloadTooLarge { len: 5, max: 4 }.into())
+        );
+        protocol.queue_reliable(b"two".to_vec()).unwrap();
+        assert_eq!(
+            protocol.queue_reliable(b"six".to_vec()),
+            Err(InvalidRequestKind::ReliableWindowFull { window: 2 }.into())
+        );
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        assert!(queued_reliable_messages(&mut protocol).is_empty());
+
+        let connect_status = vec![ConnectionStatus::default(); 2];
+        for _ in 0..2 {
+            advance_test_clock(
+                &clock,
+                sync_config.running_retry_interval + Duration::from_millis(1),
+            );
+            protocol.poll(&connect_status).for_each(drop);
+            let resent: Vec<u32> = queued_reliable_messages(&mut protocol)
+                .iter()
+                .map(|msg| msg.sequence)
+                .collect();
+            assert_eq!(resent, vec![0, 1]);
+        }
+
+        protocol.on_reliable_ack(ReliableAck { next_sequence: 2 });
+        advance_test_clock(
+            &clock,
+            sync_config.running_retry_interval + Duration::from_millis(1),
+        );
+        protocol.poll(&connect_status).for_each(drop);
+        assert!(queued_reliable_messages(&mut protocol).is_empty());
+        protocol.queue_reliable(b"six".to_vec()).unwrap();
+        assert_eq!(queued_reliable_messages(&mut protocol)[0].sequence, 2);
     }
 
     #[test]
@@ -4267,6 +8205,7 @@ fn mismatched_request_replies_with_ours_and_fails_exactly_once() {
             protocol
                 .event_queue
                 .iter()
+                .map(|(_, event)| event)
                 .filter(|event| matches!(event, Event::Incompatible { .. }))
                 .count(),
             1
@@ -4283,6 +8222,7 @@ fn mismatched_request_replies_with_ours_and_fails_exactly_once() {
             protocol
                 .event_queue
                 .iter()
+                .map(|(_, event)| event)
                 .filter(|event| matches!(event, Event::Incompatible { .. }))
                 .count(),
             1
@@ -4290,6 +8230,189 @@ fn mismatched_request_replies_with_ours_and_fails_exactly_once() {
         assert_eq!(protocol.send_queue.len(), 2, "failed endpoints still reply");
     }
 
+    #[test]
+    fn claimed_handles_must_match_the_handles_registered_for_the_peer() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(1)], 2, 1, 8);
+        protocol.claim_handles(&[PlayerHandle::new(0)], Some(&[PlayerHandle::new(1)]));
+        protocol.synchronize().unwrap();
+        assert_eq!(
+            protocol.local_handshake.handles_digest,
+            handles_digest(&[PlayerHandle::new(0)])
+        );
+
+        // The peer claims handle 0 as well.
+        let mut theirs = protocol.local_handshake;
+        protocol.on_sync_request(theirs.request(7));
+        assert_eq!(
+            protocol.handshake_failed,
+            Some(IncompatibleSessionReason::DuplicateHandle {
+                handle: PlayerHandle::new(0)
+            })
+        );
+        assert_eq!(
+            protocol.handshake_failed.map(|reason| reason.field()),
+            Some("player_handles")
+        );
+
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(1)], 2, 1, 8);
+        protocol.claim_handles(&[PlayerHandle::new(0)], Some(&[PlayerHandle::new(1)]));
+        protocol.synchronize().unwrap();
+        theirs.handles_digest = handles_digest(&[PlayerHandle::new(1)]);
+        protocol.on_sync_request(theirs.request(8));
+        assert_eq!(protocol.handshake_failed, None);
+    }
+
+    #[test]
+    fn a_doubly_claimed_handle_is_named_and_a_missing_claim_reports_digests() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(2)], 4, 2, 8);
+        protocol.claim_handles(
+            &[PlayerHandle::new(0), PlayerHandle::new(1)],
+            Some(&[PlayerHandle::new(2)]),
+        );
+        protocol.synchronize().unwrap();
+        let mut theirs = protocol.local_handshake;
+        theirs.handles_digest = handles_digest(&[
+            PlayerHandle::new(1),
+            PlayerHandle::new(2),
+            PlayerHandle::new(3),
+        ]);
+        protocol.on_sync_request(theirs.request(7));
+        let reason = protocol.handshake_failed.unwrap();
+        assert_eq!(
+            reason,
+            IncompatibleSessionReason::DuplicateHandle {
+                handle: PlayerHandle::new(1)
+            }
+        );
+        assert_eq!(
+            reason.to_string(),
+            "player handle 1 claimed by more than one endpoint"
+        );
+
+        // Claiming too little leaves a handle unowned, not doubly owned.
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(1), PlayerHandle::new(2)], 3, 1, 8);
+        protocol.claim_handles(
+            &[PlayerHandle::new(0)],
+            Some(&[PlayerHandle::new(1), PlayerHandle::new(2)]),
+        );
+        protocol.synchronize().unwrap();
+        let mut theirs = protocol.local_handshake;
+        theirs.handles_digest = handles_digest(&[PlayerHandle::new(1)]);
+        protocol.on_sync_request(theirs.request(8));
+        assert_eq!(
+            protocol.handshake_failed,
+            Some(IncompatibleSessionReason::PlayerHandles {
+                ours: handles_digest(&[PlayerHandle::new(1), PlayerHandle::new(2)]),
+                theirs: handles_digest(&[PlayerHandle::new(1)]),
+            })
+        );
+    }
+
+    #[test]
+    fn handle_digests_resolve_only_within_the_search_bound() {
+        let handles = [PlayerHandle::new(3), PlayerHandle::new(15)];
+        assert_eq!(
+            resolve_handles_digest(handles_digest(&handles), MAX_RESOLVED_CLAIM_PLAYERS),
+            Some(handles.to_vec())
+        );
+        assert_eq!(
+            resolve_handles_digest(handles_digest(&handles), MAX_RESOLVED_CLAIM_PLAYERS + 1),
+            None
+        );
+        assert_eq!(resolve_handles_digest(handles_digest(&handles), 4), None);
+    }
+
+    #[test]
+    fn spectator_access_gate_stays_silent_until_the_token_is_proved() {
+        const TOKEN: [u8; 16] = [7; 16];
+        let mut host: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0), PlayerHandle::new(1)], 2, 2, 8);
+        host.require_spectator_access(TOKEN);
+        host.synchronize().unwrap();
+        assert!(host.send_queue.is_empty(), "no SyncRequest before a proof");
+
+        let theirs = host.local_handshake;
+        let mut wrong = theirs.request(7);
+        wrong.access_proof = access_proof(&[8; 16], 7);
+        host.on_sync_request(theirs.request(7));
+        host.on_sync_request(wrong);
+        assert!(host.send_queue.is_empty(), "unproved requests get no reply");
+        assert_eq!(host.peer_metrics().unauthorized_sync_requests, 2);
+
+        let mut proved = theirs.request(9);
+        proved.access_proof = access_proof(&TOKEN, 9);
+        host.on_sync_request(proved);
+        let bodies: Vec<_> = host
+            .send_queue
+            .iter()
+            .map(|message| &message.body)
+            .collect();
+        assert!(
+            matches!(bodies[..], [MessageBody::SyncRequest(_), MessageBody::SyncReply(reply)] if reply.random_reply == 9)
+        );
+
+        // Access does not outlive a bad proof: every request is checked.
+        host.on_sync_request(theirs.request(10));
+        assert_eq!(host.send_queue.len(), 2);
+        assert_eq!(host.peer_metrics().unauthorized_sync_requests, 3);
+    }
+
+    #[test]
+    fn spectator_endpoints_prove_their_token_in_every_sync_request() {
+        const TOKEN: [u8; 16] = [7; 16];
+        let mut spectator: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0), PlayerHandle::new(1)], 2, 2, 8);
+        spectator.prove_spectator_access(TOKEN);
+        spectator.synchronize().unwrap();
+        let Some(MessageBody::SyncRequest(request)) =
+            spectator.send_queue.back().map(|message| &message.body)
+        else {
+            panic!("synchronize should queue a SyncRequest");
+        };
+        assert_eq!(
+            request.access_proof,
+            access_proof(&TOKEN, request.random_request)
+        );
+
+        let mut plain: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0), PlayerHandle::new(1)], 2, 2, 8);
+        plain.synchronize().unwrap();
+        assert!(matches!(
+            plain.send_queue.back().map(|message| &message.body),
+            Some(MessageBody::SyncRequest(request)) if request.access_proof == 0
+        ));
+    }
+
+    #[test]
+    fn handle_claims_ignore_order_and_unverified_endpoints_accept_any_claim() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(1), PlayerHandle::new(2)], 3, 1, 8);
+        protocol.claim_handles(
+            &[PlayerHandle::new(0)],
+            Some(&[PlayerHandle::new(2), PlayerHandle::new(1)]),
+        );
+        assert_eq!(
+            protocol.expected_handles_digest,
+            Some(handles_digest(&[
+                PlayerHandle::new(1),
+                PlayerHandle::new(2)
+            ]))
+        );
+
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(1)], 2, 1, 8);
+        protocol.claim_handles(&[PlayerHandle::new(0)], None);
+        protocol.synchronize().unwrap();
+        let mut theirs = protocol.local_handshake;
+        theirs.handles_digest = handles_digest(&[PlayerHandle::new(7)]);
+        protocol.on_sync_request(theirs.request(7));
+        assert_eq!(protocol.handshake_failed, None);
+    }
+
     #[test]
     fn reply_validates_echo_before_config_and_mismatch_is_terminal() {
         let mut protocol: UdpProtocol<TestConfig> =
@@ -4344,7 +8467,7 @@ fn failed_handshake_stops_retries_and_timeout_but_keeps_answering() {
         protocol.event_queue.clear();
 
         advance_test_clock(&clock, Duration::from_secs(1));
-        let events: Vec<_> = protocol.poll(&[]).collect();
+        let events: Vec<_> = protocol.poll(&[]).map(|(_, event)| event).collect();
         assert!(events.is_empty());
         assert!(protocol.send_queue.is_empty());
 
@@ -4451,7 +8574,7 @@ fn handle_message_ignores_shutdown_state() {
 
         let msg = Message {
             header: MessageHeader::new(123),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         protocol.handle_message(&msg);
 
@@ -4478,7 +8601,7 @@ fn handle_message_filters_wrong_conn_id_after_sync() {
         // Send message with different conn_id
         let msg = Message {
             header: MessageHeader::new(123), // Wrong conn_id
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         protocol.handle_message(&msg);
 
@@ -4513,7 +8636,7 @@ fn handle_message_accepts_correct_conn_id() {
         // Send message with correct conn_id
         let msg = Message {
             header: MessageHeader::new(999),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         protocol.handle_message(&msg);
 
@@ -4568,6 +8691,7 @@ fn handle_message_drops_gameplay_messages_while_synchronizing_without_side_effec
                 body: MessageBody::QualityReport(QualityReport {
                     frame_advantage: 7,
                     ping: 123,
+                    stalled: false,
                 }),
             },
             Message {
@@ -4583,7 +8707,7 @@ fn handle_message_drops_gameplay_messages_while_synchronizing_without_side_effec
             },
             Message {
                 header: MessageHeader::new(123),
-                body: MessageBody::KeepAlive,
+                body: MessageBody::KeepAlive(KeepAlive::default()),
             },
         ];
 
@@ -4641,12 +8765,16 @@ fn network_resumed_event_after_interrupt() {
         // Handle a valid message
         let msg = Message {
             header: MessageHeader::new(999),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         protocol.handle_message(&msg);
 
         // Should have NetworkResumed event
-        let events: Vec<_> = protocol.event_queue.drain(..).collect();
+        let events: Vec<_> = protocol
+            .event_queue
+            .drain(..)
+            .map(|(_, event)| event)
+            .collect();
         assert!(events.iter().any(|e| matches!(e, Event::NetworkResumed)));
         assert!(!protocol.disconnect_notify_sent);
     }
@@ -4681,6 +8809,7 @@ fn input_ack_pops_pending_output() {
             frame: Frame::new(2),
             bytes: vec![2, 0, 0, 0],
         });
+        protocol.newest_sent_input_frame = Frame::new(2);
 
         assert_eq!(protocol.pending_output.len(), 3);
 
@@ -4723,7 +8852,7 @@ fn input_ack_rejects_future_ack_without_popping_pending_output() {
     }
 
     #[test]
-    fn on_input_ignores_future_ack_but_accepts_valid_input() {
+    fn on_input_rejects_future_ack_and_drops_the_message() {
         let mut protocol: UdpProtocol<TestConfig> =
             create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
         protocol.synchronize().unwrap();
@@ -4733,6 +8862,7 @@ fn on_input_ignores_future_ack_but_accepts_valid_input() {
             frame: Frame::new(0),
             bytes: vec![0, 0, 0, 0],
         });
+        protocol.newest_sent_input_frame = Frame::new(0);
 
         let zeroed_bytes = protocol
             .recv_inputs
@@ -4751,17 +8881,176 @@ fn on_input_ignores_future_ack_but_accepts_valid_input() {
             peer_connect_status: vec![ConnectionStatus::default(); 2],
         });
 
-        assert!(protocol.recv_inputs.contains_key(&Frame::new(0)));
+        assert!(!protocol.recv_inputs.contains_key(&Frame::new(0)));
+        assert!(protocol.event_queue.is_empty());
         assert_eq!(protocol.pending_output.len(), 1);
+        assert_eq!(protocol.last_acked_input.frame, Frame::NULL);
         assert_eq!(
-            protocol.pending_output.front().unwrap().frame,
-            Frame::new(0)
+            protocol
+                .peer_metrics()
+                .input_messages_rejected
+                .get(InputRejection::FutureAck),
+            1
         );
-        assert_eq!(protocol.last_acked_input.frame, Frame::NULL);
-        assert!(protocol
-            .event_queue
-            .iter()
-            .any(|event| matches!(event, Event::Input { .. })));
+    }
+
+    /// Only the prefix that fit the batch went out, so an ack of a frame still
+    /// queued but never sent is forged even though it is in `pending_output`.
+    #[test]
+    fn ack_bound_is_the_newest_sent_frame_not_the_newest_pending() {
+        let config = ProtocolConfig {
+            pending_output_limit: 3,
+            ..ProtocolConfig::default()
+        };
+        let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            config,
+        );
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        for i in 0..6 {
+            protocol.pending_output.push_back(InputBytes {
+                frame: Frame::new(i),
+                bytes: vec![0, 0, 0, 0],
+            });
+        }
+        protocol.send_pending_output(&[ConnectionStatus::default(); 2]);
+        assert_eq!(protocol.newest_sent_input_frame, Frame::new(2));
+
+        protocol.on_input_ack(InputAck {
+            ack_frame: Frame::new(4),
+        });
+        assert_eq!(protocol.pending_output.len(), 6);
+        assert_eq!(
+            protocol
+                .input_messages_rejected
+                .get(InputRejection::FutureAck),
+            1
+        );
+
+        protocol.on_input_ack(InputAck {
+            ack_frame: Frame::new(2),
+        });
+        assert_eq!(protocol.pending_output.len(), 3);
+        assert_eq!(protocol.last_acked_input.frame, Frame::new(2));
+        assert_eq!(protocol.input_messages_rejected.total(), 1);
+    }
+
+    #[test]
+    fn on_input_rejects_negative_start_frames() {
+        let mut protocol = running_protocol_three_slots();
+        for start_frame in [Frame::new(-5), Frame::NULL] {
+            protocol.on_input(&Input {
+                start_frame,
+                ack_frame: Frame::NULL,
+                bytes: encode_one_frame(&[0; 4], 1),
+                peer_connect_status: status_slot2(true, 3),
+            });
+        }
+
+        assert!(
+            !protocol
+                .peer_connect_status(PlayerHandle::new(2))
+                .disconnected
+        );
+        assert_eq!(protocol.recv_inputs.len(), 1);
+        assert_eq!(
+            protocol
+                .input_messages_rejected
+                .get(InputRejection::InvalidStartFrame),
+            2
+        );
+    }
+
+    #[test]
+    fn on_input_rejects_connect_status_of_the_wrong_length() {
+        let mut protocol = running_protocol_three_slots();
+        for peer_connect_status in [
+            vec![ConnectionStatus::default(); 2],
+            vec![ConnectionStatus::default(); 4],
+        ] {
+            protocol.on_input(&Input {
+                start_frame: Frame::new(0),
+                ack_frame: Frame::NULL,
+                bytes: encode_one_frame(&[0; 4], 1),
+                peer_connect_status,
+            });
+        }
+
+        assert_eq!(protocol.recv_inputs.len(), 1);
+        assert_eq!(
+            protocol
+                .input_messages_rejected
+                .get(InputRejection::ConnectStatusLength),
+            2
+        );
+        assert_eq!(protocol.input_messages_rejected.total(), 2);
+    }
+
+    /// A first message starting just below `i32::MAX` would leave the session
+    /// no room to predict past it; it is dropped before its gossip is merged.
+    #[test]
+    fn on_input_rejects_start_frame_without_prediction_headroom() {
+        let mut protocol = running_protocol_three_slots();
+        protocol.on_input(&Input {
+            start_frame: Frame::new(i32::MAX - 8),
+            ack_frame: Frame::NULL,
+            bytes: encode_one_frame(&[0; 4], 1),
+            peer_connect_status: status_slot2(true, 3),
+        });
+
+        assert!(
+            !protocol
+                .peer_connect_status(PlayerHandle::new(2))
+                .disconnected
+        );
+        assert_eq!(protocol.last_recv_frame(), Frame::NULL);
+        assert_eq!(
+            protocol
+                .input_messages_rejected
+                .get(InputRejection::FrameRangeOutOfBounds),
+            1
+        );
+    }
+
+    /// Duplicated and reordered packets are normal UDP behavior, not rejections.
+    #[test]
+    fn duplicate_and_reordered_inputs_are_not_rejected() {
+        let mut protocol = running_protocol_three_slots();
+        let reference = protocol
+            .recv_inputs
+            .get(&Frame::NULL)
+            .unwrap()
+            .bytes
+            .clone();
+        let inputs: Vec<Vec<u8>> = (0..3)
+            .map(|inp| crate::network::codec::encode(&TestInput { inp }).unwrap())
+            .collect();
+        let batch = |start: usize| Input {
+            start_frame: Frame::new(i32::try_from(start).unwrap()),
+            ack_frame: Frame::NULL,
+            bytes: crate::network::compression::encode(
+                if start == 0 {
+                    &reference
+                } else {
+                    &inputs[start - 1]
+                },
+                inputs[start..].iter(),
+            ),
+            peer_connect_status: vec![ConnectionStatus::default(); 3],
+        };
+
+        protocol.on_input(&batch(0));
+        protocol.on_input(&batch(1));
+        protocol.on_input(&batch(0));
+        protocol.on_input(&batch(2));
+
+        assert_eq!(protocol.last_recv_frame(), Frame::new(2));
+        assert_eq!(protocol.input_messages_rejected.total(), 0);
     }
 
     #[test]
@@ -4775,7 +9064,11 @@ fn on_goodbye_emits_exactly_one_disconnect_event() {
         protocol.on_goodbye(Goodbye { reason: 7 });
         protocol.on_goodbye(Goodbye { reason: 7 });
 
-        let events: Vec<_> = protocol.event_queue.drain(..).collect();
+        let events: Vec<_> = protocol
+            .event_queue
+            .drain(..)
+            .map(|(_, event)| event)
+            .collect();
         assert_eq!(events.len(), 1);
         assert!(matches!(events.first(), Some(Event::Disconnected)));
     }
@@ -4827,6 +9120,7 @@ fn goodbye_during_sync_requires_validated_connection_id_binding() {
         assert!(protocol
             .event_queue
             .iter()
+            .map(|(_, event)| event)
             .any(|event| matches!(event, Event::Disconnected)));
     }
 
@@ -4865,7 +9159,7 @@ fn rearm_discards_old_era_queue_before_new_sync_request() {
             create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
         protocol.force_running_for_tests();
         let old_conn_id = protocol.conn_id;
-        protocol.queue_message(MessageBody::KeepAlive);
+        protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
         protocol.disconnect();
 
         protocol
@@ -4883,7 +9177,7 @@ fn rearm_discards_old_era_queue_before_new_sync_request() {
         assert!(matches!(sync_request.body, MessageBody::SyncRequest(_)));
         assert!(protocol.send_queue.iter().all(|message| !matches!(
             message.body,
-            MessageBody::KeepAlive | MessageBody::Goodbye(_)
+            MessageBody::KeepAlive(_) | MessageBody::Goodbye(_)
         )));
     }
 
@@ -4929,7 +9223,9 @@ fn running_protocol_with_frame_zero() -> UdpProtocol<TestConfig> {
 
     /// Feed a connect-status gossip packet for slot 1 ("the dropped peer") while
     /// keeping slot 0 (the local-relative peer) connected. The packet re-uses
-    /// frame 0 so only the connect-status merge has an effect.
+    /// frame 0 so only the connect-status merge has an effect. Slot 1 is not
+    /// the peer's own, so a disconnect claim is then accepted the way a session
+    /// that corroborated it would.
     fn gossip_slot_one(
         protocol: &mut UdpProtocol<TestConfig>,
         disconnected: bool,
@@ -4957,6 +9253,64 @@ fn gossip_slot_one(
                 },
             ],
         });
+        protocol.accept_disconnect_claim(PlayerHandle::new(1));
+    }
+
+    #[test]
+    fn on_input_holds_third_party_disconnect_claim_until_accepted() {
+        let mut protocol = running_protocol_with_frame_zero();
+        let zeroed_bytes = protocol
+            .recv_inputs
+            .get(&Frame::NULL)
+            .unwrap()
+            .bytes
+            .clone();
+        let test_bytes = crate::network::codec::encode(&TestInput { inp: 1 }).unwrap();
+        let claim = |first: (bool, i32), second: (bool, i32)| Input {
+            start_frame: Frame::new(0),
+            ack_frame: Frame::NULL,
+            bytes: crate::network::compression::encode(&zeroed_bytes, std::iter::once(&test_bytes)),
+            peer_connect_status: vec![
+                ConnectionStatus {
+                    disconnected: first.0,
+                    last_frame: Frame::new(first.1),
+                    epoch: 0,
+                },
+                ConnectionStatus {
+                    disconnected: second.0,
+                    last_frame: Frame::new(second.1),
+                    epoch: 0,
+                },
+            ],
+        };
+
+        // Slot 1 is not the peer's: its claims only lower the pending frame,
+        // while the frame still counts as connected progress.
+        protocol.on_input(&claim((false, 0), (true, 6)));
+        protocol.on_input(&claim((false, 0), (true, 5)));
+        protocol.on_input(&claim((false, 0), (true, 7)));
+        let status = protocol.peer_connect_status(PlayerHandle::new(1));
+        assert!(!status.disconnected);
+        assert_eq!(status.last_frame, Frame::new(7));
+        assert_eq!(
+            protocol.disconnect_claim(PlayerHandle::new(1)),
+            Some(Frame::new(5))
+        );
+
+        protocol.accept_disconnect_claim(PlayerHandle::new(1));
+        let status = protocol.peer_connect_status(PlayerHandle::new(1));
+        assert!(status.disconnected);
+        assert_eq!(status.last_frame, Frame::new(5));
+        assert_eq!(protocol.disconnect_claim(PlayerHandle::new(1)), None);
+
+        // Slot 0 is the peer's own: its claim applies at once.
+        protocol.on_input(&claim((true, 3), (true, 5)));
+        assert!(
+            protocol
+                .peer_connect_status(PlayerHandle::new(0))
+                .disconnected
+        );
+        assert_eq!(protocol.disconnect_claim(PlayerHandle::new(0)), None);
     }
 
     #[test]
@@ -5015,14 +9369,14 @@ fn on_input_first_disconnect_adopts_remote_freeze_frame_when_higher() {
     fn on_input_first_disconnect_adopts_remote_freeze_frame_when_lower() {
         let mut protocol = running_protocol_with_frame_zero();
 
-        // Slot was connected with forward progress at 10; first disconnect gossip
+        // Slot was connected with forward progress at 7; first disconnect gossip
         // carries a lower authoritative freeze frame and must lower us to it.
-        gossip_slot_one(&mut protocol, false, 10);
+        gossip_slot_one(&mut protocol, false, 7);
         assert_eq!(
             protocol
                 .peer_connect_status(PlayerHandle::new(1))
                 .last_frame,
-            Frame::new(10)
+            Frame::new(7)
         );
 
         gossip_slot_one(&mut protocol, true, 4);
@@ -5049,21 +9403,42 @@ fn on_input_disconnected_slot_not_resurrected_by_stale_connected_gossip() {
     fn on_input_connected_slot_keeps_monotone_forward_progress() {
         let mut protocol = running_protocol_with_frame_zero();
 
-        // Connected slot advances 5 then 9 (max preserved), and a stale 3 cannot
+        // Connected slot advances 5 then 8 (max preserved), and a stale 3 cannot
         // regress it.
         gossip_slot_one(&mut protocol, false, 5);
-        gossip_slot_one(&mut protocol, false, 9);
+        gossip_slot_one(&mut protocol, false, 8);
+        assert_eq!(
+            protocol
+                .peer_connect_status(PlayerHandle::new(1))
+                .last_frame,
+            Frame::new(8)
+        );
+
+        gossip_slot_one(&mut protocol, false, 3);
+        let status = protocol.peer_connect_status(PlayerHandle::new(1));
+        assert!(!status.disconnected);
+        assert_eq!(status.last_frame, Frame::new(8));
+    }
+
+    #[test]
+    fn on_input_connected_slot_progress_is_bounded_by_the_peers_own_frames() {
+        let mut protocol = running_protocol_with_frame_zero();
+
+        // The peer has sent its inputs through frame 0 with a prediction
+        // window of 8: it cannot have received slot 1 beyond frame 8.
+        gossip_slot_one(&mut protocol, false, 1000);
+        let status = protocol.peer_connect_status(PlayerHandle::new(1));
+        assert!(!status.disconnected);
+        assert_eq!(status.last_frame, Frame::new(8));
+
+        // A disconnect claim still carries its own freeze frame.
+        gossip_slot_one(&mut protocol, true, 6);
         assert_eq!(
             protocol
                 .peer_connect_status(PlayerHandle::new(1))
                 .last_frame,
-            Frame::new(9)
+            Frame::new(6)
         );
-
-        gossip_slot_one(&mut protocol, false, 3);
-        let status = protocol.peer_connect_status(PlayerHandle::new(1));
-        assert!(!status.disconnected);
-        assert_eq!(status.last_frame, Frame::new(9));
     }
 
     /// Reactivation floor (session-33 review Finding 3, narrowed in-process
@@ -5718,6 +10093,9 @@ fn on_input_fresh_gossip_too_far_ahead_merges_before_gap_return() {
             bytes: encode_one_frame(&bytes, 99),
             peer_connect_status: status_slot2(true, 5),
         });
+        // Slot 2 is not the sender's own: accept its claim as a session that
+        // corroborated it would.
+        protocol.accept_disconnect_claim(PlayerHandle::new(2));
 
         // POST-HOIST: slot 2's drop gossip is applied even though the packet's
         // inputs were dropped by the gap-too-large branch. (Pre-hoist this stayed
@@ -5766,6 +10144,7 @@ fn on_input_decode_guard_false_only_for_stale_pruned_reference() {
             frame: Frame::new(0),
             bytes: vec![0; 12],
         });
+        protocol.newest_sent_input_frame = Frame::new(0);
 
         // A STALE retransmission for start_frame 50 (decode ref = frame 49, long
         // pruned). It passes the gap-too-large check (50 <= 100 + 1) but the
@@ -5780,6 +10159,9 @@ fn on_input_decode_guard_false_only_for_stale_pruned_reference() {
         let keys_before: Vec<Frame> = protocol.recv_inputs.keys().copied().collect();
         protocol.send_queue.clear();
         protocol.on_input(&stale);
+        // Slot 2 is not the sender's own: accept its claim as a session that
+        // corroborated it would.
+        protocol.accept_disconnect_claim(PlayerHandle::new(2));
 
         // POST-HOIST: the gossip is merged even though inputs can't be decoded.
         // (Pre-hoist the entire body was skipped at the decode guard, leaving
@@ -5812,6 +10194,7 @@ fn on_input_decode_guard_false_only_for_stale_pruned_reference() {
                 bytes: vec![0; 4],
             });
         }
+        sender.newest_sent_input_frame = Frame::new(100);
         let ack = match &protocol.send_queue.back().expect("re-ack queued").body {
             MessageBody::InputAck(ack) => *ack,
             other => panic!("expected cumulative InputAck, got {other:?}"),
@@ -5855,6 +10238,9 @@ fn on_input_low_start_frame_with_present_reference_applies_fresh_gossip() {
             bytes: encode_one_frame(&bytes, 42),
             peer_connect_status: status_slot2(true, 4),
         });
+        // Slot 2 is not the sender's own: accept its claim as a session that
+        // corroborated it would.
+        protocol.accept_disconnect_claim(PlayerHandle::new(2));
 
         let status = protocol.peer_connect_status(PlayerHandle::new(2));
         assert!(
@@ -5884,97 +10270,638 @@ fn on_input_hoisted_merge_does_not_un_converge_freeze_from_stale_packet() {
                 bytes: bytes.clone(),
             },
         );
-        protocol.on_input(&Input {
-            start_frame: Frame::new(1),
-            ack_frame: Frame::NULL,
-            bytes: encode_one_frame(&bytes, 1),
-            peer_connect_status: status_slot2(true, 4),
+        protocol.on_input(&Input {
+            start_frame: Frame::new(1),
+            ack_frame: Frame::NULL,
+            bytes: encode_one_frame(&bytes, 1),
+            peer_connect_status: status_slot2(true, 4),
+        });
+        // Slot 2 is not the sender's own: accept its claim as a session that
+        // corroborated it would.
+        protocol.accept_disconnect_claim(PlayerHandle::new(2));
+        assert_eq!(
+            protocol
+                .peer_connect_status(PlayerHandle::new(2))
+                .last_frame,
+            Frame::new(4)
+        );
+
+        // Advance last_recv far enough that frame 49 is pruned, then deliver a
+        // STALE packet (start_frame 50, ref 49 missing) re-asserting freeze @ 8.
+        protocol.recv_inputs.insert(
+            Frame::new(100),
+            InputBytes {
+                frame: Frame::new(100),
+                bytes: bytes.clone(),
+            },
+        );
+        protocol.on_input(&Input {
+            start_frame: Frame::new(50),
+            ack_frame: Frame::NULL,
+            bytes: encode_one_frame(&bytes, 2),
+            peer_connect_status: status_slot2(true, 8),
+        });
+
+        // The stale higher freeze must NOT un-converge us: min(4, 8) == 4.
+        let status = protocol.peer_connect_status(PlayerHandle::new(2));
+        assert!(status.disconnected);
+        assert_eq!(
+            status.last_frame,
+            Frame::new(4),
+            "hoisted merge from a stale packet must not re-raise a converged freeze"
+        );
+    }
+
+    /// An observer (no local player) sends an `Input` without input bytes; the
+    /// player's handle-less endpoint for it applies the gossip and the ack and
+    /// queues no input events.
+    #[test]
+    fn observer_status_input_carries_gossip_and_ack_only() {
+        let mut observer: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 3, 0, 8);
+        observer.synchronize().unwrap();
+        complete_test_sync(&mut observer);
+        observer.recv_inputs.insert(
+            Frame::new(1),
+            InputBytes {
+                frame: Frame::new(1),
+                bytes: vec![0; 4],
+            },
+        );
+        observer.send_queue.clear();
+        observer.send_observer_status(Frame::new(3), &status_slot2(true, 4));
+        let body = queued_input_body(&observer).clone();
+        assert_eq!(body.start_frame, Frame::new(3));
+        assert_eq!(body.ack_frame, Frame::new(1));
+        assert!(body.bytes.is_empty());
+        assert!(observer.pending_output.is_empty());
+
+        let mut player: UdpProtocol<TestConfig> = create_protocol(Vec::new(), 3, 1, 8);
+        player.synchronize().unwrap();
+        complete_test_sync(&mut player);
+        player.event_queue.clear();
+        for frame in 0..=2 {
+            player.pending_output.push_back(InputBytes {
+                frame: Frame::new(frame),
+                bytes: vec![0; 4],
+            });
+        }
+        player.newest_sent_input_frame = Frame::new(2);
+
+        player.on_input(&body);
+        // The observer owns no slot: its claim waits for corroboration.
+        assert_eq!(
+            player.disconnect_claim(PlayerHandle::new(2)),
+            Some(Frame::new(4))
+        );
+        player.accept_disconnect_claim(PlayerHandle::new(2));
+
+        assert_eq!(player.pending_output.len(), 1);
+        assert_eq!(player.last_acked_input.frame, Frame::new(1));
+        assert!(
+            player
+                .peer_connect_status(PlayerHandle::new(2))
+                .disconnected
+        );
+        assert_eq!(player.last_recv_frame(), Frame::NULL);
+        assert!(player.event_queue.is_empty());
+    }
+
+    #[test]
+    fn send_input_when_not_running_does_nothing() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        // Protocol is in Initializing state
+
+        let inputs = VecMap::new();
+        let connect_status = vec![ConnectionStatus::default(); 2];
+
+        protocol.send_input(&inputs, &connect_status);
+
+        // Should not queue any messages
+        assert!(protocol.send_queue.is_empty());
+        assert!(protocol.pending_output.is_empty());
+    }
+
+    // ==========================================
+    // Quality Report Tests
+    // ==========================================
+
+    #[test]
+    #[allow(clippy::wildcard_enum_match_arm)]
+    fn quality_report_triggers_reply() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+
+        // Complete sync
+        for _ in 0..TEST_NUM_SYNC_PACKETS {
+            let random = *protocol.sync_random_requests.iter().next().unwrap();
+            let header = MessageHeader::new(999);
+            protocol.on_sync_reply(header, matching_sync_reply(&protocol, random));
+        }
+        protocol.send_queue.clear();
+
+        let report = QualityReport {
+            frame_advantage: 5,
+            ping: 12345,
+            stalled: false,
+        };
+        protocol.on_quality_report(&report);
+
+        assert_eq!(protocol.remote_frame_advantage, 5);
+
+        // Should have queued a quality reply
+        assert_eq!(protocol.send_queue.len(), 1);
+        let msg = protocol.send_queue.front().unwrap();
+        match &msg.body {
+            MessageBody::QualityReply(reply) => {
+                assert_eq!(reply.pong, 12345);
+            },
+            _ => panic!("Expected QualityReply message"),
+        }
+    }
+
+    #[test]
+    fn advance_stall_hint_is_sent_and_reported_on_change_only() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        for _ in 0..TEST_NUM_SYNC_PACKETS {
+            let random = *protocol.sync_random_requests.iter().next().unwrap();
+            let header = MessageHeader::new(999);
+            protocol.on_sync_reply(header, matching_sync_reply(&protocol, random));
+        }
+        protocol.send_queue.clear();
+        protocol.event_queue.clear();
+
+        // A local change goes out at once; repeating it queues nothing new.
+        protocol.set_advance_stalled(true);
+        protocol.set_advance_stalled(true);
+        let reports: Vec<_> = protocol
+            .send_queue
+            .iter()
+            .filter_map(|msg| match &msg.body {
+                MessageBody::QualityReport(report) => Some(report.stalled),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reports, vec![true]);
+
+        // The remote's hint raises one event per change.
+        let report = |stalled| QualityReport {
+            frame_advantage: 0,
+            ping: 1,
+            stalled,
+        };
+        for stalled in [false, true, true, false, false] {
+            protocol.on_quality_report(&report(stalled));
+        }
+        let events: Vec<String> = protocol
+            .event_queue
+            .drain(..)
+            .map(|(_, event)| event)
+            .filter(|event| matches!(event, Event::AdvanceStalled | Event::AdvanceResumed))
+            .map(|event| event.to_string())
+            .collect();
+        assert_eq!(events, ["AdvanceStalled", "AdvanceResumed"]);
+    }
+
+    #[test]
+    fn queued_quality_report_is_refreshed_instead_of_duplicated() {
+        let (config, offset) = injected_clock_config();
+        let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            config,
+        );
+        protocol.send_queue.clear();
+        let packets_sent = protocol.packets_sent;
+
+        offset.store(100, std::sync::atomic::Ordering::Relaxed);
+        protocol.send_quality_report();
+        protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+        offset.store(300, std::sync::atomic::Ordering::Relaxed);
+        protocol.send_quality_report();
+
+        let reports: Vec<u128> = protocol
+            .send_queue
+            .iter()
+            .filter_map(|msg| match &msg.body {
+                MessageBody::QualityReport(report) => Some(report.ping),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reports, vec![protocol.ping_millis()]);
+        assert_eq!(protocol.send_queue.len(), 2);
+        assert_eq!(protocol.packets_sent, packets_sent + 2);
+    }
+
+    // ==========================================
+    // Send Priority Tests
+    // ==========================================
+
+    /// Records every sent message into a shared list.
+    struct RecordingSocket {
+        sent: Arc<Mutex<Vec<Message>>>,
+    }
+
+    impl NonBlockingSocket<SocketAddr> for RecordingSocket {
+        fn send_to(&mut self, msg: &Message, _addr: &SocketAddr) {
+            self.sent.lock().unwrap().push(msg.clone());
+        }
+
+        fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn send_all_messages_drains_by_priority_keeping_queued_order_within_a_class() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.send_queue.clear();
+        let checksum = |frame| {
+            MessageBody::ChecksumReport(ChecksumReport {
+                checksum: 1,
+                frame: Frame::new(frame),
+            })
+        };
+        let ack = |frame| {
+            MessageBody::InputAck(InputAck {
+                ack_frame: Frame::new(frame),
+            })
+        };
+        let sync = MessageBody::SyncRequest(matching_sync_request(&protocol, 7));
+        for body in [
+            checksum(5),
+            ack(1),
+            MessageBody::KeepAlive(KeepAlive::default()),
+            checksum(10),
+            ack(2),
+            MessageBody::QualityReply(QualityReply { pong: 3 }),
+            sync.clone(),
+        ] {
+            protocol.queue_message(body);
+        }
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut socket: Box<dyn NonBlockingSocket<SocketAddr>> = Box::new(RecordingSocket {
+            sent: Arc::clone(&sent),
+        });
+        protocol.send_all_messages(&mut socket);
+
+        assert!(protocol.send_queue.is_empty());
+        let bodies: Vec<MessageBody> = sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|msg| msg.body.clone())
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                sync,
+                MessageBody::KeepAlive(KeepAlive::default()),
+                MessageBody::QualityReply(QualityReply { pong: 3 }),
+                ack(1),
+                ack(2),
+                checksum(5),
+                checksum(10),
+            ]
+        );
+    }
+
+    #[test]
+    fn send_all_messages_counts_what_it_sends_and_nothing_once_shut_down() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.send_queue.clear();
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut socket: Box<dyn NonBlockingSocket<SocketAddr>> = Box::new(RecordingSocket {
+            sent: Arc::clone(&sent),
+        });
+
+        protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+        protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+        assert_eq!(protocol.send_all_messages(&mut socket), 2);
+        assert_eq!(protocol.send_all_messages(&mut socket), 0);
+        assert_eq!(sent.lock().unwrap().len(), 2);
+
+        protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+        protocol.state = ProtocolState::Shutdown;
+        assert_eq!(protocol.send_all_messages(&mut socket), 0);
+        assert!(protocol.send_queue.is_empty());
+        assert_eq!(sent.lock().unwrap().len(), 2);
+    }
+
+    /// Accepts `healthy_sends` sends, then refuses every send until the
+    /// shared count is raised again.
+    struct FailingSocket {
+        healthy_sends: Arc<Mutex<usize>>,
+    }
+
+    impl NonBlockingSocket<SocketAddr> for FailingSocket {
+        fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+            let _ = self.try_send_to(msg, addr);
+        }
+
+        fn try_send_to(
+            &mut self,
+            _msg: &Message,
+            _addr: &SocketAddr,
+        ) -> Result<(), crate::error::SocketErrorKind> {
+            let mut healthy_sends = self.healthy_sends.lock().unwrap();
+            if *healthy_sends == 0 {
+                return Err(crate::error::SocketErrorKind::SendFailed {
+                    kind: std::io::ErrorKind::ConnectionRefused,
+                });
+            }
+            *healthy_sends -= 1;
+            Ok(())
+        }
+
+        fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+            Vec::new()
+        }
+    }
+
+    fn transport_errors(protocol: &mut UdpProtocol<TestConfig>) -> Vec<u32> {
+        protocol
+            .event_queue
+            .drain(..)
+            .filter_map(|(_, event)| match event {
+                Event::TransportError {
+                    consecutive_failures,
+                } => Some(consecutive_failures),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn send_failures_report_a_transport_error_once_per_streak() {
+        let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                transport_error_threshold: Some(3),
+                ..ProtocolConfig::default()
+            },
+        );
+        protocol.send_queue.clear();
+        protocol.event_queue.clear();
+        let healthy_sends = Arc::new(Mutex::new(2));
+        let mut socket: Box<dyn NonBlockingSocket<SocketAddr>> = Box::new(FailingSocket {
+            healthy_sends: Arc::clone(&healthy_sends),
+        });
+        let mut send = |protocol: &mut UdpProtocol<TestConfig>, count| {
+            for _ in 0..count {
+                protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+            }
+            protocol.send_all_messages(&mut socket)
+        };
+
+        // Two sends go through, then two fail: below the threshold.
+        assert_eq!(send(&mut protocol, 4), 4);
+        assert_eq!(protocol.consecutive_send_failures, 2);
+        assert!(transport_errors(&mut protocol).is_empty());
+        // Refused messages still leave the queue.
+        assert!(protocol.send_queue.is_empty());
+
+        // The third failure reaches the threshold and reports once...
+        send(&mut protocol, 1);
+        assert_eq!(transport_errors(&mut protocol), vec![3]);
+        // ...and a longer streak does not report again.
+        send(&mut protocol, 5);
+        assert_eq!(protocol.consecutive_send_failures, 8);
+        assert!(transport_errors(&mut protocol).is_empty());
+
+        // A successful send clears the streak.
+        *healthy_sends.lock().unwrap() = 1;
+        send(&mut protocol, 1);
+        assert_eq!(protocol.consecutive_send_failures, 0);
+        assert!(transport_errors(&mut protocol).is_empty());
+
+        // So a new streak reports again at the threshold.
+        send(&mut protocol, 3);
+        assert_eq!(transport_errors(&mut protocol), vec![3]);
+    }
+
+    #[test]
+    fn send_failures_never_report_without_a_threshold() {
+        let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                transport_error_threshold: None,
+                ..ProtocolConfig::default()
+            },
+        );
+        protocol.send_queue.clear();
+        protocol.event_queue.clear();
+        let mut socket: Box<dyn NonBlockingSocket<SocketAddr>> = Box::new(FailingSocket {
+            healthy_sends: Arc::new(Mutex::new(0)),
         });
-        assert_eq!(
-            protocol
-                .peer_connect_status(PlayerHandle::new(2))
-                .last_frame,
-            Frame::new(4)
-        );
+        for _ in 0..50 {
+            protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+        }
 
-        // Advance last_recv far enough that frame 49 is pruned, then deliver a
-        // STALE packet (start_frame 50, ref 49 missing) re-asserting freeze @ 8.
-        protocol.recv_inputs.insert(
-            Frame::new(100),
-            InputBytes {
-                frame: Frame::new(100),
-                bytes: bytes.clone(),
-            },
-        );
-        protocol.on_input(&Input {
-            start_frame: Frame::new(50),
-            ack_frame: Frame::NULL,
-            bytes: encode_one_frame(&bytes, 2),
-            peer_connect_status: status_slot2(true, 8),
+        assert_eq!(protocol.send_all_messages(&mut socket), 50);
+        assert_eq!(protocol.consecutive_send_failures, 50);
+        assert!(transport_errors(&mut protocol).is_empty());
+    }
+
+    type SentMessages = Arc<Mutex<Vec<Message>>>;
+
+    fn recording_socket() -> (Box<dyn NonBlockingSocket<SocketAddr>>, SentMessages) {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let socket = Box::new(RecordingSocket {
+            sent: Arc::clone(&sent),
         });
+        (socket, sent)
+    }
 
-        // The stale higher freeze must NOT un-converge us: min(4, 8) == 4.
-        let status = protocol.peer_connect_status(PlayerHandle::new(2));
-        assert!(status.disconnected);
-        assert_eq!(
-            status.last_frame,
-            Frame::new(4),
-            "hoisted merge from a stale packet must not re-raise a converged freeze"
+    fn limited_protocol(
+        max_packets_per_poll: usize,
+        max_bytes_per_second: Option<u64>,
+    ) -> (UdpProtocol<TestConfig>, Arc<Mutex<Instant>>) {
+        let (config, clock) = mutable_clock_config();
+        let mut protocol = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            ProtocolConfig {
+                max_packets_per_poll,
+                max_bytes_per_second,
+                ..config
+            },
         );
+        protocol.send_queue.clear();
+        (protocol, clock)
     }
 
-    #[test]
-    fn send_input_when_not_running_does_nothing() {
-        let mut protocol: UdpProtocol<TestConfig> =
-            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
-        // Protocol is in Initializing state
+    fn quality_reply(pong: u128) -> MessageBody {
+        MessageBody::QualityReply(QualityReply { pong })
+    }
 
-        let inputs = BTreeMap::new();
-        let connect_status = vec![ConnectionStatus::default(); 2];
+    #[test]
+    fn packet_limit_holds_messages_until_the_next_poll() {
+        let (mut protocol, _clock) = limited_protocol(2, None);
+        let (mut socket, sent) = recording_socket();
+        for pong in 0..5 {
+            protocol.queue_message(quality_reply(pong));
+        }
 
-        protocol.send_input(&inputs, &connect_status);
+        assert_eq!(protocol.send_all_messages(&mut socket), 2);
+        // The budget is per poll, not per send.
+        assert_eq!(protocol.send_all_messages(&mut socket), 0);
+        assert_eq!(protocol.send_queue.len(), 3);
 
-        // Should not queue any messages
+        let _ = protocol.poll(&[]).count();
+        assert_eq!(protocol.send_all_messages(&mut socket), 2);
+        let _ = protocol.poll(&[]).count();
+        assert_eq!(protocol.send_all_messages(&mut socket), 1);
         assert!(protocol.send_queue.is_empty());
-        assert!(protocol.pending_output.is_empty());
-    }
 
-    // ==========================================
-    // Quality Report Tests
-    // ==========================================
+        let pongs: Vec<MessageBody> = sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|m| m.body.clone())
+            .collect();
+        assert_eq!(pongs, (0..5).map(quality_reply).collect::<Vec<_>>());
+    }
 
     #[test]
-    #[allow(clippy::wildcard_enum_match_arm)]
-    fn quality_report_triggers_reply() {
-        let mut protocol: UdpProtocol<TestConfig> =
-            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
-        protocol.synchronize().unwrap();
+    fn byte_limit_trickles_messages_out_as_the_budget_refills() {
+        let (mut protocol, clock) = limited_protocol(usize::MAX, Some(100));
+        let (mut socket, sent) = recording_socket();
+        protocol.queue_message(quality_reply(0));
+        let message_len = protocol.send_queue[0].encoded_len();
+        protocol.send_queue.clear();
+        for pong in 0..40 {
+            protocol.queue_message(quality_reply(pong));
+        }
 
-        // Complete sync
-        for _ in 0..TEST_NUM_SYNC_PACKETS {
-            let random = *protocol.sync_random_requests.iter().next().unwrap();
-            let header = MessageHeader::new(999);
-            protocol.on_sync_reply(header, matching_sync_reply(&protocol, random));
+        // A full bucket sends until it runs dry, the last message into debt.
+        let first = protocol.send_all_messages(&mut socket);
+        assert_eq!(first, 100_usize.div_ceil(message_len));
+        assert_eq!(protocol.send_all_messages(&mut socket), 0);
+
+        let mut total = first;
+        for _ in 0..10 {
+            advance_test_clock(&clock, Duration::from_millis(500));
+            total += protocol.send_all_messages(&mut socket);
+            let bytes = total * message_len;
+            assert!(bytes < 100 + 50 * 10 + message_len, "{bytes} bytes sent");
         }
-        protocol.send_queue.clear();
+        assert!(total > first, "the refill never released another message");
+        assert_eq!(sent.lock().unwrap().len(), total);
+        assert_eq!(protocol.send_queue.len(), 40 - total);
+    }
 
-        let report = QualityReport {
-            frame_advantage: 5,
-            ping: 12345,
+    #[test]
+    fn held_messages_drop_what_a_later_message_supersedes() {
+        let (mut protocol, _clock) = limited_protocol(1, None);
+        let (mut socket, _sent) = recording_socket();
+        let input = |start| {
+            MessageBody::Input(Input {
+                peer_connect_status: Vec::new(),
+                start_frame: Frame::new(start),
+                ack_frame: Frame::NULL,
+                bytes: Vec::new(),
+            })
         };
-        protocol.on_quality_report(&report);
+        let checksum = MessageBody::ChecksumReport(ChecksumReport {
+            checksum: 1,
+            frame: Frame::new(0),
+        });
+        for body in [
+            input(0),
+            checksum.clone(),
+            input(1),
+            input(2),
+            MessageBody::KeepAlive(KeepAlive::default()),
+            MessageBody::KeepAlive(KeepAlive::default()),
+            MessageBody::KeepAlive(KeepAlive::default()),
+        ] {
+            protocol.queue_message(body);
+        }
 
-        assert_eq!(protocol.remote_frame_advantage, 5);
+        assert_eq!(protocol.send_all_messages(&mut socket), 1);
+        let held: Vec<MessageBody> = protocol
+            .send_queue
+            .iter()
+            .map(|msg| msg.body.clone())
+            .collect();
+        assert_eq!(
+            held,
+            vec![
+                input(0),
+                checksum,
+                input(2),
+                MessageBody::KeepAlive(KeepAlive::default())
+            ]
+        );
+    }
 
-        // Should have queued a quality reply
-        assert_eq!(protocol.send_queue.len(), 1);
-        let msg = protocol.send_queue.front().unwrap();
-        match &msg.body {
-            MessageBody::QualityReply(reply) => {
-                assert_eq!(reply.pong, 12345);
-            },
-            _ => panic!("Expected QualityReply message"),
+    #[test]
+    fn queued_inputs_carry_the_newest_ack_under_send_limits() {
+        let (mut protocol, _clock) = limited_protocol(1, None);
+        let (mut socket, sent) = recording_socket();
+        let ack = |frame| {
+            MessageBody::InputAck(InputAck {
+                ack_frame: Frame::new(frame),
+            })
+        };
+
+        // Without an input queued, only the newest held ack survives.
+        for frame in 1..=3 {
+            protocol.queue_message(ack(frame));
         }
+        protocol.queue_message(MessageBody::KeepAlive(KeepAlive::default()));
+        assert_eq!(protocol.send_all_messages(&mut socket), 1);
+        let held: Vec<MessageBody> = protocol
+            .send_queue
+            .iter()
+            .map(|msg| msg.body.clone())
+            .collect();
+        assert_eq!(held, vec![ack(3)]);
+
+        // A queued input replaces the standalone acks and takes the budget.
+        protocol.recv_inputs.insert(
+            Frame::new(7),
+            InputBytes {
+                frame: Frame::new(7),
+                bytes: Vec::new(),
+            },
+        );
+        protocol.queue_message(MessageBody::Input(Input {
+            peer_connect_status: Vec::new(),
+            start_frame: Frame::new(0),
+            ack_frame: Frame::NULL,
+            bytes: Vec::new(),
+        }));
+        let _ = protocol.poll(&[]).count();
+        assert_eq!(protocol.send_all_messages(&mut socket), 1);
+        assert!(protocol.send_queue.is_empty());
+        let last = sent.lock().unwrap().last().unwrap().body.clone();
+        let MessageBody::Input(body) = last else {
+            panic!("expected the input to be sent, got {last:?}");
+        };
+        assert_eq!(body.ack_frame, Frame::new(7));
     }
 
     // ==========================================
@@ -6105,6 +11032,7 @@ fn input_body_with_message_len<T: Config>(
         body
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn mtu_guard_counts_inclusive_boundaries_and_diagnoses_once() {
         use crate::telemetry::{
@@ -6201,12 +11129,12 @@ fn peer_metrics_send_accounting_matches_wire_bytes_and_kinds() {
             create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
 
         let bodies = [
-            MessageBody::KeepAlive,
+            MessageBody::KeepAlive(KeepAlive::default()),
             MessageBody::QualityReply(QualityReply { pong: 7 }),
             MessageBody::InputAck(InputAck {
                 ack_frame: Frame::new(3),
             }),
-            MessageBody::KeepAlive,
+            MessageBody::KeepAlive(KeepAlive::default()),
         ];
         let mut expected_bytes = 0u64;
         for body in &bodies {
@@ -6246,12 +11174,13 @@ fn peer_metrics_receive_accounting_counts_every_message_before_filters() {
         protocol.remote_conn_id = 999;
 
         let bodies = [
-            MessageBody::KeepAlive,
+            MessageBody::KeepAlive(KeepAlive::default()),
             MessageBody::QualityReport(QualityReport {
                 frame_advantage: 0,
                 ping: 5,
+                stalled: false,
             }),
-            MessageBody::KeepAlive,
+            MessageBody::KeepAlive(KeepAlive::default()),
         ];
         let mut expected_bytes = 0u64;
         for body in &bodies {
@@ -6279,7 +11208,7 @@ fn peer_metrics_receive_accounting_counts_every_message_before_filters() {
         protocol.state = ProtocolState::Shutdown;
         protocol.handle_message(&Message {
             header: MessageHeader::new(999),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         });
         assert_eq!(
             protocol.peer_metrics().packets_received,
@@ -6326,6 +11255,47 @@ fn peer_metrics_records_input_compression_bytes() {
         assert_eq!(m.messages_sent_by_kind.total(), m.packets_sent);
     }
 
+    #[test]
+    fn compression_stats_single_out_the_changing_byte() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        for frame in 0..6u8 {
+            protocol.pending_output.push_back(InputBytes {
+                frame: Frame::new(i32::from(frame)),
+                bytes: vec![1, 2, 3, frame],
+            });
+        }
+        let frames: Vec<_> = protocol
+            .pending_output
+            .iter()
+            .map(|input| input.bytes.clone())
+            .collect();
+        let encoded =
+            crate::network::compression::encode(&protocol.last_acked_input.bytes, frames.iter());
+
+        // A resend of the same batch is another packet but compares nothing new.
+        let connect_status = vec![ConnectionStatus::default(); 2];
+        protocol.send_pending_output(&connect_status);
+        protocol.send_pending_output(&connect_status);
+
+        let stats = protocol.compression_stats();
+        assert_eq!(stats.input_packets, 2);
+        assert_eq!(stats.raw_bytes, 48);
+        assert_eq!(stats.encoded_bytes, 2 * encoded.len() as u64);
+        assert!((stats.average_raw_bytes() - 24.0).abs() < f64::EPSILON);
+        assert_eq!(stats.compression_ratio(), Some(encoded.len() as f64 / 24.0));
+        // Frame 0 follows the blank reference, not a frame: five pairs.
+        assert_eq!(stats.frames_compared, 5);
+        assert_eq!(stats.byte_change_counts, vec![0, 0, 0, 5]);
+        assert!((stats.change_frequency(3) - 1.0).abs() < f64::EPSILON);
+        assert!(stats.change_frequency(0).abs() < f64::EPSILON);
+
+        protocol.reset_compression_stats();
+        assert_eq!(protocol.compression_stats(), &CompressionStats::default());
+        // The cumulative peer metrics are not reset.
+        assert_eq!(protocol.peer_metrics().input_bytes_pre_compression, 48);
+    }
+
     #[test]
     fn peer_metrics_snapshot_reports_connection_gauges() {
         let mut protocol: UdpProtocol<TestConfig> =
@@ -6360,7 +11330,10 @@ fn poll_returns_events_and_clears_queue() {
         }
 
         let connect_status = vec![ConnectionStatus::default(); 2];
-        let events: Vec<_> = protocol.poll(&connect_status).collect();
+        let events: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
 
         // Should have Synchronizing events and Synchronized event
         assert!(!events.is_empty());
@@ -6380,10 +11353,213 @@ fn poll_in_disconnected_state_transitions_to_shutdown() {
         protocol.shutdown_timeout = Instant::now().checked_sub(Duration::from_secs(1)).unwrap();
 
         let connect_status = vec![ConnectionStatus::default(); 2];
-        let _events: Vec<_> = protocol.poll(&connect_status).collect();
+        let _events: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
+
+        // Should have transitioned to Shutdown
+        assert_eq!(protocol.state, ProtocolState::Shutdown);
+    }
+
+    /// A synced protocol on a mutable clock that received our peer's inputs
+    /// through frame 4 and sent its own through frame 6, none acked yet.
+    fn closing_link_protocol() -> (UdpProtocol<TestConfig>, Arc<Mutex<Instant>>) {
+        let (config, clock) = mutable_clock_config();
+        let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            config,
+        );
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        for f in 0..=4 {
+            protocol.recv_inputs.insert(
+                Frame::new(f),
+                InputBytes {
+                    frame: Frame::new(f),
+                    bytes: vec![0u8; 4],
+                },
+            );
+        }
+        protocol.newest_sent_input_frame = Frame::new(6);
+        protocol.event_queue.clear();
+        protocol.send_queue.clear();
+        (protocol, clock)
+    }
+
+    fn link_closed(frame: i32) -> Message {
+        Message {
+            header: MessageHeader::new(999),
+            body: MessageBody::LinkClosed(LinkClosed {
+                last_received_frame: Frame::new(frame),
+            }),
+        }
+    }
+
+    fn queued_link_closed(protocol: &UdpProtocol<TestConfig>) -> Vec<Frame> {
+        protocol
+            .send_queue
+            .iter()
+            .filter_map(|msg| match &msg.body {
+                MessageBody::LinkClosed(body) => Some(body.last_received_frame),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn disconnected_protocol_resends_link_closed_on_keepalive_cadence() {
+        let (mut protocol, clock) = closing_link_protocol();
+        protocol.disconnect_remote();
+        let connect_status = vec![ConnectionStatus::default(); 2];
+        let interval = SyncConfig::default().keepalive_interval + Duration::from_millis(1);
+
+        advance_test_clock(&clock, interval);
+        let _events: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
+        assert_eq!(queued_link_closed(&protocol), vec![Frame::new(4)]);
+
+        // Nothing more until a keepalive interval has passed.
+        let _events: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
+        assert_eq!(queued_link_closed(&protocol).len(), 1);
+
+        advance_test_clock(&clock, interval);
+        let _events: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
+        assert_eq!(queued_link_closed(&protocol).len(), 2);
+        assert_eq!(protocol.state, ProtocolState::Disconnected);
+    }
+
+    #[test]
+    fn link_closed_raises_disconnect_frame_without_refreshing_liveness() {
+        let (mut protocol, clock) = closing_link_protocol();
+        assert_eq!(protocol.disconnect_frame(), Frame::NULL);
+        let recv_time = protocol.last_recv_time;
+
+        advance_test_clock(&clock, Duration::from_millis(1));
+        protocol.handle_message(&link_closed(3));
+        assert_eq!(protocol.disconnect_frame(), Frame::new(3));
+        assert_eq!(protocol.last_recv_time, recv_time);
+
+        // A later notice only raises the frame, bounded by what we received.
+        protocol.handle_message(&link_closed(2));
+        assert_eq!(protocol.disconnect_frame(), Frame::new(3));
+        protocol.handle_message(&link_closed(6));
+        assert_eq!(protocol.disconnect_frame(), Frame::new(4));
+    }
+
+    #[test]
+    fn link_closed_beyond_newest_sent_frame_is_ignored() {
+        let (mut protocol, _clock) = closing_link_protocol();
+        protocol.handle_message(&link_closed(7));
+        assert_eq!(protocol.peer_closed_frame, Frame::NULL);
+        assert_eq!(protocol.disconnect_frame(), Frame::NULL);
+    }
+
+    #[test]
+    fn disconnected_protocol_still_applies_peer_acks() {
+        let (mut protocol, _clock) = closing_link_protocol();
+        for f in 0..=6 {
+            protocol.pending_output.push_back(InputBytes {
+                frame: Frame::new(f),
+                bytes: vec![0u8; 4],
+            });
+        }
+        protocol.disconnect_remote();
+
+        protocol.handle_message(&Message {
+            header: MessageHeader::new(999),
+            body: MessageBody::InputAck(InputAck {
+                ack_frame: Frame::new(2),
+            }),
+        });
+        assert_eq!(protocol.disconnect_frame(), Frame::new(2));
+
+        protocol.handle_message(&link_closed(5));
+        assert_eq!(protocol.disconnect_frame(), Frame::new(4));
+    }
+
+    fn queued_checksum_requests(protocol: &UdpProtocol<TestConfig>) -> Vec<Frame> {
+        protocol
+            .send_queue
+            .iter()
+            .filter_map(|msg| match &msg.body {
+                MessageBody::ChecksumRequest(body) => Some(body.frame),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn checksum_request_is_resent_until_the_report_arrives() {
+        let (mut protocol, clock) = closing_link_protocol();
+        let connect_status = vec![ConnectionStatus::default(); 2];
+        let interval = SyncConfig::default().keepalive_interval + Duration::from_millis(1);
+
+        protocol.request_checksum(Frame::new(3));
+        protocol.request_checksum(Frame::new(3));
+        assert_eq!(queued_checksum_requests(&protocol), vec![Frame::new(3)]);
+
+        advance_test_clock(&clock, interval);
+        let _events: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
+        assert_eq!(queued_checksum_requests(&protocol).len(), 2);
+
+        // A report for another frame is not the answer.
+        for frame in [2, 3] {
+            protocol.handle_message(&Message {
+                header: MessageHeader::new(999),
+                body: MessageBody::ChecksumReport(ChecksumReport {
+                    checksum: 0xAB,
+                    frame: Frame::new(frame),
+                }),
+            });
+        }
+        assert_eq!(protocol.requested_checksum(Frame::new(2)), None);
+        assert_eq!(protocol.requested_checksum(Frame::new(3)), Some(0xAB));
+
+        advance_test_clock(&clock, interval);
+        let _events: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
+        protocol.request_checksum(Frame::new(3));
+        assert_eq!(queued_checksum_requests(&protocol).len(), 2);
+    }
+
+    #[test]
+    fn checksum_request_keeps_the_newest_frame_until_answered() {
+        let (mut protocol, _clock) = closing_link_protocol();
+        for frame in [5, 3] {
+            protocol.handle_message(&Message {
+                header: MessageHeader::new(999),
+                body: MessageBody::ChecksumRequest(ChecksumRequest {
+                    frame: Frame::new(frame),
+                }),
+            });
+        }
+        assert_eq!(protocol.pending_checksum_request(), Some(Frame::new(5)));
 
-        // Should have transitioned to Shutdown
-        assert_eq!(protocol.state, ProtocolState::Shutdown);
+        protocol.answer_checksum_request(0xCD);
+        assert_eq!(protocol.pending_checksum_request(), None);
+        assert!(protocol.send_queue.iter().any(|msg| msg.body
+            == MessageBody::ChecksumReport(ChecksumReport {
+                checksum: 0xCD,
+                frame: Frame::new(5),
+            })));
     }
 
     #[test]
@@ -6414,7 +11590,10 @@ fn sync_timeout_event_emitted_only_once() {
         let connect_status = vec![ConnectionStatus::default(); 2];
 
         // First poll - should emit SyncTimeout
-        let events1: Vec<_> = protocol.poll(&connect_status).collect();
+        let events1: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
         let timeout_count1 = events1
             .iter()
             .filter(|e| matches!(e, Event::SyncTimeout { .. }))
@@ -6425,7 +11604,10 @@ fn sync_timeout_event_emitted_only_once() {
         );
 
         // Second poll - should NOT emit SyncTimeout again
-        let events2: Vec<_> = protocol.poll(&connect_status).collect();
+        let events2: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
         let timeout_count2 = events2
             .iter()
             .filter(|e| matches!(e, Event::SyncTimeout { .. }))
@@ -6436,7 +11618,10 @@ fn sync_timeout_event_emitted_only_once() {
         );
 
         // Third poll - still no SyncTimeout
-        let events3: Vec<_> = protocol.poll(&connect_status).collect();
+        let events3: Vec<_> = protocol
+            .poll(&connect_status)
+            .map(|(_, event)| event)
+            .collect();
         let timeout_count3 = events3
             .iter()
             .filter(|e| matches!(e, Event::SyncTimeout { .. }))
@@ -6501,7 +11686,7 @@ fn queue_has_keep_alive(protocol: &UdpProtocol<TestConfig>) -> bool {
         protocol
             .send_queue
             .iter()
-            .any(|message| matches!(message.body, MessageBody::KeepAlive))
+            .any(|message| matches!(message.body, MessageBody::KeepAlive(_)))
     }
 
     /// (i) Flag set + keepalive interval elapsed + idle (fully-acked) queue:
@@ -6636,7 +11821,7 @@ fn poll_nudge_with_null_last_acked_falls_back_to_keepalive() {
 
     /// (iv) Running-state gating: with the flag set and the interval elapsed,
     /// a Synchronizing protocol sends no Input (only sync-request retries) and
-    /// a Disconnected protocol sends nothing at all.
+    /// a Disconnected protocol sends nothing but its closed-link notice.
     #[test]
     fn poll_nudge_respects_running_state_gating() {
         let current = Arc::new(Mutex::new(Instant::now()));
@@ -6674,7 +11859,10 @@ fn poll_nudge_respects_running_state_gating() {
         advance_test_clock(&current, Duration::from_millis(201));
         let _ = protocol.poll(&connect_status).count();
         assert!(
-            protocol.send_queue.is_empty(),
+            protocol
+                .send_queue
+                .iter()
+                .all(|msg| matches!(msg.body, MessageBody::LinkClosed(_))),
             "a Disconnected protocol must not nudge"
         );
     }
@@ -6823,6 +12011,7 @@ fn receiver_merges_gossip_from_nudge_shaped_packet_without_staging_inputs() {
         receiver.send_queue.clear();
 
         receiver.on_input(&body);
+        receiver.accept_disconnect_claim(PlayerHandle::new(1));
 
         // The fresh gossip was merged...
         let merged = receiver.peer_connect_status(PlayerHandle::new(1));
@@ -6836,6 +12025,7 @@ fn receiver_merges_gossip_from_nudge_shaped_packet_without_staging_inputs() {
             !receiver
                 .event_queue
                 .iter()
+                .map(|(_, event)| event)
                 .any(|event| matches!(event, Event::Input { .. })),
             "a nudge must not stage any input"
         );
@@ -6935,6 +12125,206 @@ fn on_input_resets_retry_pacer_only_when_new_frames_staged() {
         );
     }
 
+    // ==========================================
+    // Keepalive Ack Tests
+    // ==========================================
+
+    fn queued_keep_alive_acks(protocol: &UdpProtocol<TestConfig>) -> Vec<Frame> {
+        protocol
+            .send_queue
+            .iter()
+            .filter_map(|message| match &message.body {
+                MessageBody::KeepAlive(body) => Some(body.ack_frame),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn receive_frame(protocol: &mut UdpProtocol<TestConfig>, frame: i32) {
+        protocol.recv_inputs.insert(
+            Frame::new(frame),
+            InputBytes {
+                frame: Frame::new(frame),
+                bytes: vec![0, 0, 0, 0],
+            },
+        );
+    }
+
+    #[test]
+    fn keep_alive_carries_the_newest_received_frame() {
+        let (mut protocol, clock) = running_nudge_protocol();
+        receive_frame(&mut protocol, 5);
+        advance_test_clock(&clock, Duration::from_millis(201));
+        let _ = protocol.poll(&[]).count();
+        assert_eq!(queued_keep_alive_acks(&protocol), vec![Frame::new(5)]);
+
+        protocol.send_queue.clear();
+        protocol.protocol_config.keep_alive_acks = false;
+        advance_test_clock(&clock, Duration::from_millis(201));
+        let _ = protocol.poll(&[]).count();
+        assert_eq!(queued_keep_alive_acks(&protocol), vec![Frame::NULL]);
+    }
+
+    /// Other traffic refreshes `last_send_time`, but only ack-carrying
+    /// messages hold the keepalive back while keepalive acks are enabled.
+    #[test]
+    fn keep_alive_ack_fires_despite_other_traffic() {
+        for keep_alive_acks in [true, false] {
+            let (mut protocol, clock) = running_nudge_protocol();
+            protocol.protocol_config.keep_alive_acks = keep_alive_acks;
+            receive_frame(&mut protocol, 5);
+            advance_test_clock(&clock, Duration::from_millis(150));
+            protocol.send_checksum_report(Frame::new(1), 0);
+            protocol.send_queue.clear();
+            advance_test_clock(&clock, Duration::from_millis(60));
+            let _ = protocol.poll(&[]).count();
+
+            let expected = if keep_alive_acks {
+                vec![Frame::new(5)]
+            } else {
+                Vec::new()
+            };
+            assert_eq!(
+                queued_keep_alive_acks(&protocol),
+                expected,
+                "keep_alive_acks = {keep_alive_acks}"
+            );
+        }
+    }
+
+    #[test]
+    fn input_message_holds_the_keep_alive_ack_back() {
+        let (mut protocol, clock) = running_nudge_protocol();
+        protocol.last_acked_input.frame = Frame::new(3);
+        receive_frame(&mut protocol, 5);
+        advance_test_clock(&clock, Duration::from_millis(150));
+        assert!(protocol.send_connect_status_nudge(&[]));
+        protocol.send_queue.clear();
+        advance_test_clock(&clock, Duration::from_millis(60));
+        let _ = protocol.poll(&[]).count();
+        assert!(queued_keep_alive_acks(&protocol).is_empty());
+    }
+
+    /// A bare `InputAck` carries no connect status, so it does not hold the
+    /// status-bearing keepalive back.
+    #[test]
+    fn input_ack_does_not_hold_the_keep_alive_ack_back() {
+        let (mut protocol, clock) = running_nudge_protocol();
+        receive_frame(&mut protocol, 5);
+        advance_test_clock(&clock, Duration::from_millis(150));
+        protocol.send_input_ack();
+        protocol.send_queue.clear();
+        advance_test_clock(&clock, Duration::from_millis(60));
+        let _ = protocol.poll(&[]).count();
+        assert_eq!(queued_keep_alive_acks(&protocol), vec![Frame::new(5)]);
+    }
+
+    #[test]
+    fn keep_alive_ack_pops_pending_output() {
+        let (mut protocol, _clock) = running_nudge_protocol();
+        for frame in 0..3 {
+            protocol.pending_output.push_back(InputBytes {
+                frame: Frame::new(frame),
+                bytes: vec![frame as u8, 0, 0, 0],
+            });
+        }
+        protocol.newest_sent_input_frame = Frame::new(2);
+
+        protocol.on_keep_alive(&KeepAlive::default());
+        assert_eq!(protocol.pending_output.len(), 3);
+
+        protocol.on_keep_alive(&KeepAlive {
+            ack_frame: Frame::new(1),
+            peer_connect_status: Vec::new(),
+        });
+        assert_eq!(protocol.pending_output.len(), 1);
+        assert_eq!(protocol.last_acked_input.frame, Frame::new(1));
+    }
+
+    #[test]
+    fn disconnected_endpoint_still_applies_keep_alive_acks() {
+        let (mut protocol, _clock) = running_nudge_protocol();
+        protocol.pending_output.push_back(InputBytes {
+            frame: Frame::new(0),
+            bytes: vec![0, 0, 0, 0],
+        });
+        protocol.newest_sent_input_frame = Frame::new(0);
+        protocol.disconnect();
+
+        protocol.handle_message(&Message {
+            header: MessageHeader::new(protocol.remote_conn_id),
+            body: MessageBody::KeepAlive(KeepAlive {
+                ack_frame: Frame::new(0),
+                peer_connect_status: Vec::new(),
+            }),
+        });
+        assert!(protocol.pending_output.is_empty());
+    }
+
+    /// Once acks drained `pending_output`, the keepalive is what keeps the
+    /// connect status flowing to the peer.
+    #[test]
+    fn keep_alive_carries_the_current_connect_status() {
+        let (mut protocol, clock) = running_nudge_protocol();
+        let status = vec![
+            ConnectionStatus {
+                disconnected: false,
+                last_frame: Frame::new(9),
+                epoch: 0,
+            },
+            ConnectionStatus {
+                disconnected: true,
+                last_frame: Frame::new(4),
+                epoch: 0,
+            },
+        ];
+        let queued_status = |protocol: &UdpProtocol<TestConfig>| {
+            protocol
+                .send_queue
+                .iter()
+                .find_map(|message| match &message.body {
+                    MessageBody::KeepAlive(body) => Some(body.peer_connect_status.clone()),
+                    _ => None,
+                })
+        };
+        advance_test_clock(&clock, Duration::from_millis(201));
+        let _ = protocol.poll(&status).count();
+        assert_eq!(queued_status(&protocol), Some(status.clone()));
+
+        protocol.send_queue.clear();
+        protocol.protocol_config.keep_alive_acks = false;
+        advance_test_clock(&clock, Duration::from_millis(201));
+        let _ = protocol.poll(&status).count();
+        assert_eq!(queued_status(&protocol), Some(Vec::new()));
+    }
+
+    #[test]
+    fn keep_alive_connect_status_merges_like_an_input() {
+        let (mut protocol, _clock) = running_nudge_protocol();
+        let dropped = ConnectionStatus {
+            disconnected: true,
+            last_frame: Frame::new(4),
+            epoch: 0,
+        };
+
+        protocol.on_keep_alive(&KeepAlive {
+            ack_frame: Frame::NULL,
+            peer_connect_status: vec![dropped],
+        });
+        assert!(
+            !protocol
+                .peer_connect_status(PlayerHandle::new(0))
+                .disconnected,
+            "a status of the wrong length is ignored"
+        );
+
+        protocol.on_keep_alive(&KeepAlive {
+            ack_frame: Frame::NULL,
+            peer_connect_status: vec![dropped, ConnectionStatus::default()],
+        });
+        assert_eq!(protocol.peer_connect_status(PlayerHandle::new(0)), dropped);
+    }
+
     // ==========================================
     // Accessor Tests
     // ==========================================
@@ -6975,11 +12365,73 @@ fn peer_addr_returns_correct_address() {
     fn is_handling_message_checks_address() {
         let protocol: UdpProtocol<TestConfig> =
             create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        let msg = Message {
+            header: MessageHeader::new(5),
+            body: MessageBody::KeepAlive(KeepAlive::default()),
+        };
 
-        assert!(protocol.is_handling_message(&test_addr()));
+        assert!(protocol.is_handling_message(&test_addr(), &msg));
 
         let other_addr: SocketAddr = "127.0.0.1:8000".parse().unwrap();
-        assert!(!protocol.is_handling_message(&other_addr));
+        assert!(!protocol.is_handling_message(&other_addr, &msg));
+    }
+
+    #[test]
+    fn routed_endpoint_matches_relay_packets_by_conn_id() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        let relay: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let keep_alive = |conn_id| Message {
+            header: MessageHeader::new(conn_id),
+            body: MessageBody::KeepAlive(KeepAlive::default()),
+        };
+
+        protocol.set_route(Some(relay));
+        assert_eq!(protocol.route(), Some(&relay));
+        assert_eq!(protocol.send_addr(), &relay);
+        // Unbound: the relay's packets are accepted like any handshake packet.
+        assert!(protocol.is_handling_message(&relay, &keep_alive(5)));
+
+        protocol.remote_conn_id = 77;
+        assert!(protocol.is_handling_message(&relay, &keep_alive(77)));
+        assert!(!protocol.is_handling_message(&relay, &keep_alive(5)));
+        // The peer's own address keeps working while routed.
+        assert!(protocol.is_handling_message(&test_addr(), &keep_alive(5)));
+
+        // Routing through the peer's own address is direct delivery.
+        protocol.set_route(Some(test_addr()));
+        assert_eq!(protocol.route(), None);
+        assert_eq!(protocol.send_addr(), &test_addr());
+        assert!(!protocol.is_handling_message(&relay, &keep_alive(77)));
+    }
+
+    #[test]
+    fn preparing_a_relay_changes_nothing_until_it_is_applied() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 3, 1, 8);
+        assert!(protocol.prepare_relay_for(PlayerHandle::new(2)).is_err());
+        protocol.synchronize().unwrap();
+        let digest = protocol.local_handshake.handles_digest;
+
+        let prepared = protocol
+            .prepare_relay_for(PlayerHandle::new(2))
+            .unwrap()
+            .unwrap();
+        assert!(protocol.relayed_handles().is_empty());
+        assert_eq!(protocol.local_players, 1);
+        assert_eq!(protocol.local_handshake.handles_digest, digest);
+
+        protocol.relay_for(prepared, &[PlayerHandle::new(1)]);
+        assert_eq!(protocol.relayed_handles(), &[PlayerHandle::new(2)]);
+        assert_eq!(protocol.local_players, 2);
+        assert_eq!(
+            protocol.local_handshake.handles_digest,
+            handles_digest(&[PlayerHandle::new(1), PlayerHandle::new(2)])
+        );
+        assert!(protocol
+            .prepare_relay_for(PlayerHandle::new(2))
+            .unwrap()
+            .is_none());
     }
 
     #[test]
@@ -7036,6 +12488,122 @@ fn update_local_frame_advantage_saturates_peer_influenced_rtt() {
         assert_eq!(protocol.local_frame_advantage, i32::MAX);
     }
 
+    /// The allowance is the base plus the round trip in frames, rounded up
+    /// and capped at the prediction window; without a base it is the window.
+    #[test]
+    fn prediction_allowance_adds_rtt_frames_to_the_base() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.fps = 60;
+        protocol.round_trip_time = 40;
+        assert_eq!(protocol.prediction_allowance(8), 8);
+
+        protocol.protocol_config.per_peer_prediction_limit = Some(2);
+        // (rtt_ms, expected): 40 ms at 60 fps is 2.4 frames, rounded up to 3.
+        for (rtt, allowance) in [(0, 2), (16, 3), (40, 5), (100, 8), (u128::MAX, 8)] {
+            protocol.round_trip_time = rtt;
+            assert_eq!(protocol.prediction_allowance(8), allowance, "{rtt} ms");
+        }
+    }
+
+    /// Half the RTT in frames is rounded to the nearest frame, so neither a
+    /// 12 fps board game nor a 240 fps rhythm game is biased low.
+    #[test]
+    fn update_local_frame_advantage_rounds_half_rtt_to_nearest_frame() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.recv_inputs.insert(
+            Frame::new(100),
+            InputBytes {
+                frame: Frame::new(100),
+                bytes: vec![0; std::mem::size_of::<TestInput>()],
+            },
+        );
+        // (fps, rtt_ms, expected frames): half of 250 ms at 12 fps is 1.5
+        // frames, 160 ms gives 0.96, 46 ms at 240 fps gives 5.52, and 36 ms 4.32.
+        for (fps, rtt, frames) in [(12, 250, 2), (12, 160, 1), (240, 46, 6), (240, 36, 4)] {
+            protocol.fps = fps;
+            protocol.round_trip_time = rtt;
+            protocol.update_local_frame_advantage(Frame::new(100));
+            assert_eq!(
+                protocol.local_frame_advantage, frames,
+                "{fps} fps, {rtt} ms"
+            );
+        }
+    }
+
+    /// Half-RTT frame estimates at the fps range games ship at, including
+    /// round trips shorter than a frame that truncation used to turn into 0.
+    /// Expected values are half the RTT times the fps over 1000, rounded to
+    /// the nearest frame with ties to even.
+    #[test]
+    fn update_local_frame_advantage_matches_hand_computed_frames_across_fps() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.recv_inputs.insert(
+            Frame::new(100),
+            InputBytes {
+                frame: Frame::new(100),
+                bytes: vec![0; std::mem::size_of::<TestInput>()],
+            },
+        );
+        // (fps, rtt_ms, frames): e.g. 12 fps, 100 ms: 50 * 12 / 1000 = 0.6 -> 1.
+        let cases = [
+            (12, 100, 1),   // 0.6
+            (12, 40, 0),    // 0.24
+            (24, 60, 1),    // 0.72
+            (24, 125, 2),   // 1.5, tie to even
+            (30, 50, 1),    // 0.75
+            (30, 300, 4),   // 4.5, tie to even
+            (60, 25, 1),    // 0.75
+            (60, 150, 4),   // 4.5, tie to even
+            (60, 170, 5),   // 5.1
+            (144, 10, 1),   // 0.72
+            (144, 125, 9),  // 9.0
+            (240, 6, 1),    // 0.72
+            (240, 4, 0),    // 0.48
+            (240, 125, 15), // 15.0
+        ];
+        for (fps, rtt, frames) in cases {
+            protocol.fps = fps;
+            protocol.round_trip_time = rtt;
+            protocol.update_local_frame_advantage(Frame::new(100));
+            assert_eq!(
+                protocol.local_frame_advantage, frames,
+                "{fps} fps, {rtt} ms"
+            );
+        }
+    }
+
+    /// The RTT allowance counts any started frame as a whole one at every
+    /// fps, so a sub-frame round trip still buys a frame of prediction.
+    #[test]
+    fn prediction_allowance_rounds_rtt_frames_up_across_fps() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.protocol_config.per_peer_prediction_limit = Some(0);
+        // (fps, rtt_ms, allowance): 12 fps, 20 ms is 0.24 frames -> 1.
+        let cases = [
+            (12, 20, 1),
+            (12, 250, 3), // 3.0
+            (24, 50, 2),  // 1.2
+            (30, 100, 3), // 3.0
+            (60, 1, 1),   // 0.06
+            (144, 50, 8), // 7.2
+            (240, 4, 1),  // 0.96
+            (240, 0, 0),
+        ];
+        for (fps, rtt, allowance) in cases {
+            protocol.fps = fps;
+            protocol.round_trip_time = rtt;
+            assert_eq!(
+                protocol.prediction_allowance(64),
+                allowance,
+                "{fps} fps, {rtt} ms"
+            );
+        }
+    }
+
     #[test]
     fn average_frame_advantage_delegates_to_time_sync() {
         let protocol: UdpProtocol<TestConfig> =
@@ -7062,7 +12630,7 @@ fn input_bytes_zeroed_creates_correct_size() {
 
     #[test]
     fn input_bytes_from_inputs_serializes_correctly() {
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(Frame::new(10), TestInput { inp: 0xAABBCCDD }),
@@ -7080,7 +12648,7 @@ fn input_bytes_from_inputs_serializes_correctly() {
 
     #[test]
     fn input_bytes_roundtrip() {
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(Frame::new(5), TestInput { inp: 12345 }),
@@ -7210,6 +12778,7 @@ fn on_input_rejects_input_with_too_large_gap() {
         let input_events: Vec<_> = protocol
             .event_queue
             .iter()
+            .map(|(_, event)| event)
             .filter(|e| matches!(e, Event::Input { .. }))
             .collect();
         assert!(
@@ -7218,6 +12787,47 @@ fn on_input_rejects_input_with_too_large_gap() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn on_input_gap_counts_one_network_protocol_warning() {
+        use crate::telemetry::{
+            push_violation_observer, CountingObserver, ViolationKind, ViolationObserver,
+            ViolationSeverity,
+        };
+
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        for _ in 0..TEST_NUM_SYNC_PACKETS {
+            let random = *protocol.sync_random_requests.iter().next().unwrap();
+            let header = MessageHeader::new(999);
+            protocol.on_sync_reply(header, matching_sync_reply(&protocol, random));
+        }
+        protocol.recv_inputs.insert(
+            Frame::new(0),
+            InputBytes {
+                frame: Frame::new(0),
+                bytes: vec![0, 0, 0, 0],
+            },
+        );
+
+        let observer = Arc::new(CountingObserver::new());
+        let _guard = push_violation_observer(Arc::clone(&observer) as Arc<dyn ViolationObserver>);
+        protocol.on_input(&Input {
+            start_frame: Frame::new(5),
+            ack_frame: Frame::NULL,
+            bytes: vec![1, 2, 3, 4],
+            peer_connect_status: vec![ConnectionStatus::default(); 2],
+        });
+
+        let counts = observer.counts();
+        assert_eq!(
+            counts.get(ViolationKind::NetworkProtocol, ViolationSeverity::Warning),
+            1
+        );
+        assert_eq!(counts.total(), 1);
+    }
+
     /// Test that consecutive frames are processed correctly
     #[test]
     fn on_input_accepts_consecutive_frame() {
@@ -7616,10 +13226,10 @@ fn on_input_boundary_gap_of_two_is_rejected() {
     /// frame metadata is inconsistent.
     #[test]
     fn from_inputs_handles_inconsistent_frames_gracefully() {
-        use std::collections::BTreeMap;
+        use crate::vec_map::VecMap;
 
         // Test 1: Consistent frames work correctly
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput {
@@ -7644,7 +13254,7 @@ fn from_inputs_handles_inconsistent_frames_gracefully() {
 
         // Test 2: Inconsistent frames still produce valid output
         // (with a warning violation logged)
-        let mut inconsistent_inputs = BTreeMap::new();
+        let mut inconsistent_inputs = VecMap::new();
         inconsistent_inputs.insert(
             PlayerHandle::new(0),
             PlayerInput {
@@ -7673,10 +13283,10 @@ fn from_inputs_handles_inconsistent_frames_gracefully() {
     /// Test that from_inputs handles consistent frames correctly
     #[test]
     fn from_inputs_accepts_consistent_frames() {
-        use std::collections::BTreeMap;
+        use crate::vec_map::VecMap;
 
         // Add inputs with consistent frames
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput {
@@ -7701,9 +13311,9 @@ fn from_inputs_accepts_consistent_frames() {
     /// Test that from_inputs handles NULL frames as wildcard
     #[test]
     fn from_inputs_null_frame_is_wildcard() {
-        use std::collections::BTreeMap;
+        use crate::vec_map::VecMap;
 
-        let mut inputs = BTreeMap::new();
+        let mut inputs = VecMap::new();
 
         // Add input with real frame and one with NULL
         inputs.insert(
@@ -7802,7 +13412,7 @@ fn protocol_uses_custom_num_sync_packets() {
             protocol.handle_message(&reply);
 
             // Check events
-            let events: Vec<_> = protocol.poll(&[]).collect();
+            let events: Vec<_> = protocol.poll(&[]).map(|(_, event)| event).collect();
             if i < 2 {
                 // Should get Synchronizing events for first 2 roundtrips
                 assert!(events.iter().any(
@@ -7966,15 +13576,61 @@ fn ping_millis_without_injected_clock_is_monotonic() {
             1,
             8,
             SyncConfig::default(),
-            ProtocolConfig::default(),
+            ProtocolConfig::default(),
+        );
+
+        let first = protocol.ping_millis();
+        let second = protocol.ping_millis();
+        assert!(
+            second >= first,
+            "monotonic clock must not go backwards (first={first}, second={second})"
+        );
+    }
+
+    #[test]
+    fn ping_millis_without_injected_clock_counts_from_the_unix_epoch() {
+        let protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        let wall = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        assert!(protocol.ping_millis().abs_diff(wall) < 1_000);
+    }
+
+    #[test]
+    fn clock_offset_estimate_starts_at_the_first_sample_and_then_smooths() {
+        let (config, offset) = injected_clock_config();
+        let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            config,
         );
+        assert_eq!(protocol.clock_offset_ms(), None);
+        protocol.round_trip_time = 40;
+        offset.store(1_000, std::sync::atomic::Ordering::Relaxed);
+
+        // Sent 20ms ago by a clock 500ms ahead.
+        let report = |ping| QualityReport {
+            frame_advantage: 0,
+            ping,
+            stalled: false,
+        };
+        protocol.on_quality_report(&report(1_480));
+        assert_eq!(protocol.clock_offset_ms(), Some(500));
 
-        let first = protocol.ping_millis();
-        let second = protocol.ping_millis();
-        assert!(
-            second >= first,
-            "monotonic clock must not go backwards (first={first}, second={second})"
-        );
+        // A sample 80ms off moves the estimate an eighth of the way.
+        protocol.on_quality_report(&report(1_560));
+        assert_eq!(protocol.clock_offset_ms(), Some(510));
+
+        // A clock behind ours gives a negative offset.
+        for _ in 0..200 {
+            protocol.on_quality_report(&report(680));
+        }
+        assert_eq!(protocol.clock_offset_ms(), Some(-300));
     }
 
     #[test]
@@ -8439,7 +14095,7 @@ fn rearm_for_rejoin_ghost_from_early_era_filters_rebuilt_endpoints_current_conn_
         let last_recv_before = ghost.last_recv_time;
         ghost.handle_message(&Message {
             header: MessageHeader::new(current_conn_id),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         });
         assert!(
             ghost.send_queue.is_empty(),
@@ -8455,7 +14111,7 @@ fn rearm_for_rejoin_ghost_from_early_era_filters_rebuilt_endpoints_current_conn_
         let accepted_recv_time = advance_test_clock(&ghost_clock, Duration::from_millis(1));
         ghost.handle_message(&Message {
             header: MessageHeader::new(early_era_conn_id),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         });
         assert_eq!(
             ghost.last_recv_time, accepted_recv_time,
@@ -8492,6 +14148,37 @@ fn protocol_new_rejects_zero_byte_input_type() {
         ));
     }
 
+    #[test]
+    fn protocol_new_rejects_default_input_that_does_not_round_trip() {
+        let result = UdpProtocol::<ShiftedInputConfig>::new(
+            vec![PlayerHandle::new(0)],
+            test_addr(),
+            2,
+            1,
+            8,
+            Duration::from_secs(5),
+            Duration::from_secs(3),
+            60,
+            DesyncDetection::Off,
+            SyncConfig::default(),
+            ProtocolConfig::default(),
+            TimeSyncConfig::default(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(FortressError::SerializationErrorStructured {
+                kind: SerializationErrorKind::DefaultInputRoundTripMismatch
+            })
+        ));
+        assert!(
+            validate_default_input_round_trip::<BalancedVariableInputConfig>(
+                codec::encoded_len(&BalancedVariableInput::default()).unwrap()
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn protocol_input_frame_wire_size_rejects_frame_larger_than_decode_cap() {
         assert!(matches!(
@@ -8650,7 +14337,7 @@ fn enqueue_replicated_input_drops_entry_when_pending_output_full() {
 
         // Try to enqueue one more — must hit the overflow guard, drop the
         // entry, and leave `pending_output` unchanged.
-        let mut inputs: BTreeMap<PlayerHandle, PlayerInput<TestInput>> = BTreeMap::new();
+        let mut inputs: VecMap<PlayerHandle, PlayerInput<TestInput>> = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(Frame::new(small_limit as i32), TestInput { inp: 7 }),
@@ -8691,7 +14378,7 @@ fn send_input_disconnects_without_mutating_when_pending_output_full() {
             });
         }
 
-        let mut inputs: BTreeMap<PlayerHandle, PlayerInput<TestInput>> = BTreeMap::new();
+        let mut inputs: VecMap<PlayerHandle, PlayerInput<TestInput>> = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(
@@ -8708,9 +14395,170 @@ fn send_input_disconnects_without_mutating_when_pending_output_full() {
         assert!(protocol
             .event_queue
             .iter()
+            .map(|(_, event)| event)
             .any(|event| matches!(event, Event::Disconnected)));
     }
 
+    fn running_suppression_protocol(max_suppressed_frames: usize) -> UdpProtocol<TestConfig> {
+        let config = ProtocolConfig {
+            suppress_redundant_inputs: true,
+            max_suppressed_frames,
+            ..ProtocolConfig::default()
+        };
+        let mut protocol = create_protocol_with_config(
+            vec![PlayerHandle::new(0)],
+            2,
+            1,
+            8,
+            SyncConfig::default(),
+            config,
+        );
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+        protocol
+    }
+
+    fn send_local_input(
+        protocol: &mut UdpProtocol<TestConfig>,
+        frame: i32,
+        inp: u32,
+        connect_status: &[ConnectionStatus],
+    ) -> usize {
+        let mut inputs: VecMap<PlayerHandle, PlayerInput<TestInput>> = VecMap::new();
+        inputs.insert(
+            PlayerHandle::new(0),
+            PlayerInput::new(Frame::new(frame), TestInput { inp }),
+        );
+        protocol.send_input(&inputs, connect_status);
+        let queued_inputs = protocol
+            .send_queue
+            .iter()
+            .filter(|msg| matches!(msg.body, MessageBody::Input(_)))
+            .count();
+        protocol.send_queue.clear();
+        queued_inputs
+    }
+
+    #[test]
+    fn send_input_without_suppression_sends_every_identical_frame() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+        let connect_status = vec![ConnectionStatus::default(); 2];
+
+        for frame in 0..6 {
+            assert_eq!(
+                send_local_input(&mut protocol, frame, 3, &connect_status),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn send_input_suppresses_identical_frames_until_budget_forces_send() {
+        let mut protocol = running_suppression_protocol(3);
+        let connect_status = vec![ConnectionStatus::default(); 2];
+
+        // The first frame has no valid predecessor and always goes out.
+        assert_eq!(send_local_input(&mut protocol, 0, 3, &connect_status), 1);
+        let sent: Vec<usize> = (1..=8)
+            .map(|frame| send_local_input(&mut protocol, frame, 3, &connect_status))
+            .collect();
+
+        assert_eq!(sent, vec![0, 0, 0, 1, 0, 0, 0, 1]);
+        assert_eq!(protocol.pending_output.len(), 9, "nothing was acked");
+        assert_eq!(protocol.suppressed_input_frames_for_tests(), 0);
+    }
+
+    #[test]
+    fn send_input_changed_frame_sends_whole_suppressed_batch_immediately() {
+        let mut protocol = running_suppression_protocol(4);
+        let connect_status = vec![ConnectionStatus::default(); 2];
+
+        assert_eq!(send_local_input(&mut protocol, 0, 3, &connect_status), 1);
+        assert_eq!(send_local_input(&mut protocol, 1, 3, &connect_status), 0);
+        assert_eq!(send_local_input(&mut protocol, 2, 3, &connect_status), 0);
+        assert_eq!(protocol.suppressed_input_frames_for_tests(), 2);
+
+        let mut inputs: VecMap<PlayerHandle, PlayerInput<TestInput>> = VecMap::new();
+        inputs.insert(
+            PlayerHandle::new(0),
+            PlayerInput::new(Frame::new(3), TestInput { inp: 4 }),
+        );
+        protocol.send_input(&inputs, &connect_status);
+
+        let body = queued_input_body(&protocol);
+        assert_eq!(
+            body.start_frame,
+            Frame::new(0),
+            "batch starts at the oldest unacked frame"
+        );
+        assert_eq!(protocol.suppressed_input_frames_for_tests(), 0);
+    }
+
+    #[test]
+    fn send_input_connect_status_change_is_never_suppressed() {
+        let mut protocol = running_suppression_protocol(4);
+        let mut connect_status = vec![ConnectionStatus::default(); 2];
+
+        assert_eq!(send_local_input(&mut protocol, 0, 3, &connect_status), 1);
+        assert_eq!(send_local_input(&mut protocol, 1, 3, &connect_status), 0);
+
+        connect_status[1].disconnected = true;
+        connect_status[1].last_frame = Frame::new(1);
+        assert_eq!(send_local_input(&mut protocol, 2, 3, &connect_status), 1);
+        assert_eq!(protocol.last_sent_input_connect_status, connect_status);
+    }
+
+    #[test]
+    fn send_input_last_frame_progress_alone_is_suppressed() {
+        let mut protocol = running_suppression_protocol(4);
+        let mut connect_status = vec![ConnectionStatus::default(); 2];
+
+        assert_eq!(send_local_input(&mut protocol, 0, 3, &connect_status), 1);
+        connect_status[1].last_frame = Frame::new(1);
+        assert_eq!(send_local_input(&mut protocol, 1, 3, &connect_status), 0);
+        connect_status[1].epoch = 1;
+        assert_eq!(send_local_input(&mut protocol, 2, 3, &connect_status), 1);
+    }
+
+    #[test]
+    fn suppressed_inputs_are_retransmitted_by_running_retry() {
+        let (mut config, clock) = mutable_clock_config();
+        config.suppress_redundant_inputs = true;
+        config.max_suppressed_frames = 4;
+        let sync_config = SyncConfig::default();
+        let mut protocol =
+            create_protocol_with_config(vec![PlayerHandle::new(0)], 2, 1, 8, sync_config, config);
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+        let connect_status = vec![ConnectionStatus::default(); 2];
+
+        assert_eq!(send_local_input(&mut protocol, 0, 3, &connect_status), 1);
+        assert_eq!(send_local_input(&mut protocol, 1, 3, &connect_status), 0);
+
+        advance_test_clock(
+            &clock,
+            sync_config.running_retry_interval + Duration::from_millis(1),
+        );
+        protocol.poll(&connect_status).for_each(drop);
+
+        let body = protocol
+            .send_queue
+            .iter()
+            .find_map(|msg| match &msg.body {
+                MessageBody::Input(body) => Some(body),
+                _ => None,
+            })
+            .expect("retry must resend the suppressed frame");
+        assert_eq!(body.start_frame, Frame::new(0));
+        assert_eq!(protocol.suppressed_input_frames_for_tests(), 0);
+    }
+
     #[test]
     fn send_pending_output_encodes_only_configured_frame_prefix() {
         let small_limit: usize = 3;
@@ -8767,6 +14615,81 @@ fn send_pending_output_encodes_only_configured_frame_prefix() {
         assert_eq!(protocol.pending_output.len(), pending_count);
     }
 
+    #[test]
+    fn send_pending_output_matches_wire_input_packet() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        protocol.send_queue.clear();
+
+        let pending: Vec<Vec<u8>> = (1..=3u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        for (frame, bytes) in (0..).zip(&pending) {
+            protocol.pending_output.push_back(InputBytes {
+                frame: Frame::new(frame),
+                bytes: bytes.clone(),
+            });
+        }
+        let connect_status = vec![
+            ConnectionStatus::default(),
+            ConnectionStatus {
+                disconnected: true,
+                last_frame: Frame::new(7),
+                epoch: 1,
+            },
+        ];
+        let reference = protocol.last_acked_input.bytes.clone();
+
+        protocol.send_pending_output(&connect_status);
+
+        let expected = crate::network::wire::encode_input_packet(
+            &reference,
+            &pending,
+            Frame::new(0),
+            protocol.last_recv_frame(),
+            &connect_status,
+        )
+        .unwrap();
+        let sent = crate::network::codec::encode(queued_input_body(&protocol)).unwrap();
+        assert_eq!(sent, expected);
+    }
+
+    #[test]
+    fn on_input_consumes_wire_input_packet() {
+        let mut protocol: UdpProtocol<TestConfig> =
+            create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.synchronize().unwrap();
+        complete_test_sync(&mut protocol);
+        let reference = protocol
+            .recv_inputs
+            .get(&Frame::NULL)
+            .unwrap()
+            .bytes
+            .clone();
+        let pending = vec![vec![1, 0, 0, 0], vec![2, 0, 0, 0]];
+
+        let packet = crate::network::wire::encode_input_packet(
+            &reference,
+            &pending,
+            Frame::new(0),
+            Frame::NULL,
+            &[ConnectionStatus::default(); 2],
+        )
+        .unwrap();
+        let input = crate::network::codec::decode_input(&packet, &mut 0).unwrap();
+        protocol.on_input(&input);
+
+        for (frame, bytes) in (0..).zip(&pending) {
+            assert_eq!(
+                protocol
+                    .recv_inputs
+                    .get(&Frame::new(frame))
+                    .map(|i| &i.bytes),
+                Some(bytes)
+            );
+        }
+    }
+
     #[test]
     fn send_pending_output_encodes_only_decoded_byte_cap_prefix() {
         let pending_limit: usize = 10;
@@ -8871,7 +14794,7 @@ fn send_input_rejects_variable_width_serialized_input_without_queueing() {
             "test requires variants with different serialized lengths"
         );
 
-        let mut inputs: BTreeMap<PlayerHandle, PlayerInput<VariableInput>> = BTreeMap::new();
+        let mut inputs: VecMap<PlayerHandle, PlayerInput<VariableInput>> = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(Frame::new(0), VariableInput::Active(7)),
@@ -8910,8 +14833,7 @@ fn send_input_rejects_per_player_width_mismatch_even_when_aggregate_matches() {
         assert_ne!(short_len, default_len);
         assert_ne!(long_len, default_len);
 
-        let mut inputs: BTreeMap<PlayerHandle, PlayerInput<BalancedVariableInput>> =
-            BTreeMap::new();
+        let mut inputs: VecMap<PlayerHandle, PlayerInput<BalancedVariableInput>> = VecMap::new();
         inputs.insert(
             PlayerHandle::new(0),
             PlayerInput::new(Frame::new(0), BalancedVariableInput::Short),
@@ -9065,6 +14987,76 @@ fn drop_control_receive_is_ignored_while_synchronizing() {
         assert_eq!(protocol.take_received_drop_messages().count(), 0);
     }
 
+    fn match_pause_messages() -> Vec<MatchPauseMessage> {
+        use crate::network::messages::{MatchPauseKind, MatchPauseStage};
+        vec![
+            MatchPauseMessage::Control(MatchPause {
+                proposer: 0,
+                sequence: 1,
+                kind: MatchPauseKind::Pause,
+                stage: MatchPauseStage::Propose,
+                frame: Frame::new(20),
+            }),
+            MatchPauseMessage::Ack(MatchPauseAck {
+                proposer: 0,
+                sequence: 1,
+                stage: MatchPauseStage::Propose,
+                accepted: true,
+                frame: Frame::new(22),
+            }),
+        ]
+    }
+
+    #[test]
+    fn match_pause_carrier_is_running_only_and_fifo() {
+        let mut protocol = create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        for message in match_pause_messages() {
+            protocol.send_match_pause_message(message);
+        }
+        assert!(protocol.send_queue.is_empty());
+
+        protocol.force_running_for_tests();
+        let messages = match_pause_messages();
+        for message in messages.iter().copied() {
+            protocol.send_match_pause_message(message);
+        }
+        assert_eq!(protocol.send_queue.len(), messages.len());
+        for message in messages.iter().copied() {
+            protocol.handle_message(&Message {
+                header: MessageHeader::new(1),
+                body: message.into_body(),
+            });
+        }
+        let received: Vec<_> = protocol.take_received_match_pause_messages().collect();
+        assert_eq!(received, messages);
+        for kind in [
+            crate::MessageKind::MatchPause,
+            crate::MessageKind::MatchPauseAck,
+        ] {
+            assert_eq!(protocol.messages_sent_by_kind.get(kind), 1);
+            assert_eq!(protocol.messages_received_by_kind.get(kind), 1);
+        }
+    }
+
+    #[test]
+    fn match_pause_mailbox_stays_within_receive_poll_bound() {
+        let mut protocol = create_protocol(vec![PlayerHandle::new(0)], 2, 1, 8);
+        protocol.force_running_for_tests();
+        let message = match_pause_messages()[0];
+        for _ in 0..=MAX_RECEIVED_MATCH_PAUSE_MESSAGES {
+            protocol.handle_message(&Message {
+                header: MessageHeader::new(1),
+                body: message.into_body(),
+            });
+        }
+        assert!(protocol.match_pause_mailbox_warning_sent);
+        assert_eq!(
+            protocol.take_received_match_pause_messages().count(),
+            MAX_RECEIVED_MATCH_PAUSE_MESSAGES
+        );
+        assert!(!protocol.match_pause_mailbox_warning_sent);
+    }
+
     // ==========================================
     // Hot-Join Message Handling Tests
     // ==========================================
@@ -9485,6 +15477,7 @@ fn handle_message_stores_join_aborted() {
 // ## Message Handling Invariants
 // - INV-PROTO-8: Sync replies only decrement counter for valid random values
 // - INV-PROTO-9: Messages in shutdown state are dropped
+// - INV-PROTO-14: Forged acks never release unsent pending output
 
 #[cfg(test)]
 #[allow(
@@ -10062,13 +16055,14 @@ fn prop_input_ack_pops_in_order(
             protocol.synchronize().unwrap();
             complete_sync(&mut protocol, 5);
 
-            // Add frames 0-9
+            // Add frames 0-9, all sent
             for i in 0..10 {
                 protocol.pending_output.push_back(InputBytes {
                     frame: Frame::new(i),
                     bytes: vec![i as u8; 4],
                 });
             }
+            protocol.newest_sent_input_frame = Frame::new(9);
 
             // Ack up to ack_frame
             protocol.on_input_ack(InputAck {
@@ -10301,7 +16295,7 @@ fn prop_input_bytes_roundtrip(
             input2 in input_value_strategy(),
             frame in frame_strategy(),
         ) {
-            let mut inputs = BTreeMap::new();
+            let mut inputs = VecMap::new();
             inputs.insert(
                 PlayerHandle::new(0),
                 PlayerInput::new(Frame::new(frame), TestInput { inp: input1 }),
@@ -10385,7 +16379,7 @@ fn prop_input_compression_roundtrip(
             // Generate a sequence of inputs to send (simulating pending_output)
             let mut pending_inputs: Vec<InputBytes> = Vec::new();
             for i in 0..num_frames {
-                let mut inputs = BTreeMap::new();
+                let mut inputs = VecMap::new();
                 for p in 0..num_players {
                     inputs.insert(
                         PlayerHandle::new(p),
@@ -10541,7 +16535,7 @@ fn prop_null_frame_as_decode_reference(
             );
 
             // Verify the input event was generated
-            let has_input_event = protocol.event_queue.iter()
+            let has_input_event = protocol.event_queue.iter().map(|(_, e)| e)
                 .any(|e| matches!(e, Event::Input { .. }));
             prop_assert!(has_input_event, "Input event should be generated");
         }
@@ -10671,7 +16665,7 @@ fn prop_multi_player_protocol_invariants(
 
             // INV-6: Adding inputs maintains correct byte sizes
             for i in 0..num_inputs {
-                let mut inputs = BTreeMap::new();
+                let mut inputs = VecMap::new();
                 for p in 0..num_players {
                     inputs.insert(
                         PlayerHandle::new(p),
@@ -10757,7 +16751,7 @@ fn prop_multi_player_input_events(
             protocol.on_input(&input);
 
             // Count Input events
-            let input_events: Vec<_> = protocol.event_queue.iter()
+            let input_events: Vec<_> = protocol.event_queue.iter().map(|(_, e)| e)
                 .filter_map(|e| {
                     if let Event::Input { player, .. } = e {
                         Some(*player)
@@ -10865,7 +16859,7 @@ fn prop_input_gap_rejection(
             );
 
             // Verify no Input events were generated
-            let input_event_count = protocol.event_queue.iter()
+            let input_event_count = protocol.event_queue.iter().map(|(_, e)| e)
                 .filter(|e| matches!(e, Event::Input { .. }))
                 .count();
             prop_assert_eq!(
@@ -11010,6 +17004,87 @@ fn prop_first_input_always_accepted(
             );
         }
     }
+    // ========================================================================
+    // INV-PROTO-14: Adversarial Input / InputAck messages never release unsent output
+    // ========================================================================
+
+    proptest! {
+        #![proptest_config(ProptestConfig {
+            cases: miri_case_count(),
+            ..ProptestConfig::default()
+        })]
+
+        /// INV-PROTO-14: No `Input` or `InputAck`, however forged, pops a
+        /// pending frame that was never sent, and every forged ack is counted.
+        ///
+        /// Six frames are queued but the batch limit of three sends only
+        /// frames 0..=2, so `pending_output` itself runs ahead of what the
+        /// peer could have seen.
+        #[test]
+        fn prop_forged_input_messages_never_drop_unsent_output(
+            seed in seed_strategy(),
+            messages in prop::collection::vec(
+                (
+                    any::<bool>(),
+                    prop_oneof![-3i32..12, any::<i32>()],
+                    prop_oneof![-3i32..12, any::<i32>()],
+                    prop::collection::vec(any::<u8>(), 0..16),
+                ),
+                1..8,
+            ),
+        ) {
+            use crate::network::messages::{ConnectionStatus, Input};
+
+            let config = ProtocolConfig {
+                pending_output_limit: 3,
+                ..ProtocolConfig::deterministic(seed)
+            };
+            let mut protocol: UdpProtocol<TestConfig> = create_protocol_with_config(
+                vec![PlayerHandle::new(0)],
+                2,
+                1,
+                8,
+                SyncConfig::default(),
+                config,
+            );
+            protocol.synchronize().unwrap();
+            complete_sync(&mut protocol, 5);
+            for i in 0..6 {
+                protocol.pending_output.push_back(InputBytes {
+                    frame: Frame::new(i),
+                    bytes: vec![0, 0, 0, 0],
+                });
+            }
+            protocol.send_pending_output(&[ConnectionStatus::default(); 2]);
+            let newest_sent = protocol.newest_sent_input_frame;
+            prop_assert_eq!(newest_sent, Frame::new(2));
+
+            for (is_ack, start_frame, ack_frame, bytes) in messages {
+                let ack_frame = Frame::new(ack_frame);
+                let forged = ack_frame > newest_sent;
+                let before = protocol
+                    .input_messages_rejected
+                    .get(InputRejection::FutureAck);
+                if is_ack {
+                    protocol.on_input_ack(InputAck { ack_frame });
+                } else {
+                    protocol.on_input(&Input {
+                        start_frame: Frame::new(start_frame),
+                        ack_frame,
+                        bytes,
+                        peer_connect_status: vec![ConnectionStatus::default(); 2],
+                    });
+                }
+
+                prop_assert!(protocol.last_acked_input.frame <= newest_sent);
+                prop_assert!(protocol.pending_output.len() >= 3);
+                let after = protocol
+                    .input_messages_rejected
+                    .get(InputRejection::FutureAck);
+                prop_assert_eq!(after, before + u64::from(forged));
+            }
+        }
+    }
 }
 
 // =============================================================================
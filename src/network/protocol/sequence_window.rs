@@ -0,0 +1,177 @@
+//! Duplicate and stale detection for sequenced message headers.
+//!
+//! With `ProtocolConfig::sequence_numbers` negotiated, every message an
+//! endpoint sends carries the next number of a wrapping `u32` sequence. The
+//! receiving endpoint keeps a [`SequenceWindow`] over the newest numbers it
+//! has seen and drops a message it has seen before or one too far behind the
+//! newest to tell.
+
+/// Sequence numbers tracked behind (and including) the newest one received.
+///
+/// Every resend of an unacknowledged input is a new message with a new
+/// number, so a legitimate message only falls out of the window when more
+/// than this many newer messages from the same peer overtake it in flight.
+pub(super) const SEQUENCE_WINDOW: u32 = 256;
+
+/// Words in the window bitmap.
+const WINDOW_WORDS: usize = SEQUENCE_WINDOW as usize / 64;
+
+/// How [`SequenceWindow::accept`] classified a sequence number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SequenceVerdict {
+    /// Not seen before and inside the window: process the message.
+    Fresh,
+    /// Already seen: drop the message.
+    Duplicate,
+    /// At least [`SEQUENCE_WINDOW`] behind the newest number: drop the message.
+    Stale,
+}
+
+/// Received sequence numbers within [`SEQUENCE_WINDOW`] of the newest one.
+///
+/// Bit `sequence % SEQUENCE_WINDOW` records whether `sequence` was received;
+/// bits are cleared as the newest number moves past them. Comparisons use
+/// wrapping distance, so the window follows the sequence across `u32::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) struct SequenceWindow {
+    /// The newest sequence number received, `None` until the first one.
+    newest: Option<u32>,
+    received: [u64; WINDOW_WORDS],
+}
+
+impl SequenceWindow {
+    /// Classifies `sequence` and, when it is fresh, records it.
+    pub(super) fn accept(&mut self, sequence: u32) -> SequenceVerdict {
+        let Some(newest) = self.newest else {
+            self.newest = Some(sequence);
+            self.set(sequence);
+            return SequenceVerdict::Fresh;
+        };
+        let ahead = sequence.wrapping_sub(newest);
+        // Half the sequence space ahead counts as newer, the other half as older.
+        if ahead != 0 && ahead < 1 << 31 {
+            if ahead >= SEQUENCE_WINDOW {
+                self.received = [0; WINDOW_WORDS];
+            } else {
+                for skipped in 1..=ahead {
+                    self.clear(newest.wrapping_add(skipped));
+                }
+            }
+            self.newest = Some(sequence);
+            self.set(sequence);
+            return SequenceVerdict::Fresh;
+        }
+        if newest.wrapping_sub(sequence) >= SEQUENCE_WINDOW {
+            return SequenceVerdict::Stale;
+        }
+        if self.is_set(sequence) {
+            return SequenceVerdict::Duplicate;
+        }
+        self.set(sequence);
+        SequenceVerdict::Fresh
+    }
+
+    const fn slot(sequence: u32) -> (usize, u64) {
+        let bit = sequence % SEQUENCE_WINDOW;
+        ((bit / 64) as usize, 1 << (bit % 64))
+    }
+
+    fn set(&mut self, sequence: u32) {
+        let (word, mask) = Self::slot(sequence);
+        if let Some(word) = self.received.get_mut(word) {
+            *word |= mask;
+        }
+    }
+
+    fn clear(&mut self, sequence: u32) {
+        let (word, mask) = Self::slot(sequence);
+        if let Some(word) = self.received.get_mut(word) {
+            *word &= !mask;
+        }
+    }
+
+    fn is_set(&self, sequence: u32) -> bool {
+        let (word, mask) = Self::slot(sequence);
+        self.received.get(word).is_some_and(|word| word & mask != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sequence_is_fresh_and_repeats_are_duplicates() {
+        let mut window = SequenceWindow::default();
+        assert_eq!(window.accept(7), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(7), SequenceVerdict::Duplicate);
+        assert_eq!(window.accept(8), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(7), SequenceVerdict::Duplicate);
+        assert_eq!(window.accept(8), SequenceVerdict::Duplicate);
+    }
+
+    #[test]
+    fn reordered_sequences_inside_the_window_are_fresh_once() {
+        let mut window = SequenceWindow::default();
+        assert_eq!(window.accept(100), SequenceVerdict::Fresh);
+        assert_eq!(
+            window.accept(100 + SEQUENCE_WINDOW - 1),
+            SequenceVerdict::Fresh
+        );
+        for sequence in 101..100 + SEQUENCE_WINDOW - 1 {
+            assert_eq!(window.accept(sequence), SequenceVerdict::Fresh);
+            assert_eq!(window.accept(sequence), SequenceVerdict::Duplicate);
+        }
+        assert_eq!(window.accept(100), SequenceVerdict::Duplicate);
+    }
+
+    #[test]
+    fn sequences_a_full_window_behind_are_stale() {
+        let mut window = SequenceWindow::default();
+        assert_eq!(window.accept(1000), SequenceVerdict::Fresh);
+        assert_eq!(
+            window.accept(1000 - SEQUENCE_WINDOW + 1),
+            SequenceVerdict::Fresh
+        );
+        assert_eq!(
+            window.accept(1000 - SEQUENCE_WINDOW),
+            SequenceVerdict::Stale
+        );
+        // A stale number is not recorded, so it stays stale.
+        assert_eq!(
+            window.accept(1000 - SEQUENCE_WINDOW),
+            SequenceVerdict::Stale
+        );
+    }
+
+    #[test]
+    fn advancing_clears_the_slots_the_window_moves_past() {
+        let mut window = SequenceWindow::default();
+        for sequence in 0..SEQUENCE_WINDOW {
+            assert_eq!(window.accept(sequence), SequenceVerdict::Fresh);
+        }
+        // Slot reuse: `SEQUENCE_WINDOW` shares slot 0 with the now-stale 0.
+        assert_eq!(window.accept(SEQUENCE_WINDOW), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(0), SequenceVerdict::Stale);
+        // A jump past the whole window forgets everything behind it.
+        let far = 10 * SEQUENCE_WINDOW;
+        assert_eq!(window.accept(far), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(far - 1), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(far - 1), SequenceVerdict::Duplicate);
+    }
+
+    #[test]
+    fn window_follows_the_sequence_across_wraparound() {
+        let mut window = SequenceWindow::default();
+        assert_eq!(window.accept(u32::MAX - 1), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(1), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(u32::MAX), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(0), SequenceVerdict::Fresh);
+        assert_eq!(window.accept(u32::MAX - 1), SequenceVerdict::Duplicate);
+        assert_eq!(window.accept(0), SequenceVerdict::Duplicate);
+        assert_eq!(
+            window.accept(1u32.wrapping_sub(SEQUENCE_WINDOW)),
+            SequenceVerdict::Stale
+        );
+    }
+}
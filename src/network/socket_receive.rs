@@ -6,9 +6,13 @@
 use crate::network::MAX_RECEIVE_MESSAGES_PER_POLL;
 use crate::report_violation;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
+use crate::DecodeFailureCounts;
 
+/// Drains `receive_next` into decoded messages, counting each datagram that
+/// fails to decode in `decode_failures`.
 pub(super) fn receive_all_messages_from<A: Debug>(
     recv_buffer: &mut [u8],
+    decode_failures: &mut DecodeFailureCounts,
     adapter_name: &str,
     mut receive_next: impl FnMut(&mut [u8]) -> io::Result<(usize, A)>,
 ) -> Vec<(A, Message)> {
@@ -84,7 +88,10 @@ pub(super) fn receive_all_messages_from<A: Debug>(
                             }
                             received_messages.push((src_addr, msg));
                         },
-                        Err(_err) => {
+                        Err(err) => {
+                            if let Some(failure) = err.decode_failure() {
+                                decode_failures.record(failure);
+                            }
                             let reject = codec::classify_wire_bytes(buf_slice);
                             let bit = reject.rate_limit_bit();
                             if reported_wire_rejects & bit == 0 {
@@ -92,10 +99,11 @@ pub(super) fn receive_all_messages_from<A: Debug>(
                                 report_violation!(
                                     ViolationSeverity::Warning,
                                     ViolationKind::NetworkProtocol,
-                                    "{} rejected datagram from {:?}: {}",
+                                    "{} rejected datagram from {:?}: {} ({})",
                                     adapter_name,
                                     src_addr,
-                                    reject
+                                    reject,
+                                    err
                                 );
                             }
                         },
@@ -139,10 +147,12 @@ pub(super) fn receive_all_messages_from<A: Debug>(
     clippy::expect_used,
     clippy::indexing_slicing
 )]
+#[cfg_attr(not(feature = "telemetry"), allow(dead_code, unused_imports))]
 mod tests {
     use super::*;
-    use crate::network::messages::{MessageBody, MessageHeader};
+    use crate::network::messages::{KeepAlive, MessageBody, MessageHeader};
     use crate::telemetry::{push_violation_observer, CollectingObserver};
+    use crate::DecodeFailure;
     use std::collections::VecDeque;
     use std::net::SocketAddr;
     use std::sync::Arc;
@@ -150,9 +160,10 @@ mod tests {
     fn receive_packets(
         packets: &mut VecDeque<Vec<u8>>,
         addr: SocketAddr,
+        decode_failures: &mut DecodeFailureCounts,
     ) -> Vec<(SocketAddr, Message)> {
         let mut recv_buffer = vec![0; 64];
-        receive_all_messages_from(&mut recv_buffer, "test", |buffer| {
+        receive_all_messages_from(&mut recv_buffer, decode_failures, "test", |buffer| {
             let packet = packets
                 .pop_front()
                 .ok_or_else(|| io::Error::from(ErrorKind::WouldBlock))?;
@@ -167,7 +178,7 @@ fn receive_all_messages_from_caps_raw_malformed_datagrams() {
         let addr: SocketAddr = "127.0.0.1:7000".parse().unwrap();
         let msg = Message {
             header: MessageHeader::new(0xCAFE),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         let mut packets = VecDeque::new();
@@ -186,22 +197,34 @@ fn receive_all_messages_from_caps_raw_malformed_datagrams() {
             Ok((len, addr))
         };
 
-        let first_poll = receive_all_messages_from(&mut recv_buffer, "test", &mut receive_next);
+        let mut decode_failures = DecodeFailureCounts::default();
+        let first_poll = receive_all_messages_from(
+            &mut recv_buffer,
+            &mut decode_failures,
+            "test",
+            &mut receive_next,
+        );
         assert!(
             first_poll.is_empty(),
             "malformed datagrams should count toward the raw receive-attempt cap without decoding"
         );
 
-        let second_poll = receive_all_messages_from(&mut recv_buffer, "test", &mut receive_next);
+        let second_poll = receive_all_messages_from(
+            &mut recv_buffer,
+            &mut decode_failures,
+            "test",
+            &mut receive_next,
+        );
         assert_eq!(second_poll, vec![(addr, msg)]);
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn receive_all_messages_from_reports_each_reject_family_once_per_poll() {
         let addr: SocketAddr = "127.0.0.1:7001".parse().unwrap();
         let valid = Message {
             header: MessageHeader::new(1),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let mut unsupported = codec::encode(&valid).unwrap();
         unsupported[2] = crate::PROTOCOL_VERSION.saturating_add(1);
@@ -225,9 +248,15 @@ fn receive_all_messages_from_reports_each_reject_family_once_per_poll() {
 
         let observer = Arc::new(CollectingObserver::new());
         let _guard = push_violation_observer(observer.clone());
-        let received = receive_packets(&mut packets, addr);
+        let mut decode_failures = DecodeFailureCounts::default();
+        let received = receive_packets(&mut packets, addr, &mut decode_failures);
 
         assert_eq!(received, vec![(addr, valid)]);
+        // Legacy, unknown-flags, and bad-sentinel bytes are all malformed.
+        assert_eq!(decode_failures.get(DecodeFailure::Malformed), 6);
+        assert_eq!(decode_failures.get(DecodeFailure::UnsupportedVersion), 2);
+        assert_eq!(decode_failures.get(DecodeFailure::Truncated), 2);
+        assert_eq!(decode_failures.get(DecodeFailure::TrailingBytes), 0);
         let violations = observer.violations();
         assert_eq!(violations.len(), 5);
         for expected in [
@@ -251,6 +280,7 @@ fn receive_all_messages_from_reports_each_reject_family_once_per_poll() {
             .all(|violation| violation.message.contains(&addr.to_string())));
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn receive_all_messages_from_resets_reject_limit_each_poll() {
         let addr: SocketAddr = "127.0.0.1:7002".parse().unwrap();
@@ -259,7 +289,9 @@ fn receive_all_messages_from_resets_reject_limit_each_poll() {
 
         for _ in 0..2 {
             let mut packets = VecDeque::from([vec![0xF5]]);
-            assert!(receive_packets(&mut packets, addr).is_empty());
+            assert!(
+                receive_packets(&mut packets, addr, &mut DecodeFailureCounts::default()).is_empty()
+            );
         }
 
         assert_eq!(observer.len(), 2);
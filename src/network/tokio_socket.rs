@@ -110,12 +110,12 @@
 
 use tokio::net::UdpSocket;
 
-use crate::network::buffer::{report_send_buffer_too_small, zeroed_buffer};
+use crate::network::buffer::{report_send_buffer_too_small, zeroed_buffer, ENCODE_FAILED};
 use crate::network::codec;
 use crate::network::socket_receive;
 use crate::report_violation;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
-use crate::{network::messages::Message, NonBlockingSocket};
+use crate::{network::messages::Message, DecodeFailureCounts, NonBlockingSocket, SocketErrorKind};
 
 /// Size of the receive buffer. Same as `UdpNonBlockingSocket` for consistency.
 const RECV_BUFFER_SIZE: usize = 4096;
@@ -164,6 +164,8 @@ pub struct TokioUdpSocket {
     recv_buffer: Vec<u8>,
     /// Send buffer - reused across send_to calls to avoid allocation
     send_buffer: Vec<u8>,
+    /// Received datagrams that failed to decode, by category.
+    decode_failures: DecodeFailureCounts,
 }
 
 impl TokioUdpSocket {
@@ -198,6 +200,7 @@ pub fn new(socket: UdpSocket) -> Self {
                 IDEAL_MAX_UDP_PACKET_SIZE,
                 "tokio udp send buffer",
             ),
+            decode_failures: DecodeFailureCounts::default(),
         }
     }
 
@@ -221,6 +224,7 @@ pub fn with_buffer_sizes(
             socket,
             recv_buffer: zeroed_buffer(recv_buffer_size, "tokio udp recv buffer")?,
             send_buffer: zeroed_buffer(send_buffer_size, "tokio udp send buffer")?,
+            decode_failures: DecodeFailureCounts::default(),
         })
     }
 
@@ -306,6 +310,13 @@ pub fn inner(&self) -> &UdpSocket {
         &self.socket
     }
 
+    /// Counts of received datagrams that failed to decode, by category; see
+    /// [`UdpNonBlockingSocket::decode_failures`](crate::UdpNonBlockingSocket::decode_failures).
+    #[must_use]
+    pub fn decode_failures(&self) -> DecodeFailureCounts {
+        self.decode_failures
+    }
+
     /// Waits until the socket is readable, then receives all available messages.
     ///
     /// This is the recommended way to receive messages in an async context.
@@ -393,7 +404,7 @@ pub async fn wait_readable(&self) -> Result<(), std::io::Error> {
     /// ```ignore
     /// use fortress_rollback::tokio_socket::TokioUdpSocket;
     /// use fortress_rollback::NonBlockingSocket;
-    /// use fortress_rollback::network::messages::{Message, MessageBody, MessageHeader};
+    /// use fortress_rollback::network::messages::{KeepAlive, Message, MessageBody, MessageHeader};
     /// use std::net::SocketAddr;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
@@ -401,7 +412,7 @@ pub async fn wait_readable(&self) -> Result<(), std::io::Error> {
     /// let target: SocketAddr = "192.168.1.2:7000".parse()?;
     /// let msg = Message {
     ///     header: MessageHeader::new(0x1234),
-    ///     body: MessageBody::KeepAlive,
+    ///     body: MessageBody::KeepAlive(KeepAlive::default()),
     /// };
     ///
     /// // Wait for socket to be writable before sending
@@ -517,7 +528,7 @@ async fn send_encoded_packet_async(&self, buf: &[u8], addr: &SocketAddr) {
     /// This uses `try_send_to` which may fail with `WouldBlock` if the socket
     /// isn't ready. In async contexts, call [`wait_writable()`](Self::wait_writable)
     /// first or use [`send_to_async()`](Self::send_to_async) instead.
-    fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) {
+    fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) -> Result<(), SocketErrorKind> {
         if buf.len() > IDEAL_MAX_UDP_PACKET_SIZE {
             report_violation!(
                 ViolationSeverity::Warning,
@@ -529,9 +540,10 @@ fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) {
         }
 
         // Use try_send_to for non-blocking send
-        match self.socket.try_send_to(buf, *addr) {
+        let result = self.socket.try_send_to(buf, *addr);
+        match &result {
             Ok(_) => {},
-            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
                 // Socket not ready - this is expected in non-blocking mode.
                 // Report as warning since the packet will be dropped.
                 // Users should call wait_writable() first or use send_to_async().
@@ -553,11 +565,19 @@ fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) {
                 );
             },
         }
+        result
+            .map(|_| ())
+            .map_err(|e| SocketErrorKind::SendFailed { kind: e.kind() })
     }
 }
 
 impl NonBlockingSocket<SocketAddr> for TokioUdpSocket {
     fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        // `try_send_to` already reports failures as violations.
+        let _ = self.try_send_to(msg, addr);
+    }
+
+    fn try_send_to(&mut self, msg: &Message, addr: &SocketAddr) -> Result<(), SocketErrorKind> {
         // Serialize into the pre-allocated send buffer to avoid allocation.
         let len = match codec::encode_into(msg, &mut self.send_buffer) {
             Ok(len) => len,
@@ -565,10 +585,7 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
                 report_send_buffer_too_small(msg, provided);
                 // Fall back to allocating encode
                 match codec::encode(msg) {
-                    Ok(buf) => {
-                        self.send_encoded_packet(&buf, addr);
-                        return;
-                    },
+                    Ok(buf) => return self.send_encoded_packet(&buf, addr),
                     Err(e) => {
                         report_violation!(
                             ViolationSeverity::Error,
@@ -576,7 +593,7 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
                             "Failed to serialize message: {}",
                             e
                         );
-                        return;
+                        return Err(ENCODE_FAILED);
                     },
                 }
             },
@@ -587,7 +604,7 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
                     "Failed to serialize message: {}",
                     e
                 );
-                return;
+                return Err(ENCODE_FAILED);
             },
         };
 
@@ -599,16 +616,23 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
                 len,
                 self.send_buffer.len()
             );
-            return;
+            return Err(ENCODE_FAILED);
         };
-        self.send_encoded_packet(buf, addr);
+        self.send_encoded_packet(buf, addr)
     }
 
     fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
         let socket = &self.socket;
-        socket_receive::receive_all_messages_from(&mut self.recv_buffer, "Tokio UDP", |buffer| {
-            socket.try_recv_from(buffer)
-        })
+        socket_receive::receive_all_messages_from(
+            &mut self.recv_buffer,
+            &mut self.decode_failures,
+            "Tokio UDP",
+            |buffer| socket.try_recv_from(buffer),
+        )
+    }
+
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        self.decode_failures
     }
 }
 
@@ -649,7 +673,7 @@ fn default_socket_buffer(preferred: usize, minimal: usize, name: &'static str) -
 )]
 mod tests {
     use super::*;
-    use crate::network::messages::{MessageBody, MessageHeader};
+    use crate::network::messages::{KeepAlive, MessageBody, MessageHeader};
     use std::net::{IpAddr, Ipv4Addr};
 
     // Helper function to wait for messages with retry logic using the async recv_all method.
@@ -790,7 +814,7 @@ async fn test_tokio_socket_send_and_receive() {
 
         let msg = Message {
             header: MessageHeader::new(0x1234),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         // Send from socket1 to socket2 using async method
@@ -821,11 +845,11 @@ async fn test_tokio_socket_receive_multiple_messages() {
 
         let msg1 = Message {
             header: MessageHeader::new(0x1111),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let msg2 = Message {
             header: MessageHeader::new(0x2222),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         // Send using async method
@@ -857,7 +881,7 @@ async fn test_tokio_socket_send_to_invalid_address() {
         let invalid_addr: SocketAddr = "0.0.0.0:0".parse().unwrap();
         let msg = Message {
             header: MessageHeader::new(0x1234),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         // This should log an error but not panic
         socket.send_to(&msg, &invalid_addr);
@@ -887,7 +911,7 @@ async fn test_tokio_socket_send_after_wait_writable() {
 
         let msg = Message {
             header: MessageHeader::new(0xABCD),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         // Wait for socket to be writable, then send using sync method
@@ -1,11 +1,11 @@
-use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV6, UdpSocket};
 
-use crate::network::buffer::{report_send_buffer_too_small, zeroed_buffer};
+use crate::network::buffer::{report_send_buffer_too_small, zeroed_buffer, ENCODE_FAILED};
 use crate::network::codec;
 use crate::network::socket_receive;
 use crate::report_violation;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
-use crate::{network::messages::Message, NonBlockingSocket};
+use crate::{network::messages::Message, DecodeFailureCounts, NonBlockingSocket, SocketErrorKind};
 
 const RECV_BUFFER_SIZE: usize = 4096;
 /// Size of the pre-allocated send buffer. This should be large enough to hold
@@ -16,7 +16,20 @@
 /// Source: <https://stackoverflow.com/a/35697810/775982>
 const IDEAL_MAX_UDP_PACKET_SIZE: usize = 508;
 
-/// A simple non-blocking UDP socket to use with Fortress Rollback Sessions. Listens to 0.0.0.0 on a given port.
+/// A simple non-blocking UDP socket to use with Fortress Rollback Sessions.
+///
+/// [`bind_to_port`](Self::bind_to_port) listens on 0.0.0.0, [`bind`](Self::bind)
+/// on a specific interface or IPv6 address, and
+/// [`bind_dual_stack`](Self::bind_dual_stack) on both IPv4 and IPv6.
+///
+/// # Address normalization
+///
+/// An IPv6 socket sees IPv4 peers as IPv4-mapped addresses (`::ffff:a.b.c.d`).
+/// This socket always reports them as plain IPv4 addresses, and sends to a
+/// plain IPv4 address through its mapped form, so an IPv4 peer is identified by
+/// the same `a.b.c.d:port` whichever socket family talks to it. Register IPv4
+/// peers with the builder in that plain form; [`normalize_addr`](Self::normalize_addr)
+/// converts an address from matchmaking the same way.
 ///
 /// # Performance
 ///
@@ -32,6 +45,10 @@ pub struct UdpNonBlockingSocket {
     recv_buffer: Vec<u8>,
     /// Send buffer - reused across send_to calls to avoid allocation
     send_buffer: Vec<u8>,
+    /// Whether the socket is IPv6, so plain IPv4 destinations must be mapped.
+    ipv6: bool,
+    /// Received datagrams that failed to decode, by category.
+    decode_failures: DecodeFailureCounts,
 }
 
 impl UdpNonBlockingSocket {
@@ -62,6 +79,69 @@ pub fn bind_to_port_with_buffer_sizes(
         Self::from_socket_with_buffer_sizes(socket, recv_buffer_size, send_buffer_size)
     }
 
+    /// Binds a UDP socket to a specific local address and sets it to
+    /// non-blocking mode.
+    ///
+    /// Use this on multi-homed hosts to pick the interface, or with an IPv6
+    /// address (an IPv6 socket bound this way is IPv6-only on platforms that
+    /// default to it; see [`bind_dual_stack`](Self::bind_dual_stack)).
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error from binding the underlying socket.
+    pub fn bind(addr: SocketAddr) -> Result<Self, std::io::Error> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Self::from_socket_with_buffer_sizes(socket, RECV_BUFFER_SIZE, SEND_BUFFER_SIZE)
+    }
+
+    /// Binds an IPv6 UDP socket to `[::]:port` with IPv6-only mode disabled,
+    /// so one session can talk to both IPv4 and IPv6 peers.
+    ///
+    /// IPv4 peers are reported by their plain IPv4 address (see
+    /// [Address normalization](Self#address-normalization)). If the platform
+    /// refuses to disable IPv6-only mode, the socket stays IPv6-only and a
+    /// warning is reported; IPv4 peers are then unreachable through it.
+    ///
+    /// Not available on wasm targets.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error from creating or binding the underlying socket,
+    /// for example when IPv6 is disabled on the host.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn bind_dual_stack(port: u16) -> Result<Self, std::io::Error> {
+        use socket2::{Domain, Protocol, Socket, Type};
+
+        let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+        if let Err(err) = socket.set_only_v6(false) {
+            report_violation!(
+                ViolationSeverity::Warning,
+                ViolationKind::Configuration,
+                "Could not disable IPv6-only mode on the dual-stack UDP socket; IPv4 peers will be unreachable: {}",
+                err
+            );
+        }
+        let addr = SocketAddr::new(IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED), port);
+        socket.bind(&addr.into())?;
+        socket.set_nonblocking(true)?;
+        Self::from_socket_with_buffer_sizes(socket.into(), RECV_BUFFER_SIZE, SEND_BUFFER_SIZE)
+    }
+
+    /// Converts an IPv4-mapped IPv6 address (`[::ffff:a.b.c.d]:port`) to the
+    /// plain IPv4 address (`a.b.c.d:port`) this socket reports for that peer.
+    /// Every other address is returned unchanged.
+    #[must_use]
+    pub fn normalize_addr(addr: SocketAddr) -> SocketAddr {
+        match addr {
+            SocketAddr::V6(v6) => match v6.ip().to_ipv4_mapped() {
+                Some(v4) => SocketAddr::new(IpAddr::V4(v4), v6.port()),
+                None => addr,
+            },
+            SocketAddr::V4(_) => addr,
+        }
+    }
+
     /// Wraps an existing UDP socket with caller-configured buffers.
     ///
     /// # Errors
@@ -74,10 +154,13 @@ pub fn from_socket_with_buffer_sizes(
         recv_buffer_size: usize,
         send_buffer_size: usize,
     ) -> Result<Self, std::io::Error> {
+        let ipv6 = socket.local_addr().is_ok_and(|addr| addr.is_ipv6());
         Ok(Self {
             socket,
             recv_buffer: zeroed_buffer(recv_buffer_size, "udp recv buffer")?,
             send_buffer: zeroed_buffer(send_buffer_size, "udp send buffer")?,
+            ipv6,
+            decode_failures: DecodeFailureCounts::default(),
         })
     }
 
@@ -86,12 +169,17 @@ pub fn from_socket_with_buffer_sizes(
     /// This is useful when binding to port 0 (ephemeral port) to discover
     /// the actual port assigned by the operating system.
     ///
+    /// It is also the address to hand to a matchmaking service, after
+    /// replacing an unspecified IP (`0.0.0.0` or `::`) with a reachable one.
+    ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```no_run
+    /// # use fortress_rollback::UdpNonBlockingSocket;
     /// let socket = UdpNonBlockingSocket::bind_to_port(0)?;
     /// let local_addr = socket.local_addr()?;
     /// println!("Bound to port: {}", local_addr.port());
+    /// # Ok::<(), std::io::Error>(())
     /// ```
     ///
     /// # Errors
@@ -100,10 +188,40 @@ pub fn from_socket_with_buffer_sizes(
     pub fn local_addr(&self) -> Result<SocketAddr, std::io::Error> {
         self.socket.local_addr()
     }
+
+    /// Counts of received datagrams that failed to decode, by category.
+    ///
+    /// A cut-off datagram counts as [`DecodeFailure::Truncated`], a peer on
+    /// another protocol version as [`DecodeFailure::UnsupportedVersion`], and
+    /// corrupt or foreign bytes as [`DecodeFailure::Malformed`]. The counts
+    /// cover the socket's whole life; a session using the socket reports them
+    /// in [`SessionMetrics::decode_failures`](crate::SessionMetrics::decode_failures).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use fortress_rollback::{DecodeFailure, UdpNonBlockingSocket};
+    /// let socket = UdpNonBlockingSocket::bind_to_port(0)?;
+    /// assert_eq!(socket.decode_failures().get(DecodeFailure::Truncated), 0);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`DecodeFailure::Truncated`]: crate::DecodeFailure::Truncated
+    /// [`DecodeFailure::UnsupportedVersion`]: crate::DecodeFailure::UnsupportedVersion
+    /// [`DecodeFailure::Malformed`]: crate::DecodeFailure::Malformed
+    #[must_use]
+    pub fn decode_failures(&self) -> DecodeFailureCounts {
+        self.decode_failures
+    }
 }
 
 impl NonBlockingSocket<SocketAddr> for UdpNonBlockingSocket {
     fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        // `try_send_to` already reports failures as violations.
+        let _ = self.try_send_to(msg, addr);
+    }
+
+    fn try_send_to(&mut self, msg: &Message, addr: &SocketAddr) -> Result<(), SocketErrorKind> {
         // Serialize into the pre-allocated send buffer to avoid allocation.
         // This is the hot path for network sends.
         let len = match codec::encode_into(msg, &mut self.send_buffer) {
@@ -115,10 +233,7 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
                 report_send_buffer_too_small(msg, provided);
                 // Fall back to allocating encode
                 match codec::encode(msg) {
-                    Ok(buf) => {
-                        self.send_encoded_packet(&buf, addr);
-                        return;
-                    },
+                    Ok(buf) => return self.send_encoded_packet(&buf, addr),
                     Err(e) => {
                         report_violation!(
                             ViolationSeverity::Error,
@@ -126,7 +241,7 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
                             "Failed to serialize message: {}",
                             e
                         );
-                        return;
+                        return Err(ENCODE_FAILED);
                     },
                 }
             },
@@ -137,7 +252,7 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
                     "Failed to serialize message: {}",
                     e
                 );
-                return;
+                return Err(ENCODE_FAILED);
             },
         };
 
@@ -151,22 +266,34 @@ fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
             );
             &[]
         });
-        self.send_encoded_packet(buf_slice, addr);
+        self.send_encoded_packet(buf_slice, addr)
     }
 
     fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
         let socket = &self.socket;
-        socket_receive::receive_all_messages_from(&mut self.recv_buffer, "UDP", |buffer| {
-            socket.recv_from(buffer)
-        })
+        socket_receive::receive_all_messages_from(
+            &mut self.recv_buffer,
+            &mut self.decode_failures,
+            "UDP",
+            |buffer| {
+                socket
+                    .recv_from(buffer)
+                    .map(|(len, from)| (len, Self::normalize_addr(from)))
+            },
+        )
+    }
+
+    fn decode_failures(&self) -> DecodeFailureCounts {
+        self.decode_failures
     }
 }
 
 impl UdpNonBlockingSocket {
     /// Sends an already-encoded packet to the given address.
     ///
-    /// This is a helper that handles packet size warnings and send errors.
-    fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) {
+    /// This is a helper that handles packet size warnings and reports send
+    /// errors.
+    fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) -> Result<(), SocketErrorKind> {
         // Overly large packets risk being fragmented, which can increase packet loss (any fragment
         // of a packet getting lost will cause the whole fragment to be lost), or increase latency
         // to be delayed (have to wait for all fragments to arrive).
@@ -190,16 +317,28 @@ fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) {
             );
         }
 
+        // An IPv6 socket cannot send to a plain IPv4 address; use its mapped form.
+        let dest = match addr {
+            SocketAddr::V4(v4) if self.ipv6 => {
+                SocketAddr::V6(SocketAddrV6::new(v4.ip().to_ipv6_mapped(), v4.port(), 0, 0))
+            },
+            _ => *addr,
+        };
+
         // Send the packet; if this fails, log an error but don't panic.
         // UDP is best-effort, so dropped packets are expected behavior.
-        if let Err(e) = self.socket.send_to(buf, addr) {
-            report_violation!(
-                ViolationSeverity::Warning,
-                ViolationKind::NetworkProtocol,
-                "Failed to send UDP packet to {}: {}",
-                addr,
-                e
-            );
+        match self.socket.send_to(buf, dest) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                report_violation!(
+                    ViolationSeverity::Warning,
+                    ViolationKind::NetworkProtocol,
+                    "Failed to send UDP packet to {}: {}",
+                    addr,
+                    e
+                );
+                Err(SocketErrorKind::SendFailed { kind: e.kind() })
+            },
         }
     }
 }
@@ -214,7 +353,7 @@ fn send_encoded_packet(&self, buf: &[u8], addr: &SocketAddr) {
 mod tests {
     use super::*;
     #[cfg(not(miri))]
-    use crate::network::messages::{MessageBody, MessageHeader};
+    use crate::network::messages::{KeepAlive, MessageBody, MessageHeader};
     #[cfg(not(miri))]
     use std::io::ErrorKind;
 
@@ -318,7 +457,7 @@ fn test_udp_socket_send_and_receive() {
 
         let msg = Message {
             header: MessageHeader::new(0x1234),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         // Send from socket1 to socket2
@@ -349,11 +488,11 @@ fn test_udp_socket_receive_multiple_messages() {
 
         let msg1 = Message {
             header: MessageHeader::new(0x1111),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         let msg2 = Message {
             header: MessageHeader::new(0x2222),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         socket1.send_to(&msg1, &addr2);
@@ -440,7 +579,7 @@ fn test_udp_socket_send_to_invalid_address() {
         let invalid_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
         let msg = Message {
             header: MessageHeader::new(0x1234),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
         // This should log an error but not panic
         socket.send_to(&msg, &invalid_addr);
@@ -523,7 +662,7 @@ fn test_udp_socket_multiple_sends_reuse_buffer() {
         for i in 0..5u16 {
             let msg = Message {
                 header: MessageHeader::new(u32::from(i)),
-                body: MessageBody::KeepAlive,
+                body: MessageBody::KeepAlive(KeepAlive::default()),
             };
             socket1.send_to(&msg, &addr2);
         }
@@ -543,7 +682,7 @@ fn test_udp_socket_keepalive_message_roundtrip() {
 
         let msg = Message {
             header: MessageHeader::new(0xDEAD),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         socket1.send_to(&msg, &addr2);
@@ -551,7 +690,7 @@ fn test_udp_socket_keepalive_message_roundtrip() {
 
         assert_eq!(received.len(), 1);
         assert_eq!(received[0].1.header.conn_id, 0xDEAD);
-        assert!(matches!(received[0].1.body, MessageBody::KeepAlive));
+        assert!(matches!(received[0].1.body, MessageBody::KeepAlive(_)));
     }
 
     #[test]
@@ -562,7 +701,7 @@ fn test_udp_socket_handles_self_send() {
 
         let msg = Message {
             header: MessageHeader::new(0xBEEF),
-            body: MessageBody::KeepAlive,
+            body: MessageBody::KeepAlive(KeepAlive::default()),
         };
 
         // Send to self
@@ -573,4 +712,57 @@ fn test_udp_socket_handles_self_send() {
         assert_eq!(received.len(), 1);
         assert_eq!(received[0].1.header.conn_id, 0xBEEF);
     }
+
+    #[test]
+    fn normalize_addr_unmaps_only_ipv4_mapped_addresses() {
+        let mapped: SocketAddr = "[::ffff:192.0.2.7]:7000".parse().unwrap();
+        assert_eq!(
+            UdpNonBlockingSocket::normalize_addr(mapped),
+            "192.0.2.7:7000".parse::<SocketAddr>().unwrap()
+        );
+        for unchanged in ["192.0.2.7:7000", "[::1]:7000", "[2001:db8::7]:7000"] {
+            let addr: SocketAddr = unchanged.parse().unwrap();
+            assert_eq!(UdpNonBlockingSocket::normalize_addr(addr), addr);
+        }
+    }
+
+    #[test]
+    #[cfg(not(miri))]
+    fn test_udp_socket_bind_to_specific_address() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let socket = UdpNonBlockingSocket::bind(addr).unwrap();
+        let local = socket.local_addr().unwrap();
+        assert_eq!(local.ip(), IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_ne!(local.port(), 0);
+        assert!(!socket.ipv6);
+    }
+
+    #[test]
+    #[cfg(not(miri))]
+    fn dual_stack_socket_talks_to_ipv4_peer_by_its_plain_address() {
+        let Ok(mut dual) = UdpNonBlockingSocket::bind_dual_stack(0) else {
+            return; // IPv6 unavailable on this host
+        };
+        assert!(dual.ipv6);
+        let dual_addr = to_loopback_addr(&dual);
+        let mut v4 =
+            UdpNonBlockingSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+                .unwrap();
+        let v4_addr = v4.local_addr().unwrap();
+
+        let msg = Message {
+            header: MessageHeader::new(0xC0DE),
+            body: MessageBody::KeepAlive(KeepAlive::default()),
+        };
+        v4.send_to(&msg, &dual_addr);
+        let received = wait_for_messages(&mut dual, 1, 20);
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0, v4_addr, "IPv4-mapped source is normalized");
+
+        // Replies go to the plain IPv4 address through its mapped form.
+        dual.send_to(&msg, &v4_addr);
+        let received = wait_for_messages(&mut v4, 1, 20);
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0, dual_addr);
+    }
 }
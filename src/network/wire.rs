@@ -0,0 +1,197 @@
+//! Standalone encoding of `Input` message bodies for other implementations of
+//! the wire protocol.
+//!
+//! An `Input` message carries a batch of consecutive frames of one peer's
+//! input. On the wire it is the message header, the `u32` tag `2`, and then the
+//! body that [`encode_input_packet()`] produces and [`decode_input_packet()`]
+//! reads:
+//!
+//! | Field | Encoding |
+//! |-------|----------|
+//! | connection statuses | `u64` count, then per slot a `u8` disconnected flag, an `i32` last frame and a `u16` epoch |
+//! | `start_frame` | `i32` |
+//! | `ack_frame` | `i32` |
+//! | input batch | `u64` length, then the compressed batch |
+//!
+//! All integers are little-endian and [`Frame::NULL`] is `-1`. The batch XORs
+//! each frame's input bytes with the reference input, concatenates the results
+//! and run-length encodes them in the `bitfield-rle` format described in
+//! [`rle`](crate::rle).
+//!
+//! The reference is the encoded input of the frame before `start_frame` that
+//! the receiver already has: the last input it acked, or the encoded
+//! `Config::Input::default()` before the first ack. This is exactly the body a
+//! session sends on a link without input resync
+//! ([`ProtocolConfig::input_resync_threshold`](crate::ProtocolConfig::input_resync_threshold)),
+//! frame tags ([`ProtocolConfig::frame_tags`](crate::ProtocolConfig::frame_tags))
+//! or spectator keyframes negotiated, which is the default. Those features add
+//! sections this module does not produce.
+//!
+//! The unit tests pin golden vectors (inputs to exact body bytes) that other
+//! implementations can validate against; a change to the format fails them.
+//!
+//! # Examples
+//!
+//! ```
+//! use fortress_rollback::network::wire::{
+//!     decode_input_packet, encode_input_packet, ConnectionStatus,
+//! };
+//! use fortress_rollback::Frame;
+//!
+//! let reference = [0u8; 2];
+//! let pending = vec![vec![1, 0], vec![1, 0]];
+//! let statuses = [ConnectionStatus::default(); 2];
+//! let body = encode_input_packet(&reference, &pending, Frame::new(0), Frame::NULL, &statuses)?;
+//!
+//! let packet = decode_input_packet(&reference, &body)?;
+//! assert_eq!(packet.start_frame, Frame::new(0));
+//! assert_eq!(packet.inputs, pending);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::network::codec::{self, CodecError, CodecOperation, CodecResult};
+use crate::network::compression;
+use crate::network::messages::Input;
+use crate::Frame;
+
+pub use crate::network::messages::ConnectionStatus;
+
+/// An `Input` message body decoded by [`decode_input_packet()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputPacket {
+    /// The sender's view of every slot's connection.
+    pub statuses: Vec<ConnectionStatus>,
+    /// The frame of the first input in [`inputs`](Self::inputs).
+    pub start_frame: Frame,
+    /// The newest frame of the receiver's input the sender has received.
+    pub ack_frame: Frame,
+    /// The encoded input of each frame from `start_frame` on, in order.
+    pub inputs: Vec<Vec<u8>>,
+}
+
+/// Encodes an `Input` message body.
+///
+/// `last_acked` is the reference input the receiver decodes against and
+/// `pending` the encoded input of each frame from `start_frame` on; every
+/// entry must be as long as `last_acked`.
+///
+/// # Errors
+///
+/// Returns [`CodecError::EncodeError`] if `last_acked` or `pending` is empty,
+/// or if an entry of `pending` differs in length from `last_acked`.
+pub fn encode_input_packet(
+    last_acked: &[u8],
+    pending: &[Vec<u8>],
+    start_frame: Frame,
+    ack_frame: Frame,
+    statuses: &[ConnectionStatus],
+) -> CodecResult<Vec<u8>> {
+    if last_acked.is_empty() || pending.is_empty() {
+        return Err(CodecError::encode(
+            "an input packet needs a reference input and at least one frame",
+            CodecOperation::Encode,
+        ));
+    }
+    if let Some(input) = pending.iter().find(|input| input.len() != last_acked.len()) {
+        return Err(CodecError::encode(
+            format!(
+                "input length {} does not match reference length {}",
+                input.len(),
+                last_acked.len()
+            ),
+            CodecOperation::Encode,
+        ));
+    }
+    let bytes = compression::try_encode(last_acked, pending.iter())
+        .map_err(|err| CodecError::encode(err.to_string(), CodecOperation::Encode))?;
+    codec::encode(&Input {
+        peer_connect_status: statuses.to_vec(),
+        start_frame,
+        ack_frame,
+        bytes,
+    })
+}
+
+/// Decodes an `Input` message body produced by [`encode_input_packet()`] or
+/// received from a peer, against the reference input `last_acked`.
+///
+/// # Errors
+///
+/// Returns [`CodecError::DecodeError`] if `packet` is truncated, has trailing
+/// bytes, holds an out-of-range frame, or its batch does not decode against
+/// `last_acked`.
+pub fn decode_input_packet(last_acked: &[u8], packet: &[u8]) -> CodecResult<InputPacket> {
+    let mut cursor = 0;
+    let input = codec::decode_input(packet, &mut cursor)?;
+    if cursor != packet.len() {
+        return Err(CodecError::decode(
+            format!(
+                "{} trailing bytes after input packet",
+                packet.len().saturating_sub(cursor)
+            ),
+            CodecOperation::Decode,
+        ));
+    }
+    let inputs = compression::decode(last_acked, &input.bytes)
+        .map_err(|err| CodecError::decode(err.to_string(), CodecOperation::Decode))?;
+    Ok(InputPacket {
+        statuses: input.peer_connect_status,
+        start_frame: input.start_frame,
+        ack_frame: input.ack_frame,
+        inputs,
+    })
+}
+
+#[cfg(test)]
+#[path = "wire_input_golden.rs"]
+mod wire_input_golden;
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    fn statuses() -> Vec<ConnectionStatus> {
+        vec![ConnectionStatus::default(); 2]
+    }
+
+    #[test]
+    fn encode_rejects_empty_reference_batch_and_length_mismatch() {
+        let start = Frame::new(0);
+        for (reference, pending) in [
+            (&[][..], vec![vec![]]),
+            (&[0u8][..], vec![]),
+            (&[0u8][..], vec![vec![1], vec![1, 2]]),
+        ] {
+            let result = encode_input_packet(reference, &pending, start, Frame::NULL, &statuses());
+            assert!(
+                matches!(result, Err(CodecError::EncodeError { .. })),
+                "{reference:?} {pending:?}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_rejects_trailing_truncated_and_mismatched_packets() {
+        let reference = [0u8; 2];
+        let mut body = encode_input_packet(
+            &reference,
+            &[vec![1, 2]],
+            Frame::new(4),
+            Frame::new(3),
+            &statuses(),
+        )
+        .unwrap();
+        assert!(decode_input_packet(&reference, &body).is_ok());
+
+        assert!(decode_input_packet(&reference, &body[..body.len() - 1]).is_err());
+        assert!(decode_input_packet(&[0u8; 3], &body).is_err());
+        body.push(0);
+        assert!(decode_input_packet(&reference, &body).is_err());
+    }
+}
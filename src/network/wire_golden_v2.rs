@@ -3,12 +3,13 @@
 //! Changing any literal in this released-version file requires a protocol-version
 //! bump. `scripts/hooks/check-wire-golden-immutable.py` enforces that rule.
 
+use super::{decode_message, decode_value};
 use crate::network::messages::{
     ChecksumReport, ConnectionStatus, DropAbort, DropAbortReason, DropBackfill, DropCommit,
     DropOperationId, DropPrepare, DropReceipt, DropReport, DropReportStage, DropTarget, FloorReply,
-    FloorRequest, Goodbye, Input, InputAck, JoinAborted, JoinCommitted, JoinRequest, Message,
-    MessageBody, MessageHeader, QualityReply, QualityReport, ReactivateSlot, ReactivateSlotAck,
-    SessionConfigBlock, StateSnapshot, StateSnapshotAck, SyncReply, SyncRequest,
+    FloorRequest, Goodbye, Input, InputAck, JoinAborted, JoinCommitted, JoinRequest, KeepAlive,
+    Message, MessageBody, MessageHeader, QualityReply, QualityReport, ReactivateSlot,
+    ReactivateSlotAck, SessionConfigBlock, StateSnapshot, StateSnapshotAck, SyncReply, SyncRequest,
 };
 use crate::Frame;
 
@@ -142,6 +143,8 @@ pub(super) fn fixtures() -> Vec<(&'static str, Message)> {
             features: 1,
             config,
             config_digest: 0x0102_0304_0506_0708,
+            handles_digest: 0,
+            access_proof: 0,
         }),
         MessageBody::SyncReply(SyncReply {
             random_reply: 0x5060_7080,
@@ -149,6 +152,7 @@ pub(super) fn fixtures() -> Vec<(&'static str, Message)> {
             features: 1,
             config,
             config_digest: 0x1112_1314_1516_1718,
+            handles_digest: 0,
         }),
         MessageBody::Input(Input {
             peer_connect_status: vec![
@@ -173,6 +177,7 @@ pub(super) fn fixtures() -> Vec<(&'static str, Message)> {
         MessageBody::QualityReport(QualityReport {
             frame_advantage: -2,
             ping: 0x0102_0304_0506_0708_090A_0B0C_0D0E_0F10,
+            stalled: false,
         }),
         MessageBody::QualityReply(QualityReply {
             pong: 0x1112_1314_1516_1718_191A_1B1C_1D1E_1F20,
@@ -181,7 +186,7 @@ pub(super) fn fixtures() -> Vec<(&'static str, Message)> {
             checksum: 0x2122_2324_2526_2728_292A_2B2C_2D2E_2F30,
             frame: Frame::new(88),
         }),
-        MessageBody::KeepAlive,
+        MessageBody::KeepAlive(KeepAlive::default()),
         MessageBody::FloorRequest(FloorRequest { round_seq: 42 }),
         MessageBody::FloorReply(FloorReply {
             round_seq: 42,
@@ -295,7 +300,7 @@ fn name(body: &MessageBody) -> &'static str {
         MessageBody::QualityReport(_) => "QualityReport",
         MessageBody::QualityReply(_) => "QualityReply",
         MessageBody::ChecksumReport(_) => "ChecksumReport",
-        MessageBody::KeepAlive => "KeepAlive",
+        MessageBody::KeepAlive(_) => "KeepAlive",
         MessageBody::FloorRequest(_) => "FloorRequest",
         MessageBody::FloorReply(_) => "FloorReply",
         MessageBody::JoinRequest(_) => "JoinRequest",
@@ -311,6 +316,18 @@ fn name(body: &MessageBody) -> &'static str {
         MessageBody::DropBackfill(_) => "DropBackfill",
         MessageBody::DropCommit(_) => "DropCommit",
         MessageBody::DropAbort(_) => "DropAbort",
+        // Introduced by protocol v3; never part of this released suite.
+        MessageBody::MatchPause(_) => "MatchPause",
+        MessageBody::MatchPauseAck(_) => "MatchPauseAck",
+        // Introduced by protocol v4; never part of this released suite.
+        MessageBody::StreamEnd(_) => "StreamEnd",
+        // Introduced by protocol v5; never part of this released suite.
+        MessageBody::ReliableMessage(_) => "ReliableMessage",
+        MessageBody::ReliableAck(_) => "ReliableAck",
+        // Introduced by protocol v6; never part of this released suite.
+        MessageBody::LinkClosed(_) => "LinkClosed",
+        // Introduced by protocol v7; never part of this released suite.
+        MessageBody::ChecksumRequest(_) => "ChecksumRequest",
     }
 }
 
@@ -323,7 +340,7 @@ pub(super) fn expected(body: &MessageBody) -> &'static [u8] {
         MessageBody::QualityReport(_) => QUALITY_REPORT,
         MessageBody::QualityReply(_) => QUALITY_REPLY,
         MessageBody::ChecksumReport(_) => CHECKSUM_REPORT,
-        MessageBody::KeepAlive => KEEP_ALIVE,
+        MessageBody::KeepAlive(_) => KEEP_ALIVE,
         MessageBody::FloorRequest(_) => FLOOR_REQUEST,
         MessageBody::FloorReply(_) => FLOOR_REPLY,
         MessageBody::JoinRequest(_) => JOIN_REQUEST,
@@ -339,17 +356,66 @@ pub(super) fn expected(body: &MessageBody) -> &'static [u8] {
         MessageBody::DropBackfill(_) => DROP_BACKFILL,
         MessageBody::DropCommit(_) => DROP_COMMIT,
         MessageBody::DropAbort(_) => DROP_ABORT,
+        // Introduced by protocol v3; no released bytes exist.
+        MessageBody::MatchPause(_) => &[],
+        MessageBody::MatchPauseAck(_) => &[],
+        // Introduced by protocol v4; no released bytes exist.
+        MessageBody::StreamEnd(_) => &[],
+        // Introduced by protocol v5; no released bytes exist.
+        MessageBody::ReliableMessage(_) => &[],
+        MessageBody::ReliableAck(_) => &[],
+        // Introduced by protocol v6; no released bytes exist.
+        MessageBody::LinkClosed(_) => &[],
+        // Introduced by protocol v7; no released bytes exist.
+        MessageBody::ChecksumRequest(_) => &[],
     }
 }
 
 #[test]
 fn every_protocol_v2_variant_has_immutable_exact_bytes() {
-    super::assert_wire_golden_suite(WIRE_GOLDEN_VERSION, fixtures(), expected);
+    const {
+        assert!(
+            crate::PROTOCOL_VERSION > WIRE_GOLDEN_VERSION,
+            "released v2 fixtures become a rejection suite after a version bump"
+        );
+    }
+    let fixtures = fixtures();
+    for (variant, message) in fixtures {
+        let expected = expected(&message.body);
+        // Protocol v8 appended `stalled` to the quality report, v9 appended
+        // `handles_digest` to the sync handshake, and v10 appended `ack_frame`
+        // to the keepalive, so these bytes no longer match the current layout;
+        // only their rejection is checked.
+        if !matches!(
+            message.body,
+            MessageBody::QualityReport(_)
+                | MessageBody::SyncRequest(_)
+                | MessageBody::SyncReply(_)
+                | MessageBody::KeepAlive(_)
+        ) {
+            assert_eq!(
+                message.encoded_len(),
+                expected.len(),
+                "encoded length for {variant}"
+            );
+            let generic: Message = decode_value(expected).expect("fixture must generically decode");
+            assert_eq!(
+                generic.body, message.body,
+                "generic body decode for {variant}"
+            );
+            assert_eq!(generic.header.protocol_version, WIRE_GOLDEN_VERSION);
+        }
+        let error = decode_message(expected).expect_err("released v2 packet must reject");
+        assert!(
+            error.to_string().contains("unsupported protocol version 2"),
+            "v2 rejection for {variant}: {error}"
+        );
+    }
 }
 
 #[cfg(not(feature = "hot-join"))]
 #[test]
-fn hot_join_v2_goldens_are_recognized_when_feature_is_disabled() {
+fn hot_join_v2_goldens_reject_before_feature_dispatch() {
     for (_, message) in fixtures().into_iter().filter(|(_, message)| {
         matches!(
             &message.body,
@@ -362,10 +428,8 @@ fn hot_join_v2_goldens_are_recognized_when_feature_is_disabled() {
                 | MessageBody::JoinAborted(_)
         )
     }) {
-        let error = super::decode_message(expected(&message.body))
-            .expect_err("disabled hot-join fixture must reject");
-        assert!(error
-            .to_string()
-            .contains("requires the disabled hot-join feature"));
+        let error = decode_message(expected(&message.body))
+            .expect_err("released v2 hot-join fixture must reject");
+        assert!(error.to_string().contains("unsupported protocol version 2"));
     }
 }
@@ -0,0 +1,111 @@
+//! Golden vectors for the `Input` message body.
+//!
+//! Each vector pins the exact bytes [`encode_input_packet`] produces for its
+//! arguments, so other implementations of the wire protocol can validate
+//! against them. Changing a literal here changes the wire format and needs the
+//! same deliberate review as the `wire_golden_v*` fixtures.
+
+use super::{decode_input_packet, encode_input_packet, ConnectionStatus, InputPacket};
+use crate::Frame;
+
+struct Vector {
+    name: &'static str,
+    reference: &'static [u8],
+    packet: InputPacket,
+    bytes: &'static [u8],
+}
+
+fn status(disconnected: bool, last_frame: i32, epoch: u16) -> ConnectionStatus {
+    ConnectionStatus {
+        disconnected,
+        last_frame: Frame::new(last_frame),
+        epoch,
+    }
+}
+
+/// The first packet of a two-player link: one frame against the default
+/// input, nothing received yet.
+const FIRST_FRAME: &[u8] = &[
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x03, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x05, 0x05,
+];
+
+/// Three frames mixing zero runs and literal bytes, with one slot
+/// disconnected in a later epoch.
+const BATCH: &[u8] = &[
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+    0x09, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0A, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x07, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09, 0x02, 0x02, 0x05, 0x04, 0x02, 0x01,
+];
+
+/// A frame whose every bit flips, compressed to a single `0xFF` run.
+const ONES_RUN: &[u8] = &[
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+    0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x43,
+];
+
+fn vectors() -> Vec<Vector> {
+    vec![
+        Vector {
+            name: "first_frame",
+            reference: &[0x00, 0x00],
+            packet: InputPacket {
+                statuses: vec![ConnectionStatus::default(); 2],
+                start_frame: Frame::new(0),
+                ack_frame: Frame::NULL,
+                inputs: vec![vec![0x05, 0x00]],
+            },
+            bytes: FIRST_FRAME,
+        },
+        Vector {
+            name: "batch",
+            reference: &[0x05, 0x00],
+            packet: InputPacket {
+                statuses: vec![status(false, 12, 0), status(true, 9, 1)],
+                start_frame: Frame::new(10),
+                ack_frame: Frame::new(8),
+                inputs: vec![vec![0x05, 0x00], vec![0x07, 0x00], vec![0x07, 0x01]],
+            },
+            bytes: BATCH,
+        },
+        Vector {
+            name: "ones_run",
+            reference: &[0xFF; 16],
+            packet: InputPacket {
+                statuses: vec![status(false, 2, 0)],
+                start_frame: Frame::new(3),
+                ack_frame: Frame::new(2),
+                inputs: vec![vec![0x00; 16]],
+            },
+            bytes: ONES_RUN,
+        },
+    ]
+}
+
+#[test]
+fn input_packet_golden_vectors_encode_exactly() {
+    for vector in vectors() {
+        let packet = &vector.packet;
+        let encoded = encode_input_packet(
+            vector.reference,
+            &packet.inputs,
+            packet.start_frame,
+            packet.ack_frame,
+            &packet.statuses,
+        );
+        assert_eq!(encoded.as_deref(), Ok(vector.bytes), "{}", vector.name);
+    }
+}
+
+#[test]
+fn input_packet_golden_vectors_decode_exactly() {
+    for vector in vectors() {
+        assert_eq!(
+            decode_input_packet(vector.reference, vector.bytes),
+            Ok(vector.packet),
+            "{}",
+            vector.name
+        );
+    }
+}
@@ -390,7 +390,16 @@ fn reserve_replay_vec<T>(
     // count has no byte-proportional bound, so bounding it would reject valid
     // (`validate`-passing) zero-sized replays. See this fn's docs.
     if core::mem::size_of::<T>() != 0 {
-        codec::ensure_length_within_remaining(bytes, cursor, len, min_encoded_len, field)?;
+        codec::ensure_length_within_remaining(bytes, cursor, len, min_encoded_len, field).map_err(
+            |err| match err {
+                // Keep the field in the message; a replay is not a datagram.
+                codec::CodecError::Truncated { .. } => replay_decode_error(format!(
+                    "{field} length {len} exceeds the {} remaining byte(s)",
+                    bytes.len().saturating_sub(cursor)
+                )),
+                other => other,
+            },
+        )?;
     }
     vec.try_reserve_exact(len).map_err(|_err| {
         replay_decode_error(format!(
@@ -736,6 +745,13 @@ pub(crate) fn record_frame(&mut self, inputs: Vec<I>, checksum: Option<u128>) {
         self.checksums.push(checksum);
     }
 
+    /// Drops every frame from index `frames` on, for a session that went back
+    /// to an earlier frame and will record the frames from there again.
+    pub(crate) fn truncate(&mut self, frames: usize) {
+        self.frames.truncate(frames);
+        self.checksums.truncate(frames);
+    }
+
     /// Records a skipped frame with default placeholder inputs and no checksum.
     ///
     /// This maintains frame index alignment in the replay when the real
@@ -192,14 +192,32 @@ pub fn try_encode(buf: impl AsRef<[u8]>) -> RleResult<Vec<u8>> {
 
 /// Encode a bitfield starting at a specific offset.
 fn try_encode_with_offset(buf: &[u8], offset: usize) -> RleResult<Vec<u8>> {
-    let encoded_len = encode_len_with_offset(buf, offset);
     let mut enc = Vec::new();
+    try_encode_with_offset_into(buf, offset, &mut enc)?;
+    Ok(enc)
+}
+
+/// Encode a bitfield, appending the encoded bytes to `enc`.
+///
+/// Reuses `enc`'s spare capacity, so a caller that keeps the buffer between
+/// calls stops allocating once it has grown to the largest encoding.
+///
+/// # Errors
+///
+/// Returns [`InvalidRequestKind::AllocationFailed`](crate::InvalidRequestKind::AllocationFailed)
+/// if `enc` cannot grow to hold the encoding.
+pub(crate) fn try_encode_into(buf: &[u8], enc: &mut Vec<u8>) -> RleResult<()> {
+    try_encode_with_offset_into(buf, 0, enc)
+}
+
+fn try_encode_with_offset_into(buf: &[u8], offset: usize, enc: &mut Vec<u8>) -> RleResult<()> {
+    let encoded_len = encode_len_with_offset(buf, offset);
     enc.try_reserve_exact(encoded_len)
-        .map_err(|_err| allocation_failed("rle.encode", encoded_len))?;
+        .map_err(|_err| allocation_failed("rle.encode", enc.len().saturating_add(encoded_len)))?;
 
     let slice = match buf.get(offset..) {
         Some(s) => s,
-        None => return Ok(enc), // Invalid offset, return empty
+        None => return Ok(()), // Invalid offset, append nothing
     };
 
     let mut cursor = 0;
@@ -213,7 +231,7 @@ fn try_encode_with_offset(buf: &[u8], offset: usize) -> RleResult<Vec<u8>> {
             while slice.get(cursor) == Some(&byte) {
                 cursor += 1;
             }
-            write_contiguous(&mut enc, (cursor - start) as u64, byte);
+            write_contiguous(enc, (cursor - start) as u64, byte);
         } else {
             while let Some(&b) = slice.get(cursor) {
                 if b == 0 || b == 255 {
@@ -223,11 +241,11 @@ fn try_encode_with_offset(buf: &[u8], offset: usize) -> RleResult<Vec<u8>> {
             }
             // start <= cursor <= slice.len() by construction, so this range is
             // always valid; `unwrap_or_default()` is an unreachable safety net.
-            write_noncontiguous_slice(&mut enc, slice.get(start..cursor).unwrap_or_default());
+            write_noncontiguous_slice(enc, slice.get(start..cursor).unwrap_or_default());
         }
     }
 
-    Ok(enc)
+    Ok(())
 }
 
 /// Write a contiguous (compressed) sequence to the output.
@@ -414,20 +432,44 @@ fn checked_run_len(next: u64, max_decoded_len: usize) -> RleResult<usize> {
 }
 
 /// Decode an RLE-encoded bitfield starting at a specific offset.
-fn decode_with_offset(buf: &[u8], mut offset: usize, max_decoded_len: usize) -> RleResult<Vec<u8>> {
-    let decoded_len = decode_len_with_offset(buf, offset, max_decoded_len)?;
+fn decode_with_offset(buf: &[u8], offset: usize, max_decoded_len: usize) -> RleResult<Vec<u8>> {
     let mut bitfield = Vec::new();
-    bitfield.try_reserve_exact(decoded_len).map_err(|_err| {
-        FortressError::InternalErrorStructured {
+    decode_with_offset_into(buf, offset, max_decoded_len, &mut bitfield)?;
+    Ok(bitfield)
+}
+
+/// Decode an RLE-encoded bitfield, appending the decoded bytes to `out`.
+///
+/// The allocation counterpart of [`try_encode_into`]: `out`'s spare capacity
+/// is reused, and `max_decoded_len` caps the bytes appended by this call.
+///
+/// # Errors
+///
+/// Returns the same structured RLE errors as [`decode_with_max_len`]. On error
+/// `out` may hold a partially decoded tail past its original length.
+pub(crate) fn decode_into(buf: &[u8], max_decoded_len: usize, out: &mut Vec<u8>) -> RleResult<()> {
+    decode_with_offset_into(buf, 0, max_decoded_len, out)
+}
+
+fn decode_with_offset_into(
+    buf: &[u8],
+    mut offset: usize,
+    max_decoded_len: usize,
+    out: &mut Vec<u8>,
+) -> RleResult<()> {
+    let decoded_len = decode_len_with_offset(buf, offset, max_decoded_len)?;
+    out.try_reserve_exact(decoded_len)
+        .map_err(|_err| FortressError::InternalErrorStructured {
             kind: InternalErrorKind::RleDecodeError {
                 reason: RleDecodeReason::AllocationFailed {
                     requested_len: decoded_len,
                 },
             },
-        }
-    })?;
+        })?;
+    let start = out.len();
     // alloc-bound: exact decoded_len was reserved fallibly above; resize only initializes that capacity
-    bitfield.resize(decoded_len, 0);
+    out.resize(start.saturating_add(decoded_len), 0);
+    let bitfield = out.get_mut(start..).unwrap_or_default();
     let mut ptr = 0;
 
     while offset < buf.len() {
@@ -466,7 +508,7 @@ fn decode_with_offset(buf: &[u8], mut offset: usize, max_decoded_len: usize) ->
         ptr = next_ptr;
     }
 
-    Ok(bitfield)
+    Ok(())
 }
 
 /// Returns the decoded length for an RLE-encoded bitfield.
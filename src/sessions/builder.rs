@@ -1,18 +1,25 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
-use web_time::Duration;
+use web_time::{Duration, Instant};
 
 use crate::{
-    error::InvalidRequestKind,
-    network::protocol::UdpProtocol,
+    error::{InvalidFrameReason, InvalidRequestKind},
+    network::protocol::{PreEstablished, UdpProtocol},
     replay::Replay,
-    sessions::player_registry::PlayerRegistry,
+    report_violation_to,
+    sessions::confirmation_latency::MAX_CONFIRMATION_LATENCY_CAPACITY,
+    sessions::frame_timeline::MAX_FRAME_TIMELINE_CAPACITY,
+    sessions::match_pause::DEFAULT_MATCH_PAUSE_MARGIN,
+    sessions::player_registry::{EndpointMap, PlayerRegistry},
+    sessions::player_roster::{PlayerId, PlayerRoster},
     sessions::replay_session::ReplaySession,
-    telemetry::{SessionTelemetry, ViolationObserver},
-    time_sync::TimeSyncConfig,
-    Config, DesyncDetection, FortressError, NonBlockingSocket, P2PSession, PlayerHandle,
-    PlayerType, SpectatorSession, SyncTestSession,
+    sessions::session_preset::{PresetSettings, SessionPreset},
+    sessions::trace_recorder::MAX_TRACE_RECORDER_CAPACITY,
+    telemetry::{SessionTelemetry, ViolationKind, ViolationObserver, ViolationSeverity},
+    time_sync::{duration_to_frames_ceil, TimeSyncConfig},
+    Config, DesyncDetection, FortressError, Frame, NonBlockingSocket, P2PSession, P2PSessionCore,
+    PlayerHandle, PlayerType, SpectatorSession, StateDiffCodec, SyncTestSession,
 };
 
 // Re-export config types for backwards compatibility with code that imports from builder
@@ -47,6 +54,25 @@
 /// # Formal Specification Alignment
 /// - **formal-spec.md**: `DEFAULT_FPS = 60`
 const DEFAULT_FPS: usize = 60;
+/// Highest frame rate [`SessionBuilder::with_fps`] accepts.
+///
+/// Above this, a frame is shorter than a millisecond and every millisecond-based
+/// timing config collapses to a fraction of a frame.
+pub const MAX_FPS: usize = 1000;
+/// Highest (and default) frame limit [`SessionBuilder::with_max_session_frames`]
+/// accepts.
+///
+/// Leaves 2^20 frames below `i32::MAX` for the frame arithmetic that runs ahead
+/// of the current frame (input delay, prediction, match pause proposals). That
+/// is about 414 days at 60 fps and 103 days at 240 fps.
+pub const MAX_SESSION_FRAMES: Frame = Frame::new(i32::MAX - (1 << 20));
+/// `disconnect_notify_start` must span at least this many frames at the
+/// configured fps, or a single late packet raises `NetworkInterrupted`.
+const MIN_DISCONNECT_NOTIFY_FRAMES: u128 = 3;
+/// A `running_retry_interval` longer than this many frames at the configured
+/// fps is reported: one lost input packet then stalls the peer for twice the
+/// default prediction window before the retransmit.
+const MAX_RUNNING_RETRY_FRAMES: u128 = 16;
 /// Default maximum prediction window in frames.
 ///
 /// # Formal Specification Alignment
@@ -76,6 +102,10 @@
 ///
 /// After setting all appropriate values, use `SessionBuilder::start_yxz_session(...)`
 /// to consume the builder and create a Session of desired type.
+///
+/// To start many identical P2P sessions, keep one builder as a template and call
+/// [`build_p2p_session`](Self::build_p2p_session) or
+/// [`build_with_players`](Self::build_with_players), which leave it in place.
 #[must_use = "SessionBuilder must be consumed by calling a start_*_session method"]
 pub struct SessionBuilder<T>
 where
@@ -93,10 +123,24 @@ pub struct SessionBuilder<T>
     /// The time until the client will get a notification that a remote player is about to be disconnected.
     disconnect_notify_start: Duration,
     player_reg: PlayerRegistry<T>,
+    /// Stable ids registered via [`add_player_with_id`](Self::add_player_with_id).
+    roster: PlayerRoster,
     input_delay: usize,
+    /// Whether a local input delay may reach the prediction window; see
+    /// [`with_input_delay_beyond_prediction`](Self::with_input_delay_beyond_prediction).
+    input_delay_beyond_prediction: bool,
     check_dist: usize,
+    /// Seed and maximum depth of randomized sync-test rollbacks; see
+    /// [`with_randomized_rollbacks`](Self::with_randomized_rollbacks).
+    randomized_rollbacks: Option<(u64, usize)>,
+    /// Whether sync-test verification passes alternate with perturbed ones;
+    /// see [`with_perturbed_resimulation`](Self::with_perturbed_resimulation).
+    perturbed_resimulation: bool,
     max_frames_behind: usize,
     catchup_speed: usize,
+    /// Token a spectator proves to its host; see
+    /// [`with_spectator_token`](Self::with_spectator_token).
+    spectator_token: Option<[u8; 16]>,
     /// Optional observer for specification violations.
     violation_observer: Option<Arc<dyn ViolationObserver>>,
     /// Configuration for the synchronization protocol.
@@ -109,10 +153,14 @@ pub struct SessionBuilder<T>
     time_sync_config: TimeSyncConfig,
     /// Configuration for input queue sizing.
     input_queue_config: InputQueueConfig,
+    /// The preset last applied via [`preset`](Self::preset), if any.
+    preset: Option<SessionPreset>,
     /// Hard event-queue bound; queued routine events are evicted first at capacity.
     event_queue_size: usize,
     /// Whether to enable replay recording during P2P sessions.
     recording: bool,
+    /// Whether P2P sessions reserve their per-frame buffers up front.
+    preallocated_buffers: bool,
     /// Optional telemetry observer for session performance events.
     telemetry: Option<Arc<dyn SessionTelemetry>>,
     /// Controls how a [`P2PSession`] reacts when a remote peer's
@@ -120,6 +168,69 @@ pub struct SessionBuilder<T>
     /// Defaults to [`DisconnectBehavior::Halt`] for back-compat with legacy
     /// GGRS-style behavior.
     disconnect_behavior: DisconnectBehavior,
+    /// Cap on resimulation `AdvanceFrame` requests per [`P2PSession::advance_frame`]
+    /// call; `None` (the default) is unlimited.
+    max_resimulation_per_advance: Option<usize>,
+    /// Whether [`AdvanceFrame`](crate::FortressRequest::AdvanceFrame) requests carry the previous
+    /// frame's inputs; see
+    /// [`with_previous_inputs_in_requests`](Self::with_previous_inputs_in_requests).
+    previous_inputs_in_requests: bool,
+    /// Frames between replay `SaveGameState` requests; see
+    /// [`with_replay_save_interval`](Self::with_replay_save_interval).
+    replay_save_interval: usize,
+    /// Frames past the current frame at which [`P2PSession::request_match_pause`]
+    /// proposes the pause.
+    match_pause_margin: usize,
+    /// The session's final frame; see
+    /// [`with_max_session_frames`](Self::with_max_session_frames).
+    max_session_frames: Frame,
+    /// Rejoin state to verify against the remote peers; see
+    /// [`with_expected_state_checksum`](Self::with_expected_state_checksum).
+    expected_state_checksum: Option<(Frame, u128)>,
+    /// Threshold of the advance-stall watchdog; see
+    /// [`with_advance_stall_warning`](Self::with_advance_stall_warning).
+    advance_stall_warning: Option<Duration>,
+    /// Capacity of the per-frame flag timeline; see
+    /// [`with_frame_timeline`](Self::with_frame_timeline).
+    frame_timeline: Option<usize>,
+    /// Capacity of the trace recorder's ring; see
+    /// [`with_trace_recorder`](Self::with_trace_recorder).
+    trace_recorder: Option<usize>,
+    /// Capacity of the confirmation latency ring; see
+    /// [`with_confirmation_latency`](Self::with_confirmation_latency).
+    confirmation_latency: Option<usize>,
+    /// Whether P2P sessions report the players each rollback changed; see
+    /// [`with_rollback_context`](Self::with_rollback_context).
+    rollback_context: bool,
+    /// Deepest rollback P2P sessions perform, `None` for the prediction
+    /// window; see [`with_max_rollback_depth`](Self::with_max_rollback_depth).
+    max_rollback_depth: Option<usize>,
+    /// Codec for diff-encoded saved states; see
+    /// [`with_state_diff_codec`](Self::with_state_diff_codec).
+    state_diff_codec: Option<Arc<dyn StateDiffCodec<T::State>>>,
+    /// Size of one saved game state for memory reports; see
+    /// [`with_state_size_hint`](Self::with_state_size_hint).
+    state_size_hint: Option<usize>,
+    /// Whether `P2PSession::advance_frame` honors wait recommendations itself;
+    /// see [`with_auto_frame_skip`](Self::with_auto_frame_skip).
+    auto_frame_skip: bool,
+    /// Addresses of observer peers; see [`add_observer`](Self::add_observer).
+    observers: BTreeSet<T::Address>,
+    /// Remote players reached through another peer; see
+    /// [`add_player_via_relay`](Self::add_player_via_relay).
+    relayed_players: BTreeSet<PlayerHandle>,
+    /// Out-of-band connection IDs per peer address; see
+    /// [`with_pre_established`](Self::with_pre_established).
+    pre_established: BTreeMap<T::Address, PreEstablished>,
+    /// Connection ID every endpoint sends with; see
+    /// [`with_protocol_identity`](Self::with_protocol_identity).
+    protocol_identity: Option<u32>,
+    /// Whether a restarted peer restarts its disconnected endpoint's
+    /// handshake; see [`with_peer_restart`](Self::with_peer_restart).
+    peer_restart: bool,
+    /// Whether a peer's packets are accepted from a new source address; see
+    /// [`with_address_migration`](Self::with_address_migration).
+    address_migration: bool,
     /// Fixed record capacity for the unstable handshake refinement recorder.
     #[cfg(feature = "trace-validation")]
     handshake_trace_capacity: Option<usize>,
@@ -162,20 +273,48 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             disconnect_timeout,
             disconnect_notify_start,
             player_reg,
+            roster,
             input_delay,
+            input_delay_beyond_prediction,
             check_dist,
+            randomized_rollbacks,
+            perturbed_resimulation,
             max_frames_behind,
             catchup_speed,
+            spectator_token,
             violation_observer,
             sync_config,
             protocol_config,
             spectator_config,
             time_sync_config,
             input_queue_config,
+            preset,
             event_queue_size,
             recording,
+            preallocated_buffers,
             telemetry,
             disconnect_behavior,
+            max_resimulation_per_advance,
+            previous_inputs_in_requests,
+            replay_save_interval,
+            match_pause_margin,
+            max_session_frames,
+            expected_state_checksum,
+            advance_stall_warning,
+            frame_timeline,
+            trace_recorder,
+            confirmation_latency,
+            rollback_context,
+            max_rollback_depth,
+            state_diff_codec,
+            state_size_hint,
+            auto_frame_skip,
+            observers,
+            relayed_players,
+            pre_established,
+            protocol_identity,
+            peer_restart,
+            address_migration,
             #[cfg(feature = "trace-validation")]
             handshake_trace_capacity,
             #[cfg(feature = "hot-join")]
@@ -201,10 +340,18 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             .field("disconnect_timeout", disconnect_timeout)
             .field("disconnect_notify_start", disconnect_notify_start)
             .field("player_reg", player_reg)
+            .field("roster", roster)
             .field("input_delay", input_delay)
+            .field(
+                "input_delay_beyond_prediction",
+                input_delay_beyond_prediction,
+            )
             .field("check_dist", check_dist)
+            .field("randomized_rollbacks", randomized_rollbacks)
+            .field("perturbed_resimulation", perturbed_resimulation)
             .field("max_frames_behind", max_frames_behind)
             .field("catchup_speed", catchup_speed)
+            .field("has_spectator_token", &spectator_token.is_some())
             .field("has_violation_observer", &violation_observer.is_some())
             .field("has_telemetry", &telemetry.is_some())
             .field("sync_config", sync_config)
@@ -212,9 +359,32 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             .field("spectator_config", spectator_config)
             .field("time_sync_config", time_sync_config)
             .field("input_queue_config", input_queue_config)
+            .field("preset", preset)
             .field("event_queue_size", event_queue_size)
             .field("recording", recording)
-            .field("disconnect_behavior", disconnect_behavior);
+            .field("preallocated_buffers", preallocated_buffers)
+            .field("disconnect_behavior", disconnect_behavior)
+            .field("max_resimulation_per_advance", max_resimulation_per_advance)
+            .field("previous_inputs_in_requests", previous_inputs_in_requests)
+            .field("replay_save_interval", replay_save_interval)
+            .field("match_pause_margin", match_pause_margin)
+            .field("max_session_frames", max_session_frames)
+            .field("expected_state_checksum", expected_state_checksum)
+            .field("advance_stall_warning", advance_stall_warning)
+            .field("frame_timeline", frame_timeline)
+            .field("trace_recorder", trace_recorder)
+            .field("confirmation_latency", confirmation_latency)
+            .field("rollback_context", rollback_context)
+            .field("max_rollback_depth", max_rollback_depth)
+            .field("has_state_diff_codec", &state_diff_codec.is_some())
+            .field("state_size_hint", state_size_hint)
+            .field("auto_frame_skip", auto_frame_skip)
+            .field("observers", observers)
+            .field("relayed_players", relayed_players)
+            .field("pre_established", pre_established)
+            .field("protocol_identity", protocol_identity)
+            .field("peer_restart", peer_restart)
+            .field("address_migration", address_migration);
         #[cfg(feature = "trace-validation")]
         debug.field("handshake_trace_capacity", handshake_trace_capacity);
         #[cfg(feature = "hot-join")]
@@ -231,6 +401,149 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
+/// A clone holds the same players and settings. Endpoints only exist inside
+/// started sessions, so cloning a builder never shares network state.
+impl<T: Config> Clone for SessionBuilder<T> {
+    fn clone(&self) -> Self {
+        // Destructure to ensure all fields are included when new fields are added.
+        let Self {
+            num_players,
+            local_players,
+            max_prediction,
+            fps,
+            save_mode,
+            desync_detection,
+            disconnect_timeout,
+            disconnect_notify_start,
+            player_reg,
+            roster,
+            input_delay,
+            input_delay_beyond_prediction,
+            check_dist,
+            randomized_rollbacks,
+            perturbed_resimulation,
+            max_frames_behind,
+            catchup_speed,
+            spectator_token,
+            violation_observer,
+            sync_config,
+            protocol_config,
+            spectator_config,
+            time_sync_config,
+            input_queue_config,
+            preset,
+            event_queue_size,
+            recording,
+            preallocated_buffers,
+            telemetry,
+            disconnect_behavior,
+            max_resimulation_per_advance,
+            previous_inputs_in_requests,
+            replay_save_interval,
+            match_pause_margin,
+            max_session_frames,
+            expected_state_checksum,
+            advance_stall_warning,
+            frame_timeline,
+            trace_recorder,
+            confirmation_latency,
+            rollback_context,
+            max_rollback_depth,
+            state_diff_codec,
+            state_size_hint,
+            auto_frame_skip,
+            observers,
+            relayed_players,
+            pre_established,
+            protocol_identity,
+            peer_restart,
+            address_migration,
+            #[cfg(feature = "trace-validation")]
+            handshake_trace_capacity,
+            #[cfg(feature = "hot-join")]
+            accept_hot_join,
+            #[cfg(feature = "hot-join")]
+            reserved_slots,
+            #[cfg(feature = "hot-join")]
+            hot_join_serve_timeout_polls,
+            #[cfg(feature = "hot-join")]
+            hot_join_max_snapshot_wire_bytes,
+            #[cfg(feature = "hot-join")]
+            hot_join_ack_resends,
+        } = self;
+
+        Self {
+            num_players: *num_players,
+            local_players: *local_players,
+            max_prediction: *max_prediction,
+            fps: *fps,
+            save_mode: *save_mode,
+            desync_detection: *desync_detection,
+            disconnect_timeout: *disconnect_timeout,
+            disconnect_notify_start: *disconnect_notify_start,
+            player_reg: PlayerRegistry {
+                handles: player_reg.handles.clone(),
+                remotes: EndpointMap::new(),
+                spectators: EndpointMap::new(),
+            },
+            roster: roster.clone(),
+            input_delay: *input_delay,
+            input_delay_beyond_prediction: *input_delay_beyond_prediction,
+            check_dist: *check_dist,
+            randomized_rollbacks: *randomized_rollbacks,
+            perturbed_resimulation: *perturbed_resimulation,
+            max_frames_behind: *max_frames_behind,
+            catchup_speed: *catchup_speed,
+            spectator_token: *spectator_token,
+            violation_observer: violation_observer.clone(),
+            sync_config: *sync_config,
+            protocol_config: protocol_config.clone(),
+            spectator_config: *spectator_config,
+            time_sync_config: *time_sync_config,
+            input_queue_config: *input_queue_config,
+            preset: *preset,
+            event_queue_size: *event_queue_size,
+            recording: *recording,
+            preallocated_buffers: *preallocated_buffers,
+            telemetry: telemetry.clone(),
+            disconnect_behavior: *disconnect_behavior,
+            max_resimulation_per_advance: *max_resimulation_per_advance,
+            previous_inputs_in_requests: *previous_inputs_in_requests,
+            replay_save_interval: *replay_save_interval,
+            match_pause_margin: *match_pause_margin,
+            max_session_frames: *max_session_frames,
+            expected_state_checksum: *expected_state_checksum,
+            advance_stall_warning: *advance_stall_warning,
+            frame_timeline: *frame_timeline,
+            trace_recorder: *trace_recorder,
+            confirmation_latency: *confirmation_latency,
+            rollback_context: *rollback_context,
+            max_rollback_depth: *max_rollback_depth,
+            state_diff_codec: state_diff_codec.clone(),
+            state_size_hint: *state_size_hint,
+            auto_frame_skip: *auto_frame_skip,
+            observers: observers.clone(),
+            relayed_players: relayed_players.clone(),
+            pre_established: pre_established.clone(),
+            protocol_identity: *protocol_identity,
+            peer_restart: *peer_restart,
+            address_migration: *address_migration,
+            #[cfg(feature = "trace-validation")]
+            handshake_trace_capacity: *handshake_trace_capacity,
+            #[cfg(feature = "hot-join")]
+            accept_hot_join: *accept_hot_join,
+            #[cfg(feature = "hot-join")]
+            reserved_slots: reserved_slots.clone(),
+            #[cfg(feature = "hot-join")]
+            hot_join_serve_timeout_polls: *hot_join_serve_timeout_polls,
+            #[cfg(feature = "hot-join")]
+            hot_join_max_snapshot_wire_bytes: *hot_join_max_snapshot_wire_bytes,
+            #[cfg(feature = "hot-join")]
+            hot_join_ack_resends: *hot_join_ack_resends,
+        }
+    }
+}
+
 impl<T: Config> Default for SessionBuilder<T> {
     fn default() -> Self {
         Self::new()
@@ -242,6 +555,7 @@ impl<T: Config> SessionBuilder<T> {
     pub fn new() -> Self {
         Self {
             player_reg: PlayerRegistry::new(),
+            roster: PlayerRoster::new(),
             local_players: 0,
             num_players: DEFAULT_PLAYERS,
             max_prediction: DEFAULT_MAX_PREDICTION_FRAMES,
@@ -251,19 +565,46 @@ pub fn new() -> Self {
             disconnect_timeout: DEFAULT_DISCONNECT_TIMEOUT,
             disconnect_notify_start: DEFAULT_DISCONNECT_NOTIFY_START,
             input_delay: DEFAULT_INPUT_DELAY,
+            input_delay_beyond_prediction: false,
             check_dist: DEFAULT_CHECK_DISTANCE,
+            randomized_rollbacks: None,
+            perturbed_resimulation: false,
             max_frames_behind: DEFAULT_MAX_FRAMES_BEHIND,
             catchup_speed: DEFAULT_CATCHUP_SPEED,
+            spectator_token: None,
             violation_observer: None,
             sync_config: SyncConfig::default(),
             protocol_config: ProtocolConfig::default(),
             spectator_config: SpectatorConfig::default(),
             time_sync_config: TimeSyncConfig::default(),
             input_queue_config: InputQueueConfig::default(),
+            preset: None,
             event_queue_size: DEFAULT_EVENT_QUEUE_SIZE,
             recording: false,
+            preallocated_buffers: false,
             telemetry: None,
             disconnect_behavior: DisconnectBehavior::default(),
+            max_resimulation_per_advance: None,
+            previous_inputs_in_requests: false,
+            replay_save_interval: 0,
+            match_pause_margin: DEFAULT_MATCH_PAUSE_MARGIN,
+            max_session_frames: MAX_SESSION_FRAMES,
+            expected_state_checksum: None,
+            advance_stall_warning: None,
+            frame_timeline: None,
+            trace_recorder: None,
+            confirmation_latency: None,
+            rollback_context: false,
+            max_rollback_depth: None,
+            state_diff_codec: None,
+            state_size_hint: None,
+            auto_frame_skip: false,
+            observers: BTreeSet::new(),
+            relayed_players: BTreeSet::new(),
+            pre_established: BTreeMap::new(),
+            protocol_identity: None,
+            peer_restart: false,
+            address_migration: false,
             #[cfg(feature = "trace-validation")]
             handshake_trace_capacity: None,
             #[cfg(feature = "hot-join")]
@@ -363,6 +704,47 @@ pub fn add_player(
         Ok(self)
     }
 
+    /// Like [`Self::add_player`], but also records a stable, application-provided
+    /// `id` for the handle in the session's [`PlayerRoster`].
+    ///
+    /// The id stays attached to the handle for the whole session; read it back
+    /// through [`P2PSession::roster`]. Only P2P sessions expose the roster.
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] for every reason [`Self::add_player`] does
+    /// - Returns [`InvalidRequestKind::PlayerIdInUse`] if another handle already has `id`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// let remote: SocketAddr = "127.0.0.1:7001".parse().unwrap();
+    /// let builder = SessionBuilder::<TestConfig>::new()
+    ///     .add_player_with_id(PlayerType::Local, PlayerHandle::new(0), 1001_u64)?
+    ///     .add_player_with_id(PlayerType::Remote(remote), PlayerHandle::new(1), "rival")?;
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    pub fn add_player_with_id(
+        self,
+        player_type: PlayerType<T::Address>,
+        player_handle: PlayerHandle,
+        id: impl Into<PlayerId>,
+    ) -> Result<Self, FortressError> {
+        let id = id.into();
+        self.roster.check_insert(player_handle, &id)?;
+        let mut builder = self.add_player(player_type, player_handle)?;
+        builder.roster.insert(player_handle, id)?;
+        Ok(builder)
+    }
+
     /// Adds a local player at the specified handle index.
     ///
     /// This is a convenience wrapper around [`Self::add_player`] with [`PlayerType::Local`].
@@ -431,6 +813,266 @@ pub fn add_remote_player(self, handle: usize, addr: T::Address) -> Result<Self,
         self.add_player(PlayerType::Remote(addr), PlayerHandle::new(handle))
     }
 
+    /// Adds an observer peer: a remote node that takes part in the session
+    /// without owning a player slot.
+    ///
+    /// The observer is a [`P2PSession`] built with every player registered as
+    /// [`PlayerType::Remote`] and no [`PlayerType::Local`] player, typically a
+    /// headless recorder or relay. Unlike a spectator it runs the full peer
+    /// protocol: it acks this session's inputs, gossips connection status and,
+    /// with [`DesyncDetection::On`], exchanges checksums. It sends no inputs
+    /// and never delays confirmation, so `num_players` and every player's
+    /// input layout are the same whether observers are present or not.
+    ///
+    /// An observer is only known to the sessions that add it; a session
+    /// without the observer simply ignores its packets.
+    /// [`start_p2p_session`](Self::start_p2p_session) rejects an observer at
+    /// the address of a registered player or spectator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// let remote: SocketAddr = "127.0.0.1:7000".parse()?;
+    /// let observer: SocketAddr = "127.0.0.1:7002".parse()?;
+    /// let builder = SessionBuilder::<TestConfig>::new()
+    ///     .with_num_players(2)?
+    ///     .add_local_player(0)?
+    ///     .add_remote_player(1, remote)?
+    ///     .add_observer(observer);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_observer(mut self, addr: T::Address) -> Self {
+        self.observers.insert(addr);
+        self
+    }
+
+    /// Adds a remote player whose inputs arrive from, and whose packets are
+    /// sent to, another peer's address instead of the player's own, for a
+    /// star topology where two peers cannot reach each other directly.
+    ///
+    /// The relaying peer must be added as a remote player at `relay_addr` as
+    /// well, and must call
+    /// [`P2PSession::enable_relay_for`](crate::P2PSession::enable_relay_for)
+    /// with the two players it connects. Events for the relayed player carry
+    /// `relay_addr`, and its [`network_stats`](crate::P2PSession::network_stats)
+    /// describe the link to the relay, which is also what times it out. Desync
+    /// detection needs nothing extra: the relay compares checksums with both
+    /// players, so a mismatch between them shows as a mismatch with the relay.
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] under the same conditions as
+    ///   [`add_player`](Self::add_player).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// // player 2 cannot be reached directly, so player 1 relays its inputs
+    /// let relay: SocketAddr = "127.0.0.1:7001".parse()?;
+    /// let builder = SessionBuilder::<TestConfig>::new()
+    ///     .with_num_players(3)?
+    ///     .add_local_player(0)?
+    ///     .add_remote_player(1, relay)?
+    ///     .add_player_via_relay(PlayerHandle::new(2), relay)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_player_via_relay(
+        mut self,
+        player_handle: PlayerHandle,
+        relay_addr: T::Address,
+    ) -> Result<Self, FortressError> {
+        self = self.add_player(PlayerType::Remote(relay_addr), player_handle)?;
+        self.relayed_players.insert(player_handle);
+        Ok(self)
+    }
+
+    /// Shortens the sync handshake with the peer at `addr` to a single
+    /// roundtrip, for links the application has already verified (its own
+    /// hole punching and ping exchange, for example).
+    ///
+    /// Both peers are given each other's connection IDs out of band: this
+    /// session sends with `local_conn_id` and expects the peer to send with
+    /// `remote_conn_id`, and the peer is configured the other way round. One
+    /// `SyncRequest`/`SyncReply` roundtrip still validates the channel, starts
+    /// the timing statistics, and seeds the RTT. A reply carrying any other
+    /// connection ID falls back to the full
+    /// [`SyncConfig::num_sync_packets`] handshake instead of failing.
+    ///
+    /// Applies to P2P sessions; the address must belong to a remote player,
+    /// spectator, or observer by the time the session starts. Calling this
+    /// again for the same address replaces its IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::InvalidConnectionId`] if either ID is `0`
+    /// or has its low 16 bits all zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// // IDs handed out by the matchmaker; the peer swaps them.
+    /// let remote: SocketAddr = "127.0.0.1:7000".parse()?;
+    /// let builder = SessionBuilder::<TestConfig>::new()
+    ///     .add_local_player(0)?
+    ///     .add_remote_player(1, remote)?
+    ///     .with_pre_established(remote, 0x1234_5678, 0x8765_4321)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_pre_established(
+        mut self,
+        addr: T::Address,
+        local_conn_id: u32,
+        remote_conn_id: u32,
+    ) -> Result<Self, FortressError> {
+        for conn_id in [local_conn_id, remote_conn_id] {
+            if !crate::network::is_valid_conn_id(conn_id) {
+                return Err(InvalidRequestKind::InvalidConnectionId { conn_id }.into());
+            }
+        }
+        self.pre_established.insert(
+            addr,
+            PreEstablished {
+                local_conn_id,
+                remote_conn_id,
+            },
+        );
+        Ok(self)
+    }
+
+    /// Pins the connection ID every endpoint of this session sends with,
+    /// instead of drawing a random one per endpoint.
+    ///
+    /// A peer filters packets by the connection ID it learned during the
+    /// handshake. When a restarted process pins the same ID it had before,
+    /// peers that still hold that ID accept its packets again and answer its
+    /// new handshake, without waiting for their disconnect timeout. A pinned
+    /// ID is never re-rolled, so two endpoints pinned to the same ID never
+    /// synchronize with each other. [`with_pre_established`](Self::with_pre_established)
+    /// takes precedence for its address, and a hot-join rearm still advances
+    /// the ID to fence off the previous era.
+    ///
+    /// Applies to P2P sessions. Read the IDs in use with
+    /// [`P2PSession::local_connection_id`] and
+    /// [`P2PSession::remote_connection_id`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::InvalidConnectionId`] if `conn_id` is `0`
+    /// or has its low 16 bits all zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// // An ID persisted across restarts of this process.
+    /// let remote: SocketAddr = "127.0.0.1:7000".parse()?;
+    /// let builder = SessionBuilder::<TestConfig>::new()
+    ///     .add_local_player(0)?
+    ///     .add_remote_player(1, remote)?
+    ///     .with_protocol_identity(0x0bad_cafe)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_protocol_identity(mut self, conn_id: u32) -> Result<Self, FortressError> {
+        if !crate::network::is_valid_conn_id(conn_id) {
+            return Err(InvalidRequestKind::InvalidConnectionId { conn_id }.into());
+        }
+        self.protocol_identity = Some(conn_id);
+        Ok(self)
+    }
+
+    /// Lets a restarted peer reconnect to its disconnected endpoint.
+    ///
+    /// Off by default: once a peer's endpoint is disconnected, packets from a
+    /// new process at the same address carry a different connection ID and
+    /// are ignored. When enabled, a `SyncRequest` from the peer's address with
+    /// a new, valid connection ID, arriving while the endpoint is disconnected
+    /// and before it shuts down (see [`ProtocolConfig::shutdown_delay`]),
+    /// rebuilds the endpoint and restarts its handshake. The session reports
+    /// the new handshake with the usual [`FortressEvent::Synchronizing`] and
+    /// [`FortressEvent::Synchronized`] events for that address.
+    ///
+    /// The player stays disconnected in the session: inputs from the new
+    /// process are ignored, and resuming play with it (a hot-join, or a new
+    /// session) is up to the application. Applies to P2P sessions.
+    ///
+    /// [`FortressEvent::Synchronizing`]: crate::FortressEvent::Synchronizing
+    /// [`FortressEvent::Synchronized`]: crate::FortressEvent::Synchronized
+    pub fn with_peer_restart(mut self, enabled: bool) -> Self {
+        self.peer_restart = enabled;
+        self
+    }
+
+    /// Lets a peer keep its connection when its source address changes, such
+    /// as a phone moving from WiFi to cellular or a NAT rebinding its port.
+    ///
+    /// Off by default: an address is the peer's identity, and packets from
+    /// any other address are ignored as coming from an unknown source. When
+    /// enabled, a packet from an unknown address carrying the connection ID a
+    /// running endpoint learned during synchronization moves that endpoint to
+    /// the new address: the session keeps its inputs, acks, and statistics,
+    /// replies to the new address from then on, and reports the player under
+    /// it in [`P2PSession::player_type`] and later events. Each move is
+    /// counted in [`SessionMetrics::peer_address_migrations`]. The peer that
+    /// moved switches sockets with [`P2PSession::replace_socket`]; it needs
+    /// this option only if its own peers may move too.
+    ///
+    /// # Security
+    ///
+    /// The connection ID is sent in the clear and is not a secret, so this
+    /// trades away source-address filtering: anyone who can observe a
+    /// packet between the peers can redirect the session's traffic for that
+    /// peer to an address of their choosing, cutting the real peer off until
+    /// its next packet moves the endpoint back. Without this option an
+    /// attacker must also spoof the peer's source address. Enable it only
+    /// where that is acceptable, or where the socket itself authenticates
+    /// packets (an encrypted tunnel, for example) before the session sees them.
+    ///
+    /// Endpoints do not move while a coordinated drop or a hot-join is in
+    /// progress; the packet is ignored and the peer's next one is matched
+    /// once it finishes. Applies to P2P sessions.
+    ///
+    /// [`P2PSession::player_type`]: crate::P2PSession::player_type
+    /// [`P2PSession::replace_socket`]: crate::P2PSession::replace_socket
+    /// [`SessionMetrics::peer_address_migrations`]: crate::SessionMetrics::peer_address_migrations
+    pub fn with_address_migration(mut self, enabled: bool) -> Self {
+        self.address_migration = enabled;
+        self
+    }
+
     /// Enables (or disables) serving hot-joins for this session (host role).
     ///
     /// When enabled, a host [`P2PSession`] responds to a hot-joiner's snapshot
@@ -552,6 +1194,29 @@ pub fn add_reserved_player(
         Ok(self)
     }
 
+    /// Like [`add_reserved_player`](Self::add_reserved_player), but also records
+    /// a stable `id` for the slot in the session's [`PlayerRoster`]. The entry
+    /// starts disconnected and is marked connected when a joiner fills the slot.
+    ///
+    /// This is feature-gated behind the `hot-join` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as
+    /// [`add_player_with_id`](Self::add_player_with_id).
+    #[cfg(feature = "hot-join")]
+    pub fn add_reserved_player_with_id(
+        self,
+        addr: T::Address,
+        handle: PlayerHandle,
+        id: impl Into<PlayerId>,
+    ) -> Result<Self, FortressError> {
+        let mut builder = self.add_player_with_id(PlayerType::Remote(addr), handle, id)?;
+        builder.reserved_slots.insert(handle);
+        builder.accept_hot_join = true;
+        Ok(builder)
+    }
+
     /// Overrides the hot-join **serve timeout**, in
     /// [`poll_remote_clients`](P2PSession::poll_remote_clients) calls.
     ///
@@ -656,11 +1321,12 @@ pub fn with_hot_join_ack_resends(mut self, resends: usize) -> Self {
 
     /// Change the maximum prediction window. Default is 8.
     ///
-    /// Session construction requires `max_prediction + input_delay <
-    /// queue_length`, so the rollback history and delayed-input lead fit in
-    /// the configured input ring. The setters remain order-independent; the
-    /// combined relationship is validated when a rollback or synctest session
-    /// is started.
+    /// Session construction requires `queue_length` of at least
+    /// [`InputQueueConfig::min_queue_length`], so the rollback history and
+    /// delayed-input lead fit in the configured input ring. The setters remain
+    /// order-independent; the combined relationship is validated when a
+    /// rollback or synctest session is started, failing with
+    /// [`InvalidRequestKind::QueueLengthBelowMinimum`].
     ///
     /// ## Lockstep mode
     ///
@@ -686,8 +1352,10 @@ pub fn with_max_prediction_window(mut self, window: usize) -> Self {
     /// Returns a [`FortressError`] if `delay` exceeds the maximum allowed value.
     /// The standalone maximum delay is `queue_length - 1` (default 127,
     /// configurable via [`with_input_queue_config`](Self::with_input_queue_config)).
-    /// Session construction also requires `max_prediction + input_delay <
-    /// queue_length`; reduce the prediction window when using a large delay.
+    /// Session construction also requires `queue_length` of at least
+    /// [`InputQueueConfig::min_queue_length`]; reduce the prediction window
+    /// when using a large delay, or size the queue with
+    /// [`InputQueueConfig::recommended`].
     ///
     /// This limit ensures the circular input buffer doesn't overflow.
     /// At 60fps with default settings, max delay is 127 frames (~2.1 seconds),
@@ -695,6 +1363,12 @@ pub fn with_max_prediction_window(mut self, window: usize) -> Self {
     ///
     /// This constraint was discovered through Kani formal verification.
     ///
+    /// A P2P session with a prediction window also requires `delay` below
+    /// `max_prediction`, unless
+    /// [`with_input_delay_beyond_prediction`](Self::with_input_delay_beyond_prediction)
+    /// is set; [`start_p2p_session`](Self::start_p2p_session) fails with
+    /// [`InvalidRequestKind::InputDelayBeyondPrediction`] otherwise.
+    ///
     /// # Example
     ///
     /// ```
@@ -732,6 +1406,88 @@ pub fn with_input_delay(mut self, delay: usize) -> Result<Self, FortressError> {
         Ok(self)
     }
 
+    /// Allows a local input delay of `max_prediction` frames or more.
+    ///
+    /// By default, [`start_p2p_session`](Self::start_p2p_session) and
+    /// [`P2PSession::set_input_delay`] reject such a delay with
+    /// [`InvalidRequestKind::InputDelayBeyondPrediction`]: every local input
+    /// would be applied further ahead than any remote input is ever predicted,
+    /// which usually means the two settings were mixed up. The session handles
+    /// it correctly, though. Local inputs are queued `delay` frames ahead and
+    /// count as confirmed once queued, so they are never predicted and never
+    /// cause a rollback; only remote inputs are predicted, within the window
+    /// as usual. Set this when the large delay is intended, for example to
+    /// hide most of a long round trip with delay and keep a short window for
+    /// the rest.
+    ///
+    /// Lockstep sessions (`max_prediction == 0`) accept any delay without it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, FortressError, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_max_prediction_window(8)
+    ///     .with_input_delay(10)?
+    ///     .with_input_delay_beyond_prediction(true);
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    pub fn with_input_delay_beyond_prediction(mut self, allow: bool) -> Self {
+        self.input_delay_beyond_prediction = allow;
+        self
+    }
+
+    /// Caps how many frames a P2P session rolls back to correct a
+    /// misprediction. Defaults to the prediction window
+    /// ([`with_max_prediction_window`](Self::with_max_prediction_window)),
+    /// which is the deepest rollback possible anyway.
+    ///
+    /// A lower cap keeps speculation deep while bounding the resimulation
+    /// cost: the session still predicts up to `max_prediction` frames ahead,
+    /// but when a corrected input would require re-simulating more than
+    /// `frames` frames, it skips the rollback and emits
+    /// [`FortressEvent::RollbackTooDeep`]. The corrected inputs are then
+    /// accepted without correction, so the local state keeps the
+    /// mispredicted simulation and diverges from the peers'. Handle the event
+    /// with the game's own resynchronization, or rely on desync detection
+    /// ([`with_desync_detection_mode`](Self::with_desync_detection_mode)) to
+    /// notice the divergence.
+    ///
+    /// The cap is validated when the session starts:
+    /// [`start_p2p_session`](Self::start_p2p_session) fails with
+    /// [`InvalidRequestKind::ConfigValueOutOfRange`] unless
+    /// `1 <= frames <= max_prediction`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// // Speculate 10 frames ahead, but never re-simulate more than 6.
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_max_prediction_window(10)
+    ///     .with_max_rollback_depth(6);
+    /// ```
+    ///
+    /// [`FortressEvent::RollbackTooDeep`]: crate::FortressEvent::RollbackTooDeep
+    pub fn with_max_rollback_depth(mut self, frames: usize) -> Self {
+        self.max_rollback_depth = Some(frames);
+        self
+    }
+
     /// Change number of total players. Default is 2.
     ///
     /// # Errors
@@ -943,6 +1699,21 @@ pub fn with_spectator_config(mut self, spectator_config: SpectatorConfig) -> Sel
         self
     }
 
+    /// Proves `token` to the host of a spectator session, for a host whose
+    /// [`SpectatorConfig::access_token`] is set. A host with a different
+    /// token, or one that requires a token this spectator lacks, ignores the
+    /// handshake, so the spectator never synchronizes. Hosts without a token
+    /// accept the spectator either way.
+    ///
+    /// The token is never sent: each `SyncRequest` carries a keyed MAC of its
+    /// random nonce instead. It only affects
+    /// [`start_spectator_session`](Self::start_spectator_session) and
+    /// [`start_spectator_session_multi`](Self::start_spectator_session_multi).
+    pub fn with_spectator_token(mut self, token: [u8; 16]) -> Self {
+        self.spectator_token = Some(token);
+        self
+    }
+
     /// Sets the time synchronization configuration.
     ///
     /// This allows fine-tuning the frame advantage averaging window size,
@@ -967,6 +1738,7 @@ pub fn with_spectator_config(mut self, spectator_config: SpectatorConfig) -> Sel
     /// // Or customize the window size
     /// let custom_config = TimeSyncConfig {
     ///     window_size: 45,
+    ///     ..TimeSyncConfig::default()
     /// };
     /// let builder = SessionBuilder::<MyConfig>::new()
     ///     .with_time_sync_config(custom_config);
@@ -1011,6 +1783,7 @@ pub fn with_time_sync_config(mut self, time_sync_config: TimeSyncConfig) -> Self
     /// // Or customize the queue length
     /// let custom_config = InputQueueConfig {
     ///     queue_length: 64,
+    ///     ..InputQueueConfig::default()
     /// };
     /// let builder = SessionBuilder::<MyConfig>::new()
     ///     .with_input_queue_config(custom_config);
@@ -1065,20 +1838,589 @@ pub fn with_event_queue_size(mut self, size: usize) -> Result<Self, FortressErro
         Ok(self)
     }
 
-    /// Enables or disables replay recording during a P2P session.
+    /// Caps how many resimulation [`AdvanceFrame`] requests a single
+    /// [`P2PSession::advance_frame`] call emits during a rollback.
     ///
-    /// When recording is enabled, the [`P2PSession`] will capture all confirmed
-    /// inputs as they are processed. After the session ends, call
-    /// [`P2PSession::into_replay`] to extract the recorded [`Replay`].
+    /// A deep rollback of an expensive simulation can blow the frame budget of
+    /// the call that detects it. With a cap, that call emits only the first
+    /// `frames` resimulated frames; the rest carry over and are emitted by the
+    /// following `advance_frame` (or [`P2PSession::continue_resimulation`]) calls
+    /// before any new frame is simulated. Query the outstanding work with
+    /// [`P2PSession::resimulation_debt`]. The default is unlimited: every
+    /// rolled-back frame is resimulated in the call that detected the rollback.
     ///
-    /// Recording is disabled by default.
+    /// While resimulation is outstanding, `advance_frame` keeps the queued local
+    /// inputs and does not send them, so a cap well below the prediction window
+    /// trades a visible hitch for a few frames of extra input latency.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FortressError`] if `frames` is 0.
     ///
     /// # Example
     ///
     /// ```
-    /// # use fortress_rollback::prelude::*;
-    /// # use std::net::SocketAddr;
-    /// # #[derive(Debug)]
+    /// use fortress_rollback::{SessionBuilder, Config, FortressError};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// // Spread a deep rollback over several calls, at most 4 frames each
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_max_resimulation_per_advance(4)?;
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    ///
+    /// [`AdvanceFrame`]: crate::FortressRequest::AdvanceFrame
+    pub fn with_max_resimulation_per_advance(
+        mut self,
+        frames: usize,
+    ) -> Result<Self, FortressError> {
+        if frames == 0 {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_resimulation_per_advance",
+                min: 1,
+                max: u64::MAX,
+                actual: 0,
+            }
+            .into());
+        }
+        self.max_resimulation_per_advance = Some(frames);
+        Ok(self)
+    }
+
+    /// Makes every [`AdvanceFrame`] request carry `previous_inputs`: the inputs
+    /// the session provided for the previous frame in the same simulation pass.
+    /// Defaults to `false`, which leaves `previous_inputs` empty and skips the
+    /// extra copy of each frame's inputs.
+    ///
+    /// Recomputing "the previous frame's input" in game code (for edge
+    /// detection such as "button just pressed") goes wrong across rollbacks:
+    /// when a correction arrives for frame `N - 1`, the resimulated frame `N`
+    /// must see the corrected inputs, not the prediction the game saw the
+    /// first time. The session keeps this history for you, so each
+    /// `previous_inputs` always matches the inputs that produced the state
+    /// being advanced.
+    ///
+    /// Applies to every session type this builder starts. `previous_inputs` is
+    /// empty on the first frame and whenever the previous frame was not
+    /// simulated by the session, such as straight after a hot-join snapshot
+    /// load.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new().with_previous_inputs_in_requests(true);
+    /// ```
+    ///
+    /// [`AdvanceFrame`]: crate::FortressRequest::AdvanceFrame
+    pub fn with_previous_inputs_in_requests(mut self, enabled: bool) -> Self {
+        self.previous_inputs_in_requests = enabled;
+        self
+    }
+
+    /// Makes replay sessions request a [`SaveGameState`] before every
+    /// `interval`th frame, starting with frame 0, so a replay viewer can keep
+    /// snapshots to seek from. `0`, the default, saves only when checksum
+    /// validation asks for it.
+    ///
+    /// Validating replay sessions already save before every frame and ignore
+    /// this setting. Other session types ignore it too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new().with_replay_save_interval(60);
+    /// ```
+    ///
+    /// [`SaveGameState`]: crate::FortressRequest::SaveGameState
+    pub fn with_replay_save_interval(mut self, interval: usize) -> Self {
+        self.replay_save_interval = interval;
+        self
+    }
+
+    /// Sets how many frames ahead [`P2PSession::request_match_pause`] proposes
+    /// the pause frame. Defaults to 16.
+    ///
+    /// The margin gives the proposal time to reach every peer before the
+    /// proposed frame: a peer already past it pauses at its own current frame
+    /// instead, which moves the agreed frame later for everyone. Use at least a
+    /// round trip's worth of frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FortressError`] if `frames` is 0 or greater than 65535.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{SessionBuilder, Config, FortressError};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// // Propose pauses half a second ahead at 60 FPS
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_match_pause_margin(30)?;
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    pub fn with_match_pause_margin(mut self, frames: usize) -> Result<Self, FortressError> {
+        if frames == 0 || frames > usize::from(u16::MAX) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "match_pause_margin",
+                min: 1,
+                max: u64::from(u16::MAX),
+                actual: u64::try_from(frames).unwrap_or(u64::MAX),
+            }
+            .into());
+        }
+        self.match_pause_margin = frames;
+        Ok(self)
+    }
+
+    /// Sets the frame a session ends at. Defaults to [`MAX_SESSION_FRAMES`],
+    /// which is also the highest accepted value.
+    ///
+    /// A session simulates frames `0..frames` and never advances past `frames`.
+    /// Once it reaches `frames` with every earlier frame confirmed, it emits
+    /// [`FortressEvent::SessionFrameLimitReached`] and every later
+    /// `advance_frame` returns [`InvalidRequestKind::SessionFrameLimitReached`].
+    /// Every peer stops at the same frame with the same state, so a long-running
+    /// session ends cleanly instead of drifting towards `i32::MAX`.
+    ///
+    /// Applies to P2P, spectator and sync test sessions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FortressError`] if `frames` is not in `1..=MAX_SESSION_FRAMES`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, FortressError, Frame, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// // End a persistent-world session after 30 days at 240 FPS
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_max_session_frames(Frame::new(240 * 60 * 60 * 24 * 30))?;
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    ///
+    /// [`FortressEvent::SessionFrameLimitReached`]: crate::FortressEvent::SessionFrameLimitReached
+    pub fn with_max_session_frames(mut self, frames: Frame) -> Result<Self, FortressError> {
+        if frames.as_i32() < 1 || frames > MAX_SESSION_FRAMES {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_session_frames",
+                min: 1,
+                max: MAX_SESSION_FRAMES.as_i32() as u64,
+                actual: u64::try_from(frames.as_i32()).unwrap_or(0),
+            }
+            .into());
+        }
+        self.max_session_frames = frames;
+        Ok(self)
+    }
+
+    /// Verifies a rejoin against the remote peers before accepting local input.
+    ///
+    /// When a player rejoins a match with game state transferred out of band,
+    /// pass the frame that state describes and its checksum. Once every
+    /// endpoint has synchronized, the P2P session requests each remote
+    /// player's checksum of `frame` and compares it with `checksum`. On a
+    /// match the session starts [`Running`](crate::SessionState::Running) as
+    /// usual. On a mismatch it emits [`FortressEvent::RejoinStateMismatch`]
+    /// once and stays [`Synchronizing`](crate::SessionState::Synchronizing),
+    /// so [`P2PSession::advance_frame`] keeps returning
+    /// [`FortressError::NotSynchronized`] instead of desyncing at once.
+    ///
+    /// A peer answers once every input before `frame` is confirmed on its side
+    /// and it still holds the state at `frame`: a saved state, or a checksum
+    /// it kept for desync detection. A peer that no longer holds that state
+    /// never answers, and the session keeps waiting. Both peers need protocol
+    /// v7 or later.
+    ///
+    /// Applies to [`start_p2p_session`](Self::start_p2p_session) only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, Frame, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// // The checksum of the snapshot the rejoining player loaded.
+    /// let snapshot_checksum: u128 = 0x5eed;
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_expected_state_checksum(Frame::new(0), snapshot_checksum);
+    /// ```
+    ///
+    /// [`FortressEvent::RejoinStateMismatch`]: crate::FortressEvent::RejoinStateMismatch
+    pub fn with_expected_state_checksum(mut self, frame: Frame, checksum: u128) -> Self {
+        self.expected_state_checksum = Some((frame, checksum));
+        self
+    }
+
+    /// Warns when a running session stops advancing while it is still polled.
+    ///
+    /// A game whose simulation hangs often keeps its network thread alive, so
+    /// its peers see a connected player that never sends new inputs and soon
+    /// wait on it at the prediction window. With this set, a
+    /// [`Running`](crate::SessionState::Running) P2P session that goes
+    /// `threshold` without a call to [`P2PSession::advance_frame`] while
+    /// [`P2PSession::poll_remote_clients`] keeps being called emits
+    /// [`FortressEvent::LocalAdvanceStalled`] once, and its quality reports
+    /// tell the remote players, which emit
+    /// [`FortressEvent::RemoteAdvanceStalled`]. The next `advance_frame` ends
+    /// the stall with [`FortressEvent::LocalAdvanceResumed`] and
+    /// [`FortressEvent::RemoteAdvanceResumed`] on the peers.
+    ///
+    /// Time is read from the protocol clock (see [`ProtocolConfig::clock`]).
+    /// Off by default. Both peers need protocol v8 or later. Applies to
+    /// [`start_p2p_session`](Self::start_p2p_session) only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder};
+    /// use web_time::Duration;
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_advance_stall_warning(Duration::from_secs(2));
+    /// ```
+    ///
+    /// [`FortressEvent::LocalAdvanceStalled`]: crate::FortressEvent::LocalAdvanceStalled
+    /// [`FortressEvent::LocalAdvanceResumed`]: crate::FortressEvent::LocalAdvanceResumed
+    /// [`FortressEvent::RemoteAdvanceStalled`]: crate::FortressEvent::RemoteAdvanceStalled
+    /// [`FortressEvent::RemoteAdvanceResumed`]: crate::FortressEvent::RemoteAdvanceResumed
+    pub fn with_advance_stall_warning(mut self, threshold: Duration) -> Self {
+        self.advance_stall_warning = Some(threshold);
+        self
+    }
+
+    /// Makes [`P2PSession::advance_frame`] honor wait recommendations itself.
+    /// Defaults to `false`.
+    ///
+    /// When enabled, instead of emitting [`FortressEvent::WaitRecommendation`]
+    /// the session skips the recommended number of subsequent
+    /// `advance_frame` calls: each still polls the network, but returns an
+    /// empty request list without simulating, and
+    /// [`P2PSession::skipped_this_call`] returns `true` for it. Local input
+    /// added for a skipped call is discarded. A recommendation made while
+    /// skips are still outstanding replaces the remainder rather than adding
+    /// to it, since it reflects the current frame lead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new().with_auto_frame_skip(true);
+    /// ```
+    ///
+    /// [`FortressEvent::WaitRecommendation`]: crate::FortressEvent::WaitRecommendation
+    pub fn with_auto_frame_skip(mut self, enabled: bool) -> Self {
+        self.auto_frame_skip = enabled;
+        self
+    }
+
+    /// Records how each of the last `capacity` simulated frames was simulated,
+    /// for replay scrubbers and connection-quality timelines.
+    ///
+    /// The session keeps one [`FrameFlags`] byte per frame in a ring of
+    /// `capacity` frames (1024 frames are about 17 seconds at 60 FPS), read
+    /// with [`P2PSession::frame_timeline`]. Off by default. Applies to
+    /// [`start_p2p_session`](Self::start_p2p_session) only.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::ConfigValueOutOfRange`] if `capacity` is
+    /// 0 or above 65536.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, FortressError, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_frame_timeline(1024)?;
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    ///
+    /// [`FrameFlags`]: crate::FrameFlags
+    pub fn with_frame_timeline(mut self, capacity: usize) -> Result<Self, FortressError> {
+        if !(1..=MAX_FRAME_TIMELINE_CAPACITY).contains(&capacity) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "frame_timeline",
+                min: 1,
+                max: MAX_FRAME_TIMELINE_CAPACITY as u64,
+                actual: u64::try_from(capacity).unwrap_or(u64::MAX),
+            }
+            .into());
+        }
+        self.frame_timeline = Some(capacity);
+        Ok(self)
+    }
+
+    /// Records a timeline of the session's work for `chrome://tracing` or
+    /// Perfetto, to diagnose pacing problems.
+    ///
+    /// The session keeps the last `capacity_events` records in a preallocated
+    /// ring: how long each `advance_frame`, network poll and rollback took,
+    /// how many messages each poll received and how deep each rollback went,
+    /// and when each [`FortressEvent`] such as `Synchronized` or
+    /// `WaitRecommendation` was raised. Recording only copies a few numbers;
+    /// [`P2PSession::export_trace_json`] formats them in Chrome trace-event
+    /// format. Times are on the protocol clock. Off by default. Applies to
+    /// [`start_p2p_session`](Self::start_p2p_session) only.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::ConfigValueOutOfRange`] if
+    /// `capacity_events` is 0 or above 1048576.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, FortressError, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_trace_recorder(16_384)?;
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    ///
+    /// [`FortressEvent`]: crate::FortressEvent
+    pub fn with_trace_recorder(mut self, capacity_events: usize) -> Result<Self, FortressError> {
+        if !(1..=MAX_TRACE_RECORDER_CAPACITY).contains(&capacity_events) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "trace_recorder",
+                min: 1,
+                max: MAX_TRACE_RECORDER_CAPACITY as u64,
+                actual: u64::try_from(capacity_events).unwrap_or(u64::MAX),
+            }
+            .into());
+        }
+        self.trace_recorder = Some(capacity_events);
+        Ok(self)
+    }
+
+    /// Measures how long each local input frame takes to become confirmed,
+    /// for netcode tuning overlays.
+    ///
+    /// The session stamps each local input frame when its input enters the
+    /// session and, once every remote input for the frame has arrived, keeps
+    /// the elapsed time and frame count in a ring of the last `capacity`
+    /// measurements. Read them with
+    /// [`P2PSession::confirmation_latency_samples`] and
+    /// [`P2PSession::confirmation_latency_stats`]. Off by default. Applies to
+    /// [`start_p2p_session`](Self::start_p2p_session) only.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::ConfigValueOutOfRange`] if `capacity` is
+    /// 0 or above 65536.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, FortressError, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_confirmation_latency(600)?;
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    pub fn with_confirmation_latency(mut self, capacity: usize) -> Result<Self, FortressError> {
+        if !(1..=MAX_CONFIRMATION_LATENCY_CAPACITY).contains(&capacity) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "confirmation_latency",
+                min: 1,
+                max: MAX_CONFIRMATION_LATENCY_CAPACITY as u64,
+                actual: u64::try_from(capacity).unwrap_or(u64::MAX),
+            }
+            .into());
+        }
+        self.confirmation_latency = Some(capacity);
+        Ok(self)
+    }
+
+    /// Reports, for every rollback, which players' inputs changed in each
+    /// re-simulated frame, through [`P2PSession::current_rollback_context`].
+    /// Defaults to `false`, which skips keeping a copy of each frame's
+    /// inputs.
+    ///
+    /// A rollback always re-simulates the whole game state. Games whose
+    /// players are loosely coupled, such as racing games or large open
+    /// worlds, can use the context to re-run only the subsystems of the
+    /// players a misprediction actually affected. Applies to
+    /// [`start_p2p_session`](Self::start_p2p_session) only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new().with_rollback_context(true);
+    /// ```
+    pub fn with_rollback_context(mut self, enabled: bool) -> Self {
+        self.rollback_context = enabled;
+        self
+    }
+
+    /// Keeps saved states as keyframes and diffs encoded by `codec` instead of
+    /// one full state per saved frame.
+    ///
+    /// For games whose state is large but changes little per frame. Most
+    /// saved-state cells then hold a diff against the previously saved frame;
+    /// a full keyframe is kept every few saves and always for the oldest saved
+    /// frame, so every frame a rollback can load stays rebuildable.
+    /// [`LoadGameState`](crate::FortressRequest::LoadGameState) cells hold the
+    /// rebuilt full state, so request handling is unchanged. A save is turned
+    /// into a diff on the session's next save or load, so the saves of one
+    /// rollback are held in full until the following
+    /// [`advance_frame`](P2PSession::advance_frame).
+    ///
+    /// Applies to [`start_p2p_session`](Self::start_p2p_session) and
+    /// [`start_synctest_session`](Self::start_synctest_session); a sync test
+    /// checks the codec at every rollback depth it covers. Off by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder, StateDiffCodec};
+    ///
+    /// /// Stores the whole state; a real codec records only what changed.
+    /// struct WholeState;
+    ///
+    /// impl StateDiffCodec<Vec<u8>> for WholeState {
+    ///     fn diff(&self, _prev: &Vec<u8>, curr: &Vec<u8>) -> Vec<u8> {
+    ///         curr.clone()
+    ///     }
+    ///
+    ///     fn apply(&self, _base: &Vec<u8>, diff: &[u8]) -> Vec<u8> {
+    ///         diff.to_vec()
+    ///     }
+    /// }
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = Vec<u8>;
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .with_state_diff_codec(Box::new(WholeState));
+    /// ```
+    pub fn with_state_diff_codec(mut self, codec: Box<dyn StateDiffCodec<T::State>>) -> Self {
+        self.state_diff_codec = Some(Arc::from(codec));
+        self
+    }
+
+    /// Sets the size, in bytes, of one saved game state, heap data included.
+    ///
+    /// The session cannot see how much memory a `T::State` owns, so
+    /// [`P2PSession::memory_report`] and
+    /// [`SpectatorSession::memory_report`] multiply the number of saved
+    /// states by this hint. Without it they report only the count. The hint
+    /// is used for nothing else.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fortress_rollback::{Config, SessionBuilder};
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = Vec<u8>;
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// // Every state holds a 64 KiB buffer.
+    /// let builder = SessionBuilder::<MyConfig>::new().with_state_size_hint(64 * 1024);
+    /// ```
+    pub fn with_state_size_hint(mut self, bytes: usize) -> Self {
+        self.state_size_hint = Some(bytes);
+        self
+    }
+
+    /// Enables or disables replay recording during a P2P session.
+    ///
+    /// When recording is enabled, the [`P2PSession`] will capture all confirmed
+    /// inputs as they are processed. After the session ends, call
+    /// [`P2PSession::into_replay`] to extract the recorded [`Replay`].
+    ///
+    /// Recording is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
     /// # struct TestConfig;
     /// # impl Config for TestConfig {
     /// #     type Input = u8;
@@ -1095,13 +2437,71 @@ pub fn with_recording(mut self, enabled: bool) -> Self {
         self
     }
 
+    /// Enables or disables preallocated buffers for a P2P session.
+    ///
+    /// When enabled, [`start_p2p_session`](Self::start_p2p_session) reserves
+    /// the session's per-frame buffers and every endpoint's input buffers up
+    /// to their configured bounds, so that once synchronized, the steady-state
+    /// loop of `poll_remote_clients`, `events`, `add_local_input`, and
+    /// `advance_frame` does not allocate. The bounds come from the
+    /// [`ProtocolConfig`] (`pending_output_limit`, `input_history_multiplier`,
+    /// `max_checksum_history`) and the prediction window. A reservation failure
+    /// makes session creation fail with
+    /// [`InvalidRequestKind::AllocationFailed`].
+    ///
+    /// Saving state, deep rollbacks, the socket itself, and rarer paths such
+    /// as spectators, resyncs, and replay recording may still allocate; see
+    /// the user guide for the full list.
+    ///
+    /// Preallocation is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<TestConfig>::new()
+    ///     .with_preallocated_buffers(true);
+    /// ```
+    pub fn with_preallocated_buffers(mut self, enabled: bool) -> Self {
+        self.preallocated_buffers = enabled;
+        self
+    }
+
     /// Sets the FPS this session is used with. This influences estimations for frame synchronization between sessions.
+    ///
+    /// The millisecond-based timing configs are checked against this rate when
+    /// a networked session starts: `disconnect_notify_start` must span at least
+    /// three frames, and a `SyncConfig::running_retry_interval` longer than 16
+    /// frames is reported as a [`ViolationKind::Configuration`] warning. Very
+    /// low (12 fps board games) or high (240 fps rhythm games) rates usually
+    /// need those configs scaled to match.
+    ///
     /// # Errors
     /// - Returns a [`FortressError`] if the fps is 0
+    /// - Returns [`InvalidRequestKind::ConfigValueOutOfRange`] if the fps is above [`MAX_FPS`]
+    ///
+    /// [`ViolationKind::Configuration`]: crate::telemetry::ViolationKind::Configuration
     pub fn with_fps(mut self, fps: usize) -> Result<Self, FortressError> {
         if fps == 0 {
             return Err(InvalidRequestKind::ZeroFps.into());
         }
+        if fps > MAX_FPS {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "fps",
+                min: 1,
+                max: MAX_FPS as u64,
+                actual: u64::try_from(fps).unwrap_or(u64::MAX),
+            }
+            .into());
+        }
         self.fps = fps;
         Ok(self)
     }
@@ -1112,6 +2512,53 @@ pub fn with_check_distance(mut self, check_distance: usize) -> Self {
         self
     }
 
+    /// Makes a [`SyncTestSession`] roll back a pseudo-random depth between 1
+    /// and `max_depth` frames each frame instead of the fixed check distance.
+    ///
+    /// The depths come from a [`Pcg32`](crate::rng::Pcg32) seeded with
+    /// `seed`, so a session built with the same seed and fed the same inputs
+    /// repeats the same rollback schedule. Checksums are still compared for
+    /// every frame in the last `max_depth`, which replaces the check
+    /// distance. [`SyncTestSession::run_script`] reports the seed together
+    /// with the frame of any mismatch.
+    ///
+    /// [`start_synctest_session`](Self::start_synctest_session) rejects a
+    /// `max_depth` of 0 or one not below the prediction window.
+    pub fn with_randomized_rollbacks(mut self, seed: u64, max_depth: usize) -> Self {
+        self.randomized_rollbacks = Some((seed, max_depth));
+        self
+    }
+
+    /// Makes every other verification pass of a [`SyncTestSession`] perturb
+    /// the order of its own requests, to expose games that depend on
+    /// something besides their saved state and inputs, such as the iteration
+    /// order of a `HashMap`. Off by default.
+    ///
+    /// A perturbed pass gives the game the same states and inputs in a
+    /// different but equivalent order:
+    ///
+    /// - it loads the state it rolls back to twice in a row;
+    /// - when it resimulates two or more frames, it loads the state it just
+    ///   saved halfway through, then resimulates the rest from that copy.
+    ///
+    /// Each load hands the game a fresh copy of a saved state, so a game that
+    /// rebuilds lookup tables on load, or builds a `HashMap` or `HashSet`
+    /// while simulating a frame, gets new random hash keys and iterates them
+    /// in another order. A checksum mismatch found after a perturbed pass is
+    /// reported by [`SyncTestSession::run_script`] as
+    /// [`SyncTestMismatchKind::OrderDependenceSuspected`]. Maps copied along
+    /// with the state keep their iteration order, so this cannot find those;
+    /// iterate them in a sorted order or use a `BTreeMap`.
+    ///
+    /// [`start_synctest_session`](Self::start_synctest_session) rejects this
+    /// together with [`with_state_diff_codec`](Self::with_state_diff_codec).
+    ///
+    /// [`SyncTestMismatchKind::OrderDependenceSuspected`]: crate::SyncTestMismatchKind::OrderDependenceSuspected
+    pub fn with_perturbed_resimulation(mut self, enabled: bool) -> Self {
+        self.perturbed_resimulation = enabled;
+        self
+    }
+
     /// Sets the maximum frames behind. If the spectator is more than this amount of frames behind the received inputs,
     /// it will catch up with `catchup_speed` amount of frames per step.
     ///
@@ -1208,6 +2655,125 @@ pub fn with_telemetry(mut self, telemetry: Arc<dyn SessionTelemetry>) -> Self {
     // Session Presets
     // =========================================================================
 
+    /// Applies a whole-session [`SessionPreset`] for a common game genre.
+    ///
+    /// Every setting in the preset's [`PresetSettings`] is copied onto the
+    /// builder: fps, prediction window, input delay, input queue, save mode,
+    /// desync detection, disconnect timing, and the sync, protocol and time
+    /// sync configs. Call it first; later builder calls override individual
+    /// settings, and [`describe`](Self::describe) lists those overrides.
+    /// Because the whole [`ProtocolConfig`] is replaced, a custom
+    /// [`clock`](ProtocolConfig::clock) has to be set after the preset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, FortressError, SaveMode, SessionBuilder, SessionPreset};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new()
+    ///     .preset(SessionPreset::Rts30)
+    ///     .with_save_mode(SaveMode::EveryFrame);
+    /// assert_eq!(
+    ///     builder.describe(),
+    ///     "preset: Rts30; overrides: save_mode = EveryFrame"
+    /// );
+    /// # Ok::<(), FortressError>(())
+    /// ```
+    pub fn preset(mut self, preset: SessionPreset) -> Self {
+        // Destructure to ensure all fields are included when new fields are added.
+        let PresetSettings {
+            fps,
+            max_prediction,
+            input_delay,
+            input_queue_config,
+            save_mode,
+            desync_detection,
+            disconnect_timeout,
+            disconnect_notify_start,
+            sync_config,
+            protocol_config,
+            time_sync_config,
+        } = preset.settings();
+        self.fps = fps;
+        self.max_prediction = max_prediction;
+        self.input_delay = input_delay;
+        self.input_queue_config = input_queue_config;
+        self.save_mode = save_mode;
+        self.desync_detection = desync_detection;
+        self.disconnect_timeout = disconnect_timeout;
+        self.disconnect_notify_start = disconnect_notify_start;
+        self.sync_config = sync_config;
+        self.protocol_config = protocol_config;
+        self.time_sync_config = time_sync_config;
+        self.preset = Some(preset);
+        self
+    }
+
+    /// Summarizes the applied [`SessionPreset`] and every preset setting that
+    /// has been changed since.
+    ///
+    /// Without a preset, overrides are relative to [`SessionBuilder::new`].
+    /// The format is `preset: <name>; overrides: <name = value, ...>`, with
+    /// `none` standing in for a missing preset or an empty override list.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::{Config, SessionBuilder, SessionPreset};
+    ///
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = ();
+    /// #     type Address = std::net::SocketAddr;
+    /// # }
+    /// let builder = SessionBuilder::<MyConfig>::new();
+    /// assert_eq!(builder.describe(), "preset: none; overrides: none");
+    ///
+    /// let builder = builder.preset(SessionPreset::LockstepTurnBased);
+    /// assert_eq!(builder.describe(), "preset: LockstepTurnBased; overrides: none");
+    /// ```
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let base = match self.preset {
+            Some(preset) => preset.settings(),
+            None => Self::new().preset_settings(),
+        };
+        let mut overrides = String::new();
+        self.preset_settings()
+            .write_differences(&base, &mut overrides);
+        if overrides.is_empty() {
+            overrides.push_str("none");
+        }
+        match self.preset {
+            Some(preset) => format!("preset: {preset:?}; overrides: {overrides}"),
+            None => format!("preset: none; overrides: {overrides}"),
+        }
+    }
+
+    /// The builder's current values for the settings a preset covers.
+    fn preset_settings(&self) -> PresetSettings {
+        PresetSettings {
+            fps: self.fps,
+            max_prediction: self.max_prediction,
+            input_delay: self.input_delay,
+            input_queue_config: self.input_queue_config,
+            save_mode: self.save_mode,
+            desync_detection: self.desync_detection,
+            disconnect_timeout: self.disconnect_timeout,
+            disconnect_notify_start: self.disconnect_notify_start,
+            sync_config: self.sync_config,
+            protocol_config: self.protocol_config.clone(),
+            time_sync_config: self.time_sync_config,
+        }
+    }
+
     /// Applies LAN-optimized defaults for low-latency local network play.
     ///
     /// This preset configures the session for minimal latency scenarios typical
@@ -1342,15 +2908,161 @@ fn validate_rollback_config(&self) -> Result<(), FortressError> {
         self.input_queue_config
             .validate_frame_delay(self.input_delay)?;
         self.validate_rollback_window_storage()?;
+        self.validate_input_delay_window()?;
+        self.validate_max_rollback_depth()?;
         self.protocol_config.validate()?;
+        self.sync_config.validate()?;
+        self.validate_input_suppression()?;
+        self.validate_send_limits()?;
         self.validate_network_desync_detection()?;
+        self.validate_session_frame_limit()?;
+        self.validate_frame_timing()
+    }
+
+    /// Frames run ahead of the current frame by at most the prediction window
+    /// plus the input delay, or by the match pause margin; both must fit
+    /// between the frame limit and `i32::MAX`.
+    fn validate_session_frame_limit(&self) -> Result<(), FortressError> {
+        let lead = self
+            .max_prediction
+            .saturating_add(self.input_delay)
+            .max(self.match_pause_margin);
+        let max = i64::from(i32::MAX).saturating_sub(i64::try_from(lead).unwrap_or(i64::MAX));
+        if i64::from(self.max_session_frames.as_i32()) > max {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_session_frames",
+                min: 1,
+                max: u64::try_from(max).unwrap_or(0),
+                actual: u64::try_from(self.max_session_frames.as_i32()).unwrap_or(0),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Checks the millisecond-based timing configs against the frame period at
+    /// the configured fps.
+    fn validate_frame_timing(&self) -> Result<(), FortressError> {
+        let fps = self.fps as u128;
+        // Durations in frames are `millis * fps / 1000`; compare without dividing.
+        let notify_frames_x1000 = self.disconnect_notify_start.as_millis() * fps;
+        if notify_frames_x1000 < MIN_DISCONNECT_NOTIFY_FRAMES * 1000 {
+            let min_ms = (MIN_DISCONNECT_NOTIFY_FRAMES * 1000).div_ceil(fps);
+            return Err(InvalidRequestKind::DurationConfigOutOfRange {
+                field: "disconnect_notify_start",
+                min_ms: u64::try_from(min_ms).unwrap_or(u64::MAX),
+                max_ms: u64::try_from(self.disconnect_timeout.as_millis()).unwrap_or(u64::MAX),
+                actual_ms: u64::try_from(self.disconnect_notify_start.as_millis())
+                    .unwrap_or(u64::MAX),
+            }
+            .into());
+        }
+        let retry = self.sync_config.running_retry_interval;
+        let retry_frames = duration_to_frames_ceil(retry, self.fps);
+        if u128::from(retry_frames) > MAX_RUNNING_RETRY_FRAMES {
+            report_violation_to!(
+                &self.violation_observer,
+                ViolationSeverity::Warning,
+                ViolationKind::Configuration,
+                "running_retry_interval {}ms is {} frames at {} fps (more than {}); a lost input stalls the peer until the retry",
+                retry.as_millis(),
+                retry_frames,
+                self.fps,
+                MAX_RUNNING_RETRY_FRAMES
+            );
+        }
+        Ok(())
+    }
+
+    /// Redundant-input suppression delays confirmation of idle frames and thins
+    /// the input stream, so it must fit inside the prediction window and leave
+    /// keepalives to refresh the peer's disconnect timers.
+    fn validate_input_suppression(&self) -> Result<(), FortressError> {
+        if !self.protocol_config.suppress_redundant_inputs {
+            return Ok(());
+        }
+        let max_suppressed = self.max_prediction.saturating_sub(1);
+        if !(1..=max_suppressed).contains(&self.protocol_config.max_suppressed_frames) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "protocol_config.max_suppressed_frames",
+                min: 1,
+                max: u64::try_from(max_suppressed).unwrap_or(u64::MAX),
+                actual: u64::try_from(self.protocol_config.max_suppressed_frames)
+                    .unwrap_or(u64::MAX),
+            }
+            .into());
+        }
+        if self.sync_config.keepalive_interval >= self.disconnect_notify_start {
+            return Err(InvalidRequestKind::DurationConfigOutOfRange {
+                field: "sync_config.keepalive_interval",
+                min_ms: 0,
+                max_ms: (self.disconnect_notify_start.as_millis() as u64).saturating_sub(1),
+                actual_ms: self.sync_config.keepalive_interval.as_millis() as u64,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Send limits hold messages back, so keepalives must still refresh the
+    /// peer's disconnect timers: the keepalive interval must be shorter than
+    /// the disconnect notify delay, and the byte rate must refill a full
+    /// unfragmented packet within one keepalive interval.
+    fn validate_send_limits(&self) -> Result<(), FortressError> {
+        if !self.protocol_config.limits_sends() {
+            return Ok(());
+        }
+        let keepalive_ms = self.sync_config.keepalive_interval.as_millis().max(1);
+        if self.sync_config.keepalive_interval >= self.disconnect_notify_start {
+            return Err(InvalidRequestKind::DurationConfigOutOfRange {
+                field: "sync_config.keepalive_interval",
+                min_ms: 0,
+                max_ms: (self.disconnect_notify_start.as_millis() as u64).saturating_sub(1),
+                actual_ms: self.sync_config.keepalive_interval.as_millis() as u64,
+            }
+            .into());
+        }
+        if let Some(rate) = self.protocol_config.max_bytes_per_second {
+            let min_rate = (ProtocolConfig::MAX_UNFRAGMENTED_PACKET_BYTES as u128 * 1000)
+                .div_ceil(keepalive_ms);
+            if u128::from(rate) < min_rate {
+                return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "protocol_config.max_bytes_per_second",
+                    min: u64::try_from(min_rate).unwrap_or(u64::MAX),
+                    max: u64::MAX,
+                    actual: rate,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Every pre-established address must get an endpoint, or its IDs would
+    /// silently go unused.
+    fn validate_pre_established(&self) -> Result<(), FortressError> {
+        for addr in self.pre_established.keys() {
+            let has_endpoint = self.observers.contains(addr)
+                || self.player_reg.handles.values().any(|player_type| {
+                    matches!(player_type, PlayerType::Remote(a) | PlayerType::Spectator(a) if a == addr)
+                });
+            if !has_endpoint {
+                return Err(InvalidRequestKind::NotSupported {
+                    operation: "pre-established connection to an address that is not a remote player, spectator, or observer",
+                }
+                .into());
+            }
+        }
         Ok(())
     }
 
     fn validate_spectator_config(&self) -> Result<(), FortressError> {
         self.protocol_config.validate()?;
+        self.sync_config.validate()?;
         self.spectator_config.validate()?;
-        self.validate_network_desync_detection()
+        self.validate_send_limits()?;
+        self.validate_network_desync_detection()?;
+        self.validate_frame_timing()
     }
 
     fn validate_network_desync_detection(&self) -> Result<(), FortressError> {
@@ -1370,7 +3082,41 @@ fn validate_synctest_config(&self) -> Result<(), FortressError> {
         self.input_queue_config.validate()?;
         self.input_queue_config
             .validate_frame_delay(self.input_delay)?;
-        self.validate_rollback_window_storage()
+        self.validate_rollback_window_storage()?;
+        self.validate_session_frame_limit()
+    }
+
+    /// A local input delay must stay below a non-zero prediction window unless
+    /// explicitly allowed.
+    fn validate_input_delay_window(&self) -> Result<(), FortressError> {
+        if self.max_prediction > 0
+            && self.input_delay >= self.max_prediction
+            && !self.input_delay_beyond_prediction
+        {
+            return Err(InvalidRequestKind::InputDelayBeyondPrediction {
+                delay: self.input_delay,
+                max_prediction: self.max_prediction,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// A rollback depth cap must be a non-empty part of the prediction window.
+    fn validate_max_rollback_depth(&self) -> Result<(), FortressError> {
+        let Some(depth) = self.max_rollback_depth else {
+            return Ok(());
+        };
+        if !(1..=self.max_prediction).contains(&depth) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_rollback_depth",
+                min: 1,
+                max: u64::try_from(self.max_prediction).unwrap_or(u64::MAX),
+                actual: u64::try_from(depth).unwrap_or(u64::MAX),
+            }
+            .into());
+        }
+        Ok(())
     }
 
     /// The ring must simultaneously retain the complete rollback window and
@@ -1378,16 +3124,10 @@ fn validate_synctest_config(&self) -> Result<(), FortressError> {
     /// by the queue's bounded recovery side slot when a full protocol batch
     /// arrives, but the steady-state span must fit in the ring itself.
     fn validate_rollback_window_storage(&self) -> Result<(), FortressError> {
-        let actual = self.max_prediction.saturating_add(self.input_delay);
-        let max = self.input_queue_config.queue_length.saturating_sub(1);
-        if actual > max {
-            return Err(InvalidRequestKind::ConfigValueOutOfRange {
-                field: "max_prediction + input_delay",
-                min: 0,
-                max: u64::try_from(max).unwrap_or(u64::MAX),
-                actual: u64::try_from(actual).unwrap_or(u64::MAX),
-            }
-            .into());
+        let length = self.input_queue_config.queue_length;
+        let minimum = InputQueueConfig::min_queue_length(self.max_prediction, self.input_delay);
+        if length < minimum {
+            return Err(InvalidRequestKind::QueueLengthBelowMinimum { length, minimum }.into());
         }
         Ok(())
     }
@@ -1417,9 +3157,57 @@ fn distinct_remote_machine_count(&self) -> usize {
         addrs.len()
     }
 
+    /// Checks every player added with
+    /// [`add_player_via_relay`](Self::add_player_via_relay) has a relay: a
+    /// directly added remote player at the same address.
+    fn validate_relayed_players(&self) -> Result<(), FortressError> {
+        if self.relayed_players.is_empty() {
+            return Ok(());
+        }
+        if self.disconnect_behavior == DisconnectBehavior::ContinueWithout {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "add_player_via_relay with DisconnectBehavior::ContinueWithout",
+            }
+            .into());
+        }
+        #[cfg(feature = "hot-join")]
+        if self.accept_hot_join || !self.reserved_slots.is_empty() {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "add_player_via_relay on a hot-join host",
+            }
+            .into());
+        }
+        for handle in &self.relayed_players {
+            let Some(PlayerType::Remote(relay_addr)) = self.player_reg.handles.get(handle) else {
+                continue;
+            };
+            let has_relay = self.player_reg.handles.iter().any(|(other, player_type)| {
+                !self.relayed_players.contains(other)
+                    && matches!(player_type, PlayerType::Remote(addr) if addr == relay_addr)
+            });
+            if !has_relay {
+                return Err(InvalidRequestKind::NotSupported {
+                    operation: "add_player_via_relay without a remote player at the relay address",
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
     /// Consumes the builder to construct a [`P2PSession`] and starts synchronization of endpoints.
     /// # Errors
     /// - Returns a [`FortressError`] if insufficient players have been registered.
+    /// - Returns [`InvalidRequestKind::NotSupported`] if a player added with
+    ///   [`add_player_via_relay`](Self::add_player_via_relay) has no remote
+    ///   player added directly at its relay's address, or relayed players are
+    ///   combined with [`DisconnectBehavior::ContinueWithout`] or, under the
+    ///   `hot-join` feature, with serving hot-joins.
+    /// - Returns [`InvalidRequestKind::NotSupported`] if an observer added with
+    ///   [`add_observer`](Self::add_observer) shares the address of a
+    ///   registered player or spectator.
+    /// - Returns [`InvalidRequestKind::NotSupported`] if this host serves
+    ///   hot-joins and has observers. Requires the `hot-join` feature.
     /// - Returns [`InvalidRequestKind::NotSupported`] if this host serves
     ///   hot-joins (`with_hot_join(true)` or any reserved slot) while
     ///   `max_prediction == 0` (lockstep): the host never saves state, so it
@@ -1451,12 +3239,47 @@ fn distinct_remote_machine_count(&self) -> usize {
     ///   Machines are counted per network address, so 2-machine couch co-op
     ///   (multiple remote handles sharing one address) is unaffected by these
     ///   requirements. Requires the `hot-join` feature.
+    /// - Returns [`FortressError::InvalidFrameStructured`] if the frame set with
+    ///   [`with_expected_state_checksum`](Self::with_expected_state_checksum)
+    ///   is negative.
+    /// - Returns [`InvalidRequestKind::NotSupported`] if
+    ///   [`with_expected_state_checksum`](Self::with_expected_state_checksum)
+    ///   was set without a remote player to verify against.
+    /// - Returns [`InvalidRequestKind::InputDelayBeyondPrediction`] if the
+    ///   input delay is not below a non-zero prediction window and
+    ///   [`with_input_delay_beyond_prediction`](Self::with_input_delay_beyond_prediction)
+    ///   is not set.
+    /// - Returns [`InvalidRequestKind::ConfigValueOutOfRange`] if the cap set
+    ///   with [`with_max_rollback_depth`](Self::with_max_rollback_depth) is 0
+    ///   or exceeds the prediction window.
     pub fn start_p2p_session(
         self,
         socket: impl NonBlockingSocket<T::Address> + 'static,
     ) -> Result<P2PSession<T>, FortressError> {
         self.validate_rollback_config()?;
 
+        if let Some((frame, _)) = self.expected_state_checksum {
+            if frame.as_i32() < 0 {
+                return Err(FortressError::InvalidFrameStructured {
+                    frame,
+                    reason: InvalidFrameReason::MustBeNonNegative,
+                });
+            }
+            if !self
+                .player_reg
+                .handles
+                .values()
+                .any(|player_type| matches!(player_type, PlayerType::Remote(_)))
+            {
+                return Err(InvalidRequestKind::NotSupported {
+                    operation: "with_expected_state_checksum without a remote player",
+                }
+                .into());
+            }
+        }
+
+        self.validate_relayed_players()?;
+
         // Hot-join requires a non-zero prediction window. In lockstep mode
         // (`max_prediction == 0`) the host never saves state, so it can never
         // capture a snapshot to serve a joiner — the join could never complete.
@@ -1472,6 +3295,16 @@ pub fn start_p2p_session(
             .into());
         }
 
+        // A join snapshot reactivates a player slot; observers own none, and
+        // the join commit is only agreed among the player endpoints.
+        #[cfg(feature = "hot-join")]
+        if (self.accept_hot_join || !self.reserved_slots.is_empty()) && !self.observers.is_empty() {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "hot-join host with observer peers",
+            }
+            .into());
+        }
+
         // N-peer (3+ machine) build requirements — BUILD-TIME MIRRORS of the
         // runtime serve gates in `try_open_npeer_serve` (S20 guard lift,
         // chunk N5). The runtime gates stay as per-serve defense in depth;
@@ -1525,6 +3358,94 @@ pub fn start_p2p_session(
         self.start_p2p_session_after_mesh_guard(socket)
     }
 
+    /// Consumes the builder to construct a [`P2PSessionCore`]: a
+    /// [`P2PSession`] driven by explicit [`tick`](P2PSessionCore::tick) calls
+    /// instead of a socket and the wall clock, for custom schedulers and
+    /// browser main loops.
+    ///
+    /// The session's protocol clock reads the time of the latest tick,
+    /// starting at `now`; a clock set in
+    /// [`with_protocol_config`](Self::with_protocol_config) is replaced.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`start_p2p_session`](Self::start_p2p_session).
+    pub fn start_p2p_session_core(
+        mut self,
+        now: Instant,
+    ) -> Result<P2PSessionCore<T>, FortressError> {
+        P2PSessionCore::start(now, |clock, socket| {
+            self.protocol_config.clock = Some(clock);
+            self.start_p2p_session(socket)
+        })
+    }
+
+    /// Like [`start_p2p_session`](Self::start_p2p_session), but leaves the
+    /// builder in place so one template can start many identical sessions.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`start_p2p_session`](Self::start_p2p_session);
+    /// validation runs on every call.
+    pub fn build_p2p_session(
+        &self,
+        socket: impl NonBlockingSocket<T::Address> + 'static,
+    ) -> Result<P2PSession<T>, FortressError> {
+        self.clone().start_p2p_session(socket)
+    }
+
+    /// Starts a [`P2PSession`] with `players` added to a copy of this builder,
+    /// so a template holding only the tuning knobs can serve every match.
+    ///
+    /// The players are added as by [`add_player`](Self::add_player), after any
+    /// the builder already holds. The builder itself is left unchanged.
+    ///
+    /// # Errors
+    /// - Returns the errors of [`add_player`](Self::add_player) for an invalid or
+    ///   duplicate handle.
+    /// - Returns the same errors as [`start_p2p_session`](Self::start_p2p_session).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// # use fortress_rollback::UdpNonBlockingSocket;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let template = SessionBuilder::<TestConfig>::new().with_input_delay(2)?;
+    ///
+    /// for port in [7001_u16, 7002] {
+    ///     let remote: SocketAddr = ([127, 0, 0, 1], port).into();
+    ///     let socket = UdpNonBlockingSocket::bind_to_port(0)?;
+    ///     let session = template.build_with_players(
+    ///         &[
+    ///             (PlayerType::Local, PlayerHandle::new(0)),
+    ///             (PlayerType::Remote(remote), PlayerHandle::new(1)),
+    ///         ],
+    ///         socket,
+    ///     )?;
+    /// #   drop(session);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_with_players(
+        &self,
+        players: &[(PlayerType<T::Address>, PlayerHandle)],
+        socket: impl NonBlockingSocket<T::Address> + 'static,
+    ) -> Result<P2PSession<T>, FortressError> {
+        let mut builder = self.clone();
+        for (player_type, handle) in players {
+            builder = builder.add_player(player_type.clone(), *handle)?;
+        }
+        builder.start_p2p_session(socket)
+    }
+
     /// Test-only escape hatch that constructs a [`P2PSession`] **skipping every
     /// hot-join build-time guard in [`start_p2p_session`]** — the N-peer
     /// (N>=3 mesh) build-requirement MIRRORS of the runtime serve gates
@@ -1578,6 +3499,8 @@ fn start_p2p_session_after_mesh_guard(
             .into());
         }
 
+        self.validate_pre_established()?;
+
         // count the number of players per address
         let mut addr_count = BTreeMap::<PlayerType<T::Address>, Vec<PlayerHandle>>::new();
         for (handle, player_type) in self.player_reg.handles.iter() {
@@ -1590,6 +3513,14 @@ fn start_p2p_session_after_mesh_guard(
             }
         }
 
+        // Every peer must claim exactly the handles registered for its address.
+        // Hot-join hosts hand reserved slots to whichever joiner arrives, so
+        // they skip the check.
+        #[cfg(feature = "hot-join")]
+        let verify_handles = !self.accept_hot_join && self.reserved_slots.is_empty();
+        #[cfg(not(feature = "hot-join"))]
+        let verify_handles = true;
+
         // for each unique address, create an endpoint
         for (player_type, handles) in addr_count.into_iter() {
             match player_type {
@@ -1598,29 +3529,58 @@ fn start_p2p_session_after_mesh_guard(
                     // callers can distinguish IO (socket), protocol, and config
                     // failures (and `AllocationFailed`) instead of forcing every
                     // cause to a single opaque endpoint-creation error.
+                    let expected = handles.clone();
                     let endpoint = self.create_endpoint(
                         handles,
                         peer_addr.clone(),
                         self.local_players,
                         self.desync_detection,
+                        verify_handles.then_some(expected.as_slice()),
+                        None,
                     )?;
                     self.player_reg.remotes.insert(peer_addr, endpoint);
                 },
                 PlayerType::Spectator(peer_addr) => {
                     // the host of the spectator sends inputs for all players;
                     // propagate the original error verbatim (see above).
-                    let endpoint = self.create_endpoint(
+                    let mut endpoint = self.create_endpoint(
                         handles,
                         peer_addr.clone(),
                         self.num_players,
                         DesyncDetection::Off,
+                        verify_handles.then_some(&[]),
+                        self.spectator_config.access_token,
                     )?;
+                    endpoint
+                        .enable_spectator_keyframes(self.spectator_config.keyframe_interval, false);
                     self.player_reg.spectators.insert(peer_addr, endpoint);
                 },
                 PlayerType::Local => (),
             }
         }
 
+        // observers own no player slot: their endpoints receive no inputs
+        for observer_addr in std::mem::take(&mut self.observers) {
+            let shares_address = self.player_reg.handles.values().any(|player_type| {
+                matches!(player_type, PlayerType::Remote(addr) | PlayerType::Spectator(addr) if *addr == observer_addr)
+            });
+            if shares_address {
+                return Err(InvalidRequestKind::NotSupported {
+                    operation: "observer at the address of a registered player or spectator",
+                }
+                .into());
+            }
+            let endpoint = self.create_endpoint(
+                Vec::new(),
+                observer_addr.clone(),
+                self.local_players,
+                self.desync_detection,
+                verify_handles.then_some(&[]),
+                None,
+            )?;
+            self.player_reg.remotes.insert(observer_addr, endpoint);
+        }
+
         #[cfg(feature = "hot-join")]
         let hot_join = crate::sessions::p2p_session::HotJoinConfig {
             reserved_slots: self.reserved_slots,
@@ -1631,7 +3591,10 @@ fn start_p2p_session_after_mesh_guard(
             ack_resends: self.hot_join_ack_resends,
         };
 
-        P2PSession::<T>::new(
+        let state_diff_codec = self.state_diff_codec;
+        let max_frames_ahead = self.time_sync_config.max_frames_ahead;
+        let relayed_players = self.relayed_players;
+        let mut session = P2PSession::<T>::new(
             self.num_players,
             self.max_prediction,
             Box::new(socket),
@@ -1642,13 +3605,36 @@ fn start_p2p_session_after_mesh_guard(
             self.violation_observer,
             self.protocol_config,
             self.input_queue_config.queue_length,
+            self.input_queue_config.remote_input_buffer_frames,
             self.event_queue_size,
             self.recording,
             self.telemetry,
             self.disconnect_behavior,
+            self.max_resimulation_per_advance,
+            self.previous_inputs_in_requests,
+            self.match_pause_margin,
+            self.max_session_frames,
+            self.roster,
+            self.preallocated_buffers,
+            self.expected_state_checksum,
+            self.advance_stall_warning,
+            self.input_delay_beyond_prediction,
+            self.frame_timeline,
+            self.trace_recorder,
+            self.state_size_hint,
+            self.auto_frame_skip,
+            self.confirmation_latency,
+            self.rollback_context,
+            self.max_rollback_depth,
             #[cfg(feature = "hot-join")]
             hot_join,
-        )
+        )?;
+        if let Some(codec) = state_diff_codec {
+            session.enable_state_diffs(codec);
+        }
+        session.limit_frames_ahead(max_frames_ahead);
+        session.reach_via_relay(relayed_players);
+        Ok(session)
     }
 
     /// Consumes the builder to construct a hot-joiner [`P2PSession`] that joins a
@@ -1687,6 +3673,8 @@ fn start_p2p_session_after_mesh_guard(
     ///   (lockstep): the host never saves state, so it could never serve a
     ///   snapshot and the joiner would hang in
     ///   [`HotJoining`](crate::SessionState::HotJoining) forever.
+    /// - Returns [`InvalidRequestKind::NotSupported`] if any observer was added
+    ///   with [`add_observer`](Self::add_observer).
     /// - Returns an error if exactly one local player is not registered.
     /// - Returns the same player-count errors as
     ///   [`start_p2p_session`](Self::start_p2p_session) (every slot must be
@@ -1749,6 +3737,20 @@ pub fn start_hot_join_session(
             .into());
         }
 
+        if !self.observers.is_empty() {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "start_hot_join_session with observer peers",
+            }
+            .into());
+        }
+
+        if !self.relayed_players.is_empty() {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "start_hot_join_session with relayed players",
+            }
+            .into());
+        }
+
         // The joiner must have exactly one local player (the slot it fills).
         let local_handle = self.player_reg.local_player_handle_required()?;
 
@@ -1869,6 +3871,8 @@ fn finish_hot_join_session(
             .into());
         }
 
+        self.validate_pre_established()?;
+
         // Build remote endpoints exactly like start_p2p_session: one per unique
         // remote address (the host plus any other remote slots in scope).
         let mut addr_count = BTreeMap::<PlayerType<T::Address>, Vec<PlayerHandle>>::new();
@@ -1889,6 +3893,8 @@ fn finish_hot_join_session(
                         peer_addr.clone(),
                         self.local_players,
                         self.desync_detection,
+                        None,
+                        None,
                     )?;
                     // Defer input processing until the snapshot is applied: the
                     // joiner must not ack the host's inputs before the activation
@@ -1899,12 +3905,16 @@ fn finish_hot_join_session(
                     self.player_reg.remotes.insert(peer_addr, endpoint);
                 },
                 PlayerType::Spectator(peer_addr) => {
-                    let endpoint = self.create_endpoint(
+                    let mut endpoint = self.create_endpoint(
                         handles,
                         peer_addr.clone(),
                         self.num_players,
                         DesyncDetection::Off,
+                        None,
+                        self.spectator_config.access_token,
                     )?;
+                    endpoint
+                        .enable_spectator_keyframes(self.spectator_config.keyframe_interval, false);
                     self.player_reg.spectators.insert(peer_addr, endpoint);
                 },
                 PlayerType::Local => (),
@@ -1925,7 +3935,9 @@ fn finish_hot_join_session(
             ack_resends: self.hot_join_ack_resends,
         };
 
-        P2PSession::<T>::new(
+        let state_diff_codec = self.state_diff_codec;
+        let max_frames_ahead = self.time_sync_config.max_frames_ahead;
+        let mut session = P2PSession::<T>::new(
             self.num_players,
             self.max_prediction,
             Box::new(socket),
@@ -1936,12 +3948,35 @@ fn finish_hot_join_session(
             self.violation_observer,
             self.protocol_config,
             self.input_queue_config.queue_length,
+            self.input_queue_config.remote_input_buffer_frames,
             self.event_queue_size,
             self.recording,
             self.telemetry,
             self.disconnect_behavior,
+            self.max_resimulation_per_advance,
+            self.previous_inputs_in_requests,
+            self.match_pause_margin,
+            self.max_session_frames,
+            self.roster,
+            self.preallocated_buffers,
+            // The joiner's snapshot carries its own checksum.
+            None,
+            self.advance_stall_warning,
+            self.input_delay_beyond_prediction,
+            self.frame_timeline,
+            self.trace_recorder,
+            self.state_size_hint,
+            self.auto_frame_skip,
+            self.confirmation_latency,
+            self.rollback_context,
+            self.max_rollback_depth,
             hot_join,
-        )
+        )?;
+        if let Some(codec) = state_diff_codec {
+            session.enable_state_diffs(codec);
+        }
+        session.limit_frames_ahead(max_frames_ahead);
+        Ok(session)
     }
 
     /// Consumes the builder to create a new [`SpectatorSession`].
@@ -1975,8 +4010,15 @@ pub fn start_spectator_session(
             self.spectator_config.catchup_speed,
             self.spectator_config.stream_delay,
             self.spectator_config.enable_rewind,
+            self.previous_inputs_in_requests,
             self.violation_observer,
             self.event_queue_size,
+            self.max_session_frames,
+            self.protocol_config.clock.clone(),
+            self.state_size_hint,
+            self.spectator_config.input_gap_timeout,
+            self.spectator_config.checksum_authority,
+            self.fps,
         )
         .ok()
     }
@@ -2082,8 +4124,15 @@ pub fn start_spectator_session_multi(
             self.spectator_config.catchup_speed,
             self.spectator_config.stream_delay,
             self.spectator_config.enable_rewind,
+            self.previous_inputs_in_requests,
             self.violation_observer,
             self.event_queue_size,
+            self.max_session_frames,
+            self.protocol_config.clock.clone(),
+            self.state_size_hint,
+            self.spectator_config.input_gap_timeout,
+            self.spectator_config.checksum_authority,
+            self.fps,
         )
         .ok()
     }
@@ -2116,6 +4165,14 @@ fn build_spectator_host(&self, host_addr: T::Address) -> Option<UdpProtocol<T>>
             self.time_sync_config,
         )
         .ok()?;
+        host.enable_spectator_keyframes(self.spectator_config.keyframe_interval, true);
+        host.enable_input_reordering(self.spectator_config.buffer_size);
+        if self.spectator_config.checksum_authority {
+            host.enable_checksum_authority();
+        }
+        if let Some(token) = self.spectator_token {
+            host.prove_spectator_access(token);
+        }
         host.synchronize().ok()?;
         Some(host)
     }
@@ -2129,22 +4186,53 @@ fn build_spectator_host(&self, host_addr: T::Address) -> Option<UdpProtocol<T>>
     pub fn start_synctest_session(self) -> Result<SyncTestSession<T>, FortressError> {
         self.validate_synctest_config()?;
 
-        if self.check_dist >= self.max_prediction {
+        let check_dist = match self.randomized_rollbacks {
+            Some((_, 0)) => {
+                return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "max_depth",
+                    min: 1,
+                    max: u64::try_from(self.max_prediction.saturating_sub(1)).unwrap_or(u64::MAX),
+                    actual: 0,
+                }
+                .into());
+            },
+            Some((_, max_depth)) => max_depth,
+            None => self.check_dist,
+        };
+        if check_dist >= self.max_prediction {
             return Err(InvalidRequestKind::CheckDistanceTooLarge {
-                check_dist: self.check_dist,
+                check_dist,
                 max_prediction: self.max_prediction,
             }
             .into());
         }
+        if self.perturbed_resimulation && self.state_diff_codec.is_some() {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "with_perturbed_resimulation with a state diff codec",
+            }
+            .into());
+        }
 
-        SyncTestSession::try_with_queue_length(
+        let mut session = SyncTestSession::try_with_queue_length(
             self.num_players,
             self.max_prediction,
-            self.check_dist,
+            check_dist,
             self.input_delay,
             self.violation_observer,
             self.input_queue_config.queue_length,
-        )
+            self.max_session_frames,
+            self.previous_inputs_in_requests,
+        )?;
+        if let Some((seed, _)) = self.randomized_rollbacks {
+            session.randomize_rollbacks(seed);
+        }
+        if self.perturbed_resimulation {
+            session.perturb_resimulation();
+        }
+        if let Some(codec) = self.state_diff_codec {
+            session.enable_state_diffs(codec);
+        }
+        Ok(session)
     }
 
     /// Creates a replay playback session from a recorded [`Replay`].
@@ -2205,6 +4293,8 @@ pub fn start_replay_session(
             false,
             self.event_queue_size,
             self.violation_observer,
+            self.previous_inputs_in_requests,
+            self.replay_save_interval,
         )
     }
 
@@ -2268,16 +4358,88 @@ pub fn start_replay_session_with_validation(
             true,
             self.event_queue_size,
             self.violation_observer,
+            self.previous_inputs_in_requests,
+            self.replay_save_interval,
+        )
+    }
+
+    /// Creates a replay playback session from a bare recorded input stream,
+    /// such as the [`P2PSession::confirmed_inputs_for_frame`] values of each
+    /// frame: `inputs[frame][handle]` is the input of player `handle` on
+    /// `frame`.
+    ///
+    /// A frame may hold fewer inputs than the builder's number of players,
+    /// as when a player disconnected partway through the recording; the
+    /// missing trailing handles are padded with default inputs in
+    /// [`ReplaySession::replay`] and play back as
+    /// [`InputStatus::Disconnected`]. There are no recorded checksums to
+    /// validate against, but
+    /// [`with_replay_save_interval`](Self::with_replay_save_interval) still
+    /// applies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// // The second player left after two frames.
+    /// let inputs = vec![vec![1, 2], vec![3, 4], vec![5]];
+    /// let session = SessionBuilder::<TestConfig>::new()
+    ///     .start_replay_session_from_inputs(inputs)?;
+    /// assert_eq!(session.frames_remaining(), 3);
+    /// # Ok::<(), fortress_rollback::FortressError>(())
+    /// ```
+    ///
+    /// [`P2PSession::confirmed_inputs_for_frame`]: crate::P2PSession::confirmed_inputs_for_frame
+    /// [`InputStatus::Disconnected`]: crate::InputStatus::Disconnected
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a frame holds more inputs than the builder's number
+    /// of players or the configured event queue cannot be reserved.
+    pub fn start_replay_session_from_inputs(
+        self,
+        inputs: Vec<Vec<T::Input>>,
+    ) -> crate::FortressResult<ReplaySession<T>> {
+        ReplaySession::from_recorded_inputs(
+            inputs,
+            self.num_players,
+            self.event_queue_size,
+            self.violation_observer,
+            self.previous_inputs_in_requests,
+            self.replay_save_interval,
         )
     }
 
+    /// Creates and synchronizes the endpoint for `peer_addr`. The endpoint
+    /// advertises this session's local handles in its handshake and, with
+    /// `expected_handles` set, fails the handshake unless the peer claims
+    /// exactly those handles. With `access_token` set, it stays silent until
+    /// the peer proves that token.
     fn create_endpoint(
         &self,
         handles: Vec<PlayerHandle>,
         peer_addr: T::Address,
         local_players: usize,
         desync_detection: DesyncDetection,
+        expected_handles: Option<&[PlayerHandle]>,
+        access_token: Option<[u8; 16]>,
     ) -> Result<UdpProtocol<T>, FortressError> {
+        let pre_established = self.pre_established.get(&peer_addr).copied();
+        let local_handles: Vec<PlayerHandle> = self
+            .player_reg
+            .handles
+            .iter()
+            .filter(|(_, player_type)| matches!(player_type, PlayerType::Local))
+            .map(|(handle, _)| *handle)
+            .collect();
         // create the endpoint, set parameters
         let mut endpoint = UdpProtocol::new(
             handles,
@@ -2293,6 +4455,22 @@ fn create_endpoint(
             self.protocol_config.clone(),
             self.time_sync_config,
         )?;
+        endpoint.claim_handles(&local_handles, expected_handles);
+        if let Some(token) = access_token {
+            endpoint.require_spectator_access(token);
+        }
+        if let Some(conn_id) = self.protocol_identity {
+            endpoint.pin_conn_id(conn_id);
+        }
+        if self.peer_restart {
+            endpoint.enable_peer_restart();
+        }
+        if self.address_migration {
+            endpoint.enable_address_migration();
+        }
+        if let Some(ids) = pre_established {
+            endpoint.pre_establish(ids);
+        }
         #[cfg(feature = "trace-validation")]
         if let Some(capacity) = self.handshake_trace_capacity {
             endpoint.activate_handshake_trace(capacity)?;
@@ -2443,7 +4621,10 @@ fn rollback_window_and_input_delay_must_fit_queue_storage() {
             ..ProtocolConfig::default()
         };
         let exact = SessionBuilder::<TestConfig>::new()
-            .with_input_queue_config(InputQueueConfig { queue_length: 32 })
+            .with_input_queue_config(InputQueueConfig {
+                queue_length: 32,
+                ..InputQueueConfig::default()
+            })
             .with_protocol_config(bounded_protocol.clone())
             .with_max_prediction_window(30)
             .with_input_delay(1)
@@ -2456,7 +4637,10 @@ fn rollback_window_and_input_delay_must_fit_queue_storage() {
             .expect("synctest uses the same exact storage bound");
 
         let over = SessionBuilder::<TestConfig>::new()
-            .with_input_queue_config(InputQueueConfig { queue_length: 32 })
+            .with_input_queue_config(InputQueueConfig {
+                queue_length: 32,
+                ..InputQueueConfig::default()
+            })
             .with_protocol_config(bounded_protocol)
             .with_max_prediction_window(30)
             .with_input_delay(2)
@@ -2470,15 +4654,32 @@ fn rollback_window_and_input_delay_must_fit_queue_storage() {
             assert!(matches!(
                 error,
                 FortressError::InvalidRequestStructured {
-                    kind: InvalidRequestKind::ConfigValueOutOfRange {
-                        field: "max_prediction + input_delay",
-                        min: 0,
-                        max: 31,
-                        actual: 32,
+                    kind: InvalidRequestKind::QueueLengthBelowMinimum {
+                        length: 32,
+                        minimum: 33,
                     }
                 }
             ));
         }
+
+        // One slot short of the minimum for the `exact` settings.
+        let short = SessionBuilder::<TestConfig>::new()
+            .with_input_queue_config(InputQueueConfig {
+                queue_length: InputQueueConfig::min_queue_length(30, 1) - 1,
+                ..InputQueueConfig::default()
+            })
+            .with_max_prediction_window(30)
+            .with_input_delay(1)
+            .expect("individual delay bound");
+        assert!(matches!(
+            short.validate_rollback_config(),
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::QueueLengthBelowMinimum {
+                    length: 31,
+                    minimum: 32,
+                }
+            })
+        ));
     }
 
     #[test]
@@ -2740,22 +4941,270 @@ fn start_p2p_session_rejects_max_prediction_beyond_queue_storage() {
 
         match err {
             FortressError::InvalidRequestStructured {
-                kind:
-                    InvalidRequestKind::ConfigValueOutOfRange {
-                        field,
-                        min,
-                        max,
-                        actual,
-                    },
+                kind: InvalidRequestKind::QueueLengthBelowMinimum { length, minimum },
             } => {
-                assert_eq!(field, "max_prediction + input_delay");
-                assert_eq!((min, max), (0, 127));
-                assert_eq!(actual, u64::try_from(usize::MAX).unwrap_or(u64::MAX));
+                assert_eq!(length, 128);
+                assert_eq!(minimum, usize::MAX);
             },
             other => panic!("expected combined storage range error, got {other:?}"),
         }
     }
 
+    fn suppression_protocol_config(max_suppressed_frames: usize) -> ProtocolConfig {
+        ProtocolConfig {
+            suppress_redundant_inputs: true,
+            max_suppressed_frames,
+            ..ProtocolConfig::default()
+        }
+    }
+
+    #[test]
+    fn start_p2p_session_rejects_suppression_budget_outside_prediction_window() {
+        let err = single_local_builder()
+            .with_protocol_config(suppression_protocol_config(8))
+            .start_p2p_session(DummySocket)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "protocol_config.max_suppressed_frames",
+                    min: 1,
+                    max: 7,
+                    actual: 8,
+                }
+            }
+        ));
+
+        // Lockstep has no prediction window to hide a suppressed frame in.
+        let err = single_local_builder()
+            .with_max_prediction_window(0)
+            .with_protocol_config(suppression_protocol_config(1))
+            .start_p2p_session(DummySocket)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "protocol_config.max_suppressed_frames",
+                    max: 0,
+                    ..
+                }
+            }
+        ));
+
+        single_local_builder()
+            .with_protocol_config(suppression_protocol_config(7))
+            .start_p2p_session(DummySocket)
+            .unwrap();
+    }
+
+    #[test]
+    fn start_p2p_session_rejects_rollback_depth_outside_prediction_window() {
+        for depth in [0, 9] {
+            let err = single_local_builder()
+                .with_max_rollback_depth(depth)
+                .start_p2p_session(DummySocket)
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                FortressError::InvalidRequestStructured {
+                    kind: InvalidRequestKind::ConfigValueOutOfRange {
+                        field: "max_rollback_depth",
+                        min: 1,
+                        max: 8,
+                        actual,
+                    }
+                } if actual == depth as u64
+            ));
+        }
+
+        let session = single_local_builder()
+            .with_max_rollback_depth(8)
+            .start_p2p_session(DummySocket)
+            .unwrap();
+        assert_eq!(session.max_rollback_depth(), 8);
+        let session = single_local_builder()
+            .start_p2p_session(DummySocket)
+            .unwrap();
+        assert_eq!(session.max_rollback_depth(), session.max_prediction());
+    }
+
+    #[test]
+    fn start_p2p_session_rejects_suppression_when_keepalive_cannot_beat_disconnect_notify() {
+        let err = single_local_builder()
+            .with_protocol_config(suppression_protocol_config(4))
+            .with_sync_config(SyncConfig {
+                keepalive_interval: Duration::from_millis(500),
+                ..SyncConfig::default()
+            })
+            .start_p2p_session(DummySocket)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::DurationConfigOutOfRange {
+                    field: "sync_config.keepalive_interval",
+                    max_ms: 499,
+                    actual_ms: 500,
+                    ..
+                }
+            }
+        ));
+
+        // Without suppression the same timing is accepted as before.
+        single_local_builder()
+            .with_sync_config(SyncConfig {
+                keepalive_interval: Duration::from_millis(500),
+                ..SyncConfig::default()
+            })
+            .start_p2p_session(DummySocket)
+            .unwrap();
+    }
+
+    #[test]
+    fn start_p2p_session_rejects_send_limits_that_would_starve_keepalives() {
+        let limited = |max_bytes_per_second| ProtocolConfig {
+            max_packets_per_poll: 1,
+            max_bytes_per_second,
+            ..ProtocolConfig::default()
+        };
+        let err = single_local_builder()
+            .with_protocol_config(limited(Some(7_359)))
+            .start_p2p_session(DummySocket)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "protocol_config.max_bytes_per_second",
+                    min: 7_360,
+                    actual: 7_359,
+                    ..
+                }
+            }
+        ));
+
+        let err = single_local_builder()
+            .with_protocol_config(limited(None))
+            .with_sync_config(SyncConfig {
+                keepalive_interval: Duration::from_millis(500),
+                ..SyncConfig::default()
+            })
+            .start_p2p_session(DummySocket)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::DurationConfigOutOfRange {
+                    field: "sync_config.keepalive_interval",
+                    max_ms: 499,
+                    actual_ms: 500,
+                    ..
+                }
+            }
+        ));
+
+        let spectator = SessionBuilder::<TestConfig>::new()
+            .with_protocol_config(limited(Some(1_000)))
+            .start_spectator_session(test_addr(7_506), DummySocket);
+        assert!(spectator.is_none());
+
+        single_local_builder()
+            .with_protocol_config(limited(Some(7_360)))
+            .start_p2p_session(DummySocket)
+            .unwrap();
+    }
+
+    #[test]
+    fn with_fps_rejects_zero_and_values_above_max() {
+        assert!(SessionBuilder::<TestConfig>::new().with_fps(0).is_err());
+        let err = SessionBuilder::<TestConfig>::new()
+            .with_fps(MAX_FPS + 1)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange { field: "fps", .. }
+            }
+        ));
+        for fps in [1, 12, 240, MAX_FPS] {
+            assert_eq!(
+                SessionBuilder::<TestConfig>::new()
+                    .with_fps(fps)
+                    .unwrap()
+                    .fps,
+                fps
+            );
+        }
+    }
+
+    #[test]
+    fn session_start_rejects_disconnect_notify_shorter_than_three_frames() {
+        // 3 frames at 12 fps is 250 ms.
+        let err = single_local_builder()
+            .with_fps(12)
+            .unwrap()
+            .with_disconnect_notify_delay(Duration::from_millis(200))
+            .start_p2p_session(DummySocket)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::DurationConfigOutOfRange {
+                    field: "disconnect_notify_start",
+                    min_ms: 250,
+                    actual_ms: 200,
+                    ..
+                }
+            }
+        ));
+        let spectator = SessionBuilder::<TestConfig>::new()
+            .with_fps(12)
+            .unwrap()
+            .with_disconnect_notify_delay(Duration::from_millis(200))
+            .start_spectator_session(test_addr(7_505), DummySocket);
+        assert!(spectator.is_none());
+
+        // The same delay spans 48 frames at 240 fps.
+        single_local_builder()
+            .with_fps(240)
+            .unwrap()
+            .with_disconnect_notify_delay(Duration::from_millis(200))
+            .start_p2p_session(DummySocket)
+            .unwrap();
+    }
+
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn session_start_warns_when_running_retry_spans_too_many_frames() {
+        use crate::telemetry::CollectingObserver;
+
+        let start = |fps| {
+            let observer = Arc::new(CollectingObserver::new());
+            single_local_builder()
+                .with_fps(fps)
+                .unwrap()
+                .with_violation_observer(Arc::clone(&observer) as Arc<dyn ViolationObserver>)
+                .start_p2p_session(DummySocket)
+                .unwrap();
+            observer
+                .violations()
+                .into_iter()
+                .filter(|violation| violation.message.contains("running_retry_interval"))
+                .collect::<Vec<_>>()
+        };
+
+        // The default 200 ms retry is 12 frames at 60 fps but 48 at 240 fps.
+        assert!(start(60).is_empty());
+        assert!(start(12).is_empty());
+        let warnings = start(240);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, ViolationSeverity::Warning);
+        assert_eq!(warnings[0].kind, ViolationKind::Configuration);
+        assert!(warnings[0].message.contains("48 frames at 240 fps"));
+    }
+
     #[test]
     fn every_network_session_start_rejects_zero_desync_interval() {
         let invalid_mode = DesyncDetection::On { interval: 0 };
@@ -2826,6 +5275,7 @@ fn start_p2p_session_reports_time_sync_allocation_failure() {
             .unwrap()
             .with_time_sync_config(TimeSyncConfig {
                 window_size: usize::MAX,
+                ..TimeSyncConfig::default()
             })
             .add_local_player(0)
             .unwrap()
@@ -2842,6 +5292,7 @@ fn start_p2p_session_reports_input_queue_allocation_failure() {
         let err = single_local_builder()
             .with_input_queue_config(InputQueueConfig {
                 queue_length: usize::MAX,
+                ..InputQueueConfig::default()
             })
             .start_p2p_session(DummySocket)
             .unwrap_err();
@@ -3152,6 +5603,80 @@ fn presets_are_chainable_with_other_methods() {
         assert_eq!(builder.desync_detection, DesyncDetection::Off);
     }
 
+    #[test]
+    fn preset_applies_every_setting() {
+        for preset in SessionPreset::ALL {
+            let builder = SessionBuilder::<TestConfig>::new().preset(preset);
+
+            assert_eq!(builder.preset_settings(), preset.settings(), "{preset:?}");
+            assert_eq!(
+                builder.describe(),
+                format!("preset: {preset:?}; overrides: none")
+            );
+        }
+    }
+
+    #[test]
+    fn preset_starts_valid_sessions() {
+        for preset in SessionPreset::ALL {
+            let builder = SessionBuilder::<TestConfig>::new()
+                .preset(preset)
+                .add_local_player(0)
+                .unwrap()
+                .add_remote_player(1, test_addr(7000))
+                .unwrap();
+
+            builder
+                .clone()
+                .start_p2p_session(DummySocket)
+                .unwrap_or_else(|err| panic!("{preset:?}: {err}"));
+            // Lockstep never rolls back, so there is nothing to sync-test.
+            if preset.settings().max_prediction > 0 {
+                builder
+                    .start_synctest_session()
+                    .unwrap_or_else(|err| panic!("{preset:?}: {err}"));
+            }
+        }
+    }
+
+    #[test]
+    fn later_builder_calls_override_the_preset() {
+        let builder = SessionBuilder::<TestConfig>::new()
+            .preset(SessionPreset::Fighting60)
+            .with_input_delay(4)
+            .unwrap()
+            .with_save_mode(SaveMode::Sparse);
+
+        assert_eq!(builder.input_delay, 4);
+        assert_eq!(builder.save_mode, SaveMode::Sparse);
+        assert_eq!(builder.sync_config, SyncConfig::competitive());
+        assert_eq!(
+            builder.describe(),
+            "preset: Fighting60; overrides: input_delay = 4, save_mode = Sparse"
+        );
+    }
+
+    #[test]
+    fn later_preset_replaces_earlier_preset() {
+        let builder = SessionBuilder::<TestConfig>::new()
+            .preset(SessionPreset::Fighting60)
+            .with_input_delay(4)
+            .unwrap()
+            .preset(SessionPreset::Rts30);
+
+        assert_eq!(builder.preset_settings(), SessionPreset::Rts30.settings());
+        assert_eq!(builder.describe(), "preset: Rts30; overrides: none");
+    }
+
+    #[test]
+    fn describe_without_preset_is_relative_to_defaults() {
+        let builder = SessionBuilder::<TestConfig>::new();
+        assert_eq!(builder.describe(), "preset: none; overrides: none");
+
+        let builder = builder.with_fps(30).unwrap();
+        assert_eq!(builder.describe(), "preset: none; overrides: fps = 30");
+    }
+
     #[test]
     fn builder_start_replay_session_with_validation() {
         use crate::replay::{Replay, ReplayMetadata};
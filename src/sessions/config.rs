@@ -12,7 +12,7 @@
 //! | `ProtocolConfig` | Network protocol settings | `debug()`, `mobile()` |
 //! | `SpectatorConfig` | Spectator session behavior | `broadcast()`, `fast_paced()` |
 //! | `InputQueueConfig` | Input queue sizing | `high_latency()`, `minimal()` |
-//! | `SaveMode` | Game state save strategy | `EveryFrame`, `Sparse` |
+//! | `SaveMode` | Game state save strategy | `EveryFrame`, `Sparse`, `Pyramid` |
 //!
 //! # Example
 //!
@@ -38,6 +38,21 @@
 use crate::input_queue::INPUT_QUEUE_LENGTH;
 use crate::{FortressError, InvalidRequestKind};
 
+/// Default for [`ProtocolConfig::max_suppressed_frames`]: half the default
+/// prediction window, so an idle peer's confirmation trails by at most four
+/// frames beyond network latency.
+const DEFAULT_MAX_SUPPRESSED_FRAMES: usize = 4;
+
+/// Default for [`ProtocolConfig::reliable_window`].
+const DEFAULT_RELIABLE_WINDOW: usize = 16;
+
+/// Default for [`ProtocolConfig::max_reliable_payload`].
+const DEFAULT_MAX_RELIABLE_PAYLOAD: usize = 256;
+
+/// Default for [`ProtocolConfig::transport_error_threshold`]: a few polls'
+/// worth of sends, so a dead interface is reported within a handful of frames.
+const DEFAULT_TRANSPORT_ERROR_THRESHOLD: u32 = 10;
+
 fn usize_to_u64_saturating(value: usize) -> u64 {
     u64::try_from(value).unwrap_or(u64::MAX)
 }
@@ -366,6 +381,28 @@ pub fn stress_test() -> Self {
             keepalive_interval: Duration::from_millis(150),
         }
     }
+
+    /// Validates the sync configuration.
+    ///
+    /// A single roundtrip (`num_sync_packets: 1`) is allowed; it suits links
+    /// the application has already verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FortressError`] if [`num_sync_packets`](Self::num_sync_packets)
+    /// is `0`.
+    pub fn validate(&self) -> Result<(), FortressError> {
+        if self.num_sync_packets == 0 {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "sync_config.num_sync_packets",
+                min: 1,
+                max: u64::from(u32::MAX),
+                actual: 0,
+            }
+            .into());
+        }
+        Ok(())
+    }
 }
 
 /// Configuration for network protocol behavior.
@@ -470,6 +507,267 @@ pub struct ProtocolConfig {
     /// Default: 2
     pub input_history_multiplier: usize,
 
+    /// Skip the immediate `Input` send when a new local frame serializes to the
+    /// same bytes as the frame before it.
+    ///
+    /// Idle periods (menus, waiting for a round to start) otherwise cost one
+    /// packet per frame even though the delta-encoded payload is nearly empty.
+    /// With suppression enabled, identical frames stay in the pending-output
+    /// batch and reach the peer through the next non-identical frame, the
+    /// running retry interval, or the forced send after
+    /// [`max_suppressed_frames`](Self::max_suppressed_frames) consecutive
+    /// suppressed frames — whichever comes first. A change in the gossiped
+    /// connection status always sends immediately.
+    ///
+    /// Silence on the wire is still bounded by
+    /// [`SyncConfig::keepalive_interval`]: a keepalive goes out whenever no
+    /// packet was sent for that long, so the peer's disconnect timers keep
+    /// running off real traffic. Session construction therefore requires
+    /// `keepalive_interval` to be shorter than the disconnect notify delay, and
+    /// `max_suppressed_frames` to be smaller than the prediction window so the
+    /// peer's confirmation never stalls on a suppressed frame.
+    ///
+    /// Default: `false`
+    pub suppress_redundant_inputs: bool,
+
+    /// Maximum number of consecutive identical frames whose immediate send is
+    /// suppressed before one is sent anyway.
+    ///
+    /// Only consulted when
+    /// [`suppress_redundant_inputs`](Self::suppress_redundant_inputs) is set.
+    /// Bounds how far the peer's confirmed frame can trail this endpoint while
+    /// idle, and guarantees acknowledgements and connection-status gossip keep
+    /// flowing. Must be at least 1 and, while suppression is enabled, smaller
+    /// than [`pending_output_limit`](Self::pending_output_limit).
+    ///
+    /// Default: 4
+    pub max_suppressed_frames: usize,
+
+    /// Pending-output depth above which `Input` packets are sent in a
+    /// self-contained resync encoding instead of delta-encoded against the
+    /// last acknowledged frame.
+    ///
+    /// Inputs are delta-encoded against the newest frame the peer has
+    /// acknowledged. If acknowledgements are lost in one direction for a
+    /// while, that reference falls behind, every packet re-sends the whole
+    /// unacknowledged batch, and a peer that has since pruned the reference
+    /// from its input history cannot decode the packets at all. Once more
+    /// than this many frames are pending, each packet is instead encoded
+    /// against a blank frame, so the peer can decode it without any history;
+    /// normal delta encoding resumes as soon as an acknowledgement trims the
+    /// backlog back to the threshold.
+    ///
+    /// Set it below the peer's input history (`input_history_multiplier` ×
+    /// the prediction window, 16 frames by default) so resync starts before
+    /// the reference can be pruned. Must be at least 1 and smaller than
+    /// [`pending_output_limit`](Self::pending_output_limit).
+    ///
+    /// The resync encoding is negotiated during the handshake and only used
+    /// when both peers enable it. Peers running a version without this
+    /// feature fail the handshake against an endpoint that enables it, so
+    /// only turn it on once every peer has been upgraded.
+    ///
+    /// Default: `None` (disabled)
+    pub input_resync_threshold: Option<usize>,
+
+    /// Minimum number of the newest input frames every `Input` packet carries,
+    /// acknowledged or not.
+    ///
+    /// Every packet already carries all unacknowledged frames. With a value
+    /// above 1, frames the peer has acknowledged are resent as well until the
+    /// packet covers this many, so any single packet that arrives holds the
+    /// last `redundant_input_frames` frames on its own. The extra frames are
+    /// delta-encoded like the rest, so steady inputs cost little; the overhead
+    /// is reported by
+    /// [`PeerMetrics::redundant_input_frames_sent`](crate::metrics::PeerMetrics::redundant_input_frames_sent).
+    /// Receivers skip frames they already have, so peers need not agree on
+    /// the value. A packet never reaches further back than the peer's input
+    /// history (`input_history_multiplier` × the prediction window).
+    ///
+    /// Must be between 1 and
+    /// [`pending_output_limit`](Self::pending_output_limit).
+    ///
+    /// Default: `1` (no acknowledged frames are resent)
+    pub redundant_input_frames: usize,
+
+    /// Maximum number of messages each endpoint hands to the socket per send.
+    ///
+    /// An endpoint sends its queued messages once per
+    /// [`poll_remote_clients`](crate::P2PSession::poll_remote_clients) (and
+    /// when a local input is added). Messages beyond this budget stay queued
+    /// for the next send, drained handshake first, then keepalives and quality
+    /// reports, then input acknowledgements, then inputs and other control
+    /// messages. Use it together with
+    /// [`max_bytes_per_second`](Self::max_bytes_per_second) when the socket is
+    /// shared with other traffic and bursts of rollback traffic would
+    /// otherwise cause self-induced loss. The current backlog is reported by
+    /// [`NetworkStats::send_backlog_len`](crate::NetworkStats::send_backlog_len).
+    ///
+    /// With a limit set, queued `Input` messages carry the newest input
+    /// acknowledgement and replace standalone acknowledgements, so a peer that
+    /// keeps receiving inputs still gets its own inputs out. While messages are
+    /// held back, an `Input` message is dropped in favor of a later one (each
+    /// carries every unacknowledged frame), and only the newest held
+    /// acknowledgement and keepalive are kept, so the backlog stays small
+    /// under sustained pressure.
+    ///
+    /// Must be at least 1. Any limit also requires
+    /// [`SyncConfig::keepalive_interval`] to be shorter than the disconnect
+    /// notify delay, so keepalives still refresh the peer's disconnect timers,
+    /// and the session must be polled at least once per keepalive interval.
+    ///
+    /// Default: `usize::MAX` (unlimited)
+    pub max_packets_per_poll: usize,
+
+    /// Maximum average outbound bytes per second for each endpoint.
+    ///
+    /// A token bucket holding up to one second of budget refills at this rate;
+    /// an endpoint sends while the bucket is not empty, and each message's
+    /// encoded size is taken from it, so a message larger than the remaining
+    /// budget still goes out and delays the following ones until the debt is
+    /// repaid. Held messages stay queued as for
+    /// [`max_packets_per_poll`](Self::max_packets_per_poll).
+    ///
+    /// The rate must refill a full unfragmented UDP payload
+    /// ([`MAX_UNFRAGMENTED_PACKET_BYTES`](Self::MAX_UNFRAGMENTED_PACKET_BYTES))
+    /// within [`SyncConfig::keepalive_interval`]; session construction rejects
+    /// slower rates, since the peer would otherwise go silent long enough to
+    /// trigger its disconnect timers. With the default 200 ms keepalive
+    /// interval the minimum is 7360 bytes per second. Larger messages, such as
+    /// hot-join state snapshots, delay later traffic in proportion to their
+    /// size, so relax the disconnect timeouts accordingly.
+    ///
+    /// Default: `None` (unlimited)
+    pub max_bytes_per_second: Option<u64>,
+
+    /// Enables frame tags: small, frame-indexed payloads sent with
+    /// [`P2PSession::send_frame_tag`](crate::P2PSession::send_frame_tag) and
+    /// delivered to the remote peers as
+    /// [`FortressEvent::FrameTag`](crate::FortressEvent::FrameTag).
+    ///
+    /// Tags ride on the `Input` messages and are resent with them until the
+    /// peer acknowledges the input they were sent with, so they need no
+    /// traffic of their own. Like
+    /// [`input_resync_threshold`](Self::input_resync_threshold), frame tags
+    /// are negotiated during the handshake and only carried between peers
+    /// that both enable them; peers running a version without this feature
+    /// fail the handshake against an endpoint that enables it.
+    ///
+    /// Default: `false`
+    pub frame_tags: bool,
+
+    /// Maximum reliable-channel messages in flight to each peer.
+    ///
+    /// Messages sent with
+    /// [`P2PSession::send_reliable`](crate::P2PSession::send_reliable) are
+    /// numbered, resent every [`SyncConfig::running_retry_interval`] until the
+    /// peer acknowledges them, and delivered in order as
+    /// [`FortressEvent::ReliableMessage`](crate::FortressEvent::ReliableMessage).
+    /// Once this many are unacknowledged, `send_reliable` fails with
+    /// [`InvalidRequestKind::ReliableWindowFull`] until acknowledgements
+    /// arrive. The receiver buffers at most this many messages that arrive
+    /// ahead of a missing one, so both peers should use the same value.
+    ///
+    /// Must be between 1 and
+    /// [`MAX_RELIABLE_WINDOW`](Self::MAX_RELIABLE_WINDOW).
+    ///
+    /// Default: `16`
+    pub reliable_window: usize,
+
+    /// Maximum payload bytes of one reliable-channel message.
+    ///
+    /// The channel is meant for small control messages, such as a character
+    /// selection or an agreed match result, and every message travels in a
+    /// single packet. Larger payloads are rejected with
+    /// [`InvalidRequestKind::ReliablePayloadTooLarge`].
+    ///
+    /// Must be between 1 and
+    /// [`MAX_RELIABLE_PAYLOAD`](Self::MAX_RELIABLE_PAYLOAD).
+    ///
+    /// Default: `256`
+    pub max_reliable_payload: usize,
+
+    /// Gives each remote endpoint its own prediction allowance, derived from
+    /// its measured round-trip time.
+    ///
+    /// By default the session may run up to the prediction window
+    /// ([`SessionBuilder::with_max_prediction_window`](crate::SessionBuilder::with_max_prediction_window))
+    /// frames ahead of every remote player's confirmed input, so one
+    /// high-latency link sets how deep every rollback can get. With
+    /// `Some(base)`, an endpoint may only be predicted `base` frames plus the
+    /// frames that fit in its round-trip time, capped at the prediction
+    /// window: a nearby peer that stops sending stalls the session after a
+    /// few frames, while a distant one keeps the allowance it needs. The
+    /// session stops advancing as soon as any endpoint reaches its allowance;
+    /// [`P2PSession::prediction_limit_for`](crate::P2PSession::prediction_limit_for)
+    /// reports the current value.
+    ///
+    /// The prediction window still sizes the saved states and stays the hard
+    /// cap. Must be at least 1.
+    ///
+    /// Default: `None` (every endpoint gets the full prediction window)
+    pub per_peer_prediction_limit: Option<usize>,
+
+    /// Piggybacks the newest input acknowledgement on keepalive messages.
+    ///
+    /// A peer drains its buffered inputs only when the other side acknowledges
+    /// them, and acknowledgements normally ride on that side's own `Input`
+    /// messages or on a dedicated `InputAck`. When inputs flow in one
+    /// direction only (a host streaming to a spectator, or a peer that
+    /// suppresses redundant inputs) and those acks are lost, the sender's
+    /// buffer grows until the next ack happens to arrive. With this enabled,
+    /// every keepalive carries the newest acknowledgement and this peer's
+    /// connect status, and a keepalive is sent once per keepalive interval
+    /// whenever no input carried them, even while unrelated traffic keeps the
+    /// link busy.
+    ///
+    /// Set to `false` to send keepalives without an acknowledgement, as
+    /// earlier versions did. Peers apply a received keepalive ack either way.
+    ///
+    /// Default: `true`
+    pub keep_alive_acks: bool,
+
+    /// Numbers every message sent to a peer and drops duplicate and very
+    /// stale messages on receipt.
+    ///
+    /// Networks that duplicate datagrams otherwise make the receiver process
+    /// some messages twice: a duplicated quality report is answered twice,
+    /// and the late second answer inflates the measured round-trip time. With
+    /// this enabled, each message header carries a wrapping sequence number,
+    /// and the receiver remembers which of the newest 256 numbers it has seen
+    /// from the peer. A number it has already seen is dropped as a duplicate,
+    /// and one more than 256 behind the newest is dropped as stale; both are
+    /// counted in [`NetworkStats`](crate::NetworkStats). Every resend of an
+    /// unacknowledged input is a new message with a new number, so input
+    /// handling is unchanged.
+    ///
+    /// Sequence numbers are negotiated during the handshake and only carried
+    /// between peers that both enable them, adding four bytes to every
+    /// message.
+    ///
+    /// Default: `false`
+    pub sequence_numbers: bool,
+
+    /// Failed sends in a row to one peer after which the session emits
+    /// [`FortressEvent::TransportError`](crate::FortressEvent::TransportError).
+    ///
+    /// Endpoints send through
+    /// [`NonBlockingSocket::try_send_to`](crate::NonBlockingSocket::try_send_to)
+    /// and count the sends the socket refuses, for instance while the network
+    /// interface is down. When the count reaches this value the event fires
+    /// once; a successful send resets the count. Failed messages are not
+    /// retried, but unacknowledged inputs are resent with every `Input`
+    /// message as usual, so the session recovers by itself once sends succeed.
+    ///
+    /// The event usually arrives well before
+    /// [`FortressEvent::NetworkInterrupted`](crate::FortressEvent::NetworkInterrupted):
+    /// an endpoint sends several messages per poll. Sockets that keep the
+    /// default `try_send_to` never report failures. `None` disables the
+    /// event; `Some(0)` is rejected.
+    ///
+    /// Default: `Some(10)`
+    pub transport_error_threshold: Option<u32>,
+
     /// Optional seed for protocol RNG, enabling deterministic behavior.
     ///
     /// When set to `Some(seed)`, the protocol will use a deterministic RNG seeded
@@ -555,6 +853,19 @@ fn eq(&self, other: &Self) -> bool {
             sync_retry_warning_threshold,
             sync_duration_warning_ms,
             input_history_multiplier,
+            suppress_redundant_inputs,
+            max_suppressed_frames,
+            input_resync_threshold,
+            redundant_input_frames,
+            max_packets_per_poll,
+            max_bytes_per_second,
+            frame_tags,
+            reliable_window,
+            max_reliable_payload,
+            per_peer_prediction_limit,
+            keep_alive_acks,
+            sequence_numbers,
+            transport_error_threshold,
             protocol_rng_seed,
             clock,
         } = self;
@@ -565,6 +876,19 @@ fn eq(&self, other: &Self) -> bool {
             && *sync_retry_warning_threshold == other.sync_retry_warning_threshold
             && *sync_duration_warning_ms == other.sync_duration_warning_ms
             && *input_history_multiplier == other.input_history_multiplier
+            && *suppress_redundant_inputs == other.suppress_redundant_inputs
+            && *max_suppressed_frames == other.max_suppressed_frames
+            && *input_resync_threshold == other.input_resync_threshold
+            && *redundant_input_frames == other.redundant_input_frames
+            && *max_packets_per_poll == other.max_packets_per_poll
+            && *max_bytes_per_second == other.max_bytes_per_second
+            && *frame_tags == other.frame_tags
+            && *reliable_window == other.reliable_window
+            && *max_reliable_payload == other.max_reliable_payload
+            && *per_peer_prediction_limit == other.per_peer_prediction_limit
+            && *keep_alive_acks == other.keep_alive_acks
+            && *sequence_numbers == other.sequence_numbers
+            && *transport_error_threshold == other.transport_error_threshold
             && *protocol_rng_seed == other.protocol_rng_seed
             && clock.is_some() == other.clock.is_some()
     }
@@ -583,6 +907,19 @@ fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
             sync_retry_warning_threshold,
             sync_duration_warning_ms,
             input_history_multiplier,
+            suppress_redundant_inputs,
+            max_suppressed_frames,
+            input_resync_threshold,
+            redundant_input_frames,
+            max_packets_per_poll,
+            max_bytes_per_second,
+            frame_tags,
+            reliable_window,
+            max_reliable_payload,
+            per_peer_prediction_limit,
+            keep_alive_acks,
+            sequence_numbers,
+            transport_error_threshold,
             protocol_rng_seed,
             clock,
         } = self;
@@ -593,6 +930,19 @@ fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         sync_retry_warning_threshold.hash(state);
         sync_duration_warning_ms.hash(state);
         input_history_multiplier.hash(state);
+        suppress_redundant_inputs.hash(state);
+        max_suppressed_frames.hash(state);
+        input_resync_threshold.hash(state);
+        redundant_input_frames.hash(state);
+        max_packets_per_poll.hash(state);
+        max_bytes_per_second.hash(state);
+        frame_tags.hash(state);
+        reliable_window.hash(state);
+        max_reliable_payload.hash(state);
+        per_peer_prediction_limit.hash(state);
+        keep_alive_acks.hash(state);
+        sequence_numbers.hash(state);
+        transport_error_threshold.hash(state);
         protocol_rng_seed.hash(state);
         clock.is_some().hash(state);
     }
@@ -611,6 +961,19 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             )
             .field("sync_duration_warning_ms", &self.sync_duration_warning_ms)
             .field("input_history_multiplier", &self.input_history_multiplier)
+            .field("suppress_redundant_inputs", &self.suppress_redundant_inputs)
+            .field("max_suppressed_frames", &self.max_suppressed_frames)
+            .field("input_resync_threshold", &self.input_resync_threshold)
+            .field("redundant_input_frames", &self.redundant_input_frames)
+            .field("max_packets_per_poll", &self.max_packets_per_poll)
+            .field("max_bytes_per_second", &self.max_bytes_per_second)
+            .field("frame_tags", &self.frame_tags)
+            .field("reliable_window", &self.reliable_window)
+            .field("max_reliable_payload", &self.max_reliable_payload)
+            .field("per_peer_prediction_limit", &self.per_peer_prediction_limit)
+            .field("keep_alive_acks", &self.keep_alive_acks)
+            .field("sequence_numbers", &self.sequence_numbers)
+            .field("transport_error_threshold", &self.transport_error_threshold)
             .field("protocol_rng_seed", &self.protocol_rng_seed)
             .field(
                 "clock",
@@ -634,6 +997,19 @@ fn default() -> Self {
             sync_retry_warning_threshold: 10,
             sync_duration_warning_ms: 3000,
             input_history_multiplier: 2,
+            suppress_redundant_inputs: false,
+            max_suppressed_frames: DEFAULT_MAX_SUPPRESSED_FRAMES,
+            input_resync_threshold: None,
+            redundant_input_frames: 1,
+            max_packets_per_poll: usize::MAX,
+            max_bytes_per_second: None,
+            frame_tags: false,
+            reliable_window: DEFAULT_RELIABLE_WINDOW,
+            max_reliable_payload: DEFAULT_MAX_RELIABLE_PAYLOAD,
+            per_peer_prediction_limit: None,
+            keep_alive_acks: true,
+            sequence_numbers: false,
+            transport_error_threshold: Some(DEFAULT_TRANSPORT_ERROR_THRESHOLD),
             protocol_rng_seed: None,
             clock: None,
         }
@@ -651,13 +1027,26 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             sync_retry_warning_threshold,
             sync_duration_warning_ms,
             input_history_multiplier,
+            suppress_redundant_inputs,
+            max_suppressed_frames,
+            input_resync_threshold,
+            redundant_input_frames,
+            max_packets_per_poll,
+            max_bytes_per_second,
+            frame_tags,
+            reliable_window,
+            max_reliable_payload,
+            per_peer_prediction_limit,
+            keep_alive_acks,
+            sequence_numbers,
+            transport_error_threshold,
             protocol_rng_seed,
             clock,
         } = self;
 
         write!(
             f,
-            "ProtocolConfig {{ quality_report: {:?}, shutdown: {:?}, checksum_history: {}, pending_limit: {}, retry_warn: {}, duration_warn_ms: {}, history_mult: {}, seed: {}, clock: {} }}",
+            "ProtocolConfig {{ quality_report: {:?}, shutdown: {:?}, checksum_history: {}, pending_limit: {}, retry_warn: {}, duration_warn_ms: {}, history_mult: {}, suppress_redundant: {}, max_suppressed: {}, resync_threshold: {:?}, redundant_inputs: {}, max_packets: {}, max_bytes_per_sec: {:?}, frame_tags: {}, reliable_window: {}, max_reliable_payload: {}, per_peer_prediction: {:?}, keep_alive_acks: {}, sequence_numbers: {}, transport_error_threshold: {:?}, seed: {}, clock: {} }}",
             quality_report_interval,
             shutdown_delay,
             max_checksum_history,
@@ -665,6 +1054,19 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             sync_retry_warning_threshold,
             sync_duration_warning_ms,
             input_history_multiplier,
+            suppress_redundant_inputs,
+            max_suppressed_frames,
+            input_resync_threshold,
+            redundant_input_frames,
+            max_packets_per_poll,
+            max_bytes_per_second,
+            frame_tags,
+            reliable_window,
+            max_reliable_payload,
+            per_peer_prediction_limit,
+            keep_alive_acks,
+            sequence_numbers,
+            transport_error_threshold,
             protocol_rng_seed.map_or_else(|| "None".to_string(), |s| s.to_string()),
             if clock.is_some() { "custom" } else { "system" },
         )
@@ -681,6 +1083,18 @@ impl ProtocolConfig {
     pub const MAX_PENDING_OUTPUT_LIMIT: usize =
         crate::network::compression::MAX_DELTA_DECODED_FRAMES;
 
+    /// The largest UDP payload that crosses an IPv4 link with a 1500-byte MTU
+    /// without fragmenting. [`max_bytes_per_second`](Self::max_bytes_per_second)
+    /// must refill at least this many bytes per keepalive interval.
+    pub const MAX_UNFRAGMENTED_PACKET_BYTES: usize = 1472;
+
+    /// Maximum allowed [`ProtocolConfig::reliable_window`].
+    pub const MAX_RELIABLE_WINDOW: usize = 256;
+
+    /// Maximum allowed [`ProtocolConfig::max_reliable_payload`], so a reliable
+    /// message always fits in an unfragmented packet.
+    pub const MAX_RELIABLE_PAYLOAD: usize = 1024;
+
     /// Creates a new `ProtocolConfig` with default values.
     pub fn new() -> Self {
         Self::default()
@@ -699,6 +1113,19 @@ pub fn competitive() -> Self {
             sync_retry_warning_threshold: 10,
             sync_duration_warning_ms: 2000,
             input_history_multiplier: 2,
+            suppress_redundant_inputs: false,
+            max_suppressed_frames: DEFAULT_MAX_SUPPRESSED_FRAMES,
+            input_resync_threshold: None,
+            redundant_input_frames: 1,
+            max_packets_per_poll: usize::MAX,
+            max_bytes_per_second: None,
+            frame_tags: false,
+            reliable_window: DEFAULT_RELIABLE_WINDOW,
+            max_reliable_payload: DEFAULT_MAX_RELIABLE_PAYLOAD,
+            per_peer_prediction_limit: None,
+            keep_alive_acks: true,
+            sequence_numbers: false,
+            transport_error_threshold: Some(DEFAULT_TRANSPORT_ERROR_THRESHOLD),
             protocol_rng_seed: None,
             clock: None,
         }
@@ -717,6 +1144,19 @@ pub fn high_latency() -> Self {
             sync_retry_warning_threshold: 20,
             sync_duration_warning_ms: 10000,
             input_history_multiplier: 3,
+            suppress_redundant_inputs: false,
+            max_suppressed_frames: DEFAULT_MAX_SUPPRESSED_FRAMES,
+            input_resync_threshold: None,
+            redundant_input_frames: 1,
+            max_packets_per_poll: usize::MAX,
+            max_bytes_per_second: None,
+            frame_tags: false,
+            reliable_window: DEFAULT_RELIABLE_WINDOW,
+            max_reliable_payload: DEFAULT_MAX_RELIABLE_PAYLOAD,
+            per_peer_prediction_limit: None,
+            keep_alive_acks: true,
+            sequence_numbers: false,
+            transport_error_threshold: Some(DEFAULT_TRANSPORT_ERROR_THRESHOLD),
             protocol_rng_seed: None,
             clock: None,
         }
@@ -735,6 +1175,19 @@ pub fn debug() -> Self {
             sync_retry_warning_threshold: 5,
             sync_duration_warning_ms: 1000,
             input_history_multiplier: 4,
+            suppress_redundant_inputs: false,
+            max_suppressed_frames: DEFAULT_MAX_SUPPRESSED_FRAMES,
+            input_resync_threshold: None,
+            redundant_input_frames: 1,
+            max_packets_per_poll: usize::MAX,
+            max_bytes_per_second: None,
+            frame_tags: false,
+            reliable_window: DEFAULT_RELIABLE_WINDOW,
+            max_reliable_payload: DEFAULT_MAX_RELIABLE_PAYLOAD,
+            per_peer_prediction_limit: None,
+            keep_alive_acks: true,
+            sequence_numbers: false,
+            transport_error_threshold: Some(DEFAULT_TRANSPORT_ERROR_THRESHOLD),
             protocol_rng_seed: None,
             clock: None,
         }
@@ -766,6 +1219,19 @@ pub fn mobile() -> Self {
             sync_duration_warning_ms: 12000,
             // More history for packet reordering on mobile
             input_history_multiplier: 3,
+            suppress_redundant_inputs: false,
+            max_suppressed_frames: DEFAULT_MAX_SUPPRESSED_FRAMES,
+            input_resync_threshold: None,
+            redundant_input_frames: 1,
+            max_packets_per_poll: usize::MAX,
+            max_bytes_per_second: None,
+            frame_tags: false,
+            reliable_window: DEFAULT_RELIABLE_WINDOW,
+            max_reliable_payload: DEFAULT_MAX_RELIABLE_PAYLOAD,
+            per_peer_prediction_limit: None,
+            keep_alive_acks: true,
+            sequence_numbers: false,
+            transport_error_threshold: Some(DEFAULT_TRANSPORT_ERROR_THRESHOLD),
             protocol_rng_seed: None,
             clock: None,
         }
@@ -887,8 +1353,122 @@ pub fn validate(&self) -> Result<(), FortressError> {
             .into());
         }
 
+        // Validate max_suppressed_frames: a forced send must eventually happen,
+        // and a suppressed batch must never reach the pending-output disconnect
+        // threshold.
+        let max_suppressed = if self.suppress_redundant_inputs {
+            self.pending_output_limit.saturating_sub(1)
+        } else {
+            usize::MAX
+        };
+        if !(1..=max_suppressed).contains(&self.max_suppressed_frames) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_suppressed_frames",
+                min: 1,
+                max: usize_to_u64_saturating(max_suppressed),
+                actual: usize_to_u64_saturating(self.max_suppressed_frames),
+            }
+            .into());
+        }
+
+        // Validate input_resync_threshold: resync packets must start before the
+        // backlog reaches the pending-output disconnect threshold.
+        if let Some(threshold) = self.input_resync_threshold {
+            let max_threshold = self.pending_output_limit.saturating_sub(1);
+            if !(1..=max_threshold).contains(&threshold) {
+                return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "input_resync_threshold",
+                    min: 1,
+                    max: usize_to_u64_saturating(max_threshold),
+                    actual: usize_to_u64_saturating(threshold),
+                }
+                .into());
+            }
+        }
+
+        // Validate redundant_input_frames: a packet carries at least one frame
+        // and never more than a batch.
+        if !(1..=self.pending_output_limit).contains(&self.redundant_input_frames) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "redundant_input_frames",
+                min: 1,
+                max: usize_to_u64_saturating(self.pending_output_limit),
+                actual: usize_to_u64_saturating(self.redundant_input_frames),
+            }
+            .into());
+        }
+
+        // Validate the send limits: every send must make progress.
+        if self.max_packets_per_poll < 1 {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_packets_per_poll",
+                min: 1,
+                max: usize_to_u64_saturating(usize::MAX),
+                actual: 0,
+            }
+            .into());
+        }
+        if self.max_bytes_per_second == Some(0) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_bytes_per_second",
+                min: 1,
+                max: u64::MAX,
+                actual: 0,
+            }
+            .into());
+        }
+
+        // Validate the reliable channel: room for at least one message.
+        if !(1..=Self::MAX_RELIABLE_WINDOW).contains(&self.reliable_window) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "reliable_window",
+                min: 1,
+                max: usize_to_u64_saturating(Self::MAX_RELIABLE_WINDOW),
+                actual: usize_to_u64_saturating(self.reliable_window),
+            }
+            .into());
+        }
+        if !(1..=Self::MAX_RELIABLE_PAYLOAD).contains(&self.max_reliable_payload) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_reliable_payload",
+                min: 1,
+                max: usize_to_u64_saturating(Self::MAX_RELIABLE_PAYLOAD),
+                actual: usize_to_u64_saturating(self.max_reliable_payload),
+            }
+            .into());
+        }
+
+        // Validate per_peer_prediction_limit: every endpoint may be predicted
+        // at least one frame.
+        if self.per_peer_prediction_limit == Some(0) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "per_peer_prediction_limit",
+                min: 1,
+                max: usize_to_u64_saturating(usize::MAX),
+                actual: 0,
+            }
+            .into());
+        }
+
+        // Validate transport_error_threshold: the event needs at least one
+        // failed send.
+        if self.transport_error_threshold == Some(0) {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "transport_error_threshold",
+                min: 1,
+                max: u64::from(u32::MAX),
+                actual: 0,
+            }
+            .into());
+        }
+
         Ok(())
     }
+
+    /// Whether either send limit is set.
+    pub(crate) fn limits_sends(&self) -> bool {
+        self.max_packets_per_poll != usize::MAX || self.max_bytes_per_second.is_some()
+    }
 }
 
 /// Configuration for spectator sessions.
@@ -930,7 +1510,7 @@ pub fn validate(&self) -> Result<(), FortressError> {
 ///     ..SpectatorConfig::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[must_use = "SpectatorConfig has no effect unless passed to SessionBuilder::with_spectator_config()"]
 pub struct SpectatorConfig {
     /// The number of frames of input that the spectator can buffer.
@@ -996,6 +1576,126 @@ pub struct SpectatorConfig {
     /// [`SaveGameState`]: crate::FortressRequest::SaveGameState
     /// [`seek_to_frame`]: crate::SpectatorSession::seek_to_frame
     pub enable_rewind: bool,
+
+    /// Keyframe interval for the compressed host-to-spectator input stream.
+    ///
+    /// By default the host sends every spectator the full input of every
+    /// player for each frame, so spectator bandwidth grows with player count.
+    /// With a non-zero interval, the host sends the full input set only on
+    /// frames that are a multiple of this value and, in between, only the
+    /// players whose input changed (a slot bitmask plus the changed slots).
+    /// The spectator rebuilds the full per-frame inputs before they reach its
+    /// input queues, so playback is unaffected.
+    ///
+    /// The mode is negotiated in the sync handshake and requires both sides
+    /// to opt in: on the host, this value sets the interval used for every
+    /// spectator that asked for the compressed stream; on a spectator, any
+    /// non-zero value asks the host for it. A host that leaves this at `0`
+    /// keeps sending the current format, including to spectators that asked.
+    /// Hosts from releases without this mode reject the handshake of a
+    /// spectator that asks, so only opt a spectator in when its hosts support it.
+    ///
+    /// Default: 0 (disabled)
+    pub keyframe_interval: usize,
+
+    /// How long the spectator waits for a missing frame's inputs, while the
+    /// host has already sent later frames, before it emits
+    /// [`FortressEvent::SpectatorInputGap`].
+    ///
+    /// Frames only become playable as a contiguous prefix: inputs that arrive
+    /// out of order wait until every earlier frame has arrived, and the host
+    /// resends every unacknowledged frame on its retry interval, so a gap left
+    /// by a lost or reordered packet normally closes on its own. The event
+    /// reports a gap that outlasts this timeout; the spectator keeps waiting
+    /// and resumes as soon as the missing frame arrives. `None` never emits
+    /// the event.
+    ///
+    /// Out-of-order input packets are buffered per host, up to
+    /// [`buffer_size`](Self::buffer_size) packets. When the buffer is full,
+    /// the packet that has waited longest is evicted, and its frames are
+    /// recovered from the host's retransmission.
+    ///
+    /// Default: `Some(Duration::from_secs(1))`
+    ///
+    /// [`FortressEvent::SpectatorInputGap`]: crate::FortressEvent::SpectatorInputGap
+    pub input_gap_timeout: Option<Duration>,
+
+    /// Shared secret a spectator must prove before the host streams to it.
+    ///
+    /// Host-side only: with a token set, the host ignores every `SyncRequest`
+    /// from a spectator address that does not prove the token, never answers
+    /// it, and sends that address nothing until a valid proof arrives, so a
+    /// peer without the token cannot tell a host is listening. Each ignored
+    /// request is counted in
+    /// [`PeerMetrics::unauthorized_sync_requests`](crate::metrics::PeerMetrics::unauthorized_sync_requests).
+    /// Spectators supply the token with
+    /// [`SessionBuilder::with_spectator_token`](crate::SessionBuilder::with_spectator_token).
+    /// Player connections are unaffected.
+    ///
+    /// The token itself never crosses the wire: each request carries a keyed
+    /// MAC of its random nonce. This is access control, not encryption; the
+    /// stream stays readable to anyone on the path.
+    ///
+    /// Default: `None` (any registered spectator address may connect)
+    pub access_token: Option<[u8; 16]>,
+
+    /// Whether the spectator arbitrates the match's checksums.
+    ///
+    /// Spectator-side only. Each host that registered the spectator sends it
+    /// the checksum reports it sends its own peers, so the hosts need desync
+    /// detection on (see
+    /// [`SessionBuilder::with_desync_detection_mode`](crate::SessionBuilder::with_desync_detection_mode)).
+    /// The spectator also saves its state every frame, like
+    /// [`enable_rewind`](Self::enable_rewind), and fills in the checksum of
+    /// its own simulation. Once every host reported a frame the spectator has
+    /// simulated, it takes the checksum most of them agree on, its own
+    /// breaking ties, and emits
+    /// [`FortressEvent::AuthoritativeDesyncVerdict`] naming every host that
+    /// reported another one. Connect the spectator to each player with
+    /// [`SessionBuilder::start_spectator_session_multi`](crate::SessionBuilder::start_spectator_session_multi)
+    /// to have every player's checksum judged.
+    ///
+    /// The mode is negotiated in the sync handshake. Hosts from releases
+    /// without it reject the handshake of a spectator that asks, so only opt
+    /// a spectator in when its hosts support it. Each host's reports are kept
+    /// up to [`ProtocolConfig::max_checksum_history`](crate::ProtocolConfig::max_checksum_history)
+    /// frames; a frame that some host never reports is skipped once a later
+    /// frame is judged.
+    ///
+    /// Default: false
+    ///
+    /// [`FortressEvent::AuthoritativeDesyncVerdict`]: crate::FortressEvent::AuthoritativeDesyncVerdict
+    pub checksum_authority: bool,
+}
+
+impl std::fmt::Debug for SpectatorConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Destructure to ensure all fields are included when new fields are added.
+        let Self {
+            buffer_size,
+            catchup_speed,
+            max_frames_behind,
+            stream_delay,
+            enable_rewind,
+            keyframe_interval,
+            input_gap_timeout,
+            access_token,
+            checksum_authority,
+        } = self;
+
+        f.debug_struct("SpectatorConfig")
+            .field("buffer_size", buffer_size)
+            .field("catchup_speed", catchup_speed)
+            .field("max_frames_behind", max_frames_behind)
+            .field("stream_delay", stream_delay)
+            .field("enable_rewind", enable_rewind)
+            .field("keyframe_interval", keyframe_interval)
+            .field("input_gap_timeout", input_gap_timeout)
+            // Never print the secret itself.
+            .field("access_token", &access_token.map(|_| "<redacted>"))
+            .field("checksum_authority", checksum_authority)
+            .finish()
+    }
 }
 
 impl Default for SpectatorConfig {
@@ -1006,6 +1706,10 @@ fn default() -> Self {
             max_frames_behind: 10,
             stream_delay: 0,
             enable_rewind: false,
+            keyframe_interval: 0,
+            input_gap_timeout: Some(Duration::from_secs(1)),
+            access_token: None,
+            checksum_authority: false,
         }
     }
 }
@@ -1019,12 +1723,18 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             max_frames_behind,
             stream_delay,
             enable_rewind,
+            keyframe_interval,
+            input_gap_timeout,
+            access_token,
+            checksum_authority,
         } = self;
 
         write!(
             f,
-            "SpectatorConfig {{ buffer: {}, catchup_speed: {}, max_behind: {}, stream_delay: {}, enable_rewind: {} }}",
-            buffer_size, catchup_speed, max_frames_behind, stream_delay, enable_rewind,
+            "SpectatorConfig {{ buffer: {}, catchup_speed: {}, max_behind: {}, stream_delay: {}, enable_rewind: {}, keyframe_interval: {}, input_gap_timeout: {:?}, access_token: {}, checksum_authority: {} }}",
+            buffer_size, catchup_speed, max_frames_behind, stream_delay, enable_rewind, keyframe_interval, input_gap_timeout,
+            if access_token.is_some() { "set" } else { "none" },
+            checksum_authority,
         )
     }
 }
@@ -1075,6 +1785,10 @@ pub fn fast_paced() -> Self {
             max_frames_behind: 15,
             stream_delay: 0,
             enable_rewind: false,
+            keyframe_interval: 0,
+            input_gap_timeout: Some(Duration::from_secs(1)),
+            access_token: None,
+            checksum_authority: false,
         }
     }
 
@@ -1088,6 +1802,10 @@ pub fn slow_connection() -> Self {
             max_frames_behind: 20,
             stream_delay: 0,
             enable_rewind: false,
+            keyframe_interval: 0,
+            input_gap_timeout: Some(Duration::from_secs(1)),
+            access_token: None,
+            checksum_authority: false,
         }
     }
 
@@ -1101,6 +1819,10 @@ pub fn local() -> Self {
             max_frames_behind: 5,
             stream_delay: 0,
             enable_rewind: false,
+            keyframe_interval: 0,
+            input_gap_timeout: Some(Duration::from_secs(1)),
+            access_token: None,
+            checksum_authority: false,
         }
     }
 
@@ -1124,6 +1846,10 @@ pub fn broadcast() -> Self {
             max_frames_behind: 30,
             stream_delay: 0,
             enable_rewind: false,
+            keyframe_interval: 0,
+            input_gap_timeout: Some(Duration::from_secs(1)),
+            access_token: None,
+            checksum_authority: false,
         }
     }
 
@@ -1141,6 +1867,10 @@ pub fn mobile() -> Self {
             max_frames_behind: 25,
             stream_delay: 0,
             enable_rewind: false,
+            keyframe_interval: 0,
+            input_gap_timeout: Some(Duration::from_secs(1)),
+            access_token: None,
+            checksum_authority: false,
         }
     }
 }
@@ -1212,12 +1942,31 @@ pub struct InputQueueConfig {
     ///
     /// Default: 128
     pub queue_length: usize,
+    /// How many [`advance_frame`](crate::P2PSession::advance_frame) calls a
+    /// late remote input is held back before the session sees it (P2P
+    /// sessions only).
+    ///
+    /// A remote input for a frame the session already predicted is held, so
+    /// the prediction stands a little longer and several late corrections
+    /// land together in one rollback instead of many small ones. An input
+    /// for the frame about to be simulated, or a later one, is delivered at
+    /// once together with everything held before it, so the buffer never
+    /// delays a frame that has not been predicted yet. Held inputs are also
+    /// delivered at once, and nothing is held, once a player has disconnected
+    /// or while a drop or hot-join is in progress. See
+    /// [`P2PSession::buffered_remote_inputs`](crate::P2PSession::buffered_remote_inputs).
+    ///
+    /// Must be less than `queue_length`.
+    ///
+    /// Default: 0 (inputs are delivered as soon as they arrive)
+    pub remote_input_buffer_frames: u32,
 }
 
 impl Default for InputQueueConfig {
     fn default() -> Self {
         Self {
             queue_length: INPUT_QUEUE_LENGTH,
+            remote_input_buffer_frames: 0,
         }
     }
 }
@@ -1225,8 +1974,15 @@ fn default() -> Self {
 impl std::fmt::Display for InputQueueConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Destructure to ensure all fields are included when new fields are added.
-        let Self { queue_length } = self;
-        write!(f, "InputQueueConfig {{ queue_length: {} }}", queue_length)
+        let Self {
+            queue_length,
+            remote_input_buffer_frames,
+        } = self;
+        write!(
+            f,
+            "InputQueueConfig {{ queue_length: {}, remote_input_buffer_frames: {} }}",
+            queue_length, remote_input_buffer_frames
+        )
     }
 }
 
@@ -1241,7 +1997,10 @@ pub fn new() -> Self {
     /// Uses a larger queue (256 frames = ~4.3 seconds at 60 FPS) to allow
     /// for higher frame delays and longer rollback windows.
     pub fn high_latency() -> Self {
-        Self { queue_length: 256 }
+        Self {
+            queue_length: 256,
+            ..Self::default()
+        }
     }
 
     /// Configuration for minimal memory usage.
@@ -1249,7 +2008,10 @@ pub fn high_latency() -> Self {
     /// Uses a smaller queue (32 frames = ~0.5 seconds at 60 FPS).
     /// Suitable for games with low latency requirements.
     pub fn minimal() -> Self {
-        Self { queue_length: 32 }
+        Self {
+            queue_length: 32,
+            ..Self::default()
+        }
     }
 
     /// Configuration for standard networks.
@@ -1259,6 +2021,98 @@ pub fn standard() -> Self {
         Self::default()
     }
 
+    /// Queue sized for the given prediction window, input delay, and stall
+    /// tolerance; see [`recommended_queue_length`](Self::recommended_queue_length).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::InputQueueConfig;
+    ///
+    /// // 8 frames of prediction, up to 2 frames of delay, ride out 30-frame stalls.
+    /// let config = InputQueueConfig::recommended(8, 2, 30);
+    /// assert_eq!(config.queue_length, 64);
+    /// assert!(config.queue_length >= InputQueueConfig::min_queue_length(8, 2));
+    /// ```
+    pub fn recommended(
+        max_prediction: usize,
+        max_input_delay: usize,
+        stall_tolerance_frames: usize,
+    ) -> Self {
+        Self {
+            queue_length: Self::recommended_queue_length(
+                max_prediction,
+                max_input_delay,
+                stall_tolerance_frames,
+            ),
+            ..Self::default()
+        }
+    }
+
+    /// The smallest `queue_length` a session accepts for the given
+    /// `max_prediction` and `input_delay`: `max_prediction + input_delay + 1`.
+    ///
+    /// A local input is queued `input_delay` frames ahead and may stay
+    /// unconfirmed for `max_prediction` frames, and the ring needs one more
+    /// slot so its head never catches its tail.
+    /// [`SessionBuilder`](crate::SessionBuilder) rejects anything shorter with
+    /// [`InvalidRequestKind::QueueLengthBelowMinimum`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::InputQueueConfig;
+    ///
+    /// const MIN: usize = InputQueueConfig::min_queue_length(8, 2);
+    /// assert_eq!(MIN, 11);
+    /// ```
+    #[must_use]
+    pub const fn min_queue_length(max_prediction: usize, input_delay: usize) -> usize {
+        max_prediction.saturating_add(input_delay).saturating_add(1)
+    }
+
+    /// A `queue_length` with headroom over
+    /// [`min_queue_length`](Self::min_queue_length).
+    ///
+    /// The formula is `min_queue_length + max_prediction + max_input_delay +
+    /// stall_tolerance_frames`, rounded up to a power of two:
+    /// - the minimum covers our own prediction window and delay;
+    /// - a remote peer may run up to `max_prediction + max_input_delay`
+    ///   frames ahead of us, and its inputs are queued until we catch up;
+    /// - `stall_tolerance_frames` covers a peer that stops sending for that
+    ///   many frames while the other keeps polling.
+    ///
+    /// The power of two keeps sizes in line with the presets. Never less than 2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fortress_rollback::InputQueueConfig;
+    ///
+    /// const LEN: usize = InputQueueConfig::recommended_queue_length(8, 2, 0);
+    /// assert_eq!(LEN, 32); // 11 + 8 + 2 = 21, rounded up
+    /// ```
+    #[must_use]
+    pub const fn recommended_queue_length(
+        max_prediction: usize,
+        max_input_delay: usize,
+        stall_tolerance_frames: usize,
+    ) -> usize {
+        let span = Self::min_queue_length(max_prediction, max_input_delay)
+            .saturating_add(max_prediction)
+            .saturating_add(max_input_delay)
+            .saturating_add(stall_tolerance_frames);
+        let rounded = match span.checked_next_power_of_two() {
+            Some(rounded) => rounded,
+            None => span,
+        };
+        if rounded < 2 {
+            2
+        } else {
+            rounded
+        }
+    }
+
     /// Returns the maximum allowed frame delay for this configuration.
     ///
     /// This is always `queue_length - 1` to ensure the circular buffer
@@ -1288,7 +2142,8 @@ pub fn validate_frame_delay(&self, frame_delay: usize) -> Result<(), FortressErr
     ///
     /// # Errors
     ///
-    /// Returns a [`FortressError`] if `queue_length < 2`.
+    /// Returns a [`FortressError`] if `queue_length < 2` or
+    /// `remote_input_buffer_frames >= queue_length`.
     pub fn validate(&self) -> Result<(), FortressError> {
         if self.queue_length < 2 {
             return Err(InvalidRequestKind::QueueLengthTooSmall {
@@ -1296,6 +2151,17 @@ pub fn validate(&self) -> Result<(), FortressError> {
             }
             .into());
         }
+        let buffer_frames = u64::from(self.remote_input_buffer_frames);
+        let max_buffer_frames = u64::try_from(self.max_frame_delay()).unwrap_or(u64::MAX);
+        if buffer_frames > max_buffer_frames {
+            return Err(InvalidRequestKind::ConfigValueOutOfRange {
+                field: "remote_input_buffer_frames",
+                min: 0,
+                max: max_buffer_frames,
+                actual: buffer_frames,
+            }
+            .into());
+        }
         Ok(())
     }
 }
@@ -1318,6 +2184,11 @@ pub fn validate(&self) -> Result<(), FortressError> {
 ///   - You want to minimize save overhead
 ///   - You can tolerate potentially longer rollbacks
 ///
+/// - **`SaveMode::Pyramid`**: Saves every new frame, but a rollback re-saves only
+///   some of the frames it resimulates. Best when:
+///   - State serialization is expensive and `max_prediction` is large
+///   - Most rollbacks are shallow, but deep ones still happen
+///
 /// # Example
 ///
 /// ```
@@ -1362,7 +2233,46 @@ pub enum SaveMode {
     /// - Saving your game state is expensive (large or complex state)
     /// - Advancing the game state is relatively cheap
     /// - You can tolerate longer rollbacks in exchange for fewer saves
+    ///
+    /// With [`DesyncDetection::On`](crate::DesyncDetection::On), the interval
+    /// counts saved and confirmed frames instead of frame numbers.
     Sparse,
+
+    /// Save every new frame, but only some of the frames a rollback resimulates.
+    ///
+    /// Each newly simulated frame is saved, as with [`EveryFrame`](Self::EveryFrame).
+    /// A rollback then saves again only the `recent` resimulated frames nearest
+    /// the frame it resimulates back up to, plus the older ones at a power-of-two
+    /// distance from it (2, 4, 8, ...). The other resimulated frames drop their
+    /// stale state. A later rollback to one of them loads the newest saved frame
+    /// before it and resimulates the difference, which is less than the frame's
+    /// distance from the present when its state was dropped. With `recent: 2`,
+    /// a 15-frame rollback saves 4 states instead of 14.
+    ///
+    /// Inputs are kept from the newest saved frame at or before the confirmed
+    /// frame, so right after a deep rollback the prediction window can be a few
+    /// frames shorter until confirmation moves past the dropped states.
+    ///
+    /// Use this mode when:
+    /// - Saving your game state is expensive and `max_prediction` is large
+    /// - Rollbacks are usually shallow, so recent frames should load directly
+    Pyramid {
+        /// How many resimulated frames nearest the present are always saved again.
+        recent: u32,
+    },
+}
+
+impl SaveMode {
+    /// Whether a rollback under this mode saves again the resimulated state
+    /// `distance` frames before the frame it resimulates back up to. Sparse
+    /// saving picks its one frame by the confirmed frame instead.
+    pub(crate) fn resaves_at_distance(self, distance: u32) -> bool {
+        match self {
+            Self::EveryFrame => true,
+            Self::Sparse => false,
+            Self::Pyramid { recent } => distance <= recent || distance.is_power_of_two(),
+        }
+    }
 }
 
 impl std::fmt::Display for SaveMode {
@@ -1370,6 +2280,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::EveryFrame => write!(f, "EveryFrame"),
             Self::Sparse => write!(f, "Sparse"),
+            Self::Pyramid { recent } => write!(f, "Pyramid {{ recent: {recent} }}"),
         }
     }
 }
@@ -1507,6 +2418,21 @@ fn test_save_mode_debug_format() {
     fn test_save_mode_display() {
         assert_eq!(SaveMode::EveryFrame.to_string(), "EveryFrame");
         assert_eq!(SaveMode::Sparse.to_string(), "Sparse");
+        assert_eq!(
+            SaveMode::Pyramid { recent: 2 }.to_string(),
+            "Pyramid { recent: 2 }"
+        );
+    }
+
+    #[test]
+    fn test_save_mode_pyramid_resaves_recent_and_power_of_two_distances() {
+        let pyramid = SaveMode::Pyramid { recent: 3 };
+        let resaved: Vec<u32> = (1..=16)
+            .filter(|&distance| pyramid.resaves_at_distance(distance))
+            .collect();
+        assert_eq!(resaved, vec![1, 2, 3, 4, 8, 16]);
+        assert!(SaveMode::EveryFrame.resaves_at_distance(7));
+        assert!(!SaveMode::Sparse.resaves_at_distance(1));
     }
 
     #[test]
@@ -1625,32 +2551,86 @@ fn test_hardcoded_preset_values() {
 
     #[test]
     fn test_input_queue_config_max_frame_delay() {
-        let config = InputQueueConfig { queue_length: 64 };
+        let config = InputQueueConfig {
+            queue_length: 64,
+            ..InputQueueConfig::default()
+        };
         assert_eq!(config.max_frame_delay(), 63);
 
-        let config = InputQueueConfig { queue_length: 128 };
+        let config = InputQueueConfig {
+            queue_length: 128,
+            ..InputQueueConfig::default()
+        };
         assert_eq!(config.max_frame_delay(), 127);
     }
 
     #[test]
     fn test_input_queue_config_validate() {
         // Valid configs
-        assert!(InputQueueConfig { queue_length: 2 }.validate().is_ok());
-        assert!(InputQueueConfig { queue_length: 128 }.validate().is_ok());
+        assert!(InputQueueConfig {
+            queue_length: 2,
+            ..InputQueueConfig::default()
+        }
+        .validate()
+        .is_ok());
+        assert!(InputQueueConfig {
+            queue_length: 128,
+            ..InputQueueConfig::default()
+        }
+        .validate()
+        .is_ok());
         assert!(InputQueueConfig {
             queue_length: usize::MAX,
+            ..InputQueueConfig::default()
         }
         .validate()
         .is_ok());
 
         // Invalid configs
-        assert!(InputQueueConfig { queue_length: 0 }.validate().is_err());
-        assert!(InputQueueConfig { queue_length: 1 }.validate().is_err());
+        assert!(InputQueueConfig {
+            queue_length: 0,
+            ..InputQueueConfig::default()
+        }
+        .validate()
+        .is_err());
+        assert!(InputQueueConfig {
+            queue_length: 1,
+            ..InputQueueConfig::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_input_queue_config_validate_remote_input_buffer_frames() {
+        let config = InputQueueConfig {
+            queue_length: 32,
+            remote_input_buffer_frames: 31,
+        };
+        assert!(config.validate().is_ok());
+        let config = InputQueueConfig {
+            queue_length: 32,
+            remote_input_buffer_frames: 32,
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "remote_input_buffer_frames",
+                    max: 31,
+                    actual: 32,
+                    ..
+                }
+            })
+        ));
     }
 
     #[test]
     fn test_input_queue_config_validate_frame_delay() {
-        let config = InputQueueConfig { queue_length: 32 };
+        let config = InputQueueConfig {
+            queue_length: 32,
+            ..InputQueueConfig::default()
+        };
 
         // Valid delays
         assert!(config.validate_frame_delay(0).is_ok());
@@ -1661,13 +2641,53 @@ fn test_input_queue_config_validate_frame_delay() {
         assert!(config.validate_frame_delay(100).is_err());
     }
 
+    #[test]
+    fn test_input_queue_config_min_and_recommended_lengths() {
+        assert_eq!(InputQueueConfig::min_queue_length(0, 0), 1);
+        assert_eq!(InputQueueConfig::min_queue_length(8, 2), 11);
+        assert_eq!(
+            InputQueueConfig::min_queue_length(usize::MAX, 1),
+            usize::MAX
+        );
+
+        // Floor of 2, then powers of two.
+        assert_eq!(InputQueueConfig::recommended_queue_length(0, 0, 0), 2);
+        assert_eq!(InputQueueConfig::recommended_queue_length(8, 2, 0), 32);
+        assert_eq!(InputQueueConfig::recommended_queue_length(8, 2, 30), 64);
+        assert_eq!(InputQueueConfig::recommended_queue_length(8, 0, 15), 32);
+        assert_eq!(InputQueueConfig::recommended_queue_length(8, 0, 16), 64);
+        // Saturates instead of overflowing.
+        assert_eq!(
+            InputQueueConfig::recommended_queue_length(usize::MAX, 1, 1),
+            usize::MAX
+        );
+
+        let config = InputQueueConfig::recommended(8, 2, 30);
+        assert_eq!(config.queue_length, 64);
+        assert_eq!(config.remote_input_buffer_frames, 0);
+        assert!(config.validate().is_ok());
+        assert!(config.validate_frame_delay(2).is_ok());
+    }
+
     #[test]
     fn test_input_queue_config_display() {
-        let config = InputQueueConfig { queue_length: 128 };
-        assert_eq!(config.to_string(), "InputQueueConfig { queue_length: 128 }");
+        let config = InputQueueConfig {
+            queue_length: 128,
+            ..InputQueueConfig::default()
+        };
+        assert_eq!(
+            config.to_string(),
+            "InputQueueConfig { queue_length: 128, remote_input_buffer_frames: 0 }"
+        );
 
-        let config = InputQueueConfig { queue_length: 256 };
-        assert_eq!(config.to_string(), "InputQueueConfig { queue_length: 256 }");
+        let config = InputQueueConfig {
+            queue_length: 256,
+            ..InputQueueConfig::default()
+        };
+        assert_eq!(
+            config.to_string(),
+            "InputQueueConfig { queue_length: 256, remote_input_buffer_frames: 0 }"
+        );
     }
 
     // ========================================================================
@@ -1761,6 +2781,28 @@ fn sync_config_stress_test_preset() {
         assert_eq!(config.keepalive_interval, Duration::from_millis(150));
     }
 
+    #[test]
+    fn sync_config_validate_accepts_one_roundtrip_and_rejects_zero() {
+        let one = SyncConfig {
+            num_sync_packets: 1,
+            ..SyncConfig::default()
+        };
+        assert!(one.validate().is_ok());
+        let zero = SyncConfig {
+            num_sync_packets: 0,
+            ..SyncConfig::default()
+        };
+        assert!(matches!(
+            zero.validate(),
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "sync_config.num_sync_packets",
+                    ..
+                }
+            })
+        ));
+    }
+
     #[test]
     fn sync_config_equality() {
         let config1 = SyncConfig::default();
@@ -1850,6 +2892,13 @@ fn protocol_config_default_values() {
         assert_eq!(config.pending_output_limit, 128);
         assert_eq!(config.sync_retry_warning_threshold, 10);
         assert_eq!(config.sync_duration_warning_ms, 3000);
+        assert!(!config.suppress_redundant_inputs);
+        assert_eq!(config.max_suppressed_frames, 4);
+        assert_eq!(config.input_resync_threshold, None);
+        assert!(!config.frame_tags);
+        assert_eq!(config.reliable_window, 16);
+        assert_eq!(config.max_reliable_payload, 256);
+        assert_eq!(config.per_peer_prediction_limit, None);
     }
 
     #[test]
@@ -2435,6 +3484,233 @@ fn test_protocol_config_validate_input_history_multiplier_accepts_large_values()
         config.validate().unwrap();
     }
 
+    #[test]
+    fn test_protocol_config_validate_max_suppressed_frames_zero_rejected() {
+        let config = ProtocolConfig {
+            max_suppressed_frames: 0,
+            ..ProtocolConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "max_suppressed_frames",
+                    min: 1,
+                    actual: 0,
+                    ..
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_protocol_config_validate_max_suppressed_frames_below_pending_output_limit() {
+        let config = ProtocolConfig {
+            suppress_redundant_inputs: true,
+            pending_output_limit: 8,
+            max_suppressed_frames: 7,
+            ..ProtocolConfig::default()
+        };
+        config.validate().unwrap();
+
+        let config = ProtocolConfig {
+            max_suppressed_frames: 8,
+            ..config
+        };
+        let err = config.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "max_suppressed_frames",
+                    min: 1,
+                    max: 7,
+                    actual: 8,
+                }
+            }
+        ));
+
+        // The pending-output bound only applies while suppression is enabled.
+        let config = ProtocolConfig {
+            suppress_redundant_inputs: false,
+            ..config
+        };
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_protocol_config_validate_input_resync_threshold_below_pending_output_limit() {
+        let config = ProtocolConfig {
+            pending_output_limit: 8,
+            input_resync_threshold: Some(7),
+            ..ProtocolConfig::default()
+        };
+        config.validate().unwrap();
+
+        for threshold in [0, 8] {
+            let config = ProtocolConfig {
+                input_resync_threshold: Some(threshold),
+                ..config.clone()
+            };
+            let err = config.validate().unwrap_err();
+            assert!(matches!(
+                err,
+                FortressError::InvalidRequestStructured {
+                    kind: InvalidRequestKind::ConfigValueOutOfRange {
+                        field: "input_resync_threshold",
+                        min: 1,
+                        max: 7,
+                        actual,
+                    }
+                } if actual == threshold as u64
+            ));
+        }
+    }
+
+    #[test]
+    fn test_protocol_config_validate_redundant_input_frames_within_pending_output_limit() {
+        let config = ProtocolConfig {
+            pending_output_limit: 8,
+            redundant_input_frames: 8,
+            ..ProtocolConfig::default()
+        };
+        config.validate().unwrap();
+
+        for frames in [0, 9] {
+            let config = ProtocolConfig {
+                redundant_input_frames: frames,
+                ..config.clone()
+            };
+            let err = config.validate().unwrap_err();
+            assert!(matches!(
+                err,
+                FortressError::InvalidRequestStructured {
+                    kind: InvalidRequestKind::ConfigValueOutOfRange {
+                        field: "redundant_input_frames",
+                        min: 1,
+                        max: 8,
+                        actual,
+                    }
+                } if actual == frames as u64
+            ));
+        }
+    }
+
+    #[test]
+    fn test_protocol_config_validate_transport_error_threshold_nonzero() {
+        for threshold in [None, Some(1), Some(u32::MAX)] {
+            let config = ProtocolConfig {
+                transport_error_threshold: threshold,
+                ..ProtocolConfig::default()
+            };
+            config.validate().unwrap();
+        }
+
+        let config = ProtocolConfig {
+            transport_error_threshold: Some(0),
+            ..ProtocolConfig::default()
+        };
+        assert!(matches!(
+            config.validate().unwrap_err(),
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "transport_error_threshold",
+                    min: 1,
+                    actual: 0,
+                    ..
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_protocol_config_validate_send_limits_must_allow_progress() {
+        let config = ProtocolConfig {
+            max_packets_per_poll: 0,
+            ..ProtocolConfig::default()
+        };
+        assert!(matches!(
+            config.validate().unwrap_err(),
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "max_packets_per_poll",
+                    min: 1,
+                    actual: 0,
+                    ..
+                }
+            }
+        ));
+
+        let config = ProtocolConfig {
+            max_bytes_per_second: Some(0),
+            ..ProtocolConfig::default()
+        };
+        assert!(matches!(
+            config.validate().unwrap_err(),
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "max_bytes_per_second",
+                    min: 1,
+                    actual: 0,
+                    ..
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_protocol_config_validate_per_peer_prediction_limit_is_positive() {
+        let config = ProtocolConfig {
+            per_peer_prediction_limit: Some(0),
+            ..ProtocolConfig::default()
+        };
+        assert!(matches!(
+            config.validate().unwrap_err(),
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "per_peer_prediction_limit",
+                    min: 1,
+                    actual: 0,
+                    ..
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_protocol_config_validate_reliable_channel_bounds() {
+        for (window, payload, field) in [
+            (0, 256, "reliable_window"),
+            (
+                ProtocolConfig::MAX_RELIABLE_WINDOW + 1,
+                256,
+                "reliable_window",
+            ),
+            (16, 0, "max_reliable_payload"),
+            (
+                16,
+                ProtocolConfig::MAX_RELIABLE_PAYLOAD + 1,
+                "max_reliable_payload",
+            ),
+        ] {
+            let config = ProtocolConfig {
+                reliable_window: window,
+                max_reliable_payload: payload,
+                ..ProtocolConfig::default()
+            };
+            assert!(
+                matches!(
+                    config.validate().unwrap_err(),
+                    FortressError::InvalidRequestStructured {
+                        kind: InvalidRequestKind::ConfigValueOutOfRange { field: f, min: 1, .. }
+                    } if f == field
+                ),
+                "{window} / {payload}"
+            );
+        }
+    }
+
     #[test]
     fn test_protocol_config_validate_multiple_invalid_fields() {
         // Test that validation stops at the first invalid field
@@ -2471,6 +3747,19 @@ fn test_protocol_config_validate_all_fields_at_boundaries() {
             sync_retry_warning_threshold: 1,
             sync_duration_warning_ms: 1,
             input_history_multiplier: 1,
+            suppress_redundant_inputs: false,
+            max_suppressed_frames: 1,
+            input_resync_threshold: None,
+            redundant_input_frames: 1,
+            max_packets_per_poll: 1,
+            max_bytes_per_second: Some(1),
+            frame_tags: false,
+            reliable_window: 1,
+            max_reliable_payload: 1,
+            per_peer_prediction_limit: Some(1),
+            keep_alive_acks: false,
+            sequence_numbers: false,
+            transport_error_threshold: Some(1),
             protocol_rng_seed: None,
             clock: None,
         };
@@ -2485,6 +3774,19 @@ fn test_protocol_config_validate_all_fields_at_boundaries() {
             sync_retry_warning_threshold: u32::MAX,
             sync_duration_warning_ms: u128::MAX,
             input_history_multiplier: usize::MAX,
+            suppress_redundant_inputs: true,
+            max_suppressed_frames: ProtocolConfig::MAX_PENDING_OUTPUT_LIMIT - 1,
+            input_resync_threshold: Some(ProtocolConfig::MAX_PENDING_OUTPUT_LIMIT - 1),
+            redundant_input_frames: ProtocolConfig::MAX_PENDING_OUTPUT_LIMIT,
+            max_packets_per_poll: usize::MAX,
+            max_bytes_per_second: Some(u64::MAX),
+            frame_tags: true,
+            reliable_window: ProtocolConfig::MAX_RELIABLE_WINDOW,
+            max_reliable_payload: ProtocolConfig::MAX_RELIABLE_PAYLOAD,
+            per_peer_prediction_limit: Some(usize::MAX),
+            keep_alive_acks: true,
+            sequence_numbers: true,
+            transport_error_threshold: Some(u32::MAX),
             protocol_rng_seed: None,
             clock: None,
         };
@@ -2547,6 +3849,7 @@ fn spectator_config_default_values() {
         assert_eq!(config.max_frames_behind, 10);
         assert_eq!(config.stream_delay, 0);
         assert!(!config.enable_rewind);
+        assert_eq!(config.keyframe_interval, 0);
     }
 
     #[test]
@@ -2562,6 +3865,7 @@ fn spectator_config_presets_have_default_stream_and_rewind() {
         ] {
             assert_eq!(config.stream_delay, 0);
             assert!(!config.enable_rewind);
+            assert_eq!(config.keyframe_interval, 0);
         }
     }
 
@@ -2676,6 +3980,7 @@ fn spectator_config_display() {
         assert!(display_str.contains("max_behind: 10"));
         assert!(display_str.contains("stream_delay: 0"));
         assert!(display_str.contains("enable_rewind: false"));
+        assert!(display_str.contains("keyframe_interval: 0"));
 
         let config = SpectatorConfig::broadcast();
         let display_str = config.to_string();
@@ -2690,6 +3995,21 @@ fn spectator_config_display() {
         let display_str = config.to_string();
         assert!(display_str.contains("stream_delay: 7"));
         assert!(display_str.contains("enable_rewind: true"));
+        assert!(display_str.contains("access_token: none"));
+        assert!(display_str.contains("checksum_authority: false"));
+    }
+
+    #[test]
+    fn spectator_config_never_prints_the_access_token() {
+        let config = SpectatorConfig {
+            access_token: Some([0xAB; 16]),
+            ..SpectatorConfig::default()
+        };
+        for printed in [config.to_string(), format!("{config:?}")] {
+            assert!(!printed.contains("171"), "{printed}");
+        }
+        assert!(config.to_string().contains("access_token: set"));
+        assert!(format!("{config:?}").contains("<redacted>"));
     }
 
     #[test]
@@ -2757,7 +4077,10 @@ fn proof_validate_accepts_valid_queue_lengths() {
         // Focus on boundary region for tractability
         kani::assume(queue_length <= 512);
 
-        let config = InputQueueConfig { queue_length };
+        let config = InputQueueConfig {
+            queue_length,
+            ..InputQueueConfig::default()
+        };
         let result = config.validate();
 
         if queue_length >= 2 {
@@ -2778,14 +4101,20 @@ fn proof_validate_accepts_valid_queue_lengths() {
     #[kani::unwind(2)]
     fn proof_validate_boundary_at_two() {
         // queue_length = 1 should fail
-        let config_one = InputQueueConfig { queue_length: 1 };
+        let config_one = InputQueueConfig {
+            queue_length: 1,
+            ..InputQueueConfig::default()
+        };
         kani::assert(
             config_one.validate().is_err(),
             "queue_length=1 should be invalid",
         );
 
         // queue_length = 2 should succeed
-        let config_two = InputQueueConfig { queue_length: 2 };
+        let config_two = InputQueueConfig {
+            queue_length: 2,
+            ..InputQueueConfig::default()
+        };
         kani::assert(
             config_two.validate().is_ok(),
             "queue_length=2 should be valid",
@@ -2810,7 +4139,10 @@ fn proof_validate_frame_delay_constraint() {
         kani::assume(queue_length >= 2 && queue_length <= 256);
         kani::assume(frame_delay <= 256);
 
-        let config = InputQueueConfig { queue_length };
+        let config = InputQueueConfig {
+            queue_length,
+            ..InputQueueConfig::default()
+        };
         let result = config.validate_frame_delay(frame_delay);
 
         if frame_delay < queue_length {
@@ -2840,7 +4172,10 @@ fn proof_max_frame_delay_derivation() {
         let queue_length: usize = kani::any();
         kani::assume(queue_length <= 512);
 
-        let config = InputQueueConfig { queue_length };
+        let config = InputQueueConfig {
+            queue_length,
+            ..InputQueueConfig::default()
+        };
         let max_delay = config.max_frame_delay();
 
         // Should be queue_length - 1, or 0 if queue_length is 0
@@ -2865,7 +4200,10 @@ fn proof_max_frame_delay_is_valid_delay() {
         let queue_length: usize = kani::any();
         kani::assume(queue_length >= 2 && queue_length <= 256);
 
-        let config = InputQueueConfig { queue_length };
+        let config = InputQueueConfig {
+            queue_length,
+            ..InputQueueConfig::default()
+        };
         let max_delay = config.max_frame_delay();
         let result = config.validate_frame_delay(max_delay);
 
@@ -0,0 +1,300 @@
+//! Input confirmation latency for netcode tuning overlays.
+//!
+//! With [`SessionBuilder::with_confirmation_latency`](crate::SessionBuilder::with_confirmation_latency)
+//! enabled, a [`P2PSession`](crate::P2PSession) stamps each local input frame
+//! when its input enters the session, and measures how long the frame took to
+//! become confirmed (every remote input for it arrived). The last `capacity`
+//! measurements are kept in a fixed-size ring, readable through
+//! [`P2PSession::confirmation_latency_samples`](crate::P2PSession::confirmation_latency_samples)
+//! and summarized by
+//! [`P2PSession::confirmation_latency_stats`](crate::P2PSession::confirmation_latency_stats).
+//! Recording costs O(1) per frame; only the summary sorts.
+
+use std::collections::VecDeque;
+
+use web_time::{Duration, Instant};
+
+use crate::error::allocation_failed;
+use crate::{FortressError, Frame};
+
+/// Largest capacity accepted by
+/// [`SessionBuilder::with_confirmation_latency`](crate::SessionBuilder::with_confirmation_latency).
+pub(crate) const MAX_CONFIRMATION_LATENCY_CAPACITY: usize = 1 << 16;
+
+/// How long one local input frame took to become confirmed, as recorded by
+/// [`P2PSession::confirmation_latency_samples`](crate::P2PSession::confirmation_latency_samples).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConfirmationLatencySample {
+    /// The frame the local input was for, including input delay.
+    pub frame: Frame,
+    /// Frames the session advanced between adding the input and confirming
+    /// `frame`.
+    pub frames: u32,
+    /// Time between adding the input and confirming `frame`.
+    pub elapsed: Duration,
+}
+
+/// Percentiles of the recent [`ConfirmationLatencySample`]s, as returned by
+/// [`P2PSession::confirmation_latency_stats`](crate::P2PSession::confirmation_latency_stats).
+///
+/// Percentiles use the nearest-rank method, so each is one of the samples.
+/// Every field is 0 while there are no samples.
+///
+/// This type is `#[non_exhaustive]`: future library versions may add fields.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LatencyStats {
+    /// The number of samples summarized.
+    pub samples: usize,
+    /// Median confirmation time, in milliseconds.
+    pub p50_ms: f64,
+    /// 95th percentile confirmation time, in milliseconds.
+    pub p95_ms: f64,
+    /// Slowest confirmation time, in milliseconds.
+    pub max_ms: f64,
+    /// Median confirmation time, in frames.
+    pub p50_frames: u32,
+    /// 95th percentile confirmation time, in frames.
+    pub p95_frames: u32,
+}
+
+/// A local input frame waiting to be confirmed.
+#[derive(Debug, Clone, Copy)]
+struct PendingFrame {
+    frame: Frame,
+    added_at_frame: Frame,
+    added_at: Instant,
+}
+
+/// Rings of the frames awaiting confirmation and of the `capacity` most recent
+/// measurements.
+#[derive(Debug)]
+pub(crate) struct ConfirmationLatency {
+    capacity: usize,
+    /// Local input frames not yet confirmed, oldest first.
+    pending: VecDeque<PendingFrame>,
+    /// Measurements, oldest first.
+    samples: VecDeque<ConfirmationLatencySample>,
+}
+
+impl ConfirmationLatency {
+    /// An empty tracker keeping the `capacity` most recent measurements;
+    /// `capacity` must be at least 1.
+    pub(crate) fn new(capacity: usize) -> Result<Self, FortressError> {
+        let capacity = capacity.max(1);
+        // alloc-bound: `capacity` is validated against
+        // `MAX_CONFIRMATION_LATENCY_CAPACITY` by the builder.
+        let mut pending = VecDeque::new();
+        pending
+            .try_reserve_exact(capacity)
+            .map_err(|_err| allocation_failed("confirmation_latency.pending", capacity))?;
+        let mut samples = VecDeque::new();
+        samples
+            .try_reserve_exact(capacity)
+            .map_err(|_err| allocation_failed("confirmation_latency.samples", capacity))?;
+        Ok(Self {
+            capacity,
+            pending,
+            samples,
+        })
+    }
+
+    /// Stamps the local input for `frame`, added while the session was at
+    /// `current_frame`. Frames not after the newest stamped one (a second
+    /// local player, or an input the last call already queued) are ignored.
+    pub(crate) fn record_input(&mut self, frame: Frame, current_frame: Frame, now: Instant) {
+        if !frame.is_valid() || self.pending.back().is_some_and(|last| last.frame >= frame) {
+            return;
+        }
+        if self.pending.len() >= self.capacity {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(PendingFrame {
+            frame,
+            added_at_frame: current_frame,
+            added_at: now,
+        });
+    }
+
+    /// Measures every stamped frame up to `confirmed_frame`, confirmed while
+    /// the session is at `current_frame`.
+    pub(crate) fn confirm(&mut self, confirmed_frame: Frame, current_frame: Frame, now: Instant) {
+        while let Some(pending) = self.pending.front().copied() {
+            if pending.frame > confirmed_frame {
+                break;
+            }
+            self.pending.pop_front();
+            if self.samples.len() >= self.capacity {
+                self.samples.pop_front();
+            }
+            let frames = current_frame
+                .as_i32()
+                .saturating_sub(pending.added_at_frame.as_i32());
+            self.samples.push_back(ConfirmationLatencySample {
+                frame: pending.frame,
+                frames: u32::try_from(frames).unwrap_or(0),
+                elapsed: now.saturating_duration_since(pending.added_at),
+            });
+        }
+    }
+
+    /// Forgets the stamped frames from `frame` on, whose inputs a state
+    /// restore threw away unconfirmed.
+    pub(crate) fn discard_from(&mut self, frame: Frame) {
+        self.pending.retain(|pending| pending.frame < frame);
+    }
+
+    /// The measurements, oldest first.
+    pub(crate) fn samples(&self) -> impl Iterator<Item = ConfirmationLatencySample> + '_ {
+        self.samples.iter().copied()
+    }
+
+    /// Summarizes the measurements.
+    pub(crate) fn stats(&self) -> LatencyStats {
+        // alloc-bound: at most `capacity` samples, only when queried.
+        let mut elapsed: Vec<Duration> = self.samples.iter().map(|sample| sample.elapsed).collect();
+        let mut frames: Vec<u32> = self.samples.iter().map(|sample| sample.frames).collect();
+        elapsed.sort_unstable();
+        frames.sort_unstable();
+        let ms = |duration: Option<Duration>| {
+            duration.map_or(0.0, |duration| duration.as_secs_f64() * 1000.0)
+        };
+        LatencyStats {
+            samples: elapsed.len(),
+            p50_ms: ms(nearest_rank(&elapsed, 50)),
+            p95_ms: ms(nearest_rank(&elapsed, 95)),
+            max_ms: ms(elapsed.last().copied()),
+            p50_frames: nearest_rank(&frames, 50).unwrap_or(0),
+            p95_frames: nearest_rank(&frames, 95).unwrap_or(0),
+        }
+    }
+}
+
+/// The nearest-rank `percent`th percentile of `sorted`, or `None` if it is
+/// empty.
+fn nearest_rank<T: Copy>(sorted: &[T], percent: usize) -> Option<T> {
+    let rank = sorted.len().saturating_mul(percent).div_ceil(100);
+    sorted.get(rank.saturating_sub(1)).copied()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn measures_frames_once_confirmed() {
+        let start = Instant::now();
+        let mut latency = ConfirmationLatency::new(8).unwrap();
+        latency.record_input(Frame::new(0), Frame::new(0), start);
+        latency.record_input(Frame::new(1), Frame::new(1), start + ms(16));
+        latency.confirm(Frame::NULL, Frame::new(2), start + ms(32));
+        assert_eq!(latency.samples().count(), 0);
+
+        latency.confirm(Frame::new(0), Frame::new(3), start + ms(48));
+        assert_eq!(
+            latency.samples().collect::<Vec<_>>(),
+            vec![ConfirmationLatencySample {
+                frame: Frame::new(0),
+                frames: 3,
+                elapsed: ms(48),
+            }]
+        );
+
+        latency.confirm(Frame::new(1), Frame::new(3), start + ms(48));
+        assert_eq!(latency.samples().count(), 2);
+        assert_eq!(latency.samples().last().unwrap().elapsed, ms(32));
+    }
+
+    #[test]
+    fn stamps_each_frame_once() {
+        let start = Instant::now();
+        let mut latency = ConfirmationLatency::new(8).unwrap();
+        latency.record_input(Frame::new(2), Frame::new(0), start);
+        latency.record_input(Frame::new(2), Frame::new(0), start + ms(5));
+        latency.record_input(Frame::new(1), Frame::new(0), start + ms(5));
+        latency.confirm(Frame::new(2), Frame::new(1), start + ms(20));
+        assert_eq!(
+            latency.samples().collect::<Vec<_>>(),
+            vec![ConfirmationLatencySample {
+                frame: Frame::new(2),
+                frames: 1,
+                elapsed: ms(20),
+            }]
+        );
+    }
+
+    #[test]
+    fn keeps_the_most_recent_measurements() {
+        let start = Instant::now();
+        let mut latency = ConfirmationLatency::new(3).unwrap();
+        for frame in 0..5 {
+            latency.record_input(Frame::new(frame), Frame::new(frame), start);
+            latency.confirm(Frame::new(frame), Frame::new(frame + 1), start);
+        }
+        let frames: Vec<i32> = latency
+            .samples()
+            .map(|sample| sample.frame.as_i32())
+            .collect();
+        assert_eq!(frames, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn pending_frames_are_bounded() {
+        let start = Instant::now();
+        let mut latency = ConfirmationLatency::new(2).unwrap();
+        for frame in 0..5 {
+            latency.record_input(Frame::new(frame), Frame::new(frame), start);
+        }
+        latency.confirm(Frame::new(4), Frame::new(5), start);
+        let frames: Vec<i32> = latency
+            .samples()
+            .map(|sample| sample.frame.as_i32())
+            .collect();
+        assert_eq!(frames, vec![3, 4]);
+    }
+
+    #[test]
+    fn stats_use_nearest_rank_percentiles() {
+        assert_eq!(
+            ConfirmationLatency::new(4).unwrap().stats(),
+            LatencyStats::default()
+        );
+
+        let start = Instant::now();
+        let mut latency = ConfirmationLatency::new(100).unwrap();
+        for frame in 0..20 {
+            latency.record_input(Frame::new(frame), Frame::new(0), start);
+        }
+        // Frame `n` is confirmed `n + 1` frames and `10 * (n + 1)` ms later.
+        for frame in 0..20 {
+            let elapsed = ms(10 * (frame as u64 + 1));
+            latency.confirm(Frame::new(frame), Frame::new(frame + 1), start + elapsed);
+        }
+        let stats = latency.stats();
+        assert_eq!(stats.samples, 20);
+        assert!((stats.p50_ms - 100.0).abs() < 1e-9, "{stats:?}");
+        assert!((stats.p95_ms - 190.0).abs() < 1e-9, "{stats:?}");
+        assert!((stats.max_ms - 200.0).abs() < 1e-9, "{stats:?}");
+        assert_eq!(stats.p50_frames, 10);
+        assert_eq!(stats.p95_frames, 19);
+    }
+
+    #[test]
+    fn nearest_rank_picks_a_sample() {
+        assert_eq!(nearest_rank::<u32>(&[], 50), None);
+        assert_eq!(nearest_rank(&[7], 50), Some(7));
+        assert_eq!(nearest_rank(&[1, 2, 3, 4], 50), Some(2));
+        assert_eq!(nearest_rank(&[1, 2, 3, 4], 95), Some(4));
+        assert_eq!(nearest_rank(&[1, 2, 3, 4], 100), Some(4));
+    }
+}
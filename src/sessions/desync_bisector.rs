@@ -0,0 +1,526 @@
+//! Finding the first divergent frame of a recorded desync.
+//!
+//! [`DesyncBisector`] re-simulates a recorded input script and binary-searches
+//! for the earliest frame whose re-simulated checksum stops matching a remote
+//! checksum history, typically the peer's history attached to a desync report.
+//! Each probe loads the newest state known to match, advances to the probed
+//! frame, and saves it, so the search takes a logarithmic number of passes and
+//! simulates each frame about once.
+//!
+//! The game is driven through the same [`FortressRequest`]s every session
+//! emits, so the request handler you already use for [`SyncTestSession`] or
+//! [`P2PSession`] works unchanged. It must start from the match's initial
+//! state and store a checksum with every save.
+//!
+//! # Example
+//!
+//! ```
+//! use fortress_rollback::replay::{Replay, ReplayMetadata};
+//! use fortress_rollback::{handle_requests, Config, DesyncBisector, Frame, InputVec};
+//! use serde::{Deserialize, Serialize};
+//! use std::net::SocketAddr;
+//!
+//! #[derive(Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+//! struct Input(u8);
+//!
+//! #[derive(Debug)]
+//! struct Cfg;
+//! impl Config for Cfg {
+//!     type Input = Input;
+//!     type State = u64;
+//!     type Address = SocketAddr;
+//! }
+//!
+//! let script = Replay {
+//!     num_players: 1,
+//!     frames: vec![vec![Input(1)]; 8],
+//!     checksums: vec![None; 8],
+//!     metadata: ReplayMetadata {
+//!         library_version: String::new(),
+//!         num_players: 1,
+//!         total_frames: 8,
+//!         skipped_frames: 0,
+//!     },
+//! };
+//! // The peer's state drifted by one from frame 5 on.
+//! let remote: Vec<Option<u128>> = (0..=8).map(|f| Some(f + u128::from(f >= 5))).collect();
+//!
+//! let bisector = DesyncBisector::<Cfg>::new(script, Vec::new(), remote)?;
+//! let mut state = 0u64;
+//! let report = bisector
+//!     .bisect(|requests| {
+//!         handle_requests!(
+//!             requests,
+//!             save: |cell: fortress_rollback::GameStateCell<u64>, frame: Frame| {
+//!                 cell.save(frame, Some(state), Some(u128::from(state)));
+//!             },
+//!             load: |cell: fortress_rollback::GameStateCell<u64>, _frame: Frame| {
+//!                 state = cell.load().unwrap_or_default();
+//!             },
+//!             advance: |inputs: InputVec<Input>| {
+//!                 state += inputs.iter().map(|(input, _)| u64::from(input.0)).sum::<u64>();
+//!             }
+//!         );
+//!     })?
+//!     .expect("the histories diverge");
+//! assert_eq!(report.frame, Frame::new(5));
+//! assert_eq!(report.last_matching_frame, Some(Frame::new(4)));
+//! # Ok::<(), fortress_rollback::FortressError>(())
+//! ```
+//!
+//! [`DesyncBisector`]: crate::sessions::desync_bisector::DesyncBisector
+//! [`FortressRequest`]: crate::FortressRequest
+//! [`SyncTestSession`]: crate::SyncTestSession
+//! [`P2PSession`]: crate::P2PSession
+
+use std::fmt;
+
+use crate::error::allocation_failed;
+use crate::replay::Replay;
+use crate::sync_layer::GameStateCell;
+use crate::{Config, FortressRequest, FortressResult, Frame, InputStatus, InputVec, RequestVec};
+
+/// Frames of inputs reported on each side of the divergent frame by default.
+const DEFAULT_CONTEXT_FRAMES: usize = 3;
+
+/// Binary-searches a recorded match for the first frame whose re-simulated
+/// checksum differs from a remote checksum history.
+///
+/// See the [module documentation](self) for how the game is driven.
+///
+/// Checksum histories are indexed by frame: entry `f` is the checksum of the
+/// state saved at frame `f`, after the inputs of frames `0..f` were applied,
+/// the same convention as [`Replay::checksums`]. Frames whose remote checksum
+/// is `None` are not probed. The search assumes a divergence persists once it
+/// appears, which holds for a deterministic game that diverged because of
+/// different inputs or state.
+pub struct DesyncBisector<T: Config> {
+    script: Replay<T::Input>,
+    local_history: Vec<Option<u128>>,
+    remote_history: Vec<Option<u128>>,
+    context_frames: usize,
+    previous_inputs: bool,
+}
+
+/// The first divergent frame found by [`DesyncBisector::bisect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DesyncBisection<I> {
+    /// The earliest probed frame whose re-simulated checksum differs from the
+    /// remote history. The inputs that produced this state are those of the
+    /// frames before it.
+    pub frame: Frame,
+    /// The latest probed frame before [`frame`](Self::frame) that matched the
+    /// remote history, or `None` if no earlier frame had a remote checksum.
+    pub last_matching_frame: Option<Frame>,
+    /// The re-simulated checksum at [`frame`](Self::frame).
+    pub local_checksum: Option<u128>,
+    /// The remote history's checksum at [`frame`](Self::frame).
+    pub remote_checksum: u128,
+    /// The local history's checksum at [`frame`](Self::frame), if recorded.
+    /// When it differs from [`local_checksum`](Self::local_checksum), the
+    /// re-simulation does not even reproduce the local side.
+    pub recorded_local_checksum: Option<u128>,
+    /// The inputs of the frames around [`frame`](Self::frame), one entry per
+    /// player, in frame order.
+    pub inputs: Vec<(Frame, Vec<I>)>,
+    /// Probes made: each loads a matching state, advances, and saves once.
+    pub simulation_passes: usize,
+    /// Frames advanced over all probes.
+    pub frames_simulated: usize,
+}
+
+impl<T: Config> DesyncBisector<T> {
+    /// Creates a bisector for a recorded input script and the local and remote
+    /// checksum histories of the match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the script fails [`Replay::validate`].
+    pub fn new(
+        script: Replay<T::Input>,
+        local_history: Vec<Option<u128>>,
+        remote_history: Vec<Option<u128>>,
+    ) -> FortressResult<Self> {
+        script.validate()?;
+        Ok(Self {
+            script,
+            local_history,
+            remote_history,
+            context_frames: DEFAULT_CONTEXT_FRAMES,
+            previous_inputs: false,
+        })
+    }
+
+    /// Sets how many frames of inputs on each side of the divergent frame the
+    /// report includes. Default: 3.
+    #[must_use]
+    pub fn with_context_frames(mut self, frames: usize) -> Self {
+        self.context_frames = frames;
+        self
+    }
+
+    /// Fills the `previous_inputs` of every
+    /// [`AdvanceFrame`](FortressRequest::AdvanceFrame) with the script's inputs
+    /// for the previous frame, matching a session built with
+    /// [`SessionBuilder::with_previous_inputs_in_requests`](crate::SessionBuilder::with_previous_inputs_in_requests).
+    /// Default: `false`.
+    #[must_use]
+    pub fn with_previous_inputs_in_requests(mut self, enabled: bool) -> Self {
+        self.previous_inputs = enabled;
+        self
+    }
+
+    /// Runs the search, passing every request to `handle` in order.
+    ///
+    /// Returns `None` if every probed frame matches the remote history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a frame number does not fit in a [`Frame`] or an
+    /// allocation fails.
+    pub fn bisect<H>(&self, mut handle: H) -> FortressResult<Option<DesyncBisection<T::Input>>>
+    where
+        H: FnMut(RequestVec<T>),
+    {
+        // Frames with a state to compare: 0 (the initial state) through the
+        // state after the last scripted frame.
+        let last_frame = self.script.frames.len();
+        let probe_count = self.remote_history.len().min(last_frame.saturating_add(1));
+        let mut candidates = Vec::new();
+        candidates
+            .try_reserve_exact(probe_count)
+            .map_err(|_err| allocation_failed("desync_bisector.candidates", probe_count))?;
+        for (index, checksum) in self.remote_history.iter().take(probe_count).enumerate() {
+            if let Some(checksum) = checksum {
+                candidates.push((Frame::try_from_usize(index)?, *checksum));
+            }
+        }
+
+        let mut checkpoint = GameStateCell::<T::State>::default();
+        handle(request_vec(FortressRequest::SaveGameState {
+            cell: checkpoint.clone(),
+            frame: Frame::new(0),
+        }));
+        let mut checkpoint_frame = Frame::new(0);
+        let mut last_matching_frame = None;
+        let mut divergence = None;
+        let mut simulation_passes = 0usize;
+        let mut frames_simulated = 0usize;
+
+        // Invariant: candidates[..low] match, candidates[high..] diverge.
+        let (mut low, mut high) = (0, candidates.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let Some(&(frame, remote_checksum)) = candidates.get(mid) else {
+                break;
+            };
+            let probe = self.probe(&mut handle, &checkpoint, checkpoint_frame, frame)?;
+            simulation_passes += 1;
+            frames_simulated += frame
+                .try_as_usize()?
+                .saturating_sub(checkpoint_frame.try_as_usize()?);
+            let local_checksum = probe.checksum();
+            if local_checksum == Some(remote_checksum) {
+                low = mid + 1;
+                checkpoint = probe;
+                checkpoint_frame = frame;
+                last_matching_frame = Some(frame);
+            } else {
+                high = mid;
+                divergence = Some((frame, local_checksum, remote_checksum));
+            }
+        }
+
+        let Some((frame, local_checksum, remote_checksum)) = divergence else {
+            return Ok(None);
+        };
+        let recorded_local_checksum = self
+            .local_history
+            .get(frame.try_as_usize()?)
+            .copied()
+            .flatten();
+        Ok(Some(DesyncBisection {
+            frame,
+            last_matching_frame,
+            local_checksum,
+            remote_checksum,
+            recorded_local_checksum,
+            inputs: self.inputs_around(frame)?,
+            simulation_passes,
+            frames_simulated,
+        }))
+    }
+
+    /// The script's inputs for frame `index`, all confirmed.
+    fn script_inputs(&self, index: usize) -> FortressResult<InputVec<T::Input>> {
+        let frame_inputs = self.script.frames.get(index).map_or(&[][..], Vec::as_slice);
+        let mut inputs = InputVec::new();
+        inputs
+            .try_reserve(frame_inputs.len())
+            .map_err(|_err| allocation_failed("desync_bisector.inputs", frame_inputs.len()))?;
+        for input in frame_inputs {
+            inputs.push((*input, InputStatus::Confirmed));
+        }
+        Ok(inputs)
+    }
+
+    /// Loads the checkpoint at `from`, advances to `to`, and saves there.
+    fn probe<H>(
+        &self,
+        handle: &mut H,
+        checkpoint: &GameStateCell<T::State>,
+        from: Frame,
+        to: Frame,
+    ) -> FortressResult<GameStateCell<T::State>>
+    where
+        H: FnMut(RequestVec<T>),
+    {
+        handle(request_vec(FortressRequest::LoadGameState {
+            cell: checkpoint.clone(),
+            frame: from,
+        }));
+        let (start, end) = (from.try_as_usize()?, to.try_as_usize()?);
+        for index in start..end.min(self.script.frames.len()) {
+            let inputs = self.script_inputs(index)?;
+            let previous_inputs = match index.checked_sub(1) {
+                Some(previous) if self.previous_inputs => self.script_inputs(previous)?,
+                _ => InputVec::new(),
+            };
+            handle(request_vec(FortressRequest::AdvanceFrame {
+                inputs,
+                previous_inputs,
+            }));
+        }
+        let cell = GameStateCell::default();
+        handle(request_vec(FortressRequest::SaveGameState {
+            cell: cell.clone(),
+            frame: to,
+        }));
+        Ok(cell)
+    }
+
+    /// The scripted inputs within `context_frames` of `frame`.
+    fn inputs_around(&self, frame: Frame) -> FortressResult<Vec<(Frame, Vec<T::Input>)>> {
+        let center = frame.try_as_usize()?;
+        let start = center.saturating_sub(self.context_frames);
+        let end = center
+            .saturating_add(self.context_frames)
+            .saturating_add(1)
+            .min(self.script.frames.len());
+        let len = end.saturating_sub(start);
+        let mut inputs = Vec::new();
+        inputs
+            .try_reserve_exact(len)
+            .map_err(|_err| allocation_failed("desync_bisector.context", len))?;
+        for (index, frame_inputs) in self.script.frames.iter().enumerate().take(end).skip(start) {
+            inputs.push((Frame::try_from_usize(index)?, frame_inputs.clone()));
+        }
+        Ok(inputs)
+    }
+}
+
+fn request_vec<T: Config>(request: FortressRequest<T>) -> RequestVec<T> {
+    let mut requests = RequestVec::new();
+    requests.push(request);
+    requests
+}
+
+impl<T: Config> fmt::Debug for DesyncBisector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DesyncBisector")
+            .field("script_frames", &self.script.total_frames())
+            .field("num_players", &self.script.num_players)
+            .field("local_history_len", &self.local_history.len())
+            .field("remote_history_len", &self.remote_history.len())
+            .field("context_frames", &self.context_frames)
+            .field("previous_inputs", &self.previous_inputs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+    use crate::replay::ReplayMetadata;
+    use serde::{Deserialize, Serialize};
+    use std::net::SocketAddr;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+    struct TestInput(u8);
+
+    #[derive(Debug)]
+    struct TestConfig;
+
+    impl Config for TestConfig {
+        type Input = TestInput;
+        type State = u64;
+        type Address = SocketAddr;
+    }
+
+    const FRAMES: usize = 300;
+    const DIVERGENT_FRAME: usize = 137;
+
+    fn script() -> Replay<TestInput> {
+        let frames: Vec<_> = (0..FRAMES)
+            .map(|frame| vec![TestInput(frame as u8 % 5), TestInput(frame as u8 % 3)])
+            .collect();
+        Replay {
+            num_players: 2,
+            checksums: vec![None; frames.len()],
+            metadata: ReplayMetadata {
+                library_version: String::new(),
+                num_players: 2,
+                total_frames: frames.len(),
+                skipped_frames: 0,
+            },
+            frames,
+        }
+    }
+
+    fn step(state: u64, inputs: &[TestInput]) -> u64 {
+        inputs.iter().fold(state, |state, input| {
+            state.wrapping_mul(31).wrapping_add(u64::from(input.0) + 1)
+        })
+    }
+
+    /// Checksums of the states at frames `0..=FRAMES`; from `diverge_at` on,
+    /// the state carries an extra bit, as if one frame went differently.
+    fn history(script: &Replay<TestInput>, diverge_at: Option<usize>) -> Vec<Option<u128>> {
+        let mut state = 0u64;
+        let mut history = vec![Some(u128::from(state))];
+        for (frame, inputs) in script.frames.iter().enumerate() {
+            state = step(state, inputs);
+            if Some(frame + 1) == diverge_at {
+                state ^= 1;
+            }
+            history.push(Some(u128::from(state)));
+        }
+        history
+    }
+
+    /// A game that counts the frames it advances.
+    #[derive(Default)]
+    struct Game {
+        state: u64,
+        advances: usize,
+    }
+
+    impl Game {
+        fn handle(&mut self, requests: RequestVec<TestConfig>) {
+            for request in requests {
+                match request {
+                    FortressRequest::SaveGameState { cell, frame } => {
+                        cell.save(frame, Some(self.state), Some(u128::from(self.state)));
+                    },
+                    FortressRequest::LoadGameState { cell, .. } => {
+                        self.state = cell.load().unwrap();
+                    },
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
+                        let inputs: Vec<_> = inputs.iter().map(|(input, _)| *input).collect();
+                        self.state = step(self.state, &inputs);
+                        self.advances += 1;
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_the_scripted_divergence_in_logarithmic_passes() {
+        let script = script();
+        let local = history(&script, None);
+        let remote = history(&script, Some(DIVERGENT_FRAME));
+        let bisector = DesyncBisector::<TestConfig>::new(script.clone(), local.clone(), remote)
+            .unwrap()
+            .with_context_frames(1);
+        let mut game = Game::default();
+
+        let report = bisector
+            .bisect(|requests| game.handle(requests))
+            .unwrap()
+            .expect("divergence");
+
+        assert_eq!(report.frame, Frame::new(DIVERGENT_FRAME as i32));
+        assert_eq!(
+            report.last_matching_frame,
+            Some(Frame::new(DIVERGENT_FRAME as i32 - 1))
+        );
+        assert_eq!(report.local_checksum, local[DIVERGENT_FRAME]);
+        assert_eq!(report.recorded_local_checksum, local[DIVERGENT_FRAME]);
+        assert_ne!(Some(report.remote_checksum), report.local_checksum);
+        let frames: Vec<_> = report
+            .inputs
+            .iter()
+            .map(|(frame, _)| frame.as_i32())
+            .collect();
+        assert_eq!(frames, vec![136, 137, 138]);
+        assert_eq!(report.inputs[0].1, script.frames[136]);
+
+        // ceil(log2(301)) probes, and each frame advanced about once rather
+        // than once per probe.
+        assert!(report.simulation_passes <= 9, "{report:?}");
+        assert_eq!(report.frames_simulated, game.advances);
+        assert!(game.advances <= FRAMES, "{report:?}");
+    }
+
+    #[test]
+    fn matching_histories_report_no_divergence() {
+        let script = script();
+        let local = history(&script, None);
+        let bisector = DesyncBisector::<TestConfig>::new(script, local.clone(), local).unwrap();
+        let mut game = Game::default();
+        assert_eq!(
+            bisector.bisect(|requests| game.handle(requests)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn frames_without_a_remote_checksum_are_not_probed() {
+        let script = script();
+        let mut remote = history(&script, Some(DIVERGENT_FRAME));
+        // Only every tenth remote checksum survived.
+        for (frame, checksum) in remote.iter_mut().enumerate() {
+            if frame % 10 != 0 {
+                *checksum = None;
+            }
+        }
+        let bisector = DesyncBisector::<TestConfig>::new(script, Vec::new(), remote).unwrap();
+        let mut game = Game::default();
+
+        let report = bisector
+            .bisect(|requests| game.handle(requests))
+            .unwrap()
+            .expect("divergence");
+
+        assert_eq!(report.frame, Frame::new(140));
+        assert_eq!(report.last_matching_frame, Some(Frame::new(130)));
+        assert_eq!(report.recorded_local_checksum, None);
+    }
+
+    #[test]
+    fn divergence_in_the_initial_state_is_frame_zero() {
+        let script = script();
+        let mut remote = history(&script, None);
+        for checksum in &mut remote {
+            *checksum = checksum.map(|checksum| checksum ^ 1);
+        }
+        let bisector = DesyncBisector::<TestConfig>::new(script, Vec::new(), remote).unwrap();
+        let mut game = Game::default();
+
+        let report = bisector
+            .bisect(|requests| game.handle(requests))
+            .unwrap()
+            .expect("divergence");
+
+        assert_eq!(report.frame, Frame::new(0));
+        assert_eq!(report.last_matching_frame, None);
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::{vec_deque::Drain, VecDeque};
 use std::iter::FusedIterator;
 
-use crate::{Config, EventKind, FortressEvent};
+use crate::{Config, EventKind, FortressEvent, Frame};
 
 /// Whether an event should survive queue pressure ahead of routine updates.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,7 +29,23 @@ const fn event_retention(kind: EventKind) -> EventRetention {
         | EventKind::IncompatibleSession
         | EventKind::ReplayDesync
         | EventKind::SpectatorDivergence
-        | EventKind::PeerDropped => EventRetention::Durable,
+        | EventKind::PeerDropped
+        | EventKind::MatchPaused
+        | EventKind::MatchResumed
+        | EventKind::MatchPauseCancelled
+        | EventKind::SessionFrameLimitReached
+        | EventKind::StreamEnded
+        | EventKind::FrameTag
+        | EventKind::ReliableMessage
+        | EventKind::RejoinStateMismatch
+        | EventKind::LocalAdvanceStalled
+        | EventKind::LocalAdvanceResumed
+        | EventKind::RemoteAdvanceStalled
+        | EventKind::RemoteAdvanceResumed
+        | EventKind::SpectatorInputGap
+        | EventKind::TransportError
+        | EventKind::AuthoritativeDesyncVerdict
+        | EventKind::RollbackTooDeep => EventRetention::Durable,
         #[cfg(feature = "hot-join")]
         EventKind::JoinRequested => EventRetention::Routine,
         #[cfg(feature = "hot-join")]
@@ -37,6 +53,48 @@ const fn event_retention(kind: EventKind) -> EventRetention {
     }
 }
 
+/// A [`FortressEvent`] stamped with when its session generated it.
+///
+/// Returned by [`P2PSession::events_timestamped`] and
+/// [`SpectatorSession::events_timestamped`], so logged events can be lined up
+/// with the application's own frame log or a packet capture.
+///
+/// [`P2PSession::events_timestamped`]: crate::P2PSession::events_timestamped
+/// [`SpectatorSession::events_timestamped`]: crate::SpectatorSession::events_timestamped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampedEvent<T>
+where
+    T: Config,
+{
+    /// The session's current frame when the event was generated.
+    pub frame: Frame,
+    /// Milliseconds since the session was created, read from the session's
+    /// clock (the [`ProtocolConfig::clock`](crate::ProtocolConfig::clock) when
+    /// one is injected).
+    pub session_ms: u64,
+    /// The event itself.
+    pub event: FortressEvent<T>,
+}
+
+impl<T: Config + Copy> Copy for TimestampedEvent<T> where FortressEvent<T>: Copy {}
+
+/// Queue entries the bounded-enqueue helpers can classify.
+pub(crate) trait QueuedEvent {
+    fn event_kind(&self) -> EventKind;
+}
+
+impl<T: Config> QueuedEvent for FortressEvent<T> {
+    fn event_kind(&self) -> EventKind {
+        self.kind()
+    }
+}
+
+impl<T: Config> QueuedEvent for TimestampedEvent<T> {
+    fn event_kind(&self) -> EventKind {
+        self.event.kind()
+    }
+}
+
 /// Removes one event to restore a bounded queue after overflow.
 ///
 /// The oldest routine event is removed first, wherever it sits in the queue.
@@ -44,12 +102,10 @@ const fn event_retention(kind: EventKind) -> EventRetention {
 /// removed: bounded allocation remains mandatory even during a fault storm.
 /// Relative order among all retained events is preserved.
 #[cfg(test)]
-pub(crate) fn remove_event_for_overflow<T: Config>(
-    queue: &mut VecDeque<FortressEvent<T>>,
-) -> Option<FortressEvent<T>> {
+pub(crate) fn remove_event_for_overflow<E: QueuedEvent>(queue: &mut VecDeque<E>) -> Option<E> {
     let routine_index = queue
         .iter()
-        .position(|event| event_retention(event.kind()) == EventRetention::Routine);
+        .position(|event| event_retention(event.event_kind()) == EventRetention::Routine);
     match routine_index {
         Some(index) => queue.remove(index),
         None => queue.pop_front(),
@@ -65,24 +121,24 @@ pub(crate) fn remove_event_for_overflow<T: Config>(
 /// The returned event is exactly the rejected incoming event or removed queued
 /// event. Thus every insertion stays within the reservation and cannot trigger
 /// growth. A zero cap rejects every event.
-pub(crate) fn enqueue_event_bounded<T: Config>(
-    queue: &mut VecDeque<FortressEvent<T>>,
+pub(crate) fn enqueue_event_bounded<E: QueuedEvent>(
+    queue: &mut VecDeque<E>,
     cap: usize,
-    event: FortressEvent<T>,
-) -> Option<FortressEvent<T>> {
+    event: E,
+) -> Option<E> {
     if cap == 0 {
         return Some(event);
     }
     if queue.len() >= cap {
         if let Some(routine_index) = queue
             .iter()
-            .position(|queued| event_retention(queued.kind()) == EventRetention::Routine)
+            .position(|queued| event_retention(queued.event_kind()) == EventRetention::Routine)
         {
             let dropped = queue.remove(routine_index);
             queue.push_back(event);
             return dropped;
         }
-        if event_retention(event.kind()) == EventRetention::Routine {
+        if event_retention(event.event_kind()) == EventRetention::Routine {
             return Some(event);
         }
         let dropped = queue.pop_front();
@@ -128,6 +184,7 @@ pub struct EventDrain<'a, T: Config> {
 
 enum EventDrainInner<'a, T: Config> {
     Queue(Drain<'a, FortressEvent<T>>),
+    Timestamped(Drain<'a, TimestampedEvent<T>>),
     #[allow(dead_code)]
     Empty,
 }
@@ -139,6 +196,13 @@ pub(crate) fn from_drain(drain: Drain<'a, FortressEvent<T>>) -> Self {
         }
     }
 
+    /// Drains a timestamped queue, yielding the events without their stamps.
+    pub(crate) fn from_timestamped_drain(drain: Drain<'a, TimestampedEvent<T>>) -> Self {
+        Self {
+            inner: EventDrainInner::Timestamped(drain),
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn empty() -> Self {
         Self {
@@ -153,6 +217,7 @@ impl<T: Config> Iterator for EventDrain<'_, T> {
     fn next(&mut self) -> Option<Self::Item> {
         match &mut self.inner {
             EventDrainInner::Queue(drain) => drain.next(),
+            EventDrainInner::Timestamped(drain) => drain.next().map(|stamped| stamped.event),
             EventDrainInner::Empty => None,
         }
     }
@@ -160,6 +225,7 @@ fn next(&mut self) -> Option<Self::Item> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         match &self.inner {
             EventDrainInner::Queue(drain) => drain.size_hint(),
+            EventDrainInner::Timestamped(drain) => drain.size_hint(),
             EventDrainInner::Empty => (0, Some(0)),
         }
     }
@@ -169,6 +235,7 @@ impl<T: Config> DoubleEndedIterator for EventDrain<'_, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         match &mut self.inner {
             EventDrainInner::Queue(drain) => drain.next_back(),
+            EventDrainInner::Timestamped(drain) => drain.next_back().map(|stamped| stamped.event),
             EventDrainInner::Empty => None,
         }
     }
@@ -178,6 +245,7 @@ impl<T: Config> ExactSizeIterator for EventDrain<'_, T> {
     fn len(&self) -> usize {
         match &self.inner {
             EventDrainInner::Queue(drain) => drain.len(),
+            EventDrainInner::Timestamped(drain) => drain.len(),
             EventDrainInner::Empty => 0,
         }
     }
@@ -193,6 +261,69 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
+/// A zero-allocation opaque iterator that drains [`TimestampedEvent`]s from a
+/// session.
+///
+/// The timestamped counterpart of [`EventDrain`], obtained from
+/// [`P2PSession::events_timestamped()`] or
+/// [`SpectatorSession::events_timestamped()`]. Both drain the same queue, so
+/// each event is returned by exactly one of them.
+///
+/// # Examples
+///
+/// ```ignore
+/// for stamped in session.events_timestamped() {
+///     println!("[{}ms frame {}] {:?}", stamped.session_ms, stamped.frame, stamped.event);
+/// }
+/// ```
+///
+/// [`P2PSession::events_timestamped()`]: crate::P2PSession::events_timestamped
+/// [`SpectatorSession::events_timestamped()`]: crate::SpectatorSession::events_timestamped
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TimestampedEventDrain<'a, T: Config> {
+    inner: Drain<'a, TimestampedEvent<T>>,
+}
+
+impl<'a, T: Config> TimestampedEventDrain<'a, T> {
+    pub(crate) fn from_drain(drain: Drain<'a, TimestampedEvent<T>>) -> Self {
+        Self { inner: drain }
+    }
+}
+
+impl<T: Config> Iterator for TimestampedEventDrain<'_, T> {
+    type Item = TimestampedEvent<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Config> DoubleEndedIterator for TimestampedEventDrain<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T: Config> ExactSizeIterator for TimestampedEventDrain<'_, T> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<T: Config> FusedIterator for TimestampedEventDrain<'_, T> {}
+
+impl<T: Config> std::fmt::Debug for TimestampedEventDrain<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimestampedEventDrain")
+            .field("remaining", &self.len())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 #[allow(
     clippy::panic,
@@ -240,8 +371,27 @@ fn event_retention_classifies_every_kind() {
             (EventKind::SpectatorDivergence, EventRetention::Durable),
             (EventKind::InputDelayRecommendation, EventRetention::Routine),
             (EventKind::PeerDropped, EventRetention::Durable),
+            (EventKind::MatchPaused, EventRetention::Durable),
+            (EventKind::MatchResumed, EventRetention::Durable),
+            (EventKind::MatchPauseCancelled, EventRetention::Durable),
+            (EventKind::SessionFrameLimitReached, EventRetention::Durable),
+            (EventKind::StreamEnded, EventRetention::Durable),
+            (EventKind::FrameTag, EventRetention::Durable),
+            (EventKind::ReliableMessage, EventRetention::Durable),
+            (EventKind::RejoinStateMismatch, EventRetention::Durable),
+            (EventKind::LocalAdvanceStalled, EventRetention::Durable),
+            (EventKind::LocalAdvanceResumed, EventRetention::Durable),
+            (EventKind::RemoteAdvanceStalled, EventRetention::Durable),
+            (EventKind::RemoteAdvanceResumed, EventRetention::Durable),
+            (EventKind::SpectatorInputGap, EventRetention::Durable),
+            (EventKind::TransportError, EventRetention::Durable),
+            (
+                EventKind::AuthoritativeDesyncVerdict,
+                EventRetention::Durable,
+            ),
+            (EventKind::RollbackTooDeep, EventRetention::Durable),
         ];
-        assert_eq!(cases.len(), 13);
+        assert_eq!(cases.len(), 29);
         for (kind, expected) in cases {
             assert_eq!(
                 event_retention(kind),
@@ -252,7 +402,7 @@ fn event_retention_classifies_every_kind() {
 
         #[cfg(feature = "hot-join")]
         {
-            assert_eq!(EventKind::COUNT, 15);
+            assert_eq!(EventKind::COUNT, 31);
             assert_eq!(
                 event_retention(EventKind::JoinRequested),
                 EventRetention::Routine
@@ -270,12 +420,17 @@ fn overflow_removes_oldest_routine_and_preserves_relative_order() {
         let routine_a = make_event(1);
         let durable_b = FortressEvent::Disconnected { addr: addr(7002) };
         let routine_b = make_event(2);
-        let mut queue = VecDeque::from([durable_a, routine_a, durable_b, routine_b]);
+        let mut queue = VecDeque::from([
+            durable_a.clone(),
+            routine_a.clone(),
+            durable_b.clone(),
+            routine_b.clone(),
+        ]);
 
         assert_eq!(remove_event_for_overflow(&mut queue), Some(routine_a));
         assert_eq!(
             queue,
-            VecDeque::from([durable_a, durable_b, routine_b]),
+            VecDeque::from([durable_a.clone(), durable_b.clone(), routine_b.clone()]),
             "removing a middle event must preserve FIFO order among survivors"
         );
         assert_eq!(remove_event_for_overflow(&mut queue), Some(routine_b));
@@ -287,9 +442,12 @@ fn routine_arrival_cannot_displace_full_durable_queue() {
         let durable_a = FortressEvent::Synchronized { addr: addr(7011) };
         let durable_b = FortressEvent::Disconnected { addr: addr(7012) };
         let routine = make_event(3);
-        let mut queue = VecDeque::from([durable_a, durable_b]);
+        let mut queue = VecDeque::from([durable_a.clone(), durable_b.clone()]);
 
-        assert_eq!(enqueue_event_bounded(&mut queue, 2, routine), Some(routine));
+        assert_eq!(
+            enqueue_event_bounded(&mut queue, 2, routine.clone()),
+            Some(routine)
+        );
         assert_eq!(queue, VecDeque::from([durable_a, durable_b]));
     }
 
@@ -301,10 +459,10 @@ fn durable_arrival_to_full_durable_queue_drops_oldest_and_stays_bounded() {
         };
         let disconnected = FortressEvent::Disconnected { addr: addr(7021) };
         let synchronized = FortressEvent::Synchronized { addr: addr(7022) };
-        let mut queue = VecDeque::from([peer_dropped, disconnected]);
+        let mut queue = VecDeque::from([peer_dropped.clone(), disconnected.clone()]);
 
         assert_eq!(
-            enqueue_event_bounded(&mut queue, 2, synchronized),
+            enqueue_event_bounded(&mut queue, 2, synchronized.clone()),
             Some(peer_dropped),
             "a durable-only queue must still stay bounded by dropping its oldest event"
         );
@@ -317,7 +475,7 @@ fn bounded_enqueue_pre_evicts_without_exceeding_reserved_capacity() {
         queue.try_reserve_exact(2).expect("test queue reserves");
         let capacity = queue.capacity();
         let durable = FortressEvent::Disconnected { addr: addr(7031) };
-        assert_eq!(enqueue_event_bounded(&mut queue, 2, durable), None);
+        assert_eq!(enqueue_event_bounded(&mut queue, 2, durable.clone()), None);
         assert_eq!(enqueue_event_bounded(&mut queue, 2, make_event(1)), None);
 
         assert_eq!(
@@ -421,6 +579,44 @@ fn size_hint_for_queue_drain() {
         assert_eq!(drain.size_hint(), (3, Some(3)));
     }
 
+    #[test]
+    fn timestamped_drains_yield_events_with_and_without_stamps() {
+        let stamped = |skip: u32| TimestampedEvent {
+            frame: Frame::new(i32::try_from(skip).unwrap()),
+            session_ms: u64::from(skip) * 16,
+            event: make_event(skip),
+        };
+        let mut queue: VecDeque<TimestampedEvent<TestConfig>> =
+            VecDeque::from([stamped(1), stamped(2), stamped(3)]);
+
+        {
+            let mut drain = TimestampedEventDrain::from_drain(queue.drain(..1));
+            assert_eq!(drain.len(), 1);
+            assert_eq!(drain.next(), Some(stamped(1)));
+            assert!(drain.next().is_none());
+        }
+
+        let events: Vec<_> = EventDrain::from_timestamped_drain(queue.drain(..)).collect();
+        assert_eq!(events, vec![make_event(2), make_event(3)]);
+    }
+
+    #[test]
+    fn bounded_enqueue_classifies_timestamped_events_by_their_event() {
+        let stamped = |event| TimestampedEvent::<TestConfig> {
+            frame: Frame::new(0),
+            session_ms: 0,
+            event,
+        };
+        let durable = stamped(FortressEvent::Disconnected { addr: addr(7041) });
+        let mut queue = VecDeque::from([stamped(make_event(1)), durable.clone()]);
+
+        assert_eq!(
+            enqueue_event_bounded(&mut queue, 2, stamped(make_event(2))),
+            Some(stamped(make_event(1)))
+        );
+        assert_eq!(queue, VecDeque::from([durable, stamped(make_event(2))]));
+    }
+
     #[test]
     fn size_hint_for_empty_drain() {
         let drain: EventDrain<'_, TestConfig> = EventDrain::empty();
@@ -0,0 +1,229 @@
+//! Render-rate interpolation metadata for spectators.
+//!
+//! A [`SpectatorSession`](crate::SpectatorSession) only ever simulates whole
+//! frames, but a broadcast overlay usually renders at a different rate than
+//! the match ticks. The session watches when the host's confirmed inputs
+//! arrive, smooths the host's frame cadence from those arrival times (seeded
+//! with the configured fps), and at each simulated frame estimates when the
+//! next one will follow. [`SpectatorSession::frame_progress`](crate::SpectatorSession::frame_progress)
+//! turns that estimate into a fraction a renderer can interpolate with.
+
+use web_time::{Duration, Instant};
+
+use crate::time_sync::frames_to_duration;
+use crate::Frame;
+
+/// Weight of a new cadence sample in the smoothed frame interval, as
+/// `1 / CADENCE_SMOOTHING`.
+const CADENCE_SMOOTHING: u32 = 8;
+/// A cadence sample is clamped to this many times the nominal frame interval
+/// (and its reciprocal), so one stalled or bursty packet cannot swing the
+/// estimate.
+const CADENCE_CLAMP: u32 = 4;
+
+/// Where a spectator's playback stands between two simulated frames, as
+/// returned by [`SpectatorSession::frame_progress`](crate::SpectatorSession::frame_progress).
+///
+/// Render `frame` blended towards the frame after it by `fraction`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameProgress {
+    /// The last simulated frame.
+    pub frame: Frame,
+    /// How far playback has moved from `frame` towards the next frame, from
+    /// `0.0` right after `frame` was simulated to `1.0` once the next frame is
+    /// due. Never decreases until the next frame is simulated, and stays at
+    /// `1.0` while the next frame is late.
+    pub fraction: f64,
+    /// When the next frame is expected to be simulated, on the session clock.
+    pub next_frame_at: Instant,
+    /// The host's smoothed frame interval, observed from the arrival times of
+    /// its inputs.
+    pub frame_interval: Duration,
+}
+
+/// Tracks the host's input cadence and the current frame's timing.
+#[derive(Debug, Clone)]
+pub(crate) struct FramePacer {
+    /// Frame interval implied by the configured fps.
+    nominal: Duration,
+    /// Smoothed interval between the host's frames.
+    interval: Duration,
+    /// The newest arrived frame and when it arrived.
+    last_arrival: Option<(Frame, Instant)>,
+    /// The last simulated frame, when it was simulated, and when the next
+    /// frame was then expected.
+    simulated: Option<(Frame, Instant, Instant)>,
+}
+
+impl FramePacer {
+    /// A pacer that assumes `fps` frames per second until the host's inputs
+    /// say otherwise.
+    pub(crate) fn new(fps: usize) -> Self {
+        let nominal = frames_to_duration(1, fps);
+        Self {
+            nominal,
+            interval: nominal,
+            last_arrival: None,
+            simulated: None,
+        }
+    }
+
+    /// Records that the host's inputs up to `frame` arrived at `now`.
+    ///
+    /// Frames arriving together count as one arrival: the elapsed time since
+    /// the previous arrival is spread over all frames it brought.
+    pub(crate) fn record_arrival(&mut self, frame: Frame, now: Instant) {
+        let Some((last_frame, last_at)) = self.last_arrival else {
+            self.last_arrival = Some((frame, now));
+            return;
+        };
+        if frame <= last_frame {
+            return;
+        }
+        let elapsed = now.saturating_duration_since(last_at);
+        if elapsed.is_zero() {
+            // Same poll as the previous arrival: extend it.
+            self.last_arrival = Some((frame, last_at));
+            return;
+        }
+        let frames = u32::try_from(frame.as_i32() - last_frame.as_i32()).unwrap_or(u32::MAX);
+        let sample = (elapsed / frames).clamp(
+            self.nominal / CADENCE_CLAMP,
+            self.nominal.saturating_mul(CADENCE_CLAMP),
+        );
+        self.interval = if sample >= self.interval {
+            self.interval + (sample - self.interval) / CADENCE_SMOOTHING
+        } else {
+            self.interval - (self.interval - sample) / CADENCE_SMOOTHING
+        };
+        self.last_arrival = Some((frame, now));
+    }
+
+    /// Records that `frame` was simulated at `now`, while the newest frame
+    /// that may be simulated was `viewable`, and latches when the next frame
+    /// is expected. Behind the viewable edge that is one interval from now; at
+    /// the edge the next frame waits for the host, so it is one interval after
+    /// the newest arrival.
+    pub(crate) fn record_simulated(&mut self, frame: Frame, viewable: Frame, now: Instant) {
+        let next_at = match self.last_arrival {
+            Some((_, arrived_at)) if frame >= viewable => (arrived_at + self.interval).max(now),
+            _ => now + self.interval,
+        };
+        self.simulated = Some((frame, now, next_at));
+    }
+
+    /// The progress towards the frame after the last simulated one at `now`,
+    /// or `None` before the first simulated frame.
+    pub(crate) fn progress(&self, now: Instant) -> Option<FrameProgress> {
+        let (frame, simulated_at, next_frame_at) = self.simulated?;
+        let span = next_frame_at.saturating_duration_since(simulated_at);
+        let elapsed = now.saturating_duration_since(simulated_at);
+        let fraction = if span.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f64() / span.as_secs_f64()).min(1.0)
+        };
+        Some(FrameProgress {
+            frame,
+            fraction,
+            next_frame_at,
+            frame_interval: self.interval,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn interval_starts_at_the_configured_fps() {
+        let pacer = FramePacer::new(50);
+        assert_eq!(pacer.interval, ms(20));
+        assert_eq!(pacer.progress(Instant::now()), None);
+    }
+
+    #[test]
+    fn interval_converges_to_the_observed_cadence() {
+        let start = Instant::now();
+        let mut pacer = FramePacer::new(60);
+        for frame in 0..100 {
+            pacer.record_arrival(Frame::new(frame), start + ms(33) * frame as u32);
+        }
+        let interval = pacer.interval.as_secs_f64() * 1000.0;
+        assert!((interval - 33.0).abs() < 0.5, "{interval}");
+    }
+
+    #[test]
+    fn bundled_frames_split_the_elapsed_time() {
+        let start = Instant::now();
+        let mut pacer = FramePacer::new(30);
+        // Two frames per packet every 66ms; a repeated poll at the same
+        // instant brings nothing new.
+        for packet in 0..100_u32 {
+            let at = start + ms(66) * packet;
+            pacer.record_arrival(Frame::new(2 * packet as i32 + 1), at);
+            pacer.record_arrival(Frame::new(2 * packet as i32 + 1), at);
+        }
+        let interval = pacer.interval.as_secs_f64() * 1000.0;
+        assert!((interval - 33.0).abs() < 0.5, "{interval}");
+    }
+
+    #[test]
+    fn stalls_are_clamped() {
+        let start = Instant::now();
+        let mut pacer = FramePacer::new(60);
+        pacer.record_arrival(Frame::new(0), start);
+        pacer.record_arrival(Frame::new(1), start + Duration::from_secs(10));
+        assert!(pacer.interval < ms(30), "{:?}", pacer.interval);
+    }
+
+    #[test]
+    fn fraction_grows_to_one_and_resets_on_the_next_frame() {
+        let start = Instant::now();
+        let mut pacer = FramePacer::new(50);
+        pacer.record_arrival(Frame::new(3), start);
+        pacer.record_simulated(Frame::new(0), Frame::new(3), start);
+
+        let at = |offset| pacer.progress(start + ms(offset)).unwrap().fraction;
+        assert_eq!(at(0), 0.0);
+        assert!((at(5) - 0.25).abs() < 1e-9);
+        assert!((at(10) - 0.5).abs() < 1e-9);
+        assert_eq!(at(20), 1.0);
+        assert_eq!(at(35), 1.0, "a late frame holds at 1.0");
+
+        pacer.record_simulated(Frame::new(1), Frame::new(3), start + ms(35));
+        let progress = pacer.progress(start + ms(35)).unwrap();
+        assert_eq!(progress.frame, Frame::new(1));
+        assert_eq!(progress.fraction, 0.0);
+        assert_eq!(progress.next_frame_at, start + ms(55));
+    }
+
+    #[test]
+    fn next_frame_waits_for_the_host_at_the_live_edge() {
+        let start = Instant::now();
+        let mut pacer = FramePacer::new(50);
+        pacer.record_arrival(Frame::new(4), start);
+        // Simulated the newest arrived frame 5ms after it arrived: the next
+        // one is due an interval after the arrival, 15ms from now.
+        pacer.record_simulated(Frame::new(4), Frame::new(4), start + ms(5));
+        let progress = pacer.progress(start + ms(10)).unwrap();
+        assert_eq!(progress.next_frame_at, start + ms(20));
+        assert!((progress.fraction - 1.0 / 3.0).abs() < 1e-9);
+        // Two frames behind a delayed edge: arrival times do not matter.
+        pacer.record_simulated(Frame::new(2), Frame::new(4), start + ms(5));
+        let progress = pacer.progress(start + ms(5)).unwrap();
+        assert_eq!(progress.next_frame_at, start + ms(25));
+    }
+}
@@ -0,0 +1,280 @@
+//! Per-frame simulation flags for replay scrubbers and connection-quality
+//! timelines.
+//!
+//! With [`SessionBuilder::with_frame_timeline`](crate::SessionBuilder::with_frame_timeline)
+//! enabled, a [`P2PSession`](crate::P2PSession) records one [`FrameFlags`]
+//! byte per simulated frame in a fixed-size ring, readable through
+//! [`P2PSession::frame_timeline`](crate::P2PSession::frame_timeline). The first
+//! simulation of a frame sets its flags; a rollback that resimulates it only
+//! adds flags.
+
+use crate::error::allocation_failed;
+use crate::{FortressError, Frame};
+
+/// Largest capacity accepted by
+/// [`SessionBuilder::with_frame_timeline`](crate::SessionBuilder::with_frame_timeline).
+pub(crate) const MAX_FRAME_TIMELINE_CAPACITY: usize = 1 << 16;
+
+/// How a frame was simulated, as recorded by
+/// [`P2PSession::frame_timeline`](crate::P2PSession::frame_timeline).
+///
+/// A set of flags packed in one byte. The first simulation of a frame sets
+/// exactly one of [`FIRST_PASS_CONFIRMED`](Self::FIRST_PASS_CONFIRMED) and
+/// [`PREDICTED`](Self::PREDICTED); rollbacks add
+/// [`RESIMULATED`](Self::RESIMULATED) and
+/// [`ROLLBACK_TARGET`](Self::ROLLBACK_TARGET) later and never clear a flag.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::FrameFlags;
+///
+/// let flags = FrameFlags::PREDICTED | FrameFlags::RESIMULATED;
+/// assert!(flags.contains(FrameFlags::RESIMULATED));
+/// assert!(!flags.contains(FrameFlags::FIRST_PASS_CONFIRMED));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FrameFlags(u8);
+
+impl FrameFlags {
+    /// The frame was first simulated with every player's input confirmed.
+    pub const FIRST_PASS_CONFIRMED: Self = Self(1);
+    /// The frame was first simulated with at least one predicted input.
+    pub const PREDICTED: Self = Self(1 << 1);
+    /// A rollback simulated the frame again.
+    pub const RESIMULATED: Self = Self(1 << 2);
+    /// A rollback loaded the state of this frame to resimulate from it.
+    pub const ROLLBACK_TARGET: Self = Self(1 << 3);
+
+    /// Every flag with its name, in bit order.
+    const NAMED: [(Self, &'static str); 4] = [
+        (Self::FIRST_PASS_CONFIRMED, "FIRST_PASS_CONFIRMED"),
+        (Self::PREDICTED, "PREDICTED"),
+        (Self::RESIMULATED, "RESIMULATED"),
+        (Self::ROLLBACK_TARGET, "ROLLBACK_TARGET"),
+    ];
+
+    /// No flags set.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The raw bits, one per flag.
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Whether no flag is set.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every flag of `other` is set in `self`.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets every flag of `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl std::ops::BitOr for FrameFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for FrameFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+/// Lists the set flags, e.g. `FrameFlags(PREDICTED | RESIMULATED)`.
+impl std::fmt::Debug for FrameFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FrameFlags(")?;
+        let mut first = true;
+        for (flag, name) in Self::NAMED {
+            if self.contains(flag) {
+                if !first {
+                    write!(f, " | ")?;
+                }
+                write!(f, "{name}")?;
+                first = false;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+/// Ring of the flags of the `capacity` most recently simulated frames.
+#[derive(Debug)]
+pub(crate) struct FrameTimeline {
+    /// Flags indexed by `frame % capacity`; its length is the capacity.
+    flags: Vec<FrameFlags>,
+    /// The newest recorded frame, or [`Frame::NULL`] before the first one.
+    newest: Frame,
+    /// Contiguous frames recorded up to `newest`, at most the capacity.
+    len: usize,
+}
+
+impl FrameTimeline {
+    /// An empty timeline keeping the `capacity` most recent frames; `capacity`
+    /// must be at least 1.
+    pub(crate) fn new(capacity: usize) -> Result<Self, FortressError> {
+        let capacity = capacity.max(1);
+        let mut flags = Vec::new();
+        flags
+            .try_reserve_exact(capacity)
+            .map_err(|_err| allocation_failed("frame_timeline.flags", capacity))?;
+        // alloc-bound: the builder caps `capacity` at `MAX_FRAME_TIMELINE_CAPACITY`.
+        flags.resize(capacity, FrameFlags::empty());
+        Ok(Self {
+            flags,
+            newest: Frame::NULL,
+            len: 0,
+        })
+    }
+
+    /// Records the first simulation of `frame`. A frame that does not follow
+    /// the newest one (a snapshot load moved the session) restarts the
+    /// timeline.
+    pub(crate) fn record(&mut self, frame: Frame, flags: FrameFlags) {
+        if !frame.is_valid() {
+            return;
+        }
+        if self.newest.is_null() || frame.as_i32() != self.newest.as_i32().saturating_add(1) {
+            self.len = 0;
+        }
+        self.newest = frame;
+        self.len = (self.len + 1).min(self.flags.len());
+        if let Some(slot) = self.slot_mut(frame) {
+            *slot = flags;
+        }
+    }
+
+    /// Adds `flags` to `frame` if it is still in the timeline.
+    pub(crate) fn mark(&mut self, frame: Frame, flags: FrameFlags) {
+        if self.contains(frame) {
+            if let Some(slot) = self.slot_mut(frame) {
+                slot.insert(flags);
+            }
+        }
+    }
+
+    /// The recorded frames and their flags, oldest first.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Frame, FrameFlags)> + '_ {
+        let oldest = self.oldest();
+        (0..self.len).filter_map(move |offset| {
+            let frame = Frame::new(oldest.saturating_add(i32::try_from(offset).ok()?));
+            let flags = *self.flags.get(self.index(frame))?;
+            Some((frame, flags))
+        })
+    }
+
+    /// The oldest recorded frame number; only meaningful while `len > 0`.
+    fn oldest(&self) -> i32 {
+        let span = i32::try_from(self.len).unwrap_or(i32::MAX);
+        self.newest.as_i32().saturating_sub(span).saturating_add(1)
+    }
+
+    fn contains(&self, frame: Frame) -> bool {
+        self.len > 0 && frame <= self.newest && frame.as_i32() >= self.oldest()
+    }
+
+    fn index(&self, frame: Frame) -> usize {
+        usize::try_from(frame.as_i32()).unwrap_or(0) % self.flags.len()
+    }
+
+    fn slot_mut(&mut self, frame: Frame) -> Option<&mut FrameFlags> {
+        let index = self.index(frame);
+        self.flags.get_mut(index)
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    fn frames(timeline: &FrameTimeline) -> Vec<(i32, FrameFlags)> {
+        timeline
+            .iter()
+            .map(|(frame, flags)| (frame.as_i32(), flags))
+            .collect()
+    }
+
+    #[test]
+    fn debug_lists_the_set_flags() {
+        assert_eq!(format!("{:?}", FrameFlags::empty()), "FrameFlags()");
+        assert_eq!(
+            format!("{:?}", FrameFlags::PREDICTED | FrameFlags::ROLLBACK_TARGET),
+            "FrameFlags(PREDICTED | ROLLBACK_TARGET)"
+        );
+    }
+
+    #[test]
+    fn keeps_the_most_recent_frames() {
+        let mut timeline = FrameTimeline::new(3).unwrap();
+        assert!(frames(&timeline).is_empty());
+        for frame in 0..5 {
+            timeline.record(Frame::new(frame), FrameFlags::PREDICTED);
+        }
+        assert_eq!(
+            frames(&timeline),
+            vec![
+                (2, FrameFlags::PREDICTED),
+                (3, FrameFlags::PREDICTED),
+                (4, FrameFlags::PREDICTED),
+            ]
+        );
+    }
+
+    #[test]
+    fn marks_only_add_flags_to_recorded_frames() {
+        let mut timeline = FrameTimeline::new(3).unwrap();
+        for frame in 0..4 {
+            timeline.record(Frame::new(frame), FrameFlags::FIRST_PASS_CONFIRMED);
+        }
+        timeline.mark(Frame::new(0), FrameFlags::RESIMULATED);
+        timeline.mark(Frame::new(2), FrameFlags::RESIMULATED);
+        timeline.mark(Frame::new(4), FrameFlags::RESIMULATED);
+        assert_eq!(
+            frames(&timeline),
+            vec![
+                (1, FrameFlags::FIRST_PASS_CONFIRMED),
+                (
+                    2,
+                    FrameFlags::FIRST_PASS_CONFIRMED | FrameFlags::RESIMULATED
+                ),
+                (3, FrameFlags::FIRST_PASS_CONFIRMED),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_gap_restarts_the_timeline() {
+        let mut timeline = FrameTimeline::new(4).unwrap();
+        timeline.record(Frame::new(0), FrameFlags::PREDICTED);
+        timeline.record(Frame::new(1), FrameFlags::PREDICTED);
+        timeline.record(Frame::new(10), FrameFlags::FIRST_PASS_CONFIRMED);
+        assert_eq!(
+            frames(&timeline),
+            vec![(10, FrameFlags::FIRST_PASS_CONFIRMED)]
+        );
+    }
+}
@@ -787,7 +787,14 @@ pub(crate) fn apply_npeer_snapshot<T: Config>(
         }
     }
 
-    Ok((load, FortressRequest::AdvanceFrame { inputs }))
+    // The frame before the snapshot was never simulated here.
+    Ok((
+        load,
+        FortressRequest::AdvanceFrame {
+            inputs,
+            previous_inputs: InputVec::new(),
+        },
+    ))
 }
 
 #[cfg(all(test, feature = "hot-join"))]
@@ -1495,7 +1502,7 @@ fn apply_npeer_snapshot_bridges_one_frame_and_positions_real_from_f() {
         // order — the joining slot presented Disconnected (it was frozen at S
         // on every peer), the others Confirmed.
         match bridge {
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 assert_eq!(
                     inputs.as_slice(),
                     &[
@@ -1644,7 +1651,7 @@ fn apply_npeer_snapshot_freezes_carried_disconnected_slot_with_carried_value() {
         // Bridge statuses from the carried predicate: the live slot
         // Confirmed, BOTH dead slots Disconnected.
         match bridge {
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 assert_eq!(
                     inputs.as_slice(),
                     &[
@@ -1737,7 +1744,7 @@ fn apply_npeer_snapshot_presents_slot_frozen_at_snapshot_frame_confirmed() {
         .unwrap();
 
         match bridge {
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 assert_eq!(
                     inputs.as_slice(),
                     &[
@@ -1868,7 +1875,7 @@ fn apply_npeer_snapshot_bridge_frame_replay_presents_original_statuses() {
         )
         .unwrap();
         let original = match bridge {
-            FortressRequest::AdvanceFrame { inputs } => inputs,
+            FortressRequest::AdvanceFrame { inputs, .. } => inputs,
             other => panic!("expected AdvanceFrame, got {other}"),
         };
         assert_eq!(joiner.current_frame(), activation_frame);
@@ -1955,7 +1962,7 @@ fn apply_npeer_snapshot_replay_at_freeze_equals_snapshot_boundary_stays_confirme
         )
         .unwrap();
         let original = match bridge {
-            FortressRequest::AdvanceFrame { inputs } => inputs,
+            FortressRequest::AdvanceFrame { inputs, .. } => inputs,
             other => panic!("expected AdvanceFrame, got {other}"),
         };
         // The boundary presents the real frame-S input Confirmed mesh-wide
@@ -0,0 +1,810 @@
+//! Match pause/resume negotiation for [`P2PSession`](crate::P2PSession).
+//!
+//! One peer proposes; every other running peer answers. A pause proposal names
+//! a frame, and each peer that accepts stops advancing at the larger of that
+//! frame and its own current frame (its *hold*), reporting the hold in its ack.
+//! Once every peer accepted, the proposer commits the largest hold, so every
+//! peer can reach the agreed frame and none has passed it. A resume proposal
+//! names the paused frame and is accepted only by peers already paused there.
+//!
+//! The outcome is deterministic on every peer:
+//! - a committed negotiation is final; the proposer retransmits the commit
+//!   until every peer acknowledges it or disconnects;
+//! - a rejection, the proposer's [`MATCH_PAUSE_TIMEOUT`], or a peer
+//!   disconnecting before the commit cancels the negotiation on the proposer
+//!   and on every peer that had accepted.
+//!
+//! Concurrent proposals reject each other (a peer with an open negotiation
+//! declines every other one), so both are cancelled. The state machine here is
+//! I/O-free: the session supplies the live peer set and clock, then sends the
+//! returned messages and maps the returned events.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use web_time::{Duration, Instant};
+
+use crate::error::InvalidRequestKind;
+use crate::network::messages::{MatchPause, MatchPauseAck, MatchPauseKind, MatchPauseStage};
+use crate::network::protocol::MatchPauseMessage;
+use crate::Frame;
+
+/// Default number of frames past the confirmed frame at which a pause is
+/// proposed (about a quarter second at 60 FPS).
+pub(crate) const DEFAULT_MATCH_PAUSE_MARGIN: usize = 16;
+
+/// How long a proposer waits for every peer to accept before cancelling.
+pub(crate) const MATCH_PAUSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Session-visible outcome of one negotiation step.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum MatchPauseEvent {
+    /// A resume was committed; the match continues from `frame`.
+    Resumed { frame: Frame },
+    /// A negotiation this peer took part in closed without an agreement.
+    Cancelled { frame: Frame, resume: bool },
+}
+
+/// Messages to send and events to emit after a negotiation step.
+#[derive(Debug, Default)]
+pub(crate) struct MatchPauseEffects {
+    /// `(peer, message)` pairs, in send order.
+    pub sends: Vec<(u16, MatchPauseMessage)>,
+    pub events: Vec<MatchPauseEvent>,
+}
+
+/// Proposer-only bookkeeping of an open negotiation.
+#[derive(Debug)]
+struct Proposal {
+    /// Every peer the proposal was sent to.
+    asked: BTreeSet<u16>,
+    /// Peers that have not accepted yet.
+    pending: BTreeSet<u16>,
+    /// Largest hold reported so far (the commit frame once `pending` drains).
+    agreed: Frame,
+    started_at: Instant,
+}
+
+/// A negotiation this peer proposed or accepted, not yet committed or cancelled.
+#[derive(Debug)]
+struct Negotiation {
+    proposer: u16,
+    sequence: u32,
+    kind: MatchPauseKind,
+    /// The proposed frame.
+    frame: Frame,
+    /// The frame this peer holds at while the negotiation is open.
+    hold: Frame,
+    /// `Some` when this peer is the proposer.
+    proposal: Option<Proposal>,
+}
+
+/// A commit or cancel the proposer retransmits until every peer acknowledges.
+#[derive(Debug)]
+struct Closing {
+    message: MatchPause,
+    pending: BTreeSet<u16>,
+}
+
+/// Per-session match pause/resume state.
+#[derive(Debug)]
+pub(crate) struct MatchPauseState {
+    /// Lowest local player handle, identifying this peer as a proposer.
+    local_id: u16,
+    next_sequence: u32,
+    open: Option<Negotiation>,
+    closing: Option<Closing>,
+    /// Highest closed (answered, committed, or cancelled) sequence per remote
+    /// proposer, so a stale duplicate proposal is never accepted again.
+    closed: BTreeMap<u16, u32>,
+    /// The agreed pause frame while a pause is committed.
+    paused_at: Option<Frame>,
+    /// Whether this peer has reached `paused_at` (and reported it).
+    reached: bool,
+}
+
+impl MatchPauseState {
+    pub(crate) fn new(local_id: u16) -> Self {
+        Self {
+            local_id,
+            next_sequence: 1,
+            open: None,
+            closing: None,
+            closed: BTreeMap::new(),
+            paused_at: None,
+            reached: false,
+        }
+    }
+
+    /// The frame this peer must not advance past, if any.
+    pub(crate) fn hold_frame(&self) -> Option<Frame> {
+        self.paused_at.or_else(|| {
+            self.open
+                .as_ref()
+                .filter(|open| open.kind == MatchPauseKind::Pause)
+                .map(|open| open.hold)
+        })
+    }
+
+    /// Whether no negotiation is open or closing, so [`poll`](Self::poll) has
+    /// nothing to time out or retransmit.
+    pub(crate) fn is_idle(&self) -> bool {
+        self.open.is_none() && self.closing.is_none()
+    }
+
+    /// Whether this peer has reached an agreed pause and not resumed since.
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused_at.is_some() && self.reached
+    }
+
+    /// Marks the agreed pause frame as reached, returning it the first time.
+    pub(crate) fn reach(&mut self, current_frame: Frame) -> Option<Frame> {
+        match self.paused_at {
+            Some(frame) if !self.reached && current_frame >= frame => {
+                self.reached = true;
+                Some(frame)
+            },
+            _ => None,
+        }
+    }
+
+    /// Opens a pause (`frame` is the proposed frame) or resume negotiation
+    /// with every peer in `peers`. Commits immediately when `peers` is empty.
+    pub(crate) fn propose(
+        &mut self,
+        kind: MatchPauseKind,
+        frame: Frame,
+        peers: &BTreeSet<u16>,
+        now: Instant,
+        effects: &mut MatchPauseEffects,
+    ) -> Result<(), InvalidRequestKind> {
+        if self.open.is_some() {
+            return Err(InvalidRequestKind::MatchPauseInProgress);
+        }
+        let frame = match kind {
+            MatchPauseKind::Pause if self.paused_at.is_some() => {
+                return Err(InvalidRequestKind::MatchAlreadyPaused);
+            },
+            MatchPauseKind::Pause => frame,
+            MatchPauseKind::Resume => match self.paused_at {
+                Some(paused_at) if self.reached => paused_at,
+                _ => return Err(InvalidRequestKind::MatchNotPaused),
+            },
+        };
+        let sequence = self.next_sequence;
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.open = Some(Negotiation {
+            proposer: self.local_id,
+            sequence,
+            kind,
+            frame,
+            hold: frame,
+            proposal: Some(Proposal {
+                asked: peers.clone(),
+                pending: peers.clone(),
+                agreed: frame,
+                started_at: now,
+            }),
+        });
+        self.broadcast_proposal(effects);
+        self.commit_if_agreed(effects);
+        Ok(())
+    }
+
+    /// Handles one message received from `source`.
+    pub(crate) fn on_message(
+        &mut self,
+        source: u16,
+        message: MatchPauseMessage,
+        current_frame: Frame,
+        effects: &mut MatchPauseEffects,
+    ) {
+        match message {
+            MatchPauseMessage::Control(control) => {
+                self.on_control(source, control, current_frame, effects);
+            },
+            MatchPauseMessage::Ack(ack) => self.on_ack(source, ack, effects),
+        }
+    }
+
+    /// Drives timeouts, peer loss, and retransmission. `live_peers` holds every
+    /// running, connected remote peer.
+    pub(crate) fn poll(
+        &mut self,
+        now: Instant,
+        live_peers: &BTreeSet<u16>,
+        effects: &mut MatchPauseEffects,
+    ) {
+        if let Some(closing) = self.closing.as_mut() {
+            closing.pending.retain(|peer| live_peers.contains(peer));
+            if closing.pending.is_empty() {
+                self.closing = None;
+            }
+        }
+
+        if let Some(open) = self.open.as_ref() {
+            let cancel = match open.proposal.as_ref() {
+                Some(proposal) => {
+                    now.saturating_duration_since(proposal.started_at) >= MATCH_PAUSE_TIMEOUT
+                        || proposal
+                            .pending
+                            .iter()
+                            .any(|peer| !live_peers.contains(peer))
+                },
+                None => !live_peers.contains(&open.proposer),
+            };
+            if cancel {
+                self.cancel_open(effects);
+            }
+        }
+
+        // Poll-driven retransmission, like the graceful-drop barrier: the held
+        // peers are not sending inputs, so there is no other traffic to pace it.
+        self.broadcast_proposal(effects);
+        if let Some(closing) = self.closing.as_ref() {
+            for &peer in &closing.pending {
+                effects
+                    .sends
+                    .push((peer, MatchPauseMessage::Control(closing.message)));
+            }
+        }
+    }
+
+    fn broadcast_proposal(&self, effects: &mut MatchPauseEffects) {
+        let Some(open) = self.open.as_ref() else {
+            return;
+        };
+        let Some(proposal) = open.proposal.as_ref() else {
+            return;
+        };
+        let message = MatchPause {
+            proposer: open.proposer,
+            sequence: open.sequence,
+            kind: open.kind,
+            stage: MatchPauseStage::Propose,
+            frame: open.frame,
+        };
+        for &peer in &proposal.pending {
+            effects
+                .sends
+                .push((peer, MatchPauseMessage::Control(message)));
+        }
+    }
+
+    fn on_control(
+        &mut self,
+        source: u16,
+        control: MatchPause,
+        current_frame: Frame,
+        effects: &mut MatchPauseEffects,
+    ) {
+        // Only the proposer itself may drive its negotiation.
+        if control.proposer != source || control.proposer == self.local_id {
+            return;
+        }
+        let is_open = self.open.as_ref().is_some_and(|open| {
+            open.proposer == control.proposer && open.sequence == control.sequence
+        });
+        match control.stage {
+            MatchPauseStage::Propose => {
+                let (accepted, frame) = if is_open {
+                    (
+                        true,
+                        self.open.as_ref().map_or(Frame::NULL, |open| open.hold),
+                    )
+                } else if self.is_closed(control.proposer, control.sequence) {
+                    (false, Frame::NULL)
+                } else {
+                    self.close(control.proposer, control.sequence);
+                    match self.accept(control, current_frame) {
+                        Some(hold) => (true, hold),
+                        None => (false, Frame::NULL),
+                    }
+                };
+                effects.sends.push((
+                    source,
+                    MatchPauseMessage::Ack(MatchPauseAck {
+                        proposer: control.proposer,
+                        sequence: control.sequence,
+                        stage: MatchPauseStage::Propose,
+                        accepted,
+                        frame,
+                    }),
+                ));
+            },
+            MatchPauseStage::Commit | MatchPauseStage::Cancel => {
+                if is_open {
+                    self.open = None;
+                    if control.stage == MatchPauseStage::Commit {
+                        self.apply_commit(control.kind, control.frame, effects);
+                    } else {
+                        effects.events.push(MatchPauseEvent::Cancelled {
+                            frame: control.frame,
+                            resume: control.kind == MatchPauseKind::Resume,
+                        });
+                    }
+                }
+                self.close(control.proposer, control.sequence);
+                // Acknowledge duplicates too, so the proposer stops retransmitting.
+                effects.sends.push((
+                    source,
+                    MatchPauseMessage::Ack(MatchPauseAck {
+                        proposer: control.proposer,
+                        sequence: control.sequence,
+                        stage: control.stage,
+                        accepted: true,
+                        frame: control.frame,
+                    }),
+                ));
+            },
+        }
+    }
+
+    /// Accepts a fresh proposal if this peer is free to, returning its hold.
+    fn accept(&mut self, control: MatchPause, current_frame: Frame) -> Option<Frame> {
+        if self.open.is_some() {
+            return None;
+        }
+        let hold = match control.kind {
+            MatchPauseKind::Pause if self.paused_at.is_none() => control.frame.max(current_frame),
+            MatchPauseKind::Resume if self.reached && self.paused_at == Some(control.frame) => {
+                control.frame
+            },
+            _ => return None,
+        };
+        self.open = Some(Negotiation {
+            proposer: control.proposer,
+            sequence: control.sequence,
+            kind: control.kind,
+            frame: control.frame,
+            hold,
+            proposal: None,
+        });
+        Some(hold)
+    }
+
+    fn on_ack(&mut self, source: u16, ack: MatchPauseAck, effects: &mut MatchPauseEffects) {
+        if ack.proposer != self.local_id {
+            return;
+        }
+        if ack.stage != MatchPauseStage::Propose {
+            if let Some(closing) = self.closing.as_mut() {
+                if closing.message.sequence == ack.sequence && closing.message.stage == ack.stage {
+                    closing.pending.remove(&source);
+                    if closing.pending.is_empty() {
+                        self.closing = None;
+                    }
+                }
+            }
+            return;
+        }
+        let Some(open) = self.open.as_mut() else {
+            return;
+        };
+        if open.sequence != ack.sequence {
+            return;
+        }
+        let Some(proposal) = open.proposal.as_mut() else {
+            return;
+        };
+        if !proposal.pending.remove(&source) {
+            return;
+        }
+        if !ack.accepted {
+            self.cancel_open(effects);
+            return;
+        }
+        proposal.agreed = proposal.agreed.max(ack.frame);
+        self.commit_if_agreed(effects);
+    }
+
+    fn commit_if_agreed(&mut self, effects: &mut MatchPauseEffects) {
+        let agreed = match self.open.as_ref().and_then(|open| open.proposal.as_ref()) {
+            Some(proposal) if proposal.pending.is_empty() => proposal.agreed,
+            _ => return,
+        };
+        self.close_open(MatchPauseStage::Commit, agreed, effects);
+    }
+
+    fn cancel_open(&mut self, effects: &mut MatchPauseEffects) {
+        let Some(open) = self.open.as_ref() else {
+            return;
+        };
+        let frame = open.frame;
+        let resume = open.kind == MatchPauseKind::Resume;
+        if open.proposal.is_some() {
+            self.close_open(MatchPauseStage::Cancel, frame, effects);
+        } else {
+            self.open = None;
+        }
+        effects
+            .events
+            .push(MatchPauseEvent::Cancelled { frame, resume });
+    }
+
+    /// Closes the locally proposed negotiation with a commit or cancel and
+    /// starts retransmitting it to every peer that was asked.
+    fn close_open(
+        &mut self,
+        stage: MatchPauseStage,
+        frame: Frame,
+        effects: &mut MatchPauseEffects,
+    ) {
+        let Some(open) = self.open.take() else {
+            return;
+        };
+        let Some(proposal) = open.proposal else {
+            return;
+        };
+        let message = MatchPause {
+            proposer: open.proposer,
+            sequence: open.sequence,
+            kind: open.kind,
+            stage,
+            frame,
+        };
+        // Every peer that was asked gets the outcome, including the ones that
+        // already answered.
+        let mut pending = proposal.asked;
+        if let Some(previous) = self.closing.take() {
+            pending.extend(previous.pending);
+        }
+        for &peer in &pending {
+            effects
+                .sends
+                .push((peer, MatchPauseMessage::Control(message)));
+        }
+        if stage == MatchPauseStage::Commit {
+            self.apply_commit(open.kind, frame, effects);
+        }
+        self.closing = (!pending.is_empty()).then_some(Closing { message, pending });
+    }
+
+    fn apply_commit(
+        &mut self,
+        kind: MatchPauseKind,
+        frame: Frame,
+        effects: &mut MatchPauseEffects,
+    ) {
+        match kind {
+            MatchPauseKind::Pause => {
+                self.paused_at = Some(frame);
+                self.reached = false;
+            },
+            MatchPauseKind::Resume => {
+                self.paused_at = None;
+                self.reached = false;
+                effects.events.push(MatchPauseEvent::Resumed { frame });
+            },
+        }
+    }
+
+    fn is_closed(&self, proposer: u16, sequence: u32) -> bool {
+        self.closed
+            .get(&proposer)
+            .is_some_and(|&closed| sequence <= closed)
+    }
+
+    fn close(&mut self, proposer: u16, sequence: u32) {
+        let closed = self.closed.entry(proposer).or_insert(0);
+        *closed = (*closed).max(sequence);
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    /// A mesh of peers `0..n`, each at a fixed current frame, with instant
+    /// lossless delivery.
+    struct Mesh {
+        peers: Vec<MatchPauseState>,
+        frames: Vec<Frame>,
+        events: Vec<Vec<MatchPauseEvent>>,
+        now: Instant,
+    }
+
+    impl Mesh {
+        fn new(frames: &[i32]) -> Self {
+            Self {
+                peers: (0..frames.len())
+                    .map(|id| MatchPauseState::new(id as u16))
+                    .collect(),
+                frames: frames.iter().map(|&frame| Frame::new(frame)).collect(),
+                events: vec![Vec::new(); frames.len()],
+                now: Instant::now(),
+            }
+        }
+
+        fn others(&self, id: u16) -> BTreeSet<u16> {
+            (0..self.peers.len() as u16)
+                .filter(|&peer| peer != id)
+                .collect()
+        }
+
+        fn propose(&mut self, id: u16, kind: MatchPauseKind, frame: i32) -> MatchPauseEffects {
+            let mut effects = MatchPauseEffects::default();
+            let peers = self.others(id);
+            self.peers[id as usize]
+                .propose(kind, Frame::new(frame), &peers, self.now, &mut effects)
+                .unwrap();
+            effects
+        }
+
+        /// Delivers `effects` (produced by `from`) and every reply, to quiescence.
+        fn deliver(&mut self, from: u16, effects: MatchPauseEffects) {
+            self.deliver_all(vec![(from, effects)]);
+        }
+
+        /// Delivers several peers' effects in one interleaved FIFO.
+        fn deliver_all(&mut self, batches: Vec<(u16, MatchPauseEffects)>) {
+            let mut queue: Vec<(u16, u16, MatchPauseMessage)> = Vec::new();
+            for (from, effects) in batches {
+                self.events[from as usize].extend(effects.events);
+                queue.extend(effects.sends.into_iter().map(|(to, msg)| (from, to, msg)));
+            }
+            while !queue.is_empty() {
+                let (source, to, message) = queue.remove(0);
+                let mut effects = MatchPauseEffects::default();
+                self.peers[to as usize].on_message(
+                    source,
+                    message,
+                    self.frames[to as usize],
+                    &mut effects,
+                );
+                self.events[to as usize].extend(effects.events);
+                queue.extend(effects.sends.into_iter().map(|(dest, msg)| (to, dest, msg)));
+            }
+        }
+
+        fn poll(&mut self, id: u16) {
+            let mut effects = MatchPauseEffects::default();
+            let peers = self.others(id);
+            self.peers[id as usize].poll(self.now, &peers, &mut effects);
+            self.deliver(id, effects);
+        }
+
+        fn reach_all(&mut self) {
+            for (peer, frame) in self.peers.iter_mut().zip(&self.frames) {
+                peer.reach(*frame);
+            }
+        }
+    }
+
+    #[test]
+    fn pause_commits_the_latest_hold_on_every_peer() {
+        let mut mesh = Mesh::new(&[10, 25, 12]);
+        let effects = mesh.propose(0, MatchPauseKind::Pause, 20);
+        assert_eq!(mesh.peers[0].hold_frame(), Some(Frame::new(20)));
+        mesh.deliver(0, effects);
+
+        for peer in &mesh.peers {
+            assert_eq!(peer.hold_frame(), Some(Frame::new(25)));
+            assert!(!peer.is_paused());
+        }
+        assert!(mesh.events.iter().all(Vec::is_empty));
+
+        mesh.frames = vec![Frame::new(25); 3];
+        for peer in &mut mesh.peers {
+            assert_eq!(peer.reach(Frame::new(24)), None);
+            assert_eq!(peer.reach(Frame::new(25)), Some(Frame::new(25)));
+            assert_eq!(peer.reach(Frame::new(25)), None);
+            assert!(peer.is_paused());
+        }
+        // Every commit was acknowledged, so nothing is retransmitted.
+        let mut effects = MatchPauseEffects::default();
+        let others = mesh.others(0);
+        mesh.peers[0].poll(mesh.now, &others, &mut effects);
+        assert!(effects.sends.is_empty());
+    }
+
+    #[test]
+    fn resume_needs_every_peer_at_the_pause_frame() {
+        let mut mesh = Mesh::new(&[30, 30]);
+        let effects = mesh.propose(1, MatchPauseKind::Pause, 40);
+        mesh.deliver(1, effects);
+        mesh.frames = vec![Frame::new(40), Frame::new(40)];
+        mesh.peers[1].reach(Frame::new(40));
+
+        // Peer 0 has not reached the pause yet.
+        let effects = mesh.propose(1, MatchPauseKind::Resume, 0);
+        mesh.deliver(1, effects);
+        assert_eq!(
+            mesh.events[1],
+            vec![MatchPauseEvent::Cancelled {
+                frame: Frame::new(40),
+                resume: true
+            }]
+        );
+        assert!(mesh.events[0].is_empty());
+        assert!(mesh.peers[1].is_paused());
+
+        mesh.reach_all();
+        let effects = mesh.propose(1, MatchPauseKind::Resume, 0);
+        mesh.deliver(1, effects);
+        for (peer, events) in mesh.peers.iter().zip(&mesh.events) {
+            assert_eq!(peer.hold_frame(), None);
+            assert_eq!(
+                events.last(),
+                Some(&MatchPauseEvent::Resumed {
+                    frame: Frame::new(40)
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn concurrent_proposals_cancel_each_other() {
+        let mut mesh = Mesh::new(&[10, 10]);
+        let first = mesh.propose(0, MatchPauseKind::Pause, 20);
+        let second = mesh.propose(1, MatchPauseKind::Pause, 22);
+        mesh.deliver_all(vec![(0, first), (1, second)]);
+        assert_eq!(
+            mesh.events[0],
+            vec![MatchPauseEvent::Cancelled {
+                frame: Frame::new(20),
+                resume: false
+            }]
+        );
+        assert_eq!(
+            mesh.events[1],
+            vec![MatchPauseEvent::Cancelled {
+                frame: Frame::new(22),
+                resume: false
+            }]
+        );
+        for peer in &mesh.peers {
+            assert_eq!(peer.hold_frame(), None);
+        }
+    }
+
+    #[test]
+    fn proposer_timeout_cancels_on_every_accepting_peer() {
+        let mut mesh = Mesh::new(&[10, 10, 10]);
+        let mut effects = mesh.propose(0, MatchPauseKind::Pause, 20);
+        // Peer 2 never hears of the proposal.
+        effects.sends.retain(|(to, _)| *to != 2);
+        mesh.deliver(0, effects);
+        assert_eq!(mesh.peers[1].hold_frame(), Some(Frame::new(20)));
+
+        mesh.now += MATCH_PAUSE_TIMEOUT;
+        mesh.poll(0);
+
+        let cancelled = MatchPauseEvent::Cancelled {
+            frame: Frame::new(20),
+            resume: false,
+        };
+        assert_eq!(mesh.events[0], vec![cancelled]);
+        assert_eq!(mesh.events[1], vec![cancelled]);
+        assert!(mesh.events[2].is_empty());
+        for peer in &mesh.peers {
+            assert_eq!(peer.hold_frame(), None);
+        }
+    }
+
+    #[test]
+    fn responder_cancels_when_proposer_is_lost_and_rejects_its_stale_proposal() {
+        let mut mesh = Mesh::new(&[10, 10]);
+        let proposal = mesh.propose(0, MatchPauseKind::Pause, 20);
+        let (_, stale) = proposal.sends[0];
+        // Peer 1 accepts, but its ack never reaches the proposer.
+        let mut effects = MatchPauseEffects::default();
+        mesh.peers[1].on_message(0, stale, Frame::new(10), &mut effects);
+        assert_eq!(mesh.peers[1].hold_frame(), Some(Frame::new(20)));
+
+        let mut effects = MatchPauseEffects::default();
+        mesh.peers[1].poll(mesh.now, &BTreeSet::new(), &mut effects);
+        assert_eq!(
+            effects.events,
+            vec![MatchPauseEvent::Cancelled {
+                frame: Frame::new(20),
+                resume: false
+            }]
+        );
+        assert_eq!(mesh.peers[1].hold_frame(), None);
+
+        let mut effects = MatchPauseEffects::default();
+        mesh.peers[1].on_message(0, stale, Frame::new(10), &mut effects);
+        assert!(matches!(
+            effects.sends.as_slice(),
+            [(
+                0,
+                MatchPauseMessage::Ack(MatchPauseAck {
+                    accepted: false,
+                    ..
+                })
+            )]
+        ));
+        assert_eq!(mesh.peers[1].hold_frame(), None);
+    }
+
+    #[test]
+    fn request_errors_follow_the_pause_state() {
+        let mut state = MatchPauseState::new(0);
+        let mut effects = MatchPauseEffects::default();
+        let now = Instant::now();
+        let none = BTreeSet::new();
+        assert_eq!(
+            state.propose(
+                MatchPauseKind::Resume,
+                Frame::new(5),
+                &none,
+                now,
+                &mut effects
+            ),
+            Err(InvalidRequestKind::MatchNotPaused)
+        );
+        // Without peers the pause commits immediately.
+        state
+            .propose(
+                MatchPauseKind::Pause,
+                Frame::new(5),
+                &none,
+                now,
+                &mut effects,
+            )
+            .unwrap();
+        assert_eq!(
+            state.propose(
+                MatchPauseKind::Pause,
+                Frame::new(9),
+                &none,
+                now,
+                &mut effects
+            ),
+            Err(InvalidRequestKind::MatchAlreadyPaused)
+        );
+        assert_eq!(
+            state.propose(
+                MatchPauseKind::Resume,
+                Frame::new(5),
+                &none,
+                now,
+                &mut effects
+            ),
+            Err(InvalidRequestKind::MatchNotPaused)
+        );
+        state.reach(Frame::new(5));
+        state
+            .propose(
+                MatchPauseKind::Resume,
+                Frame::new(5),
+                &none,
+                now,
+                &mut effects,
+            )
+            .unwrap();
+        assert_eq!(
+            effects.events,
+            vec![MatchPauseEvent::Resumed {
+                frame: Frame::new(5)
+            }]
+        );
+
+        let peers = BTreeSet::from([1]);
+        state
+            .propose(
+                MatchPauseKind::Pause,
+                Frame::new(9),
+                &peers,
+                now,
+                &mut effects,
+            )
+            .unwrap();
+        assert_eq!(
+            state.propose(
+                MatchPauseKind::Pause,
+                Frame::new(9),
+                &peers,
+                now,
+                &mut effects
+            ),
+            Err(InvalidRequestKind::MatchPauseInProgress)
+        );
+    }
+}
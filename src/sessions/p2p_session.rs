@@ -2,12 +2,13 @@
 use crate::frame_info::PlayerInput;
 #[cfg(feature = "hot-join")]
 use crate::metrics::HotJoinMetrics;
-use crate::metrics::{PeerMetrics, SessionMetrics};
+use crate::metrics::{CompressionStats, MemoryReport, MemoryUsage, PeerMetrics, SessionMetrics};
+use crate::network::messages::MatchPauseKind;
 #[cfg(feature = "hot-join")]
 use crate::network::messages::StateSnapshot;
 use crate::network::messages::{
     ConnectionStatus, DropAbort, DropAbortReason, DropBackfill, DropCommit, DropOperationId,
-    DropPrepare, DropReceipt, DropReport, DropReportStage, DropTarget,
+    DropPrepare, DropReceipt, DropReport, DropReportStage, DropTarget, Message,
 };
 use crate::network::network_stats::NetworkStats;
 use crate::network::protocol::{DropControlMessage, UdpProtocol};
@@ -15,29 +16,45 @@
 use crate::network::protocol::{HandshakeTraceEvent, HandshakeTraceOverflow};
 use crate::replay::{Replay, ReplayRecorder};
 use crate::safe_frame_sub;
-#[cfg(feature = "hot-join")]
 use crate::sessions::config::ClockFn;
 use crate::sessions::config::{DisconnectBehavior, ProtocolConfig, SaveMode};
-use crate::sessions::event_drain::enqueue_event_bounded;
+use crate::sessions::confirmation_latency::{
+    ConfirmationLatency, ConfirmationLatencySample, LatencyStats,
+};
+use crate::sessions::event_drain::{
+    enqueue_event_bounded, TimestampedEvent, TimestampedEventDrain,
+};
+use crate::sessions::frame_timeline::{FrameFlags, FrameTimeline};
+use crate::sessions::match_pause::{MatchPauseEffects, MatchPauseEvent, MatchPauseState};
 use crate::sessions::player_registry::PlayerRegistry;
+use crate::sessions::player_roster::PlayerRoster;
+use crate::sessions::previous_inputs::PreviousInputs;
+use crate::sessions::rollback_cause::RollbackCause;
+use crate::sessions::rollback_context::{RollbackContext, RollbackTracker};
+use crate::sessions::session_health::{HealthTracker, SessionHealth};
 use crate::sessions::session_trait::Session;
+use crate::sessions::state_slot::StateSlot;
+use crate::sessions::stats_publisher::{StatsPublisher, StatsSubscription};
 use crate::sessions::sync_health::SyncHealth;
-use crate::sync_layer::SyncLayer;
+#[cfg(not(target_family = "wasm"))]
+use crate::sessions::sync_wait::{wait_until_running, SyncWaitError};
+use crate::sessions::trace_recorder::{self, TraceRecorder, TraceSpan};
+use crate::sync_layer::{StateDiffCodec, SyncLayer};
 use crate::telemetry::{
     InvariantChecker, InvariantViolation, SessionTelemetry, ViolationKind, ViolationObserver,
     ViolationSeverity,
 };
+use crate::vec_map::VecMap;
 use crate::DesyncDetection;
 use crate::HandleVec;
 use crate::{
     network::protocol::Event, Config, EventDrain, FortressEvent, FortressRequest, FortressResult,
-    Frame, InvalidFrameReason, NonBlockingSocket, PlayerHandle, PlayerType, RequestVec,
-    SessionState,
+    Frame, InputStatus, InvalidFrameReason, NonBlockingSocket, PlayerHandle, PlayerType,
+    RequestVec, SavedFrameInfo, SessionState,
 };
 use crate::{report_violation, safe_frame_add};
 use std::collections::BTreeMap;
 use std::collections::VecDeque;
-use std::convert::TryInto;
 use std::fmt;
 use std::hash::Hasher;
 use std::sync::atomic::{AtomicI32, Ordering};
@@ -59,6 +76,16 @@
 /// and provides enough time for network conditions to improve.
 const MIN_RECOMMENDATION: u32 = 3;
 
+/// The `skip_frames` of a [`FortressEvent::WaitRecommendation`] for a session
+/// `frames_ahead` of its furthest-behind peer, or 0 below
+/// [`MIN_RECOMMENDATION`].
+fn recommended_skip_frames(frames_ahead: i32) -> u32 {
+    match u32::try_from(frames_ahead) {
+        Ok(skip_frames) if skip_frames >= MIN_RECOMMENDATION => skip_frames,
+        _ => 0,
+    }
+}
+
 /// Default maximum number of retained events.
 ///
 /// This prevents unbounded memory growth if events aren't being consumed.
@@ -70,6 +97,15 @@
 #[cfg(test)]
 const DEFAULT_MAX_EVENT_QUEUE_SIZE: usize = 100;
 
+/// Keep-alives each running endpoint sends from a socket installed with
+/// [`P2PSession::replace_socket`], so at least one reaches the peer and
+/// updates its view of our address even on a lossy link.
+const SOCKET_REPLACEMENT_KEEP_ALIVES: usize = 3;
+
+/// The local input sampler of [`P2PSession::advance_frame_with`]: given the
+/// local player handles, returns their inputs for the new frame.
+type InputSampler<'a, I> = dyn FnMut(&[PlayerHandle]) -> Vec<(PlayerHandle, I)> + 'a;
+
 /// Number of mismatching confirmed-frame checksums from a single peer at which
 /// the library emits a one-time advisory trust-downgrade WARNING (B3 Byzantine
 /// hardening).
@@ -238,6 +274,29 @@ struct CoordinatedDropAttempt<A> {
     last_broadcast_at: web_time::Instant,
 }
 
+/// The state a rejoining session expects every remote peer to hold (see
+/// [`with_expected_state_checksum`](crate::SessionBuilder::with_expected_state_checksum)).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct RejoinCheck {
+    frame: Frame,
+    expected: u128,
+    /// Whether [`FortressEvent::RejoinStateMismatch`] was already emitted.
+    mismatch_reported: bool,
+}
+
+/// Tracks how long a running session went without advancing (see
+/// [`with_advance_stall_warning`](crate::SessionBuilder::with_advance_stall_warning)).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct AdvanceStallWatch {
+    threshold: web_time::Duration,
+    /// The last `advance_frame` call while running, or the first poll after
+    /// the session started running; `None` while it is not running.
+    last_advance: Option<web_time::Instant>,
+    /// Whether [`FortressEvent::LocalAdvanceStalled`] was emitted for the
+    /// current stall.
+    stalled: bool,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 struct CoordinatedDropFence {
     operation: DropOperationId,
@@ -302,6 +361,10 @@ pub struct P2PSession<T>
     num_players: usize,
     /// The maximum number of frames Fortress Rollback will roll back. Every gamestate older than this is guaranteed to be correct.
     max_prediction: usize,
+    /// Deepest rollback the session performs, if capped below
+    /// `max_prediction`; see
+    /// [`SessionBuilder::with_max_rollback_depth`](crate::SessionBuilder::with_max_rollback_depth).
+    max_rollback_depth: Option<usize>,
     /// The sync layer handles player input queues and provides predictions.
     sync_layer: SyncLayer<T>,
     /// Controls how game states are saved for rollback.
@@ -309,6 +372,80 @@ pub struct P2PSession<T>
 
     /// If we receive a disconnect from another client, we have to rollback from that frame on in order to prevent wrong predictions
     disconnect_frame: Frame,
+    /// Maximum number of resimulation [`FortressRequest::AdvanceFrame`] requests emitted by one
+    /// call. `None` resimulates every rolled-back frame in the call that detected the rollback.
+    max_resimulation_per_advance: Option<usize>,
+    /// The frame a capped rollback is resimulating back up to, or [`Frame::NULL`] when no
+    /// resimulation is outstanding. While set, the sync layer's current frame trails it by
+    /// [`resimulation_debt`](Self::resimulation_debt) frames.
+    resimulation_target: Frame,
+    /// Depth of the outstanding rollback, recorded in the metrics once it completes.
+    resimulation_depth: usize,
+    /// Inputs of recently simulated frames, for the `previous_inputs` of each
+    /// [`FortressRequest::AdvanceFrame`]; disabled unless
+    /// [`SessionBuilder::with_previous_inputs_in_requests`](crate::SessionBuilder::with_previous_inputs_in_requests)
+    /// is set.
+    previous_inputs: PreviousInputs<T::Input>,
+    /// Inputs of recently simulated frames and the players changed by the
+    /// current batch's rollback (see
+    /// [`current_rollback_context`](Self::current_rollback_context)); `None`
+    /// unless enabled.
+    rollback_context: Option<RollbackTracker<T::Input>>,
+    /// The misprediction behind the most recent rollback (see
+    /// [`last_rollback_cause`](Self::last_rollback_cause)); its vectors are reused.
+    last_rollback_cause: Option<RollbackCause<T::Input>>,
+    /// A reserved cause for the first rollback to fill, set by
+    /// `preallocate_buffers`.
+    spare_rollback_cause: Option<RollbackCause<T::Input>>,
+    /// Flags of recently simulated frames (see
+    /// [`frame_timeline`](Self::frame_timeline)); `None` unless enabled.
+    frame_timeline: Option<FrameTimeline>,
+    /// Timeline of advances, polls, rollbacks and events (see
+    /// [`export_trace_json`](Self::export_trace_json)); `None` unless enabled.
+    trace_recorder: Option<TraceRecorder>,
+    /// How long local input frames took to be confirmed (see
+    /// [`confirmation_latency_stats`](Self::confirmation_latency_stats));
+    /// `None` unless enabled.
+    confirmation_latency: Option<ConfirmationLatency>,
+    /// Match pause/resume negotiation (see [`request_match_pause`](Self::request_match_pause)).
+    match_pause: MatchPauseState,
+    /// Frames past the current frame at which a locally requested pause is proposed.
+    match_pause_margin: usize,
+    /// The frame of the most recent [`send_frame_tag`](Self::send_frame_tag).
+    last_frame_tag: Frame,
+    /// The session's final frame (see
+    /// [`with_max_session_frames`](crate::SessionBuilder::with_max_session_frames)).
+    max_session_frames: Frame,
+    /// Set once the session reached `max_session_frames` with every earlier frame
+    /// confirmed; `advance_frame` refuses from then on.
+    frame_limit_reached: bool,
+    /// The rejoin state every remote peer must confirm before the session
+    /// runs; `None` once they did, or if none was set.
+    rejoin_check: Option<RejoinCheck>,
+    /// The advance-stall watchdog; `None` unless enabled.
+    advance_stall: Option<AdvanceStallWatch>,
+    /// Frame-rate health since the session started running; see
+    /// [`session_health`](Self::session_health).
+    health: HealthTracker,
+    /// Peers' pending disconnect claims about players they do not own that
+    /// this session could not corroborate yet, by claiming peer and player:
+    /// when the claim was first seen and whether it has been warned about.
+    disconnect_hints: BTreeMap<(T::Address, PlayerHandle), (web_time::Instant, bool)>,
+    /// Whether `set_input_delay` accepts a delay of `max_prediction` or more
+    /// (see
+    /// [`with_input_delay_beyond_prediction`](crate::SessionBuilder::with_input_delay_beyond_prediction)).
+    input_delay_beyond_prediction: bool,
+    /// `advance_frame` calls a late remote input is held back (see
+    /// [`InputQueueConfig::remote_input_buffer_frames`](crate::InputQueueConfig::remote_input_buffer_frames)).
+    remote_input_buffer_frames: u32,
+    /// Late remote inputs held back, oldest first per handle, each with the
+    /// `advance_ticks` value it is due at. Only inputs for frames already
+    /// predicted are held, so each handle holds at most the prediction window.
+    remote_input_buffer: BTreeMap<PlayerHandle, VecDeque<(PlayerInput<T::Input>, u64)>>,
+    /// Number of `advance_frame` calls that reached the running session.
+    advance_ticks: u64,
+    /// Stable player ids; connection flags are filled in by [`roster`](Self::roster).
+    roster: PlayerRoster,
 
     /// Internal State of the Session.
     state: SessionState,
@@ -317,27 +454,70 @@ pub struct P2PSession<T>
     socket: Box<dyn NonBlockingSocket<T::Address>>,
     /// Handles players and their endpoints
     player_reg: PlayerRegistry<T>,
+    /// Remote players reached through another peer's endpoint; see
+    /// [`SessionBuilder::add_player_via_relay`](crate::SessionBuilder::add_player_via_relay).
+    relayed_players: std::collections::BTreeSet<PlayerHandle>,
     /// This struct contains information about remote players, like connection status and the frame of last received input.
     local_connect_status: Vec<ConnectionStatus>,
 
     /// notes which inputs have already been sent to the spectators
     next_spectator_frame: Frame,
+    /// The final frame announced to spectators once the stream ended (see
+    /// [`end_spectator_stream`](Self::end_spectator_stream)); no input is
+    /// forwarded past it.
+    spectator_stream_end: Option<Frame>,
     /// The soonest frame on which the session can send a [`FortressEvent::WaitRecommendation`] again.
     next_recommended_sleep: Frame,
     /// How many frames we estimate we are ahead of every remote client
     frames_ahead: i32,
-
-    /// Contains all events to be forwarded to the user.
-    event_queue: VecDeque<FortressEvent<T>>,
+    /// The hard limit on the lead over the slowest peer; see
+    /// [`TimeSyncConfig::max_frames_ahead`](crate::TimeSyncConfig::max_frames_ahead).
+    max_frames_ahead: Option<u32>,
+    /// Whether `advance_frame` honors wait recommendations itself instead of
+    /// emitting them; see
+    /// [`SessionBuilder::with_auto_frame_skip`](crate::SessionBuilder::with_auto_frame_skip).
+    auto_frame_skip: bool,
+    /// `advance_frame` calls still to skip for the last honored recommendation.
+    pending_skip_frames: u32,
+    /// Whether the last `advance_frame` call skipped its simulation; see
+    /// [`skipped_this_call`](Self::skipped_this_call).
+    skipped_this_call: bool,
+
+    /// Contains all events to be forwarded to the user, stamped when enqueued.
+    event_queue: VecDeque<TimestampedEvent<T>>,
+    /// Scratch for the endpoint events `poll_remote_clients` stages and merges
+    /// by generation stamp before handling them, kept so each poll reuses one
+    /// allocation.
+    endpoint_events: VecDeque<(u64, Event<T>, Arc<[PlayerHandle]>, T::Address)>,
+    /// When the session was created on the protocol clock; the origin of
+    /// [`TimestampedEvent::session_ms`].
+    created_at: web_time::Instant,
     /// Contains all local inputs not yet sent into the system. This should have inputs for every local player before calling advance_frame
-    local_inputs: BTreeMap<PlayerHandle, PlayerInput<T::Input>>,
+    local_inputs: VecMap<PlayerHandle, PlayerInput<T::Input>>,
+    /// Requests of an [`advance_frame_with`](Self::advance_frame_with) call
+    /// whose sampled inputs were rejected after it had already repaired a
+    /// misprediction. Returned ahead of the next successful advance's requests
+    /// so the repair is never lost.
+    carried_requests: RequestVec<T>,
+    /// Publishes [`StatsSnapshot`](crate::StatsSnapshot)s once
+    /// [`stats_publisher`](Self::stats_publisher) was called.
+    stats_publisher: Option<StatsPublisher>,
 
     /// With desync detection, the session will compare checksums for all peers to detect discrepancies / desyncs between peers
     desync_detection: DesyncDetection,
     /// Desync detection over the network
-    local_checksum_history: BTreeMap<Frame, u128>,
+    local_checksum_history: VecMap<Frame, u128>,
+    /// Scratch for the frames `compare_local_checksums_against_peers` evicts,
+    /// kept so the comparison reuses one allocation.
+    checked_checksum_frames: Vec<Frame>,
     /// The last frame we sent a checksum for
     last_sent_checksum_frame: Frame,
+    /// Under [`SaveMode::Sparse`], the newest saved and confirmed frame counted
+    /// towards the checksum interval.
+    last_counted_saved_frame: Frame,
+    /// Under [`SaveMode::Sparse`], saved and confirmed frames counted since the
+    /// last checksum report.
+    saved_frames_since_checksum: u32,
     /// Optional observer for specification violations.
     violation_observer: Option<Arc<dyn ViolationObserver>>,
     /// Optional telemetry observer for session performance events.
@@ -346,6 +526,9 @@ pub struct P2PSession<T>
     protocol_config: ProtocolConfig,
     /// Hard event-queue bound; routine events are discarded first on overflow.
     max_event_queue_size: usize,
+    /// The size of one saved game state, if the application gave it (see
+    /// `SessionBuilder::with_state_size_hint`).
+    state_size_hint: Option<usize>,
     /// Optional replay recorder for capturing confirmed inputs.
     recording: Option<ReplayRecorder<T::Input>>,
     /// The last frame recorded to the replay recorder.
@@ -415,7 +598,6 @@ struct HotJoinTiming {
 /// the platform's monotonic clock when none is configured — the same rule the protocol
 /// endpoints use, so session-level and endpoint-level timings share a basis and
 /// stay deterministic under the simulation harness.
-#[cfg(feature = "hot-join")]
 fn clock_now(clock: Option<&ClockFn>) -> web_time::Instant {
     match clock {
         Some(clock_fn) => clock_fn(),
@@ -1154,6 +1336,8 @@ fn coordinated_drop_inventory(
     }
 
     fn begin_coordinated_drop(&mut self, player_handle: PlayerHandle) -> Result<(), FortressError> {
+        // Drop receipts report received frames; deliver held inputs first.
+        self.release_all_remote_inputs(u64::MAX);
         if let Some(active) = self.coordinated_drop.active.as_ref() {
             if active
                 .targets
@@ -1473,6 +1657,8 @@ fn coordinated_drop_accept_prepare(
         &mut self,
         prepare: DropPrepare,
     ) -> Result<(), DropAbortReason> {
+        // Drop receipts report received frames; deliver held inputs first.
+        self.release_all_remote_inputs(u64::MAX);
         if self
             .coordinated_drop
             .closed
@@ -2596,11 +2782,14 @@ fn drive_coordinated_drop(&mut self) {
     }
 
     fn poll_coordinated_drop(&mut self) {
+        // Sized to the mailboxes' actual contents so a quiet poll, the common
+        // case, reserves nothing.
         let capacity = self
             .player_reg
             .remotes
-            .len()
-            .saturating_mul(crate::network::MAX_RECEIVE_MESSAGES_PER_POLL);
+            .values()
+            .map(UdpProtocol::received_drop_message_count)
+            .fold(0_usize, usize::saturating_add);
         let mut received = Vec::new();
         if received.try_reserve_exact(capacity).is_err() {
             self.coordinated_drop_fail_closed(DropAbortReason::ResourceLimit, true);
@@ -2647,7 +2836,7 @@ fn poll_coordinated_drop(&mut self) {
     ///
     /// Note: This is an internal constructor called via SessionBuilder. The many parameters are
     /// acceptable here because users interact through the builder pattern, not this method directly.
-    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
     pub(crate) fn new(
         num_players: usize,
         max_prediction: usize,
@@ -2659,10 +2848,27 @@ pub(crate) fn new(
         violation_observer: Option<Arc<dyn ViolationObserver>>,
         protocol_config: ProtocolConfig,
         queue_length: usize,
+        remote_input_buffer_frames: u32,
         event_queue_size: usize,
         recording: bool,
         telemetry: Option<Arc<dyn SessionTelemetry>>,
         disconnect_behavior: DisconnectBehavior,
+        max_resimulation_per_advance: Option<usize>,
+        previous_inputs: bool,
+        match_pause_margin: usize,
+        max_session_frames: Frame,
+        roster: PlayerRoster,
+        preallocated_buffers: bool,
+        expected_state_checksum: Option<(Frame, u128)>,
+        advance_stall_warning: Option<web_time::Duration>,
+        input_delay_beyond_prediction: bool,
+        frame_timeline_capacity: Option<usize>,
+        trace_recorder_capacity: Option<usize>,
+        state_size_hint: Option<usize>,
+        auto_frame_skip: bool,
+        confirmation_latency_capacity: Option<usize>,
+        rollback_context: bool,
+        max_rollback_depth: Option<usize>,
         #[cfg(feature = "hot-join")] hot_join: HotJoinConfig<T>,
     ) -> Result<Self, FortressError> {
         // Route construction-time violations (e.g. a failed frame-delay setup or
@@ -2765,14 +2971,16 @@ pub(crate) fn new(
             }
         }
 
-        let save_mode = if max_prediction == 0 && save_mode == SaveMode::Sparse {
-            // in lockstep mode, saving will never happen, but we use the last saved frame to mark
-            // control marking frames confirmed, so we need to turn off sparse saving to ensure that
-            // frames are marked as confirmed - otherwise we will never advance the game state.
+        let save_mode = if max_prediction == 0 && save_mode != SaveMode::EveryFrame {
+            // in lockstep mode, saving will never happen, but sparse and pyramid saving use the
+            // saved frames to control marking frames confirmed, so we need to turn them off to
+            // ensure that frames are marked as confirmed - otherwise we will never advance the
+            // game state.
             report_violation!(
                 ViolationSeverity::Warning,
                 ViolationKind::Configuration,
-                "Sparse saving setting is ignored because lockstep mode is on (max_prediction set to 0), so no saving will take place"
+                "{} saving setting is ignored because lockstep mode is on (max_prediction set to 0), so no saving will take place",
+                save_mode
             );
             SaveMode::EveryFrame
         } else {
@@ -2783,29 +2991,97 @@ pub(crate) fn new(
         event_queue
             .try_reserve_exact(event_queue_size)
             .map_err(|_err| allocation_failed("p2p.event_queue", event_queue_size))?;
+        let created_at = clock_now(protocol_config.clock.as_ref());
+        // A rollback reloads at most `max_prediction` frames back, and the frame
+        // before the reloaded one must still be known.
+        let previous_inputs =
+            PreviousInputs::new(previous_inputs, max_prediction.saturating_add(1))?;
+
+        // A peer proposes match pauses under its lowest local handle, the same
+        // id its remotes derive from the endpoint's handles.
+        let match_pause_id = players
+            .local_player_handles_iter()
+            .filter_map(|handle| u16::try_from(handle.as_usize()).ok())
+            .min()
+            .unwrap_or(0);
 
-        Ok(Self {
+        let mut session = Self {
             state,
             num_players,
             max_prediction,
+            max_rollback_depth,
             save_mode,
             socket,
             local_connect_status,
             next_recommended_sleep: Frame::new(0),
             next_spectator_frame: Frame::new(0),
+            spectator_stream_end: None,
             frames_ahead: 0,
+            max_frames_ahead: None,
+            relayed_players: std::collections::BTreeSet::new(),
+            auto_frame_skip,
+            pending_skip_frames: 0,
+            skipped_this_call: false,
             sync_layer,
             disconnect_frame: Frame::NULL,
+            max_resimulation_per_advance,
+            resimulation_target: Frame::NULL,
+            resimulation_depth: 0,
+            previous_inputs,
+            rollback_context: rollback_context
+                .then(|| RollbackTracker::new(max_prediction.saturating_add(1), num_players))
+                .transpose()?,
+            last_rollback_cause: None,
+            spare_rollback_cause: None,
+            frame_timeline: frame_timeline_capacity
+                .map(FrameTimeline::new)
+                .transpose()?,
+            trace_recorder: trace_recorder_capacity
+                .map(|capacity| TraceRecorder::new(capacity, protocol_config.clock.clone()))
+                .transpose()?,
+            confirmation_latency: confirmation_latency_capacity
+                .map(ConfirmationLatency::new)
+                .transpose()?,
+            match_pause: MatchPauseState::new(match_pause_id),
+            match_pause_margin,
+            last_frame_tag: Frame::NULL,
+            max_session_frames,
+            frame_limit_reached: false,
+            rejoin_check: expected_state_checksum.map(|(frame, expected)| RejoinCheck {
+                frame,
+                expected,
+                mismatch_reported: false,
+            }),
+            advance_stall: advance_stall_warning.map(|threshold| AdvanceStallWatch {
+                threshold,
+                last_advance: None,
+                stalled: false,
+            }),
+            health: HealthTracker::default(),
+            disconnect_hints: BTreeMap::new(),
+            input_delay_beyond_prediction,
+            remote_input_buffer_frames,
+            remote_input_buffer: BTreeMap::new(),
+            advance_ticks: 0,
+            roster,
             player_reg: players,
             event_queue,
-            local_inputs: BTreeMap::new(),
+            endpoint_events: VecDeque::new(),
+            created_at,
+            local_inputs: VecMap::new(),
+            carried_requests: RequestVec::new(),
+            stats_publisher: None,
             desync_detection,
-            local_checksum_history: BTreeMap::new(),
+            local_checksum_history: VecMap::new(),
+            checked_checksum_frames: Vec::new(),
             last_sent_checksum_frame: Frame::NULL,
+            last_counted_saved_frame: Frame::NULL,
+            saved_frames_since_checksum: 0,
             violation_observer,
             telemetry,
             protocol_config,
             max_event_queue_size: event_queue_size,
+            state_size_hint,
             recording: recording.then(|| ReplayRecorder::new(num_players)),
             last_recorded_frame: Frame::NULL,
             disconnect_behavior,
@@ -2843,7 +3119,68 @@ pub(crate) fn new(
             },
             #[cfg(feature = "hot-join")]
             hot_join_timing,
-        })
+        };
+        if preallocated_buffers {
+            session.preallocate_buffers()?;
+        }
+        Ok(session)
+    }
+
+    /// Keeps the saved states as keyframes and diffs encoded by `codec` (see
+    /// `SessionBuilder::with_state_diff_codec`).
+    pub(crate) fn enable_state_diffs(&mut self, codec: Arc<dyn StateDiffCodec<T::State>>) {
+        self.sync_layer.enable_state_diffs(codec);
+    }
+
+    /// Refuses to simulate while the session is more than `limit` frames
+    /// ahead of its slowest peer (see `TimeSyncConfig::max_frames_ahead`).
+    pub(crate) fn limit_frames_ahead(&mut self, limit: Option<u32>) {
+        self.max_frames_ahead = limit;
+    }
+
+    /// Records the remote players whose inputs arrive through another peer's
+    /// endpoint (see `SessionBuilder::add_player_via_relay`).
+    pub(crate) fn reach_via_relay(&mut self, handles: std::collections::BTreeSet<PlayerHandle>) {
+        self.relayed_players = handles;
+    }
+
+    /// Reserves the session's per-frame buffers and every endpoint's input
+    /// buffers up front (see `SessionBuilder::with_preallocated_buffers`).
+    fn preallocate_buffers(&mut self) -> Result<(), FortressError> {
+        let players = self.num_players;
+        self.local_inputs
+            .try_reserve(players)
+            .map_err(|_err| allocation_failed("p2p.local_inputs", players))?;
+        // The checksum history is pruned before each insert, so it peaks one
+        // above the configured cap.
+        let history = self.protocol_config.max_checksum_history.saturating_add(1);
+        self.local_checksum_history
+            .try_reserve(history)
+            .map_err(|_err| allocation_failed("p2p.local_checksum_history", history))?;
+        self.checked_checksum_frames
+            .try_reserve(history)
+            .map_err(|_err| allocation_failed("p2p.checked_checksum_frames", history))?;
+        self.spare_rollback_cause = Some(RollbackCause::try_with_capacity(players)?);
+        let endpoints = self
+            .player_reg
+            .remotes
+            .len()
+            .saturating_add(self.player_reg.spectators.len());
+        let endpoint_events = endpoints
+            .saturating_mul(self.protocol_config.pending_output_limit)
+            .saturating_mul(players);
+        self.endpoint_events
+            .try_reserve(endpoint_events)
+            .map_err(|_err| allocation_failed("p2p.endpoint_events", endpoint_events))?;
+        for endpoint in self
+            .player_reg
+            .remotes
+            .values_mut()
+            .chain(self.player_reg.spectators.values_mut())
+        {
+            endpoint.preallocate_buffers()?;
+        }
+        Ok(())
     }
 
     /// Registers local input for a player for the current frame. This should be successfully called for every local player before calling [`advance_frame()`](Self::advance_frame).
@@ -2851,13 +3188,65 @@ pub(crate) fn new(
     ///
     /// # Errors
     /// - Returns a [`FortressError`] when the given handle does not refer to a local player.
+    /// - Returns [`FortressError::LocalInputRejected`] when the player's input queue would not
+    ///   accept the input, rather than letting it be dropped silently.
     ///
     pub fn add_local_input(
         &mut self,
         player_handle: PlayerHandle,
         input: T::Input,
     ) -> Result<(), FortressError> {
+        self.add_local_input_with_frame(player_handle, input)
+            .map(|_frame| ())
+    }
+
+    /// Like [`add_local_input`](Self::add_local_input), but returns the frame the input will
+    /// first apply to: the frame it is added for plus the player's input delay.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`add_local_input`](Self::add_local_input).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Debug)]
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let socket = fortress_rollback::UdpNonBlockingSocket::bind_to_port(0)?;
+    /// # let remote: SocketAddr = "127.0.0.1:7001".parse()?;
+    /// let mut session = SessionBuilder::<TestConfig>::new()
+    ///     .with_input_delay(2)?
+    ///     .add_player(PlayerType::Local, PlayerHandle::new(0))?
+    ///     .add_player(PlayerType::Remote(remote), PlayerHandle::new(1))?
+    ///     .start_p2p_session(socket)?;
+    /// let applies_at = session.add_local_input_with_frame(PlayerHandle::new(0), 7)?;
+    /// assert_eq!(applies_at, session.current_frame() + 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_local_input_with_frame(
+        &mut self,
+        player_handle: PlayerHandle,
+        input: T::Input,
+    ) -> Result<Frame, FortressError> {
         let _violation_scope = self.scoped_violation_observer();
+        self.queue_local_input(player_handle, input)
+    }
+
+    /// Stores a local player's input for the frame the session simulates next
+    /// and returns the frame it will first apply to.
+    fn queue_local_input(
+        &mut self,
+        player_handle: PlayerHandle,
+        input: T::Input,
+    ) -> Result<Frame, FortressError> {
         // make sure the input is for a registered local player (zero-allocation check)
         if !self.player_reg.is_local_player(player_handle) {
             return Err(InvalidRequestKind::NotLocalPlayer {
@@ -2865,9 +3254,17 @@ pub fn add_local_input(
             }
             .into());
         }
-        let player_input = PlayerInput::<T::Input>::new(self.sync_layer.current_frame(), input);
+        // While a capped rollback is still resimulating, the input belongs to the frame the
+        // session will simulate next once it has caught up, not to the resimulation frame.
+        let frame = if self.resimulation_target.is_null() {
+            self.sync_layer.current_frame()
+        } else {
+            self.resimulation_target
+        };
+        let applies_at = self.sync_layer.local_input_frame(player_handle, frame)?;
+        let player_input = PlayerInput::<T::Input>::new(frame, input);
         self.local_inputs.insert(player_handle, player_input);
-        Ok(())
+        Ok(applies_at)
     }
 
     /// You should call this to notify Fortress Rollback that you are ready to advance your gamestate by a single frame.
@@ -2896,14 +3293,164 @@ pub fn add_local_input(
     /// [`poll_remote_clients`](Self::poll_remote_clients)) during the pause to
     /// drive the handshake to completion.
     ///
+    /// # Auto frame skip
+    ///
+    /// With [`SessionBuilder::with_auto_frame_skip`](crate::SessionBuilder::with_auto_frame_skip),
+    /// the calls that honor a wait recommendation poll the network and return
+    /// an empty request set without simulating;
+    /// [`skipped_this_call`](Self::skipped_this_call) reports them.
+    ///
     /// # Errors
     /// - Returns a [`FortressError`] if the provided player handle refers to a remote player.
     /// - Returns a [`FortressError`] if the session is not yet ready to accept input. In this case, you either need to start the session or wait for synchronization between clients.
+    /// - Returns [`InvalidRequestKind::SessionFrameLimitReached`] once the session reached its
+    ///   frame limit (see [`FortressEvent::SessionFrameLimitReached`]).
+    /// - Returns [`FortressError::AheadOfRemotes`] while the session is further ahead of its
+    ///   slowest peer than [`TimeSyncConfig::max_frames_ahead`](crate::TimeSyncConfig::max_frames_ahead)
+    ///   allows. The network was polled and the local inputs stay queued.
     ///
     /// [`RequestVec`]: crate::RequestVec
     #[must_use = "FortressRequests must be processed to advance the game state"]
     pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
         let _violation_scope = self.scoped_violation_observer();
+        let started = self.trace_begin();
+        let result = self.advance_frame_sampling(None);
+        self.tick_stats_publisher();
+        let result = result.map(|requests| self.prepend_carried_requests(requests));
+        self.trace_advance(started, &result);
+        result
+    }
+
+    /// Like [`advance_frame`](Self::advance_frame), but samples the local
+    /// inputs at the last possible moment instead of taking the ones added
+    /// with [`add_local_input`](Self::add_local_input) beforehand.
+    ///
+    /// The session first polls the network and performs any pending rollback
+    /// and resimulation, then calls `sample` with the handles of the local
+    /// players, adds the inputs it returns, and simulates the new frame. The
+    /// requests are the ones `advance_frame` would return had the same inputs
+    /// been added before it. When a long rollback precedes the new frame, the
+    /// inputs it simulates are younger by the time that rollback took to
+    /// compute, which removes up to a frame of input latency.
+    ///
+    /// `sample` runs inside the advance, so it must be fast: read the
+    /// already-polled device state rather than polling devices or blocking.
+    /// It is not called when the call simulates no new frame (for example
+    /// while a capped rollback is still resimulating or a match pause holds),
+    /// and it cannot reach the session, which is mutably borrowed for the
+    /// whole call:
+    ///
+    /// ```compile_fail
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// # fn reenter(session: &mut P2PSession<TestConfig>) {
+    /// let _ = session.advance_frame_with(|handles| {
+    ///     let frame = session.current_frame(); // error: `session` is already borrowed
+    ///     handles.iter().map(|&handle| (handle, frame.as_i32() as u8)).collect()
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// Local inputs added before the call are replaced by the sampled ones.
+    ///
+    /// # Errors
+    /// - Returns the errors of [`advance_frame`](Self::advance_frame).
+    /// - Returns the errors of [`add_local_input`](Self::add_local_input) for a sampled input,
+    ///   and [`InvalidRequestKind::MissingLocalInput`] if `sample` leaves out a local player.
+    ///   A misprediction repaired before the sample is not lost: its requests are returned
+    ///   ahead of the next successful advance's.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::prelude::*;
+    /// # use std::net::SocketAddr;
+    /// # struct TestConfig;
+    /// # impl Config for TestConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// # fn read_gamepad(_handle: PlayerHandle) -> u8 { 0 }
+    /// # fn step(session: &mut P2PSession<TestConfig>) -> Result<(), FortressError> {
+    /// let requests = session.advance_frame_with(|handles| {
+    ///     handles
+    ///         .iter()
+    ///         .map(|&handle| (handle, read_gamepad(handle)))
+    ///         .collect()
+    /// })?;
+    /// # drop(requests);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use = "FortressRequests must be processed to advance the game state"]
+    pub fn advance_frame_with<F>(&mut self, sample: F) -> FortressResult<RequestVec<T>>
+    where
+        F: FnOnce(&[PlayerHandle]) -> Vec<(PlayerHandle, T::Input)>,
+    {
+        let _violation_scope = self.scoped_violation_observer();
+        let mut sample = Some(sample);
+        let mut sample_once = |handles: &[PlayerHandle]| {
+            sample
+                .take()
+                .map_or_else(Vec::new, |sample| sample(handles))
+        };
+        let started = self.trace_begin();
+        let result = self.advance_frame_sampling(Some(&mut sample_once));
+        self.tick_stats_publisher();
+        let result = result.map(|requests| self.prepend_carried_requests(requests));
+        self.trace_advance(started, &result);
+        result
+    }
+
+    /// Returns `requests` behind any carried over from a failed
+    /// [`advance_frame_with`](Self::advance_frame_with).
+    fn prepend_carried_requests(&mut self, requests: RequestVec<T>) -> RequestVec<T> {
+        if self.carried_requests.is_empty() {
+            return requests;
+        }
+        let mut carried = std::mem::take(&mut self.carried_requests);
+        carried.extend(requests);
+        carried
+    }
+
+    /// Calls `sample` with the local player handles and queues the inputs it
+    /// returns, which must cover every local player.
+    fn add_sampled_inputs(
+        &mut self,
+        sample: &mut InputSampler<'_, T::Input>,
+    ) -> Result<(), FortressError> {
+        let handles = self.player_reg.local_player_handles();
+        self.local_inputs.clear();
+        for (handle, input) in sample(&handles) {
+            self.queue_local_input(handle, input)?;
+        }
+        if handles
+            .iter()
+            .any(|handle| !self.local_inputs.contains_key(handle))
+        {
+            return Err(InvalidRequestKind::MissingLocalInput.into());
+        }
+        Ok(())
+    }
+
+    /// The body of [`advance_frame`](Self::advance_frame). With `sample`, the
+    /// local inputs are sampled once any rollback has been performed instead
+    /// of being required up front.
+    fn advance_frame_sampling(
+        &mut self,
+        sample: Option<&mut InputSampler<'_, T::Input>>,
+    ) -> FortressResult<RequestVec<T>> {
+        self.skipped_this_call = false;
+        if let Some(tracker) = &mut self.rollback_context {
+            tracker.begin_batch();
+        }
         // receive info from remote players, trigger events and send messages
         self.poll_remote_clients();
 
@@ -2917,6 +3464,31 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             trace!("Session not synchronized; returning error");
             return Err(FortressError::NotSynchronized);
         }
+        self.note_advance();
+        self.health.record_advance_call(self.now());
+
+        // Auto frame skip: an honored wait recommendation skips this call's
+        // simulation. The network was polled above; the local input is not
+        // needed and is discarded.
+        if self.pending_skip_frames > 0 {
+            self.pending_skip_frames = self.pending_skip_frames.saturating_sub(1);
+            self.skipped_this_call = true;
+            self.local_inputs.clear();
+            return Ok(RequestVec::<T>::new());
+        }
+
+        // Remote input jitter buffer: deliver the held inputs that are due, or
+        // all of them once the buffer is suspended.
+        self.advance_ticks = self.advance_ticks.saturating_add(1);
+        let release_at = if self.remote_input_buffer_suspended() {
+            u64::MAX
+        } else {
+            self.advance_ticks
+        };
+        self.release_all_remote_inputs(release_at);
+        if self.state != SessionState::Running {
+            return Err(FortressError::NotSynchronized);
+        }
 
         // Hot-join: if a snapshot was just applied, the joiner must restore the
         // received state BEFORE any normal-path AdvanceFrame. Return exactly
@@ -2969,10 +3541,66 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             return Ok(RequestVec::<T>::new());
         }
 
-        // check if input for all local players is queued (zero-allocation via iterator)
-        for handle in self.player_reg.local_player_handles_iter() {
-            if !self.local_inputs.contains_key(&handle) {
-                return Err(InvalidRequestKind::MissingLocalInput.into());
+        // Session frame limit: no frame past `max_session_frames` is ever
+        // simulated. At the limit, mispredicted frames are still repaired; the
+        // session ends once every earlier frame is confirmed, so every peer ends
+        // on the same frame with the same state rather than clamping frame
+        // arithmetic near `i32::MAX` at different times.
+        if self.frame_limit_reached {
+            return Err(self.frame_limit_error());
+        }
+        if self.match_pause_frame() >= self.max_session_frames {
+            self.local_inputs.clear();
+            let requests = self.advance_frame_match_paused()?;
+            let last_frame = safe_frame_sub!(self.max_session_frames, 1, "P2PSession frame limit");
+            if requests.is_empty()
+                && self.resimulation_target.is_null()
+                && self.confirmed_frame() >= last_frame
+            {
+                self.frame_limit_reached = true;
+                self.health.end(self.now());
+                self.end_spectator_stream_at_forwarded_frame();
+                self.enqueue_event(FortressEvent::SessionFrameLimitReached {
+                    frame: self.max_session_frames,
+                });
+                return Err(self.frame_limit_error());
+            }
+            return Ok(requests);
+        }
+
+        // Match pause hold: a peer that reached the frame it agreed (or offered)
+        // to stop at never simulates a new frame until the pause is resumed or
+        // cancelled. Local input is not needed while held and is discarded.
+        if let Some(hold) = self.match_pause.hold_frame() {
+            if self.match_pause_frame() >= hold {
+                self.local_inputs.clear();
+                if let Some(frame) = self.match_pause.reach(self.match_pause_frame()) {
+                    self.enqueue_event(FortressEvent::MatchPaused { frame });
+                }
+                return self.advance_frame_match_paused();
+            }
+        }
+
+        // Frames-ahead ceiling: unlike a wait recommendation this is enforced.
+        // Nothing is simulated, but the network was polled above and the local
+        // inputs stay queued for the call that can advance.
+        if let Some(limit) = self.max_frames_ahead {
+            let frames_ahead = self.frames_ahead_of_slowest_peer();
+            if frames_ahead > limit {
+                return Err(FortressError::AheadOfRemotes {
+                    frames_ahead,
+                    limit,
+                });
+            }
+        }
+
+        // check if input for all local players is queued (zero-allocation via iterator);
+        // sampled inputs are checked once they are sampled
+        if sample.is_none() {
+            for handle in self.player_reg.local_player_handles_iter() {
+                if !self.local_inputs.contains_key(&handle) {
+                    return Err(InvalidRequestKind::MissingLocalInput.into());
+                }
             }
         }
 
@@ -3012,55 +3640,71 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
 
         // find the confirmed frame for which we received all inputs
         let confirmed_frame = self.confirmed_frame();
+        if self.confirmation_latency.is_some() {
+            let now = self.now();
+            let current_frame = self.sync_layer.current_frame();
+            if let Some(latency) = &mut self.confirmation_latency {
+                latency.confirm(confirmed_frame, current_frame, now);
+            }
+        }
 
         // check game consistency and roll back, if necessary
         if !lockstep {
-            // the disconnect frame indicates if a rollback is necessary due to a previously
-            // disconnected player (whose input would have been incorrectly predicted).
-            let first_incorrect = self
-                .sync_layer
-                .check_simulation_consistency(self.disconnect_frame);
-            // if we have an incorrect frame, then we need to rollback
-            if first_incorrect != Frame::NULL {
-                self.metrics.record_prediction_misses(
-                    self.sync_layer
-                        .count_players_with_incorrect_predictions(self.disconnect_frame),
-                );
-                if let Some(telemetry) = &self.telemetry {
-                    for (player, frame) in self
-                        .sync_layer
-                        .players_with_incorrect_predictions(self.disconnect_frame)
-                    {
-                        telemetry.on_prediction_miss(player, frame);
-                    }
-                }
-                self.adjust_gamestate(first_incorrect, confirmed_frame, &mut requests)?;
-                self.disconnect_frame = Frame::NULL;
+            self.repair_gamestate(confirmed_frame, &mut requests)?;
+            // A capped rollback that still has frames to resimulate ends the call here: no new
+            // frame is simulated until the resimulation has caught up, and the local inputs stay
+            // queued for the call that does.
+            if !self.resimulation_target.is_null() {
+                return Ok(requests);
             }
 
             // request gamestate save of current frame
             let last_saved = self.sync_layer.last_saved_frame();
             if self.save_mode == SaveMode::Sparse {
                 self.check_last_saved_state(last_saved, confirmed_frame, &mut requests)?;
+                if !self.resimulation_target.is_null() {
+                    return Ok(requests);
+                }
             } else {
                 // without sparse saving, always save the current frame after correcting and rollbacking
                 requests.push(self.sync_layer.save_current_state());
             }
         }
 
+        /*
+         *  SAMPLED INPUTS
+         */
+
+        // The rollback is done; sample the inputs for the new frame now. A rejected sample must
+        // not lose the repair requested above, so those requests are carried to the next advance.
+        if let Some(sample) = sample {
+            if let Err(err) = self.add_sampled_inputs(sample) {
+                self.carried_requests.extend(requests);
+                return Err(err);
+            }
+        }
+
         /*
          *  SEND OFF AND THROW AWAY INPUTS BEFORE THE CONFIRMED FRAME
          */
 
         // send confirmed inputs to spectators before throwing them away
         self.send_confirmed_inputs_to_spectators(confirmed_frame)?;
+        // With every remote player gone, nothing a spectator could still
+        // receive would come from the match it was watching.
+        if self.all_remote_players_disconnected() {
+            self.end_spectator_stream_at_forwarded_frame();
+        }
 
         // record confirmed inputs to the replay recorder before they are discarded
         self.record_confirmed_inputs(confirmed_frame);
 
         // set the last confirmed frame and discard all saved inputs before that frame
-        self.sync_layer
-            .set_last_confirmed_frame(confirmed_frame, self.save_mode);
+        self.sync_layer.set_last_confirmed_frame_before_rollback(
+            confirmed_frame,
+            self.save_mode,
+            self.disconnect_frame,
+        );
 
         /*
          *  WAIT RECOMMENDATION
@@ -3074,6 +3718,8 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
          */
 
         // register local inputs in the system and send them (zero-allocation via iterator)
+        let mut inputs_queued = true;
+        let input_added_at = self.confirmation_latency.as_ref().map(|_| self.now());
         for handle in self.player_reg.local_player_handles_iter() {
             // we have checked that these all exist above, but return error for safety
             let player_input =
@@ -3083,28 +3729,51 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                         player_handle: handle,
                         frame: self.sync_layer.current_frame(),
                     })?;
-            // send the input into the sync layer
-            let actual_frame = self.sync_layer.add_local_input(handle, *player_input);
+            // The input belongs to the frame simulated now, whatever frame the session was at
+            // when it was added (a hot-join snapshot load moves the current frame, for example).
+            let frame = self.sync_layer.current_frame();
+            // The last call queued and sent this frame's input but could not advance; that
+            // input stands.
+            if self.sync_layer.local_input_queued(handle, frame) {
+                player_input.frame = Frame::NULL;
+                inputs_queued = false;
+                continue;
+            }
+            player_input.frame = frame;
+            // send the input into the sync layer; a rejected input must not vanish silently
+            let actual_frame = self.sync_layer.add_local_input(handle, *player_input)?;
             player_input.frame = actual_frame;
-            // if the input has not been dropped
-            if actual_frame != Frame::NULL {
-                self.local_connect_status
-                    .get_mut(handle.as_usize())
-                    .ok_or(FortressError::InternalErrorStructured {
-                        kind: InternalErrorKind::ConnectionStatusIndexOutOfBounds {
-                            player_handle: handle,
-                        },
-                    })?
-                    .last_frame = actual_frame;
+            if let (Some(latency), Some(now)) = (&mut self.confirmation_latency, input_added_at) {
+                latency.record_input(actual_frame, frame, now);
             }
+            self.local_connect_status
+                .get_mut(handle.as_usize())
+                .ok_or(FortressError::InternalErrorStructured {
+                    kind: InternalErrorKind::ConnectionStatusIndexOutOfBounds {
+                        player_handle: handle,
+                    },
+                })?
+                .last_frame = actual_frame;
         }
 
-        // if the local inputs have not been dropped by the sync layer, send to all remote clients
-        if !self.local_inputs.values().any(|&i| i.frame == Frame::NULL) {
+        // an observer has no inputs to send, but still acks and gossips its connect status
+        if self.player_reg.num_local_players() == 0 {
+            let frame = self.sync_layer.current_frame();
             for endpoint in self.player_reg.remotes.values_mut() {
+                endpoint.send_observer_status(frame, &self.local_connect_status);
+                endpoint.send_all_messages(&mut self.socket);
+            }
+        } else if inputs_queued {
+            // if the local inputs were newly queued by the sync layer, send to all remote clients
+            for endpoint in self.player_reg.remotes.values_mut() {
+                // a relaying endpoint sends the local inputs with the relayed ones
+                if !endpoint.relayed_handles().is_empty() {
+                    continue;
+                }
                 endpoint.send_input(&self.local_inputs, &self.local_connect_status);
                 endpoint.send_all_messages(&mut self.socket);
             }
+            self.send_relayed_inputs();
         }
 
         /*
@@ -3124,7 +3793,7 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                 // we're not at the first frame, so we have to subtract the last confirmed frame
                 self.sync_layer.current_frame() - self.sync_layer.last_confirmed_frame()
             };
-            frames_ahead < self.max_prediction as i32
+            frames_ahead < self.max_prediction as i32 && !self.peer_prediction_limit_reached()
         };
         if can_advance {
             // get correct inputs for the current frame
@@ -3147,11 +3816,31 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                     });
                 },
             };
+            let previous_inputs = self
+                .previous_inputs
+                .record(self.sync_layer.current_frame(), &inputs)?;
+            if let Some(tracker) = &mut self.rollback_context {
+                tracker.record(self.sync_layer.current_frame(), &inputs, false)?;
+            }
+            if let Some(timeline) = &mut self.frame_timeline {
+                let flags = if inputs
+                    .iter()
+                    .any(|(_, status)| *status == InputStatus::Predicted)
+                {
+                    FrameFlags::PREDICTED
+                } else {
+                    FrameFlags::FIRST_PASS_CONFIRMED
+                };
+                timeline.record(self.sync_layer.current_frame(), flags);
+            }
             // advance the frame count
             self.sync_layer.advance_frame();
             // clear the local inputs after advancing the frame to allow new inputs to be ingested
             self.local_inputs.clear();
-            requests.push(FortressRequest::AdvanceFrame { inputs });
+            requests.push(FortressRequest::AdvanceFrame {
+                inputs,
+                previous_inputs,
+            });
 
             // Record the forward (visual) advance and sample confirmation lag:
             // how many frames ahead of the last confirmed frame we now are.
@@ -3164,6 +3853,7 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             };
             self.metrics
                 .record_forward_advance(u64::try_from(lag.max(0)).unwrap_or(0));
+            self.health.record_frames(1);
 
             if let Some(telemetry) = &self.telemetry {
                 telemetry.on_frame_advance(current);
@@ -3180,116 +3870,397 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             // counter's documented "prediction window was full" meaning exact.
             if !lockstep {
                 self.metrics.record_stall();
+                self.health.record_starved();
             }
         }
 
         Ok(requests)
     }
 
-    /// Should be called periodically by your application to give Fortress Rollback a chance to do internal work.
-    /// Fortress Rollback will receive packets, distribute them to corresponding endpoints, handle all occurring events and send all outgoing packets.
-    pub fn poll_remote_clients(&mut self) {
+    /// Emits the next batch of an outstanding capped rollback without simulating
+    /// a new frame.
+    ///
+    /// When a rollback deeper than
+    /// [`SessionBuilder::with_max_resimulation_per_advance`](crate::SessionBuilder::with_max_resimulation_per_advance)
+    /// is detected, [`advance_frame`](Self::advance_frame) emits only the first
+    /// `cap` resimulation frames and carries the rest over. This method emits
+    /// up to `cap` more of them (re-rolling back first if inputs received since
+    /// then invalidate frames already resimulated), for example to spend spare
+    /// time at the end of a render frame. Unlike `advance_frame`, it neither
+    /// polls the network nor needs local input. Returns an empty request set when
+    /// [`resimulation_debt`](Self::resimulation_debt) is `0`.
+    ///
+    /// # Errors
+    /// - Returns [`FortressError::NotSynchronized`] if the session is not running.
+    ///
+    #[must_use = "FortressRequests must be processed to advance the game state"]
+    pub fn continue_resimulation(&mut self) -> FortressResult<RequestVec<T>> {
         let _violation_scope = self.scoped_violation_observer();
-        // Hot-join joiner latency: count every poll spent still `HotJoining`.
-        // Only a joiner is ever `HotJoining` (a host never is), so this needs no
-        // further guard. Placed before the body so early returns cannot skip it.
-        #[cfg(feature = "hot-join")]
-        if self.state == SessionState::HotJoining {
-            self.hot_join_timing.polls_while_joining =
-                self.hot_join_timing.polls_while_joining.saturating_add(1);
+        if self.state != SessionState::Running {
+            return Err(FortressError::NotSynchronized);
         }
-        // Get all packets and distribute them to associated endpoints.
-        // The endpoints will handle their packets, which will trigger both events and UDP replies.
-        for (from_addr, msg) in &self.socket.receive_all_messages() {
-            let mut known_source = false;
-            if let Some(endpoint) = self.player_reg.remotes.get_mut(from_addr) {
-                known_source = true;
-                endpoint.handle_message(msg);
-            }
-            if let Some(endpoint) = self.player_reg.spectators.get_mut(from_addr) {
-                known_source = true;
-                endpoint.handle_message(msg);
-            }
-            if !known_source {
-                self.metrics.record_unknown_source_packet();
-                if !self.unknown_source_warned {
-                    self.unknown_source_warned = true;
-                    report_violation!(
-                        ViolationSeverity::Warning,
-                        ViolationKind::NetworkProtocol,
-                        "ignoring decoded message from unknown source address {:?}; this may indicate stale traffic, spoofing, or a peer NAT rebind. Further warnings are suppressed for this session; see SessionMetrics::unknown_source_packets for the running count",
-                        from_addr
-                    );
-                }
-            }
+        let mut requests = RequestVec::<T>::new();
+        if self.resimulation_target.is_null() {
+            return Ok(requests);
         }
-
-        // update frame information between remote players
-        for remote_endpoint in self.player_reg.remotes.values_mut() {
-            if remote_endpoint.is_running() {
-                remote_endpoint.update_local_frame_advantage(self.sync_layer.current_frame());
-            }
+        let confirmed_frame = self.confirmed_frame();
+        self.repair_gamestate(confirmed_frame, &mut requests)?;
+        // `advance_frame` saves the caught-up frame on its normal path; do the
+        // same here so the state at the resimulation target is never missing.
+        if self.resimulation_target.is_null() && self.save_mode != SaveMode::Sparse {
+            requests.push(self.sync_layer.save_current_state());
         }
+        Ok(requests)
+    }
 
-        // Connect-status nudge: while any remote slot is locally disconnected
-        // but its drop is not yet mesh-agreed, every running remote endpoint
-        // must keep gossiping our view even when input-idle — otherwise a
-        // survivor capped at its prediction window with a fully-acked send
-        // queue can never deliver the `disconnected` gossip and mesh agreement
-        // (the release condition in `remote_slot_confirmed_bound`) becomes
-        // unreachable. Computed before the endpoint poll below so the flag is
-        // live on the poll that follows a detection; cleared the poll after
-        // the mesh agrees. Allocation-free.
-        let nudge_needed = self.connect_status_nudge_needed();
-        for remote_endpoint in self.player_reg.remotes.values_mut() {
-            // A reserved hot-join endpoint (the host↔joiner channel) is
-            // excluded from every confirmation fold, so nudging it cannot
-            // release any hold — and a duplicate `Input` injected into the
-            // join handshake (the endpoint is `Running` mid-dance) interferes
-            // with the joiner's deferred input processing. Never nudge it.
-            #[cfg(feature = "hot-join")]
-            if self.hot_join.endpoint_is_reserved(remote_endpoint) {
-                remote_endpoint.set_connect_status_nudge(false);
-                continue;
-            }
-            remote_endpoint.set_connect_status_nudge(nudge_needed);
+    /// Ends the stream to every spectator and returns its final frame.
+    ///
+    /// Spectators are told that the last confirmed frame already forwarded to
+    /// them is the final one; each emits [`FortressEvent::StreamEnded`] once it
+    /// has simulated up to it instead of reporting the host as disconnected.
+    /// No input is forwarded to spectators afterwards. Call this before
+    /// dropping a session whose spectators should see a clean end; the notice
+    /// is resent on the keepalive cadence for as long as the session is
+    /// polled.
+    ///
+    /// The stream also ends on its own when the session reaches its frame
+    /// limit or every remote player has disconnected.
+    ///
+    /// Calling this again returns the final frame of the first call.
+    ///
+    /// # Errors
+    /// - Returns [`FortressError::NotSynchronized`] if the session is not running.
+    pub fn end_spectator_stream(&mut self) -> Result<Frame, FortressError> {
+        let _violation_scope = self.scoped_violation_observer();
+        if self.state != SessionState::Running {
+            return Err(FortressError::NotSynchronized);
         }
+        Ok(self.end_spectator_stream_at_forwarded_frame())
+    }
 
-        // Floor-round driving (double-failure-relay connected-relay reorder
-        // fix): detect prunes (bump the generation), answer received
-        // `FloorRequest`s with our current pessimistic floors, and push each
-        // endpoint the generation + whether it must (re)issue a `FloorRequest`.
-        // Runs before the endpoint poll below so a just-set request flag is
-        // honored on this same poll.
-        self.drive_floor_round();
-
-        // run endpoint poll and get events from players and spectators. This will trigger additional packets to be sent.
-        let mut events = VecDeque::new();
-        for endpoint in self.player_reg.remotes.values_mut() {
-            let handles = endpoint.handles(); // Returns Arc<[PlayerHandle]>, cheap to clone
-            let addr = endpoint.peer_addr();
-            for event in endpoint.poll(&self.local_connect_status) {
-                events.push_back((event, handles.clone(), addr.clone()))
-            }
+    /// Ends the spectator stream (once) at the last frame forwarded so far.
+    fn end_spectator_stream_at_forwarded_frame(&mut self) -> Frame {
+        if let Some(final_frame) = self.spectator_stream_end {
+            return final_frame;
         }
+        let final_frame = safe_frame_sub!(self.next_spectator_frame, 1, "spectator stream end");
+        self.spectator_stream_end = Some(final_frame);
         for endpoint in self.player_reg.spectators.values_mut() {
-            let handles = endpoint.handles(); // Returns Arc<[PlayerHandle]>, cheap to clone
-            let addr = endpoint.peer_addr();
-            for event in endpoint.poll(&self.local_connect_status) {
-                events.push_back((event, handles.clone(), addr.clone()))
-            }
-        }
-
-        // handle all events locally
-        for (event, handles, addr) in std::mem::take(&mut events) {
-            self.handle_event(event, handles, addr);
+            endpoint.end_stream(final_frame);
         }
+        final_frame
+    }
+
+    /// Whether the session has remote players and every one of them is
+    /// disconnected.
+    fn all_remote_players_disconnected(&self) -> bool {
+        // A coordinator can still refill a dropped slot.
+        #[cfg(feature = "hot-join")]
+        if self.hot_join.accept_hot_join {
+            return false;
+        }
+        let mut remotes = self.player_reg.remote_player_handles_iter().peekable();
+        remotes.peek().is_some()
+            && remotes.all(|handle| {
+                self.local_connect_status
+                    .get(handle.as_usize())
+                    .is_some_and(|status| status.disconnected)
+            })
+    }
+
+    /// The held `advance_frame` body: never simulates a new frame, but keeps
+    /// repairing mispredictions of the frames before the hold (and paying off
+    /// a capped rollback), so the state at the pause frame converges to the
+    /// confirmed one on every peer. Inputs are not discarded while held.
+    fn advance_frame_match_paused(&mut self) -> FortressResult<RequestVec<T>> {
+        let mut requests = RequestVec::<T>::new();
+        let confirmed_frame = self.confirmed_frame();
+        if !self.in_lockstep_mode() {
+            let had_debt = !self.resimulation_target.is_null();
+            self.repair_gamestate(confirmed_frame, &mut requests)?;
+            if had_debt && self.resimulation_target.is_null() && self.save_mode != SaveMode::Sparse
+            {
+                requests.push(self.sync_layer.save_current_state());
+            }
+        }
+        self.send_confirmed_inputs_to_spectators(confirmed_frame)?;
+        self.record_confirmed_inputs(confirmed_frame);
+        Ok(requests)
+    }
+
+    fn frame_limit_error(&self) -> FortressError {
+        InvalidRequestKind::SessionFrameLimitReached {
+            frame: self.max_session_frames,
+        }
+        .into()
+    }
+
+    /// The frame match pause negotiation compares holds against: the frame a
+    /// capped rollback is resimulating back up to, else the current frame.
+    fn match_pause_frame(&self) -> Frame {
+        if self.resimulation_target.is_null() {
+            self.sync_layer.current_frame()
+        } else {
+            self.resimulation_target
+        }
+    }
+
+    /// Remote peers (by lowest handle) whose endpoint is running and whose
+    /// players are all still connected.
+    fn match_pause_live_peers(&self) -> std::collections::BTreeSet<u16> {
+        self.player_reg
+            .remotes
+            .values()
+            .filter(|endpoint| endpoint.is_running())
+            .filter_map(|endpoint| {
+                let handles = endpoint.handles();
+                let connected = handles.iter().all(|handle| {
+                    self.local_connect_status
+                        .get(handle.as_usize())
+                        .is_some_and(|status| !status.disconnected)
+                });
+                let id = handles
+                    .iter()
+                    .filter_map(|handle| u16::try_from(handle.as_usize()).ok())
+                    .min();
+                id.filter(|_| connected)
+            })
+            .collect()
+    }
+
+    /// Sends the messages and raises the events of one negotiation step.
+    fn apply_match_pause_effects(&mut self, effects: MatchPauseEffects) {
+        for (peer, message) in effects.sends {
+            for endpoint in self.player_reg.remotes.values_mut() {
+                let id = endpoint
+                    .handles()
+                    .iter()
+                    .filter_map(|handle| u16::try_from(handle.as_usize()).ok())
+                    .min();
+                if id == Some(peer) {
+                    endpoint.send_match_pause_message(message);
+                }
+            }
+        }
+        for event in effects.events {
+            self.enqueue_event(match event {
+                MatchPauseEvent::Resumed { frame } => FortressEvent::MatchResumed { frame },
+                MatchPauseEvent::Cancelled { frame, resume } => {
+                    FortressEvent::MatchPauseCancelled { frame, resume }
+                },
+            });
+        }
+    }
+
+    /// Drains received match pause messages and drives timeouts and
+    /// retransmission of the open negotiation.
+    fn poll_match_pause(&mut self) {
+        let mut effects = MatchPauseEffects::default();
+        let current_frame = self.match_pause_frame();
+        let mut received = Vec::new(); // alloc-bound: at most MAX_RECEIVE_MESSAGES_PER_POLL per remote endpoint (mailbox-capped).
+        for endpoint in self.player_reg.remotes.values_mut() {
+            let source = endpoint
+                .handles()
+                .iter()
+                .filter_map(|handle| u16::try_from(handle.as_usize()).ok())
+                .min();
+            let messages = endpoint.take_received_match_pause_messages();
+            if let Some(source) = source {
+                received.extend(messages.map(|message| (source, message)));
+            }
+        }
+        for (source, message) in received {
+            self.match_pause
+                .on_message(source, message, current_frame, &mut effects);
+        }
+        // Collecting the live peers allocates, so skip it while nothing is
+        // negotiating.
+        if !self.match_pause.is_idle() {
+            let now = self.coordinated_drop_now();
+            let live_peers = self.match_pause_live_peers();
+            self.match_pause.poll(now, &live_peers, &mut effects);
+        }
+        self.apply_match_pause_effects(effects);
+    }
+
+    /// Should be called periodically by your application to give Fortress Rollback a chance to do internal work.
+    /// Fortress Rollback will receive packets, distribute them to corresponding endpoints, handle all occurring events and send all outgoing packets.
+    pub fn poll_remote_clients(&mut self) {
+        self.poll_remote_clients_received();
+    }
+
+    /// Whether [`advance_frame`](Self::advance_frame) can run now: the session
+    /// is running and input for every local player is queued.
+    pub(crate) fn ready_to_advance(&self) -> bool {
+        self.state == SessionState::Running
+            && self
+                .player_reg
+                .local_player_handles_iter()
+                .all(|handle| self.local_inputs.contains_key(&handle))
+    }
+
+    /// [`poll_remote_clients`](Self::poll_remote_clients), returning the number
+    /// of messages the socket delivered.
+    pub(crate) fn poll_remote_clients_received(&mut self) -> usize {
+        let started = self.trace_begin();
+        let received = self.poll_remote_clients_untraced();
+        self.trace_span(
+            started,
+            TraceSpan::Poll,
+            [i64::try_from(received).unwrap_or(i64::MAX), 0],
+        );
+        received
+    }
+
+    /// The body of [`poll_remote_clients_received`](Self::poll_remote_clients_received).
+    fn poll_remote_clients_untraced(&mut self) -> usize {
+        let _violation_scope = self.scoped_violation_observer();
+        // Hot-join joiner latency: count every poll spent still `HotJoining`.
+        // Only a joiner is ever `HotJoining` (a host never is), so this needs no
+        // further guard. Placed before the body so early returns cannot skip it.
+        #[cfg(feature = "hot-join")]
+        if self.state == SessionState::HotJoining {
+            self.hot_join_timing.polls_while_joining =
+                self.hot_join_timing.polls_while_joining.saturating_add(1);
+        }
+        // Get all packets and distribute them to associated endpoints.
+        // The endpoints will handle their packets, which will trigger both events and UDP replies.
+        let received = self.socket.receive_all_messages();
+        for (from_addr, msg) in &received {
+            let mut known_source = false;
+            if let Some(endpoint) = self.player_reg.remotes.get_mut(from_addr) {
+                known_source = true;
+                endpoint.handle_message(msg);
+            }
+            if let Some(endpoint) = self.player_reg.spectators.get_mut(from_addr) {
+                known_source = true;
+                endpoint.handle_message(msg);
+            }
+            // Not a peer's own address: it may be the relay of a routed
+            // endpoint (see `set_route`), which is matched by connection id.
+            if !known_source {
+                if let Some(endpoint) = self
+                    .player_reg
+                    .remotes
+                    .values_mut()
+                    .chain(self.player_reg.spectators.values_mut())
+                    .find(|endpoint| endpoint.is_handling_message(from_addr, msg))
+                {
+                    known_source = true;
+                    endpoint.handle_message(msg);
+                }
+            }
+            // Not a relay either: with address migration enabled, it may be a
+            // peer continuing from a new address.
+            if !known_source && self.migrate_endpoint(from_addr, msg) {
+                known_source = true;
+                if let Some(endpoint) = self.player_reg.remotes.get_mut(from_addr) {
+                    endpoint.handle_message(msg);
+                }
+                if let Some(endpoint) = self.player_reg.spectators.get_mut(from_addr) {
+                    endpoint.handle_message(msg);
+                }
+            }
+            if !known_source {
+                self.metrics.record_unknown_source_packet();
+                if !self.unknown_source_warned {
+                    self.unknown_source_warned = true;
+                    report_violation!(
+                        ViolationSeverity::Warning,
+                        ViolationKind::NetworkProtocol,
+                        "ignoring decoded message from unknown source address {:?}; this may indicate stale traffic, spoofing, or a peer NAT rebind. Further warnings are suppressed for this session; see SessionMetrics::unknown_source_packets for the running count",
+                        from_addr
+                    );
+                }
+            }
+        }
+
+        // update frame information between remote players; a peer held by a
+        // match pause is deliberately behind, which must not skew time sync
+        let held = self
+            .match_pause
+            .hold_frame()
+            .is_some_and(|hold| self.match_pause_frame() >= hold);
+        for remote_endpoint in self.player_reg.remotes.values_mut() {
+            if remote_endpoint.is_running() && !held {
+                remote_endpoint.update_local_frame_advantage(self.sync_layer.current_frame());
+            }
+        }
+
+        // Connect-status nudge: while any remote slot is locally disconnected
+        // but its drop is not yet mesh-agreed, every running remote endpoint
+        // must keep gossiping our view even when input-idle — otherwise a
+        // survivor capped at its prediction window with a fully-acked send
+        // queue can never deliver the `disconnected` gossip and mesh agreement
+        // (the release condition in `remote_slot_confirmed_bound`) becomes
+        // unreachable. Computed before the endpoint poll below so the flag is
+        // live on the poll that follows a detection; cleared the poll after
+        // the mesh agrees. Allocation-free.
+        let nudge_needed = self.connect_status_nudge_needed();
+        for remote_endpoint in self.player_reg.remotes.values_mut() {
+            // A reserved hot-join endpoint (the host↔joiner channel) is
+            // excluded from every confirmation fold, so nudging it cannot
+            // release any hold — and a duplicate `Input` injected into the
+            // join handshake (the endpoint is `Running` mid-dance) interferes
+            // with the joiner's deferred input processing. Never nudge it.
+            #[cfg(feature = "hot-join")]
+            if self.hot_join.endpoint_is_reserved(remote_endpoint) {
+                remote_endpoint.set_connect_status_nudge(false);
+                continue;
+            }
+            remote_endpoint.set_connect_status_nudge(nudge_needed);
+        }
+
+        // Floor-round driving (double-failure-relay connected-relay reorder
+        // fix): detect prunes (bump the generation), answer received
+        // `FloorRequest`s with our current pessimistic floors, and push each
+        // endpoint the generation + whether it must (re)issue a `FloorRequest`.
+        // Runs before the endpoint poll below so a just-set request flag is
+        // honored on this same poll.
+        self.drive_floor_round();
+
+        // Checked before the endpoint poll so a stall hint goes out this poll.
+        self.poll_advance_stall();
+
+        // run endpoint poll and get events from players and spectators. This will trigger additional packets to be sent.
+        let mut events = std::mem::take(&mut self.endpoint_events);
+        for endpoint in self.player_reg.remotes.values_mut() {
+            let handles = endpoint.handles(); // Returns Arc<[PlayerHandle]>, cheap to clone
+            let addr = endpoint.peer_addr();
+            for (stamp, event) in endpoint.poll(&self.local_connect_status) {
+                events.push_back((stamp, event, handles.clone(), addr.clone()))
+            }
+        }
+        for endpoint in self.player_reg.spectators.values_mut() {
+            let handles = endpoint.handles(); // Returns Arc<[PlayerHandle]>, cheap to clone
+            let addr = endpoint.peer_addr();
+            for (stamp, event) in endpoint.poll(&self.local_connect_status) {
+                events.push_back((stamp, event, handles.clone(), addr.clone()))
+            }
+        }
+        // Each endpoint's events are already in order; merge them across
+        // endpoints in the order they were generated. Stamps are unique, and
+        // the in-place sort does not allocate.
+        events
+            .make_contiguous()
+            .sort_unstable_by_key(|(stamp, ..)| *stamp);
+
+        // handle all events locally
+        while let Some((_, event, handles, addr)) = events.pop_front() {
+            self.handle_event(event, handles, addr);
+        }
+        self.endpoint_events = events;
+        // Third-party disconnect claims gathered by this poll take effect
+        // here once corroborated, ahead of every fold that reads them.
+        self.review_disconnect_claims();
 
         // D14 graceful-drop orchestration runs after endpoint messages/events
         // are staged and before the final send flush below. Thus a prepare,
         // report, backfill, ready, or commit produced here is transmitted on
         // this same application poll.
         self.poll_coordinated_drop();
+        self.poll_match_pause();
+        self.poll_checksum_requests();
 
         // emit network stats telemetry for each running remote endpoint
         if let Some(telemetry) = &self.telemetry {
@@ -3309,6 +4280,9 @@ pub fn poll_remote_clients(&mut self) {
         #[cfg(feature = "hot-join")]
         self.poll_hot_join();
 
+        // forward the relayed players' inputs received in this poll
+        self.send_relayed_inputs();
+
         // send all queued packets
         for endpoint in self.player_reg.remotes.values_mut() {
             endpoint.send_all_messages(&mut self.socket);
@@ -3316,6 +4290,99 @@ pub fn poll_remote_clients(&mut self) {
         for endpoint in self.player_reg.spectators.values_mut() {
             endpoint.send_all_messages(&mut self.socket);
         }
+        received.len()
+    }
+
+    /// Moves the endpoint whose peer sent `msg` from an unregistered address
+    /// to `new_addr`, if address migration accepts it (see
+    /// [`SessionBuilder::with_address_migration`](crate::SessionBuilder::with_address_migration)).
+    /// Remote and spectator endpoints sharing the old address move together.
+    /// Returns whether an endpoint moved.
+    fn migrate_endpoint(&mut self, new_addr: &T::Address, msg: &Message) -> bool {
+        if self.address_migration_blocked() {
+            return false;
+        }
+        let Some(old_addr) = self
+            .player_reg
+            .remotes
+            .values()
+            .chain(self.player_reg.spectators.values())
+            .find(|endpoint| endpoint.is_migrating_to(msg))
+            .map(UdpProtocol::peer_addr)
+        else {
+            return false;
+        };
+        for endpoints in [
+            &mut self.player_reg.remotes,
+            &mut self.player_reg.spectators,
+        ] {
+            if let Some(mut endpoint) = endpoints.remove(&old_addr) {
+                endpoint.migrate_to(new_addr.clone());
+                endpoints.insert(new_addr.clone(), endpoint);
+            }
+        }
+        for player_type in self.player_reg.handles.values_mut() {
+            if let PlayerType::Remote(addr) | PlayerType::Spectator(addr) = player_type {
+                if *addr == old_addr {
+                    *addr = new_addr.clone();
+                }
+            }
+        }
+        self.metrics.record_peer_address_migration();
+        debug!(
+            "Peer at {:?} continued from new address {:?}; moving its endpoint",
+            old_addr, new_addr
+        );
+        true
+    }
+
+    /// Whether a coordinated drop or hot-join is in progress. Both track
+    /// peers by address, so endpoints must not move until they finish.
+    fn address_migration_blocked(&self) -> bool {
+        if self.coordinated_drop.active.is_some() {
+            return true;
+        }
+        #[cfg(feature = "hot-join")]
+        if !self.hot_join.joining.is_empty()
+            || self.hot_join.npeer.is_some()
+            || self.hot_join.pending_reactivation.is_some()
+            || self.state == SessionState::HotJoining
+        {
+            return true;
+        }
+        false
+    }
+
+    /// Sends every message still queued for the remote and spectator endpoints
+    /// and returns how many went out.
+    ///
+    /// [`poll_remote_clients`](Self::poll_remote_clients) already flushes the
+    /// queues on every poll, so this only matters for messages queued since the
+    /// last poll, such as the goodbye [`disconnect_player`](Self::disconnect_player)
+    /// queues for a spectator. Call it before dropping a session to hand those
+    /// messages to the socket deliberately. It never blocks: each message is
+    /// passed to [`NonBlockingSocket::send_to`] once, and endpoints that have
+    /// already shut down are skipped. Messages held back by the send limits
+    /// ([`ProtocolConfig::max_packets_per_poll`] and
+    /// [`ProtocolConfig::max_bytes_per_second`]) stay queued.
+    ///
+    /// # Dropping the session
+    ///
+    /// Dropping a `P2PSession` performs the same flush once, on a best-effort
+    /// basis. The socket is owned by the session and is dropped only after the
+    /// flush, so it is always available. The drop-time flush is skipped while
+    /// the thread is already panicking, and a panic raised by the socket during
+    /// it is caught and reported as a violation instead of aborting the process.
+    pub fn flush(&mut self) -> usize {
+        let _violation_scope = self.scoped_violation_observer();
+        let mut flushed = 0;
+        for endpoint in self.player_reg.remotes.values_mut() {
+            flushed += endpoint.send_all_messages(&mut self.socket);
+        }
+        for endpoint in self.player_reg.spectators.values_mut() {
+            flushed += endpoint.send_all_messages(&mut self.socket);
+        }
+        flushed
     }
 
     /// Drives hot-join orchestration once per [`poll_remote_clients`](Self::poll_remote_clients) call:
@@ -3483,82 +4550,15 @@ fn poll_hot_join_host(&mut self) {
                 continue;
             }
 
-            // Activation frame F = last_saved_frame (current_frame - 1). Because
-            // the host pauses for the entire serve, F stays in-window.
-            let activation_frame = self.sync_layer.last_saved_frame();
-            if activation_frame.is_null() {
-                // Nothing saved yet; the joiner will re-send. Skip this poll.
+            let Some((activation_frame, snapshot)) = self.capture_join_snapshot() else {
                 continue;
-            }
-            // Defensive clamp: never serve a frame more than max_prediction
-            // behind current_frame (outside the rollback window).
-            let behind = self.sync_layer.current_frame() - activation_frame;
-            if behind < 0 || behind > self.max_prediction as i32 {
-                report_violation!(
-                    ViolationSeverity::Warning,
-                    ViolationKind::FrameSync,
-                    "Skipping hot-join serve: activation frame {} is {} frames behind current {} (max_prediction {})",
-                    activation_frame,
-                    behind,
-                    self.sync_layer.current_frame(),
-                    self.max_prediction
-                );
-                continue;
-            }
-
-            let snapshot = match crate::sessions::hot_join::capture_snapshot_with_max_wire_bytes(
-                &self.sync_layer,
-                activation_frame,
-                self.num_players,
-                self.hot_join.max_snapshot_wire_bytes,
-            ) {
-                Ok(Some(snapshot)) => snapshot,
-                Ok(None) => continue, // no valid saved state at F; retry next poll
-                Err(e) => {
-                    report_violation!(
-                        ViolationSeverity::Error,
-                        ViolationKind::InternalError,
-                        "Failed to capture hot-join snapshot at frame {}: {}",
-                        activation_frame,
-                        e
-                    );
-                    continue;
-                },
             };
-
             // The joiner endpoint must still exist to receive the snapshot that
             // Phase 2 sends below this same poll.
             if !self.player_reg.remotes.contains_key(&addr) {
                 continue;
             }
-            // The joiner applies the snapshot before the host consumes its
-            // snapshot ack. Under loss/jitter, a real Input(F) can overtake
-            // that ack. Defer Input processing until Phase 3 reactivates the
-            // session-level slot; otherwise the protocol consumes and acks F
-            // while `local_connect_status` is still disconnected, and the
-            // session then rejects F+1 forever as a sequence gap.
-            if let Some(endpoint) = self.player_reg.remotes.get_mut(&addr) {
-                endpoint.set_defer_input_processing(true);
-            }
-            // Cache the serve. The actual send is Phase 2's job — it is the SOLE
-            // snapshot-send site, so the serve we open here is transmitted exactly
-            // once this poll (Phase 2 runs immediately below) and once per poll
-            // thereafter. Sending here too would double the first poll's traffic
-            // and desync the `polls_since_serve`/timeout accounting. The slot is
-            // NOT reactivated yet: it stays frozen/disconnected until the ack
-            // arrives (Phase 3). `handle` stays in `reserved_slots` AND is added to
-            // `joining`, which pauses the host until the join resolves.
-            self.hot_join.joining.insert(
-                handle,
-                JoinServe {
-                    addr: addr.clone(),
-                    frame: activation_frame,
-                    snapshot,
-                    polls_since_serve: 0,
-                },
-            );
-
-            self.enqueue_event(FortressEvent::JoinRequested { handle, addr });
+            self.open_join_serve(addr, handle, activation_frame, snapshot);
         }
 
         // Phase 2: the SOLE snapshot-send site (reliable retransmit). Send the
@@ -3736,6 +4736,93 @@ fn poll_hot_join_host(&mut self) {
         self.poll_npeer_post_serve();
     }
 
+    /// Captures the 2-peer serve snapshot at `F = last_saved_frame`, or `None`
+    /// when no servable state exists this poll (the caller retries later).
+    #[cfg(feature = "hot-join")]
+    fn capture_join_snapshot(&self) -> Option<(Frame, StateSnapshot)> {
+        // Activation frame F = last_saved_frame (current_frame - 1). Because
+        // the host pauses for the entire serve, F stays in-window.
+        let activation_frame = self.sync_layer.last_saved_frame();
+        if activation_frame.is_null() {
+            // Nothing saved yet; the joiner will re-send. Skip this poll.
+            return None;
+        }
+        // Defensive clamp: never serve a frame more than max_prediction
+        // behind current_frame (outside the rollback window).
+        let behind = self.sync_layer.current_frame() - activation_frame;
+        if behind < 0 || behind > self.max_prediction as i32 {
+            report_violation!(
+                ViolationSeverity::Warning,
+                ViolationKind::FrameSync,
+                "Skipping hot-join serve: activation frame {} is {} frames behind current {} (max_prediction {})",
+                activation_frame,
+                behind,
+                self.sync_layer.current_frame(),
+                self.max_prediction
+            );
+            return None;
+        }
+
+        match crate::sessions::hot_join::capture_snapshot_with_max_wire_bytes(
+            &self.sync_layer,
+            activation_frame,
+            self.num_players,
+            self.hot_join.max_snapshot_wire_bytes,
+        ) {
+            Ok(Some(snapshot)) => Some((activation_frame, snapshot)),
+            Ok(None) => None, // no valid saved state at F; retry next poll
+            Err(e) => {
+                report_violation!(
+                    ViolationSeverity::Error,
+                    ViolationKind::InternalError,
+                    "Failed to capture hot-join snapshot at frame {}: {}",
+                    activation_frame,
+                    e
+                );
+                None
+            },
+        }
+    }
+
+    /// Opens a 2-peer serve of `snapshot` for `handle` to the endpoint at `addr`.
+    #[cfg(feature = "hot-join")]
+    fn open_join_serve(
+        &mut self,
+        addr: T::Address,
+        handle: PlayerHandle,
+        activation_frame: Frame,
+        snapshot: StateSnapshot,
+    ) {
+        // The joiner applies the snapshot before the host consumes its
+        // snapshot ack. Under loss/jitter, a real Input(F) can overtake
+        // that ack. Defer Input processing until Phase 3 reactivates the
+        // session-level slot; otherwise the protocol consumes and acks F
+        // while `local_connect_status` is still disconnected, and the
+        // session then rejects F+1 forever as a sequence gap.
+        if let Some(endpoint) = self.player_reg.remotes.get_mut(&addr) {
+            endpoint.set_defer_input_processing(true);
+        }
+        // Cache the serve. The actual send is Phase 2's job — it is the SOLE
+        // snapshot-send site, so the serve we open here is transmitted exactly
+        // once this poll (Phase 2 runs immediately below) and once per poll
+        // thereafter. Sending here too would double the first poll's traffic
+        // and desync the `polls_since_serve`/timeout accounting. The slot is
+        // NOT reactivated yet: it stays frozen/disconnected until the ack
+        // arrives (Phase 3). `handle` stays in `reserved_slots` AND is added to
+        // `joining`, which pauses the host until the join resolves.
+        self.hot_join.joining.insert(
+            handle,
+            JoinServe {
+                addr: addr.clone(),
+                frame: activation_frame,
+                snapshot,
+                polls_since_serve: 0,
+            },
+        );
+
+        self.enqueue_event(FortressEvent::JoinRequested { handle, addr });
+    }
+
     /// Returns the **survivor set** for a prospective N-peer serve: the
     /// addresses of every running, non-reserved remote endpoint other than the
     /// joiner's. Spectators live in a separate registry and are never
@@ -4601,25 +5688,7 @@ fn advance_frame_npeer_paused(&mut self) -> FortressResult<RequestVec<T>> {
         // cannot create mispredictions (prediction episodes only cover
         // requested frames), so this repairs strictly pre-pause speculation.
         let confirmed_frame = self.confirmed_frame();
-        let first_incorrect = self
-            .sync_layer
-            .check_simulation_consistency(self.disconnect_frame);
-        if !first_incorrect.is_null() {
-            self.metrics.record_prediction_misses(
-                self.sync_layer
-                    .count_players_with_incorrect_predictions(self.disconnect_frame),
-            );
-            if let Some(telemetry) = &self.telemetry {
-                for (player, frame) in self
-                    .sync_layer
-                    .players_with_incorrect_predictions(self.disconnect_frame)
-                {
-                    telemetry.on_prediction_miss(player, frame);
-                }
-            }
-            self.adjust_gamestate(first_incorrect, confirmed_frame, &mut requests)?;
-            self.disconnect_frame = Frame::NULL;
-        }
+        self.repair_gamestate(confirmed_frame, &mut requests)?;
 
         // Spectator/replay flush (no input discard: `set_last_confirmed_frame`
         // is deliberately NOT called while paused, so the rollback window and
@@ -4847,10 +5916,10 @@ fn backfill_joiner_pending_inputs(
         // Gather the window's inputs (all local players per frame) BEFORE
         // touching the endpoint, so a missing frame aborts without a
         // half-cleared queue.
-        let mut window: Vec<BTreeMap<PlayerHandle, PlayerInput<T::Input>>> = Vec::new(); // alloc-bound: at most max_prediction frames (the pause cap bounds the window), pre-checked against the pending-output capacity above.
+        let mut window: Vec<VecMap<PlayerHandle, PlayerInput<T::Input>>> = Vec::new(); // alloc-bound: at most max_prediction frames (the pause cap bounds the window), pre-checked against the pending-output capacity above.
         let mut frame = activation_frame;
         while frame <= last_added {
-            let mut inputs = BTreeMap::new();
+            let mut inputs = VecMap::new();
             for &local in &locals {
                 match self.sync_layer.confirmed_input(local, frame) {
                     Ok(player_input) => {
@@ -6354,6 +7423,7 @@ fn poll_hot_join_joiner(&mut self) {
             joiner.ack_resends_remaining = ack_resends;
         }
         self.state = SessionState::Running;
+        self.health.start(self.now());
         self.record_hot_join_activation();
     }
 
@@ -7263,6 +8333,7 @@ fn apply_buffered_npeer_snapshot(&mut self) {
             joiner.pending_backfill = pending_backfill;
         }
         self.state = SessionState::Running;
+        self.health.start(self.now());
         self.record_hot_join_activation();
     }
 
@@ -7385,6 +8456,7 @@ pub fn disconnect_behavior(&self) -> DisconnectBehavior {
     #[must_use = "remove_player errors should be handled"]
     pub fn remove_player(&mut self, player_handle: PlayerHandle) -> Result<(), FortressError> {
         let _violation_scope = self.scoped_violation_observer();
+        self.release_all_remote_inputs(u64::MAX);
         let player_type = self.player_reg.handles.get(&player_handle).ok_or(
             InvalidRequestKind::DisconnectInvalidHandle {
                 handle: player_handle,
@@ -7491,6 +8563,7 @@ pub fn remove_player(&mut self, player_handle: PlayerHandle) -> Result<(), Fortr
     #[must_use = "disconnect errors should be handled"]
     pub fn disconnect_player(&mut self, player_handle: PlayerHandle) -> Result<(), FortressError> {
         let _violation_scope = self.scoped_violation_observer();
+        self.release_all_remote_inputs(u64::MAX);
         match self.player_reg.handles.get(&player_handle) {
             // the local player cannot be disconnected
             None => Err(InvalidRequestKind::DisconnectInvalidHandle {
@@ -7521,9 +8594,14 @@ pub fn disconnect_player(&mut self, player_handle: PlayerHandle) -> Result<(), F
                         kind: InternalErrorKind::DisconnectStatusNotFound { player_handle },
                     })?;
                 if !status.disconnected {
+                    // a relayed player is disconnected alone, keeping the relay
+                    let relayed = self
+                        .relayed_players
+                        .contains(&player_handle)
+                        .then(|| BTreeMap::from([(player_handle, status.last_frame)]));
                     let result = self.disconnect_player_with_policy(
                         player_handle,
-                        None,
+                        relayed.as_ref(),
                         DisconnectBehavior::Halt,
                         DisconnectEventPolicy::Suppress,
                         GracefulDropFailurePolicy::DisconnectAndHalt,
@@ -7550,6 +8628,149 @@ pub fn disconnect_player(&mut self, player_handle: PlayerHandle) -> Result<(), F
         }
     }
 
+    /// Promotes a spectator into the slot of a disconnected remote player
+    /// (hot-join feature).
+    ///
+    /// The spectator's endpoint is rebound as the player endpoint for
+    /// `player_handle`, keeping its address and connection era, and the
+    /// player's previous endpoint is dropped. The slot is then served like a
+    /// hot-join: this session captures a snapshot at the returned activation
+    /// frame `F` and pauses until the promoted peer acks it. On the promoted
+    /// peer's machine, the application replaces its
+    /// [`SpectatorSession`](crate::SpectatorSession) with a joiner built by
+    /// [`SessionBuilder::start_hot_join_session`](crate::SessionBuilder::start_hot_join_session)
+    /// on the same address; it loads the snapshot at `F` and sends real inputs
+    /// from `F` on. Frames before `F` keep the slot's `Disconnected` dummy
+    /// inputs. Once the ack arrives the slot's connection status turns
+    /// connected with `last_frame = F - 1`, which every peer folds the same way.
+    ///
+    /// Promotion needs hot-join serving
+    /// ([`SessionBuilder::with_hot_join`](crate::SessionBuilder::with_hot_join))
+    /// and no other connected remote players: an N-peer serve agrees its
+    /// activation frame with the surviving peers and cannot commit to one when
+    /// the call returns. If the promoted peer does not ack within the serve
+    /// timeout, the serve is aborted and the slot stays reserved for its
+    /// address; a later join is served at a fresh frame.
+    ///
+    /// # Errors
+    /// - Returns [`FortressError::NotSynchronized`] if the session is not running.
+    /// - Returns [`InvalidRequestKind::NotSpectator`] if `spectator_handle` is
+    ///   not a registered spectator.
+    /// - Returns [`InvalidRequestKind::InvalidRemotePlayerHandle`] if
+    ///   `player_handle` is not a remote player.
+    /// - Returns [`InvalidRequestKind::PlayerNotDisconnected`] if the player's
+    ///   slot is still connected.
+    /// - Returns [`InvalidRequestKind::NotSupported`] if hot-join serving is
+    ///   disabled, the session halted, another join is in progress, other
+    ///   remote players are connected, the player's endpoint also carries other
+    ///   players, the spectator shares an address with another remote player,
+    ///   or no snapshot can be served this frame.
+    ///
+    /// [`InvalidRequestKind::NotSpectator`]: crate::error::InvalidRequestKind::NotSpectator
+    /// [`InvalidRequestKind::InvalidRemotePlayerHandle`]: crate::error::InvalidRequestKind::InvalidRemotePlayerHandle
+    /// [`InvalidRequestKind::PlayerNotDisconnected`]: crate::error::InvalidRequestKind::PlayerNotDisconnected
+    /// [`InvalidRequestKind::NotSupported`]: crate::error::InvalidRequestKind::NotSupported
+    #[cfg(feature = "hot-join")]
+    #[must_use = "promote_spectator errors should be handled"]
+    pub fn promote_spectator(
+        &mut self,
+        spectator_handle: PlayerHandle,
+        player_handle: PlayerHandle,
+    ) -> Result<Frame, FortressError> {
+        let _violation_scope = self.scoped_violation_observer();
+        if self.state != SessionState::Running {
+            return Err(FortressError::NotSynchronized);
+        }
+        let Some(PlayerType::Spectator(spectator_addr)) =
+            self.player_reg.handles.get(&spectator_handle)
+        else {
+            return Err(InvalidRequestKind::NotSpectator {
+                handle: spectator_handle,
+            }
+            .into());
+        };
+        let spectator_addr = spectator_addr.clone();
+        let Some(PlayerType::Remote(player_addr)) = self.player_reg.handles.get(&player_handle)
+        else {
+            return Err(InvalidRequestKind::InvalidRemotePlayerHandle {
+                handle: player_handle,
+                num_players: self.num_players,
+            }
+            .into());
+        };
+        let player_addr = player_addr.clone();
+        let disconnected = self
+            .local_connect_status
+            .get(player_handle.as_usize())
+            .ok_or(FortressError::InternalErrorStructured {
+                kind: InternalErrorKind::DisconnectStatusNotFound { player_handle },
+            })?
+            .disconnected;
+        if !disconnected {
+            return Err(InvalidRequestKind::PlayerNotDisconnected {
+                handle: player_handle,
+            }
+            .into());
+        }
+
+        let unsupported = |operation| Err(InvalidRequestKind::NotSupported { operation }.into());
+        if !self.hot_join.accept_hot_join {
+            return unsupported("promote_spectator (hot-join serving disabled)");
+        }
+        if self.halt_confirmed_ceiling.is_some() {
+            return unsupported("promote_spectator (session halted)");
+        }
+        if !self.hot_join.joining.is_empty() || self.hot_join.npeer.is_some() {
+            return unsupported("promote_spectator (hot-join serve in progress)");
+        }
+        let shares_endpoint = self
+            .player_reg
+            .remotes
+            .get(&player_addr)
+            .is_some_and(|endpoint| *endpoint.handles() != [player_handle]);
+        if shares_endpoint {
+            return unsupported("promote_spectator (player endpoint carries other players)");
+        }
+        if spectator_addr != player_addr && self.player_reg.remotes.contains_key(&spectator_addr) {
+            return unsupported("promote_spectator (spectator address has a remote player)");
+        }
+        let survivors = self.npeer_survivor_addrs(&spectator_addr);
+        if survivors.iter().any(|addr| *addr != player_addr) {
+            return unsupported("promote_spectator (other remote players connected)");
+        }
+        let Some((activation_frame, snapshot)) = self.capture_join_snapshot() else {
+            return unsupported("promote_spectator (no servable snapshot this frame)");
+        };
+
+        let local_players = self.player_reg.num_local_players();
+        let endpoint = self.player_reg.spectators.get_mut(&spectator_addr).ok_or(
+            FortressError::InternalErrorStructured {
+                kind: InternalErrorKind::EndpointNotFoundForSpectator {
+                    player_handle: spectator_handle,
+                },
+            },
+        )?;
+        endpoint.rearm_as_player(vec![player_handle], local_players, self.desync_detection)?;
+        let Some(endpoint) = self.player_reg.spectators.remove(&spectator_addr) else {
+            return Err(FortressError::InternalErrorStructured {
+                kind: InternalErrorKind::EndpointNotFoundForSpectator {
+                    player_handle: spectator_handle,
+                },
+            });
+        };
+        self.player_reg.remotes.remove(&player_addr);
+        self.player_reg
+            .remotes
+            .insert(spectator_addr.clone(), endpoint);
+        self.player_reg.handles.remove(&spectator_handle);
+        self.player_reg
+            .handles
+            .insert(player_handle, PlayerType::Remote(spectator_addr.clone()));
+        self.hot_join.reserved_slots.insert(player_handle);
+        self.open_join_serve(spectator_addr, player_handle, activation_frame, snapshot);
+        Ok(activation_frame)
+    }
+
     /// Returns a [`NetworkStats`] struct that gives information about the quality of the network connection.
     ///
     /// The returned struct includes:
@@ -7602,6 +8823,139 @@ pub fn network_stats(
         Ok(stats)
     }
 
+    /// Returns the estimated offset of a remote player's or spectator's clock
+    /// from this machine's, in milliseconds: positive when the remote clock is
+    /// ahead. `None` until the first quality report from the peer arrives.
+    ///
+    /// The same estimate as
+    /// [`NetworkStats::estimated_clock_offset_ms`], accurate to within half the
+    /// round trip at best. Use it to align logs from both machines after a
+    /// match, never as an input to the simulation.
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] if the handle does not refer to a remote
+    ///   player or spectator.
+    pub fn clock_offset(&self, player_handle: PlayerHandle) -> Result<Option<i64>, FortressError> {
+        Ok(self.endpoint_for_handle(player_handle)?.clock_offset_ms())
+    }
+
+    /// Returns a [`StatsSubscription`] other threads can read this session's
+    /// statistics from without locking it.
+    ///
+    /// Every `interval` calls of [`advance_frame`](Self::advance_frame) or
+    /// [`advance_frame_with`](Self::advance_frame_with), failed ones included,
+    /// the session publishes a [`StatsSnapshot`](crate::StatsSnapshot) of its
+    /// current and confirmed frame, [`frames_ahead`](Self::frames_ahead), its
+    /// rollback count and the [`network_stats`](Self::network_stats) of every
+    /// remote player and spectator. Publishing is wait-free and does not
+    /// allocate; reading with [`StatsSubscription::latest`] never blocks the
+    /// session, so a render thread can poll it every frame while the session
+    /// is driven elsewhere.
+    ///
+    /// The first call publishes a snapshot right away. Later calls change the
+    /// interval and return further subscriptions to the same snapshots.
+    /// Handles added after the first call are not included.
+    ///
+    /// # Errors
+    /// - Returns [`InvalidRequestKind::ConfigValueOutOfRange`] if `interval` is 0.
+    /// - Returns [`InvalidRequestKind::AllocationFailed`] if the snapshot buffers
+    ///   cannot be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fortress_rollback::{Config, PlayerHandle, PlayerType, SessionBuilder, UdpNonBlockingSocket};
+    /// # use std::net::SocketAddr;
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = u8;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let socket = UdpNonBlockingSocket::bind_to_port(0)?;
+    /// let mut session = SessionBuilder::<MyConfig>::new()
+    ///     .add_player(PlayerType::Local, PlayerHandle::new(0))?
+    ///     .add_player(PlayerType::Remote("127.0.0.1:7001".parse()?), PlayerHandle::new(1))?
+    ///     .start_p2p_session(socket)?;
+    /// let stats = session.stats_publisher(4)?;
+    /// let overlay = std::thread::spawn(move || {
+    ///     let snapshot = stats.latest();
+    ///     format!("frame {} (+{})", snapshot.current_frame, snapshot.frames_ahead)
+    /// });
+    /// assert_eq!(overlay.join().unwrap(), "frame 0 (+0)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stats_publisher(&mut self, interval: u32) -> Result<StatsSubscription, FortressError> {
+        if let Some(publisher) = &mut self.stats_publisher {
+            publisher.set_interval(interval)?;
+            return Ok(publisher.subscribe());
+        }
+        let count =
+            self.remote_player_handles_iter().count() + self.spectator_handles_iter().count();
+        let mut handles = Vec::new();
+        handles
+            .try_reserve_exact(count)
+            .map_err(|_err| allocation_failed("stats_publisher.handles", count))?;
+        handles.extend(
+            self.remote_player_handles_iter()
+                .chain(self.spectator_handles_iter()),
+        );
+        let publisher = StatsPublisher::new(handles, interval)?;
+        let subscription = publisher.subscribe();
+        self.stats_publisher = Some(publisher);
+        self.tick_stats_publisher();
+        Ok(subscription)
+    }
+
+    /// Counts one advance towards the next stats snapshot and publishes it
+    /// when due.
+    /// The start of a traced span, or `None` when no trace is recorded.
+    fn trace_begin(&self) -> Option<web_time::Instant> {
+        self.trace_recorder.as_ref().map(TraceRecorder::now)
+    }
+
+    /// Records `span` from `started`, a [`trace_begin`](Self::trace_begin).
+    fn trace_span(&mut self, started: Option<web_time::Instant>, span: TraceSpan, args: [i64; 2]) {
+        if let (Some(recorder), Some(started)) = (&mut self.trace_recorder, started) {
+            recorder.span(span, started, args);
+        }
+    }
+
+    /// Records an `advance_frame` call from `started` that returned `result`.
+    fn trace_advance(
+        &mut self,
+        started: Option<web_time::Instant>,
+        result: &FortressResult<RequestVec<T>>,
+    ) {
+        if started.is_none() {
+            return;
+        }
+        let frame = i64::from(self.sync_layer.current_frame().as_i32());
+        let requests = match result {
+            Ok(requests) => i64::try_from(requests.len()).unwrap_or(i64::MAX),
+            Err(_) => 0,
+        };
+        self.trace_span(started, TraceSpan::AdvanceFrame, [frame, requests]);
+    }
+
+    fn tick_stats_publisher(&mut self) {
+        let Some(mut publisher) = self.stats_publisher.take() else {
+            return;
+        };
+        if publisher.tick() {
+            publisher.refresh_network_stats(|handle| self.network_stats(handle).ok());
+            let scratch = publisher.scratch_mut();
+            scratch.current_frame = self.current_frame();
+            scratch.confirmed_frame = self.confirmed_frame();
+            scratch.frames_ahead = self.frames_ahead;
+            scratch.rollback_count = self.metrics.rollback_count;
+            publisher.publish();
+        }
+        self.stats_publisher = Some(publisher);
+    }
+
     /// Returns a [`PeerMetrics`] snapshot of protocol-level traffic and
     /// connection metrics for one remote peer or spectator.
     ///
@@ -7618,16 +8972,301 @@ pub fn network_stats(
     /// # Errors
     /// - Returns a [`FortressError`] if the handle does not refer to a remote
     ///   player or spectator.
-    pub fn peer_metrics(&self, player_handle: PlayerHandle) -> Result<PeerMetrics, FortressError> {
+    pub fn peer_metrics(&self, player_handle: PlayerHandle) -> Result<PeerMetrics, FortressError> {
+        match self.player_reg.handles.get(&player_handle) {
+            Some(PlayerType::Remote(addr)) => match self.player_reg.remotes.get(addr) {
+                Some(endpoint) => Ok(endpoint.peer_metrics()),
+                None => Err(FortressError::InternalErrorStructured {
+                    kind: InternalErrorKind::EndpointNotFoundForRemote { player_handle },
+                }),
+            },
+            Some(PlayerType::Spectator(addr)) => match self.player_reg.spectators.get(addr) {
+                Some(endpoint) => Ok(endpoint.peer_metrics()),
+                None => Err(FortressError::InternalErrorStructured {
+                    kind: InternalErrorKind::EndpointNotFoundForSpectator { player_handle },
+                }),
+            },
+            _ => Err(InvalidRequestKind::NotRemotePlayerOrSpectator {
+                handle: player_handle,
+            }
+            .into()),
+        }
+    }
+
+    /// Routes all outgoing packets for a remote player or spectator through
+    /// `via`, such as a relay or TURN server, instead of the address the
+    /// player was added with.
+    ///
+    /// The player keeps its identity: events, handles, and
+    /// [`player_type`](Self::player_type) still use the original address, and
+    /// packets from it are still accepted. Packets arriving from `via` are
+    /// matched to the player by the connection id both peers agreed on during
+    /// synchronization, so one relay address can carry several players. Routing
+    /// through the player's own address restores direct delivery.
+    ///
+    /// Only the send address changes, so a route can be set, changed, or
+    /// cleared at any time, including mid-match, without synchronizing again.
+    /// Packets still in flight through a previous route are dropped as coming
+    /// from an unknown source; lost inputs are resent as usual.
+    /// Players added under the same address share one connection, so the route
+    /// applies to all of them.
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] if the handle does not refer to a remote
+    ///   player or spectator.
+    pub fn set_route(
+        &mut self,
+        player_handle: PlayerHandle,
+        via: T::Address,
+    ) -> Result<(), FortressError> {
+        let endpoint = self.endpoint_for_handle_mut(player_handle)?;
+        endpoint.set_route(Some(via));
+        Ok(())
+    }
+
+    /// Returns the address outgoing packets for `player_handle` are routed
+    /// through (see [`set_route`](Self::set_route)), or `None` when they go
+    /// directly to the player's own address or the handle is not a remote
+    /// player or spectator.
+    #[must_use]
+    pub fn route(&self, player_handle: PlayerHandle) -> Option<T::Address> {
+        self.endpoint_for_handle(player_handle)
+            .ok()?
+            .route()
+            .cloned()
+    }
+
+    /// Makes this session the relay between two remote players that cannot
+    /// reach each other directly, for a star topology over NATs where one
+    /// pair of a full mesh fails to connect.
+    ///
+    /// Both players add each other with
+    /// [`SessionBuilder::add_player_via_relay`](crate::SessionBuilder::add_player_via_relay)
+    /// and this session's address. From then on this session sends each of
+    /// them the other's inputs along with its own: every link keeps its own
+    /// delta reference, so the forwarded inputs are re-encoded per link
+    /// rather than copied. An input is forwarded once this session has
+    /// received it, so the relayed players see each other one hop later.
+    /// This session's own inputs travel in the same frames, so they reach
+    /// each relayed player only once the other one's input for that frame
+    /// has arrived.
+    ///
+    /// Call this before the first [`poll_remote_clients`](Self::poll_remote_clients):
+    /// the relayed handles are announced in the handshake, and each endpoint
+    /// then expects the extra inputs.
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] if either handle is not a remote player,
+    ///   or both share an address.
+    /// - Returns [`InvalidRequestKind::NotSupported`] if the session has no
+    ///   local player, disconnects players with
+    ///   [`DisconnectBehavior::ContinueWithout`], serves hot-joins, or has
+    ///   already sent a handshake to either player.
+    pub fn enable_relay_for(
+        &mut self,
+        handle_pair: (PlayerHandle, PlayerHandle),
+    ) -> Result<(), FortressError> {
+        let (first, second) = handle_pair;
+        let addrs = [first, second].map(|handle| match self.player_reg.handles.get(&handle) {
+            Some(PlayerType::Remote(addr)) => Ok(addr.clone()),
+            _ => Err(FortressError::from(
+                InvalidRequestKind::NotRemotePlayerOrSpectator { handle },
+            )),
+        });
+        let [first_addr, second_addr] = addrs;
+        let (first_addr, second_addr) = (first_addr?, second_addr?);
+        if first_addr == second_addr {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "relaying between players at the same address",
+            }
+            .into());
+        }
+        if self.num_local_players() == 0 {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "relaying inputs without a local player",
+            }
+            .into());
+        }
+        if self.disconnect_behavior == DisconnectBehavior::ContinueWithout {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "relaying inputs with DisconnectBehavior::ContinueWithout",
+            }
+            .into());
+        }
+        #[cfg(feature = "hot-join")]
+        if self.hot_join.accept_hot_join || !self.hot_join.reserved_slots.is_empty() {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "relaying inputs on a hot-join host",
+            }
+            .into());
+        }
+        let unsent = [&first_addr, &second_addr].iter().all(|addr| {
+            self.player_reg
+                .remotes
+                .get(*addr)
+                .is_some_and(UdpProtocol::handshake_unsent)
+        });
+        if !unsent {
+            return Err(InvalidRequestKind::NotSupported {
+                operation: "relaying inputs after the handshake was sent",
+            }
+            .into());
+        }
+        // Check both endpoints before changing either, so a failure leaves
+        // neither peer expecting relayed inputs the other will never send.
+        let mut prepared = [None, None];
+        for (slot, (addr, relayed)) in prepared
+            .iter_mut()
+            .zip([(&first_addr, second), (&second_addr, first)])
+        {
+            if let Some(endpoint) = self.player_reg.remotes.get(addr) {
+                *slot = endpoint
+                    .prepare_relay_for(relayed)?
+                    .map(|relay| (addr, relay));
+            }
+        }
+        let local_handles = self.player_reg.local_player_handles();
+        for (addr, relay) in prepared.into_iter().flatten() {
+            if let Some(endpoint) = self.player_reg.remotes.get_mut(addr) {
+                endpoint.relay_for(relay, &local_handles);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the socket the session sends and receives with, keeping every
+    /// connection, input, and statistic, for when the local network changes
+    /// mid-match (a phone moving from WiFi to cellular, say) and the old
+    /// socket stops working.
+    ///
+    /// Every running endpoint immediately sends a few keep-alives from the
+    /// new socket. Its source address differs from the old one, so peers
+    /// accept its packets only with
+    /// [`SessionBuilder::with_address_migration`](crate::SessionBuilder::with_address_migration)
+    /// enabled; they then reply to the new address. Packets still addressed
+    /// to the old socket are lost, and lost inputs are resent as usual.
+    ///
+    /// The old socket is dropped without flushing: messages queued for it
+    /// are sent from the new socket instead. Decode failures counted by the
+    /// old socket no longer appear in
+    /// [`SessionMetrics::decode_failures`](crate::SessionMetrics::decode_failures).
+    pub fn replace_socket(&mut self, socket: impl NonBlockingSocket<T::Address> + 'static) {
+        self.socket = Box::new(socket);
+        for endpoint in self.player_reg.remotes.values_mut() {
+            if endpoint.is_running() {
+                endpoint.queue_keep_alive_burst(SOCKET_REPLACEMENT_KEEP_ALIVES);
+            }
+            endpoint.send_all_messages(&mut self.socket);
+        }
+        for endpoint in self.player_reg.spectators.values_mut() {
+            if endpoint.is_running() {
+                endpoint.queue_keep_alive_burst(SOCKET_REPLACEMENT_KEEP_ALIVES);
+            }
+            endpoint.send_all_messages(&mut self.socket);
+        }
+    }
+
+    /// Returns the connection ID this session sends with to `player_handle`,
+    /// or `None` when the handle is not a remote player or spectator.
+    ///
+    /// Each endpoint draws a random ID unless one is pinned with
+    /// [`SessionBuilder::with_protocol_identity`] or
+    /// [`SessionBuilder::with_pre_established`].
+    ///
+    /// [`SessionBuilder::with_protocol_identity`]: crate::SessionBuilder::with_protocol_identity
+    /// [`SessionBuilder::with_pre_established`]: crate::SessionBuilder::with_pre_established
+    #[must_use]
+    pub fn local_connection_id(&self, player_handle: PlayerHandle) -> Option<u32> {
+        Some(self.endpoint_for_handle(player_handle).ok()?.conn_id())
+    }
+
+    /// Returns the connection ID the peer of `player_handle` sends with, or
+    /// `None` before the handshake has learned it or when the handle is not a
+    /// remote player or spectator.
+    #[must_use]
+    pub fn remote_connection_id(&self, player_handle: PlayerHandle) -> Option<u32> {
+        self.endpoint_for_handle(player_handle)
+            .ok()?
+            .remote_conn_id()
+    }
+
+    /// Looks up the endpoint of a remote player or spectator.
+    fn endpoint_for_handle_mut(
+        &mut self,
+        player_handle: PlayerHandle,
+    ) -> Result<&mut UdpProtocol<T>, FortressError> {
+        match self.player_reg.handles.get(&player_handle) {
+            Some(PlayerType::Remote(addr)) => match self.player_reg.remotes.get_mut(addr) {
+                Some(endpoint) => Ok(endpoint),
+                None => Err(FortressError::InternalErrorStructured {
+                    kind: InternalErrorKind::EndpointNotFoundForRemote { player_handle },
+                }),
+            },
+            Some(PlayerType::Spectator(addr)) => match self.player_reg.spectators.get_mut(addr) {
+                Some(endpoint) => Ok(endpoint),
+                None => Err(FortressError::InternalErrorStructured {
+                    kind: InternalErrorKind::EndpointNotFoundForSpectator { player_handle },
+                }),
+            },
+            _ => Err(InvalidRequestKind::NotRemotePlayerOrSpectator {
+                handle: player_handle,
+            }
+            .into()),
+        }
+    }
+
+    /// Returns the [`CompressionStats`] of the inputs sent to one remote peer
+    /// or spectator: average raw and encoded bytes per `Input` packet, the
+    /// compression ratio, and how often each byte of the serialized input
+    /// changes between consecutive frames.
+    ///
+    /// Use the per-byte change counts to lay out the input type for the delta
+    /// encoder: put the fields that change often next to each other. The
+    /// statistics accumulate until
+    /// [`reset_compression_stats`](Self::reset_compression_stats).
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] if the handle does not refer to a remote
+    ///   player or spectator.
+    pub fn compression_stats(
+        &self,
+        player_handle: PlayerHandle,
+    ) -> Result<CompressionStats, FortressError> {
+        Ok(self
+            .endpoint_for_handle(player_handle)?
+            .compression_stats()
+            .clone())
+    }
+
+    /// Clears the [`CompressionStats`] of one remote peer or spectator. Players
+    /// added under the same address share one connection and its statistics.
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] if the handle does not refer to a remote
+    ///   player or spectator.
+    pub fn reset_compression_stats(
+        &mut self,
+        player_handle: PlayerHandle,
+    ) -> Result<(), FortressError> {
+        self.endpoint_for_handle_mut(player_handle)?
+            .reset_compression_stats();
+        Ok(())
+    }
+
+    /// Looks up the endpoint of a remote player or spectator.
+    fn endpoint_for_handle(
+        &self,
+        player_handle: PlayerHandle,
+    ) -> Result<&UdpProtocol<T>, FortressError> {
         match self.player_reg.handles.get(&player_handle) {
             Some(PlayerType::Remote(addr)) => match self.player_reg.remotes.get(addr) {
-                Some(endpoint) => Ok(endpoint.peer_metrics()),
+                Some(endpoint) => Ok(endpoint),
                 None => Err(FortressError::InternalErrorStructured {
                     kind: InternalErrorKind::EndpointNotFoundForRemote { player_handle },
                 }),
             },
             Some(PlayerType::Spectator(addr)) => match self.player_reg.spectators.get(addr) {
-                Some(endpoint) => Ok(endpoint.peer_metrics()),
+                Some(endpoint) => Ok(endpoint),
                 None => Err(FortressError::InternalErrorStructured {
                     kind: InternalErrorKind::EndpointNotFoundForSpectator { player_handle },
                 }),
@@ -7682,7 +9321,6 @@ pub fn handshake_trace(
 
     /// The current instant on this session's injectable protocol clock (or the
     /// platform's monotonic clock if none was configured). Deterministic under the DST harness.
-    #[cfg(feature = "hot-join")]
     fn now(&self) -> web_time::Instant {
         clock_now(self.protocol_config.clock.as_ref())
     }
@@ -7867,51 +9505,10 @@ pub fn confirmed_frame(&self) -> Frame {
         // is a cheap `Option` check (no push), so it adds no cost to the common
         // path; nested under a driver method's scope it is a harmless re-push.
         let _violation_scope = self.scoped_violation_observer();
-        let mut confirmed_frame = Frame::new(i32::MAX);
-
-        // The double-failure-relay fold-membership-asymmetry gate is
-        // slot-independent — compute it once and reuse it for the floor-round
-        // hold check (`slot_round_incomplete`) below.
-        let relay_topology = self.pessimistic_floor_relay_topology();
-
-        for (idx, con_stat) in self.local_connect_status.iter().enumerate() {
-            let handle = PlayerHandle::new(idx);
-            if self.player_reg.is_local_player(handle) {
-                // Local players can never be disconnected (`remove_player` and
-                // `disconnect_player` both reject local handles), so the local
-                // slot always contributes its own last added frame.
-                confirmed_frame = std::cmp::min(confirmed_frame, con_stat.last_frame);
-            } else if let Some(bound) = self.remote_slot_confirmed_bound(handle, con_stat) {
-                // Post-prune fresh-ack HOLD (the double-failure-relay floor round,
-                // `AsyncAckSoundRoundSeq`, S55 — closing the connected-relay
-                // reorder facet and subsuming the S49 cold-cache facet). When a
-                // folded relay reports this slot CONNECTED but has not yet
-                // delivered a `FloorReply` for the current prune generation
-                // (`slot_round_incomplete`), its connect-status `last_frame` is its
-                // own (possibly high) receipt — NOT a freeze — so it may hide a
-                // departed origin's low the relay still folds, so confirming past
-                // it could discard the contested window irreversibly. HOLD at the
-                // current confirmed frame — the
-                // value held while the origin was still in our fold (the model's
-                // `CurBound`) — until every folded connected relay has answered the
-                // round (`remote_slot_confirmed_bound` then folds the reorder-immune
-                // `round_floor`) or the slot mesh-agrees (excluded). Capping with
-                // `min` PRESERVES a genuine lowering (a fresh relay's relayed
-                // converge-down below the current confirmed flows through, so freeze
-                // convergence still follows down); it only blocks an ADVANCE past
-                // the held frame. Zero cost outside the relay topology —
-                // `slot_round_incomplete` cannot fire there (its rustdoc explains
-                // why).
-                let capped = if self.slot_round_incomplete(handle, relay_topology) {
-                    std::cmp::min(bound, self.sync_layer.last_confirmed_frame())
-                } else {
-                    bound
-                };
-                confirmed_frame = std::cmp::min(confirmed_frame, capped);
-            }
-            // `None`: the slot's disconnect is mesh-agreed — excluded from the
-            // minimum; the frozen input value carries the slot from here on.
-        }
+        let mut confirmed_frame = self
+            .slot_confirmed_frames()
+            .map(|(_, frame)| frame)
+            .fold(Frame::new(i32::MAX), std::cmp::min);
 
         // If all players are disconnected, this should not happen in a running session
         if confirmed_frame.as_i32() == i32::MAX {
@@ -7938,12 +9535,516 @@ pub fn confirmed_frame(&self) -> Frame {
         reported
     }
 
+    /// Returns the last frame for which `handle`'s inputs are confirmed.
+    ///
+    /// This is the per-player term of [`confirmed_frame`](Self::confirmed_frame):
+    /// for a local player the last frame it added input for, for a remote
+    /// player the same bound the session folds into the global minimum (its
+    /// received inputs, lowered by what other peers report in larger meshes).
+    /// The global confirmed frame is the minimum of these over all players
+    /// whose disconnect has not been agreed by the mesh, further held by a
+    /// [`DisconnectBehavior::Halt`] ceiling or a drop in progress. For a
+    /// player whose disconnect the mesh has agreed on, this is the frame its
+    /// input froze at; such a player no longer holds back the global frame.
+    ///
+    /// Returns `None` for a handle that is not a player of this session
+    /// (spectators included).
+    #[must_use]
+    pub fn confirmed_frame_for(&self, handle: PlayerHandle) -> Option<Frame> {
+        let _violation_scope = self.scoped_violation_observer();
+        let con_stat = self.local_connect_status.get(handle.as_usize())?;
+        let relay_topology = self.pessimistic_floor_relay_topology();
+        Some(
+            self.slot_confirmed_frame(handle, con_stat, relay_topology)
+                .unwrap_or(con_stat.last_frame),
+        )
+    }
+
+    /// Returns the newest frame that no rollback can simulate again.
+    ///
+    /// Effects that cannot be undone, such as awarding a round or spending
+    /// an item, are safe to apply once the frame they happened on is at or
+    /// below this frame: the inputs for it are confirmed, the session has
+    /// simulated it with them, and no rollback the session can still perform
+    /// loads a state at or before it.
+    ///
+    /// This is at most [`confirmed_frame`](Self::confirmed_frame), and the two
+    /// differ when:
+    /// - a misprediction has been detected (by
+    ///   [`poll_remote_clients`](Self::poll_remote_clients) receiving the
+    ///   real input) but the rollback only runs in the next
+    ///   [`advance_frame`](Self::advance_frame). The confirmed frame already
+    ///   covers the corrected input, while this stays below the mispredicted
+    ///   frame until the rollback has been requested.
+    /// - a rollback would load an older saved state than the frame it
+    ///   repairs: with [`SaveMode::Sparse`] the last saved frame, with
+    ///   [`SaveMode::Pyramid`] the newest one kept.
+    /// - a capped resimulation (see
+    ///   [`resimulation_debt`](Self::resimulation_debt)) has not reached the
+    ///   frame yet.
+    ///
+    /// It follows [`confirmed_frame`](Self::confirmed_frame), so it can move
+    /// back in the same rare cases described there. Returns [`Frame::NULL`]
+    /// until the first frame is final.
+    ///
+    /// [`SaveMode::Sparse`]: crate::SaveMode::Sparse
+    /// [`SaveMode::Pyramid`]: crate::SaveMode::Pyramid
+    #[must_use]
+    pub fn finalized_frame(&self) -> Frame {
+        let _violation_scope = self.scoped_violation_observer();
+        let confirmed = self.confirmed_frame();
+        // Any future misprediction is past the confirmed frame; a detected
+        // one may be earlier.
+        let next_unconfirmed =
+            safe_frame_add!(confirmed, 1, "P2PSession::finalized_frame").max(Frame::new(0));
+        let pending = self
+            .sync_layer
+            .check_simulation_consistency(self.disconnect_frame);
+        let first_incorrect = if pending.is_null() {
+            next_unconfirmed
+        } else {
+            pending.min(next_unconfirmed)
+        };
+        // The same load frame `adjust_gamestate` would pick.
+        let resimulated_frame = self.sync_layer.current_frame();
+        let current_frame = if self.resimulation_target.is_null() {
+            resimulated_frame
+        } else {
+            self.resimulation_target
+        };
+        let window_floor = safe_frame_sub!(
+            current_frame,
+            self.max_prediction as i32,
+            "P2PSession::finalized_frame"
+        )
+        .max(Frame::new(0));
+        let load_frame = self
+            .rollback_load_frame(first_incorrect, window_floor)
+            .max(window_floor)
+            .min(resimulated_frame);
+        let finalized = confirmed.min(safe_frame_sub!(
+            load_frame,
+            1,
+            "P2PSession::finalized_frame"
+        ));
+        if finalized.is_valid() {
+            finalized
+        } else {
+            Frame::NULL
+        }
+    }
+
+    /// Copies the newest saved state no rollback can change, at or below
+    /// [`finalized_frame`](Self::finalized_frame), into a [`StateSlot`] for a
+    /// training mode's save-state. Returns `None` while no such state is
+    /// saved.
+    ///
+    /// Restoring the slot with
+    /// [`propose_state_restore`](Self::propose_state_restore) is limited to
+    /// sessions whose players are all local; exporting works in any session.
+    #[must_use]
+    pub fn export_state_slot(&self) -> Option<StateSlot<T::State>>
+    where
+        T::State: Clone,
+    {
+        let finalized = self.finalized_frame();
+        if finalized.is_null() {
+            return None;
+        }
+        let frame = self
+            .sync_layer
+            .newest_saved_frame_in_range(Frame::new(0), finalized);
+        let (state, checksum) = self.sync_layer.capture_snapshot_state(frame)?;
+        Some(StateSlot::new(frame, state, checksum))
+    }
+
+    /// Restores a [`StateSlot`] taken with
+    /// [`export_state_slot`](Self::export_state_slot), possibly in an earlier
+    /// run of the game, and continues the session from its frame.
+    ///
+    /// This is a user-driven load, not a rollback: the slot's frame may lie
+    /// anywhere before or after the current one. The next
+    /// [`advance_frame`](Self::advance_frame) returns a
+    /// [`FortressRequest::LoadGameState`] of the slot's state ahead of its
+    /// other requests, and the local inputs from then on are simulated from
+    /// the slot's frame. Inputs queued for later frames are dropped, and a
+    /// replay being recorded drops the frames from the slot's frame on, so it
+    /// follows the restored match.
+    ///
+    /// With an input delay, the frames the delay covers from the slot's frame
+    /// on hold the default input, as at the start of a session: their inputs
+    /// were given before the slot was saved and are not part of it.
+    ///
+    /// Only sessions whose players are all local can restore: with a remote
+    /// player or spectator every peer would have to load the slot at the same
+    /// point, which this session cannot arrange.
+    ///
+    /// # Errors
+    /// - Returns [`InvalidRequestKind::StateRestoreRequiresLocalSession`] if
+    ///   the session has a remote player or a spectator.
+    /// - Returns [`InvalidRequestKind::UnsupportedStateSlotVersion`] if the
+    ///   slot was written in another format version.
+    /// - Returns [`FortressError::InvalidFrameStructured`] if the slot's frame
+    ///   is negative.
+    pub fn propose_state_restore(
+        &mut self,
+        slot: StateSlot<T::State>,
+    ) -> Result<(), FortressError> {
+        let _violation_scope = self.scoped_violation_observer();
+        if self.player_reg.num_local_players() != self.num_players
+            || self.player_reg.num_spectators() > 0
+        {
+            return Err(InvalidRequestKind::StateRestoreRequiresLocalSession.into());
+        }
+        let (frame, state, checksum) = slot.into_restorable()?;
+        let load = self.sync_layer.restore_state(frame, state, checksum)?;
+        // Requests carried from a failed `advance_frame_with` belong to the
+        // abandoned frames.
+        self.carried_requests.clear();
+        self.carried_requests.push(load);
+        let confirmed = self.sync_layer.last_confirmed_frame();
+        for con_stat in &mut self.local_connect_status {
+            con_stat.last_frame = confirmed;
+        }
+        self.exposed_confirmed_high_water
+            .store(confirmed.as_i32(), Ordering::Relaxed);
+        self.local_inputs.clear();
+        self.previous_inputs.clear();
+        self.resimulation_target = Frame::NULL;
+        self.resimulation_depth = 0;
+        self.disconnect_frame = Frame::NULL;
+        self.frame_limit_reached = false;
+        self.local_checksum_history.retain(|&f, _| f < frame);
+        if let Some(latency) = &mut self.confirmation_latency {
+            latency.discard_from(frame);
+        }
+        if self.last_recorded_frame >= frame {
+            if let Some(recorder) = &mut self.recording {
+                recorder.truncate(usize::try_from(frame.as_i32()).unwrap_or(0));
+            }
+            self.last_recorded_frame = confirmed;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if no rollback can simulate `frame` again, i.e. it is
+    /// at or below [`finalized_frame`](Self::finalized_frame).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fortress_rollback::{Config, Frame, P2PSession};
+    /// fn award_round<T: Config>(session: &P2PSession<T>, round_won_at: Frame) {
+    ///     if session.is_frame_final(round_won_at) {
+    ///         // Safe: no rollback can replay the frame the round was won on.
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn is_frame_final(&self, frame: Frame) -> bool {
+        frame.is_valid() && frame <= self.finalized_frame()
+    }
+
+    /// Returns how many frames the session may run ahead of `handle`'s
+    /// confirmed input before it stops advancing.
+    ///
+    /// This is the prediction window unless
+    /// [`ProtocolConfig::per_peer_prediction_limit`] is set, in which case it
+    /// is that base plus the frames in the measured round-trip time to
+    /// `handle`'s endpoint, capped at the window. It follows the RTT as
+    /// quality reports update it, so it suits a HUD next to
+    /// [`confirmed_frame_for`](Self::confirmed_frame_for).
+    ///
+    /// Returns `None` unless `handle` is a remote player.
+    #[must_use]
+    pub fn prediction_limit_for(&self, handle: PlayerHandle) -> Option<usize> {
+        match self.player_reg.handles.get(&handle)? {
+            PlayerType::Remote(addr) => self
+                .player_reg
+                .remotes
+                .get(addr)
+                .map(|endpoint| endpoint.prediction_allowance(self.max_prediction)),
+            _ => None,
+        }
+    }
+
+    /// Returns the player whose confirmation currently holds back
+    /// [`confirmed_frame`](Self::confirmed_frame).
+    ///
+    /// This is the player with the lowest
+    /// [`confirmed_frame_for`](Self::confirmed_frame_for) among those still
+    /// counted, with ties going to the lowest handle. Useful for showing who
+    /// is lagging the match. Returns `None` when no player is counted, which
+    /// only happens once every remote disconnect is agreed and no local player
+    /// exists.
+    #[must_use]
+    pub fn limiting_player(&self) -> Option<PlayerHandle> {
+        let _violation_scope = self.scoped_violation_observer();
+        self.slot_confirmed_frames()
+            .min_by_key(|(_, frame)| *frame)
+            .map(|(handle, _)| handle)
+    }
+
+    /// Returns the frame a remote player was disconnected at.
+    ///
+    /// This is the last frame for which both this peer and `handle`'s peer
+    /// had each other's inputs, or [`Frame::NULL`] if there is none. Both ends
+    /// of a link that timed out on each other derive the same value, which
+    /// makes it suitable for adjudicating the match (e.g. who forfeits from
+    /// which frame). Until the endpoint shuts down, late messages from the
+    /// peer can still raise it toward the agreed value, so read it once the
+    /// link has had a moment to settle rather than at the
+    /// [`Disconnected`](crate::FortressEvent::Disconnected) event itself.
+    ///
+    /// Returns `None` unless `handle` is a remote player that is disconnected.
+    #[must_use]
+    pub fn disconnect_frame(&self, handle: PlayerHandle) -> Option<Frame> {
+        let status = self.local_connect_status.get(handle.as_usize())?;
+        if !status.disconnected {
+            return None;
+        }
+        match self.player_reg.handles.get(&handle)? {
+            PlayerType::Remote(addr) => self
+                .player_reg
+                .remotes
+                .get(addr)
+                .map(UdpProtocol::disconnect_frame),
+            _ => None,
+        }
+    }
+
+    /// Returns a read-only view of the session's input queues and frame
+    /// counters; see the [`advanced`](crate::advanced) module for its
+    /// stability guarantees.
+    #[cfg(feature = "advanced")]
+    #[must_use]
+    pub fn sync_layer_view(&self) -> crate::advanced::SyncLayerView<'_, T> {
+        crate::advanced::SyncLayerView::new(&self.sync_layer)
+    }
+
+    /// Returns a read-only view of the time synchronization with the endpoint
+    /// of a remote player or spectator.
+    ///
+    /// # Errors
+    /// - Returns a [`FortressError`] if the handle does not refer to a remote
+    ///   player or spectator.
+    #[cfg(feature = "advanced")]
+    pub fn time_sync_view(
+        &self,
+        player_handle: PlayerHandle,
+    ) -> Result<crate::advanced::TimeSyncView<'_>, FortressError> {
+        Ok(crate::advanced::TimeSyncView::new(
+            self.endpoint_for_handle(player_handle)?.time_sync(),
+        ))
+    }
+
     /// Returns the current frame of a session.
+    ///
+    /// While a capped rollback is still resimulating (see
+    /// [`resimulation_debt`](Self::resimulation_debt)), this is the frame the
+    /// resimulation has reached so far, i.e. the frame of the game state after
+    /// fulfilling the requests returned so far.
     #[must_use]
     pub fn current_frame(&self) -> Frame {
         self.sync_layer.current_frame()
     }
 
+    /// Returns how many rolled-back frames are still waiting to be resimulated.
+    ///
+    /// Always `0` unless
+    /// [`SessionBuilder::with_max_resimulation_per_advance`](crate::SessionBuilder::with_max_resimulation_per_advance)
+    /// is set and a rollback deeper than the cap was detected. The outstanding
+    /// frames are emitted by the following [`advance_frame`](Self::advance_frame)
+    /// (or [`continue_resimulation`](Self::continue_resimulation)) calls before
+    /// any new frame is simulated, so a non-zero value is a good cue to show a
+    /// brief "catching up" state.
+    #[must_use]
+    pub fn resimulation_debt(&self) -> u32 {
+        if self.resimulation_target.is_null() {
+            return 0;
+        }
+        u32::try_from(self.resimulation_target - self.sync_layer.current_frame()).unwrap_or(0)
+    }
+
+    /// Asks every peer to pause the match at a common frame.
+    ///
+    /// The pause is proposed
+    /// [`with_match_pause_margin`](crate::SessionBuilder::with_match_pause_margin)
+    /// frames after the current frame. Each peer that accepts stops advancing
+    /// at the later of that frame and its own current frame, and the proposer
+    /// commits the latest of those, so every peer pauses at exactly the same
+    /// frame. While held, [`advance_frame`](Self::advance_frame) needs no local
+    /// input and only returns rollback repairs of earlier frames;
+    /// [`FortressEvent::MatchPaused`] is emitted when this peer reaches the
+    /// agreed frame.
+    ///
+    /// If any peer declines (for example because it is negotiating a pause of
+    /// its own), does not answer within two seconds, or disconnects first, the
+    /// attempt ends with [`FortressEvent::MatchPauseCancelled`] on the
+    /// proposer and on every peer that had accepted, and play continues. Two
+    /// peers requesting at the same time cancel each other; retry later.
+    ///
+    /// # Errors
+    /// - Returns [`FortressError::NotSynchronized`] if the session is not running.
+    /// - Returns [`InvalidRequestKind::MatchPauseInProgress`] while a pause or
+    ///   resume negotiation is open.
+    /// - Returns [`InvalidRequestKind::MatchAlreadyPaused`] if a pause is
+    ///   already committed.
+    pub fn request_match_pause(&mut self) -> Result<(), FortressError> {
+        let frame = self
+            .match_pause_frame()
+            .saturating_add(i32::try_from(self.match_pause_margin).unwrap_or(i32::MAX));
+        self.request_match_pause_kind(MatchPauseKind::Pause, frame)
+    }
+
+    /// Asks every peer to resume a paused match.
+    ///
+    /// Only valid once this peer has reached the agreed pause frame; peers that
+    /// have not reached it yet decline, cancelling the attempt with
+    /// [`FortressEvent::MatchPauseCancelled`] (`resume: true`). Once every peer
+    /// accepts, [`FortressEvent::MatchResumed`] is emitted everywhere and
+    /// [`advance_frame`](Self::advance_frame) simulates from the pause frame
+    /// again.
+    ///
+    /// # Errors
+    /// - Returns [`FortressError::NotSynchronized`] if the session is not running.
+    /// - Returns [`InvalidRequestKind::MatchPauseInProgress`] while a pause or
+    ///   resume negotiation is open.
+    /// - Returns [`InvalidRequestKind::MatchNotPaused`] unless this peer has
+    ///   reached an agreed pause.
+    pub fn request_match_resume(&mut self) -> Result<(), FortressError> {
+        self.request_match_pause_kind(MatchPauseKind::Resume, self.match_pause_frame())
+    }
+
+    fn request_match_pause_kind(
+        &mut self,
+        kind: MatchPauseKind,
+        frame: Frame,
+    ) -> Result<(), FortressError> {
+        let _violation_scope = self.scoped_violation_observer();
+        if self.state != SessionState::Running {
+            return Err(FortressError::NotSynchronized);
+        }
+        let mut effects = MatchPauseEffects::default();
+        let now = self.coordinated_drop_now();
+        let live_peers = self.match_pause_live_peers();
+        self.match_pause
+            .propose(kind, frame, &live_peers, now, &mut effects)?;
+        self.apply_match_pause_effects(effects);
+        // Flush the proposal now rather than on the next poll.
+        for endpoint in self.player_reg.remotes.values_mut() {
+            endpoint.send_all_messages(&mut self.socket);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` once this peer has reached an agreed match pause and
+    /// until the pause is resumed.
+    #[must_use]
+    pub fn is_match_paused(&self) -> bool {
+        self.match_pause.is_paused()
+    }
+
+    /// Tags `frame` with eight bytes of application data for the remote
+    /// players, such as a round-end marker or the id of a game event.
+    ///
+    /// The tag travels with the next local input added, is resent with it
+    /// until each peer acknowledges that input, and is delivered to each
+    /// remote peer exactly once as [`FortressEvent::FrameTag`], no earlier than
+    /// the input it travels with. Tags are not forwarded to spectators, and
+    /// peers that did not enable [`ProtocolConfig::frame_tags`] are skipped.
+    ///
+    /// Tagged frames must increase from one tag to the next, so every peer
+    /// sees at most one tag per frame. A tag still unacknowledged when a tag
+    /// more than the prediction window later is sent is dropped and counted in
+    /// [`PeerMetrics::frame_tags_dropped`].
+    ///
+    /// # Errors
+    /// - Returns [`InvalidRequestKind::FrameTagsDisabled`] unless
+    ///   [`ProtocolConfig::frame_tags`] is enabled.
+    /// - Returns [`InvalidRequestKind::NoLocalPlayers`] if this session has no
+    ///   local player, since tags travel with local inputs.
+    /// - Returns [`FortressError::InvalidFrameStructured`] if `frame` is
+    ///   negative.
+    /// - Returns [`InvalidRequestKind::FrameTagNotIncreasing`] unless `frame`
+    ///   is later than the previously tagged frame.
+    pub fn send_frame_tag(&mut self, frame: Frame, data: [u8; 8]) -> Result<(), FortressError> {
+        let _violation_scope = self.scoped_violation_observer();
+        if !self.protocol_config.frame_tags {
+            return Err(InvalidRequestKind::FrameTagsDisabled.into());
+        }
+        if self.player_reg.num_local_players() == 0 {
+            return Err(InvalidRequestKind::NoLocalPlayers.into());
+        }
+        if !frame.is_valid() {
+            return Err(FortressError::InvalidFrameStructured {
+                frame,
+                reason: InvalidFrameReason::NullOrNegative,
+            });
+        }
+        if frame <= self.last_frame_tag {
+            return Err(InvalidRequestKind::FrameTagNotIncreasing {
+                frame,
+                last_tagged: self.last_frame_tag,
+            }
+            .into());
+        }
+        self.last_frame_tag = frame;
+        for endpoint in self.player_reg.remotes.values_mut() {
+            endpoint.queue_frame_tag(frame, data);
+        }
+        Ok(())
+    }
+
+    /// Sends `payload` to the remote player or spectator `handle` on a
+    /// reliable channel, for the few control messages a game cannot afford to
+    /// lose, such as a character selection or an agreed match result.
+    ///
+    /// Each message is numbered, sent with the next outgoing packets, and
+    /// resent every
+    /// [`SyncConfig::running_retry_interval`](crate::SyncConfig::running_retry_interval)
+    /// until the peer acknowledges it. The peer receives every message exactly
+    /// once, in the order they were sent, as
+    /// [`FortressEvent::ReliableMessage`]. Messages sent before the peer is
+    /// synchronized go out once it is. The channel
+    /// sends after inputs, so it never delays them; under
+    /// [`ProtocolConfig::max_packets_per_poll`] or
+    /// [`ProtocolConfig::max_bytes_per_second`] it only uses budget the inputs
+    /// leave over.
+    ///
+    /// All handles at one address share the peer's channel.
+    ///
+    /// # Errors
+    /// - Returns [`InvalidRequestKind::NotRemotePlayerOrSpectator`] if `handle`
+    ///   is not a remote player or spectator.
+    /// - Returns [`InvalidRequestKind::ReliablePayloadTooLarge`] if `payload`
+    ///   is longer than [`ProtocolConfig::max_reliable_payload`].
+    /// - Returns [`InvalidRequestKind::ReliableWindowFull`] if
+    ///   [`ProtocolConfig::reliable_window`] messages to the peer are still
+    ///   unacknowledged.
+    pub fn send_reliable(
+        &mut self,
+        handle: PlayerHandle,
+        payload: Vec<u8>,
+    ) -> Result<(), FortressError> {
+        let _violation_scope = self.scoped_violation_observer();
+        let endpoint = match self.player_reg.handles.get(&handle) {
+            Some(PlayerType::Remote(addr)) => self.player_reg.remotes.get_mut(addr),
+            Some(PlayerType::Spectator(addr)) => self.player_reg.spectators.get_mut(addr),
+            _ => {
+                return Err(InvalidRequestKind::NotRemotePlayerOrSpectator { handle }.into());
+            },
+        };
+        match endpoint {
+            Some(endpoint) => endpoint.queue_reliable(payload),
+            None => Err(FortressError::InternalErrorStructured {
+                kind: InternalErrorKind::EndpointNotFoundForRemote {
+                    player_handle: handle,
+                },
+            }),
+        }
+    }
+
     /// Diagnostics/testing surface (hidden; **not** part of the stable public
     /// API, like [`__internal`](crate::__internal)): renders this session's
     /// per-slot local connect status and every remote endpoint's gossiped view
@@ -8100,11 +10201,38 @@ pub fn diagnostic_player_retained_input_range(
     }
 
     /// Returns the maximum prediction window of a session.
+    ///
+    /// The session keeps `max_prediction + 1` saved states, see
+    /// [`saved_frames`](Self::saved_frames).
     #[must_use]
     pub fn max_prediction(&self) -> usize {
         self.max_prediction
     }
 
+    /// Returns the deepest rollback the session performs, in frames.
+    ///
+    /// Equals [`max_prediction`](Self::max_prediction) unless capped with
+    /// [`SessionBuilder::with_max_rollback_depth`](crate::SessionBuilder::with_max_rollback_depth).
+    #[must_use]
+    pub fn max_rollback_depth(&self) -> usize {
+        self.max_rollback_depth.unwrap_or(self.max_prediction)
+    }
+
+    /// Returns what the saved-state ring currently holds, oldest frame first.
+    ///
+    /// One [`SavedFrameInfo`] per slot that has been saved at least once:
+    /// the frame, whether the save carried a state, and its checksum. Meant
+    /// for debug overlays showing save cadence; each cell is locked only long
+    /// enough to read this metadata, and no state is cloned. Slots are reused
+    /// round-robin, so there are at most
+    /// <code>[max_prediction](Self::max_prediction) + 1</code> entries, and with
+    /// [`SaveMode::Sparse`] older frames stay listed until overwritten. With
+    /// [`SaveMode::Pyramid`], frames whose state a rollback dropped are not listed.
+    #[must_use]
+    pub fn saved_frames(&self) -> Vec<SavedFrameInfo> {
+        self.sync_layer.saved_frames()
+    }
+
     /// Returns true if the session is running in lockstep mode.
     ///
     /// In lockstep mode, a session will only advance if the current frame has inputs confirmed from
@@ -8120,6 +10248,47 @@ pub fn current_state(&self) -> SessionState {
         self.state
     }
 
+    /// Blocks until the session is [`SessionState::Running`], polling every
+    /// `poll_interval`, or fails once `timeout` has elapsed.
+    ///
+    /// Meant for CLI tools and tests; a game should keep calling
+    /// [`poll_remote_clients`](Self::poll_remote_clients) from its own loop.
+    /// Events are not drained: everything raised while waiting, including
+    /// [`FortressEvent::Synchronized`], stays queued for [`events`](Self::events).
+    ///
+    /// Not available on wasm targets.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SyncWaitError`] with the handshake progress of every remote
+    /// player and spectator endpoint if the session is not running in time.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait_for_synchronization(
+        &mut self,
+        timeout: web_time::Duration,
+        poll_interval: web_time::Duration,
+    ) -> Result<(), SyncWaitError<T::Address>> {
+        wait_until_running(
+            self,
+            timeout,
+            poll_interval,
+            |session| {
+                session.poll_remote_clients();
+                session.state == SessionState::Running
+            },
+            |session| {
+                // alloc-bound: one entry per remote endpoint, on the error path only.
+                session
+                    .player_reg
+                    .remotes
+                    .values()
+                    .chain(session.player_reg.spectators.values())
+                    .map(UdpProtocol::sync_progress)
+                    .collect()
+            },
+        )
+    }
+
     /// Returns all events that happened since last queried for events. When an
     /// event arrives at capacity, the oldest queued routine progress/advisory
     /// event is discarded first. If only durable events are queued, an incoming
@@ -8129,7 +10298,18 @@ pub fn events(&mut self) -> EventDrain<'_, T> {
         // Draining starts a new overflow episode: re-arm the warning emitted by
         // `record_event_discard` when a bounded enqueue discards an event.
         self.event_discard_warned = false;
-        EventDrain::from_drain(self.event_queue.drain(..))
+        EventDrain::from_timestamped_drain(self.event_queue.drain(..))
+    }
+
+    /// Like [`events`](Self::events), but each event carries the session's
+    /// [`current_frame`](Self::current_frame) and the milliseconds since the
+    /// session was created when it was generated.
+    ///
+    /// Both methods drain the same queue, so use one or the other.
+    #[must_use = "events should be handled to react to session state changes"]
+    pub fn events_timestamped(&mut self) -> TimestampedEventDrain<'_, T> {
+        self.event_discard_warned = false;
+        TimestampedEventDrain::from_drain(self.event_queue.drain(..))
     }
 
     /// Returns a snapshot of this session's cumulative [`SessionMetrics`].
@@ -8156,7 +10336,102 @@ pub fn events(&mut self) -> EventDrain<'_, T> {
     /// }
     /// ```
     pub fn metrics(&self) -> SessionMetrics {
-        self.metrics
+        SessionMetrics {
+            decode_failures: self.socket.decode_failures(),
+            ..self.metrics
+        }
+    }
+
+    /// Returns how well the session kept up with its configured frame rate
+    /// since it reached [`SessionState::Running`], or `None` before that.
+    ///
+    /// The values are kept up to date as the session runs, so this is cheap
+    /// to call every frame. Once the session reaches its frame limit (see
+    /// [`FortressEvent::SessionFrameLimitReached`]) they stop changing.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Some(health) = session.session_health() {
+    ///     println!(
+    ///         "{:.1} fps over {:?}, longest hitch {}ms, {} starved advances",
+    ///         health.achieved_fps,
+    ///         health.running_duration,
+    ///         health.longest_advance_gap_ms,
+    ///         health.starved_frames,
+    ///     );
+    /// }
+    /// ```
+    #[must_use]
+    pub fn session_health(&self) -> Option<SessionHealth> {
+        self.health.health(self.now())
+    }
+
+    /// Returns an estimate of the memory held by the session's buffers, per
+    /// component: saved states, input queues, every endpoint's protocol
+    /// buffers, the checksum history and the event queue.
+    ///
+    /// Saved states are sized with
+    /// [`SessionBuilder::with_state_size_hint`](crate::SessionBuilder::with_state_size_hint)
+    /// and only counted without it; everything else is measured. See
+    /// [`MemoryReport`] for what each component covers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fortress_rollback::{Config, PlayerHandle, PlayerType, SessionBuilder, UdpNonBlockingSocket};
+    /// # use std::net::SocketAddr;
+    /// # struct MyConfig;
+    /// # impl Config for MyConfig {
+    /// #     type Input = u8;
+    /// #     type State = Vec<u8>;
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let socket = UdpNonBlockingSocket::bind_to_port(0)?;
+    /// let session = SessionBuilder::<MyConfig>::new()
+    ///     .with_state_size_hint(64 * 1024)
+    ///     .add_player(PlayerType::Local, PlayerHandle::new(0))?
+    ///     .add_player(PlayerType::Remote("127.0.0.1:7001".parse()?), PlayerHandle::new(1))?
+    ///     .start_p2p_session(socket)?;
+    /// let report = session.memory_report();
+    /// assert_eq!(report.saved_states.count, 0);
+    /// println!("{} bytes", report.total_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn memory_report(&self) -> MemoryReport {
+        let saved_states = self
+            .sync_layer
+            .saved_frames()
+            .iter()
+            .filter(|info| info.has_state)
+            .count();
+        let mut report = MemoryReport {
+            saved_states: MemoryUsage::of_hint(saved_states, self.state_size_hint),
+            input_queues: MemoryUsage::of(
+                self.sync_layer.input_queue_slots(),
+                std::mem::size_of::<PlayerInput<T::Input>>(),
+            ),
+            checksum_history: MemoryUsage::of(
+                self.local_checksum_history.len(),
+                std::mem::size_of::<(Frame, u128)>(),
+            ),
+            event_queue: MemoryUsage::of(
+                self.event_queue.len(),
+                std::mem::size_of::<TimestampedEvent<T>>(),
+            ),
+            ..MemoryReport::default()
+        };
+        for endpoint in self
+            .player_reg
+            .remotes
+            .values()
+            .chain(self.player_reg.spectators.values())
+        {
+            endpoint.add_memory_usage(&mut report);
+        }
+        report
     }
 
     /// Returns current bounded-container lengths for integration diagnostics.
@@ -8258,8 +10533,9 @@ pub fn is_recording(&self) -> bool {
     /// ```
     ///
     /// [`SessionBuilder::with_recording`]: crate::SessionBuilder::with_recording
-    pub fn into_replay(self) -> FortressResult<Replay<T::Input>> {
+    pub fn into_replay(mut self) -> FortressResult<Replay<T::Input>> {
         self.recording
+            .take()
             .map(ReplayRecorder::into_replay)
             .ok_or_else(|| {
                 InvalidRequestKind::NotSupported {
@@ -8729,6 +11005,78 @@ pub fn frames_ahead(&self) -> i32 {
         self.frames_ahead
     }
 
+    /// Returns the rolling average frame advantage against each connected
+    /// remote player, sorted by handle.
+    ///
+    /// This is the per-endpoint breakdown behind [`frames_ahead`], using the
+    /// same sign convention: a positive value means the local session is ahead
+    /// of that player, a negative value means that player is ahead. When a
+    /// [`FortressEvent::WaitRecommendation`] fires, the players with the
+    /// largest values are the ones being waited for, so a game can show
+    /// "waiting for Player 3" instead of a generic stall message.
+    ///
+    /// Players sharing one endpoint (several players behind one address)
+    /// report the same value. Disconnected players and spectators are not
+    /// listed. Values update once per [`advance_frame`].
+    ///
+    /// [`frames_ahead`]: Self::frames_ahead
+    /// [`advance_frame`]: Self::advance_frame
+    #[must_use]
+    pub fn frame_advantages(&self) -> Vec<(PlayerHandle, i32)> {
+        let mut advantages = Vec::new();
+        for endpoint in self.player_reg.remotes.values() {
+            let advantage = endpoint.average_frame_advantage();
+            for &handle in endpoint.handles().iter() {
+                if self
+                    .local_connect_status
+                    .get(handle.as_usize())
+                    .is_some_and(|status| !status.disconnected)
+                {
+                    advantages.push((handle, advantage));
+                }
+            }
+        }
+        advantages.sort_unstable_by_key(|&(handle, _)| handle);
+        advantages
+    }
+
+    /// Returns how many frames the session would currently recommend skipping,
+    /// or 0 if it would not recommend waiting at all.
+    ///
+    /// This is the `skip_frames` computation behind
+    /// [`FortressEvent::WaitRecommendation`], evaluated on demand: the event is
+    /// rate-limited, while this can be polled every frame to drive a smooth
+    /// pacing indicator. It is the largest value in [`frame_advantages`] once
+    /// that reaches the recommendation threshold.
+    ///
+    /// [`frame_advantages`]: Self::frame_advantages
+    #[must_use]
+    pub fn recommended_skip_frames(&self) -> u32 {
+        recommended_skip_frames(self.max_frame_advantage())
+    }
+
+    /// Returns `true` if the last [`advance_frame`](Self::advance_frame) call
+    /// skipped its simulation to honor a wait recommendation.
+    ///
+    /// Only happens with
+    /// [`SessionBuilder::with_auto_frame_skip`](crate::SessionBuilder::with_auto_frame_skip).
+    /// A skipped call returns an empty request list; this tells it apart
+    /// from other calls that simulate nothing, such as a hot-join host pause.
+    #[must_use]
+    pub fn skipped_this_call(&self) -> bool {
+        self.skipped_this_call
+    }
+
+    /// Returns how many upcoming [`advance_frame`](Self::advance_frame) calls
+    /// will be skipped to honor the last wait recommendation.
+    ///
+    /// Always 0 without
+    /// [`SessionBuilder::with_auto_frame_skip`](crate::SessionBuilder::with_auto_frame_skip).
+    #[must_use]
+    pub fn pending_skip_frames(&self) -> u32 {
+        self.pending_skip_frames
+    }
+
     /// Adjusts the input delay for a local player at runtime.
     ///
     /// This enables hybrid delay+rollback: a small fixed delay (1-3 frames)
@@ -8744,9 +11092,13 @@ pub fn frames_ahead(&self) -> i32 {
     ///   replicated frames are pushed onto every remote endpoint's
     ///   pending-output buffer so the remote peer's input sequence remains
     ///   strictly monotonic.
-    /// - **Decreasing** the delay mid-session is **not** supported; doing so
-    ///   would require dropping inputs that may already have been sent to
-    ///   remote peers. An error is returned in that case.
+    /// - **Decreasing** the delay mid-session is only supported on a session
+    ///   without remote endpoints or spectators (all players local). The
+    ///   newest queued inputs the smaller delay overlaps are dropped and the
+    ///   next inputs take their frames; see
+    ///   [`InputQueue::set_frame_delay`](crate::__internal::InputQueue::set_frame_delay).
+    ///   With remote peers or spectators those inputs have already been
+    ///   sent, so an error is returned instead.
     /// - Mid-session increases require **exactly one local player on this
     ///   peer**. The protocol bundles all local players' inputs into a single
     ///   packet per frame; with multiple local players, synthesizing
@@ -8787,9 +11139,13 @@ pub fn frames_ahead(&self) -> i32 {
     ///   local player.
     /// - Returns [`FortressError`] (`FrameDelayTooLarge`) if `delay` exceeds
     ///   `queue_length - 1`.
+    /// - Returns [`FortressError`] (`InputDelayBeyondPrediction`) if `delay`
+    ///   is not below a non-zero prediction window and the session was not
+    ///   built with
+    ///   [`with_input_delay_beyond_prediction`](crate::SessionBuilder::with_input_delay_beyond_prediction).
     /// - Returns [`FortressError`] (`InputDelayDecreaseUnsupported`) if
-    ///   `delay` is less than the current delay and inputs have already been
-    ///   added.
+    ///   `delay` is less than the current delay, inputs have already been
+    ///   added and the session has remote endpoints or spectators.
     /// - Returns [`FortressError`]
     ///   (`InputDelayMidSessionMultiLocalUnsupported`) if attempting to
     ///   increase delay mid-session with more than one local player.
@@ -8905,10 +11261,33 @@ pub fn set_input_delay(
             }
             .into());
         }
+        if self.max_prediction > 0
+            && delay >= self.max_prediction
+            && !self.input_delay_beyond_prediction
+        {
+            return Err(InvalidRequestKind::InputDelayBeyondPrediction {
+                delay,
+                max_prediction: self.max_prediction,
+            }
+            .into());
+        }
 
         let current_delay = self.sync_layer.frame_delay(player_handle)?;
         let prev_last_added = self.sync_layer.last_added_frame(player_handle)?;
 
+        // A mid-session decrease drops the newest queued inputs, which every
+        // remote endpoint or spectator has already been sent.
+        let mid_session_decrease = !prev_last_added.is_null() && delay < current_delay;
+        if mid_session_decrease
+            && (!self.player_reg.remotes.is_empty() || !self.player_reg.spectators.is_empty())
+        {
+            return Err(InvalidRequestKind::InputDelayDecreaseUnsupported {
+                current: current_delay,
+                requested: delay,
+            }
+            .into());
+        }
+
         // Detect mid-session increase: there are inputs in the queue and the
         // requested delay is strictly greater than the current delay. Only in
         // this case do we need to coordinate gap-fill on the protocol layer;
@@ -8951,6 +11330,15 @@ pub fn set_input_delay(
         // advanced by `delta` if a mid-session gap-fill happened.
         self.sync_layer.set_frame_delay(player_handle, delay)?;
 
+        if mid_session_decrease {
+            let new_last_added = self.sync_layer.last_added_frame(player_handle)?;
+            self.local_connect_status
+                .get_mut(player_handle.as_usize())
+                .ok_or(FortressError::InternalErrorStructured {
+                    kind: InternalErrorKind::ConnectionStatusIndexOutOfBounds { player_handle },
+                })?
+                .last_frame = new_last_added;
+        }
         if !mid_session_increase {
             return Ok(());
         }
@@ -8961,10 +11349,13 @@ pub fn set_input_delay(
         // endpoint's pending_output. We pre-validated capacity above.
         while frame <= new_last_added {
             let player_input = self.sync_layer.confirmed_input(player_handle, frame)?;
-            let mut inputs = std::collections::BTreeMap::new();
+            let mut inputs = VecMap::new();
             inputs.insert(player_handle, player_input);
             for endpoint in self.player_reg.remotes.values_mut() {
-                endpoint.enqueue_replicated_input(&inputs);
+                // a relaying endpoint picks the gap frames up with the relayed inputs
+                if endpoint.relayed_handles().is_empty() {
+                    endpoint.enqueue_replicated_input(&inputs);
+                }
             }
             frame = safe_frame_add!(frame, 1, "P2PSession::set_input_delay gap fill loop");
         }
@@ -8988,9 +11379,12 @@ pub fn set_input_delay(
         // on the wire promptly, just as they would have if produced by a
         // regular advance_frame.
         for endpoint in self.player_reg.remotes.values_mut() {
-            endpoint.flush_pending_output(&self.local_connect_status);
-            endpoint.send_all_messages(&mut self.socket);
+            if endpoint.relayed_handles().is_empty() {
+                endpoint.flush_pending_output(&self.local_connect_status);
+                endpoint.send_all_messages(&mut self.socket);
+            }
         }
+        self.send_relayed_inputs();
 
         Ok(())
     }
@@ -9324,6 +11718,182 @@ pub fn last_verified_frame(&self) -> Option<Frame> {
         }
     }
 
+    /// Returns how many frames desync detection currently trails the confirmed
+    /// frame.
+    ///
+    /// This is the distance from the last confirmed frame back to the newest
+    /// frame whose checksums have been compared with **every** currently
+    /// connected remote peer, whether or not they matched. Checksums are
+    /// reported for the same frames on every peer (the multiples of the
+    /// [`DesyncDetection::On`] interval), and a pair is compared as soon as both
+    /// reports exist, so in steady play the lag stays within about one
+    /// interval plus one round trip.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(frames)` - The lag in frames. `0` when the newest compared frame
+    ///   is the confirmed frame.
+    /// * `None` - Desync detection is off, there are no connected remote peers,
+    ///   or some connected peer has not had a checksum compared yet.
+    ///
+    /// Disconnected slots and reserved-but-unjoined hot-join endpoints are
+    /// excluded, as in [`last_verified_frame`](Self::last_verified_frame).
+    #[must_use]
+    pub fn desync_detection_lag(&self) -> Option<u32> {
+        if self.desync_detection == DesyncDetection::Off {
+            return None;
+        }
+        let mut newest_compared: Option<Frame> = None;
+        for remote in self.player_reg.remotes.values() {
+            if !self.remote_is_connected(remote) {
+                continue;
+            }
+            let frame = remote.last_compared_checksum_frame?;
+            newest_compared = Some(match newest_compared {
+                Some(current) => std::cmp::min(current, frame),
+                None => frame,
+            });
+        }
+        let lag = self
+            .sync_layer
+            .last_confirmed_frame()
+            .as_i32()
+            .saturating_sub(newest_compared?.as_i32());
+        Some(u32::try_from(lag).unwrap_or(0))
+    }
+
+    /// Returns the session's [`PlayerRoster`]: the stable ids registered with
+    /// [`SessionBuilder::add_player_with_id`](crate::SessionBuilder::add_player_with_id),
+    /// with each entry's connection flag taken from the session's current state.
+    ///
+    /// Each id stays on its handle for the whole session. A remote player's
+    /// entry is disconnected once the player is disconnected, removed, or
+    /// dropped, and connected again when a hot-joiner fills the slot; reserved
+    /// hot-join slots start disconnected. A spectator's entry follows its
+    /// endpoint, and local players are always connected. Handles added without
+    /// an id are not listed.
+    ///
+    /// The roster is built on each call, so keep the returned value rather than
+    /// calling this in a tight loop.
+    #[must_use]
+    pub fn roster(&self) -> PlayerRoster {
+        // alloc-bound: one entry per handle registered with an id.
+        let mut roster = self.roster.clone();
+        for (&handle, player_type) in &self.player_reg.handles {
+            let connected = match player_type {
+                PlayerType::Local => true,
+                PlayerType::Remote(_) => self
+                    .local_connect_status
+                    .get(handle.as_usize())
+                    .is_some_and(|status| !status.disconnected),
+                PlayerType::Spectator(addr) => self
+                    .player_reg
+                    .spectators
+                    .get(addr)
+                    .is_some_and(|endpoint| !endpoint.is_disconnected()),
+            };
+            roster.set_connected(handle, connected);
+        }
+        roster
+    }
+
+    /// Returns which input mismatch triggered the most recent misprediction
+    /// rollback: the earliest mispredicted frame, the players mispredicted at
+    /// that frame, and their predicted and confirmed inputs there.
+    ///
+    /// The cause is kept until the next such rollback, so read it after each
+    /// [`advance_frame`](Self::advance_frame) to see every one. Returns `None`
+    /// until the session first rolls back. Rollbacks that only restore a
+    /// sparse-saving checkpoint do not replace it.
+    #[must_use]
+    pub fn last_rollback_cause(&self) -> Option<&RollbackCause<T::Input>> {
+        self.last_rollback_cause.as_ref()
+    }
+
+    /// Returns the frames re-simulated by the requests of the latest
+    /// [`advance_frame`](Self::advance_frame) call, with the players whose
+    /// input changed in each, for games that re-run only the subsystems a
+    /// misprediction affected.
+    ///
+    /// Valid while fulfilling that call's requests: the next call replaces or
+    /// clears it. Returns `None` when the call re-simulated nothing, and always
+    /// unless [`SessionBuilder::with_rollback_context`](crate::SessionBuilder::with_rollback_context)
+    /// is set.
+    #[must_use]
+    pub fn current_rollback_context(&self) -> Option<&RollbackContext> {
+        self.rollback_context
+            .as_ref()
+            .and_then(RollbackTracker::context)
+    }
+
+    /// Returns how each recently simulated frame was simulated, oldest first.
+    ///
+    /// Each frame's [`FrameFlags`] record whether its first simulation had
+    /// every input confirmed or needed a prediction, and whether a rollback
+    /// later loaded it or resimulated it. Rollbacks only add flags. The
+    /// timeline covers the most recent frames up to the capacity set with
+    /// [`SessionBuilder::with_frame_timeline`](crate::SessionBuilder::with_frame_timeline),
+    /// and is empty unless that is set. A hot-join snapshot load restarts it.
+    pub fn frame_timeline(&self) -> impl Iterator<Item = (Frame, FrameFlags)> + '_ {
+        self.frame_timeline.iter().flat_map(FrameTimeline::iter)
+    }
+
+    /// Returns the recorded timeline as a Chrome trace-event JSON document,
+    /// for `chrome://tracing` or Perfetto.
+    ///
+    /// Every `advance_frame` (args: the frame reached and the number of
+    /// requests, 0 on an error), network poll (the number of messages
+    /// received) and completed rollback (the first incorrect frame and the
+    /// frames resimulated) is a complete (`"ph":"X"`) event with its duration
+    /// in microseconds; polls inside an advance nest under it. Every
+    /// [`FortressEvent`] is an instant (`"ph":"i"`) event named after its
+    /// [`EventKind`](crate::metrics::EventKind), with the current frame and,
+    /// for `WaitRecommendation`, `skip_frames`. Timestamps are microseconds
+    /// on the protocol clock since the session was created.
+    ///
+    /// Holds the records kept by the ring set with
+    /// [`SessionBuilder::with_trace_recorder`](crate::SessionBuilder::with_trace_recorder),
+    /// oldest first; `otherData.dropped_events` counts the older ones it
+    /// overwrote. Without a recorder the document has no events. The ring is
+    /// not cleared.
+    #[must_use]
+    pub fn export_trace_json(&self) -> String {
+        self.trace_recorder
+            .as_ref()
+            .map_or_else(trace_recorder::empty_trace_json, TraceRecorder::export_json)
+    }
+
+    /// Returns the recent measurements of how long local input frames took to
+    /// become confirmed, oldest first.
+    ///
+    /// A frame is measured from the [`advance_frame`](Self::advance_frame)
+    /// call that adds its local input to the first call that finds it
+    /// confirmed, so the resolution is one call. The measurements cover the
+    /// most recent frames up to the capacity set with
+    /// [`SessionBuilder::with_confirmation_latency`](crate::SessionBuilder::with_confirmation_latency),
+    /// and are empty unless that is set.
+    pub fn confirmation_latency_samples(
+        &self,
+    ) -> impl Iterator<Item = ConfirmationLatencySample> + '_ {
+        self.confirmation_latency
+            .iter()
+            .flat_map(ConfirmationLatency::samples)
+    }
+
+    /// Summarizes [`confirmation_latency_samples`](Self::confirmation_latency_samples)
+    /// as percentiles in milliseconds and frames.
+    ///
+    /// With matching input delays on every peer, a frame's inputs are all
+    /// added at about the same moment, so the median tracks the one-way
+    /// latency from the slowest peer plus up to one frame of polling. Sorts a
+    /// copy of the samples, so call it for display rather than every frame.
+    #[must_use]
+    pub fn confirmation_latency_stats(&self) -> LatencyStats {
+        self.confirmation_latency
+            .as_ref()
+            .map_or_else(LatencyStats::default, ConfirmationLatency::stats)
+    }
+
     /// Returns `true` if a remote endpoint counts toward mesh-wide verification.
     ///
     /// A remote is connected for this purpose when it is not a reserved
@@ -9477,7 +12047,9 @@ fn remote_disconnect_snapshot(
                 .ok_or(FortressError::InternalErrorStructured {
                     kind: InternalErrorKind::EndpointNotFoundForRemote { player_handle },
                 })?;
-        let mut handles: Vec<PlayerHandle> = endpoint.handles().iter().copied().collect();
+        let mut handles: Vec<PlayerHandle> = self
+            .relayed_disconnect(last_frame_overrides)
+            .unwrap_or_else(|| endpoint.handles().iter().copied().collect());
         if handles.is_empty() {
             report_violation!(
                 ViolationSeverity::Warning,
@@ -9517,6 +12089,21 @@ fn remote_disconnect_snapshot(
         Ok((addr, handles, earliest_last_frame))
     }
 
+    /// Returns the handles a disconnect is narrowed to when it only concerns
+    /// players reached through a relay: the relay itself and the link to it
+    /// stay up, so the relay's own players are not disconnected with them.
+    fn relayed_disconnect(
+        &self,
+        last_frame_overrides: Option<&BTreeMap<PlayerHandle, Frame>>,
+    ) -> Option<Vec<PlayerHandle>> {
+        let overrides = last_frame_overrides?;
+        let relayed_only = !overrides.is_empty()
+            && overrides
+                .keys()
+                .all(|handle| self.relayed_players.contains(handle));
+        relayed_only.then(|| overrides.keys().copied().collect())
+    }
+
     fn validate_graceful_drop_handles(
         &self,
         handles: &[PlayerHandle],
@@ -9640,7 +12227,9 @@ fn disconnect_player_with_policy_tracked(
         // Goodbye there would cause reciprocal/cascaded drops in an N-peer mesh.
         // Reason 0 is the v1 "user requested" reason; receivers intentionally
         // treat unknown future reason values as the same idempotent closure.
-        if notification == RemoteDisconnectNotification::UserRequested {
+        if notification == RemoteDisconnectNotification::UserRequested
+            && self.relayed_disconnect(last_frame_overrides).is_none()
+        {
             let Some(endpoint) = self.player_reg.remotes.get_mut(&addr) else {
                 let error = FortressError::InternalErrorStructured {
                     kind: InternalErrorKind::EndpointNotFoundForRemote { player_handle },
@@ -9823,6 +12412,7 @@ fn disconnect_player_at_frames(
             return;
         };
 
+        let relayed = self.relayed_disconnect(last_frame_overrides);
         match player_type {
             PlayerType::Remote(addr) => {
                 let Some(endpoint) = self.player_reg.remotes.get_mut(addr) else {
@@ -9837,9 +12427,14 @@ fn disconnect_player_at_frames(
                 // Collect the affected handles first so the per-handle status
                 // mutation below does not hold a borrow of `endpoint` across the
                 // `self.sync_layer` borrow needed for the frozen-value re-roll.
-                let affected_handles: Vec<PlayerHandle> =
-                    endpoint.handles().iter().copied().collect();
-                endpoint.disconnect_remote();
+                // Relayed players leave the relay's endpoint connected.
+                let affected_handles: Vec<PlayerHandle> = match relayed {
+                    Some(handles) => handles,
+                    None => {
+                        endpoint.disconnect_remote();
+                        endpoint.handles().iter().copied().collect()
+                    },
+                };
 
                 // mark the affected players as disconnected
                 for &handle in &affected_handles {
@@ -10055,8 +12650,14 @@ fn check_initial_sync(&mut self) {
             }
         }
 
+        // A rejoin only runs from the state every remote peer confirmed.
+        if !self.verify_rejoin_state() {
+            return;
+        }
+
         // everyone is synchronized, so we can change state and accept input
         self.state = SessionState::Running;
+        self.health.start(self.now());
         // Defensive hot-join metrics coverage. Durable fail-closed joiners are
         // rejected above and the reachable joiner transitions record at their
         // snapshot-apply sites. Keep this idempotent call so a future valid
@@ -10066,8 +12667,218 @@ fn check_initial_sync(&mut self) {
         self.record_hot_join_activation();
     }
 
+    /// Asks every remote player for its checksum of the rejoin frame and
+    /// compares the answers with the expected checksum. Returns `true` once
+    /// every player answered with a match, or if there is nothing to verify.
+    /// A mismatch is reported once and keeps the session from running.
+    fn verify_rejoin_state(&mut self) -> bool {
+        let Some(mut check) = self.rejoin_check else {
+            return true;
+        };
+        let mut verified = true;
+        let mut mismatch = None;
+        for endpoint in self.player_reg.remotes.values_mut() {
+            // Observers hold no game state.
+            if endpoint.handles().is_empty() {
+                continue;
+            }
+            #[cfg(feature = "hot-join")]
+            if self.hot_join.endpoint_is_reserved(endpoint) {
+                continue;
+            }
+            endpoint.request_checksum(check.frame);
+            match endpoint.requested_checksum(check.frame) {
+                Some(actual) if actual == check.expected => (),
+                Some(actual) => {
+                    verified = false;
+                    mismatch.get_or_insert(actual);
+                },
+                None => verified = false,
+            }
+        }
+        if let Some(actual) = mismatch {
+            if !check.mismatch_reported {
+                check.mismatch_reported = true;
+                self.rejoin_check = Some(check);
+                self.enqueue_event(FortressEvent::RejoinStateMismatch {
+                    frame: check.frame,
+                    expected: check.expected,
+                    actual,
+                });
+            }
+        }
+        if verified {
+            self.rejoin_check = None;
+        }
+        verified
+    }
+
+    /// Answers each remote's pending checksum request once this session
+    /// holds the final state at the requested frame, and drives the rejoin
+    /// check while it waits for the answers to its own.
+    ///
+    /// A state is final once every input before it is confirmed. A frame this
+    /// session no longer holds a checksum for is never answered.
+    fn poll_checksum_requests(&mut self) {
+        let final_frame = std::cmp::min(
+            self.sync_layer.last_confirmed_frame() + 1,
+            self.sync_layer.current_frame(),
+        );
+        for endpoint in self.player_reg.remotes.values_mut() {
+            let Some(frame) = endpoint.pending_checksum_request() else {
+                continue;
+            };
+            if frame > final_frame {
+                continue;
+            }
+            let checksum = self
+                .local_checksum_history
+                .get(&frame)
+                .copied()
+                .or_else(|| {
+                    self.sync_layer
+                        .saved_state_by_frame(frame)
+                        .and_then(|cell| cell.checksum())
+                });
+            if let Some(checksum) = checksum {
+                endpoint.answer_checksum_request(checksum);
+            }
+        }
+
+        if self.rejoin_check.is_some() && self.state == SessionState::Synchronizing {
+            self.check_initial_sync();
+        }
+    }
+
+    /// Starts the advance-stall timer once the session runs and, once the
+    /// session went `threshold` without advancing, reports the stall locally
+    /// and to every remote peer.
+    fn poll_advance_stall(&mut self) {
+        let Some(mut watch) = self.advance_stall else {
+            return;
+        };
+        if self.state != SessionState::Running {
+            watch.last_advance = None;
+            self.advance_stall = Some(watch);
+            return;
+        }
+        let now = self.now();
+        let last_advance = *watch.last_advance.get_or_insert(now);
+        let since = now.saturating_duration_since(last_advance);
+        if !watch.stalled && since >= watch.threshold {
+            watch.stalled = true;
+            for endpoint in self.player_reg.remotes.values_mut() {
+                endpoint.set_advance_stalled(true);
+            }
+            self.enqueue_event(FortressEvent::LocalAdvanceStalled {
+                since_ms: since.as_millis(),
+            });
+        }
+        self.advance_stall = Some(watch);
+    }
+
+    /// Restarts the advance-stall timer, ending a reported stall.
+    fn note_advance(&mut self) {
+        let Some(mut watch) = self.advance_stall else {
+            return;
+        };
+        let now = self.now();
+        if watch.stalled {
+            watch.stalled = false;
+            for endpoint in self.player_reg.remotes.values_mut() {
+                endpoint.set_advance_stalled(false);
+            }
+            let stalled_ms = watch
+                .last_advance
+                .map_or(0, |last| now.saturating_duration_since(last).as_millis());
+            self.enqueue_event(FortressEvent::LocalAdvanceResumed { stalled_ms });
+        }
+        watch.last_advance = Some(now);
+        self.advance_stall = Some(watch);
+    }
+
+    /// Rolls back if a misprediction was detected, or otherwise continues an
+    /// outstanding capped resimulation.
+    fn repair_gamestate(
+        &mut self,
+        confirmed_frame: Frame,
+        requests: &mut RequestVec<T>,
+    ) -> Result<(), FortressError> {
+        // the disconnect frame indicates if a rollback is necessary due to a previously
+        // disconnected player (whose input would have been incorrectly predicted).
+        let first_incorrect = self
+            .sync_layer
+            .check_simulation_consistency(self.disconnect_frame);
+        // if we have an incorrect frame, then we need to rollback
+        if first_incorrect != Frame::NULL {
+            self.metrics.record_prediction_misses(
+                self.sync_layer
+                    .count_players_with_incorrect_predictions(self.disconnect_frame),
+            );
+            if let Some(telemetry) = &self.telemetry {
+                for (player, frame) in self
+                    .sync_layer
+                    .players_with_incorrect_predictions(self.disconnect_frame)
+                {
+                    telemetry.on_prediction_miss(player, frame);
+                }
+            }
+            if self.rollback_exceeds_cap(first_incorrect) {
+                return self.resimulate_frames(confirmed_frame, requests);
+            }
+            // Capture before `adjust_gamestate` resets the prediction markers.
+            let mut cause = self
+                .last_rollback_cause
+                .take()
+                .or_else(|| self.spare_rollback_cause.take())
+                .unwrap_or_else(RollbackCause::new);
+            self.sync_layer
+                .fill_rollback_cause(first_incorrect, &mut cause);
+            self.last_rollback_cause = Some(cause);
+            self.adjust_gamestate(first_incorrect, confirmed_frame, requests)?;
+            self.disconnect_frame = Frame::NULL;
+        } else {
+            self.resimulate_frames(confirmed_frame, requests)?;
+        }
+        Ok(())
+    }
+
+    /// Whether correcting a misprediction at `first_incorrect` would re-simulate
+    /// more frames than [`max_rollback_depth`](Self::max_rollback_depth). If
+    /// so, the corrected inputs are accepted without a rollback: the
+    /// prediction markers are reset and [`FortressEvent::RollbackTooDeep`] is
+    /// emitted.
+    fn rollback_exceeds_cap(&mut self, first_incorrect: Frame) -> bool {
+        let Some(cap) = self.max_rollback_depth else {
+            return false;
+        };
+        let current_frame = if self.resimulation_target.is_null() {
+            self.sync_layer.current_frame()
+        } else {
+            self.resimulation_target
+        };
+        let required_depth = usize::try_from(current_frame - first_incorrect).unwrap_or(0);
+        if required_depth <= cap {
+            return false;
+        }
+        debug!(
+            "Skipping rollback to {}: depth {} exceeds max_rollback_depth {}",
+            first_incorrect, required_depth, cap
+        );
+        self.sync_layer.reset_prediction();
+        self.disconnect_frame = Frame::NULL;
+        self.enqueue_event(FortressEvent::RollbackTooDeep {
+            required_depth,
+            cap,
+        });
+        true
+    }
+
     /// Roll back to `min_confirmed` frame and resimulate the game with most up-to-date input data.
     ///
+    /// If a capped resimulation is outstanding, the rollback resimulates back up
+    /// to the same target instead of the sync layer's (trailing) current frame.
+    ///
     /// # Errors
     /// Returns `FortressError::InvalidFrame` if the frame to load is invalid.
     fn adjust_gamestate(
@@ -10076,7 +12887,41 @@ fn adjust_gamestate(
         min_confirmed: Frame,
         requests: &mut RequestVec<T>,
     ) -> Result<(), FortressError> {
-        let current_frame = self.sync_layer.current_frame();
+        let started = self.trace_begin();
+        let rollbacks = self.metrics.rollback_count;
+        let result = self.adjust_gamestate_untraced(first_incorrect, min_confirmed, requests);
+        // Traced once the rollback completed; a capped one completes on a
+        // later call.
+        if self.metrics.rollback_count != rollbacks {
+            let depth = i64::try_from(self.resimulation_depth).unwrap_or(i64::MAX);
+            self.trace_span(
+                started,
+                TraceSpan::Rollback,
+                [i64::from(first_incorrect.as_i32()), depth],
+            );
+        }
+        result
+    }
+
+    /// The body of [`adjust_gamestate`](Self::adjust_gamestate).
+    fn adjust_gamestate_untraced(
+        &mut self,
+        first_incorrect: Frame,
+        min_confirmed: Frame,
+        requests: &mut RequestVec<T>,
+    ) -> Result<(), FortressError> {
+        let resimulated_frame = self.sync_layer.current_frame();
+        let current_frame = if self.resimulation_target.is_null() {
+            resimulated_frame
+        } else {
+            self.resimulation_target
+        };
+        if first_incorrect >= resimulated_frame && current_frame > resimulated_frame {
+            // Only frames the outstanding resimulation has not requested yet are
+            // affected; they will be requested with the corrected inputs anyway.
+            self.sync_layer.reset_prediction();
+            return self.resimulate_frames(min_confirmed, requests);
+        }
         // Floor of the live prediction window; computed up front because the
         // sparse earlier-checkpoint search below is bounded by it.
         let window_floor = safe_frame_sub!(
@@ -10087,42 +12932,7 @@ fn adjust_gamestate(
         .max(Frame::new(0));
 
         // determine the frame to load
-        let frame_to_load = if self.save_mode == SaveMode::Sparse {
-            // With sparse saving we normally roll back to the sole tracked saved
-            // state. But when a gossip-lowered disconnect frame drives
-            // `first_incorrect` BELOW `last_saved_frame`, that single saved state
-            // is CONTAMINATED: it embeds the dropped peer's pre-convergence
-            // (predicted/high-frame) inputs for the `[first_incorrect,
-            // last_saved_frame)` window, so re-simulating forward from it would
-            // keep this survivor's confirmed history out of sync with peers that
-            // re-simulated those frames with the dropped slot frozen at the
-            // agreed frame `F` (audit finding F7). Loading the contaminated state
-            // cannot fix its own embedded history. The circular buffer, however,
-            // usually still holds an EARLIER sparse checkpoint taken at or below
-            // `first_incorrect` (sparse saves at confirmed frames roughly every
-            // `max_prediction` frames, and the previous one commonly lands at the
-            // freeze frame, where the dropped slot's value is identical on every
-            // survivor). Prefer that earlier checkpoint so re-simulation restarts
-            // from an uncontaminated base and converges. If no such state is
-            // buffered, fall back to `last_saved_frame` (the gap is then a genuine
-            // unrecoverable residual, still flagged below).
-            let last_saved = self.sync_layer.last_saved_frame();
-            if last_saved > first_incorrect {
-                let earlier = self
-                    .sync_layer
-                    .newest_saved_frame_in_range(window_floor, first_incorrect);
-                if earlier.is_null() {
-                    last_saved
-                } else {
-                    earlier
-                }
-            } else {
-                last_saved
-            }
-        } else {
-            // otherwise, we will rollback to first_incorrect
-            first_incorrect
-        };
+        let frame_to_load = self.rollback_load_frame(first_incorrect, window_floor);
 
         // we should always load a frame that is before or exactly the first incorrect frame.
         // This check runs on the UN-clamped `frame_to_load` (sparse mode's resolved load frame,
@@ -10178,13 +12988,13 @@ fn adjust_gamestate(
         // This can happen when a misprediction is detected at the current frame
         // (e.g., at frame 0 when we haven't advanced yet). In this case, we just
         // need to reset predictions - the next frame advance will use the correct inputs.
-        if load_target >= current_frame {
+        if load_target >= resimulated_frame {
             debug!(
                 "Skipping rollback: load_target {} >= current_frame {} - resetting predictions only",
-                load_target, current_frame
+                load_target, resimulated_frame
             );
             self.sync_layer.reset_prediction();
-            return Ok(());
+            return self.resimulate_frames(min_confirmed, requests);
         }
 
         let count = current_frame - load_target;
@@ -10201,6 +13011,12 @@ fn adjust_gamestate(
             load_target, current_frame
         );
         requests.push(self.sync_layer.load_frame(load_target)?);
+        if let Some(timeline) = &mut self.frame_timeline {
+            timeline.mark(load_target, FrameFlags::ROLLBACK_TARGET);
+        }
+        if let Some(tracker) = &mut self.rollback_context {
+            tracker.loaded(load_target);
+        }
 
         // we are now at the desired frame
         let actual_frame = self.sync_layer.current_frame();
@@ -10214,6 +13030,95 @@ fn adjust_gamestate(
             );
         }
         self.sync_layer.reset_prediction();
+        self.resimulation_target = current_frame;
+        self.resimulation_depth = usize::try_from(count).unwrap_or(0);
+        self.resimulate_frames(min_confirmed, requests)
+    }
+
+    /// Returns the saved frame a rollback for a misprediction at
+    /// `first_incorrect` loads, before clamping to `window_floor`, the oldest
+    /// frame still in the prediction window.
+    fn rollback_load_frame(&self, first_incorrect: Frame, window_floor: Frame) -> Frame {
+        if self.save_mode == SaveMode::Sparse {
+            // With sparse saving we normally roll back to the sole tracked saved
+            // state. But when a gossip-lowered disconnect frame drives
+            // `first_incorrect` BELOW `last_saved_frame`, that single saved state
+            // is CONTAMINATED: it embeds the dropped peer's pre-convergence
+            // (predicted/high-frame) inputs for the `[first_incorrect,
+            // last_saved_frame)` window, so re-simulating forward from it would
+            // keep this survivor's confirmed history out of sync with peers that
+            // re-simulated those frames with the dropped slot frozen at the
+            // agreed frame `F` (audit finding F7). Loading the contaminated state
+            // cannot fix its own embedded history. The circular buffer, however,
+            // usually still holds an EARLIER sparse checkpoint taken at or below
+            // `first_incorrect` (sparse saves at confirmed frames roughly every
+            // `max_prediction` frames, and the previous one commonly lands at the
+            // freeze frame, where the dropped slot's value is identical on every
+            // survivor). Prefer that earlier checkpoint so re-simulation restarts
+            // from an uncontaminated base and converges. If no such state is
+            // buffered, fall back to `last_saved_frame` (the gap is then a genuine
+            // unrecoverable residual, still flagged below).
+            let last_saved = self.sync_layer.last_saved_frame();
+            if last_saved > first_incorrect {
+                let earlier = self
+                    .sync_layer
+                    .newest_saved_frame_in_range(window_floor, first_incorrect);
+                if earlier.is_null() {
+                    last_saved
+                } else {
+                    earlier
+                }
+            } else {
+                last_saved
+            }
+        } else if (matches!(self.save_mode, SaveMode::Pyramid { .. })
+            || self.sync_layer.diff_saves())
+            && first_incorrect < self.sync_layer.current_frame()
+            && self
+                .sync_layer
+                .saved_state_by_frame(first_incorrect)
+                .is_none()
+        {
+            // An earlier rollback resimulated past `first_incorrect` without saving it again (or
+            // diff saving discarded it as stale), so load the newest frame before it that is still
+            // saved and resimulate the difference.
+            let earlier = self
+                .sync_layer
+                .newest_saved_frame_in_range(window_floor, first_incorrect);
+            if earlier.is_null() {
+                first_incorrect
+            } else {
+                earlier
+            }
+        } else {
+            // otherwise, we will rollback to first_incorrect
+            first_incorrect
+        }
+    }
+
+    /// Steps the sync layer forward towards the outstanding resimulation target
+    /// with the most up-to-date inputs, emitting at most
+    /// `max_resimulation_per_advance` [`FortressRequest::AdvanceFrame`] requests.
+    ///
+    /// The state at the sync layer's current frame must already be saved (it was
+    /// just loaded, or saved at the end of the previous batch). A batch that stops
+    /// short of the target saves the state it reached (except in sparse mode) so a
+    /// later rollback into the resimulated range never loads the stale
+    /// pre-rollback state for that frame. Does nothing without an outstanding
+    /// target.
+    fn resimulate_frames(
+        &mut self,
+        min_confirmed: Frame,
+        requests: &mut RequestVec<T>,
+    ) -> Result<(), FortressError> {
+        let target = self.resimulation_target;
+        if target.is_null() {
+            return Ok(());
+        }
+        let remaining = usize::try_from(target - self.sync_layer.current_frame()).unwrap_or(0);
+        let count = self
+            .max_resimulation_per_advance
+            .map_or(remaining, |cap| remaining.min(cap));
 
         // step forward to the previous current state, but with updated inputs
         for i in 0..count {
@@ -10243,32 +13148,64 @@ fn adjust_gamestate(
                 if self.sync_layer.current_frame() == min_confirmed {
                     requests.push(self.sync_layer.save_current_state());
                 }
-            } else {
-                // without sparse saving, we save every state except the very first (just loaded that))
-                if i > 0 {
+            } else if i > 0 {
+                // without sparse saving, we save every state except the very first (just loaded
+                // or saved at the end of the previous batch); pyramid saving skips some of them
+                // and drops their state from before the rollback
+                let distance = u32::try_from(target - self.sync_layer.current_frame()).unwrap_or(0);
+                if self.save_mode.resaves_at_distance(distance) {
                     requests.push(self.sync_layer.save_current_state());
+                } else {
+                    self.sync_layer
+                        .discard_saved_state(self.sync_layer.current_frame());
                 }
             }
 
+            let previous_inputs = self
+                .previous_inputs
+                .record(self.sync_layer.current_frame(), &inputs)?;
+            if let Some(tracker) = &mut self.rollback_context {
+                tracker.record(self.sync_layer.current_frame(), &inputs, true)?;
+            }
+            if let Some(timeline) = &mut self.frame_timeline {
+                timeline.mark(self.sync_layer.current_frame(), FrameFlags::RESIMULATED);
+            }
             // advance the frame
             self.sync_layer.advance_frame();
-            requests.push(FortressRequest::AdvanceFrame { inputs });
+            requests.push(FortressRequest::AdvanceFrame {
+                inputs,
+                previous_inputs,
+            });
+        }
+
+        let final_frame = self.sync_layer.current_frame();
+        if final_frame < target {
+            trace!(
+                "Resimulation capped at frame {}; {} frames left until {}",
+                final_frame,
+                target - final_frame,
+                target
+            );
+            if self.save_mode != SaveMode::Sparse {
+                requests.push(self.sync_layer.save_current_state());
+            }
+            return Ok(());
         }
+        self.resimulation_target = Frame::NULL;
+
         // Record the rollback only after every re-simulated frame was actually
         // advanced. A mid-loop internal error returns above without recording,
         // so `resimulated_frames`/`frames_advanced` can never over-count the
         // frames the application actually stepped.
-        if let Ok(depth) = usize::try_from(count) {
-            self.metrics.record_rollback(depth);
-        }
+        self.metrics.record_rollback(self.resimulation_depth);
+        self.health.record_rollback(self.resimulation_depth);
         // after all this, we should have arrived at the same frame where we started
-        let final_frame = self.sync_layer.current_frame();
-        if final_frame != current_frame {
+        if final_frame != target {
             report_violation!(
                 ViolationSeverity::Error,
                 ViolationKind::FrameSync,
                 "current frame mismatch after resimulation: expected={}, actual={}",
-                current_frame,
+                target,
                 final_frame
             );
         }
@@ -10276,14 +13213,36 @@ fn adjust_gamestate(
     }
 
     /// For each spectator, send all confirmed input up until the minimum confirmed frame.
+    ///
+    /// Spectators never roll back, so nothing past `confirmed_frame` or at a
+    /// misprediction still awaiting its rollback is ever forwarded, and each
+    /// frame is forwarded once.
     fn send_confirmed_inputs_to_spectators(
         &mut self,
-        confirmed_frame: Frame,
+        mut confirmed_frame: Frame,
     ) -> Result<(), FortressError> {
-        if self.num_spectators() == 0 {
+        if self.num_spectators() == 0 || self.spectator_stream_end.is_some() {
             return Ok(());
         }
 
+        let pending_rollback = self
+            .sync_layer
+            .check_simulation_consistency(self.disconnect_frame);
+        if !pending_rollback.is_null() && pending_rollback <= confirmed_frame {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::FrameSync,
+                "confirmed frame {} is at or past pending rollback {} - holding spectator inputs back",
+                confirmed_frame,
+                pending_rollback
+            );
+            confirmed_frame = safe_frame_sub!(
+                pending_rollback,
+                1,
+                "P2PSession::send_confirmed_inputs_to_spectators"
+            );
+        }
+
         while self.next_spectator_frame <= confirmed_frame {
             let mut inputs = self
                 .sync_layer
@@ -10304,7 +13263,7 @@ fn send_confirmed_inputs_to_spectators(
                 continue;
             }
 
-            let mut input_map = BTreeMap::new();
+            let mut input_map = VecMap::new();
             for (handle, input) in inputs.iter_mut().enumerate() {
                 // Validate frame consistency - should be NULL or match expected frame
                 if input.frame != Frame::NULL && input.frame != self.next_spectator_frame {
@@ -10334,6 +13293,63 @@ fn send_confirmed_inputs_to_spectators(
         Ok(())
     }
 
+    /// Sends each endpoint that relays other players' inputs (see
+    /// [`enable_relay_for`](Self::enable_relay_for)) every frame for which both
+    /// the local players' and the relayed players' inputs are now known.
+    ///
+    /// Called after local inputs are added and at the end of every poll, so
+    /// a frame is sent before the sync layer can discard it: inputs are only
+    /// discarded at or below the confirmed frame, which no relayed player's
+    /// last received frame is behind.
+    fn send_relayed_inputs(&mut self) {
+        let local_handles = self.player_reg.local_player_handles();
+        for endpoint in self.player_reg.remotes.values_mut() {
+            if endpoint.relayed_handles().is_empty() || !endpoint.is_running() {
+                continue;
+            }
+            let relayed: HandleVec = endpoint.relayed_handles().iter().copied().collect();
+            let mut frame = endpoint.next_input_frame();
+            'frames: loop {
+                let mut inputs = VecMap::new();
+                for &handle in local_handles.iter().chain(relayed.iter()) {
+                    let status = self.local_connect_status.get(handle.as_usize());
+                    let input = match status {
+                        // a disconnected player's inputs after its last frame are blank
+                        Some(status) if status.disconnected && status.last_frame < frame => {
+                            PlayerInput::blank_input(frame)
+                        },
+                        _ => match self.sync_layer.last_added_frame(handle) {
+                            Ok(last) if last >= frame => {
+                                match self.sync_layer.confirmed_input(handle, frame) {
+                                    Ok(input) => input,
+                                    Err(err) => {
+                                        report_violation!(
+                                            ViolationSeverity::Error,
+                                            ViolationKind::InternalError,
+                                            "cannot relay the input of {} for frame {}: {:?}",
+                                            handle,
+                                            frame,
+                                            err
+                                        );
+                                        break 'frames;
+                                    },
+                                }
+                            },
+                            _ => break 'frames,
+                        },
+                    };
+                    inputs.insert(handle, input);
+                }
+                endpoint.send_input(&inputs, &self.local_connect_status);
+                frame = match frame.try_add(1) {
+                    Ok(next) => next,
+                    Err(_) => break,
+                };
+            }
+            endpoint.send_all_messages(&mut self.socket);
+        }
+    }
+
     /// Returns the [`Self::confirmed_frame`] contribution for a **remote** slot,
     /// or `None` when the slot's disconnect is mesh-agreed (the slot is then
     /// excluded from the confirmed-frame minimum; its frozen input value
@@ -10420,7 +13436,8 @@ fn send_confirmed_inputs_to_spectators(
     ///   survivors can burn their entire window against the lagging receipt
     ///   `F` before either detects the drop; folding the local detection value
     ///   would then keep BOTH bounds at `F` — both capped, both gossip-mute
-    ///   (KeepAlives carry no connect status). Before the connect-status
+    ///   (KeepAlives carry no connect status without
+    ///   [`ProtocolConfig::keep_alive_acks`]). Before the connect-status
     ///   nudge existed this was a permanent, silent deadlock; with the nudge
     ///   it would still hold every staggered release hostage to the nudge
     ///   cadence. Gossip-only folding (GGPO parity) instead lifts the low
@@ -10775,6 +13792,89 @@ fn slot_round_incomplete(&self, handle: PlayerHandle, relay_topology: bool) -> b
         })
     }
 
+    /// Each player slot's contribution to [`confirmed_frame`](Self::confirmed_frame),
+    /// in handle order. Slots whose disconnect is mesh-agreed are skipped.
+    fn slot_confirmed_frames(&self) -> impl Iterator<Item = (PlayerHandle, Frame)> + '_ {
+        // The double-failure-relay fold-membership-asymmetry gate is
+        // slot-independent — compute it once and reuse it for the floor-round
+        // hold check (`slot_round_incomplete`) of every slot.
+        let relay_topology = self.pessimistic_floor_relay_topology();
+        self.local_connect_status
+            .iter()
+            .enumerate()
+            .filter_map(move |(idx, con_stat)| {
+                let handle = PlayerHandle::new(idx);
+                self.slot_confirmed_frame(handle, con_stat, relay_topology)
+                    .map(|frame| (handle, frame))
+            })
+    }
+
+    /// Whether the session runs as far ahead of some remote player's confirmed
+    /// input as its endpoint's prediction allowance permits. Always `false`
+    /// without [`ProtocolConfig::per_peer_prediction_limit`], where the
+    /// global window is the only limit.
+    fn peer_prediction_limit_reached(&self) -> bool {
+        if self.protocol_config.per_peer_prediction_limit.is_none() {
+            return false;
+        }
+        let current = self.sync_layer.current_frame();
+        self.slot_confirmed_frames().any(|(handle, confirmed)| {
+            let Some(allowance) = self.prediction_limit_for(handle) else {
+                return false;
+            };
+            let frames_ahead = if confirmed.is_null() {
+                current.as_i32()
+            } else {
+                current - confirmed
+            };
+            i64::from(frames_ahead) >= i64::try_from(allowance).unwrap_or(i64::MAX)
+        })
+    }
+
+    /// One slot's term of the confirmed-frame minimum; `None` when the slot's
+    /// disconnect is mesh-agreed.
+    fn slot_confirmed_frame(
+        &self,
+        handle: PlayerHandle,
+        con_stat: &ConnectionStatus,
+        relay_topology: bool,
+    ) -> Option<Frame> {
+        if self.player_reg.is_local_player(handle) {
+            // Local players can never be disconnected (`remove_player` and
+            // `disconnect_player` both reject local handles), so the local
+            // slot always contributes its own last added frame.
+            return Some(con_stat.last_frame);
+        }
+        // `None`: the slot's disconnect is mesh-agreed — excluded from the
+        // minimum; the frozen input value carries the slot from here on.
+        let bound = self.remote_slot_confirmed_bound(handle, con_stat)?;
+        // Post-prune fresh-ack HOLD (the double-failure-relay floor round,
+        // `AsyncAckSoundRoundSeq`, S55 — closing the connected-relay
+        // reorder facet and subsuming the S49 cold-cache facet). When a
+        // folded relay reports this slot CONNECTED but has not yet
+        // delivered a `FloorReply` for the current prune generation
+        // (`slot_round_incomplete`), its connect-status `last_frame` is its
+        // own (possibly high) receipt — NOT a freeze — so it may hide a
+        // departed origin's low the relay still folds, so confirming past
+        // it could discard the contested window irreversibly. HOLD at the
+        // current confirmed frame — the
+        // value held while the origin was still in our fold (the model's
+        // `CurBound`) — until every folded connected relay has answered the
+        // round (`remote_slot_confirmed_bound` then folds the reorder-immune
+        // `round_floor`) or the slot mesh-agrees (excluded). Capping with
+        // `min` PRESERVES a genuine lowering (a fresh relay's relayed
+        // converge-down below the current confirmed flows through, so freeze
+        // convergence still follows down); it only blocks an ADVANCE past
+        // the held frame. Zero cost outside the relay topology —
+        // `slot_round_incomplete` cannot fire there (its rustdoc explains
+        // why).
+        if self.slot_round_incomplete(handle, relay_topology) {
+            Some(std::cmp::min(bound, self.sync_layer.last_confirmed_frame()))
+        } else {
+            Some(bound)
+        }
+    }
+
     fn remote_slot_confirmed_bound(
         &self,
         handle: PlayerHandle,
@@ -11118,14 +14218,16 @@ fn connect_status_nudge_needed(&self) -> bool {
     /// back.
     ///
     /// Why this needs a dedicated liveness carrier: connect-status gossip
-    /// rides only `Input` messages. At `N >= 3`, if a peer sends its entire
+    /// rides only `Input` messages and, with
+    /// [`ProtocolConfig::keep_alive_acks`], keepalives. At `N >= 3`, if a peer sends its entire
     /// initial prediction window's inputs before hearing a third peer's first
     /// input (transient startup loss/jitter/reorder is enough), its last
     /// gossip leaves that slot's view at `Frame::NULL` in every receiver's
     /// cache. Once **every** peer exhausts its prediction window
     /// (`current - confirmed >= max_prediction`) with fully-acked send
-    /// queues, no peer ever sends another `Input` — `KeepAlive`,
-    /// `QualityReport`, and `InputAck` carry no connect status — so the stale
+    /// queues, no peer ever sends another `Input` — `QualityReport`,
+    /// `InputAck`, and a `KeepAlive` without
+    /// [`ProtocolConfig::keep_alive_acks`] carry no connect status — so the stale
     /// caches never refresh and the whole mesh deadlocks permanently at
     /// `confirmed == NULL` on a perfectly healed network, every session
     /// `Running`. (Found by the deterministic simulation fleet; the 0.9.0
@@ -11327,9 +14429,78 @@ fn abort_npeer_serve_if_snapshot_invalidated(&mut self, disconnect_frame: Frame)
         }
     }
 
+    /// Whether this session's own view of `handle` agrees that the player
+    /// disconnected: it already dropped the player, or its link to the player
+    /// is down or interrupted.
+    fn disconnect_corroborated(&self, handle: PlayerHandle) -> bool {
+        if self
+            .local_connect_status
+            .get(handle.as_usize())
+            .is_some_and(|status| status.disconnected)
+        {
+            return true;
+        }
+        match self.player_reg.handles.get(&handle) {
+            Some(PlayerType::Remote(addr)) => self
+                .player_reg
+                .remotes
+                .get(addr)
+                .is_none_or(|own| !own.is_running() || own.is_interrupted()),
+            _ => false,
+        }
+    }
+
+    /// Acts on peers' pending claims that a player they do not own
+    /// disconnected, once this session corroborates them. A third party's
+    /// say-so alone never drops a player this session still hears from: such
+    /// a claim is counted and kept pending. Its own link normally corroborates
+    /// a real drop within one disconnect timeout, so only a claim still
+    /// pending after that is reported as a warning.
+    fn review_disconnect_claims(&mut self) {
+        let now = self.now();
+        for handle_idx in 0..self.num_players {
+            let handle = PlayerHandle::new(handle_idx);
+            let corroborated = self.disconnect_corroborated(handle);
+            for (addr, endpoint) in &mut self.player_reg.remotes {
+                let claimed = endpoint.disconnect_claim(handle).is_some();
+                if !claimed || corroborated {
+                    if claimed {
+                        endpoint.accept_disconnect_claim(handle);
+                    }
+                    if !self.disconnect_hints.is_empty() {
+                        self.disconnect_hints.remove(&(addr.clone(), handle));
+                    }
+                    continue;
+                }
+                let metrics = &mut self.metrics;
+                let hint = self.disconnect_hints.entry((addr.clone(), handle));
+                let (since, warned) = hint.or_insert_with(|| {
+                    metrics.record_uncorroborated_disconnect_claim();
+                    (now, false)
+                });
+                if !*warned && now.duration_since(*since) >= endpoint.disconnect_timeout() {
+                    *warned = true;
+                    report_violation!(
+                        ViolationSeverity::Warning,
+                        ViolationKind::NetworkProtocol,
+                        "Peer {:?} claims player {} disconnected, but this session still hears from it; holding the claim until corroborated",
+                        addr,
+                        handle_idx
+                    );
+                }
+            }
+        }
+    }
+
     /// Check if players are registered as disconnected for earlier frames on other remote players in comparison to our local assumption.
     /// Disconnect players that are disconnected for other players and update the frame they disconnected
     fn update_player_disconnects(&mut self) {
+        // The folds below read received frames; never leave one held back.
+        if !self.remote_input_buffer.is_empty() && self.remote_input_buffer_suspended() {
+            self.release_all_remote_inputs(u64::MAX);
+        }
+        // A link may have gone quiet since the last poll.
+        self.review_disconnect_claims();
         // Lazily captured immediately before the first mutation. One update
         // can apply several endpoint folds, so every later failure must retain
         // the prefix safe before the first fold. Keeping this lazy avoids a
@@ -11536,7 +14707,12 @@ fn update_player_disconnects(&mut self) {
             }
         }
 
-        for (addr, overrides) in &propagated_by_addr {
+        // Apply in the registry's endpoint order (lowest handle first; see
+        // `EndpointMap`), so the resulting events do not depend on how the
+        // address type orders.
+        let mut propagated: Vec<_> = propagated_by_addr.iter().collect();
+        propagated.sort_by_key(|(addr, _)| representative_by_addr.get(*addr).copied());
+        for (addr, overrides) in propagated {
             let Some(&representative) = representative_by_addr.get(addr) else {
                 // The two maps are populated together earlier in this function,
                 // so reaching this branch indicates an internal invariant
@@ -11617,18 +14793,56 @@ fn max_frame_advantage(&self) -> i32 {
         interval
     }
 
+    /// How many frames ahead of its slowest connected peer the session would be
+    /// after simulating the current frame: the local input frame this advance
+    /// adds, less the newest input frame that peer has probably sent by now.
+    fn frames_ahead_of_slowest_peer(&self) -> u32 {
+        let local_delay = self
+            .player_reg
+            .local_player_handles_iter()
+            .filter_map(|handle| self.sync_layer.frame_delay(handle).ok())
+            .max()
+            .unwrap_or(0);
+        let local_frame = self
+            .sync_layer
+            .current_frame()
+            .as_i32()
+            .saturating_add(i32::try_from(local_delay).unwrap_or(i32::MAX));
+        let mut frames_ahead = 0_i32;
+        for endpoint in self.player_reg.remotes.values() {
+            let connected = endpoint.handles().iter().any(|handle| {
+                self.local_connect_status
+                    .get(handle.as_usize())
+                    .is_some_and(|status| !status.disconnected)
+            });
+            if connected && endpoint.is_running() {
+                frames_ahead = frames_ahead
+                    .max(local_frame.saturating_sub(endpoint.estimated_remote_input_frame()));
+            }
+        }
+        u32::try_from(frames_ahead).unwrap_or(0)
+    }
+
     fn check_wait_recommendation(&mut self) {
         self.frames_ahead = self.max_frame_advantage();
-        if self.sync_layer.current_frame() > self.next_recommended_sleep
-            && self.frames_ahead >= MIN_RECOMMENDATION as i32
-        {
+        let skip_frames = recommended_skip_frames(self.frames_ahead);
+        if self.sync_layer.current_frame() > self.next_recommended_sleep && skip_frames > 0 {
             self.next_recommended_sleep = self.sync_layer.current_frame() + RECOMMENDATION_INTERVAL;
-            // frames_ahead is guaranteed to be >= MIN_RECOMMENDATION (positive), so try_into should succeed.
-            // Using unwrap_or(0) as defense-in-depth; 0 effectively skips the recommendation.
-            let skip_frames = self.frames_ahead.try_into().unwrap_or(0);
+            self.recommend_wait(skip_frames);
+        }
+    }
+
+    /// Emits a [`FortressEvent::WaitRecommendation`], or with auto frame skip
+    /// schedules the skips instead. A recommendation reflects the current
+    /// frame lead, so it replaces any skips still outstanding rather than
+    /// adding to them.
+    fn recommend_wait(&mut self, skip_frames: u32) {
+        if self.auto_frame_skip {
+            self.pending_skip_frames = skip_frames;
+        } else {
             self.enqueue_event(FortressEvent::WaitRecommendation { skip_frames });
-            self.metrics.record_wait_recommendation();
         }
+        self.metrics.record_wait_recommendation();
     }
 
     fn check_last_saved_state(
@@ -11648,10 +14862,12 @@ fn check_last_saved_state(
                 self.adjust_gamestate(last_saved, confirmed_frame, requests)?;
             }
 
-            // after all this, we should have saved the confirmed state
+            // after all this, we should have saved the confirmed state (unless a capped
+            // resimulation has not reached it yet)
             let expected_saved_frame =
                 std::cmp::min(confirmed_frame, self.sync_layer.current_frame());
             if confirmed_frame != Frame::NULL
+                && self.resimulation_target.is_null()
                 && self.sync_layer.last_saved_frame() != expected_saved_frame
             {
                 report_violation!(
@@ -11836,6 +15052,23 @@ fn handle_event_inner(
             Event::NetworkResumed => {
                 self.enqueue_event(FortressEvent::NetworkResumed { addr });
             },
+            // forward to user
+            Event::TransportError {
+                consecutive_failures,
+            } => {
+                self.enqueue_event(FortressEvent::TransportError {
+                    addr,
+                    consecutive_failures,
+                });
+            },
+            // forward to user
+            Event::AdvanceStalled => {
+                self.enqueue_event(FortressEvent::RemoteAdvanceStalled { addr });
+            },
+            // forward to user
+            Event::AdvanceResumed => {
+                self.enqueue_event(FortressEvent::RemoteAdvanceResumed { addr });
+            },
             // check if all remotes are synced, then forward to user
             Event::Synchronized => {
                 self.check_initial_sync();
@@ -11843,6 +15076,8 @@ fn handle_event_inner(
             },
             // disconnect the player, then forward to user
             Event::Disconnected => {
+                // The disconnect freezes slots at their received frames.
+                self.release_all_remote_inputs(u64::MAX);
                 // Capture before the hot-join pending-close path or ordinary
                 // disconnect machinery mutates endpoint/status/freeze state.
                 let confirmed_before_disconnect = self.confirmed_frame();
@@ -12037,6 +15272,27 @@ fn handle_event_inner(
             Event::Incompatible { reason } => {
                 self.enqueue_event(FortressEvent::IncompatibleSession { addr, reason });
             },
+            Event::FrameTag { frame, data } => match player_handles.first() {
+                Some(&handle) => {
+                    self.enqueue_event(FortressEvent::FrameTag {
+                        handle,
+                        frame,
+                        data,
+                    });
+                },
+                None => trace!("Ignoring frame tag for frame {} from {:?}", frame, addr),
+            },
+            Event::ReliableMessage { payload } => {
+                self.enqueue_event(FortressEvent::ReliableMessage { addr, payload });
+            },
+            // only hosts end spectator streams; a peer has no stream to end
+            Event::StreamEnded { final_frame } => {
+                trace!(
+                    "Ignoring stream end at frame {} from {:?}",
+                    final_frame,
+                    addr
+                );
+            },
             // add the input and all associated information
             Event::Input { input, player, .. } => {
                 // input only comes from remote players, not spectators
@@ -12050,6 +15306,20 @@ fn handle_event_inner(
                     );
                     return;
                 }
+                // Merging inputs for a handle another machine also sends
+                // would resolve by arrival order and desync.
+                if !player_handles.contains(&player) {
+                    self.metrics.record_undeclared_handle_input();
+                    report_violation!(
+                        ViolationSeverity::Error,
+                        ViolationKind::NetworkProtocol,
+                        "Dropping input for player handle {} from {:?}, which is registered for handles {:?}",
+                        player,
+                        addr,
+                        player_handles
+                    );
+                    return;
+                }
                 let Some(status) = self.local_connect_status.get(player.as_usize()) else {
                     report_violation!(
                         ViolationSeverity::Warning,
@@ -12060,7 +15330,14 @@ fn handle_event_inner(
                     return;
                 };
                 let disconnected = status.disconnected;
-                let current_remote_frame = status.last_frame;
+                // Held inputs were received already: the sequence continues
+                // after the newest of them.
+                let last_frame = status.last_frame;
+                let current_remote_frame = self
+                    .remote_input_buffer
+                    .get(&player)
+                    .and_then(VecDeque::back)
+                    .map_or(last_frame, |(held, _)| held.frame);
                 if !disconnected {
                     // check if the input comes in the correct sequence
                     let expected_frame = safe_frame_add!(
@@ -12096,24 +15373,128 @@ fn handle_event_inner(
                         );
                         return;
                     }
-                    // Retain the input before advancing receipt state. A
-                    // capacity refusal means the protocol has already handed
-                    // us a frame we cannot safely represent; fail closed
-                    // rather than acknowledge/advertise an unretained frame
-                    // and continue toward divergence.
-                    if !self.sync_layer.add_remote_input(player, input) {
-                        let confirmed_before_failure = self.confirmed_frame();
-                        self.enter_fail_closed_disconnect_state_at(confirmed_before_failure);
+                    if self.holds_remote_input(input.frame) {
+                        let due = self
+                            .advance_ticks
+                            .saturating_add(u64::from(self.remote_input_buffer_frames));
+                        self.remote_input_buffer
+                            .entry(player)
+                            .or_default()
+                            .push_back((input, due));
                         return;
                     }
-                    if let Some(status) = self.local_connect_status.get_mut(player.as_usize()) {
-                        status.last_frame = input.frame;
-                    }
+                    // Everything held for the handle goes first, in sequence.
+                    self.release_remote_inputs(player, u64::MAX);
+                    self.deliver_remote_input(player, input);
                 }
             },
         }
     }
 
+    /// Hands a remote input to the sync layer and records it as received.
+    /// Returns `false` if the session failed closed instead.
+    fn deliver_remote_input(&mut self, player: PlayerHandle, input: PlayerInput<T::Input>) -> bool {
+        // Retain the input before advancing receipt state. A capacity refusal
+        // means the protocol has already handed us a frame we cannot safely
+        // represent; fail closed rather than acknowledge/advertise an
+        // unretained frame and continue toward divergence.
+        if !self.sync_layer.add_remote_input(player, input) {
+            let confirmed_before_failure = self.confirmed_frame();
+            self.enter_fail_closed_disconnect_state_at(confirmed_before_failure);
+            return false;
+        }
+        if let Some(status) = self.local_connect_status.get_mut(player.as_usize()) {
+            status.last_frame = input.frame;
+        }
+        true
+    }
+
+    /// Whether a newly received remote input for `frame` is held back: the
+    /// frame was already predicted and the jitter buffer is in use.
+    fn holds_remote_input(&self, frame: Frame) -> bool {
+        self.remote_input_buffer_frames > 0
+            && frame < self.sync_layer.current_frame()
+            && !self.remote_input_buffer_suspended()
+    }
+
+    /// Whether held remote inputs must be delivered at once: a player has
+    /// disconnected (locally or in a peer's view), or a coordinated drop or
+    /// hot-join is in progress. Disconnect folds and drop receipts read the
+    /// received frames, so they must never miss a held input.
+    fn remote_input_buffer_suspended(&self) -> bool {
+        #[cfg(feature = "hot-join")]
+        if !self.hot_join.joining.is_empty()
+            || self.hot_join.pending_reactivation.is_some()
+            || self.hot_join.joiner.is_some()
+        {
+            return true;
+        }
+        let drop = &self.coordinated_drop;
+        if drop.active.is_some() || !drop.deferred_prepares.is_empty() || !drop.queued.is_empty() {
+            return true;
+        }
+        (0..self.num_players).any(|handle_idx| {
+            let handle = PlayerHandle::new(handle_idx);
+            self.local_connect_status
+                .get(handle_idx)
+                .is_some_and(|status| status.disconnected)
+                || self
+                    .player_reg
+                    .remotes
+                    .values()
+                    .any(|endpoint| endpoint.peer_connect_status(handle).disconnected)
+        })
+    }
+
+    /// Delivers the remote inputs held for `player` that are due at `now`,
+    /// oldest first. Inputs of a player that has since disconnected are
+    /// discarded, as they would have been on arrival.
+    fn release_remote_inputs(&mut self, player: PlayerHandle, now: u64) {
+        while let Some(held) = self.remote_input_buffer.get_mut(&player) {
+            let input = match held.front() {
+                Some((_, due)) if *due > now => return,
+                Some(_) => held.pop_front(),
+                None => None,
+            };
+            let Some((input, _)) = input else {
+                self.remote_input_buffer.remove(&player);
+                return;
+            };
+            let connected = self
+                .local_connect_status
+                .get(player.as_usize())
+                .is_some_and(|status| !status.disconnected);
+            if !connected || !self.deliver_remote_input(player, input) {
+                self.remote_input_buffer.remove(&player);
+                return;
+            }
+        }
+    }
+
+    /// Delivers every held remote input due at `now`; `u64::MAX` delivers all.
+    fn release_all_remote_inputs(&mut self, now: u64) {
+        if self.remote_input_buffer.is_empty() {
+            return;
+        }
+        let handles: HandleVec = self.remote_input_buffer.keys().copied().collect();
+        for handle in handles {
+            self.release_remote_inputs(handle, now);
+        }
+    }
+
+    /// Returns how many received remote inputs of `player_handle` are held
+    /// back by the jitter buffer (see
+    /// [`InputQueueConfig::remote_input_buffer_frames`](crate::InputQueueConfig::remote_input_buffer_frames)).
+    ///
+    /// Always `0` for local players, spectators, unknown handles, and when the
+    /// buffer is disabled.
+    #[must_use]
+    pub fn buffered_remote_inputs(&self, player_handle: PlayerHandle) -> usize {
+        self.remote_input_buffer
+            .get(&player_handle)
+            .map_or(0, VecDeque::len)
+    }
+
     /// Handles a protocol event. Every emission is bounded and accounted inline,
     /// including emissions immediately before an early return.
     fn handle_event(
@@ -12129,26 +15510,51 @@ fn handle_event(
     /// routine first; otherwise discard an incoming routine or replace the oldest
     /// durable with an incoming durable.
     fn enqueue_event(&mut self, event: FortressEvent<T>) {
+        let event = self.stamp_event(event);
         Self::enqueue_event_fields(
             &mut self.event_queue,
             self.max_event_queue_size,
             &mut self.metrics,
             &mut self.event_discard_warned,
+            self.trace_recorder.as_mut(),
             event,
         );
     }
 
+    /// Stamps `event` with the current frame and the time since creation.
+    fn stamp_event(&self, event: FortressEvent<T>) -> TimestampedEvent<T> {
+        let (frame, session_ms) = self.event_stamp();
+        TimestampedEvent {
+            frame,
+            session_ms,
+            event,
+        }
+    }
+
+    /// The current frame and milliseconds since creation, as stamped on events.
+    fn event_stamp(&self) -> (Frame, u64) {
+        let elapsed = self.now().saturating_duration_since(self.created_at);
+        (
+            self.sync_layer.current_frame(),
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+        )
+    }
+
     /// Field-level enqueue used when another field of the session is already
     /// mutably borrowed (notably a remote endpoint during checksum comparison).
     fn enqueue_event_fields(
-        event_queue: &mut VecDeque<FortressEvent<T>>,
+        event_queue: &mut VecDeque<TimestampedEvent<T>>,
         max_event_queue_size: usize,
         metrics: &mut SessionMetrics,
         event_discard_warned: &mut bool,
-        event: FortressEvent<T>,
+        trace_recorder: Option<&mut TraceRecorder>,
+        event: TimestampedEvent<T>,
     ) {
+        if let Some(recorder) = trace_recorder {
+            recorder.event(&event.event, event.frame);
+        }
         if let Some(dropped) = enqueue_event_bounded(event_queue, max_event_queue_size, event) {
-            metrics.record_event_discard(dropped.kind());
+            metrics.record_event_discard(dropped.event.kind());
             if !*event_discard_warned {
                 *event_discard_warned = true;
                 report_violation!(
@@ -12168,32 +15574,58 @@ fn enqueue_event_fields(
 
     fn compare_local_checksums_against_peers(&mut self) {
         match self.desync_detection {
-            DesyncDetection::On { .. } => {
+            DesyncDetection::On { interval } => {
+                // Read the stamp up front: the remotes are mutably borrowed below.
+                let (stamp_frame, stamp_ms) = self.event_stamp();
+                let interval_frames = checksum_interval_frame_delta(interval).max(1);
+                let mut checked_frames = std::mem::take(&mut self.checked_checksum_frames);
                 for remote in self.player_reg.remotes.values_mut() {
-                    let mut checked_frames = Vec::new();
-
                     for (&remote_frame, &remote_checksum) in &remote.pending_checksums {
                         if remote_frame >= self.sync_layer.last_confirmed_frame() {
                             // we're still waiting for inputs for this frame
                             continue;
                         }
-                        if let Some(&local_checksum) =
-                            self.local_checksum_history.get(&remote_frame)
-                        {
+                        // A sparse-saving peer labels its reports with the frames
+                        // it saved, which our own schedule never reports. Such a
+                        // frame is answered from its saved cell while we still
+                        // hold it; own-schedule frames only ever come from the
+                        // history, which the schedule's deferrals keep clean.
+                        let off_schedule = self.save_mode == SaveMode::Sparse
+                            || remote_frame.as_i32() % interval_frames != 0;
+                        let local_checksum = self
+                            .local_checksum_history
+                            .get(&remote_frame)
+                            .copied()
+                            .or_else(|| {
+                                let deferred = !self.disconnect_frame.is_null()
+                                    && remote_frame >= self.disconnect_frame;
+                                if !off_schedule || deferred {
+                                    return None;
+                                }
+                                self.sync_layer
+                                    .saved_state_by_frame(remote_frame)
+                                    .and_then(|cell| cell.checksum())
+                            });
+                        if let Some(local_checksum) = local_checksum {
                             self.metrics
                                 .record_checksum_comparison(local_checksum == remote_checksum);
                             if local_checksum != remote_checksum {
-                                let event = FortressEvent::DesyncDetected {
-                                    frame: remote_frame,
-                                    local_checksum,
-                                    remote_checksum,
-                                    addr: remote.peer_addr(),
+                                let event = TimestampedEvent {
+                                    frame: stamp_frame,
+                                    session_ms: stamp_ms,
+                                    event: FortressEvent::DesyncDetected {
+                                        frame: remote_frame,
+                                        local_checksum,
+                                        remote_checksum,
+                                        addr: remote.peer_addr(),
+                                    },
                                 };
                                 Self::enqueue_event_fields(
                                     &mut self.event_queue,
                                     self.max_event_queue_size,
                                     &mut self.metrics,
                                     &mut self.event_discard_warned,
+                                    self.trace_recorder.as_mut(),
                                     event,
                                 );
                                 // B3 (Byzantine hardening): track per-peer
@@ -12249,32 +15681,60 @@ fn compare_local_checksums_against_peers(&mut self) {
                         }
                     }
 
-                    for frame in checked_frames {
-                        remote.pending_checksums.remove_entry(&frame);
+                    // Each frame is compared once: the pair is evicted here and
+                    // the local schedule never reports the same frame again.
+                    while let Some(frame) = checked_frames.pop() {
+                        remote.pending_checksums.remove(&frame);
+                        if remote
+                            .last_compared_checksum_frame
+                            .is_none_or(|compared| frame > compared)
+                        {
+                            remote.last_compared_checksum_frame = Some(frame);
+                        }
                     }
                 }
+                self.checked_checksum_frames = checked_frames;
             },
             DesyncDetection::Off => (),
         }
     }
 
+    /// Reports the local checksum of every confirmed frame on the interval
+    /// grid (the positive multiples of `interval`) not yet reported.
+    ///
+    /// The grid is a pure function of the frame number, so every peer reports
+    /// the same frames no matter how its confirmed frame progresses. All grid
+    /// frames confirmed since the previous call are reported in one pass:
+    /// reporting one per call would fall behind whenever the confirmed frame
+    /// jumps, until the cells it still needs are overwritten.
     fn check_checksum_send_interval(&mut self) {
         match self.desync_detection {
+            DesyncDetection::On { interval } if self.save_mode == SaveMode::Sparse => {
+                self.check_sparse_checksum_send_interval(interval);
+            },
             DesyncDetection::On { interval } => {
                 let interval_frames = checksum_interval_frame_delta(interval);
-                let frame_to_send = if self.last_sent_checksum_frame.is_null() {
-                    Frame::new(interval_frames)
-                } else {
-                    safe_frame_add!(
-                        self.last_sent_checksum_frame,
-                        interval_frames,
-                        "P2PSession::check_checksum_send_interval"
-                    )
-                };
+                loop {
+                    let frame_to_send = if self.last_sent_checksum_frame.is_null() {
+                        Frame::new(interval_frames)
+                    } else {
+                        safe_frame_add!(
+                            self.last_sent_checksum_frame,
+                            interval_frames,
+                            "P2PSession::check_checksum_send_interval"
+                        )
+                    };
+
+                    // Stop at the first grid frame not yet confirmed and saved. A
+                    // grid saturated at `i32::MAX` cannot advance past the last
+                    // report, so it never reports that frame twice.
+                    if frame_to_send <= self.last_sent_checksum_frame
+                        || frame_to_send > self.sync_layer.last_confirmed_frame()
+                        || frame_to_send > self.sync_layer.last_saved_frame()
+                    {
+                        return;
+                    }
 
-                if frame_to_send <= self.sync_layer.last_confirmed_frame()
-                    && frame_to_send <= self.sync_layer.last_saved_frame()
-                {
                     // M1: A disconnect-induced rollback is armed for this
                     // `advance_frame` (set in `update_player_disconnects`, cleared
                     // only after `adjust_gamestate`, which runs AFTER this
@@ -12286,8 +15746,8 @@ fn check_checksum_send_interval(&mut self) {
                     // — causing a false DesyncDetected when a survivor's correct
                     // post-convergence checksum arrives — and (b) gossip a stale
                     // checksum to peers. Defer: `last_sent_checksum_frame` is not
-                    // advanced (the advance happens only inside the
-                    // `if let Some(checksum)` block below), so the same frame is
+                    // advanced (the advance happens only once a checksum is
+                    // reported below), so the same frame is
                     // re-attempted next `advance_frame` once the cell has been
                     // re-simulated with the dropped slot frozen at the agreed frame.
                     // Bounded: `disconnect_frame` is non-null only on a frame
@@ -12327,22 +15787,106 @@ fn check_checksum_send_interval(&mut self) {
                             .retain(|&frame, _| frame >= oldest_frame_to_keep);
                     }
 
-                    if let Some(checksum) = cell.checksum() {
-                        for remote in self.player_reg.remotes.values_mut() {
-                            remote.send_checksum_report(frame_to_send, checksum);
-                        }
-                        self.last_sent_checksum_frame = frame_to_send;
-                        // Pre-pruning above keeps the configured cap as a hard
-                        // allocation bound, including the insertion high-water.
-                        self.local_checksum_history.insert(frame_to_send, checksum);
-                        self.metrics
-                            .observe_checksum_history_len(self.local_checksum_history.len());
+                    // A frame without a checksum stays scheduled and is retried
+                    // on the next call; later frames wait behind it.
+                    let Some(checksum) = cell.checksum() else {
+                        return;
+                    };
+                    for remote in self.player_reg.remotes.values_mut() {
+                        remote.send_checksum_report(frame_to_send, checksum);
                     }
+                    self.send_checksum_to_verifying_spectators(frame_to_send, checksum);
+                    self.last_sent_checksum_frame = frame_to_send;
+                    // Pre-pruning above keeps the configured cap as a hard
+                    // allocation bound, including the insertion high-water.
+                    self.local_checksum_history.insert(frame_to_send, checksum);
+                    self.metrics
+                        .observe_checksum_history_len(self.local_checksum_history.len());
                 }
             },
             DesyncDetection::Off => (),
         }
     }
+
+    /// The [`SaveMode::Sparse`] schedule: reports every `interval`-th saved
+    /// and confirmed frame.
+    ///
+    /// Sparse saving keeps only occasional confirmed checkpoints, so the cells
+    /// of most grid frames never exist. Counting checkpoints instead keeps
+    /// reports flowing at one per `interval` saves, each labeled with the frame
+    /// its cell actually holds. Peers answer such off-grid frames from their
+    /// own saved cells (see `compare_local_checksums_against_peers`).
+    fn check_sparse_checksum_send_interval(&mut self, interval: u32) {
+        let frame = self.sync_layer.last_saved_frame();
+        if frame.is_null()
+            || frame <= self.last_counted_saved_frame
+            || frame > self.sync_layer.last_confirmed_frame()
+        {
+            return;
+        }
+        // M1: the cell predates the pending disconnect rollback; see
+        // `check_checksum_send_interval`. It is counted once re-simulated.
+        if !self.disconnect_frame.is_null() && frame >= self.disconnect_frame {
+            return;
+        }
+        // A checkpoint without a checksum is retried on the next call.
+        let Some(checksum) = self
+            .sync_layer
+            .saved_state_by_frame(frame)
+            .and_then(|cell| cell.checksum())
+        else {
+            return;
+        };
+        self.last_counted_saved_frame = frame;
+        self.saved_frames_since_checksum = self.saved_frames_since_checksum.saturating_add(1);
+        if self.saved_frames_since_checksum < interval.max(1) {
+            return;
+        }
+        self.saved_frames_since_checksum = 0;
+
+        // Reports are spaced by the save cadence, not the grid, so retention
+        // keeps the newest entries instead of a frame window.
+        let max_history = self.protocol_config.max_checksum_history;
+        while self.local_checksum_history.len() >= max_history.max(1) {
+            self.local_checksum_history.pop_first();
+        }
+        for remote in self.player_reg.remotes.values_mut() {
+            remote.send_checksum_report(frame, checksum);
+        }
+        self.send_checksum_to_verifying_spectators(frame, checksum);
+        self.last_sent_checksum_frame = frame;
+        self.local_checksum_history.insert(frame, checksum);
+        self.metrics
+            .observe_checksum_history_len(self.local_checksum_history.len());
+    }
+
+    /// Sends a checksum report to every spectator that verifies the match
+    /// ([`SpectatorConfig::checksum_authority`](crate::SpectatorConfig::checksum_authority)).
+    fn send_checksum_to_verifying_spectators(&mut self, frame: Frame, checksum: u128) {
+        for spectator in self.player_reg.spectators.values_mut() {
+            if spectator.wants_checksum_reports() {
+                spectator.send_checksum_report(frame, checksum);
+            }
+        }
+    }
+}
+
+impl<T: Config> Drop for P2PSession<T> {
+    /// Best-effort final [`flush`](P2PSession::flush) of the queued messages.
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let flushed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.flush()));
+        if flushed.is_err() {
+            let _violation_scope = self.scoped_violation_observer();
+            report_violation!(
+                ViolationSeverity::Warning,
+                ViolationKind::NetworkProtocol,
+                "socket panicked while flushing queued messages on session drop"
+            );
+        }
+    }
 }
 
 impl<T: Config> fmt::Debug for P2PSession<T> {
@@ -12369,6 +15913,7 @@ impl<T: Config> InvariantChecker for P2PSession<T> {
     /// This method verifies:
     /// 1. No desync has been detected with any remote peer
     /// 2. The session state is consistent
+    /// 3. The player registry iterates endpoints in its documented order
     ///
     /// # Returns
     ///
@@ -12388,6 +15933,8 @@ impl<T: Config> InvariantChecker for P2PSession<T> {
     /// fortress_rollback::debug_check_invariants!(session);
     /// ```
     fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        self.player_reg.check_invariants()?;
+
         // Check for any desync with remote peers
         for (handle, health) in self.all_sync_health() {
             if let SyncHealth::DesyncDetected {
@@ -12441,6 +15988,14 @@ fn current_state(&self) -> SessionState {
     fn poll_remote_clients(&mut self) {
         Self::poll_remote_clients(self)
     }
+
+    fn current_frame(&self) -> Frame {
+        Self::current_frame(self)
+    }
+
+    fn confirmed_frame(&self) -> Frame {
+        Self::confirmed_frame(self)
+    }
 }
 
 #[cfg(test)]
@@ -12451,6 +16006,7 @@ fn poll_remote_clients(&mut self) {
     clippy::indexing_slicing,
     clippy::needless_collect
 )]
+#[cfg_attr(not(feature = "telemetry"), allow(dead_code, unused_imports))]
 mod tests {
     use super::*;
     use crate::network::messages::{Message, MessageBody, MessageHeader, SyncRequest};
@@ -12508,6 +16064,7 @@ fn sync_request_message() -> Message {
         }
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn unknown_source_packets_are_counted_and_warn_once_per_session() {
         let observer = Arc::new(crate::telemetry::CollectingObserver::new());
@@ -12632,7 +16189,14 @@ fn create_two_local_players_session() -> P2PSession<TestConfig> {
 
     #[test]
     fn runtime_input_delay_respects_combined_rollback_storage_bound() {
-        let mut session = create_local_only_session();
+        let mut session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .unwrap()
+            .with_input_delay_beyond_prediction(true)
+            .add_player(PlayerType::Local, PlayerHandle::new(0))
+            .expect("Failed to add player")
+            .start_p2p_session(DummySocket)
+            .expect("Failed to create session");
         let local = PlayerHandle::new(0);
 
         session
@@ -12659,6 +16223,36 @@ fn runtime_input_delay_respects_combined_rollback_storage_bound() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn inputs_for_handles_the_endpoint_does_not_own_are_dropped_and_counted() {
+        let mut session = create_two_player_session();
+        session.state = SessionState::Running;
+        let observer = Arc::new(crate::telemetry::CollectingObserver::new());
+        let _guard = crate::telemetry::push_violation_observer(
+            Arc::clone(&observer) as Arc<dyn crate::telemetry::ViolationObserver>
+        );
+        let input = |player| Event::Input {
+            input: PlayerInput::new(Frame::new(0), 9),
+            player: PlayerHandle::new(player),
+            peer_connect_status: vec![ConnectionStatus::default(); 2].into(),
+        };
+
+        // The endpoint at 8080 owns handle 1; handle 0 is ours.
+        session.handle_event(input(0), Arc::from([PlayerHandle::new(1)]), test_addr(8080));
+        assert_eq!(session.metrics().undeclared_handle_inputs, 1);
+        assert_eq!(session.local_connect_status[0].last_frame, Frame::NULL);
+        assert!(observer
+            .violations()
+            .iter()
+            .any(|v| v.severity == ViolationSeverity::Error
+                && v.message.contains("Dropping input for player handle")));
+
+        session.handle_event(input(1), Arc::from([PlayerHandle::new(1)]), test_addr(8080));
+        assert_eq!(session.metrics().undeclared_handle_inputs, 1);
+        assert_eq!(session.local_connect_status[1].last_frame, Frame::new(0));
+    }
+
     #[test]
     fn remote_capacity_refusal_does_not_advance_receipt_state() {
         let protocol = ProtocolConfig {
@@ -12667,7 +16261,10 @@ fn remote_capacity_refusal_does_not_advance_receipt_state() {
         };
         let mut session = SessionBuilder::<TestConfig>::new()
             .with_max_prediction_window(1)
-            .with_input_queue_config(crate::InputQueueConfig { queue_length: 2 })
+            .with_input_queue_config(crate::InputQueueConfig {
+                queue_length: 2,
+                ..crate::InputQueueConfig::default()
+            })
             .with_protocol_config(protocol)
             .with_num_players(2)
             .expect("two players")
@@ -12685,7 +16282,7 @@ fn remote_capacity_refusal_does_not_advance_receipt_state() {
                 Event::Input {
                     input: PlayerInput::new(Frame::new(frame), frame as u8),
                     player: PlayerHandle::new(1),
-                    peer_connect_status: vec![ConnectionStatus::default(); 2],
+                    peer_connect_status: vec![ConnectionStatus::default(); 2].into(),
                 },
                 Arc::clone(&handles),
                 test_addr(8080),
@@ -12697,7 +16294,7 @@ fn remote_capacity_refusal_does_not_advance_receipt_state() {
             Event::Input {
                 input: PlayerInput::new(Frame::new(3), 3),
                 player: PlayerHandle::new(1),
-                peer_connect_status: vec![ConnectionStatus::default(); 2],
+                peer_connect_status: vec![ConnectionStatus::default(); 2].into(),
             },
             handles,
             test_addr(8080),
@@ -12710,6 +16307,68 @@ fn remote_capacity_refusal_does_not_advance_receipt_state() {
         assert_eq!(session.current_state(), SessionState::Synchronizing);
     }
 
+    #[test]
+    fn auto_frame_skip_skips_calls_without_simulating() {
+        let mut session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .unwrap()
+            .with_auto_frame_skip(true)
+            .add_player(PlayerType::Local, PlayerHandle::new(0))
+            .unwrap()
+            .start_p2p_session(DummySocket)
+            .unwrap();
+        let local = PlayerHandle::new(0);
+        session.add_local_input(local, 1).unwrap();
+        let _ = session.advance_frame().unwrap();
+        assert!(!session.skipped_this_call());
+
+        session.recommend_wait(2);
+        assert_eq!(session.pending_skip_frames(), 2);
+        assert!(session.events().next().is_none(), "honored, not emitted");
+        assert_eq!(session.metrics().wait_recommendations, 1);
+        let frame = session.current_frame();
+        for remaining in [1, 0] {
+            session.add_local_input(local, 1).unwrap();
+            let requests = session.advance_frame().unwrap();
+            assert!(requests.is_empty());
+            assert!(session.skipped_this_call());
+            assert_eq!(session.current_frame(), frame);
+            assert_eq!(session.pending_skip_frames(), remaining);
+        }
+
+        session.add_local_input(local, 1).unwrap();
+        let requests = session.advance_frame().unwrap();
+        assert!(!session.skipped_this_call());
+        assert!(requests
+            .iter()
+            .any(|request| matches!(request, FortressRequest::AdvanceFrame { .. })));
+        assert_eq!(session.current_frame(), frame + 1);
+    }
+
+    #[test]
+    fn auto_frame_skip_recommendations_replace_outstanding_skips() {
+        let mut session = create_two_local_players_session();
+        session.auto_frame_skip = true;
+
+        session.recommend_wait(5);
+        session.recommend_wait(3);
+        assert_eq!(session.pending_skip_frames(), 3, "must not stack to 8");
+        session.recommend_wait(7);
+        assert_eq!(session.pending_skip_frames(), 7);
+    }
+
+    #[test]
+    fn wait_recommendations_are_emitted_without_auto_frame_skip() {
+        let mut session = create_two_local_players_session();
+
+        session.recommend_wait(4);
+        assert_eq!(session.pending_skip_frames(), 0);
+        assert!(matches!(
+            session.events().next(),
+            Some(FortressEvent::WaitRecommendation { skip_frames: 4 })
+        ));
+    }
+
     // ==========================================
     // Constant Tests
     // ==========================================
@@ -12937,6 +16596,7 @@ fn default_max_event_queue_size_is_reasonable() {
     /// This is the green successor to the red-documentation test that pinned
     /// the silent behavior (PLAN.md §21.3): the final assertions flipped from
     /// "no telemetry" to "telemetry recorded" once the fix landed.
+    #[cfg(feature = "telemetry")]
     #[test]
     fn event_queue_overflow_records_discard_telemetry() {
         use crate::metrics::EventKind;
@@ -12954,7 +16614,7 @@ fn event_queue_overflow_records_discard_telemetry() {
         // Canary: an undrained durable Disconnected event at the front.
         session
             .event_queue
-            .push_back(FortressEvent::Disconnected { addr });
+            .push_back(session.stamp_event(FortressEvent::Disconnected { addr }));
 
         // Churn wave: `max_event_queue_size` benign protocol events arrive
         // before the application drains — the D9 scenario (a slow-draining
@@ -12980,7 +16640,7 @@ fn event_queue_overflow_records_discard_telemetry() {
         let disconnected_still_queued = session
             .event_queue
             .iter()
-            .any(|e| matches!(e, FortressEvent::Disconnected { .. }));
+            .any(|e| matches!(&e.event, FortressEvent::Disconnected { .. }));
         assert!(
             disconnected_still_queued,
             "routine churn must not evict the durable Disconnected canary"
@@ -13047,7 +16707,7 @@ fn saturated_graceful_drop_error_fails_closed_without_uncertified_event() {
             session
                 .event_queue
                 .iter()
-                .filter(|event| matches!(event, FortressEvent::Disconnected { addr: event_addr } if *event_addr == addr))
+                .filter(|event| matches!(&event.event, FortressEvent::Disconnected { addr: event_addr } if *event_addr == addr))
                 .count(),
             0,
             "a failed certificate must not evict an event to emit an uncertified disconnect"
@@ -13060,6 +16720,7 @@ fn saturated_graceful_drop_error_fails_closed_without_uncertified_event() {
     /// a single `Warning` (not one per message), and draining events
     /// (`events()`) re-arms it so a subsequent overflow warns again. The
     /// per-kind counters keep incrementing regardless.
+    #[cfg(feature = "telemetry")]
     #[test]
     fn event_queue_overflow_warning_is_rate_limited_per_drain_gap() {
         let mut session = create_two_player_session();
@@ -13153,9 +16814,9 @@ fn desync_detected_push_outside_handle_event_is_bounded_and_counted() {
 
         // Saturate the event queue with benign events first.
         for _ in 0..session.max_event_queue_size {
-            session
-                .event_queue
-                .push_back(FortressEvent::WaitRecommendation { skip_frames: 1 });
+            session.event_queue.push_back(
+                session.stamp_event(FortressEvent::WaitRecommendation { skip_frames: 1 }),
+            );
         }
         assert_eq!(session.event_queue.len(), session.max_event_queue_size);
         let before = session.metrics().events_discarded_total;
@@ -13176,7 +16837,7 @@ fn desync_detected_push_outside_handle_event_is_bounded_and_counted() {
             session
                 .event_queue
                 .iter()
-                .any(|event| matches!(event, FortressEvent::DesyncDetected { .. })),
+                .any(|event| matches!(&event.event, FortressEvent::DesyncDetected { .. })),
             "the durable DesyncDetected event must displace routine filler"
         );
         assert_eq!(
@@ -13199,9 +16860,9 @@ fn handle_event_early_return_emission_is_bounded_at_enqueue() {
 
         // Saturate the queue.
         for _ in 0..session.max_event_queue_size {
-            session
-                .event_queue
-                .push_back(FortressEvent::WaitRecommendation { skip_frames: 1 });
+            session.event_queue.push_back(
+                session.stamp_event(FortressEvent::WaitRecommendation { skip_frames: 1 }),
+            );
         }
         assert_eq!(session.event_queue.len(), session.max_event_queue_size);
         let before = session.metrics().events_discarded_total;
@@ -13227,7 +16888,7 @@ fn handle_event_early_return_emission_is_bounded_at_enqueue() {
         );
         assert!(
             session.event_queue.iter().any(
-                |event| matches!(event, FortressEvent::Disconnected { addr } if *addr == unknown)
+                |event| matches!(&event.event, FortressEvent::Disconnected { addr } if *addr == unknown)
             ),
             "the durable Disconnected event must survive routine filler pressure"
         );
@@ -14282,6 +17943,7 @@ fn create_three_player_desync_session() -> P2PSession<TestConfig> {
     ///
     /// NON-VACUITY: deleting the `let _violation_scope = self.scoped_violation_observer();`
     /// guard from `advance_frame` leaves the observer empty and fails this test.
+    #[cfg(feature = "telemetry")]
     #[test]
     fn p2p_advance_frame_routes_report_violation_to_session_observer() {
         let observer = Arc::new(crate::telemetry::CollectingObserver::new());
@@ -14543,7 +18205,7 @@ fn graceful_drop_invalidates_stale_local_checksums_above_freeze_frame() {
             !session
                 .event_queue
                 .iter()
-                .any(|e| matches!(e, FortressEvent::DesyncDetected { .. })),
+                .any(|e| matches!(&e.event, FortressEvent::DesyncDetected { .. })),
             "no false-positive DesyncDetected after stale checksum invalidation"
         );
     }
@@ -14616,6 +18278,7 @@ fn exit(&self, _: &tracing::span::Id) {}
     /// — with two endpoints we cannot tell which side is wrong, so dropping a
     /// peer risks removing the honest one. The count is per-peer, so a
     /// misbehaving peer does not taint a well-behaved one.
+    #[cfg(feature = "telemetry")]
     #[test]
     fn persistent_checksum_mismatch_counts_warns_once_and_never_ejects() {
         let threshold = CHECKSUM_MISMATCH_TRUST_DOWNGRADE_THRESHOLD;
@@ -14706,6 +18369,7 @@ fn persistent_checksum_mismatch_counts_warns_once_and_never_ejects() {
     /// the exact threshold value even on an overshooting batch, so the equality
     /// can never be skipped. (A future refactor to `+= n` would silently break
     /// this; the test locks it in.)
+    #[cfg(feature = "telemetry")]
     #[test]
     fn checksum_mismatch_warning_fires_once_on_batch_overshooting_threshold() {
         let threshold = CHECKSUM_MISMATCH_TRUST_DOWNGRADE_THRESHOLD;
@@ -14883,10 +18547,22 @@ fn checksum_harvest_uses_exact_frame_to_send_cell_not_a_later_cell() {
             Some(expected),
             "harvest must take the checksum of the cell at frame_to_send, not a later cell"
         );
+        // The same call catches up through the confirmed frame, each grid
+        // frame from its own cell.
         assert_eq!(
-            session.last_sent_checksum_frame, frame_to_send,
-            "harvest of a confirmed, exact-match cell advances the send cursor"
+            session.last_sent_checksum_frame,
+            Frame::new(3),
+            "harvest of confirmed, exact-match cells advances the send cursor"
         );
+        for f in 2..=3u128 {
+            assert_eq!(
+                session
+                    .local_checksum_history
+                    .get(&Frame::new(f as i32))
+                    .copied(),
+                Some(0xC0DE_0000 + f)
+            );
+        }
         // Sanity: the later (current_frame) cell carried a DIFFERENT checksum, so
         // the equality above genuinely discriminates wrong-cell reads.
         assert_ne!(expected, 0xC0DE_0000 + 5u128);
@@ -14969,27 +18645,18 @@ fn checksum_harvest_skips_speculative_cell_above_last_confirmed_frame() {
     /// harvest's EXACT-MATCH `saved_state_by_frame` behavior.
     ///
     /// In sparse mode only a single checkpoint is kept (at the confirmed frame);
-    /// the other ring slots hold stale frames from earlier saves. The harvest must
-    /// rely on `saved_state_by_frame` returning a cell ONLY on an EXACT frame
-    /// match: when `frame_to_send` has no matching checkpoint, the lookup returns
-    /// `None` and the harvest is SKIPPED — it must never read a stale/gap cell that
-    /// happens to occupy `frame_to_send`'s ring slot.
+    /// the other ring slots hold stale frames from earlier saves. The sparse
+    /// schedule reports the checkpoint itself, labeled with the frame its cell
+    /// holds, and must never read a stale/gap cell through the ring slot of a
+    /// grid frame.
     ///
     /// Construction: a sparse session whose ring has `MAX_PREDICTION + 1 = 9`
-    /// cells, so frame 10 maps to the SAME ring slot as `frame_to_send = 1`. We
-    /// stamp a single checksummed checkpoint at frame 10 (a "stale" wrapped cell,
-    /// from frame 1's perspective) and leave the rest of the ring at defaults.
+    /// cells, so frame 10 maps to the SAME ring slot as grid frame 1. We stamp
+    /// a single checksummed checkpoint at frame 10 (a "stale" wrapped cell, from
+    /// frame 1's perspective) and leave the rest of the ring at defaults.
     /// `saved_state_by_frame(1)` then finds a cell whose stored frame is 10, not 1
-    /// — an EXACT-MATCH MISS — so it returns `None` and the harvest is skipped.
-    /// Crucially the wrapped cell CARRIES a checksum, so a guard-less lookup would
-    /// genuinely harvest a wrong value (not a no-op default cell).
-    ///
-    /// Non-vacuity (verified by probe): replacing the `saved_state_by_frame`
-    /// exact-match guard with a raw `Some(cell)` (i.e. dropping the
-    /// `cell_frame == frame` check in `src/sync_layer/mod.rs` ~1305) turns this RED
-    /// — the harvest would then read the stale wrapped cell at slot 1 and store its
-    /// checksum (`0x5A5A_000A`) at frame 1. With the real exact-match guard, no
-    /// entry is stored.
+    /// — an EXACT-MATCH MISS — so nothing is stored for frame 1, while the
+    /// checkpoint's checksum (`0x5A5A_000A`) is reported as frame 10.
     #[test]
     fn checksum_harvest_sparse_exact_match_miss_skips_no_stale_read() {
         const MAX_PREDICTION: usize = 8; // ring size = MAX_PREDICTION + 1 = 9
@@ -15054,17 +18721,17 @@ fn checksum_harvest_sparse_exact_match_miss_skips_no_stale_read() {
 
         session.check_checksum_send_interval();
 
-        // Exact-match miss => harvest skipped: no history entry at frame 1. (The
-        // production `report_violation!` Warning on the `None` branch is expected
-        // here and is NOT a panic.) The cursor stays put so frame 1 is retried.
+        // The checkpoint is reported under its own frame; the wrapped slot is
+        // never read as frame 1.
         assert!(
             !session.local_checksum_history.contains_key(&frame_to_send),
-            "an exact-match miss must skip the harvest, never read a stale ring cell"
+            "a sparse report must never read a stale ring cell as a grid frame"
         );
-        assert!(
-            session.last_sent_checksum_frame.is_null(),
-            "send cursor must not advance on an exact-match miss"
+        assert_eq!(
+            session.local_checksum_history.get(&Frame::new(10)),
+            Some(&0x5A5A_000A)
         );
+        assert_eq!(session.last_sent_checksum_frame, Frame::new(10));
     }
 
     /// A saved cell can legitimately have no checksum when the application did
@@ -15161,6 +18828,126 @@ fn missing_local_checksum_still_prunes_history_by_scheduled_frame() {
         );
     }
 
+    /// When the confirmed frame jumps past several grid frames between calls,
+    /// one call reports all of them, so the schedule never falls behind the
+    /// saved-state ring.
+    #[test]
+    fn checksum_send_catches_up_every_confirmed_grid_frame_in_one_call() {
+        let mut session: P2PSession<TestConfig> = SessionBuilder::new()
+            .with_num_players(2)
+            .expect("num_players")
+            .with_desync_detection_mode(DesyncDetection::On { interval: 2 })
+            .add_player(PlayerType::Local, PlayerHandle::new(0))
+            .expect("local player")
+            .add_player(PlayerType::Remote(test_addr(8080)), PlayerHandle::new(1))
+            .expect("remote player")
+            .start_p2p_session(DummySocket)
+            .expect("session");
+
+        for f in 0..=7 {
+            let request = session.sync_layer.save_current_state();
+            if let FortressRequest::SaveGameState { cell, frame } = request {
+                assert_eq!(frame, Frame::new(f));
+                assert!(cell.save(frame, Some(0u8), Some(0x100 + f as u128)));
+            }
+            session.sync_layer.advance_frame();
+        }
+        session
+            .sync_layer
+            .set_last_confirmed_frame(Frame::new(7), session.save_mode);
+
+        session.check_checksum_send_interval();
+
+        assert_eq!(session.last_sent_checksum_frame, Frame::new(6));
+        assert_eq!(
+            session
+                .local_checksum_history
+                .iter()
+                .map(|(&frame, &checksum)| (frame.as_i32(), checksum))
+                .collect::<Vec<_>>(),
+            vec![(2, 0x102), (4, 0x104), (6, 0x106)]
+        );
+
+        // Nothing new is confirmed, so a second call reports nothing again.
+        session.check_checksum_send_interval();
+        assert_eq!(session.local_checksum_history.len(), 3);
+    }
+
+    /// Under sparse saving the interval counts saved and confirmed frames:
+    /// every second checkpoint is reported under the frame its cell holds,
+    /// whatever the checkpoints' spacing. A checkpoint that is not yet
+    /// confirmed, or has no checksum yet, is counted once it qualifies.
+    #[test]
+    fn sparse_checksum_send_counts_saved_and_confirmed_frames() {
+        let mut session: P2PSession<TestConfig> = SessionBuilder::new()
+            .with_num_players(2)
+            .expect("num_players")
+            .with_desync_detection_mode(DesyncDetection::On { interval: 2 })
+            .with_save_mode(SaveMode::Sparse)
+            .add_player(PlayerType::Local, PlayerHandle::new(0))
+            .expect("local player")
+            .add_player(PlayerType::Remote(test_addr(8080)), PlayerHandle::new(1))
+            .expect("remote player")
+            .start_p2p_session(DummySocket)
+            .expect("session");
+
+        let checkpoint = |session: &mut P2PSession<TestConfig>, to: i32, checksum| {
+            while session.sync_layer.current_frame() < Frame::new(to) {
+                session.sync_layer.advance_frame();
+            }
+            if let FortressRequest::SaveGameState { cell, frame } =
+                session.sync_layer.save_current_state()
+            {
+                assert!(cell.save(frame, Some(0u8), checksum));
+            }
+        };
+        let reported = |session: &P2PSession<TestConfig>| {
+            session
+                .local_checksum_history
+                .iter()
+                .map(|(&frame, &checksum)| (frame.as_i32(), checksum))
+                .collect::<Vec<_>>()
+        };
+
+        // Irregularly spaced checkpoints; no frame besides 9 is on the grid.
+        for (frame, checksum) in [(3, 0x103), (9, 0x109), (17, 0x117)] {
+            checkpoint(&mut session, frame, Some(checksum));
+            session
+                .sync_layer
+                .set_last_confirmed_frame(Frame::new(frame), session.save_mode);
+            session.check_checksum_send_interval();
+            // A repeated call does not count the same checkpoint twice.
+            session.check_checksum_send_interval();
+        }
+        assert_eq!(reported(&session), vec![(9, 0x109)]);
+        assert_eq!(session.last_sent_checksum_frame, Frame::new(9));
+
+        // Saved but not yet confirmed: not counted.
+        checkpoint(&mut session, 21, Some(0x121));
+        session.check_checksum_send_interval();
+        assert_eq!(session.saved_frames_since_checksum, 1);
+        session
+            .sync_layer
+            .set_last_confirmed_frame(Frame::new(21), session.save_mode);
+        session.check_checksum_send_interval();
+        assert_eq!(reported(&session), vec![(9, 0x109), (21, 0x121)]);
+
+        // Confirmed without a checksum: retried once the checksum is there.
+        checkpoint(&mut session, 26, None);
+        session
+            .sync_layer
+            .set_last_confirmed_frame(Frame::new(26), session.save_mode);
+        session.check_checksum_send_interval();
+        assert_eq!(session.saved_frames_since_checksum, 0);
+        let cell = session
+            .sync_layer
+            .saved_state_by_frame(Frame::new(26))
+            .expect("checkpoint");
+        assert!(cell.save(Frame::new(26), Some(0u8), Some(0x126)));
+        session.check_checksum_send_interval();
+        assert_eq!(session.saved_frames_since_checksum, 1);
+    }
+
     #[test]
     fn checksum_schedule_extreme_config_saturates_without_negative_frames_or_overflow() {
         assert_eq!(
@@ -17077,6 +20864,65 @@ fn remote_slot_confirmed_bound_mesh_agreed_disconnect_excludes_slot() {
         );
     }
 
+    /// Per-player accessors over the same setup: ties go to the lowest handle,
+    /// and a mesh-agreed slot reports its freeze frame but never limits.
+    #[test]
+    fn confirmed_frame_for_and_limiting_player_follow_the_slot_fold() {
+        let (mut session, addr_b, addr_c, addr_d) = build_abcd_live_session();
+        let c = PlayerHandle::new(2);
+
+        // Nothing received yet: every slot ties at NULL.
+        assert_eq!(session.limiting_player(), Some(PlayerHandle::new(0)));
+        for idx in 0..4 {
+            assert_eq!(
+                session.confirmed_frame_for(PlayerHandle::new(idx)),
+                Some(session.confirmed_frame())
+            );
+        }
+        assert_eq!(session.confirmed_frame_for(PlayerHandle::new(4)), None);
+
+        session
+            .player_reg
+            .remotes
+            .get_mut(&addr_c)
+            .expect("C endpoint")
+            .disconnect_remote();
+        session.local_connect_status[c.as_usize()] = ConnectionStatus {
+            disconnected: true,
+            last_frame: Frame::new(5),
+            epoch: 0,
+        };
+        for addr in [addr_b, addr_d] {
+            session
+                .player_reg
+                .remotes
+                .get_mut(&addr)
+                .expect("survivor endpoint")
+                .set_peer_connect_status_for_tests(
+                    c,
+                    ConnectionStatus {
+                        disconnected: true,
+                        last_frame: Frame::new(5),
+                        epoch: 0,
+                    },
+                );
+        }
+
+        assert_eq!(session.confirmed_frame_for(c), Some(Frame::new(5)));
+        assert_eq!(session.limiting_player(), Some(PlayerHandle::new(0)));
+        assert_eq!(
+            session
+                .slot_confirmed_frames()
+                .map(|(handle, _)| handle)
+                .collect::<Vec<_>>(),
+            vec![
+                PlayerHandle::new(0),
+                PlayerHandle::new(1),
+                PlayerHandle::new(3)
+            ]
+        );
+    }
+
     /// (b) Pre-agreement hold: the slot is locally disconnected (frozen high on
     /// direct detection) but one running endpoint still reports it connected ->
     /// `Some(min over gossiped views ONLY)` (the local term is dropped — GGPO
@@ -18838,6 +22684,46 @@ fn max_frame_advantage_excludes_disconnected_multi_handle_counts_connected_runni
         );
     }
 
+    /// `frame_advantages` lists every connected handle of a multi-handle
+    /// endpoint with the endpoint's average, and `recommended_skip_frames`
+    /// only recommends waits from `MIN_RECOMMENDATION` up.
+    #[test]
+    fn frame_advantages_list_connected_handles_and_skip_frames_respect_threshold() {
+        let mut session = create_multi_handle_remote_session();
+        let addr = test_addr(8080);
+        let seed = |session: &mut P2PSession<TestConfig>, advantage| {
+            let endpoint = session
+                .player_reg
+                .remotes
+                .get_mut(&addr)
+                .expect("multi-handle endpoint must exist");
+            endpoint.force_running_for_tests();
+            endpoint.seed_frame_advantage_for_tests(advantage);
+        };
+        for handle in [1, 2] {
+            session.local_connect_status[handle] = ConnectionStatus {
+                disconnected: false,
+                last_frame: Frame::new(10),
+                epoch: 0,
+            };
+        }
+
+        seed(&mut session, 5);
+        assert_eq!(
+            session.frame_advantages(),
+            vec![(PlayerHandle::new(1), 5), (PlayerHandle::new(2), 5)]
+        );
+        assert_eq!(session.recommended_skip_frames(), 5);
+
+        seed(&mut session, MIN_RECOMMENDATION as i32 - 1);
+        assert_eq!(session.recommended_skip_frames(), 0);
+        seed(&mut session, -4);
+        assert_eq!(session.recommended_skip_frames(), 0);
+
+        session.local_connect_status[2].disconnected = true;
+        assert_eq!(session.frame_advantages(), vec![(PlayerHandle::new(1), -4)]);
+    }
+
     // ==========================================
     // N-peer mesh coordination tests (chunks N2-N5)
     // ==========================================
@@ -18977,7 +22863,7 @@ fn body_kind(body: &MessageBody) -> &'static str {
                 MessageBody::ChecksumReport(_) => "ChecksumReport",
                 MessageBody::FloorRequest(_) => "FloorRequest",
                 MessageBody::FloorReply(_) => "FloorReply",
-                MessageBody::KeepAlive => "KeepAlive",
+                MessageBody::KeepAlive(_) => "KeepAlive",
                 MessageBody::JoinRequest(_) => "JoinRequest",
                 MessageBody::StateSnapshot(_) => "StateSnapshot",
                 MessageBody::StateSnapshotAck(_) => "StateSnapshotAck",
@@ -18991,6 +22877,13 @@ fn body_kind(body: &MessageBody) -> &'static str {
                 MessageBody::DropBackfill(_) => "DropBackfill",
                 MessageBody::DropCommit(_) => "DropCommit",
                 MessageBody::DropAbort(_) => "DropAbort",
+                MessageBody::MatchPause(_) => "MatchPause",
+                MessageBody::MatchPauseAck(_) => "MatchPauseAck",
+                MessageBody::StreamEnd(_) => "StreamEnd",
+                MessageBody::ReliableMessage(_) => "ReliableMessage",
+                MessageBody::ReliableAck(_) => "ReliableAck",
+                MessageBody::LinkClosed(_) => "LinkClosed",
+                MessageBody::ChecksumRequest(_) => "ChecksumRequest",
             }
         }
 
@@ -19156,7 +23049,7 @@ fn apply_requests_disconnect_folding(
                     FortressRequest::LoadGameState { cell, .. } => {
                         shadow.state = cell.load().expect("loaded cell must hold a state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         shadow.state = next_state_disconnect_folding(shadow.state, inputs);
                     },
                 }
@@ -19173,7 +23066,7 @@ fn apply_requests(requests: &RequestVec<TestConfig>, shadow: &mut Shadow) {
                     FortressRequest::LoadGameState { cell, .. } => {
                         shadow.state = cell.load().expect("loaded cell must hold a state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         shadow.state = next_state(shadow.state, inputs);
                     },
                 }
@@ -19363,7 +23256,7 @@ fn pump_collecting_inputs(&mut self, sink: &mut BTreeMap<i32, BTreeMap<usize, u8
                     }
                 }
                 for proto in self.protos.values_mut() {
-                    for event in proto.poll(&self.status) {
+                    for (_, event) in proto.poll(&self.status) {
                         if let Event::Input { input, player, .. } = event {
                             sink.entry(input.frame.as_i32())
                                 .or_default()
@@ -19393,7 +23286,7 @@ fn send_input(&mut self, frame: Frame, value: u8) {
                 for status in &mut self.status {
                     status.last_frame = frame;
                 }
-                let mut inputs = BTreeMap::new();
+                let mut inputs = VecMap::new();
                 inputs.insert(PlayerHandle::new(2), PlayerInput::new(frame, value));
                 let status = self.status.clone();
                 for proto in self.protos.values_mut() {
@@ -20859,7 +24752,7 @@ fn npeer_post_ack_abort_refreezes_survivor_byte_identically() {
                     .expect("B local input");
                 let requests = duo.b.advance_frame().expect("B advance");
                 for request in requests.iter() {
-                    if let FortressRequest::AdvanceFrame { inputs } = request {
+                    if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                         let (value, status) = inputs[2];
                         assert_eq!(
                             value, C_FROZEN_INPUT,
@@ -22532,7 +26425,7 @@ fn assert_sub_f_rollback_serves_frozen_disconnected(
                             load_frame = *frame;
                             cursor = *frame;
                         },
-                        FortressRequest::AdvanceFrame { inputs } => {
+                        FortressRequest::AdvanceFrame { inputs, .. } => {
                             if !cursor.is_null() {
                                 let (value, status) = inputs[2];
                                 if cursor < serve_f {
@@ -25301,7 +29194,7 @@ fn npeer_joiner_buffers_snapshot_and_applies_only_on_matching_commit() {
                 ),
             }
             match requests.get(1) {
-                Some(FortressRequest::AdvanceFrame { inputs }) => {
+                Some(FortressRequest::AdvanceFrame { inputs, .. }) => {
                     assert_eq!(
                         inputs.as_slice(),
                         &[
@@ -26707,7 +30600,7 @@ fn npeer_out_of_sequence_input_above_floor_keeps_error_severity() {
                     Event::Input {
                         input: PlayerInput::new(Frame::new(claim.as_i32() + 5), 7),
                         player: PlayerHandle::new(0),
-                        peer_connect_status: vec![ConnectionStatus::default(); 3],
+                        peer_connect_status: vec![ConnectionStatus::default(); 3].into(),
                     },
                     Arc::clone(&handles),
                     addr_a(),
@@ -26727,7 +30620,7 @@ fn npeer_out_of_sequence_input_above_floor_keeps_error_severity() {
                     Event::Input {
                         input: PlayerInput::new(Frame::new(serve_s.as_i32() - 1), 7),
                         player: PlayerHandle::new(0),
-                        peer_connect_status: vec![ConnectionStatus::default(); 3],
+                        peer_connect_status: vec![ConnectionStatus::default(); 3].into(),
                     },
                     Arc::clone(&handles),
                     addr_a(),
@@ -27806,7 +31699,7 @@ fn npeer_joiner_apply_stamps_connect_status_from_carried_statuses() {
             // same predicate.
             let requests = c2.session.advance_frame().expect("post-commit advance");
             match requests.get(1) {
-                Some(FortressRequest::AdvanceFrame { inputs }) => {
+                Some(FortressRequest::AdvanceFrame { inputs, .. }) => {
                     assert_eq!(
                         inputs.as_slice(),
                         &[
@@ -30102,10 +33995,11 @@ fn npeer_quad_serve_defers_capture_for_slot_frozen_above_snapshot_frame_until_co
             let stop_frame = duo.a.sync_layer.last_saved_frame();
             assert!(stop_frame.as_i32() > 0, "staging: the mesh advanced");
 
-            // Hide D's further progress from B (claims ride Input packets
-            // exclusively): B's receipt of D pins at the settled frame while
-            // A's keeps rising.
+            // Hide D's further progress from B (claims ride Input and
+            // KeepAlive packets): B's receipt of D pins at the settled frame
+            // while A's keeps rising.
             duo.bus.block(addr_d(), addr_b(), "Input");
+            duo.bus.block(addr_d(), addr_b(), "KeepAlive");
 
             // B and D run ahead alone under their prediction caps; D's
             // post-stop inputs reach ONLY A.
@@ -30142,11 +34036,12 @@ fn npeer_quad_serve_defers_capture_for_slot_frozen_above_snapshot_frame_until_co
             );
 
             // B's freeze claim must not reach A until the convergence leg:
-            // block B->A Input (the gate folds B's already-cached claims;
-            // ReactivateSlotAck still flows for the barrier), then both drop
-            // D — A at its HIGH receipt (the above-S freeze under test), B
-            // at its low one (the eventual converged truth).
+            // block B->A Input and KeepAlive (the gate folds B's already-
+            // cached claims; ReactivateSlotAck still flows for the barrier),
+            // then both drop D — A at its HIGH receipt (the above-S freeze
+            // under test), B at its low one (the eventual converged truth).
             duo.bus.block(addr_b(), addr_a(), "Input");
+            duo.bus.block(addr_b(), addr_a(), "KeepAlive");
             stage_uncoordinated_drop_for_hot_join_test(
                 &mut duo.a,
                 PlayerHandle::new(3),
@@ -30165,6 +34060,7 @@ fn npeer_quad_serve_defers_capture_for_slot_frozen_above_snapshot_frame_until_co
             // so nothing sends from `addr_d` again and the filter entry is
             // inert — removed for block/unblock hygiene only.
             duo.bus.unblock(addr_d(), addr_b(), "Input");
+            duo.bus.unblock(addr_d(), addr_b(), "KeepAlive");
             duo.a_events.extend(duo.a.events());
             duo.b_events.extend(duo.b.events());
             let a_freeze = duo
@@ -30244,6 +34140,7 @@ fn npeer_quad_serve_defers_capture_for_slot_frozen_above_snapshot_frame_until_co
             // the reserved-slot converge arm mines A's freeze down to <= S;
             // the SAME serve captures and the join completes.
             duo.bus.unblock(addr_b(), addr_a(), "Input");
+            duo.bus.unblock(addr_b(), addr_a(), "KeepAlive");
             for i in 0..300_u32 {
                 poll_round_real(&mut duo, &mut c2);
                 if i % 3 == 2 {
@@ -30868,7 +34765,7 @@ fn run_no_desync_battery_variant(variant: &BatteryVariant) {
             // machinery. Advances are PRODUCTION-FAITHFUL tolerant calls
             // (`try_advance_capped`): after a quiescent outage window the
             // peers' mutual gossip claims are stale at the pre-outage frame
-            // (claims ride Input packets exclusively), and a driver that
+            // (claims ride Input and KeepAlive packets), and a driver that
             // stops short of the real prediction cap would jam on them —
             // each successful advance up to the cap sends a fresh
             // claim-bearing Input, which is exactly how a real app's
@@ -0,0 +1,345 @@
+//! A sans-IO driver for [`P2PSession`].
+//!
+//! [`P2PSessionCore`] runs the same session state machine as a
+//! [`P2PSession`], but never touches a socket or a clock. Each
+//! [`tick`](P2PSessionCore::tick) takes the time, the datagrams received since
+//! the last tick and the local inputs, and returns the datagrams to send, the
+//! requests to fulfill and the events raised. The caller decides when to tick
+//! and how packets travel, which suits `requestAnimationFrame` loops, job
+//! systems and deterministic tests.
+//!
+//! The core owns a `P2PSession` built on an in-memory mailbox socket whose
+//! protocol clock reads the time passed to `tick`. One tick does exactly what
+//! a loop driving a `P2PSession` directly would do with the same inputs:
+//! [`poll_remote_clients`](P2PSession::poll_remote_clients), then
+//! [`add_local_input`](P2PSession::add_local_input) and
+//! [`advance_frame`](P2PSession::advance_frame) if inputs were given, so the
+//! two behave identically.
+
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use web_time::Instant;
+
+use crate::network::messages::Message;
+use crate::sessions::config::ClockFn;
+use crate::sessions::p2p_session::P2PSession;
+use crate::{
+    Config, FortressError, FortressEvent, FortressResult, NonBlockingSocket, PlayerHandle,
+    RequestVec, SessionState,
+};
+
+/// Datagrams waiting to be delivered to the session or handed to the caller.
+pub(crate) struct Mailbox<A> {
+    /// Received by the caller, not yet polled by the session.
+    inbox: Vec<(A, Message)>,
+    /// Sent by the session, not yet returned from a tick.
+    outbox: Vec<(A, Message)>,
+}
+
+/// The socket of the core's session: the session reads its inbox and writes
+/// its outbox.
+pub(crate) struct MailboxSocket<A> {
+    mailbox: Arc<Mutex<Mailbox<A>>>,
+}
+
+impl<A: Clone> MailboxSocket<A> {
+    fn send(&self, msg: &Message, addr: &A) {
+        self.mailbox.lock().outbox.push((addr.clone(), msg.clone()));
+    }
+
+    fn receive(&self) -> Vec<(A, Message)> {
+        std::mem::take(&mut self.mailbox.lock().inbox)
+    }
+}
+
+#[cfg(feature = "sync-send")]
+impl<A: Clone + Eq + Hash + Send + Sync> NonBlockingSocket<A> for MailboxSocket<A> {
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        self.send(msg, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        self.receive()
+    }
+}
+
+#[cfg(not(feature = "sync-send"))]
+impl<A: Clone + Eq + Hash> NonBlockingSocket<A> for MailboxSocket<A> {
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        self.send(msg, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        self.receive()
+    }
+}
+
+/// Everything one [`P2PSessionCore::tick`] produced.
+pub struct TickOutput<T: Config> {
+    /// Datagrams the session sent, with their destination, in send order.
+    pub to_send: Vec<(T::Address, Message)>,
+    /// Requests to fulfill in order, as returned by
+    /// [`P2PSession::advance_frame`]. Empty if the tick did not advance.
+    pub requests: RequestVec<T>,
+    /// Events raised since the previous tick, oldest first.
+    pub events: Vec<FortressEvent<T>>,
+    /// Why the tick's advance failed, if it did. The datagrams and events are
+    /// returned regardless; [`FortressError::PredictionThreshold`] means the
+    /// session is waiting for remote inputs and the inputs should be given
+    /// again on a later tick.
+    pub error: Option<FortressError>,
+}
+
+impl<T: Config> fmt::Debug for TickOutput<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TickOutput")
+            .field("to_send", &self.to_send.len())
+            .field("requests", &self.requests.len())
+            .field("events", &self.events.len())
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// A [`P2PSession`] driven by explicit ticks instead of a socket and a clock.
+///
+/// Created by
+/// [`SessionBuilder::start_p2p_session_core`](crate::SessionBuilder::start_p2p_session_core).
+/// Read-only queries go through [`session`](Self::session).
+///
+/// # Example
+///
+/// ```
+/// # use fortress_rollback::{Config, PlayerHandle, PlayerType, SessionBuilder};
+/// # use std::net::SocketAddr;
+/// # use web_time::{Duration, Instant};
+/// # struct MyConfig;
+/// # impl Config for MyConfig {
+/// #     type Input = u8;
+/// #     type State = u8;
+/// #     type Address = SocketAddr;
+/// # }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let start = Instant::now();
+/// let mut core = SessionBuilder::<MyConfig>::new()
+///     .add_player(PlayerType::Local, PlayerHandle::new(0))?
+///     .add_player(PlayerType::Remote("127.0.0.1:7001".parse()?), PlayerHandle::new(1))?
+///     .start_p2p_session_core(start)?;
+///
+/// // No datagrams yet; the session starts synchronizing with its peer.
+/// let output = core.tick(start + Duration::from_millis(16), &[], &[]);
+/// assert!(output.to_send.iter().all(|(addr, _)| addr.port() == 7001));
+/// assert!(output.requests.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub struct P2PSessionCore<T: Config> {
+    session: P2PSession<T>,
+    /// The time the session's protocol clock reports.
+    now: Arc<Mutex<Instant>>,
+    mailbox: Arc<Mutex<Mailbox<T::Address>>>,
+}
+
+impl<T: Config> P2PSessionCore<T> {
+    /// Starts a core at `now`: `start` builds the session from the core's
+    /// clock and socket.
+    pub(crate) fn start(
+        now: Instant,
+        start: impl FnOnce(ClockFn, MailboxSocket<T::Address>) -> FortressResult<P2PSession<T>>,
+    ) -> FortressResult<Self> {
+        let now = Arc::new(Mutex::new(now));
+        let mailbox = Arc::new(Mutex::new(Mailbox {
+            inbox: Vec::new(),
+            outbox: Vec::new(),
+        }));
+        let clock_now = Arc::clone(&now);
+        let clock: ClockFn = Arc::new(move || *clock_now.lock());
+        let session = start(
+            clock,
+            MailboxSocket {
+                mailbox: Arc::clone(&mailbox),
+            },
+        )?;
+        Ok(Self {
+            session,
+            now,
+            mailbox,
+        })
+    }
+
+    /// Runs one step of the session at `now`.
+    ///
+    /// Delivers `received` to the session and polls it, exactly like
+    /// [`P2PSession::poll_remote_clients`]. If `local_inputs` is not empty and
+    /// the session is running, it then adds them and advances a frame, like
+    /// [`P2PSession::add_local_input`] and [`P2PSession::advance_frame`]; an
+    /// empty `local_inputs` only polls. A `now` earlier than the previous
+    /// tick's is treated as the previous tick's.
+    pub fn tick(
+        &mut self,
+        now: Instant,
+        received: &[(T::Address, Message)],
+        local_inputs: &[(PlayerHandle, T::Input)],
+    ) -> TickOutput<T> {
+        {
+            let mut clock = self.now.lock();
+            *clock = (*clock).max(now);
+        }
+        self.mailbox.lock().inbox.extend_from_slice(received);
+
+        self.session.poll_remote_clients();
+        let mut requests = RequestVec::new();
+        let mut error = None;
+        if !local_inputs.is_empty() && self.session.current_state() == SessionState::Running {
+            match self.advance(local_inputs) {
+                Ok(advanced) => requests = advanced,
+                Err(err) => error = Some(err),
+            }
+        }
+
+        TickOutput {
+            to_send: std::mem::take(&mut self.mailbox.lock().outbox),
+            requests,
+            events: self.session.events().collect(),
+            error,
+        }
+    }
+
+    fn advance(
+        &mut self,
+        local_inputs: &[(PlayerHandle, T::Input)],
+    ) -> FortressResult<RequestVec<T>> {
+        for (handle, input) in local_inputs {
+            self.session.add_local_input(*handle, *input)?;
+        }
+        self.session.advance_frame()
+    }
+
+    /// The wrapped session, for queries such as
+    /// [`current_frame`](P2PSession::current_frame) or
+    /// [`network_stats`](P2PSession::network_stats).
+    #[must_use]
+    pub fn session(&self) -> &P2PSession<T> {
+        &self.session
+    }
+
+    /// The time of the latest tick, as the session's clock reports it.
+    #[must_use]
+    pub fn now(&self) -> Instant {
+        *self.now.lock()
+    }
+}
+
+impl<T: Config> fmt::Debug for P2PSessionCore<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("P2PSessionCore")
+            .field("session", &self.session)
+            .field("now", &self.now())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+    use crate::{FortressRequest, PlayerType, SessionBuilder};
+    use std::net::SocketAddr;
+    use web_time::Duration;
+
+    struct TestConfig;
+
+    impl Config for TestConfig {
+        type Input = u8;
+        type State = u8;
+        type Address = SocketAddr;
+    }
+
+    fn core(
+        local: usize,
+        remote: SocketAddr,
+        start: Instant,
+    ) -> Result<P2PSessionCore<TestConfig>, FortressError> {
+        let mut builder = SessionBuilder::<TestConfig>::new();
+        for handle in 0..2 {
+            let player = if handle == local {
+                PlayerType::Local
+            } else {
+                PlayerType::Remote(remote)
+            };
+            builder = builder.add_player(player, PlayerHandle::new(handle))?;
+        }
+        builder.start_p2p_session_core(start)
+    }
+
+    #[test]
+    fn two_cores_synchronize_and_advance_by_exchanging_tick_output() {
+        let start = Instant::now();
+        let addrs: [SocketAddr; 2] = [
+            "127.0.0.1:7000".parse().unwrap(),
+            "127.0.0.1:7001".parse().unwrap(),
+        ];
+        let mut cores = [
+            core(0, addrs[1], start).unwrap(),
+            core(1, addrs[0], start).unwrap(),
+        ];
+        let mut inboxes: [Vec<(SocketAddr, Message)>; 2] = [Vec::new(), Vec::new()];
+        let mut frames = 0;
+        for step in 1..=400_u32 {
+            let now = start + Duration::from_millis(u64::from(step) * 16);
+            for index in 0..2 {
+                let running = cores[index].session().current_state() == SessionState::Running;
+                let inputs = if running {
+                    vec![(PlayerHandle::new(index), step as u8)]
+                } else {
+                    Vec::new()
+                };
+                let received = std::mem::take(&mut inboxes[index]);
+                let output = cores[index].tick(now, &received, &inputs);
+                assert!(
+                    matches!(
+                        output.error,
+                        None | Some(FortressError::PredictionThreshold)
+                    ),
+                    "{:?}",
+                    output.error
+                );
+                for (addr, message) in output.to_send {
+                    let to = usize::from(addr == addrs[1]);
+                    inboxes[to].push((addrs[index], message));
+                }
+                for request in &output.requests {
+                    match request {
+                        FortressRequest::SaveGameState { cell, frame } => {
+                            cell.save(*frame, Some(0), None);
+                        },
+                        FortressRequest::AdvanceFrame { .. } if index == 0 => frames += 1,
+                        _ => {},
+                    }
+                }
+            }
+            assert_eq!(cores[0].now(), now);
+        }
+        assert!(frames > 300, "advanced only {frames} frames");
+        assert!(cores[1].session().confirmed_frame().as_i32() > 300);
+    }
+
+    #[test]
+    fn time_never_goes_back() {
+        let start = Instant::now();
+        let mut core = core(0, "127.0.0.1:7001".parse().unwrap(), start).unwrap();
+        let later = start + Duration::from_secs(1);
+        let output = core.tick(later, &[], &[]);
+        assert!(output.error.is_none());
+        core.tick(start, &[], &[]);
+        assert_eq!(core.now(), later);
+    }
+}
@@ -3,8 +3,14 @@
 use std::sync::Arc;
 
 use crate::error::{allocation_failed, try_reserve_hint};
-#[cfg(test)]
+use crate::sessions::config::ClockFn;
+#[cfg(all(test, feature = "telemetry"))]
 use crate::sessions::event_drain::remove_event_for_overflow;
+use crate::sessions::frame_progress::{FramePacer, FrameProgress};
+use crate::sessions::previous_inputs::PreviousInputs;
+use crate::sessions::session_health::{HealthTracker, SpectatorHealth};
+#[cfg(not(target_family = "wasm"))]
+use crate::sessions::sync_wait::{wait_until_running, SyncWaitError};
 use crate::{
     frame_info::PlayerInput,
     network::{
@@ -12,13 +18,13 @@
         protocol::{Event, UdpProtocol},
     },
     report_violation, report_violation_to,
-    sessions::event_drain::enqueue_event_bounded,
+    sessions::event_drain::{enqueue_event_bounded, TimestampedEvent, TimestampedEventDrain},
     sessions::session_trait::Session,
     telemetry::{ViolationKind, ViolationObserver, ViolationSeverity},
     Config, EventDrain, FortressError, FortressEvent, FortressRequest, FortressResult, Frame,
     GameStateCell, InputStatus, InputVec, InternalErrorKind, InvalidFrameReason,
-    InvalidRequestKind, NetworkStats, NonBlockingSocket, PeerMetrics, PlayerHandle, RequestVec,
-    SessionMetrics, SessionState,
+    InvalidRequestKind, MemoryReport, MemoryUsage, NetworkStats, NonBlockingSocket, PeerMetrics,
+    PlayerHandle, RequestVec, SessionMetrics, SessionState,
 };
 
 /// The number of frames the spectator advances in a single step during normal operation.
@@ -80,6 +86,17 @@ struct CanonicalFrameHost<A> {
     addr: A,
 }
 
+/// The first missing frame the spectator is waiting for while a host has
+/// already sent later ones.
+#[derive(Clone, Copy)]
+struct InputGap {
+    first_missing: Frame,
+    /// When the spectator first saw the gap, on the session clock.
+    since: web_time::Instant,
+    /// Whether [`FortressEvent::SpectatorInputGap`] was emitted for it.
+    reported: bool,
+}
+
 #[derive(Clone)]
 struct SpectatorDivergenceState<A> {
     frame: Frame,
@@ -92,6 +109,17 @@ struct SpectatorDivergenceState<A> {
 /// The host will broadcast all confirmed inputs to this session.
 /// This session can be used to spectate a session without contributing to the game input.
 ///
+/// # Consistency guarantee
+///
+/// A spectator only ever simulates confirmed frames, so it never shows a
+/// mispredicted frame and never rolls back. Hosts forward a frame's inputs
+/// only once the frame is at or before their confirmed frame, never
+/// speculatively, and each frame's inputs are delivered once: the stream is
+/// append-only. Should one host still send a second, different input for a
+/// frame it already sent, the session reports an `Error` violation and keeps
+/// the first. Redundant hosts that disagree with each other fail closed with
+/// [`FortressEvent::SpectatorDivergence`].
+///
 /// This type implements the [`Session`] trait. Note that [`add_local_input`](Session::add_local_input)
 /// and [`local_player_handle_required`](Session::local_player_handle_required) return
 /// "not supported" errors, since spectators do not contribute input.
@@ -159,7 +187,13 @@ pub struct SpectatorSession<T>
     /// Index-parallel with [`Self::host_drop_witness`].
     host_status_epoch: Vec<Vec<u16>>,
     canonical_hosts: Vec<Option<CanonicalFrameHost<T::Address>>>,
-    event_queue: VecDeque<FortressEvent<T>>,
+    /// Contains all events to be forwarded to the user, stamped when enqueued.
+    event_queue: VecDeque<TimestampedEvent<T>>,
+    /// The injected protocol clock, if any; events are stamped from it.
+    clock: Option<ClockFn>,
+    /// When the session was created on [`Self::clock`]; the origin of
+    /// [`TimestampedEvent::session_ms`].
+    created_at: web_time::Instant,
     current_frame: Frame,
     last_recv_frame: Frame,
     max_frames_behind: usize,
@@ -168,16 +202,40 @@ pub struct SpectatorSession<T>
     stream_delay: usize,
     /// Whether the spectator records game state every frame to support rewind/seek.
     enable_rewind: bool,
-    /// Per-frame saved game-state ring buffer used for rewind/seek.
+    /// Whether the spectator judges the hosts' checksum reports against each
+    /// other and its own saved states (see
+    /// [`SpectatorConfig::checksum_authority`](crate::SpectatorConfig::checksum_authority)).
+    checksum_authority: bool,
+    /// Per-frame saved game-state ring buffer used for rewind/seek and
+    /// checksum authority.
     ///
-    /// Empty when [`Self::enable_rewind`] is `false`. When rewind is enabled,
-    /// its length equals [`Self::buffer_size`] and each slot is indexed by
-    /// `frame.buffer_index(buffer_size)`.
+    /// Empty unless [`Self::enable_rewind`] or [`Self::checksum_authority`]
+    /// is set. Otherwise its length equals [`Self::buffer_size`] and each slot
+    /// is indexed by `frame.buffer_index(buffer_size)`.
     state_buffer: Vec<GameStateCell<T::State>>,
+    /// Inputs of recently simulated frames, for the `previous_inputs` of each
+    /// [`FortressRequest::AdvanceFrame`].
+    previous_inputs: PreviousInputs<T::Input>,
     /// Optional observer for specification violations.
     violation_observer: Option<Arc<dyn ViolationObserver>>,
     /// Hard event-queue bound; routine events are discarded first on overflow.
     max_event_queue_size: usize,
+    /// The size of one saved game state, if the application gave it (see
+    /// `SessionBuilder::with_state_size_hint`).
+    state_size_hint: Option<usize>,
+    /// The session's final frame (see
+    /// [`with_max_session_frames`](crate::SessionBuilder::with_max_session_frames)):
+    /// inputs are applied for frames before it only.
+    max_session_frames: Frame,
+    /// Set once every frame before `max_session_frames` was simulated;
+    /// `advance_frame` refuses from then on.
+    frame_limit_reached: bool,
+    /// Highest final frame a host announced when it ended its stream.
+    stream_end_frame: Option<Frame>,
+    /// Set once every frame up to `stream_end_frame` was simulated and
+    /// `StreamEnded` was emitted; host disconnects are not reported from then
+    /// on.
+    stream_ended: bool,
     /// Cumulative, always-on session metrics (see [`SpectatorSession::metrics`]).
     metrics: SessionMetrics,
     /// Whether an event-queue-overflow `Warning` has already been reported since
@@ -193,6 +251,17 @@ pub struct SpectatorSession<T>
     /// Cross-host comparisons must ignore these hosts so same-poll failover
     /// cannot falsely latch divergence against a host that is no longer connected.
     disconnecting_hosts: Vec<usize>,
+    /// How long a gap in the canonical host's inputs may last before
+    /// [`FortressEvent::SpectatorInputGap`] is emitted; `None` never emits it.
+    input_gap_timeout: Option<web_time::Duration>,
+    /// The gap the spectator is currently waiting on, if any.
+    input_gap: Option<InputGap>,
+    /// The host's observed frame cadence and the current frame's timing, for
+    /// [`Self::frame_progress`].
+    frame_pacer: FramePacer,
+    /// Frame-rate health since the session started running, for
+    /// [`Self::session_health`].
+    health: HealthTracker,
 }
 
 impl<T: Config> SpectatorSession<T> {
@@ -214,8 +283,15 @@ pub(crate) fn new(
         catchup_speed: usize,
         stream_delay: usize,
         enable_rewind: bool,
+        previous_inputs: bool,
         violation_observer: Option<Arc<dyn ViolationObserver>>,
         event_queue_size: usize,
+        max_session_frames: Frame,
+        clock: Option<ClockFn>,
+        state_size_hint: Option<usize>,
+        input_gap_timeout: Option<web_time::Duration>,
+        checksum_authority: bool,
+        fps: usize,
     ) -> Result<Self, FortressError> {
         // host connection status
         let mut host_connect_status = Vec::new();
@@ -229,9 +305,10 @@ pub(crate) fn new(
         // Use at least 1 for buffer size to prevent panics
         let actual_buffer_size = buffer_size.max(1);
 
-        // When rewind is enabled, allocate one game-state cell per ring slot.
+        // When rewind or checksum authority is enabled, allocate one
+        // game-state cell per ring slot.
         let mut state_buffer = Vec::new();
-        if enable_rewind {
+        if enable_rewind || checksum_authority {
             state_buffer
                 .try_reserve_exact(actual_buffer_size)
                 .map_err(|_err| allocation_failed("spectator.state_buffer", actual_buffer_size))?;
@@ -317,6 +394,17 @@ pub(crate) fn new(
         event_queue
             .try_reserve_exact(event_queue_size)
             .map_err(|_err| allocation_failed("spectator.event_queue", event_queue_size))?;
+        let created_at = clock
+            .as_ref()
+            .map_or_else(web_time::Instant::now, |clock| clock());
+        // A seek reloads a frame at most `buffer_size` frames back, and the
+        // frame before the reloaded one must still be known.
+        let history = if enable_rewind {
+            actual_buffer_size.saturating_add(1)
+        } else {
+            1
+        };
+        let previous_inputs = PreviousInputs::new(previous_inputs, history)?;
 
         Ok(Self {
             state: SessionState::Synchronizing,
@@ -331,20 +419,33 @@ pub(crate) fn new(
             host_status_epoch,
             canonical_hosts,
             event_queue,
+            clock,
+            created_at,
             current_frame: Frame::NULL,
             last_recv_frame: Frame::NULL,
             max_frames_behind,
             catchup_speed,
             stream_delay,
             enable_rewind,
+            checksum_authority,
             state_buffer,
+            previous_inputs,
             violation_observer,
             max_event_queue_size: event_queue_size,
+            state_size_hint,
+            max_session_frames,
+            frame_limit_reached: false,
+            stream_end_frame: None,
+            stream_ended: false,
             metrics: SessionMetrics::new(),
             event_discard_warned: false,
             unknown_source_warned: false,
             spectator_divergence: None,
             disconnecting_hosts: Vec::new(),
+            input_gap_timeout,
+            input_gap: None,
+            frame_pacer: FramePacer::new(fps),
+            health: HealthTracker::default(),
         })
     }
 
@@ -509,6 +610,44 @@ pub fn current_state(&self) -> SessionState {
         self.state
     }
 
+    /// Blocks until the session is [`SessionState::Running`], polling every
+    /// `poll_interval`, or fails once `timeout` has elapsed.
+    ///
+    /// Meant for CLI tools and tests; see
+    /// [`P2PSession::wait_for_synchronization`](crate::P2PSession::wait_for_synchronization).
+    /// Events raised while waiting stay queued for [`events`](Self::events).
+    ///
+    /// Not available on wasm targets.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SyncWaitError`] with the handshake progress of every host
+    /// if the session is not running in time.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait_for_synchronization(
+        &mut self,
+        timeout: web_time::Duration,
+        poll_interval: web_time::Duration,
+    ) -> Result<(), SyncWaitError<T::Address>> {
+        wait_until_running(
+            self,
+            timeout,
+            poll_interval,
+            |session| {
+                session.poll_remote_clients();
+                session.state == SessionState::Running
+            },
+            |session| {
+                // alloc-bound: one entry per host, on the error path only.
+                session
+                    .hosts
+                    .iter()
+                    .map(UdpProtocol::sync_progress)
+                    .collect()
+            },
+        )
+    }
+
     /// Returns the number of frames behind the host
     #[must_use]
     pub fn frames_behind_host(&self) -> usize {
@@ -600,7 +739,18 @@ pub fn events(&mut self) -> EventDrain<'_, T> {
         // Draining starts a new overflow episode: re-arm the warning emitted by
         // `record_event_discard` when a bounded enqueue discards an event.
         self.event_discard_warned = false;
-        EventDrain::from_drain(self.event_queue.drain(..))
+        EventDrain::from_timestamped_drain(self.event_queue.drain(..))
+    }
+
+    /// Like [`events`](Self::events), but each event carries the spectator's
+    /// [`current_frame`](Self::current_frame) and the milliseconds since the
+    /// session was created when it was generated.
+    ///
+    /// Both methods drain the same queue, so use one or the other.
+    #[must_use = "events should be handled to react to session state changes"]
+    pub fn events_timestamped(&mut self) -> TimestampedEventDrain<'_, T> {
+        self.event_discard_warned = false;
+        TimestampedEventDrain::from_drain(self.event_queue.drain(..))
     }
 
     /// Returns a snapshot of this spectator's cumulative [`SessionMetrics`].
@@ -619,7 +769,10 @@ pub fn events(&mut self) -> EventDrain<'_, T> {
     /// per-kind categories a spectator never emits (for example
     /// `wait_recommendation`) stay at zero here.
     pub fn metrics(&self) -> SessionMetrics {
-        self.metrics
+        SessionMetrics {
+            decode_failures: self.socket.decode_failures(),
+            ..self.metrics
+        }
     }
 
     /// Returns an exact encoded [`PeerMetrics`] snapshot for the host at
@@ -659,6 +812,40 @@ pub fn peer_metrics(&self, host_index: usize) -> Option<PeerMetrics> {
         self.hosts.get(host_index).map(UdpProtocol::peer_metrics)
     }
 
+    /// Returns an estimate of the memory held by the session's buffers, per
+    /// component: rewind states, the input buffer, every host endpoint's
+    /// protocol buffers and the event queue.
+    ///
+    /// Rewind states (see
+    /// [`SpectatorConfig::enable_rewind`](crate::SpectatorConfig::enable_rewind))
+    /// are sized with
+    /// [`SessionBuilder::with_state_size_hint`](crate::SessionBuilder::with_state_size_hint)
+    /// and only counted without it; everything else is measured. See
+    /// [`MemoryReport`] for what each component covers.
+    pub fn memory_report(&self) -> MemoryReport {
+        let saved_states = self
+            .state_buffer
+            .iter()
+            .filter(|cell| cell.saved_frame_info().has_state)
+            .count();
+        let mut report = MemoryReport {
+            saved_states: MemoryUsage::of_hint(saved_states, self.state_size_hint),
+            input_queues: MemoryUsage::of(
+                self.inputs.iter().map(Vec::len).sum(),
+                std::mem::size_of::<PlayerInput<T::Input>>(),
+            ),
+            event_queue: MemoryUsage::of(
+                self.event_queue.len(),
+                std::mem::size_of::<TimestampedEvent<T>>(),
+            ),
+            ..MemoryReport::default()
+        };
+        for host in &self.hosts {
+            host.add_memory_usage(&mut report);
+        }
+        report
+    }
+
     /// Returns a reference to the violation observer, if one was configured.
     ///
     /// This allows checking for violations that occurred during session operations
@@ -724,6 +911,11 @@ fn advance_capacity(
     /// # Errors
     /// - Returns [`NotSynchronized`] if the session is not yet ready to accept input.
     ///   In this case, you either need to start the session or wait for synchronization between clients.
+    /// - Returns [`InvalidRequestKind::SessionFrameLimitReached`] once every frame before the
+    ///   session frame limit has been simulated.
+    ///
+    /// Once the host's stream ended (see [`Self::stream_ended`]), returns no
+    /// requests instead of [`FortressError::PredictionThreshold`].
     ///
     /// [`RequestVec`]: crate::RequestVec
     /// [`NotSynchronized`]: FortressError::NotSynchronized
@@ -741,10 +933,20 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             return Err(err);
         }
 
+        // The requests of the previous call are fulfilled by now, so every
+        // simulated frame's checksum is in its saved cell.
+        self.judge_checksums();
+
         if self.state != SessionState::Running {
             return Err(FortressError::NotSynchronized);
         }
 
+        if self.frame_limit_reached {
+            return Err(self.frame_limit_error());
+        }
+        self.health.record_advance_call(self.now());
+        let start_frame = self.current_frame;
+
         // The most recent frame the spectator may display. With stream_delay == 0
         // this is the live edge; otherwise it trails the live edge so playback is
         // held back from the host's most recent inputs.
@@ -766,7 +968,7 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
         // this stays inline; when users configure a very large catchup_speed, a
         // failed heap reservation becomes a structured error instead of an abort.
         let capacity =
-            Self::advance_capacity(frames_to_advance, self.buffer_size, self.enable_rewind);
+            Self::advance_capacity(frames_to_advance, self.buffer_size, self.saves_states());
         let mut requests = RequestVec::<T>::new();
         requests
             .try_reserve(capacity)
@@ -783,6 +985,10 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             if frame_to_grab > viewable {
                 break;
             }
+            // Inputs are never applied at or past the session frame limit.
+            if frame_to_grab >= self.max_session_frames {
+                break;
+            }
 
             match self.inputs_at_frame(frame_to_grab) {
                 Ok(synced_inputs) => {
@@ -797,7 +1003,7 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                     // SaveGameState{frame: frame_to_grab} BEFORE AdvanceFrame. The saved
                     // cell labeled F holds the state at the START of frame F, stored in
                     // state_buffer[F.buffer_index(buffer_size)].
-                    if self.enable_rewind {
+                    if self.saves_states() {
                         if let Some(cell) = self.save_cell_for(frame_to_grab) {
                             requests.push(FortressRequest::SaveGameState {
                                 cell,
@@ -806,8 +1012,11 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                         }
                     }
 
+                    let previous_inputs =
+                        self.previous_inputs.record(frame_to_grab, &synced_inputs)?;
                     requests.push(FortressRequest::AdvanceFrame {
                         inputs: synced_inputs,
+                        previous_inputs,
                     });
 
                     // advance the frame, but only after grabbing the inputs succeeded
@@ -822,16 +1031,47 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             }
         }
 
+        if !requests.is_empty() {
+            let now = self.now();
+            self.frame_pacer
+                .record_simulated(self.current_frame, viewable, now);
+            let simulated = self.current_frame.as_i32() - start_frame.as_i32();
+            self.health
+                .record_frames(u64::try_from(simulated).unwrap_or(0));
+        }
+
+        self.check_stream_end();
+
+        // Every frame before the limit has been simulated: the session is over.
+        if requests.is_empty()
+            && self.current_frame.as_i32() >= self.max_session_frames.as_i32() - 1
+        {
+            self.frame_limit_reached = true;
+            self.health.end(self.now());
+            self.enqueue_event(FortressEvent::SessionFrameLimitReached {
+                frame: self.max_session_frames,
+            });
+            return Err(self.frame_limit_error());
+        }
+
         // Preserve the historical Ok(empty) result when no advance was even attempted
         // (e.g. catchup_speed == 0 while behind). Only surface "nothing available yet"
         // as PredictionThreshold when we actually tried to advance at least one frame.
-        if frames_to_advance > 0 && requests.is_empty() {
+        // An ended stream has nothing left to wait for.
+        if frames_to_advance > 0 && requests.is_empty() && !self.stream_ended {
             return Err(FortressError::PredictionThreshold);
         }
 
         Ok(requests)
     }
 
+    fn frame_limit_error(&self) -> FortressError {
+        InvalidRequestKind::SessionFrameLimitReached {
+            frame: self.max_session_frames,
+        }
+        .into()
+    }
+
     /// Returns the rewind ring cell for `frame`, cloned so the user can save into it.
     ///
     /// Cloning shares the underlying storage (the cell is backed by an `Arc<Mutex<…>>`),
@@ -843,6 +1083,111 @@ fn save_cell_for(&self, frame: Frame) -> Option<GameStateCell<T::State>> {
         self.state_buffer.get(idx).cloned()
     }
 
+    /// Whether each advanced frame emits a `SaveGameState` request.
+    fn saves_states(&self) -> bool {
+        self.enable_rewind || self.checksum_authority
+    }
+
+    /// The checksum the application saved for `frame`, if its cell still
+    /// holds that frame.
+    fn own_checksum(&self, frame: Frame) -> Option<u128> {
+        let idx = frame.buffer_index(self.buffer_size)?;
+        let cell = self.state_buffer.get(idx)?;
+        if cell.frame() != frame {
+            return None;
+        }
+        cell.checksum()
+    }
+
+    /// Judges, oldest first, every simulated frame each host has reported a
+    /// checksum for, then drops those reports and any older ones: a frame
+    /// some host never reported is skipped once a later one is judged.
+    fn judge_checksums(&mut self) {
+        if !self.checksum_authority {
+            return;
+        }
+        let mut judged = Frame::NULL;
+        loop {
+            let next = self.hosts.first().and_then(|first| {
+                first.pending_checksums.keys().copied().find(|&frame| {
+                    frame > judged
+                        && frame <= self.current_frame
+                        && self
+                            .hosts
+                            .iter()
+                            .all(|host| host.pending_checksums.contains_key(&frame))
+                })
+            });
+            let Some(frame) = next else {
+                break;
+            };
+            self.judge_frame(frame);
+            judged = frame;
+        }
+        if judged.is_null() {
+            return;
+        }
+        for host in &mut self.hosts {
+            host.pending_checksums.retain(|&frame, _| frame > judged);
+        }
+    }
+
+    /// Compares every host's checksum of `frame` with the majority and emits
+    /// [`FortressEvent::AuthoritativeDesyncVerdict`] when some host differs.
+    /// The spectator's own checksum votes too and wins ties; remaining ties
+    /// go to the host listed first.
+    fn judge_frame(&mut self, frame: Frame) {
+        let own = self.own_checksum(frame);
+        let votes = |checksum: u128| {
+            self.hosts
+                .iter()
+                .filter(|host| host.pending_checksums.get(&frame) == Some(&checksum))
+                .count()
+                .saturating_add(usize::from(own == Some(checksum)))
+        };
+        let mut majority: Option<(u128, usize)> = None;
+        let candidates = own.into_iter().chain(
+            self.hosts
+                .iter()
+                .filter_map(|host| host.pending_checksums.get(&frame).copied()),
+        );
+        for checksum in candidates {
+            let count = votes(checksum);
+            if majority.is_none_or(|(_, best)| count > best) {
+                majority = Some((checksum, count));
+            }
+        }
+        let Some((majority, _)) = majority else {
+            return;
+        };
+
+        // At most one address per host.
+        let mut mismatching = Vec::new();
+        if mismatching.try_reserve(self.hosts.len()).is_err() {
+            report_violation_to!(
+                &self.violation_observer,
+                ViolationSeverity::Error,
+                ViolationKind::InternalError,
+                "spectator: failed to grow the checksum verdict for frame {}",
+                frame
+            );
+            return;
+        }
+        for host in &self.hosts {
+            if host
+                .pending_checksums
+                .get(&frame)
+                .is_none_or(|&checksum| checksum == majority)
+            {
+                continue;
+            }
+            mismatching.push(host.peer_addr());
+        }
+        if !mismatching.is_empty() {
+            self.enqueue_event(FortressEvent::AuthoritativeDesyncVerdict { frame, mismatching });
+        }
+    }
+
     /// Seeks the spectator to `target_frame` within the buffered rewind window.
     /// After the returned requests are processed, [`current_frame()`](Self::current_frame)
     /// `== target_frame` and the game state reflects all frames up to and including
@@ -957,6 +1302,10 @@ pub fn seek_to_frame(&mut self, target_frame: Frame) -> FortressResult<RequestVe
         let mut requests = RequestVec::<T>::with_capacity(1);
         requests.push(FortressRequest::LoadGameState { cell, frame: label });
         self.current_frame = target_frame;
+        let now = self.now();
+        let viewable = self.viewable_frame();
+        self.frame_pacer
+            .record_simulated(self.current_frame, viewable, now);
         Ok(requests)
     }
 
@@ -970,7 +1319,7 @@ pub fn poll_remote_clients(&mut self) {
         for (from, msg) in &self.socket.receive_all_messages() {
             let mut known_source = false;
             for host in &mut self.hosts {
-                if host.is_handling_message(from) {
+                if host.is_handling_message(from, msg) {
                     known_source = true;
                     host.handle_message(msg);
                     break;
@@ -1067,7 +1416,7 @@ pub fn poll_remote_clients(&mut self) {
                 // panic-free growth path.
                 let (lower_bound, upper_bound) = events.size_hint();
                 try_reserve_hint(&mut host_events, upper_bound.or(Some(lower_bound)), 1);
-                for event in events {
+                for (_, event) in events {
                     // The bulk pre-reservation above covers the common case in a
                     // single allocation; this fallible guard keeps growth
                     // panic-free when the untrusted size_hint under-reported.
@@ -1128,6 +1477,12 @@ pub fn poll_remote_clients(&mut self) {
         self.remove_disconnected_hosts(disconnected_hosts);
         self.disconnecting_hosts.clear();
         self.try_commit_ready_frames();
+        if self.last_recv_frame.is_valid() {
+            let now = self.now();
+            self.frame_pacer.record_arrival(self.last_recv_frame, now);
+        }
+        self.check_stream_end();
+        self.check_input_gap();
 
         // send out all pending UDP messages
         for host in &mut self.hosts {
@@ -1135,6 +1490,29 @@ pub fn poll_remote_clients(&mut self) {
         }
     }
 
+    /// Sends every message still queued for the hosts and returns how many
+    /// went out.
+    ///
+    /// [`poll_remote_clients`](Self::poll_remote_clients) already flushes the
+    /// queues on every poll, so this only matters for messages queued since the
+    /// last poll. It never blocks, and hosts that have already shut down are
+    /// skipped.
+    ///
+    /// Dropping a `SpectatorSession` performs the same flush once, on a
+    /// best-effort basis, exactly like [`P2PSession::flush`]: it is skipped
+    /// while the thread is panicking, and a panic raised by the socket is caught
+    /// and reported as a violation.
+    ///
+    /// [`P2PSession::flush`]: crate::P2PSession::flush
+    pub fn flush(&mut self) -> usize {
+        let _violation_scope = self.scoped_violation_observer();
+        let mut flushed = 0;
+        for host in &mut self.hosts {
+            flushed += host.send_all_messages(&mut self.socket);
+        }
+        flushed
+    }
+
     fn remove_disconnected_hosts(&mut self, mut disconnected_hosts: Vec<usize>) {
         if disconnected_hosts.is_empty() {
             return;
@@ -1174,6 +1552,69 @@ pub fn current_frame(&self) -> Frame {
         self.current_frame
     }
 
+    /// Returns how far playback has moved from the last simulated frame
+    /// towards the next one, for renderers that run at a different rate than
+    /// the match ticks. `None` before the first frame was simulated.
+    ///
+    /// The estimate follows the host's frame cadence, smoothed from the
+    /// arrival times of its inputs and seeded with the builder's
+    /// [`fps`](crate::SessionBuilder::with_fps). When a frame is simulated the
+    /// session latches when the next one should follow: one host interval
+    /// later, or, when playback is at the viewable edge, one interval after
+    /// the host's newest inputs arrived. The fraction then grows from `0.0`
+    /// to `1.0` by that time on the session clock and holds at `1.0` while the
+    /// next frame is late. This is read-only metadata; it never changes what
+    /// [`advance_frame`](Self::advance_frame) simulates.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let requests = spectator.advance_frame();
+    /// // ... fulfil the requests ...
+    /// if let Some(progress) = spectator.frame_progress() {
+    ///     render_interpolated(progress.frame, progress.fraction);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn frame_progress(&self) -> Option<FrameProgress> {
+        self.frame_pacer.progress(self.now())
+    }
+
+    /// Returns how well the spectator kept up with its configured frame rate
+    /// since it reached [`SessionState::Running`], or `None` before that.
+    ///
+    /// Like [`P2PSession::session_health`](crate::P2PSession::session_health),
+    /// without the starvation and rollback counts a spectator never has. The
+    /// values stop changing once the host's stream ended (see
+    /// [`Self::stream_ended`]) or the session reached its frame limit.
+    #[must_use]
+    pub fn session_health(&self) -> Option<SpectatorHealth> {
+        self.health.health(self.now()).map(SpectatorHealth::from)
+    }
+
+    /// Returns the final frame of the match once the host ended its stream and
+    /// this session simulated every frame up to it (see
+    /// [`FortressEvent::StreamEnded`]), or `None` while the stream is live.
+    #[must_use]
+    pub fn stream_ended(&self) -> Option<Frame> {
+        self.stream_end_frame.filter(|_| self.stream_ended)
+    }
+
+    /// Emits [`FortressEvent::StreamEnded`] once every frame up to the
+    /// announced final frame was simulated.
+    fn check_stream_end(&mut self) {
+        if self.stream_ended {
+            return;
+        }
+        if let Some(final_frame) = self.stream_end_frame {
+            if self.current_frame >= final_frame {
+                self.stream_ended = true;
+                self.health.end(self.now());
+                self.enqueue_event(FortressEvent::StreamEnded { final_frame });
+            }
+        }
+    }
+
     /// Returns the number of players this session was constructed with.
     #[must_use]
     pub fn num_players(&self) -> usize {
@@ -1400,7 +1841,7 @@ fn latch_spectator_divergence(
     /// on each [`events`](Self::events) drain) so the loss is observable via
     /// [`metrics`](Self::metrics) and any registered violation observer without
     /// flooding on a churn burst.
-    #[cfg(test)]
+    #[cfg(all(test, feature = "telemetry"))]
     fn trim_event_queue(&mut self) {
         while self.event_queue.len() > self.max_event_queue_size {
             if let Some(dropped) = remove_event_for_overflow(&mut self.event_queue) {
@@ -1416,6 +1857,7 @@ fn trim_event_queue(&mut self) {
     /// routine first; otherwise discard an incoming routine or replace the oldest
     /// durable with an incoming durable.
     fn enqueue_event(&mut self, event: FortressEvent<T>) {
+        let event = self.stamp_event(event);
         if let Some(dropped) =
             enqueue_event_bounded(&mut self.event_queue, self.max_event_queue_size, event)
         {
@@ -1424,8 +1866,25 @@ fn enqueue_event(&mut self, event: FortressEvent<T>) {
         self.metrics.observe_event_queue_len(self.event_queue.len());
     }
 
-    fn record_event_discard(&mut self, dropped: FortressEvent<T>) {
-        self.metrics.record_event_discard(dropped.kind());
+    /// Stamps `event` with the current frame and the time since creation.
+    /// The current time on the session clock.
+    fn now(&self) -> web_time::Instant {
+        self.clock
+            .as_ref()
+            .map_or_else(web_time::Instant::now, |clock| clock())
+    }
+
+    fn stamp_event(&self, event: FortressEvent<T>) -> TimestampedEvent<T> {
+        let elapsed = self.now().saturating_duration_since(self.created_at);
+        TimestampedEvent {
+            frame: self.current_frame,
+            session_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            event,
+        }
+    }
+
+    fn record_event_discard(&mut self, dropped: TimestampedEvent<T>) {
+        self.metrics.record_event_discard(dropped.event.kind());
         if !self.event_discard_warned {
             self.event_discard_warned = true;
             report_violation_to!(
@@ -1772,6 +2231,70 @@ fn try_commit_ready_frames(&mut self) {
         self.try_commit_ready_frames_with_pending_host(None);
     }
 
+    /// The newest frame `host_index` sent inputs for, whether staged or still
+    /// buffered by its endpoint behind a missing frame.
+    fn newest_host_frame(&self, host_index: usize) -> Option<Frame> {
+        let staged = self
+            .host_snapshots
+            .get(host_index)?
+            .iter()
+            .flatten()
+            .map(|snapshot| snapshot.frame)
+            .max();
+        let deferred = self
+            .hosts
+            .get(host_index)
+            .and_then(UdpProtocol::newest_deferred_input_frame);
+        staged.max(deferred)
+    }
+
+    /// Tracks how long the next frame to commit has been missing while the
+    /// canonical host has already sent later frames, and emits
+    /// [`FortressEvent::SpectatorInputGap`] once per missing frame when that
+    /// exceeds [`Self::input_gap_timeout`].
+    ///
+    /// Later frames wait in the per-host snapshot ring and in the endpoint's
+    /// out-of-order buffer; frames only commit as a contiguous prefix, so the
+    /// spectator never advances past a gap. The host resends every
+    /// unacknowledged frame on its retry interval, which fills the gap unless
+    /// the host itself stopped sending it.
+    fn check_input_gap(&mut self) {
+        let Some(timeout) = self.input_gap_timeout else {
+            return;
+        };
+        let Some(first_missing) = self.last_recv_frame.checked_add(1) else {
+            return;
+        };
+        // Hosts removed this poll are gone, so the first host is canonical.
+        let waiting = self.spectator_divergence.is_none()
+            && !self.stream_ended
+            && self
+                .newest_host_frame(0)
+                .is_some_and(|newest| newest > first_missing);
+        if !waiting {
+            self.input_gap = None;
+            return;
+        }
+
+        let now = self.now();
+        let gap = match self.input_gap {
+            Some(gap) if gap.first_missing == first_missing => gap,
+            _ => InputGap {
+                first_missing,
+                since: now,
+                reported: false,
+            },
+        };
+        let expired = !gap.reported && now.saturating_duration_since(gap.since) >= timeout;
+        self.input_gap = Some(InputGap {
+            reported: gap.reported || expired,
+            ..gap
+        });
+        if expired {
+            self.enqueue_event(FortressEvent::SpectatorInputGap { first_missing });
+        }
+    }
+
     fn try_commit_ready_frames_with_pending_host(
         &mut self,
         pending_host_to_include: Option<usize>,
@@ -1985,7 +2508,7 @@ fn handle_host_input(
         host_index: usize,
         input: PlayerInput<T::Input>,
         player: PlayerHandle,
-        status_snapshot: Vec<ConnectionStatus>,
+        status_snapshot: &[ConnectionStatus],
         addr: T::Address,
     ) {
         // Validate frame before using as index - negative frames would wrap around
@@ -2019,7 +2542,7 @@ fn handle_host_input(
         // regardless of which host is canonical, of arrival order, and of the
         // first-writer-wins staged-snapshot policy below (which would mask a
         // retransmitted frame's newer status).
-        self.witness_host_status_reports(host_index, &status_snapshot);
+        self.witness_host_status_reports(host_index, status_snapshot);
 
         let Some(frame_index) = input.frame.buffer_index(self.buffer_size) else {
             return;
@@ -2048,7 +2571,7 @@ fn handle_host_input(
             };
             if !matches!(slot, Some(snapshot) if snapshot.frame == input.frame) {
                 let Ok(snapshot) =
-                    HostFrameSnapshot::new(input.frame, self.num_players, status_snapshot)
+                    HostFrameSnapshot::new(input.frame, self.num_players, status_snapshot.to_vec())
                 else {
                     return;
                 };
@@ -2068,8 +2591,18 @@ fn handle_host_input(
             }
         }
 
+        // Hosts only forward confirmed inputs, so a frame is never re-delivered
+        // with different data; if one is, the stream stays append-only.
         if same_host_conflict {
-            self.latch_spectator_divergence(input.frame, player, addr.clone(), addr);
+            report_violation_to!(
+                &self.violation_observer,
+                ViolationSeverity::Error,
+                ViolationKind::FrameSync,
+                "spectator: host {} re-delivered frame {} for player {} with a different input; keeping the first",
+                host_index,
+                input.frame,
+                player
+            );
             return;
         }
 
@@ -2168,17 +2701,45 @@ fn handle_event(
             Event::NetworkResumed => {
                 self.enqueue_event(FortressEvent::NetworkResumed { addr });
             },
+            // forward to user
+            Event::TransportError {
+                consecutive_failures,
+            } => {
+                self.enqueue_event(FortressEvent::TransportError {
+                    addr,
+                    consecutive_failures,
+                });
+            },
             // synced with a host, then forward to user. The first host to sync flips
             // the session to Running; subsequent hosts are idempotent.
             Event::Synchronized => {
                 self.state = SessionState::Running;
+                self.health.start(self.now());
                 self.enqueue_event(FortressEvent::Synchronized { addr });
             },
             // disconnect the host, then forward to user. The host is removed by the
             // caller after all events have been handled.
             Event::Disconnected => {
                 disconnected_host = Some(host_index);
-                self.enqueue_event(FortressEvent::Disconnected { addr });
+                // A host leaving after its stream ended cleanly is expected.
+                if !self.stream_ended {
+                    self.enqueue_event(FortressEvent::Disconnected { addr });
+                }
+            },
+            // Redundant hosts each announce the frames they forwarded; the
+            // highest is still reachable from the hosts that sent it.
+            Event::StreamEnded { final_frame } => {
+                self.stream_end_frame = Some(
+                    self.stream_end_frame
+                        .map_or(final_frame, |frame| frame.max(final_frame)),
+                );
+            },
+            // hosts never tag the spectator stream
+            Event::FrameTag { .. } => {},
+            // spectators never send a stall hint, so hosts never answer one
+            Event::AdvanceStalled | Event::AdvanceResumed => {},
+            Event::ReliableMessage { payload } => {
+                self.enqueue_event(FortressEvent::ReliableMessage { addr, payload });
             },
             // forward sync timeout to user
             Event::SyncTimeout { elapsed_ms } => {
@@ -2193,7 +2754,7 @@ fn handle_event(
                 player,
                 peer_connect_status,
             } => {
-                self.handle_host_input(host_index, input, player, peer_connect_status, addr);
+                self.handle_host_input(host_index, input, player, &peer_connect_status, addr);
             },
         }
 
@@ -2391,6 +2952,24 @@ fn merge_connection_status(
     }
 }
 
+impl<T: Config> Drop for SpectatorSession<T> {
+    /// Best-effort final [`flush`](SpectatorSession::flush) of the queued messages.
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let flushed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.flush()));
+        if flushed.is_err() {
+            report_violation_to!(
+                &self.violation_observer,
+                ViolationSeverity::Warning,
+                ViolationKind::NetworkProtocol,
+                "spectator: socket panicked while flushing queued messages on session drop"
+            );
+        }
+    }
+}
+
 impl<T: Config> fmt::Debug for SpectatorSession<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SpectatorSession")
@@ -2436,6 +3015,14 @@ fn current_state(&self) -> SessionState {
     fn poll_remote_clients(&mut self) {
         Self::poll_remote_clients(self)
     }
+
+    fn current_frame(&self) -> Frame {
+        Self::current_frame(self)
+    }
+
+    fn confirmed_frame(&self) -> Frame {
+        Self::current_frame(self)
+    }
 }
 
 #[cfg(test)]
@@ -2446,6 +3033,7 @@ fn poll_remote_clients(&mut self) {
     clippy::indexing_slicing,
     clippy::needless_collect
 )]
+#[cfg_attr(not(feature = "telemetry"), allow(dead_code, unused_imports))]
 mod tests {
     use super::*;
     use crate::network::{
@@ -2491,6 +3079,7 @@ fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
         }
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_unknown_source_packets_are_counted_and_warn_once_per_session() {
         use crate::telemetry::CollectingObserver;
@@ -2738,6 +3327,7 @@ fn spectator_peer_metrics_index_follows_surviving_host_after_compaction() {
     /// the P2P session. The overflow now increments [`SessionMetrics`] (total +
     /// per-[`EventKind`](crate::metrics::EventKind)) and reports a single
     /// rate-limited `Warning` to the configured violation observer.
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_event_queue_overflow_records_discard_telemetry() {
         use crate::metrics::EventKind;
@@ -2759,16 +3349,16 @@ fn spectator_event_queue_overflow_records_discard_telemetry() {
         // Canary at the front: a durable Disconnected.
         session
             .event_queue
-            .push_back(FortressEvent::Disconnected { addr });
+            .push_back(session.stamp_event(FortressEvent::Disconnected { addr }));
         // Push past the cap with routine events; they must be selected first.
         for _ in 0..session.max_event_queue_size {
-            session
-                .event_queue
-                .push_back(FortressEvent::InputDelayRecommendation {
+            session.event_queue.push_back(session.stamp_event(
+                FortressEvent::InputDelayRecommendation {
                     player_handle: PlayerHandle::new(0),
                     current_delay: 0,
                     suggested_delay: 1,
-                });
+                },
+            ));
         }
         session.trim_event_queue();
 
@@ -2786,7 +3376,7 @@ fn spectator_event_queue_overflow_records_discard_telemetry() {
             session
                 .event_queue
                 .iter()
-                .any(|event| matches!(event, FortressEvent::Disconnected { .. })),
+                .any(|event| matches!(&event.event, FortressEvent::Disconnected { .. })),
             "routine churn must not evict the durable Disconnected canary"
         );
         let metrics = session.metrics();
@@ -2824,6 +3414,7 @@ fn spectator_event_queue_overflow_records_discard_telemetry() {
     /// (mirror of the P2P contract): several trim passes within one drain gap
     /// yield a single `Warning`, and `events()` re-arms it. The per-kind
     /// counters keep incrementing regardless.
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_event_queue_overflow_warning_is_rate_limited_per_drain_gap() {
         use crate::telemetry::CollectingObserver;
@@ -2853,13 +3444,13 @@ fn spectator_event_queue_overflow_warning_is_rate_limited_per_drain_gap() {
         // First episode: several overflowing trim passes, no drain between them.
         for _ in 0..5 {
             for _ in 0..(session.max_event_queue_size + 1) {
-                session
-                    .event_queue
-                    .push_back(FortressEvent::InputDelayRecommendation {
+                session.event_queue.push_back(session.stamp_event(
+                    FortressEvent::InputDelayRecommendation {
                         player_handle: PlayerHandle::new(0),
                         current_delay: 0,
                         suggested_delay: 1,
-                    });
+                    },
+                ));
             }
             session.trim_event_queue();
         }
@@ -2877,13 +3468,13 @@ fn spectator_event_queue_overflow_warning_is_rate_limited_per_drain_gap() {
         // Draining re-arms the rate limiter.
         let _ = session.events();
         for _ in 0..(session.max_event_queue_size + 1) {
-            session
-                .event_queue
-                .push_back(FortressEvent::InputDelayRecommendation {
+            session.event_queue.push_back(session.stamp_event(
+                FortressEvent::InputDelayRecommendation {
                     player_handle: PlayerHandle::new(0),
                     current_delay: 0,
                     suggested_delay: 1,
-                });
+                },
+            ));
         }
         session.trim_event_queue();
         assert_eq!(
@@ -3135,6 +3726,161 @@ fn spectator_session_poll_remote_clients_multiple_times() {
         assert_eq!(session.current_state(), SessionState::Synchronizing);
     }
 
+    // ==========================================
+    // Input Gap Tests
+    // ==========================================
+
+    /// A spectator whose clock only moves when the returned offset (in
+    /// milliseconds) does.
+    fn create_gap_test_session(
+        buffer_size: usize,
+        input_gap_timeout: Option<web_time::Duration>,
+    ) -> (
+        SpectatorSession<TestConfig>,
+        Arc<std::sync::atomic::AtomicU64>,
+    ) {
+        use crate::{ProtocolConfig, SpectatorConfig};
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let base = web_time::Instant::now();
+        let offset_ms = Arc::new(AtomicU64::new(0));
+        let clock_offset = Arc::clone(&offset_ms);
+        let session = SessionBuilder::new()
+            .with_num_players(2)
+            .unwrap()
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(Arc::new(move || {
+                    base + web_time::Duration::from_millis(clock_offset.load(Ordering::Relaxed))
+                })),
+                ..ProtocolConfig::default()
+            })
+            .with_spectator_config(SpectatorConfig {
+                buffer_size,
+                input_gap_timeout,
+                ..SpectatorConfig::default()
+            })
+            .start_spectator_session(test_addr(7440), DummySocket)
+            .unwrap();
+        (session, offset_ms)
+    }
+
+    fn advance_gap_clock(offset_ms: &std::sync::atomic::AtomicU64, millis: u64) {
+        offset_ms.fetch_add(millis, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn input_gap_events(session: &mut SpectatorSession<TestConfig>) -> Vec<Frame> {
+        session
+            .events()
+            .filter_map(|event| match event {
+                FortressEvent::SpectatorInputGap { first_missing } => Some(first_missing),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spectator_replays_out_of_order_host_inputs_once_the_gap_closes() {
+        let (mut session, offset_ms) =
+            create_gap_test_session(60, Some(web_time::Duration::from_millis(100)));
+        let status = vec![ConnectionStatus::default(); 2];
+        queue_host_input(&mut session, 0, Frame::new(0), [0, 0], status.clone());
+        queue_host_input(&mut session, 0, Frame::new(3), [0, 0], status.clone());
+        queue_host_input(&mut session, 0, Frame::new(2), [0, 0], status.clone());
+        session.poll_remote_clients();
+        assert_eq!(session.last_recv_frame, Frame::new(0));
+        assert_eq!(
+            session.hosts[0].newest_deferred_input_frame(),
+            Some(Frame::new(3))
+        );
+
+        advance_gap_clock(&offset_ms, 50);
+        queue_host_input(&mut session, 0, Frame::new(1), [0, 0], status);
+        session.poll_remote_clients();
+
+        // Frame 1 makes the buffered frames 2 and 3 decodable in turn.
+        assert_eq!(session.last_recv_frame, Frame::new(3));
+        assert_eq!(session.hosts[0].newest_deferred_input_frame(), None);
+        advance_gap_clock(&offset_ms, 500);
+        session.poll_remote_clients();
+        assert!(input_gap_events(&mut session).is_empty());
+        assert!(session.input_gap.is_none());
+    }
+
+    #[test]
+    fn spectator_reports_input_gap_once_after_timeout() {
+        let (mut session, offset_ms) =
+            create_gap_test_session(60, Some(web_time::Duration::from_millis(100)));
+        let status = vec![ConnectionStatus::default(); 2];
+        queue_host_input(&mut session, 0, Frame::new(0), [0, 0], status.clone());
+        queue_host_input(&mut session, 0, Frame::new(2), [0, 0], status.clone());
+        session.poll_remote_clients();
+
+        advance_gap_clock(&offset_ms, 99);
+        session.poll_remote_clients();
+        assert!(input_gap_events(&mut session).is_empty());
+
+        advance_gap_clock(&offset_ms, 1);
+        session.poll_remote_clients();
+        assert_eq!(input_gap_events(&mut session), vec![Frame::new(1)]);
+
+        advance_gap_clock(&offset_ms, 500);
+        session.poll_remote_clients();
+        assert!(input_gap_events(&mut session).is_empty());
+
+        // The spectator kept waiting and resumes once the frame arrives.
+        queue_host_input(&mut session, 0, Frame::new(1), [0, 0], status);
+        session.poll_remote_clients();
+        assert_eq!(session.last_recv_frame, Frame::new(2));
+        assert!(session.input_gap.is_none());
+    }
+
+    #[test]
+    fn spectator_without_input_gap_timeout_never_reports_gaps() {
+        let (mut session, offset_ms) = create_gap_test_session(60, None);
+        let status = vec![ConnectionStatus::default(); 2];
+        queue_host_input(&mut session, 0, Frame::new(0), [0, 0], status.clone());
+        queue_host_input(&mut session, 0, Frame::new(2), [0, 0], status);
+        for _ in 0..5 {
+            advance_gap_clock(&offset_ms, 500);
+            session.poll_remote_clients();
+        }
+        assert_eq!(session.last_recv_frame, Frame::new(0));
+        assert!(input_gap_events(&mut session).is_empty());
+    }
+
+    #[test]
+    fn spectator_out_of_order_buffer_evicts_the_longest_waiting_packet() {
+        const BUFFER_SIZE: usize = 4;
+
+        let (mut session, _offset_ms) = create_gap_test_session(BUFFER_SIZE, None);
+        let status = vec![ConnectionStatus::default(); 2];
+        queue_host_input(&mut session, 0, Frame::new(0), [0, 0], status.clone());
+        // Six packets behind the missing frame 1; frames 2 and 3 arrived first
+        // and are evicted.
+        for frame in 2..=7 {
+            queue_host_input(&mut session, 0, Frame::new(frame), [0, 0], status.clone());
+        }
+        session.poll_remote_clients();
+        assert_eq!(
+            session.hosts[0].newest_deferred_input_frame(),
+            Some(Frame::new(7))
+        );
+
+        queue_host_input(&mut session, 0, Frame::new(1), [0, 0], status.clone());
+        session.poll_remote_clients();
+        assert_eq!(session.last_recv_frame, Frame::new(1));
+
+        // The host's retransmission recovers the evicted frames, and the
+        // buffered ones follow.
+        queue_host_input(&mut session, 0, Frame::new(2), [0, 0], status.clone());
+        session.poll_remote_clients();
+        assert_eq!(session.last_recv_frame, Frame::new(2));
+        queue_host_input(&mut session, 0, Frame::new(3), [0, 0], status);
+        session.poll_remote_clients();
+        assert_eq!(session.last_recv_frame, Frame::new(7));
+        assert_eq!(session.hosts[0].newest_deferred_input_frame(), None);
+    }
+
     // ==========================================
     // SpectatorConfig Tests
     // ==========================================
@@ -3552,6 +4298,7 @@ fn spectator_inputs_at_frame_stale_player_slot_returns_prediction_threshold() {
         assert!(matches!(result, Err(FortressError::PredictionThreshold)));
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_inputs_at_frame_newer_wrapped_player_slot_returns_too_far_behind() {
         use crate::telemetry::CollectingObserver;
@@ -3579,6 +4326,7 @@ fn spectator_inputs_at_frame_newer_wrapped_player_slot_returns_too_far_behind()
             .any(|violation| violation.kind == ViolationKind::FrameSync));
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_frames_behind_host_routes_report_violation_to_session_observer() {
         use crate::telemetry::CollectingObserver;
@@ -3618,6 +4366,7 @@ fn spectator_frames_behind_host_routes_report_violation_to_session_observer() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_inputs_at_frame_stale_then_newer_slot_returns_too_far_behind() {
         use crate::telemetry::CollectingObserver;
@@ -3646,6 +4395,7 @@ fn spectator_inputs_at_frame_stale_then_newer_slot_returns_too_far_behind() {
             .any(|violation| violation.kind == ViolationKind::FrameSync));
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_redundant_host_divergence_latches_error_and_event() {
         use crate::telemetry::CollectingObserver;
@@ -3716,14 +4466,14 @@ fn spectator_partial_host_input_conflict_latches_after_canonical_commit() {
             0,
             PlayerInput::new(frame, 11),
             PlayerHandle::new(0),
-            vec![ConnectionStatus::default(); 2],
+            &[ConnectionStatus::default(); 2],
             test_addr(7305),
         );
         session.handle_host_input(
             0,
             PlayerInput::new(frame, 22),
             PlayerHandle::new(1),
-            vec![ConnectionStatus::default(); 2],
+            &[ConnectionStatus::default(); 2],
             test_addr(7305),
         );
         assert_eq!(session.last_recv_frame, frame);
@@ -3732,7 +4482,7 @@ fn spectator_partial_host_input_conflict_latches_after_canonical_commit() {
             1,
             PlayerInput::new(frame, 99),
             PlayerHandle::new(0),
-            vec![ConnectionStatus::default(); 2],
+            &[ConnectionStatus::default(); 2],
             test_addr(7306),
         );
 
@@ -3777,7 +4527,7 @@ fn spectator_nonoverlapping_divergent_late_stream_latches_divergence() {
                 0,
                 PlayerInput::new(frame, 11),
                 PlayerHandle::new(0),
-                status.clone(),
+                &status.clone(),
                 test_addr(7401),
             );
             // test:
@@ -3785,7 +4535,7 @@ fn spectator_nonoverlapping_divergent_late_stream_latches_divergence() {
                 0,
                 PlayerInput::new(frame, 22),
                 PlayerHandle::new(1),
-                status.clone(),
+                &status.clone(),
                 test_addr(7401),
             );
         }
@@ -3798,7 +4548,7 @@ fn spectator_nonoverlapping_divergent_late_stream_latches_divergence() {
             1,
             PlayerInput::new(Frame::new(1), 99),
             PlayerHandle::new(0),
-            status,
+            &status,
             test_addr(7402),
         );
 
@@ -3848,7 +4598,7 @@ fn spectator_frozen_slot_divergent_value_at_freeze_frame_latches_before_playback
                 0,
                 PlayerInput::new(frame, 11),
                 PlayerHandle::new(0),
-                status.clone(),
+                &status.clone(),
                 test_addr(7411),
             );
             // test:
@@ -3856,7 +4606,7 @@ fn spectator_frozen_slot_divergent_value_at_freeze_frame_latches_before_playback
                 0,
                 PlayerInput::new(frame, p1),
                 PlayerHandle::new(1),
-                status.clone(),
+                &status.clone(),
                 test_addr(7411),
             );
         }
@@ -3872,7 +4622,7 @@ fn spectator_frozen_slot_divergent_value_at_freeze_frame_latches_before_playback
             0,
             PlayerInput::new(Frame::new(2), 12),
             PlayerHandle::new(0),
-            status.clone(),
+            &status.clone(),
             test_addr(7411),
         );
         // (player 1 is dropped; host stops forwarding new player-1 inputs)
@@ -3886,7 +4636,7 @@ fn spectator_frozen_slot_divergent_value_at_freeze_frame_latches_before_playback
             1,
             PlayerInput::new(Frame::new(1), 60),
             PlayerHandle::new(1),
-            status,
+            &status,
             test_addr(7412),
         );
 
@@ -3930,7 +4680,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
             0,
             PlayerInput::new(Frame::new(0), 11),
             PlayerHandle::new(0),
-            connected.clone(),
+            &connected.clone(),
             test_addr(7421),
         );
         // test:
@@ -3938,7 +4688,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
             0,
             PlayerInput::new(Frame::new(0), 40),
             PlayerHandle::new(1),
-            connected.clone(),
+            &connected.clone(),
             test_addr(7421),
         );
         assert_eq!(session.last_recv_frame, Frame::new(0));
@@ -3964,7 +4714,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
                 0,
                 PlayerInput::new(frame, 12),
                 PlayerHandle::new(0),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7421),
             );
             // test:
@@ -3972,7 +4722,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
                 0,
                 PlayerInput::new(frame, p1),
                 PlayerHandle::new(1),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7421),
             );
         }
@@ -3994,7 +4744,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
             1,
             PlayerInput::new(Frame::new(0), 11),
             PlayerHandle::new(0),
-            connected.clone(),
+            &connected.clone(),
             test_addr(7422),
         );
         // test:
@@ -4002,7 +4752,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
             1,
             PlayerInput::new(Frame::new(0), 40),
             PlayerHandle::new(1),
-            connected,
+            &connected,
             test_addr(7422),
         );
         // Host 1's DIVERGENT frozen player-1 value 60 at its freeze frame F_B = 1.
@@ -4011,7 +4761,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
             1,
             PlayerInput::new(Frame::new(1), 12),
             PlayerHandle::new(0),
-            dropped_at_1.clone(),
+            &dropped_at_1.clone(),
             test_addr(7422),
         );
         // test:
@@ -4019,7 +4769,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
             1,
             PlayerInput::new(Frame::new(1), 60),
             PlayerHandle::new(1),
-            dropped_at_1.clone(),
+            &dropped_at_1.clone(),
             test_addr(7422),
         );
         // Host 1 forwards player 0 at frame 2 (overlap on the live slot) but NOT
@@ -4029,7 +4779,7 @@ fn spectator_asymmetric_freeze_frame_nonoverlapping_region_converges_to_global_m
             1,
             PlayerInput::new(Frame::new(2), 12),
             PlayerHandle::new(0),
-            dropped_at_1,
+            &dropped_at_1,
             test_addr(7422),
         );
 
@@ -4099,7 +4849,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
             1,
             PlayerInput::new(Frame::new(0), 11),
             PlayerHandle::new(0),
-            connected.clone(),
+            &connected.clone(),
             test_addr(7432),
         );
         // test:
@@ -4107,7 +4857,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
             1,
             PlayerInput::new(Frame::new(0), 40),
             PlayerHandle::new(1),
-            connected.clone(),
+            &connected.clone(),
             test_addr(7432),
         );
         // Frame 1: host 1 freezes player 1 at F_B = 1 with the mesh-agreed value 60.
@@ -4116,7 +4866,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
             1,
             PlayerInput::new(Frame::new(1), 12),
             PlayerHandle::new(0),
-            dropped_at_1.clone(),
+            &dropped_at_1.clone(),
             test_addr(7432),
         );
         // test:
@@ -4124,7 +4874,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
             1,
             PlayerInput::new(Frame::new(1), 60),
             PlayerHandle::new(1),
-            dropped_at_1.clone(),
+            &dropped_at_1.clone(),
             test_addr(7432),
         );
         // Frame 2: host 1 forwards player 0 (overlap on the live slot) but NOT
@@ -4136,7 +4886,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
             1,
             PlayerInput::new(Frame::new(2), 12),
             PlayerHandle::new(0),
-            dropped_at_1,
+            &dropped_at_1,
             test_addr(7432),
         );
         // Nothing has committed yet: the canonical host (0) is not staged.
@@ -4152,7 +4902,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
             0,
             PlayerInput::new(Frame::new(0), 11),
             PlayerHandle::new(0),
-            connected.clone(),
+            &connected.clone(),
             test_addr(7431),
         );
         // test:
@@ -4160,7 +4910,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
             0,
             PlayerInput::new(Frame::new(0), 40),
             PlayerHandle::new(1),
-            connected,
+            &connected,
             test_addr(7431),
         );
         // Host 0 froze player 1 HIGH at F_A = 2. At the OVERLAPPING frame 1 it
@@ -4181,7 +4931,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
                 0,
                 PlayerInput::new(frame, 12),
                 PlayerHandle::new(0),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7431),
             );
             // test:
@@ -4189,7 +4939,7 @@ fn spectator_asymmetric_freeze_frame_converges_at_commit_time_when_lower_host_st
                 0,
                 PlayerInput::new(frame, p1),
                 PlayerHandle::new(1),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7431),
             );
         }
@@ -4229,7 +4979,7 @@ fn spectator_partial_host_input_conflict_latches_before_canonical_commit() {
             1,
             PlayerInput::new(frame, 99),
             PlayerHandle::new(0),
-            vec![ConnectionStatus::default(); 2],
+            &[ConnectionStatus::default(); 2],
             test_addr(7308),
         );
         assert_eq!(session.last_recv_frame, Frame::NULL);
@@ -4238,7 +4988,7 @@ fn spectator_partial_host_input_conflict_latches_before_canonical_commit() {
             0,
             PlayerInput::new(frame, 11),
             PlayerHandle::new(0),
-            vec![ConnectionStatus::default(); 2],
+            &[ConnectionStatus::default(); 2],
             test_addr(7307),
         );
 
@@ -4279,7 +5029,7 @@ fn spectator_pending_primary_disconnect_allows_unresolved_failover_without_diver
             0,
             PlayerInput::new(frame, 99),
             PlayerHandle::new(0),
-            status.clone(),
+            &status.clone(),
             test_addr(7311),
         );
         assert_eq!(session.last_recv_frame, Frame::NULL);
@@ -4289,14 +5039,14 @@ fn spectator_pending_primary_disconnect_allows_unresolved_failover_without_diver
             1,
             PlayerInput::new(frame, 11),
             PlayerHandle::new(0),
-            status.clone(),
+            &status.clone(),
             test_addr(7312),
         );
         session.handle_host_input(
             1,
             PlayerInput::new(frame, 22),
             PlayerHandle::new(1),
-            status,
+            &status,
             test_addr(7312),
         );
 
@@ -4324,7 +5074,7 @@ fn spectator_same_poll_later_disconnect_is_excluded_before_earlier_input() {
             1,
             PlayerInput::new(frame, 99),
             PlayerHandle::new(0),
-            status.clone(),
+            &status,
             test_addr(7314),
         );
         assert_eq!(session.last_recv_frame, Frame::NULL);
@@ -4366,6 +5116,43 @@ fn spectator_same_host_input_before_disconnect_is_preserved() {
         assert_eq!(session.inputs[buffer_index][1].input, 22_u8);
     }
 
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn spectator_same_host_redelivered_input_keeps_the_first() {
+        use crate::telemetry::CollectingObserver;
+
+        let observer = Arc::new(CollectingObserver::new());
+        let host = test_addr(7317);
+        let mut session: SpectatorSession<TestConfig> = SessionBuilder::new()
+            .with_num_players(2)
+            .unwrap()
+            .with_violation_observer(Arc::clone(&observer) as Arc<dyn ViolationObserver>)
+            .start_spectator_session(host, DummySocket)
+            .unwrap();
+        let frame = Frame::new(0);
+        let status = vec![ConnectionStatus::default(); 2];
+
+        for (player, input) in [(0, 11), (1, 22), (0, 33)] {
+            session.handle_host_input(
+                0,
+                PlayerInput::new(frame, input),
+                PlayerHandle::new(player),
+                &status,
+                host,
+            );
+        }
+
+        assert!(session.spectator_divergence.is_none());
+        assert_eq!(session.last_recv_frame, frame);
+        let buffer_index = frame.buffer_index(session.buffer_size).unwrap();
+        assert_eq!(session.inputs[buffer_index][0].input, 11_u8);
+        assert_eq!(session.inputs[buffer_index][1].input, 22_u8);
+        let violations = observer.violations();
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert_eq!(violations[0].severity, ViolationSeverity::Error);
+        assert!(violations[0].message.contains("keeping the first"));
+    }
+
     #[test]
     fn spectator_disconnect_packet_preserves_final_inputs() {
         let mut session: SpectatorSession<TestConfig> = SessionBuilder::new()
@@ -5115,7 +5902,7 @@ fn stale_lagging_canonical_host_cannot_resurrect_permanently_dropped_slot() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7501),
             );
             // test:
@@ -5123,7 +5910,7 @@ fn stale_lagging_canonical_host_cannot_resurrect_permanently_dropped_slot() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7501),
             );
         }
@@ -5160,7 +5947,7 @@ fn stale_lagging_canonical_host_cannot_resurrect_permanently_dropped_slot() {
                 1,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                stale_connected.clone(),
+                &stale_connected.clone(),
                 test_addr(7502),
             );
             // test:
@@ -5168,7 +5955,7 @@ fn stale_lagging_canonical_host_cannot_resurrect_permanently_dropped_slot() {
                 1,
                 PlayerInput::new(Frame::new(frame), 90 + frame as u8),
                 PlayerHandle::new(1),
-                stale_connected,
+                &stale_connected,
                 test_addr(7502),
             );
         }
@@ -5240,7 +6027,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7503),
             );
             // test:
@@ -5248,7 +6035,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7503),
             );
         }
@@ -5262,7 +6049,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
             1,
             PlayerInput::new(Frame::new(1), 11),
             PlayerHandle::new(0),
-            dropped_at_1.clone(),
+            &dropped_at_1.clone(),
             test_addr(7504),
         );
         // test:
@@ -5270,7 +6057,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
             1,
             PlayerInput::new(Frame::new(1), 21),
             PlayerHandle::new(1),
-            dropped_at_1,
+            &dropped_at_1,
             test_addr(7504),
         );
         assert!(session.spectator_divergence.is_none());
@@ -5292,7 +6079,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7503),
             );
             // test:
@@ -5300,7 +6087,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7503),
             );
         }
@@ -5326,7 +6113,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7503),
             );
             // test:
@@ -5334,7 +6121,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7503),
             );
         }
@@ -5369,7 +6156,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 1,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                stale_connected.clone(),
+                &stale_connected.clone(),
                 test_addr(7504),
             );
             // test:
@@ -5377,7 +6164,7 @@ fn second_drop_after_reactivation_not_resurrected_by_cycle_one_witness() {
                 1,
                 PlayerInput::new(Frame::new(frame), 90 + frame as u8),
                 PlayerHandle::new(1),
-                stale_connected,
+                &stale_connected,
                 test_addr(7504),
             );
         }
@@ -5450,7 +6237,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7509),
             );
             // test:
@@ -5458,7 +6245,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7509),
             );
         }
@@ -5482,7 +6269,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
             1,
             PlayerInput::new(Frame::new(1), 11),
             PlayerHandle::new(0),
-            dropped_at_6.clone(),
+            &dropped_at_6.clone(),
             test_addr(7510),
         );
         // test:
@@ -5490,7 +6277,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
             1,
             PlayerInput::new(Frame::new(1), 21),
             PlayerHandle::new(1),
-            dropped_at_6,
+            &dropped_at_6,
             test_addr(7510),
         );
         assert!(session.spectator_divergence.is_none());
@@ -5521,7 +6308,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7509),
             );
             // test:
@@ -5529,7 +6316,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7509),
             );
         }
@@ -5556,7 +6343,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7509),
             );
             // test:
@@ -5564,7 +6351,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7509),
             );
         }
@@ -5599,7 +6386,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 1,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                stale_connected.clone(),
+                &stale_connected.clone(),
                 test_addr(7510),
             );
             // test:
@@ -5607,7 +6394,7 @@ fn second_drop_after_reactivation_not_resurrected_by_high_view_cycle_one_witness
                 1,
                 PlayerInput::new(Frame::new(frame), 90 + frame as u8),
                 PlayerHandle::new(1),
-                stale_connected,
+                &stale_connected,
                 test_addr(7510),
             );
         }
@@ -5684,7 +6471,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                h0_drop1.clone(),
+                &h0_drop1.clone(),
                 test_addr(7515),
             );
             // test:
@@ -5692,7 +6479,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                h0_drop1.clone(),
+                &h0_drop1.clone(),
                 test_addr(7515),
             );
         }
@@ -5706,7 +6493,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
             1,
             PlayerInput::new(Frame::new(1), 11),
             PlayerHandle::new(0),
-            h1_drop6.clone(),
+            &h1_drop6.clone(),
             test_addr(7516),
         );
         // test:
@@ -5714,7 +6501,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
             1,
             PlayerInput::new(Frame::new(1), 21),
             PlayerHandle::new(1),
-            h1_drop6.clone(),
+            &h1_drop6.clone(),
             test_addr(7516),
         );
 
@@ -5735,7 +6522,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                     host,
                     PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                     PlayerHandle::new(0),
-                    react.clone(),
+                    &react.clone(),
                     addr,
                 );
                 // test:
@@ -5743,7 +6530,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                     host,
                     PlayerInput::new(Frame::new(frame), p1),
                     PlayerHandle::new(1),
-                    react.clone(),
+                    &react.clone(),
                     addr,
                 );
             }
@@ -5763,7 +6550,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                h0_drop5.clone(),
+                &h0_drop5.clone(),
                 test_addr(7515),
             );
             // test:
@@ -5771,7 +6558,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                h0_drop5.clone(),
+                &h0_drop5.clone(),
                 test_addr(7515),
             );
         }
@@ -5787,7 +6574,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
             1,
             PlayerInput::new(Frame::new(1), 21),
             PlayerHandle::new(1),
-            h1_drop6,
+            &h1_drop6,
             test_addr(7516),
         );
 
@@ -5805,7 +6592,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                 1,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                stale_conn.clone(),
+                &stale_conn.clone(),
                 test_addr(7516),
             );
             // test:
@@ -5813,7 +6600,7 @@ fn cross_cycle_stale_drop_epoch_blocks_resurrect_after_consume() {
                 1,
                 PlayerInput::new(Frame::new(frame), 90 + frame as u8),
                 PlayerHandle::new(1),
-                stale_conn,
+                &stale_conn,
                 test_addr(7516),
             );
         }
@@ -5872,7 +6659,7 @@ fn witnessed_failover_host_reactivation_is_followed() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7505),
             );
             // test:
@@ -5880,7 +6667,7 @@ fn witnessed_failover_host_reactivation_is_followed() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7505),
             );
         }
@@ -5893,7 +6680,7 @@ fn witnessed_failover_host_reactivation_is_followed() {
             1,
             PlayerInput::new(Frame::new(2), 12),
             PlayerHandle::new(0),
-            dropped_at_2.clone(),
+            &dropped_at_2.clone(),
             test_addr(7506),
         );
         // test:
@@ -5901,7 +6688,7 @@ fn witnessed_failover_host_reactivation_is_followed() {
             1,
             PlayerInput::new(Frame::new(2), 22),
             PlayerHandle::new(1),
-            dropped_at_2,
+            &dropped_at_2,
             test_addr(7506),
         );
         assert!(session.spectator_divergence.is_none());
@@ -5923,7 +6710,7 @@ fn witnessed_failover_host_reactivation_is_followed() {
                 1,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7506),
             );
             // test:
@@ -5931,7 +6718,7 @@ fn witnessed_failover_host_reactivation_is_followed() {
                 1,
                 PlayerInput::new(Frame::new(frame), 50 + frame as u8),
                 PlayerHandle::new(1),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7506),
             );
         }
@@ -6109,7 +6896,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7512),
             );
             // test:
@@ -6117,7 +6904,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7512),
             );
         }
@@ -6145,7 +6932,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                reactivated_1.clone(),
+                &reactivated_1.clone(),
                 test_addr(7512),
             );
             // test:
@@ -6153,7 +6940,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                reactivated_1.clone(),
+                &reactivated_1.clone(),
                 test_addr(7512),
             );
         }
@@ -6179,7 +6966,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7512),
             );
             // test:
@@ -6187,7 +6974,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7512),
             );
         }
@@ -6224,7 +7011,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                reactivated_2.clone(),
+                &reactivated_2.clone(),
                 test_addr(7512),
             );
             // test:
@@ -6232,7 +7019,7 @@ fn second_reactivation_followed_after_witness_consumption() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                reactivated_2.clone(),
+                &reactivated_2.clone(),
                 test_addr(7512),
             );
         }
@@ -6276,7 +7063,7 @@ fn late_arriving_connected_snapshot_does_not_resurrect_dropped_slot() {
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7507),
             );
             // test:
@@ -6284,7 +7071,7 @@ fn late_arriving_connected_snapshot_does_not_resurrect_dropped_slot() {
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_2.clone(),
+                &dropped_at_2.clone(),
                 test_addr(7507),
             );
         }
@@ -6310,7 +7097,7 @@ fn late_arriving_connected_snapshot_does_not_resurrect_dropped_slot() {
             1,
             PlayerInput::new(Frame::new(1), 11),
             PlayerHandle::new(0),
-            stale_connected.clone(),
+            &stale_connected.clone(),
             test_addr(7508),
         );
         // test:
@@ -6318,7 +7105,7 @@ fn late_arriving_connected_snapshot_does_not_resurrect_dropped_slot() {
             1,
             PlayerInput::new(Frame::new(1), 21),
             PlayerHandle::new(1),
-            stale_connected,
+            &stale_connected,
             test_addr(7508),
         );
 
@@ -6377,7 +7164,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7513),
             );
             // test:
@@ -6385,7 +7172,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7513),
             );
         }
@@ -6410,7 +7197,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
                 1,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7514),
             );
             // test:
@@ -6418,7 +7205,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
                 1,
                 PlayerInput::new(Frame::new(frame), 21),
                 PlayerHandle::new(1),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7514),
             );
         }
@@ -6448,7 +7235,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
             0,
             PlayerInput::new(Frame::new(2), 12),
             PlayerHandle::new(0),
-            reactivated.clone(),
+            &reactivated.clone(),
             test_addr(7513),
         );
         // test:
@@ -6456,7 +7243,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
             0,
             PlayerInput::new(Frame::new(2), 32),
             PlayerHandle::new(1),
-            reactivated,
+            &reactivated,
             test_addr(7513),
         );
         assert_eq!(session.last_recv_frame, Frame::new(2));
@@ -6529,7 +7316,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                caught_up.clone(),
+                &caught_up.clone(),
                 test_addr(7514),
             );
             // test:
@@ -6537,7 +7324,7 @@ fn stale_staged_adopt_after_witness_consumption_recovers_when_host_catches_up()
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                caught_up.clone(),
+                &caught_up.clone(),
                 test_addr(7514),
             );
         }
@@ -6591,7 +7378,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7515),
             );
             // test:
@@ -6599,7 +7386,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_1.clone(),
+                &dropped_at_1.clone(),
                 test_addr(7515),
             );
         }
@@ -6608,7 +7395,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
             1,
             PlayerInput::new(Frame::new(1), 11),
             PlayerHandle::new(0),
-            dropped_at_1.clone(),
+            &dropped_at_1.clone(),
             test_addr(7516),
         );
         // test:
@@ -6616,7 +7403,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
             1,
             PlayerInput::new(Frame::new(1), 21),
             PlayerHandle::new(1),
-            dropped_at_1,
+            &dropped_at_1,
             test_addr(7516),
         );
         assert_eq!(
@@ -6644,7 +7431,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7515),
             );
             // test:
@@ -6652,7 +7439,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                reactivated.clone(),
+                &reactivated.clone(),
                 test_addr(7515),
             );
         }
@@ -6677,7 +7464,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 0,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7515),
             );
             // test:
@@ -6685,7 +7472,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 0,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                dropped_at_5.clone(),
+                &dropped_at_5.clone(),
                 test_addr(7515),
             );
         }
@@ -6711,7 +7498,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 1,
                 PlayerInput::new(Frame::new(frame), 10 + frame as u8),
                 PlayerHandle::new(0),
-                rearmed.clone(),
+                &rearmed.clone(),
                 test_addr(7516),
             );
             // test:
@@ -6719,7 +7506,7 @@ fn genuine_reactivation_with_only_preconsume_witnesses_fails_closed_at_frozen_la
                 1,
                 PlayerInput::new(Frame::new(frame), p1),
                 PlayerHandle::new(1),
-                rearmed.clone(),
+                &rearmed.clone(),
                 test_addr(7516),
             );
         }
@@ -6905,6 +7692,7 @@ fn spectator_remove_disconnected_hosts_uses_original_indices() {
         assert_eq!(remaining_ports, vec![7111, 7113]);
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn spectator_remove_disconnected_hosts_ignores_invalid_indices() {
         use crate::telemetry::CollectingObserver;
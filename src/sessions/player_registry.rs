@@ -2,12 +2,232 @@
 //!
 //! This module provides the [`PlayerRegistry`] struct that manages all players
 //! (local, remote, and spectators) and their protocol handlers.
+//!
+//! Endpoints are kept in an [`EndpointMap`], which iterates them in a fixed
+//! order — by the lowest player handle each one owns, then by address — so
+//! every per-endpoint loop of a session (input broadcast, polling, event
+//! translation) runs in the same order on every build, whatever the
+//! address type's `Ord` or the order the endpoints were added in.
 
 use crate::error::{FortressError, InvalidRequestKind};
 use crate::network::protocol::UdpProtocol;
+use crate::telemetry::{InvariantChecker, InvariantViolation};
 use crate::{Config, HandleVec, PlayerHandle, PlayerType};
 use std::collections::BTreeMap;
 
+/// Protocol endpoints keyed by peer address, iterated in a deterministic
+/// order.
+///
+/// Iteration visits endpoints by the lowest [`PlayerHandle`] each owns, then
+/// by address; endpoints owning no handle (observers) come last, by address.
+/// Lookups by address work like a [`BTreeMap`]'s. An endpoint's handles never
+/// change after it is built, so the order only changes on
+/// [`insert`](Self::insert) and [`remove`](Self::remove).
+///
+/// # Note
+///
+/// This type is re-exported in [`__internal`](crate::__internal) for testing and fuzzing.
+/// It is not part of the stable public API.
+pub struct EndpointMap<T>
+where
+    T: Config,
+{
+    /// Endpoints in iteration order.
+    entries: Vec<(T::Address, UdpProtocol<T>)>,
+    /// Position of each address in `entries`.
+    index: BTreeMap<T::Address, usize>,
+}
+
+type Entry<T> = (<T as Config>::Address, UdpProtocol<T>);
+
+/// Iterator over the `(address, endpoint)` pairs of an [`EndpointMap`], in
+/// its iteration order.
+pub type EndpointIter<'a, T> = std::iter::Map<
+    std::slice::Iter<'a, Entry<T>>,
+    fn(&'a Entry<T>) -> (&'a <T as Config>::Address, &'a UdpProtocol<T>),
+>;
+
+/// Mutable iterator over the `(address, endpoint)` pairs of an
+/// [`EndpointMap`], in its iteration order.
+pub type EndpointIterMut<'a, T> = std::iter::Map<
+    std::slice::IterMut<'a, Entry<T>>,
+    fn(&'a mut Entry<T>) -> (&'a <T as Config>::Address, &'a mut UdpProtocol<T>),
+>;
+
+impl<T: Config> EndpointMap<T> {
+    /// Creates an empty map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            index: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of endpoints.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the map holds no endpoint.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns whether an endpoint is registered at `addr`.
+    #[must_use]
+    pub fn contains_key(&self, addr: &T::Address) -> bool {
+        self.index.contains_key(addr)
+    }
+
+    /// Returns the endpoint at `addr`.
+    #[must_use]
+    pub fn get(&self, addr: &T::Address) -> Option<&UdpProtocol<T>> {
+        let &position = self.index.get(addr)?;
+        self.entries.get(position).map(|(_, endpoint)| endpoint)
+    }
+
+    /// Returns the endpoint at `addr` mutably.
+    pub fn get_mut(&mut self, addr: &T::Address) -> Option<&mut UdpProtocol<T>> {
+        let &position = self.index.get(addr)?;
+        self.entries.get_mut(position).map(|(_, endpoint)| endpoint)
+    }
+
+    /// Registers `endpoint` at `addr`, returning the endpoint it replaces.
+    pub fn insert(&mut self, addr: T::Address, endpoint: UdpProtocol<T>) -> Option<UdpProtocol<T>> {
+        let replaced = match self
+            .index
+            .get(&addr)
+            .and_then(|&position| self.entries.get_mut(position))
+        {
+            Some((_, existing)) => Some(std::mem::replace(existing, endpoint)),
+            None => {
+                self.entries.push((addr, endpoint));
+                None
+            },
+        };
+        self.reorder();
+        replaced
+    }
+
+    /// Removes and returns the endpoint at `addr`.
+    pub fn remove(&mut self, addr: &T::Address) -> Option<UdpProtocol<T>> {
+        let position = self.index.remove(addr)?;
+        if position >= self.entries.len() {
+            return None;
+        }
+        let (_, endpoint) = self.entries.remove(position);
+        self.reorder();
+        Some(endpoint)
+    }
+
+    /// Iterates over the `(address, endpoint)` pairs in order.
+    pub fn iter(&self) -> EndpointIter<'_, T> {
+        self.entries.iter().map(|(addr, endpoint)| (addr, endpoint))
+    }
+
+    /// Iterates mutably over the `(address, endpoint)` pairs in order.
+    pub fn iter_mut(&mut self) -> EndpointIterMut<'_, T> {
+        self.entries
+            .iter_mut()
+            .map(|(addr, endpoint)| (&*addr, endpoint))
+    }
+
+    /// Iterates over the addresses in order.
+    pub fn keys(&self) -> impl Iterator<Item = &T::Address> + '_ {
+        self.entries.iter().map(|(addr, _)| addr)
+    }
+
+    /// Iterates over the endpoints in order.
+    pub fn values(&self) -> impl Iterator<Item = &UdpProtocol<T>> + '_ {
+        self.entries.iter().map(|(_, endpoint)| endpoint)
+    }
+
+    /// Iterates mutably over the endpoints in order.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut UdpProtocol<T>> + '_ {
+        self.entries.iter_mut().map(|(_, endpoint)| endpoint)
+    }
+
+    /// The sort key of an entry: endpoints owning a handle first, by their
+    /// lowest one, then by address.
+    fn order_key<'a>(
+        addr: &'a T::Address,
+        endpoint: &UdpProtocol<T>,
+    ) -> (bool, Option<PlayerHandle>, &'a T::Address) {
+        let lowest = endpoint.handles().iter().min().copied();
+        (lowest.is_none(), lowest, addr)
+    }
+
+    /// Sorts the entries into iteration order and rebuilds the index.
+    fn reorder(&mut self) {
+        self.entries.sort_by(|(a_addr, a), (b_addr, b)| {
+            Self::order_key(a_addr, a).cmp(&Self::order_key(b_addr, b))
+        });
+        self.index = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(position, (addr, _))| (addr.clone(), position))
+            .collect();
+    }
+
+    /// Checks the entries are in iteration order and the index points at
+    /// each of them.
+    fn check_order(&self, field: &str) -> Result<(), InvariantViolation> {
+        let ordered = self.entries.windows(2).all(|pair| match pair {
+            [(a_addr, a), (b_addr, b)] => Self::order_key(a_addr, a) < Self::order_key(b_addr, b),
+            _ => true,
+        });
+        if !ordered {
+            return Err(InvariantViolation::new(
+                "PlayerRegistry",
+                "endpoints are not ordered by lowest handle, then address",
+            )
+            .with_details(field));
+        }
+        let indexed = self.index.len() == self.entries.len()
+            && self
+                .entries
+                .iter()
+                .enumerate()
+                .all(|(position, (addr, _))| self.index.get(addr) == Some(&position));
+        if !indexed {
+            return Err(InvariantViolation::new(
+                "PlayerRegistry",
+                "endpoint index does not match the endpoints",
+            )
+            .with_details(field));
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config> Default for EndpointMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Config> IntoIterator for &'a EndpointMap<T> {
+    type Item = (&'a T::Address, &'a UdpProtocol<T>);
+    type IntoIter = EndpointIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Config> IntoIterator for &'a mut EndpointMap<T> {
+    type Item = (&'a T::Address, &'a mut UdpProtocol<T>);
+    type IntoIter = EndpointIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 /// Registry tracking all players and their connection states.
 ///
 /// # Note
@@ -21,9 +241,9 @@ pub struct PlayerRegistry<T>
     /// Map from player handles to their types.
     pub handles: BTreeMap<PlayerHandle, PlayerType<T::Address>>,
     /// Map from addresses to protocol handlers for remote players.
-    pub remotes: BTreeMap<T::Address, UdpProtocol<T>>,
+    pub remotes: EndpointMap<T>,
     /// Map from addresses to protocol handlers for spectators.
-    pub spectators: BTreeMap<T::Address, UdpProtocol<T>>,
+    pub spectators: EndpointMap<T>,
 }
 
 impl<T> std::fmt::Debug for PlayerRegistry<T>
@@ -40,8 +260,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 
         f.debug_struct("PlayerRegistry")
             .field("handles", handles)
-            .field("remotes", &remotes.keys())
-            .field("spectators", &spectators.keys())
+            .field("remotes", &remotes.keys().collect::<Vec<_>>())
+            .field("spectators", &spectators.keys().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -52,8 +272,8 @@ impl<T: Config> PlayerRegistry<T> {
     pub fn new() -> Self {
         Self {
             handles: BTreeMap::new(),
-            remotes: BTreeMap::new(),
-            spectators: BTreeMap::new(),
+            remotes: EndpointMap::new(),
+            spectators: EndpointMap::new(),
         }
     }
 
@@ -541,6 +761,19 @@ fn default() -> Self {
     }
 }
 
+impl<T: Config> InvariantChecker for PlayerRegistry<T> {
+    /// Checks the invariants of the PlayerRegistry.
+    ///
+    /// # Invariants
+    ///
+    /// 1. Remote endpoints iterate by lowest owned handle, then address
+    /// 2. Spectator endpoints iterate by lowest owned handle, then address
+    fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        self.remotes.check_order("remotes")?;
+        self.spectators.check_order("spectators")
+    }
+}
+
 #[cfg(test)]
 #[allow(
     clippy::panic,
@@ -550,7 +783,11 @@ fn default() -> Self {
 )]
 mod tests {
     use super::*;
+    use crate::sessions::config::{ProtocolConfig, SyncConfig};
+    use crate::time_sync::TimeSyncConfig;
+    use crate::DesyncDetection;
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::time::Duration;
 
     struct TestConfig;
 
@@ -1003,4 +1240,93 @@ fn remote_player_handle_required_ignores_local_and_spectators() {
         let result = registry.remote_player_handle_required();
         assert!(result.is_err());
     }
+
+    // ==========================================
+    // EndpointMap order tests
+    // ==========================================
+
+    fn endpoint(handles: &[usize], port: u16) -> UdpProtocol<TestConfig> {
+        UdpProtocol::new(
+            handles.iter().copied().map(PlayerHandle::new).collect(),
+            test_addr(port),
+            4,
+            1,
+            8,
+            Duration::from_secs(5),
+            Duration::from_secs(3),
+            60,
+            DesyncDetection::Off,
+            SyncConfig::default(),
+            ProtocolConfig::default(),
+            TimeSyncConfig::default(),
+        )
+        .unwrap()
+    }
+
+    /// Endpoints as `(port, handles)`, inserted in the given order.
+    fn endpoint_map(endpoints: &[(u16, &[usize])]) -> EndpointMap<TestConfig> {
+        let mut map = EndpointMap::new();
+        for &(port, handles) in endpoints {
+            map.insert(test_addr(port), endpoint(handles, port));
+        }
+        map
+    }
+
+    fn ports(map: &EndpointMap<TestConfig>) -> Vec<u16> {
+        map.keys().map(SocketAddr::port).collect()
+    }
+
+    #[test]
+    fn endpoint_map_iterates_by_lowest_handle_whatever_the_insertion_order() {
+        // the address order is the reverse of the handle order
+        let endpoints: [(u16, &[usize]); 4] =
+            [(9000, &[0]), (8000, &[3, 1]), (7000, &[2]), (6000, &[])];
+        let forward = endpoint_map(&endpoints);
+        let mut reversed = endpoints;
+        reversed.reverse();
+        let reversed = endpoint_map(&reversed);
+
+        // the observer without handles comes last
+        assert_eq!(ports(&forward), [9000, 8000, 7000, 6000]);
+        assert_eq!(ports(&reversed), ports(&forward));
+        let handles: Vec<_> = forward
+            .values()
+            .map(|endpoint| endpoint.handles().to_vec())
+            .collect();
+        assert_eq!(handles[1], [PlayerHandle::new(1), PlayerHandle::new(3)]);
+        assert!(forward.check_order("remotes").is_ok());
+    }
+
+    #[test]
+    fn endpoint_map_breaks_ties_by_address_and_keeps_lookups_after_removal() {
+        let mut map = endpoint_map(&[(7002, &[]), (7001, &[]), (7003, &[1])]);
+        assert_eq!(ports(&map), [7003, 7001, 7002]);
+        assert_eq!(map.len(), 3);
+
+        assert!(map.remove(&test_addr(7001)).is_some());
+        assert!(map.remove(&test_addr(7001)).is_none());
+        assert!(!map.contains_key(&test_addr(7001)));
+        assert_eq!(ports(&map), [7003, 7002]);
+        assert_eq!(
+            map.get(&test_addr(7002)).unwrap().peer_addr(),
+            test_addr(7002)
+        );
+        assert!(map.get_mut(&test_addr(7003)).is_some());
+
+        // replacing an endpoint keeps a single entry
+        assert!(map.insert(test_addr(7002), endpoint(&[0], 7002)).is_some());
+        assert_eq!(ports(&map), [7002, 7003]);
+        assert!(map.check_order("remotes").is_ok());
+    }
+
+    #[test]
+    fn registry_invariants_check_the_endpoint_order() {
+        let mut registry = PlayerRegistry::<TestConfig>::new();
+        registry.remotes = endpoint_map(&[(7001, &[2]), (7000, &[1])]);
+        assert!(registry.check_invariants().is_ok());
+
+        registry.remotes.entries.swap(0, 1);
+        let violation = registry.check_invariants().unwrap_err();
+        assert!(violation.to_string().contains("not ordered"));
+    }
 }
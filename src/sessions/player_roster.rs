@@ -0,0 +1,342 @@
+//! Stable player ids mapped onto session player handles.
+//!
+//! This module provides the [`PlayerRoster`] that associates application ids
+//! (account ids, entity ids, display names) with [`PlayerHandle`]s. Register
+//! ids with [`SessionBuilder::add_player_with_id`] and read the roster back
+//! from [`P2PSession::roster`]; the session keeps it current as players
+//! disconnect, are dropped, and rejoin.
+//!
+//! [`SessionBuilder::add_player_with_id`]: crate::SessionBuilder::add_player_with_id
+//! [`P2PSession::roster`]: crate::P2PSession::roster
+
+use crate::error::{FortressError, InvalidRequestKind};
+use crate::PlayerHandle;
+use std::fmt;
+
+/// A stable, application-provided player id.
+///
+/// Converts from `u64`, `String`, and `&str`, so any of them can be passed to
+/// [`SessionBuilder::add_player_with_id`](crate::SessionBuilder::add_player_with_id).
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(untagged)]
+pub enum PlayerId {
+    /// A numeric id, e.g. an account or entity id.
+    Number(u64),
+    /// A textual id, e.g. a user name.
+    Name(String),
+}
+
+impl From<u64> for PlayerId {
+    fn from(id: u64) -> Self {
+        Self::Number(id)
+    }
+}
+
+impl From<String> for PlayerId {
+    fn from(id: String) -> Self {
+        Self::Name(id)
+    }
+}
+
+impl From<&str> for PlayerId {
+    fn from(id: &str) -> Self {
+        Self::Name(id.to_owned())
+    }
+}
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(id) => write!(f, "{id}"),
+            Self::Name(id) => f.write_str(id),
+        }
+    }
+}
+
+/// One roster entry: a player handle, its stable id, and whether the session
+/// currently considers that player connected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RosterEntry {
+    handle: PlayerHandle,
+    id: PlayerId,
+    connected: bool,
+}
+
+impl RosterEntry {
+    /// Returns the player's handle.
+    #[must_use]
+    pub fn handle(&self) -> PlayerHandle {
+        self.handle
+    }
+
+    /// Returns the player's stable id.
+    #[must_use]
+    pub fn id(&self) -> &PlayerId {
+        &self.id
+    }
+
+    /// Returns `true` while the session considers the player connected.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
+
+/// Two-way mapping between stable [`PlayerId`]s and [`PlayerHandle`]s.
+///
+/// A handle keeps its id for the whole session: when a player disconnects or
+/// is dropped the entry stays and is only marked disconnected, and when a
+/// player rejoins the same slot (hot-join) the entry is marked connected again
+/// under the same id.
+///
+/// Rosters are small (one entry per player), so lookups scan the entries,
+/// which are kept in handle order.
+///
+/// # Serialization
+///
+/// Serializes as the list of entries in handle order. Deserializing rejects a
+/// list that repeats a handle or an id.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::{PlayerHandle, PlayerRoster, PlayerId};
+///
+/// let roster = PlayerRoster::new();
+/// assert!(roster.is_empty());
+/// assert_eq!(roster.handle_of(&PlayerId::from(42)), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "Vec<RosterEntry>", into = "Vec<RosterEntry>")]
+pub struct PlayerRoster {
+    entries: Vec<RosterEntry>,
+}
+
+impl PlayerRoster {
+    /// Creates an empty roster.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the handle associated with `id`, if any.
+    #[must_use]
+    pub fn handle_of(&self, id: &PlayerId) -> Option<PlayerHandle> {
+        self.entries
+            .iter()
+            .find(|entry| entry.id == *id)
+            .map(|entry| entry.handle)
+    }
+
+    /// Returns the id associated with `handle`, if any.
+    #[must_use]
+    pub fn id_of(&self, handle: PlayerHandle) -> Option<&PlayerId> {
+        self.entry(handle).map(|entry| &entry.id)
+    }
+
+    /// Returns whether the player at `handle` is connected, or `None` if the
+    /// handle has no id.
+    #[must_use]
+    pub fn is_connected(&self, handle: PlayerHandle) -> Option<bool> {
+        self.entry(handle).map(|entry| entry.connected)
+    }
+
+    /// Iterates over the entries in handle order.
+    pub fn iter(&self) -> impl Iterator<Item = &RosterEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no player has an id.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn entry(&self, handle: PlayerHandle) -> Option<&RosterEntry> {
+        self.entries
+            .binary_search_by_key(&handle, |entry| entry.handle)
+            .ok()
+            .and_then(|index| self.entries.get(index))
+    }
+
+    /// Checks that `handle` and `id` can be added without touching the roster.
+    pub(crate) fn check_insert(
+        &self,
+        handle: PlayerHandle,
+        id: &PlayerId,
+    ) -> Result<(), FortressError> {
+        if self.entry(handle).is_some() {
+            return Err(InvalidRequestKind::PlayerHandleInUse { handle }.into());
+        }
+        if let Some(holder) = self.handle_of(id) {
+            return Err(InvalidRequestKind::PlayerIdInUse { handle: holder }.into());
+        }
+        Ok(())
+    }
+
+    /// Adds a connected entry, keeping the entries in handle order.
+    pub(crate) fn insert(
+        &mut self,
+        handle: PlayerHandle,
+        id: PlayerId,
+    ) -> Result<(), FortressError> {
+        self.check_insert(handle, &id)?;
+        let index = self.entries.partition_point(|entry| entry.handle < handle);
+        // alloc-bound: one entry per player handle, checked unique above.
+        self.entries.insert(
+            index,
+            RosterEntry {
+                handle,
+                id,
+                connected: true,
+            },
+        );
+        Ok(())
+    }
+
+    /// Marks the player at `handle` connected or disconnected. Handles without
+    /// an id are ignored.
+    pub(crate) fn set_connected(&mut self, handle: PlayerHandle, connected: bool) {
+        if let Ok(index) = self
+            .entries
+            .binary_search_by_key(&handle, |entry| entry.handle)
+        {
+            if let Some(entry) = self.entries.get_mut(index) {
+                entry.connected = connected;
+            }
+        }
+    }
+}
+
+impl TryFrom<Vec<RosterEntry>> for PlayerRoster {
+    type Error = FortressError;
+
+    fn try_from(entries: Vec<RosterEntry>) -> Result<Self, Self::Error> {
+        let mut roster = Self::new();
+        for entry in entries {
+            let connected = entry.connected;
+            let handle = entry.handle;
+            roster.insert(handle, entry.id)?;
+            roster.set_connected(handle, connected);
+        }
+        Ok(roster)
+    }
+}
+
+impl From<PlayerRoster> for Vec<RosterEntry> {
+    fn from(roster: PlayerRoster) -> Self {
+        roster.entries
+    }
+}
+
+impl<'a> IntoIterator for &'a PlayerRoster {
+    type Item = &'a RosterEntry;
+    type IntoIter = std::slice::Iter<'a, RosterEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    fn roster() -> PlayerRoster {
+        let mut roster = PlayerRoster::new();
+        roster.insert(PlayerHandle::new(2), "carol".into()).unwrap();
+        roster.insert(PlayerHandle::new(0), 7.into()).unwrap();
+        roster.insert(PlayerHandle::new(1), "bob".into()).unwrap();
+        roster
+    }
+
+    #[test]
+    fn lookups_work_both_ways_and_iterate_in_handle_order() {
+        let roster = roster();
+        assert_eq!(roster.len(), 3);
+        assert_eq!(
+            roster.handle_of(&PlayerId::from("bob")),
+            Some(PlayerHandle::new(1))
+        );
+        assert_eq!(
+            roster.handle_of(&PlayerId::Number(7)),
+            Some(PlayerHandle::new(0))
+        );
+        assert_eq!(roster.handle_of(&PlayerId::from("dave")), None);
+        assert_eq!(
+            roster.id_of(PlayerHandle::new(2)),
+            Some(&PlayerId::from("carol"))
+        );
+        assert_eq!(roster.id_of(PlayerHandle::new(3)), None);
+        assert_eq!(
+            roster.iter().map(RosterEntry::handle).collect::<Vec<_>>(),
+            vec![
+                PlayerHandle::new(0),
+                PlayerHandle::new(1),
+                PlayerHandle::new(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_handles_and_ids_are_rejected() {
+        let mut roster = roster();
+        assert!(matches!(
+            roster.insert(PlayerHandle::new(1), "erin".into()),
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::PlayerHandleInUse { .. }
+            })
+        ));
+        assert!(matches!(
+            roster.insert(PlayerHandle::new(3), "carol".into()),
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::PlayerIdInUse { handle }
+            }) if handle == PlayerHandle::new(2)
+        ));
+        assert_eq!(roster.len(), 3);
+    }
+
+    #[test]
+    fn connection_state_changes_keep_the_id_on_the_handle() {
+        let mut roster = roster();
+        roster.set_connected(PlayerHandle::new(1), false);
+        assert_eq!(roster.is_connected(PlayerHandle::new(1)), Some(false));
+        assert_eq!(
+            roster.handle_of(&PlayerId::from("bob")),
+            Some(PlayerHandle::new(1))
+        );
+        roster.set_connected(PlayerHandle::new(1), true);
+        assert_eq!(roster.is_connected(PlayerHandle::new(1)), Some(true));
+        roster.set_connected(PlayerHandle::new(5), false);
+        assert_eq!(roster.is_connected(PlayerHandle::new(5)), None);
+    }
+
+    #[test]
+    fn serde_round_trip_preserves_entries_and_rejects_duplicates() {
+        let mut roster = roster();
+        roster.set_connected(PlayerHandle::new(2), false);
+        let json = serde_json::to_string(&roster).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"handle":0,"id":7,"connected":true},{"handle":1,"id":"bob","connected":true},{"handle":2,"id":"carol","connected":false}]"#
+        );
+        assert_eq!(serde_json::from_str::<PlayerRoster>(&json).unwrap(), roster);
+
+        let duplicate = r#"[{"handle":0,"id":"bob","connected":true},{"handle":1,"id":"bob","connected":true}]"#;
+        assert!(serde_json::from_str::<PlayerRoster>(duplicate).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Previous-frame inputs for the `previous_inputs` field of
+//! [`FortressRequest::AdvanceFrame`](crate::FortressRequest::AdvanceFrame).
+//!
+//! With [`SessionBuilder::with_previous_inputs_in_requests`](crate::SessionBuilder::with_previous_inputs_in_requests)
+//! enabled, a session records the inputs of every frame it simulates, keyed by
+//! frame. Simulating a frame again (a rollback) drops the entries for that
+//! frame and everything after it, so the entry for `frame - 1` is always the
+//! one from the pass that produced the state being advanced.
+
+use std::collections::VecDeque;
+
+use crate::error::allocation_failed;
+use crate::{FortressError, Frame, InputVec};
+
+/// Bounded history of the inputs handed out with each simulated frame.
+#[derive(Debug)]
+pub(crate) struct PreviousInputs<I> {
+    /// `(frame, inputs)` pairs in ascending frame order.
+    frames: VecDeque<(Frame, InputVec<I>)>,
+    /// Most entries kept; `0` disables the history.
+    capacity: usize,
+}
+
+impl<I: Copy> PreviousInputs<I> {
+    /// A history that records nothing; [`record`](Self::record) always returns
+    /// empty inputs.
+    pub(crate) fn disabled() -> Self {
+        Self {
+            frames: VecDeque::new(),
+            capacity: 0,
+        }
+    }
+
+    /// A history keeping the `capacity` most recent frames, or a disabled one
+    /// when `enabled` is false. `capacity` must cover the deepest rollback plus
+    /// one, so the frame before a rollback's load frame is still known.
+    pub(crate) fn new(enabled: bool, capacity: usize) -> Result<Self, FortressError> {
+        if !enabled {
+            return Ok(Self::disabled());
+        }
+        let capacity = capacity.max(1);
+        let mut frames = VecDeque::new();
+        // alloc-bound: `capacity` is derived from the validated prediction
+        // window or check distance.
+        frames
+            .try_reserve_exact(capacity)
+            .map_err(|_err| allocation_failed("previous_inputs.frames", capacity))?;
+        Ok(Self { frames, capacity })
+    }
+
+    /// Forgets every recorded frame, for a session restored to a state this
+    /// history may not lead to.
+    pub(crate) fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Records `inputs` as the inputs of `frame` and returns the inputs
+    /// recorded for `frame - 1`, or empty inputs when that frame is unknown or
+    /// the history is disabled.
+    pub(crate) fn record(
+        &mut self,
+        frame: Frame,
+        inputs: &InputVec<I>,
+    ) -> Result<InputVec<I>, FortressError> {
+        if self.capacity == 0 {
+            return Ok(InputVec::new());
+        }
+        while self.frames.back().is_some_and(|(f, _)| *f >= frame) {
+            self.frames.pop_back();
+        }
+        let previous = match self.frames.back() {
+            Some((f, previous)) if *f == frame - 1 => copy_inputs(previous)?,
+            _ => InputVec::new(),
+        };
+        if self.frames.len() >= self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back((frame, copy_inputs(inputs)?));
+        Ok(previous)
+    }
+}
+
+/// Copies `inputs` without aborting on allocation failure.
+fn copy_inputs<I: Copy>(inputs: &InputVec<I>) -> Result<InputVec<I>, FortressError> {
+    let mut copy = InputVec::new();
+    copy.try_reserve_exact(inputs.len())
+        .map_err(|_err| allocation_failed("previous_inputs.inputs", inputs.len()))?;
+    copy.extend_from_slice(inputs);
+    Ok(copy)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+    use crate::InputStatus;
+
+    fn inputs(value: u8) -> InputVec<u8> {
+        let mut inputs = InputVec::new();
+        inputs.push((value, InputStatus::Confirmed));
+        inputs.push((value, InputStatus::Predicted));
+        inputs
+    }
+
+    #[test]
+    fn disabled_history_returns_empty_inputs() {
+        let mut history = PreviousInputs::<u8>::disabled();
+        assert!(history
+            .record(Frame::new(0), &inputs(1))
+            .unwrap()
+            .is_empty());
+        assert!(history
+            .record(Frame::new(1), &inputs(2))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn returns_the_inputs_of_the_previous_frame() {
+        let mut history = PreviousInputs::new(true, 4).unwrap();
+        assert!(history
+            .record(Frame::new(0), &inputs(1))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            history.record(Frame::new(1), &inputs(2)).unwrap(),
+            inputs(1)
+        );
+        assert_eq!(
+            history.record(Frame::new(2), &inputs(3)).unwrap(),
+            inputs(2)
+        );
+    }
+
+    #[test]
+    fn resimulation_sees_the_corrected_inputs() {
+        let mut history = PreviousInputs::new(true, 4).unwrap();
+        for frame in 0..4 {
+            history.record(Frame::new(frame), &inputs(1)).unwrap();
+        }
+        // Roll back to frame 2: frame 1 keeps its inputs, then the corrected
+        // frame 2 is what frame 3 sees.
+        assert_eq!(
+            history.record(Frame::new(2), &inputs(7)).unwrap(),
+            inputs(1)
+        );
+        assert_eq!(
+            history.record(Frame::new(3), &inputs(8)).unwrap(),
+            inputs(7)
+        );
+    }
+
+    #[test]
+    fn frames_beyond_the_capacity_are_unknown() {
+        let mut history = PreviousInputs::new(true, 2).unwrap();
+        for frame in 0..4 {
+            history.record(Frame::new(frame), &inputs(1)).unwrap();
+        }
+        assert!(history.frames.len() <= 2);
+        assert!(history
+            .record(Frame::new(2), &inputs(2))
+            .unwrap()
+            .is_empty());
+        // A gap (e.g. after loading a snapshot) leaves the previous frame unknown.
+        assert!(history
+            .record(Frame::new(5), &inputs(3))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -61,7 +61,7 @@
 use std::sync::Arc;
 
 use crate::error::allocation_failed;
-use crate::replay::Replay;
+use crate::replay::{Replay, ReplayMetadata};
 use crate::sessions::builder::DEFAULT_EVENT_QUEUE_SIZE;
 use crate::sessions::event_drain::enqueue_event_bounded;
 use crate::sessions::session_trait::Session;
@@ -155,6 +155,14 @@ pub struct ReplaySession<T>
     /// Stored as `(frame, cell)` so we can compare the checksum after the user
     /// has filled the cell.
     pending_validation: Option<(Frame, GameStateCell<T::State>)>,
+    /// Whether each `AdvanceFrame` carries the previous frame's inputs.
+    previous_inputs: bool,
+    /// Frames between interval `SaveGameState` requests; `0` disables them.
+    save_interval: usize,
+    /// How many inputs each frame of a bare recorded stream held before it
+    /// was padded to `num_players`; the padding plays back as
+    /// [`InputStatus::Disconnected`]. Empty when no frame was padded.
+    recorded_players: Vec<usize>,
 }
 
 impl<T: Config> ReplaySession<T> {
@@ -205,7 +213,7 @@ impl<T: Config> ReplaySession<T> {
     /// Returns an error if the replay fails internal consistency validation
     /// (see [`Replay::validate`]) or the default event queue cannot be reserved.
     pub fn new(replay: Replay<T::Input>) -> FortressResult<Self> {
-        Self::new_with_options(replay, false, DEFAULT_EVENT_QUEUE_SIZE, None)
+        Self::new_with_options(replay, false, DEFAULT_EVENT_QUEUE_SIZE, None, false, 0)
     }
 
     /// Creates a new [`ReplaySession`] with checksum validation enabled.
@@ -264,7 +272,7 @@ pub fn new(replay: Replay<T::Input>) -> FortressResult<Self> {
     /// Returns an error if the replay fails internal consistency validation
     /// (see [`Replay::validate`]) or the default event queue cannot be reserved.
     pub fn new_with_validation(replay: Replay<T::Input>) -> FortressResult<Self> {
-        Self::new_with_options(replay, true, DEFAULT_EVENT_QUEUE_SIZE, None)
+        Self::new_with_options(replay, true, DEFAULT_EVENT_QUEUE_SIZE, None, false, 0)
     }
 
     /// Shared construction tail for public direct constructors and builder paths.
@@ -273,8 +281,94 @@ pub(crate) fn new_with_options(
         validate_checksums: bool,
         max_event_queue_size: usize,
         violation_observer: Option<Arc<dyn ViolationObserver>>,
+        previous_inputs: bool,
+        save_interval: usize,
     ) -> FortressResult<Self> {
         replay.validate()?;
+        Self::assemble(
+            replay,
+            validate_checksums,
+            max_event_queue_size,
+            violation_observer,
+            previous_inputs,
+            save_interval,
+        )
+    }
+
+    /// Builder path for a bare recorded input stream, one `Vec` per frame
+    /// indexed by player handle.
+    ///
+    /// Frames may hold fewer than `num_players` inputs, as when a recording
+    /// lost a player partway. They are padded with default inputs, so the
+    /// [`replay`](Self::replay) passes [`Replay::validate`], and the padded
+    /// trailing handles play back as [`InputStatus::Disconnected`].
+    pub(crate) fn from_recorded_inputs(
+        mut inputs: Vec<Vec<T::Input>>,
+        num_players: usize,
+        max_event_queue_size: usize,
+        violation_observer: Option<Arc<dyn ViolationObserver>>,
+        previous_inputs: bool,
+        save_interval: usize,
+    ) -> FortressResult<Self> {
+        if inputs.iter().any(|frame| frame.len() > num_players) {
+            return Err(InvalidRequestKind::Custom(
+                "replay inputs: frame has more than num_players inputs",
+            )
+            .into());
+        }
+        let total_frames = inputs.len();
+        let mut checksums = Vec::new();
+        checksums
+            .try_reserve_exact(total_frames)
+            .map_err(|_err| allocation_failed("replay.checksums", total_frames))?;
+        // alloc-bound: `total_frames` was just reserved for and the frames already hold that many.
+        checksums.resize(total_frames, None);
+        let mut recorded_players = Vec::new();
+        if inputs.iter().any(|frame| frame.len() < num_players) {
+            recorded_players
+                .try_reserve_exact(total_frames)
+                .map_err(|_err| allocation_failed("replay.recorded_players", total_frames))?;
+            for frame in &mut inputs {
+                recorded_players.push(frame.len());
+                let missing = num_players.saturating_sub(frame.len());
+                // reserve-in-loop: each frame is its own `Vec`, grown to `num_players` once.
+                let reserved = frame.try_reserve_exact(missing);
+                reserved.map_err(|_err| allocation_failed("replay.frames", missing))?;
+                // alloc-bound: reserved just above.
+                frame.resize(num_players, T::Input::default());
+            }
+        }
+        let replay = Replay {
+            num_players,
+            frames: inputs,
+            checksums,
+            metadata: ReplayMetadata {
+                library_version: env!("CARGO_PKG_VERSION").to_string(),
+                num_players,
+                total_frames,
+                skipped_frames: 0,
+            },
+        };
+        let mut session = Self::assemble(
+            replay,
+            false,
+            max_event_queue_size,
+            violation_observer,
+            previous_inputs,
+            save_interval,
+        )?;
+        session.recorded_players = recorded_players;
+        Ok(session)
+    }
+
+    fn assemble(
+        replay: Replay<T::Input>,
+        validate_checksums: bool,
+        max_event_queue_size: usize,
+        violation_observer: Option<Arc<dyn ViolationObserver>>,
+        previous_inputs: bool,
+        save_interval: usize,
+    ) -> FortressResult<Self> {
         let mut event_queue = VecDeque::new();
         event_queue
             .try_reserve_exact(max_event_queue_size)
@@ -289,6 +383,9 @@ pub(crate) fn new_with_options(
             violation_observer,
             validate_checksums,
             pending_validation: None,
+            previous_inputs,
+            save_interval,
+            recorded_players: Vec::new(),
         })
     }
 
@@ -466,6 +563,52 @@ pub fn total_frames(&self) -> usize {
         self.replay.total_frames()
     }
 
+    /// Returns how many more frames [`advance_frame`](Session::advance_frame)
+    /// will play back before the replay is exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fortress_rollback::replay::{Replay, ReplayMetadata};
+    /// # use fortress_rollback::sessions::replay_session::ReplaySession;
+    /// # use fortress_rollback::{Config, Session};
+    /// # use serde::{Deserialize, Serialize};
+    /// # use std::net::SocketAddr;
+    /// # #[derive(Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+    /// # struct Input(u8);
+    /// # #[derive(Debug)]
+    /// # struct Cfg;
+    /// # impl Config for Cfg {
+    /// #     type Input = Input;
+    /// #     type State = ();
+    /// #     type Address = SocketAddr;
+    /// # }
+    /// let replay = Replay {
+    ///     num_players: 1,
+    ///     frames: vec![vec![Input(0)]; 3],
+    ///     checksums: vec![None; 3],
+    ///     metadata: ReplayMetadata {
+    ///         library_version: String::new(),
+    ///         num_players: 1,
+    ///         total_frames: 3,
+    ///         skipped_frames: 0,
+    ///     },
+    /// };
+    /// let mut session = ReplaySession::<Cfg>::new(replay)?;
+    /// assert_eq!(session.frames_remaining(), 3);
+    /// let _ = session.advance_frame()?;
+    /// assert_eq!(session.frames_remaining(), 2);
+    /// # Ok::<(), fortress_rollback::FortressError>(())
+    /// ```
+    #[must_use]
+    pub fn frames_remaining(&self) -> usize {
+        let total = self.replay.total_frames();
+        match self.current_frame.try_as_usize() {
+            Ok(played) => total.saturating_sub(played.saturating_add(1)),
+            Err(_) => total,
+        }
+    }
+
     /// Returns `true` if all frames in the replay have been played back.
     ///
     /// # Example
@@ -662,11 +805,24 @@ pub fn current_state(&self) -> SessionState {
         SessionState::Running
     }
 
+    /// How many leading inputs of frame `frame_index` were recorded rather
+    /// than padded.
+    fn recorded_players(&self, frame_index: usize) -> usize {
+        self.recorded_players
+            .get(frame_index)
+            .copied()
+            .unwrap_or(self.replay.num_players)
+    }
+
     /// Advances the replay by one frame, returning the recorded inputs.
     ///
     /// Returns a single [`FortressRequest::AdvanceFrame`] containing the
-    /// confirmed inputs for the next frame. Returns an error if there are
-    /// no more frames to play back.
+    /// confirmed inputs for the next frame, preceded by a
+    /// [`FortressRequest::SaveGameState`] when validating or when the frame
+    /// falls on the builder's replay save interval. Handles the recording
+    /// has no input for on this frame get a default input with
+    /// [`InputStatus::Disconnected`]. Returns an error if there are no more
+    /// frames to play back.
     ///
     /// # Errors
     ///
@@ -728,14 +884,16 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                         last_frame: self.current_frame,
                     },
                 })?;
-
-        let mut inputs = InputVec::new();
-        inputs
-            .try_reserve(frame_inputs.len())
-            .map_err(|_err| allocation_failed("replay_session.inputs", frame_inputs.len()))?;
-        for input in frame_inputs {
-            inputs.push((*input, InputStatus::Confirmed));
-        }
+        let inputs = recorded_inputs(frame_inputs, self.recorded_players(frame_index))?;
+        let previous_inputs = match frame_index.checked_sub(1) {
+            Some(previous) if self.previous_inputs => {
+                self.replay.frames.get(previous).map_or_else(
+                    || Ok(InputVec::new()),
+                    |inputs| recorded_inputs(inputs, self.recorded_players(previous)),
+                )?
+            },
+            _ => InputVec::new(),
+        };
 
         self.current_frame = next_frame;
 
@@ -748,13 +906,40 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                 frame: next_frame,
             });
             self.pending_validation = Some((next_frame, cell));
+        } else if self.save_interval > 0 && frame_index % self.save_interval == 0 {
+            requests.push(FortressRequest::SaveGameState {
+                cell: GameStateCell::default(),
+                frame: next_frame,
+            });
         }
 
-        requests.push(FortressRequest::AdvanceFrame { inputs });
+        requests.push(FortressRequest::AdvanceFrame {
+            inputs,
+            previous_inputs,
+        });
         Ok(requests)
     }
 }
 
+/// Inputs of one frame, the first `recorded` as [`InputStatus::Confirmed`]
+/// and the padding after them as [`InputStatus::Disconnected`].
+fn recorded_inputs<I: Copy>(frame_inputs: &[I], recorded: usize) -> FortressResult<InputVec<I>> {
+    let len = frame_inputs.len();
+    let mut inputs = InputVec::new();
+    inputs
+        .try_reserve(len)
+        .map_err(|_err| allocation_failed("replay_session.inputs", len))?;
+    for (handle, input) in frame_inputs.iter().enumerate() {
+        let status = if handle < recorded {
+            InputStatus::Confirmed
+        } else {
+            InputStatus::Disconnected
+        };
+        inputs.push((*input, status));
+    }
+    Ok(inputs)
+}
+
 impl<T: Config> Session<T> for ReplaySession<T> {
     fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
         Self::advance_frame(self)
@@ -779,6 +964,14 @@ fn events(&mut self) -> EventDrain<'_, T> {
     fn current_state(&self) -> SessionState {
         Self::current_state(self)
     }
+
+    fn current_frame(&self) -> Frame {
+        Self::current_frame(self)
+    }
+
+    fn confirmed_frame(&self) -> Frame {
+        Self::current_frame(self)
+    }
 }
 
 impl<T: Config> fmt::Debug for ReplaySession<T> {
@@ -789,6 +982,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("is_complete", &self.is_complete())
             .field("num_players", &self.replay.num_players)
             .field("validate_checksums", &self.validate_checksums)
+            .field("save_interval", &self.save_interval)
             .field(
                 "pending_validation_frame",
                 &self.pending_validation.as_ref().map(|(frame, _)| *frame),
@@ -808,6 +1002,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     clippy::expect_used,
     clippy::indexing_slicing
 )]
+#[cfg_attr(not(feature = "telemetry"), allow(dead_code, unused_imports))]
 mod tests {
     use super::*;
     use crate::metrics::EventKind;
@@ -861,7 +1056,7 @@ fn advance_frame_returns_correct_inputs() {
         let requests = session.advance_frame().unwrap();
         assert_eq!(requests.len(), 1);
         match &requests[0] {
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 assert_eq!(inputs.len(), 2);
                 assert_eq!(inputs[0], (0, InputStatus::Confirmed));
                 assert_eq!(inputs[1], (1, InputStatus::Confirmed));
@@ -873,7 +1068,7 @@ fn advance_frame_returns_correct_inputs() {
         // Frame 1
         let requests = session.advance_frame().unwrap();
         match &requests[0] {
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 assert_eq!(inputs[0], (2, InputStatus::Confirmed));
                 assert_eq!(inputs[1], (3, InputStatus::Confirmed));
             },
@@ -981,7 +1176,7 @@ fn full_playback_single_player() {
             let requests = session.advance_frame().unwrap();
             assert_eq!(requests.len(), 1);
             match &requests[0] {
-                FortressRequest::AdvanceFrame { inputs } => {
+                FortressRequest::AdvanceFrame { inputs, .. } => {
                     assert_eq!(inputs.len(), 1);
                     assert_eq!(inputs[0].0, expected_frame as u8);
                     assert_eq!(inputs[0].1, InputStatus::Confirmed);
@@ -1064,6 +1259,7 @@ fn validation_mode_detects_checksum_mismatch() {
         }
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn validation_overflow_is_bounded_counted_and_retains_newest_desyncs() {
         const CAP: usize = 10;
@@ -1427,4 +1623,100 @@ fn is_validating_returns_correct_value() {
         let validating = ReplaySession::<TestConfig>::new_with_validation(replay).unwrap();
         assert!(validating.is_validating());
     }
+
+    #[test]
+    fn short_recorded_frames_play_back_missing_handles_as_disconnected() {
+        let inputs = vec![vec![1, 2, 3], vec![4, 5], vec![6]];
+        let mut session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(3)
+            .unwrap()
+            .with_previous_inputs_in_requests(true)
+            .start_replay_session_from_inputs(inputs)
+            .unwrap();
+
+        let mut played = Vec::new();
+        while !session.is_complete() {
+            for request in session.advance_frame().unwrap() {
+                if let FortressRequest::AdvanceFrame {
+                    inputs,
+                    previous_inputs,
+                } = request
+                {
+                    played.push((inputs.to_vec(), previous_inputs.to_vec()));
+                }
+            }
+        }
+
+        let confirmed = InputStatus::Confirmed;
+        let disconnected = InputStatus::Disconnected;
+        assert_eq!(
+            played,
+            vec![
+                (vec![(1, confirmed), (2, confirmed), (3, confirmed)], vec![]),
+                (
+                    vec![(4, confirmed), (5, confirmed), (0, disconnected)],
+                    vec![(1, confirmed), (2, confirmed), (3, confirmed)],
+                ),
+                (
+                    vec![(6, confirmed), (0, disconnected), (0, disconnected)],
+                    vec![(4, confirmed), (5, confirmed), (0, disconnected)],
+                ),
+            ]
+        );
+        assert_eq!(session.frames_remaining(), 0);
+        assert!(session.advance_frame().is_err());
+    }
+
+    #[test]
+    fn recorded_inputs_reject_a_frame_with_more_inputs_than_players() {
+        let result = SessionBuilder::<TestConfig>::new()
+            .start_replay_session_from_inputs(vec![vec![1, 2], vec![3, 4, 5]]);
+        assert!(matches!(
+            result,
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::Custom(
+                    "replay inputs: frame has more than num_players inputs"
+                ),
+            })
+        ));
+    }
+
+    #[test]
+    fn short_recorded_frames_are_padded_in_the_replay() {
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(3)
+            .unwrap()
+            .start_replay_session_from_inputs(vec![vec![1, 2, 3], vec![4, 5], vec![6]])
+            .unwrap();
+        let replay = session.replay();
+        replay.validate().unwrap();
+        assert_eq!(
+            replay.frames,
+            vec![vec![1, 2, 3], vec![4, 5, 0], vec![6, 0, 0]]
+        );
+    }
+
+    #[test]
+    fn empty_recorded_inputs_are_complete_immediately() {
+        let mut session = SessionBuilder::<TestConfig>::new()
+            .start_replay_session_from_inputs(Vec::new())
+            .unwrap();
+        assert!(session.is_complete());
+        assert_eq!(session.frames_remaining(), 0);
+        assert!(session.advance_frame().is_err());
+    }
+
+    #[test]
+    fn validation_ignores_the_save_interval() {
+        let replay = make_replay(4, 1);
+        let mut session = SessionBuilder::<TestConfig>::new()
+            .with_replay_save_interval(3)
+            .start_replay_session_with_validation(replay)
+            .unwrap();
+        for _ in 0..4 {
+            let requests = session.advance_frame().unwrap();
+            assert!(matches!(requests[0], FortressRequest::SaveGameState { .. }));
+            assert_eq!(requests.len(), 2);
+        }
+    }
 }
@@ -0,0 +1,66 @@
+//! Which input mismatch triggered the most recent rollback.
+//!
+//! This module provides [`RollbackCause`], returned by
+//! [`P2PSession::last_rollback_cause`](crate::P2PSession::last_rollback_cause)
+//! for rollback debuggers and other tooling.
+
+use crate::error::{allocation_failed, FortressError};
+use crate::{Frame, PlayerHandle};
+
+/// The misprediction behind a rollback, captured when the session decided to
+/// roll back.
+///
+/// `triggering_players`, `predicted`, and `confirmed` are index-parallel: for
+/// each player whose input at [`first_incorrect_frame`](Self::first_incorrect_frame)
+/// was mispredicted, `predicted` holds the input the session simulated with
+/// and `confirmed` the input that actually arrived. A rollback forced by a
+/// disconnect rather than a misprediction lists no players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackCause<I> {
+    /// Players whose misprediction is at `first_incorrect_frame`, in handle order.
+    pub triggering_players: Vec<PlayerHandle>,
+    /// The earliest mispredicted frame; the rollback re-simulates from here.
+    pub first_incorrect_frame: Frame,
+    /// The predicted input of each triggering player at `first_incorrect_frame`.
+    pub predicted: Vec<I>,
+    /// The confirmed input of each triggering player at `first_incorrect_frame`.
+    pub confirmed: Vec<I>,
+}
+
+impl<I> RollbackCause<I> {
+    /// An empty cause, filled in by `SyncLayer::fill_rollback_cause`.
+    pub(crate) fn new() -> Self {
+        Self {
+            triggering_players: Vec::new(),
+            first_incorrect_frame: Frame::NULL,
+            predicted: Vec::new(),
+            confirmed: Vec::new(),
+        }
+    }
+
+    /// An empty cause with room for `players` triggering players.
+    pub(crate) fn try_with_capacity(players: usize) -> Result<Self, FortressError> {
+        let mut cause = Self::new();
+        cause
+            .triggering_players
+            .try_reserve_exact(players)
+            .map_err(|_err| allocation_failed("rollback_cause.triggering_players", players))?;
+        cause
+            .predicted
+            .try_reserve_exact(players)
+            .map_err(|_err| allocation_failed("rollback_cause.predicted", players))?;
+        cause
+            .confirmed
+            .try_reserve_exact(players)
+            .map_err(|_err| allocation_failed("rollback_cause.confirmed", players))?;
+        Ok(cause)
+    }
+
+    /// Empties the cause, keeping the allocations for reuse.
+    pub(crate) fn clear(&mut self, first_incorrect_frame: Frame) {
+        self.triggering_players.clear();
+        self.first_incorrect_frame = first_incorrect_frame;
+        self.predicted.clear();
+        self.confirmed.clear();
+    }
+}
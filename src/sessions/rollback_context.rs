@@ -0,0 +1,333 @@
+//! Which players' inputs changed in each frame a rollback re-simulates.
+//!
+//! With [`SessionBuilder::with_rollback_context`](crate::SessionBuilder::with_rollback_context)
+//! enabled, a [`P2PSession`](crate::P2PSession) keeps the inputs of its recently
+//! simulated frames. When a rollback simulates a frame again, the session
+//! compares the frame's new inputs with the ones it simulated the frame with
+//! before and lists the players whose input changed in the [`RollbackContext`]
+//! returned by
+//! [`P2PSession::current_rollback_context`](crate::P2PSession::current_rollback_context).
+//! The library still re-simulates the whole state; the context lets games
+//! whose players are loosely coupled re-run only the affected subsystems.
+
+use std::collections::VecDeque;
+
+use crate::error::allocation_failed;
+use crate::{FortressError, Frame, InputStatus, InputVec, PlayerHandle};
+
+/// The frames one [`advance_frame`](crate::P2PSession::advance_frame) call
+/// re-simulates, with the players whose input changed in each.
+///
+/// A frame's changed players are those whose input differs from the input
+/// the frame was simulated with before. A player counts as changed when their input value differs, or when they
+/// became disconnected (or reconnected) for the frame. A frame whose earlier
+/// simulation is no longer known, such as one from before a hot-join snapshot
+/// load, lists every player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackContext {
+    /// The frame restored by the batch's `LoadGameState`, or
+    /// [`Frame::NULL`] when the batch continues a capped re-simulation.
+    loaded_frame: Frame,
+    /// Each re-simulated frame, ascending, with the end of its players in
+    /// `players`.
+    frames: Vec<(Frame, usize)>,
+    /// The changed players of every frame in `frames`, back to back, in
+    /// handle order within a frame.
+    players: Vec<PlayerHandle>,
+}
+
+impl RollbackContext {
+    /// An empty context with room for `frames` frames of `players` players.
+    fn try_with_capacity(frames: usize, players: usize) -> Result<Self, FortressError> {
+        let mut context = Self {
+            loaded_frame: Frame::NULL,
+            frames: Vec::new(),
+            players: Vec::new(),
+        };
+        context
+            .frames
+            .try_reserve_exact(frames)
+            .map_err(|_err| allocation_failed("rollback_context.frames", frames))?;
+        let slots = frames.saturating_mul(players);
+        context
+            .players
+            .try_reserve_exact(slots)
+            .map_err(|_err| allocation_failed("rollback_context.players", slots))?;
+        Ok(context)
+    }
+
+    /// The frame the batch's `LoadGameState` request restores, or `None` when
+    /// the batch continues a re-simulation capped by
+    /// [`SessionBuilder::with_max_resimulation_per_advance`](crate::SessionBuilder::with_max_resimulation_per_advance)
+    /// in an earlier call.
+    #[must_use]
+    pub fn loaded_frame(&self) -> Option<Frame> {
+        (!self.loaded_frame.is_null()).then_some(self.loaded_frame)
+    }
+
+    /// The first re-simulated frame.
+    #[must_use]
+    pub fn first_frame(&self) -> Frame {
+        self.frames.first().map_or(Frame::NULL, |(frame, _)| *frame)
+    }
+
+    /// The last re-simulated frame.
+    #[must_use]
+    pub fn last_frame(&self) -> Frame {
+        self.frames.last().map_or(Frame::NULL, |(frame, _)| *frame)
+    }
+
+    /// Each re-simulated frame with the players whose input changed in it,
+    /// in the order of the batch's `AdvanceFrame` requests.
+    pub fn frames(&self) -> impl Iterator<Item = (Frame, &[PlayerHandle])> + '_ {
+        let mut start = 0;
+        self.frames.iter().map(move |&(frame, end)| {
+            let players = self.players.get(start..end).unwrap_or_default();
+            start = end;
+            (frame, players)
+        })
+    }
+
+    /// The players whose input changed in `frame`, in handle order; empty
+    /// when `frame` was not re-simulated by the batch.
+    #[must_use]
+    pub fn mispredicted_players(&self, frame: Frame) -> &[PlayerHandle] {
+        self.frames()
+            .find(|(resimulated, _)| *resimulated == frame)
+            .map_or(&[], |(_, players)| players)
+    }
+
+    /// Returns `true` if `player`'s input changed in `frame`.
+    #[must_use]
+    pub fn is_mispredicted(&self, frame: Frame, player: PlayerHandle) -> bool {
+        self.mispredicted_players(frame).contains(&player)
+    }
+
+    /// Empties the context, keeping the allocations for reuse.
+    fn clear(&mut self) {
+        self.loaded_frame = Frame::NULL;
+        self.frames.clear();
+        self.players.clear();
+    }
+}
+
+/// Inputs of recently simulated frames and the context of the current batch.
+#[derive(Debug)]
+pub(crate) struct RollbackTracker<I> {
+    /// `(frame, inputs)` pairs in ascending frame order.
+    simulated: VecDeque<(Frame, InputVec<I>)>,
+    /// Most entries kept in `simulated`.
+    capacity: usize,
+    context: RollbackContext,
+}
+
+impl<I: Copy + PartialEq> RollbackTracker<I> {
+    /// A tracker keeping the `capacity` most recent frames of `players`
+    /// players. `capacity` must cover the deepest rollback.
+    pub(crate) fn new(capacity: usize, players: usize) -> Result<Self, FortressError> {
+        let capacity = capacity.max(1);
+        let mut simulated = VecDeque::new();
+        // alloc-bound: `capacity` is derived from the validated prediction window.
+        simulated
+            .try_reserve_exact(capacity)
+            .map_err(|_err| allocation_failed("rollback_context.simulated", capacity))?;
+        Ok(Self {
+            simulated,
+            capacity,
+            context: RollbackContext::try_with_capacity(capacity, players)?,
+        })
+    }
+
+    /// Starts a new batch of requests, dropping the previous batch's context.
+    pub(crate) fn begin_batch(&mut self) {
+        self.context.clear();
+    }
+
+    /// Notes that the batch loads `frame` before re-simulating.
+    pub(crate) fn loaded(&mut self, frame: Frame) {
+        self.context.loaded_frame = frame;
+    }
+
+    /// Records `inputs` as the inputs `frame` is simulated with. A
+    /// `resimulated` frame is first compared with its earlier inputs and
+    /// added to the batch's context.
+    pub(crate) fn record(
+        &mut self,
+        frame: Frame,
+        inputs: &InputVec<I>,
+        resimulated: bool,
+    ) -> Result<(), FortressError> {
+        if resimulated {
+            let earlier = self
+                .simulated
+                .iter()
+                .find(|(simulated, _)| *simulated == frame)
+                .map(|(_, earlier)| earlier);
+            for (handle, (input, status)) in inputs.iter().enumerate() {
+                let unchanged = earlier.and_then(|earlier| earlier.get(handle)).is_some_and(
+                    |(earlier_input, earlier_status)| {
+                        earlier_input == input
+                            && (*earlier_status == InputStatus::Disconnected)
+                                == (*status == InputStatus::Disconnected)
+                    },
+                );
+                if !unchanged {
+                    // alloc-bound: reserved for a full prediction window of players.
+                    self.context.players.push(PlayerHandle::new(handle));
+                }
+            }
+            // alloc-bound: reserved for a full prediction window of frames.
+            self.context
+                .frames
+                .push((frame, self.context.players.len()));
+        }
+
+        // A re-simulated frame replaces its entry in place: the frames after it
+        // keep their earlier inputs until they are re-simulated in turn.
+        if let Some((_, slot)) = self.simulated.iter_mut().find(|(f, _)| *f == frame) {
+            return copy_into(slot, inputs);
+        }
+        while self.simulated.back().is_some_and(|(f, _)| *f >= frame) {
+            self.simulated.pop_back();
+        }
+        let mut slot = if self.simulated.len() >= self.capacity {
+            self.simulated
+                .pop_front()
+                .map_or_else(InputVec::new, |(_, slot)| slot)
+        } else {
+            InputVec::new()
+        };
+        copy_into(&mut slot, inputs)?;
+        self.simulated.push_back((frame, slot));
+        Ok(())
+    }
+
+    /// The current batch's context, or `None` when the batch re-simulates
+    /// nothing.
+    pub(crate) fn context(&self) -> Option<&RollbackContext> {
+        (!self.context.frames.is_empty()).then_some(&self.context)
+    }
+}
+
+/// Overwrites `slot` with `inputs` without aborting on allocation failure.
+fn copy_into<I: Copy>(slot: &mut InputVec<I>, inputs: &InputVec<I>) -> Result<(), FortressError> {
+    slot.clear();
+    slot.try_reserve_exact(inputs.len())
+        .map_err(|_err| allocation_failed("rollback_context.inputs", inputs.len()))?;
+    slot.extend_from_slice(inputs);
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    fn inputs(values: &[u8]) -> InputVec<u8> {
+        values
+            .iter()
+            .map(|value| (*value, InputStatus::Confirmed))
+            .collect()
+    }
+
+    fn simulate(tracker: &mut RollbackTracker<u8>, frames: std::ops::Range<i32>, values: &[u8]) {
+        for frame in frames {
+            tracker
+                .record(Frame::new(frame), &inputs(values), false)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn forward_simulation_has_no_context() {
+        let mut tracker = RollbackTracker::new(8, 2).unwrap();
+        tracker.begin_batch();
+        simulate(&mut tracker, 0..4, &[1, 1]);
+        assert_eq!(tracker.context(), None);
+    }
+
+    #[test]
+    fn lists_the_changed_players_of_each_resimulated_frame() {
+        let mut tracker = RollbackTracker::new(8, 3).unwrap();
+        simulate(&mut tracker, 0..5, &[1, 1, 1]);
+
+        tracker.begin_batch();
+        tracker.loaded(Frame::new(2));
+        tracker
+            .record(Frame::new(2), &inputs(&[1, 2, 1]), true)
+            .unwrap();
+        tracker
+            .record(Frame::new(3), &inputs(&[1, 1, 1]), true)
+            .unwrap();
+        tracker
+            .record(Frame::new(4), &inputs(&[3, 2, 1]), true)
+            .unwrap();
+        tracker
+            .record(Frame::new(5), &inputs(&[3, 2, 1]), false)
+            .unwrap();
+
+        let context = tracker.context().unwrap();
+        assert_eq!(context.loaded_frame(), Some(Frame::new(2)));
+        assert_eq!(context.first_frame(), Frame::new(2));
+        assert_eq!(context.last_frame(), Frame::new(4));
+        let frames: Vec<_> = context
+            .frames()
+            .map(|(frame, players)| (frame.as_i32(), players.to_vec()))
+            .collect();
+        assert_eq!(
+            frames,
+            vec![
+                (2, vec![PlayerHandle::new(1)]),
+                (3, vec![]),
+                (4, vec![PlayerHandle::new(0), PlayerHandle::new(1)]),
+            ]
+        );
+        assert!(context.is_mispredicted(Frame::new(4), PlayerHandle::new(0)));
+        assert!(!context.is_mispredicted(Frame::new(4), PlayerHandle::new(2)));
+        assert!(context.mispredicted_players(Frame::new(5)).is_empty());
+
+        tracker.begin_batch();
+        assert_eq!(tracker.context(), None);
+    }
+
+    #[test]
+    fn disconnecting_counts_as_a_change() {
+        let mut tracker = RollbackTracker::new(4, 2).unwrap();
+        simulate(&mut tracker, 0..2, &[0, 0]);
+        let mut disconnected = inputs(&[0, 0]);
+        disconnected[1].1 = InputStatus::Disconnected;
+
+        tracker.begin_batch();
+        tracker.record(Frame::new(1), &disconnected, true).unwrap();
+        let context = tracker.context().unwrap();
+        assert_eq!(context.loaded_frame(), None);
+        assert_eq!(
+            context.mispredicted_players(Frame::new(1)),
+            &[PlayerHandle::new(1)]
+        );
+    }
+
+    #[test]
+    fn unknown_earlier_simulation_lists_every_player() {
+        let mut tracker = RollbackTracker::new(2, 2).unwrap();
+        simulate(&mut tracker, 0..4, &[1, 1]);
+
+        // Frame 0 fell out of the two-frame history.
+        tracker.begin_batch();
+        tracker
+            .record(Frame::new(0), &inputs(&[1, 1]), true)
+            .unwrap();
+        assert_eq!(
+            tracker
+                .context()
+                .unwrap()
+                .mispredicted_players(Frame::new(0)),
+            &[PlayerHandle::new(0), PlayerHandle::new(1)]
+        );
+    }
+}
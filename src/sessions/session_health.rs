@@ -0,0 +1,247 @@
+//! Frame-rate health of a running session.
+//!
+//! A session stamps when it reaches [`SessionState::Running`](crate::SessionState::Running),
+//! times the gap between consecutive `advance_frame` calls and counts the
+//! frames it simulates as they happen, so reading the health is constant-time
+//! at any point of a match. Once the session shuts down the values stop
+//! changing, so a post-match report reads the same numbers whenever it runs.
+
+use web_time::{Duration, Instant};
+
+/// How well a [`P2PSession`](crate::P2PSession) kept up with its configured
+/// frame rate, as returned by
+/// [`P2PSession::session_health`](crate::P2PSession::session_health).
+///
+/// All values cover the time since the session reached
+/// [`SessionState::Running`](crate::SessionState::Running), up to now or, once
+/// the session reached its frame limit, up to that point.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionHealth {
+    /// Time spent running, on the session clock.
+    pub running_duration: Duration,
+    /// Frames simulated forward (each new frame once, not counting
+    /// resimulation).
+    pub frames_advanced: u64,
+    /// [`frames_advanced`](Self::frames_advanced) per second of
+    /// [`running_duration`](Self::running_duration), to compare against the
+    /// configured fps; `0.0` until any time has passed.
+    pub achieved_fps: f64,
+    /// The longest time between two consecutive `advance_frame` calls, in
+    /// milliseconds.
+    pub longest_advance_gap_ms: u64,
+    /// `advance_frame` calls that simulated no new frame because the
+    /// prediction window was full, waiting on remote inputs.
+    pub starved_frames: u64,
+    /// Frames resimulated by rollbacks.
+    pub total_rollback_frames: u64,
+}
+
+/// How well a [`SpectatorSession`](crate::SpectatorSession) kept up with its
+/// configured frame rate, as returned by
+/// [`SpectatorSession::session_health`](crate::SpectatorSession::session_health).
+///
+/// All values cover the time since the session reached
+/// [`SessionState::Running`](crate::SessionState::Running), up to now or, once
+/// the host's stream ended or the session reached its frame limit, up to that
+/// point. A spectator never predicts or rolls back, so there are no starvation
+/// or rollback counts.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectatorHealth {
+    /// Time spent running, on the session clock.
+    pub running_duration: Duration,
+    /// Frames simulated, including catch-up frames.
+    pub frames_advanced: u64,
+    /// [`frames_advanced`](Self::frames_advanced) per second of
+    /// [`running_duration`](Self::running_duration); `0.0` until any time has
+    /// passed.
+    pub achieved_fps: f64,
+    /// The longest time between two consecutive `advance_frame` calls, in
+    /// milliseconds.
+    pub longest_advance_gap_ms: u64,
+}
+
+impl From<SessionHealth> for SpectatorHealth {
+    fn from(health: SessionHealth) -> Self {
+        Self {
+            running_duration: health.running_duration,
+            frames_advanced: health.frames_advanced,
+            achieved_fps: health.achieved_fps,
+            longest_advance_gap_ms: health.longest_advance_gap_ms,
+        }
+    }
+}
+
+/// Accumulates a session's health as it runs.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HealthTracker {
+    running_since: Option<Instant>,
+    /// When the session shut down; nothing is recorded after it.
+    ended_at: Option<Instant>,
+    last_advance_call: Option<Instant>,
+    longest_advance_gap: Duration,
+    frames_advanced: u64,
+    starved_frames: u64,
+    rollback_frames: u64,
+}
+
+impl HealthTracker {
+    fn recording(&self) -> bool {
+        self.running_since.is_some() && self.ended_at.is_none()
+    }
+
+    /// Records that the session reached `Running` at `now`; later calls keep
+    /// the first time.
+    pub(crate) fn start(&mut self, now: Instant) {
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+    }
+
+    /// Records that the session shut down at `now`, freezing every value.
+    pub(crate) fn end(&mut self, now: Instant) {
+        if self.recording() {
+            self.ended_at = Some(now);
+        }
+    }
+
+    /// Records an `advance_frame` call made at `now` while running.
+    pub(crate) fn record_advance_call(&mut self, now: Instant) {
+        if !self.recording() {
+            return;
+        }
+        if let Some(last) = self.last_advance_call {
+            let gap = now.saturating_duration_since(last);
+            self.longest_advance_gap = self.longest_advance_gap.max(gap);
+        }
+        self.last_advance_call = Some(now);
+    }
+
+    /// Records `frames` newly simulated frames.
+    pub(crate) fn record_frames(&mut self, frames: u64) {
+        if self.recording() {
+            self.frames_advanced = self.frames_advanced.saturating_add(frames);
+        }
+    }
+
+    /// Records an advance that simulated nothing for want of remote inputs.
+    pub(crate) fn record_starved(&mut self) {
+        if self.recording() {
+            self.starved_frames = self.starved_frames.saturating_add(1);
+        }
+    }
+
+    /// Records a rollback that resimulated `depth` frames.
+    pub(crate) fn record_rollback(&mut self, depth: usize) {
+        if self.recording() {
+            let depth = u64::try_from(depth).unwrap_or(u64::MAX);
+            self.rollback_frames = self.rollback_frames.saturating_add(depth);
+        }
+    }
+
+    /// The health at `now`, or `None` before the session ran.
+    pub(crate) fn health(&self, now: Instant) -> Option<SessionHealth> {
+        let since = self.running_since?;
+        let running_duration = self
+            .ended_at
+            .unwrap_or(now)
+            .saturating_duration_since(since);
+        let seconds = running_duration.as_secs_f64();
+        let achieved_fps = if seconds > 0.0 {
+            self.frames_advanced as f64 / seconds
+        } else {
+            0.0
+        };
+        Some(SessionHealth {
+            running_duration,
+            frames_advanced: self.frames_advanced,
+            achieved_fps,
+            longest_advance_gap_ms: u64::try_from(self.longest_advance_gap.as_millis())
+                .unwrap_or(u64::MAX),
+            starved_frames: self.starved_frames,
+            total_rollback_frames: self.rollback_frames,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn nothing_is_reported_or_recorded_before_running() {
+        let start = Instant::now();
+        let mut tracker = HealthTracker::default();
+        tracker.record_advance_call(start);
+        tracker.record_frames(3);
+        tracker.record_starved();
+        assert_eq!(tracker.health(start), None);
+
+        tracker.start(start);
+        let health = tracker.health(start).unwrap();
+        assert_eq!(health.frames_advanced, 0);
+        assert_eq!(health.starved_frames, 0);
+        assert_eq!(health.achieved_fps, 0.0);
+    }
+
+    #[test]
+    fn tracks_rate_gaps_and_counts() {
+        let start = Instant::now();
+        let mut tracker = HealthTracker::default();
+        tracker.start(start);
+        for call in 0..50_u32 {
+            tracker.record_advance_call(start + ms(20) * call);
+            tracker.record_frames(1);
+        }
+        tracker.record_advance_call(start + ms(20) * 49 + ms(300));
+        tracker.record_starved();
+        tracker.record_rollback(4);
+
+        let health = tracker.health(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(health.running_duration, Duration::from_secs(1));
+        assert_eq!(health.frames_advanced, 50);
+        assert_eq!(health.achieved_fps, 50.0);
+        assert_eq!(health.longest_advance_gap_ms, 300);
+        assert_eq!(health.starved_frames, 1);
+        assert_eq!(health.total_rollback_frames, 4);
+    }
+
+    #[test]
+    fn values_freeze_when_the_session_ends() {
+        let start = Instant::now();
+        let mut tracker = HealthTracker::default();
+        tracker.start(start);
+        tracker.record_frames(10);
+        tracker.end(start + ms(500));
+        tracker.record_frames(10);
+        tracker.record_advance_call(start + ms(600));
+        tracker.record_advance_call(start + ms(2000));
+        tracker.end(start + ms(3000));
+
+        let frozen = tracker.health(start + ms(500)).unwrap();
+        assert_eq!(
+            tracker.health(start + Duration::from_secs(60)),
+            Some(frozen)
+        );
+        assert_eq!(frozen.running_duration, ms(500));
+        assert_eq!(frozen.frames_advanced, 10);
+        assert_eq!(frozen.achieved_fps, 20.0);
+        assert_eq!(frozen.longest_advance_gap_ms, 0);
+        assert_eq!(
+            SpectatorHealth::from(frozen).frames_advanced,
+            frozen.frames_advanced
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Whole-session presets for common game genres.
+//!
+//! A [`SessionPreset`] names a coherent bundle of the builder settings that
+//! usually have to agree with each other: frame rate, prediction window, input
+//! delay, input queue, save mode, desync detection, disconnect timing and the
+//! network configs. [`SessionPreset::settings`] returns the bundle as plain
+//! data, and [`SessionBuilder::preset`](crate::SessionBuilder::preset) applies
+//! it.
+
+use std::fmt::Write as _;
+
+use web_time::Duration;
+
+use crate::sessions::config::{InputQueueConfig, ProtocolConfig, SaveMode, SyncConfig};
+use crate::time_sync::TimeSyncConfig;
+use crate::DesyncDetection;
+
+/// A named bundle of session settings for a common kind of game.
+///
+/// Apply one with [`SessionBuilder::preset`](crate::SessionBuilder::preset)
+/// before any individual setting: every builder call made afterwards
+/// overrides the matching field, and
+/// [`SessionBuilder::describe`](crate::SessionBuilder::describe) lists the
+/// preset together with those overrides.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::{Config, FortressError, SessionBuilder, SessionPreset};
+///
+/// # struct MyConfig;
+/// # impl Config for MyConfig {
+/// #     type Input = u8;
+/// #     type State = ();
+/// #     type Address = std::net::SocketAddr;
+/// # }
+/// let builder = SessionBuilder::<MyConfig>::new()
+///     .preset(SessionPreset::Fighting60)
+///     .with_input_delay(3)?;
+/// assert_eq!(
+///     builder.describe(),
+///     "preset: Fighting60; overrides: input_delay = 3"
+/// );
+/// # Ok::<(), FortressError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionPreset {
+    /// 60 FPS fighting games: a short input delay, a full prediction window,
+    /// and fast disconnect detection.
+    ///
+    /// | Setting | Value |
+    /// |---------|-------|
+    /// | fps | 60 |
+    /// | max prediction | 8 frames |
+    /// | input delay | 2 frames |
+    /// | input queue | [`InputQueueConfig::standard`] |
+    /// | save mode | [`SaveMode::EveryFrame`] |
+    /// | desync detection | every 60 frames |
+    /// | disconnect timeout / notify | 2 s / 500 ms |
+    /// | sync / protocol / time sync | `competitive()` presets |
+    Fighting60,
+    /// 60 FPS platformers and other action games: one more frame of input
+    /// delay to keep rollbacks short, and smoother time sync.
+    ///
+    /// | Setting | Value |
+    /// |---------|-------|
+    /// | fps | 60 |
+    /// | max prediction | 8 frames |
+    /// | input delay | 3 frames |
+    /// | input queue | [`InputQueueConfig::standard`] |
+    /// | save mode | [`SaveMode::EveryFrame`] |
+    /// | desync detection | every 60 frames |
+    /// | disconnect timeout / notify | 3 s / 750 ms |
+    /// | sync / protocol | defaults |
+    /// | time sync | [`TimeSyncConfig::smooth`] |
+    Platformer60,
+    /// 30 FPS real-time strategy games: large states saved sparsely, a few
+    /// frames of delay, and tolerant network timing.
+    ///
+    /// | Setting | Value |
+    /// |---------|-------|
+    /// | fps | 30 |
+    /// | max prediction | 6 frames |
+    /// | input delay | 3 frames |
+    /// | input queue | [`InputQueueConfig::standard`] |
+    /// | save mode | [`SaveMode::Sparse`] |
+    /// | desync detection | every 30 frames |
+    /// | disconnect timeout / notify | 5 s / 1 s |
+    /// | sync / protocol | `high_latency()` presets |
+    /// | time sync | [`TimeSyncConfig::responsive`] |
+    Rts30,
+    /// Turn-based games in lockstep: no prediction, so the session never
+    /// saves or rolls back, and generous timeouts.
+    ///
+    /// | Setting | Value |
+    /// |---------|-------|
+    /// | fps | 30 |
+    /// | max prediction | 0 (lockstep) |
+    /// | input delay | 0 frames |
+    /// | input queue | [`InputQueueConfig::standard`] |
+    /// | save mode | [`SaveMode::EveryFrame`] |
+    /// | desync detection | every 30 frames |
+    /// | disconnect timeout / notify | 10 s / 2 s |
+    /// | sync / protocol | `high_latency()` presets |
+    /// | time sync | default |
+    LockstepTurnBased,
+}
+
+impl SessionPreset {
+    /// The number of presets.
+    pub const COUNT: usize = 4;
+
+    /// Every preset, in declaration order.
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::Fighting60,
+        Self::Platformer60,
+        Self::Rts30,
+        Self::LockstepTurnBased,
+    ];
+
+    /// The settings this preset applies.
+    #[must_use]
+    pub fn settings(self) -> PresetSettings {
+        match self {
+            Self::Fighting60 => PresetSettings {
+                fps: 60,
+                max_prediction: 8,
+                input_delay: 2,
+                input_queue_config: InputQueueConfig::standard(),
+                save_mode: SaveMode::EveryFrame,
+                desync_detection: DesyncDetection::On { interval: 60 },
+                disconnect_timeout: Duration::from_secs(2),
+                disconnect_notify_start: Duration::from_millis(500),
+                sync_config: SyncConfig::competitive(),
+                protocol_config: ProtocolConfig::competitive(),
+                time_sync_config: TimeSyncConfig::competitive(),
+            },
+            Self::Platformer60 => PresetSettings {
+                fps: 60,
+                max_prediction: 8,
+                input_delay: 3,
+                input_queue_config: InputQueueConfig::standard(),
+                save_mode: SaveMode::EveryFrame,
+                desync_detection: DesyncDetection::On { interval: 60 },
+                disconnect_timeout: Duration::from_secs(3),
+                disconnect_notify_start: Duration::from_millis(750),
+                sync_config: SyncConfig::default(),
+                protocol_config: ProtocolConfig::default(),
+                time_sync_config: TimeSyncConfig::smooth(),
+            },
+            Self::Rts30 => PresetSettings {
+                fps: 30,
+                max_prediction: 6,
+                input_delay: 3,
+                input_queue_config: InputQueueConfig::standard(),
+                save_mode: SaveMode::Sparse,
+                desync_detection: DesyncDetection::On { interval: 30 },
+                disconnect_timeout: Duration::from_secs(5),
+                disconnect_notify_start: Duration::from_secs(1),
+                sync_config: SyncConfig::high_latency(),
+                protocol_config: ProtocolConfig::high_latency(),
+                time_sync_config: TimeSyncConfig::responsive(),
+            },
+            Self::LockstepTurnBased => PresetSettings {
+                fps: 30,
+                max_prediction: 0,
+                input_delay: 0,
+                input_queue_config: InputQueueConfig::standard(),
+                save_mode: SaveMode::EveryFrame,
+                desync_detection: DesyncDetection::On { interval: 30 },
+                disconnect_timeout: Duration::from_secs(10),
+                disconnect_notify_start: Duration::from_secs(2),
+                sync_config: SyncConfig::high_latency(),
+                protocol_config: ProtocolConfig::high_latency(),
+                time_sync_config: TimeSyncConfig::default(),
+            },
+        }
+    }
+}
+
+/// The builder settings a [`SessionPreset`] bundles, as plain data.
+///
+/// [`SessionBuilder::preset`](crate::SessionBuilder::preset) copies every
+/// field onto the builder; each field matches the builder method of the same
+/// name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetSettings {
+    /// See [`SessionBuilder::with_fps`](crate::SessionBuilder::with_fps).
+    pub fps: usize,
+    /// See [`SessionBuilder::with_max_prediction_window`](crate::SessionBuilder::with_max_prediction_window).
+    pub max_prediction: usize,
+    /// See [`SessionBuilder::with_input_delay`](crate::SessionBuilder::with_input_delay).
+    pub input_delay: usize,
+    /// See [`SessionBuilder::with_input_queue_config`](crate::SessionBuilder::with_input_queue_config).
+    pub input_queue_config: InputQueueConfig,
+    /// See [`SessionBuilder::with_save_mode`](crate::SessionBuilder::with_save_mode).
+    pub save_mode: SaveMode,
+    /// See [`SessionBuilder::with_desync_detection_mode`](crate::SessionBuilder::with_desync_detection_mode).
+    pub desync_detection: DesyncDetection,
+    /// See [`SessionBuilder::with_disconnect_timeout`](crate::SessionBuilder::with_disconnect_timeout).
+    pub disconnect_timeout: Duration,
+    /// See [`SessionBuilder::with_disconnect_notify_delay`](crate::SessionBuilder::with_disconnect_notify_delay).
+    pub disconnect_notify_start: Duration,
+    /// See [`SessionBuilder::with_sync_config`](crate::SessionBuilder::with_sync_config).
+    pub sync_config: SyncConfig,
+    /// See [`SessionBuilder::with_protocol_config`](crate::SessionBuilder::with_protocol_config).
+    pub protocol_config: ProtocolConfig,
+    /// See [`SessionBuilder::with_time_sync_config`](crate::SessionBuilder::with_time_sync_config).
+    pub time_sync_config: TimeSyncConfig,
+}
+
+impl PresetSettings {
+    /// Appends `name = value` to `out` for every field that differs from
+    /// `base`, separated by `", "`.
+    pub(crate) fn write_differences(&self, base: &Self, out: &mut String) {
+        // Destructure to ensure all fields are included when new fields are added.
+        let Self {
+            fps,
+            max_prediction,
+            input_delay,
+            input_queue_config,
+            save_mode,
+            desync_detection,
+            disconnect_timeout,
+            disconnect_notify_start,
+            sync_config,
+            protocol_config,
+            time_sync_config,
+        } = self;
+        let mut write = |name: &str, differs: bool, value: &dyn std::fmt::Display| {
+            if differs {
+                if !out.is_empty() {
+                    out.push_str(", ");
+                }
+                let _ = write!(out, "{name} = {value}");
+            }
+        };
+        write("fps", *fps != base.fps, fps);
+        write(
+            "max_prediction",
+            *max_prediction != base.max_prediction,
+            max_prediction,
+        );
+        write("input_delay", *input_delay != base.input_delay, input_delay);
+        write(
+            "input_queue_config",
+            *input_queue_config != base.input_queue_config,
+            input_queue_config,
+        );
+        write(
+            "save_mode",
+            *save_mode != base.save_mode,
+            &format_args!("{save_mode:?}"),
+        );
+        write(
+            "desync_detection",
+            *desync_detection != base.desync_detection,
+            &format_args!("{desync_detection:?}"),
+        );
+        write(
+            "disconnect_timeout",
+            *disconnect_timeout != base.disconnect_timeout,
+            &format_args!("{disconnect_timeout:?}"),
+        );
+        write(
+            "disconnect_notify_start",
+            *disconnect_notify_start != base.disconnect_notify_start,
+            &format_args!("{disconnect_notify_start:?}"),
+        );
+        write("sync_config", *sync_config != base.sync_config, sync_config);
+        write(
+            "protocol_config",
+            *protocol_config != base.protocol_config,
+            protocol_config,
+        );
+        write(
+            "time_sync_config",
+            *time_sync_config != base.time_sync_config,
+            time_sync_config,
+        );
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_lists_every_preset_once() {
+        let unique: std::collections::BTreeSet<_> = SessionPreset::ALL
+            .iter()
+            .map(|p| format!("{p:?}"))
+            .collect();
+        assert_eq!(unique.len(), SessionPreset::COUNT);
+    }
+
+    #[test]
+    fn fighting60_settings() {
+        let settings = SessionPreset::Fighting60.settings();
+        assert_eq!(settings.fps, 60);
+        assert_eq!(settings.max_prediction, 8);
+        assert_eq!(settings.input_delay, 2);
+        assert_eq!(settings.input_queue_config.queue_length, 128);
+        assert_eq!(settings.save_mode, SaveMode::EveryFrame);
+        assert_eq!(
+            settings.desync_detection,
+            DesyncDetection::On { interval: 60 }
+        );
+        assert_eq!(settings.disconnect_timeout, Duration::from_secs(2));
+        assert_eq!(settings.disconnect_notify_start, Duration::from_millis(500));
+        assert_eq!(settings.sync_config, SyncConfig::competitive());
+        assert_eq!(settings.protocol_config, ProtocolConfig::competitive());
+        assert_eq!(settings.time_sync_config.window_size, 20);
+    }
+
+    #[test]
+    fn platformer60_settings() {
+        let settings = SessionPreset::Platformer60.settings();
+        assert_eq!(settings.fps, 60);
+        assert_eq!(settings.max_prediction, 8);
+        assert_eq!(settings.input_delay, 3);
+        assert_eq!(settings.input_queue_config.queue_length, 128);
+        assert_eq!(settings.save_mode, SaveMode::EveryFrame);
+        assert_eq!(
+            settings.desync_detection,
+            DesyncDetection::On { interval: 60 }
+        );
+        assert_eq!(settings.disconnect_timeout, Duration::from_secs(3));
+        assert_eq!(settings.disconnect_notify_start, Duration::from_millis(750));
+        assert_eq!(settings.sync_config, SyncConfig::default());
+        assert_eq!(settings.protocol_config, ProtocolConfig::default());
+        assert_eq!(settings.time_sync_config.window_size, 60);
+    }
+
+    #[test]
+    fn rts30_settings() {
+        let settings = SessionPreset::Rts30.settings();
+        assert_eq!(settings.fps, 30);
+        assert_eq!(settings.max_prediction, 6);
+        assert_eq!(settings.input_delay, 3);
+        assert_eq!(settings.input_queue_config.queue_length, 128);
+        assert_eq!(settings.save_mode, SaveMode::Sparse);
+        assert_eq!(
+            settings.desync_detection,
+            DesyncDetection::On { interval: 30 }
+        );
+        assert_eq!(settings.disconnect_timeout, Duration::from_secs(5));
+        assert_eq!(settings.disconnect_notify_start, Duration::from_secs(1));
+        assert_eq!(settings.sync_config, SyncConfig::high_latency());
+        assert_eq!(settings.protocol_config, ProtocolConfig::high_latency());
+        assert_eq!(settings.time_sync_config.window_size, 15);
+    }
+
+    #[test]
+    fn lockstep_turn_based_settings() {
+        let settings = SessionPreset::LockstepTurnBased.settings();
+        assert_eq!(settings.fps, 30);
+        assert_eq!(settings.max_prediction, 0);
+        assert_eq!(settings.input_delay, 0);
+        assert_eq!(settings.input_queue_config.queue_length, 128);
+        assert_eq!(settings.save_mode, SaveMode::EveryFrame);
+        assert_eq!(
+            settings.desync_detection,
+            DesyncDetection::On { interval: 30 }
+        );
+        assert_eq!(settings.disconnect_timeout, Duration::from_secs(10));
+        assert_eq!(settings.disconnect_notify_start, Duration::from_secs(2));
+        assert_eq!(settings.sync_config, SyncConfig::high_latency());
+        assert_eq!(settings.protocol_config, ProtocolConfig::high_latency());
+        assert_eq!(settings.time_sync_config, TimeSyncConfig::default());
+    }
+
+    #[test]
+    fn write_differences_lists_only_changed_fields() {
+        let base = SessionPreset::Fighting60.settings();
+        let mut out = String::new();
+        base.write_differences(&base, &mut out);
+        assert_eq!(out, "");
+
+        let changed = PresetSettings {
+            input_delay: 4,
+            save_mode: SaveMode::Sparse,
+            ..base.clone()
+        };
+        changed.write_differences(&base, &mut out);
+        assert_eq!(out, "input_delay = 4, save_mode = Sparse");
+    }
+}
@@ -0,0 +1,196 @@
+//! Many [`P2PSession`]s driven together by a server process.
+//!
+//! A relay or match server hosting hundreds of sessions wants to spend its
+//! time on the ones with work to do. [`SessionSet`] owns the sessions under
+//! application-chosen keys, reports which of them received data on a poll,
+//! advances ready sessions round-robin under a per-call budget, and drains
+//! every session's events tagged with its key.
+
+use std::collections::BTreeMap;
+
+use crate::{Config, FortressEvent, FortressResult, P2PSession, RequestVec};
+
+/// A keyed collection of [`P2PSession`]s polled and advanced together.
+///
+/// Each session keeps its own socket; the set only decides which sessions to
+/// drive. Keys are ordered, so [`poll_all`](Self::poll_all) and
+/// [`drain_events`](Self::drain_events) report sessions in key order.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::{Config, SessionSet};
+///
+/// # struct MyConfig;
+/// # impl Config for MyConfig {
+/// #     type Input = u8;
+/// #     type State = ();
+/// #     type Address = std::net::SocketAddr;
+/// # }
+/// let mut sessions = SessionSet::<MyConfig, u64>::new();
+/// // sessions.insert(match_id, session) for every hosted match, then per tick:
+/// let active = sessions.poll_all();
+/// assert!(active.is_empty());
+/// for (_match_id, requests) in sessions.advance_ready(32) {
+///     // fulfill `requests?` for the session's game state
+/// #   let _ = requests;
+/// }
+/// ```
+pub struct SessionSet<T, K>
+where
+    T: Config,
+    K: Ord + Clone,
+{
+    sessions: BTreeMap<K, P2PSession<T>>,
+    /// The last session [`advance_ready`](Self::advance_ready) advanced; the
+    /// next call starts after it.
+    last_advanced: Option<K>,
+}
+
+impl<T, K> std::fmt::Debug for SessionSet<T, K>
+where
+    T: Config,
+    K: Ord + Clone + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Destructure to ensure all fields are included when new fields are added.
+        let Self {
+            sessions,
+            last_advanced,
+        } = self;
+        f.debug_struct("SessionSet")
+            .field("sessions", &sessions.keys().collect::<Vec<_>>())
+            .field("last_advanced", last_advanced)
+            .finish()
+    }
+}
+
+impl<T, K> Default for SessionSet<T, K>
+where
+    T: Config,
+    K: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, K> SessionSet<T, K>
+where
+    T: Config,
+    K: Ord + Clone,
+{
+    /// Creates an empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            sessions: BTreeMap::new(),
+            last_advanced: None,
+        }
+    }
+
+    /// Adds `session` under `key`, returning the session it replaced.
+    pub fn insert(&mut self, key: K, session: P2PSession<T>) -> Option<P2PSession<T>> {
+        self.sessions.insert(key, session)
+    }
+
+    /// Removes and returns the session under `key`.
+    pub fn remove(&mut self, key: &K) -> Option<P2PSession<T>> {
+        self.sessions.remove(key)
+    }
+
+    /// The session under `key`.
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<&P2PSession<T>> {
+        self.sessions.get(key)
+    }
+
+    /// The session under `key`, mutably; use it to add local input before
+    /// [`advance_ready`](Self::advance_ready).
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut P2PSession<T>> {
+        self.sessions.get_mut(key)
+    }
+
+    /// The number of sessions in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Whether the set holds no sessions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// The keys of every session, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.sessions.keys()
+    }
+
+    /// Every session with its key, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &P2PSession<T>)> + '_ {
+        self.sessions.iter()
+    }
+
+    /// Every session with its key, mutably, in key order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut P2PSession<T>)> + '_ {
+        self.sessions.iter_mut()
+    }
+
+    /// Calls [`P2PSession::poll_remote_clients`] on every session and returns
+    /// the keys, in order, of the sessions whose socket delivered at least one
+    /// message.
+    ///
+    /// Every session is polled, so timers such as keepalives and disconnect
+    /// timeouts keep running for idle sessions too.
+    pub fn poll_all(&mut self) -> Vec<K> {
+        self.sessions
+            .iter_mut()
+            .filter_map(|(key, session)| {
+                (session.poll_remote_clients_received() > 0).then(|| key.clone())
+            })
+            .collect()
+    }
+
+    /// Advances up to `budget` sessions that are ready, and returns each
+    /// advanced session's key with its [`P2PSession::advance_frame`] result.
+    ///
+    /// A session is ready when it is running and input for each of its local
+    /// players has been added. Sessions are visited round-robin: each call
+    /// starts after the last session the previous call advanced, so a budget
+    /// smaller than the number of ready sessions still gives every one of them
+    /// a turn. One session's error does not stop the others.
+    pub fn advance_ready(&mut self, budget: usize) -> Vec<(K, FortressResult<RequestVec<T>>)> {
+        // Sessions up to and including the last one advanced wait for their
+        // turn after everyone else.
+        let last = self.last_advanced.as_ref();
+        let waits = |key: &K| last.is_some_and(|last| key <= last);
+        let after = self.sessions.iter().filter(|(key, _)| !waits(key));
+        let up_to = self.sessions.iter().filter(|(key, _)| waits(key));
+        let ready: Vec<K> = after
+            .chain(up_to)
+            .filter(|(_, session)| session.ready_to_advance())
+            .map(|(key, _)| key.clone())
+            .take(budget)
+            .collect();
+        let mut advanced = Vec::new();
+        for key in ready {
+            if let Some(session) = self.sessions.get_mut(&key) {
+                advanced.push((key.clone(), session.advance_frame()));
+                self.last_advanced = Some(key);
+            }
+        }
+        advanced
+    }
+
+    /// Drains the events of every session, each tagged with its session's
+    /// key, in key order.
+    pub fn drain_events(&mut self) -> Vec<(K, FortressEvent<T>)> {
+        let mut events = Vec::new();
+        for (key, session) in &mut self.sessions {
+            events.extend(session.events().map(|event| (key.clone(), event)));
+        }
+        events
+    }
+}
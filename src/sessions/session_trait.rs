@@ -1,5 +1,6 @@
 use crate::{
-    Config, EventDrain, FortressResult, InvalidRequestKind, PlayerHandle, RequestVec, SessionState,
+    Config, EventDrain, FortressResult, Frame, InvalidRequestKind, PlayerHandle, RequestVec,
+    SessionState,
 };
 
 /// A unified interface for all Fortress Rollback session types.
@@ -23,6 +24,30 @@
 /// | [`events`](Session::events) | Override | Override | Override | Override |
 /// | [`current_state`](Session::current_state) | Override | Override | Default (`Running`) | Override (`Running`) |
 /// | [`poll_remote_clients`](Session::poll_remote_clients) | Override | Override | Default (no-op) | Default (no-op) |
+/// | [`current_frame`](Session::current_frame) | Override | Override | Override | Override |
+/// | [`confirmed_frame`](Session::confirmed_frame) | Override | Override (`current_frame`) | Override | Override (`current_frame`) |
+///
+/// # Frame Semantics
+///
+/// The frame accessors mean slightly different things per session type, so a
+/// generic loop should only rely on `confirmed_frame() <= current_frame()` and
+/// on neither decreasing while the session advances normally:
+///
+/// - [`P2PSession`]: `current_frame` is the frame the next
+///   [`advance_frame`](Session::advance_frame) simulates, starting at `0`.
+///   `confirmed_frame` is the newest frame with input from every player; it can
+///   briefly decrease in the cases described on
+///   [`P2PSession::confirmed_frame`](crate::P2PSession::confirmed_frame).
+/// - [`SpectatorSession`]: `current_frame` is the last frame simulated, starting
+///   at [`Frame::NULL`]. A spectator only ever simulates confirmed input, so
+///   `confirmed_frame` equals `current_frame`.
+/// - [`SyncTestSession`]: `current_frame` is the frame the next `advance_frame`
+///   simulates, starting at `0`. All input is local, but the session rolls back
+///   `check_distance` frames to verify determinism, so `confirmed_frame` is the
+///   newest frame it will not roll back to, `current_frame - check_distance`.
+/// - [`ReplaySession`]: `current_frame` is the last frame replayed, starting at
+///   [`Frame::NULL`]. Recorded input is final, so `confirmed_frame` equals
+///   `current_frame`.
 ///
 /// # Example
 ///
@@ -121,6 +146,20 @@ fn current_state(&self) -> SessionState {
     /// The default implementation is a no-op, suitable for session types
     /// without network communication (e.g., sync test sessions).
     fn poll_remote_clients(&mut self) {}
+
+    /// Returns the session's current frame.
+    ///
+    /// See [Frame Semantics](Session#frame-semantics) for what each session
+    /// type reports.
+    #[must_use]
+    fn current_frame(&self) -> Frame;
+
+    /// Returns the newest frame whose state will not be rolled back.
+    ///
+    /// See [Frame Semantics](Session#frame-semantics) for what each session
+    /// type reports.
+    #[must_use]
+    fn confirmed_frame(&self) -> Frame;
 }
 
 #[cfg(test)]
@@ -0,0 +1,137 @@
+//! Save-state slots for training modes.
+//!
+//! A [`StateSlot`] is a copy of a confirmed saved state, taken with
+//! `export_state_slot` and handed back with `propose_state_restore` on a
+//! [`P2PSession`](crate::P2PSession) whose players are all local or a
+//! [`SyncTestSession`](crate::SyncTestSession). Unlike a rollback, the restore
+//! may reach any frame the game ever passed through, so the slot owns its state
+//! instead of pointing into the session's saved-state ring.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FortressError, InvalidFrameReason, InvalidRequestKind};
+use crate::Frame;
+
+/// A confirmed game state and the frame it was saved at.
+///
+/// Returned by
+/// [`P2PSession::export_state_slot`](crate::P2PSession::export_state_slot) and
+/// [`SyncTestSession::export_state_slot`](crate::SyncTestSession::export_state_slot).
+/// The slot is serializable whenever the game state is, so a training mode can
+/// keep save-states on disk. Its format is versioned: a slot whose
+/// [`version`](Self::version) is not [`FORMAT_VERSION`](Self::FORMAT_VERSION)
+/// is rejected by `propose_state_restore`.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateSlot<S> {
+    version: u16,
+    frame: Frame,
+    checksum: Option<u128>,
+    state: S,
+}
+
+impl<S> StateSlot<S> {
+    /// The slot format this build writes and restores.
+    pub const FORMAT_VERSION: u16 = 1;
+
+    pub(crate) fn new(frame: Frame, state: S, checksum: Option<u128>) -> Self {
+        Self {
+            version: Self::FORMAT_VERSION,
+            frame,
+            checksum,
+            state,
+        }
+    }
+
+    /// The format version the slot was written with.
+    #[must_use]
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// The frame the state was saved at; a restored session continues from it.
+    #[must_use]
+    pub fn frame(&self) -> Frame {
+        self.frame
+    }
+
+    /// The checksum the game saved with the state, if any.
+    #[must_use]
+    pub fn checksum(&self) -> Option<u128> {
+        self.checksum
+    }
+
+    /// The saved game state.
+    #[must_use]
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Takes the saved game state out of the slot.
+    #[must_use]
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    /// Checks the slot can be restored, returning its frame, state and
+    /// checksum.
+    pub(crate) fn into_restorable(self) -> Result<(Frame, S, Option<u128>), FortressError> {
+        if self.version != Self::FORMAT_VERSION {
+            return Err(InvalidRequestKind::UnsupportedStateSlotVersion {
+                version: self.version,
+            }
+            .into());
+        }
+        if self.frame.as_i32() < 0 {
+            return Err(FortressError::InvalidFrameStructured {
+                frame: self.frame,
+                reason: InvalidFrameReason::MustBeNonNegative,
+            });
+        }
+        Ok((self.frame, self.state, self.checksum))
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_slot_round_trips_through_serde() {
+        let slot = StateSlot::new(Frame::new(100), vec![1_u32, 2, 3], Some(0xfeed));
+        let json = serde_json::to_string(&slot).unwrap();
+        let decoded: StateSlot<Vec<u32>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, slot);
+        assert_eq!(decoded.version(), StateSlot::<Vec<u32>>::FORMAT_VERSION);
+        assert_eq!(decoded.frame(), Frame::new(100));
+        assert_eq!(decoded.checksum(), Some(0xfeed));
+        assert_eq!(decoded.into_state(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn an_unknown_version_or_negative_frame_is_not_restorable() {
+        let mut slot = StateSlot::new(Frame::new(5), 7_u8, None);
+        slot.version = 2;
+        assert_eq!(
+            slot.into_restorable().unwrap_err(),
+            InvalidRequestKind::UnsupportedStateSlotVersion { version: 2 }.into()
+        );
+
+        let slot = StateSlot::new(Frame::NULL, 7_u8, None);
+        assert!(matches!(
+            slot.into_restorable(),
+            Err(FortressError::InvalidFrameStructured {
+                reason: InvalidFrameReason::MustBeNonNegative,
+                ..
+            })
+        ));
+        let slot = StateSlot::new(Frame::new(5), 7_u8, Some(1));
+        assert_eq!(slot.into_restorable().unwrap(), (Frame::new(5), 7, Some(1)));
+    }
+}
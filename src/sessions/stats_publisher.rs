@@ -0,0 +1,592 @@
+//! Wait-free stats snapshots for threads that do not own the session.
+//!
+//! [`P2PSession::stats_publisher`](crate::P2PSession::stats_publisher) makes
+//! the session write a [`StatsSnapshot`] every few
+//! [`advance_frame`](crate::P2PSession::advance_frame) calls into a shared
+//! double buffer that a render thread reads through a [`StatsSubscription`].
+//!
+//! Each of the two slots is a seqlock over atomic words: the writer bumps the
+//! slot's sequence to odd, stores the words, bumps it back to even, then
+//! publishes the slot as the latest. Writes alternate between the slots, so a
+//! reader copying the latest slot only has to retry when the writer publishes
+//! twice during the copy. The writer never waits for a reader, and a reader
+//! never takes a lock or observes a partly written snapshot.
+
+use crate::error::allocation_failed;
+use crate::network::network_stats::NetworkStats;
+use crate::sync::{fence, yield_now, Arc, AtomicU64, Ordering};
+use crate::{FortressError, Frame, PlayerHandle};
+
+/// Header words: version, current frame, confirmed frame, frames ahead,
+/// rollback count.
+const HEADER_WORDS: usize = 5;
+/// Words of one endpoint: a presence flag, then the encoded [`NetworkStats`].
+const ENDPOINT_WORDS: usize = 1 + STATS_WORDS;
+/// Words of an encoded [`NetworkStats`].
+const STATS_WORDS: usize = 19;
+
+/// Bits of the optional-field word of an encoded [`NetworkStats`].
+const HAS_COMPARED_FRAME: u64 = 1 << 0;
+const HAS_LOCAL_CHECKSUM: u64 = 1 << 1;
+const HAS_REMOTE_CHECKSUM: u64 = 1 << 2;
+const HAS_CHECKSUMS_MATCH: u64 = 1 << 3;
+const CHECKSUMS_MATCH: u64 = 1 << 4;
+const HAS_CLOCK_OFFSET: u64 = 1 << 5;
+
+/// Session statistics as of one
+/// [`advance_frame`](crate::P2PSession::advance_frame) call, read from a
+/// [`StatsSubscription`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    /// How many snapshots the session has published, this one included.
+    /// Starts at 1; a larger version is a newer snapshot.
+    pub version: u64,
+    /// The session's current frame.
+    pub current_frame: Frame,
+    /// The newest frame whose inputs every player has confirmed.
+    pub confirmed_frame: Frame,
+    /// The session's estimate of how many frames it is ahead of its peers;
+    /// see [`P2PSession::frames_ahead`](crate::P2PSession::frames_ahead).
+    pub frames_ahead: i32,
+    /// Rollbacks performed so far; see
+    /// [`SessionMetrics::rollback_count`](crate::SessionMetrics::rollback_count).
+    pub rollback_count: u64,
+    /// [`NetworkStats`] of every remote player and spectator handle, in handle
+    /// order. Handles whose stats are unavailable, for example because
+    /// their endpoint is not synchronized yet, are left out.
+    pub network_stats: Vec<(PlayerHandle, NetworkStats)>,
+}
+
+/// One seqlock-protected copy of an encoded snapshot.
+#[derive(Debug)]
+struct Slot {
+    /// Odd while the writer stores `words`.
+    seq: AtomicU64,
+    words: Vec<AtomicU64>,
+}
+
+/// The double buffer shared by the publisher and its subscriptions.
+#[derive(Debug)]
+struct StatsCell {
+    /// The handles whose stats each snapshot has room for, ascending.
+    handles: Vec<PlayerHandle>,
+    slots: [Slot; 2],
+    /// Version of the newest complete snapshot; it lives in slot
+    /// `published % 2`.
+    published: AtomicU64,
+}
+
+impl StatsCell {
+    fn try_new(handles: Vec<PlayerHandle>) -> Result<Self, FortressError> {
+        let len = handles
+            .len()
+            .saturating_mul(ENDPOINT_WORDS)
+            .saturating_add(HEADER_WORDS);
+        let slot = || -> Result<Slot, FortressError> {
+            let mut words = Vec::new();
+            words
+                .try_reserve_exact(len)
+                .map_err(|_err| allocation_failed("stats_publisher.slot", len))?;
+            words.extend((0..len).map(|_| AtomicU64::new(0)));
+            Ok(Slot {
+                seq: AtomicU64::new(0),
+                words,
+            })
+        };
+        Ok(Self {
+            handles,
+            slots: [slot()?, slot()?],
+            published: AtomicU64::new(0),
+        })
+    }
+
+    fn slot(&self, version: u64) -> &Slot {
+        if version % 2 == 0 {
+            &self.slots[0]
+        } else {
+            &self.slots[1]
+        }
+    }
+}
+
+/// The session side of a stats publisher: writes a snapshot every
+/// `interval` calls.
+///
+/// # Note
+///
+/// This type is exposed via `__internal` for loom testing. It is not part of
+/// the stable public API.
+#[derive(Debug)]
+pub struct StatsPublisher {
+    cell: Arc<StatsCell>,
+    /// `advance_frame` calls between snapshots.
+    interval: u32,
+    /// Calls left until the next snapshot.
+    countdown: u32,
+    /// Reused snapshot the session fills before each publish.
+    scratch: StatsSnapshot,
+}
+
+impl StatsPublisher {
+    /// A publisher with room for the stats of `handles`, publishing every
+    /// `interval` calls of [`tick`](Self::tick). Nothing is published yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::ConfigValueOutOfRange`](crate::InvalidRequestKind::ConfigValueOutOfRange)
+    /// if `interval` is 0, or
+    /// [`InvalidRequestKind::AllocationFailed`](crate::InvalidRequestKind::AllocationFailed)
+    /// if the buffers cannot be reserved.
+    pub fn new(mut handles: Vec<PlayerHandle>, interval: u32) -> Result<Self, FortressError> {
+        handles.sort_unstable();
+        handles.dedup();
+        let mut network_stats = Vec::new();
+        network_stats
+            .try_reserve_exact(handles.len())
+            .map_err(|_err| allocation_failed("stats_publisher.scratch", handles.len()))?;
+        let mut publisher = Self {
+            cell: Arc::new(StatsCell::try_new(handles)?),
+            interval: 1,
+            countdown: 0,
+            scratch: StatsSnapshot {
+                network_stats,
+                ..StatsSnapshot::default()
+            },
+        };
+        publisher.set_interval(interval)?;
+        Ok(publisher)
+    }
+
+    /// Changes how many [`tick`](Self::tick) calls pass between snapshots.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidRequestKind::ConfigValueOutOfRange`](crate::InvalidRequestKind::ConfigValueOutOfRange)
+    /// if `interval` is 0.
+    pub fn set_interval(&mut self, interval: u32) -> Result<(), FortressError> {
+        if interval == 0 {
+            return Err(crate::InvalidRequestKind::ConfigValueOutOfRange {
+                field: "stats_publisher.interval",
+                min: 1,
+                max: u64::from(u32::MAX),
+                actual: 0,
+            }
+            .into());
+        }
+        self.interval = interval;
+        self.countdown = self.countdown.min(interval);
+        Ok(())
+    }
+
+    /// A new reader of this publisher's snapshots.
+    #[must_use]
+    pub fn subscribe(&self) -> StatsSubscription {
+        StatsSubscription {
+            cell: Arc::clone(&self.cell),
+        }
+    }
+
+    /// The handles each snapshot has room for, ascending.
+    #[must_use]
+    pub fn handles(&self) -> &[PlayerHandle] {
+        &self.cell.handles
+    }
+
+    /// Counts one call and returns whether a snapshot is due. The first call
+    /// is always due.
+    pub fn tick(&mut self) -> bool {
+        if self.countdown == 0 {
+            self.countdown = self.interval;
+        }
+        self.countdown -= 1;
+        self.countdown == 0 || self.cell.published.load(Ordering::Relaxed) == 0
+    }
+
+    /// Replaces the scratch snapshot's `network_stats` with `stats` of each
+    /// handle, leaving out handles it returns `None` for.
+    pub fn refresh_network_stats(
+        &mut self,
+        mut stats: impl FnMut(PlayerHandle) -> Option<NetworkStats>,
+    ) {
+        let network_stats = &mut self.scratch.network_stats;
+        network_stats.clear();
+        for &handle in &self.cell.handles {
+            if let Some(stats) = stats(handle) {
+                network_stats.push((handle, stats));
+            }
+        }
+    }
+
+    /// The reusable snapshot to fill before [`publish`](Self::publish); its
+    /// `network_stats` has room for every handle without reallocating.
+    pub fn scratch_mut(&mut self) -> &mut StatsSnapshot {
+        &mut self.scratch
+    }
+
+    /// Publishes the scratch snapshot as the newest one. Its `version` is
+    /// assigned here; entries for handles the publisher has no room for are
+    /// ignored.
+    pub fn publish(&mut self) {
+        let cell = &*self.cell;
+        let version = cell.published.load(Ordering::Relaxed).wrapping_add(1);
+        let slot = cell.slot(version);
+
+        let seq = slot.seq.load(Ordering::Relaxed);
+        slot.seq.store(seq.wrapping_add(1), Ordering::Relaxed);
+        // Orders the odd sequence before the word stores: a reader that sees
+        // any of the new words also sees the odd (or a later) sequence.
+        fence(Ordering::Release);
+
+        let snapshot = &self.scratch;
+        let header = [
+            version,
+            frame_word(snapshot.current_frame),
+            frame_word(snapshot.confirmed_frame),
+            i32_word(snapshot.frames_ahead),
+            snapshot.rollback_count,
+        ];
+        let mut words = slot.words.iter();
+        // The finite side comes first so `zip` never pulls a word it drops.
+        for (value, word) in header.into_iter().zip(words.by_ref()) {
+            word.store(value, Ordering::Relaxed);
+        }
+        let mut encoded = [0; STATS_WORDS];
+        for handle in &cell.handles {
+            let stats = snapshot
+                .network_stats
+                .iter()
+                .find(|(entry, _)| entry == handle)
+                .map(|(_, stats)| stats);
+            if let Some(stats) = stats {
+                encode_stats(stats, &mut encoded);
+            }
+            let present = std::iter::once(u64::from(stats.is_some()));
+            for (value, word) in present.chain(encoded).zip(words.by_ref()) {
+                word.store(value, Ordering::Relaxed);
+            }
+        }
+
+        slot.seq.store(seq.wrapping_add(2), Ordering::Release);
+        cell.published.store(version, Ordering::Release);
+    }
+}
+
+/// A reader of the snapshots a session publishes, usable from any thread.
+///
+/// Returned by [`P2PSession::stats_publisher`](crate::P2PSession::stats_publisher).
+/// Clones read the same snapshots. Reading never blocks the session or waits
+/// for it: [`latest`](Self::latest) copies the newest complete snapshot and
+/// only repeats the copy if the session published twice in the meantime.
+#[derive(Debug, Clone)]
+pub struct StatsSubscription {
+    cell: Arc<StatsCell>,
+}
+
+impl StatsSubscription {
+    /// The version of the newest snapshot, without copying it. Compare with
+    /// [`StatsSnapshot::version`] to skip copies of a snapshot already read.
+    #[must_use]
+    pub fn version(&self) -> u64 {
+        self.cell.published.load(Ordering::Acquire)
+    }
+
+    /// Copies the newest snapshot.
+    #[must_use]
+    pub fn latest(&self) -> StatsSnapshot {
+        let mut snapshot = StatsSnapshot::default();
+        self.latest_into(&mut snapshot);
+        snapshot
+    }
+
+    /// Copies the newest snapshot into `snapshot`, reusing its allocation.
+    pub fn latest_into(&self, snapshot: &mut StatsSnapshot) {
+        let cell = &*self.cell;
+        loop {
+            let slot = cell.slot(cell.published.load(Ordering::Acquire));
+            let seq = slot.seq.load(Ordering::Acquire);
+            if seq % 2 == 0 {
+                read_words(&cell.handles, &slot.words, snapshot);
+                // Orders the word loads before the sequence check: if any of
+                // them saw a newer write, the check sees the newer sequence.
+                fence(Ordering::Acquire);
+                if slot.seq.load(Ordering::Relaxed) == seq {
+                    return;
+                }
+            }
+            // The session lapped this reader mid-copy; copy the newer slot.
+            yield_now();
+        }
+    }
+}
+
+/// Decodes a slot's words into `snapshot`. The result is only meaningful if
+/// the slot's sequence did not change meanwhile.
+fn read_words(handles: &[PlayerHandle], words: &[AtomicU64], snapshot: &mut StatsSnapshot) {
+    let mut words = words.iter().map(|word| word.load(Ordering::Relaxed));
+    let mut next = || words.next().unwrap_or(0);
+    snapshot.version = next();
+    snapshot.current_frame = word_frame(next());
+    snapshot.confirmed_frame = word_frame(next());
+    snapshot.frames_ahead = word_i32(next());
+    snapshot.rollback_count = next();
+    snapshot.network_stats.clear();
+    for handle in handles {
+        let present = next() != 0;
+        let mut encoded = [0; STATS_WORDS];
+        for value in &mut encoded {
+            *value = next();
+        }
+        if present {
+            snapshot
+                .network_stats
+                .push((*handle, decode_stats(&encoded)));
+        }
+    }
+}
+
+fn frame_word(frame: Frame) -> u64 {
+    i32_word(frame.as_i32())
+}
+
+fn word_frame(word: u64) -> Frame {
+    Frame::new(word_i32(word))
+}
+
+fn i32_word(value: i32) -> u64 {
+    u64::from(value as u32)
+}
+
+fn word_i32(word: u64) -> i32 {
+    word as u32 as i32
+}
+
+fn i64_word(value: i64) -> u64 {
+    value as u64
+}
+
+fn word_i64(word: u64) -> i64 {
+    word as i64
+}
+
+fn usize_word(value: usize) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+fn word_usize(word: u64) -> usize {
+    usize::try_from(word).unwrap_or(usize::MAX)
+}
+
+fn split_u128(value: u128) -> [u64; 2] {
+    [value as u64, (value >> 64) as u64]
+}
+
+fn join_u128(low: u64, high: u64) -> u128 {
+    u128::from(low) | (u128::from(high) << 64)
+}
+
+fn encode_stats(stats: &NetworkStats, words: &mut [u64; STATS_WORDS]) {
+    let mut flags = 0;
+    if stats.last_compared_frame.is_some() {
+        flags |= HAS_COMPARED_FRAME;
+    }
+    if stats.local_checksum.is_some() {
+        flags |= HAS_LOCAL_CHECKSUM;
+    }
+    if stats.remote_checksum.is_some() {
+        flags |= HAS_REMOTE_CHECKSUM;
+    }
+    match stats.checksums_match {
+        Some(true) => flags |= HAS_CHECKSUMS_MATCH | CHECKSUMS_MATCH,
+        Some(false) => flags |= HAS_CHECKSUMS_MATCH,
+        None => {},
+    }
+    if stats.estimated_clock_offset_ms.is_some() {
+        flags |= HAS_CLOCK_OFFSET;
+    }
+    let [ping_low, ping_high] = split_u128(stats.ping);
+    let [age_low, age_high] = split_u128(stats.stats_age_ms);
+    let [local_low, local_high] = split_u128(stats.local_checksum.unwrap_or(0));
+    let [remote_low, remote_high] = split_u128(stats.remote_checksum.unwrap_or(0));
+    *words = [
+        usize_word(stats.send_queue_len),
+        ping_low,
+        ping_high,
+        usize_word(stats.kbps_sent),
+        usize_word(stats.send_backlog_len),
+        usize_word(stats.send_backlog_bytes),
+        i32_word(stats.local_frame_advantage),
+        i32_word(stats.remote_frame_advantage),
+        age_low,
+        age_high,
+        flags,
+        stats.last_compared_frame.map_or(0, frame_word),
+        local_low,
+        local_high,
+        remote_low,
+        remote_high,
+        stats.duplicates_dropped,
+        stats.stale_dropped,
+        i64_word(stats.estimated_clock_offset_ms.unwrap_or(0)),
+    ];
+}
+
+fn decode_stats(words: &[u64; STATS_WORDS]) -> NetworkStats {
+    let [send_queue_len, ping_low, ping_high, kbps_sent, send_backlog_len, send_backlog_bytes, local_frame_advantage, remote_frame_advantage, age_low, age_high, flags, compared_frame, local_low, local_high, remote_low, remote_high, duplicates_dropped, stale_dropped, clock_offset] =
+        *words;
+    let local_frame_advantage = word_i32(local_frame_advantage);
+    let remote_frame_advantage = word_i32(remote_frame_advantage);
+    #[allow(deprecated)]
+    NetworkStats {
+        send_queue_len: word_usize(send_queue_len),
+        ping: join_u128(ping_low, ping_high),
+        kbps_sent: word_usize(kbps_sent),
+        send_backlog_len: word_usize(send_backlog_len),
+        send_backlog_bytes: word_usize(send_backlog_bytes),
+        local_frame_advantage,
+        remote_frame_advantage,
+        stats_age_ms: join_u128(age_low, age_high),
+        estimated_clock_offset_ms: (flags & HAS_CLOCK_OFFSET != 0)
+            .then_some(word_i64(clock_offset)),
+        local_frames_behind: local_frame_advantage,
+        remote_frames_behind: remote_frame_advantage,
+        last_compared_frame: (flags & HAS_COMPARED_FRAME != 0).then(|| word_frame(compared_frame)),
+        local_checksum: (flags & HAS_LOCAL_CHECKSUM != 0).then(|| join_u128(local_low, local_high)),
+        remote_checksum: (flags & HAS_REMOTE_CHECKSUM != 0)
+            .then(|| join_u128(remote_low, remote_high)),
+        checksums_match: (flags & HAS_CHECKSUMS_MATCH != 0).then_some(flags & CHECKSUMS_MATCH != 0),
+        duplicates_dropped,
+        stale_dropped,
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    #[allow(deprecated)]
+    fn sample_stats(seed: u64) -> NetworkStats {
+        NetworkStats {
+            send_queue_len: 3,
+            ping: (u128::from(seed) << 70) + 17,
+            kbps_sent: 40,
+            send_backlog_len: 2,
+            send_backlog_bytes: 900,
+            local_frame_advantage: -3,
+            remote_frame_advantage: 4,
+            stats_age_ms: 12,
+            estimated_clock_offset_ms: Some(-(seed as i64) - 250),
+            local_frames_behind: -3,
+            remote_frames_behind: 4,
+            last_compared_frame: Some(Frame::new(60)),
+            local_checksum: Some(u128::MAX - u128::from(seed)),
+            remote_checksum: None,
+            checksums_match: Some(false),
+            duplicates_dropped: seed,
+            stale_dropped: 1,
+        }
+    }
+
+    #[test]
+    fn network_stats_round_trip_through_words() {
+        for stats in [NetworkStats::default(), sample_stats(0), sample_stats(9)] {
+            let mut words = [0; STATS_WORDS];
+            encode_stats(&stats, &mut words);
+            assert_eq!(decode_stats(&words), stats);
+        }
+    }
+
+    #[test]
+    fn latest_returns_the_newest_published_snapshot() {
+        let handles = vec![PlayerHandle::new(2), PlayerHandle::new(1)];
+        let mut publisher = StatsPublisher::new(handles, 1).unwrap();
+        let subscription = publisher.subscribe();
+        assert_eq!(subscription.version(), 0);
+        assert_eq!(subscription.latest(), StatsSnapshot::default());
+
+        for round in 1..=3_u64 {
+            let scratch = publisher.scratch_mut();
+            scratch.current_frame = Frame::new(10 * round as i32);
+            scratch.confirmed_frame = Frame::NULL;
+            scratch.frames_ahead = -2;
+            scratch.rollback_count = round;
+            scratch.network_stats.clear();
+            // Handle 2 only; handle 7 has no room and is ignored.
+            scratch
+                .network_stats
+                .push((PlayerHandle::new(2), sample_stats(round)));
+            scratch
+                .network_stats
+                .push((PlayerHandle::new(7), sample_stats(0)));
+            publisher.publish();
+
+            let snapshot = subscription.clone().latest();
+            assert_eq!(snapshot.version, round);
+            assert_eq!(subscription.version(), round);
+            assert_eq!(snapshot.current_frame, Frame::new(10 * round as i32));
+            assert_eq!(snapshot.confirmed_frame, Frame::NULL);
+            assert_eq!(snapshot.frames_ahead, -2);
+            assert_eq!(snapshot.rollback_count, round);
+            assert_eq!(
+                snapshot.network_stats,
+                vec![(PlayerHandle::new(2), sample_stats(round))]
+            );
+        }
+    }
+
+    #[test]
+    fn tick_is_due_first_and_then_every_interval() {
+        let mut publisher = StatsPublisher::new(Vec::new(), 3).unwrap();
+        assert!(publisher.tick(), "nothing published yet");
+        publisher.publish();
+        let due: Vec<bool> = (0..6).map(|_| publisher.tick()).collect();
+        assert_eq!(due, [false, true, false, false, true, false]);
+
+        publisher.set_interval(1).unwrap();
+        assert!(publisher.tick());
+        assert!(publisher.set_interval(0).is_err());
+        assert!(StatsPublisher::new(Vec::new(), 0).is_err());
+    }
+
+    #[test]
+    fn readers_never_observe_torn_snapshots() {
+        let mut publisher = StatsPublisher::new(vec![PlayerHandle::new(1)], 1).unwrap();
+        let subscription = publisher.subscribe();
+        let reader = std::thread::spawn(move || {
+            let mut snapshot = StatsSnapshot::default();
+            let mut last_version = 0;
+            while last_version < 5_000 {
+                subscription.latest_into(&mut snapshot);
+                assert!(snapshot.version >= last_version, "versions never go back");
+                last_version = snapshot.version;
+                if snapshot.version == 0 {
+                    continue;
+                }
+                // Every field of a snapshot comes from the same publish.
+                let round = snapshot.rollback_count;
+                assert_eq!(snapshot.version, round);
+                assert_eq!(snapshot.current_frame, Frame::new(round as i32));
+                assert_eq!(
+                    snapshot.network_stats,
+                    vec![(PlayerHandle::new(1), sample_stats(round))]
+                );
+            }
+        });
+        for round in 1..=5_000_u64 {
+            let scratch = publisher.scratch_mut();
+            scratch.current_frame = Frame::new(round as i32);
+            scratch.rollback_count = round;
+            scratch.network_stats.clear();
+            scratch
+                .network_stats
+                .push((PlayerHandle::new(1), sample_stats(round)));
+            publisher.publish();
+        }
+        reader.join().unwrap();
+    }
+}
@@ -2,20 +2,32 @@
 use std::fmt;
 use std::sync::Arc;
 
-use crate::error::{allocation_failed, FortressError, InternalErrorKind, InvalidRequestKind};
+use crate::error::{
+    allocation_failed, FortressError, InternalErrorKind, InvalidFrameReason, InvalidRequestKind,
+};
 use crate::frame_info::PlayerInput;
 use crate::network::messages::ConnectionStatus;
 use crate::report_violation;
+use crate::rng::{Pcg32, Rng, SeedableRng};
 use crate::sessions::config::SaveMode;
 use crate::sessions::event_drain::EventDrain;
+use crate::sessions::previous_inputs::PreviousInputs;
 use crate::sessions::session_trait::Session;
-use crate::sync_layer::SyncLayer;
+use crate::sessions::state_slot::StateSlot;
+use crate::sync_layer::{StateDiffCodec, SyncLayer};
 use crate::telemetry::{ViolationKind, ViolationObserver, ViolationSeverity};
 use crate::{
     Config, FortressEvent, FortressRequest, FortressResult, Frame, HandleVec, PlayerHandle,
     RequestVec,
 };
 
+/// Converts a rollback depth into a frame delta. Depths are bounded by
+/// `max_prediction` at build time; an out-of-range one clamps rather than
+/// wrapping into a negative delta.
+fn frame_depth(depth: usize) -> i32 {
+    i32::try_from(depth).unwrap_or(i32::MAX)
+}
+
 /// During a [`SyncTestSession`], Fortress Rollback will simulate a rollback every frame and resimulate the last n states, where n is the given check distance.
 ///
 /// The resimulated checksums will be compared with the original checksums and report if there was a mismatch.
@@ -42,6 +54,85 @@ pub struct SyncTestSession<T>
     event_queue: VecDeque<FortressEvent<T>>,
     /// Optional observer for specification violations.
     violation_observer: Option<Arc<dyn ViolationObserver>>,
+    /// The session's final frame (see
+    /// [`with_max_session_frames`](crate::SessionBuilder::with_max_session_frames)).
+    max_session_frames: Frame,
+    /// Set once the session reached `max_session_frames`.
+    frame_limit_reached: bool,
+    /// Inputs of recently simulated frames, for the `previous_inputs` of each
+    /// [`FortressRequest::AdvanceFrame`].
+    previous_inputs: PreviousInputs<T::Input>,
+    /// Seed and generator of the rollback depths when rollbacks are
+    /// randomized; `None` rolls back `check_distance` frames every frame.
+    rollback_rng: Option<(u64, Pcg32)>,
+    /// Whether every other verification pass is perturbed (see
+    /// [`SessionBuilder::with_perturbed_resimulation`]).
+    ///
+    /// [`SessionBuilder::with_perturbed_resimulation`]: crate::SessionBuilder::with_perturbed_resimulation
+    perturbed_resimulation: bool,
+    /// Whether the last verification pass was perturbed, so a mismatch the
+    /// next check finds came from it.
+    last_pass_perturbed: bool,
+    /// The frame the session was at before its last
+    /// [`step_back`](Self::step_back); until the session is back there,
+    /// [`advance_frame`](Self::advance_frame) replays the recorded inputs.
+    replay_end: Frame,
+    /// The frame the last [`step_back`](Self::step_back) loaded. Determinism
+    /// checks roll back no further than this while states older than it may
+    /// be overwritten.
+    step_back_floor: Frame,
+    /// The [`FortressRequest::LoadGameState`] of the last
+    /// [`propose_state_restore`](Self::propose_state_restore), returned ahead
+    /// of the next advance's requests.
+    restored_load: Option<FortressRequest<T>>,
+}
+
+/// The outcome of [`SyncTestSession::run_script`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SyncTestSummary {
+    /// Frames the session advanced to before the script ended or a mismatch
+    /// was found.
+    pub frames: usize,
+    /// Rollbacks performed.
+    pub rollbacks: usize,
+    /// Frames resimulated over all rollbacks.
+    pub resimulated_frames: usize,
+    /// The deepest rollback performed, in frames.
+    pub deepest_rollback: usize,
+    /// The checksum mismatch that ended the script early, if any.
+    pub mismatch: Option<SyncTestMismatch>,
+}
+
+/// Which verification pass found a [`SyncTestMismatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyncTestMismatchKind {
+    /// A plain resimulation differed from the first simulation: the game is
+    /// not deterministic.
+    Nondeterministic,
+    /// A resimulation perturbed by
+    /// [`SessionBuilder::with_perturbed_resimulation`] differed: the game
+    /// likely depends on the order of something it should not, such as the
+    /// iteration order of a `HashMap`.
+    ///
+    /// [`SessionBuilder::with_perturbed_resimulation`]: crate::SessionBuilder::with_perturbed_resimulation
+    OrderDependenceSuspected,
+}
+
+/// A checksum mismatch found by [`SyncTestSession::run_script`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SyncTestMismatch {
+    /// The rollback seed the session was built with, or `None` for fixed
+    /// check-distance rollbacks. A session built with the same seed and fed
+    /// the same inputs reproduces the mismatch.
+    pub seed: Option<u64>,
+    /// The frame the session was on when it detected the mismatch.
+    pub frame: Frame,
+    /// The frames whose resimulated checksum differed from the original.
+    pub mismatched_frames: Vec<Frame>,
+    /// Whether a plain or a perturbed resimulation found the mismatch.
+    pub kind: SyncTestMismatchKind,
 }
 
 impl<T: Config> SyncTestSession<T> {
@@ -87,6 +178,8 @@ pub(crate) fn with_queue_length(
             input_delay,
             violation_observer,
             queue_length,
+            crate::MAX_SESSION_FRAMES,
+            false,
         ) {
             Ok(session) => session,
             Err(error) => {
@@ -110,11 +203,21 @@ pub(crate) fn with_queue_length(
                     local_inputs: BTreeMap::new(),
                     event_queue: VecDeque::new(),
                     violation_observer: None,
+                    max_session_frames: crate::MAX_SESSION_FRAMES,
+                    frame_limit_reached: false,
+                    previous_inputs: PreviousInputs::disabled(),
+                    rollback_rng: None,
+                    perturbed_resimulation: false,
+                    last_pass_perturbed: false,
+                    replay_end: Frame::NULL,
+                    step_back_floor: Frame::NULL,
+                    restored_load: None,
                 }
             },
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn try_with_queue_length(
         num_players: usize,
         max_prediction: usize,
@@ -122,6 +225,8 @@ pub(crate) fn try_with_queue_length(
         input_delay: usize,
         violation_observer: Option<Arc<dyn ViolationObserver>>,
         queue_length: usize,
+        max_session_frames: Frame,
+        previous_inputs: bool,
     ) -> Result<Self, FortressError> {
         let mut dummy_connect_status = Vec::new();
         dummy_connect_status
@@ -133,6 +238,9 @@ pub(crate) fn try_with_queue_length(
 
         let mut sync_layer =
             SyncLayer::try_with_queue_length(num_players, max_prediction, queue_length)?;
+        // `step_back` may return to any saved frame, so the inputs of the whole
+        // saved window stay queued.
+        sync_layer.retain_inputs(max_prediction);
         for i in 0..num_players {
             // This should never fail during construction as player handles are sequential and valid
             if let Err(e) = sync_layer.set_frame_delay(PlayerHandle::new(i), input_delay) {
@@ -156,9 +264,39 @@ pub(crate) fn try_with_queue_length(
             local_inputs: BTreeMap::new(),
             event_queue: VecDeque::new(),
             violation_observer,
+            max_session_frames,
+            frame_limit_reached: false,
+            // Checks and step backs load frames up to `max_prediction` back,
+            // and the frame before the loaded one must still be known.
+            previous_inputs: PreviousInputs::new(
+                previous_inputs,
+                max_prediction.saturating_add(1),
+            )?,
+            rollback_rng: None,
+            perturbed_resimulation: false,
+            last_pass_perturbed: false,
+            replay_end: Frame::NULL,
+            step_back_floor: Frame::NULL,
+            restored_load: None,
         })
     }
 
+    /// Rolls back a depth drawn from a generator seeded with `seed`, between
+    /// 1 and `check_distance` frames, instead of `check_distance` every frame.
+    pub(crate) fn randomize_rollbacks(&mut self, seed: u64) {
+        self.rollback_rng = Some((seed, Pcg32::seed_from_u64(seed)));
+    }
+
+    /// Perturbs every other verification pass.
+    pub(crate) fn perturb_resimulation(&mut self) {
+        self.perturbed_resimulation = true;
+    }
+
+    /// Keeps the saved states as keyframes and diffs encoded by `codec`.
+    pub(crate) fn enable_state_diffs(&mut self, codec: Arc<dyn StateDiffCodec<T::State>>) {
+        self.sync_layer.enable_state_diffs(codec);
+    }
+
     /// Registers local input for a player for the current frame. This should be successfully called for every local player before calling [`advance_frame()`](Self::advance_frame).
     /// If this is called multiple times for the same player before advancing the frame, older given inputs will be overwritten.
     /// In a sync test, all players are considered to be local, so you need to add input for all of them.
@@ -190,6 +328,8 @@ pub fn add_local_input(
     ///
     /// # Errors
     /// - Returns [`MismatchedChecksum`] if checksums don't match after resimulation.
+    /// - Returns [`InvalidRequestKind::SessionFrameLimitReached`] once the session reached its
+    ///   frame limit.
     ///
     /// [`RequestVec`]: crate::RequestVec
     /// [`MismatchedChecksum`]: FortressError::MismatchedChecksum
@@ -200,11 +340,26 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
         // without heap allocation. During rollback testing, it spills to the heap as needed.
         let mut requests = RequestVec::<T>::new();
 
-        // if we advanced far enough into the game do comparisons and rollbacks
+        // The session never simulates past its frame limit.
         let current_frame = self.sync_layer.current_frame();
-        if self.check_distance > 0 && current_frame.as_i32() > self.check_distance as i32 {
+        if current_frame >= self.max_session_frames {
+            if !self.frame_limit_reached {
+                self.frame_limit_reached = true;
+                self.event_queue
+                    .push_back(FortressEvent::SessionFrameLimitReached {
+                        frame: self.max_session_frames,
+                    });
+            }
+            return Err(InvalidRequestKind::SessionFrameLimitReached {
+                frame: self.max_session_frames,
+            }
+            .into());
+        }
+
+        // if we advanced far enough into the game do comparisons and rollbacks
+        if self.check_distance > 0 && current_frame.as_i32() > frame_depth(self.check_distance) {
             // compare checksums of older frames to our checksum history (where only the first version of any checksum is recorded)
-            let oldest_frame_to_check = current_frame.as_i32() - self.check_distance as i32;
+            let oldest_frame_to_check = current_frame.as_i32() - frame_depth(self.check_distance);
             let mismatched_frames: Vec<_> = (oldest_frame_to_check..=current_frame.as_i32())
                 .filter(|&frame_to_check| !self.checksums_consistent(Frame::new(frame_to_check)))
                 .map(Frame::new)
@@ -217,19 +372,33 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
                 });
             }
 
-            // simulate rollbacks according to the check_distance
-            let frame_to = self.sync_layer.current_frame() - self.check_distance as i32;
-            self.adjust_gamestate(frame_to, &mut requests)?;
+            // simulate rollbacks according to the check_distance, or a random
+            // depth within it
+            let depth = match &mut self.rollback_rng {
+                Some((_, rng)) => rng.gen_range_usize(1..self.check_distance.saturating_add(1)),
+                None => self.check_distance,
+            };
+            // right after a step back, the frames before the loaded one may
+            // no longer be saved
+            let frame_to = std::cmp::max(current_frame - frame_depth(depth), self.step_back_floor);
+            if frame_to < current_frame {
+                let perturb = self.perturbed_resimulation && !self.last_pass_perturbed;
+                self.last_pass_perturbed = perturb;
+                self.adjust_gamestate(frame_to, perturb, &mut requests)?;
+            }
         }
 
-        // we require inputs for all players
-        if self.num_players != self.local_inputs.len() {
-            return Err(InvalidRequestKind::MissingLocalInput.into());
-        }
-        // pass all inputs into the sync layer
-        for (&handle, &input) in self.local_inputs.iter() {
-            // send the input into the sync layer
-            self.sync_layer.add_local_input(handle, input);
+        // the inputs of a frame simulated before a step back are already recorded
+        if current_frame >= self.replay_end {
+            // we require inputs for all players
+            if self.num_players != self.local_inputs.len() {
+                return Err(InvalidRequestKind::MissingLocalInput.into());
+            }
+            // pass all inputs into the sync layer
+            for (&handle, &input) in self.local_inputs.iter() {
+                // send the input into the sync layer
+                self.sync_layer.add_local_input(handle, input)?;
+            }
         }
         // clear local inputs after using them
         self.local_inputs.clear();
@@ -262,12 +431,18 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
         };
 
         // advance the frame
-        requests.push(FortressRequest::AdvanceFrame { inputs });
+        let previous_inputs = self
+            .previous_inputs
+            .record(self.sync_layer.current_frame(), &inputs)?;
+        requests.push(FortressRequest::AdvanceFrame {
+            inputs,
+            previous_inputs,
+        });
         self.sync_layer.advance_frame();
 
         // since this is a sync test, we "cheat" by setting the last confirmed state to the (current state - check_distance), so the sync layer won't complain about missing
         // inputs from other players
-        let safe_frame = self.sync_layer.current_frame() - self.check_distance as i32;
+        let safe_frame = self.sync_layer.current_frame() - frame_depth(self.check_distance);
 
         self.sync_layer
             .set_last_confirmed_frame(safe_frame, SaveMode::EveryFrame);
@@ -277,15 +452,175 @@ pub fn advance_frame(&mut self) -> FortressResult<RequestVec<T>> {
             con_stat.last_frame = self.sync_layer.current_frame();
         }
 
+        if let Some(load) = self.restored_load.take() {
+            requests.insert(0, load);
+        }
+        Ok(requests)
+    }
+
+    /// Steps the simulation back `frames` frames, for debugging it frame by
+    /// frame. Returns the [`FortressRequest::LoadGameState`] of the state saved
+    /// then.
+    ///
+    /// The following [`advance_frame`](Self::advance_frame) calls resimulate
+    /// forward with the inputs recorded for those frames, ignoring any given to
+    /// [`add_local_input`](Self::add_local_input), until the session is back at
+    /// the frame it stepped back from. The determinism checks keep running, so
+    /// a resimulated frame whose checksum differs from the first pass returns
+    /// [`MismatchedChecksum`](FortressError::MismatchedChecksum).
+    ///
+    /// The session keeps the states and inputs of its last
+    /// [`max_prediction`](Self::max_prediction) frames; raise it with
+    /// [`SessionBuilder::with_max_prediction_window`] to step back further.
+    /// With a check distance of 0 no state is saved, so only a step of 0
+    /// frames succeeds.
+    ///
+    /// # Errors
+    /// - Returns [`FortressError::InvalidFrameStructured`] with
+    ///   [`InvalidFrameReason::BeforeOldestSavedFrame`], naming the oldest frame
+    ///   the session can step back to, if `frames` reaches further back.
+    ///
+    /// [`SessionBuilder::with_max_prediction_window`]: crate::SessionBuilder::with_max_prediction_window
+    #[must_use = "FortressRequests must be processed to step the game state back"]
+    pub fn step_back(&mut self, frames: u32) -> FortressResult<RequestVec<T>> {
+        let _violation_scope = self.scoped_violation_observer();
+        let mut requests = RequestVec::<T>::new();
+        let current_frame = self.sync_layer.current_frame();
+        if frames == 0 {
+            return Ok(requests);
+        }
+
+        let oldest_frame = self.oldest_step_back_frame();
+        let target = i32::try_from(frames)
+            .ok()
+            .and_then(|frames| current_frame.as_i32().checked_sub(frames))
+            .unwrap_or(i32::MIN);
+        let target = Frame::new(target);
+        if target < oldest_frame {
+            return Err(FortressError::InvalidFrameStructured {
+                frame: target,
+                reason: InvalidFrameReason::BeforeOldestSavedFrame { oldest_frame },
+            });
+        }
+
+        requests.push(self.sync_layer.load_frame(target)?);
+        self.sync_layer.reset_prediction();
+        // keep the confirmed frame `check_distance` behind the current one
+        let confirmed = std::cmp::max(target - frame_depth(self.check_distance), Frame::NULL);
+        self.sync_layer
+            .set_last_confirmed_frame(confirmed, SaveMode::EveryFrame);
+        self.replay_end = std::cmp::max(self.replay_end, current_frame);
+        self.step_back_floor = target;
+        self.local_inputs.clear();
         Ok(requests)
     }
 
+    /// Copies the most recent confirmed saved state into a [`StateSlot`], for
+    /// a training mode's save-state. Returns `None` until a state at or before
+    /// the [`confirmed_frame`](Self::confirmed_frame) is saved, and always with
+    /// a check distance of 0, which saves no states.
+    #[must_use]
+    pub fn export_state_slot(&self) -> Option<StateSlot<T::State>>
+    where
+        T::State: Clone,
+    {
+        let confirmed = self.confirmed_frame();
+        if confirmed.is_null() {
+            return None;
+        }
+        let frame = self
+            .sync_layer
+            .newest_saved_frame_in_range(Frame::new(0), confirmed);
+        let (state, checksum) = self.sync_layer.capture_snapshot_state(frame)?;
+        Some(StateSlot::new(frame, state, checksum))
+    }
+
+    /// Restores a [`StateSlot`] taken with
+    /// [`export_state_slot`](Self::export_state_slot), possibly by another
+    /// session of the same game, and continues the session from its frame.
+    ///
+    /// The next [`advance_frame`](Self::advance_frame) returns a
+    /// [`FortressRequest::LoadGameState`] of the slot's state ahead of its
+    /// other requests. The inputs of the frames from the slot's frame on are
+    /// forgotten: that call and the following ones take new ones from
+    /// [`add_local_input`](Self::add_local_input), also after a
+    /// [`step_back`](Self::step_back), and the determinism checks start over
+    /// from the restored frame.
+    ///
+    /// With an input delay, the frames the delay covers from the slot's frame
+    /// on hold the default input, as at the start of a session: their inputs
+    /// were given before the slot was saved and are not part of it.
+    ///
+    /// # Errors
+    /// - Returns [`InvalidRequestKind::UnsupportedStateSlotVersion`] if the
+    ///   slot was written in another format version.
+    /// - Returns [`FortressError::InvalidFrameStructured`] if the slot's frame
+    ///   is negative.
+    pub fn propose_state_restore(
+        &mut self,
+        slot: StateSlot<T::State>,
+    ) -> Result<(), FortressError> {
+        let _violation_scope = self.scoped_violation_observer();
+        let (frame, state, checksum) = slot.into_restorable()?;
+        let load = self.sync_layer.restore_state(frame, state, checksum)?;
+        self.restored_load = Some(load);
+        self.checksum_history.clear();
+        self.local_inputs.clear();
+        self.previous_inputs.clear();
+        self.replay_end = frame;
+        // nothing before the restored frame is saved any more
+        self.step_back_floor = frame;
+        self.frame_limit_reached = false;
+        for con_stat in &mut self.dummy_connect_status {
+            con_stat.last_frame = frame;
+        }
+        Ok(())
+    }
+
+    /// The oldest frame [`step_back`](Self::step_back) can load: the oldest of
+    /// the saved frames directly before the current one.
+    fn oldest_step_back_frame(&self) -> Frame {
+        let current_frame = self.sync_layer.current_frame();
+        let mut oldest = current_frame;
+        for depth in 1..=self.max_prediction {
+            let frame = current_frame - frame_depth(depth);
+            if !frame.is_valid() || self.sync_layer.saved_state_by_frame(frame).is_none() {
+                break;
+            }
+            oldest = frame;
+        }
+        oldest
+    }
+
     /// Returns the current frame of a session.
     #[must_use]
     pub fn current_frame(&self) -> Frame {
         self.sync_layer.current_frame()
     }
 
+    /// Returns a read-only view of the session's input queues and frame
+    /// counters; see the [`advanced`](crate::advanced) module for its
+    /// stability guarantees.
+    #[cfg(feature = "advanced")]
+    #[must_use]
+    pub fn sync_layer_view(&self) -> crate::advanced::SyncLayerView<'_, T> {
+        crate::advanced::SyncLayerView::new(&self.sync_layer)
+    }
+
+    /// Returns the newest frame this session will not roll back to while
+    /// checking determinism: `check_distance` frames behind
+    /// [`current_frame`](Self::current_frame), or [`Frame::NULL`] until the
+    /// session is that far in.
+    #[must_use]
+    pub fn confirmed_frame(&self) -> Frame {
+        let frame = self.sync_layer.last_confirmed_frame();
+        if frame.is_valid() {
+            frame
+        } else {
+            Frame::NULL
+        }
+    }
+
     /// Returns the number of players this session was constructed with.
     #[must_use]
     pub fn num_players(&self) -> usize {
@@ -299,11 +634,106 @@ pub fn max_prediction(&self) -> usize {
     }
 
     /// Returns the check distance set on creation, i.e. the length of the simulated rollbacks
+    /// (their maximum length when rollbacks are randomized)
     #[must_use]
     pub fn check_distance(&self) -> usize {
         self.check_distance
     }
 
+    /// Returns the seed of the randomized rollback depths, or `None` when the
+    /// session rolls back the check distance every frame (see
+    /// [`SessionBuilder::with_randomized_rollbacks`]).
+    ///
+    /// [`SessionBuilder::with_randomized_rollbacks`]: crate::SessionBuilder::with_randomized_rollbacks
+    #[must_use]
+    pub fn rollback_seed(&self) -> Option<u64> {
+        self.rollback_rng.as_ref().map(|&(seed, _)| seed)
+    }
+
+    /// Returns whether every other verification pass is perturbed (see
+    /// [`SessionBuilder::with_perturbed_resimulation`]).
+    ///
+    /// [`SessionBuilder::with_perturbed_resimulation`]: crate::SessionBuilder::with_perturbed_resimulation
+    #[must_use]
+    pub fn perturbed_resimulation(&self) -> bool {
+        self.perturbed_resimulation
+    }
+
+    /// Drives the session for `frames` frames, passing every request to
+    /// `handle` in order.
+    ///
+    /// Before each frame, `inputs` is asked for every player's input for the
+    /// current frame. A checksum mismatch ends the run early and is reported
+    /// in [`SyncTestSummary::mismatch`] together with the
+    /// [`rollback_seed`](Self::rollback_seed), rather than as an error.
+    ///
+    /// # Errors
+    /// - Returns any other error [`add_local_input`](Self::add_local_input) or
+    ///   [`advance_frame`](Self::advance_frame) returns, such as
+    ///   [`InvalidRequestKind::SessionFrameLimitReached`].
+    pub fn run_script<I, H>(
+        &mut self,
+        mut inputs: I,
+        frames: usize,
+        mut handle: H,
+    ) -> FortressResult<SyncTestSummary>
+    where
+        I: FnMut(Frame, PlayerHandle) -> T::Input,
+        H: FnMut(RequestVec<T>),
+    {
+        let mut summary = SyncTestSummary {
+            frames: 0,
+            rollbacks: 0,
+            resimulated_frames: 0,
+            deepest_rollback: 0,
+            mismatch: None,
+        };
+        for _ in 0..frames {
+            let frame = self.current_frame();
+            for handle in (0..self.num_players).map(PlayerHandle::new) {
+                self.add_local_input(handle, inputs(frame, handle))?;
+            }
+            let requests = match self.advance_frame() {
+                Ok(requests) => requests,
+                Err(FortressError::MismatchedChecksum {
+                    current_frame,
+                    mismatched_frames,
+                }) => {
+                    let kind = if self.last_pass_perturbed {
+                        SyncTestMismatchKind::OrderDependenceSuspected
+                    } else {
+                        SyncTestMismatchKind::Nondeterministic
+                    };
+                    summary.mismatch = Some(SyncTestMismatch {
+                        seed: self.rollback_seed(),
+                        frame: current_frame,
+                        mismatched_frames,
+                        kind,
+                    });
+                    return Ok(summary);
+                },
+                Err(error) => return Err(error),
+            };
+            if requests
+                .iter()
+                .any(|request| matches!(request, FortressRequest::LoadGameState { .. }))
+            {
+                // Every advance but the new frame's resimulates.
+                let depth = requests
+                    .iter()
+                    .filter(|request| matches!(request, FortressRequest::AdvanceFrame { .. }))
+                    .count()
+                    .saturating_sub(1);
+                summary.rollbacks += 1;
+                summary.resimulated_frames += depth;
+                summary.deepest_rollback = summary.deepest_rollback.max(depth);
+            }
+            handle(requests);
+            summary.frames += 1;
+        }
+        Ok(summary)
+    }
+
     /// Returns a reference to the violation observer, if one was configured.
     ///
     /// This allows checking for violations that occurred during session operations
@@ -508,7 +938,7 @@ pub fn local_player_handle(&self) -> Option<PlayerHandle> {
     ///         .with_check_distance(2)
     ///         .start_synctest_session()?;
     ///
-    /// // SyncTestSession currently produces no events
+    /// // SyncTestSession produces no events until it reaches its frame limit
     /// let events: Vec<_> = session.events().collect();
     /// assert!(events.is_empty());
     /// # Ok(())
@@ -525,7 +955,8 @@ pub fn events(&mut self) -> EventDrain<'_, T> {
     /// Updates the `checksum_history` and checks if the checksum is identical if it already has been recorded once
     fn checksums_consistent(&mut self, frame_to_check: Frame) -> bool {
         // remove entries older than the `check_distance`
-        let oldest_allowed_frame = self.sync_layer.current_frame() - self.check_distance as i32;
+        let oldest_allowed_frame =
+            self.sync_layer.current_frame() - frame_depth(self.check_distance);
         self.checksum_history
             .retain(|&k, _| k >= oldest_allowed_frame);
 
@@ -542,16 +973,30 @@ fn checksums_consistent(&mut self, frame_to_check: Frame) -> bool {
         }
     }
 
+    /// Rolls back to `frame_to` and resimulates to the current frame. A
+    /// `perturb`ed pass loads `frame_to` twice and, past the first two
+    /// frames, reloads the state it saves halfway.
     fn adjust_gamestate(
         &mut self,
         frame_to: Frame,
+        perturb: bool,
         requests: &mut RequestVec<T>,
     ) -> Result<(), FortressError> {
         let start_frame = self.sync_layer.current_frame();
         let count = start_frame - frame_to;
+        let reload_at = if perturb && count >= 2 { count / 2 } else { 0 };
 
         // rollback to the first incorrect state
-        requests.push(self.sync_layer.load_frame(frame_to)?);
+        let load = self.sync_layer.load_frame(frame_to)?;
+        if perturb {
+            if let FortressRequest::LoadGameState { cell, frame } = &load {
+                requests.push(FortressRequest::LoadGameState {
+                    cell: cell.clone(),
+                    frame: *frame,
+                });
+            }
+        }
+        requests.push(load);
         self.sync_layer.reset_prediction();
         let actual_frame = self.sync_layer.current_frame();
         if actual_frame != frame_to {
@@ -588,12 +1033,29 @@ fn adjust_gamestate(
 
             // first save (except in the first step, because we just loaded that state)
             if i > 0 {
-                requests.push(self.sync_layer.save_current_state());
+                let save = self.sync_layer.save_current_state();
+                let reload = match &save {
+                    FortressRequest::SaveGameState { cell, frame } if i == reload_at => {
+                        Some(FortressRequest::LoadGameState {
+                            cell: cell.clone(),
+                            frame: *frame,
+                        })
+                    },
+                    _ => None,
+                };
+                requests.push(save);
+                requests.extend(reload);
             }
             // then advance
+            let previous_inputs = self
+                .previous_inputs
+                .record(self.sync_layer.current_frame(), &inputs)?;
             self.sync_layer.advance_frame();
 
-            requests.push(FortressRequest::AdvanceFrame { inputs });
+            requests.push(FortressRequest::AdvanceFrame {
+                inputs,
+                previous_inputs,
+            });
         }
         let final_frame = self.sync_layer.current_frame();
         if final_frame != start_frame {
@@ -615,6 +1077,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("num_players", &self.num_players)
             .field("max_prediction", &self.max_prediction)
             .field("check_distance", &self.check_distance)
+            .field("rollback_seed", &self.rollback_seed())
+            .field("perturbed_resimulation", &self.perturbed_resimulation)
             .field("current_frame", &self.sync_layer.current_frame())
             .finish_non_exhaustive()
     }
@@ -640,6 +1104,14 @@ fn add_local_input(
     fn events(&mut self) -> EventDrain<'_, T> {
         Self::events(self)
     }
+
+    fn current_frame(&self) -> Frame {
+        Self::current_frame(self)
+    }
+
+    fn confirmed_frame(&self) -> Frame {
+        Self::confirmed_frame(self)
+    }
 }
 
 #[cfg(test)]
@@ -706,6 +1178,7 @@ fn sync_test_session_with_violation_observer() {
     ///
     /// NON-VACUITY: removing the construction-time scope push leaves the observer
     /// empty and fails this test.
+    #[cfg(feature = "telemetry")]
     #[test]
     fn sync_test_construction_violation_routes_to_session_observer() {
         let observer = Arc::new(CollectingObserver::new());
@@ -797,7 +1270,7 @@ fn add_local_input_overwrites_previous_input() {
             .find(|r| matches!(r, FortressRequest::AdvanceFrame { .. }));
         assert!(advance_request.is_some());
 
-        if let Some(FortressRequest::AdvanceFrame { inputs }) = advance_request {
+        if let Some(FortressRequest::AdvanceFrame { inputs, .. }) = advance_request {
             assert_eq!(inputs[0].0, 100); // Second input should be used
         }
     }
@@ -1237,7 +1710,7 @@ fn requests_contain_advance_frame_with_inputs() {
             "Should have AdvanceFrame request"
         );
 
-        if let Some(FortressRequest::AdvanceFrame { inputs }) = advance_request {
+        if let Some(FortressRequest::AdvanceFrame { inputs, .. }) = advance_request {
             assert_eq!(inputs.len(), 2);
             assert_eq!(inputs[0].0, 111);
             assert_eq!(inputs[1].0, 222);
@@ -1277,6 +1750,79 @@ fn requests_order_save_before_advance() {
         );
     }
 
+    /// The shape of each request: `L`oad or `S`ave with its frame, or `A`dvance.
+    fn request_shapes(requests: &RequestVec<TestConfig>) -> Vec<(char, i32)> {
+        requests
+            .iter()
+            .map(|request| match request {
+                FortressRequest::LoadGameState { frame, .. } => ('L', frame.as_i32()),
+                FortressRequest::SaveGameState { frame, .. } => ('S', frame.as_i32()),
+                FortressRequest::AdvanceFrame { .. } => ('A', -1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn perturbed_resimulation_alternates_with_plain_passes() {
+        let mut session: SyncTestSession<TestConfig> = SyncTestSession::new(1, 8, 4, 0, None);
+        session.perturb_resimulation();
+        let advance = |session: &mut SyncTestSession<TestConfig>| {
+            session
+                .add_local_input(PlayerHandle::new(0), 1)
+                .expect("valid handle");
+            let requests = session.advance_frame().expect("checksums match");
+            let shapes = request_shapes(&requests);
+            for request in requests {
+                if let FortressRequest::SaveGameState { cell, frame } = request {
+                    cell.save(frame, Some(Vec::new()), Some(frame.as_i32() as u128));
+                }
+            }
+            shapes
+        };
+        for _ in 0..5 {
+            advance(&mut session);
+        }
+
+        // Frame 5: the first pass is perturbed. It loads frame 1 twice and
+        // reloads frame 3 after saving it.
+        let a = ('A', -1);
+        assert_eq!(
+            advance(&mut session),
+            vec![
+                ('L', 1),
+                ('L', 1),
+                a,
+                ('S', 2),
+                a,
+                ('S', 3),
+                ('L', 3),
+                a,
+                ('S', 4),
+                a,
+                ('S', 5),
+                a,
+            ]
+        );
+        assert!(session.last_pass_perturbed);
+        // Frame 6: a plain pass.
+        assert_eq!(
+            advance(&mut session),
+            vec![
+                ('L', 2),
+                a,
+                ('S', 3),
+                a,
+                ('S', 4),
+                a,
+                ('S', 5),
+                a,
+                ('S', 6),
+                a,
+            ]
+        );
+        assert!(!session.last_pass_perturbed);
+    }
+
     // ==========================================
     // SessionBuilder Integration Tests
     // ==========================================
@@ -1391,7 +1937,7 @@ fn advance_frame_returns_confirmed_input_status() {
 
         let requests = session.advance_frame().expect("should advance");
 
-        if let Some(FortressRequest::AdvanceFrame { inputs }) = requests
+        if let Some(FortressRequest::AdvanceFrame { inputs, .. }) = requests
             .iter()
             .find(|r| matches!(r, FortressRequest::AdvanceFrame { .. }))
         {
@@ -0,0 +1,157 @@
+//! Blocking wait for the initial synchronization of a session.
+//!
+//! This module provides [`SyncWaitError`] and [`EndpointSyncProgress`], returned
+//! by [`P2PSession::wait_for_synchronization`] and
+//! [`SpectatorSession::wait_for_synchronization`]. Those methods replace the
+//! "poll, check state, sleep, bail on timeout" loop that CLI tools and tests
+//! otherwise write by hand. Games should keep polling from their own loop.
+//!
+//! [`P2PSession::wait_for_synchronization`]: crate::P2PSession::wait_for_synchronization
+//! [`SpectatorSession::wait_for_synchronization`]: crate::SpectatorSession::wait_for_synchronization
+
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use web_time::Duration;
+
+/// Synchronization progress of one remote endpoint, captured when a wait
+/// timed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointSyncProgress<A> {
+    /// Address of the remote peer, spectator, or host.
+    pub addr: A,
+    /// Whether this endpoint finished synchronizing.
+    pub synchronized: bool,
+    /// Sync roundtrips completed so far.
+    pub roundtrips_done: u32,
+    /// Sync roundtrips required (`SyncConfig::num_sync_packets`).
+    pub roundtrips_required: u32,
+    /// Sync requests sent so far, including retries.
+    pub requests_sent: u32,
+    /// Time since the endpoint started synchronizing, on the session's
+    /// protocol clock.
+    pub elapsed: Duration,
+}
+
+/// Returned by `wait_for_synchronization` when the session is still not
+/// running once the timeout elapsed.
+///
+/// Lists the progress of every remote endpoint so a stuck handshake can be
+/// told apart from a peer that never answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncWaitError<A> {
+    /// Wall-clock time spent waiting.
+    pub waited: Duration,
+    /// Progress of every remote endpoint, synchronized or not.
+    pub endpoints: Vec<EndpointSyncProgress<A>>,
+}
+
+impl<A: Debug> Display for SyncWaitError<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "session did not synchronize within {:?}", self.waited)?;
+        for endpoint in &self.endpoints {
+            write!(
+                f,
+                "; {:?}: {}/{} roundtrips, {} requests sent, {:?} elapsed",
+                endpoint.addr,
+                endpoint.roundtrips_done,
+                endpoint.roundtrips_required,
+                endpoint.requests_sent,
+                endpoint.elapsed
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<A: Debug> Error for SyncWaitError<A> {}
+
+/// Calls `poll` every `poll_interval` until it reports the session running or
+/// `timeout` elapses, then builds the error from `progress`.
+///
+/// `poll` is called at least once, so a zero timeout still gives the session
+/// one chance to process what already arrived.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn wait_until_running<S, A>(
+    session: &mut S,
+    timeout: Duration,
+    poll_interval: Duration,
+    poll: fn(&mut S) -> bool,
+    progress: fn(&S) -> Vec<EndpointSyncProgress<A>>,
+) -> Result<(), SyncWaitError<A>> {
+    let start = web_time::Instant::now();
+    loop {
+        if poll(session) {
+            return Ok(());
+        }
+        let waited = start.elapsed();
+        let Some(remaining) = timeout.checked_sub(waited).filter(|left| !left.is_zero()) else {
+            return Err(SyncWaitError {
+                waited,
+                endpoints: progress(session),
+            });
+        };
+        std::thread::sleep(poll_interval.min(remaining));
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_lists_every_endpoint() {
+        let err = SyncWaitError {
+            waited: Duration::from_millis(300),
+            endpoints: vec![EndpointSyncProgress {
+                addr: 7_u8,
+                synchronized: false,
+                roundtrips_done: 2,
+                roundtrips_required: 5,
+                requests_sent: 4,
+                elapsed: Duration::from_millis(250),
+            }],
+        };
+        assert_eq!(
+            err.to_string(),
+            "session did not synchronize within 300ms; 7: 2/5 roundtrips, 4 requests sent, 250ms elapsed"
+        );
+    }
+
+    #[test]
+    fn wait_polls_until_running_and_times_out_otherwise() {
+        let mut polls = 0_u32;
+        let result = wait_until_running(
+            &mut polls,
+            Duration::from_secs(5),
+            Duration::ZERO,
+            |polls| {
+                *polls += 1;
+                *polls == 3
+            },
+            |_| Vec::<EndpointSyncProgress<u8>>::new(),
+        );
+        assert!(result.is_ok());
+        assert_eq!(polls, 3);
+
+        let mut polls = 0_u32;
+        let err = wait_until_running(
+            &mut polls,
+            Duration::ZERO,
+            Duration::from_millis(1),
+            |polls| {
+                *polls += 1;
+                false
+            },
+            |_| Vec::<EndpointSyncProgress<u8>>::new(),
+        )
+        .unwrap_err();
+        assert_eq!(polls, 1);
+        assert!(err.endpoints.is_empty());
+    }
+}
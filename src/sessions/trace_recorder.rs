@@ -0,0 +1,320 @@
+//! Timeline recording in Chrome trace-event format.
+//!
+//! With [`SessionBuilder::with_trace_recorder`](crate::SessionBuilder::with_trace_recorder)
+//! enabled, a [`P2PSession`](crate::P2PSession) records how long each
+//! `advance_frame`, network poll and rollback took, and when each
+//! [`FortressEvent`] was raised, in a fixed-size ring of plain records.
+//! Nothing is formatted until
+//! [`P2PSession::export_trace_json`](crate::P2PSession::export_trace_json)
+//! writes the ring as a [trace-event] JSON document, which `chrome://tracing`
+//! and Perfetto open directly.
+//!
+//! [trace-event]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+
+use std::fmt::Write;
+
+use web_time::Instant;
+
+use crate::error::allocation_failed;
+use crate::metrics::EventKind;
+use crate::sessions::config::ClockFn;
+use crate::{Config, FortressError, FortressEvent, Frame};
+
+/// Largest capacity accepted by
+/// [`SessionBuilder::with_trace_recorder`](crate::SessionBuilder::with_trace_recorder).
+pub(crate) const MAX_TRACE_RECORDER_CAPACITY: usize = 1 << 20;
+
+/// A timed operation of the session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TraceSpan {
+    /// One `advance_frame` call; args are the frame reached and the number of
+    /// requests returned.
+    AdvanceFrame,
+    /// One network poll; the arg is the number of messages received.
+    Poll,
+    /// A rollback; args are the first incorrect frame and the number of
+    /// frames resimulated.
+    Rollback,
+}
+
+impl TraceSpan {
+    fn name(self) -> &'static str {
+        match self {
+            Self::AdvanceFrame => "advance_frame",
+            Self::Poll => "poll",
+            Self::Rollback => "rollback",
+        }
+    }
+
+    fn arg_names(self) -> [&'static str; 2] {
+        match self {
+            Self::AdvanceFrame => ["frame", "requests"],
+            Self::Poll => ["received", ""],
+            Self::Rollback => ["first_incorrect", "depth"],
+        }
+    }
+}
+
+/// What a record describes.
+#[derive(Debug, Clone, Copy)]
+enum TraceKind {
+    Span { span: TraceSpan, duration_us: u64 },
+    Event(EventKind),
+}
+
+/// One recorded span or event.
+#[derive(Debug, Clone, Copy)]
+struct TraceRecord {
+    kind: TraceKind,
+    /// Microseconds from the recorder's creation to the start of the span or
+    /// the event.
+    at_us: u64,
+    /// Span arguments, or the event's frame and its skip count for a wait
+    /// recommendation.
+    args: [i64; 2],
+}
+
+/// Ring of the `capacity` most recent spans and events.
+pub(crate) struct TraceRecorder {
+    /// Records in insertion order modulo the capacity.
+    records: Vec<TraceRecord>,
+    capacity: usize,
+    /// Where the next record goes once the ring is full.
+    next: usize,
+    /// Records overwritten since creation.
+    dropped: u64,
+    clock: Option<ClockFn>,
+    origin: Instant,
+}
+
+impl std::fmt::Debug for TraceRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TraceRecorder")
+            .field("len", &self.records.len())
+            .field("capacity", &self.capacity)
+            .field("dropped", &self.dropped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TraceRecorder {
+    /// An empty recorder keeping the `capacity` most recent records, timed on
+    /// `clock` (or the platform clock); `capacity` must be at least 1.
+    pub(crate) fn new(capacity: usize, clock: Option<ClockFn>) -> Result<Self, FortressError> {
+        let capacity = capacity.max(1);
+        let mut records = Vec::new();
+        // alloc-bound: `capacity` is validated against
+        // `MAX_TRACE_RECORDER_CAPACITY` by the builder.
+        records
+            .try_reserve_exact(capacity)
+            .map_err(|_err| allocation_failed("trace_recorder.records", capacity))?;
+        let origin = match &clock {
+            Some(clock_fn) => clock_fn(),
+            None => Instant::now(),
+        };
+        Ok(Self {
+            records,
+            capacity,
+            next: 0,
+            dropped: 0,
+            clock,
+            origin,
+        })
+    }
+
+    /// The current instant on the recorder's clock.
+    pub(crate) fn now(&self) -> Instant {
+        match &self.clock {
+            Some(clock_fn) => clock_fn(),
+            None => Instant::now(),
+        }
+    }
+
+    /// Records `span` from `started` until now.
+    pub(crate) fn span(&mut self, span: TraceSpan, started: Instant, args: [i64; 2]) {
+        let duration = self.now().saturating_duration_since(started);
+        self.push(TraceRecord {
+            kind: TraceKind::Span {
+                span,
+                duration_us: micros(duration),
+            },
+            at_us: micros(started.saturating_duration_since(self.origin)),
+            args,
+        });
+    }
+
+    /// Records that `event` was raised now, at `frame`.
+    pub(crate) fn event<T: Config>(&mut self, event: &FortressEvent<T>, frame: Frame) {
+        let detail = match event {
+            FortressEvent::WaitRecommendation { skip_frames } => i64::from(*skip_frames),
+            _ => 0,
+        };
+        let at_us = micros(self.now().saturating_duration_since(self.origin));
+        self.push(TraceRecord {
+            kind: TraceKind::Event(event.kind()),
+            at_us,
+            args: [i64::from(frame.as_i32()), detail],
+        });
+    }
+
+    fn push(&mut self, record: TraceRecord) {
+        if self.records.len() < self.capacity {
+            self.records.push(record);
+            return;
+        }
+        if let Some(slot) = self.records.get_mut(self.next) {
+            *slot = record;
+        }
+        self.next = (self.next + 1) % self.capacity;
+        self.dropped = self.dropped.saturating_add(1);
+    }
+
+    /// The records, oldest first.
+    fn iter(&self) -> impl Iterator<Item = &TraceRecord> {
+        let (newer, older) = self.records.split_at(self.next.min(self.records.len()));
+        older.iter().chain(newer)
+    }
+
+    /// The records as a trace-event JSON document.
+    pub(crate) fn export_json(&self) -> String {
+        export_json(self.iter(), self.dropped)
+    }
+}
+
+/// The trace-event JSON document of a session without a recorder.
+pub(crate) fn empty_trace_json() -> String {
+    export_json(std::iter::empty(), 0)
+}
+
+fn export_json<'a>(records: impl Iterator<Item = &'a TraceRecord>, dropped: u64) -> String {
+    let mut json = String::from("{\"traceEvents\":[");
+    for (index, record) in records.enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        // Writing to a `String` cannot fail.
+        let _ = write_record(&mut json, record);
+    }
+    let _ = write!(
+        json,
+        "],\"displayTimeUnit\":\"ms\",\"otherData\":{{\"dropped_events\":{dropped}}}}}"
+    );
+    json
+}
+
+fn write_record(json: &mut String, record: &TraceRecord) -> std::fmt::Result {
+    let [first, second] = record.args;
+    match record.kind {
+        TraceKind::Span { span, duration_us } => {
+            let [first_name, second_name] = span.arg_names();
+            write!(
+                json,
+                "{{\"name\":\"{}\",\"cat\":\"session\",\"ph\":\"X\",\"ts\":{},\"dur\":{duration_us},\"pid\":1,\"tid\":1,\"args\":{{\"{first_name}\":{first}",
+                span.name(),
+                record.at_us,
+            )?;
+            if !second_name.is_empty() {
+                write!(json, ",\"{second_name}\":{second}")?;
+            }
+            json.push_str("}}");
+        },
+        TraceKind::Event(kind) => {
+            write!(
+                json,
+                "{{\"name\":\"{kind:?}\",\"cat\":\"event\",\"ph\":\"i\",\"s\":\"t\",\"ts\":{},\"pid\":1,\"tid\":1,\"args\":{{\"frame\":{first}",
+                record.at_us,
+            )?;
+            if kind == EventKind::WaitRecommendation {
+                write!(json, ",\"skip_frames\":{second}")?;
+            }
+            json.push_str("}}");
+        },
+    }
+    Ok(())
+}
+
+fn micros(duration: web_time::Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use web_time::Duration;
+
+    struct TestConfig;
+
+    impl Config for TestConfig {
+        type Input = u8;
+        type State = u8;
+        type Address = SocketAddr;
+    }
+
+    fn recorder(capacity: usize) -> (TraceRecorder, Arc<Mutex<Instant>>) {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = Arc::clone(&now);
+        let clock: ClockFn = Arc::new(move || *clock_now.lock());
+        (TraceRecorder::new(capacity, Some(clock)).unwrap(), now)
+    }
+
+    #[test]
+    fn spans_and_events_are_written_in_trace_event_format() {
+        let (mut recorder, now) = recorder(8);
+        *now.lock() += Duration::from_micros(100);
+        let started = recorder.now();
+        *now.lock() += Duration::from_micros(250);
+        recorder.span(TraceSpan::Rollback, started, [7, 3]);
+        recorder.event::<TestConfig>(
+            &FortressEvent::WaitRecommendation { skip_frames: 2 },
+            Frame::new(9),
+        );
+        recorder.event::<TestConfig>(
+            &FortressEvent::Synchronized {
+                addr: "127.0.0.1:7000".parse().unwrap(),
+            },
+            Frame::new(0),
+        );
+        assert_eq!(
+            recorder.export_json(),
+            concat!(
+                "{\"traceEvents\":[",
+                "{\"name\":\"rollback\",\"cat\":\"session\",\"ph\":\"X\",\"ts\":100,\"dur\":250,\"pid\":1,\"tid\":1,\"args\":{\"first_incorrect\":7,\"depth\":3}},",
+                "{\"name\":\"WaitRecommendation\",\"cat\":\"event\",\"ph\":\"i\",\"s\":\"t\",\"ts\":350,\"pid\":1,\"tid\":1,\"args\":{\"frame\":9,\"skip_frames\":2}},",
+                "{\"name\":\"Synchronized\",\"cat\":\"event\",\"ph\":\"i\",\"s\":\"t\",\"ts\":350,\"pid\":1,\"tid\":1,\"args\":{\"frame\":0}}",
+                "],\"displayTimeUnit\":\"ms\",\"otherData\":{\"dropped_events\":0}}"
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_the_most_recent_records() {
+        let (mut recorder, now) = recorder(3);
+        for received in 0..5 {
+            let started = recorder.now();
+            *now.lock() += Duration::from_micros(10);
+            recorder.span(TraceSpan::Poll, started, [received, 0]);
+        }
+        let received: Vec<i64> = recorder.iter().map(|record| record.args[0]).collect();
+        assert_eq!(received, vec![2, 3, 4]);
+        assert!(recorder
+            .export_json()
+            .ends_with("\"otherData\":{\"dropped_events\":2}}"));
+    }
+
+    #[test]
+    fn an_empty_trace_is_a_valid_document() {
+        assert_eq!(
+            empty_trace_json(),
+            "{\"traceEvents\":[],\"displayTimeUnit\":\"ms\",\"otherData\":{\"dropped_events\":0}}"
+        );
+    }
+}
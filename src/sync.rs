@@ -40,6 +40,7 @@
 /// When running under loom (`RUSTFLAGS="--cfg loom"`), use loom's types
 #[cfg(loom)]
 pub(crate) mod inner {
+    pub use loom::sync::atomic::{fence, AtomicU64, Ordering};
     pub use loom::sync::Arc;
     pub use loom::sync::Mutex;
     #[allow(unused_imports)] // Used for API consistency
@@ -97,6 +98,7 @@ pub(crate) mod inner {
     pub use parking_lot::Mutex;
     #[allow(unused_imports)] // Used for loom compatibility abstraction
     pub use parking_lot::MutexGuard;
+    pub use std::sync::atomic::{fence, AtomicU64, Ordering};
     #[cfg_attr(kani, allow(unused_imports))]
     pub use std::sync::Arc;
     #[allow(unused_imports)] // Used for loom compatibility abstraction
@@ -22,9 +22,18 @@
 
 use crate::frame_info::GameState;
 use crate::report_violation;
+use crate::sync_layer::SavedFrameInfo;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
 use crate::Frame;
 
+/// How a cell holds the saved state: behind an `Arc`, so
+/// [`GameStateCell::load_shared`] can hand it out without cloning it. Kani
+/// keeps the bare value (see the import comment above).
+#[cfg(not(kani))]
+pub(crate) type StoredState<T> = std::sync::Arc<T>;
+#[cfg(kani)]
+pub(crate) type StoredState<T> = T;
+
 /// A thread-safe cell for saving and loading game states during rollback.
 ///
 /// `GameStateCell` wraps your game state in an [`Arc<Mutex>`], allowing it to be shared
@@ -62,7 +71,7 @@
 /// [`FortressRequest::SaveGameState`]: crate::FortressRequest::SaveGameState
 /// [`FortressRequest::LoadGameState`]: crate::FortressRequest::LoadGameState
 #[cfg(not(kani))]
-pub struct GameStateCell<T>(pub(crate) Arc<Mutex<GameState<T>>>);
+pub struct GameStateCell<T>(pub(crate) Arc<Mutex<GameState<StoredState<T>>>>);
 
 /// Kani-only representation: a non-atomic `Rc<RefCell<..>>`. See the module-level
 /// import comment for why. The public API is identical to the production struct.
@@ -123,8 +132,46 @@ impl<T> GameStateCell<T> {
     /// ```
     ///
     /// [`Frame::NULL`]: crate::Frame::NULL
-    #[cfg(all(not(loom), not(kani)))]
     pub fn save(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> bool {
+        self.store(frame, data, checksum)
+    }
+
+    /// Starts a save of `frame` whose state is staged outside the cell's lock.
+    ///
+    /// Write the state into the returned [`SaveGuard`], then
+    /// [`commit`](SaveGuard::commit) it: only the final swap runs under the
+    /// lock, and the replaced state is dropped after the lock is released. The
+    /// cell keeps its previous state until the commit, and dropping the guard
+    /// uncommitted leaves it untouched. The guard owns a handle to the cell,
+    /// so it can be moved to a worker thread (with the `sync-send` feature).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fortress_rollback::{Frame, GameStateCell};
+    ///
+    /// let cell = GameStateCell::<Vec<u8>>::default();
+    /// let mut guard = cell.begin_save(Frame::new(3));
+    /// guard.write(vec![1, 2, 3]);
+    /// assert!(cell.load().is_none()); // not committed yet
+    ///
+    /// assert!(guard.commit(Some(6)));
+    /// assert_eq!(cell.load(), Some(vec![1, 2, 3]));
+    /// assert_eq!(cell.checksum(), Some(6));
+    /// ```
+    pub fn begin_save(&self, frame: Frame) -> SaveGuard<T> {
+        SaveGuard {
+            cell: self.clone(),
+            frame,
+            data: None,
+        }
+    }
+
+    /// Swaps `data` into the cell. The `Arc` is allocated before locking and
+    /// the replaced state dropped after unlocking, so the critical section is
+    /// three field writes however large the state is.
+    #[cfg(all(not(loom), not(kani)))]
+    fn store(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> bool {
         if frame.is_null() {
             report_violation!(
                 ViolationSeverity::Error,
@@ -133,18 +180,20 @@ pub fn save(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> boo
             );
             return false;
         }
-        let mut state = self.0.lock();
-        state.frame = frame;
-        state.data = data;
-        state.checksum = checksum;
+        let data = data.map(StoredState::new);
+        let replaced = {
+            let mut state = self.0.lock();
+            state.frame = frame;
+            state.checksum = checksum;
+            std::mem::replace(&mut state.data, data)
+        };
+        drop(replaced);
         true
     }
 
-    /// Saves a game state into the cell (Kani version).
-    ///
-    /// See the production version for full documentation and examples.
-    #[cfg(kani)]
-    pub fn save(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> bool {
+    /// Loom version of `store`.
+    #[cfg(loom)]
+    fn store(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> bool {
         if frame.is_null() {
             report_violation!(
                 ViolationSeverity::Error,
@@ -153,22 +202,20 @@ pub fn save(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> boo
             );
             return false;
         }
-        let mut state = self.0.borrow_mut();
-        state.frame = frame;
-        state.data = data;
-        state.checksum = checksum;
+        let data = data.map(StoredState::new);
+        let replaced = {
+            let mut state = self.0.lock().unwrap();
+            state.frame = frame;
+            state.checksum = checksum;
+            std::mem::replace(&mut state.data, data)
+        };
+        drop(replaced);
         true
     }
 
-    /// Saves a game state into the cell (loom version).
-    ///
-    /// See the non-loom version for full documentation and examples.
-    ///
-    /// # Returns
-    ///
-    /// Returns `true` if the save succeeded, `false` if the frame was null.
-    #[cfg(loom)]
-    pub fn save(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> bool {
+    /// Kani version of `store`.
+    #[cfg(kani)]
+    fn store(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> bool {
         if frame.is_null() {
             report_violation!(
                 ViolationSeverity::Error,
@@ -177,7 +224,7 @@ pub fn save(&self, frame: Frame, data: Option<T>, checksum: Option<u128>) -> boo
             );
             return false;
         }
-        let mut state = self.0.lock().unwrap();
+        let mut state = self.0.borrow_mut();
         state.frame = frame;
         state.data = data;
         state.checksum = checksum;
@@ -349,6 +396,120 @@ pub fn checksum(&self) -> Option<u128> {
     pub fn checksum(&self) -> Option<u128> {
         self.0.borrow().checksum
     }
+
+    /// The cell's frame, whether it holds data, and its checksum, read under
+    /// one lock without touching the state itself.
+    #[cfg(all(not(loom), not(kani)))]
+    pub(crate) fn saved_frame_info(&self) -> SavedFrameInfo {
+        let state = self.0.lock();
+        SavedFrameInfo {
+            frame: state.frame,
+            has_state: state.data.is_some(),
+            checksum: state.checksum,
+        }
+    }
+
+    #[cfg(loom)]
+    /// Reads the cell's metadata (loom version).
+    pub(crate) fn saved_frame_info(&self) -> SavedFrameInfo {
+        let state = self.0.lock().unwrap();
+        SavedFrameInfo {
+            frame: state.frame,
+            has_state: state.data.is_some(),
+            checksum: state.checksum,
+        }
+    }
+
+    #[cfg(kani)]
+    /// Reads the cell's metadata (Kani version).
+    pub(crate) fn saved_frame_info(&self) -> SavedFrameInfo {
+        let state = self.0.borrow();
+        SavedFrameInfo {
+            frame: state.frame,
+            has_state: state.data.is_some(),
+            checksum: state.checksum,
+        }
+    }
+
+    /// Takes the shared state out of the cell if it still holds `frame`,
+    /// leaving the frame and checksum in place. Diff saving keeps the state
+    /// elsewhere until a load needs it back.
+    #[cfg(all(not(loom), not(kani)))]
+    pub(crate) fn take_shared(&self, frame: Frame) -> Option<StoredState<T>> {
+        let mut state = self.0.lock();
+        if state.frame != frame {
+            return None;
+        }
+        state.data.take()
+    }
+
+    #[cfg(loom)]
+    /// Takes the shared state out of the cell (loom version).
+    pub(crate) fn take_shared(&self, frame: Frame) -> Option<StoredState<T>> {
+        let mut state = self.0.lock().unwrap();
+        if state.frame != frame {
+            return None;
+        }
+        state.data.take()
+    }
+
+    /// Shares the state in the cell if it still holds `frame`.
+    #[cfg(all(not(loom), not(kani)))]
+    pub(crate) fn peek_shared(&self, frame: Frame) -> Option<StoredState<T>> {
+        let state = self.0.lock();
+        (state.frame == frame).then(|| state.data.clone()).flatten()
+    }
+
+    #[cfg(loom)]
+    /// Shares the state in the cell (loom version).
+    pub(crate) fn peek_shared(&self, frame: Frame) -> Option<StoredState<T>> {
+        let state = self.0.lock().unwrap();
+        (state.frame == frame).then(|| state.data.clone()).flatten()
+    }
+
+    /// Puts a reconstructed state back into the cell if it still holds
+    /// `frame`. Returns whether it did.
+    #[cfg(all(not(loom), not(kani)))]
+    pub(crate) fn restore_shared(&self, frame: Frame, data: StoredState<T>) -> bool {
+        let mut state = self.0.lock();
+        if state.frame != frame {
+            return false;
+        }
+        state.data = Some(data);
+        true
+    }
+
+    #[cfg(loom)]
+    /// Puts a reconstructed state back into the cell (loom version).
+    pub(crate) fn restore_shared(&self, frame: Frame, data: StoredState<T>) -> bool {
+        let mut state = self.0.lock().unwrap();
+        if state.frame != frame {
+            return false;
+        }
+        state.data = Some(data);
+        true
+    }
+
+    /// Empties the cell back to its never-saved state, so it no longer
+    /// answers for any frame. The state is dropped after unlocking.
+    #[cfg(all(not(loom), not(kani)))]
+    pub(crate) fn clear(&self) {
+        let cleared = std::mem::take(&mut *self.0.lock());
+        drop(cleared);
+    }
+
+    #[cfg(loom)]
+    /// Empties the cell (loom version).
+    pub(crate) fn clear(&self) {
+        let cleared = std::mem::take(&mut *self.0.lock().unwrap());
+        drop(cleared);
+    }
+
+    #[cfg(kani)]
+    /// Empties the cell (Kani version).
+    pub(crate) fn clear(&self) {
+        *self.0.borrow_mut() = GameState::default();
+    }
 }
 
 impl<T: Clone> GameStateCell<T> {
@@ -415,7 +576,7 @@ pub fn load(&self) -> Option<T> {
     #[cfg(loom)]
     pub fn load(&self) -> Option<T> {
         let guard = self.0.lock().unwrap();
-        guard.data.clone()
+        guard.data.as_deref().cloned()
     }
 
     /// Loads a previously saved state, returning an error if none exists.
@@ -484,6 +645,90 @@ pub fn load_or_err(&self, frame: Frame) -> Result<T, crate::FortressError> {
     }
 }
 
+#[cfg(not(kani))]
+impl<T: Send + Sync> GameStateCell<T> {
+    /// Loads a previously saved game state as a shared handle, without cloning it.
+    ///
+    /// Only the `Arc` is cloned under the lock, so this is constant-time
+    /// however large the state is. The handle stays valid after the cell is
+    /// overwritten by a later save. While it is alive,
+    /// [`GameStateAccessor::as_mut_dangerous`] on this state returns `None`.
+    ///
+    /// Returns `None` if no state has been saved, or if `None` was explicitly saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fortress_rollback::{Frame, GameStateCell};
+    ///
+    /// let cell = GameStateCell::<Vec<u8>>::default();
+    /// cell.save(Frame::new(1), Some(vec![7; 4]), None);
+    ///
+    /// let shared = cell.load_shared().expect("state was just saved");
+    /// cell.save(Frame::new(2), Some(vec![9; 4]), None);
+    /// assert_eq!(*shared, vec![7; 4]); // unaffected by the later save
+    /// ```
+    #[cfg(not(loom))]
+    #[must_use]
+    pub fn load_shared(&self) -> Option<std::sync::Arc<T>> {
+        self.0.lock().data.clone()
+    }
+
+    /// Loom version of [`load_shared()`](Self::load_shared).
+    #[cfg(loom)]
+    pub fn load_shared(&self) -> Option<std::sync::Arc<T>> {
+        self.0.lock().unwrap().data.clone()
+    }
+}
+
+/// A save in progress, created by [`GameStateCell::begin_save`].
+///
+/// The guard holds the state being written outside the cell's lock;
+/// [`commit()`](Self::commit) swaps it in. Dropping the guard without
+/// committing discards the staged state and leaves the cell unchanged.
+#[must_use = "a SaveGuard does nothing until it is committed"]
+pub struct SaveGuard<T> {
+    cell: GameStateCell<T>,
+    frame: Frame,
+    data: Option<T>,
+}
+
+impl<T> SaveGuard<T> {
+    /// The frame this save is for.
+    #[must_use]
+    pub fn frame(&self) -> Frame {
+        self.frame
+    }
+
+    /// Stages `data` as the state to commit, replacing anything staged before.
+    pub fn write(&mut self, data: T) {
+        self.data = Some(data);
+    }
+
+    /// Returns the staged state, if any, for in-place edits before committing.
+    pub fn staged_mut(&mut self) -> Option<&mut T> {
+        self.data.as_mut()
+    }
+
+    /// Commits the staged state and `checksum` to the cell.
+    ///
+    /// Committing with nothing staged clears the cell, like
+    /// [`GameStateCell::save`] with `None`. Returns `false`, leaving the cell
+    /// unchanged, if the save's frame is [`Frame::NULL`].
+    pub fn commit(self, checksum: Option<u128>) -> bool {
+        self.cell.store(self.frame, self.data, checksum)
+    }
+}
+
+impl<T> std::fmt::Debug for SaveGuard<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaveGuard")
+            .field("frame", &self.frame)
+            .field("staged", &self.data.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Creates an empty `GameStateCell` with no saved state.
 ///
 /// The initial state has [`Frame::NULL`] and no data or checksum.
@@ -573,7 +818,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 /// Note: Under loom testing, this type is not available as loom doesn't support `MappedMutexGuard`.
 /// Use [`GameStateCell::load()`] instead which requires `T: Clone`.
 #[cfg(all(not(loom), not(kani)))]
-pub struct GameStateAccessor<'c, T>(MappedMutexGuard<'c, T>);
+pub struct GameStateAccessor<'c, T>(MappedMutexGuard<'c, StoredState<T>>);
 
 /// Placeholder type under loom - the actual accessor cannot be created.
 #[cfg(loom)]
@@ -654,7 +899,7 @@ impl<T> GameStateAccessor<'_, T> {
     /// // SAFE: Updating debug/telemetry counters that don't affect gameplay
     /// // We just saved the state, so we know it exists
     /// let mut accessor = cell.data().expect("state was just saved");
-    /// let state = accessor.as_mut_dangerous();
+    /// let state = accessor.as_mut_dangerous().expect("state is not shared");
     /// state.debug_load_count += 1;
     /// state.last_accessed_timestamp = 1234567890;
     /// // player_x, player_y, health remain unchanged
@@ -665,22 +910,25 @@ impl<T> GameStateAccessor<'_, T> {
     /// ```ignore
     /// // ❌ WRONG: This WILL cause desyncs!
     /// let mut accessor = cell.data().expect("state exists");
-    /// let state = accessor.as_mut_dangerous();
+    /// let state = accessor.as_mut_dangerous().expect("state is not shared");
     /// state.player_x += 10;  // NEVER modify gameplay state!
     /// state.health = 50;      // This breaks determinism!
     /// ```
     ///
     /// The correct approach for gameplay changes is to modify your current game state
     /// during `AdvanceFrame`, not the saved states in cells.
-    pub fn as_mut_dangerous(&mut self) -> &mut T {
-        &mut self.0
+    ///
+    /// Returns `None` while a handle from [`GameStateCell::load_shared`] to this
+    /// state is still alive: a shared state is never mutated.
+    pub fn as_mut_dangerous(&mut self) -> Option<&mut T> {
+        StoredState::get_mut(&mut self.0)
     }
 }
 
 #[cfg(loom)]
 impl<'c, T> GameStateAccessor<'c, T> {
     /// Under loom, this method is not available.
-    pub fn as_mut_dangerous(&mut self) -> &mut T {
+    pub fn as_mut_dangerous(&mut self) -> Option<&mut T> {
         unreachable!(
             "GameStateAccessor::as_mut_dangerous called under loom - this should not happen"
         )
@@ -691,8 +939,8 @@ pub fn as_mut_dangerous(&mut self) -> &mut T {
 impl<T> GameStateAccessor<'_, T> {
     /// Kani version of [`as_mut_dangerous()`]. See the production version for
     /// the full safety contract.
-    pub fn as_mut_dangerous(&mut self) -> &mut T {
-        &mut self.0
+    pub fn as_mut_dangerous(&mut self) -> Option<&mut T> {
+        Some(&mut self.0)
     }
 }
 
@@ -943,7 +1191,7 @@ fn game_state_accessor_as_mut_dangerous() {
         {
             let mut accessor = cell.data().unwrap();
             // Use dangerous mutable access
-            let data = accessor.as_mut_dangerous();
+            let data = accessor.as_mut_dangerous().unwrap();
             data.push(4);
         }
 
@@ -1081,4 +1329,96 @@ fn load_or_err_succeeds_after_save() {
         let result = cell.load_or_err(frame);
         assert_eq!(result.unwrap(), 42);
     }
+
+    // ==========================================
+    // Staged saves and shared loads
+    // ==========================================
+
+    #[test]
+    fn begin_save_keeps_previous_state_until_commit() {
+        let cell = GameStateCell::<Vec<u8>>::default();
+        cell.save(Frame::new(1), Some(vec![1]), Some(1));
+
+        let mut guard = cell.begin_save(Frame::new(2));
+        guard.write(vec![2]);
+        guard.staged_mut().unwrap().push(3);
+        assert_eq!(guard.frame(), Frame::new(2));
+        assert_eq!(cell.frame(), Frame::new(1));
+        assert_eq!(cell.load(), Some(vec![1]));
+
+        assert!(guard.commit(Some(5)));
+        assert_eq!(cell.frame(), Frame::new(2));
+        assert_eq!(cell.checksum(), Some(5));
+        assert_eq!(cell.load(), Some(vec![2, 3]));
+    }
+
+    #[test]
+    fn dropped_save_guard_leaves_cell_unchanged() {
+        let cell = GameStateCell::<u32>::default();
+        cell.save(Frame::new(1), Some(10), Some(1));
+        let mut guard = cell.begin_save(Frame::new(2));
+        guard.write(20);
+        drop(guard);
+        assert_eq!(cell.frame(), Frame::new(1));
+        assert_eq!(cell.load(), Some(10));
+    }
+
+    #[test]
+    fn commit_with_null_frame_is_rejected() {
+        let cell = GameStateCell::<u32>::default();
+        cell.save(Frame::new(1), Some(10), None);
+        let mut guard = cell.begin_save(Frame::NULL);
+        guard.write(20);
+        assert!(!guard.commit(None));
+        assert_eq!(cell.load(), Some(10));
+    }
+
+    #[test]
+    fn commit_without_staged_state_clears_the_cell() {
+        let cell = GameStateCell::<u32>::default();
+        cell.save(Frame::new(1), Some(10), None);
+        assert!(cell.begin_save(Frame::new(2)).commit(None));
+        assert_eq!(cell.frame(), Frame::new(2));
+        assert!(cell.load().is_none());
+    }
+
+    #[test]
+    fn load_shared_returns_the_saved_allocation() {
+        let cell = GameStateCell::<Vec<u8>>::default();
+        assert!(cell.load_shared().is_none());
+        cell.save(Frame::new(1), Some(vec![1, 2, 3]), None);
+
+        let first = cell.load_shared().unwrap();
+        let second = cell.load_shared().unwrap();
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+
+        cell.save(Frame::new(2), Some(vec![4]), None);
+        assert_eq!(*first, vec![1, 2, 3]);
+        assert_eq!(*cell.load_shared().unwrap(), vec![4]);
+    }
+
+    #[test]
+    fn as_mut_dangerous_is_refused_while_state_is_shared() {
+        let cell = GameStateCell::<Vec<u8>>::default();
+        cell.save(Frame::new(1), Some(vec![1]), None);
+        let shared = cell.load_shared().unwrap();
+        assert!(cell.data().unwrap().as_mut_dangerous().is_none());
+
+        drop(shared);
+        cell.data().unwrap().as_mut_dangerous().unwrap().push(2);
+        assert_eq!(cell.load(), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn save_guard_commits_from_another_thread() {
+        let cell = GameStateCell::<Vec<u8>>::default();
+        let mut guard = cell.begin_save(Frame::new(4));
+        std::thread::spawn(move || {
+            guard.write(vec![9; 16]);
+            assert!(guard.commit(None));
+        })
+        .join()
+        .unwrap();
+        assert_eq!(cell.load(), Some(vec![9; 16]));
+    }
 }
@@ -103,27 +103,28 @@
 
 mod game_state_cell;
 mod saved_states;
+mod state_diff;
 
-pub use game_state_cell::{GameStateAccessor, GameStateCell};
-pub use saved_states::SavedStates;
+pub(crate) use game_state_cell::StoredState;
+pub use game_state_cell::{GameStateAccessor, GameStateCell, SaveGuard};
+pub use saved_states::{SavedFrameInfo, SavedStates};
+#[cfg(not(kani))]
+use state_diff::DiffSave;
+pub use state_diff::StateDiffCodec;
 
 use crate::frame_info::PlayerInput;
 use crate::input_queue::{InputQueue, RetainedHistoryError, RetainedInputRange};
 use crate::network::messages::ConnectionStatus;
 use crate::proof_vec::ProofVec;
+use crate::safe_frame_sub;
 use crate::sessions::config::SaveMode;
+use crate::sessions::rollback_cause::RollbackCause;
 use crate::telemetry::{InvariantChecker, InvariantViolation, ViolationKind, ViolationSeverity};
-use crate::{report_violation, safe_frame_add};
-// `safe_frame_sub!` is invoked from the confirmed-frame discard pass (compiled
-// out under Kani; see `set_last_confirmed_frame`) and, under the `hot-join`
-// feature, from `seek_to_frame`. Import it whenever either consumer is present.
-#[cfg(any(not(kani), feature = "hot-join"))]
-use crate::safe_frame_sub;
-#[cfg(feature = "hot-join")]
 use crate::InvalidRequestKind;
+use crate::{report_violation, safe_frame_add};
 use crate::{
     Config, FortressError, FortressRequest, Frame, IndexOutOfBounds, InputStatus, InputVec,
-    InternalErrorKind, InvalidFrameReason, PlayerHandle,
+    InternalErrorKind, InvalidFrameReason, LocalInputRejectReason, PlayerHandle,
 };
 
 /// A retained-history transaction failed before commit.
@@ -205,6 +206,13 @@ pub struct SyncLayer<T>
     /// (nothing arms it), so their behavior is byte-identical.
     #[cfg(feature = "hot-join")]
     reactivation_floors: Vec<Option<ReactivationFloor<T::Input>>>,
+    /// Keyframe and diff bookkeeping for the saved states, when a
+    /// [`StateDiffCodec`] is registered; see [`Self::enable_state_diffs`].
+    #[cfg(not(kani))]
+    diff_save: Option<DiffSave<T::State>>,
+    /// Frames before the current one whose inputs stay queued even once
+    /// confirmed; see [`Self::retain_inputs`].
+    input_retention: usize,
 }
 
 /// Pre-activation serving floor for a reactivated slot (N-peer hot-join).
@@ -327,6 +335,9 @@ pub fn with_queue_length(
                     input_queues: ProofVec::new(),
                     #[cfg(feature = "hot-join")]
                     reactivation_floors: Vec::new(),
+                    #[cfg(not(kani))]
+                    diff_save: None,
+                    input_retention: 0,
                 }
             },
         }
@@ -362,9 +373,49 @@ pub(crate) fn try_with_queue_length(
             // at session construction (mirrors `input_queues` above).
             #[cfg(feature = "hot-join")]
             reactivation_floors: (0..num_players).map(|_| None).collect(),
+            #[cfg(not(kani))]
+            diff_save: None,
+            input_retention: 0,
         })
     }
 
+    /// Keeps the saved states as keyframes and diffs encoded by `codec`; see
+    /// [`StateDiffCodec`].
+    #[cfg(not(kani))]
+    pub(crate) fn enable_state_diffs(
+        &mut self,
+        codec: std::sync::Arc<dyn StateDiffCodec<T::State>>,
+    ) {
+        self.diff_save = Some(DiffSave::new(codec, self.saved_states.states.len()));
+    }
+
+    /// Kani builds keep every saved state in full.
+    #[cfg(kani)]
+    pub(crate) fn enable_state_diffs(
+        &mut self,
+        _codec: std::sync::Arc<dyn StateDiffCodec<T::State>>,
+    ) {
+    }
+
+    /// Whether saved states are kept as keyframes and diffs.
+    #[cfg(not(kani))]
+    pub(crate) fn diff_saves(&self) -> bool {
+        self.diff_save.is_some()
+    }
+
+    /// Kani builds keep every saved state in full.
+    #[cfg(kani)]
+    pub(crate) fn diff_saves(&self) -> bool {
+        false
+    }
+
+    /// Keeps the inputs of the `frames` frames before the current one queued
+    /// after [`Self::set_last_confirmed_frame`] confirms them, so a sync test
+    /// can load one of those frames and resimulate from it.
+    pub(crate) fn retain_inputs(&mut self, frames: usize) {
+        self.input_retention = frames;
+    }
+
     /// Returns the current simulation frame.
     ///
     /// # Note
@@ -410,6 +461,10 @@ fn rollback_window_floor(&self) -> Frame {
     /// This method is exposed via `__internal` for testing. It is not part of the stable public API.
     pub fn save_current_state(&mut self) -> FortressRequest<T> {
         self.last_saved_frame = self.current_frame;
+        #[cfg(not(kani))]
+        if let Some(diff_save) = &mut self.diff_save {
+            diff_save.before_save(&self.saved_states, self.current_frame);
+        }
         // Debug assertion to catch invariant violations during development.
         // Every current_frame mutation path validates its target first, so this
         // should never fail.
@@ -455,8 +510,8 @@ pub fn save_current_state(&mut self) -> FortressRequest<T> {
     ///     queue's `max_frame_delay()`.
     ///   - [`FortressError::InvalidRequestStructured`] with
     ///     [`InvalidRequestKind::InputDelayDecreaseUnsupported`] if `delay`
-    ///     is strictly less than the current frame delay and inputs have
-    ///     already been added (mid-session decreases are unsupported).
+    ///     is strictly less than the current frame delay and an input the
+    ///     decrease would drop was already requested.
     ///   - [`FortressError::InternalErrorStructured`] with
     ///     [`InternalErrorKind::IndexOutOfBounds`] (name `"inputs"`) if the
     ///     queue's most-recent input slot cannot be located while computing
@@ -902,7 +957,7 @@ pub(crate) fn reactivate_player_at_frame(
     ///   The snapshot's state is injected into the saved-states cell (and
     ///   `last_saved_frame` set) by a later chunk; this method does not set it.
     /// - Every input queue has been repositioned via
-    ///   [`InputQueue::reset_to_frame`] to accept inputs from `frame` onward.
+    ///   `InputQueue::reset_to_frame` to accept inputs from `frame` onward.
     /// - [`InvariantChecker::check_invariants`] still holds
     ///   (`last_confirmed_frame <= current_frame`, `last_saved_frame <=
     ///   current_frame`, queue count unchanged).
@@ -922,10 +977,11 @@ pub(crate) fn reactivate_player_at_frame(
     ///
     /// Returns [`InvalidRequestKind::Custom`] with `"seek_to_frame requires a
     /// fresh SyncLayer"` for any other non-fresh layer state.
-    // dead_code: consumed by chunk 5's joiner orchestration.
-    #[cfg(feature = "hot-join")]
-    #[allow(dead_code)]
-    pub(crate) fn seek_to_frame(&mut self, frame: Frame) -> Result<(), FortressError> {
+    ///
+    /// # Note
+    /// This method is exposed via `__internal` for testing (e.g. running a layer
+    /// up to the session frame limit). It is not part of the stable public API.
+    pub fn seek_to_frame(&mut self, frame: Frame) -> Result<(), FortressError> {
         if frame.as_i32() < 0 {
             return Err(FortressError::InvalidFrameStructured {
                 frame,
@@ -949,7 +1005,9 @@ pub(crate) fn seek_to_frame(&mut self, frame: Frame) -> Result<(), FortressError
         // The freshness precheck above keeps both frame-ordering invariants: the
         // new last_confirmed_frame is frame - 1 (or NULL), and the unchanged
         // last_saved_frame is NULL until snapshot injection. This debug
-        // assert surfaces a regression in development; production is unaffected.
+        // assert surfaces a regression in development builds with `telemetry`;
+        // production is unaffected.
+        #[cfg(feature = "telemetry")]
         debug_assert!(
             self.check_invariants().is_ok(),
             "seek_to_frame must preserve SyncLayer invariants"
@@ -958,7 +1016,6 @@ pub(crate) fn seek_to_frame(&mut self, frame: Frame) -> Result<(), FortressError
         Ok(())
     }
 
-    #[cfg(feature = "hot-join")]
     fn validate_fresh_seek_target(&self, frame: Frame) -> Result<(), FortressError> {
         if !self.last_saved_frame.is_null() && self.last_saved_frame > frame {
             report_violation!(
@@ -1001,7 +1058,7 @@ fn validate_fresh_seek_target(&self, frame: Frame) -> Result<(), FortressError>
     }
 
     /// Reads the saved state and checksum at `frame` for a hot-join snapshot
-    /// (host side).
+    /// (host side) or a [`StateSlot`](crate::StateSlot).
     ///
     /// Returns `Some((state, checksum))` only when the circular-buffer slot for
     /// `frame` actually holds `frame` and contains data: [`SavedStates`] indexes
@@ -1012,10 +1069,6 @@ fn validate_fresh_seek_target(&self, frame: Frame) -> Result<(), FortressError>
     /// (`cell.load()` is `None`), or `frame` is invalid for `get_cell`.
     ///
     /// This is a pure read: no sync-layer state is mutated.
-    // dead_code: consumed by chunk 5's host snapshot orchestration; only the
-    // sync-layer accessor lands in this chunk.
-    #[cfg(feature = "hot-join")]
-    #[allow(dead_code)]
     pub(crate) fn capture_snapshot_state(&self, frame: Frame) -> Option<(T::State, Option<u128>)>
     where
         // `cell.load()` clones the saved state out of the cell. `Config::State`
@@ -1027,7 +1080,16 @@ pub(crate) fn capture_snapshot_state(&self, frame: Frame) -> Option<(T::State, O
         if cell.frame() != frame {
             return None;
         }
+        #[cfg(kani)]
         let state = cell.load()?;
+        #[cfg(not(kani))]
+        let state = match &self.diff_save {
+            Some(diff_save) => {
+                let state = diff_save.reconstruct(&self.saved_states, frame)?;
+                T::State::clone(&state)
+            },
+            None => cell.load()?,
+        };
         Some((state, cell.checksum()))
     }
 
@@ -1078,7 +1140,64 @@ pub(crate) fn inject_snapshot_state(
             });
         }
 
+        self.store_current_state(state, checksum)
+    }
+
+    /// Moves the layer to `frame` with `state` as its only saved state and
+    /// returns the [`FortressRequest::LoadGameState`] that restores it, for a
+    /// user-driven [`StateSlot`](crate::StateSlot) restore.
+    ///
+    /// Unlike [`Self::seek_to_frame`] this works on a running layer, in either
+    /// direction: every saved state is dropped, `frame - 1` becomes the last
+    /// confirmed frame and every input queue is restarted with
+    /// [`InputQueue::restart_at_frame`] to accept inputs from `frame` on.
+    ///
+    /// # Errors
+    /// Returns [`FortressError::InvalidFrameStructured`] with
+    /// [`InvalidFrameReason::MustBeNonNegative`] if `frame` is negative or
+    /// [`Frame::NULL`], leaving the layer unchanged, and the error of
+    /// [`InputQueue::restart_at_frame`] if a queue fails to restart.
+    pub(crate) fn restore_state(
+        &mut self,
+        frame: Frame,
+        state: T::State,
+        checksum: Option<u128>,
+    ) -> Result<FortressRequest<T>, FortressError> {
+        if frame.as_i32() < 0 {
+            return Err(FortressError::InvalidFrameStructured {
+                frame,
+                reason: InvalidFrameReason::MustBeNonNegative,
+            });
+        }
+        for cell in self.saved_states.states.iter() {
+            cell.clear();
+        }
+        #[cfg(not(kani))]
+        if let Some(diff_save) = &mut self.diff_save {
+            diff_save.reset();
+        }
+        self.current_frame = frame;
+        self.last_confirmed_frame = safe_frame_sub!(frame, 1, "SyncLayer::restore_state confirmed");
+        self.last_saved_frame = Frame::NULL;
+        for queue in self.input_queues.iter_mut() {
+            queue.restart_at_frame(frame)?;
+        }
+        self.store_current_state(state, checksum)
+    }
+
+    /// Saves `state` into the cell of the current frame on the layer's behalf
+    /// and returns the request loading it.
+    fn store_current_state(
+        &mut self,
+        state: T::State,
+        checksum: Option<u128>,
+    ) -> Result<FortressRequest<T>, FortressError> {
+        let frame = self.current_frame;
         let cell = self.saved_states.get_cell(frame)?;
+        #[cfg(not(kani))]
+        if let Some(diff_save) = &mut self.diff_save {
+            diff_save.before_save(&self.saved_states, frame);
+        }
         // `GameStateCell::save` only returns `false` for a `Frame::NULL`, which
         // `get_cell` above already rejects (it errors on any frame < 0). Propagate
         // the result anyway so this stays correct if either guard ever changes,
@@ -1091,9 +1210,10 @@ pub(crate) fn inject_snapshot_state(
             });
         }
         self.last_saved_frame = frame;
+        #[cfg(feature = "telemetry")]
         debug_assert!(
             self.check_invariants().is_ok(),
-            "inject_snapshot_state must preserve SyncLayer invariants"
+            "storing a loaded state must preserve SyncLayer invariants"
         );
         Ok(FortressRequest::LoadGameState { cell, frame })
     }
@@ -1281,6 +1401,19 @@ pub(crate) fn newest_saved_frame_in_range(
         best
     }
 
+    /// Drops the saved state of `frame`, if its cell still holds it, so no
+    /// later rollback loads it. Used when a rollback resimulates past the frame
+    /// without saving it again, leaving the state from before the rollback stale.
+    pub(crate) fn discard_saved_state(&mut self, frame: Frame) {
+        if let Some(cell) = self.saved_state_by_frame(frame) {
+            #[cfg(not(kani))]
+            if let Some(diff_save) = &mut self.diff_save {
+                diff_save.before_discard(&self.saved_states, frame);
+            }
+            cell.clear();
+        }
+    }
+
     /// Loads the gamestate indicated by `frame_to_load`.
     ///
     /// # Errors
@@ -1338,6 +1471,12 @@ pub fn load_frame(
                 },
             });
         }
+        // A diff-encoded cell is rebuilt to its full state before the user
+        // loads it.
+        #[cfg(not(kani))]
+        if let Some(diff_save) = &mut self.diff_save {
+            diff_save.before_load(&self.saved_states, frame_to_load);
+        }
         self.current_frame = frame_to_load;
         // Update last_saved_frame to maintain invariant: last_saved_frame <= current_frame
         // After rollback, we're working from the loaded state, which is now our reference point
@@ -1349,15 +1488,18 @@ pub fn load_frame(
         })
     }
 
-    /// Adds local input to the corresponding input queue. Checks if the prediction threshold has been reached. Returns the frame number where the input is actually added to.
-    /// This number will only be different if the input delay was set to a number higher than 0.
+    /// Adds local input to the corresponding input queue. Returns the frame number where the
+    /// input is actually added to. This number will only be different if the input delay was set
+    /// to a number higher than 0.
     ///
-    /// Returns `Frame::NULL` if the input frame doesn't match the current frame.
+    /// # Errors
+    /// Returns [`FortressError::LocalInputRejected`] if the input frame doesn't match the current
+    /// frame or the player's input queue refuses the input.
     pub(crate) fn add_local_input(
         &mut self,
         player_handle: PlayerHandle,
         input: PlayerInput<T::Input>,
-    ) -> Frame {
+    ) -> Result<Frame, FortressError> {
         // The input provided should match the current frame, we account for input delay later
         if input.frame != self.current_frame {
             report_violation!(
@@ -1367,23 +1509,86 @@ pub(crate) fn add_local_input(
                 input.frame,
                 self.current_frame
             );
-            return Frame::NULL;
+            return Err(FortressError::LocalInputRejected {
+                frame: input.frame,
+                reason: LocalInputRejectReason::FrameMismatch {
+                    current_frame: self.current_frame,
+                },
+            });
         }
         let reclaim_before = self.rollback_window_floor();
         let queue_count = self.input_queues.len();
-        match self.input_queues.get_mut(player_handle.as_usize()) {
-            Some(queue) => queue.add_input_reclaiming_before(input, reclaim_before),
-            None => {
-                report_violation!(
-                    ViolationSeverity::Error,
-                    ViolationKind::InputQueue,
-                    "add_local_input: missing input_queues entry for player handle {} (input_queues.len()={})",
-                    player_handle.as_usize(),
-                    queue_count
-                );
-                Frame::NULL
-            },
+        let Some(queue) = self.input_queues.get_mut(player_handle.as_usize()) else {
+            report_violation!(
+                ViolationSeverity::Error,
+                ViolationKind::InputQueue,
+                "add_local_input: missing input_queues entry for player handle {} (input_queues.len()={})",
+                player_handle.as_usize(),
+                queue_count
+            );
+            return Err(FortressError::LocalInputRejected {
+                frame: input.frame,
+                reason: LocalInputRejectReason::MissingQueue,
+            });
+        };
+        let last_added_frame = queue.last_added_frame();
+        let actual_frame = queue.add_input_reclaiming_before(input, reclaim_before);
+        if actual_frame.is_null() {
+            return Err(FortressError::LocalInputRejected {
+                frame: input.frame,
+                reason: LocalInputRejectReason::QueueRejected { last_added_frame },
+            });
+        }
+        Ok(actual_frame)
+    }
+
+    /// Whether the player's input queue already holds the local input for `frame`, because an
+    /// earlier advance queued it but could not simulate the frame.
+    pub(crate) fn local_input_queued(&self, player_handle: PlayerHandle, frame: Frame) -> bool {
+        self.input_queues
+            .get(player_handle.as_usize())
+            .is_some_and(|queue| {
+                let last_added_frame = queue.last_added_frame();
+                !last_added_frame.is_null()
+                    && frame.as_i32().saturating_add(queue.frame_delay() as i32)
+                        <= last_added_frame.as_i32()
+            })
+    }
+
+    /// Returns the frame a local input for `frame` will first apply to (`frame` plus the
+    /// player's input delay) without queueing it.
+    ///
+    /// # Errors
+    /// Returns [`FortressError::LocalInputRejected`] if the player's input queue would refuse
+    /// the input because it would not directly follow the last queued input.
+    pub(crate) fn local_input_frame(
+        &self,
+        player_handle: PlayerHandle,
+        frame: Frame,
+    ) -> Result<Frame, FortressError> {
+        let Some(queue) = self.input_queues.get(player_handle.as_usize()) else {
+            return Err(FortressError::LocalInputRejected {
+                frame,
+                reason: LocalInputRejectReason::MissingQueue,
+            });
+        };
+        let delayed = safe_frame_add!(
+            frame,
+            queue.frame_delay() as i32,
+            "SyncLayer::local_input_frame delay"
+        );
+        let last_added_frame = queue.last_added_frame();
+        // An input already queued for this frame (the last advance stalled) stands.
+        let follows_queue = last_added_frame.is_null()
+            || delayed == last_added_frame
+            || delayed == safe_frame_add!(last_added_frame, 1, "SyncLayer::local_input_frame");
+        if !follows_queue && !queue.is_frozen() {
+            return Err(FortressError::LocalInputRejected {
+                frame,
+                reason: LocalInputRejectReason::QueueRejected { last_added_frame },
+            });
         }
+        Ok(delayed)
     }
 
     /// Adds remote input to the corresponding input queue.
@@ -1572,19 +1777,39 @@ pub(crate) fn confirmed_inputs(
     }
 
     /// Sets the last confirmed frame to a given frame. By raising the last confirmed frame, we can discard all previous frames, as they are no longer necessary.
-    pub(crate) fn set_last_confirmed_frame(&mut self, mut frame: Frame, save_mode: SaveMode) {
-        // don't set the last confirmed frame after the first incorrect frame before a rollback has happened
-        let first_incorrect: Frame = self
-            .input_queues
-            .iter()
-            .map(InputQueue::first_incorrect_frame)
-            .fold(Frame::NULL, std::cmp::max);
+    pub(crate) fn set_last_confirmed_frame(&mut self, frame: Frame, save_mode: SaveMode) {
+        self.set_last_confirmed_frame_before_rollback(frame, save_mode, Frame::NULL);
+    }
+
+    /// Like [`set_last_confirmed_frame`](Self::set_last_confirmed_frame), while
+    /// the session has a rollback to `pending_rollback` scheduled but not yet
+    /// run (such as a disconnect frame). Neither that frame nor any detected
+    /// misprediction loses the inputs its resimulation replays.
+    pub(crate) fn set_last_confirmed_frame_before_rollback(
+        &mut self,
+        mut frame: Frame,
+        save_mode: SaveMode,
+        pending_rollback: Frame,
+    ) {
+        // don't set the last confirmed frame after the earliest pending rollback target before
+        // that rollback has happened; each queue's `first_incorrect_frame` is already
+        // delay-adjusted, so it is comparable with the confirmed frame
+        let first_incorrect = self.check_simulation_consistency(pending_rollback);
 
         // if sparse saving option is turned on, don't set the last confirmed frame after the last saved frame
         if save_mode == SaveMode::Sparse {
             frame = std::cmp::min(frame, self.last_saved_frame);
         }
 
+        // with pyramid saving, a rollback may have dropped the state at that frame, so keep the
+        // inputs from the newest frame before it that is still saved
+        if let SaveMode::Pyramid { .. } = save_mode {
+            frame = std::cmp::min(
+                frame,
+                self.newest_saved_frame_in_range(Frame::new(0), frame),
+            );
+        }
+
         // never delete stuff ahead of the current frame
         frame = std::cmp::min(frame, self.current_frame());
 
@@ -1621,10 +1846,17 @@ pub(crate) fn set_last_confirmed_frame(&mut self, mut frame: Frame, save_mode: S
         // unchanged. Queue-trimming behavior is covered by the regular test
         // suite, not by these frame-clamp proofs.
         #[cfg(not(kani))]
-        if self.last_confirmed_frame.as_i32() > 0 {
-            let discard_frame = safe_frame_sub!(frame, 1, "SyncLayer::confirm_frame");
-            for queue in self.input_queues.iter_mut() {
-                queue.discard_confirmed_frames(discard_frame);
+        {
+            let retention = i32::try_from(self.input_retention).unwrap_or(i32::MAX);
+            let keep_from = std::cmp::min(
+                frame,
+                Frame::new(self.current_frame.as_i32().saturating_sub(retention)),
+            );
+            if keep_from.as_i32() > 0 {
+                let discard_frame = safe_frame_sub!(keep_from, 1, "SyncLayer::confirm_frame");
+                for queue in self.input_queues.iter_mut() {
+                    queue.discard_confirmed_frames(discard_frame);
+                }
             }
         }
     }
@@ -1671,6 +1903,28 @@ pub(crate) fn players_with_incorrect_predictions(
         result
     }
 
+    /// Overwrites `cause` with every player whose misprediction sits at
+    /// `first_incorrect`, with the predicted and confirmed input at that frame.
+    /// Must run before [`Self::reset_prediction`] clears the markers.
+    pub(crate) fn fill_rollback_cause(
+        &self,
+        first_incorrect: Frame,
+        cause: &mut RollbackCause<T::Input>,
+    ) {
+        cause.clear(first_incorrect);
+        for (handle, queue) in self.input_queues.iter().enumerate() {
+            if queue.first_incorrect_frame() != first_incorrect {
+                continue;
+            }
+            if let Some((predicted, confirmed)) = queue.misprediction() {
+                // alloc-bound: one entry per player; the vectors are reused across rollbacks.
+                cause.triggering_players.push(PlayerHandle::new(handle));
+                cause.predicted.push(predicted);
+                cause.confirmed.push(confirmed);
+            }
+        }
+    }
+
     /// Returns a gamestate through given frame
     pub(crate) fn saved_state_by_frame(&self, frame: Frame) -> Option<GameStateCell<T::State>> {
         let cell = self.saved_states.get_cell(frame).ok()?;
@@ -1694,6 +1948,21 @@ pub fn last_saved_frame(&self) -> Frame {
         self.last_saved_frame
     }
 
+    /// Metadata of the saved-state ring, oldest frame first; see
+    /// [`SavedStates::saved_frames`].
+    #[must_use]
+    pub fn saved_frames(&self) -> Vec<SavedFrameInfo> {
+        #[allow(unused_mut)] // only diff saving patches the list
+        let mut frames = self.saved_states.saved_frames();
+        #[cfg(not(kani))]
+        if let Some(diff_save) = &self.diff_save {
+            for info in &mut frames {
+                info.has_state |= diff_save.holds_diff(&self.saved_states, info.frame);
+            }
+        }
+        frames
+    }
+
     /// Returns the latest confirmed frame.
     ///
     /// # Note
@@ -1702,6 +1971,24 @@ pub fn last_saved_frame(&self) -> Frame {
     pub fn last_confirmed_frame(&self) -> Frame {
         self.last_confirmed_frame
     }
+
+    /// The input slots allocated across all players' input queues.
+    pub(crate) fn input_queue_slots(&self) -> usize {
+        self.input_queues.iter().map(InputQueue::queue_length).sum()
+    }
+
+    /// Returns the number of player input queues.
+    #[cfg(feature = "advanced")]
+    pub(crate) fn num_players(&self) -> usize {
+        self.num_players
+    }
+
+    /// Returns the input queue of `player_handle`, or `None` for a handle
+    /// outside the player slots.
+    #[cfg(feature = "advanced")]
+    pub(crate) fn input_queue(&self, player_handle: PlayerHandle) -> Option<&InputQueue<T>> {
+        self.input_queues.get(player_handle.as_usize())
+    }
 }
 
 /// Compile-time guarantee that the fallback inside
@@ -1876,6 +2163,62 @@ fn add_remote_input_reports_frozen_noop_as_not_accepted() {
         );
     }
 
+    #[test]
+    fn add_local_input_rejects_a_frame_other_than_the_current_one() {
+        let mut sync_layer = SyncLayer::<TestConfig>::new(1, 8);
+        let result = sync_layer.add_local_input(
+            PlayerHandle::new(0),
+            PlayerInput::new(Frame::new(3), TestInput { inp: 1 }),
+        );
+        assert!(matches!(
+            result,
+            Err(FortressError::LocalInputRejected {
+                frame,
+                reason: LocalInputRejectReason::FrameMismatch { current_frame },
+            }) if frame == Frame::new(3) && current_frame == Frame::new(0)
+        ));
+        assert!(sync_layer.input_queues[0].last_added_frame().is_null());
+    }
+
+    #[test]
+    fn local_input_frame_applies_delay_and_rejects_gaps() {
+        let mut sync_layer = SyncLayer::<TestConfig>::new(1, 8);
+        let player = PlayerHandle::new(0);
+        sync_layer.set_frame_delay(player, 2).unwrap();
+        assert_eq!(
+            sync_layer.local_input_frame(player, Frame::new(0)).unwrap(),
+            Frame::new(2)
+        );
+        assert!(!sync_layer.local_input_queued(player, Frame::new(0)));
+        assert_eq!(
+            sync_layer
+                .add_local_input(
+                    player,
+                    PlayerInput::new(Frame::new(0), TestInput { inp: 1 })
+                )
+                .unwrap(),
+            Frame::new(2)
+        );
+
+        // Re-adding for a frame that is already queued (a stalled advance) stands.
+        assert!(sync_layer.local_input_queued(player, Frame::new(0)));
+        assert_eq!(
+            sync_layer.local_input_frame(player, Frame::new(0)).unwrap(),
+            Frame::new(2)
+        );
+        assert_eq!(
+            sync_layer.local_input_frame(player, Frame::new(1)).unwrap(),
+            Frame::new(3)
+        );
+        assert!(matches!(
+            sync_layer.local_input_frame(player, Frame::new(5)),
+            Err(FortressError::LocalInputRejected {
+                reason: LocalInputRejectReason::QueueRejected { last_added_frame },
+                ..
+            }) if last_added_frame == Frame::new(2)
+        ));
+    }
+
     #[test]
     fn transactional_freeze_rejects_missing_cut_without_freezing_any_handle() {
         let mut sync_layer = SyncLayer::<TestConfig>::new(2, 8);
@@ -2647,6 +2990,7 @@ fn test_saved_state_by_frame_negative() {
         assert!(cell.is_none());
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn advance_frame_saturates_at_i32_max_without_corrupting_saved_states() {
         let observer = Arc::new(crate::telemetry::CollectingObserver::new());
@@ -2703,6 +3047,203 @@ fn advance_frame_saturates_at_i32_max_without_corrupting_saved_states() {
         assert_eq!(terminal.checksum(), Some(0x42));
     }
 
+    /// Saves the current state, applies the synchronized inputs and advances;
+    /// the state is the wrapping sum of every input applied.
+    fn step_near_limit(
+        sync_layer: &mut SyncLayer<TestConfig>,
+        connect_status: &[ConnectionStatus],
+        state: u8,
+    ) -> u8 {
+        let frame = sync_layer.current_frame();
+        match sync_layer.save_current_state() {
+            FortressRequest::SaveGameState { cell, frame: saved } => {
+                assert_eq!(saved, frame);
+                assert!(cell.save(frame, Some(state), None));
+            },
+            _ => panic!("Expected SaveGameState"),
+        }
+        let inputs = sync_layer
+            .synchronized_inputs(connect_status)
+            .expect("inputs near the frame limit");
+        sync_layer.advance_frame();
+        inputs
+            .iter()
+            .fold(state, |state, (input, _)| state.wrapping_add(input.inp))
+    }
+
+    /// Rolls back to the earliest mispredicted frame, if any, and resimulates up
+    /// to `frame`.
+    fn repair_near_limit(
+        sync_layer: &mut SyncLayer<TestConfig>,
+        connect_status: &[ConnectionStatus],
+        frame: Frame,
+        state: u8,
+    ) -> u8 {
+        let first_incorrect = sync_layer.check_simulation_consistency(Frame::NULL);
+        if first_incorrect.is_null() {
+            return state;
+        }
+        let mut state = match sync_layer.load_frame(first_incorrect).unwrap() {
+            FortressRequest::LoadGameState {
+                cell,
+                frame: loaded,
+            } => {
+                assert_eq!(loaded, first_incorrect);
+                cell.load().expect("saved state near the frame limit")
+            },
+            _ => panic!("Expected LoadGameState"),
+        };
+        sync_layer.reset_prediction();
+        while sync_layer.current_frame() < frame {
+            state = step_near_limit(sync_layer, connect_status, state);
+        }
+        state
+    }
+
+    /// The save/predict/rollback cycle over the last frames before
+    /// `MAX_SESSION_FRAMES` (reached through `seek_to_frame`): saved-state and
+    /// input-queue slots, indexed as `frame % len`, and the confirmed-frame
+    /// discards behave as they do near frame 0, with no violation reported.
+    #[test]
+    fn rollback_cycle_up_to_session_frame_limit() {
+        let observer = Arc::new(crate::telemetry::CollectingObserver::new());
+        let _observer_guard = crate::telemetry::push_violation_observer(
+            Arc::clone(&observer) as Arc<dyn crate::telemetry::ViolationObserver>
+        );
+        let limit = crate::MAX_SESSION_FRAMES;
+        let start = limit - 40;
+        let input = |frame: Frame, player: u8| TestInput {
+            inp: (frame.as_i32() as u8).wrapping_mul(3).wrapping_add(player),
+        };
+        let mut sync_layer = SyncLayer::<TestConfig>::with_queue_length(2, 8, 16);
+        sync_layer.seek_to_frame(start).unwrap();
+        let mut connect_status = vec![ConnectionStatus::default(); 2];
+        let mut remote_next = start;
+        let mut state = 0_u8;
+
+        while sync_layer.current_frame() < limit {
+            let frame = sync_layer.current_frame();
+            // Player 1's inputs arrive three frames late.
+            while remote_next <= frame - 3 {
+                sync_layer.add_remote_input(
+                    PlayerHandle::new(1),
+                    PlayerInput::new(remote_next, input(remote_next, 1)),
+                );
+                connect_status[1].last_frame = remote_next;
+                remote_next += 1;
+            }
+            state = repair_near_limit(&mut sync_layer, &connect_status, frame, state);
+            sync_layer
+                .add_local_input(
+                    PlayerHandle::new(0),
+                    PlayerInput::new(frame, input(frame, 0)),
+                )
+                .unwrap();
+            connect_status[0].last_frame = frame;
+            state = step_near_limit(&mut sync_layer, &connect_status, state);
+            sync_layer.set_last_confirmed_frame(remote_next - 1, SaveMode::EveryFrame);
+            assert!(sync_layer.check_invariants().is_ok());
+        }
+
+        // Deliver the last late inputs and repair the final frames.
+        while remote_next < limit {
+            sync_layer.add_remote_input(
+                PlayerHandle::new(1),
+                PlayerInput::new(remote_next, input(remote_next, 1)),
+            );
+            connect_status[1].last_frame = remote_next;
+            remote_next += 1;
+        }
+        state = repair_near_limit(&mut sync_layer, &connect_status, limit, state);
+
+        let expected = (start.as_i32()..limit.as_i32())
+            .map(Frame::new)
+            .fold(0_u8, |sum, frame| {
+                sum.wrapping_add(input(frame, 0).inp)
+                    .wrapping_add(input(frame, 1).inp)
+            });
+        assert_eq!(sync_layer.current_frame(), limit);
+        assert_eq!(state, expected);
+        assert!(
+            observer.is_empty(),
+            "no violation near the limit: {:?}",
+            observer.violations()
+        );
+    }
+
+    /// A confirmation arriving between a misprediction's detection and its
+    /// rollback keeps every input the rollback replays: the earliest pending
+    /// target wins, whether it is a queue's misprediction or a rollback the
+    /// session has scheduled.
+    #[test]
+    fn confirming_past_a_pending_rollback_keeps_its_inputs() {
+        let input = |frame: i32, inp: u8| PlayerInput::new(Frame::new(frame), TestInput { inp });
+        let connect_status = vec![ConnectionStatus::default(); 2];
+        let mut sync_layer = SyncLayer::<TestConfig>::new(2, 8);
+        // Player 0's inputs are known to frame 4, player 1's to frame 1; the
+        // rest of frames 0-7 is predicted.
+        for frame in 0..8 {
+            if frame <= 4 {
+                sync_layer.add_remote_input(PlayerHandle::new(0), input(frame, 0));
+            }
+            if frame <= 1 {
+                sync_layer.add_remote_input(PlayerHandle::new(1), input(frame, 0));
+            }
+            sync_layer.synchronized_inputs(&connect_status).unwrap();
+            sync_layer.advance_frame();
+        }
+        // The late inputs mispredict player 1 from frame 2 and player 0 from
+        // frame 5.
+        for frame in 2..8 {
+            sync_layer.add_remote_input(PlayerHandle::new(1), input(frame, 42));
+            if frame >= 5 {
+                sync_layer.add_remote_input(PlayerHandle::new(0), input(frame, 42));
+            }
+        }
+        assert_eq!(
+            sync_layer.check_simulation_consistency(Frame::NULL),
+            Frame::new(2)
+        );
+
+        sync_layer.set_last_confirmed_frame(Frame::new(7), SaveMode::EveryFrame);
+
+        assert_eq!(sync_layer.last_confirmed_frame(), Frame::new(2));
+        sync_layer.check_invariants().unwrap();
+        let retained = |sync_layer: &SyncLayer<TestConfig>, handle: usize| {
+            let range = sync_layer
+                .retained_input_range(PlayerHandle::new(handle))
+                .unwrap()
+                .unwrap();
+            (range.first, range.last)
+        };
+        for handle in 0..2 {
+            let (first, last) = retained(&sync_layer, handle);
+            assert!(
+                first <= Frame::new(2),
+                "player {handle} kept only {first}.."
+            );
+            assert_eq!(last, Frame::new(7));
+        }
+
+        // A scheduled rollback clamps the same way without any misprediction.
+        sync_layer.reset_prediction();
+        sync_layer.set_last_confirmed_frame_before_rollback(
+            Frame::new(7),
+            SaveMode::EveryFrame,
+            Frame::new(5),
+        );
+        assert_eq!(sync_layer.last_confirmed_frame(), Frame::new(5));
+        for handle in 0..2 {
+            let (first, _) = retained(&sync_layer, handle);
+            assert!(
+                first <= Frame::new(5),
+                "player {handle} kept only {first}.."
+            );
+        }
+        sync_layer.set_last_confirmed_frame(Frame::new(7), SaveMode::EveryFrame);
+        assert_eq!(retained(&sync_layer, 1), (Frame::new(6), Frame::new(7)));
+    }
+
     #[test]
     fn test_set_last_confirmed_frame() {
         let mut sync_layer = SyncLayer::<TestConfig>::new(2, 8);
@@ -2946,7 +3487,7 @@ fn test_game_state_cell_data_accessor_mut_dangerous() {
         {
             let mut accessor = cell.data().unwrap();
             // Use the dangerous mut accessor
-            let data = accessor.as_mut_dangerous();
+            let data = accessor.as_mut_dangerous().unwrap();
             data.push(4);
         }
 
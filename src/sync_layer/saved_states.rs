@@ -10,6 +10,18 @@
 use crate::telemetry::{ViolationKind, ViolationSeverity};
 use crate::{FortressError, Frame, IndexOutOfBounds, InternalErrorKind, InvalidFrameReason};
 
+/// What one saved-state slot holds, as reported by
+/// [`P2PSession::saved_frames`](crate::P2PSession::saved_frames).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavedFrameInfo {
+    /// The frame the slot was last saved for.
+    pub frame: Frame,
+    /// Whether the save carried a state (`cell.save(frame, Some(..), ..)`).
+    pub has_state: bool,
+    /// The checksum passed to the save, if any.
+    pub checksum: Option<u128>,
+}
+
 /// Container for saved game states used during rollback.
 ///
 /// # Note
@@ -71,6 +83,20 @@ pub(crate) fn try_new(max_pred: usize) -> Result<Self, FortressError> {
         Ok(Self { states })
     }
 
+    /// Metadata of every saved cell, oldest frame first. Cells never saved
+    /// (still at [`Frame::NULL`]) are skipped; no state is cloned.
+    #[must_use]
+    pub fn saved_frames(&self) -> Vec<SavedFrameInfo> {
+        let mut frames: Vec<SavedFrameInfo> = self
+            .states
+            .iter()
+            .map(GameStateCell::saved_frame_info)
+            .filter(|info| !info.frame.is_null())
+            .collect();
+        frames.sort_unstable_by_key(|info| info.frame);
+        frames
+    }
+
     /// Gets the cell for a given frame.
     pub fn get_cell(&self, frame: Frame) -> Result<GameStateCell<T>, FortressError> {
         if frame.as_i32() < 0 {
@@ -318,4 +344,36 @@ fn cells_independent_per_index() {
         assert_eq!(cell1.load(), Some(20));
         assert_eq!(cell2.load(), Some(30));
     }
+
+    // ========================================================================
+    // SavedStates::saved_frames Tests
+    // ========================================================================
+
+    #[test]
+    fn saved_frames_is_empty_before_any_save() {
+        let saved_states: SavedStates<u32> = SavedStates::new(3);
+        assert!(saved_states.saved_frames().is_empty());
+    }
+
+    #[test]
+    fn saved_frames_lists_wrapped_slots_oldest_first() {
+        let saved_states: SavedStates<u32> = SavedStates::new(3); // 4 cells
+
+        // Frames 4 and 5 overwrite the slots of 0 and 1; slot 3 stays empty.
+        for frame in [0, 1, 2, 4, 5] {
+            let cell = saved_states.get_cell(Frame::new(frame)).unwrap();
+            let data = (frame != 4).then_some(frame as u32);
+            cell.save(Frame::new(frame), data, Some(frame as u128 * 7));
+        }
+
+        let info = |frame: i32, has_state| SavedFrameInfo {
+            frame: Frame::new(frame),
+            has_state,
+            checksum: Some(frame as u128 * 7),
+        };
+        assert_eq!(
+            saved_states.saved_frames(),
+            vec![info(2, true), info(4, false), info(5, true)]
+        );
+    }
 }
@@ -0,0 +1,587 @@
+//! Diff-encoded saved states for large game states.
+//!
+//! A session normally keeps one full state per saved-state cell. With a
+//! [`StateDiffCodec`] registered through
+//! [`SessionBuilder::with_state_diff_codec`](crate::SessionBuilder::with_state_diff_codec),
+//! most cells instead keep the bytes of a diff against the previously saved
+//! frame, and only keyframes keep a full state. A keyframe is taken every
+//! [`DIFF_KEYFRAME_INTERVAL`] saves, and the oldest saved cell is always one.
+//! [`LoadGameState`](crate::FortressRequest::LoadGameState) cells are rebuilt
+//! to full states before they are handed out, so loading code is unchanged.
+//!
+//! The user fills a cell after the session hands it out, so a save is
+//! compacted into a diff on the session's next save or load.
+
+use std::sync::Arc;
+
+use crate::sync_layer::{GameStateCell, SavedStates, StoredState};
+use crate::Frame;
+
+/// Diffs compacted after a keyframe before the next save becomes a keyframe.
+///
+/// Bounds the number of [`StateDiffCodec::apply`] calls a load costs.
+pub(crate) const DIFF_KEYFRAME_INTERVAL: usize = 8;
+
+/// Encodes a saved game state as the difference from the previous one.
+///
+/// Register an implementation with
+/// [`SessionBuilder::with_state_diff_codec`](crate::SessionBuilder::with_state_diff_codec)
+/// when the game state is large and changes little per frame. The session
+/// then keeps most saved frames as diffs instead of full states.
+///
+/// `apply(prev, &diff(prev, curr))` must equal `curr`. The session relies on
+/// it for every rollback; a codec that loses information desynchronizes the
+/// game.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::StateDiffCodec;
+///
+/// /// Records each changed byte as its index and new value.
+/// struct ByteDiff;
+///
+/// impl StateDiffCodec<Vec<u8>> for ByteDiff {
+///     fn diff(&self, prev: &Vec<u8>, curr: &Vec<u8>) -> Vec<u8> {
+///         let mut out = Vec::new();
+///         for (index, (a, b)) in prev.iter().zip(curr).enumerate() {
+///             if a != b {
+///                 out.extend_from_slice(&(index as u32).to_le_bytes());
+///                 out.push(*b);
+///             }
+///         }
+///         out
+///     }
+///
+///     fn apply(&self, base: &Vec<u8>, diff: &[u8]) -> Vec<u8> {
+///         let mut state = base.clone();
+///         for change in diff.chunks_exact(5) {
+///             let index = u32::from_le_bytes([change[0], change[1], change[2], change[3]]);
+///             state[index as usize] = change[4];
+///         }
+///         state
+///     }
+/// }
+///
+/// let prev = vec![0u8; 1024];
+/// let mut curr = prev.clone();
+/// curr[7] = 3;
+/// let diff = ByteDiff.diff(&prev, &curr);
+/// assert_eq!(diff.len(), 5);
+/// assert_eq!(ByteDiff.apply(&prev, &diff), curr);
+/// ```
+#[cfg(feature = "sync-send")]
+pub trait StateDiffCodec<S>: Send + Sync {
+    /// Encodes how `curr` differs from `prev`.
+    fn diff(&self, prev: &S, curr: &S) -> Vec<u8>;
+
+    /// Rebuilds the state `diff` was taken for from the state it was taken
+    /// against.
+    fn apply(&self, base: &S, diff: &[u8]) -> S;
+}
+
+/// Encodes a saved game state as the difference from the previous one.
+///
+/// Register an implementation with
+/// [`SessionBuilder::with_state_diff_codec`](crate::SessionBuilder::with_state_diff_codec)
+/// when the game state is large and changes little per frame. The session
+/// then keeps most saved frames as diffs instead of full states.
+///
+/// `apply(prev, &diff(prev, curr))` must equal `curr`. The session relies on
+/// it for every rollback; a codec that loses information desynchronizes the
+/// game.
+#[cfg(not(feature = "sync-send"))]
+pub trait StateDiffCodec<S> {
+    /// Encodes how `curr` differs from `prev`.
+    fn diff(&self, prev: &S, curr: &S) -> Vec<u8>;
+
+    /// Rebuilds the state `diff` was taken for from the state it was taken
+    /// against.
+    fn apply(&self, base: &S, diff: &[u8]) -> S;
+}
+
+/// How one saved-state cell is held.
+#[derive(Debug)]
+enum SlotEncoding {
+    /// Not saved through diff saving; the cell is read as it is.
+    Untracked,
+    /// Handed out for a save that is not compacted yet.
+    Pending,
+    /// The cell holds a full state.
+    Keyframe,
+    /// The cell's state is `bytes` applied to the state saved for `base`.
+    /// The cell itself holds no state, except after a load of it until the
+    /// next load.
+    Diff { base: Frame, bytes: Vec<u8> },
+}
+
+/// Keeps saved-state cells as keyframes and diff chains.
+///
+/// Every diff's base frame is still saved: before a cell is handed out again,
+/// the cells diffed against its old content are either rebuilt into
+/// keyframes (the cell is being reused for a newer frame, so the oldest
+/// saved cell becomes a keyframe) or discarded (the same frame is saved
+/// again after a rollback, so the frames after it are stale and resimulated
+/// anyway).
+pub(crate) struct DiffSave<S> {
+    codec: Arc<dyn StateDiffCodec<S>>,
+    /// One entry per saved-state cell, indexed the same way.
+    slots: Vec<SlotEncoding>,
+    /// The newest compacted frame and its full state: the base of the next
+    /// diff.
+    tip: Option<(Frame, StoredState<S>)>,
+    /// Diffs compacted since the last keyframe.
+    since_keyframe: usize,
+    /// The diff-encoded frame whose cell holds the state rebuilt for the
+    /// last load.
+    rebuilt: Option<Frame>,
+}
+
+impl<S> std::fmt::Debug for DiffSave<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Destructure to ensure all fields are included when new fields are added.
+        let Self {
+            codec,
+            slots,
+            tip,
+            since_keyframe,
+            rebuilt,
+        } = self;
+        f.debug_struct("DiffSave")
+            .field("codec", &Arc::as_ptr(codec))
+            .field("slots", slots)
+            .field("tip", &tip.as_ref().map(|(frame, _)| *frame))
+            .field("since_keyframe", since_keyframe)
+            .field("rebuilt", rebuilt)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> DiffSave<S> {
+    /// Creates diff saving for `cells` saved-state cells.
+    pub(crate) fn new(codec: Arc<dyn StateDiffCodec<S>>, cells: usize) -> Self {
+        Self {
+            codec,
+            // alloc-bound: one entry per saved-state cell, which the session
+            // already allocated for `max_prediction + 1` cells.
+            slots: (0..cells).map(|_| SlotEncoding::Untracked).collect(),
+            tip: None,
+            since_keyframe: 0,
+            rebuilt: None,
+        }
+    }
+
+    /// Forgets every encoding, for saved-state cells that were all cleared.
+    pub(crate) fn reset(&mut self) {
+        for slot in &mut self.slots {
+            *slot = SlotEncoding::Untracked;
+        }
+        self.tip = None;
+        self.since_keyframe = 0;
+        self.rebuilt = None;
+    }
+
+    fn slot_index(&self, frame: Frame) -> Option<usize> {
+        let frame = usize::try_from(frame.as_i32()).ok()?;
+        frame.checked_rem(self.slots.len())
+    }
+
+    /// Prepares the cell for `frame` to be handed out for a save.
+    pub(crate) fn before_save(&mut self, states: &SavedStates<S>, frame: Frame) {
+        self.compact(states);
+        let Some(index) = self.slot_index(frame) else {
+            return;
+        };
+        let Some(old_frame) = states.states.get(index).map(GameStateCell::frame) else {
+            return;
+        };
+        if !old_frame.is_null() {
+            if old_frame == frame {
+                self.discard_dependents(states, old_frame);
+            } else {
+                self.promote_dependents(states, old_frame);
+            }
+            self.forget_tip(old_frame);
+        }
+        if let Some(slot) = self.slots.get_mut(index) {
+            *slot = SlotEncoding::Pending;
+        }
+    }
+
+    /// Prepares the cell for `frame` to be cleared.
+    pub(crate) fn before_discard(&mut self, states: &SavedStates<S>, frame: Frame) {
+        self.compact(states);
+        self.discard_dependents(states, frame);
+        self.forget_tip(frame);
+        if let Some(slot) = self.slot_index(frame).and_then(|i| self.slots.get_mut(i)) {
+            *slot = SlotEncoding::Untracked;
+        }
+    }
+
+    /// Rebuilds the full state of `frame` into its cell before a load hands
+    /// the cell out. The loaded frame becomes the base of the next diff.
+    pub(crate) fn before_load(&mut self, states: &SavedStates<S>, frame: Frame) {
+        self.compact(states);
+        // The user has loaded the previous rebuild by now.
+        if let Some(rebuilt) = self.rebuilt.take() {
+            if self.holds_diff(states, rebuilt) {
+                let index = self.slot_index(rebuilt);
+                if let Some(cell) = index.and_then(|index| states.states.get(index)) {
+                    drop(cell.take_shared(rebuilt));
+                }
+            }
+        }
+        let Some(index) = self.slot_index(frame) else {
+            return;
+        };
+        let Some(state) = self.reconstruct(states, frame) else {
+            return;
+        };
+        // The cell keeps its diff; the rebuilt state is dropped again on the
+        // next load.
+        if matches!(self.slots.get(index), Some(SlotEncoding::Diff { .. })) {
+            if let Some(cell) = states.states.get(index) {
+                if cell.restore_shared(frame, StoredState::clone(&state)) {
+                    self.rebuilt = Some(frame);
+                }
+            }
+        }
+        self.tip = Some((frame, state));
+        self.since_keyframe = 0;
+    }
+
+    /// Whether the cell for `frame` holds it as a diff.
+    pub(crate) fn holds_diff(&self, states: &SavedStates<S>, frame: Frame) -> bool {
+        self.slot_index(frame).is_some_and(|index| {
+            matches!(self.slots.get(index), Some(SlotEncoding::Diff { .. }))
+                && states.states.get(index).map(GameStateCell::frame) == Some(frame)
+        })
+    }
+
+    /// The full state saved for `frame`, rebuilt from its keyframe if the
+    /// cell holds a diff. `None` if `frame` is not saved or was saved without
+    /// a state.
+    pub(crate) fn reconstruct(
+        &self,
+        states: &SavedStates<S>,
+        frame: Frame,
+    ) -> Option<StoredState<S>> {
+        // Walk back to the keyframe, collecting the diffs on the way.
+        let mut chain = Vec::new();
+        let mut current = frame;
+        let base = loop {
+            let index = self.slot_index(current)?;
+            let cell = states.states.get(index)?;
+            if cell.frame() != current {
+                return None;
+            }
+            match self.slots.get(index)? {
+                SlotEncoding::Diff { base, bytes } => {
+                    // A chain visits every cell at most once.
+                    if chain.len() >= self.slots.len() {
+                        return None;
+                    }
+                    chain.push(bytes);
+                    current = *base;
+                },
+                _ => break cell.peek_shared(current)?,
+            }
+        };
+        let mut diffs = chain.into_iter().rev();
+        let Some(first) = diffs.next() else {
+            return Some(base);
+        };
+        let mut state = self.codec.apply(&base, first);
+        for bytes in diffs {
+            state = self.codec.apply(&state, bytes);
+        }
+        Some(StoredState::new(state))
+    }
+
+    /// Turns the saves the user has filled since the last call into diffs
+    /// and keyframes, oldest frame first.
+    fn compact(&mut self, states: &SavedStates<S>) {
+        let mut filled: Vec<(Frame, usize)> = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| matches!(slot, SlotEncoding::Pending))
+            .filter_map(|(index, _)| {
+                let info = states.states.get(index)?.saved_frame_info();
+                (info.has_state && !info.frame.is_null()).then_some((info.frame, index))
+            })
+            .collect();
+        filled.sort_unstable();
+        for (frame, index) in filled {
+            let Some(cell) = states.states.get(index) else {
+                continue;
+            };
+            let base = self.tip.as_ref().filter(|(tip_frame, _)| {
+                *tip_frame < frame && self.since_keyframe < DIFF_KEYFRAME_INTERVAL
+            });
+            let (encoding, state) = match base {
+                Some((base_frame, base_state)) => {
+                    let Some(state) = cell.take_shared(frame) else {
+                        continue;
+                    };
+                    let bytes = self.codec.diff(base_state, &state);
+                    self.since_keyframe += 1;
+                    (
+                        SlotEncoding::Diff {
+                            base: *base_frame,
+                            bytes,
+                        },
+                        state,
+                    )
+                },
+                None => {
+                    let Some(state) = cell.peek_shared(frame) else {
+                        continue;
+                    };
+                    self.since_keyframe = 0;
+                    (SlotEncoding::Keyframe, state)
+                },
+            };
+            if let Some(slot) = self.slots.get_mut(index) {
+                *slot = encoding;
+            }
+            self.tip = Some((frame, state));
+        }
+    }
+
+    /// Rebuilds every cell diffed against `frame` into a keyframe, before
+    /// `frame`'s cell is reused for a newer frame.
+    fn promote_dependents(&mut self, states: &SavedStates<S>, frame: Frame) {
+        for index in self.dependents(frame) {
+            let Some(cell) = states.states.get(index) else {
+                continue;
+            };
+            let dependent = cell.frame();
+            let promoted = self
+                .reconstruct(states, dependent)
+                .is_some_and(|state| cell.restore_shared(dependent, state));
+            if let Some(slot) = self.slots.get_mut(index) {
+                *slot = if promoted {
+                    SlotEncoding::Keyframe
+                } else {
+                    SlotEncoding::Untracked
+                };
+            }
+            if !promoted {
+                cell.clear();
+            }
+        }
+    }
+
+    /// Clears every cell diffed, directly or through other diffs, against
+    /// `frame`, before `frame` is saved again or discarded.
+    fn discard_dependents(&mut self, states: &SavedStates<S>, frame: Frame) {
+        let mut stale = vec![frame];
+        while let Some(base) = stale.pop() {
+            for index in self.dependents(base) {
+                let Some(cell) = states.states.get(index) else {
+                    continue;
+                };
+                let dependent = cell.frame();
+                cell.clear();
+                if let Some(slot) = self.slots.get_mut(index) {
+                    *slot = SlotEncoding::Untracked;
+                }
+                self.forget_tip(dependent);
+                stale.push(dependent);
+            }
+        }
+    }
+
+    /// Indexes of the cells holding a diff against `frame`.
+    fn dependents(&self, frame: Frame) -> Vec<usize> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| matches!(slot, SlotEncoding::Diff { base, .. } if *base == frame))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn forget_tip(&mut self, frame: Frame) {
+        if self.tip.as_ref().is_some_and(|(tip, _)| *tip == frame) {
+            self.tip = None;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use super::*;
+
+    /// Records every changed element as its index and new value.
+    struct ElementDiff;
+
+    impl StateDiffCodec<Vec<u32>> for ElementDiff {
+        fn diff(&self, prev: &Vec<u32>, curr: &Vec<u32>) -> Vec<u8> {
+            let mut out = Vec::new();
+            for (index, (a, b)) in prev.iter().zip(curr).enumerate() {
+                if a != b {
+                    out.extend_from_slice(&(index as u32).to_le_bytes());
+                    out.extend_from_slice(&b.to_le_bytes());
+                }
+            }
+            out
+        }
+
+        fn apply(&self, base: &Vec<u32>, diff: &[u8]) -> Vec<u32> {
+            let mut state = base.clone();
+            for change in diff.chunks_exact(8) {
+                let index = u32::from_le_bytes(change[..4].try_into().unwrap());
+                state[index as usize] = u32::from_le_bytes(change[4..].try_into().unwrap());
+            }
+            state
+        }
+    }
+
+    const CELLS: usize = 4;
+
+    fn state_at(frame: i32) -> Vec<u32> {
+        let mut state = vec![0; 64];
+        for step in 0..=frame {
+            state[step as usize % 64] += step as u32 + 1;
+        }
+        state
+    }
+
+    fn setup() -> (SavedStates<Vec<u32>>, DiffSave<Vec<u32>>) {
+        (
+            SavedStates::new(CELLS - 1),
+            DiffSave::new(Arc::new(ElementDiff), CELLS),
+        )
+    }
+
+    /// Hands out the cell for `frame` and fills it the way the user would.
+    fn save(states: &SavedStates<Vec<u32>>, diff_save: &mut DiffSave<Vec<u32>>, frame: i32) {
+        let frame = Frame::new(frame);
+        diff_save.before_save(states, frame);
+        let cell = states.get_cell(frame).unwrap();
+        cell.save(
+            frame,
+            Some(state_at(frame.as_i32())),
+            Some(frame.as_i32() as u128),
+        );
+    }
+
+    fn encoding(diff_save: &DiffSave<Vec<u32>>, frame: i32) -> &SlotEncoding {
+        &diff_save.slots[frame as usize % CELLS]
+    }
+
+    #[test]
+    fn saves_compact_to_a_keyframe_and_diffs_that_rebuild_every_frame() {
+        let (states, mut diff_save) = setup();
+        for frame in 0..=3 {
+            save(&states, &mut diff_save, frame);
+        }
+        // The last save is compacted on the next call.
+        diff_save.compact(&states);
+
+        assert!(matches!(encoding(&diff_save, 0), SlotEncoding::Keyframe));
+        for frame in 1..=3 {
+            assert!(matches!(
+                encoding(&diff_save, frame),
+                SlotEncoding::Diff { base, .. } if *base == Frame::new(frame - 1)
+            ));
+            assert!(states.get_cell(Frame::new(frame)).unwrap().load().is_none());
+            assert!(diff_save.holds_diff(&states, Frame::new(frame)));
+        }
+        for frame in 0..=3 {
+            let state = diff_save.reconstruct(&states, Frame::new(frame)).unwrap();
+            assert_eq!(*state, state_at(frame));
+        }
+    }
+
+    #[test]
+    fn reusing_a_cell_promotes_the_next_frame_to_a_keyframe() {
+        let (states, mut diff_save) = setup();
+        for frame in 0..=4 {
+            save(&states, &mut diff_save, frame);
+        }
+        diff_save.compact(&states);
+
+        // Frame 4 reused frame 0's cell, so frame 1 is the oldest and full.
+        assert!(matches!(encoding(&diff_save, 1), SlotEncoding::Keyframe));
+        assert_eq!(
+            states.get_cell(Frame::new(1)).unwrap().load(),
+            Some(state_at(1))
+        );
+        for frame in 1..=4 {
+            let state = diff_save.reconstruct(&states, Frame::new(frame)).unwrap();
+            assert_eq!(*state, state_at(frame));
+        }
+    }
+
+    #[test]
+    fn keyframes_recur_after_the_interval() {
+        let states: SavedStates<Vec<u32>> = SavedStates::new(2 * DIFF_KEYFRAME_INTERVAL);
+        let mut diff_save = DiffSave::new(Arc::new(ElementDiff), states.states.len());
+        let last = DIFF_KEYFRAME_INTERVAL as i32 + 1;
+        for frame in 0..=last {
+            save(&states, &mut diff_save, frame);
+        }
+        diff_save.compact(&states);
+
+        let keyframes: Vec<usize> = diff_save
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| matches!(slot, SlotEncoding::Keyframe))
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(keyframes, vec![0, DIFF_KEYFRAME_INTERVAL + 1]);
+    }
+
+    #[test]
+    fn loading_rebuilds_the_cell_until_the_next_load() {
+        let (states, mut diff_save) = setup();
+        for frame in 0..=3 {
+            save(&states, &mut diff_save, frame);
+        }
+        diff_save.before_load(&states, Frame::new(2));
+
+        let cell = states.get_cell(Frame::new(2)).unwrap();
+        assert_eq!(cell.load(), Some(state_at(2)));
+        assert!(matches!(encoding(&diff_save, 2), SlotEncoding::Diff { .. }));
+        diff_save.before_load(&states, Frame::new(3));
+        assert!(cell.load().is_none());
+        assert_eq!(
+            *diff_save.reconstruct(&states, Frame::new(2)).unwrap(),
+            state_at(2)
+        );
+    }
+
+    #[test]
+    fn saving_a_frame_again_discards_the_frames_diffed_after_it() {
+        let (states, mut diff_save) = setup();
+        for frame in 0..=3 {
+            save(&states, &mut diff_save, frame);
+        }
+        // Roll back to frame 0 and save frame 1 again.
+        diff_save.before_load(&states, Frame::new(0));
+        save(&states, &mut diff_save, 1);
+
+        for frame in 2..=3 {
+            assert!(states
+                .get_cell(Frame::new(frame))
+                .unwrap()
+                .frame()
+                .is_null());
+            assert!(diff_save.reconstruct(&states, Frame::new(frame)).is_none());
+        }
+        diff_save.compact(&states);
+        assert!(matches!(
+            encoding(&diff_save, 1),
+            SlotEncoding::Diff { base, .. } if *base == Frame::new(0)
+        ));
+        assert_eq!(
+            *diff_save.reconstruct(&states, Frame::new(1)).unwrap(),
+            state_at(1)
+        );
+    }
+}
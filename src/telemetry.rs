@@ -7,6 +7,8 @@
 //! - Logged via tracing (default behavior, backward compatible)
 //! - Collected programmatically for testing
 //! - Sent to custom observers (metrics, alerting, etc.)
+//! - Counted by kind and severity via lock-free counters ([`violation_counts`],
+//!   [`CountingObserver`])
 //!
 //! # Example
 //!
@@ -28,6 +30,7 @@
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Custom serializer for `Option<Frame>` that outputs clean integers or null.
@@ -74,6 +77,9 @@ pub enum ViolationSeverity {
 }
 
 impl ViolationSeverity {
+    /// Every severity, in ascending order.
+    const ALL: [Self; 3] = [Self::Warning, Self::Error, Self::Critical];
+
     /// Returns a string representation suitable for logging/metrics labels.
     #[must_use]
     pub const fn as_str(&self) -> &'static str {
@@ -142,7 +148,8 @@ pub enum ViolationKind {
     ///
     /// These violations indicate that a type's invariants were broken,
     /// which could lead to undefined behavior or incorrect results.
-    /// Only checked in debug builds or when `paranoid` feature is enabled.
+    /// Only checked in debug builds or when `paranoid` feature is enabled,
+    /// and never without the `telemetry` feature.
     Invariant,
     /// Synchronization protocol issues.
     ///
@@ -160,6 +167,20 @@ pub enum ViolationKind {
 }
 
 impl ViolationKind {
+    /// Every kind, in declaration order.
+    const ALL: [Self; 10] = [
+        Self::FrameSync,
+        Self::InputQueue,
+        Self::StateManagement,
+        Self::NetworkProtocol,
+        Self::ChecksumMismatch,
+        Self::Configuration,
+        Self::InternalError,
+        Self::Invariant,
+        Self::Synchronization,
+        Self::ArithmeticOverflow,
+    ];
+
     /// Returns a string representation suitable for logging/metrics labels.
     #[must_use]
     pub const fn as_str(&self) -> &'static str {
@@ -730,8 +751,263 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
-/// Implementation detail for Kani-friendly telemetry macros.
-#[cfg(kani)]
+// ==========================================
+// Violation Counters
+// ==========================================
+
+const SEVERITY_COUNT: usize = ViolationSeverity::ALL.len();
+const KIND_COUNT: usize = ViolationKind::ALL.len();
+
+/// Lock-free violation counters bucketed by (kind, severity).
+struct ViolationCounters {
+    buckets: [[AtomicU64; SEVERITY_COUNT]; KIND_COUNT],
+}
+
+impl ViolationCounters {
+    const fn new() -> Self {
+        Self {
+            buckets: [const { [const { AtomicU64::new(0) }; SEVERITY_COUNT] }; KIND_COUNT],
+        }
+    }
+
+    fn bucket(&self, kind: ViolationKind, severity: ViolationSeverity) -> Option<&AtomicU64> {
+        self.buckets
+            .get(kind as usize)
+            .and_then(|row| row.get(severity as usize))
+    }
+
+    fn record(&self, violation: &SpecViolation) {
+        if let Some(bucket) = self.bucket(violation.kind, violation.severity) {
+            bucket.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> ViolationCounts {
+        let mut counts = ViolationCounts::default();
+        for (row, buckets) in counts.buckets.iter_mut().zip(&self.buckets) {
+            for (count, bucket) in row.iter_mut().zip(buckets) {
+                *count = bucket.load(Ordering::Relaxed);
+            }
+        }
+        counts
+    }
+
+    fn reset(&self) {
+        for bucket in self.buckets.iter().flatten() {
+            bucket.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Process-wide counters behind [`violation_counts`].
+static GLOBAL_VIOLATION_COUNTS: ViolationCounters = ViolationCounters::new();
+
+/// Counts every violation reported through [`report_to_current_observer`] or
+/// [`report_to_observer`], whichever observer ends up receiving it.
+fn count_violation(violation: &SpecViolation) {
+    GLOBAL_VIOLATION_COUNTS.record(violation);
+}
+
+/// Counts a violation without building or reporting it.
+///
+/// This is what `report_violation!` and `report_violation_to!` expand to
+/// when the `telemetry` feature is disabled, so [`violation_counts`] keeps
+/// working while no message is formatted.
+#[doc(hidden)]
+#[cold]
+#[inline(never)]
+pub fn count_unreported_violation(severity: ViolationSeverity, kind: ViolationKind) {
+    if let Some(bucket) = GLOBAL_VIOLATION_COUNTS.bucket(kind, severity) {
+        bucket.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A snapshot of violation counts bucketed by [`ViolationKind`] and
+/// [`ViolationSeverity`].
+///
+/// Returned by [`violation_counts`] and [`CountingObserver::counts`].
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::telemetry::{
+///     CountingObserver, SpecViolation, ViolationKind, ViolationObserver, ViolationSeverity,
+/// };
+///
+/// let observer = CountingObserver::new();
+/// observer.on_violation(&SpecViolation::new(
+///     ViolationSeverity::Warning,
+///     ViolationKind::NetworkProtocol,
+///     "dropped packet",
+///     "test.rs:1",
+/// ));
+///
+/// let counts = observer.counts();
+/// assert_eq!(counts.get(ViolationKind::NetworkProtocol, ViolationSeverity::Warning), 1);
+/// assert_eq!(counts.of_kind(ViolationKind::FrameSync), 0);
+/// assert_eq!(counts.total(), 1);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ViolationCounts {
+    buckets: [[u64; SEVERITY_COUNT]; KIND_COUNT],
+}
+
+impl ViolationCounts {
+    /// Returns the number of violations of `kind` at exactly `severity`.
+    #[must_use]
+    pub fn get(&self, kind: ViolationKind, severity: ViolationSeverity) -> u64 {
+        self.buckets
+            .get(kind as usize)
+            .and_then(|row| row.get(severity as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of violations of `kind` at any severity.
+    #[must_use]
+    pub fn of_kind(&self, kind: ViolationKind) -> u64 {
+        self.buckets
+            .get(kind as usize)
+            .map_or(0, |row| row.iter().fold(0, |sum, &n| sum.saturating_add(n)))
+    }
+
+    /// Returns the number of violations at exactly `severity`, of any kind.
+    #[must_use]
+    pub fn at_severity(&self, severity: ViolationSeverity) -> u64 {
+        ViolationKind::ALL
+            .iter()
+            .fold(0, |sum, &kind| sum.saturating_add(self.get(kind, severity)))
+    }
+
+    /// Returns the total number of violations across all buckets.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.buckets
+            .iter()
+            .flatten()
+            .fold(0, |sum, &n| sum.saturating_add(n))
+    }
+
+    /// Iterates over the non-empty buckets as `(kind, severity, count)`.
+    pub fn iter(&self) -> impl Iterator<Item = (ViolationKind, ViolationSeverity, u64)> + '_ {
+        ViolationKind::ALL.into_iter().flat_map(move |kind| {
+            ViolationSeverity::ALL
+                .into_iter()
+                .map(move |severity| (kind, severity, self.get(kind, severity)))
+                .filter(|&(_, _, count)| count > 0)
+        })
+    }
+}
+
+/// Returns a snapshot of the process-wide violation counters.
+///
+/// Every violation reported through `report_violation!` or
+/// `report_violation_to!` increments the bucket for its kind and severity,
+/// regardless of which observer receives it. Counting is a relaxed atomic
+/// increment, so it is cheap on the hot path and safe across threads.
+///
+/// The counters are shared by every session in the process. For counts scoped
+/// to one session, install a [`CountingObserver`] on it instead.
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::report_violation;
+/// use fortress_rollback::telemetry::{violation_counts, ViolationKind, ViolationSeverity};
+///
+/// let before = violation_counts().get(ViolationKind::NetworkProtocol, ViolationSeverity::Warning);
+/// report_violation!(ViolationSeverity::Warning, ViolationKind::NetworkProtocol, "late packet");
+/// let after = violation_counts().get(ViolationKind::NetworkProtocol, ViolationSeverity::Warning);
+/// assert!(after > before);
+/// ```
+#[must_use]
+pub fn violation_counts() -> ViolationCounts {
+    GLOBAL_VIOLATION_COUNTS.snapshot()
+}
+
+/// Resets every process-wide violation counter to zero.
+///
+/// Buckets are cleared one at a time, so a violation reported concurrently
+/// with the reset may or may not be counted.
+pub fn reset_violation_counts() {
+    GLOBAL_VIOLATION_COUNTS.reset();
+}
+
+/// Observer that counts violations by kind and severity.
+///
+/// The per-session counterpart of [`violation_counts`]: install it with
+/// [`SessionBuilder::with_violation_observer`] (or alongside other observers
+/// via [`CompositeObserver`]) to count only the violations that session
+/// reports. Counting is lock-free.
+///
+/// [`SessionBuilder::with_violation_observer`]: crate::SessionBuilder::with_violation_observer
+///
+/// # Example
+///
+/// ```
+/// use fortress_rollback::{report_violation, telemetry::{
+///     push_violation_observer, CountingObserver, ViolationKind, ViolationSeverity,
+/// }};
+/// use std::sync::Arc;
+///
+/// let observer = Arc::new(CountingObserver::new());
+/// {
+///     let _guard = push_violation_observer(observer.clone());
+///     report_violation!(ViolationSeverity::Error, ViolationKind::InputQueue, "gap");
+/// }
+/// assert_eq!(observer.counts().get(ViolationKind::InputQueue, ViolationSeverity::Error), 1);
+///
+/// observer.reset();
+/// assert_eq!(observer.counts().total(), 0);
+/// ```
+pub struct CountingObserver {
+    counters: ViolationCounters,
+}
+
+impl CountingObserver {
+    /// Creates a new counting observer with every count at zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            counters: ViolationCounters::new(),
+        }
+    }
+
+    /// Returns a snapshot of the counts recorded so far.
+    #[must_use]
+    pub fn counts(&self) -> ViolationCounts {
+        self.counters.snapshot()
+    }
+
+    /// Resets every count to zero.
+    pub fn reset(&self) {
+        self.counters.reset();
+    }
+}
+
+impl Default for CountingObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViolationObserver for CountingObserver {
+    fn on_violation(&self, violation: &SpecViolation) {
+        self.counters.record(violation);
+    }
+}
+
+impl std::fmt::Debug for CountingObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CountingObserver")
+            .field("counts", &self.counts())
+            .finish()
+    }
+}
+
+/// Implementation detail for Kani-friendly telemetry macros, and for the
+/// macros of builds without the `telemetry` feature.
+#[cfg(any(kani, not(feature = "telemetry")))]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __fortress_borrow_format_args {
@@ -781,6 +1057,14 @@ macro_rules! __fortress_borrow_format_args {
 /// `cargo clippy`), so format-string regressions are still caught at
 /// development time. Skipping reporting under Kani does not affect
 /// correctness verification — proofs check state, not logging.
+///
+/// # Without the `telemetry` feature
+///
+/// The macro only increments the [`violation_counts`] bucket of its kind and
+/// severity. No [`SpecViolation`] is built, no observer is called, and the
+/// message and its arguments are never formatted, so the message literals do
+/// not end up in the binary.
+#[cfg(feature = "telemetry")]
 #[macro_export]
 macro_rules! report_violation {
     // Under Kani, report_violation is a no-op to avoid CBMC state explosion
@@ -831,6 +1115,26 @@ macro_rules! report_violation {
     }};
 }
 
+/// Counter-only version for builds without the `telemetry` feature.
+///
+/// The format arguments are borrowed inside a statically-dead `if false`
+/// block, like the release no-op of [`debug_check_invariants!`], so they are
+/// neither evaluated nor formatted.
+#[cfg(not(feature = "telemetry"))]
+#[macro_export]
+macro_rules! report_violation {
+    ($severity:expr, $kind:expr, $msg:literal) => {{
+        $crate::telemetry::count_unreported_violation($severity, $kind);
+    }};
+
+    ($severity:expr, $kind:expr, $fmt:literal, $($arg:tt)+) => {{
+        $crate::telemetry::count_unreported_violation($severity, $kind);
+        if false {
+            $crate::__fortress_borrow_format_args!($($arg)+);
+        }
+    }};
+}
+
 /// Safely adds a value to a Frame, reporting a violation if overflow would occur.
 ///
 /// Returns the result of checked addition, or the saturated value if overflow occurs.
@@ -1024,6 +1328,7 @@ pub fn report_to_observer<O: ViolationObserver + ?Sized>(
     observer: Option<&Arc<O>>,
     violation: &SpecViolation,
 ) {
+    count_violation(violation);
     match observer {
         Some(obs) => obs.on_violation(violation),
         None => TracingObserver.on_violation(violation),
@@ -1065,12 +1370,14 @@ fn current_observer(&self) -> Option<Arc<dyn ViolationObserver>> {
 }
 
 fn report_scoped_observer_drop_failure() {
-    TracingObserver.on_violation(&SpecViolation::new(
+    let violation = SpecViolation::new(
         ViolationSeverity::Critical,
         ViolationKind::InternalError,
         "scoped observer guard could not borrow the thread-local observer stack during drop",
         "src/telemetry.rs:ScopedObserverGuard::drop",
-    ));
+    );
+    count_violation(&violation);
+    TracingObserver.on_violation(&violation);
 }
 
 thread_local! {
@@ -1211,6 +1518,7 @@ pub fn push_violation_observer(observer: Arc<dyn ViolationObserver>) -> ScopedOb
 #[cold]
 #[inline(never)]
 pub fn report_to_current_observer(violation: &SpecViolation) {
+    count_violation(violation);
     let current = CURRENT_OBSERVER.with(|stack| stack.borrow().current_observer());
     match current {
         Some(observer) => observer.on_violation(violation),
@@ -1242,6 +1550,10 @@ pub fn report_to_current_observer(violation: &SpecViolation) {
 /// report_violation_to!(&observer, ViolationSeverity::Warning, ViolationKind::FrameSync,
 ///     "frame mismatch: expected={}, actual={}", 10, 15);
 /// ```
+///
+/// Without the `telemetry` feature the macro only counts the violation, the
+/// same way as [`report_violation!`]; the observer receives nothing.
+#[cfg(feature = "telemetry")]
 #[macro_export]
 macro_rules! report_violation_to {
     // Basic: observer, severity, kind, message (no format args)
@@ -1285,6 +1597,24 @@ macro_rules! report_violation_to {
     }};
 }
 
+/// Counter-only version for builds without the `telemetry` feature.
+#[cfg(not(feature = "telemetry"))]
+#[macro_export]
+macro_rules! report_violation_to {
+    ($observer:expr, $severity:expr, $kind:expr, $msg:literal) => {{
+        let _ = &$observer;
+        $crate::telemetry::count_unreported_violation($severity, $kind);
+    }};
+
+    ($observer:expr, $severity:expr, $kind:expr, $fmt:literal, $($arg:tt)+) => {{
+        let _ = &$observer;
+        $crate::telemetry::count_unreported_violation($severity, $kind);
+        if false {
+            $crate::__fortress_borrow_format_args!($($arg)+);
+        }
+    }};
+}
+
 // ==========================================
 // Runtime Invariant Checking
 // ==========================================
@@ -1483,6 +1813,8 @@ pub trait InvariantChecker {
 ///
 /// This macro expands to an invariant check in debug builds but compiles
 /// to nothing in release builds, unless the `paranoid` feature is enabled.
+/// Without the `telemetry` feature it always compiles to nothing, since there
+/// would be nowhere to report a violation.
 ///
 /// # Syntax
 ///
@@ -1507,7 +1839,7 @@ pub trait InvariantChecker {
 /// }
 /// ```
 #[macro_export]
-#[cfg(any(debug_assertions, feature = "paranoid"))]
+#[cfg(all(feature = "telemetry", any(debug_assertions, feature = "paranoid")))]
 macro_rules! debug_check_invariants {
     ($expr:expr) => {{
         use $crate::telemetry::InvariantChecker as _;
@@ -1535,7 +1867,8 @@ macro_rules! debug_check_invariants {
     }};
 }
 
-/// No-op version for release builds without `paranoid` feature.
+/// No-op version for release builds without `paranoid` feature, and for
+/// builds without the `telemetry` feature.
 ///
 /// The arguments are referenced inside a statically-dead `if false` block so a
 /// caller binding used *only* by this macro is not flagged `unused_variables`
@@ -1544,7 +1877,7 @@ macro_rules! debug_check_invariants {
 /// referenced for the lint but never evaluated, preserving the macro's contract
 /// of doing no work in release.
 #[macro_export]
-#[cfg(not(any(debug_assertions, feature = "paranoid")))]
+#[cfg(not(all(feature = "telemetry", any(debug_assertions, feature = "paranoid"))))]
 macro_rules! debug_check_invariants {
     ($expr:expr) => {{
         if false {
@@ -2439,6 +2772,127 @@ fn test_collecting_observer_no_poison_on_panic() {
         assert_eq!(observer.len(), 2);
     }
 
+    // ==========================================
+    // Violation Counter Tests
+    // ==========================================
+
+    #[test]
+    fn test_violation_bucket_order_matches_discriminants() {
+        for (index, kind) in ViolationKind::ALL.into_iter().enumerate() {
+            assert_eq!(kind as usize, index, "{kind}");
+        }
+        for (index, severity) in ViolationSeverity::ALL.into_iter().enumerate() {
+            assert_eq!(severity as usize, index, "{severity}");
+        }
+    }
+
+    #[test]
+    fn test_counting_observer_buckets_by_kind_and_severity() {
+        let observer = CountingObserver::new();
+        for (severity, kind, times) in [
+            (
+                ViolationSeverity::Warning,
+                ViolationKind::NetworkProtocol,
+                3,
+            ),
+            (ViolationSeverity::Error, ViolationKind::NetworkProtocol, 1),
+            (ViolationSeverity::Critical, ViolationKind::InternalError, 2),
+        ] {
+            for _ in 0..times {
+                observer.on_violation(&SpecViolation::new(severity, kind, "test", "test.rs:1"));
+            }
+        }
+
+        let counts = observer.counts();
+        assert_eq!(
+            counts.get(ViolationKind::NetworkProtocol, ViolationSeverity::Warning),
+            3
+        );
+        assert_eq!(
+            counts.get(ViolationKind::NetworkProtocol, ViolationSeverity::Error),
+            1
+        );
+        assert_eq!(
+            counts.get(ViolationKind::NetworkProtocol, ViolationSeverity::Critical),
+            0
+        );
+        assert_eq!(counts.of_kind(ViolationKind::NetworkProtocol), 4);
+        assert_eq!(counts.at_severity(ViolationSeverity::Critical), 2);
+        assert_eq!(counts.total(), 6);
+        assert_eq!(
+            counts.iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    ViolationKind::NetworkProtocol,
+                    ViolationSeverity::Warning,
+                    3
+                ),
+                (ViolationKind::NetworkProtocol, ViolationSeverity::Error, 1),
+                (ViolationKind::InternalError, ViolationSeverity::Critical, 2),
+            ]
+        );
+
+        observer.reset();
+        assert_eq!(observer.counts(), ViolationCounts::default());
+    }
+
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn test_global_violation_counts_include_routed_reports() {
+        let observer = Arc::new(CollectingObserver::new());
+        let kind = ViolationKind::ArithmeticOverflow;
+        let severity = ViolationSeverity::Error;
+        let before = violation_counts().get(kind, severity);
+        {
+            let _guard = push_violation_observer(observer.clone());
+            report_violation!(severity, kind, "scoped");
+        }
+        let silent: Option<Arc<dyn ViolationObserver>> = Some(Arc::new(CountingObserver::new()));
+        report_violation_to!(&silent, severity, kind, "explicit");
+
+        // Other tests report concurrently, so only a lower bound is exact.
+        assert!(violation_counts().get(kind, severity) >= before + 2);
+        assert_eq!(observer.len(), 1);
+    }
+
+    /// Scoped observers are shared across threads only with `sync-send`.
+    #[cfg(feature = "sync-send")]
+    #[test]
+    fn test_violation_counters_are_atomic_across_threads() {
+        use std::thread;
+
+        const THREADS: u64 = 8;
+        const REPORTS: u64 = 500;
+
+        let observer = Arc::new(CountingObserver::new());
+        let kind = ViolationKind::Synchronization;
+        let before = violation_counts().get(kind, ViolationSeverity::Critical);
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let observer: Arc<dyn ViolationObserver> = observer.clone();
+                thread::spawn(move || {
+                    let _guard = push_violation_observer(observer);
+                    for _ in 0..REPORTS {
+                        report_violation!(ViolationSeverity::Critical, kind, "concurrent");
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("Thread should not panic");
+        }
+
+        let counts = observer.counts();
+        assert_eq!(
+            counts.get(kind, ViolationSeverity::Critical),
+            THREADS * REPORTS
+        );
+        assert_eq!(counts.total(), THREADS * REPORTS);
+        assert!(
+            violation_counts().get(kind, ViolationSeverity::Critical) >= before + THREADS * REPORTS
+        );
+    }
+
     #[test]
     fn test_composite_observer() {
         let collector1 = Arc::new(CollectingObserver::new());
@@ -2628,6 +3082,7 @@ fn test_report_violation_to_macro_with_format() {
         // Just ensure it compiles and doesn't panic
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn test_report_violation_to_macro_accepts_named_format_args() {
         let observer = Arc::new(CollectingObserver::new());
@@ -2649,6 +3104,7 @@ fn test_report_violation_to_macro_accepts_named_format_args() {
         assert_eq!(violations[0].message, "mismatch: expected=10, actual=15");
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn test_report_violation_to_macro_accepts_macro_format_args() {
         macro_rules! sample_frame {
@@ -2694,6 +3150,7 @@ fn test_violation_kind_synchronization_as_str() {
         assert_eq!(ViolationKind::Synchronization.as_str(), "synchronization");
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn test_synchronization_violation_can_be_created() {
         let observer = Arc::new(CollectingObserver::new());
@@ -2724,6 +3181,7 @@ fn test_synchronization_violation_ordering() {
         assert!(ViolationKind::Invariant < ViolationKind::Synchronization);
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn test_synchronization_duration_warning() {
         let observer = Arc::new(CollectingObserver::new());
@@ -3332,6 +3790,7 @@ fn collecting_telemetry_network_stats_and_frame_advance_filters() {
     // change, `report_violation!` unconditionally targeted `TracingObserver`, so
     // the pushed observer received nothing — these are the red tests.
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn report_violation_routes_to_pushed_thread_local_observer() {
         let observer = Arc::new(CollectingObserver::new());
@@ -3356,6 +3815,46 @@ fn report_violation_routes_to_pushed_thread_local_observer() {
         );
     }
 
+    #[cfg(not(feature = "telemetry"))]
+    #[test]
+    fn without_telemetry_violations_are_counted_but_neither_formatted_nor_observed() {
+        let observer = Arc::new(CollectingObserver::new());
+        let observer_ref: Option<Arc<dyn ViolationObserver>> = Some(observer.clone());
+        let formatted = std::cell::Cell::new(0);
+        let argument = || {
+            formatted.set(formatted.get() + 1);
+            7
+        };
+        let count =
+            || violation_counts().get(ViolationKind::Configuration, ViolationSeverity::Critical);
+        let before = count();
+        {
+            let _guard = push_violation_observer(observer.clone());
+            report_violation!(
+                ViolationSeverity::Critical,
+                ViolationKind::Configuration,
+                "unformatted {}",
+                argument()
+            );
+            report_violation_to!(
+                &observer_ref,
+                ViolationSeverity::Critical,
+                ViolationKind::Configuration,
+                "unformatted {value}",
+                value = argument()
+            );
+            report_violation!(
+                ViolationSeverity::Critical,
+                ViolationKind::Configuration,
+                "unformatted"
+            );
+        }
+        assert!(count() >= before + 3);
+        assert_eq!(formatted.get(), 0, "format arguments must not be evaluated");
+        assert!(observer.is_empty());
+    }
+
+    #[cfg(feature = "telemetry")]
     #[test]
     fn report_violation_falls_back_to_tracing_when_no_observer_installed() {
         // With no observer pushed, the pushed-elsewhere observer must NOT receive
@@ -3384,6 +3883,7 @@ fn report_violation_falls_back_to_tracing_when_no_observer_installed() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn nested_scoped_observers_route_to_innermost_then_restore() {
         let outer = Arc::new(CollectingObserver::new());
@@ -3421,6 +3921,7 @@ fn nested_scoped_observers_route_to_innermost_then_restore() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn out_of_order_scoped_observer_drop_removes_matching_scope() {
         let outer = Arc::new(CollectingObserver::new());
@@ -3461,6 +3962,7 @@ fn out_of_order_scoped_observer_drop_removes_matching_scope() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn pushed_observer_is_thread_local_to_installing_thread() {
         let observer = Arc::new(CollectingObserver::new());
@@ -3488,6 +3990,7 @@ fn pushed_observer_is_thread_local_to_installing_thread() {
         );
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn observer_dropped_by_scope_can_report_without_refcell_panic() {
         struct DropReportingObserver;
@@ -3521,6 +4024,7 @@ fn drop(&mut self) {
         drop(outer_guard);
     }
 
+    #[cfg(feature = "telemetry")]
     #[test]
     fn reentrant_report_from_observer_does_not_deadlock() {
         use std::sync::atomic::{AtomicUsize, Ordering};
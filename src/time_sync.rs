@@ -1,3 +1,5 @@
+use web_time::Duration;
+
 use crate::error::allocation_failed;
 use crate::report_violation;
 use crate::telemetry::{ViolationKind, ViolationSeverity};
@@ -39,12 +41,28 @@ pub struct TimeSyncConfig {
     ///
     /// Default: 30 frames (0.5 seconds at 60 FPS)
     pub window_size: usize,
+    /// How many frames a P2P session may run ahead of its slowest connected
+    /// peer before [`advance_frame`](crate::P2PSession::advance_frame) refuses
+    /// to simulate.
+    ///
+    /// [`WaitRecommendation`](crate::FortressEvent::WaitRecommendation) is
+    /// advisory; this is the hard backstop for a game that ignores it. While
+    /// the lead exceeds the limit, `advance_frame` returns
+    /// [`FortressError::AheadOfRemotes`] and simulates nothing; the network is
+    /// still polled and local input is still accepted, so the session resumes
+    /// once the slowest peer catches up. The lead compares the newest local
+    /// input frame with the newest one received from the peer, aged by half
+    /// the round trip, so equal input delays cancel out.
+    ///
+    /// Default: `None` (no limit)
+    pub max_frames_ahead: Option<u32>,
 }
 
 impl Default for TimeSyncConfig {
     fn default() -> Self {
         Self {
             window_size: DEFAULT_FRAME_WINDOW_SIZE,
+            max_frames_ahead: None,
         }
     }
 }
@@ -52,8 +70,15 @@ fn default() -> Self {
 impl std::fmt::Display for TimeSyncConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Destructure to ensure all fields are included when new fields are added.
-        let Self { window_size } = self;
-        write!(f, "TimeSyncConfig {{ window_size: {} }}", window_size)
+        let Self {
+            window_size,
+            max_frames_ahead,
+        } = self;
+        write!(
+            f,
+            "TimeSyncConfig {{ window_size: {}, max_frames_ahead: {:?} }}",
+            window_size, max_frames_ahead
+        )
     }
 }
 
@@ -68,7 +93,10 @@ pub fn new() -> Self {
     /// Uses a smaller window to react quickly to network changes,
     /// at the cost of potentially more fluctuation in game speed.
     pub fn responsive() -> Self {
-        Self { window_size: 15 }
+        Self {
+            window_size: 15,
+            max_frames_ahead: None,
+        }
     }
 
     /// Configuration preset for smooth synchronization.
@@ -76,14 +104,20 @@ pub fn responsive() -> Self {
     /// Uses a larger window to provide stable, smooth synchronization,
     /// at the cost of slower adaptation to network changes.
     pub fn smooth() -> Self {
-        Self { window_size: 60 }
+        Self {
+            window_size: 60,
+            max_frames_ahead: None,
+        }
     }
 
     /// Configuration preset for LAN play.
     ///
     /// Uses a small window since LAN connections are typically stable.
     pub fn lan() -> Self {
-        Self { window_size: 10 }
+        Self {
+            window_size: 10,
+            max_frames_ahead: None,
+        }
     }
 
     /// Configuration preset for mobile/cellular networks.
@@ -95,7 +129,10 @@ pub fn lan() -> Self {
     /// Trade-off: Slower adaptation to actual network condition changes,
     /// but much smoother gameplay during normal mobile network variance.
     pub fn mobile() -> Self {
-        Self { window_size: 90 }
+        Self {
+            window_size: 90,
+            max_frames_ahead: None,
+        }
     }
 
     /// Configuration preset for competitive/esports scenarios.
@@ -104,7 +141,10 @@ pub fn mobile() -> Self {
     /// prioritizing accurate sync over smooth speed transitions.
     /// Assumes good, stable network conditions.
     pub fn competitive() -> Self {
-        Self { window_size: 20 }
+        Self {
+            window_size: 20,
+            max_frames_ahead: None,
+        }
     }
 }
 
@@ -236,6 +276,30 @@ pub fn average_frame_advantage(&self) -> i32 {
         })
     }
 
+    /// The mean of the local frame advantages in the window: how many frames
+    /// the remote peer was estimated to be ahead of the local one.
+    #[must_use]
+    pub(crate) fn local_average(&self) -> i32 {
+        Self::mean(self.local_sum, self.local.len())
+    }
+
+    /// The mean of the frame advantages the remote peer reported in the
+    /// window: how many frames it estimated the local peer to be ahead of it.
+    #[must_use]
+    pub(crate) fn remote_average(&self) -> i32 {
+        Self::mean(self.remote_sum, self.remote.len())
+    }
+
+    fn mean(sum: i128, len: usize) -> i32 {
+        let count = i128::try_from(len).unwrap_or(i128::MAX).max(1);
+        let mean = sum / count;
+        i32::try_from(mean).unwrap_or(if mean.is_negative() {
+            i32::MIN
+        } else {
+            i32::MAX
+        })
+    }
+
     /// Test-only: deterministically seeds the rolling window so that
     /// [`average_frame_advantage`](Self::average_frame_advantage) returns exactly
     /// `target`.
@@ -259,6 +323,61 @@ pub(crate) fn seed_average_for_tests(&mut self, target: i32) {
     }
 }
 
+// ##########################
+// # FRAME/TIME CONVERSIONS #
+// ##########################
+
+// Frames and durations meet wherever the protocol turns a measured time (a
+// round trip, a frame period) into frames, and a frame at 12 or 24 FPS is long
+// enough that truncating a fraction of one biases every estimate toward zero.
+// These conversions work in nanoseconds times frames, exactly in integers, and
+// round only once at the end. They feed timing estimates, never the simulation.
+
+/// Nanoseconds in a second.
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// `numerator / denominator` rounded to the nearest integer, ties to even, so
+/// rounding errors cancel out over many conversions instead of drifting.
+fn div_round_half_even(numerator: u128, denominator: u128) -> u128 {
+    let denominator = denominator.max(1);
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    match remainder.cmp(&(denominator - remainder)) {
+        std::cmp::Ordering::Less => quotient,
+        std::cmp::Ordering::Greater => quotient.saturating_add(1),
+        std::cmp::Ordering::Equal => quotient.saturating_add(quotient % 2),
+    }
+}
+
+/// `duration` times `fps`, in nanosecond-frames.
+fn nano_frames(duration: Duration, fps: usize) -> u128 {
+    duration
+        .as_nanos()
+        .saturating_mul(u128::try_from(fps.max(1)).unwrap_or(u128::MAX))
+}
+
+/// The duration of `frames` frames at `fps`, rounded to the nearest
+/// nanosecond (ties to even). Saturates at `u64::MAX` nanoseconds.
+pub(crate) fn frames_to_duration(frames: u64, fps: usize) -> Duration {
+    let fps = u128::try_from(fps.max(1)).unwrap_or(u128::MAX);
+    let nanos = div_round_half_even(u128::from(frames).saturating_mul(NANOS_PER_SECOND), fps);
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+/// How many frames at `fps` fit in `duration`, rounded to the nearest frame
+/// (ties to even). Saturates at `u64::MAX`.
+pub(crate) fn duration_to_frames(duration: Duration, fps: usize) -> u64 {
+    let frames = div_round_half_even(nano_frames(duration, fps), NANOS_PER_SECOND);
+    u64::try_from(frames).unwrap_or(u64::MAX)
+}
+
+/// How many frames at `fps` it takes to cover `duration`: like
+/// [`duration_to_frames`], but any fraction of a frame counts as a whole one.
+pub(crate) fn duration_to_frames_ceil(duration: Duration, fps: usize) -> u64 {
+    let frames = nano_frames(duration, fps).div_ceil(NANOS_PER_SECOND);
+    u64::try_from(frames).unwrap_or(u64::MAX)
+}
+
 // #########
 // # TESTS #
 // #########
@@ -342,6 +461,61 @@ fn test_advance_frame_big_remote_advantage() {
         assert_eq!(time_sync.average_frame_advantage(), 40);
     }
 
+    /// Frame periods at common fps, exact to the nanosecond with ties to
+    /// even, and conversions back that neither truncate nor drift.
+    #[test]
+    fn frame_time_conversions_round_to_nearest() {
+        let ms = Duration::from_millis;
+        for (frames, fps, nanos) in [
+            (1, 12, 83_333_333),
+            (1, 24, 41_666_667),
+            (1, 30, 33_333_333),
+            (1, 60, 16_666_667),
+            (1, 144, 6_944_444),
+            (3, 144, 20_833_333),
+            (1, 240, 4_166_667),
+            (0, 60, 0),
+        ] {
+            assert_eq!(
+                frames_to_duration(frames, fps),
+                Duration::from_nanos(nanos),
+                "{frames} frames at {fps} fps"
+            );
+        }
+        for fps in [12, 24, 30, 60, 144, 240] {
+            for frames in [0, 1, 2, 7, 1000] {
+                let duration = frames_to_duration(frames, fps);
+                assert_eq!(duration_to_frames(duration, fps), frames, "{fps} fps");
+            }
+        }
+        // (duration, fps, nearest, ceil)
+        for (duration, fps, nearest, ceil) in [
+            (ms(50), 12, 1, 1),  // 0.6
+            (ms(30), 24, 1, 1),  // 0.72
+            (ms(10), 30, 0, 1),  // 0.3
+            (ms(125), 12, 2, 2), // 1.5, tie to even
+            (ms(375), 12, 4, 5), // 4.5, tie to even
+            (ms(5), 144, 1, 1),  // 0.72
+            (ms(2), 240, 0, 1),  // 0.48
+        ] {
+            assert_eq!(
+                duration_to_frames(duration, fps),
+                nearest,
+                "{duration:?} at {fps}"
+            );
+            assert_eq!(
+                duration_to_frames_ceil(duration, fps),
+                ceil,
+                "{duration:?} at {fps}"
+            );
+        }
+        assert_eq!(duration_to_frames(Duration::MAX, 240), u64::MAX);
+        assert_eq!(
+            frames_to_duration(u64::MAX, 1),
+            Duration::from_nanos(u64::MAX)
+        );
+    }
+
     #[test]
     fn test_new_creates_default() {
         let time_sync = TimeSync::new();
@@ -369,7 +543,10 @@ fn test_window_sliding_behavior() {
 
     #[test]
     fn test_cached_sums_track_rolling_overwrites() {
-        let mut time_sync = TimeSync::with_config(TimeSyncConfig { window_size: 3 });
+        let mut time_sync = TimeSync::with_config(TimeSyncConfig {
+            window_size: 3,
+            ..TimeSyncConfig::default()
+        });
         let samples = [(0, 4, -2), (1, -3, 7), (2, 5, 9), (3, -8, 6), (4, 2, -4)];
 
         for (frame, local, remote) in samples {
@@ -423,6 +600,29 @@ fn test_asymmetric_advantages() {
         assert_eq!(time_sync.average_frame_advantage(), 3);
     }
 
+    #[test]
+    fn test_local_and_remote_averages() {
+        let mut time_sync = TimeSync::default();
+        assert_eq!(
+            (time_sync.local_average(), time_sync.remote_average()),
+            (0, 0)
+        );
+
+        // Only fill half the window: the averages are diluted by the zeros
+        // in the other half, like `average_frame_advantage`.
+        for i in 0..(FRAME_WINDOW_SIZE / 2) {
+            time_sync.advance_frame(Frame::new(i as i32), 8, -8);
+        }
+        assert_eq!(time_sync.local_average(), 4);
+        assert_eq!(time_sync.remote_average(), -4);
+
+        for i in 0..FRAME_WINDOW_SIZE {
+            time_sync.advance_frame(Frame::new(i as i32), 7, -7);
+        }
+        assert_eq!(time_sync.local_average(), 7);
+        assert_eq!(time_sync.remote_average(), -7);
+    }
+
     #[test]
     fn test_frame_wraparound_modulo() {
         let mut time_sync = TimeSync::default();
@@ -454,6 +654,32 @@ fn test_advance_frame_null_frame_skipped() {
         assert_eq!(time_sync.local[0], 10); // unchanged
     }
 
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn test_advance_frame_invalid_frame_counts_one_frame_sync_warning() {
+        use crate::telemetry::{
+            push_violation_observer, CountingObserver, ViolationKind, ViolationObserver,
+            ViolationSeverity,
+        };
+        use std::sync::Arc;
+
+        let mut time_sync = TimeSync::default();
+        let observer = Arc::new(CountingObserver::new());
+        let _guard = push_violation_observer(Arc::clone(&observer) as Arc<dyn ViolationObserver>);
+
+        time_sync.advance_frame(Frame::new(0), 10, 20);
+        assert_eq!(observer.counts().total(), 0);
+        time_sync.advance_frame(Frame::NULL, 99, 99);
+        time_sync.advance_frame(Frame::new(-5), 99, 99);
+
+        let counts = observer.counts();
+        assert_eq!(
+            counts.get(ViolationKind::FrameSync, ViolationSeverity::Warning),
+            2
+        );
+        assert_eq!(counts.total(), 2);
+    }
+
     #[test]
     fn test_advance_frame_negative_frame_skipped() {
         let mut time_sync = TimeSync::default();
@@ -559,11 +785,23 @@ fn test_config_presets_data_driven() {
 
     #[test]
     fn test_time_sync_config_display() {
-        let config = TimeSyncConfig { window_size: 30 };
-        assert_eq!(config.to_string(), "TimeSyncConfig { window_size: 30 }");
+        let config = TimeSyncConfig {
+            window_size: 30,
+            max_frames_ahead: None,
+        };
+        assert_eq!(
+            config.to_string(),
+            "TimeSyncConfig { window_size: 30, max_frames_ahead: None }"
+        );
 
-        let config = TimeSyncConfig { window_size: 60 };
-        assert_eq!(config.to_string(), "TimeSyncConfig { window_size: 60 }");
+        let config = TimeSyncConfig {
+            window_size: 60,
+            max_frames_ahead: Some(8),
+        };
+        assert_eq!(
+            config.to_string(),
+            "TimeSyncConfig { window_size: 60, max_frames_ahead: Some(8) }"
+        );
     }
 
     // ==========================================================================
@@ -573,7 +811,10 @@ fn test_time_sync_config_display() {
     /// Test window_size of 0 is corrected to 1
     #[test]
     fn test_window_size_zero_corrected_to_one() {
-        let config = TimeSyncConfig { window_size: 0 };
+        let config = TimeSyncConfig {
+            window_size: 0,
+            ..TimeSyncConfig::default()
+        };
         let ts = TimeSync::with_config(config);
 
         assert_eq!(ts.window_size, 1, "Window size 0 should be corrected to 1");
@@ -585,6 +826,7 @@ fn test_window_size_zero_corrected_to_one() {
     fn try_with_config_reports_allocation_failure_for_impossible_window() {
         let err = TimeSync::try_with_config(TimeSyncConfig {
             window_size: usize::MAX,
+            ..TimeSyncConfig::default()
         })
         .unwrap_err();
 
@@ -602,7 +844,10 @@ fn try_with_config_reports_allocation_failure_for_impossible_window() {
     /// Test window_size of 1 (minimum valid)
     #[test]
     fn test_window_size_minimum_one() {
-        let config = TimeSyncConfig { window_size: 1 };
+        let config = TimeSyncConfig {
+            window_size: 1,
+            ..TimeSyncConfig::default()
+        };
         let mut ts = TimeSync::with_config(config);
 
         // With window size 1, every frame overwrites the same index
@@ -741,7 +986,7 @@ fn prop_window_index_in_bounds(
             remote_adv in advantage_value(),
             window_size in window_size(),
         ) {
-            let config = TimeSyncConfig { window_size };
+            let config = TimeSyncConfig { window_size, ..TimeSyncConfig::default() };
             let mut ts = TimeSync::with_config(config);
 
             // This should not panic due to out-of-bounds access
@@ -863,7 +1108,7 @@ fn prop_negative_frame_no_effect(
         /// Older values should be overwritten as new frames advance beyond the window.
         #[test]
         fn prop_window_slides(window_size in 5..50usize) {
-            let config = TimeSyncConfig { window_size };
+            let config = TimeSyncConfig { window_size, ..TimeSyncConfig::default() };
             let mut ts = TimeSync::with_config(config);
 
             // Fill window with local advantage = 10
@@ -913,7 +1158,7 @@ fn prop_average_formula_correct(
         /// Property: Custom window size is respected.
         #[test]
         fn prop_custom_window_size_respected(window_size in 1..100usize) {
-            let config = TimeSyncConfig { window_size };
+            let config = TimeSyncConfig { window_size, ..TimeSyncConfig::default() };
             let ts = TimeSync::with_config(config);
 
             prop_assert_eq!(ts.window_size, window_size);
@@ -1032,7 +1277,10 @@ fn proof_advance_frame_safe() {
         kani::assume(local_adv >= -5 && local_adv <= 5);
         kani::assume(remote_adv >= -5 && remote_adv <= 5);
 
-        let config = TimeSyncConfig { window_size: 4 };
+        let config = TimeSyncConfig {
+            window_size: 4,
+            ..TimeSyncConfig::default()
+        };
         let mut ts = TimeSync::with_config(config);
 
         // This should not panic
@@ -1062,7 +1310,10 @@ fn proof_window_size_minimum() {
         let window_size: usize = kani::any();
         kani::assume(window_size <= 8);
         // Even if user passes 0, it should be corrected
-        let config = TimeSyncConfig { window_size };
+        let config = TimeSyncConfig {
+            window_size,
+            ..TimeSyncConfig::default()
+        };
         let ts = TimeSync::with_config(config);
 
         kani::assert(ts.window_size >= 1, "Window size must be at least 1");
@@ -1156,7 +1407,10 @@ fn proof_preset_configs_valid() {
     /// - Related: proof_window_size_minimum, proof_division_safe
     #[kani::proof]
     fn proof_zero_window_size_corrected() {
-        let config = TimeSyncConfig { window_size: 0 };
+        let config = TimeSyncConfig {
+            window_size: 0,
+            ..TimeSyncConfig::default()
+        };
         let ts = TimeSync::with_config(config);
 
         kani::assert(ts.window_size == 1, "window_size 0 must be corrected to 1");
@@ -1223,7 +1477,10 @@ fn proof_negative_frame_safe() {
         kani::assume(local_adv >= -10 && local_adv <= 10);
         kani::assume(remote_adv >= -10 && remote_adv <= 10);
 
-        let config = TimeSyncConfig { window_size: 4 };
+        let config = TimeSyncConfig {
+            window_size: 4,
+            ..TimeSyncConfig::default()
+        };
         let mut ts = TimeSync::with_config(config);
 
         // This should not panic even with negative frame
@@ -0,0 +1,247 @@
+//! `VecMap`: an ordered map backed by a sorted [`Vec`], for the small maps the
+//! session and protocol refill every frame.
+//!
+//! # Why this exists
+//!
+//! A [`BTreeMap`](std::collections::BTreeMap) allocates a node on the first
+//! insert into an empty map, frees it again on `clear`, and splits and merges
+//! nodes as a sliding frame window moves through it. For the per-frame maps on
+//! the `poll_remote_clients` / `advance_frame` path that is a steady trickle of
+//! allocations. A `VecMap` keeps its entries in one buffer whose capacity
+//! survives [`clear`](VecMap::clear), [`remove`](VecMap::remove),
+//! [`retain`](VecMap::retain), and [`pop_first`](VecMap::pop_first), so a map
+//! stops allocating once it reaches its high-water mark, or immediately when it
+//! is reserved up front (see `SessionBuilder::with_preallocated_buffers`).
+//!
+//! Lookups are a binary search; inserting or removing shifts the tail, which is
+//! cheap for the few dozen entries these maps hold. Iteration is in ascending
+//! key order, exactly like the `BTreeMap` it replaces, so determinism is
+//! unaffected.
+
+use std::collections::TryReserveError;
+use std::fmt;
+use std::ops::Index;
+
+/// An ordered map stored as a `Vec` of entries sorted by key.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct VecMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> Default for VecMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for VecMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
+
+impl<K, V> VecMap<K, V> {
+    /// Creates an empty map without allocating.
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Reserves room for at least `additional` more entries.
+    pub(crate) fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.entries.try_reserve(additional)
+    }
+
+    /// Number of entries the map holds without reallocating.
+    #[cfg(test)]
+    pub(crate) fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every entry, keeping the allocation.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterates over the entries in ascending key order.
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// Iterates over the keys in ascending order.
+    pub(crate) fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    /// The entry with the smallest key.
+    pub(crate) fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.first().map(|(key, value)| (key, value))
+    }
+
+    /// Removes and returns the entry with the smallest key.
+    pub(crate) fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.entries.is_empty() {
+            None
+        } else {
+            Some(self.entries.remove(0))
+        }
+    }
+
+    /// Keeps only the entries for which `keep` returns `true`.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&K, &mut V) -> bool) {
+        self.entries.retain_mut(|(key, value)| keep(key, value));
+    }
+}
+
+impl<K: Ord, V> VecMap<K, V> {
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(probe, _)| probe.cmp(key))
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    ///
+    /// Appending a key larger than every present key (the common case for a
+    /// frame-keyed window) never shifts entries.
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.entries.last().is_none_or(|(last, _)| *last < key) {
+            self.entries.push((key, value));
+            return None;
+        }
+        match self.search(&key) {
+            Ok(index) => self
+                .entries
+                .get_mut(index)
+                .map(|(_, slot)| std::mem::replace(slot, value)),
+            Err(index) => {
+                self.entries.insert(index, (key, value));
+                None
+            },
+        }
+    }
+
+    pub(crate) fn get(&self, key: &K) -> Option<&V> {
+        let index = self.search(key).ok()?;
+        self.entries.get(index).map(|(_, value)| value)
+    }
+
+    pub(crate) fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.search(key).ok()?;
+        self.entries.get_mut(index).map(|(_, value)| value)
+    }
+
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        self.search(key).is_ok()
+    }
+
+    /// Removes the entry under `key`, returning its value.
+    pub(crate) fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.search(key).ok()?;
+        Some(self.entries.remove(index).1)
+    }
+}
+
+impl<K: Ord, V> Index<&K> for VecMap<K, V> {
+    type Output = V;
+
+    /// # Panics
+    ///
+    /// Panics if `key` is absent, like `BTreeMap`'s `Index`. Production code
+    /// uses [`VecMap::get`]; this exists for test assertions.
+    #[allow(clippy::panic)]
+    fn index(&self, index: &K) -> &V {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!("VecMap: key not present"),
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a VecMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = std::iter::Map<std::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for VecMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn insert_keeps_keys_sorted_and_replaces_duplicates() {
+        let mut map = VecMap::new();
+        assert_eq!(map.insert(5, "five"), None);
+        assert_eq!(map.insert(1, "one"), None);
+        assert_eq!(map.insert(3, "three"), None);
+        assert_eq!(map.insert(3, "THREE"), Some("three"));
+
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![1, 3, 5]);
+        assert_eq!(map.get(&3), Some(&"THREE"));
+        assert_eq!(map[&5], "five");
+        assert!(!map.contains_key(&2));
+        assert_eq!(map.first_key_value(), Some((&1, &"one")));
+    }
+
+    #[test]
+    fn removal_keeps_the_allocation() {
+        let mut map = VecMap::new();
+        map.try_reserve(8).unwrap();
+        let capacity = map.capacity();
+        for key in 0..8 {
+            map.insert(key, key * 10);
+        }
+        assert_eq!(map.pop_first(), Some((0, 0)));
+        assert_eq!(map.remove(&4), Some(40));
+        map.retain(|&key, _| key > 2);
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![3, 5, 6, 7]);
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.pop_first(), None);
+        assert_eq!(map.capacity(), capacity);
+    }
+
+    #[test]
+    fn matches_btree_map_for_any_operation_sequence() {
+        let mut map = VecMap::new();
+        let mut reference = BTreeMap::new();
+        let mut state = 0x2545_f491_u32;
+        for _ in 0..2_000 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let key = state % 32;
+            match state % 3 {
+                0 => assert_eq!(map.remove(&key), reference.remove(&key)),
+                _ => assert_eq!(map.insert(key, state), reference.insert(key, state)),
+            }
+            assert_eq!(map.len(), reference.len());
+        }
+        assert!(map.iter().eq(reference.iter()));
+    }
+}
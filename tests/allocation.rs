@@ -0,0 +1,194 @@
+//! Allocation tests for [`SessionBuilder::with_preallocated_buffers`].
+//!
+//! A counting global allocator records every allocation made on the test's
+//! own thread while counting is armed. It lives in its own test binary so the
+//! allocator cannot disturb the other integration tests.
+//!
+//! The measured region is the steady-state loop a game drives every frame:
+//! `poll_remote_clients`, draining `events`, `add_local_input`, and
+//! `advance_frame`. Fulfilling the returned requests stays outside it (saving
+//! clones the user's state), and so does the socket, whose receive API hands
+//! out a fresh `Vec` by design.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+#[path = "common/mod.rs"]
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::net::SocketAddr;
+
+use common::stubs::{GameStub, StubConfig, StubInput};
+use common::{
+    create_channel_pair, synchronize_sessions_deterministic, ChannelSocket, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    DesyncDetection, FortressError, Message, NonBlockingSocket, P2PSession, PlayerHandle,
+    PlayerType, ProtocolConfig, SessionBuilder,
+};
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Counts allocations and reallocations on threads that armed [`COUNTING`].
+struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record() {
+        // `try_with`: the thread-locals are gone while a thread shuts down.
+        if COUNTING.try_with(Cell::get).unwrap_or(false) {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        }
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to the system allocator.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record();
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record();
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record();
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` with allocation counting paused.
+fn uncounted<R>(f: impl FnOnce() -> R) -> R {
+    let armed = COUNTING.with(|counting| counting.replace(false));
+    let result = f();
+    COUNTING.with(|counting| counting.set(armed));
+    result
+}
+
+/// Runs `f`, returning its result and the allocations it made on this thread.
+fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, u64) {
+    let before = ALLOCATIONS.with(Cell::get);
+    COUNTING.with(|counting| counting.set(true));
+    let result = f();
+    COUNTING.with(|counting| counting.set(false));
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+/// A [`ChannelSocket`] whose own work (encoding, decoding, and the received
+/// `Vec`) is excluded from the count.
+struct UncountedSocket(ChannelSocket);
+
+impl NonBlockingSocket<SocketAddr> for UncountedSocket {
+    fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        uncounted(|| self.0.send_to(msg, addr));
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+        uncounted(|| self.0.receive_all_messages())
+    }
+}
+
+fn session_pair(
+    clock: &TestClock,
+    preallocated_buffers: bool,
+) -> Result<(P2PSession<StubConfig>, P2PSession<StubConfig>), FortressError> {
+    let builder = || {
+        SessionBuilder::<StubConfig>::new()
+            .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+            .with_preallocated_buffers(preallocated_buffers)
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            })
+    };
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let sess1 = builder()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(UncountedSocket(s1))?;
+    let sess2 = builder()
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(UncountedSocket(s2))?;
+    Ok((sess1, sess2))
+}
+
+/// Plays `frames` frames with inputs that change at different rates on each
+/// peer, so predictions miss and the sessions roll back. Returns the
+/// allocations made inside the per-frame session calls, and both final states.
+fn play(
+    sess1: &mut P2PSession<StubConfig>,
+    sess2: &mut P2PSession<StubConfig>,
+    clock: &TestClock,
+    frames: u32,
+) -> Result<(u64, GameStub, GameStub), FortressError> {
+    let mut stub1 = GameStub::new();
+    let mut stub2 = GameStub::new();
+    let mut allocations = 0;
+    for frame in 0..frames {
+        let (requests, count) = count_allocations(|| -> Result<_, FortressError> {
+            sess1.poll_remote_clients();
+            sess2.poll_remote_clients();
+            for _ in sess1.events() {}
+            for _ in sess2.events() {}
+            sess1.add_local_input(PlayerHandle::new(0), StubInput { inp: frame / 7 })?;
+            sess2.add_local_input(PlayerHandle::new(1), StubInput { inp: frame / 3 })?;
+            Ok((sess1.advance_frame()?, sess2.advance_frame()?))
+        });
+        allocations += count;
+        let (requests1, requests2) = requests?;
+        stub1.handle_requests(requests1);
+        stub2.handle_requests(requests2);
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    Ok((allocations, stub1, stub2))
+}
+
+#[test]
+fn steady_state_loop_does_not_allocate_with_preallocated_buffers() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut sess1, mut sess2) = session_pair(&clock, true)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())?;
+
+    let (allocations, _, _) = play(&mut sess1, &mut sess2, &clock, 1_000)?;
+
+    assert_eq!(allocations, 0, "allocations over 1000 synchronized frames");
+    assert!(
+        sess1.last_rollback_cause().is_some(),
+        "the run never rolled back"
+    );
+    Ok(())
+}
+
+#[test]
+fn preallocated_buffers_do_not_change_the_simulation() -> Result<(), FortressError> {
+    let mut states = Vec::new();
+    for preallocated_buffers in [false, true] {
+        let clock = TestClock::new();
+        let (mut sess1, mut sess2) = session_pair(&clock, preallocated_buffers)?;
+        synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())?;
+        let (_, stub1, stub2) = play(&mut sess1, &mut sess2, &clock, 300)?;
+        states.push((stub1.gs, stub2.gs, sess1.confirmed_frame()));
+    }
+    assert_eq!(states[0], states[1]);
+    Ok(())
+}
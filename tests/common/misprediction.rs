@@ -0,0 +1,158 @@
+//! Two synchronized peers that force a misprediction on the first one.
+//!
+//! [`MispredictionHarness`] runs both peers in lockstep for
+//! [`WARMUP_FRAMES`], lets session 1 run ahead predicting session 2's input,
+//! then lets session 2 catch up with different inputs, so session 1's next
+//! `advance_frame` rolls back. Session 1 owns handle 0 and session 2 handle 1.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use super::test_utils::{
+    drain_sync_events, poll_with_advance, protocol_config, start_two_peer_session,
+    synchronize_sessions_deterministic, SyncConfig,
+};
+use super::{create_channel_pair, TestClock};
+use fortress_rollback::telemetry::CollectingObserver;
+use fortress_rollback::{
+    DesyncDetection, FortressError, P2PSession, PlayerHandle, RequestVec, SessionBuilder,
+};
+
+/// Frames both peers simulate in lockstep before the divergence.
+#[allow(dead_code)]
+pub const WARMUP_FRAMES: u32 = 5;
+
+/// Two synchronized sessions on virtual time, with every (re)simulated state
+/// of session 1 recorded.
+#[allow(dead_code)]
+pub struct MispredictionHarness {
+    pub clock: TestClock,
+    pub sess1: P2PSession<StubConfig>,
+    pub sess2: P2PSession<StubConfig>,
+    pub stub1: GameStub,
+    pub stub2: GameStub,
+    /// The state session 1 reached after each frame, keyed by the next frame.
+    pub states1: BTreeMap<i32, StateStub>,
+    /// Violations reported by session 1.
+    pub observer1: Arc<CollectingObserver>,
+}
+
+#[allow(dead_code)]
+impl MispredictionHarness {
+    /// Starts and synchronizes both sessions with desync detection off.
+    /// `configure` finishes each builder and is given the handle of its
+    /// local player.
+    #[allow(clippy::expect_used)]
+    pub fn new(
+        configure: impl Fn(
+            SessionBuilder<StubConfig>,
+            usize,
+        ) -> Result<SessionBuilder<StubConfig>, FortressError>,
+    ) -> Result<Self, FortressError> {
+        let clock = TestClock::new();
+        let (s1, s2, a1, a2) = create_channel_pair();
+        let observer1 = Arc::new(CollectingObserver::new());
+        let builder = |local: usize| {
+            let builder = SessionBuilder::<StubConfig>::new()
+                .with_protocol_config(protocol_config(&clock))
+                .with_desync_detection_mode(DesyncDetection::Off);
+            let builder = if local == 0 {
+                builder.with_violation_observer(observer1.clone())
+            } else {
+                builder
+            };
+            configure(builder, local)
+        };
+        let mut sess1 = start_two_peer_session(builder(0)?, 0, a2, s1)?;
+        let mut sess2 = start_two_peer_session(builder(1)?, 1, a1, s2)?;
+        synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+            .expect("sessions should synchronize");
+        drain_sync_events(&mut sess1, &mut sess2);
+
+        Ok(Self {
+            clock,
+            sess1,
+            sess2,
+            stub1: GameStub::new(),
+            stub2: GameStub::new(),
+            states1: BTreeMap::new(),
+            observer1,
+        })
+    }
+
+    /// Advances session 1 with `inp` and returns the requests unhandled.
+    pub fn request1(&mut self, inp: u32) -> Result<RequestVec<StubConfig>, FortressError> {
+        self.sess1
+            .add_local_input(PlayerHandle::new(0), StubInput { inp })?;
+        self.sess1.advance_frame()
+    }
+
+    /// Advances session 1 with `inp` and handles the requests.
+    pub fn advance1(&mut self, inp: u32) -> Result<(), FortressError> {
+        let requests = self.request1(inp)?;
+        self.handle1(requests);
+        Ok(())
+    }
+
+    /// Hands requests of session 1 to `stub1`, recording the states.
+    pub fn handle1(&mut self, requests: RequestVec<StubConfig>) {
+        self.stub1
+            .handle_requests_recording(requests, &mut self.states1);
+    }
+
+    pub fn advance2(&mut self, inp: u32) -> Result<(), FortressError> {
+        self.sess2
+            .add_local_input(PlayerHandle::new(1), StubInput { inp })?;
+        let requests = self.sess2.advance_frame()?;
+        self.stub2.handle_requests(requests);
+        Ok(())
+    }
+
+    /// Polls both sessions `iterations` times, advancing the clock.
+    pub fn poll(&mut self, iterations: usize) {
+        poll_with_advance(&mut self.sess1, &mut self.sess2, &self.clock, iterations);
+    }
+
+    /// Runs the warm-up, then lets session 1 run `depth` frames ahead
+    /// predicting session 2's input. `advance1` advances session 1 on the
+    /// given frame.
+    pub fn run_ahead(
+        &mut self,
+        depth: u32,
+        mut advance1: impl FnMut(&mut Self, u32) -> Result<(), FortressError>,
+    ) -> Result<(), FortressError> {
+        for frame in 0..WARMUP_FRAMES {
+            self.poll(1);
+            advance1(self, frame)?;
+            self.advance2(0)?;
+        }
+        self.poll(2);
+        for frame in WARMUP_FRAMES..WARMUP_FRAMES + depth {
+            advance1(self, frame)?;
+        }
+        Ok(())
+    }
+
+    /// Lets session 2 play the `depth` frames session 1 ran ahead with
+    /// `input2(frame)` and delivers them: session 1 has received the
+    /// mispredicted inputs but not yet rolled back.
+    pub fn catch_up(
+        &mut self,
+        depth: u32,
+        input2: impl Fn(u32) -> u32,
+    ) -> Result<(), FortressError> {
+        for frame in WARMUP_FRAMES..WARMUP_FRAMES + depth {
+            self.advance2(input2(frame))?;
+        }
+        self.poll(2);
+        Ok(())
+    }
+
+    /// Runs ahead `depth` frames with session 1 always playing 0, and catches
+    /// up with session 2 playing 1.
+    pub fn force_misprediction(&mut self, depth: u32) -> Result<(), FortressError> {
+        self.run_ahead(depth, |run, _| run.advance1(0))?;
+        self.catch_up(depth, |_| 1)
+    }
+}
@@ -3,6 +3,8 @@
 //! This module provides:
 //! - `stubs`: Game stub implementations with struct-based inputs
 //! - `stubs_enum`: Game stub implementations with enum-based inputs
+//! - `misprediction`: Two synchronized peers that force a rollback
+//! - `rollback_pair`: Two synchronized peers with scripted (mis)predicted rounds
 //! - `test_utils`: Shared constants, helpers, and synchronization utilities
 //!
 //! # Usage
@@ -19,7 +21,9 @@
 pub mod bus_socket;
 pub mod channel_socket;
 pub mod filter_socket;
+pub mod misprediction;
 pub mod reorder_socket;
+pub mod rollback_pair;
 pub mod sim_net;
 pub mod stubs;
 pub mod stubs_enum;
@@ -48,9 +52,10 @@
 pub use test_clock::TestClock;
 #[allow(unused_imports)]
 pub use test_utils::{
-    assert_spectator_synchronized, bind_socket_ephemeral, bind_socket_with_retry, calculate_hash,
-    create_chaos_socket, drain_sync_events, poll_with_advance,
-    run_p2p_frame_advancement_test_deterministic, run_synctest_with_delayed_input,
+    add_mesh_players, advance_local, assert_spectator_synchronized, bind_socket_ephemeral,
+    bind_socket_with_retry, calculate_hash, create_chaos_socket, drain_sync_events,
+    poll_with_advance, protocol_config, run_p2p_frame_advancement_test_deterministic,
+    run_synctest_with_delayed_input, start_two_peer_session, step_peers,
     synchronize_sessions_deterministic, synchronize_spectator, synchronize_spectator_deterministic,
     test_addr, GameStubHandler, PortAllocator, SyncConfig, SyncResult, MAX_SYNC_ITERATIONS,
     POLL_INTERVAL, POLL_INTERVAL_DETERMINISTIC, SYNC_TIMEOUT,
@@ -0,0 +1,149 @@
+//! Two synchronized peers whose rounds are scripted to predict or mispredict.
+//!
+//! [`Pair`] inspects only the `host`, which plays handle 0 with a constant
+//! input of 1; the `peer` plays handle 1 with whatever input a test scripts.
+//! Rounds in which the peer advances first are predicted correctly, while
+//! [`Pair::diverge`] lets the host run ahead so it rolls back on its next
+//! advance.
+
+use super::stubs::{GameStub, StubConfig, StubInput};
+use super::test_utils::{
+    add_mesh_players, protocol_config, start_two_peer_session, synchronize_sessions_deterministic,
+    SyncConfig, POLL_INTERVAL_DETERMINISTIC,
+};
+use super::{create_channel_pair, ChannelSocket, TestClock};
+use fortress_rollback::{
+    FortressError, FortressRequest, Frame, P2PSession, PlayerHandle, PlayerType, SessionBuilder,
+};
+use std::net::SocketAddr;
+
+/// The peer's player handle.
+#[allow(dead_code)]
+pub const PEER_HANDLE: usize = 1;
+
+/// Two synchronized peers; only `host` is inspected.
+#[allow(dead_code)]
+pub struct Pair {
+    pub host: P2PSession<StubConfig>,
+    pub peer: P2PSession<StubConfig>,
+    pub stubs: [GameStub; 2],
+    pub clock: TestClock,
+    /// Handles the host enters input for.
+    host_handles: Vec<PlayerHandle>,
+}
+
+#[allow(dead_code)]
+impl Pair {
+    /// Starts and synchronizes a two-player match. `configure` finishes both
+    /// builders.
+    pub fn new(
+        configure: impl Fn(SessionBuilder<StubConfig>) -> SessionBuilder<StubConfig>,
+    ) -> Self {
+        Self::start(&[0], |builder, local, remote, socket| {
+            start_two_peer_session(configure(builder), local, remote, socket)
+        })
+    }
+
+    /// Like [`Pair::new`], with a third player, handle 2, local to the host.
+    pub fn with_second_host_player(
+        configure: impl Fn(SessionBuilder<StubConfig>) -> SessionBuilder<StubConfig>,
+    ) -> Self {
+        Self::start(&[0, 2], |builder, local, remote, socket| {
+            let builder =
+                add_mesh_players(configure(builder).with_num_players(3)?, local, &[remote; 2])?;
+            let third = if local == 0 {
+                PlayerType::Local
+            } else {
+                PlayerType::Remote(remote)
+            };
+            builder
+                .add_player(third, PlayerHandle::new(2))?
+                .start_p2p_session(socket)
+        })
+    }
+
+    #[allow(clippy::expect_used)]
+    fn start(
+        host_handles: &[usize],
+        session: impl Fn(
+            SessionBuilder<StubConfig>,
+            usize,
+            SocketAddr,
+            ChannelSocket,
+        ) -> Result<P2PSession<StubConfig>, FortressError>,
+    ) -> Self {
+        let clock = TestClock::new();
+        let (s1, s2, a1, a2) = create_channel_pair();
+        let builder =
+            || SessionBuilder::<StubConfig>::new().with_protocol_config(protocol_config(&clock));
+        let mut host = session(builder(), 0, a2, s1).expect("host session should start");
+        let mut peer = session(builder(), PEER_HANDLE, a1, s2).expect("peer session should start");
+        synchronize_sessions_deterministic(&mut host, &mut peer, &clock, &SyncConfig::default())
+            .expect("sessions should synchronize");
+        Self {
+            host,
+            peer,
+            stubs: [GameStub::new(), GameStub::new()],
+            clock,
+            host_handles: host_handles
+                .iter()
+                .copied()
+                .map(PlayerHandle::new)
+                .collect(),
+        }
+    }
+
+    /// Advances the host with input 1 for each of its players and returns
+    /// whether it rolled back.
+    #[allow(clippy::expect_used)]
+    pub fn advance_host(&mut self) -> bool {
+        self.host.poll_remote_clients();
+        for handle in &self.host_handles {
+            self.host
+                .add_local_input(*handle, StubInput { inp: 1 })
+                .expect("host input should be accepted");
+        }
+        let requests = self.host.advance_frame().expect("host should advance");
+        let rolled_back = requests
+            .iter()
+            .any(|request| matches!(request, FortressRequest::LoadGameState { .. }));
+        self.stubs[0].handle_requests(requests);
+        self.clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        rolled_back
+    }
+
+    /// Advances the peer with `inp` and returns the frame it was entered for.
+    #[allow(clippy::expect_used)]
+    pub fn advance_peer(&mut self, inp: u32) -> Frame {
+        self.peer.poll_remote_clients();
+        let frame = self.peer.current_frame();
+        self.peer
+            .add_local_input(PlayerHandle::new(PEER_HANDLE), StubInput { inp })
+            .expect("peer input should be accepted");
+        self.stubs[1].handle_requests(self.peer.advance_frame().expect("peer should advance"));
+        self.clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        frame
+    }
+
+    /// Rounds in which the host already has the peer's `inp` when it
+    /// advances; returns whether the host rolled back, which it only does to
+    /// correct an earlier divergence.
+    pub fn predictable_rounds(&mut self, rounds: usize, inp: u32) -> bool {
+        let mut rolled_back = false;
+        for _ in 0..rounds {
+            self.advance_peer(inp);
+            rolled_back |= self.advance_host();
+        }
+        rolled_back
+    }
+
+    /// The host runs `inputs.len()` frames ahead, predicting the peer's last
+    /// input, then the peer plays `inputs`; returns the peer's frames. The
+    /// host rolls back on its next advance if any prediction was wrong.
+    pub fn diverge(&mut self, inputs: &[u32]) -> Vec<Frame> {
+        for _ in inputs {
+            assert!(!self.advance_host(), "the host rolled back running ahead");
+        }
+        inputs.iter().map(|inp| self.advance_peer(*inp)).collect()
+    }
+}
@@ -28,7 +28,7 @@ pub struct GameStub {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct StubInput {
     pub inp: u32,
 }
@@ -63,7 +63,7 @@ pub fn handle_requests(&mut self, requests: RequestVec<StubConfig>) {
             match request {
                 FortressRequest::LoadGameState { cell, .. } => self.load_game_state(cell),
                 FortressRequest::SaveGameState { cell, frame } => self.save_game_state(cell, frame),
-                FortressRequest::AdvanceFrame { inputs } => self.advance_frame(inputs),
+                FortressRequest::AdvanceFrame { inputs, .. } => self.advance_frame(inputs),
             }
         }
     }
@@ -83,7 +83,7 @@ pub fn handle_requests_recording(
             match request {
                 FortressRequest::LoadGameState { cell, .. } => self.load_game_state(cell),
                 FortressRequest::SaveGameState { cell, frame } => self.save_game_state(cell, frame),
-                FortressRequest::AdvanceFrame { inputs } => {
+                FortressRequest::AdvanceFrame { inputs, .. } => {
                     self.advance_frame(inputs);
                     states.insert(self.gs.frame, self.gs);
                 },
@@ -140,7 +140,7 @@ pub fn handle_requests(&mut self, requests: RequestVec<StubConfig>) {
             match request {
                 FortressRequest::LoadGameState { cell, .. } => self.load_game_state(cell),
                 FortressRequest::SaveGameState { cell, frame } => self.save_game_state(cell, frame),
-                FortressRequest::AdvanceFrame { inputs } => self.advance_frame(inputs),
+                FortressRequest::AdvanceFrame { inputs, .. } => self.advance_frame(inputs),
             }
         }
     }
@@ -212,7 +212,7 @@ pub fn handle_requests(&mut self, requests: RequestVec<StubConfig>) {
             match request {
                 FortressRequest::LoadGameState { cell, .. } => self.load_game_state(cell),
                 FortressRequest::SaveGameState { cell, frame } => self.save_game_state(cell, frame),
-                FortressRequest::AdvanceFrame { inputs } => self.advance_frame(inputs),
+                FortressRequest::AdvanceFrame { inputs, .. } => self.advance_frame(inputs),
             }
         }
     }
@@ -245,8 +245,7 @@ fn advance_frame(&mut self, inputs: InputVec<StubInput>) {
     }
 }
 
-#[derive(Default, Copy, Clone, Hash, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "hot-join", derive(Serialize, Deserialize))]
+#[derive(Default, Copy, Clone, Hash, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StateStub {
     pub frame: i32,
     pub state: i32,
@@ -65,7 +65,7 @@ pub fn handle_requests(&mut self, requests: RequestVec<StubEnumConfig>) {
             match request {
                 FortressRequest::LoadGameState { cell, .. } => self.load_game_state(cell),
                 FortressRequest::SaveGameState { cell, frame } => self.save_game_state(cell, frame),
-                FortressRequest::AdvanceFrame { inputs } => self.advance_frame(inputs),
+                FortressRequest::AdvanceFrame { inputs, .. } => self.advance_frame(inputs),
             }
         }
     }
@@ -896,7 +896,7 @@ pub fn assert_spectator_synchronized<C: Config>(
 // Generic P2P Session Test Helpers
 // ============================================================================
 
-use fortress_rollback::{PlayerHandle, PlayerType, RequestVec};
+use fortress_rollback::{Frame, PlayerHandle, PlayerType, RequestVec};
 
 /// Trait for game stubs that can handle fortress requests.
 ///
@@ -990,6 +990,86 @@ pub fn run_p2p_frame_advancement_test_deterministic<C, S>(
     Ok(())
 }
 
+/// Returns the default protocol config running on `clock`'s virtual time.
+#[allow(dead_code)]
+pub fn protocol_config(clock: &TestClock) -> fortress_rollback::ProtocolConfig {
+    fortress_rollback::ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..fortress_rollback::ProtocolConfig::default()
+    }
+}
+
+/// Adds one player per entry of `addrs` to `builder`: handle `local` is the
+/// local player and every other handle is remote at its address. The entry
+/// at `local` is ignored.
+#[allow(dead_code)]
+pub fn add_mesh_players<C: Config<Address = SocketAddr>>(
+    mut builder: fortress_rollback::SessionBuilder<C>,
+    local: usize,
+    addrs: &[SocketAddr],
+) -> Result<fortress_rollback::SessionBuilder<C>, FortressError> {
+    for (handle, addr) in addrs.iter().enumerate() {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(*addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    Ok(builder)
+}
+
+/// Starts one side of a two-player match from `builder`: handle `local` plays
+/// on this socket and the other handle is remote at `remote_addr`.
+#[allow(dead_code)]
+pub fn start_two_peer_session<C: Config<Address = SocketAddr>>(
+    builder: fortress_rollback::SessionBuilder<C>,
+    local: usize,
+    remote_addr: SocketAddr,
+    socket: impl fortress_rollback::NonBlockingSocket<SocketAddr> + 'static,
+) -> Result<P2PSession<C>, FortressError> {
+    add_mesh_players(builder, local, &[remote_addr; 2])?.start_p2p_session(socket)
+}
+
+/// Adds `input` for `handle` and advances `session`, returning its requests.
+/// Returns `None` without advancing while the session is still synchronizing
+/// or its prediction window is full.
+#[allow(dead_code)]
+pub fn advance_local<C: Config>(
+    session: &mut P2PSession<C>,
+    handle: usize,
+    input: C::Input,
+) -> Result<Option<RequestVec<C>>, FortressError> {
+    if session.current_state() != SessionState::Running {
+        return Ok(None);
+    }
+    session.add_local_input(PlayerHandle::new(handle), input)?;
+    match session.advance_frame() {
+        Ok(requests) => Ok(Some(requests)),
+        Err(FortressError::PredictionThreshold) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Polls every peer and advances each one frame with `input_for(handle,
+/// frame)`, where peer `handle` owns the local player of the same handle,
+/// handing the requests to its stub.
+#[allow(dead_code)]
+pub fn step_peers<C: Config, S: GameStubHandler<C>>(
+    sessions: &mut [P2PSession<C>],
+    stubs: &mut [S],
+    input_for: impl Fn(usize, Frame) -> C::Input,
+) -> Result<(), FortressError> {
+    for (handle, (session, stub)) in sessions.iter_mut().zip(stubs.iter_mut()).enumerate() {
+        session.poll_remote_clients();
+        let input = input_for(handle, session.current_frame());
+        if let Some(requests) = advance_local(session, handle, input)? {
+            stub.handle_requests(requests);
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Generic SyncTest Session Test Helpers
 // ============================================================================
@@ -1103,6 +1183,9 @@ fn bind_socket_with_retry_fails_on_privileged_port() {
                             SocketErrorKind::BindFailedAfterRetries { port, .. } => {
                                 assert_eq!(port, 1, "Error should mention port 1");
                             },
+                            SocketErrorKind::SendFailed { .. } => {
+                                panic!("Binding must not report a send failure");
+                            },
                             SocketErrorKind::Custom(_) => {
                                 // Custom errors are acceptable as fallback
                             },
@@ -1162,6 +1245,9 @@ fn bind_error_includes_port_in_context() {
                     SocketErrorKind::BindFailedAfterRetries { port, .. } => {
                         assert_eq!(port, 1, "Error should include port number");
                     },
+                    SocketErrorKind::SendFailed { .. } => {
+                        panic!("Binding must not report a send failure");
+                    },
                     SocketErrorKind::Custom(_) => {
                         // Custom errors may not include port in struct form
                     },
@@ -1233,6 +1319,9 @@ struct BindTestCase {
                                     case.name
                                 );
                             },
+                            SocketErrorKind::SendFailed { .. } => {
+                                panic!("[{}] Binding must not report a send failure", case.name);
+                            },
                             SocketErrorKind::Custom(_) => {
                                 // Custom errors are acceptable as fallback
                             },
@@ -316,7 +316,7 @@ fn handle_requests(&mut self, requests: RequestVec<TestConfig>) {
                     self.debug_log.log_save(frame.as_i32(), self.state.value);
                     cell.save(frame, Some(self.state.clone()), Some(checksum));
                 },
-                FortressRequest::AdvanceFrame { inputs } => {
+                FortressRequest::AdvanceFrame { inputs, .. } => {
                     // Log BEFORE advancing so we can see the inputs that are being used
                     self.debug_log
                         .log_advance(self.state.frame, self.state.value, &inputs);
@@ -376,6 +376,22 @@ struct EventSummary {
     peer_dropped: u32,
     replay_desync: u32,
     spectator_divergence: u32,
+    match_paused: u32,
+    match_resumed: u32,
+    match_pause_cancelled: u32,
+    session_frame_limit_reached: u32,
+    stream_ended: u32,
+    frame_tag: u32,
+    reliable_message: u32,
+    rejoin_state_mismatch: u32,
+    local_advance_stalled: u32,
+    local_advance_resumed: u32,
+    remote_advance_stalled: u32,
+    remote_advance_resumed: u32,
+    spectator_input_gap: u32,
+    transport_error: u32,
+    authoritative_desync_verdict: u32,
+    rollback_too_deep: u32,
     #[cfg(feature = "hot-join")]
     join_requested: u32,
     #[cfg(feature = "hot-join")]
@@ -404,6 +420,26 @@ fn record(&mut self, event: FortressEvent<TestConfig>) {
             },
             FortressEvent::PeerDropped { .. } => self.peer_dropped += 1,
             FortressEvent::SpectatorDivergence { .. } => self.spectator_divergence += 1,
+            FortressEvent::MatchPaused { .. } => self.match_paused += 1,
+            FortressEvent::MatchResumed { .. } => self.match_resumed += 1,
+            FortressEvent::MatchPauseCancelled { .. } => self.match_pause_cancelled += 1,
+            FortressEvent::SessionFrameLimitReached { .. } => {
+                self.session_frame_limit_reached += 1;
+            },
+            FortressEvent::StreamEnded { .. } => self.stream_ended += 1,
+            FortressEvent::FrameTag { .. } => self.frame_tag += 1,
+            FortressEvent::ReliableMessage { .. } => self.reliable_message += 1,
+            FortressEvent::RejoinStateMismatch { .. } => self.rejoin_state_mismatch += 1,
+            FortressEvent::LocalAdvanceStalled { .. } => self.local_advance_stalled += 1,
+            FortressEvent::LocalAdvanceResumed { .. } => self.local_advance_resumed += 1,
+            FortressEvent::RemoteAdvanceStalled { .. } => self.remote_advance_stalled += 1,
+            FortressEvent::RemoteAdvanceResumed { .. } => self.remote_advance_resumed += 1,
+            FortressEvent::SpectatorInputGap { .. } => self.spectator_input_gap += 1,
+            FortressEvent::TransportError { .. } => self.transport_error += 1,
+            FortressEvent::AuthoritativeDesyncVerdict { .. } => {
+                self.authoritative_desync_verdict += 1;
+            },
+            FortressEvent::RollbackTooDeep { .. } => self.rollback_too_deep += 1,
             #[cfg(feature = "hot-join")]
             FortressEvent::JoinRequested { .. } => self.join_requested += 1,
             #[cfg(feature = "hot-join")]
@@ -738,6 +774,8 @@ fn run_test(args: &Args) -> TestResult {
         .unwrap()
         .with_input_delay(args.input_delay)
         .unwrap()
+        // The drivers test delays up to and past the prediction window.
+        .with_input_delay_beyond_prediction(true)
         .with_sync_config(sync_config)
         .with_protocol_config(protocol_config.clone())
         .with_time_sync_config(time_sync_config);
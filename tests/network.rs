@@ -10,12 +10,37 @@
 
 // Network test modules
 mod network {
+    pub mod address_migration;
+    pub mod asymmetric_latency;
+    pub mod auto_frame_skip;
+    pub mod chaos_address_types;
+    pub mod confirmation_latency;
+    pub mod decode_failures;
     pub mod deterministic_ping;
+    pub mod disconnect_frame;
+    pub mod dual_stack;
+    pub mod frame_advantages;
+    pub mod frame_tags;
     pub mod in_process_chaos;
+    pub mod input_resync;
+    pub mod input_suppression;
+    pub mod keep_alive_acks;
     pub mod multi_process;
+    pub mod packet_trace;
     pub mod peer_metrics;
+    pub mod peer_prediction_limit;
+    pub mod player_confirmation;
+    pub mod pre_established;
+    pub mod protocol_identity;
     pub mod protocol_version;
+    pub mod redundant_inputs;
+    pub mod reliable_channel;
     pub mod resilience;
+    pub mod send_limits;
+    pub mod send_priority;
+    pub mod sequence_numbers;
     #[cfg(feature = "hot-join")]
     pub mod soak;
+    pub mod sync_wait;
+    pub mod transport_errors;
 }
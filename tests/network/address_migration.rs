@@ -0,0 +1,185 @@
+//! [`P2PSession::replace_socket`] and
+//! [`SessionBuilder::with_address_migration`] over loopback UDP.
+//!
+//! Two peers play a match; partway through, the first moves to a socket bound
+//! to a new port, as a phone does when it switches networks. With address
+//! migration enabled, the second follows it to the new address and the match
+//! completes without a disconnect or desync.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, calculate_hash, protocol_config, start_two_peer_session, TestClock,
+};
+use fortress_rollback::{
+    DesyncDetection, FortressError, FortressEvent, Frame, P2PSession, PlayerHandle, PlayerType,
+    SessionBuilder, SessionState, UdpNonBlockingSocket,
+};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+/// Frame at which the first peer switches sockets.
+const SWITCH_AT: i32 = 60;
+/// Frame whose confirmed state both peers must agree on.
+const CHECK_AT: i32 = 150;
+/// Poll rounds allowed for each phase; loopback delivery is near-instant.
+const MAX_ROUNDS: usize = 2_000;
+
+fn bind() -> UdpNonBlockingSocket {
+    UdpNonBlockingSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap()
+}
+
+fn session(
+    socket: UdpNonBlockingSocket,
+    local: usize,
+    remote_addr: SocketAddr,
+    address_migration: bool,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+        .with_address_migration(address_migration)
+        .with_protocol_config(protocol_config(clock));
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+/// Two synchronized sessions and the first one's address, the second one
+/// opting into address migration if `address_migration` is set.
+fn pair(address_migration: bool, clock: &TestClock) -> ([P2PSession<StubConfig>; 2], SocketAddr) {
+    let (socket1, socket2) = (bind(), bind());
+    let addr1 = socket1.local_addr().unwrap();
+    let addr2 = socket2.local_addr().unwrap();
+    let mut sessions = [
+        session(socket1, 0, addr2, false, clock).unwrap(),
+        session(socket2, 1, addr1, address_migration, clock).unwrap(),
+    ];
+    let mut rounds = 0;
+    while sessions
+        .iter()
+        .any(|session| session.current_state() != SessionState::Running)
+    {
+        assert!(rounds < MAX_ROUNDS, "sessions did not synchronize");
+        poll_round(&mut sessions, clock);
+        rounds += 1;
+    }
+    (sessions, addr1)
+}
+
+/// One poll round: both sessions poll, then real time passes for the
+/// loopback packets to land and virtual time advances a frame.
+fn poll_round(sessions: &mut [P2PSession<StubConfig>; 2], clock: &TestClock) {
+    for session in sessions.iter_mut() {
+        session.poll_remote_clients();
+    }
+    std::thread::sleep(Duration::from_millis(1));
+    clock.advance(Duration::from_millis(16));
+}
+
+/// Both peers' sessions and games, with every event they reported.
+struct Match {
+    sessions: [P2PSession<StubConfig>; 2],
+    stubs: [GameStub; 2],
+    states: [BTreeMap<i32, StateStub>; 2],
+    events: Vec<FortressEvent<StubConfig>>,
+}
+
+impl Match {
+    fn new(sessions: [P2PSession<StubConfig>; 2]) -> Self {
+        Self {
+            sessions,
+            stubs: [GameStub::new(), GameStub::new()],
+            states: [BTreeMap::new(), BTreeMap::new()],
+            events: Vec::new(),
+        }
+    }
+
+    /// Plays until both peers have confirmed `until`.
+    fn run_until(&mut self, until: Frame, clock: &TestClock) {
+        let mut rounds = 0;
+        while self
+            .sessions
+            .iter()
+            .any(|session| session.confirmed_frame() < until)
+        {
+            assert!(rounds < MAX_ROUNDS, "sessions did not confirm {until}");
+            self.step(clock);
+            rounds += 1;
+        }
+    }
+
+    /// Both peers add an input and advance, then poll.
+    fn step(&mut self, clock: &TestClock) {
+        for (handle, session) in self.sessions.iter_mut().enumerate() {
+            let inp = session.current_frame().as_i32() as u32 % 5 + handle as u32;
+            if let Some(requests) = advance_local(session, handle, StubInput { inp }).unwrap() {
+                self.stubs[handle].handle_requests_recording(requests, &mut self.states[handle]);
+            }
+        }
+        poll_round(&mut self.sessions, clock);
+        for session in &mut self.sessions {
+            self.events.extend(session.events());
+        }
+    }
+}
+
+#[test]
+#[cfg(not(miri))]
+fn socket_swap_mid_match_completes_without_disconnect_or_desync() {
+    let clock = TestClock::new();
+    let (sessions, old_addr) = pair(true, &clock);
+    let mut game = Match::new(sessions);
+    game.run_until(Frame::new(SWITCH_AT), &clock);
+
+    let socket = bind();
+    let new_addr = socket.local_addr().unwrap();
+    assert_ne!(new_addr, old_addr);
+    game.sessions[0].replace_socket(socket);
+    game.run_until(Frame::new(CHECK_AT + 10), &clock);
+
+    assert_eq!(
+        game.sessions[1].player_type(PlayerHandle::new(0)),
+        Some(PlayerType::Remote(new_addr))
+    );
+    assert_eq!(game.sessions[1].metrics().peer_address_migrations, 1);
+    assert!(
+        game.events.iter().all(|event| !matches!(
+            event,
+            FortressEvent::Disconnected { .. } | FortressEvent::DesyncDetected { .. }
+        )),
+        "{:?}",
+        game.events
+    );
+    assert_eq!(
+        calculate_hash(&game.states[0][&CHECK_AT]),
+        calculate_hash(&game.states[1][&CHECK_AT])
+    );
+}
+
+#[test]
+#[cfg(not(miri))]
+fn packets_from_a_new_socket_are_ignored_without_address_migration() {
+    let clock = TestClock::new();
+    let (sessions, old_addr) = pair(false, &clock);
+    let mut game = Match::new(sessions);
+    game.run_until(Frame::new(SWITCH_AT), &clock);
+
+    game.sessions[0].replace_socket(bind());
+    for _ in 0..30 {
+        game.step(&clock);
+    }
+
+    assert_eq!(
+        game.sessions[1].player_type(PlayerHandle::new(0)),
+        Some(PlayerType::Remote(old_addr))
+    );
+    let metrics = game.sessions[1].metrics();
+    assert_eq!(metrics.peer_address_migrations, 0);
+    assert!(metrics.unknown_source_packets > 0);
+}
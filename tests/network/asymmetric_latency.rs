@@ -0,0 +1,132 @@
+//! [`NetworkStats`] frame advantage sign and staleness end-to-end tests.
+//!
+//! Two peers play in lockstep, but only the link into peer 1 delays its
+//! packets. Both measure the same round trip and assume half of it in each
+//! direction, so peer 0 receives peer 1's inputs earlier than it assumes and
+//! estimates peer 1 to be ahead of it, while peer 1 receives peer 0's inputs
+//! later than it assumes and estimates peer 0 to be behind it. Per the
+//! documented convention (remote frame minus local frame), peer 0's
+//! `local_frame_advantage` is positive and the delayed peer 1 reports a
+//! positive `remote_frame_advantage` for peer 0. Each side's remote value is
+//! the other side's local one, so the signs mirror.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, create_chaos_channel_pair, protocol_config, start_two_peer_session,
+    synchronize_sessions_deterministic, SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    ChaosConfig, FortressError, NetworkStats, NonBlockingSocket, P2PSession, PlayerHandle,
+    ProtocolConfig, SessionBuilder,
+};
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Frames played once both peers are synchronized.
+const FRAMES: usize = 300;
+/// Frames played before sampling, so the averaging window is full.
+const WARMUP: usize = 60;
+/// Delay added to every packet peer 1 receives. Half of it is three frames.
+const LATENCY_MS: u64 = 100;
+/// Room for the peers to keep advancing despite the delay.
+const MAX_PREDICTION: usize = 16;
+
+fn session(
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_max_prediction_window(MAX_PREDICTION)
+        .with_protocol_config(protocol_config(clock));
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+/// Plays [`FRAMES`] steps and returns every stats sample each peer read for
+/// the other one after [`WARMUP`].
+fn play() -> Result<[Vec<NetworkStats>; 2], FortressError> {
+    let clock = TestClock::new();
+    let (s0, s1, a0, a1) = create_chaos_channel_pair(
+        ChaosConfig::passthrough(),
+        ChaosConfig::builder().latency_ms(LATENCY_MS).build(),
+        &clock,
+    );
+    let mut sessions = [session(s0, 0, a1, &clock)?, session(s1, 1, a0, &clock)?];
+    let [first, second] = &mut sessions;
+    synchronize_sessions_deterministic(first, second, &clock, &SyncConfig::default())?;
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut samples = [Vec::new(), Vec::new()];
+    for step in 0..FRAMES {
+        for (index, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            for _ in session.events() {}
+            if step >= WARMUP {
+                samples[index].push(session.network_stats(PlayerHandle::new(1 - index))?);
+            }
+            let inp = session.current_frame().as_i32() as u32 % 3;
+            if let Some(requests) = advance_local(session, index, StubInput { inp })? {
+                stubs[index].handle_requests(requests);
+            }
+        }
+        clock.advance(STEP);
+    }
+    Ok(samples)
+}
+
+#[test]
+fn delayed_peer_reports_positive_advantage_for_the_other_side() -> Result<(), FortressError> {
+    let [undelayed, delayed] = play()?;
+
+    for stats in &undelayed {
+        assert!(stats.local_frame_advantage > 0, "{stats}");
+        assert!(stats.remote_frame_advantage < 0, "{stats}");
+    }
+    for stats in &delayed {
+        assert!(stats.local_frame_advantage < 0, "{stats}");
+        assert!(stats.remote_frame_advantage > 0, "{stats}");
+    }
+    Ok(())
+}
+
+#[test]
+fn deprecated_aliases_match_the_frame_advantages() -> Result<(), FortressError> {
+    for stats in play()?.iter().flatten() {
+        #[allow(deprecated)]
+        let aliases = (stats.local_frames_behind, stats.remote_frames_behind);
+        assert_eq!(
+            aliases,
+            (stats.local_frame_advantage, stats.remote_frame_advantage)
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn stats_age_stays_within_a_report_round_trip() -> Result<(), FortressError> {
+    let interval = ProtocolConfig::default()
+        .quality_report_interval
+        .as_millis();
+    // A report interval, the delay on one leg of the round trip and a frame
+    // of polling slack on each side.
+    let bound = interval + u128::from(LATENCY_MS) + 2 * STEP.as_millis();
+    for (peer, samples) in play()?.iter().enumerate() {
+        let oldest = samples
+            .iter()
+            .map(|stats| stats.stats_age_ms)
+            .max()
+            .unwrap();
+        assert!(oldest <= bound, "peer {peer}: {oldest}ms > {bound}ms");
+        // Without reports the age would only grow with the clock.
+        let frames = u128::try_from(samples.len()).unwrap();
+        assert!(oldest < frames * STEP.as_millis());
+    }
+    Ok(())
+}
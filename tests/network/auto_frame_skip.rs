@@ -0,0 +1,237 @@
+//! [`SessionBuilder::with_auto_frame_skip`] end-to-end tests.
+//!
+//! The mesh of `frame_advantages.rs`: three peers over links with the same
+//! chaos latency, one of them running slowly by skipping every other step, so
+//! the other two keep getting told to wait. The fast peers honor the
+//! recommendations either by hand, the way the `ex_game_p2p` example does, or
+//! through auto frame skip. Everything is deterministic, so the two runs must
+//! produce the same frame advantages, frames and game states step for step.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_mesh, TestClock};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressEvent, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, SessionState,
+};
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Frames played once the mesh is synchronized.
+const FRAMES: usize = 400;
+/// The peer that skips frames.
+const SLOW: usize = 2;
+/// [`SLOW`] skips one step in this many.
+const SLOW_SKIPS_EVERY: usize = 2;
+/// Room for the fast peers to pull well ahead before they stall.
+const MAX_PREDICTION: usize = 16;
+/// Delay added to every packet received, on every peer.
+const LATENCY_MS: u64 = 40;
+
+/// How the fast peers honor wait recommendations.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Count down `WaitRecommendation` events in the game loop.
+    Manual,
+    /// Let the session skip with [`SessionBuilder::with_auto_frame_skip`].
+    Auto,
+}
+
+/// What one peer looked like after one step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Sample {
+    frame: i32,
+    frames_ahead: i32,
+    advantages: Vec<(PlayerHandle, i32)>,
+    game_frame: i32,
+    game_state: i32,
+}
+
+/// The outcome of one run.
+struct Run {
+    /// Every peer's [`Sample`] after every step.
+    samples: Vec<Vec<Sample>>,
+    /// Steps on which each peer skipped its simulation to wait.
+    waits: Vec<usize>,
+    /// Steps on which each peer simulated a new frame.
+    simulated: Vec<usize>,
+    /// Each peer's wait recommendations, as counted by its metrics.
+    recommendations: Vec<u64>,
+    /// Each peer's `WaitRecommendation` events.
+    events: Vec<usize>,
+}
+
+fn mesh(clock: &TestClock, mode: Mode) -> Result<Vec<P2PSession<StubConfig>>, FortressError> {
+    let configs = (0..3)
+        .map(|peer| {
+            ChaosConfig::builder()
+                .latency_ms(LATENCY_MS)
+                .seed(peer as u64)
+                .build()
+        })
+        .collect();
+    let (sockets, addrs) = create_chaos_channel_mesh(configs, clock);
+    let mut sessions = Vec::new();
+    for (local, socket) in sockets.into_iter().enumerate() {
+        let mut builder = SessionBuilder::<StubConfig>::new()
+            .with_num_players(3)?
+            .with_max_prediction_window(MAX_PREDICTION)
+            .with_auto_frame_skip(mode == Mode::Auto)
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            });
+        for (handle, &addr) in addrs.iter().enumerate() {
+            let player = if handle == local {
+                PlayerType::Local
+            } else {
+                PlayerType::Remote(addr)
+            };
+            builder = builder.add_player(player, PlayerHandle::new(handle))?;
+        }
+        sessions.push(builder.start_p2p_session(socket)?);
+    }
+    Ok(sessions)
+}
+
+fn play(mode: Mode) -> Result<Run, FortressError> {
+    let clock = TestClock::new();
+    let mut sessions = mesh(&clock, mode)?;
+    for _ in 0..1000 {
+        for session in &mut sessions {
+            session.poll_remote_clients();
+        }
+        if sessions
+            .iter()
+            .all(|session| session.current_state() == SessionState::Running)
+        {
+            break;
+        }
+        clock.advance(Duration::from_millis(20));
+    }
+    assert!(sessions
+        .iter()
+        .all(|session| session.current_state() == SessionState::Running));
+
+    let peers = sessions.len();
+    let mut stubs: Vec<GameStub> = sessions.iter().map(|_| GameStub::new()).collect();
+    let mut run = Run {
+        samples: vec![Vec::new(); peers],
+        waits: vec![0; peers],
+        simulated: vec![0; peers],
+        recommendations: vec![0; peers],
+        events: vec![0; peers],
+    };
+    let mut manual_skips = vec![0_u32; peers];
+    for step in 0..FRAMES {
+        for (index, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            for event in session.events() {
+                if let FortressEvent::WaitRecommendation { skip_frames } = event {
+                    run.events[index] += 1;
+                    // A fresh event reports the current frame lead, so it
+                    // replaces any residual skip.
+                    manual_skips[index] = skip_frames;
+                }
+            }
+            let slow_step = index == SLOW && step % SLOW_SKIPS_EVERY == 0;
+            if !slow_step {
+                if manual_skips[index] > 0 {
+                    // The skipped call's own poll, so both modes poll alike.
+                    manual_skips[index] -= 1;
+                    session.poll_remote_clients();
+                    run.waits[index] += 1;
+                } else {
+                    let frame_before = session.current_frame();
+                    let frame = frame_before.as_i32();
+                    session.add_local_input(
+                        PlayerHandle::new(index),
+                        StubInput {
+                            inp: frame as u32 % 3,
+                        },
+                    )?;
+                    match session.advance_frame() {
+                        Ok(requests) => {
+                            if session.skipped_this_call() {
+                                assert!(requests.is_empty());
+                                assert_eq!(session.current_frame(), frame_before);
+                                run.waits[index] += 1;
+                            } else if session.current_frame() > frame_before {
+                                assert_eq!(session.current_frame(), frame_before + 1);
+                                run.simulated[index] += 1;
+                            }
+                            stubs[index].handle_requests(requests);
+                        },
+                        Err(FortressError::PredictionThreshold) => {},
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+            // The game simulated exactly the session's frames: none lost,
+            // none run twice.
+            assert_eq!(stubs[index].gs.frame, session.current_frame().as_i32());
+            run.samples[index].push(Sample {
+                frame: session.current_frame().as_i32(),
+                frames_ahead: session.frames_ahead(),
+                advantages: session.frame_advantages(),
+                game_frame: stubs[index].gs.frame,
+                game_state: stubs[index].gs.state,
+            });
+        }
+        clock.advance(STEP);
+    }
+    for (index, session) in sessions.iter().enumerate() {
+        run.recommendations[index] = session.metrics().wait_recommendations;
+        assert_eq!(
+            run.simulated[index],
+            session.current_frame().as_i32() as usize,
+            "peer {index}: every new frame was simulated exactly once"
+        );
+    }
+    Ok(run)
+}
+
+#[test]
+fn auto_frame_skip_converges_like_a_manual_skip_loop() -> Result<(), FortressError> {
+    let manual = play(Mode::Manual)?;
+    let auto = play(Mode::Auto)?;
+
+    for index in (0..3).filter(|&index| index != SLOW) {
+        assert!(manual.waits[index] > 0, "peer {index} never waited");
+        assert!(manual.events[index] > 0);
+    }
+    for index in 0..3 {
+        for (step, (manual, auto)) in manual.samples[index]
+            .iter()
+            .zip(&auto.samples[index])
+            .enumerate()
+        {
+            assert_eq!(manual, auto, "peer {index} diverged at step {step}");
+        }
+    }
+    assert_eq!(manual.waits, auto.waits);
+    assert_eq!(manual.simulated, auto.simulated);
+    assert_eq!(manual.recommendations, auto.recommendations);
+    Ok(())
+}
+
+#[test]
+fn auto_frame_skip_suppresses_wait_recommendation_events() -> Result<(), FortressError> {
+    let auto = play(Mode::Auto)?;
+
+    assert_eq!(auto.events, vec![0; 3]);
+    for index in (0..3).filter(|&index| index != SLOW) {
+        assert!(auto.recommendations[index] > 0, "peer {index}");
+        assert!(auto.waits[index] > 0, "peer {index} never waited");
+    }
+    Ok(())
+}
@@ -0,0 +1,216 @@
+//! `ChaosSocket` over an inner socket with a non-`SocketAddr` address type.
+//!
+//! Wraps an in-memory socket addressed by `u64` in seeded chaos and runs a
+//! full two-peer P2P session over it. Chaos decisions never look at the
+//! address, so the same seeds and the same call sequence must reproduce the
+//! same `ChaosStats`, whatever the address type.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::common::stubs::{StateStub, StubInput};
+use crate::common::TestClock;
+use fortress_rollback::{
+    ChaosConfig, ChaosSocket, ChaosStats, Config, FortressRequest, Message, NonBlockingSocket,
+    P2PSession, PlayerHandle, PlayerType, ProtocolConfig, RequestVec, SessionBuilder, SessionState,
+};
+
+struct U64Config;
+
+impl Config for U64Config {
+    type Input = StubInput;
+    type State = StateStub;
+    type Address = u64;
+}
+
+/// `GameStub::handle_requests` for `U64Config`.
+fn handle_requests(state: &mut StateStub, requests: RequestVec<U64Config>) {
+    for request in requests {
+        match request {
+            FortressRequest::LoadGameState { cell, .. } => *state = cell.load().unwrap(),
+            FortressRequest::SaveGameState { cell, frame } => {
+                assert!(cell.save(frame, Some(*state), None));
+            },
+            FortressRequest::AdvanceFrame { inputs, .. } => state.advance_frame_pub(inputs),
+        }
+    }
+}
+
+/// Mailboxes of every in-memory socket, keyed by address.
+type Mailboxes = Arc<Mutex<BTreeMap<u64, VecDeque<(u64, Message)>>>>;
+
+/// An in-memory socket addressed by `u64`, delivering instantly.
+struct MemorySocket {
+    addr: u64,
+    mailboxes: Mailboxes,
+    sent: u64,
+}
+
+impl NonBlockingSocket<u64> for MemorySocket {
+    fn send_to(&mut self, msg: &Message, addr: &u64) {
+        self.sent += 1;
+        if let Some(mailbox) = self.mailboxes.lock().unwrap().get_mut(addr) {
+            mailbox.push_back((self.addr, msg.clone()));
+        }
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(u64, Message)> {
+        let mut mailboxes = self.mailboxes.lock().unwrap();
+        mailboxes
+            .get_mut(&self.addr)
+            .map(|mailbox| mailbox.drain(..).collect())
+            .unwrap_or_default()
+    }
+}
+
+type U64Chaos = ChaosSocket<u64, MemorySocket>;
+
+/// Hands the session a chaos socket the test can still inspect.
+struct SharedChaos(Arc<Mutex<U64Chaos>>);
+
+impl NonBlockingSocket<u64> for SharedChaos {
+    fn send_to(&mut self, msg: &Message, addr: &u64) {
+        self.0.lock().unwrap().send_to(msg, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(u64, Message)> {
+        self.0.lock().unwrap().receive_all_messages()
+    }
+}
+
+const ADDRS: [u64; 2] = [0xDEAD_BEEF_0000_0001, 0xDEAD_BEEF_0000_0002];
+const TARGET_CONFIRMED: i32 = 60;
+
+fn chaos_config(seed: u64) -> ChaosConfig {
+    ChaosConfig::builder()
+        .latency_ms(20)
+        .jitter_ms(10)
+        .packet_loss_rate(0.1)
+        .duplication_rate(0.05)
+        .reorder_buffer_size(3)
+        .reorder_rate(0.2)
+        .seed(seed)
+        .build()
+}
+
+/// One seeded run: the chaos stats of both peers, their confirmed frames and
+/// the inner sockets' send counts.
+#[derive(Debug, PartialEq, Eq)]
+struct Run {
+    stats: [ChaosStats; 2],
+    confirmed: [i32; 2],
+    inner_sent: [u64; 2],
+}
+
+fn run_once(seed: u64) -> Run {
+    let clock = TestClock::new();
+    let mailboxes: Mailboxes = Arc::default();
+    let mut chaos = Vec::new();
+    let mut sessions: Vec<P2PSession<U64Config>> = Vec::new();
+    for (i, &addr) in ADDRS.iter().enumerate() {
+        mailboxes.lock().unwrap().insert(addr, VecDeque::new());
+        let inner = MemorySocket {
+            addr,
+            mailboxes: Arc::clone(&mailboxes),
+            sent: 0,
+        };
+        // Different seeds per peer, see `in_process_chaos`.
+        let socket = Arc::new(Mutex::new(
+            ChaosSocket::new(inner, chaos_config(seed + i as u64))
+                .with_clock(clock.as_chaos_clock()),
+        ));
+        let mut builder = SessionBuilder::<U64Config>::new().with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        });
+        for (handle, &player) in ADDRS.iter().enumerate() {
+            let player_type = if player == addr {
+                PlayerType::Local
+            } else {
+                PlayerType::Remote(player)
+            };
+            builder = builder
+                .add_player(player_type, PlayerHandle::new(handle))
+                .unwrap();
+        }
+        sessions.push(
+            builder
+                .start_p2p_session(SharedChaos(Arc::clone(&socket)))
+                .unwrap(),
+        );
+        chaos.push(socket);
+    }
+
+    let mut states = [StateStub::default(); 2];
+    for tick in 0..3000u32 {
+        for session in &mut sessions {
+            session.poll_remote_clients();
+        }
+        clock.advance(Duration::from_millis(16));
+        if sessions
+            .iter()
+            .all(|s| s.confirmed_frame().as_i32() >= TARGET_CONFIRMED)
+        {
+            break;
+        }
+        if sessions
+            .iter()
+            .any(|s| s.current_state() != SessionState::Running)
+        {
+            continue;
+        }
+        for (handle, (session, state)) in sessions.iter_mut().zip(&mut states).enumerate() {
+            session
+                .add_local_input(PlayerHandle::new(handle), StubInput { inp: tick })
+                .unwrap();
+            handle_requests(state, session.advance_frame().unwrap());
+        }
+    }
+
+    let confirmed = [0, 1].map(|i| sessions[i].confirmed_frame().as_i32());
+    drop(sessions);
+    let stats = [0, 1].map(|i| *chaos[i].lock().unwrap().stats());
+    let mut inner_sent = [0; 2];
+    for (i, socket) in chaos.into_iter().enumerate() {
+        let socket = Arc::try_unwrap(socket)
+            .expect("the session dropped its handle")
+            .into_inner()
+            .unwrap();
+        assert_eq!(socket.inner().addr, ADDRS[i]);
+        inner_sent[i] = socket.into_inner().sent;
+    }
+    Run {
+        stats,
+        confirmed,
+        inner_sent,
+    }
+}
+
+#[test]
+fn seeded_chaos_over_u64_addresses_is_reproducible() {
+    let first = run_once(42);
+    for i in 0..2 {
+        assert!(
+            first.confirmed[i] >= TARGET_CONFIRMED,
+            "peer {i} stalled: {first:?}"
+        );
+        let stats = &first.stats[i];
+        assert!(stats.packets_dropped_send > 0, "{stats}");
+        assert!(stats.packets_duplicated > 0, "{stats}");
+        assert_eq!(
+            first.inner_sent[i],
+            stats.packets_sent - stats.packets_dropped_send - stats.packets_dropped_burst
+                + stats.packets_duplicated
+        );
+    }
+    assert_eq!(run_once(42), first);
+}
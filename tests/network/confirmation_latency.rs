@@ -0,0 +1,159 @@
+//! [`P2PSession::confirmation_latency_stats`] end-to-end tests.
+//!
+//! Two peers play in step over links with a fixed chaos latency. With matching
+//! input delays on both, the inputs for a frame are added at the same moment
+//! on both peers, so each frame is confirmed once the other peer's input has
+//! crossed the link: the measured median must track the configured one-way
+//! latency. Two frames of slack cover the polling: `ChaosSocket` starts a
+//! packet's latency when the receiver first polls for it, and the session
+//! notices the packet at its next poll after that.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_pair, synchronize_sessions_deterministic, TestClock};
+use crate::common::{protocol_config, start_two_peer_session, step_peers};
+use crate::common::{ChannelSocket, SyncConfig};
+use fortress_rollback::{
+    ChaosConfig, ChaosSocket, FortressError, InvalidRequestKind, LatencyStats, P2PSession,
+    PlayerHandle, PlayerType, SessionBuilder,
+};
+use std::net::SocketAddr;
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Frames played once the peers are synchronized.
+const FRAMES: usize = 300;
+/// Measurements kept per session.
+const CAPACITY: usize = 120;
+
+fn session(
+    socket: ChaosSocket<SocketAddr, ChannelSocket>,
+    local: usize,
+    remote_addr: SocketAddr,
+    input_delay: usize,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_max_prediction_window(16)
+        .with_input_delay(input_delay)?
+        .with_confirmation_latency(CAPACITY)?
+        .with_protocol_config(protocol_config(clock));
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+/// Plays [`FRAMES`] frames over links delaying every packet by `latency` and
+/// returns both peers' stats.
+fn play(latency: Duration, input_delay: usize) -> Result<[LatencyStats; 2], FortressError> {
+    let clock = TestClock::new();
+    let chaos = |seed| ChaosConfig::builder().latency(latency).seed(seed).build();
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(1), chaos(2), &clock);
+    let mut sessions = [
+        session(s1, 0, a2, input_delay, &clock)?,
+        session(s2, 1, a1, input_delay, &clock)?,
+    ];
+    let [first, second] = &mut sessions;
+    synchronize_sessions_deterministic(first, second, &clock, &SyncConfig::default())?;
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    for _ in 0..FRAMES {
+        step_peers(&mut sessions, &mut stubs, |_, frame| StubInput {
+            inp: frame.as_i32() as u32 % 5,
+        })?;
+        clock.advance(STEP);
+    }
+
+    for session in &sessions {
+        let samples: Vec<_> = session.confirmation_latency_samples().collect();
+        assert_eq!(samples.len(), CAPACITY, "the ring keeps the newest samples");
+        assert!(samples.windows(2).all(|pair| pair[0].frame < pair[1].frame));
+    }
+    Ok([
+        sessions[0].confirmation_latency_stats(),
+        sessions[1].confirmation_latency_stats(),
+    ])
+}
+
+/// Asserts the median lies between the one-way `latency` and two frames more.
+fn assert_tracks(stats: &LatencyStats, latency: Duration) {
+    let latency_ms = latency.as_secs_f64() * 1000.0;
+    let step_ms = STEP.as_secs_f64() * 1000.0;
+    assert_eq!(stats.samples, CAPACITY);
+    assert!(
+        stats.p50_ms >= latency_ms && stats.p50_ms <= latency_ms + 2.0 * step_ms,
+        "median {} ms should be within two frames above {latency_ms} ms: {stats:?}",
+        stats.p50_ms
+    );
+    assert!(stats.p50_ms <= stats.p95_ms && stats.p95_ms <= stats.max_ms);
+    let expected_frames = (latency_ms / step_ms).ceil() as u32;
+    assert!(
+        (expected_frames..=expected_frames + 1).contains(&stats.p50_frames),
+        "median {} frames should be about {expected_frames}: {stats:?}",
+        stats.p50_frames
+    );
+    assert!(stats.p50_frames <= stats.p95_frames);
+}
+
+#[test]
+fn median_tracks_the_one_way_latency() -> Result<(), FortressError> {
+    for latency_ms in [30, 60, 100] {
+        let latency = Duration::from_millis(latency_ms);
+        for stats in play(latency, 0)? {
+            assert_tracks(&stats, latency);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn matching_input_delay_does_not_add_to_the_latency() -> Result<(), FortressError> {
+    let latency = Duration::from_millis(60);
+    for stats in play(latency, 2)? {
+        assert_tracks(&stats, latency);
+    }
+    Ok(())
+}
+
+#[test]
+fn disabled_tracking_reports_nothing() -> Result<(), FortressError> {
+    let session = SessionBuilder::<StubConfig>::new()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(crate::common::create_channel_pair().0)?;
+    assert_eq!(session.confirmation_latency_samples().count(), 0);
+    assert_eq!(
+        session.confirmation_latency_stats(),
+        LatencyStats::default()
+    );
+    Ok(())
+}
+
+#[test]
+fn confirmation_latency_capacity_must_be_in_range() {
+    for capacity in [0, 65_537] {
+        let result = SessionBuilder::<StubConfig>::new().with_confirmation_latency(capacity);
+        assert!(
+            matches!(
+                result,
+                Err(FortressError::InvalidRequestStructured {
+                    kind: InvalidRequestKind::ConfigValueOutOfRange {
+                        field: "confirmation_latency",
+                        ..
+                    }
+                })
+            ),
+            "capacity {capacity}"
+        );
+    }
+    assert!(SessionBuilder::<StubConfig>::new()
+        .with_confirmation_latency(65_536)
+        .is_ok());
+}
@@ -0,0 +1,131 @@
+//! Decode-failure counters through the real raw-UDP receive path.
+//!
+//! Covers:
+//! - A raw peer sending one datagram per failure category; the receiving
+//!   `UdpNonBlockingSocket` drops each and counts it under the matching
+//!   `DecodeFailure`.
+//! - A session reporting a truncated datagram in
+//!   `SessionMetrics::decode_failures` as `Truncated` and nothing else.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::StubConfig;
+use crate::common::TestClock;
+use fortress_rollback::{
+    DecodeFailure, NonBlockingSocket, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+    UdpNonBlockingSocket, PROTOCOL_VERSION,
+};
+use std::net::{Ipv4Addr, UdpSocket};
+use std::time::Duration;
+
+/// Sentinel, version, flags and connection id 1, followed by `variant`.
+fn wire_prefix(version: u8, variant: u32) -> Vec<u8> {
+    let mut bytes = vec![0xF5, 0x52, version, 0];
+    bytes.extend_from_slice(&1_u32.to_le_bytes());
+    bytes.extend_from_slice(&variant.to_le_bytes());
+    bytes
+}
+
+#[test]
+#[cfg(not(miri))]
+fn each_decode_failure_category_is_counted() {
+    let receiver = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    receiver.set_nonblocking(true).unwrap();
+    let receiver_addr = receiver.local_addr().unwrap();
+    let mut socket = UdpNonBlockingSocket::from_socket_with_buffer_sizes(receiver, 4096, 1024)
+        .expect("valid non-zero socket buffers");
+    let raw_peer = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+
+    // KeepAlive (variant 7) carries its ack frame, here `Frame::NULL`, and
+    // an empty connect status.
+    let mut keep_alive = wire_prefix(PROTOCOL_VERSION, 7);
+    keep_alive.extend_from_slice(&(-1_i32).to_le_bytes());
+    keep_alive.extend_from_slice(&0_u64.to_le_bytes());
+    let mut trailing = keep_alive.clone();
+    trailing.push(0);
+    let mut truncated = keep_alive.clone();
+    truncated.truncate(10);
+    let mut bad_sentinel = keep_alive.clone();
+    bad_sentinel[0] = 0;
+    let unsupported = wire_prefix(PROTOCOL_VERSION.wrapping_add(1), 7);
+    for datagram in [
+        &truncated,
+        &trailing,
+        &trailing,
+        &bad_sentinel,
+        &unsupported,
+    ] {
+        raw_peer.send_to(datagram, receiver_addr).unwrap();
+    }
+    raw_peer.send_to(&keep_alive, receiver_addr).unwrap();
+
+    let mut received = Vec::new();
+    for _ in 0..200 {
+        received.extend(socket.receive_all_messages());
+        if socket.decode_failures().total() == 5 && !received.is_empty() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    let counts = socket.decode_failures();
+    assert_eq!(counts.get(DecodeFailure::Truncated), 1, "{counts:?}");
+    assert_eq!(counts.get(DecodeFailure::TrailingBytes), 2, "{counts:?}");
+    assert_eq!(counts.get(DecodeFailure::Malformed), 1, "{counts:?}");
+    assert_eq!(
+        counts.get(DecodeFailure::UnsupportedVersion),
+        1,
+        "{counts:?}"
+    );
+    assert_eq!(counts.total(), 5);
+    assert_eq!(received.len(), 1, "the valid KeepAlive is still delivered");
+}
+
+#[test]
+#[cfg(not(miri))]
+fn truncated_datagram_counts_only_as_truncated_in_session_metrics() {
+    let clock = TestClock::new();
+    let receiver = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    receiver.set_nonblocking(true).unwrap();
+    let receiver_addr = receiver.local_addr().unwrap();
+    let socket = UdpNonBlockingSocket::from_socket_with_buffer_sizes(receiver, 4096, 1024)
+        .expect("valid non-zero socket buffers");
+    let raw_peer = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        })
+        .add_player(PlayerType::Local, PlayerHandle::new(0))
+        .unwrap()
+        .add_player(
+            PlayerType::Remote(raw_peer.local_addr().unwrap()),
+            PlayerHandle::new(1),
+        )
+        .unwrap()
+        .start_p2p_session(socket)
+        .unwrap();
+
+    // A `QualityReport` (variant 4) cut off two bytes into its body.
+    let mut truncated = wire_prefix(PROTOCOL_VERSION, 4);
+    truncated.extend_from_slice(&[0, 0]);
+    raw_peer.send_to(&truncated, receiver_addr).unwrap();
+
+    for _ in 0..200 {
+        session.poll_remote_clients();
+        if session.metrics().decode_failures.total() > 0 {
+            break;
+        }
+        clock.advance(Duration::from_millis(1));
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    let counts = session.metrics().decode_failures;
+    assert_eq!(counts.get(DecodeFailure::Truncated), 1, "{counts:?}");
+    assert_eq!(counts.total(), 1, "{counts:?}");
+}
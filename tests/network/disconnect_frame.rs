@@ -0,0 +1,127 @@
+//! Integration tests for `P2PSession::disconnect_frame`.
+//!
+//! Covers:
+//! - Two peers whose link blacks out long enough for both to time out, at
+//!   different frames, report the identical disconnect frame for each other
+//!   once the link heals before shutdown.
+//!
+//! The closed-link notice and ack bookkeeping behind the agreement are
+//! unit-tested in `network::protocol`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_chaos_channel_pair, protocol_config, start_two_peer_session, TestClock,
+};
+use fortress_rollback::{
+    ChaosConfig, DisconnectBehavior, FortressEvent, P2PSession, PlayerHandle, SessionBuilder,
+    SessionState,
+};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+const TICK: Duration = Duration::from_millis(16);
+
+fn session(
+    clock: &TestClock,
+    local: usize,
+    remote_addr: SocketAddr,
+    socket: fortress_rollback::ChaosSocket<SocketAddr, crate::common::ChannelSocket>,
+) -> P2PSession<StubConfig> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(clock))
+        .with_disconnect_timeout(Duration::from_secs(1))
+        .with_disconnect_notify_delay(Duration::from_millis(500))
+        .with_disconnect_behavior(DisconnectBehavior::ContinueWithout);
+    start_two_peer_session(builder, local, remote_addr, socket).unwrap()
+}
+
+/// 60ms one-way latency until `clean`, then every send is lost until `heal`,
+/// then clean again. The latency makes acks trail the inputs they confirm.
+fn blackout(clean: Duration, heal: Duration, seed: u64) -> ChaosConfig {
+    ChaosConfig::builder()
+        .phase(clean, |b| b.latency(Duration::from_millis(60)))
+        .phase(heal - clean, |b| b.send_loss_rate(1.0))
+        .seed(seed)
+        .build()
+}
+
+fn step(
+    sess: &mut P2PSession<StubConfig>,
+    stub: &mut GameStub,
+    local: PlayerHandle,
+    inp: u32,
+    disconnected: &mut bool,
+) {
+    for event in sess.events() {
+        if matches!(event, FortressEvent::Disconnected { .. }) {
+            *disconnected = true;
+        }
+    }
+    sess.add_local_input(local, StubInput { inp }).unwrap();
+    match sess.advance_frame() {
+        Ok(requests) => stub.handle_requests(requests),
+        Err(err) => panic!("unexpected advance_frame error: {err:?}"),
+    }
+}
+
+#[test]
+fn mutual_timeout_converges_on_one_disconnect_frame() {
+    let clock = TestClock::new();
+    // Peer 2 goes quiet first, so each side times out having received a
+    // different amount of the other's input and with acks trailing it. Only
+    // the closed-link notices exchanged after the link heals, well before the
+    // five-second shutdown delay ends, make the two views agree.
+    let heal = Duration::from_secs(4);
+    let (socket1, socket2, addr1, addr2) = create_chaos_channel_pair(
+        blackout(Duration::from_millis(1700), heal, 1),
+        blackout(Duration::from_millis(1500), heal, 2),
+        &clock,
+    );
+    let mut sess1 = session(&clock, 0, addr2, socket1);
+    let mut sess2 = session(&clock, 1, addr1, socket2);
+
+    for _ in 0..100 {
+        sess1.poll_remote_clients();
+        sess2.poll_remote_clients();
+        if sess1.current_state() == SessionState::Running
+            && sess2.current_state() == SessionState::Running
+        {
+            break;
+        }
+        clock.advance(Duration::from_millis(20));
+    }
+    assert_eq!(sess1.current_state(), SessionState::Running);
+    assert_eq!(sess2.current_state(), SessionState::Running);
+    assert_eq!(sess1.disconnect_frame(PlayerHandle::new(1)), None);
+
+    let (mut stub1, mut stub2) = (GameStub::new(), GameStub::new());
+    let (mut gone1, mut gone2) = (false, false);
+    for i in 0..400u32 {
+        sess1.poll_remote_clients();
+        sess2.poll_remote_clients();
+        clock.advance(TICK);
+        step(&mut sess1, &mut stub1, PlayerHandle::new(0), i, &mut gone1);
+        step(&mut sess2, &mut stub2, PlayerHandle::new(1), i, &mut gone2);
+    }
+    assert!(gone1 && gone2, "both peers should have timed out");
+
+    // The local-handle and out-of-range cases are not disconnected remotes.
+    assert_eq!(sess1.disconnect_frame(PlayerHandle::new(0)), None);
+    assert_eq!(sess1.disconnect_frame(PlayerHandle::new(7)), None);
+
+    let frame1 = sess1.disconnect_frame(PlayerHandle::new(1)).unwrap();
+    let frame2 = sess2.disconnect_frame(PlayerHandle::new(0)).unwrap();
+    assert!(
+        !frame1.is_null(),
+        "both peers exchanged inputs before the blackout"
+    );
+    assert_eq!(frame1, frame2);
+}
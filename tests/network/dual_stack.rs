@@ -0,0 +1,148 @@
+//! P2P sessions over IPv6 and dual-stack `UdpNonBlockingSocket`s.
+//!
+//! Covers `UdpNonBlockingSocket::bind` on `::1`, and a
+//! `UdpNonBlockingSocket::bind_dual_stack` peer talking to an IPv4-only peer
+//! that it knows by an IPv4-mapped address. Hosts without IPv6 skip the tests.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, calculate_hash, protocol_config, start_two_peer_session, TestClock,
+};
+use fortress_rollback::{
+    FortressError, Frame, P2PSession, SessionBuilder, SessionState, UdpNonBlockingSocket,
+};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+/// Frame whose confirmed state both peers must agree on.
+const FRAMES: u32 = 60;
+/// Poll rounds allowed for each phase; loopback delivery is near-instant.
+const MAX_ROUNDS: usize = 2_000;
+
+fn session(
+    socket: UdpNonBlockingSocket,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new().with_protocol_config(protocol_config(clock));
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+/// One poll round: both sessions poll, then real time passes for the
+/// loopback packets to land and virtual time advances for the retries.
+fn poll_round(sessions: &mut [P2PSession<StubConfig>; 2], clock: &TestClock) {
+    for session in sessions.iter_mut() {
+        session.poll_remote_clients();
+    }
+    std::thread::sleep(Duration::from_millis(1));
+    clock.advance(Duration::from_millis(20));
+}
+
+/// Synchronizes both sessions, runs them for [`FRAMES`] frames, and checks
+/// they agree on the confirmed state.
+fn sync_and_run(mut sessions: [P2PSession<StubConfig>; 2], clock: &TestClock) {
+    let mut rounds = 0;
+    while sessions
+        .iter()
+        .any(|session| session.current_state() != SessionState::Running)
+    {
+        assert!(rounds < MAX_ROUNDS, "sessions did not synchronize");
+        poll_round(&mut sessions, clock);
+        rounds += 1;
+    }
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut states = [BTreeMap::new(), BTreeMap::new()];
+    // Keep running until every frame up to `FRAMES` is confirmed and repaired.
+    let settled = Frame::new(FRAMES as i32 + 10);
+    let mut rounds = 0;
+    while sessions
+        .iter()
+        .any(|session| session.confirmed_frame() < settled)
+    {
+        assert!(
+            rounds < MAX_ROUNDS,
+            "sessions did not confirm {FRAMES} frames"
+        );
+        for (handle, (session, (stub, states))) in sessions
+            .iter_mut()
+            .zip(stubs.iter_mut().zip(&mut states))
+            .enumerate()
+        {
+            let inp = session.current_frame().as_i32() as u32 % 5 + handle as u32;
+            match advance_local(session, handle, StubInput { inp }) {
+                Ok(Some(requests)) => stub.handle_requests_recording(requests, states),
+                Ok(None) => {},
+                Err(err) => panic!("peer {handle} failed to advance: {err}"),
+            }
+        }
+        poll_round(&mut sessions, clock);
+        rounds += 1;
+    }
+    let frame = FRAMES as i32;
+    assert_eq!(
+        calculate_hash(&states[0][&frame]),
+        calculate_hash(&states[1][&frame])
+    );
+}
+
+#[test]
+#[cfg(not(miri))]
+fn sessions_sync_and_run_over_ipv6_loopback() {
+    let loopback = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 0);
+    let (Ok(socket1), Ok(socket2)) = (
+        UdpNonBlockingSocket::bind(loopback),
+        UdpNonBlockingSocket::bind(loopback),
+    ) else {
+        return; // IPv6 unavailable on this host
+    };
+    let addr1 = socket1.local_addr().unwrap();
+    let addr2 = socket2.local_addr().unwrap();
+    assert!(addr1.is_ipv6() && addr2.is_ipv6());
+
+    let clock = TestClock::new();
+    let sessions = [
+        session(socket1, 0, addr2, &clock).unwrap(),
+        session(socket2, 1, addr1, &clock).unwrap(),
+    ];
+    sync_and_run(sessions, &clock);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn dual_stack_session_syncs_with_ipv4_peer_known_by_mapped_address() {
+    let Ok(dual) = UdpNonBlockingSocket::bind_dual_stack(0) else {
+        return; // IPv6 unavailable on this host
+    };
+    let v4 =
+        UdpNonBlockingSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+    let dual_addr = SocketAddr::new(
+        IpAddr::V4(Ipv4Addr::LOCALHOST),
+        dual.local_addr().unwrap().port(),
+    );
+    let v4_addr = v4.local_addr().unwrap();
+
+    // Matchmaking handed the dual-stack peer the IPv4 peer's mapped form.
+    let mapped = SocketAddr::new(
+        IpAddr::V6(Ipv4Addr::LOCALHOST.to_ipv6_mapped()),
+        v4_addr.port(),
+    );
+    let normalized = UdpNonBlockingSocket::normalize_addr(mapped);
+    assert_eq!(normalized, v4_addr);
+
+    let clock = TestClock::new();
+    let sessions = [
+        session(dual, 0, normalized, &clock).unwrap(),
+        session(v4, 1, dual_addr, &clock).unwrap(),
+    ];
+    sync_and_run(sessions, &clock);
+}
@@ -0,0 +1,195 @@
+//! Per-peer frame advantage ([`P2PSession::frame_advantages`]) and
+//! [`P2PSession::recommended_skip_frames`] end-to-end tests.
+//!
+//! Three peers play over links with the same chaos latency, but one of them
+//! runs slowly and skips every other frame. The other two see it as behind
+//! them (a positive advantage against it) while it sees both of them as ahead
+//! (negative). The two fast peers see each other as level, which is what lets
+//! a game name the slow player when a `WaitRecommendation` fires.
+//!
+//! Every link gets the same latency on purpose: each endpoint assumes half the
+//! round trip in each direction, so a one-sided delay would bias the estimate
+//! rather than make anyone fall behind.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_mesh, TestClock};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressEvent, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, SessionState,
+};
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Frames played once the mesh is synchronized.
+const FRAMES: usize = 300;
+/// The peer that skips frames.
+const SLOW: usize = 2;
+/// [`SLOW`] skips one step in this many.
+const SLOW_SKIPS_EVERY: usize = 2;
+/// Room for the fast peers to pull well ahead before they stall.
+const MAX_PREDICTION: usize = 16;
+/// Delay added to every packet received, on every peer.
+const LATENCY_MS: u64 = 40;
+
+fn mesh(clock: &TestClock) -> Result<Vec<P2PSession<StubConfig>>, FortressError> {
+    let configs = (0..3)
+        .map(|peer| {
+            ChaosConfig::builder()
+                .latency_ms(LATENCY_MS)
+                .seed(peer as u64)
+                .build()
+        })
+        .collect();
+    let (sockets, addrs) = create_chaos_channel_mesh(configs, clock);
+    let mut sessions = Vec::new();
+    for (local, socket) in sockets.into_iter().enumerate() {
+        let mut builder = SessionBuilder::<StubConfig>::new()
+            .with_num_players(3)?
+            .with_max_prediction_window(MAX_PREDICTION)
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            });
+        for (handle, &addr) in addrs.iter().enumerate() {
+            let player = if handle == local {
+                PlayerType::Local
+            } else {
+                PlayerType::Remote(addr)
+            };
+            builder = builder.add_player(player, PlayerHandle::new(handle))?;
+        }
+        sessions.push(builder.start_p2p_session(socket)?);
+    }
+    Ok(sessions)
+}
+
+/// Synchronizes the mesh, then plays [`FRAMES`] steps. Returns, for each
+/// peer, the `skip_frames` of every `WaitRecommendation` it received together
+/// with its [`P2PSession::frame_advantages`] at that moment.
+#[allow(clippy::type_complexity)]
+fn play(
+    sessions: &mut [P2PSession<StubConfig>],
+    clock: &TestClock,
+) -> Result<Vec<Vec<(u32, Vec<(PlayerHandle, i32)>)>>, FortressError> {
+    for _ in 0..1000 {
+        for session in sessions.iter_mut() {
+            session.poll_remote_clients();
+        }
+        if sessions
+            .iter()
+            .all(|session| session.current_state() == SessionState::Running)
+        {
+            break;
+        }
+        clock.advance(Duration::from_millis(20));
+    }
+    assert!(sessions
+        .iter()
+        .all(|session| session.current_state() == SessionState::Running));
+
+    let mut stubs: Vec<GameStub> = sessions.iter().map(|_| GameStub::new()).collect();
+    let mut recommendations = vec![Vec::new(); sessions.len()];
+    for step in 0..FRAMES {
+        for (index, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            let advantages = session.frame_advantages();
+            for event in session.events() {
+                if let FortressEvent::WaitRecommendation { skip_frames } = event {
+                    recommendations[index].push((skip_frames, advantages.clone()));
+                }
+            }
+            if index == SLOW && step % SLOW_SKIPS_EVERY == 0 {
+                continue;
+            }
+            let frame = session.current_frame().as_i32();
+            session.add_local_input(
+                PlayerHandle::new(index),
+                StubInput {
+                    inp: frame as u32 % 3,
+                },
+            )?;
+            match session.advance_frame() {
+                Ok(requests) => stubs[index].handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+        }
+        clock.advance(STEP);
+    }
+    Ok(recommendations)
+}
+
+#[test]
+fn slow_peer_is_behind_everyone_else() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let mut sessions = mesh(&clock)?;
+    let recommendations = play(&mut sessions, &clock)?;
+
+    for (index, session) in sessions.iter().enumerate() {
+        let advantages = session.frame_advantages();
+        let handles: Vec<usize> = advantages
+            .iter()
+            .map(|&(handle, _)| handle.as_usize())
+            .collect();
+        let expected: Vec<usize> = (0..3).filter(|&handle| handle != index).collect();
+        assert_eq!(handles, expected, "peer {index}: {advantages:?}");
+
+        for &(handle, advantage) in &advantages {
+            if index == SLOW {
+                assert!(advantage < 0, "slow peer vs {handle}: {advantages:?}");
+            } else if handle.as_usize() == SLOW {
+                assert!(advantage > 0, "peer {index} vs slow peer: {advantages:?}");
+            } else {
+                assert!(
+                    advantage.abs() <= 1,
+                    "peer {index} vs {handle}: {advantages:?}"
+                );
+            }
+        }
+
+        let max_advantage = advantages
+            .iter()
+            .map(|&(_, advantage)| advantage)
+            .max()
+            .unwrap();
+        assert_eq!(session.frames_ahead(), max_advantage, "peer {index}");
+        let skip_frames = session.recommended_skip_frames();
+        if index == SLOW {
+            assert_eq!(skip_frames, 0);
+        } else {
+            assert_eq!(
+                skip_frames, max_advantage as u32,
+                "peer {index}: {advantages:?} {skip_frames}"
+            );
+        }
+    }
+
+    // The fast peers were told to wait, and each time the slow peer was the
+    // one furthest behind them.
+    assert!(recommendations[SLOW].is_empty());
+    for index in (0..3).filter(|&index| index != SLOW) {
+        assert!(
+            !recommendations[index].is_empty(),
+            "peer {index} was never told to wait"
+        );
+        for (skip_frames, advantages) in &recommendations[index] {
+            let &(slowest, advantage) = advantages
+                .iter()
+                .max_by_key(|&&(_, advantage)| advantage)
+                .unwrap();
+            assert_eq!(slowest, PlayerHandle::new(SLOW), "{advantages:?}");
+            assert_eq!(*skip_frames, advantage as u32, "{advantages:?}");
+        }
+    }
+    Ok(())
+}
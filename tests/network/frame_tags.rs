@@ -0,0 +1,219 @@
+//! Frame tag ([`ProtocolConfig::frame_tags`]) end-to-end tests.
+//!
+//! Two peers tag every few frames over a lossy link. Every tag must reach the
+//! other peer exactly once, as a `FrameTag` event naming the sender's handle
+//! and the frame it tagged, even though the packets carrying it are lost and
+//! resent. The wire format and acknowledgement rules are asserted at the unit
+//! level in `network::protocol`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, create_chaos_channel_pair, synchronize_sessions_deterministic, SyncConfig,
+    TestClock,
+};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressEvent, Frame, InvalidFrameReason, InvalidRequestKind,
+    NonBlockingSocket, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+};
+use std::net::SocketAddr;
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Frames during which both peers tag.
+const TAGGED_FRAMES: i32 = 240;
+/// Untagged frames played afterwards so the last tags arrive.
+const TAIL_FRAMES: i32 = 60;
+/// Each peer tags every this many frames.
+const TAG_EVERY: i32 = 4;
+
+/// The payload a peer attaches to its tag for `frame`, so the receiver can
+/// check the tag arrived with the frame it was sent for.
+fn payload(handle: usize, frame: Frame) -> [u8; 8] {
+    let mut data = [0; 8];
+    data[..4].copy_from_slice(&frame.as_i32().to_le_bytes());
+    data[4] = handle as u8;
+    data
+}
+
+fn session<S: NonBlockingSocket<SocketAddr> + 'static>(
+    socket: S,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+    frame_tags: bool,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        frame_tags,
+        ..ProtocolConfig::default()
+    });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+/// Plays both sessions, tagging every [`TAG_EVERY`] frames on the peers in
+/// `tagging`. Returns the `(handle, frame, data)` of every `FrameTag` event
+/// each peer received, in order.
+fn play(
+    sessions: &mut [P2PSession<StubConfig>; 2],
+    clock: &TestClock,
+    tagging: [bool; 2],
+) -> Result<[Vec<(PlayerHandle, Frame, [u8; 8])>; 2], FortressError> {
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut received = [Vec::new(), Vec::new()];
+    for _ in 0..TAGGED_FRAMES + TAIL_FRAMES {
+        for (index, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            for event in session.events() {
+                if let FortressEvent::FrameTag {
+                    handle,
+                    frame,
+                    data,
+                } = event
+                {
+                    received[index].push((handle, frame, data));
+                }
+            }
+            let frame = session.current_frame();
+            if tagging[index] && frame.as_i32() < TAGGED_FRAMES && frame.as_i32() % TAG_EVERY == 0 {
+                match session.send_frame_tag(frame, payload(index, frame)) {
+                    // A peer stalled at the prediction threshold stays on
+                    // the frame it already tagged.
+                    Ok(())
+                    | Err(FortressError::InvalidRequestStructured {
+                        kind: InvalidRequestKind::FrameTagNotIncreasing { .. },
+                    }) => {},
+                    Err(err) => return Err(err),
+                }
+            }
+            session.add_local_input(
+                PlayerHandle::new(index),
+                StubInput {
+                    inp: frame.as_i32() as u32 % 5,
+                },
+            )?;
+            match session.advance_frame() {
+                Ok(requests) => stubs[index].handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+        }
+        clock.advance(STEP);
+    }
+    Ok(received)
+}
+
+#[test]
+fn tags_arrive_exactly_once_with_their_frames_under_packet_loss() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .latency_ms(20)
+            .packet_loss_rate(0.25)
+            .seed(seed)
+            .build()
+    };
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(3), chaos(5), &clock);
+    let mut sessions = [
+        session(s1, 0, a2, &clock, true)?,
+        session(s2, 1, a1, &clock, true)?,
+    ];
+    let [sess1, sess2] = &mut sessions;
+    let sync_config = SyncConfig {
+        max_iterations: 2000,
+    };
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &sync_config)
+        .expect("sessions should synchronize");
+
+    let received = play(&mut sessions, &clock, [true, true])?;
+
+    let expected: Vec<Frame> = (0..TAGGED_FRAMES)
+        .step_by(TAG_EVERY as usize)
+        .map(Frame::new)
+        .collect();
+    for (index, tags) in received.iter().enumerate() {
+        let sender = 1 - index;
+        let frames: Vec<Frame> = tags.iter().map(|&(_, frame, _)| frame).collect();
+        assert_eq!(frames, expected, "peer {index} received {frames:?}");
+        for &(handle, frame, data) in tags {
+            assert_eq!(handle, PlayerHandle::new(sender));
+            assert_eq!(data, payload(sender, frame), "tag for frame {frame}");
+        }
+        let metrics = sessions[sender].peer_metrics(PlayerHandle::new(index))?;
+        assert_eq!(metrics.frame_tags_dropped, 0);
+        assert!(
+            metrics.packets_sent > metrics.packets_received,
+            "peer {sender} lost no packets"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn tags_are_skipped_for_peers_that_did_not_enable_them() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let mut sessions = [
+        session(s1, 0, a2, &clock, true)?,
+        session(s2, 1, a1, &clock, false)?,
+    ];
+    let [sess1, sess2] = &mut sessions;
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &SyncConfig::default())
+        .expect("frame tags are negotiated, so the handshake succeeds");
+
+    let received = play(&mut sessions, &clock, [true, false])?;
+
+    assert!(received[1].is_empty(), "{:?}", received[1]);
+    assert!(sessions[1].confirmed_frame().as_i32() > TAGGED_FRAMES);
+    Ok(())
+}
+
+#[test]
+fn send_frame_tag_rejects_invalid_requests() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let mut enabled = session(s1, 0, a2, &clock, true)?;
+    let mut disabled = session(s2, 1, a1, &clock, false)?;
+
+    assert_eq!(
+        disabled.send_frame_tag(Frame::new(0), [0; 8]),
+        Err(InvalidRequestKind::FrameTagsDisabled.into())
+    );
+    assert_eq!(
+        enabled.send_frame_tag(Frame::NULL, [0; 8]),
+        Err(FortressError::InvalidFrameStructured {
+            frame: Frame::NULL,
+            reason: InvalidFrameReason::NullOrNegative,
+        })
+    );
+    enabled.send_frame_tag(Frame::new(5), [0; 8])?;
+    for frame in [Frame::new(5), Frame::new(3)] {
+        assert_eq!(
+            enabled.send_frame_tag(frame, [0; 8]),
+            Err(InvalidRequestKind::FrameTagNotIncreasing {
+                frame,
+                last_tagged: Frame::new(5),
+            }
+            .into())
+        );
+    }
+    enabled.send_frame_tag(Frame::new(6), [0; 8])?;
+    Ok(())
+}
@@ -0,0 +1,198 @@
+//! Input resync ([`ProtocolConfig::input_resync_threshold`]) end-to-end tests.
+//!
+//! A host streams to a spectator while every spectator -> host packet is lost
+//! for two seconds, so the host's delta reference (the last frame the
+//! spectator acknowledged) goes stale and the spectator prunes it from its
+//! input history. Without resync the spectator can no longer decode the
+//! host's batches and falls behind until the host gives up on it; with resync
+//! the host switches to batches that decode without history, the spectator
+//! keeps up, and packet sizes return to normal once acks flow again.
+//!
+//! The encoding and negotiation are asserted at the unit level in
+//! `network::protocol`.
+
+// Allow test-specific patterns that are appropriate for test code
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_filtered_channel_triple, synchronize_spectator_deterministic, TestClock,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, MessageKind, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder,
+};
+
+/// One frame at 60 FPS.
+const FRAME_TIME: Duration = Duration::from_micros(16_667);
+/// Frames played before, during, and after the blackout.
+const WARMUP_FRAMES: u32 = 30;
+/// Two seconds of lost spectator -> host traffic.
+const BLACKOUT_FRAMES: u32 = 120;
+const RECOVERY_FRAMES: u32 = 60;
+/// Backlog above which the host sends resync batches; below the spectator's
+/// 16-frame input history, so resync starts before the reference is pruned.
+const RESYNC_THRESHOLD: usize = 8;
+/// Comfortably longer than the blackout, so nobody is dropped.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of one host/spectator run.
+struct RunOutcome {
+    /// Frames the spectator trailed the host by right before the blackout.
+    warmup_lag: i32,
+    /// Most frames the spectator trailed the host by during the blackout.
+    max_blackout_lag: i32,
+    /// Frames the spectator trailed the host by at the end.
+    final_lag: i32,
+    /// Resync batches the host sent to the spectator.
+    resyncs_sent: u64,
+    /// Average encoded input bytes per `Input` packet during the blackout.
+    blackout_bytes_per_packet: u64,
+    /// Average encoded input bytes per `Input` packet after recovery.
+    recovered_bytes_per_packet: u64,
+    /// The host's unacknowledged backlog to the spectator at the end.
+    final_pending_output: u64,
+    /// Whether either side reported a disconnect.
+    disconnected: bool,
+}
+
+/// Encoded input bytes and `Input` packets the host sent to the spectator.
+fn input_traffic(host: &P2PSession<StubConfig>) -> (u64, u64) {
+    let metrics = host.peer_metrics(PlayerHandle::new(1)).unwrap();
+    (
+        metrics.input_bytes_post_compression,
+        metrics.messages_sent_by_kind.get(MessageKind::Input),
+    )
+}
+
+fn bytes_per_packet(start: (u64, u64), end: (u64, u64)) -> u64 {
+    (end.0 - start.0) / (end.1 - start.1).max(1)
+}
+
+fn run(input_resync_threshold: Option<usize>) -> Result<RunOutcome, FortressError> {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, _unused, host_addr, spec_addr, _unused_addr, blocked) =
+        create_filtered_channel_triple();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        input_resync_threshold,
+        ..ProtocolConfig::default()
+    };
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config.clone())
+        .with_disconnect_timeout(DISCONNECT_TIMEOUT)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(1))?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config)
+        .with_disconnect_timeout(DISCONNECT_TIMEOUT)
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let result = synchronize_spectator_deterministic(&mut spectator, &mut host, &clock);
+    assert!(result.success, "spectator should synchronize");
+
+    let mut host_stub = GameStub::new();
+    let mut spec_stub = GameStub::new();
+    let mut disconnected = false;
+    let mut warmup_lag = 0;
+    let mut max_blackout_lag = 0;
+    let mut blackout_start = (0, 0);
+    let mut blackout_end = (0, 0);
+    let mut recovered_start = (0, 0);
+    for frame in 0..WARMUP_FRAMES + BLACKOUT_FRAMES + RECOVERY_FRAMES {
+        let blackout = (WARMUP_FRAMES..WARMUP_FRAMES + BLACKOUT_FRAMES).contains(&frame);
+        if frame == WARMUP_FRAMES {
+            warmup_lag = host.current_frame().as_i32() - spectator.current_frame().as_i32();
+            blocked.block(spec_addr, host_addr);
+            blackout_start = input_traffic(&host);
+        } else if frame == WARMUP_FRAMES + BLACKOUT_FRAMES {
+            blocked.unblock(spec_addr, host_addr);
+            blackout_end = input_traffic(&host);
+        } else if frame == WARMUP_FRAMES + BLACKOUT_FRAMES + RECOVERY_FRAMES / 2 {
+            recovered_start = input_traffic(&host);
+        }
+
+        host.poll_remote_clients();
+        host.add_local_input(PlayerHandle::new(0), StubInput { inp: frame % 7 })?;
+        host_stub.handle_requests(host.advance_frame()?);
+        spectator.poll_remote_clients();
+        match spectator.advance_frame() {
+            Ok(requests) => spec_stub.handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => return Err(err),
+        }
+        disconnected |= host
+            .events()
+            .chain(spectator.events())
+            .any(|event| matches!(event, FortressEvent::Disconnected { .. }));
+        if blackout {
+            let lag = host.current_frame().as_i32() - spectator.current_frame().as_i32();
+            max_blackout_lag = max_blackout_lag.max(lag);
+        }
+        clock.advance(FRAME_TIME);
+    }
+
+    let recovered_end = input_traffic(&host);
+    let metrics = host.peer_metrics(PlayerHandle::new(1))?;
+    Ok(RunOutcome {
+        warmup_lag,
+        max_blackout_lag,
+        final_lag: host.current_frame().as_i32() - spectator.current_frame().as_i32(),
+        resyncs_sent: metrics.input_resyncs_sent,
+        blackout_bytes_per_packet: bytes_per_packet(blackout_start, blackout_end),
+        recovered_bytes_per_packet: bytes_per_packet(recovered_start, recovered_end),
+        final_pending_output: metrics.pending_output_len,
+        disconnected,
+    })
+}
+
+#[test]
+fn spectator_keeps_up_through_one_sided_ack_loss_with_resync() -> Result<(), FortressError> {
+    let outcome = run(Some(RESYNC_THRESHOLD))?;
+
+    assert!(!outcome.disconnected, "nobody is dropped");
+    assert!(
+        outcome.resyncs_sent > 0,
+        "the backlog crossed the threshold"
+    );
+    // Resync batches decode without history, so the spectator follows the
+    // host through the whole blackout.
+    assert!(
+        outcome.max_blackout_lag <= outcome.warmup_lag + 1,
+        "spectator trailed by {} frames, {} before the blackout",
+        outcome.max_blackout_lag,
+        outcome.warmup_lag
+    );
+    // Once acks flow again the backlog drains and batches shrink back to a
+    // frame or two of delta-encoded input.
+    assert!(
+        outcome.final_pending_output <= 2,
+        "{}",
+        outcome.final_pending_output
+    );
+    assert!(
+        outcome.recovered_bytes_per_packet * 4 < outcome.blackout_bytes_per_packet,
+        "recovered {} bytes/packet vs {} during the blackout",
+        outcome.recovered_bytes_per_packet,
+        outcome.blackout_bytes_per_packet
+    );
+    assert_eq!(outcome.final_lag, outcome.warmup_lag);
+    Ok(())
+}
+
+#[test]
+fn spectator_falls_behind_through_one_sided_ack_loss_without_resync() {
+    // The host keeps encoding against a reference the spectator pruned, so the
+    // spectator stops receiving input and the host gives up waiting for it.
+    let result = run(None);
+    assert!(
+        matches!(result, Err(FortressError::SpectatorTooFarBehind)),
+        "{:?}",
+        result.err()
+    );
+}
@@ -0,0 +1,142 @@
+//! Redundant input-send suppression ([`ProtocolConfig::suppress_redundant_inputs`])
+//! end-to-end tests.
+//!
+//! With suppression enabled, a peer whose local input repeats frame after frame
+//! batches those frames instead of sending one packet per frame. These tests
+//! drive two sessions over in-memory sockets under virtual time and check the
+//! observable contract: idle traffic drops, the remote never sees a network
+//! interruption, and both simulations converge to the same state as an
+//! unsuppressed run of the same input schedule.
+//!
+//! The exact batching rules (budget, connect-status changes, retransmission)
+//! are asserted at the unit level in `network::protocol`.
+
+// Allow test-specific patterns that are appropriate for test code
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, drain_sync_events, poll_with_advance, synchronize_sessions_deterministic,
+    SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+};
+
+/// Frames simulated per run.
+const FRAMES: u32 = 120;
+
+/// Outcome of one two-session run.
+struct RunOutcome {
+    /// Packets session 1 sent to its remote peer.
+    packets_sent: u64,
+    /// Whether either session reported `NetworkInterrupted`.
+    interrupted: bool,
+    /// Final game state of each session.
+    states: (StateStub, StateStub),
+}
+
+/// Runs two sessions for [`FRAMES`] frames, feeding both local players the
+/// value `input(frame)`, then settles so every frame is confirmed.
+fn run_pair(suppress: bool, input: impl Fn(u32) -> u32) -> Result<RunOutcome, FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+
+    let protocol_config = |seed: u64| ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        protocol_rng_seed: Some(seed),
+        suppress_redundant_inputs: suppress,
+        ..ProtocolConfig::default()
+    };
+
+    let mut sess1 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(101))
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+
+    let mut sess2 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(202))
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize under virtual time");
+    drain_sync_events(&mut sess1, &mut sess2);
+
+    let baseline = sess1.peer_metrics(PlayerHandle::new(1))?.packets_sent;
+    let mut stub1 = GameStub::new();
+    let mut stub2 = GameStub::new();
+    let mut interrupted = false;
+
+    for frame in 0..FRAMES {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        sess1.add_local_input(PlayerHandle::new(0), StubInput { inp: input(frame) })?;
+        sess2.add_local_input(PlayerHandle::new(1), StubInput { inp: input(frame) })?;
+        stub1.handle_requests(sess1.advance_frame()?);
+        stub2.handle_requests(sess2.advance_frame()?);
+        interrupted |= sess1
+            .events()
+            .chain(sess2.events())
+            .any(|event| matches!(event, FortressEvent::NetworkInterrupted { .. }));
+    }
+    let packets_sent = sess1.peer_metrics(PlayerHandle::new(1))?.packets_sent - baseline;
+
+    // Hold the last input so the final frames get delivered and any
+    // misprediction is rolled back before the states are compared.
+    let last = input(FRAMES - 1);
+    for _ in 0..16 {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        sess1.add_local_input(PlayerHandle::new(0), StubInput { inp: last })?;
+        sess2.add_local_input(PlayerHandle::new(1), StubInput { inp: last })?;
+        stub1.handle_requests(sess1.advance_frame()?);
+        stub2.handle_requests(sess2.advance_frame()?);
+    }
+
+    Ok(RunOutcome {
+        packets_sent,
+        interrupted,
+        states: (stub1.gs, stub2.gs),
+    })
+}
+
+/// A fully idle input stream sends far fewer packets with suppression on, and
+/// the remote never treats the quiet stretches as a network interruption.
+#[test]
+fn idle_inputs_send_fewer_packets_without_interrupting_peer() -> Result<(), FortressError> {
+    let plain = run_pair(false, |_| 0)?;
+    let suppressed = run_pair(true, |_| 0)?;
+
+    assert!(!plain.interrupted && !suppressed.interrupted);
+    assert!(
+        suppressed.packets_sent * 2 < plain.packets_sent,
+        "suppression should at least halve idle traffic: \
+         {} suppressed vs {} plain",
+        suppressed.packets_sent,
+        plain.packets_sent
+    );
+    assert_eq!(suppressed.states.0, suppressed.states.1);
+    assert_eq!(suppressed.states, plain.states);
+    Ok(())
+}
+
+/// Idle stretches around a burst of changing inputs: the burst is delivered
+/// promptly and both peers end in exactly the state an unsuppressed run
+/// reaches.
+#[test]
+fn burst_between_idle_stretches_matches_unsuppressed_run() -> Result<(), FortressError> {
+    let schedule = |frame: u32| match frame {
+        40..=59 => frame,
+        60.. => 7,
+        _ => 0,
+    };
+    let plain = run_pair(false, schedule)?;
+    let suppressed = run_pair(true, schedule)?;
+
+    assert!(!suppressed.interrupted);
+    assert!(suppressed.packets_sent < plain.packets_sent);
+    assert_eq!(suppressed.states.0, suppressed.states.1);
+    assert_eq!(suppressed.states, plain.states);
+    Ok(())
+}
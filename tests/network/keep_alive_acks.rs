@@ -0,0 +1,145 @@
+//! Keepalive acknowledgement ([`ProtocolConfig::keep_alive_acks`]) end-to-end
+//! tests.
+//!
+//! A host streams to a spectator whose outgoing packets suffer 30% loss, and
+//! the host pauses between bursts of play. The spectator only sends an
+//! `InputAck` when an `Input` arrives, so if the acks for the end of a burst
+//! are lost, the host's unacknowledged backlog sits until its next
+//! retransmission gets through. With keepalive acks the spectator's keepalives
+//! carry the acknowledgement as well, and the backlog drains within a few
+//! keepalive intervals.
+//!
+//! The wire format and receive path are asserted at the unit level in
+//! `network::protocol`.
+
+// Allow test-specific patterns that are appropriate for test code
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_pair, synchronize_spectator_deterministic, TestClock};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressEvent, PlayerHandle, PlayerType, ProtocolConfig,
+    SessionBuilder, SyncConfig,
+};
+
+/// One frame at 60 FPS.
+const FRAME_TIME: Duration = Duration::from_micros(16_667);
+/// Frames the host plays, then frames it sits paused, per cycle.
+const PLAY_FRAMES: u32 = 20;
+const PAUSE_FRAMES: u32 = 60;
+const CYCLES: u32 = 200;
+/// Loss on every spectator -> host packet.
+const SPECTATOR_LOSS: f64 = 0.3;
+
+/// Outcome of one host/spectator run.
+struct RunOutcome {
+    /// The host's unacknowledged backlog to the spectator, summed over every
+    /// paused frame.
+    paused_backlog: u64,
+    /// Paused frames on which the backlog was still non-empty.
+    paused_frames_with_backlog: u32,
+    /// Whether either side reported a disconnect.
+    disconnected: bool,
+}
+
+fn run(keep_alive_acks: bool) -> Result<RunOutcome, FortressError> {
+    let clock = TestClock::new();
+    let spectator_chaos = ChaosConfig::builder()
+        .send_loss_rate(SPECTATOR_LOSS)
+        .seed(42)
+        .build();
+    let (host_socket, spec_socket, host_addr, spec_addr) =
+        create_chaos_channel_pair(ChaosConfig::passthrough(), spectator_chaos, &clock);
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        keep_alive_acks,
+        ..ProtocolConfig::default()
+    };
+    // Retransmissions are paced well apart from keepalives, so the two ack
+    // carriers are easy to tell apart.
+    let sync_config = SyncConfig {
+        running_retry_interval: Duration::from_millis(500),
+        keepalive_interval: Duration::from_millis(100),
+        ..SyncConfig::default()
+    };
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config.clone())
+        .with_sync_config(sync_config)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(1))?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config)
+        .with_sync_config(sync_config)
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let result = synchronize_spectator_deterministic(&mut spectator, &mut host, &clock);
+    assert!(result.success, "spectator should synchronize");
+
+    let mut host_stub = GameStub::new();
+    let mut spec_stub = GameStub::new();
+    let mut outcome = RunOutcome {
+        paused_backlog: 0,
+        paused_frames_with_backlog: 0,
+        disconnected: false,
+    };
+    for frame in 0..CYCLES * (PLAY_FRAMES + PAUSE_FRAMES) {
+        let paused = frame % (PLAY_FRAMES + PAUSE_FRAMES) >= PLAY_FRAMES;
+        host.poll_remote_clients();
+        if !paused {
+            host.add_local_input(PlayerHandle::new(0), StubInput { inp: frame % 7 })?;
+            host_stub.handle_requests(host.advance_frame()?);
+        }
+        spectator.poll_remote_clients();
+        match spectator.advance_frame() {
+            Ok(requests) => spec_stub.handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => return Err(err),
+        }
+        outcome.disconnected |= host
+            .events()
+            .chain(spectator.events())
+            .any(|event| matches!(event, FortressEvent::Disconnected { .. }));
+        if paused {
+            let backlog = host.peer_metrics(PlayerHandle::new(1))?.pending_output_len;
+            outcome.paused_backlog += backlog;
+            if backlog > 0 {
+                outcome.paused_frames_with_backlog += 1;
+            }
+        }
+        clock.advance(FRAME_TIME);
+    }
+    Ok(outcome)
+}
+
+#[test]
+fn keepalive_acks_drain_the_backlog_during_pauses() -> Result<(), FortressError> {
+    let with_acks = run(true)?;
+    let without_acks = run(false)?;
+
+    assert!(!with_acks.disconnected, "nobody is dropped");
+    assert!(!without_acks.disconnected, "nobody is dropped");
+    // Without keepalive acks, lost acks at the end of a burst leave the
+    // backlog in place until a retransmission is acknowledged.
+    assert!(
+        without_acks.paused_frames_with_backlog > 0,
+        "the lossy link should strand some acks"
+    );
+    assert!(
+        with_acks.paused_backlog < without_acks.paused_backlog,
+        "backlog {} with keepalive acks vs {} without",
+        with_acks.paused_backlog,
+        without_acks.paused_backlog
+    );
+    assert!(
+        with_acks.paused_frames_with_backlog < without_acks.paused_frames_with_backlog,
+        "{} paused frames with a backlog with keepalive acks vs {} without",
+        with_acks.paused_frames_with_backlog,
+        without_acks.paused_frames_with_backlog
+    );
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! [`TracingSocket`] capture and [`ReplaySocket`] playback end-to-end.
+//!
+//! Two peers play a short match over in-memory sockets, the second one
+//! through a [`TracingSocket`]. A fresh session for the second peer then
+//! replays the captured trace alone, driven through the same schedule on a
+//! new test clock, and must reach the same confirmed frame with the same
+//! state at every confirmed frame.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, calculate_hash, create_channel_pair, protocol_config, start_two_peer_session,
+    TestClock,
+};
+use fortress_rollback::{
+    DesyncDetection, FortressError, Frame, NonBlockingSocket, P2PSession, PacketTrace,
+    ProtocolConfig, ReplaySocket, SessionBuilder, SessionState, TracingSocket,
+};
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Poll-only rounds for the handshake, plenty for in-memory delivery.
+const SYNC_ROUNDS: usize = 50;
+/// Rounds in which both peers play a frame.
+const FRAMES: usize = 120;
+
+fn session(
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+        .with_protocol_config(ProtocolConfig {
+            protocol_rng_seed: Some(local as u64 + 1),
+            ..protocol_config(clock)
+        });
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+/// A peer's session with the game it runs.
+struct Peer {
+    session: P2PSession<StubConfig>,
+    local: usize,
+    stub: GameStub,
+    states: BTreeMap<i32, StateStub>,
+}
+
+impl Peer {
+    fn new(session: P2PSession<StubConfig>, local: usize) -> Self {
+        Self {
+            session,
+            local,
+            stub: GameStub::new(),
+            states: BTreeMap::new(),
+        }
+    }
+
+    /// Round `round` of the fixed schedule: poll during the handshake, then
+    /// poll, add an input, and advance.
+    fn step(&mut self, round: usize) {
+        self.session.poll_remote_clients();
+        if round < SYNC_ROUNDS {
+            return;
+        }
+        assert_eq!(self.session.current_state(), SessionState::Running);
+        let frame = self.session.current_frame().as_i32();
+        let inp = frame as u32 % 7 + self.local as u32;
+        match advance_local(&mut self.session, self.local, StubInput { inp }) {
+            Ok(Some(requests)) => self
+                .stub
+                .handle_requests_recording(requests, &mut self.states),
+            Ok(None) => {},
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Hashes of the states of every frame up to the confirmed frame.
+    fn confirmed_states(&self) -> Vec<(i32, u64)> {
+        let confirmed = self.session.confirmed_frame().as_i32();
+        self.states
+            .range(..=confirmed)
+            .map(|(frame, state)| (*frame, calculate_hash(state)))
+            .collect()
+    }
+}
+
+/// Plays the fixed schedule with both peers, the second one traced, and
+/// returns it with its serialized trace.
+fn capture() -> Result<(Peer, Vec<u8>), FortressError> {
+    let clock = TestClock::new();
+    let (socket1, socket2, addr1, addr2) = create_channel_pair();
+    let traced = TracingSocket::new(socket2).with_clock(clock.as_protocol_clock());
+    let trace = traced.trace();
+    let mut first = Peer::new(session(socket1, 0, addr2, &clock)?, 0);
+    let mut second = Peer::new(session(traced, 1, addr1, &clock)?, 1);
+    for round in 0..SYNC_ROUNDS + FRAMES {
+        first.step(round);
+        second.step(round);
+        clock.advance(STEP);
+    }
+    // Serialized, the way a field build would save it.
+    let bytes = trace.take().to_bytes().expect("trace serializes");
+    Ok((second, bytes))
+}
+
+#[test]
+fn replayed_trace_reaches_the_same_confirmed_frame_and_states() -> Result<(), FortressError> {
+    let (original, bytes) = capture()?;
+    let confirmed = original.session.confirmed_frame();
+    assert!(confirmed > Frame::new(100), "confirmed only {confirmed}");
+    let trace = PacketTrace::<SocketAddr>::from_bytes(&bytes).expect("trace deserializes");
+    assert!(trace.received().count() > 0 && trace.sent().count() > 0);
+
+    // Replay the second peer alone, on a new clock, through the same schedule.
+    let clock = TestClock::new();
+    let replay = ReplaySocket::new(&trace)
+        .expect("trace decodes")
+        .with_clock(clock.as_protocol_clock());
+    let sent = replay.sent();
+    let remote_addr = trace.received().next().unwrap().addr;
+    let mut replayed = Peer::new(session(replay, 1, remote_addr, &clock)?, 1);
+    for round in 0..SYNC_ROUNDS + FRAMES {
+        replayed.step(round);
+        clock.advance(STEP);
+    }
+
+    assert_eq!(replayed.session.confirmed_frame(), confirmed);
+    assert_eq!(
+        replayed.session.current_frame(),
+        original.session.current_frame()
+    );
+    assert_eq!(replayed.confirmed_states(), original.confirmed_states());
+    // The replay sent exactly what the original did, when it did.
+    let sent = sent.take();
+    assert!(sent.sent().eq(trace.sent()));
+    Ok(())
+}
@@ -14,7 +14,7 @@
 // Allow test-specific patterns that are appropriate for test code
 #![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
 
-use crate::common::stubs::StubConfig;
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
 use crate::common::{
     create_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
     POLL_INTERVAL_DETERMINISTIC,
@@ -132,3 +132,62 @@ fn peer_metrics_rejects_non_remote_handles() -> Result<(), FortressError> {
     assert!(sess1.peer_metrics(PlayerHandle::new(1)).is_ok());
     Ok(())
 }
+
+/// `compression_stats` over a real session: an input whose serialized form
+/// only ever changes in byte 3 shows up as a histogram with a single hot
+/// offset, and a reset starts the statistics over.
+#[test]
+fn compression_stats_point_at_the_volatile_input_byte() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut sess1 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config.clone())
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config)
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize under virtual time");
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    for frame in 0..MEASUREMENT_ITERATIONS as u32 {
+        for (index, session) in [&mut sess1, &mut sess2].into_iter().enumerate() {
+            session.poll_remote_clients();
+            // The little-endian `u32` changes only in its top byte.
+            let input = StubInput { inp: frame << 24 };
+            session.add_local_input(PlayerHandle::new(index), input)?;
+            stubs[index].handle_requests(session.advance_frame()?);
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    let stats = sess1.compression_stats(PlayerHandle::new(1))?;
+    assert!(stats.input_packets > 0);
+    assert!(stats.frames_compared > 0);
+    assert_eq!(stats.byte_change_counts.len(), 4, "{stats:?}");
+    assert_eq!(&stats.byte_change_counts[..3], &[0, 0, 0], "{stats:?}");
+    assert_eq!(stats.byte_change_counts[3], stats.frames_compared);
+    assert!((stats.change_frequency(3) - 1.0).abs() < f64::EPSILON);
+    let ratio = stats.compression_ratio().expect("inputs were sent");
+    assert!(ratio > 0.0, "{stats:?}");
+    assert!(stats.average_raw_bytes() >= 4.0, "{stats:?}");
+
+    sess1.reset_compression_stats(PlayerHandle::new(1))?;
+    assert_eq!(
+        sess1.compression_stats(PlayerHandle::new(1))?.input_packets,
+        0
+    );
+    assert!(sess1.compression_stats(PlayerHandle::new(0)).is_err());
+    assert!(sess1
+        .reset_compression_stats(PlayerHandle::new(99))
+        .is_err());
+    Ok(())
+}
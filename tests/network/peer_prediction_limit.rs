@@ -0,0 +1,231 @@
+//! Integration tests for `ProtocolConfig::per_peer_prediction_limit`.
+//!
+//! Covers:
+//! - In a three-peer mesh where one remote's link has 50ms of `ChaosSocket`
+//!   latency, the distant remote gets a larger prediction allowance than the
+//!   nearby one, both below the global window.
+//! - When either remote stops sending inputs, peer 0 advances on exactly the
+//!   ticks it is below every remote's allowance, and stalls inside the
+//!   global window rather than at it.
+//! - Without the option every remote's allowance is the prediction window,
+//!   and a stopped remote stalls peer 0 at the window.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_mesh, TestClock};
+use fortress_rollback::{
+    ChaosConfig, FortressError, P2PSession, PlayerHandle, PlayerType, ProtocolConfig,
+    SessionBuilder, SessionState,
+};
+
+const TICK: Duration = Duration::from_millis(16);
+const MAX_PREDICTION: usize = 8;
+const NEAR: PlayerHandle = PlayerHandle::new(1);
+const FAR: PlayerHandle = PlayerHandle::new(2);
+
+/// A three-peer mesh in which peer 2 receives everything 50ms late, so
+/// peer 0 measures a longer round trip to it than to peer 1. Only peer 0
+/// sets `per_peer_prediction_limit`.
+struct Mesh {
+    clock: TestClock,
+    sessions: Vec<P2PSession<StubConfig>>,
+    stubs: Vec<GameStub>,
+}
+
+impl Mesh {
+    fn new(per_peer_prediction_limit: Option<usize>) -> Self {
+        let clock = TestClock::new();
+        let far = ChaosConfig::builder()
+            .latency(Duration::from_millis(50))
+            .seed(7)
+            .build();
+        let (sockets, addrs) = create_chaos_channel_mesh(
+            vec![ChaosConfig::passthrough(), ChaosConfig::passthrough(), far],
+            &clock,
+        );
+        let mut sessions = Vec::new();
+        for (local, socket) in sockets.into_iter().enumerate() {
+            let mut builder = SessionBuilder::<StubConfig>::new()
+                .with_num_players(3)
+                .unwrap()
+                .with_max_prediction_window(MAX_PREDICTION)
+                .with_protocol_config(ProtocolConfig {
+                    clock: Some(clock.as_protocol_clock()),
+                    per_peer_prediction_limit: per_peer_prediction_limit.filter(|_| local == 0),
+                    ..ProtocolConfig::default()
+                });
+            for (handle, &addr) in addrs.iter().enumerate() {
+                let player = if handle == local {
+                    PlayerType::Local
+                } else {
+                    PlayerType::Remote(addr)
+                };
+                builder = builder
+                    .add_player(player, PlayerHandle::new(handle))
+                    .unwrap();
+            }
+            sessions.push(builder.start_p2p_session(socket).unwrap());
+        }
+
+        for _ in 0..200 {
+            for session in &mut sessions {
+                session.poll_remote_clients();
+            }
+            if sessions
+                .iter()
+                .all(|s| s.current_state() == SessionState::Running)
+            {
+                break;
+            }
+            clock.advance(Duration::from_millis(50));
+        }
+        assert!(sessions
+            .iter()
+            .all(|s| s.current_state() == SessionState::Running));
+
+        let mut mesh = Self {
+            clock,
+            sessions,
+            stubs: (0..3).map(|_| GameStub::new()).collect(),
+        };
+        // Let several quality reports settle the round-trip estimates.
+        for _ in 0..60 {
+            mesh.tick(None);
+        }
+        mesh
+    }
+
+    /// One tick in which every peer but `stopped` advances. Returns whether
+    /// peer 0 simulated a new frame, and whether, after polling, it already
+    /// ran some remote's allowance (or the global window) ahead.
+    fn tick(&mut self, stopped: Option<usize>) -> (bool, bool) {
+        self.clock.advance(TICK);
+        // Peer 0 polls last, so the poll inside its `advance_frame` finds
+        // nothing the others sent after `blocked` was measured.
+        for session in self.sessions.iter_mut().rev() {
+            session.poll_remote_clients();
+        }
+        let blocked = self.at_limit();
+        let mut advanced = false;
+        for (i, (session, stub)) in self.sessions.iter_mut().zip(&mut self.stubs).enumerate() {
+            if Some(i) == stopped {
+                continue;
+            }
+            let before = session.current_frame();
+            session
+                .add_local_input(PlayerHandle::new(i), StubInput { inp: 0 })
+                .unwrap();
+            match session.advance_frame() {
+                Ok(requests) => stub.handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(error) => panic!("peer {i}: {error}"),
+            }
+            if i == 0 {
+                advanced = session.current_frame() > before;
+            }
+        }
+        (advanced, blocked)
+    }
+
+    /// How far peer 0 runs ahead of `handle`'s confirmed input.
+    fn frames_ahead(&self, handle: PlayerHandle) -> i32 {
+        let session = &self.sessions[0];
+        session.current_frame() - session.confirmed_frame_for(handle).unwrap()
+    }
+
+    /// How far peer 0 runs ahead of its overall confirmed frame.
+    fn global_frames_ahead(&self) -> i32 {
+        let session = &self.sessions[0];
+        session.current_frame() - session.confirmed_frame()
+    }
+
+    fn limit(&self, handle: PlayerHandle) -> usize {
+        self.sessions[0].prediction_limit_for(handle).unwrap()
+    }
+
+    fn at_limit(&self) -> bool {
+        self.global_frames_ahead() >= MAX_PREDICTION as i32
+            || [NEAR, FAR]
+                .into_iter()
+                .any(|handle| self.frames_ahead(handle) >= self.limit(handle) as i32)
+    }
+}
+
+/// Stops `stopped` and checks that peer 0 advances on exactly the ticks it
+/// is below every remote's allowance; returns the mesh once it stalled.
+fn stall_behind(stopped: PlayerHandle, per_peer_prediction_limit: Option<usize>) -> Mesh {
+    let mut mesh = Mesh::new(per_peer_prediction_limit);
+    let mut stalled = false;
+    for tick in 0..30 {
+        let (advanced, blocked) = mesh.tick(Some(stopped.as_usize()));
+        assert_eq!(
+            advanced,
+            !blocked,
+            "tick {tick} after stopping {stopped}: near {}/{}, far {}/{}",
+            mesh.frames_ahead(NEAR),
+            mesh.limit(NEAR),
+            mesh.frames_ahead(FAR),
+            mesh.limit(FAR),
+        );
+        stalled |= !advanced;
+    }
+    assert!(stalled, "peer 0 never stalled behind {stopped}");
+    mesh
+}
+
+#[test]
+fn distant_remote_gets_a_larger_allowance() {
+    let mesh = Mesh::new(Some(2));
+    let near = mesh.limit(NEAR);
+    let far = mesh.limit(FAR);
+    assert!(near >= 2, "near allowance {near}");
+    assert!(near < far, "near {near}, far {far}");
+    assert!(far < MAX_PREDICTION, "far allowance {far}");
+    assert_eq!(
+        mesh.sessions[0].prediction_limit_for(PlayerHandle::new(0)),
+        None
+    );
+    assert_eq!(
+        mesh.sessions[0].prediction_limit_for(PlayerHandle::new(3)),
+        None
+    );
+}
+
+#[test]
+fn stopped_distant_remote_stalls_inside_the_window() {
+    let mesh = stall_behind(FAR, Some(2));
+    assert!(mesh.at_limit());
+    assert!(
+        mesh.global_frames_ahead() < MAX_PREDICTION as i32,
+        "stalled {} frames ahead",
+        mesh.global_frames_ahead()
+    );
+}
+
+#[test]
+fn stopped_nearby_remote_stalls_inside_the_window() {
+    let mesh = stall_behind(NEAR, Some(2));
+    assert!(mesh.at_limit());
+    assert!(
+        mesh.global_frames_ahead() < MAX_PREDICTION as i32,
+        "stalled {} frames ahead",
+        mesh.global_frames_ahead()
+    );
+}
+
+#[test]
+fn without_the_option_every_remote_gets_the_window() {
+    let mesh = stall_behind(FAR, None);
+    assert_eq!(mesh.limit(NEAR), MAX_PREDICTION);
+    assert_eq!(mesh.limit(FAR), MAX_PREDICTION);
+    assert_eq!(mesh.global_frames_ahead(), MAX_PREDICTION as i32);
+}
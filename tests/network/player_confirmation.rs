@@ -0,0 +1,121 @@
+//! Integration tests for `P2PSession::confirmed_frame_for` and
+//! `P2PSession::limiting_player`.
+//!
+//! Covers:
+//! - With the remote peer's inputs delayed by a `ChaosSocket`, that peer is
+//!   reported as limiting, its per-player confirmed frame is the global
+//!   confirmed frame, and it trails the local player by about the latency in
+//!   frames.
+//! - Without latency the gap is at most a frame.
+//! - Spectator and out-of-range handles have no per-player confirmed frame.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_pair, TestClock};
+use fortress_rollback::{
+    ChaosConfig, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+};
+
+const TICK: Duration = Duration::from_millis(16);
+const FRAMES: usize = 120;
+
+/// Runs two peers for [`FRAMES`] ticks while peer 0 receives everything
+/// `latency` late, and returns peer 0's session.
+fn run(latency: Duration) -> P2PSession<StubConfig> {
+    let clock = TestClock::new();
+    let delayed = ChaosConfig::builder().latency(latency).seed(7).build();
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(delayed, ChaosConfig::passthrough(), &clock);
+    let builder = |local: usize, remote| {
+        let (first, second) = if local == 0 {
+            (PlayerType::Local, PlayerType::Remote(remote))
+        } else {
+            (PlayerType::Remote(remote), PlayerType::Local)
+        };
+        SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            })
+            .add_player(first, PlayerHandle::new(0))
+            .unwrap()
+            .add_player(second, PlayerHandle::new(1))
+            .unwrap()
+    };
+    let mut sess1 = builder(0, a2).start_p2p_session(s1).unwrap();
+    let mut sess2 = builder(1, a1).start_p2p_session(s2).unwrap();
+
+    for _ in 0..200 {
+        sess1.poll_remote_clients();
+        sess2.poll_remote_clients();
+        if sess1.current_state() == SessionState::Running
+            && sess2.current_state() == SessionState::Running
+        {
+            break;
+        }
+        clock.advance(Duration::from_millis(50));
+    }
+    assert_eq!(sess1.current_state(), SessionState::Running);
+    assert_eq!(sess2.current_state(), SessionState::Running);
+
+    let (mut stub1, mut stub2) = (GameStub::new(), GameStub::new());
+    for frame in 0..FRAMES {
+        clock.advance(TICK);
+        sess1.poll_remote_clients();
+        sess2.poll_remote_clients();
+        let inp = frame as u32;
+        sess1
+            .add_local_input(PlayerHandle::new(0), StubInput { inp })
+            .unwrap();
+        stub1.handle_requests(sess1.advance_frame().unwrap());
+        sess2
+            .add_local_input(PlayerHandle::new(1), StubInput { inp })
+            .unwrap();
+        stub2.handle_requests(sess2.advance_frame().unwrap());
+    }
+    sess1
+}
+
+/// `(local, remote)` confirmed frames of peer 0, after checking they agree
+/// with the global confirmed frame and the limiting player.
+fn per_player(session: &P2PSession<StubConfig>) -> (i32, i32) {
+    let local = session.confirmed_frame_for(PlayerHandle::new(0)).unwrap();
+    let remote = session.confirmed_frame_for(PlayerHandle::new(1)).unwrap();
+    assert_eq!(session.confirmed_frame(), std::cmp::min(local, remote));
+    let limiting = session.limiting_player().unwrap();
+    assert_eq!(
+        session.confirmed_frame_for(limiting),
+        Some(session.confirmed_frame())
+    );
+    (local.as_i32(), remote.as_i32())
+}
+
+#[test]
+fn delayed_peer_is_limiting_and_trails_by_its_latency() {
+    let (local, remote) = per_player(&run(Duration::ZERO));
+    let baseline = local - remote;
+    assert!((0..=1).contains(&baseline), "baseline gap {baseline}");
+
+    let session = run(Duration::from_millis(80));
+    let (local, remote) = per_player(&session);
+    assert_eq!(session.limiting_player(), Some(PlayerHandle::new(1)));
+    // 80ms at 16ms per frame: 5 more frames behind, give or take the tick
+    // the packet lands in.
+    let extra = local - remote - baseline;
+    assert!((5..=6).contains(&extra), "extra gap {extra}");
+}
+
+#[test]
+fn non_player_handles_have_no_confirmed_frame() {
+    let session = run(Duration::ZERO);
+    assert_eq!(session.confirmed_frame_for(PlayerHandle::new(2)), None);
+    assert_eq!(session.confirmed_frame_for(PlayerHandle::new(100)), None);
+}
@@ -0,0 +1,163 @@
+//! Integration tests for `SessionBuilder::with_pre_established`.
+//!
+//! Covers:
+//! - Two peers given each other's connection IDs reach `Running` after a
+//!   single sync roundtrip over an in-memory loopback, where the default
+//!   handshake is still synchronizing.
+//! - A wrong pre-shared ID falls back to the full handshake and converges.
+//! - Builder validation: reserved IDs and addresses without an endpoint.
+//!
+//! RTT seeding and the fallback bookkeeping are unit-tested in
+//! `network::protocol`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::StubConfig;
+use crate::common::{
+    create_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, InvalidRequestKind, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, SessionState,
+};
+use std::net::SocketAddr;
+
+const HOST_CONN_ID: u32 = 0x1111_2222;
+const GUEST_CONN_ID: u32 = 0x3333_4444;
+
+fn builder(clock: &TestClock, local: usize, remote_addr: SocketAddr) -> SessionBuilder<StubConfig> {
+    let remote = PlayerType::Remote(remote_addr);
+    let (first, second) = if local == 0 {
+        (PlayerType::Local, remote)
+    } else {
+        (remote, PlayerType::Local)
+    };
+    SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        })
+        .add_player(first, PlayerHandle::new(0))
+        .unwrap()
+        .add_player(second, PlayerHandle::new(1))
+        .unwrap()
+}
+
+/// Host and guest sessions; `host_expects` is the guest ID the host was told.
+fn sessions(
+    clock: &TestClock,
+    pre_established: bool,
+    host_expects: u32,
+) -> Result<(P2PSession<StubConfig>, P2PSession<StubConfig>), FortressError> {
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let (mut host, mut guest) = (builder(clock, 0, a2), builder(clock, 1, a1));
+    if pre_established {
+        host = host.with_pre_established(a2, HOST_CONN_ID, host_expects)?;
+        guest = guest.with_pre_established(a1, GUEST_CONN_ID, HOST_CONN_ID)?;
+    }
+    Ok((host.start_p2p_session(s1)?, guest.start_p2p_session(s2)?))
+}
+
+/// Polls each session twice in turn without advancing the clock, so no sync
+/// request is retried: exactly one roundtrip in each direction.
+fn one_roundtrip(host: &mut P2PSession<StubConfig>, guest: &mut P2PSession<StubConfig>) {
+    for _ in 0..2 {
+        host.poll_remote_clients();
+        guest.poll_remote_clients();
+    }
+}
+
+/// `(total, count)` of every `Synchronizing` event, and whether the session
+/// reported `Synchronized`.
+fn sync_events(session: &mut P2PSession<StubConfig>) -> (Vec<(u32, u32)>, bool) {
+    let mut steps = Vec::new();
+    let mut synchronized = false;
+    for event in session.events() {
+        match event {
+            FortressEvent::Synchronizing { total, count, .. } => steps.push((total, count)),
+            FortressEvent::Synchronized { .. } => synchronized = true,
+            _ => {},
+        }
+    }
+    (steps, synchronized)
+}
+
+#[test]
+fn pre_established_peers_run_after_one_roundtrip() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut host, mut guest) = sessions(&clock, true, GUEST_CONN_ID)?;
+    one_roundtrip(&mut host, &mut guest);
+
+    assert_eq!(host.current_state(), SessionState::Running);
+    assert_eq!(guest.current_state(), SessionState::Running);
+    for session in [&mut host, &mut guest] {
+        let (steps, synchronized) = sync_events(session);
+        assert!(steps.is_empty(), "no intermediate steps: {steps:?}");
+        assert!(synchronized);
+    }
+
+    // The default handshake needs more roundtrips than that.
+    let clock = TestClock::new();
+    let (mut host, mut guest) = sessions(&clock, false, GUEST_CONN_ID)?;
+    one_roundtrip(&mut host, &mut guest);
+    assert_eq!(host.current_state(), SessionState::Synchronizing);
+    assert_eq!(guest.current_state(), SessionState::Synchronizing);
+    Ok(())
+}
+
+#[test]
+fn wrong_pre_shared_id_falls_back_to_the_full_handshake() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut host, mut guest) = sessions(&clock, true, 0x5555_6666)?;
+    one_roundtrip(&mut host, &mut guest);
+
+    // The guest was told the host's real ID; the host waits for the rest of
+    // the default five roundtrips.
+    assert_eq!(guest.current_state(), SessionState::Running);
+    assert_eq!(host.current_state(), SessionState::Synchronizing);
+
+    synchronize_sessions_deterministic(&mut host, &mut guest, &clock, &SyncConfig::default())
+        .expect("the fallback handshake should converge");
+    let (steps, synchronized) = sync_events(&mut host);
+    assert_eq!(steps, vec![(5, 1), (5, 2), (5, 3), (5, 4)]);
+    assert!(synchronized);
+    Ok(())
+}
+
+#[test]
+fn reserved_connection_ids_are_rejected() {
+    let clock = TestClock::new();
+    let (_s1, _s2, _a1, a2) = create_channel_pair();
+    for conn_id in [0, 0x0001_0000] {
+        let result = builder(&clock, 0, a2).with_pre_established(a2, conn_id, GUEST_CONN_ID);
+        assert!(matches!(
+            result,
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::InvalidConnectionId { conn_id: id }
+            }) if id == conn_id
+        ));
+    }
+}
+
+#[test]
+fn pre_established_address_without_endpoint_is_rejected() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, _s2, _a1, a2) = create_channel_pair();
+    let stranger: SocketAddr = ([127, 0, 0, 1], 19999).into();
+    let result = builder(&clock, 0, a2)
+        .with_pre_established(stranger, HOST_CONN_ID, GUEST_CONN_ID)?
+        .start_p2p_session(s1);
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotSupported { .. }
+        })
+    ));
+    Ok(())
+}
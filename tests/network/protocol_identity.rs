@@ -0,0 +1,212 @@
+//! Integration tests for connection identity across a peer restart.
+//!
+//! Covers:
+//! - `SessionBuilder::with_protocol_identity` pins the ID every endpoint sends
+//!   with, and both sessions report the IDs in use.
+//! - A restarted peer that pins its old ID is answered by a peer that still
+//!   runs the old connection; one with a fresh ID is not.
+//! - `SessionBuilder::with_peer_restart` lets a restarted peer re-handshake
+//!   with its disconnected endpoint; without it the endpoint ignores the peer.
+//!
+//! Every process restart is a new session attached at the same address of a
+//! shared [`RoutingBus`]. Collision re-rolls and the endpoint rebuild are
+//! unit-tested in `network::protocol`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::StubConfig;
+use crate::common::{
+    synchronize_sessions_deterministic, RoutingBus, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    DisconnectBehavior, FortressError, FortressEvent, InvalidRequestKind, P2PSession, PlayerHandle,
+    PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+};
+use std::net::SocketAddr;
+
+const HOST: PlayerHandle = PlayerHandle::new(0);
+const GUEST: PlayerHandle = PlayerHandle::new(1);
+const PINNED_ID: u32 = 0x0bad_cafe;
+/// Polls that keep the restarted guest from synchronizing with a host that
+/// ignores it (well past the sync retry interval).
+const IGNORED_POLLS: usize = 40;
+
+fn addrs() -> (SocketAddr, SocketAddr) {
+    (
+        ([127, 0, 0, 1], 21001).into(),
+        ([127, 0, 0, 1], 21002).into(),
+    )
+}
+
+fn builder(
+    clock: &TestClock,
+    local: PlayerHandle,
+    remote_addr: SocketAddr,
+) -> SessionBuilder<StubConfig> {
+    let remote = PlayerType::Remote(remote_addr);
+    let (first, second) = if local == HOST {
+        (PlayerType::Local, remote)
+    } else {
+        (remote, PlayerType::Local)
+    };
+    SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        })
+        .with_disconnect_behavior(DisconnectBehavior::ContinueWithout)
+        .add_player(first, PlayerHandle::new(0))
+        .unwrap()
+        .add_player(second, PlayerHandle::new(1))
+        .unwrap()
+}
+
+fn guest(
+    bus: &RoutingBus,
+    clock: &TestClock,
+    pinned: Option<u32>,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let (host_addr, guest_addr) = addrs();
+    let mut builder = builder(clock, GUEST, host_addr);
+    if let Some(conn_id) = pinned {
+        builder = builder.with_protocol_identity(conn_id)?;
+    }
+    builder.start_p2p_session(bus.socket(guest_addr))
+}
+
+/// Polls the host alone until its guest endpoint times out.
+fn wait_for_guest_disconnect(host: &mut P2PSession<StubConfig>, clock: &TestClock) {
+    for _ in 0..200 {
+        host.poll_remote_clients();
+        if host
+            .events()
+            .any(|event| matches!(event, FortressEvent::Disconnected { .. }))
+        {
+            return;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    panic!("the host never noticed the guest leaving");
+}
+
+/// Polls both sessions; returns whether the restarted guest reached
+/// `Running` and whether the host reported a new handshake for it.
+fn poll_restart(
+    host: &mut P2PSession<StubConfig>,
+    guest: &mut P2PSession<StubConfig>,
+    clock: &TestClock,
+) -> (bool, bool) {
+    let mut resynchronized = false;
+    for _ in 0..IGNORED_POLLS {
+        host.poll_remote_clients();
+        guest.poll_remote_clients();
+        resynchronized |= host
+            .events()
+            .any(|event| matches!(event, FortressEvent::Synchronized { .. }));
+        if resynchronized && guest.current_state() == SessionState::Running {
+            break;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    (
+        guest.current_state() == SessionState::Running,
+        resynchronized,
+    )
+}
+
+#[test]
+fn pinned_identity_is_used_and_learned() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let bus = RoutingBus::new();
+    let (host_addr, guest_addr) = addrs();
+    let mut host = builder(&clock, HOST, guest_addr)
+        .with_protocol_identity(PINNED_ID)?
+        .start_p2p_session(bus.socket(host_addr))?;
+    let mut guest = guest(&bus, &clock, None)?;
+
+    assert_eq!(host.local_connection_id(GUEST), Some(PINNED_ID));
+    assert_eq!(host.remote_connection_id(GUEST), None);
+    // Local players have no endpoint.
+    assert_eq!(host.local_connection_id(HOST), None);
+
+    synchronize_sessions_deterministic(&mut host, &mut guest, &clock, &SyncConfig::default())?;
+    assert_eq!(guest.remote_connection_id(HOST), Some(PINNED_ID));
+    assert_eq!(
+        host.remote_connection_id(GUEST),
+        guest.local_connection_id(HOST)
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_protocol_identities_are_rejected() {
+    let clock = TestClock::new();
+    let (_, guest_addr) = addrs();
+    for conn_id in [0, 0x0001_0000] {
+        let result = builder(&clock, HOST, guest_addr).with_protocol_identity(conn_id);
+        assert!(matches!(
+            result,
+            Err(FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::InvalidConnectionId { conn_id: id }
+            }) if id == conn_id
+        ));
+    }
+}
+
+#[test]
+fn restarted_guest_with_its_pinned_identity_is_answered() -> Result<(), FortressError> {
+    for (pinned_again, answered) in [(Some(PINNED_ID), true), (None, false)] {
+        let clock = TestClock::new();
+        let bus = RoutingBus::new();
+        let (host_addr, guest_addr) = addrs();
+        let mut host =
+            builder(&clock, HOST, guest_addr).start_p2p_session(bus.socket(host_addr))?;
+        let mut first = guest(&bus, &clock, Some(PINNED_ID))?;
+        synchronize_sessions_deterministic(&mut host, &mut first, &clock, &SyncConfig::default())?;
+        drop(first);
+
+        // The host still runs the old connection when the guest comes back.
+        let mut restarted = guest(&bus, &clock, pinned_again)?;
+        let (running, _) = poll_restart(&mut host, &mut restarted, &clock);
+        assert_eq!(running, answered, "pinned again: {pinned_again:?}");
+    }
+    Ok(())
+}
+
+#[test]
+fn restarted_guest_rehandshakes_only_with_peer_restart() -> Result<(), FortressError> {
+    for peer_restart in [true, false] {
+        let clock = TestClock::new();
+        let bus = RoutingBus::new();
+        let (host_addr, guest_addr) = addrs();
+        let mut host = builder(&clock, HOST, guest_addr)
+            .with_peer_restart(peer_restart)
+            .start_p2p_session(bus.socket(host_addr))?;
+        let mut first = guest(&bus, &clock, None)?;
+        synchronize_sessions_deterministic(&mut host, &mut first, &clock, &SyncConfig::default())?;
+        let old_guest_id = host.remote_connection_id(GUEST);
+        drop(first);
+        wait_for_guest_disconnect(&mut host, &clock);
+
+        let mut restarted = guest(&bus, &clock, None)?;
+        let (running, resynchronized) = poll_restart(&mut host, &mut restarted, &clock);
+        assert_eq!(running, peer_restart);
+        assert_eq!(resynchronized, peer_restart);
+        let expected_guest_id = if peer_restart {
+            restarted.local_connection_id(HOST)
+        } else {
+            old_guest_id
+        };
+        assert_eq!(host.remote_connection_id(GUEST), expected_guest_id);
+        // The slot stays dropped; resuming play is up to the application.
+        assert_eq!(host.diagnostic_player_connected(GUEST), Some(false));
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! Redundant input ([`ProtocolConfig::redundant_input_frames`]) end-to-end
+//! tests.
+//!
+//! Two peers play over a link that loses 20% of packets in each direction,
+//! once sending only unacknowledged inputs (`1`, the default) and once
+//! resending up to four frames per packet. Redundancy must never make remote
+//! input arrive later, must not desync the peers, and must show up as
+//! overhead in the peer metrics. The encoding is asserted at the unit level
+//! in `network::protocol`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_chaos_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    ChaosConfig, DesyncDetection, FortressError, FortressEvent, NonBlockingSocket, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+};
+use std::net::SocketAddr;
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Frames played over the lossy link.
+const FRAMES: u32 = 600;
+
+fn session<S: NonBlockingSocket<SocketAddr> + 'static>(
+    socket: S,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+    redundant_input_frames: usize,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            redundant_input_frames,
+            ..ProtocolConfig::default()
+        });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+/// Outcome of one lossy run.
+struct RunOutcome {
+    /// Frames either peer simulated without the other's input, summed over
+    /// every advance.
+    frames_predicted: i64,
+    desyncs: usize,
+    checksums_compared: u64,
+    /// Acknowledged frames each peer resent.
+    redundant_frames_sent: [u64; 2],
+}
+
+fn run(redundant_input_frames: usize) -> Result<RunOutcome, FortressError> {
+    let clock = TestClock::new();
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .packet_loss_rate(0.20)
+            .seed(seed)
+            .build()
+    };
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(11), chaos(13), &clock);
+    let mut sessions = [
+        session(s1, 0, a2, &clock, redundant_input_frames)?,
+        session(s2, 1, a1, &clock, redundant_input_frames)?,
+    ];
+    let [sess1, sess2] = &mut sessions;
+    let sync_config = SyncConfig {
+        max_iterations: 2000,
+    };
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &sync_config)
+        .expect("sessions should synchronize");
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut frames_predicted = 0;
+    let mut desyncs = 0;
+    for step in 0..FRAMES {
+        for (index, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            desyncs += session
+                .events()
+                .filter(|event| matches!(event, FortressEvent::DesyncDetected { .. }))
+                .count();
+            session.add_local_input(PlayerHandle::new(index), StubInput { inp: step % 5 })?;
+            match session.advance_frame() {
+                Ok(requests) => stubs[index].handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+            let remote = session
+                .confirmed_frame_for(PlayerHandle::new(1 - index))
+                .expect("remote handle");
+            let newest = session.current_frame().as_i32() - 1;
+            frames_predicted += i64::from((newest - remote.as_i32()).max(0));
+        }
+        clock.advance(STEP);
+    }
+
+    let redundant_frames_sent = [0, 1].map(|index| {
+        sessions[index]
+            .peer_metrics(PlayerHandle::new(1 - index))
+            .unwrap()
+            .redundant_input_frames_sent
+    });
+    Ok(RunOutcome {
+        frames_predicted,
+        desyncs,
+        checksums_compared: sessions[0].metrics().checksums_compared,
+        redundant_frames_sent,
+    })
+}
+
+#[test]
+fn redundant_inputs_cost_no_latency_under_packet_loss() -> Result<(), FortressError> {
+    let single = run(1)?;
+    let redundant = run(4)?;
+
+    assert!(
+        redundant.frames_predicted <= single.frames_predicted,
+        "{} frames predicted with redundancy, {} without",
+        redundant.frames_predicted,
+        single.frames_predicted
+    );
+    for outcome in [&single, &redundant] {
+        assert_eq!(outcome.desyncs, 0);
+        assert!(outcome.checksums_compared > 0);
+    }
+    assert_eq!(single.redundant_frames_sent, [0, 0]);
+    assert!(
+        redundant.redundant_frames_sent.iter().all(|&sent| sent > 0),
+        "{:?}",
+        redundant.redundant_frames_sent
+    );
+    Ok(())
+}
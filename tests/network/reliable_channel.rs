@@ -0,0 +1,177 @@
+//! Reliable channel ([`P2PSession::send_reliable`]) end-to-end tests.
+//!
+//! Two peers play over a link that loses a fifth of the packets and reorders
+//! many of the rest, each sending a numbered sequence of messages to the other
+//! through a small window. Every message must reach the other peer exactly
+//! once and in order, as a `ReliableMessage` event naming the sender's
+//! address. Sequencing, acknowledgement, and resend rules are asserted at the
+//! unit level in `network::protocol`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, create_chaos_channel_pair, synchronize_sessions_deterministic, SyncConfig,
+    TestClock,
+};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressEvent, InvalidRequestKind, NonBlockingSocket, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+};
+use std::net::SocketAddr;
+
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Messages each peer sends.
+const MESSAGES: usize = 50;
+/// Unacknowledged messages each peer may have in flight.
+const WINDOW: usize = 4;
+/// Steps after which the test gives up on delivery.
+const MAX_STEPS: usize = 3000;
+
+/// The payload of the `index`th message sent by `handle`.
+fn payload(handle: usize, index: usize) -> Vec<u8> {
+    format!("peer {handle} message {index}").into_bytes()
+}
+
+fn session<S: NonBlockingSocket<SocketAddr> + 'static>(
+    socket: S,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        reliable_window: WINDOW,
+        max_reliable_payload: 32,
+        ..ProtocolConfig::default()
+    });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+#[test]
+fn messages_arrive_in_order_exactly_once_under_loss_and_reordering() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .latency_ms(20)
+            .jitter_ms(15)
+            .packet_loss_rate(0.2)
+            .reorder_buffer_size(4)
+            .reorder_rate(0.3)
+            .seed(seed)
+            .build()
+    };
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(11), chaos(13), &clock);
+    let addrs = [a1, a2];
+    let mut sessions = [session(s1, 0, a2, &clock)?, session(s2, 1, a1, &clock)?];
+    let [sess1, sess2] = &mut sessions;
+    let sync_config = SyncConfig {
+        max_iterations: 2000,
+    };
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &sync_config)
+        .expect("sessions should synchronize");
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut sent = [0; 2];
+    let mut window_full = [false; 2];
+    let mut received: [Vec<Vec<u8>>; 2] = [Vec::new(), Vec::new()];
+    for _ in 0..MAX_STEPS {
+        if received.iter().all(|messages| messages.len() >= MESSAGES) {
+            break;
+        }
+        for (index, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            for event in session.events() {
+                if let FortressEvent::ReliableMessage { addr, payload } = event {
+                    assert_eq!(addr, addrs[1 - index], "peer {index}");
+                    received[index].push(payload);
+                }
+            }
+            while sent[index] < MESSAGES {
+                match session
+                    .send_reliable(PlayerHandle::new(1 - index), payload(index, sent[index]))
+                {
+                    Ok(()) => sent[index] += 1,
+                    Err(FortressError::InvalidRequestStructured {
+                        kind: InvalidRequestKind::ReliableWindowFull { window: WINDOW },
+                    }) => {
+                        window_full[index] = true;
+                        break;
+                    },
+                    Err(err) => return Err(err),
+                }
+            }
+            let frame = session.current_frame();
+            session.add_local_input(
+                PlayerHandle::new(index),
+                StubInput {
+                    inp: frame.as_i32() as u32 % 5,
+                },
+            )?;
+            match session.advance_frame() {
+                Ok(requests) => stubs[index].handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+        }
+        clock.advance(STEP);
+    }
+
+    for (index, messages) in received.iter().enumerate() {
+        let sender = 1 - index;
+        let expected: Vec<Vec<u8>> = (0..MESSAGES).map(|i| payload(sender, i)).collect();
+        assert_eq!(messages, &expected, "peer {index}");
+        assert!(window_full[sender], "peer {sender} never filled its window");
+        let metrics = sessions[sender].peer_metrics(PlayerHandle::new(index))?;
+        assert!(
+            metrics.packets_sent > metrics.packets_received,
+            "peer {sender} lost no packets"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn send_reliable_rejects_invalid_requests() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, _s2, _a1, a2) = create_channel_pair();
+    let mut session = session(s1, 0, a2, &clock)?;
+
+    assert_eq!(
+        session.send_reliable(PlayerHandle::new(0), b"local".to_vec()),
+        Err(InvalidRequestKind::NotRemotePlayerOrSpectator {
+            handle: PlayerHandle::new(0),
+        }
+        .into())
+    );
+    assert_eq!(
+        session.send_reliable(PlayerHandle::new(1), vec![0; 33]),
+        Err(InvalidRequestKind::ReliablePayloadTooLarge { len: 33, max: 32 }.into())
+    );
+    // Messages queue before the peer is synchronized, up to the window.
+    for index in 0..WINDOW {
+        session.send_reliable(PlayerHandle::new(1), payload(0, index))?;
+    }
+    assert_eq!(
+        session.send_reliable(PlayerHandle::new(1), payload(0, WINDOW)),
+        Err(InvalidRequestKind::ReliableWindowFull { window: WINDOW }.into())
+    );
+    Ok(())
+}
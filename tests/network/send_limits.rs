@@ -0,0 +1,138 @@
+//! Per-endpoint send limits ([`ProtocolConfig::max_packets_per_poll`] and
+//! [`ProtocolConfig::max_bytes_per_second`]).
+//!
+//! Both peers run with a budget of one message per poll and the smallest byte
+//! rate session construction accepts. Messages beyond the budget must stay
+//! queued and trickle out over the following polls, visible as a send backlog
+//! in `NetworkStats`, while keepalives and inputs still get through often
+//! enough that neither peer ever reports the other as interrupted.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, create_channel_pair, protocol_config, start_two_peer_session,
+    synchronize_sessions_deterministic, ChannelSocket, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, Message, NonBlockingSocket, P2PSession, PlayerHandle,
+    ProtocolConfig, SessionBuilder,
+};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 1472 bytes per 200 ms keepalive interval: the minimum accepted rate.
+const MAX_BYTES_PER_SECOND: u64 = 7_360;
+/// Steps run once both peers are synchronized (10 s of simulated time).
+const STEPS: usize = 600;
+
+/// A [`ChannelSocket`] recording the most sends made between two polls.
+struct CountingSocket {
+    inner: ChannelSocket,
+    sends_since_poll: usize,
+    max_sends_per_poll: Arc<AtomicUsize>,
+}
+
+impl NonBlockingSocket<SocketAddr> for CountingSocket {
+    fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        self.sends_since_poll += 1;
+        self.max_sends_per_poll
+            .fetch_max(self.sends_since_poll, Ordering::Relaxed);
+        self.inner.send_to(msg, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+        self.sends_since_poll = 0;
+        self.inner.receive_all_messages()
+    }
+}
+
+fn session(
+    socket: CountingSocket,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(ProtocolConfig {
+            max_packets_per_poll: 1,
+            max_bytes_per_second: Some(MAX_BYTES_PER_SECOND),
+            ..protocol_config(clock)
+        })
+        // Inputs arrive later than usual, so give the timers more room.
+        .with_disconnect_notify_delay(Duration::from_secs(2))
+        .with_disconnect_timeout(Duration::from_secs(5));
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+#[test]
+fn tiny_send_budget_trickles_messages_out_and_the_match_survives() {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let max_sends = [Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0))];
+    let counting = |inner, index: usize| CountingSocket {
+        inner,
+        sends_since_poll: 0,
+        max_sends_per_poll: Arc::clone(&max_sends[index]),
+    };
+    let mut sessions = [
+        session(counting(s1, 0), 0, a2, &clock).unwrap(),
+        session(counting(s2, 1), 1, a1, &clock).unwrap(),
+    ];
+    let [sess1, sess2] = &mut sessions;
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize under the send limits");
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut max_backlog = [0; 2];
+    for step in 0..STEPS {
+        for (handle, (session, stub)) in sessions.iter_mut().zip(&mut stubs).enumerate() {
+            session.poll_remote_clients();
+            for event in session.events() {
+                assert!(
+                    !matches!(
+                        event,
+                        FortressEvent::NetworkInterrupted { .. }
+                            | FortressEvent::Disconnected { .. }
+                    ),
+                    "peer {handle} at step {step}: {event:?}"
+                );
+            }
+            let inp = session.current_frame().as_i32() as u32 % 7 + handle as u32;
+            match advance_local(session, handle, StubInput { inp }) {
+                Ok(Some(requests)) => stub.handle_requests(requests),
+                Ok(None) => {},
+                Err(err) => panic!("peer {handle} at step {step}: {err}"),
+            }
+            if let Ok(stats) = session.network_stats(PlayerHandle::new(1 - handle)) {
+                max_backlog[handle] = max_backlog[handle].max(stats.send_backlog_len);
+            }
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    for (index, session) in sessions.iter().enumerate() {
+        assert_eq!(
+            max_sends[index].load(Ordering::Relaxed),
+            1,
+            "peer {index} exceeded its per-poll budget"
+        );
+        assert!(
+            max_backlog[index] > 0,
+            "peer {index} never held a message back"
+        );
+        assert!(
+            session.confirmed_frame().as_i32() > (STEPS / 2) as i32,
+            "peer {index} only confirmed up to {:?}",
+            session.confirmed_frame()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Send prioritization under a congested socket.
+//!
+//! Both peers run over a socket that only accepts a few sends per poll and
+//! silently drops the rest, as a saturated link would. `UdpProtocol` drains
+//! its queue handshake first, then keepalive/quality, then input acks, then
+//! inputs, so the quality reports still leave at their configured interval
+//! and the RTT estimate keeps being refreshed while inputs absorb the loss.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, create_channel_pair, protocol_config, start_two_peer_session,
+    synchronize_sessions_deterministic, ChannelSocket, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, Message, MessageKind, NonBlockingSocket, P2PSession, PlayerHandle,
+    ProtocolConfig, SessionBuilder,
+};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Sends the socket accepts between two polls.
+const SENDS_PER_POLL: usize = 2;
+/// Quality report interval used by both peers.
+const QUALITY_INTERVAL: Duration = Duration::from_millis(200);
+/// Steps run once both peers are synchronized.
+const STEPS: usize = 200;
+
+/// Shared view of one throttled socket.
+#[derive(Clone, Default)]
+struct Throttle {
+    /// Whether sends are currently limited; off during synchronization.
+    enabled: Arc<AtomicBool>,
+    /// Quality reports the socket accepted.
+    quality_reports: Arc<AtomicUsize>,
+    /// Sends the socket dropped.
+    dropped: Arc<AtomicUsize>,
+}
+
+/// A [`ChannelSocket`] that accepts at most [`SENDS_PER_POLL`] sends after
+/// each `receive_all_messages` call and drops the rest.
+struct ThrottledSocket {
+    inner: ChannelSocket,
+    throttle: Throttle,
+    budget: usize,
+}
+
+impl ThrottledSocket {
+    fn new(inner: ChannelSocket, throttle: Throttle) -> Self {
+        Self {
+            inner,
+            throttle,
+            budget: SENDS_PER_POLL,
+        }
+    }
+}
+
+impl NonBlockingSocket<SocketAddr> for ThrottledSocket {
+    fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        if self.throttle.enabled.load(Ordering::Relaxed) {
+            if self.budget == 0 {
+                self.throttle.dropped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            self.budget -= 1;
+        }
+        if fortress_rollback::__internal::message_metadata(msg).1 == MessageKind::QualityReport {
+            self.throttle
+                .quality_reports
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        self.inner.send_to(msg, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+        self.budget = SENDS_PER_POLL;
+        self.inner.receive_all_messages()
+    }
+}
+
+fn session(
+    socket: ThrottledSocket,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        quality_report_interval: QUALITY_INTERVAL,
+        ..protocol_config(clock)
+    });
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+#[test]
+fn quality_reports_keep_their_interval_on_a_congested_socket() {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let throttles = [Throttle::default(), Throttle::default()];
+    let mut sessions = [
+        session(
+            ThrottledSocket::new(s1, throttles[0].clone()),
+            0,
+            a2,
+            &clock,
+        )
+        .unwrap(),
+        session(
+            ThrottledSocket::new(s2, throttles[1].clone()),
+            1,
+            a1,
+            &clock,
+        )
+        .unwrap(),
+    ];
+    let [sess1, sess2] = &mut sessions;
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+
+    for throttle in &throttles {
+        throttle.quality_reports.store(0, Ordering::Relaxed);
+        throttle.enabled.store(true, Ordering::Relaxed);
+    }
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    for _ in 0..STEPS {
+        for (handle, (session, stub)) in sessions.iter_mut().zip(&mut stubs).enumerate() {
+            session.poll_remote_clients();
+            let inp = session.current_frame().as_i32() as u32 % 5 + handle as u32;
+            // A full prediction window is expected while inputs are dropped.
+            match advance_local(session, handle, StubInput { inp }) {
+                Ok(Some(requests)) => stub.handle_requests(requests),
+                Ok(None) => {},
+                Err(err) => panic!("peer {handle} failed to advance: {err}"),
+            }
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    // A report is due on the first poll strictly past the interval.
+    let polls_per_report =
+        (QUALITY_INTERVAL.as_millis() / POLL_INTERVAL_DETERMINISTIC.as_millis()) as usize + 1;
+    let expected_reports = STEPS / polls_per_report;
+    assert!(
+        throttles
+            .iter()
+            .any(|throttle| throttle.dropped.load(Ordering::Relaxed) > 0),
+        "the sockets never congested"
+    );
+    for (index, (session, throttle)) in sessions.iter().zip(&throttles).enumerate() {
+        let reports = throttle.quality_reports.load(Ordering::Relaxed);
+        assert!(
+            reports + 1 >= expected_reports,
+            "peer {index}: {reports} quality reports sent, expected about {expected_reports}"
+        );
+        // Replies land one poll later, so a fresh estimate is one poll interval.
+        let remote = PlayerHandle::new(1 - index);
+        assert_eq!(
+            session.network_stats(remote).unwrap().ping,
+            POLL_INTERVAL_DETERMINISTIC.as_millis(),
+            "peer {index}: stale RTT"
+        );
+        assert!(session.confirmed_frame().as_i32() > 0);
+    }
+}
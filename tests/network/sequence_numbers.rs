@@ -0,0 +1,182 @@
+//! Message sequence number ([`ProtocolConfig::sequence_numbers`]) end-to-end
+//! tests.
+//!
+//! Two peers play over links that duplicate 30% of their datagrams, with
+//! jitter so a duplicate usually arrives some time after its original. A
+//! duplicated quality report is answered twice, and the late second answer
+//! reads as a longer round trip. With sequence numbers the receiver drops the
+//! duplicates before any handler sees them, so the measured ping stays close
+//! steadier. Both runs stop at the same frame limit, and the final states of
+//! every peer in both runs must match: dropping duplicates never drops an
+//! input.
+//!
+//! The window bookkeeping and header format are asserted at the unit level in
+//! `network::protocol` and `network::codec`.
+
+// Allow test-specific patterns that are appropriate for test code
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+use std::net::SocketAddr;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, create_chaos_channel_pair, protocol_config, start_two_peer_session,
+    synchronize_sessions_deterministic, SyncConfig, TestClock, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    ChaosConfig, DesyncDetection, FortressError, FortressEvent, Frame, InvalidRequestKind,
+    NonBlockingSocket, P2PSession, PlayerHandle, ProtocolConfig, SessionBuilder,
+};
+
+/// Share of datagrams each link sends twice.
+const DUPLICATION: f64 = 0.3;
+/// Frame limit both peers stop at.
+const END_FRAME: i32 = 600;
+/// Upper bound on advance rounds.
+const MAX_ROUNDS: usize = 5_000;
+
+/// Outcome of one two-peer run.
+struct RunOutcome {
+    /// Every ping sample peer 0 read for peer 1 once stats were available.
+    pings: Vec<u128>,
+    /// Each peer's game state at the frame limit.
+    final_states: [StateStub; 2],
+    desyncs: usize,
+    duplicates_dropped: u64,
+    stale_dropped: u64,
+}
+
+impl RunOutcome {
+    /// Total change between consecutive ping samples, in milliseconds.
+    fn ping_variation(&self) -> u128 {
+        self.pings
+            .windows(2)
+            .map(|pair| pair[0].abs_diff(pair[1]))
+            .sum()
+    }
+}
+
+fn session(
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+    sequence_numbers: bool,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+        .with_max_session_frames(Frame::new(END_FRAME))?
+        .with_protocol_config(ProtocolConfig {
+            sequence_numbers,
+            ..protocol_config(clock)
+        });
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+fn run(sequence_numbers: bool) -> Result<RunOutcome, FortressError> {
+    let clock = TestClock::new();
+    // Jitter spreads a duplicate's arrival away from its original's.
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .latency_ms(40)
+            .jitter_ms(30)
+            .duplication_rate(DUPLICATION)
+            .seed(seed)
+            .build()
+    };
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(7), chaos(8), &clock);
+    let mut sessions = [
+        session(s1, 0, a2, &clock, sequence_numbers)?,
+        session(s2, 1, a1, &clock, sequence_numbers)?,
+    ];
+    let [first, second] = &mut sessions;
+    synchronize_sessions_deterministic(first, second, &clock, &SyncConfig::default())?;
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut finished = [false; 2];
+    let limit_reached: FortressError = InvalidRequestKind::SessionFrameLimitReached {
+        frame: Frame::new(END_FRAME),
+    }
+    .into();
+    let mut outcome = RunOutcome {
+        pings: Vec::new(),
+        final_states: [StateStub::default(); 2],
+        desyncs: 0,
+        duplicates_dropped: 0,
+        stale_dropped: 0,
+    };
+    for _ in 0..MAX_ROUNDS {
+        if finished.iter().all(|&finished| finished) {
+            break;
+        }
+        for (handle, (session, stub)) in sessions.iter_mut().zip(stubs.iter_mut()).enumerate() {
+            session.poll_remote_clients();
+            if !finished[handle] {
+                let frame = session.current_frame().as_i32() as u32;
+                let input = StubInput {
+                    inp: frame / 7 + handle as u32,
+                };
+                match advance_local(session, handle, input) {
+                    Ok(Some(requests)) => stub.handle_requests(requests),
+                    Ok(None) => {},
+                    Err(err) if err == limit_reached => finished[handle] = true,
+                    Err(err) => return Err(err),
+                }
+            }
+            outcome.desyncs += session
+                .events()
+                .filter(|event| matches!(event, FortressEvent::DesyncDetected { .. }))
+                .count();
+            // Stats become available a moment after synchronizing.
+            if handle == 0 {
+                if let Ok(stats) = session.network_stats(PlayerHandle::new(1)) {
+                    outcome.pings.push(stats.ping);
+                }
+            }
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    for (handle, session) in sessions.iter().enumerate() {
+        assert!(
+            finished[handle],
+            "peer {handle} should stop at the frame limit"
+        );
+        outcome.final_states[handle] = stubs[handle].gs;
+        let stats = session.network_stats(PlayerHandle::new(1 - handle))?;
+        outcome.duplicates_dropped += stats.duplicates_dropped;
+        outcome.stale_dropped += stats.stale_dropped;
+    }
+    Ok(outcome)
+}
+
+#[test]
+fn duplicate_drops_steady_the_ping_and_keep_state_parity() -> Result<(), FortressError> {
+    let deduplicated = run(true)?;
+    let control = run(false)?;
+
+    assert_eq!(deduplicated.desyncs, 0);
+    assert_eq!(control.desyncs, 0);
+    assert!(
+        deduplicated.duplicates_dropped > 0,
+        "30% duplication should produce duplicates to drop"
+    );
+    // Resent inputs carry new sequence numbers, and nothing here overtakes a
+    // whole window's worth of messages.
+    assert_eq!(deduplicated.stale_dropped, 0);
+    assert_eq!((control.duplicates_dropped, control.stale_dropped), (0, 0));
+
+    // The same inputs produce the same states with or without deduplication.
+    let expected = control.final_states[0];
+    assert_eq!(expected.frame, END_FRAME);
+    assert_eq!(control.final_states, [expected; 2]);
+    assert_eq!(deduplicated.final_states, [expected; 2]);
+
+    assert!(
+        deduplicated.ping_variation() < control.ping_variation(),
+        "ping varied by {} ms with sequence numbers vs {} ms without",
+        deduplicated.ping_variation(),
+        control.ping_variation()
+    );
+    Ok(())
+}
@@ -0,0 +1,169 @@
+//! `wait_for_synchronization` on `P2PSession` and `SpectatorSession` over
+//! real loopback UDP sockets.
+//!
+//! The success paths run the other side on its own thread, since a blocking
+//! wait only polls its own session. The timeout paths point the session at a
+//! bound socket that never reads or answers.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::start_two_peer_session;
+use crate::common::stubs::StubConfig;
+use fortress_rollback::{
+    FortressError, FortressEvent, P2PSession, PlayerHandle, PlayerType, SessionBuilder,
+    SessionState, SyncConfig, UdpNonBlockingSocket,
+};
+use std::net::{SocketAddr, UdpSocket};
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+/// Generous enough for a loaded CI machine; loopback sync takes milliseconds.
+const SYNC_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn loopback_socket() -> (UdpNonBlockingSocket, SocketAddr) {
+    let socket = UdpNonBlockingSocket::bind(([127, 0, 0, 1], 0).into()).unwrap();
+    let addr = socket.local_addr().unwrap();
+    (socket, addr)
+}
+
+/// A bound socket nobody reads from: requests sent to it are never answered.
+fn black_hole() -> (UdpSocket, SocketAddr) {
+    let socket = UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+    let addr = socket.local_addr().unwrap();
+    (socket, addr)
+}
+
+fn session(
+    socket: UdpNonBlockingSocket,
+    local: usize,
+    remote_addr: SocketAddr,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    start_two_peer_session(SessionBuilder::new(), local, remote_addr, socket)
+}
+
+#[test]
+#[cfg(not(miri))]
+fn p2p_sessions_synchronize_and_keep_their_events() {
+    let (socket1, addr1) = loopback_socket();
+    let (socket2, addr2) = loopback_socket();
+    let peer = thread::spawn(move || {
+        let mut session = session(socket2, 1, addr1).unwrap();
+        session
+            .wait_for_synchronization(SYNC_TIMEOUT, POLL_INTERVAL)
+            .unwrap();
+    });
+
+    let mut session = session(socket1, 0, addr2).unwrap();
+    session
+        .wait_for_synchronization(SYNC_TIMEOUT, POLL_INTERVAL)
+        .unwrap();
+    peer.join().unwrap();
+
+    assert_eq!(session.current_state(), SessionState::Running);
+    let events: Vec<_> = session.events().collect();
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, FortressEvent::Synchronized { addr } if *addr == addr2)),
+        "Synchronized event was lost: {events:?}"
+    );
+}
+
+#[test]
+#[cfg(not(miri))]
+fn p2p_timeout_reports_endpoint_progress() {
+    let (socket, _addr) = loopback_socket();
+    let (_black_hole, remote) = black_hole();
+    let retry = Duration::from_millis(50);
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_sync_config(SyncConfig {
+            sync_retry_interval: retry,
+            ..SyncConfig::default()
+        })
+        .add_player(PlayerType::Local, PlayerHandle::new(0))
+        .unwrap()
+        .add_player(PlayerType::Remote(remote), PlayerHandle::new(1))
+        .unwrap()
+        .start_p2p_session(socket)
+        .unwrap();
+
+    let timeout = Duration::from_millis(300);
+    let err = session
+        .wait_for_synchronization(timeout, POLL_INTERVAL)
+        .unwrap_err();
+
+    assert!(err.waited >= timeout);
+    assert_eq!(session.current_state(), SessionState::Synchronizing);
+    assert_eq!(err.endpoints.len(), 1);
+    let progress = &err.endpoints[0];
+    assert_eq!(progress.addr, remote);
+    assert!(!progress.synchronized);
+    assert_eq!(progress.roundtrips_done, 0);
+    assert_eq!(
+        progress.roundtrips_required,
+        SyncConfig::default().num_sync_packets
+    );
+    // The first request plus at least a couple of retries.
+    assert!(progress.requests_sent >= 3, "{progress:?}");
+    assert!(progress.elapsed >= timeout, "{progress:?}");
+    assert!(err.to_string().contains(&remote.to_string()));
+}
+
+#[test]
+#[cfg(not(miri))]
+fn spectator_synchronizes_with_its_host() {
+    let (host_socket, host_addr) = loopback_socket();
+    let (spectator_socket, spectator_addr) = loopback_socket();
+    let host = thread::spawn(move || {
+        let mut host = SessionBuilder::<StubConfig>::new()
+            .with_num_players(1)
+            .unwrap()
+            .add_player(PlayerType::Local, PlayerHandle::new(0))
+            .unwrap()
+            .add_player(PlayerType::Spectator(spectator_addr), PlayerHandle::new(1))
+            .unwrap()
+            .start_p2p_session(host_socket)
+            .unwrap();
+        host.wait_for_synchronization(SYNC_TIMEOUT, POLL_INTERVAL)
+            .unwrap();
+    });
+
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)
+        .unwrap()
+        .start_spectator_session(host_addr, spectator_socket)
+        .unwrap();
+    spectator
+        .wait_for_synchronization(SYNC_TIMEOUT, POLL_INTERVAL)
+        .unwrap();
+    host.join().unwrap();
+    assert_eq!(spectator.current_state(), SessionState::Running);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn spectator_timeout_reports_host_progress() {
+    let (socket, _addr) = loopback_socket();
+    let (_black_hole, host) = black_hole();
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .start_spectator_session(host, socket)
+        .unwrap();
+
+    let timeout = Duration::from_millis(100);
+    let err = spectator
+        .wait_for_synchronization(timeout, POLL_INTERVAL)
+        .unwrap_err();
+
+    assert_eq!(err.endpoints.len(), 1);
+    let progress = &err.endpoints[0];
+    assert_eq!(progress.addr, host);
+    assert!(!progress.synchronized);
+    assert_eq!(progress.roundtrips_done, 0);
+    assert!(progress.requests_sent >= 1, "{progress:?}");
+}
@@ -0,0 +1,156 @@
+//! Socket send failures ([`NonBlockingSocket::try_send_to`] and
+//! [`FortressEvent::TransportError`]).
+//!
+//! One peer's socket refuses every send for a stretch of the match, twice.
+//! The session must keep running through both outages, report each one once
+//! as a `TransportError` when the streak reaches
+//! [`ProtocolConfig::transport_error_threshold`], and forget the streak as
+//! soon as a send succeeds again so the second outage is reported as well.
+
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    advance_local, create_channel_pair, protocol_config, start_two_peer_session,
+    synchronize_sessions_deterministic, ChannelSocket, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, Message, NonBlockingSocket, P2PSession, ProtocolConfig,
+    SessionBuilder, SessionState, SocketErrorKind,
+};
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const THRESHOLD: u32 = 5;
+/// Steps per phase (healthy, outage, healthy, outage, healthy).
+const PHASE_STEPS: usize = 20;
+
+/// A [`ChannelSocket`] that refuses every send while `failing` is set.
+struct FlakySocket {
+    inner: ChannelSocket,
+    failing: Arc<AtomicBool>,
+}
+
+impl NonBlockingSocket<SocketAddr> for FlakySocket {
+    fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        let _ = self.try_send_to(msg, addr);
+    }
+
+    fn try_send_to(&mut self, msg: &Message, addr: &SocketAddr) -> Result<(), SocketErrorKind> {
+        if self.failing.load(Ordering::Relaxed) {
+            return Err(SocketErrorKind::SendFailed {
+                kind: ErrorKind::NetworkUnreachable,
+            });
+        }
+        self.inner.send_to(msg, addr);
+        Ok(())
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+        self.inner.receive_all_messages()
+    }
+}
+
+fn session(
+    socket: FlakySocket,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(ProtocolConfig {
+            transport_error_threshold: Some(THRESHOLD),
+            ..protocol_config(clock)
+        })
+        // The outages are local send failures, not a peer going away.
+        .with_disconnect_notify_delay(Duration::from_secs(2))
+        .with_disconnect_timeout(Duration::from_secs(5));
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+#[test]
+fn send_failures_are_reported_once_per_outage_and_the_match_survives() {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let failing = Arc::new(AtomicBool::new(false));
+    let mut sessions = [
+        session(
+            FlakySocket {
+                inner: s1,
+                failing: Arc::clone(&failing),
+            },
+            0,
+            a2,
+            &clock,
+        )
+        .unwrap(),
+        session(
+            FlakySocket {
+                inner: s2,
+                failing: Arc::new(AtomicBool::new(false)),
+            },
+            1,
+            a1,
+            &clock,
+        )
+        .unwrap(),
+    ];
+    let [sess1, sess2] = &mut sessions;
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize over a healthy socket");
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut transport_errors: [Vec<(usize, SocketAddr, u32)>; 2] = [Vec::new(), Vec::new()];
+    for phase in 0..5 {
+        failing.store(phase % 2 == 1, Ordering::Relaxed);
+        for step in 0..PHASE_STEPS {
+            for (handle, (session, stub)) in sessions.iter_mut().zip(&mut stubs).enumerate() {
+                session.poll_remote_clients();
+                for event in session.events() {
+                    if let FortressEvent::TransportError {
+                        addr,
+                        consecutive_failures,
+                    } = event
+                    {
+                        transport_errors[handle].push((phase, addr, consecutive_failures));
+                    }
+                }
+                let inp = session.current_frame().as_i32() as u32 % 7 + handle as u32;
+                match advance_local(session, handle, StubInput { inp }) {
+                    Ok(Some(requests)) => stub.handle_requests(requests),
+                    Ok(None) => {},
+                    Err(err) => panic!("peer {handle} in phase {phase} at step {step}: {err}"),
+                }
+                assert_eq!(session.current_state(), SessionState::Running);
+            }
+            clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        }
+    }
+
+    assert_eq!(
+        transport_errors[0],
+        vec![(1, a2, THRESHOLD), (3, a2, THRESHOLD)],
+        "each outage should be reported exactly once"
+    );
+    assert!(
+        transport_errors[1].is_empty(),
+        "the healthy peer reported {:?}",
+        transport_errors[1]
+    );
+    for (index, session) in sessions.iter().enumerate() {
+        assert!(
+            session.confirmed_frame().as_i32() > (4 * PHASE_STEPS) as i32 - 20,
+            "peer {index} only confirmed up to {:?}",
+            session.confirmed_frame()
+        );
+    }
+}
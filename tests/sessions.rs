@@ -12,17 +12,68 @@
 
 // Session test modules
 mod sessions {
+    pub mod advance_frame_with;
+    pub mod advance_stall;
+    #[cfg(feature = "advanced")]
+    pub mod advanced_views;
+    pub mod builder_template;
+    pub mod clock_offset;
     pub mod compat;
+    pub mod desync_detection_lag;
     pub mod desync_harvest;
+    pub mod disconnect_gossip;
+    pub mod endpoint_order;
+    pub mod event_order;
+    pub mod event_timestamps;
+    pub mod frame_finality;
+    pub mod frame_limit;
+    pub mod frame_timeline;
+    pub mod frames_ahead_limit;
     #[cfg(feature = "hot-join")]
     pub mod hot_join;
     pub mod input_delay;
+    pub mod input_queue_sizing;
+    pub mod local_input;
     pub mod macro_tests;
+    pub mod match_pause;
+    pub mod memory_report;
+    pub mod non_zero_default_input;
+    pub mod observer_peer;
     pub mod p2p;
     pub mod p2p_enum;
+    pub mod p2p_session_core;
     pub mod peer_drop;
+    pub mod player_roster;
+    pub mod previous_inputs;
+    pub mod pyramid_saving;
+    pub mod rejoin_verification;
+    pub mod relay_route;
+    pub mod relay_topology;
+    pub mod remote_input_buffer;
+    pub mod replay_from_inputs;
+    pub mod resimulation_budget;
+    pub mod rollback_cause;
+    pub mod rollback_context;
+    pub mod rollback_depth;
+    pub mod saved_frames;
+    pub mod session_drop;
+    pub mod session_health;
+    pub mod session_set;
     pub mod session_trait;
+    pub mod sparse_desync_detection;
     pub mod spectator;
+    pub mod spectator_access;
+    pub mod spectator_checksum_authority;
+    pub mod spectator_consistency;
+    pub mod spectator_frame_progress;
+    pub mod spectator_keyframes;
+    pub mod spectator_reorder;
+    pub mod state_slots;
+    pub mod stats_publisher;
+    pub mod stream_end;
     pub mod synctest;
     pub mod synctest_enum;
+    pub mod synctest_perturbed;
+    pub mod synctest_randomized;
+    pub mod trace_recorder;
 }
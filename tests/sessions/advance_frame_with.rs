@@ -0,0 +1,258 @@
+//! Integration tests for sampling local input inside
+//! `P2PSession::advance_frame_with`.
+//!
+//! Covers:
+//! - The requests match the classic `add_local_input` + `advance_frame` flow
+//!   given the same inputs, across a rollback.
+//! - The sampler is called once per new frame with the local handles, after
+//!   the rollback, and not at all when the call cannot advance.
+//! - A rejected sample returns the error without losing the rollback repaired
+//!   before it.
+//!
+//! That the sampler cannot reach the session is a `compile_fail` doctest on
+//! `advance_frame_with`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::create_channel_pair;
+use crate::common::misprediction::MispredictionHarness;
+use crate::common::stubs::{StubConfig, StubInput};
+use fortress_rollback::{
+    FortressError, FortressRequest, InvalidRequestKind, PlayerHandle, PlayerType, RequestVec,
+    SessionBuilder,
+};
+
+const LOCAL: PlayerHandle = PlayerHandle::new(0);
+const REMOTE: PlayerHandle = PlayerHandle::new(1);
+/// Depth of the forced rollback.
+const ROLLBACK_DEPTH: u32 = 6;
+
+/// How session 1 supplies its local input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Flow {
+    /// `add_local_input` followed by `advance_frame`.
+    Classic,
+    /// `advance_frame_with`.
+    Sampled,
+}
+
+/// One request, reduced to what two runs can compare.
+fn describe(request: &FortressRequest<StubConfig>) -> String {
+    match request {
+        FortressRequest::SaveGameState { frame, .. } => format!("save {frame}"),
+        FortressRequest::LoadGameState { frame, .. } => format!("load {frame}"),
+        FortressRequest::AdvanceFrame { inputs, .. } => format!("advance {inputs:?}"),
+    }
+}
+
+/// How session 1 supplies its input, and everything it returned.
+struct Recorder {
+    flow: Flow,
+    trace: Vec<String>,
+    /// The handles of every sampler call, in order.
+    sampled: Vec<Vec<PlayerHandle>>,
+}
+
+impl Recorder {
+    /// Advances session 1 with `sample` as its sampler, whatever the flow.
+    fn advance1_sampling(
+        &mut self,
+        peers: &mut MispredictionHarness,
+        sample: impl FnOnce(&[PlayerHandle]) -> Vec<(PlayerHandle, StubInput)>,
+    ) -> Result<RequestVec<StubConfig>, FortressError> {
+        let sampled = &mut self.sampled;
+        peers.sess1.advance_frame_with(|handles| {
+            sampled.push(handles.to_vec());
+            sample(handles)
+        })
+    }
+
+    /// Advances session 1 with `inp` through the recorder's flow and records
+    /// the requests.
+    fn advance1(
+        &mut self,
+        peers: &mut MispredictionHarness,
+        inp: u32,
+    ) -> Result<(), FortressError> {
+        let requests = match self.flow {
+            Flow::Classic => peers.request1(inp)?,
+            Flow::Sampled => self.advance1_sampling(peers, |handles| {
+                handles
+                    .iter()
+                    .map(|&handle| (handle, StubInput { inp }))
+                    .collect()
+            })?,
+        };
+        self.handle1(peers, requests);
+        Ok(())
+    }
+
+    fn handle1(&mut self, peers: &mut MispredictionHarness, requests: RequestVec<StubConfig>) {
+        self.trace.extend(requests.iter().map(describe));
+        peers.handle1(requests);
+    }
+}
+
+/// Two synchronized sessions, session 1 supplying its input through a
+/// [`Flow`].
+struct Harness {
+    peers: MispredictionHarness,
+    recorder: Recorder,
+}
+
+impl Harness {
+    fn new(flow: Flow) -> Result<Self, FortressError> {
+        Ok(Self {
+            peers: MispredictionHarness::new(|builder, _| Ok(builder))?,
+            recorder: Recorder {
+                flow,
+                trace: Vec::new(),
+                sampled: Vec::new(),
+            },
+        })
+    }
+
+    fn advance1_sampling(
+        &mut self,
+        sample: impl FnOnce(&[PlayerHandle]) -> Vec<(PlayerHandle, StubInput)>,
+    ) -> Result<RequestVec<StubConfig>, FortressError> {
+        self.recorder.advance1_sampling(&mut self.peers, sample)
+    }
+
+    fn advance1(&mut self, inp: u32) -> Result<(), FortressError> {
+        self.recorder.advance1(&mut self.peers, inp)
+    }
+
+    fn handle1(&mut self, requests: RequestVec<StubConfig>) {
+        self.recorder.handle1(&mut self.peers, requests);
+    }
+
+    /// Forces a `ROLLBACK_DEPTH`-frame misprediction, with session 1 playing
+    /// the frame number as its input. Session 1's next call detects it.
+    fn force_misprediction(&mut self) -> Result<(), FortressError> {
+        let Self { peers, recorder } = self;
+        peers.run_ahead(ROLLBACK_DEPTH, |peers, frame| {
+            recorder.advance1(peers, frame)
+        })?;
+        peers.catch_up(ROLLBACK_DEPTH, |_| 1)
+    }
+
+    /// Lets both peers run on with varying inputs, so rollbacks keep
+    /// happening.
+    fn settle(&mut self) -> Result<(), FortressError> {
+        for frame in 0..20 {
+            self.peers.poll(1);
+            self.advance1(100 + frame)?;
+            self.peers.advance2(frame % 3)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn sampled_requests_match_classic_flow() -> Result<(), FortressError> {
+    let mut classic = Harness::new(Flow::Classic)?;
+    let mut sampled = Harness::new(Flow::Sampled)?;
+    for run in [&mut classic, &mut sampled] {
+        run.force_misprediction()?;
+        run.advance1(42)?;
+        run.settle()?;
+    }
+
+    assert!(
+        classic
+            .recorder
+            .trace
+            .iter()
+            .any(|request| request.starts_with("load")),
+        "the schedule must include a rollback"
+    );
+    assert_eq!(sampled.recorder.trace, classic.recorder.trace);
+    assert_eq!(sampled.peers.stub1.gs, classic.peers.stub1.gs);
+    // One sampler call per frame simulated, each with the local handles only.
+    assert_eq!(
+        sampled.recorder.sampled.len(),
+        sampled.peers.sess1.current_frame().as_i32() as usize
+    );
+    assert!(sampled
+        .recorder
+        .sampled
+        .iter()
+        .all(|handles| handles == &[LOCAL]));
+    Ok(())
+}
+
+#[test]
+fn sampler_runs_after_the_rollback() -> Result<(), FortressError> {
+    let mut run = Harness::new(Flow::Sampled)?;
+    run.force_misprediction()?;
+    let frame = run.peers.sess1.current_frame();
+    let requests = run.advance1_sampling(|handles| vec![(handles[0], StubInput { inp: 42 })])?;
+
+    // The rollback came first; the sampled input went into the one new frame.
+    assert!(matches!(
+        requests.first(),
+        Some(FortressRequest::LoadGameState { .. })
+    ));
+    let Some(FortressRequest::AdvanceFrame { inputs, .. }) = requests.last() else {
+        panic!("the call must end with the new frame: {requests:?}");
+    };
+    assert_eq!(inputs[0].0, StubInput { inp: 42 });
+    run.handle1(requests);
+    assert_eq!(run.peers.sess1.current_frame(), frame + 1);
+    Ok(())
+}
+
+#[test]
+fn sampler_is_not_called_when_the_session_cannot_advance() -> Result<(), FortressError> {
+    let (s1, _s2, _a1, a2) = create_channel_pair();
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .add_player(PlayerType::Local, LOCAL)?
+        .add_player(PlayerType::Remote(a2), REMOTE)?
+        .start_p2p_session(s1)?;
+    let mut calls = 0;
+    let result = session.advance_frame_with(|handles| {
+        calls += 1;
+        vec![(handles[0], StubInput::default())]
+    });
+    assert!(matches!(result, Err(FortressError::NotSynchronized)));
+    assert_eq!(calls, 0);
+    Ok(())
+}
+
+#[test]
+fn rejected_sample_keeps_the_repaired_rollback() -> Result<(), FortressError> {
+    let bad_samples = [
+        (Vec::new(), InvalidRequestKind::MissingLocalInput),
+        (
+            vec![(REMOTE, StubInput::default())],
+            InvalidRequestKind::NotLocalPlayer { handle: REMOTE },
+        ),
+    ];
+    for (bad_sample, kind) in bad_samples {
+        let mut classic = Harness::new(Flow::Classic)?;
+        classic.force_misprediction()?;
+        classic.advance1(42)?;
+
+        let mut sampled = Harness::new(Flow::Sampled)?;
+        sampled.force_misprediction()?;
+        let err = sampled.advance1_sampling(|_| bad_sample).unwrap_err();
+        assert_eq!(err, FortressError::InvalidRequestStructured { kind });
+
+        // The retry returns the rollback ahead of the new frame, as one
+        // successful call would have; it only saves the current frame again,
+        // which the comparison ignores.
+        sampled.advance1(42)?;
+        sampled.recorder.trace.dedup();
+        classic.recorder.trace.dedup();
+        assert_eq!(sampled.recorder.trace, classic.recorder.trace, "{kind:?}");
+        assert_eq!(sampled.peers.stub1.gs, classic.peers.stub1.gs, "{kind:?}");
+    }
+    Ok(())
+}
@@ -0,0 +1,191 @@
+//! Integration tests for `SessionBuilder::with_advance_stall_warning`.
+//!
+//! Covers:
+//! - A peer that keeps polling but stops advancing for 2 seconds raises
+//!   `LocalAdvanceStalled` once, and its remote raises `RemoteAdvanceStalled`.
+//! - Advancing again clears the stall on both sides with the matching
+//!   `Resumed` events.
+//! - Without the warning, a stall raises nothing.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, drain_sync_events, synchronize_sessions_deterministic, SyncConfig,
+    TestClock,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, P2PSession, PlayerHandle, PlayerType, ProtocolConfig,
+    SessionBuilder,
+};
+
+const FRAME: Duration = Duration::from_millis(16);
+const THRESHOLD: Duration = Duration::from_secs(1);
+/// Frames in the 2-second stall.
+const STALL_FRAMES: u32 = 125;
+
+/// Two synchronized sessions with the given stall threshold; returns them
+/// with session 1's address as session 2 sees it.
+fn start_pair(
+    clock: &TestClock,
+    threshold: Option<Duration>,
+) -> Result<(P2PSession<StubConfig>, P2PSession<StubConfig>, SocketAddr), FortressError> {
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let builder = |threshold: Option<Duration>| {
+        let builder =
+            SessionBuilder::<StubConfig>::new().with_protocol_config(protocol_config.clone());
+        match threshold {
+            Some(threshold) => builder.with_advance_stall_warning(threshold),
+            None => builder,
+        }
+    };
+    let mut sess1 = builder(threshold)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = builder(threshold)
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+    Ok((sess1, sess2, a1))
+}
+
+/// Runs `frames` ticks; session 1 advances only if `advance1`. Session 2
+/// advances whenever its prediction window allows.
+fn run(
+    clock: &TestClock,
+    sessions: (&mut P2PSession<StubConfig>, &mut P2PSession<StubConfig>),
+    stubs: (&mut GameStub, &mut GameStub),
+    frames: u32,
+    advance1: bool,
+) -> Result<(), FortressError> {
+    let (sess1, sess2) = sessions;
+    let (stub1, stub2) = stubs;
+    for _ in 0..frames {
+        sess1.poll_remote_clients();
+        sess2.poll_remote_clients();
+        clock.advance(FRAME);
+        if advance1 {
+            sess1.add_local_input(PlayerHandle::new(0), StubInput { inp: 0 })?;
+            stub1.handle_requests(sess1.advance_frame()?);
+        }
+        sess2.add_local_input(PlayerHandle::new(1), StubInput { inp: 0 })?;
+        match sess2.advance_frame() {
+            Ok(requests) => stub2.handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => (),
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+/// The stall events `session` has emitted since the last call.
+fn stall_events(session: &mut P2PSession<StubConfig>) -> Vec<FortressEvent<StubConfig>> {
+    session
+        .events()
+        .filter(|event| {
+            matches!(
+                event,
+                FortressEvent::LocalAdvanceStalled { .. }
+                    | FortressEvent::LocalAdvanceResumed { .. }
+                    | FortressEvent::RemoteAdvanceStalled { .. }
+                    | FortressEvent::RemoteAdvanceResumed { .. }
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn stalled_peer_is_reported_on_both_sides_until_it_advances() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut sess1, mut sess2, addr1) = start_pair(&clock, Some(THRESHOLD))?;
+    let mut stub1 = GameStub::new();
+    let mut stub2 = GameStub::new();
+
+    run(
+        &clock,
+        (&mut sess1, &mut sess2),
+        (&mut stub1, &mut stub2),
+        20,
+        true,
+    )?;
+    assert!(stall_events(&mut sess1).is_empty());
+    assert!(stall_events(&mut sess2).is_empty());
+
+    run(
+        &clock,
+        (&mut sess1, &mut sess2),
+        (&mut stub1, &mut stub2),
+        STALL_FRAMES,
+        false,
+    )?;
+    match stall_events(&mut sess1).as_slice() {
+        [FortressEvent::LocalAdvanceStalled { since_ms }] => {
+            assert!(*since_ms >= THRESHOLD.as_millis(), "{since_ms}");
+            assert!(*since_ms < THRESHOLD.as_millis() + 2 * FRAME.as_millis());
+        },
+        events => panic!("expected one LocalAdvanceStalled, got {events:?}"),
+    }
+    let events = stall_events(&mut sess2);
+    assert!(
+        matches!(events.as_slice(), [FortressEvent::RemoteAdvanceStalled { addr }] if *addr == addr1),
+        "expected one RemoteAdvanceStalled, got {events:?}"
+    );
+
+    run(
+        &clock,
+        (&mut sess1, &mut sess2),
+        (&mut stub1, &mut stub2),
+        20,
+        true,
+    )?;
+    match stall_events(&mut sess1).as_slice() {
+        [FortressEvent::LocalAdvanceResumed { stalled_ms }] => {
+            assert!(*stalled_ms >= 2000, "{stalled_ms}");
+        },
+        events => panic!("expected one LocalAdvanceResumed, got {events:?}"),
+    }
+    let events = stall_events(&mut sess2);
+    assert!(
+        matches!(events.as_slice(), [FortressEvent::RemoteAdvanceResumed { addr }] if *addr == addr1),
+        "expected one RemoteAdvanceResumed, got {events:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn stall_without_warning_raises_nothing() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut sess1, mut sess2, _) = start_pair(&clock, None)?;
+    let mut stub1 = GameStub::new();
+    let mut stub2 = GameStub::new();
+
+    for advance1 in [true, false, true] {
+        run(
+            &clock,
+            (&mut sess1, &mut sess2),
+            (&mut stub1, &mut stub2),
+            STALL_FRAMES,
+            advance1,
+        )?;
+    }
+    assert!(stall_events(&mut sess1).is_empty());
+    assert!(stall_events(&mut sess2).is_empty());
+    Ok(())
+}
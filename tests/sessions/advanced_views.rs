@@ -0,0 +1,178 @@
+//! Integration tests for the `advanced` feature's read-only views.
+//!
+//! These pin the documented behavior of every accessor in
+//! `fortress_rollback::advanced`, so any change to it is deliberate:
+//! - `SyncLayerView` frame counters and player slots on a `SyncTestSession`.
+//! - `InputQueueView` frame ranges and input delay for local players.
+//! - A pending misprediction surfacing in `first_incorrect_frame` between
+//!   receiving a remote input and the next `advance_frame`.
+//! - `TimeSyncView` and its handle validation on a `P2PSession`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, Frame, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+};
+
+/// Two synchronized peers; the host's views are inspected.
+struct Pair {
+    host: P2PSession<StubConfig>,
+    peer: P2PSession<StubConfig>,
+    stubs: [GameStub; 2],
+    clock: TestClock,
+}
+
+impl Pair {
+    fn new() -> Result<Self, FortressError> {
+        let clock = TestClock::new();
+        let (s1, s2, a1, a2) = create_channel_pair();
+        let protocol_config = ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        };
+        let mut host = SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(protocol_config.clone())
+            .add_player(PlayerType::Local, PlayerHandle::new(0))?
+            .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+            .start_p2p_session(s1)?;
+        let mut peer = SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(protocol_config)
+            .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+            .add_player(PlayerType::Local, PlayerHandle::new(1))?
+            .start_p2p_session(s2)?;
+        synchronize_sessions_deterministic(&mut host, &mut peer, &clock, &SyncConfig::default())
+            .expect("sessions should synchronize");
+        Ok(Self {
+            host,
+            peer,
+            stubs: [GameStub::new(), GameStub::new()],
+            clock,
+        })
+    }
+
+    fn advance_host(&mut self) -> Result<(), FortressError> {
+        self.host.poll_remote_clients();
+        self.host
+            .add_local_input(PlayerHandle::new(0), StubInput { inp: 0 })?;
+        self.stubs[0].handle_requests(self.host.advance_frame()?);
+        Ok(())
+    }
+
+    fn advance_peer(&mut self, inp: u32) -> Result<(), FortressError> {
+        self.peer.poll_remote_clients();
+        self.peer
+            .add_local_input(PlayerHandle::new(1), StubInput { inp })?;
+        self.stubs[1].handle_requests(self.peer.advance_frame()?);
+        Ok(())
+    }
+
+    fn confirmed_round(&mut self) -> Result<(), FortressError> {
+        self.advance_peer(0)?;
+        self.advance_host()?;
+        self.clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        Ok(())
+    }
+}
+
+#[test]
+fn synctest_sync_layer_view_tracks_frames_and_queues() -> Result<(), FortressError> {
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_input_delay(2)?
+        .with_check_distance(2)
+        .start_synctest_session()?;
+    let mut stub = GameStub::new();
+
+    let layer = session.sync_layer_view();
+    assert_eq!(layer.num_players(), 2);
+    assert_eq!(layer.current_frame(), Frame::new(0));
+    assert_eq!(layer.last_saved_frame(), Frame::NULL);
+    assert!(layer.input_queue(PlayerHandle::new(2)).is_none());
+    let queue = layer.input_queue(PlayerHandle::new(0)).unwrap();
+    assert_eq!(queue.frames(), None);
+    assert_eq!(queue.last_confirmed_frame(), None);
+    assert_eq!(queue.first_incorrect_frame(), None);
+    assert_eq!(queue.frame_delay(), 2);
+
+    for _ in 0..5 {
+        for handle in 0..2 {
+            session.add_local_input(PlayerHandle::new(handle), StubInput { inp: 3 })?;
+        }
+        stub.handle_requests(session.advance_frame()?);
+    }
+
+    let layer = session.sync_layer_view();
+    assert_eq!(layer.current_frame(), Frame::new(5));
+    assert!(layer.last_saved_frame() <= layer.current_frame());
+    assert!(layer.last_confirmed_frame() <= layer.current_frame());
+    let queue = layer.input_queue(PlayerHandle::new(1)).unwrap();
+    // Five inputs added with a delay of two: the newest lands on frame 6.
+    assert_eq!(queue.last_confirmed_frame(), Some(Frame::new(6)));
+    let frames = queue.frames().unwrap();
+    assert_eq!(*frames.end(), Frame::new(6));
+    assert!(*frames.start() <= Frame::new(6));
+    assert_eq!(queue.first_incorrect_frame(), None);
+    Ok(())
+}
+
+#[test]
+fn misprediction_is_pending_until_the_next_advance() -> Result<(), FortressError> {
+    let mut pair = Pair::new()?;
+    for _ in 0..3 {
+        pair.confirmed_round()?;
+    }
+    // The host runs frames 3..=5 alone, predicting the peer's input 0.
+    for _ in 0..3 {
+        pair.advance_host()?;
+    }
+    let remote = PlayerHandle::new(1);
+    {
+        let layer = pair.host.sync_layer_view();
+        let queue = layer.input_queue(remote).unwrap();
+        assert_eq!(queue.last_confirmed_frame(), Some(Frame::new(2)));
+        assert_eq!(queue.first_incorrect_frame(), None);
+        assert_eq!(queue.frame_delay(), 0);
+    }
+
+    // The peer plays 1 for frames 3..=6, contradicting the prediction at 3.
+    for _ in 0..4 {
+        pair.advance_peer(1)?;
+    }
+    pair.host.poll_remote_clients();
+    {
+        let layer = pair.host.sync_layer_view();
+        let queue = layer.input_queue(remote).unwrap();
+        assert_eq!(queue.last_confirmed_frame(), Some(Frame::new(6)));
+        assert_eq!(queue.first_incorrect_frame(), Some(Frame::new(3)));
+    }
+
+    pair.advance_host()?;
+    let layer = pair.host.sync_layer_view();
+    assert_eq!(layer.current_frame(), Frame::new(7));
+    assert_eq!(
+        layer.input_queue(remote).unwrap().first_incorrect_frame(),
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn time_sync_view_requires_a_remote_handle() -> Result<(), FortressError> {
+    let pair = Pair::new()?;
+    // No frame advantage has been measured right after synchronizing.
+    let view = pair.host.time_sync_view(PlayerHandle::new(1))?;
+    assert_eq!(view.average_frame_advantage(), 0);
+    assert!(pair.host.time_sync_view(PlayerHandle::new(0)).is_err());
+    assert!(pair.host.time_sync_view(PlayerHandle::new(7)).is_err());
+    Ok(())
+}
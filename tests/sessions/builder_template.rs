@@ -0,0 +1,148 @@
+//! Integration tests for reusing one `SessionBuilder` as a template
+//! (`Clone`, `build_p2p_session`, and `build_with_players`).
+//!
+//! Covers:
+//! - One template starting three concurrent matches, each with different
+//!   player addresses, all reaching the target frame.
+//! - Validation running on every build, with the template left unchanged.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_mesh, create_channel_pair, drain_sync_events, poll_with_advance,
+    synchronize_sessions_deterministic, SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    FortressError, Frame, InvalidRequestKind, P2PSession, PlayerHandle, PlayerType, ProtocolConfig,
+    SessionBuilder,
+};
+
+const MATCHES: usize = 3;
+const TARGET_FRAME: i32 = 30;
+
+fn template(clock: &TestClock) -> Result<SessionBuilder<StubConfig>, FortressError> {
+    SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        })
+        .with_input_delay(1)
+}
+
+#[test]
+fn one_template_starts_concurrent_matches() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let template = template(&clock)?;
+    let (sockets, addrs) = create_channel_mesh(2 * MATCHES);
+
+    let mut matches: Vec<(P2PSession<StubConfig>, P2PSession<StubConfig>)> = Vec::new();
+    let mut sockets = sockets.into_iter();
+    for index in 0..MATCHES {
+        let (host_addr, guest_addr) = (addrs[2 * index], addrs[2 * index + 1]);
+        let host = template.build_with_players(
+            &[
+                (PlayerType::Local, PlayerHandle::new(0)),
+                (PlayerType::Remote(guest_addr), PlayerHandle::new(1)),
+            ],
+            sockets.next().unwrap(),
+        )?;
+        let guest = template.build_with_players(
+            &[
+                (PlayerType::Remote(host_addr), PlayerHandle::new(0)),
+                (PlayerType::Local, PlayerHandle::new(1)),
+            ],
+            sockets.next().unwrap(),
+        )?;
+        matches.push((host, guest));
+    }
+
+    for (host, guest) in &mut matches {
+        synchronize_sessions_deterministic(host, guest, &clock, &SyncConfig::default())
+            .expect("sessions should synchronize");
+        drain_sync_events(host, guest);
+    }
+
+    let mut stubs: Vec<(GameStub, GameStub)> = (0..MATCHES)
+        .map(|_| (GameStub::new(), GameStub::new()))
+        .collect();
+    for frame in 0..TARGET_FRAME as u32 {
+        for ((host, guest), (host_stub, guest_stub)) in matches.iter_mut().zip(&mut stubs) {
+            poll_with_advance(host, guest, &clock, 1);
+            host.add_local_input(PlayerHandle::new(0), StubInput { inp: frame })?;
+            host_stub.handle_requests(host.advance_frame()?);
+            guest.add_local_input(PlayerHandle::new(1), StubInput { inp: frame })?;
+            guest_stub.handle_requests(guest.advance_frame()?);
+        }
+    }
+
+    for (index, (host, guest)) in matches.iter().enumerate() {
+        assert_eq!(
+            host.current_frame(),
+            Frame::new(TARGET_FRAME),
+            "match {index}"
+        );
+        assert_eq!(
+            guest.current_frame(),
+            Frame::new(TARGET_FRAME),
+            "match {index}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn every_build_is_validated_and_the_template_is_unchanged() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let template = template(&clock)?;
+    let (s1, s2, _a1, a2) = create_channel_pair();
+
+    // The template holds no players, so building it as-is is missing both.
+    let result = template.build_p2p_session(s1);
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotEnoughPlayers { .. }
+        })
+    ));
+
+    let result = template.build_with_players(
+        &[
+            (PlayerType::Local, PlayerHandle::new(0)),
+            (PlayerType::Remote(a2), PlayerHandle::new(0)),
+        ],
+        s2,
+    );
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::PlayerHandleInUse { .. }
+        })
+    ));
+
+    // A clone holding a player is a template of its own.
+    let (s3, s4, a3, a4) = create_channel_pair();
+    let with_local = template
+        .clone()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?;
+    let session =
+        with_local.build_with_players(&[(PlayerType::Remote(a4), PlayerHandle::new(1))], s3)?;
+    assert_eq!(session.num_players(), 2);
+
+    // Neither the failed builds nor the clone added players to the template.
+    let session = template.build_with_players(
+        &[
+            (PlayerType::Remote(a3), PlayerHandle::new(0)),
+            (PlayerType::Local, PlayerHandle::new(1)),
+        ],
+        s4,
+    )?;
+    assert_eq!(session.num_players(), 2);
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! Clock-offset estimate.
+//!
+//! Two players run a `ChaosSocket` loopback under virtual time. Under an
+//! injected clock the quality-report timestamps count from endpoint
+//! construction, so building the second player's session `SKEW` later mocks
+//! a clock `SKEW` behind the first one's. Covers:
+//! - Both players estimating the skew, with opposite signs, to within half the
+//!   round trip over a link with the same latency both ways.
+//! - A link whose two directions differ in latency biasing the estimate by
+//!   half the difference, still within half the round trip.
+//! - `clock_offset` being `None` before the first quality report and rejecting
+//!   a local handle.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{StubConfig, StubInput};
+use crate::common::{create_chaos_channel_pair, ChannelSocket, TestClock};
+use fortress_rollback::{
+    ChaosConfig, ChaosSocket, FortressError, InvalidRequestKind, P2PSession, PlayerHandle,
+    PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+const STEP: Duration = Duration::from_millis(16);
+const SKEW: i64 = 750;
+
+fn player(
+    local: usize,
+    remote_addr: SocketAddr,
+    socket: ChaosSocket<SocketAddr, ChannelSocket>,
+    clock: &TestClock,
+) -> P2PSession<StubConfig> {
+    let mut builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::deterministic(90 + local as u64)
+    });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder
+            .add_player(player, PlayerHandle::new(handle))
+            .unwrap();
+    }
+    builder.start_p2p_session(socket).unwrap()
+}
+
+/// Plays a match whose links to the first and to the second player have the
+/// given latencies, returning both players' estimates and pings.
+fn play(latency_to_a_ms: u64, latency_to_b_ms: u64) -> [(i64, u128); 2] {
+    let clock = TestClock::new();
+    let chaos = |latency_ms, seed| {
+        ChaosConfig::builder()
+            .latency_ms(latency_ms)
+            .seed(seed)
+            .build()
+    };
+    // A socket's latency delays what it receives.
+    let (socket_a, socket_b, addr_a, addr_b) =
+        create_chaos_channel_pair(chaos(latency_to_a_ms, 1), chaos(latency_to_b_ms, 2), &clock);
+    let mut players = [player(0, addr_b, socket_a, &clock), {
+        clock.advance(Duration::from_millis(SKEW as u64));
+        player(1, addr_a, socket_b, &clock)
+    }];
+    let remote = [PlayerHandle::new(1), PlayerHandle::new(0)];
+    for (session, remote) in players.iter().zip(remote) {
+        assert_eq!(session.clock_offset(remote), Ok(None));
+    }
+
+    for _ in 0..400 {
+        clock.advance(STEP);
+        for (handle, session) in players.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            if session.current_state() == SessionState::Running {
+                session
+                    .add_local_input(PlayerHandle::new(handle), StubInput { inp: 0 })
+                    .unwrap();
+                session.advance_frame().unwrap();
+            }
+        }
+    }
+
+    let mut estimates = [(0, 0); 2];
+    for (handle, session) in players.iter().enumerate() {
+        let stats = session.network_stats(remote[handle]).unwrap();
+        let offset = session.clock_offset(remote[handle]).unwrap().unwrap();
+        assert_eq!(stats.estimated_clock_offset_ms, Some(offset));
+        estimates[handle] = (offset, stats.ping);
+    }
+    estimates
+}
+
+/// Asserts `offset` is within the documented bound of `expected`, allowing a
+/// millisecond for the timestamps' resolution.
+fn assert_within_half_rtt(offset: i64, ping: u128, expected: i64) {
+    let bound = i64::try_from(ping / 2).unwrap() + 1;
+    assert!(
+        (offset - expected).abs() <= bound,
+        "estimate {offset}ms is not within {bound}ms of {expected}ms"
+    );
+}
+
+#[test]
+fn both_players_estimate_the_skew_over_a_symmetric_link() {
+    let [(offset_a, ping_a), (offset_b, ping_b)] = play(40, 40);
+    assert!((70..=120).contains(&ping_a), "ping {ping_a}ms");
+    // The second player's clock started later, so it reads behind.
+    assert_within_half_rtt(offset_a, ping_a, -SKEW);
+    assert_within_half_rtt(offset_b, ping_b, SKEW);
+    // With the same latency both ways only scheduling noise is left.
+    assert!((offset_a + SKEW).abs() <= 16, "estimate {offset_a}ms");
+    assert!((offset_b - SKEW).abs() <= 16, "estimate {offset_b}ms");
+}
+
+#[test]
+fn an_asymmetric_link_biases_the_estimate_within_half_the_round_trip() {
+    let [(offset_a, ping_a), (offset_b, ping_b)] = play(10, 90);
+    assert_within_half_rtt(offset_a, ping_a, -SKEW);
+    assert_within_half_rtt(offset_b, ping_b, SKEW);
+    // Reports reach the first player 40ms sooner than half the round trip,
+    // so it takes the second player's timestamps for 40ms older than they
+    // are, and the second player the other way round.
+    assert!(offset_a > -SKEW + 20, "estimate {offset_a}ms");
+    assert!(offset_b < SKEW - 20, "estimate {offset_b}ms");
+}
+
+#[test]
+fn clock_offset_rejects_a_local_handle() {
+    let clock = TestClock::new();
+    let (socket_a, _, _, addr_b) = create_chaos_channel_pair(
+        ChaosConfig::passthrough(),
+        ChaosConfig::passthrough(),
+        &clock,
+    );
+    let session = player(0, addr_b, socket_a, &clock);
+    assert_eq!(
+        session.clock_offset(PlayerHandle::new(0)),
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotRemotePlayerOrSpectator {
+                handle: PlayerHandle::new(0)
+            }
+        })
+    );
+}
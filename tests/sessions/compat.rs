@@ -1,6 +1,6 @@
 //! Public-session protocol-v1 compatibility regressions.
 
-#![allow(clippy::expect_used)]
+#![allow(clippy::expect_used, clippy::indexing_slicing, clippy::panic)]
 
 use crate::common::stubs::StubConfig;
 use crate::common::{create_channel_pair, TestClock, POLL_INTERVAL_DETERMINISTIC};
@@ -71,6 +71,10 @@ fn mismatched_player_counts_fail_both_handshakes_once_without_timeout() -> Resul
             reason: IncompatibleSessionReason::NumPlayers { ours: 3, theirs: 2 },
         } if *addr == addr_a
     )));
+    assert!(events_a.iter().all(|event| match event {
+        FortressEvent::IncompatibleSession { reason, .. } => reason.field() == "num_players",
+        _ => true,
+    }));
     assert!(events_a
         .iter()
         .all(|event| !matches!(event, FortressEvent::Synchronized { .. })));
@@ -144,3 +148,130 @@ fn spectator_translates_host_config_mismatch_and_remains_synchronizing() -> Resu
 
     Ok(())
 }
+
+fn poll_pair(
+    clock: &TestClock,
+    a: &mut fortress_rollback::P2PSession<StubConfig>,
+    b: &mut fortress_rollback::P2PSession<StubConfig>,
+) {
+    for _ in 0..20 {
+        a.poll_remote_clients();
+        b.poll_remote_clients();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+}
+
+#[test]
+fn conflicting_handle_claims_fail_both_handshakes() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (socket_a, socket_b, addr_a, addr_b) = create_channel_pair();
+    // Both peers believe they own handle 0.
+    let mut session_a = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock, 5))
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(addr_b), PlayerHandle::new(1))?
+        .start_p2p_session(socket_a)?;
+    let mut session_b = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock, 6))
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(addr_a), PlayerHandle::new(1))?
+        .start_p2p_session(socket_b)?;
+
+    poll_pair(&clock, &mut session_a, &mut session_b);
+
+    for (session, peer) in [(&mut session_a, addr_b), (&mut session_b, addr_a)] {
+        let reasons: Vec<_> = session
+            .events()
+            .filter_map(|event| match event {
+                FortressEvent::IncompatibleSession { addr, reason } if addr == peer => Some(reason),
+                FortressEvent::Synchronized { .. } => panic!("conflicting peers synchronized"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![IncompatibleSessionReason::DuplicateHandle {
+                handle: PlayerHandle::new(0)
+            }]
+        );
+        assert_eq!(reasons[0].field(), "player_handles");
+        assert_eq!(session.current_state(), SessionState::Synchronizing);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn a_doubly_claimed_handle_is_named_on_both_sides() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (socket_a, socket_b, addr_a, addr_b) = create_channel_pair();
+    // A lobby hands handle 1 to a local player on both machines.
+    let mut session_a = SessionBuilder::<StubConfig>::new()
+        .with_num_players(3)?
+        .with_protocol_config(protocol_config(&clock, 9))
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_player(PlayerType::Remote(addr_b), PlayerHandle::new(2))?
+        .start_p2p_session(socket_a)?;
+    let mut session_b = SessionBuilder::<StubConfig>::new()
+        .with_num_players(3)?
+        .with_protocol_config(protocol_config(&clock, 10))
+        .add_player(PlayerType::Remote(addr_a), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_player(PlayerType::Local, PlayerHandle::new(2))?
+        .start_p2p_session(socket_b)?;
+
+    poll_pair(&clock, &mut session_a, &mut session_b);
+
+    for session in [&mut session_a, &mut session_b] {
+        let reasons: Vec<_> = session
+            .events()
+            .filter_map(|event| match event {
+                FortressEvent::IncompatibleSession { reason, .. } => Some(reason),
+                FortressEvent::Synchronized { .. } => panic!("conflicting peers synchronized"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![IncompatibleSessionReason::DuplicateHandle {
+                handle: PlayerHandle::new(1)
+            }]
+        );
+        assert_eq!(
+            reasons[0].to_string(),
+            "player handle 1 claimed by more than one endpoint"
+        );
+        assert_eq!(session.current_state(), SessionState::Synchronizing);
+        assert_eq!(session.metrics().undeclared_handle_inputs, 0);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn matching_handle_claims_reach_running() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (socket_a, socket_b, addr_a, addr_b) = create_channel_pair();
+    let mut session_a = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock, 7))
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(addr_b), PlayerHandle::new(1))?
+        .start_p2p_session(socket_a)?;
+    let mut session_b = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock, 8))
+        .add_player(PlayerType::Remote(addr_a), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(socket_b)?;
+
+    poll_pair(&clock, &mut session_a, &mut session_b);
+
+    for session in [&mut session_a, &mut session_b] {
+        assert!(session
+            .events()
+            .all(|event| !matches!(event, FortressEvent::IncompatibleSession { .. })));
+        assert_eq!(session.current_state(), SessionState::Running);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+//! Integration tests for desync-detection frame selection and
+//! `P2PSession::desync_detection_lag`.
+//!
+//! Covers:
+//! - Two peers behind asymmetric `ChaosSocket` latency comparing checksums for
+//!   every multiple of the interval exactly once, with a bounded lag.
+//! - `desync_detection_lag` staying `None` until a comparison happened and
+//!   whenever desync detection is off.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, create_chaos_channel_pair, protocol_config, start_two_peer_session,
+    step_peers, synchronize_sessions_deterministic, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    ChaosConfig, DesyncDetection, FortressError, NonBlockingSocket, P2PSession, SessionBuilder,
+};
+use std::net::SocketAddr;
+
+/// Checksum interval used by the latency scenario.
+const INTERVAL: u32 = 5;
+/// Confirmed frame both peers must reach before the final checks.
+const END_FRAME: i32 = 240;
+/// Upper bound on advance rounds.
+const MAX_ROUNDS: usize = 2_000;
+
+fn session(
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+    desync_detection: DesyncDetection,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_desync_detection_mode(desync_detection)
+        .with_protocol_config(protocol_config(clock));
+    start_two_peer_session(builder, local, remote_addr, socket)
+}
+
+fn step(sessions: &mut [P2PSession<StubConfig>; 2], stubs: &mut [GameStub; 2], clock: &TestClock) {
+    step_peers(sessions, stubs, |handle, frame| StubInput {
+        inp: frame.as_i32() as u32 % 7 + handle as u32,
+    })
+    .unwrap();
+    clock.advance(POLL_INTERVAL_DETERMINISTIC);
+}
+
+#[test]
+fn every_interval_frame_is_compared_exactly_once_under_latency() {
+    let clock = TestClock::new();
+    let chaos = |latency_ms, seed| {
+        ChaosConfig::builder()
+            .latency_ms(latency_ms)
+            .seed(seed)
+            .build()
+    };
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(30, 1), chaos(70, 2), &clock);
+    let detection = DesyncDetection::On { interval: INTERVAL };
+    let mut sessions = [
+        session(s1, 0, a2, &clock, detection).unwrap(),
+        session(s2, 1, a1, &clock, detection).unwrap(),
+    ];
+    let [sess1, sess2] = &mut sessions;
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut max_lag = [0; 2];
+    for _ in 0..MAX_ROUNDS {
+        if sessions
+            .iter()
+            .all(|session| session.confirmed_frame().as_i32() >= END_FRAME)
+        {
+            break;
+        }
+        step(&mut sessions, &mut stubs, &clock);
+        for (session, max_lag) in sessions.iter().zip(&mut max_lag) {
+            if let Some(lag) = session.desync_detection_lag() {
+                *max_lag = (*max_lag).max(lag);
+            }
+        }
+    }
+
+    for (session, max_lag) in sessions.iter().zip(max_lag) {
+        assert!(session.confirmed_frame().as_i32() >= END_FRAME);
+        let lag = session
+            .desync_detection_lag()
+            .expect("checksums were compared");
+        let newest_compared = session.confirmed_frame().as_i32() - lag as i32;
+        assert_eq!(newest_compared % INTERVAL as i32, 0);
+
+        // Fixed latency keeps reports in order, so every grid frame up to the
+        // newest compared one has been compared, each exactly once.
+        let metrics = session.metrics();
+        assert_eq!(
+            metrics.checksums_compared,
+            (newest_compared / INTERVAL as i32) as u64
+        );
+        assert_eq!(metrics.checksums_mismatched, 0);
+        assert!(
+            max_lag <= 3 * INTERVAL,
+            "detection lag {max_lag} exceeded three intervals"
+        );
+    }
+}
+
+#[test]
+fn desync_detection_lag_is_none_until_compared_or_when_off() {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let detection = DesyncDetection::On { interval: 1 };
+    let mut sess1 = session(s1, 0, a2, &clock, detection).unwrap();
+    let mut sess2 = session(s2, 1, a1, &clock, detection).unwrap();
+    assert_eq!(sess1.desync_detection_lag(), None);
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    assert_eq!(sess1.desync_detection_lag(), None);
+
+    let mut sessions = [sess1, sess2];
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    for _ in 0..MAX_ROUNDS {
+        if sessions
+            .iter()
+            .all(|session| session.desync_detection_lag().is_some())
+        {
+            break;
+        }
+        step(&mut sessions, &mut stubs, &clock);
+    }
+    assert!(sessions
+        .iter()
+        .all(|session| session.desync_detection_lag().is_some()));
+
+    let (s1, _s2, _a1, a2) = create_channel_pair();
+    let off = session(s1, 0, a2, &clock, DesyncDetection::Off).unwrap();
+    assert_eq!(off.desync_detection_lag(), None);
+}
@@ -612,7 +612,7 @@ fn run_asymmetric_scenario(
                             fortress_rollback::FortressRequest::LoadGameState { cell, .. } => {
                                 cursor = cell.frame().as_i32();
                             },
-                            fortress_rollback::FortressRequest::AdvanceFrame { inputs } => {
+                            fortress_rollback::FortressRequest::AdvanceFrame { inputs, .. } => {
                                 cursor += 1;
                                 let vals: Vec<u32> =
                                     inputs.iter().map(|(inp, _)| inp.inp).collect();
@@ -895,7 +895,7 @@ pub fn handle_requests_recording(
                     FortressRequest::SaveGameState { cell, frame } => {
                         self.save(cell, frame);
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         self.gs.advance(&inputs);
                         states.insert(self.gs.frame, self.gs);
                     },
@@ -1054,7 +1054,7 @@ fn false_positive_desync_frames(&self) -> Vec<i32> {
                             fortress_rollback::FortressRequest::LoadGameState { cell, .. } => {
                                 cursor = cell.frame().as_i32();
                             },
-                            fortress_rollback::FortressRequest::AdvanceFrame { inputs } => {
+                            fortress_rollback::FortressRequest::AdvanceFrame { inputs, .. } => {
                                 cursor += 1;
                                 let vals: Vec<u32> =
                                     inputs.iter().map(|(inp, _)| inp.inp).collect();
@@ -0,0 +1,169 @@
+//! Trust in third-party disconnect gossip.
+//!
+//! Three players run a loopback match under virtual time while player 1 tells
+//! player 0, in every input message, that player 2 has disconnected. Player 0
+//! keeps hearing from player 2 directly. Covers:
+//! - Player 0 never dropping player 2 on that say-so alone: no `Disconnected`
+//!   event, and player 2's real inputs keep arriving and confirming frames.
+//! - The claim being surfaced once as a hint, in the session metrics and as a
+//!   warning.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{StateStub, StubConfig, StubInput};
+use crate::common::{create_channel_mesh, ChannelSocket, TestClock};
+use fortress_rollback::telemetry::{CollectingObserver, ViolationObserver, ViolationSeverity};
+use fortress_rollback::{
+    FortressError, FortressEvent, FortressRequest, InputStatus, Message, NonBlockingSocket,
+    P2PSession, PlayerHandle, PlayerType, ProtocolConfig, RequestVec, SessionBuilder, SessionState,
+};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+const STEPS: usize = 400;
+const VICTIM: usize = 2;
+
+/// Forwards to the wrapped socket, marking the victim disconnected in every
+/// input message sent to `target`.
+struct LyingSocket {
+    inner: ChannelSocket,
+    target: SocketAddr,
+}
+
+impl NonBlockingSocket<SocketAddr> for LyingSocket {
+    fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        if *addr == self.target {
+            let mut msg = msg.clone();
+            fortress_rollback::__internal::mark_input_gossip_disconnected(&mut msg, VICTIM);
+            self.inner.send_to(&msg, addr);
+        } else {
+            self.inner.send_to(msg, addr);
+        }
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+        self.inner.receive_all_messages()
+    }
+}
+
+fn player(
+    local: usize,
+    addrs: &[SocketAddr],
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    clock: &TestClock,
+    observer: &Arc<CollectingObserver>,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_num_players(3)?
+        .with_max_prediction_window(8)
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::deterministic(60 + local as u64)
+        })
+        .with_violation_observer(Arc::clone(observer) as Arc<dyn ViolationObserver>);
+    for (handle, addr) in addrs.iter().enumerate() {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(*addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+/// Fulfills `requests` on `state`, checking the victim's input in every
+/// advanced frame is live. Returns the number of advanced frames.
+fn fulfill(state: &mut StateStub, requests: RequestVec<StubConfig>) -> usize {
+    let mut advanced = 0;
+    for request in requests {
+        match request {
+            FortressRequest::SaveGameState { cell, frame } => {
+                cell.save(frame, Some(*state), None);
+            },
+            FortressRequest::LoadGameState { cell, .. } => {
+                *state = cell.load().unwrap();
+            },
+            FortressRequest::AdvanceFrame { inputs, .. } => {
+                let (input, status) = inputs[VICTIM];
+                assert_ne!(status, InputStatus::Disconnected);
+                if state.frame > 0 {
+                    assert_eq!(input.inp, 7, "frame {}: {status:?}", state.frame);
+                }
+                state.advance_frame_pub(inputs);
+                advanced += 1;
+            },
+        }
+    }
+    advanced
+}
+
+#[test]
+fn a_false_disconnect_claim_does_not_drop_a_player_we_still_hear_from() {
+    let clock = TestClock::new();
+    let observer = Arc::new(CollectingObserver::new());
+    let (sockets, addrs) = create_channel_mesh(3);
+    let mut sockets = sockets.into_iter();
+    let honest = sockets.next().unwrap();
+    let liar = LyingSocket {
+        inner: sockets.next().unwrap(),
+        target: addrs[0],
+    };
+    let victim = sockets.next().unwrap();
+    let mut players = [
+        player(0, &addrs, honest, &clock, &observer).unwrap(),
+        player(1, &addrs, liar, &clock, &observer).unwrap(),
+        player(2, &addrs, victim, &clock, &observer).unwrap(),
+    ];
+    let mut states = [StateStub::default(); 3];
+
+    for _ in 0..STEPS {
+        clock.advance(Duration::from_millis(16));
+        for (handle, session) in players.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            for event in session.events() {
+                if let FortressEvent::Disconnected { addr } = event {
+                    panic!("player {handle} dropped {addr}");
+                }
+            }
+            if session.current_state() != SessionState::Running {
+                continue;
+            }
+            // The victim's input is its constant, non-default value.
+            let inp = if handle == VICTIM { 7 } else { 1 };
+            session
+                .add_local_input(PlayerHandle::new(handle), StubInput { inp })
+                .unwrap();
+            match session.advance_frame() {
+                Ok(requests) => {
+                    fulfill(&mut states[handle], requests);
+                },
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => panic!("player {handle} failed to advance: {err}"),
+            }
+        }
+    }
+
+    // Confirming a frame takes the victim's real input for it.
+    assert!(states[0].frame > 300, "{:?}", states[0]);
+    assert!(
+        players[0].confirmed_frame().as_i32() > 300,
+        "confirmed only through {}",
+        players[0].confirmed_frame()
+    );
+    assert_eq!(players[0].metrics().uncorroborated_disconnect_claims, 1);
+    let hints: Vec<_> = observer
+        .violations()
+        .into_iter()
+        .filter(|v| v.message.contains("claims player 2 disconnected"))
+        .collect();
+    assert_eq!(hints.len(), 1, "{hints:?}");
+    assert_eq!(hints[0].severity, ViolationSeverity::Warning);
+}
@@ -0,0 +1,190 @@
+//! Deterministic endpoint iteration order.
+//!
+//! Four peers play the same seeded chaotic match twice under virtual time,
+//! once with addresses that sort like the player handles and once with the
+//! addresses shuffled and the players added to each builder in reverse, so
+//! the address order and the insertion order both differ from the handle
+//! order. Every peer records its events and, through a `TracingSocket`, its
+//! packets; addresses are mapped back to peer indices before comparing.
+//! Covers:
+//! - Identical event sequences in both runs.
+//! - Identical packet traces in both runs: same packets, same times, same
+//!   order.
+//! - Identical final game states.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_channel_mesh, ChannelSocket, TestClock};
+use fortress_rollback::hash::fnv1a_hash;
+use fortress_rollback::{
+    ChaosConfig, ChaosSocket, DesyncDetection, FortressError, Frame, PacketDirection,
+    PacketTraceHandle, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+    TracingSocket,
+};
+
+const PEERS: usize = 4;
+const STEPS: usize = 300;
+const STEP: Duration = Duration::from_millis(16);
+
+/// The socket of peer `i` in the shuffled run; its address order is neither
+/// the handle order nor its reverse.
+const SHUFFLED: [usize; PEERS] = [2, 0, 3, 1];
+
+type PeerSocket = TracingSocket<SocketAddr, ChaosSocket<SocketAddr, ChannelSocket>>;
+
+fn input_for(peer: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(7 + peer as u32) % 11,
+    }
+}
+
+/// What one peer saw, with addresses replaced by peer indices.
+#[derive(Debug, PartialEq)]
+struct Record {
+    events: Vec<String>,
+    packets: Vec<(PacketDirection, Duration, usize, Vec<u8>)>,
+    frame: i32,
+    checksum: u64,
+}
+
+/// Plays the match with peer `i` on socket `sockets[i]` of a fresh mesh.
+fn play(sockets: [usize; PEERS], reverse_insertion: bool) -> Result<Vec<Record>, FortressError> {
+    let clock = TestClock::new();
+    let (mesh, mesh_addrs) = create_channel_mesh(PEERS);
+    let mut mesh: Vec<Option<ChannelSocket>> = mesh.into_iter().map(Some).collect();
+    let addrs: Vec<SocketAddr> = sockets.iter().map(|&socket| mesh_addrs[socket]).collect();
+
+    let mut sessions = Vec::new();
+    let mut traces: Vec<PacketTraceHandle<SocketAddr>> = Vec::new();
+    for (peer, &socket) in sockets.iter().enumerate() {
+        // the chaos follows the peer, whichever socket it has
+        let chaos = ChaosConfig::builder()
+            .latency_ms(20)
+            .jitter_ms(10)
+            .packet_loss_rate(0.05)
+            .seed(300 + peer as u64)
+            .build();
+        let socket: PeerSocket = TracingSocket::new(
+            ChaosSocket::new(mesh[socket].take().unwrap(), chaos)
+                .with_clock(clock.as_chaos_clock()),
+        )
+        .with_clock(clock.as_protocol_clock());
+        traces.push(socket.trace());
+
+        let mut builder = SessionBuilder::<StubConfig>::new()
+            .with_num_players(PEERS)?
+            .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::deterministic(500 + peer as u64)
+            });
+        let mut handles: Vec<usize> = (0..PEERS).collect();
+        if reverse_insertion {
+            handles.reverse();
+        }
+        for handle in handles {
+            let player = if handle == peer {
+                PlayerType::Local
+            } else {
+                PlayerType::Remote(addrs[handle])
+            };
+            builder = builder.add_player(player, PlayerHandle::new(handle))?;
+        }
+        sessions.push(builder.start_p2p_session(socket)?);
+    }
+
+    let mut games: Vec<GameStub> = (0..PEERS).map(|_| GameStub::new()).collect();
+    let mut events: Vec<Vec<String>> = vec![Vec::new(); PEERS];
+    for _ in 0..STEPS {
+        clock.advance(STEP);
+        for (peer, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            if session.current_state() == SessionState::Running {
+                let frame = session.current_frame();
+                session.add_local_input(PlayerHandle::new(peer), input_for(peer, frame))?;
+                match session.advance_frame() {
+                    Ok(requests) => games[peer].handle_requests(requests),
+                    Err(FortressError::PredictionThreshold) => {},
+                    Err(err) => return Err(err),
+                }
+            }
+            events[peer].extend(session.events().map(|event| {
+                let mut event = format!("{event:?}");
+                for (index, addr) in addrs.iter().enumerate() {
+                    event = event.replace(&addr.to_string(), &format!("peer{index}"));
+                }
+                event
+            }));
+        }
+    }
+
+    let peer_of = |addr: &SocketAddr| addrs.iter().position(|a| a == addr).unwrap();
+    Ok(traces
+        .iter()
+        .zip(events)
+        .zip(&games)
+        .map(|((trace, events), game)| Record {
+            events,
+            packets: trace
+                .take()
+                .records
+                .into_iter()
+                .map(|record| {
+                    (
+                        record.direction,
+                        record.at,
+                        peer_of(&record.addr),
+                        record.bytes,
+                    )
+                })
+                .collect(),
+            frame: game.gs.frame,
+            checksum: fnv1a_hash(&game.gs),
+        })
+        .collect())
+}
+
+#[test]
+fn shuffled_addresses_and_insertion_order_replay_the_same_match() -> Result<(), FortressError> {
+    let ordered = play([0, 1, 2, 3], false)?;
+    let shuffled = play(SHUFFLED, true)?;
+
+    for (peer, (ordered, shuffled)) in ordered.iter().zip(&shuffled).enumerate() {
+        assert!(
+            ordered.packets.len() > 500,
+            "peer {peer} sent and received {} packets",
+            ordered.packets.len()
+        );
+        assert!(ordered
+            .events
+            .iter()
+            .any(|event| event.contains("Synchronized")));
+        assert_eq!(ordered.events, shuffled.events, "peer {peer} events");
+        assert_eq!(
+            ordered.packets.len(),
+            shuffled.packets.len(),
+            "peer {peer} packet count"
+        );
+        for (index, (a, b)) in ordered.packets.iter().zip(&shuffled.packets).enumerate() {
+            assert_eq!(a, b, "peer {peer} packet {index}");
+        }
+        assert!(
+            ordered.frame > 100,
+            "peer {peer} reached frame {}",
+            ordered.frame
+        );
+        assert_eq!(ordered.frame, shuffled.frame, "peer {peer} frame");
+        assert_eq!(ordered.checksum, shuffled.checksum, "peer {peer} state");
+    }
+    Ok(())
+}
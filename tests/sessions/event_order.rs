@@ -0,0 +1,93 @@
+//! Integration tests for the order `P2PSession::poll_remote_clients` delivers
+//! endpoint events in: the order they were generated, across every remote
+//! and spectator endpoint, rather than endpoint by endpoint.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::net::SocketAddr;
+
+use crate::common::stubs::StubConfig;
+use crate::common::{
+    add_mesh_players, create_channel_triple, protocol_config, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{FortressError, FortressEvent, P2PSession, SessionBuilder, SessionState};
+
+/// Upper bound on polls the handshake may take.
+const MAX_POLLS: usize = 200;
+
+fn session(
+    clock: &TestClock,
+    addrs: [SocketAddr; 3],
+    local: usize,
+    socket: crate::common::ChannelSocket,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new()
+        .with_num_players(3)?
+        .with_protocol_config(protocol_config(clock));
+    add_mesh_players(builder, local, &addrs)?.start_p2p_session(socket)
+}
+
+/// One handshake event the host reported, as `(peer, completed)`.
+type SyncStep = (SocketAddr, bool);
+
+fn sync_steps(host: &mut P2PSession<StubConfig>) -> Vec<SyncStep> {
+    host.events()
+        .filter_map(|event| match event {
+            FortressEvent::Synchronizing { addr, .. } => Some((addr, false)),
+            FortressEvent::Synchronized { addr } => Some((addr, true)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn handshake_events_follow_generation_order_across_peers() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, s3, a1, a2, a3) = create_channel_triple();
+    let addrs = [a1, a2, a3];
+    let mut host = session(&clock, addrs, 0, s1)?;
+    let mut early = session(&clock, addrs, 1, s2)?;
+    let mut late = session(&clock, addrs, 2, s3)?;
+    // The host iterates its endpoints by address, so the peer whose packets
+    // reach it first must have the higher address for the order to matter.
+    let (first_addr, second_addr) = (a3, a2);
+
+    let mut polls_with_both = 0;
+    for _ in 0..MAX_POLLS {
+        // The peer at `first_addr` answers first, so within each host poll
+        // its handshake events are generated before the other peer's.
+        late.poll_remote_clients();
+        early.poll_remote_clients();
+        host.poll_remote_clients();
+
+        let steps = sync_steps(&mut host);
+        let first_seen = steps.iter().filter(|(addr, _)| *addr == first_addr).count();
+        assert!(
+            steps[..first_seen]
+                .iter()
+                .all(|(addr, _)| *addr == first_addr),
+            "events out of generation order: {steps:?}"
+        );
+        if first_seen > 0 && steps.iter().any(|(addr, _)| *addr == second_addr) {
+            polls_with_both += 1;
+        }
+        if host.current_state() == SessionState::Running {
+            break;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    assert_eq!(host.current_state(), SessionState::Running);
+    assert!(
+        polls_with_both > 0,
+        "no host poll saw both peers' handshakes advance"
+    );
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! Integration tests for [`P2PSession::events_timestamped`] and
+//! [`SpectatorSession::events_timestamped`]: every event carries the session's
+//! current frame and the milliseconds since the session was created, both read
+//! when the event was generated.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, drain_sync_events, poll_with_advance, synchronize_sessions_deterministic,
+    synchronize_spectator_deterministic, SyncConfig, TestClock, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    DesyncDetection, FortressError, FortressEvent, Frame, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, TimestampedEvent,
+};
+
+fn builder(clock: &TestClock) -> SessionBuilder<StubConfig> {
+    SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    })
+}
+
+fn session_pair(
+    clock: &TestClock,
+    desync_detection: DesyncDetection,
+) -> Result<(P2PSession<StubConfig>, P2PSession<StubConfig>), FortressError> {
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let sess1 = builder(clock)
+        .with_desync_detection_mode(desync_detection)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let sess2 = builder(clock)
+        .with_desync_detection_mode(desync_detection)
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    Ok((sess1, sess2))
+}
+
+fn synchronized(events: &[TimestampedEvent<StubConfig>]) -> &TimestampedEvent<StubConfig> {
+    events
+        .iter()
+        .find(|stamped| matches!(stamped.event, FortressEvent::Synchronized { .. }))
+        .unwrap_or_else(|| panic!("no Synchronized event in {events:?}"))
+}
+
+#[test]
+fn synchronized_is_stamped_with_the_pre_game_frame_and_clock_time() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut sess1, mut sess2) = session_pair(&clock, DesyncDetection::Off)?;
+    let iterations =
+        synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())?;
+
+    // The last poll, which synchronized both sessions, ran after
+    // `iterations - 1` clock advances.
+    let expected_ms =
+        u64::try_from((POLL_INTERVAL_DETERMINISTIC * (iterations as u32 - 1)).as_millis()).unwrap();
+    for session in [&mut sess1, &mut sess2] {
+        let events: Vec<_> = session.events_timestamped().collect();
+        let stamped = synchronized(&events);
+        assert_eq!(stamped.frame, Frame::new(0));
+        assert_eq!(stamped.session_ms, expected_ms);
+        // Sync progress events come first and are never stamped later.
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].session_ms <= pair[1].session_ms));
+    }
+    Ok(())
+}
+
+#[test]
+fn desync_detected_is_stamped_with_the_frame_it_was_detected_on() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut sess1, mut sess2) = session_pair(&clock, DesyncDetection::On { interval: 10 })?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())?;
+    drain_sync_events(&mut sess1, &mut sess2);
+
+    let mut stub1 = GameStub::new();
+    let mut stub2 = GameStub::new();
+    let mut detected = Vec::new();
+    for i in 0..60 {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 3);
+        if i >= 20 {
+            // Diverge peer 1's state from frame 20 on.
+            stub1.gs.state = 1234;
+        }
+        sess1.add_local_input(PlayerHandle::new(0), StubInput { inp: 0 })?;
+        sess2.add_local_input(PlayerHandle::new(1), StubInput { inp: 1 })?;
+        // Checksums are compared at the start of `advance_frame`, before the
+        // session moves on to the next frame.
+        let frame_before = sess1.current_frame();
+        stub1.handle_requests(sess1.advance_frame()?);
+        stub2.handle_requests(sess2.advance_frame()?);
+        for stamped in sess1.events_timestamped() {
+            if let FortressEvent::DesyncDetected { frame, .. } = stamped.event {
+                detected.push((stamped.frame, frame_before, frame));
+            }
+        }
+        let _ = sess2.events();
+    }
+
+    assert!(
+        !detected.is_empty(),
+        "the diverged state was never detected"
+    );
+    for (stamped_frame, frame_before, desync_frame) in detected {
+        assert_eq!(stamped_frame, frame_before);
+        // The mismatching frame was confirmed, so it lies behind the session.
+        assert!(desync_frame < stamped_frame);
+    }
+    Ok(())
+}
+
+#[test]
+fn events_and_events_timestamped_drain_the_same_queue() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut sess1, mut sess2) = session_pair(&clock, DesyncDetection::Off)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())?;
+
+    assert!(sess1.events_timestamped().count() > 0);
+    assert_eq!(sess1.events().count(), 0);
+
+    assert!(sess2.events().count() > 0);
+    assert_eq!(sess2.events_timestamped().count(), 0);
+    Ok(())
+}
+
+#[test]
+fn spectator_synchronized_is_stamped_with_the_pre_game_frame() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let mut host = builder(&clock)
+        .with_num_players(1)?
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(1))?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = builder(&clock)
+        .with_num_players(1)?
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    let result = synchronize_spectator_deterministic(&mut spectator, &mut host, &clock);
+    assert!(result.success, "spectator should synchronize");
+
+    let events: Vec<_> = spectator.events_timestamped().collect();
+    let stamped = synchronized(&events);
+    assert_eq!(stamped.frame, Frame::NULL);
+    assert!(stamped.session_ms >= 50, "{}", stamped.session_ms);
+    Ok(())
+}
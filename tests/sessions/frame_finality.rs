@@ -0,0 +1,133 @@
+//! Integration tests for `P2PSession::finalized_frame` and
+//! `P2PSession::is_frame_final`.
+//!
+//! Covers:
+//! - Without mispredictions the finalized frame follows the confirmed frame.
+//! - While a detected rollback waits for the next `advance_frame`, the
+//!   confirmed frame already covers the corrected input but finality stays
+//!   below the mispredicted frame, and the rollback never loads a final frame,
+//!   with every save mode.
+//! - A capped resimulation holds finality back until it reaches the frame.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::misprediction::{MispredictionHarness, WARMUP_FRAMES};
+use crate::common::stubs::StubConfig;
+use fortress_rollback::{FortressError, FortressRequest, Frame, RequestVec, SaveMode};
+
+/// Depth of the forced rollback.
+const ROLLBACK_DEPTH: u32 = 6;
+
+/// Two synchronized sessions saving with `save_mode`, session 1
+/// resimulating at most `cap` frames per call.
+fn start(save_mode: SaveMode, cap: Option<usize>) -> Result<MispredictionHarness, FortressError> {
+    MispredictionHarness::new(|builder, local| {
+        let builder = builder.with_save_mode(save_mode);
+        match cap {
+            Some(cap) if local == 0 => builder.with_max_resimulation_per_advance(cap),
+            _ => Ok(builder),
+        }
+    })
+}
+
+/// Advances session 1 and returns the frames it was asked to load.
+fn advance1(run: &mut MispredictionHarness) -> Result<Vec<Frame>, FortressError> {
+    let requests = run.request1(0)?;
+    let loads = loaded_frames(&requests);
+    run.handle1(requests);
+    Ok(loads)
+}
+
+fn loaded_frames(requests: &RequestVec<StubConfig>) -> Vec<Frame> {
+    requests
+        .iter()
+        .filter_map(|request| match request {
+            FortressRequest::LoadGameState { frame, .. } => Some(*frame),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn finalized_frame_follows_confirmed_frame_without_mispredictions() -> Result<(), FortressError> {
+    let mut run = start(SaveMode::EveryFrame, None)?;
+    assert_eq!(run.sess1.finalized_frame(), Frame::NULL);
+    assert!(!run.sess1.is_frame_final(Frame::new(0)));
+    assert!(!run.sess1.is_frame_final(Frame::NULL));
+
+    for _ in 0..20 {
+        run.poll(1);
+        assert!(advance1(&mut run)?.is_empty());
+        run.advance2(0)?;
+        assert_eq!(run.sess1.finalized_frame(), run.sess1.confirmed_frame());
+    }
+    assert!(run.sess1.finalized_frame() > Frame::new(0));
+    assert!(run.sess1.is_frame_final(Frame::new(0)));
+    Ok(())
+}
+
+#[test]
+fn finality_waits_for_a_pending_rollback() -> Result<(), FortressError> {
+    let mispredicted = Frame::new(WARMUP_FRAMES as i32);
+    for save_mode in [
+        SaveMode::EveryFrame,
+        SaveMode::Sparse,
+        SaveMode::Pyramid { recent: 2 },
+    ] {
+        let mut run = start(save_mode, None)?;
+        run.force_misprediction(ROLLBACK_DEPTH)?;
+
+        // The corrected input is confirmed, but the rollback has not run yet.
+        assert!(
+            run.sess1.confirmed_frame() >= mispredicted,
+            "{save_mode:?}: confirmed {}",
+            run.sess1.confirmed_frame()
+        );
+        let finalized = run.sess1.finalized_frame();
+        assert!(finalized < mispredicted, "{save_mode:?}: {finalized}");
+        assert!(!run.sess1.is_frame_final(mispredicted), "{save_mode:?}");
+        if save_mode == SaveMode::EveryFrame {
+            assert_eq!(finalized, mispredicted - 1);
+            assert!(run.sess1.is_frame_final(mispredicted - 1));
+        }
+
+        // The rollback loads the frame after the finalized one at the latest.
+        let loads = advance1(&mut run)?;
+        assert!(!loads.is_empty(), "{save_mode:?}: the rollback must run");
+        assert!(
+            loads.iter().all(|&load| load > finalized),
+            "{save_mode:?}: loaded {loads:?} with {finalized} final"
+        );
+        assert!(run.sess1.finalized_frame() >= mispredicted, "{save_mode:?}");
+        assert!(run.sess1.finalized_frame() <= run.sess1.confirmed_frame());
+    }
+    Ok(())
+}
+
+#[test]
+fn capped_resimulation_holds_finality_back() -> Result<(), FortressError> {
+    let mispredicted = Frame::new(WARMUP_FRAMES as i32);
+    let mut run = start(SaveMode::EveryFrame, Some(2))?;
+    run.force_misprediction(ROLLBACK_DEPTH)?;
+
+    advance1(&mut run)?;
+    assert!(run.sess1.resimulation_debt() > 0);
+    assert!(run.sess1.confirmed_frame() >= run.sess1.current_frame());
+    // Only the frames resimulated so far are final.
+    assert_eq!(run.sess1.finalized_frame(), run.sess1.current_frame() - 1);
+    assert!(run.sess1.finalized_frame() < run.sess1.confirmed_frame());
+    assert!(run.sess1.finalized_frame() >= mispredicted);
+
+    while run.sess1.resimulation_debt() > 0 {
+        let requests = run.sess1.continue_resimulation()?;
+        run.stub1.handle_requests(requests);
+    }
+    assert_eq!(run.sess1.finalized_frame(), run.sess1.confirmed_frame());
+    Ok(())
+}
@@ -0,0 +1,305 @@
+//! Integration tests for the session frame limit
+//! (`SessionBuilder::with_max_session_frames`).
+//!
+//! Covers:
+//! - Two P2P peers, one running ahead with predictions outstanding, ending on
+//!   the limit frame with identical states and one terminal event each.
+//! - A spectator stopping after the last frame before the host's limit.
+//! - A sync test session stopping at the limit.
+//! - Builder validation of the limit and of the headroom above it.
+//!
+//! Frame arithmetic right below the default limit is covered at the unit level
+//! in `sync_layer` (`rollback_cycle_up_to_session_frame_limit`).
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, drain_sync_events, synchronize_sessions_deterministic,
+    synchronize_spectator_deterministic, SyncConfig, TestClock, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, Frame, InputQueueConfig, InvalidRequestKind, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, MAX_SESSION_FRAMES,
+};
+
+/// The configured limit, small enough to keep the tests short.
+const LIMIT: i32 = 60;
+/// Upper bound on steps any test may take.
+const MAX_STEPS: usize = 400;
+
+/// Input of `player` for `frame`; changes every frame so a prediction is
+/// wrong whenever a peer runs ahead.
+fn input_for(player: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(7 + player as u32) % 11,
+    }
+}
+
+fn limit_error(frame: Frame) -> FortressError {
+    InvalidRequestKind::SessionFrameLimitReached { frame }.into()
+}
+
+fn limit_events<'a>(events: impl IntoIterator<Item = &'a FortressEvent<StubConfig>>) -> Vec<Frame> {
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            FortressEvent::SessionFrameLimitReached { frame } => Some(*frame),
+            _ => None,
+        })
+        .collect()
+}
+
+/// One peer: its session, game stub, recorded states, and events seen.
+struct Peer {
+    session: P2PSession<StubConfig>,
+    handle: PlayerHandle,
+    stub: GameStub,
+    states: BTreeMap<i32, StateStub>,
+    events: Vec<FortressEvent<StubConfig>>,
+}
+
+impl Peer {
+    /// Runs one frame; the prediction threshold is not an error here.
+    fn step(&mut self) -> Result<(), FortressError> {
+        self.session.poll_remote_clients();
+        let frame = self.session.current_frame();
+        self.session
+            .add_local_input(self.handle, input_for(self.handle.as_usize(), frame))?;
+        let result = self.session.advance_frame();
+        self.events.extend(self.session.events());
+        match result {
+            Ok(requests) => {
+                self.stub
+                    .handle_requests_recording(requests, &mut self.states);
+                Ok(())
+            },
+            Err(FortressError::PredictionThreshold) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn start_peers(clock: &TestClock) -> Result<[Peer; 2], FortressError> {
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut sess1 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config.clone())
+        .with_max_session_frames(Frame::new(LIMIT))?
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config)
+        .with_max_session_frames(Frame::new(LIMIT))?
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+
+    let peer = |session, handle| Peer {
+        session,
+        handle: PlayerHandle::new(handle),
+        stub: GameStub::new(),
+        states: BTreeMap::new(),
+        events: Vec::new(),
+    };
+    Ok([peer(sess1, 0), peer(sess2, 1)])
+}
+
+#[test]
+fn both_peers_end_on_the_limit_frame_with_the_same_state() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let mut peers = start_peers(&clock)?;
+    let mut ended: [Option<FortressError>; 2] = [None, None];
+
+    for step in 0..MAX_STEPS {
+        if ended.iter().all(Option::is_some) {
+            break;
+        }
+        for (index, peer) in peers.iter_mut().enumerate() {
+            // Peer 1 runs at half speed, so peer 0 reaches the limit with
+            // mispredicted frames still to repair.
+            if index == 1 && step % 2 == 1 && ended[1].is_none() {
+                peer.session.poll_remote_clients();
+                continue;
+            }
+            if let Err(err) = peer.step() {
+                match &ended[index] {
+                    Some(first) => assert_eq!(&err, first, "the refusal is stable"),
+                    None => ended[index] = Some(err),
+                }
+            }
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    let limit = Frame::new(LIMIT);
+    for (peer, end) in peers.iter().zip(&ended) {
+        assert_eq!(end.as_ref(), Some(&limit_error(limit)));
+        assert_eq!(peer.session.current_frame(), limit);
+        assert_eq!(limit_events(&peer.events), vec![limit]);
+        assert_eq!(peer.states.keys().copied().max(), Some(LIMIT));
+        assert!(peer
+            .events
+            .iter()
+            .all(|event| !matches!(event, FortressEvent::DesyncDetected { .. })));
+    }
+    assert!(
+        peers[0].session.metrics().rollback_count > 0,
+        "peer 0 predicted ahead of peer 1"
+    );
+    for frame in 1..=LIMIT {
+        assert_eq!(
+            peers[0].states[&frame], peers[1].states[&frame],
+            "frame {frame}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn spectator_stops_after_the_last_frame_before_the_limit() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config.clone())
+        .with_max_session_frames(Frame::new(LIMIT))?
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(1))?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config)
+        .with_max_session_frames(Frame::new(LIMIT))?
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let result = synchronize_spectator_deterministic(&mut spectator, &mut host, &clock);
+    assert!(result.success, "spectator should synchronize");
+
+    let mut host_stub = GameStub::new();
+    let mut spec_stub = GameStub::new();
+    let mut spec_states = BTreeMap::new();
+    let mut spec_end = None;
+    for _ in 0..MAX_STEPS {
+        host.poll_remote_clients();
+        let frame = host.current_frame();
+        host.add_local_input(PlayerHandle::new(0), input_for(0, frame))?;
+        match host.advance_frame() {
+            Ok(requests) => host_stub.handle_requests(requests),
+            Err(err) => assert_eq!(err, limit_error(Frame::new(LIMIT))),
+        }
+        match spectator.advance_frame() {
+            Ok(requests) => spec_stub.handle_requests_recording(requests, &mut spec_states),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => {
+                spec_end = Some(err);
+                break;
+            },
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    assert_eq!(spec_end, Some(limit_error(Frame::new(LIMIT))));
+    assert_eq!(spectator.current_frame(), Frame::new(LIMIT - 1));
+    assert_eq!(spec_states.keys().copied().max(), Some(LIMIT));
+    assert_eq!(spec_stub.gs, host_stub.gs);
+    assert_eq!(
+        limit_events(&spectator.events().collect::<Vec<_>>()),
+        vec![Frame::new(LIMIT)]
+    );
+    assert_eq!(
+        spectator.advance_frame().err(),
+        Some(limit_error(Frame::new(LIMIT)))
+    );
+    Ok(())
+}
+
+#[test]
+fn synctest_stops_at_the_limit() -> Result<(), FortressError> {
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_check_distance(2)
+        .with_max_session_frames(Frame::new(LIMIT))?
+        .start_synctest_session()?;
+    let mut stub = GameStub::new();
+    for frame in 0..LIMIT {
+        session.add_local_input(PlayerHandle::new(0), input_for(0, Frame::new(frame)))?;
+        stub.handle_requests(session.advance_frame()?);
+    }
+    assert_eq!(session.current_frame(), Frame::new(LIMIT));
+    assert_eq!(stub.gs.frame, LIMIT);
+
+    for _ in 0..2 {
+        session.add_local_input(PlayerHandle::new(0), input_for(0, Frame::new(LIMIT)))?;
+        assert_eq!(
+            session.advance_frame().err(),
+            Some(limit_error(Frame::new(LIMIT)))
+        );
+    }
+    assert_eq!(
+        limit_events(&session.events().collect::<Vec<_>>()),
+        vec![Frame::new(LIMIT)]
+    );
+    assert_eq!(stub.gs.frame, LIMIT);
+    Ok(())
+}
+
+#[test]
+fn max_session_frames_is_validated() -> Result<(), FortressError> {
+    for frames in [Frame::new(0), Frame::NULL, MAX_SESSION_FRAMES + 1] {
+        let result = SessionBuilder::<StubConfig>::new().with_max_session_frames(frames);
+        assert!(
+            matches!(
+                result,
+                Err(FortressError::InvalidRequestStructured {
+                    kind: InvalidRequestKind::ConfigValueOutOfRange {
+                        field: "max_session_frames",
+                        ..
+                    }
+                })
+            ),
+            "{frames:?}"
+        );
+    }
+    let _builder =
+        SessionBuilder::<StubConfig>::new().with_max_session_frames(MAX_SESSION_FRAMES)?;
+
+    // The prediction window must fit between the limit and `i32::MAX`.
+    let result = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_input_queue_config(InputQueueConfig {
+            queue_length: 1 << 21,
+            ..InputQueueConfig::default()
+        })
+        .with_max_prediction_window((1 << 20) + 1)
+        .start_synctest_session();
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_session_frames",
+                ..
+            }
+        })
+    ));
+    Ok(())
+}
@@ -0,0 +1,191 @@
+//! Integration tests for `P2PSession::frame_timeline`.
+//!
+//! Covers:
+//! - A scripted match with one rollback spanning three frames: first-pass
+//!   flags, then the rollback target and resimulated frames gaining flags
+//!   without losing any.
+//! - The timeline keeping only the most recent `capacity` frames, staying
+//!   empty when disabled, and the builder rejecting out-of-range capacities.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, FrameFlags, InvalidRequestKind, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder,
+};
+
+const CONFIRMED: FrameFlags = FrameFlags::FIRST_PASS_CONFIRMED;
+const PREDICTED: FrameFlags = FrameFlags::PREDICTED;
+const RESIMULATED: FrameFlags = FrameFlags::RESIMULATED;
+const ROLLBACK_TARGET: FrameFlags = FrameFlags::ROLLBACK_TARGET;
+
+/// Two synchronized peers; only the host (player 0) records a timeline.
+struct Pair {
+    host: P2PSession<StubConfig>,
+    peer: P2PSession<StubConfig>,
+    stubs: [GameStub; 2],
+    clock: TestClock,
+}
+
+impl Pair {
+    fn new(capacity: Option<usize>) -> Result<Self, FortressError> {
+        let clock = TestClock::new();
+        let (s1, s2, a1, a2) = create_channel_pair();
+        let protocol_config = ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        };
+        let mut host_builder =
+            SessionBuilder::<StubConfig>::new().with_protocol_config(protocol_config.clone());
+        if let Some(capacity) = capacity {
+            host_builder = host_builder.with_frame_timeline(capacity)?;
+        }
+        let mut host = host_builder
+            .add_player(PlayerType::Local, PlayerHandle::new(0))?
+            .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+            .start_p2p_session(s1)?;
+        let mut peer = SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(protocol_config)
+            .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+            .add_player(PlayerType::Local, PlayerHandle::new(1))?
+            .start_p2p_session(s2)?;
+        synchronize_sessions_deterministic(&mut host, &mut peer, &clock, &SyncConfig::default())
+            .expect("sessions should synchronize");
+        Ok(Self {
+            host,
+            peer,
+            stubs: [GameStub::new(), GameStub::new()],
+            clock,
+        })
+    }
+
+    fn advance_host(&mut self) -> Result<(), FortressError> {
+        self.host.poll_remote_clients();
+        self.host
+            .add_local_input(PlayerHandle::new(0), StubInput { inp: 0 })?;
+        self.stubs[0].handle_requests(self.host.advance_frame()?);
+        Ok(())
+    }
+
+    fn advance_peer(&mut self, inp: u32) -> Result<(), FortressError> {
+        self.peer.poll_remote_clients();
+        self.peer
+            .add_local_input(PlayerHandle::new(1), StubInput { inp })?;
+        self.stubs[1].handle_requests(self.peer.advance_frame()?);
+        Ok(())
+    }
+
+    /// One round in which the host already has the peer's input when it advances.
+    fn confirmed_round(&mut self) -> Result<(), FortressError> {
+        self.advance_peer(0)?;
+        self.advance_host()?;
+        self.clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        Ok(())
+    }
+
+    fn timeline(&self) -> Vec<(i32, FrameFlags)> {
+        self.host
+            .frame_timeline()
+            .map(|(frame, flags)| (frame.as_i32(), flags))
+            .collect()
+    }
+}
+
+#[test]
+fn rollback_over_three_frames_adds_flags_retroactively() -> Result<(), FortressError> {
+    let mut pair = Pair::new(Some(16))?;
+    assert!(pair.timeline().is_empty());
+
+    for _ in 0..3 {
+        pair.confirmed_round()?;
+    }
+    // The host runs frames 3..=5 alone, predicting the peer's input 0.
+    for _ in 0..3 {
+        pair.advance_host()?;
+    }
+    assert_eq!(
+        pair.timeline(),
+        vec![
+            (0, CONFIRMED),
+            (1, CONFIRMED),
+            (2, CONFIRMED),
+            (3, PREDICTED),
+            (4, PREDICTED),
+            (5, PREDICTED),
+        ]
+    );
+
+    // The peer plays 1 for frames 3..=6; the host's next advance rolls back
+    // to 3, resimulates 3..=5 and simulates 6 with confirmed inputs.
+    for _ in 0..4 {
+        pair.advance_peer(1)?;
+    }
+    pair.advance_host()?;
+    assert_eq!(
+        pair.timeline(),
+        vec![
+            (0, CONFIRMED),
+            (1, CONFIRMED),
+            (2, CONFIRMED),
+            (3, PREDICTED | RESIMULATED | ROLLBACK_TARGET),
+            (4, PREDICTED | RESIMULATED),
+            (5, PREDICTED | RESIMULATED),
+            (6, CONFIRMED),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn timeline_keeps_the_most_recent_frames_and_is_off_by_default() -> Result<(), FortressError> {
+    let mut pair = Pair::new(Some(4))?;
+    let mut disabled = Pair::new(None)?;
+    for _ in 0..10 {
+        pair.confirmed_round()?;
+        disabled.confirmed_round()?;
+    }
+    assert_eq!(
+        pair.timeline(),
+        vec![
+            (6, CONFIRMED),
+            (7, CONFIRMED),
+            (8, CONFIRMED),
+            (9, CONFIRMED)
+        ]
+    );
+    assert!(disabled.timeline().is_empty());
+    Ok(())
+}
+
+#[test]
+fn frame_timeline_capacity_must_be_in_range() {
+    for capacity in [0, 65_537] {
+        let result = SessionBuilder::<StubConfig>::new().with_frame_timeline(capacity);
+        assert!(
+            matches!(
+                result,
+                Err(FortressError::InvalidRequestStructured {
+                    kind: InvalidRequestKind::ConfigValueOutOfRange {
+                        field: "frame_timeline",
+                        ..
+                    }
+                })
+            ),
+            "capacity {capacity}"
+        );
+    }
+    assert!(SessionBuilder::<StubConfig>::new()
+        .with_frame_timeline(65_536)
+        .is_ok());
+}
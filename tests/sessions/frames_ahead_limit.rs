@@ -0,0 +1,208 @@
+//! Frames-ahead hard limit.
+//!
+//! Two players run a loopback match under virtual time with
+//! `TimeSyncConfig::max_frames_ahead` set. The slow player advances on every
+//! other step only, but polls on every one, and the fast player ignores wait
+//! recommendations. Covers:
+//! - The fast player refusing to advance with `AheadOfRemotes` once it is
+//!   exactly the limit ahead, while polling and `add_local_input` still work.
+//! - The fast player advancing again once the slow player catches up.
+//! - A session without a limit running past it.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_channel_mesh, ChannelSocket, TestClock};
+use fortress_rollback::{
+    FortressError, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+    SessionState, TimeSyncConfig,
+};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+const STEP: Duration = Duration::from_millis(16);
+const LIMIT: u32 = 4;
+const FAST: usize = 0;
+const SLOW: usize = 1;
+
+fn player(
+    local: usize,
+    addrs: &[SocketAddr],
+    socket: ChannelSocket,
+    clock: &TestClock,
+    max_frames_ahead: Option<u32>,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_fps(60)?
+        .with_max_prediction_window(8)
+        .with_time_sync_config(TimeSyncConfig {
+            max_frames_ahead,
+            ..TimeSyncConfig::default()
+        })
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::deterministic(60 + local as u64)
+        });
+    for (handle, addr) in addrs.iter().enumerate() {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(*addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+struct Match {
+    clock: TestClock,
+    players: [P2PSession<StubConfig>; 2],
+    games: [GameStub; 2],
+}
+
+impl Match {
+    fn new(max_frames_ahead: Option<u32>) -> Self {
+        let clock = TestClock::new();
+        let (sockets, addrs) = create_channel_mesh(2);
+        let mut sockets = sockets.into_iter();
+        let players = [
+            player(
+                FAST,
+                &addrs,
+                sockets.next().unwrap(),
+                &clock,
+                max_frames_ahead,
+            )
+            .unwrap(),
+            player(
+                SLOW,
+                &addrs,
+                sockets.next().unwrap(),
+                &clock,
+                max_frames_ahead,
+            )
+            .unwrap(),
+        ];
+        let mut running = Self {
+            clock,
+            players,
+            games: [GameStub::new(), GameStub::new()],
+        };
+        for _ in 0..200 {
+            if running
+                .players
+                .iter()
+                .all(|session| session.current_state() == SessionState::Running)
+            {
+                return running;
+            }
+            running.clock.advance(STEP);
+            for session in &mut running.players {
+                session.poll_remote_clients();
+            }
+        }
+        panic!("the players never synchronized");
+    }
+
+    /// Adds input for and advances `handle` once, returning the error if it
+    /// refused to advance.
+    fn advance(&mut self, handle: usize) -> Option<FortressError> {
+        let session = &mut self.players[handle];
+        session
+            .add_local_input(PlayerHandle::new(handle), StubInput { inp: 1 })
+            .unwrap();
+        match session.advance_frame() {
+            Ok(requests) => {
+                self.games[handle].handle_requests(requests);
+                None
+            },
+            Err(err) => Some(err),
+        }
+    }
+
+    /// Lets both players answer each other's messages within the step, so
+    /// the measured round trip stays under a frame.
+    fn poll(&mut self) {
+        for session in &mut self.players {
+            session.poll_remote_clients();
+        }
+    }
+
+    fn lead(&self) -> i32 {
+        self.players[FAST].current_frame() - self.players[SLOW].current_frame()
+    }
+}
+
+#[test]
+fn the_fast_player_stops_at_the_limit_and_resumes_when_the_slow_one_catches_up() {
+    let mut running = Match::new(Some(LIMIT));
+
+    // The slow player advances on every other step only.
+    let mut refusals = 0;
+    let mut highest_lead = 0;
+    for step in 0..120 {
+        running.clock.advance(STEP);
+        if let Some(err) = running.advance(FAST) {
+            assert_eq!(
+                err,
+                FortressError::AheadOfRemotes {
+                    frames_ahead: LIMIT + 1,
+                    limit: LIMIT,
+                }
+            );
+            assert_eq!(running.lead(), LIMIT as i32);
+            refusals += 1;
+        }
+        if step % 2 == 0 {
+            assert_eq!(running.advance(SLOW), None);
+        }
+        running.poll();
+        highest_lead = highest_lead.max(running.lead());
+    }
+    assert!(refusals > 30, "only {refusals} refusals");
+    assert_eq!(highest_lead, LIMIT as i32);
+
+    // The slow player catches up, advancing twice per step.
+    let mut caught_up_at = None;
+    for step in 0..40 {
+        running.clock.advance(STEP);
+        let refused = running.advance(FAST);
+        if caught_up_at.is_some() {
+            assert_eq!(refused, None, "refused again at catch-up step {step}");
+        }
+        for _ in 0..2 {
+            if running.lead() > 0 {
+                assert_eq!(running.advance(SLOW), None);
+            }
+        }
+        running.poll();
+        if caught_up_at.is_none() && running.lead() <= 0 {
+            caught_up_at = Some(step);
+        }
+    }
+    assert!(caught_up_at.is_some(), "the slow player never caught up");
+    assert!(running.lead() <= 1);
+    assert!(running.games[FAST].gs.frame > 0);
+}
+
+#[test]
+fn without_a_limit_the_fast_player_runs_further_ahead() {
+    let mut running = Match::new(None);
+    let mut highest_lead = 0;
+    for step in 0..60 {
+        running.clock.advance(STEP);
+        assert_eq!(running.advance(FAST), None);
+        if step % 2 == 0 {
+            assert_eq!(running.advance(SLOW), None);
+        }
+        running.poll();
+        highest_lead = highest_lead.max(running.lead());
+    }
+    assert!(highest_lead > LIMIT as i32, "highest lead {highest_lead}");
+}
@@ -32,8 +32,8 @@
 };
 use fortress_rollback::{
     DesyncDetection, DisconnectBehavior, FortressError, FortressEvent, FortressRequest, Frame,
-    InputStatus, InputVec, Message, NonBlockingSocket, P2PSession, PlayerHandle, PlayerType,
-    ProtocolConfig, SessionBuilder, SessionState,
+    InputStatus, InputVec, InvalidRequestKind, Message, NonBlockingSocket, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SessionState,
 };
 
 /// Local mirror of the crate-private `DEFAULT_HOT_JOIN_SERVE_TIMEOUT_POLLS`
@@ -359,7 +359,7 @@ fn add_reserved_player_without_join_keeps_running() -> Result<(), FortressError>
         host.add_local_input(PlayerHandle::new(0), StubInput { inp: i })?;
         let requests = host.advance_frame()?;
         for request in &*requests {
-            if let FortressRequest::AdvanceFrame { inputs } = request {
+            if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                 let inputs: &InputVec<StubInput> = inputs;
                 if let Some(&(input, status)) = inputs.get(1) {
                     observed_reserved.push((input.inp, status));
@@ -1261,7 +1261,7 @@ fn hot_join_abandoned_join_does_not_kill_host() -> Result<(), FortressError> {
         host.add_local_input(PlayerHandle::new(0), StubInput { inp: 5 })?;
         let requests = host.advance_frame()?;
         for request in &*requests {
-            if let FortressRequest::AdvanceFrame { inputs } = request {
+            if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                 let inputs: &InputVec<StubInput> = inputs;
                 if let Some(&(input, status)) = inputs.get(1) {
                     observed_reserved.push((input.inp, status));
@@ -1656,7 +1656,7 @@ fn hot_join_phase4_serve_timeout_keeps_slot_reserved() -> Result<(), FortressErr
         host.add_local_input(PlayerHandle::new(0), StubInput { inp: 5 })?;
         let requests = host.advance_frame()?;
         for request in &*requests {
-            if let FortressRequest::AdvanceFrame { inputs } = request {
+            if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                 let inputs: &InputVec<StubInput> = inputs;
                 if let Some(&(input, status)) = inputs.get(1) {
                     observed_reserved.push((input.inp, status));
@@ -2376,7 +2376,7 @@ fn hot_join_reserved_disconnect_during_serve_keeps_host_running() -> Result<(),
     let requests = host.advance_frame()?;
     let mut reserved_status = None;
     for request in &*requests {
-        if let FortressRequest::AdvanceFrame { inputs } = request {
+        if let FortressRequest::AdvanceFrame { inputs, .. } = request {
             let inputs: &InputVec<StubInput> = inputs;
             if let Some(&(_, status)) = inputs.get(1) {
                 reserved_status = Some(status);
@@ -4388,3 +4388,232 @@ fn hot_join_host_with_spectator_constructs_ok() -> Result<(), FortressError> {
 
     Ok(())
 }
+
+// ============================================================================
+// Spectator promotion
+// ============================================================================
+
+/// A spectator takes over the slot of a player who left mid-match.
+///
+/// Host (player 0) and peer (player 1) play with one spectator; the host then
+/// removes player 1 and promotes the spectator into its slot. The spectator's
+/// machine replaces its spectator session with a hot-join joiner on the same
+/// address, which must load the snapshot at exactly the activation frame the
+/// host returned. Host and peer inputs are always even and the promoted
+/// player's always odd, so `StateStub` steps `+2` per frame while the slot
+/// holds dummy inputs and `-1` once the promoted inputs count: the step flip
+/// at the activation frame, on both machines, proves the inputs count from
+/// that frame and nowhere earlier.
+#[test]
+fn promoted_spectator_inputs_count_from_the_activation_frame() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let bus = RoutingBus::new();
+    let host_addr: std::net::SocketAddr = ([127, 0, 0, 1], 20101).into();
+    let peer_addr: std::net::SocketAddr = ([127, 0, 0, 1], 20102).into();
+    let spectator_addr: std::net::SocketAddr = ([127, 0, 0, 1], 20103).into();
+
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .with_num_players(2)?
+        .with_hot_join(true)
+        .with_disconnect_behavior(DisconnectBehavior::ContinueWithout)
+        .with_desync_detection_mode(DesyncDetection::On { interval: 2 })
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(peer_addr), PlayerHandle::new(1))?
+        .add_player(PlayerType::Spectator(spectator_addr), PlayerHandle::new(2))?
+        .start_p2p_session(bus.socket(host_addr))?;
+    let mut peer = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .with_num_players(2)?
+        .with_desync_detection_mode(DesyncDetection::On { interval: 2 })
+        .add_player(PlayerType::Remote(host_addr), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(bus.socket(peer_addr))?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .with_num_players(2)?
+        .start_spectator_session(host_addr, bus.socket(spectator_addr))
+        .expect("spectator session should build");
+
+    for _ in 0..200 {
+        host.poll_remote_clients();
+        peer.poll_remote_clients();
+        spectator.poll_remote_clients();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        if host.current_state() == SessionState::Running
+            && peer.current_state() == SessionState::Running
+            && spectator.current_state() == SessionState::Running
+        {
+            break;
+        }
+    }
+    assert_eq!(host.current_state(), SessionState::Running);
+    assert_eq!(peer.current_state(), SessionState::Running);
+    assert_eq!(spectator.current_state(), SessionState::Running);
+
+    // Promotion is refused while player 1 is still connected.
+    assert!(matches!(
+        host.promote_spectator(PlayerHandle::new(2), PlayerHandle::new(1)),
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::PlayerNotDisconnected { .. }
+        })
+    ));
+
+    let mut host_stub = GameStub::new();
+    let mut peer_stub = GameStub::new();
+    let mut host_states: BTreeMap<i32, StateStub> = BTreeMap::new();
+    let mut peer_states: BTreeMap<i32, StateStub> = BTreeMap::new();
+    for i in 0..10_u32 {
+        host.poll_remote_clients();
+        peer.poll_remote_clients();
+        spectator.poll_remote_clients();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        advance_and_record(
+            &mut host,
+            &mut host_stub,
+            PlayerHandle::new(0),
+            100 + 2 * i,
+            &mut host_states,
+        )?;
+        advance_and_record(
+            &mut peer,
+            &mut peer_stub,
+            PlayerHandle::new(1),
+            200 + 2 * i,
+            &mut peer_states,
+        )?;
+    }
+
+    // Player 1 leaves; the host keeps running with the slot frozen.
+    host.remove_player(PlayerHandle::new(1))?;
+    drop(peer);
+    for _ in 0..6 {
+        host.poll_remote_clients();
+        spectator.poll_remote_clients();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        advance_and_record(
+            &mut host,
+            &mut host_stub,
+            PlayerHandle::new(0),
+            300,
+            &mut host_states,
+        )?;
+    }
+
+    let activation = host.promote_spectator(PlayerHandle::new(2), PlayerHandle::new(1))?;
+    assert_eq!(activation, host.current_frame() - 1);
+    assert_eq!(host.num_spectators(), 0);
+    assert!(!host.is_spectator_handle(PlayerHandle::new(2)));
+    assert!(drain_events(&mut host).iter().any(|e| matches!(
+        e,
+        FortressEvent::JoinRequested { handle, addr }
+            if *handle == PlayerHandle::new(1) && *addr == spectator_addr
+    )));
+
+    // The spectator's machine swaps its spectator session for a joiner.
+    drop(spectator);
+    let mut joiner = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .with_num_players(2)?
+        .with_desync_detection_mode(DesyncDetection::On { interval: 2 })
+        .add_player(PlayerType::Remote(host_addr), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_hot_join_session(bus.socket(spectator_addr), host_addr)?;
+    let mut joiner_stub = GameStub::new();
+    let mut joiner_states: BTreeMap<i32, StateStub> = BTreeMap::new();
+    let mut host_peer_joined = false;
+    let mut host_disconnected = false;
+    let snapshot = drive_until_joiner_loads_snapshot(
+        &mut host,
+        &mut joiner,
+        &mut host_stub,
+        &mut joiner_stub,
+        &mut host_states,
+        &mut joiner_states,
+        &clock,
+        PlayerHandle::new(0),
+        &mut host_peer_joined,
+        &mut host_disconnected,
+    )?;
+    assert_eq!(snapshot, activation);
+
+    let mut events = Vec::new();
+    for i in 0..20_u32 {
+        for _ in 0..3 {
+            host.poll_remote_clients();
+            events.extend(drain_events(&mut host));
+            joiner.poll_remote_clients();
+            events.extend(drain_events(&mut joiner));
+            clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        }
+        advance_and_record(
+            &mut host,
+            &mut host_stub,
+            PlayerHandle::new(0),
+            400 + 2 * i,
+            &mut host_states,
+        )?;
+        advance_and_record(
+            &mut joiner,
+            &mut joiner_stub,
+            PlayerHandle::new(1),
+            501 + 2 * i,
+            &mut joiner_states,
+        )?;
+    }
+    for _ in 0..60 {
+        host.poll_remote_clients();
+        events.extend(drain_events(&mut host));
+        joiner.poll_remote_clients();
+        events.extend(drain_events(&mut joiner));
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    assert!(
+        host_peer_joined
+            || events
+                .iter()
+                .any(|e| matches!(e, FortressEvent::PeerJoined { .. }))
+    );
+    assert!(
+        !events.iter().any(|e| matches!(
+            e,
+            FortressEvent::DesyncDetected { .. } | FortressEvent::Disconnected { .. }
+        )),
+        "{events:?}"
+    );
+    assert!(
+        host.last_verified_frame()
+            .expect("host verified a checksum")
+            > activation
+    );
+    assert!(
+        joiner
+            .last_verified_frame()
+            .expect("joiner verified a checksum")
+            > activation
+    );
+
+    let confirmed = std::cmp::min(host.confirmed_frame(), joiner.confirmed_frame()).as_i32();
+    let f = activation.as_i32();
+    assert!(confirmed > f + 5, "confirmed {confirmed}, activation {f}");
+    for frame in f..=confirmed {
+        assert_eq!(
+            host_states.get(&frame),
+            joiner_states.get(&frame),
+            "host and joiner differ at frame {frame}"
+        );
+    }
+    // `states[k + 1]` folds frame k's inputs: dummy (even) before the
+    // activation frame, the promoted player's odd input from it on.
+    let step = |states: &BTreeMap<i32, StateStub>, frame: i32| {
+        states[&(frame + 1)].state - states[&frame].state
+    };
+    for frame in f - 3..f {
+        assert_eq!(step(&host_states, frame), 2, "frame {frame}");
+    }
+    for frame in f..confirmed {
+        assert_eq!(step(&host_states, frame), -1, "frame {frame}");
+        assert_eq!(step(&joiner_states, frame), -1, "frame {frame}");
+    }
+    Ok(())
+}
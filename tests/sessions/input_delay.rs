@@ -11,9 +11,14 @@
 //!   desyncs, or sequence violations.
 //! - Stress: a large mid-session delta (0 -> 8) followed by 12 more frames.
 //! - Mid-session **decrease** of input delay returns
-//!   `InputDelayDecreaseUnsupported`.
+//!   `InputDelayDecreaseUnsupported` with a remote peer or a spectator; with
+//!   only local players, the newest queued inputs give way to the next ones.
 //! - Mid-session increase with multiple local players returns
 //!   `InputDelayMidSessionMultiLocalUnsupported`.
+//! - A delay must stay below the prediction window, at build time and at
+//!   runtime, unless the session opts in with
+//!   `with_input_delay_beyond_prediction`; lockstep sessions are exempt.
+//! - An opted-in delay of 10 with a window of 8 keeps both peers in sync.
 //! - `Display` format for `FortressEvent::InputDelayRecommendation`.
 
 // In tests: panic/unwrap/expect/etc. are appropriate.
@@ -26,12 +31,13 @@
 
 use crate::common::stubs::{GameStub, StubConfig, StubInput};
 use crate::common::{
-    create_channel_pair, create_chaos_channel_pair, create_unconnected_socket, poll_with_advance,
-    synchronize_sessions_deterministic, SyncConfig, TestClock,
+    assert_spectator_synchronized, calculate_hash, create_channel_pair, create_chaos_channel_pair,
+    create_unconnected_socket, poll_with_advance, synchronize_sessions_deterministic,
+    synchronize_spectator_deterministic, SyncConfig, TestClock,
 };
 use fortress_rollback::{
-    ChaosConfig, FortressError, FortressEvent, InvalidRequestKind, PlayerHandle, PlayerType,
-    ProtocolConfig, SessionBuilder, SessionState,
+    ChaosConfig, DesyncDetection, FortressError, FortressEvent, FortressRequest,
+    InvalidRequestKind, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SessionState,
 };
 use std::net::SocketAddr;
 use web_time::Duration;
@@ -345,7 +351,8 @@ fn p2p_set_input_delay_mid_session_increase_succeeds() -> Result<(), FortressErr
 }
 
 /// Stress test: transition from delay=0 to a large delay (8) mid-session and
-/// drive 12 more frames, verifying both peers stay consistent.
+/// drive 12 more frames, verifying both peers stay consistent. 8 is the
+/// default prediction window, so session 1 opts in to such delays.
 #[test]
 fn p2p_set_input_delay_mid_session_increase_works_with_large_delta() -> Result<(), FortressError> {
     let clock = TestClock::new();
@@ -356,6 +363,7 @@ fn p2p_set_input_delay_mid_session_increase_works_with_large_delta() -> Result<(
         .add_player(PlayerType::Local, PlayerHandle::new(0))?
         .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
         .with_input_delay(0)?
+        .with_input_delay_beyond_prediction(true)
         .start_p2p_session(s1)?;
 
     let mut sess2 = SessionBuilder::<StubConfig>::new()
@@ -637,6 +645,88 @@ fn p2p_set_input_delay_mid_session_decrease_returns_error() -> Result<(), Fortre
     Ok(())
 }
 
+/// With only local players nothing has been sent, so a mid-session decrease
+/// is allowed: the inputs queued for the frames the smaller delay overlaps
+/// are dropped and the next inputs take those frames.
+#[test]
+fn p2p_set_input_delay_mid_session_decrease_with_only_local_players() -> Result<(), FortressError> {
+    let (socket, _addr) = create_unconnected_socket(11010);
+    let mut sess = SessionBuilder::<StubConfig>::new()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .with_input_delay(3)?
+        .start_p2p_session(socket)?;
+
+    let mut simulated = Vec::new();
+    for frame in 0..12u32 {
+        if frame == 5 {
+            sess.set_input_delay(PlayerHandle::new(0), 1)?;
+            assert_eq!(sess.input_delay(PlayerHandle::new(0))?, 1);
+        }
+        sess.add_local_input(PlayerHandle::new(0), StubInput { inp: frame + 1 })?;
+        sess.add_local_input(PlayerHandle::new(1), StubInput { inp: 0 })?;
+        for request in sess.advance_frame()? {
+            if let FortressRequest::AdvanceFrame { inputs, .. } = request {
+                simulated.push(inputs[0].0.inp);
+            }
+        }
+    }
+
+    // Frames 0-2 hold the default input and frames 3-5 the inputs added with
+    // delay 3. The inputs queued for frames 6 and 7 were dropped: from frame
+    // 6 on, each frame holds the input added one frame earlier.
+    assert_eq!(simulated, vec![0, 0, 0, 1, 2, 3, 6, 7, 8, 9, 10, 11]);
+    Ok(())
+}
+
+/// A spectator has already been streamed the confirmed inputs of an
+/// all-local session, so a decrease is refused there as well.
+#[test]
+fn p2p_set_input_delay_mid_session_decrease_with_a_spectator_returns_error(
+) -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(2))?
+        .with_input_delay(3)?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let sync_result = synchronize_spectator_deterministic(&mut spectator, &mut host, &clock);
+    assert_spectator_synchronized(&spectator, &host, &sync_result);
+
+    let mut stub = GameStub::new();
+    for frame in 0..5u32 {
+        host.add_local_input(PlayerHandle::new(0), StubInput { inp: frame + 1 })?;
+        host.add_local_input(PlayerHandle::new(1), StubInput { inp: 0 })?;
+        stub.handle_requests(host.advance_frame()?);
+        host.poll_remote_clients();
+    }
+
+    let err = host
+        .set_input_delay(PlayerHandle::new(0), 1)
+        .expect_err("decreasing input delay with a spectator should return an error");
+    assert!(
+        matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::InputDelayDecreaseUnsupported {
+                    current: 3,
+                    requested: 1,
+                }
+            }
+        ),
+        "expected InputDelayDecreaseUnsupported error, got {err:?}"
+    );
+    assert_eq!(host.input_delay(PlayerHandle::new(0))?, 3);
+    Ok(())
+}
+
 /// Multi-local mid-session input-delay increases must be rejected with
 /// `InputDelayMidSessionMultiLocalUnsupported` so we never silently emit
 /// inconsistent gap-fill bytes for the unchanged local players. We use a
@@ -712,6 +802,130 @@ fn p2p_set_input_delay_mid_session_multi_local_returns_error() -> Result<(), For
     Ok(())
 }
 
+/// A one-local, one-remote builder with the given window and delay.
+fn windowed_builder(
+    clock: &TestClock,
+    remote: SocketAddr,
+    max_prediction: usize,
+    delay: usize,
+) -> Result<SessionBuilder<StubConfig>, FortressError> {
+    SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(clock))
+        .with_max_prediction_window(max_prediction)
+        .with_input_delay(delay)?
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(remote), PlayerHandle::new(1))
+}
+
+#[test]
+fn input_delay_must_stay_below_prediction_window() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let remote: SocketAddr = ([127, 0, 0, 1], 11101).into();
+
+    let (socket, _) = create_unconnected_socket(11100);
+    windowed_builder(&clock, remote, 8, 7)?.start_p2p_session(socket)?;
+
+    let (socket, _) = create_unconnected_socket(11102);
+    let err = windowed_builder(&clock, remote, 8, 8)?
+        .start_p2p_session(socket)
+        .expect_err("a delay equal to the window must be rejected");
+    assert!(
+        matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::InputDelayBeyondPrediction {
+                    delay: 8,
+                    max_prediction: 8,
+                }
+            }
+        ),
+        "expected InputDelayBeyondPrediction, got {err:?}"
+    );
+
+    // Opting in, or running lockstep, accepts any delay the queue fits.
+    let (socket, _) = create_unconnected_socket(11103);
+    windowed_builder(&clock, remote, 8, 10)?
+        .with_input_delay_beyond_prediction(true)
+        .start_p2p_session(socket)?;
+    let (socket, _) = create_unconnected_socket(11104);
+    windowed_builder(&clock, remote, 0, 10)?.start_p2p_session(socket)?;
+    Ok(())
+}
+
+#[test]
+fn p2p_set_input_delay_must_stay_below_prediction_window() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let remote: SocketAddr = ([127, 0, 0, 1], 11111).into();
+    let (socket, _) = create_unconnected_socket(11110);
+    let mut sess = windowed_builder(&clock, remote, 8, 0)?.start_p2p_session(socket)?;
+
+    sess.set_input_delay(PlayerHandle::new(0), 7)?;
+    let err = sess
+        .set_input_delay(PlayerHandle::new(0), 8)
+        .expect_err("a delay equal to the window must be rejected");
+    assert!(
+        matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::InputDelayBeyondPrediction {
+                    delay: 8,
+                    max_prediction: 8,
+                }
+            }
+        ),
+        "expected InputDelayBeyondPrediction, got {err:?}"
+    );
+    assert_eq!(sess.input_delay(PlayerHandle::new(0))?, 7);
+    Ok(())
+}
+
+/// Both peers run a delay of 10 with a prediction window of 8. Local inputs
+/// are confirmed as soon as they are queued, so the session never predicts
+/// them and both peers reach the same state with matching checksums.
+#[test]
+fn input_delay_beyond_prediction_window_stays_in_sync() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let builder = || -> Result<SessionBuilder<StubConfig>, FortressError> {
+        SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(protocol_config(&clock))
+            .with_desync_detection_mode(DesyncDetection::On { interval: 1 })
+            .with_max_prediction_window(8)
+            .with_input_delay(10)
+            .map(|builder| builder.with_input_delay_beyond_prediction(true))
+    };
+    let mut sess1 = builder()?
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = builder()?
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("Sessions should synchronize");
+
+    let mut stub1 = GameStub::new();
+    let mut stub2 = GameStub::new();
+    drive_frames(
+        &mut sess1,
+        &mut sess2,
+        &mut stub1,
+        &mut stub2,
+        &clock,
+        40,
+        |i| StubInput { inp: i % 7 },
+        |i| StubInput { inp: i % 5 },
+        "delay=10/window=8",
+    );
+
+    assert_eq!(sess1.current_frame(), sess2.current_frame());
+    assert_eq!(calculate_hash(&stub1.gs), calculate_hash(&stub2.gs));
+    assert!(sess1.confirmed_frame().as_i32() >= 30);
+    assert!(sess2.confirmed_frame().as_i32() >= 30);
+    Ok(())
+}
+
 #[test]
 fn input_delay_recommendation_event_display() {
     let event: FortressEvent<StubConfig> = FortressEvent::InputDelayRecommendation {
@@ -0,0 +1,145 @@
+//! Integration tests for `InputQueueConfig::recommended`.
+//!
+//! Covers:
+//! - Two peers on a recommended-size queue survive one peer stalling for the
+//!   full stall tolerance while the other runs into the prediction threshold:
+//!   no spec violations (the queue never discards a needed input) and no
+//!   desync once both resume.
+//!
+//! The build-time minimum (`QueueLengthBelowMinimum`) is unit-tested in
+//! `sessions::builder`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::sync::Arc;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, drain_sync_events, poll_with_advance, synchronize_sessions_deterministic,
+    SyncConfig, TestClock,
+};
+use fortress_rollback::telemetry::CollectingObserver;
+use fortress_rollback::{
+    DesyncDetection, FortressError, FortressEvent, InputQueueConfig, P2PSession, PlayerHandle,
+    PlayerType, ProtocolConfig, SessionBuilder,
+};
+
+const MAX_PREDICTION: usize = 8;
+const INPUT_DELAY: usize = 2;
+const STALL_FRAMES: usize = 30;
+
+fn sessions(
+    observer: &Arc<CollectingObserver>,
+) -> Result<(TestClock, P2PSession<StubConfig>, P2PSession<StubConfig>), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let builder = || {
+        SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            })
+            .with_input_queue_config(InputQueueConfig::recommended(
+                MAX_PREDICTION,
+                INPUT_DELAY,
+                STALL_FRAMES,
+            ))
+            .with_max_prediction_window(MAX_PREDICTION)
+            .with_desync_detection_mode(DesyncDetection::On { interval: 1 })
+            .with_violation_observer(observer.clone())
+            .with_input_delay(INPUT_DELAY)
+    };
+    let mut sess1 = builder()?
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = builder()?
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+    Ok((clock, sess1, sess2))
+}
+
+/// Adds an input and advances; `false` if the session held its frame at the
+/// prediction threshold.
+fn step(
+    session: &mut P2PSession<StubConfig>,
+    stub: &mut GameStub,
+    handle: usize,
+) -> Result<bool, FortressError> {
+    let frame = session.current_frame();
+    let inp = frame.as_i32() as u32;
+    session.add_local_input(PlayerHandle::new(handle), StubInput { inp })?;
+    match session.advance_frame() {
+        Ok(requests) => stub.handle_requests(requests),
+        Err(FortressError::PredictionThreshold) => {},
+        Err(err) => return Err(err),
+    }
+    Ok(session.current_frame() != frame)
+}
+
+fn assert_no_desync(session: &mut P2PSession<StubConfig>) {
+    for event in session.events() {
+        assert!(
+            !matches!(event, FortressEvent::DesyncDetected { .. }),
+            "unexpected {event:?}"
+        );
+    }
+}
+
+#[test]
+fn recommended_queue_survives_a_stall_without_discards() -> Result<(), FortressError> {
+    let observer = Arc::new(CollectingObserver::new());
+    let (clock, mut sess1, mut sess2) = sessions(&observer)?;
+    let (mut stub1, mut stub2) = (GameStub::new(), GameStub::new());
+
+    for _ in 0..20 {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        assert!(step(&mut sess1, &mut stub1, 0)?);
+        assert!(step(&mut sess2, &mut stub2, 1)?);
+    }
+
+    // Peer 2 freezes: no polling, no sending. Peer 1 keeps ticking until it
+    // stalls at the prediction threshold, then keeps polling.
+    let stall_start = sess1.current_frame();
+    let mut stalled = false;
+    for _ in 0..STALL_FRAMES {
+        clock.advance(std::time::Duration::from_millis(16));
+        sess1.poll_remote_clients();
+        if !step(&mut sess1, &mut stub1, 0)? {
+            stalled = true;
+        }
+    }
+    assert!(stalled, "peer 1 should reach the prediction threshold");
+    assert!(sess1.current_frame() - stall_start <= (MAX_PREDICTION + INPUT_DELAY) as i32);
+
+    // Both resume; peer 2 catches up and the game runs on.
+    for _ in 0..(STALL_FRAMES + 60) {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        step(&mut sess1, &mut stub1, 0)?;
+        step(&mut sess2, &mut stub2, 1)?;
+    }
+    for _ in 0..10 {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+    }
+
+    assert!(sess1.confirmed_frame().as_i32() > 60);
+    assert!(sess2.confirmed_frame().as_i32() > 60);
+    assert_no_desync(&mut sess1);
+    assert_no_desync(&mut sess2);
+    assert!(
+        observer.is_empty(),
+        "violations: {:?}",
+        observer.violations()
+    );
+    Ok(())
+}
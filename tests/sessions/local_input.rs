@@ -0,0 +1,133 @@
+//! Integration tests for `P2PSession::add_local_input_with_frame`.
+//!
+//! Covers:
+//! - The returned frame is `current_frame() + input_delay` for delays 0 and 4.
+//! - The input reaches `AdvanceFrame` at exactly that frame on both peers.
+//!
+//! Rejection (`FortressError::LocalInputRejected`) is unit-tested in
+//! `sync_layer`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, drain_sync_events, poll_with_advance, synchronize_sessions_deterministic,
+    SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    FortressError, FortressRequest, Frame, InputStatus, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, RequestVec, SessionBuilder,
+};
+
+const FRAMES: i32 = 20;
+
+fn sessions(
+    delay: usize,
+) -> Result<(TestClock, P2PSession<StubConfig>, P2PSession<StubConfig>), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let builder = || {
+        SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            })
+            .with_input_delay(delay)
+    };
+    let mut sess1 = builder()?
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = builder()?
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+    Ok((clock, sess1, sess2))
+}
+
+/// Records player 0's input of every confirmed `AdvanceFrame`, keyed by the
+/// frame it advances.
+fn record_confirmed(
+    requests: &RequestVec<StubConfig>,
+    mut frame: Frame,
+    seen: &mut BTreeMap<i32, u32>,
+) {
+    for request in requests {
+        match request {
+            FortressRequest::LoadGameState { frame: loaded, .. } => frame = *loaded,
+            FortressRequest::AdvanceFrame { inputs, .. } => {
+                if inputs[0].1 == InputStatus::Confirmed {
+                    seen.insert(frame.as_i32(), inputs[0].0.inp);
+                }
+                frame += 1;
+            },
+            FortressRequest::SaveGameState { .. } => {},
+        }
+    }
+}
+
+fn input_lands_at_the_returned_frame(delay: usize) -> Result<(), FortressError> {
+    let (clock, mut sess1, mut sess2) = sessions(delay)?;
+    let (mut stub1, mut stub2) = (GameStub::new(), GameStub::new());
+    let (mut seen1, mut seen2) = (BTreeMap::new(), BTreeMap::new());
+    let mut expected = BTreeMap::new();
+
+    for step in 0..FRAMES {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        let current = sess1.current_frame();
+        let inp = 100 + step as u32;
+        let applies_to =
+            sess1.add_local_input_with_frame(PlayerHandle::new(0), StubInput { inp })?;
+        assert_eq!(applies_to, current + delay as i32, "step {step}");
+        expected.insert(applies_to.as_i32(), inp);
+
+        let requests = sess1.advance_frame()?;
+        record_confirmed(&requests, current, &mut seen1);
+        stub1.handle_requests(requests);
+
+        let current = sess2.current_frame();
+        sess2.add_local_input(PlayerHandle::new(1), StubInput { inp: 0 })?;
+        let requests = sess2.advance_frame()?;
+        record_confirmed(&requests, current, &mut seen2);
+        stub2.handle_requests(requests);
+    }
+
+    // Frames before the delay run on the blank inputs the queue starts with.
+    for frame in 0..delay as i32 {
+        assert_eq!(seen1.get(&frame), Some(&0), "frame {frame}");
+    }
+    for (frame, inp) in &expected {
+        if let Some(seen) = seen1.get(frame) {
+            assert_eq!(seen, inp, "local peer, frame {frame}");
+        }
+        if let Some(seen) = seen2.get(frame) {
+            assert_eq!(seen, inp, "remote peer, frame {frame}");
+        }
+    }
+    assert!(
+        seen2.keys().any(|frame| *frame >= delay as i32),
+        "the remote peer should confirm some delayed inputs"
+    );
+    Ok(())
+}
+
+#[test]
+fn add_local_input_with_frame_without_delay() -> Result<(), FortressError> {
+    input_lands_at_the_returned_frame(0)
+}
+
+#[test]
+fn add_local_input_with_frame_with_delay_four() -> Result<(), FortressError> {
+    input_lands_at_the_returned_frame(4)
+}
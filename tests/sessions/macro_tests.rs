@@ -70,8 +70,10 @@ fn test_handle_requests_advance() {
         (MacroTestInput(2), fortress_rollback::InputStatus::Confirmed),
     ]);
 
-    let requests: Vec<FortressRequest<MacroTestConfig>> =
-        vec![FortressRequest::AdvanceFrame { inputs }];
+    let requests: Vec<FortressRequest<MacroTestConfig>> = vec![FortressRequest::AdvanceFrame {
+        inputs,
+        previous_inputs: SmallVec::new(),
+    }];
 
     handle_requests!(
         requests,
@@ -107,18 +109,21 @@ fn test_handle_requests_multiple_advances() {
                 MacroTestInput(1),
                 fortress_rollback::InputStatus::Confirmed,
             )]),
+            previous_inputs: SmallVec::new(),
         },
         FortressRequest::AdvanceFrame {
             inputs: SmallVec::from_vec(vec![(
                 MacroTestInput(2),
                 fortress_rollback::InputStatus::Confirmed,
             )]),
+            previous_inputs: SmallVec::new(),
         },
         FortressRequest::AdvanceFrame {
             inputs: SmallVec::from_vec(vec![(
                 MacroTestInput(3),
                 fortress_rollback::InputStatus::Confirmed,
             )]),
+            previous_inputs: SmallVec::new(),
         },
     ];
 
@@ -164,9 +169,11 @@ fn test_handle_requests_mutable_capture() {
     let requests: Vec<FortressRequest<MacroTestConfig>> = vec![
         FortressRequest::AdvanceFrame {
             inputs: SmallVec::new(),
+            previous_inputs: SmallVec::new(),
         },
         FortressRequest::AdvanceFrame {
             inputs: SmallVec::new(),
+            previous_inputs: SmallVec::new(),
         },
     ];
 
@@ -195,6 +202,7 @@ fn test_handle_requests_empty_handlers() {
 
     let requests: Vec<FortressRequest<MacroTestConfig>> = vec![FortressRequest::AdvanceFrame {
         inputs: SmallVec::new(),
+        previous_inputs: SmallVec::new(),
     }];
 
     // This is the pattern for lockstep mode where save/load never happen
@@ -227,6 +235,7 @@ fn on_advance(_inputs: InputVec<MacroTestInput>) {
 
     let requests: Vec<FortressRequest<MacroTestConfig>> = vec![FortressRequest::AdvanceFrame {
         inputs: SmallVec::new(),
+        previous_inputs: SmallVec::new(),
     }];
 
     handle_requests!(
@@ -250,9 +259,11 @@ fn test_handle_requests_with_request_vec() {
     let mut requests: RequestVec<MacroTestConfig> = RequestVec::new();
     requests.push(FortressRequest::AdvanceFrame {
         inputs: SmallVec::new(),
+        previous_inputs: SmallVec::new(),
     });
     requests.push(FortressRequest::AdvanceFrame {
         inputs: SmallVec::new(),
+        previous_inputs: SmallVec::new(),
     });
 
     handle_requests!(
@@ -0,0 +1,305 @@
+//! Integration tests for coordinated match pause/resume
+//! (`P2PSession::request_match_pause` / `request_match_resume`).
+//!
+//! Covers:
+//! - Both peers pausing at the same frame, simulating nothing past it while
+//!   paused, resuming from it, and ending in the same states as a run that
+//!   never paused.
+//! - Simultaneous pause requests cancelling each other on both peers, with
+//!   play continuing.
+//! - Request errors and `SessionBuilder::with_match_pause_margin` validation.
+//!
+//! The negotiation itself (holds, timeouts, peer loss) is covered at the unit
+//! level in `sessions::match_pause`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, drain_sync_events, synchronize_sessions_deterministic, SyncConfig,
+    TestClock, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, Frame, InvalidRequestKind, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder,
+};
+
+/// Margin used by both peers, small enough to keep the tests short.
+const MARGIN: usize = 8;
+/// Upper bound on steps any single phase may take.
+const MAX_STEPS: usize = 200;
+
+/// Input of `player` for `frame`; a pure function of the frame so a paused
+/// and an unpaused run feed identical inputs.
+fn input_for(player: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(7 + player as u32) % 11,
+    }
+}
+
+/// One peer: its session, game stub, recorded states, and events seen.
+struct Peer {
+    session: P2PSession<StubConfig>,
+    handle: PlayerHandle,
+    stub: GameStub,
+    states: BTreeMap<i32, StateStub>,
+    events: Vec<FortressEvent<StubConfig>>,
+}
+
+impl Peer {
+    fn step(&mut self) -> Result<(), FortressError> {
+        self.session.poll_remote_clients();
+        let frame = self.session.current_frame();
+        self.session
+            .add_local_input(self.handle, input_for(self.handle.as_usize(), frame))?;
+        let requests = self.session.advance_frame()?;
+        self.stub
+            .handle_requests_recording(requests, &mut self.states);
+        self.events.extend(self.session.events());
+        Ok(())
+    }
+
+    fn paused_frames(&self) -> Vec<Frame> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                FortressEvent::MatchPaused { frame } => Some(*frame),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn resumed_frames(&self) -> Vec<Frame> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                FortressEvent::MatchResumed { frame } => Some(*frame),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn cancellations(&self) -> Vec<(Frame, bool)> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                FortressEvent::MatchPauseCancelled { frame, resume } => Some((*frame, *resume)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+struct Harness {
+    clock: TestClock,
+    peers: [Peer; 2],
+}
+
+impl Harness {
+    fn new() -> Result<Self, FortressError> {
+        let clock = TestClock::new();
+        let (s1, s2, a1, a2) = create_channel_pair();
+        let protocol_config = ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        };
+        let mut sess1 = SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(protocol_config.clone())
+            .with_match_pause_margin(MARGIN)?
+            .add_player(PlayerType::Local, PlayerHandle::new(0))?
+            .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+            .start_p2p_session(s1)?;
+        let mut sess2 = SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(protocol_config)
+            .with_match_pause_margin(MARGIN)?
+            .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+            .add_player(PlayerType::Local, PlayerHandle::new(1))?
+            .start_p2p_session(s2)?;
+        synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+            .expect("sessions should synchronize");
+        drain_sync_events(&mut sess1, &mut sess2);
+
+        let peer = |session, handle| Peer {
+            session,
+            handle: PlayerHandle::new(handle),
+            stub: GameStub::new(),
+            states: BTreeMap::new(),
+            events: Vec::new(),
+        };
+        Ok(Self {
+            clock,
+            peers: [peer(sess1, 0), peer(sess2, 1)],
+        })
+    }
+
+    fn step(&mut self) -> Result<(), FortressError> {
+        for peer in &mut self.peers {
+            peer.step()?;
+        }
+        self.clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        Ok(())
+    }
+
+    fn step_until(
+        &mut self,
+        mut done: impl FnMut(&[Peer; 2]) -> bool,
+    ) -> Result<(), FortressError> {
+        for _ in 0..MAX_STEPS {
+            if done(&self.peers) {
+                return Ok(());
+            }
+            self.step()?;
+        }
+        panic!("condition not reached within {MAX_STEPS} steps");
+    }
+
+    /// Steps until both peers reached `frame` and it is confirmed everywhere.
+    fn run_past(&mut self, frame: i32) -> Result<(), FortressError> {
+        self.step_until(|peers| {
+            peers
+                .iter()
+                .all(|peer| peer.session.confirmed_frame() >= Frame::new(frame))
+        })
+    }
+}
+
+#[test]
+fn pause_and_resume_agree_on_frames_and_match_unpaused_run() -> Result<(), FortressError> {
+    let mut paused = Harness::new()?;
+    for _ in 0..10 {
+        paused.step()?;
+    }
+    paused.peers[0].session.request_match_pause()?;
+    paused.step_until(|peers| peers.iter().all(|peer| peer.session.is_match_paused()))?;
+
+    let pause_frame = paused.peers[0].paused_frames();
+    assert_eq!(pause_frame.len(), 1);
+    assert_eq!(paused.peers[1].paused_frames(), pause_frame);
+    let pause_frame = pause_frame[0];
+    for peer in &paused.peers {
+        assert_eq!(peer.session.current_frame(), pause_frame);
+    }
+
+    // Nothing past the pause frame is simulated while paused.
+    for _ in 0..40 {
+        paused.step()?;
+    }
+    for peer in &paused.peers {
+        assert_eq!(peer.session.current_frame(), pause_frame);
+        assert!(peer
+            .states
+            .keys()
+            .all(|&frame| frame <= pause_frame.as_i32()));
+        assert!(peer.cancellations().is_empty());
+    }
+
+    paused.peers[1].session.request_match_resume()?;
+    paused.step_until(|peers| peers.iter().all(|peer| !peer.resumed_frames().is_empty()))?;
+    for peer in &paused.peers {
+        assert_eq!(peer.resumed_frames(), vec![pause_frame]);
+        assert!(!peer.session.is_match_paused());
+    }
+
+    let end = pause_frame.as_i32() + 30;
+    paused.run_past(end)?;
+
+    let mut control = Harness::new()?;
+    control.run_past(end)?;
+    for frame in 1..=end {
+        let expected = control.peers[0].states[&frame];
+        assert_eq!(control.peers[1].states[&frame], expected, "frame {frame}");
+        for peer in &paused.peers {
+            assert_eq!(peer.states[&frame], expected, "frame {frame}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn simultaneous_pause_requests_cancel_each_other() -> Result<(), FortressError> {
+    let mut harness = Harness::new()?;
+    for _ in 0..10 {
+        harness.step()?;
+    }
+    let proposed: Vec<Frame> = harness
+        .peers
+        .iter()
+        .map(|peer| peer.session.current_frame() + MARGIN as i32)
+        .collect();
+    for peer in &mut harness.peers {
+        peer.session.request_match_pause()?;
+    }
+    harness.step_until(|peers| peers.iter().all(|peer| !peer.cancellations().is_empty()))?;
+    for (peer, frame) in harness.peers.iter().zip(proposed) {
+        assert_eq!(peer.cancellations(), vec![(frame, false)]);
+        assert!(peer.paused_frames().is_empty());
+    }
+
+    // Play continues past both proposed frames, and a later pause succeeds.
+    let past = harness.peers[0].session.current_frame().as_i32() + 2 * MARGIN as i32;
+    harness.run_past(past)?;
+    harness.peers[1].session.request_match_pause()?;
+    harness.step_until(|peers| peers.iter().all(|peer| peer.session.is_match_paused()))?;
+    Ok(())
+}
+
+#[test]
+fn match_pause_requests_follow_session_and_pause_state() -> Result<(), FortressError> {
+    let (s1, _s2, _a1, a2) = create_channel_pair();
+    let mut unsynced = SessionBuilder::<StubConfig>::new()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    assert!(matches!(
+        unsynced.request_match_pause(),
+        Err(FortressError::NotSynchronized)
+    ));
+
+    let mut harness = Harness::new()?;
+    let session = &mut harness.peers[0].session;
+    let invalid = |result: Result<(), FortressError>| match result {
+        Err(FortressError::InvalidRequestStructured { kind }) => kind,
+        other => panic!("expected an invalid request, got {other:?}"),
+    };
+    assert_eq!(
+        invalid(session.request_match_resume()),
+        InvalidRequestKind::MatchNotPaused
+    );
+    session.request_match_pause()?;
+    assert_eq!(
+        invalid(session.request_match_pause()),
+        InvalidRequestKind::MatchPauseInProgress
+    );
+    harness.step_until(|peers| peers.iter().all(|peer| peer.session.is_match_paused()))?;
+    assert_eq!(
+        invalid(harness.peers[1].session.request_match_pause()),
+        InvalidRequestKind::MatchAlreadyPaused
+    );
+    Ok(())
+}
+
+#[test]
+fn out_of_range_match_pause_margin_is_rejected() {
+    for margin in [0, usize::from(u16::MAX) + 1] {
+        let err = SessionBuilder::<StubConfig>::new()
+            .with_match_pause_margin(margin)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FortressError::InvalidRequestStructured {
+                kind: InvalidRequestKind::ConfigValueOutOfRange {
+                    field: "match_pause_margin",
+                    ..
+                }
+            }
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! Integration tests for `P2PSession::memory_report` and
+//! `SpectatorSession::memory_report`.
+//!
+//! Covers:
+//! - After a scripted two-peer run the saved states fill the prediction
+//!   window, the input queues hold their configured slots for every player,
+//!   and the protocol, checksum and event components follow the buffers.
+//! - The state size hint sizes the saved states; without it only their count
+//!   is reported.
+//! - A spectator with rewind reports its state buffer, its input buffer and
+//!   its host endpoint.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::mem::size_of;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    assert_spectator_synchronized, create_channel_pair, drain_sync_events, poll_with_advance,
+    synchronize_sessions_deterministic, synchronize_spectator_deterministic, SyncConfig, TestClock,
+};
+use fortress_rollback::__internal::PlayerInput;
+use fortress_rollback::{
+    DesyncDetection, FortressError, Frame, InputQueueConfig, MemoryUsage, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, SpectatorConfig,
+};
+
+const MAX_PREDICTION: usize = 4;
+const QUEUE_LENGTH: usize = 128;
+const STATE_SIZE: usize = 4096;
+const FRAMES: usize = 30;
+/// A checksum and the frame it belongs to.
+const CHECKSUM_BYTES: u64 = size_of::<(Frame, u128)>() as u64;
+
+fn slots(count: usize) -> MemoryUsage {
+    let mut usage = MemoryUsage::default();
+    usage.count = count as u64;
+    usage.bytes = Some((count * size_of::<PlayerInput<StubInput>>()) as u64);
+    usage
+}
+
+#[test]
+fn p2p_memory_report_counts_each_component() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let builder = || {
+        SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            })
+            .with_max_prediction_window(MAX_PREDICTION)
+            .with_input_queue_config(InputQueueConfig {
+                queue_length: QUEUE_LENGTH,
+                ..InputQueueConfig::default()
+            })
+            .with_desync_detection_mode(DesyncDetection::On { interval: 1 })
+    };
+    let mut sess1 = builder()
+        .with_state_size_hint(STATE_SIZE)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = builder()
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+
+    let report = sess1.memory_report();
+    assert_eq!(report.saved_states.count, 0);
+    assert_eq!(report.saved_states.bytes, Some(0));
+    assert_eq!(report.input_queues, slots(QUEUE_LENGTH * 2));
+
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+
+    let (mut game1, mut game2) = (GameStub::new(), GameStub::new());
+    for frame in 0..FRAMES {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        let input = StubInput { inp: frame as u32 };
+        sess1.add_local_input(PlayerHandle::new(0), input)?;
+        game1.handle_requests(sess1.advance_frame()?);
+        sess2.add_local_input(PlayerHandle::new(1), input)?;
+        game2.handle_requests(sess2.advance_frame()?);
+    }
+    // The inputs of the last frames are not acknowledged yet.
+    let report = sess1.memory_report();
+
+    // One cell per frame in the prediction window, plus the current frame.
+    assert_eq!(report.saved_states.count, MAX_PREDICTION as u64 + 1);
+    assert_eq!(
+        report.saved_states.bytes,
+        Some(((MAX_PREDICTION + 1) * STATE_SIZE) as u64)
+    );
+    assert_eq!(report.input_queues, slots(QUEUE_LENGTH * 2));
+    // Each serialized `StubInput` is one byte or more per frame.
+    assert!(report.pending_output.count >= 1);
+    assert!(report.pending_output.bytes.unwrap() >= report.pending_output.count);
+    assert!(report.recv_inputs.count >= 1);
+    assert!(report.recv_inputs.bytes.unwrap() >= report.recv_inputs.count);
+    assert_eq!(report.send_queue, MemoryUsage::default());
+    assert!(report.checksum_history.count >= 1);
+    assert_eq!(
+        report.checksum_history.bytes,
+        Some(report.checksum_history.count * CHECKSUM_BYTES)
+    );
+    assert_eq!(
+        report.pending_checksums.bytes,
+        Some(report.pending_checksums.count * CHECKSUM_BYTES)
+    );
+
+    // The event queue counts the undrained events.
+    let events = sess1.events().count() as u64;
+    assert_eq!(report.event_queue.count, events);
+    assert_eq!(sess1.memory_report().event_queue, MemoryUsage::default());
+
+    let known = [
+        report.saved_states,
+        report.input_queues,
+        report.recv_inputs,
+        report.pending_output,
+        report.send_queue,
+        report.pending_checksums,
+        report.checksum_history,
+        report.event_queue,
+    ];
+    assert_eq!(
+        report.total_bytes(),
+        known.iter().map(|usage| usage.bytes.unwrap()).sum::<u64>()
+    );
+
+    // Without a hint the saved states are only counted.
+    let report = sess2.memory_report();
+    assert_eq!(report.saved_states.count, MAX_PREDICTION as u64 + 1);
+    assert_eq!(report.saved_states.bytes, None);
+    assert_eq!(report.input_queues, slots(QUEUE_LENGTH * 2));
+    Ok(())
+}
+
+#[test]
+fn spectator_memory_report_counts_each_component() -> Result<(), FortressError> {
+    const PLAYERS: usize = 2;
+    const BUFFER_SIZE: usize = 32;
+
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut host_sess = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config.clone())
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(2))?
+        .start_p2p_session(host_socket)?;
+    let mut spec_sess = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config)
+        .with_state_size_hint(STATE_SIZE)
+        .with_spectator_config(SpectatorConfig {
+            buffer_size: BUFFER_SIZE,
+            enable_rewind: true,
+            ..SpectatorConfig::default()
+        })
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let sync_result = synchronize_spectator_deterministic(&mut spec_sess, &mut host_sess, &clock);
+    assert_spectator_synchronized(&spec_sess, &host_sess, &sync_result);
+
+    let report = spec_sess.memory_report();
+    assert_eq!(report.saved_states.count, 0);
+    assert_eq!(report.input_queues, slots(BUFFER_SIZE * PLAYERS));
+
+    let (mut host_stub, mut spec_stub) = (GameStub::new(), GameStub::new());
+    for frame in 0..FRAMES {
+        for player in 0..PLAYERS {
+            host_sess
+                .add_local_input(PlayerHandle::new(player), StubInput { inp: frame as u32 })?;
+        }
+        host_stub.handle_requests(host_sess.advance_frame()?);
+        host_sess.poll_remote_clients();
+        spec_sess.poll_remote_clients();
+        while spec_sess.current_frame() < host_sess.current_frame() {
+            match spec_sess.advance_frame() {
+                Ok(requests) if !requests.is_empty() => spec_stub.handle_requests(requests),
+                _ => break,
+            }
+        }
+    }
+
+    let report = spec_sess.memory_report();
+    // Rewind keeps one state per simulated frame, up to the buffer size.
+    let simulated = spec_sess.current_frame().as_i32() as u64 + 1;
+    assert!(simulated > 1, "the spectator never advanced");
+    assert_eq!(report.saved_states.count, simulated.min(BUFFER_SIZE as u64));
+    assert_eq!(
+        report.saved_states.bytes,
+        Some(report.saved_states.count * STATE_SIZE as u64)
+    );
+    assert_eq!(report.input_queues, slots(BUFFER_SIZE * PLAYERS));
+    assert_eq!(report.checksum_history, MemoryUsage::default());
+    assert_eq!(report.pending_output, MemoryUsage::default());
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Regression test for `Config::Input` types whose default does not
+//! serialize to zero bytes.
+//!
+//! Covers:
+//! - A loopback P2P session with an input whose default is an enum's second
+//!   variant plus a `NonZeroU8` field reaches confirmed-frame parity: every
+//!   confirmed input is the one its owner added, on both peers.
+//!
+//! The blank delta reference and the build-time round-trip check are
+//! unit-tested in `network::protocol`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::num::NonZeroU8;
+
+use crate::common::{
+    create_channel_pair, drain_sync_events, poll_with_advance, synchronize_sessions_deterministic,
+    SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    Config, FortressError, FortressRequest, Frame, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, RequestVec, SessionBuilder,
+};
+use serde::{Deserialize, Serialize};
+
+const FRAMES: i32 = 60;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum Stick {
+    Left,
+    #[default]
+    Neutral,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct PadInput {
+    stick: Stick,
+    buttons: NonZeroU8,
+}
+
+impl Default for PadInput {
+    fn default() -> Self {
+        Self {
+            stick: Stick::Neutral,
+            buttons: NonZeroU8::MIN,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PadConfig;
+
+impl Config for PadConfig {
+    type Input = PadInput;
+    type State = u64;
+    type Address = SocketAddr;
+}
+
+fn input_for(player: usize, frame: i32) -> PadInput {
+    let stick = match (frame + player as i32) % 3 {
+        0 => Stick::Left,
+        1 => Stick::Neutral,
+        _ => Stick::Right,
+    };
+    PadInput {
+        stick,
+        buttons: NonZeroU8::new((frame % 200) as u8 + 1 + player as u8).unwrap(),
+    }
+}
+
+fn handle(requests: RequestVec<PadConfig>, state: &mut u64) {
+    for request in requests {
+        match request {
+            FortressRequest::SaveGameState { cell, frame } => {
+                cell.save(frame, Some(*state), Some(u128::from(*state)));
+            },
+            FortressRequest::LoadGameState { cell, .. } => *state = cell.load().unwrap(),
+            FortressRequest::AdvanceFrame { inputs, .. } => {
+                for (input, _) in inputs.iter() {
+                    *state = state
+                        .wrapping_mul(31)
+                        .wrapping_add(u64::from(input.buttons.get()) + input.stick as u64);
+                }
+            },
+        }
+    }
+}
+
+fn sessions() -> Result<(TestClock, P2PSession<PadConfig>, P2PSession<PadConfig>), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let builder = || {
+        SessionBuilder::<PadConfig>::new().with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        })
+    };
+    let mut sess1 = builder()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = builder()
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+    Ok((clock, sess1, sess2))
+}
+
+fn record_confirmed(session: &P2PSession<PadConfig>, seen: &mut BTreeMap<i32, Vec<PadInput>>) {
+    let confirmed = session.confirmed_frame();
+    if confirmed.as_i32() >= 0 && !seen.contains_key(&confirmed.as_i32()) {
+        seen.insert(
+            confirmed.as_i32(),
+            session.confirmed_inputs_for_frame(confirmed).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn non_zero_default_input_reaches_confirmed_parity() -> Result<(), FortressError> {
+    let default = fortress_rollback::network::codec::encode(&PadInput::default()).unwrap();
+    assert!(
+        default.iter().any(|&b| b != 0),
+        "the default must not encode to zero bytes"
+    );
+
+    let (clock, mut sess1, mut sess2) = sessions()?;
+    let (mut state1, mut state2) = (0, 0);
+    let (mut seen1, mut seen2) = (BTreeMap::new(), BTreeMap::new());
+
+    for _ in 0..FRAMES {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        let frame = sess1.current_frame().as_i32();
+        sess1.add_local_input(PlayerHandle::new(0), input_for(0, frame))?;
+        handle(sess1.advance_frame()?, &mut state1);
+        let frame = sess2.current_frame().as_i32();
+        sess2.add_local_input(PlayerHandle::new(1), input_for(1, frame))?;
+        handle(sess2.advance_frame()?, &mut state2);
+        record_confirmed(&sess1, &mut seen1);
+        record_confirmed(&sess2, &mut seen2);
+    }
+
+    assert!(sess1.confirmed_frame() > Frame::new(FRAMES / 2));
+    assert!(sess2.confirmed_frame() > Frame::new(FRAMES / 2));
+    for seen in [&seen1, &seen2] {
+        for (frame, inputs) in seen {
+            assert_eq!(
+                inputs,
+                &vec![input_for(0, *frame), input_for(1, *frame)],
+                "frame {frame}"
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Integration tests for observer peers (`SessionBuilder::add_observer`): a
+//! `P2PSession` with no local player that takes part in the match without a
+//! player slot.
+//!
+//! Covers:
+//! - Two players and one observer all reaching the target frame without
+//!   desync, with the observer's recorded confirmed inputs and checksums
+//!   matching the players'.
+//! - Builder refusals for an observer sharing a player's address.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::sync::Arc;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_channel_triple, TestClock, POLL_INTERVAL_DETERMINISTIC};
+use fortress_rollback::telemetry::CollectingObserver;
+use fortress_rollback::{
+    DesyncDetection, FortressError, FortressEvent, Frame, InvalidRequestKind, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+};
+
+/// Frame every node has to reach.
+const TARGET_FRAME: i32 = 120;
+/// Upper bound on steps any loop may take.
+const MAX_STEPS: usize = 1000;
+
+fn input_for(player: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(3 + player as u32) % 13,
+    }
+}
+
+fn builder(clock: &TestClock, violations: &Arc<CollectingObserver>) -> SessionBuilder<StubConfig> {
+    SessionBuilder::<StubConfig>::new()
+        .with_violation_observer(violations.clone())
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        })
+        .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+        .with_recording(true)
+}
+
+/// One node: its session, game stub, the local handle it plays (none for the
+/// observer), and the events it saw.
+struct Node {
+    session: P2PSession<StubConfig>,
+    stub: GameStub,
+    local: Option<PlayerHandle>,
+    events: Vec<FortressEvent<StubConfig>>,
+}
+
+impl Node {
+    fn step(&mut self) -> Result<(), FortressError> {
+        self.session.poll_remote_clients();
+        if self.session.current_state() == SessionState::Running
+            && self.session.current_frame() < Frame::new(TARGET_FRAME)
+        {
+            if let Some(handle) = self.local {
+                let frame = self.session.current_frame();
+                self.session
+                    .add_local_input(handle, input_for(handle.as_usize(), frame))?;
+            }
+            match self.session.advance_frame() {
+                Ok(requests) => self.stub.handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+        }
+        self.events.extend(self.session.events());
+        Ok(())
+    }
+}
+
+#[test]
+fn observer_follows_two_players_to_the_target_frame() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let violations = Arc::new(CollectingObserver::new());
+    let (s0, s1, observer_socket, a0, a1, observer_addr) = create_channel_triple();
+    let player0 = builder(&clock, &violations)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(1))?
+        .add_observer(observer_addr)
+        .start_p2p_session(s0)?;
+    let player1 = builder(&clock, &violations)
+        .add_player(PlayerType::Remote(a0), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_observer(observer_addr)
+        .start_p2p_session(s1)?;
+    let observer = builder(&clock, &violations)
+        .with_num_players(2)?
+        .add_player(PlayerType::Remote(a0), PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(1))?
+        .start_p2p_session(observer_socket)?;
+    assert_eq!(observer.num_local_players(), 0);
+    assert_eq!(player0.num_players(), 2, "the observer owns no slot");
+
+    let node = |session, local: Option<usize>| Node {
+        session,
+        stub: GameStub::new(),
+        local: local.map(PlayerHandle::new),
+        events: Vec::new(),
+    };
+    let mut nodes = [
+        node(player0, Some(0)),
+        node(player1, Some(1)),
+        node(observer, None),
+    ];
+    for _ in 0..MAX_STEPS {
+        for node in &mut nodes {
+            node.step()?;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        if nodes
+            .iter()
+            .all(|node| node.session.confirmed_frame() >= Frame::new(TARGET_FRAME - 1))
+        {
+            break;
+        }
+    }
+
+    for node in &nodes {
+        assert_eq!(node.session.current_frame(), Frame::new(TARGET_FRAME));
+        assert_eq!(node.stub.gs.frame, TARGET_FRAME);
+        assert!(
+            node.events.iter().all(|event| !matches!(
+                event,
+                FortressEvent::DesyncDetected { .. } | FortressEvent::Disconnected { .. }
+            )),
+            "{:?}",
+            node.events
+        );
+    }
+    // The players accept the observer's input-less `Input` messages as is.
+    assert!(violations.is_empty(), "{:?}", violations.violations());
+    assert_eq!(nodes[2].stub.gs, nodes[0].stub.gs);
+    assert_eq!(nodes[2].stub.gs, nodes[1].stub.gs);
+    // Checksums were exchanged with the observer in both directions.
+    assert!(nodes[2].session.metrics().checksums_compared > 0);
+
+    let mut replays = Vec::new();
+    for node in &mut nodes {
+        replays.push(node.session.take_replay()?);
+    }
+    let observed = &replays[2];
+    assert_eq!(observed.num_players, 2);
+    assert!(observed.frames.len() >= (TARGET_FRAME - 1) as usize);
+    for replay in &replays[..2] {
+        assert_eq!(replay.frames, observed.frames);
+        assert_eq!(replay.checksums, observed.checksums);
+    }
+    for (frame, inputs) in observed.frames.iter().enumerate() {
+        let frame = Frame::new(frame as i32);
+        assert_eq!(inputs, &vec![input_for(0, frame), input_for(1, frame)]);
+    }
+    Ok(())
+}
+
+#[test]
+fn observer_sharing_a_player_address_is_rejected() -> Result<(), FortressError> {
+    let (socket, _s1, _s2, _a0, a1, _a2) = create_channel_triple();
+    let result = SessionBuilder::<StubConfig>::new()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(1))?
+        .add_observer(a1)
+        .start_p2p_session(socket);
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotSupported { .. }
+        })
+    ));
+    Ok(())
+}
@@ -790,6 +790,7 @@ fn test_synchronization_data_driven() {
 fn run_sync_test_case(case: &SyncTestCase) -> Result<(), Box<dyn std::error::Error>> {
     let clock = TestClock::new();
     let (s1, s2, a1, a2) = create_channel_pair();
+    // `high_input_delay_8` reaches the default prediction window.
 
     let mut sess1 = SessionBuilder::<StubConfig>::new()
         .with_protocol_config(protocol_config(&clock))
@@ -797,6 +798,7 @@ fn run_sync_test_case(case: &SyncTestCase) -> Result<(), Box<dyn std::error::Err
         .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
         .with_input_delay(case.input_delay_1)
         .unwrap()
+        .with_input_delay_beyond_prediction(true)
         .start_p2p_session(s1)?;
 
     let mut sess2 = SessionBuilder::<StubConfig>::new()
@@ -805,6 +807,7 @@ fn run_sync_test_case(case: &SyncTestCase) -> Result<(), Box<dyn std::error::Err
         .add_player(PlayerType::Local, PlayerHandle::new(1))?
         .with_input_delay(case.input_delay_2)
         .unwrap()
+        .with_input_delay_beyond_prediction(true)
         .start_p2p_session(s2)?;
 
     // Synchronize using deterministic helper
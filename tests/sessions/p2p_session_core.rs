@@ -0,0 +1,201 @@
+//! Integration tests for `P2PSessionCore`.
+//!
+//! Peer A plays a `P2PSession` against peer B over a lossy, jittery
+//! `ChaosSocket` loopback under virtual time, with its socket traced. A
+//! `P2PSessionCore` built from the same configuration is ticked alongside it
+//! with exactly the packets A received, at the same times, with the same
+//! inputs. Covers:
+//! - Identical outgoing messages, requests, events and errors on every tick,
+//!   from synchronization through a long match with rollbacks.
+//! - Identical game state and session queries at the end.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_pair, TestClock, POLL_INTERVAL_DETERMINISTIC};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressRequest, Message, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, RequestVec, SessionBuilder, SessionState, TracingSocket,
+};
+use std::net::SocketAddr;
+
+const SEED: u64 = 42;
+const STEPS: usize = 1500;
+
+fn builder(local: usize, remote_addr: SocketAddr, clock: &TestClock) -> SessionBuilder<StubConfig> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_max_prediction_window(8)
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::deterministic(SEED)
+        });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder
+            .add_player(player, PlayerHandle::new(handle))
+            .unwrap();
+    }
+    builder
+}
+
+/// The requests with their cells left out, in order.
+fn describe(requests: &RequestVec<StubConfig>) -> Vec<String> {
+    requests
+        .iter()
+        .map(|request| match request {
+            FortressRequest::SaveGameState { frame, .. } => format!("save {frame}"),
+            FortressRequest::LoadGameState { frame, .. } => format!("load {frame}"),
+            FortressRequest::AdvanceFrame { inputs, .. } => format!("advance {inputs:?}"),
+        })
+        .collect()
+}
+
+/// The input a peer gives at `step`, varied enough to force rollbacks.
+fn input(step: usize, handle: usize) -> StubInput {
+    StubInput {
+        inp: ((step / 3 + handle * 7) % 5) as u32,
+    }
+}
+
+/// Polls `session` and, once running, adds its local input and advances, the
+/// way a game loop drives a `P2PSession`.
+fn drive(
+    session: &mut P2PSession<StubConfig>,
+    handle: usize,
+    step: usize,
+) -> (
+    Vec<(PlayerHandle, StubInput)>,
+    Result<RequestVec<StubConfig>, FortressError>,
+) {
+    session.poll_remote_clients();
+    if session.current_state() != SessionState::Running {
+        return (Vec::new(), Ok(RequestVec::new()));
+    }
+    let inputs = vec![(PlayerHandle::new(handle), input(step, handle))];
+    for (player, inp) in &inputs {
+        if let Err(err) = session.add_local_input(*player, *inp) {
+            return (inputs, Err(err));
+        }
+    }
+    let result = session.advance_frame();
+    (inputs, result)
+}
+
+#[test]
+fn core_matches_the_session_through_a_chaotic_match() {
+    let clock = TestClock::new();
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .latency_ms(30)
+            .jitter_ms(25)
+            .packet_loss_rate(0.1)
+            .seed(seed)
+            .build()
+    };
+    let (socket_a, socket_b, addr_a, addr_b) =
+        create_chaos_channel_pair(chaos(11), chaos(12), &clock);
+    let socket_a = TracingSocket::new(socket_a);
+    let trace = socket_a.trace();
+    let mut session_a = builder(0, addr_b, &clock)
+        .start_p2p_session(socket_a)
+        .unwrap();
+    let mut session_b = builder(1, addr_a, &clock)
+        .start_p2p_session(socket_b)
+        .unwrap();
+    let mut core = builder(0, addr_b, &clock)
+        .start_p2p_session_core(clock.now())
+        .unwrap();
+    // The trace holds what the session sent while it was being built.
+    let mut pending_sent: Vec<(SocketAddr, Message)> = trace
+        .take()
+        .sent()
+        .map(|record| (record.addr, record.message().unwrap()))
+        .collect();
+
+    let mut stub_a = GameStub::new();
+    let mut stub_b = GameStub::new();
+    let mut stub_core = GameStub::new();
+    let mut advanced = 0;
+    for step in 0..STEPS {
+        let (inputs, result) = drive(&mut session_a, 0, step);
+        let events: Vec<_> = session_a.events().collect();
+        let records = trace.take();
+        let received: Vec<(SocketAddr, Message)> = records
+            .received()
+            .map(|record| (record.addr, record.message().unwrap()))
+            .collect();
+        pending_sent.extend(
+            records
+                .sent()
+                .map(|record| (record.addr, record.message().unwrap())),
+        );
+
+        let output = core.tick(clock.now(), &received, &inputs);
+        let sent = std::mem::take(&mut pending_sent);
+        assert_eq!(output.to_send, sent, "sent messages differ at step {step}");
+        assert_eq!(
+            format!("{:?}", output.events),
+            format!("{events:?}"),
+            "events differ at step {step}"
+        );
+        match result {
+            Ok(requests) => {
+                assert_eq!(output.error, None, "step {step}");
+                assert_eq!(
+                    describe(&output.requests),
+                    describe(&requests),
+                    "requests differ at step {step}"
+                );
+                advanced += usize::from(!requests.is_empty());
+                stub_a.handle_requests(requests);
+                stub_core.handle_requests(output.requests);
+            },
+            Err(err) => {
+                assert_eq!(output.error, Some(err), "step {step}");
+                assert!(output.requests.is_empty());
+            },
+        }
+
+        let (_, result_b) = drive(&mut session_b, 1, step);
+        match result_b {
+            Ok(requests) => stub_b.handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => panic!("peer B failed at step {step}: {err}"),
+        }
+        for _ in session_b.events() {}
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    assert!(advanced > STEPS / 2, "advanced only {advanced} times");
+    assert!(
+        session_a.metrics().rollback_count > 0,
+        "the chaotic link caused rollbacks"
+    );
+    assert_eq!(stub_core.gs, stub_a.gs);
+    let session_core = core.session();
+    assert_eq!(session_core.current_frame(), session_a.current_frame());
+    assert_eq!(session_core.confirmed_frame(), session_a.confirmed_frame());
+    assert_eq!(
+        session_core.metrics().rollback_count,
+        session_a.metrics().rollback_count
+    );
+    // The core's clock stopped at its last tick; the session's kept going.
+    let mut stats = session_a.network_stats(PlayerHandle::new(1)).unwrap();
+    let core_stats = session_core.network_stats(PlayerHandle::new(1)).unwrap();
+    assert_eq!(
+        core_stats.stats_age_ms + POLL_INTERVAL_DETERMINISTIC.as_millis(),
+        stats.stats_age_ms
+    );
+    stats.stats_age_ms = core_stats.stats_age_ms;
+    assert_eq!(core_stats, stats);
+}
@@ -108,7 +108,7 @@ fn advance_session(
     let requests = session.advance_frame()?;
     let mut advanced_inputs = Vec::new();
     for request in &*requests {
-        if let FortressRequest::AdvanceFrame { inputs } = request {
+        if let FortressRequest::AdvanceFrame { inputs, .. } = request {
             advanced_inputs.push((frame, inputs.clone()));
             frame = Frame::new(frame.as_i32() + 1);
         }
@@ -735,7 +735,7 @@ fn p2p_continue_without_frozen_input_repeats_last() -> Result<(), FortressError>
             .unwrap();
         let requests = sess1.advance_frame().unwrap();
         for request in &*requests {
-            if let FortressRequest::AdvanceFrame { inputs } = request {
+            if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                 let inputs: &InputVec<StubInput> = inputs;
                 // Handle 1 is the dropped peer.
                 if let Some(&(input, status)) = inputs.get(1) {
@@ -848,7 +848,7 @@ fn p2p_continue_without_late_packets_after_freeze_do_not_mutate_input() -> Resul
         sess1.add_local_input(PlayerHandle::new(0), StubInput { inp: 50_000 + i })?;
         let requests = sess1.advance_frame()?;
         for request in &*requests {
-            if let FortressRequest::AdvanceFrame { inputs } = request {
+            if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                 if sess1.current_frame() > frame_at_drop {
                     if let Some(&(input, status)) = inputs.get(1) {
                         observed_remote_inputs.push((input.inp, status));
@@ -1177,7 +1177,7 @@ fn p2p_continue_without_spectators_get_frozen_inputs() -> Result<(), FortressErr
         match spec_sess.advance_frame() {
             Ok(requests) => {
                 for request in &*requests {
-                    if let FortressRequest::AdvanceFrame { inputs } = request {
+                    if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                         let inputs: &InputVec<StubInput> = inputs;
                         // Only collect frames produced after the drop.
                         if spec_sess.current_frame() > spec_frame_pre_drop {
@@ -1705,7 +1705,7 @@ fn p2p_remove_player_multi_handle_freezes_all_handles_at_address() -> Result<(),
             .unwrap();
         let requests = sess_a.advance_frame().unwrap();
         for request in &*requests {
-            if let FortressRequest::AdvanceFrame { inputs } = request {
+            if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                 let inputs: &InputVec<StubInput> = inputs;
                 if let Some(&(input, status)) = inputs.get(1) {
                     h1_observations.push((input.inp, status));
@@ -4859,7 +4859,7 @@ fn record_dropped_slot(
                 let checksum = crate::common::calculate_hash(&stub.gs);
                 cell.save(frame, Some(stub.gs), Some(checksum as u128));
             },
-            FortressRequest::AdvanceFrame { inputs } => {
+            FortressRequest::AdvanceFrame { inputs, .. } => {
                 let dropped = inputs
                     .get(DROPPED)
                     .map(|&(input, status)| (input.inp, status));
@@ -7665,7 +7665,7 @@ fn apply_recording(&mut self, requests: fortress_rollback::RequestVec<StubConfig
                         let checksum = crate::common::calculate_hash(&self.stub.gs);
                         cell.save(frame, Some(self.stub.gs), Some(checksum as u128));
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         let d = inputs.get(D).map(|&(input, status)| (input.inp, status));
                         self.stub.gs.advance_frame_pub(inputs);
                         if let Some(vs) = d {
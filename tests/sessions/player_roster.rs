@@ -0,0 +1,243 @@
+//! Integration tests for `PlayerRoster` (`SessionBuilder::add_player_with_id`
+//! and `P2PSession::roster`).
+//!
+//! Covers:
+//! - Ids registered on the builder showing up on the session, looked up both
+//!   ways and iterated in handle order, and duplicate ids being rejected.
+//! - A disconnected player keeping its id on its handle.
+//! - A hot-join rejoin re-associating the same id with the same handle.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::StubConfig;
+use crate::common::{
+    create_channel_pair, drain_sync_events, synchronize_sessions_deterministic, SyncConfig,
+    TestClock,
+};
+use fortress_rollback::{
+    FortressError, InvalidRequestKind, PlayerHandle, PlayerId, PlayerType, ProtocolConfig,
+    RosterEntry, SessionBuilder,
+};
+
+#[test]
+fn ids_added_on_the_builder_are_looked_up_both_ways() -> Result<(), FortressError> {
+    let (s1, _s2, _a1, a2) = create_channel_pair();
+    let spectator: std::net::SocketAddr = ([127, 0, 0, 1], 10003).into();
+    let session = SessionBuilder::<StubConfig>::new()
+        .add_player_with_id(PlayerType::Remote(a2), PlayerHandle::new(1), "rival")?
+        .add_player_with_id(PlayerType::Local, PlayerHandle::new(0), 1001_u64)?
+        .add_player_with_id(
+            PlayerType::Spectator(spectator),
+            PlayerHandle::new(2),
+            "fan",
+        )?
+        .start_p2p_session(s1)?;
+
+    let roster = session.roster();
+    assert_eq!(roster.len(), 3);
+    assert_eq!(
+        roster.handle_of(&PlayerId::from("rival")),
+        Some(PlayerHandle::new(1))
+    );
+    assert_eq!(
+        roster.id_of(PlayerHandle::new(0)),
+        Some(&PlayerId::Number(1001))
+    );
+    assert_eq!(
+        roster.iter().map(RosterEntry::handle).collect::<Vec<_>>(),
+        vec![
+            PlayerHandle::new(0),
+            PlayerHandle::new(1),
+            PlayerHandle::new(2)
+        ]
+    );
+    assert!(roster.iter().all(RosterEntry::is_connected));
+
+    let err = SessionBuilder::<StubConfig>::new()
+        .add_player_with_id(PlayerType::Local, PlayerHandle::new(0), "rival")?
+        .add_player_with_id(PlayerType::Remote(a2), PlayerHandle::new(1), "rival")
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::PlayerIdInUse { handle }
+        } if handle == PlayerHandle::new(0)
+    ));
+    Ok(())
+}
+
+#[test]
+fn disconnected_player_keeps_its_id() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut sess1 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config.clone())
+        .add_player_with_id(PlayerType::Local, PlayerHandle::new(0), "alice")?
+        .add_player_with_id(PlayerType::Remote(a2), PlayerHandle::new(1), "bob")?
+        .start_p2p_session(s1)?;
+    let mut sess2 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config)
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+    assert_eq!(
+        sess1.roster().is_connected(PlayerHandle::new(1)),
+        Some(true)
+    );
+    // Handles added without an id are not listed.
+    assert!(sess2.roster().is_empty());
+
+    sess1.disconnect_player(PlayerHandle::new(1))?;
+    let roster = sess1.roster();
+    assert_eq!(roster.is_connected(PlayerHandle::new(1)), Some(false));
+    assert_eq!(roster.is_connected(PlayerHandle::new(0)), Some(true));
+    assert_eq!(
+        roster.handle_of(&PlayerId::from("bob")),
+        Some(PlayerHandle::new(1))
+    );
+    Ok(())
+}
+
+#[cfg(feature = "hot-join")]
+mod rejoin {
+    use crate::common::stubs::{GameStub, StubConfig, StubInput};
+    use crate::common::{RoutingBus, TestClock, POLL_INTERVAL_DETERMINISTIC};
+    use fortress_rollback::{
+        DisconnectBehavior, FortressError, FortressEvent, P2PSession, PlayerHandle, PlayerId,
+        PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+    };
+    use std::net::SocketAddr;
+
+    const SLOT: PlayerHandle = PlayerHandle::new(1);
+    const MAX_ROUNDS: usize = 400;
+
+    /// A hot-joining session and its game stub.
+    struct Joiner {
+        session: P2PSession<StubConfig>,
+        stub: GameStub,
+        loaded: bool,
+    }
+
+    impl Joiner {
+        fn new(
+            bus: &RoutingBus,
+            clock: &TestClock,
+            host_addr: SocketAddr,
+            joiner_addr: SocketAddr,
+        ) -> Result<Self, FortressError> {
+            let session = SessionBuilder::<StubConfig>::new()
+                .with_protocol_config(ProtocolConfig {
+                    clock: Some(clock.as_protocol_clock()),
+                    ..ProtocolConfig::default()
+                })
+                .add_player(PlayerType::Remote(host_addr), PlayerHandle::new(0))?
+                .add_player(PlayerType::Local, SLOT)?
+                .start_hot_join_session(bus.socket(joiner_addr), host_addr)?;
+            Ok(Self {
+                session,
+                stub: GameStub::new(),
+                loaded: false,
+            })
+        }
+
+        fn step(&mut self) -> Result<(), FortressError> {
+            self.session.poll_remote_clients();
+            if self.session.current_state() == SessionState::Running {
+                // The first advance only loads the host's snapshot.
+                if self.loaded {
+                    self.session.add_local_input(SLOT, StubInput { inp: 2 })?;
+                }
+                self.stub.handle_requests(self.session.advance_frame()?);
+                self.loaded = true;
+            }
+            Ok(())
+        }
+    }
+
+    /// Steps the host and `joiner` until `done` holds for the host.
+    fn run_until(
+        host: &mut P2PSession<StubConfig>,
+        host_stub: &mut GameStub,
+        joiner: &mut Joiner,
+        clock: &TestClock,
+        mut done: impl FnMut(&mut P2PSession<StubConfig>) -> bool,
+    ) -> Result<(), FortressError> {
+        for _ in 0..MAX_ROUNDS {
+            host.poll_remote_clients();
+            if done(host) {
+                return Ok(());
+            }
+            if host.current_state() == SessionState::Running {
+                host.add_local_input(PlayerHandle::new(0), StubInput { inp: 1 })?;
+                host_stub.handle_requests(host.advance_frame()?);
+            }
+            joiner.step()?;
+            clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        }
+        panic!("condition not reached within {MAX_ROUNDS} rounds");
+    }
+
+    fn slot_joined(host: &mut P2PSession<StubConfig>) -> bool {
+        host.events().any(
+            |event| matches!(event, FortressEvent::PeerJoined { handle, .. } if handle == SLOT),
+        )
+    }
+
+    #[test]
+    fn rejoined_slot_keeps_its_id() -> Result<(), FortressError> {
+        let clock = TestClock::new();
+        let bus = RoutingBus::new();
+        let host_addr: SocketAddr = ([127, 0, 0, 1], 21001).into();
+        let joiner_addr: SocketAddr = ([127, 0, 0, 1], 21002).into();
+        let mut host = SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            })
+            .with_disconnect_behavior(DisconnectBehavior::ContinueWithout)
+            .add_player_with_id(PlayerType::Local, PlayerHandle::new(0), "host")?
+            .add_reserved_player_with_id(joiner_addr, SLOT, "guest")?
+            .start_p2p_session(bus.socket(host_addr))?;
+        let mut host_stub = GameStub::new();
+        assert_eq!(host.roster().is_connected(SLOT), Some(false));
+
+        let mut first = Joiner::new(&bus, &clock, host_addr, joiner_addr)?;
+        run_until(&mut host, &mut host_stub, &mut first, &clock, slot_joined)?;
+        let roster = host.roster();
+        assert_eq!(roster.is_connected(SLOT), Some(true));
+        assert_eq!(roster.handle_of(&PlayerId::from("guest")), Some(SLOT));
+
+        // Play on until the join is committed, so the removal drops the slot.
+        let mut rounds = 0;
+        run_until(&mut host, &mut host_stub, &mut first, &clock, |_| {
+            rounds += 1;
+            rounds > 30
+        })?;
+        host.remove_player(SLOT)?;
+        drop(first);
+        let roster = host.roster();
+        assert_eq!(roster.is_connected(SLOT), Some(false));
+        assert_eq!(roster.id_of(SLOT), Some(&PlayerId::from("guest")));
+
+        let mut second = Joiner::new(&bus, &clock, host_addr, joiner_addr)?;
+        run_until(&mut host, &mut host_stub, &mut second, &clock, slot_joined)?;
+        let roster = host.roster();
+        assert_eq!(roster.is_connected(SLOT), Some(true));
+        assert_eq!(roster.handle_of(&PlayerId::from("guest")), Some(SLOT));
+        assert_eq!(roster.len(), 2);
+        Ok(())
+    }
+}
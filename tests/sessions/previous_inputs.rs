@@ -0,0 +1,174 @@
+//! Integration tests for `SessionBuilder::with_previous_inputs_in_requests`.
+//!
+//! Covers:
+//! - `previous_inputs` stays empty by default.
+//! - A P2P rollback that corrects the previous frame's remote input: the first
+//!   pass carries the prediction, the resimulated pass the corrected value, and
+//!   each resimulated frame sees exactly the inputs of the frame before it.
+//! - A replay session carries the recorded inputs of the previous frame.
+//!
+//! The history itself (truncation on rollback, capacity) is unit-tested in
+//! `sessions::previous_inputs`.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::misprediction::{MispredictionHarness, WARMUP_FRAMES};
+use crate::common::stubs::{StubConfig, StubInput};
+use fortress_rollback::replay::{Replay, ReplayMetadata};
+use fortress_rollback::{
+    FortressError, FortressRequest, InputStatus, InputVec, RequestVec, SessionBuilder,
+};
+
+/// Frames session 1 runs ahead predicting session 2's input.
+const ROLLBACK_DEPTH: u32 = 4;
+/// Session 2's input at `frame` once it diverges from the prediction.
+fn remote_input(frame: u32) -> u32 {
+    100 + frame
+}
+
+type Advance = (InputVec<StubInput>, InputVec<StubInput>);
+
+/// `(inputs, previous_inputs)` of every `AdvanceFrame` in `requests`.
+fn advances(requests: &RequestVec<StubConfig>) -> Vec<Advance> {
+    requests
+        .iter()
+        .filter_map(|request| match request {
+            FortressRequest::AdvanceFrame {
+                inputs,
+                previous_inputs,
+            } => Some((inputs.clone(), previous_inputs.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn start(enabled: bool) -> Result<MispredictionHarness, FortressError> {
+    MispredictionHarness::new(|builder, _| Ok(builder.with_previous_inputs_in_requests(enabled)))
+}
+
+/// Advances session 1 one frame and returns the `AdvanceFrame`s of the call
+/// and whether it rolled back.
+fn advance1(run: &mut MispredictionHarness) -> Result<(Vec<Advance>, bool), FortressError> {
+    let requests = run.request1(0)?;
+    let advanced = advances(&requests);
+    let rolled_back = requests
+        .iter()
+        .any(|request| matches!(request, FortressRequest::LoadGameState { .. }));
+    run.handle1(requests);
+    Ok((advanced, rolled_back))
+}
+
+#[test]
+fn previous_inputs_are_empty_by_default() -> Result<(), FortressError> {
+    let mut run = start(false)?;
+    run.run_ahead(ROLLBACK_DEPTH, |run, frame| {
+        for (inputs, previous_inputs) in advance1(run)?.0 {
+            assert_eq!(inputs.len(), 2, "frame {frame}");
+            assert!(previous_inputs.is_empty(), "frame {frame}");
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn resimulated_frames_carry_the_corrected_previous_inputs() -> Result<(), FortressError> {
+    let mut run = start(true)?;
+
+    // Session 1 runs ahead, predicting session 2 repeats its last input.
+    let mut first_pass = Vec::new();
+    run.run_ahead(ROLLBACK_DEPTH, |run, _| {
+        first_pass.extend(advance1(run)?.0);
+        Ok(())
+    })?;
+    assert_eq!(first_pass.len(), (WARMUP_FRAMES + ROLLBACK_DEPTH) as usize);
+    assert!(first_pass[0].1.is_empty(), "nothing precedes frame 0");
+    for frame in 1..WARMUP_FRAMES {
+        assert_eq!(first_pass[frame as usize].1.len(), 2, "frame {frame}");
+    }
+    let predicted = StubInput { inp: 0 };
+    for frame in WARMUP_FRAMES + 1..WARMUP_FRAMES + ROLLBACK_DEPTH {
+        let (_, previous_inputs) = &first_pass[frame as usize];
+        assert_eq!(
+            previous_inputs[1],
+            (predicted, InputStatus::Predicted),
+            "first pass of frame {frame}"
+        );
+    }
+
+    // Session 2 plays different inputs; session 1 rolls back to the first
+    // mispredicted frame and resimulates with the corrected inputs.
+    run.catch_up(ROLLBACK_DEPTH, remote_input)?;
+    let (second_pass, rolled_back) = advance1(&mut run)?;
+    assert!(
+        rolled_back,
+        "the corrected inputs should trigger a rollback"
+    );
+    assert_eq!(second_pass.len(), ROLLBACK_DEPTH as usize + 1);
+
+    // The first resimulated frame follows the last frame kept from before the
+    // rollback.
+    let (kept_inputs, _) = &first_pass[WARMUP_FRAMES as usize - 1];
+    assert_eq!(&second_pass[0].1, kept_inputs);
+    for (offset, window) in second_pass.windows(2).enumerate() {
+        let frame = WARMUP_FRAMES + 1 + offset as u32;
+        let [(previous, _), (_, previous_inputs)] = window else {
+            unreachable!()
+        };
+        assert_eq!(previous_inputs, previous, "frame {frame}");
+        assert_eq!(
+            previous_inputs[1],
+            (
+                StubInput {
+                    inp: remote_input(frame - 1)
+                },
+                InputStatus::Confirmed
+            ),
+            "resimulated pass of frame {frame}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn replay_carries_the_recorded_previous_inputs() -> Result<(), FortressError> {
+    let frames: Vec<Vec<StubInput>> = (0..3)
+        .map(|frame| vec![StubInput { inp: frame }, StubInput { inp: frame + 10 }])
+        .collect();
+    let replay = Replay {
+        num_players: 2,
+        checksums: vec![None; frames.len()],
+        metadata: ReplayMetadata {
+            library_version: String::new(),
+            num_players: 2,
+            total_frames: frames.len(),
+            skipped_frames: 0,
+        },
+        frames: frames.clone(),
+    };
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_previous_inputs_in_requests(true)
+        .start_replay_session(replay)?;
+
+    for (frame, recorded) in frames.iter().enumerate() {
+        let advanced = advances(&session.advance_frame()?);
+        let (inputs, previous_inputs) = &advanced[0];
+        let as_confirmed = |inputs: &[StubInput]| -> InputVec<StubInput> {
+            inputs
+                .iter()
+                .map(|input| (*input, InputStatus::Confirmed))
+                .collect()
+        };
+        assert_eq!(inputs, &as_confirmed(recorded), "frame {frame}");
+        let expected = frame
+            .checked_sub(1)
+            .map_or_else(InputVec::new, |previous| as_confirmed(&frames[previous]));
+        assert_eq!(previous_inputs, &expected, "frame {frame}");
+    }
+    Ok(())
+}
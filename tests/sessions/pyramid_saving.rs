@@ -0,0 +1,172 @@
+//! Integration tests for `SaveMode::Pyramid`.
+//!
+//! Covers:
+//! - Forced rollbacks of depth 2, 7 and 15 re-save only the recent and
+//!   power-of-two frames, and every simulated state matches an `EveryFrame`
+//!   control run.
+//! - A later rollback to a frame whose state the first rollback dropped loads
+//!   the newest saved frame before it and resimulates the difference.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::calculate_hash;
+use crate::common::misprediction::{MispredictionHarness, WARMUP_FRAMES};
+use crate::common::stubs::StubConfig;
+use fortress_rollback::{FortressError, FortressRequest, Frame, RequestVec, SaveMode};
+
+/// Prediction window large enough for every forced rollback below.
+const MAX_PREDICTION: usize = 20;
+const PYRAMID: SaveMode = SaveMode::Pyramid { recent: 2 };
+
+/// `(LoadGameState, AdvanceFrame, SaveGameState)` request counts of one call.
+fn request_counts(requests: &RequestVec<StubConfig>) -> (usize, usize, usize) {
+    let mut counts = (0, 0, 0);
+    for request in requests {
+        match request {
+            FortressRequest::LoadGameState { .. } => counts.0 += 1,
+            FortressRequest::AdvanceFrame { .. } => counts.1 += 1,
+            FortressRequest::SaveGameState { .. } => counts.2 += 1,
+        }
+    }
+    counts
+}
+
+/// Two synchronized sessions, session 1 saving with `save_mode`.
+fn start(save_mode: SaveMode) -> Result<MispredictionHarness, FortressError> {
+    MispredictionHarness::new(|builder, local| {
+        let builder = builder.with_max_prediction_window(MAX_PREDICTION);
+        Ok(if local == 0 {
+            builder.with_save_mode(save_mode)
+        } else {
+            builder
+        })
+    })
+}
+
+/// Advances session 1 and returns the request counts of the call.
+fn advance1(run: &mut MispredictionHarness) -> Result<(usize, usize, usize), FortressError> {
+    let requests = run.request1(0)?;
+    let counts = request_counts(&requests);
+    run.handle1(requests);
+    Ok(counts)
+}
+
+/// Runs the warm-up, then lets session 1 run `depth` frames ahead
+/// predicting session 2's input.
+fn run_ahead(run: &mut MispredictionHarness, depth: u32) -> Result<(), FortressError> {
+    run.run_ahead(depth, |run, _| run.advance1(0))
+}
+
+/// Lets both peers run on for a while so every frame is confirmed.
+fn settle(run: &mut MispredictionHarness) -> Result<(), FortressError> {
+    for _ in 0..30 {
+        run.poll(1);
+        run.advance1(0)?;
+        run.advance2(1)?;
+    }
+    Ok(())
+}
+
+/// Asserts that two runs recorded identical states for every frame both
+/// simulated, and that neither reported a violation.
+fn assert_same_history(a: &MispredictionHarness, b: &MispredictionHarness, min_frames: usize) {
+    let mut compared = 0;
+    for (frame, state) in &a.states1 {
+        if let Some(other) = b.states1.get(frame) {
+            assert_eq!(
+                calculate_hash(state),
+                calculate_hash(other),
+                "frame {frame} diverged between the runs"
+            );
+            compared += 1;
+        }
+    }
+    assert!(compared > min_frames, "only {compared} frames compared");
+    for run in [a, b] {
+        assert!(run.observer1.is_empty(), "{:?}", run.observer1.violations());
+    }
+}
+
+#[test]
+fn pyramid_rollbacks_save_fewer_states_with_every_frame_results() -> Result<(), FortressError> {
+    // (depth, EveryFrame saves, Pyramid saves) of the rollback call: the
+    // resimulated frames after the loaded one, plus the new frame.
+    for (depth, every_frame_saves, pyramid_saves) in [(2, 2, 2), (7, 7, 4), (15, 15, 5)] {
+        let mut control = start(SaveMode::EveryFrame)?;
+        let mut pyramid = start(PYRAMID)?;
+        for (run, expected_saves) in [
+            (&mut control, every_frame_saves),
+            (&mut pyramid, pyramid_saves),
+        ] {
+            run.force_misprediction(depth)?;
+            assert_eq!(
+                advance1(run)?,
+                (1, depth as usize + 1, expected_saves),
+                "depth {depth}: one load, the resimulation plus the new frame"
+            );
+        }
+        assert_eq!(
+            calculate_hash(&pyramid.stub1.gs),
+            calculate_hash(&control.stub1.gs),
+            "depth {depth}"
+        );
+
+        settle(&mut control)?;
+        settle(&mut pyramid)?;
+        assert_eq!(pyramid.sess1.current_frame(), control.sess1.current_frame());
+        assert_same_history(&pyramid, &control, (WARMUP_FRAMES + depth) as usize);
+    }
+    Ok(())
+}
+
+#[test]
+fn rollback_to_a_dropped_frame_loads_the_newest_earlier_save() -> Result<(), FortressError> {
+    let mut control = start(SaveMode::EveryFrame)?;
+    let mut pyramid = start(PYRAMID)?;
+    for (run, second_load, second_advances) in [(&mut control, 14, 8), (&mut pyramid, 12, 10)] {
+        let saved = |run: &MispredictionHarness, frame: i32| {
+            run.sess1
+                .saved_frames()
+                .iter()
+                .any(|info| info.frame == Frame::new(frame))
+        };
+        // Session 1 runs to frame 20; session 2 changes its input at frame 5
+        // but only reaches frame 10, so the rollback to 5 re-predicts 11..=19.
+        run_ahead(run, 15)?;
+        for _ in 0..6 {
+            run.advance2(1)?;
+        }
+        run.poll(1);
+        assert_eq!(advance1(run)?.0, 1);
+        assert_eq!(run.sess1.current_frame(), Frame::new(21));
+
+        // Session 2's input changes again at frame 14. Pyramid saving dropped
+        // that state (6 frames before 20) and still has 12 (8 frames before).
+        for inp in [1, 1, 1, 2, 2, 2, 2, 2, 2] {
+            run.advance2(inp)?;
+        }
+        run.poll(1);
+        assert!(
+            saved(run, second_load),
+            "frame {second_load} should be saved"
+        );
+        assert_eq!(saved(run, 14), second_load == 14);
+        let (loads, advances, _) = advance1(run)?;
+        assert_eq!((loads, advances), (1, second_advances));
+    }
+    assert_eq!(
+        calculate_hash(&pyramid.stub1.gs),
+        calculate_hash(&control.stub1.gs)
+    );
+
+    settle(&mut control)?;
+    settle(&mut pyramid)?;
+    assert_same_history(&pyramid, &control, 20);
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! Integration tests for `SessionBuilder::with_expected_state_checksum`.
+//!
+//! Covers:
+//! - A rejoining peer whose transferred state matches the remote's starts
+//!   running and plays on in sync.
+//! - A rejoining peer holding a stale state emits `RejoinStateMismatch` once
+//!   and keeps refusing to advance.
+//! - The builder rejects a negative frame and a session without a remote
+//!   player to verify against.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{calculate_hash, create_channel_pair, poll_with_advance, TestClock};
+use fortress_rollback::{
+    DesyncDetection, FortressError, FortressEvent, Frame, InvalidRequestKind, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+};
+
+/// Upper bound on polls any loop may take.
+const MAX_STEPS: usize = 200;
+
+/// The checksum `GameStub` reports for its state at frame 0.
+fn initial_checksum() -> u128 {
+    calculate_hash(&GameStub::new().gs) as u128
+}
+
+/// A rejoining session 1 expecting `expected` at frame 0, and a regular
+/// session 2, before synchronization.
+fn start_pair(
+    clock: &TestClock,
+    expected: u128,
+) -> Result<(P2PSession<StubConfig>, P2PSession<StubConfig>), FortressError> {
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let rejoiner = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config.clone())
+        .with_desync_detection_mode(DesyncDetection::On { interval: 1 })
+        .with_expected_state_checksum(Frame::new(0), expected)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let peer = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config)
+        .with_desync_detection_mode(DesyncDetection::On { interval: 1 })
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    Ok((rejoiner, peer))
+}
+
+/// Polls both sessions until the peer runs, then lets it save frame 0 so it
+/// can answer the checksum request, and polls on for `MAX_STEPS`.
+fn run_until_answered(
+    clock: &TestClock,
+    rejoiner: &mut P2PSession<StubConfig>,
+    peer: &mut P2PSession<StubConfig>,
+    peer_stub: &mut GameStub,
+) -> Result<(), FortressError> {
+    for _ in 0..MAX_STEPS {
+        poll_with_advance(rejoiner, peer, clock, 1);
+        if peer.current_state() == SessionState::Running && peer.current_frame() == Frame::new(0) {
+            peer.add_local_input(PlayerHandle::new(1), StubInput { inp: 0 })?;
+            peer_stub.handle_requests(peer.advance_frame()?);
+        }
+        if rejoiner.current_state() == SessionState::Running {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn mismatches(session: &mut P2PSession<StubConfig>) -> Vec<(Frame, u128, u128)> {
+    session
+        .events()
+        .filter_map(|event| match event {
+            FortressEvent::RejoinStateMismatch {
+                frame,
+                expected,
+                actual,
+            } => Some((frame, expected, actual)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn matching_rejoin_state_starts_running_and_stays_in_sync() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut rejoiner, mut peer) = start_pair(&clock, initial_checksum())?;
+    let mut rejoiner_stub = GameStub::new();
+    let mut peer_stub = GameStub::new();
+
+    run_until_answered(&clock, &mut rejoiner, &mut peer, &mut peer_stub)?;
+    assert_eq!(rejoiner.current_state(), SessionState::Running);
+    assert!(mismatches(&mut rejoiner).is_empty());
+
+    for frame in 0..20 {
+        poll_with_advance(&mut rejoiner, &mut peer, &clock, 1);
+        rejoiner.add_local_input(PlayerHandle::new(0), StubInput { inp: frame })?;
+        rejoiner_stub.handle_requests(rejoiner.advance_frame()?);
+        if peer.current_frame() <= rejoiner.current_frame() {
+            peer.add_local_input(PlayerHandle::new(1), StubInput { inp: frame })?;
+            peer_stub.handle_requests(peer.advance_frame()?);
+        }
+    }
+    poll_with_advance(&mut rejoiner, &mut peer, &clock, 10);
+    for session in [&mut rejoiner, &mut peer] {
+        assert!(session
+            .events()
+            .all(|event| !matches!(event, FortressEvent::DesyncDetected { .. })));
+    }
+    assert!(rejoiner.confirmed_frame() > Frame::new(10));
+    Ok(())
+}
+
+#[test]
+fn stale_rejoin_state_reports_mismatch_and_refuses_to_advance() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let stale = initial_checksum() ^ 1;
+    let (mut rejoiner, mut peer) = start_pair(&clock, stale)?;
+    let mut peer_stub = GameStub::new();
+
+    run_until_answered(&clock, &mut rejoiner, &mut peer, &mut peer_stub)?;
+    poll_with_advance(&mut rejoiner, &mut peer, &clock, 20);
+    assert_eq!(rejoiner.current_state(), SessionState::Synchronizing);
+    assert_eq!(
+        mismatches(&mut rejoiner),
+        vec![(Frame::new(0), stale, initial_checksum())]
+    );
+
+    rejoiner.add_local_input(PlayerHandle::new(0), StubInput { inp: 0 })?;
+    assert!(matches!(
+        rejoiner.advance_frame(),
+        Err(FortressError::NotSynchronized)
+    ));
+
+    // The mismatch is reported once, however long the peer keeps answering.
+    poll_with_advance(&mut rejoiner, &mut peer, &clock, 50);
+    assert!(mismatches(&mut rejoiner).is_empty());
+    assert_eq!(rejoiner.current_state(), SessionState::Synchronizing);
+    Ok(())
+}
+
+#[test]
+fn expected_state_checksum_needs_a_remote_player_and_a_valid_frame() {
+    let (socket, _, _, remote) = create_channel_pair();
+    let result = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)
+        .unwrap()
+        .with_expected_state_checksum(Frame::new(0), 1)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))
+        .unwrap()
+        .start_p2p_session(socket);
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotSupported { .. }
+        })
+    ));
+
+    let (socket, _, _, _) = create_channel_pair();
+    let result = SessionBuilder::<StubConfig>::new()
+        .with_expected_state_checksum(Frame::NULL, 1)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))
+        .unwrap()
+        .add_player(PlayerType::Remote(remote), PlayerHandle::new(1))
+        .unwrap()
+        .start_p2p_session(socket);
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidFrameStructured { .. })
+    ));
+}
@@ -0,0 +1,218 @@
+//! Integration tests for relayed routes (`P2PSession::set_route`).
+//!
+//! Covers:
+//! - Two peers starting direct, both switching mid-match to a forwarding
+//!   relay, then back to direct, without desync or disconnect.
+//! - Route lookups and refusals for handles without an endpoint.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_triple, drain_sync_events, synchronize_sessions_deterministic, ChannelSocket,
+    SyncConfig, TestClock, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, Frame, InvalidRequestKind, NonBlockingSocket, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+};
+
+/// Frames played on each leg (direct, relayed, direct again).
+const LEG_FRAMES: usize = 60;
+
+fn input_for(player: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(5 + player as u32) % 9,
+    }
+}
+
+/// A relay between two peers: whatever one peer sends it, it forwards to the
+/// other, so both peers see every relayed packet coming from the relay.
+struct Relay {
+    socket: ChannelSocket,
+    peers: [SocketAddr; 2],
+    forwarded: usize,
+}
+
+impl Relay {
+    fn forward(&mut self) {
+        for (from, msg) in self.socket.receive_all_messages() {
+            let to = if from == self.peers[0] {
+                self.peers[1]
+            } else {
+                self.peers[0]
+            };
+            self.socket.send_to(&msg, &to);
+            self.forwarded += 1;
+        }
+    }
+}
+
+struct Peer {
+    session: P2PSession<StubConfig>,
+    stub: GameStub,
+    states: BTreeMap<i32, StateStub>,
+    events: Vec<FortressEvent<StubConfig>>,
+}
+
+fn play(
+    peers: &mut [Peer; 2],
+    relay: &mut Relay,
+    clock: &TestClock,
+    frames: usize,
+) -> Result<(), FortressError> {
+    for _ in 0..frames {
+        for (index, peer) in peers.iter_mut().enumerate() {
+            peer.session.poll_remote_clients();
+            let frame = peer.session.current_frame();
+            peer.session
+                .add_local_input(PlayerHandle::new(index), input_for(index, frame))?;
+            match peer.session.advance_frame() {
+                Ok(requests) => peer
+                    .stub
+                    .handle_requests_recording(requests, &mut peer.states),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+            peer.events.extend(peer.session.events());
+        }
+        relay.forward();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    Ok(())
+}
+
+fn start() -> Result<([Peer; 2], Relay, TestClock), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, relay_socket, a1, a2, _relay_addr) = create_channel_triple();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut sess1 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config.clone())
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config)
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+    let peer = |session| Peer {
+        session,
+        stub: GameStub::new(),
+        states: BTreeMap::new(),
+        events: Vec::new(),
+    };
+    let relay = Relay {
+        socket: relay_socket,
+        peers: [a1, a2],
+        forwarded: 0,
+    };
+    Ok(([peer(sess1), peer(sess2)], relay, clock))
+}
+
+#[test]
+fn switching_to_a_relay_mid_match_keeps_the_match_running() -> Result<(), FortressError> {
+    let (mut peers, mut relay, clock) = start()?;
+    let relay_addr = relay.socket.local_addr();
+    let direct_addrs = relay.peers;
+
+    play(&mut peers, &mut relay, &clock, LEG_FRAMES)?;
+    assert_eq!(relay.forwarded, 0, "nothing is relayed before the switch");
+
+    peers[0]
+        .session
+        .set_route(PlayerHandle::new(1), relay_addr)?;
+    peers[1]
+        .session
+        .set_route(PlayerHandle::new(0), relay_addr)?;
+    assert_eq!(
+        peers[0].session.route(PlayerHandle::new(1)),
+        Some(relay_addr)
+    );
+    let confirmed_at_switch = peers[0].session.confirmed_frame();
+    play(&mut peers, &mut relay, &clock, LEG_FRAMES)?;
+    let relayed = relay.forwarded;
+    assert!(relayed > 0, "the relay carried the traffic");
+    assert!(
+        peers[0].session.confirmed_frame() > confirmed_at_switch + 30,
+        "inputs kept confirming through the relay"
+    );
+    // The peer keeps its identity: still known by its original address.
+    assert_eq!(
+        peers[0].session.player_type(PlayerHandle::new(1)),
+        Some(PlayerType::Remote(direct_addrs[1]))
+    );
+
+    for peer in &peers {
+        assert_eq!(peer.session.metrics().unknown_source_packets, 0);
+    }
+
+    // Back to direct delivery. Packets still in flight through the relay are
+    // dropped as coming from an unknown source; inputs are resent directly.
+    peers[0]
+        .session
+        .set_route(PlayerHandle::new(1), direct_addrs[1])?;
+    peers[1]
+        .session
+        .set_route(PlayerHandle::new(0), direct_addrs[0])?;
+    assert_eq!(peers[0].session.route(PlayerHandle::new(1)), None);
+    play(&mut peers, &mut relay, &clock, 5)?;
+    let relayed = relay.forwarded;
+    play(&mut peers, &mut relay, &clock, LEG_FRAMES)?;
+    assert_eq!(relay.forwarded, relayed, "the relay is out of the path");
+
+    for peer in &peers {
+        assert!(peer.events.iter().all(|event| !matches!(
+            event,
+            FortressEvent::DesyncDetected { .. }
+                | FortressEvent::Disconnected { .. }
+                | FortressEvent::NetworkInterrupted { .. }
+        )));
+    }
+    let confirmed = peers[0]
+        .session
+        .confirmed_frame()
+        .min(peers[1].session.confirmed_frame())
+        .as_i32();
+    assert!(
+        confirmed > (2 * LEG_FRAMES) as i32,
+        "confirmed up to {confirmed}"
+    );
+    for frame in 1..=confirmed {
+        assert_eq!(
+            peers[0].states[&frame], peers[1].states[&frame],
+            "frame {frame}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn set_route_requires_a_remote_player_or_spectator() -> Result<(), FortressError> {
+    let (mut peers, relay, _clock) = start()?;
+    let relay_addr = relay.socket.local_addr();
+    for handle in [PlayerHandle::new(0), PlayerHandle::new(7)] {
+        assert_eq!(
+            peers[0].session.set_route(handle, relay_addr),
+            Err(InvalidRequestKind::NotRemotePlayerOrSpectator { handle }.into())
+        );
+        assert_eq!(peers[0].session.route(handle), None);
+    }
+    assert_eq!(peers[0].session.route(PlayerHandle::new(1)), None);
+    Ok(())
+}
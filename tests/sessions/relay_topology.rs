@@ -0,0 +1,348 @@
+//! Star relay topology (`add_player_via_relay` and `enable_relay_for`).
+//!
+//! Three peers on a channel mesh where a `ChaosSocket` with 100% loss sits on
+//! the link between the first and the third, so that pair can never connect.
+//! Covers:
+//! - A full mesh with the broken pair never starting for the two peers on it.
+//! - The same peers reaching each other through the middle peer, which
+//!   forwards their inputs, with per-frame state parity and no desync or
+//!   disconnect.
+//! - Disconnecting a relayed player keeping the relay connected.
+//! - `enable_relay_for` and `start_p2p_session` refusing the relay
+//!   configurations they cannot serve.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{create_channel_mesh, ChannelSocket, TestClock, POLL_INTERVAL_DETERMINISTIC};
+use fortress_rollback::{
+    ChaosConfig, ChaosSocket, DesyncDetection, DisconnectBehavior, FortressError, FortressEvent,
+    Frame, InvalidRequestKind, Message, NonBlockingSocket, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, SessionState,
+};
+
+const FRAMES: usize = 150;
+const RELAY: usize = 1;
+
+fn input_for(player: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(3 + player as u32) % 7,
+    }
+}
+
+/// A mesh socket whose link to one peer goes through a `ChaosSocket` that
+/// drops everything, as with a pair of NATs that cannot be traversed.
+struct BrokenLink {
+    link: ChaosSocket<SocketAddr, ChannelSocket>,
+    unreachable: SocketAddr,
+    dropped: Arc<AtomicU64>,
+}
+
+impl BrokenLink {
+    fn new(socket: ChannelSocket, unreachable: SocketAddr, clock: &TestClock) -> Self {
+        let config = ChaosConfig::builder().packet_loss_rate(1.0).seed(7).build();
+        Self {
+            link: ChaosSocket::new(socket, config).with_clock(clock.as_chaos_clock()),
+            unreachable,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl NonBlockingSocket<SocketAddr> for BrokenLink {
+    fn send_to(&mut self, msg: &Message, addr: &SocketAddr) {
+        if *addr == self.unreachable {
+            self.link.send_to(msg, addr);
+            self.dropped
+                .store(self.link.stats().packets_dropped_send, Ordering::Relaxed);
+        } else {
+            self.link.inner_mut().send_to(msg, addr);
+        }
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(SocketAddr, Message)> {
+        self.link.inner_mut().receive_all_messages()
+    }
+}
+
+struct Peer {
+    session: P2PSession<StubConfig>,
+    stub: GameStub,
+    states: BTreeMap<i32, StateStub>,
+    events: Vec<FortressEvent<StubConfig>>,
+}
+
+impl Peer {
+    fn new(session: P2PSession<StubConfig>) -> Self {
+        Self {
+            session,
+            stub: GameStub::new(),
+            states: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+fn builder(local: usize, clock: &TestClock) -> SessionBuilder<StubConfig> {
+    SessionBuilder::<StubConfig>::new()
+        .with_num_players(3)
+        .unwrap()
+        .with_desync_detection_mode(DesyncDetection::On { interval: 10 })
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::deterministic(40 + local as u64)
+        })
+}
+
+/// Builds the three peers, the outer ones behind a broken link to each
+/// other. With `relayed`, they reach each other through the middle peer;
+/// otherwise they form a full mesh. Also returns the outer peers' drop
+/// counters.
+fn start(relayed: bool, clock: &TestClock) -> ([Peer; 3], [Arc<AtomicU64>; 2]) {
+    let (sockets, addrs) = create_channel_mesh(3);
+    let mut sockets = sockets.into_iter();
+    let (socket_a, socket_b, socket_c) = (
+        sockets.next().unwrap(),
+        sockets.next().unwrap(),
+        sockets.next().unwrap(),
+    );
+    let socket_a = BrokenLink::new(socket_a, addrs[2], clock);
+    let socket_c = BrokenLink::new(socket_c, addrs[0], clock);
+    let dropped = [Arc::clone(&socket_a.dropped), Arc::clone(&socket_c.dropped)];
+
+    let outer = |local: usize, other: usize| {
+        let builder = builder(local, clock)
+            .add_player(PlayerType::Local, PlayerHandle::new(local))
+            .unwrap()
+            .add_player(PlayerType::Remote(addrs[RELAY]), PlayerHandle::new(RELAY))
+            .unwrap();
+        if relayed {
+            builder
+                .add_player_via_relay(PlayerHandle::new(other), addrs[RELAY])
+                .unwrap()
+        } else {
+            builder
+                .add_player(PlayerType::Remote(addrs[other]), PlayerHandle::new(other))
+                .unwrap()
+        }
+    };
+    let sess_a = outer(0, 2).start_p2p_session(socket_a).unwrap();
+    let sess_c = outer(2, 0).start_p2p_session(socket_c).unwrap();
+    let mut sess_b = builder(RELAY, clock)
+        .add_player(PlayerType::Remote(addrs[0]), PlayerHandle::new(0))
+        .unwrap()
+        .add_player(PlayerType::Local, PlayerHandle::new(RELAY))
+        .unwrap()
+        .add_player(PlayerType::Remote(addrs[2]), PlayerHandle::new(2))
+        .unwrap()
+        .start_p2p_session(socket_b)
+        .unwrap();
+    if relayed {
+        sess_b
+            .enable_relay_for((PlayerHandle::new(0), PlayerHandle::new(2)))
+            .unwrap();
+    }
+    (
+        [Peer::new(sess_a), Peer::new(sess_b), Peer::new(sess_c)],
+        dropped,
+    )
+}
+
+/// Polls every peer until all run, or gives up after `steps`.
+fn synchronize(peers: &mut [Peer; 3], clock: &TestClock, steps: usize) -> bool {
+    for _ in 0..steps {
+        for peer in peers.iter_mut() {
+            peer.session.poll_remote_clients();
+            peer.events.extend(peer.session.events());
+        }
+        if peers
+            .iter()
+            .all(|peer| peer.session.current_state() == SessionState::Running)
+        {
+            return true;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    false
+}
+
+fn play(peers: &mut [Peer; 3], clock: &TestClock, frames: usize) -> Result<(), FortressError> {
+    for _ in 0..frames {
+        for (index, peer) in peers.iter_mut().enumerate() {
+            peer.session.poll_remote_clients();
+            let frame = peer.session.current_frame();
+            peer.session
+                .add_local_input(PlayerHandle::new(index), input_for(index, frame))?;
+            match peer.session.advance_frame() {
+                Ok(requests) => peer
+                    .stub
+                    .handle_requests_recording(requests, &mut peer.states),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+            peer.events.extend(peer.session.events());
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    Ok(())
+}
+
+#[test]
+fn a_full_mesh_with_a_broken_pair_never_starts() {
+    let clock = TestClock::new();
+    let (mut peers, dropped) = start(false, &clock);
+    assert!(!synchronize(&mut peers, &clock, 200));
+    assert_eq!(
+        peers[0].session.current_state(),
+        SessionState::Synchronizing
+    );
+    assert_eq!(
+        peers[2].session.current_state(),
+        SessionState::Synchronizing
+    );
+    for dropped in &dropped {
+        assert!(dropped.load(Ordering::Relaxed) > 0);
+    }
+}
+
+#[test]
+fn relayed_peers_reach_state_parity_through_the_relay() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut peers, dropped) = start(true, &clock);
+    assert!(
+        synchronize(&mut peers, &clock, 200),
+        "the peers never started"
+    );
+    play(&mut peers, &clock, FRAMES)?;
+
+    // the outer peers never even try to reach each other directly
+    for dropped in &dropped {
+        assert_eq!(dropped.load(Ordering::Relaxed), 0);
+    }
+    for peer in &peers {
+        assert!(peer.events.iter().all(|event| !matches!(
+            event,
+            FortressEvent::DesyncDetected { .. }
+                | FortressEvent::Disconnected { .. }
+                | FortressEvent::NetworkInterrupted { .. }
+        )));
+    }
+    let confirmed = peers
+        .iter()
+        .map(|peer| peer.session.confirmed_frame())
+        .min()
+        .unwrap()
+        .as_i32();
+    // confirming a frame takes every player's input, the relayed ones too
+    assert!(
+        confirmed > FRAMES as i32 - 10,
+        "confirmed up to {confirmed}"
+    );
+    for frame in 1..=confirmed {
+        assert_eq!(
+            peers[0].states[&frame], peers[1].states[&frame],
+            "frame {frame}"
+        );
+        assert_eq!(
+            peers[1].states[&frame], peers[2].states[&frame],
+            "frame {frame}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn disconnecting_a_relayed_player_keeps_the_relay() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut peers, _) = start(true, &clock);
+    assert!(synchronize(&mut peers, &clock, 200));
+    play(&mut peers, &clock, 20)?;
+
+    peers[0].session.disconnect_player(PlayerHandle::new(2))?;
+    assert_eq!(
+        peers[0].session.disconnect_player(PlayerHandle::new(2)),
+        Err(InvalidRequestKind::AlreadyDisconnected {
+            handle: PlayerHandle::new(2)
+        }
+        .into())
+    );
+    // the link to the relay is still up, and the relay was not told goodbye
+    assert!(peers[0]
+        .session
+        .network_stats(PlayerHandle::new(RELAY))
+        .is_ok());
+    for peer in peers.iter_mut() {
+        peer.session.poll_remote_clients();
+    }
+    assert!(peers[RELAY]
+        .session
+        .events()
+        .all(|event| !matches!(event, FortressEvent::Disconnected { .. })));
+    Ok(())
+}
+
+#[test]
+fn relays_refuse_what_they_cannot_serve() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (sockets, addrs) = create_channel_mesh(3);
+    let mut sockets = sockets.into_iter();
+
+    // a relayed player needs a directly added player at the relay's address
+    let err = builder(0, &clock)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player_via_relay(PlayerHandle::new(1), addrs[1])?
+        .add_player_via_relay(PlayerHandle::new(2), addrs[1])?
+        .start_p2p_session(sockets.next().unwrap())
+        .err();
+    assert!(matches!(
+        err,
+        Some(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotSupported { .. }
+        })
+    ));
+    let err = builder(0, &clock)
+        .with_disconnect_behavior(DisconnectBehavior::ContinueWithout)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(addrs[1]), PlayerHandle::new(1))?
+        .add_player_via_relay(PlayerHandle::new(2), addrs[1])?
+        .start_p2p_session(sockets.next().unwrap())
+        .err();
+    assert!(matches!(
+        err,
+        Some(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotSupported { .. }
+        })
+    ));
+
+    let mut relay = builder(RELAY, &clock)
+        .add_player(PlayerType::Remote(addrs[0]), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(RELAY))?
+        .add_player(PlayerType::Remote(addrs[2]), PlayerHandle::new(2))?
+        .start_p2p_session(sockets.next().unwrap())?;
+    assert_eq!(
+        relay.enable_relay_for((PlayerHandle::new(0), PlayerHandle::new(RELAY))),
+        Err(InvalidRequestKind::NotRemotePlayerOrSpectator {
+            handle: PlayerHandle::new(RELAY)
+        }
+        .into())
+    );
+    relay.poll_remote_clients();
+    assert!(matches!(
+        relay.enable_relay_for((PlayerHandle::new(0), PlayerHandle::new(2))),
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotSupported { .. }
+        })
+    ));
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Integration tests for the remote input jitter buffer
+//! (`InputQueueConfig::remote_input_buffer_frames`).
+//!
+//! Covers:
+//! - Two peers on a jitter-heavy link: holding late inputs for two frames
+//!   batches their corrections into fewer rollbacks, and the confirmed states
+//!   match the unbuffered run frame for frame.
+//! - The buffer being validated against the input queue length.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    create_chaos_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressEvent, Frame, InputQueueConfig, InvalidRequestKind,
+    P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+};
+
+/// Frames each peer plays.
+const FRAMES: usize = 240;
+/// Virtual time per step, one frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+
+fn input_for(player: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(3 + player as u32) % 5,
+    }
+}
+
+fn chaos_config(seed: u64) -> ChaosConfig {
+    ChaosConfig::builder()
+        .latency_ms(20)
+        .jitter_ms(40)
+        .seed(seed)
+        .build()
+}
+
+/// The outcome of one run: per-peer rollback counts and confirmed states.
+struct Run {
+    rollbacks: [u64; 2],
+    max_buffered: usize,
+    states: [BTreeMap<i32, StateStub>; 2],
+    confirmed: Frame,
+}
+
+fn run(buffer_frames: u32) -> Result<Run, FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos_config(7), chaos_config(11), &clock);
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let input_queue_config = InputQueueConfig {
+        remote_input_buffer_frames: buffer_frames,
+        ..InputQueueConfig::default()
+    };
+    let mut sess1 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config.clone())
+        .with_input_queue_config(input_queue_config)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config)
+        .with_input_queue_config(input_queue_config)
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    let sync_config = SyncConfig {
+        max_iterations: 2000,
+    };
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &sync_config)
+        .expect("sessions should synchronize");
+
+    let mut sessions: [P2PSession<StubConfig>; 2] = [sess1, sess2];
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut states = [BTreeMap::new(), BTreeMap::new()];
+    let mut max_buffered = 0;
+    for _ in 0..FRAMES {
+        for (index, session) in sessions.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            let handle = PlayerHandle::new(index);
+            let frame = session.current_frame();
+            session.add_local_input(handle, input_for(index, frame))?;
+            match session.advance_frame() {
+                Ok(requests) => {
+                    stubs[index].handle_requests_recording(requests, &mut states[index])
+                },
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+            let remote = PlayerHandle::new(1 - index);
+            max_buffered = max_buffered.max(session.buffered_remote_inputs(remote));
+            assert_eq!(
+                session.buffered_remote_inputs(handle),
+                0,
+                "local inputs are never held"
+            );
+            for event in session.events() {
+                assert!(
+                    !matches!(event, FortressEvent::DesyncDetected { .. }),
+                    "{event:?}"
+                );
+            }
+        }
+        clock.advance(STEP);
+    }
+    let confirmed = sessions[0]
+        .confirmed_frame()
+        .min(sessions[1].confirmed_frame());
+    Ok(Run {
+        rollbacks: [
+            sessions[0].metrics().rollback_count,
+            sessions[1].metrics().rollback_count,
+        ],
+        max_buffered,
+        states,
+        confirmed,
+    })
+}
+
+#[test]
+fn buffering_late_inputs_trades_rollbacks_for_latency() -> Result<(), FortressError> {
+    let unbuffered = run(0)?;
+    let buffered = run(2)?;
+
+    assert_eq!(unbuffered.max_buffered, 0, "nothing is held when disabled");
+    assert!(buffered.max_buffered > 0, "late inputs were held");
+    let total = |run: &Run| run.rollbacks.iter().sum::<u64>();
+    assert!(
+        total(&unbuffered) > 0,
+        "the link is jittery enough to roll back"
+    );
+    assert!(
+        total(&buffered) < total(&unbuffered),
+        "buffered {:?} vs unbuffered {:?}",
+        buffered.rollbacks,
+        unbuffered.rollbacks
+    );
+
+    // Same inputs, same confirmed states, on both peers and in both runs.
+    let confirmed = unbuffered.confirmed.min(buffered.confirmed).as_i32();
+    assert!(
+        confirmed > (FRAMES / 2) as i32,
+        "confirmed up to {confirmed}"
+    );
+    for frame in 1..=confirmed {
+        let expected = &unbuffered.states[0][&frame];
+        for run in [&unbuffered, &buffered] {
+            for states in &run.states {
+                assert_eq!(&states[&frame], expected, "frame {frame}");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn remote_input_buffer_must_fit_the_input_queue() -> Result<(), FortressError> {
+    let result = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_input_queue_config(InputQueueConfig {
+            queue_length: 32,
+            remote_input_buffer_frames: 32,
+        })
+        .start_synctest_session();
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::ConfigValueOutOfRange {
+                field: "remote_input_buffer_frames",
+                ..
+            }
+        })
+    ));
+    Ok(())
+}
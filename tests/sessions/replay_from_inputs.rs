@@ -0,0 +1,167 @@
+//! Replaying a recorded input stream.
+//!
+//! Two players run a lossy, laggy `ChaosSocket` loopback under virtual time
+//! while the first one records `confirmed_inputs_for_frame` for every frame
+//! as it confirms. The recording is then fed to
+//! `SessionBuilder::start_replay_session_from_inputs`. Covers:
+//! - The replay reproducing the live match's confirmed state of every frame,
+//!   rollbacks included, with every input `Confirmed`.
+//! - `SaveGameState` requests on the configured interval only.
+//! - `frames_remaining` counting down to a clean end.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_pair, ChannelSocket, TestClock};
+use fortress_rollback::{
+    ChaosConfig, ChaosSocket, FortressError, FortressRequest, Frame, InputStatus, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SessionState,
+};
+
+const STEP: Duration = Duration::from_millis(16);
+const SAVE_INTERVAL: usize = 10;
+
+fn input_for(handle: usize, frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(3 + handle as u32) % 5,
+    }
+}
+
+fn player(
+    local: usize,
+    remote_addr: SocketAddr,
+    socket: ChaosSocket<SocketAddr, ChannelSocket>,
+    clock: &TestClock,
+) -> P2PSession<StubConfig> {
+    let mut builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::deterministic(40 + local as u64)
+    });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder
+            .add_player(player, PlayerHandle::new(handle))
+            .unwrap();
+    }
+    builder.start_p2p_session(socket).unwrap()
+}
+
+/// Plays the match, returning the first player's recording, the state it
+/// reached after each recorded frame and how often it rolled back.
+fn record() -> (Vec<Vec<StubInput>>, BTreeMap<i32, StateStub>, usize) {
+    let clock = TestClock::new();
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .latency_ms(30)
+            .jitter_ms(10)
+            .packet_loss_rate(0.05)
+            .seed(seed)
+            .build()
+    };
+    let (socket_a, socket_b, addr_a, addr_b) =
+        create_chaos_channel_pair(chaos(1), chaos(2), &clock);
+    let mut players = [
+        player(0, addr_b, socket_a, &clock),
+        player(1, addr_a, socket_b, &clock),
+    ];
+    let mut games = [GameStub::new(), GameStub::new()];
+    let mut states = BTreeMap::new();
+    let mut recording = Vec::new();
+    let mut rollbacks = 0;
+
+    for _ in 0..300 {
+        clock.advance(STEP);
+        for (handle, session) in players.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            if session.current_state() != SessionState::Running {
+                continue;
+            }
+            let frame = session.current_frame();
+            session
+                .add_local_input(PlayerHandle::new(handle), input_for(handle, frame))
+                .unwrap();
+            let requests = match session.advance_frame() {
+                Ok(requests) => requests,
+                Err(FortressError::PredictionThreshold) => continue,
+                Err(err) => panic!("advance failed: {err}"),
+            };
+            if handle != 0 {
+                games[handle].handle_requests(requests);
+                continue;
+            }
+            rollbacks += requests
+                .iter()
+                .filter(|request| matches!(request, FortressRequest::LoadGameState { .. }))
+                .count();
+            // This advance rolled back every frame it had newer inputs for,
+            // so the states up to the confirmed frame are final.
+            games[0].handle_requests_recording(requests, &mut states);
+            while Frame::new(recording.len() as i32) <= session.confirmed_frame() {
+                let frame = Frame::new(recording.len() as i32);
+                recording.push(session.confirmed_inputs_for_frame(frame).unwrap());
+            }
+        }
+    }
+    // Keep only the states of recorded frames, keyed by the frame after them.
+    states.retain(|&frame, _| frame as usize <= recording.len());
+    (recording, states, rollbacks)
+}
+
+#[test]
+fn a_recorded_match_replays_to_the_same_confirmed_states() -> Result<(), FortressError> {
+    let (recording, live, rollbacks) = record();
+    assert!(rollbacks > 0, "the match never rolled back");
+    assert!(recording.len() > 150, "recorded {} frames", recording.len());
+    assert_eq!(live.len(), recording.len());
+
+    let total = recording.len();
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_replay_save_interval(SAVE_INTERVAL)
+        .start_replay_session_from_inputs(recording)?;
+    let mut game = GameStub::new();
+    let mut replayed = BTreeMap::new();
+    let mut saved = Vec::new();
+    while !session.is_complete() {
+        assert_eq!(
+            session.frames_remaining(),
+            total - (session.current_frame().as_i32() + 1) as usize
+        );
+        let requests = session.advance_frame()?;
+        for request in &requests {
+            match request {
+                FortressRequest::SaveGameState { frame, .. } => saved.push(frame.as_i32()),
+                FortressRequest::AdvanceFrame { inputs, .. } => {
+                    assert!(inputs
+                        .iter()
+                        .all(|(_, status)| *status == InputStatus::Confirmed));
+                },
+                FortressRequest::LoadGameState { .. } => panic!("replays never load"),
+            }
+        }
+        game.handle_requests_recording(requests, &mut replayed);
+    }
+
+    assert_eq!(replayed, live);
+    assert_eq!(session.frames_remaining(), 0);
+    assert_eq!(session.current_frame(), Frame::new(total as i32 - 1));
+    let expected: Vec<i32> = (0..total as i32)
+        .filter(|frame| *frame as usize % SAVE_INTERVAL == 0)
+        .collect();
+    assert_eq!(saved, expected);
+    assert!(session.advance_frame().is_err());
+    Ok(())
+}
@@ -0,0 +1,198 @@
+//! Integration tests for capping rollback resimulation per `advance_frame`.
+//!
+//! Covers:
+//! - `SessionBuilder::with_max_resimulation_per_advance` rejecting a zero cap.
+//! - A forced 12-frame rollback under a cap of 4 is spread over exactly three
+//!   `advance_frame` calls, with `P2PSession::resimulation_debt` counting down,
+//!   and ends in the same state (checksum) as the uncapped run.
+//! - `P2PSession::continue_resimulation` pays the debt without simulating a new
+//!   frame.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::calculate_hash;
+use crate::common::misprediction::{MispredictionHarness, WARMUP_FRAMES};
+use crate::common::stubs::StubConfig;
+use fortress_rollback::{
+    FortressError, FortressRequest, InvalidRequestKind, RequestVec, SaveMode, SessionBuilder,
+};
+
+/// Depth of the forced rollback.
+const ROLLBACK_DEPTH: u32 = 12;
+/// Prediction window large enough to run `ROLLBACK_DEPTH` frames ahead.
+const MAX_PREDICTION: usize = 16;
+
+/// `(LoadGameState, AdvanceFrame)` request counts of one call.
+fn request_counts(requests: &RequestVec<StubConfig>) -> (usize, usize) {
+    let loads = requests
+        .iter()
+        .filter(|request| matches!(request, FortressRequest::LoadGameState { .. }))
+        .count();
+    let advances = requests
+        .iter()
+        .filter(|request| matches!(request, FortressRequest::AdvanceFrame { .. }))
+        .count();
+    (loads, advances)
+}
+
+/// Two synchronized sessions, session 1 resimulating at most `cap` frames
+/// per call.
+fn start(cap: Option<usize>, save_mode: SaveMode) -> Result<MispredictionHarness, FortressError> {
+    MispredictionHarness::new(|builder, local| {
+        let builder = builder
+            .with_max_prediction_window(MAX_PREDICTION)
+            .with_save_mode(save_mode);
+        match cap {
+            Some(cap) if local == 0 => builder.with_max_resimulation_per_advance(cap),
+            _ => Ok(builder),
+        }
+    })
+}
+
+/// Advances session 1 and returns the request counts of the call.
+fn advance1(run: &mut MispredictionHarness) -> Result<(usize, usize), FortressError> {
+    let requests = run.request1(0)?;
+    let counts = request_counts(&requests);
+    run.handle1(requests);
+    Ok(counts)
+}
+
+/// Lets both peers run on for a while so every frame is confirmed.
+fn settle(run: &mut MispredictionHarness) -> Result<(), FortressError> {
+    for _ in 0..20 {
+        run.poll(1);
+        run.advance1(0)?;
+        run.advance2(1)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn zero_resimulation_cap_is_rejected() {
+    let err = SessionBuilder::<StubConfig>::new()
+        .with_max_resimulation_per_advance(0)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_resimulation_per_advance",
+                ..
+            }
+        }
+    ));
+}
+
+#[test]
+fn capped_rollback_completes_over_three_calls_with_uncapped_result() -> Result<(), FortressError> {
+    let mut uncapped = start(None, SaveMode::EveryFrame)?;
+    uncapped.force_misprediction(ROLLBACK_DEPTH)?;
+    assert_eq!(
+        advance1(&mut uncapped)?,
+        (1, ROLLBACK_DEPTH as usize + 1),
+        "uncapped: whole rollback plus the new frame in one call"
+    );
+    assert_eq!(uncapped.sess1.resimulation_debt(), 0);
+
+    let mut capped = start(Some(4), SaveMode::EveryFrame)?;
+    capped.force_misprediction(ROLLBACK_DEPTH)?;
+    let frame_before = capped.sess1.current_frame();
+
+    assert_eq!(advance1(&mut capped)?, (1, 4));
+    assert_eq!(capped.sess1.resimulation_debt(), 8);
+
+    assert_eq!(advance1(&mut capped)?, (0, 4));
+    assert_eq!(capped.sess1.resimulation_debt(), 4);
+
+    assert_eq!(
+        advance1(&mut capped)?,
+        (0, 4 + 1),
+        "the call paying the last of the debt also simulates the new frame"
+    );
+    assert_eq!(capped.sess1.resimulation_debt(), 0);
+    assert_eq!(capped.sess1.current_frame(), frame_before + 1);
+    assert_eq!(capped.sess1.current_frame(), uncapped.sess1.current_frame());
+
+    // Every resimulated frame, and the frame reached, match the uncapped run.
+    assert_eq!(capped.states1, uncapped.states1);
+    assert_eq!(
+        calculate_hash(&capped.stub1.gs),
+        calculate_hash(&uncapped.stub1.gs)
+    );
+
+    // The runs keep agreeing once the remote's inputs are confirmed (the
+    // schedules may stall on different calls, so compare frame by frame).
+    settle(&mut capped)?;
+    settle(&mut uncapped)?;
+    assert_same_history(&capped, &uncapped);
+    Ok(())
+}
+
+/// Asserts that two runs recorded identical states for every frame both
+/// simulated, covering at least the warm-up and the rolled-back range.
+fn assert_same_history(a: &MispredictionHarness, b: &MispredictionHarness) {
+    let mut compared = 0;
+    for (frame, state) in &a.states1 {
+        if let Some(other) = b.states1.get(frame) {
+            assert_eq!(state, other, "frame {frame} diverged between the runs");
+            compared += 1;
+        }
+    }
+    assert!(compared > (WARMUP_FRAMES + ROLLBACK_DEPTH) as usize);
+    for run in [a, b] {
+        assert!(run.observer1.is_empty(), "{:?}", run.observer1.violations());
+    }
+}
+
+#[test]
+fn capped_rollback_with_sparse_saving_matches_uncapped_run() -> Result<(), FortressError> {
+    let mut uncapped = start(None, SaveMode::Sparse)?;
+    uncapped.force_misprediction(ROLLBACK_DEPTH)?;
+    settle(&mut uncapped)?;
+
+    let mut capped = start(Some(4), SaveMode::Sparse)?;
+    capped.force_misprediction(ROLLBACK_DEPTH)?;
+    advance1(&mut capped)?;
+    assert!(capped.sess1.resimulation_debt() > 0);
+    settle(&mut capped)?;
+    assert_eq!(capped.sess1.resimulation_debt(), 0);
+
+    assert_same_history(&capped, &uncapped);
+    Ok(())
+}
+
+#[test]
+fn continue_resimulation_pays_debt_without_new_frame() -> Result<(), FortressError> {
+    let mut harness = start(Some(4), SaveMode::EveryFrame)?;
+    assert!(harness.sess1.continue_resimulation()?.is_empty());
+
+    harness.force_misprediction(ROLLBACK_DEPTH)?;
+    let frame_before = harness.sess1.current_frame();
+    advance1(&mut harness)?;
+    assert_eq!(harness.sess1.resimulation_debt(), 8);
+
+    for expected_debt in [4, 0] {
+        let requests = harness.sess1.continue_resimulation()?;
+        assert_eq!(request_counts(&requests), (0, 4));
+        harness.handle1(requests);
+        assert_eq!(harness.sess1.resimulation_debt(), expected_debt);
+    }
+    assert_eq!(harness.sess1.current_frame(), frame_before);
+    assert!(harness.sess1.continue_resimulation()?.is_empty());
+
+    // The next call simulates exactly one new frame.
+    assert_eq!(advance1(&mut harness)?, (0, 1));
+    assert_eq!(harness.sess1.current_frame(), frame_before + 1);
+    assert!(
+        harness.observer1.is_empty(),
+        "{:?}",
+        harness.observer1.violations()
+    );
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//! Integration tests for `P2PSession::last_rollback_cause`.
+//!
+//! Covers:
+//! - No cause before the first rollback.
+//! - A forced misprediction naming the right player, frame, and both inputs.
+//! - The cause being kept across correctly predicted frames and replaced by
+//!   the next misprediction.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::rollback_pair::{Pair, PEER_HANDLE};
+use crate::common::stubs::StubInput;
+use fortress_rollback::{Frame, PlayerHandle, RollbackCause};
+
+fn start() -> Pair {
+    Pair::new(|builder| builder)
+}
+
+/// The host advances before the peer's `inp` arrives, so it predicts the
+/// peer's previous input for the returned frame; the host rolls back on its
+/// next advance.
+fn mispredicted_round(pair: &mut Pair, inp: u32) -> Frame {
+    pair.diverge(&[inp])[0]
+}
+
+fn expected(frame: Frame, predicted: u32, confirmed: u32) -> RollbackCause<StubInput> {
+    RollbackCause {
+        triggering_players: vec![PlayerHandle::new(PEER_HANDLE)],
+        first_incorrect_frame: frame,
+        predicted: vec![StubInput { inp: predicted }],
+        confirmed: vec![StubInput { inp: confirmed }],
+    }
+}
+
+#[test]
+fn cause_names_the_mispredicted_player_frame_and_inputs() {
+    let mut pair = start();
+    pair.predictable_rounds(10, 0);
+    assert_eq!(pair.host.last_rollback_cause(), None);
+
+    let frame = mispredicted_round(&mut pair, 9);
+    assert_eq!(pair.host.last_rollback_cause(), None);
+    pair.predictable_rounds(1, 9);
+    assert_eq!(
+        pair.host.last_rollback_cause(),
+        Some(&expected(frame, 0, 9))
+    );
+}
+
+#[test]
+fn cause_is_kept_until_the_next_rollback() {
+    let mut pair = start();
+    pair.predictable_rounds(5, 0);
+    let first = mispredicted_round(&mut pair, 3);
+    pair.predictable_rounds(1, 3);
+    assert_eq!(
+        pair.host.last_rollback_cause(),
+        Some(&expected(first, 0, 3))
+    );
+
+    // Correct predictions leave the cause alone.
+    for _ in 0..5 {
+        mispredicted_round(&mut pair, 3);
+    }
+    pair.predictable_rounds(1, 3);
+    assert_eq!(
+        pair.host.last_rollback_cause(),
+        Some(&expected(first, 0, 3))
+    );
+
+    let second = mispredicted_round(&mut pair, 4);
+    pair.predictable_rounds(1, 4);
+    assert!(second > first);
+    assert_eq!(
+        pair.host.last_rollback_cause(),
+        Some(&expected(second, 3, 4))
+    );
+}
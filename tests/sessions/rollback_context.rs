@@ -0,0 +1,72 @@
+//! Integration tests for `P2PSession::current_rollback_context`.
+//!
+//! Covers:
+//! - The context listing exactly the mispredicted player in exactly the
+//!   frames whose prediction was wrong, for a scripted divergence.
+//! - The context being cleared by the next call that does not roll back.
+//! - No context unless `SessionBuilder::with_rollback_context` is set.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::rollback_pair::{Pair, PEER_HANDLE};
+use fortress_rollback::PlayerHandle;
+
+/// The host plays handles 0 and 2, the peer handle 1.
+fn start(rollback_context: bool) -> Pair {
+    Pair::with_second_host_player(|builder| builder.with_rollback_context(rollback_context))
+}
+
+#[test]
+fn context_lists_exactly_the_mispredicted_players_and_frames() {
+    let mut pair = start(true);
+    pair.predictable_rounds(10, 0);
+    assert_eq!(pair.host.current_rollback_context(), None);
+
+    // Only the peer's second and fourth inputs differ from the prediction.
+    let frames = pair.diverge(&[0, 5, 0, 7]);
+    pair.advance_host();
+
+    let context = pair
+        .host
+        .current_rollback_context()
+        .expect("the host rolled back");
+    assert_eq!(context.loaded_frame(), Some(frames[1]));
+    assert_eq!(context.first_frame(), frames[1]);
+    assert_eq!(context.last_frame(), frames[3]);
+    let listed: Vec<_> = context
+        .frames()
+        .map(|(frame, players)| (frame, players.to_vec()))
+        .collect();
+    let peer = vec![PlayerHandle::new(PEER_HANDLE)];
+    assert_eq!(
+        listed,
+        vec![
+            (frames[1], peer.clone()),
+            (frames[2], Vec::new()),
+            (frames[3], peer),
+        ]
+    );
+    assert!(context.is_mispredicted(frames[3], PlayerHandle::new(PEER_HANDLE)));
+    assert!(!context.is_mispredicted(frames[3], PlayerHandle::new(0)));
+    assert!(context.mispredicted_players(frames[0]).is_empty());
+
+    // The next call re-simulates nothing and clears the context.
+    pair.advance_host();
+    assert_eq!(pair.host.current_rollback_context(), None);
+}
+
+#[test]
+fn context_requires_opting_in() {
+    let mut pair = start(false);
+    pair.predictable_rounds(10, 0);
+    pair.diverge(&[0, 5]);
+    pair.advance_host();
+    assert!(pair.host.last_rollback_cause().is_some());
+    assert_eq!(pair.host.current_rollback_context(), None);
+}
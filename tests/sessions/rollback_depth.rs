@@ -0,0 +1,109 @@
+//! Integration tests for `SessionBuilder::with_max_rollback_depth`.
+//!
+//! Covers:
+//! - Corrections within the cap rolling back as usual.
+//! - A correction deeper than the cap skipping the rollback and emitting
+//!   `FortressEvent::RollbackTooDeep` exactly once, with the session running
+//!   on afterwards without violations.
+//! - Sessions without a cap rolling back as deep as the prediction window.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::rollback_pair::Pair;
+use fortress_rollback::telemetry::{CollectingObserver, ViolationSeverity};
+use fortress_rollback::FortressEvent;
+use std::sync::Arc;
+
+const MAX_PREDICTION: usize = 10;
+const CAP: usize = 6;
+
+/// Starts both peers with an optional rollback depth `cap`, reporting
+/// violations of either to the returned observer.
+fn start(cap: Option<usize>) -> (Pair, Arc<CollectingObserver>) {
+    let observer = Arc::new(CollectingObserver::new());
+    let pair = Pair::new(|builder| {
+        let builder = builder
+            .with_max_prediction_window(MAX_PREDICTION)
+            .with_violation_observer(observer.clone());
+        match cap {
+            Some(cap) => builder.with_max_rollback_depth(cap),
+            None => builder,
+        }
+    });
+    (pair, observer)
+}
+
+/// The host runs `depth` frames ahead predicting the peer's input 0, the
+/// peer then plays input 5 for each of them, and the host advances once
+/// more; returns whether that advance rolled back.
+fn diverge(pair: &mut Pair, depth: usize) -> bool {
+    pair.diverge(&vec![5; depth]);
+    pair.advance_host()
+}
+
+fn too_deep_events(pair: &mut Pair) -> Vec<(usize, usize)> {
+    pair.host
+        .events()
+        .filter_map(|event| match event {
+            FortressEvent::RollbackTooDeep {
+                required_depth,
+                cap,
+            } => Some((required_depth, cap)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn corrections_within_the_cap_roll_back() {
+    let (mut pair, observer) = start(Some(CAP));
+    assert_eq!(pair.host.max_rollback_depth(), CAP);
+    assert!(!pair.predictable_rounds(10, 0));
+
+    assert!(
+        diverge(&mut pair, CAP),
+        "a correction at the cap rolls back"
+    );
+    assert!(too_deep_events(&mut pair).is_empty());
+    assert!(observer.is_empty(), "{:?}", observer.violations());
+}
+
+#[test]
+fn deeper_corrections_emit_the_event_once_and_continue() {
+    let (mut pair, observer) = start(Some(CAP));
+    assert!(!pair.predictable_rounds(10, 0));
+
+    assert!(
+        !diverge(&mut pair, CAP + 2),
+        "the deep correction is skipped"
+    );
+    assert_eq!(too_deep_events(&mut pair), vec![(CAP + 2, CAP)]);
+
+    // The corrected inputs were accepted and are not reported again; the
+    // session keeps running, rolling back only for shallow corrections.
+    let frame = pair.host.current_frame();
+    pair.predictable_rounds(20, 0);
+    assert_eq!(pair.host.current_frame(), frame + 20);
+    assert!(too_deep_events(&mut pair).is_empty());
+    assert!(
+        !observer.has_severity(ViolationSeverity::Error),
+        "{:?}",
+        observer.violations()
+    );
+}
+
+#[test]
+fn without_a_cap_the_whole_prediction_window_rolls_back() {
+    let (mut pair, _) = start(None);
+    assert_eq!(pair.host.max_rollback_depth(), MAX_PREDICTION);
+    assert!(!pair.predictable_rounds(10, 0));
+
+    assert!(diverge(&mut pair, CAP + 2));
+    assert!(too_deep_events(&mut pair).is_empty());
+}
@@ -0,0 +1,155 @@
+//! Integration tests for `P2PSession::saved_frames`.
+//!
+//! Covers:
+//! - After a predicted stretch and the rollback that corrects it, the ring
+//!   lists exactly the last `max_prediction + 1` saved frames, oldest first,
+//!   each with the checksum of its most recent (re-simulated) save.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+
+use crate::common::{
+    create_channel_pair, drain_sync_events, poll_with_advance, synchronize_sessions_deterministic,
+    SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    Config, FortressError, FortressRequest, Frame, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, RequestVec, SavedFrameInfo, SessionBuilder,
+};
+
+const MAX_PREDICTION: usize = 4;
+
+#[derive(Debug)]
+struct CountConfig;
+
+impl Config for CountConfig {
+    type Input = u8;
+    type State = u64;
+    type Address = SocketAddr;
+}
+
+/// A running sum whose checksum is the sum itself, recording the checksum of
+/// every save by frame and counting loads.
+#[derive(Default)]
+struct Game {
+    state: u64,
+    saves: BTreeMap<i32, u128>,
+    loads: usize,
+}
+
+impl Game {
+    fn handle(&mut self, requests: RequestVec<CountConfig>) {
+        for request in requests {
+            match request {
+                FortressRequest::SaveGameState { cell, frame } => {
+                    let checksum = u128::from(self.state);
+                    cell.save(frame, Some(self.state), Some(checksum));
+                    self.saves.insert(frame.as_i32(), checksum);
+                },
+                FortressRequest::LoadGameState { cell, .. } => {
+                    self.state = cell.load().unwrap();
+                    self.loads += 1;
+                },
+                FortressRequest::AdvanceFrame { inputs, .. } => {
+                    for (input, _) in inputs.iter() {
+                        self.state = self.state * 10 + u64::from(*input);
+                    }
+                },
+            }
+        }
+    }
+}
+
+fn sessions() -> Result<(TestClock, P2PSession<CountConfig>, P2PSession<CountConfig>), FortressError>
+{
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let builder = || {
+        SessionBuilder::<CountConfig>::new()
+            .with_protocol_config(ProtocolConfig {
+                clock: Some(clock.as_protocol_clock()),
+                ..ProtocolConfig::default()
+            })
+            .with_max_prediction_window(MAX_PREDICTION)
+    };
+    let mut sess1 = builder()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(a2), PlayerHandle::new(1))?
+        .start_p2p_session(s1)?;
+    let mut sess2 = builder()
+        .add_player(PlayerType::Remote(a1), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(s2)?;
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    drain_sync_events(&mut sess1, &mut sess2);
+    Ok((clock, sess1, sess2))
+}
+
+fn step(session: &mut P2PSession<CountConfig>, game: &mut Game, handle: usize, input: u8) {
+    session
+        .add_local_input(PlayerHandle::new(handle), input)
+        .unwrap();
+    game.handle(session.advance_frame().unwrap());
+}
+
+#[test]
+fn saved_frames_after_rollback_match_the_latest_saves() -> Result<(), FortressError> {
+    let (clock, mut sess1, mut sess2) = sessions()?;
+    let (mut game1, mut game2) = (Game::default(), Game::default());
+    assert!(sess1.saved_frames().is_empty());
+    assert_eq!(sess1.max_prediction(), MAX_PREDICTION);
+
+    // Lockstep for frames 0..3.
+    for _ in 0..3 {
+        poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+        step(&mut sess1, &mut game1, 0, 1);
+        step(&mut sess2, &mut game2, 1, 1);
+    }
+    // Peer 1 runs frames 3..6 predicting peer 2 repeats its last input...
+    for _ in 0..3 {
+        step(&mut sess1, &mut game1, 0, 1);
+    }
+    // ...but peer 2 changes it, so peer 1 rolls back on its next frame.
+    for _ in 0..3 {
+        step(&mut sess2, &mut game2, 1, 2);
+    }
+    poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+    poll_with_advance(&mut sess1, &mut sess2, &clock, 1);
+    let loads = game1.loads;
+    step(&mut sess1, &mut game1, 0, 1);
+    assert_eq!(game1.loads, loads + 1, "peer 1 should roll back");
+    assert_eq!(sess1.current_frame(), Frame::new(7));
+
+    // Frame 7 is saved on the next advance, so the ring holds 2..=6. Each
+    // state appends the frame's two inputs as digits; frames 4..=6 were
+    // re-saved after the rollback with peer 2's real input (2, predicted to
+    // repeat for frame 6).
+    let expected: Vec<SavedFrameInfo> = [
+        (2, 1111),
+        (3, 11_1111),
+        (4, 1111_1112),
+        (5, 11_1111_1212),
+        (6, 1111_1112_1212),
+    ]
+    .into_iter()
+    .map(|(frame, checksum)| SavedFrameInfo {
+        frame: Frame::new(frame),
+        has_state: true,
+        checksum: Some(checksum),
+    })
+    .collect();
+    assert_eq!(sess1.saved_frames(), expected);
+    for info in &expected {
+        assert_eq!(info.checksum, Some(game1.saves[&info.frame.as_i32()]));
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+//! Integration tests for [`P2PSession::flush`], [`SpectatorSession::flush`],
+//! and the best-effort flush both sessions perform when dropped, over real
+//! loopback UDP sockets.
+//!
+//! Disconnecting a spectator queues its goodbye without sending it; the next
+//! poll would normally flush it. Dropping the host instead must still hand the
+//! goodbye to the socket, so the spectator learns about the disconnect at once
+//! rather than after its disconnect timeout.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
+
+use crate::common::stubs::StubConfig;
+use crate::common::synchronize_spectator;
+use fortress_rollback::{
+    FortressEvent, P2PSession, PlayerHandle, PlayerType, SessionBuilder, SpectatorSession,
+    UdpNonBlockingSocket,
+};
+use std::net::SocketAddr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Far longer than the test waits, so a `Disconnected` event can only come
+/// from the host's goodbye.
+const SPECTATOR_DISCONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+const GOODBYE_DEADLINE: Duration = Duration::from_secs(5);
+const SPECTATOR: PlayerHandle = PlayerHandle::new(1);
+
+fn loopback_socket() -> (UdpNonBlockingSocket, SocketAddr) {
+    let socket = UdpNonBlockingSocket::bind(([127, 0, 0, 1], 0).into()).unwrap();
+    let addr = socket.local_addr().unwrap();
+    (socket, addr)
+}
+
+/// A host with one local player and a synchronized spectator.
+fn host_and_spectator() -> (P2PSession<StubConfig>, SpectatorSession<StubConfig>) {
+    let (host_socket, host_addr) = loopback_socket();
+    let (spectator_socket, spectator_addr) = loopback_socket();
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)
+        .unwrap()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))
+        .unwrap()
+        .add_player(PlayerType::Spectator(spectator_addr), SPECTATOR)
+        .unwrap()
+        .start_p2p_session(host_socket)
+        .unwrap();
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)
+        .unwrap()
+        .with_disconnect_timeout(SPECTATOR_DISCONNECT_TIMEOUT)
+        .start_spectator_session(host_addr, spectator_socket)
+        .unwrap();
+    let result = synchronize_spectator(&mut spectator, &mut host);
+    assert!(result.success, "spectator should synchronize");
+    let _ = host.events().count();
+    let _ = spectator.events().count();
+    (host, spectator)
+}
+
+/// Polls the spectator until it reports the host disconnected, returning
+/// whether it did before [`GOODBYE_DEADLINE`].
+fn spectator_sees_disconnect(spectator: &mut SpectatorSession<StubConfig>) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < GOODBYE_DEADLINE {
+        spectator.poll_remote_clients();
+        if spectator
+            .events()
+            .any(|event| matches!(event, FortressEvent::Disconnected { .. }))
+        {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    false
+}
+
+#[test]
+#[cfg(not(miri))]
+fn dropping_the_host_delivers_the_queued_goodbye() {
+    let (mut host, mut spectator) = host_and_spectator();
+
+    host.disconnect_player(SPECTATOR).unwrap();
+    drop(host);
+
+    assert!(
+        spectator_sees_disconnect(&mut spectator),
+        "the goodbye queued before the drop never arrived"
+    );
+}
+
+#[test]
+#[cfg(not(miri))]
+fn flush_reports_the_messages_it_sends() {
+    let (mut host, mut spectator) = host_and_spectator();
+
+    host.disconnect_player(SPECTATOR).unwrap();
+    // Three redundant goodbyes, and nothing left for a second flush.
+    assert_eq!(host.flush(), 3);
+    assert_eq!(host.flush(), 0);
+    // The spectator's own poll already flushed everything it queued.
+    assert_eq!(spectator.flush(), 0);
+
+    assert!(
+        spectator_sees_disconnect(&mut spectator),
+        "the flushed goodbye never arrived"
+    );
+}
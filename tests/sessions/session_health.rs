@@ -0,0 +1,172 @@
+//! Session frame-rate health.
+//!
+//! Two players and a spectator run a loopback match under virtual time. One
+//! player stops calling `advance_frame` for over 500ms in the middle. Covers:
+//! - The longest advance gap and the achieved fps reflecting the stall, and
+//!   the other player counting the advances it was starved of inputs.
+//! - The values freezing once the match reaches its frame limit, on the
+//!   players and on the spectator whose stream ends with it.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_channel_mesh, ChannelSocket, TestClock};
+use fortress_rollback::{
+    FortressError, Frame, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+    SessionState, SpectatorSession,
+};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+const FPS: usize = 60;
+const STEP: Duration = Duration::from_millis(16);
+/// Steps player 0 skips, from `STALL_START`: a 33-step (528ms) gap between
+/// two of its advances.
+const STALL_STEPS: usize = 32;
+const STALL_START: usize = 100;
+const FRAME_LIMIT: i32 = 300;
+
+fn protocol_config(clock: &TestClock, peer: u64) -> ProtocolConfig {
+    ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::deterministic(40 + peer)
+    }
+}
+
+fn player(
+    local: usize,
+    addrs: &[SocketAddr],
+    socket: ChannelSocket,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_fps(FPS)?
+        .with_max_prediction_window(8)
+        .with_max_session_frames(Frame::new(FRAME_LIMIT))?
+        .with_protocol_config(protocol_config(clock, local as u64));
+    for (handle, addr) in addrs.iter().take(2).enumerate() {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(*addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    if local == 0 {
+        builder = builder.add_player(PlayerType::Spectator(addrs[2]), PlayerHandle::new(2))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+fn stalled(step: usize) -> bool {
+    (STALL_START..STALL_START + STALL_STEPS).contains(&step)
+}
+
+#[test]
+fn health_reflects_an_advance_stall_and_freezes_at_the_frame_limit() {
+    let clock = TestClock::new();
+    let (sockets, addrs) = create_channel_mesh(3);
+    let mut sockets = sockets.into_iter();
+    let mut players = [
+        player(0, &addrs, sockets.next().unwrap(), &clock).unwrap(),
+        player(1, &addrs, sockets.next().unwrap(), &clock).unwrap(),
+    ];
+    let mut spectator: SpectatorSession<StubConfig> = SessionBuilder::<StubConfig>::new()
+        .with_fps(FPS)
+        .unwrap()
+        .with_protocol_config(protocol_config(&clock, 2))
+        .start_spectator_session(addrs[0], sockets.next().unwrap())
+        .unwrap();
+    let mut games = [GameStub::new(), GameStub::new()];
+    let mut spectator_game = GameStub::new();
+    assert!(players[0].session_health().is_none());
+    assert!(spectator.session_health().is_none());
+
+    let mut finished = [false; 2];
+    let mut step = 0;
+    while !(finished[0] && finished[1] && spectator.stream_ended().is_some()) {
+        assert!(step < 2000, "the match never reached its frame limit");
+        clock.advance(STEP);
+        for (handle, session) in players.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            if session.current_state() != SessionState::Running
+                || finished[handle]
+                || (handle == 0 && stalled(step))
+            {
+                continue;
+            }
+            session
+                .add_local_input(PlayerHandle::new(handle), StubInput { inp: 1 })
+                .unwrap();
+            match session.advance_frame() {
+                Ok(requests) => games[handle].handle_requests(requests),
+                Err(FortressError::InvalidRequestStructured { .. }) => finished[handle] = true,
+                Err(err) => panic!("player {handle} failed to advance: {err}"),
+            }
+        }
+        spectator.poll_remote_clients();
+        if spectator.current_state() == SessionState::Running {
+            match spectator.advance_frame() {
+                Ok(requests) => spectator_game.handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => panic!("spectator failed to advance: {err}"),
+            }
+        }
+        step += 1;
+    }
+
+    let stalled_health = players[0].session_health().unwrap();
+    let steady_health = players[1].session_health().unwrap();
+    for health in [stalled_health, steady_health] {
+        assert_eq!(health.frames_advanced, FRAME_LIMIT as u64);
+        let fps = health.frames_advanced as f64 / health.running_duration.as_secs_f64();
+        assert!((health.achieved_fps - fps).abs() < 1e-9);
+    }
+    assert!(
+        (528..600).contains(&stalled_health.longest_advance_gap_ms),
+        "{stalled_health:?}"
+    );
+    assert!(
+        steady_health.longest_advance_gap_ms < 100,
+        "{steady_health:?}"
+    );
+    // One frame per 16ms step would be 62.5 fps, 300 frames in 4.8s; the
+    // stall holds both players back by about half a second more.
+    for health in [stalled_health, steady_health] {
+        assert!(
+            health.running_duration >= Duration::from_millis(5250),
+            "{health:?}"
+        );
+        assert!(health.achieved_fps < 57.2, "{health:?}");
+        assert!(health.achieved_fps > 50.0, "{health:?}");
+    }
+    assert!(
+        steady_health.starved_frames > 0,
+        "the player kept waiting on the stalled one: {steady_health:?}"
+    );
+
+    let spectator_health = spectator.session_health().unwrap();
+    assert_eq!(
+        spectator_health.frames_advanced,
+        spectator.current_frame().as_i32() as u64 + 1
+    );
+    assert!(spectator_health.achieved_fps > 0.0);
+
+    // Time moves on, but the finished sessions report the same values.
+    for _ in 0..20 {
+        clock.advance(Duration::from_millis(100));
+        for session in &mut players {
+            session.poll_remote_clients();
+        }
+        spectator.poll_remote_clients();
+    }
+    assert_eq!(players[0].session_health(), Some(stalled_health));
+    assert_eq!(players[1].session_health(), Some(steady_health));
+    assert_eq!(spectator.session_health(), Some(spectator_health));
+}
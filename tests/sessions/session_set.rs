@@ -0,0 +1,170 @@
+//! Integration tests for [`SessionSet`]: many P2P sessions, each on its own
+//! loopback socket, polled and advanced together.
+//!
+//! Covers:
+//! - `poll_all` reporting exactly the sessions whose peers sent traffic.
+//! - `advance_ready` only advancing sessions with queued local input, and
+//!   sharing a small budget round-robin across ready sessions.
+//! - `drain_events` tagging every event with its session's key.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+};
+use fortress_rollback::{
+    FortressError, FortressEvent, Frame, P2PSession, PlayerHandle, PlayerType, ProtocolConfig,
+    SessionBuilder, SessionSet,
+};
+
+const SESSIONS: u32 = 10;
+
+/// Hosts in a set keyed `0..SESSIONS`, and their guests outside it, all
+/// synchronized and with every sync message delivered.
+fn synchronized_set(
+    clock: &TestClock,
+) -> Result<(SessionSet<StubConfig, u32>, Vec<P2PSession<StubConfig>>), FortressError> {
+    let builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    });
+    let mut set = SessionSet::new();
+    let mut guests = Vec::new();
+    for key in 0..SESSIONS {
+        let (host_socket, guest_socket, host_addr, guest_addr) = create_channel_pair();
+        let mut host = builder.build_with_players(
+            &[
+                (PlayerType::Local, PlayerHandle::new(0)),
+                (PlayerType::Remote(guest_addr), PlayerHandle::new(1)),
+            ],
+            host_socket,
+        )?;
+        let mut guest = builder.build_with_players(
+            &[
+                (PlayerType::Remote(host_addr), PlayerHandle::new(0)),
+                (PlayerType::Local, PlayerHandle::new(1)),
+            ],
+            guest_socket,
+        )?;
+        synchronize_sessions_deterministic(&mut host, &mut guest, clock, &SyncConfig::default())?;
+        set.insert(key, host);
+        guests.push(guest);
+    }
+    // The clock stays put from here on, so once the last sync replies are
+    // delivered nothing is sent until a test sends it.
+    for _ in 0..4 {
+        set.poll_all();
+        for guest in &mut guests {
+            guest.poll_remote_clients();
+        }
+    }
+    assert_eq!(set.poll_all(), Vec::<u32>::new(), "the set should be idle");
+    Ok((set, guests))
+}
+
+#[test]
+fn poll_all_reports_only_sessions_with_traffic() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut set, mut guests) = synchronized_set(&clock)?;
+
+    let active = [2, 5, 8];
+    for key in active {
+        let guest = &mut guests[key as usize];
+        guest.add_local_input(PlayerHandle::new(1), StubInput { inp: key })?;
+        GameStub::new().handle_requests(guest.advance_frame()?);
+    }
+
+    assert_eq!(set.poll_all(), active.to_vec());
+    assert_eq!(set.poll_all(), Vec::<u32>::new());
+    Ok(())
+}
+
+#[test]
+fn advance_ready_skips_sessions_without_input() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut set, _guests) = synchronized_set(&clock)?;
+
+    for key in [1, 4] {
+        set.get_mut(&key)
+            .unwrap()
+            .add_local_input(PlayerHandle::new(0), StubInput { inp: key })?;
+    }
+
+    let advanced = set.advance_ready(SESSIONS as usize);
+    let keys: Vec<u32> = advanced.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, vec![1, 4]);
+    for (key, requests) in advanced {
+        GameStub::new().handle_requests(requests?);
+        assert_eq!(set.get(&key).unwrap().current_frame(), Frame::new(1));
+    }
+    assert!(set.advance_ready(SESSIONS as usize).is_empty());
+    Ok(())
+}
+
+#[test]
+fn advance_ready_shares_the_budget_round_robin() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut set, _guests) = synchronized_set(&clock)?;
+    let mut stubs: Vec<GameStub> = (0..SESSIONS).map(|_| GameStub::new()).collect();
+
+    // Every session stays ready: each advanced session gets its next input
+    // before the following call. A budget of 4 then walks through the set.
+    let mut needs_input: Vec<u32> = (0..SESSIONS).collect();
+    let mut turns = Vec::new();
+    for _ in 0..5 {
+        for &key in &needs_input {
+            set.get_mut(&key)
+                .unwrap()
+                .add_local_input(PlayerHandle::new(0), StubInput { inp: key })?;
+        }
+        let advanced = set.advance_ready(4);
+        let keys: Vec<u32> = advanced.iter().map(|(key, _)| *key).collect();
+        for (key, requests) in advanced {
+            stubs[key as usize].handle_requests(requests?);
+        }
+        needs_input.clone_from(&keys);
+        turns.push(keys);
+    }
+
+    assert_eq!(
+        turns,
+        vec![
+            vec![0, 1, 2, 3],
+            vec![4, 5, 6, 7],
+            vec![8, 9, 0, 1],
+            vec![2, 3, 4, 5],
+            vec![6, 7, 8, 9],
+        ]
+    );
+    // 20 advances over 10 sessions: two frames each.
+    for key in 0..SESSIONS {
+        assert_eq!(set.get(&key).unwrap().current_frame(), Frame::new(2));
+    }
+    Ok(())
+}
+
+#[test]
+fn drain_events_tags_events_with_their_session() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut set, _guests) = synchronized_set(&clock)?;
+
+    // Synchronization left `Synchronizing`/`Synchronized` events in every host.
+    let events = set.drain_events();
+    for key in 0..SESSIONS {
+        assert!(
+            events
+                .iter()
+                .any(|(k, event)| *k == key && matches!(event, FortressEvent::Synchronized { .. })),
+            "session {key} should report its synchronization"
+        );
+    }
+    assert!(set.drain_events().is_empty());
+    Ok(())
+}
@@ -12,14 +12,18 @@
     clippy::ip_constant
 )]
 
-use crate::common::stubs::{StubConfig, StubInput};
-use crate::common::{bind_socket_ephemeral, PortAllocator};
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    bind_socket_ephemeral, create_channel_pair, synchronize_spectator_deterministic, PortAllocator,
+    TestClock,
+};
 use fortress_rollback::{
-    Config, FortressError, InvalidRequestKind, PlayerHandle, PlayerType, Session, SessionBuilder,
-    SessionState,
+    Config, FortressError, Frame, InvalidRequestKind, PlayerHandle, PlayerType, ProtocolConfig,
+    Session, SessionBuilder, SessionState,
 };
 use serial_test::serial;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
 
 // ============================================================================
 // Helper: generic function that operates on any Session<T>
@@ -36,6 +40,29 @@ fn call_via_trait<T: Config>(session: &mut impl Session<T>) -> SessionState {
     session.current_state()
 }
 
+/// Drives one frame of a game loop through the trait alone: polls, drains
+/// events, feeds input when the session has a local player, and fulfills the
+/// requests. A spectator that has no confirmed input yet skips the frame.
+///
+/// Returns the session's `(current_frame, confirmed_frame)` afterwards.
+fn drive_frame(
+    session: &mut impl Session<StubConfig>,
+    stub: &mut GameStub,
+    inp: u32,
+) -> (Frame, Frame) {
+    session.poll_remote_clients();
+    let _event_count = session.events().count();
+    if let Ok(handle) = session.local_player_handle_required() {
+        session.add_local_input(handle, StubInput { inp }).unwrap();
+    }
+    match session.advance_frame() {
+        Ok(requests) => stub.handle_requests(requests),
+        Err(FortressError::PredictionThreshold) => {},
+        Err(err) => panic!("unexpected advance_frame error: {err:?}"),
+    }
+    (session.current_frame(), session.confirmed_frame())
+}
+
 // ============================================================================
 // P2PSession implements Session
 // ============================================================================
@@ -171,6 +198,84 @@ fn generic_function_works_with_all_session_types() -> Result<(), FortressError>
     Ok(())
 }
 
+// ============================================================================
+// A generic game loop tracks current and confirmed frames for all types
+// ============================================================================
+
+#[test]
+fn generic_driver_tracks_frames_for_p2p_spectator_and_synctest() -> Result<(), FortressError> {
+    // Arrange — a one-player host streaming to a spectator, and a synctest
+    let clock = TestClock::new();
+    let protocol_config = || ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let (socket1, socket2, host_addr, spec_addr) = create_channel_pair();
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config())
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(1))?
+        .start_p2p_session(socket1)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config())
+        .start_spectator_session(host_addr, socket2)
+        .expect("spectator session should start");
+    assert!(synchronize_spectator_deterministic(&mut spectator, &mut host, &clock).success);
+    let mut synctest = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_check_distance(2)
+        .start_synctest_session()?;
+
+    // Before any frame is simulated
+    assert_eq!(Session::current_frame(&host), Frame::new(0));
+    assert_eq!(Session::current_frame(&spectator), Frame::NULL);
+    assert_eq!(Session::confirmed_frame(&spectator), Frame::NULL);
+    assert_eq!(Session::current_frame(&synctest), Frame::new(0));
+    assert_eq!(Session::confirmed_frame(&synctest), Frame::NULL);
+
+    // Act — drive all three through the same generic loop
+    let (mut host_stub, mut spec_stub, mut sync_stub) =
+        (GameStub::new(), GameStub::new(), GameStub::new());
+    let mut last = [(Frame::NULL, Frame::NULL); 3];
+    for i in 0..30u32 {
+        let frames = [
+            drive_frame(&mut host, &mut host_stub, i),
+            drive_frame(&mut spectator, &mut spec_stub, 0),
+            drive_frame(&mut synctest, &mut sync_stub, i),
+        ];
+        // Assert — the documented invariants hold for every session type
+        for (k, name) in ["p2p", "spectator", "synctest"].into_iter().enumerate() {
+            let ((current, confirmed), (last_current, last_confirmed)) = (frames[k], last[k]);
+            assert!(confirmed <= current, "{name}: {confirmed} > {current}");
+            assert!(current >= last_current, "{name}: current frame went back");
+            assert!(
+                confirmed >= last_confirmed,
+                "{name}: confirmed frame went back"
+            );
+        }
+        last = frames;
+        clock.advance(Duration::from_millis(16));
+    }
+
+    // Assert — per-type semantics from the trait docs
+    assert_eq!(Session::current_frame(&host), Frame::new(30));
+    let (spec_current, spec_confirmed) = last[1];
+    assert_eq!(spec_confirmed, spec_current);
+    assert!(
+        !spec_current.is_null(),
+        "the spectator should have caught up"
+    );
+    assert_eq!(Session::current_frame(&synctest), Frame::new(30));
+    assert_eq!(Session::confirmed_frame(&synctest), Frame::new(28));
+    assert_eq!(
+        Session::confirmed_frame(&synctest),
+        synctest.confirmed_frame()
+    );
+    Ok(())
+}
+
 // ============================================================================
 // Spectator add_local_input returns NotSupported (via trait)
 // ============================================================================
@@ -0,0 +1,152 @@
+//! Integration tests for desync detection under `SaveMode::Sparse`.
+//!
+//! Covers:
+//! - A sparse-saving peer reporting one checksum per `interval` saved and
+//!   confirmed frames, each labeled with the frame it saved.
+//! - An every-frame peer comparing those off-grid reports against its own
+//!   saved cells, with every comparison matching.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_chaos_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    ChaosConfig, DesyncDetection, FortressError, FortressEvent, FortressRequest, MessageKind,
+    NonBlockingSocket, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SaveMode,
+    SessionBuilder,
+};
+
+/// Saved and confirmed frames per sparse report.
+const INTERVAL: u32 = 2;
+/// Confirmed frame both peers must reach.
+const END_FRAME: i32 = 240;
+/// Upper bound on advance rounds.
+const MAX_ROUNDS: usize = 2_000;
+
+fn session(
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+    save_mode: SaveMode,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_desync_detection_mode(DesyncDetection::On { interval: INTERVAL })
+        .with_save_mode(save_mode)
+        .with_protocol_config(ProtocolConfig {
+            clock: Some(clock.as_protocol_clock()),
+            ..ProtocolConfig::default()
+        });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+#[test]
+fn sparse_reports_are_labeled_with_saved_frames_and_match_every_frame_peer() {
+    let clock = TestClock::new();
+    // Latency keeps the confirmed frame behind, so the sparse peer saves only
+    // every few frames.
+    let chaos = |latency_ms, seed| {
+        ChaosConfig::builder()
+            .latency_ms(latency_ms)
+            .seed(seed)
+            .build()
+    };
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(30, 1), chaos(70, 2), &clock);
+    let mut sessions = [
+        session(s1, 0, a2, &clock, SaveMode::Sparse).unwrap(),
+        session(s2, 1, a1, &clock, SaveMode::EveryFrame).unwrap(),
+    ];
+    let [sparse, every_frame] = &mut sessions;
+    synchronize_sessions_deterministic(sparse, every_frame, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut sparse_saves = BTreeSet::new();
+    let mut desyncs = 0;
+    for _ in 0..MAX_ROUNDS {
+        if sessions
+            .iter()
+            .all(|session| session.confirmed_frame().as_i32() >= END_FRAME)
+        {
+            break;
+        }
+        for (handle, (session, stub)) in sessions.iter_mut().zip(stubs.iter_mut()).enumerate() {
+            session.poll_remote_clients();
+            // Inputs change rarely, so few rollbacks save extra checkpoints.
+            let inp = session.current_frame().as_i32() as u32 / 50 + handle as u32;
+            session
+                .add_local_input(PlayerHandle::new(handle), StubInput { inp })
+                .unwrap();
+            let requests = session.advance_frame().unwrap();
+            if handle == 0 {
+                for request in &requests {
+                    if let FortressRequest::SaveGameState { frame, .. } = request {
+                        sparse_saves.insert(frame.as_i32());
+                    }
+                }
+            }
+            stub.handle_requests(requests);
+            desyncs += session
+                .events()
+                .filter(|event| matches!(event, FortressEvent::DesyncDetected { .. }))
+                .count();
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    let [sparse, every_frame] = &sessions;
+    assert!(sparse.confirmed_frame().as_i32() >= END_FRAME);
+    assert_eq!(desyncs, 0);
+
+    // One report per INTERVAL saved and confirmed frames. The newest saves
+    // may not be confirmed or counted yet.
+    let confirmed_saves = sparse_saves
+        .iter()
+        .filter(|&&frame| frame <= sparse.confirmed_frame().as_i32())
+        .count() as u64;
+    let reports = sparse
+        .peer_metrics(PlayerHandle::new(1))
+        .unwrap()
+        .messages_sent_by_kind
+        .get(MessageKind::ChecksumReport);
+    assert!(
+        confirmed_saves >= 2 * u64::from(INTERVAL) && confirmed_saves < END_FRAME as u64 / 2,
+        "{confirmed_saves} saved frames should be sparse"
+    );
+    assert!(
+        reports + 1 >= confirmed_saves / u64::from(INTERVAL)
+            && reports <= confirmed_saves / u64::from(INTERVAL),
+        "{reports} reports for {confirmed_saves} saved and confirmed frames"
+    );
+
+    // The every-frame peer checks the reports against its own cells for the
+    // labeled frames, so a mislabeled report would mismatch.
+    let metrics = every_frame.metrics();
+    assert_eq!(metrics.checksums_mismatched, 0);
+    assert!(
+        metrics.checksums_matched + 1 >= reports,
+        "{} of {reports} sparse reports matched",
+        metrics.checksums_matched
+    );
+    assert_eq!(sparse.metrics().checksums_mismatched, 0);
+}
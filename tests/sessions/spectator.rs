@@ -1967,7 +1967,10 @@ fn test_catchup_with_rewind_saves_correct_frames() -> Result<(), FortressError>
         ));
         reference.handle_requests({
             let mut r = RequestVec::<StubConfig>::new();
-            r.push(FortressRequest::AdvanceFrame { inputs });
+            r.push(FortressRequest::AdvanceFrame {
+                inputs,
+                previous_inputs: InputVec::new(),
+            });
             r
         });
     }
@@ -2117,7 +2120,7 @@ fn test_multi_host_inputs_confirmed_and_monotonic() -> Result<(), FortressError>
         host2.poll_remote_clients();
         if let Some(requests) = advance_frame_allowing_prediction_threshold(spec.advance_frame()) {
             for request in requests.iter() {
-                if let FortressRequest::AdvanceFrame { inputs } = request {
+                if let FortressRequest::AdvanceFrame { inputs, .. } = request {
                     for (_input, status) in inputs.iter() {
                         assert_eq!(
                             *status,
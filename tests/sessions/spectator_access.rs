@@ -0,0 +1,204 @@
+//! Spectator access tokens ([`SpectatorConfig::access_token`] and
+//! [`SessionBuilder::with_spectator_token`]).
+//!
+//! Covers:
+//! - A spectator proving the host's token synchronizes and plays the match.
+//! - A spectator with a wrong or missing token never hears from the host, not
+//!   even a `SyncReply`, and every ignored request is counted in
+//!   [`PeerMetrics::unauthorized_sync_requests`].
+//! - Player connections synchronize regardless of the host's token.
+//!
+//! [`PeerMetrics::unauthorized_sync_requests`]: fortress_rollback::metrics::PeerMetrics::unauthorized_sync_requests
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    assert_spectator_synchronized, create_channel_pair, synchronize_sessions_deterministic,
+    synchronize_spectator_deterministic, ChannelSocket, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::metrics::MessageKind;
+use fortress_rollback::{
+    FortressError, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+    SessionState, SpectatorConfig, SpectatorSession,
+};
+use std::net::SocketAddr;
+
+const TOKEN: [u8; 16] = *b"correct horse 16";
+const SPECTATOR: PlayerHandle = PlayerHandle::new(2);
+/// Polls given to a spectator that should never synchronize; many sync
+/// retry intervals.
+const REJECTED_POLLS: usize = 200;
+
+fn protocol_config(clock: &TestClock) -> ProtocolConfig {
+    ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    }
+}
+
+fn host(
+    socket: ChannelSocket,
+    spectator_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    SessionBuilder::<StubConfig>::new()
+        .with_num_players(2)?
+        .with_protocol_config(protocol_config(clock))
+        .with_spectator_config(SpectatorConfig {
+            access_token: Some(TOKEN),
+            ..SpectatorConfig::default()
+        })
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_player(PlayerType::Spectator(spectator_addr), SPECTATOR)?
+        .start_p2p_session(socket)
+}
+
+fn spectator(
+    socket: ChannelSocket,
+    host_addr: SocketAddr,
+    token: Option<[u8; 16]>,
+    clock: &TestClock,
+) -> SpectatorSession<StubConfig> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_num_players(2)
+        .unwrap()
+        .with_protocol_config(protocol_config(clock));
+    if let Some(token) = token {
+        builder = builder.with_spectator_token(token);
+    }
+    builder
+        .start_spectator_session(host_addr, socket)
+        .expect("spectator session should start")
+}
+
+#[test]
+fn spectator_with_the_token_synchronizes_and_plays() {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let mut host = host(host_socket, spec_addr, &clock).unwrap();
+    let mut spectator = spectator(spec_socket, host_addr, Some(TOKEN), &clock);
+
+    let sync_result = synchronize_spectator_deterministic(&mut spectator, &mut host, &clock);
+    assert_spectator_synchronized(&spectator, &host, &sync_result);
+
+    let (mut host_stub, mut spec_stub) = (GameStub::new(), GameStub::new());
+    for frame in 0..30_u32 {
+        for player in 0..2 {
+            host.add_local_input(PlayerHandle::new(player), StubInput { inp: frame })
+                .unwrap();
+        }
+        host_stub.handle_requests(host.advance_frame().unwrap());
+        host.poll_remote_clients();
+        spectator.poll_remote_clients();
+        match spectator.advance_frame() {
+            Ok(requests) => spec_stub.handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => panic!("spectator failed to advance: {err}"),
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    assert!(spectator.current_frame().as_i32() > 0);
+    assert_eq!(
+        host.peer_metrics(SPECTATOR)
+            .unwrap()
+            .unauthorized_sync_requests,
+        0
+    );
+}
+
+fn assert_rejected(token: Option<[u8; 16]>) {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let mut host = host(host_socket, spec_addr, &clock).unwrap();
+    let mut spectator = spectator(spec_socket, host_addr, token, &clock);
+
+    for _ in 0..REJECTED_POLLS {
+        spectator.poll_remote_clients();
+        host.poll_remote_clients();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    assert_eq!(spectator.current_state(), SessionState::Synchronizing);
+    let spectator_view = spectator.peer_metrics(0).unwrap();
+    assert_eq!(
+        spectator_view
+            .messages_received_by_kind
+            .get(MessageKind::SyncReply),
+        0,
+        "the host must never answer an unproved handshake"
+    );
+    assert_eq!(
+        spectator_view.packets_received, 0,
+        "the host must stay silent"
+    );
+
+    let host_view = host.peer_metrics(SPECTATOR).unwrap();
+    assert_eq!(host_view.packets_sent, 0);
+    assert!(host_view.unauthorized_sync_requests > 0);
+    assert_eq!(
+        host_view.unauthorized_sync_requests,
+        host_view
+            .messages_received_by_kind
+            .get(MessageKind::SyncRequest),
+        "every request was counted"
+    );
+}
+
+#[test]
+fn spectator_with_a_wrong_token_never_receives_a_sync_reply() {
+    let mut wrong = TOKEN;
+    wrong[0] ^= 1;
+    assert_rejected(Some(wrong));
+}
+
+#[test]
+fn spectator_without_a_token_never_receives_a_sync_reply() {
+    assert_rejected(None);
+}
+
+#[test]
+fn player_connections_ignore_the_spectator_access_token() {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let session = |socket, local: usize, remote_addr| {
+        SessionBuilder::<StubConfig>::new()
+            .with_protocol_config(protocol_config(&clock))
+            .with_spectator_config(SpectatorConfig {
+                access_token: Some(TOKEN),
+                ..SpectatorConfig::default()
+            })
+            .add_player(PlayerType::Local, PlayerHandle::new(local))
+            .unwrap()
+            .add_player(
+                PlayerType::Remote(remote_addr),
+                PlayerHandle::new(1 - local),
+            )
+            .unwrap()
+            .start_p2p_session(socket)
+            .unwrap()
+    };
+    let mut sess1 = session(s1, 0, a2);
+    let mut sess2 = session(s2, 1, a1);
+
+    synchronize_sessions_deterministic(&mut sess1, &mut sess2, &clock, &SyncConfig::default())
+        .expect("players should synchronize regardless of the spectator token");
+    for (session, remote) in [(&sess1, 1), (&sess2, 0)] {
+        assert_eq!(
+            session
+                .peer_metrics(PlayerHandle::new(remote))
+                .unwrap()
+                .unauthorized_sync_requests,
+            0
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Spectator checksum arbitration ([`SpectatorConfig::checksum_authority`]).
+//!
+//! Three peers play one match and each registers the same spectator, which
+//! follows all three with `start_spectator_session_multi`. One peer saves a
+//! wrong checksum from a given frame on. Peer-to-peer desync detection only
+//! tells each pair that they disagree; the verifying spectator compares every
+//! peer's report with its own simulation and must name the diverging peer,
+//! and only that peer.
+
+// Allow test-specific patterns that are appropriate for test code
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{CorruptibleGameStub, GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_quad, ChannelSocket, TestClock, MAX_SYNC_ITERATIONS, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    DesyncDetection, FortressError, FortressEvent, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, SessionState, SpectatorConfig, SpectatorSession,
+};
+use std::net::SocketAddr;
+
+const PLAYERS: usize = 3;
+/// Frames each peer simulates.
+const FRAMES: u32 = 120;
+/// Frame from which the diverging peer saves a wrong checksum.
+const DIVERGE_FROM: i32 = 40;
+/// Checksum report interval on every peer.
+const INTERVAL: u32 = 10;
+
+fn protocol_config(clock: &TestClock) -> ProtocolConfig {
+    ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    }
+}
+
+fn peer(
+    local: usize,
+    addrs: &[SocketAddr; 3],
+    spectator: SocketAddr,
+    socket: ChannelSocket,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config(clock))
+        .with_desync_detection_mode(DesyncDetection::On { interval: INTERVAL });
+    for (handle, addr) in addrs.iter().enumerate() {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(*addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder
+        .add_player(PlayerType::Spectator(spectator), PlayerHandle::new(PLAYERS))?
+        .start_p2p_session(socket)
+}
+
+/// Runs the match with `diverging` saving wrong checksums and returns every
+/// verdict the spectator emitted, with the peer addresses.
+fn run(
+    checksum_authority: bool,
+    diverging: usize,
+) -> Result<(Vec<FortressEvent<StubConfig>>, [SocketAddr; 3]), FortressError> {
+    let clock = TestClock::new();
+    let (s1, s2, s3, s4, a1, a2, a3, a4) = create_channel_quad();
+    let addrs = [a1, a2, a3];
+    let mut peers = [
+        peer(0, &addrs, a4, s1, &clock)?,
+        peer(1, &addrs, a4, s2, &clock)?,
+        peer(2, &addrs, a4, s3, &clock)?,
+    ];
+    let mut spectator: SpectatorSession<StubConfig> = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config(&clock))
+        .with_spectator_config(SpectatorConfig {
+            buffer_size: 120,
+            checksum_authority,
+            ..SpectatorConfig::default()
+        })
+        .start_spectator_session_multi(&addrs, s4)
+        .expect("spectator session should start");
+
+    for _ in 0..MAX_SYNC_ITERATIONS {
+        for peer in &mut peers {
+            peer.poll_remote_clients();
+        }
+        spectator.poll_remote_clients();
+        if peers
+            .iter()
+            .all(|peer| peer.current_state() == SessionState::Running)
+            && spectator.current_state() == SessionState::Running
+        {
+            break;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    assert_eq!(spectator.current_state(), SessionState::Running);
+
+    let mut games: Vec<CorruptibleGameStub> = (0..PLAYERS)
+        .map(|index| {
+            if index == diverging {
+                CorruptibleGameStub::with_corruption_from(DIVERGE_FROM)
+            } else {
+                CorruptibleGameStub::new()
+            }
+        })
+        .collect();
+    let mut spectator_game = GameStub::new();
+    let mut verdicts = Vec::new();
+    let mut collect = |spectator: &mut SpectatorSession<StubConfig>| {
+        verdicts.extend(
+            spectator
+                .events()
+                .filter(|event| matches!(event, FortressEvent::AuthoritativeDesyncVerdict { .. })),
+        );
+    };
+
+    // The last frames only let the reports and the spectator catch up.
+    for frame in 0..FRAMES + 40 {
+        if frame < FRAMES {
+            for (index, peer) in peers.iter_mut().enumerate() {
+                peer.add_local_input(
+                    PlayerHandle::new(index),
+                    StubInput {
+                        inp: frame + index as u32,
+                    },
+                )?;
+            }
+            for (peer, game) in peers.iter_mut().zip(&mut games) {
+                match peer.advance_frame() {
+                    Ok(requests) => game.handle_requests(requests),
+                    Err(FortressError::PredictionThreshold) => {},
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        for peer in &mut peers {
+            peer.poll_remote_clients();
+        }
+        match spectator.advance_frame() {
+            Ok(requests) => spectator_game.handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => return Err(err),
+        }
+        collect(&mut spectator);
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    Ok((verdicts, addrs))
+}
+
+#[test]
+fn verdict_names_only_the_diverging_peer() -> Result<(), FortressError> {
+    for diverging in 0..PLAYERS {
+        let (verdicts, addrs) = run(true, diverging)?;
+
+        // Every reported frame from the divergence on is judged once.
+        let frames: Vec<i32> = verdicts
+            .iter()
+            .map(|verdict| {
+                let FortressEvent::AuthoritativeDesyncVerdict { frame, mismatching } = verdict
+                else {
+                    unreachable!();
+                };
+                assert_eq!(
+                    mismatching,
+                    &vec![addrs[diverging]],
+                    "peer {diverging} diverged; verdict at frame {frame}"
+                );
+                frame.as_i32()
+            })
+            .collect();
+        let expected: Vec<i32> = (DIVERGE_FROM..i32::try_from(FRAMES).unwrap())
+            .step_by(INTERVAL as usize)
+            .collect();
+        assert_eq!(frames, expected, "peer {diverging} diverged");
+    }
+    Ok(())
+}
+
+#[test]
+fn verdicts_require_checksum_authority() -> Result<(), FortressError> {
+    let (verdicts, _) = run(false, 1)?;
+    assert!(verdicts.is_empty(), "unexpected verdicts: {verdicts:?}");
+    Ok(())
+}
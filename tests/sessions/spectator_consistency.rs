@@ -0,0 +1,213 @@
+//! The spectator consistency guarantee.
+//!
+//! Two players roll back constantly over lossy, jittery `ChaosSocket` links
+//! under virtual time while player 0 forwards the match to a spectator. Covers:
+//! - The spectator's input stream being append-only: every frame is simulated
+//!   once, in order, and nothing is ever loaded back.
+//! - Every frame the spectator simulated having the inputs and the state both
+//!   players ended up with once the frame was confirmed.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{StateStub, StubConfig, StubInput};
+use crate::common::{create_chaos_channel_mesh, ChannelSocket, TestClock};
+use fortress_rollback::hash::fnv1a_hash;
+use fortress_rollback::telemetry::{CollectingObserver, ViolationObserver};
+use fortress_rollback::{
+    ChaosConfig, ChaosSocket, FortressError, FortressRequest, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, RequestVec, SessionBuilder, SessionState, SpectatorSession,
+};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SEED: u64 = 7;
+const STEPS: usize = 1500;
+
+/// What a peer simulated for one frame: the inputs and the checksum of the
+/// state they produced.
+type FrameRecord = (Vec<u32>, u64);
+
+/// Each peer gets its own seed so connection IDs differ.
+fn protocol_config(clock: &TestClock, peer: u64) -> ProtocolConfig {
+    ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::deterministic(SEED + peer)
+    }
+}
+
+fn player(
+    local: usize,
+    addrs: &[SocketAddr],
+    socket: ChaosSocket<SocketAddr, ChannelSocket>,
+    clock: &TestClock,
+    observer: &Arc<CollectingObserver>,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_max_prediction_window(8)
+        .with_protocol_config(protocol_config(clock, local as u64))
+        .with_violation_observer(Arc::clone(observer) as Arc<dyn ViolationObserver>);
+    for (handle, addr) in addrs.iter().take(2).enumerate() {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(*addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    if local == 0 {
+        builder = builder.add_player(PlayerType::Spectator(addrs[2]), PlayerHandle::new(2))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+/// Fulfills `requests` on `state`, recording every advanced frame into
+/// `frames` (a resimulation overwrites the frame's earlier record). Returns
+/// the number of loads.
+fn fulfill(
+    state: &mut StateStub,
+    requests: RequestVec<StubConfig>,
+    frames: &mut BTreeMap<i32, FrameRecord>,
+) -> usize {
+    let mut loads = 0;
+    for request in requests {
+        match request {
+            FortressRequest::SaveGameState { cell, frame } => {
+                assert_eq!(state.frame, frame.as_i32());
+                cell.save(frame, Some(*state), Some(u128::from(fnv1a_hash(&*state))));
+            },
+            FortressRequest::LoadGameState { cell, .. } => {
+                *state = cell.load().unwrap();
+                loads += 1;
+            },
+            FortressRequest::AdvanceFrame { inputs, .. } => {
+                let values = inputs.iter().map(|(input, _)| input.inp).collect();
+                state.advance_frame_pub(inputs);
+                frames.insert(state.frame, (values, fnv1a_hash(&*state)));
+            },
+        }
+    }
+    loads
+}
+
+/// The input a player gives at `step`, varied enough to force rollbacks.
+fn input(step: usize, handle: usize) -> StubInput {
+    StubInput {
+        inp: ((step / 3 + handle * 7) % 5) as u32,
+    }
+}
+
+#[test]
+fn spectator_stream_is_append_only_and_matches_confirmed_results_under_rollbacks() {
+    let clock = TestClock::new();
+    let observer = Arc::new(CollectingObserver::new());
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .latency_ms(30)
+            .jitter_ms(25)
+            .packet_loss_rate(0.1)
+            .seed(seed)
+            .build()
+    };
+    let (sockets, addrs) = create_chaos_channel_mesh(vec![chaos(1), chaos(2), chaos(3)], &clock);
+    let mut sockets = sockets.into_iter();
+    let mut players = [
+        player(0, &addrs, sockets.next().unwrap(), &clock, &observer).unwrap(),
+        player(1, &addrs, sockets.next().unwrap(), &clock, &observer).unwrap(),
+    ];
+    let mut spectator: SpectatorSession<StubConfig> = SessionBuilder::<StubConfig>::new()
+        .with_max_prediction_window(8)
+        .with_protocol_config(protocol_config(&clock, 2))
+        .with_violation_observer(Arc::clone(&observer) as Arc<dyn ViolationObserver>)
+        .start_spectator_session(addrs[0], sockets.next().unwrap())
+        .unwrap();
+
+    let mut player_states = [StateStub::default(); 2];
+    let mut player_frames: [BTreeMap<i32, FrameRecord>; 2] = [BTreeMap::new(), BTreeMap::new()];
+    let mut rollbacks = 0;
+    let mut spectator_state = StateStub::default();
+    let mut spectator_frames = BTreeMap::new();
+
+    for step in 0..STEPS {
+        clock.advance(Duration::from_millis(16));
+        for (handle, session) in players.iter_mut().enumerate() {
+            session.poll_remote_clients();
+            if session.current_state() != SessionState::Running {
+                continue;
+            }
+            session
+                .add_local_input(PlayerHandle::new(handle), input(step, handle))
+                .unwrap();
+            match session.advance_frame() {
+                Ok(requests) => {
+                    rollbacks += fulfill(
+                        &mut player_states[handle],
+                        requests,
+                        &mut player_frames[handle],
+                    );
+                },
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => panic!("player {handle} failed to advance: {err}"),
+            }
+        }
+
+        spectator.poll_remote_clients();
+        if spectator.current_state() != SessionState::Running {
+            continue;
+        }
+        match spectator.advance_frame() {
+            Ok(requests) => {
+                let mut advanced = BTreeMap::new();
+                let loads = fulfill(&mut spectator_state, requests, &mut advanced);
+                assert_eq!(loads, 0, "the spectator never rolls back");
+                for (frame, record) in advanced {
+                    let last = spectator_frames.keys().next_back().copied().unwrap_or(0);
+                    assert_eq!(frame, last + 1, "frames are simulated once, in order");
+                    spectator_frames.insert(frame, record);
+                }
+            },
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => panic!("spectator failed to advance: {err}"),
+        }
+    }
+
+    assert!(rollbacks > 100, "only {rollbacks} rollbacks");
+    let confirmed = players
+        .iter()
+        .map(|session| session.confirmed_frame().as_i32())
+        .min()
+        .unwrap();
+    let watched: Vec<i32> = spectator_frames
+        .keys()
+        .copied()
+        // A record is keyed by the frame reached; its inputs belong to the
+        // frame before it.
+        .filter(|frame| frame - 1 <= confirmed)
+        .collect();
+    assert!(
+        watched.len() > 1000,
+        "only {} frames watched",
+        watched.len()
+    );
+    for frame in watched {
+        for (handle, frames) in player_frames.iter().enumerate() {
+            assert_eq!(
+                spectator_frames.get(&frame),
+                frames.get(&frame),
+                "spectator and player {handle} disagree on frame {frame}"
+            );
+        }
+    }
+    assert!(
+        observer.is_empty(),
+        "no violation expected: {:?}",
+        observer.violations()
+    );
+}
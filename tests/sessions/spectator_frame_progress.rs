@@ -0,0 +1,159 @@
+//! Integration tests for `SpectatorSession::frame_progress`.
+//!
+//! A host configured for 30 fps but ticking at a jittered 25 Hz feeds a
+//! spectator that renders at 60 Hz and probes its progress every few
+//! milliseconds in between, all under virtual time. Covers:
+//! - The fraction growing monotonically within a frame and resetting to 0.0
+//!   when the next frame is simulated.
+//! - The smoothed frame interval converging to the host's actual cadence
+//!   rather than the configured fps.
+//! - No progress before the first simulated frame.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::float_cmp
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    assert_spectator_synchronized, create_channel_pair, synchronize_spectator_deterministic,
+    TestClock,
+};
+use fortress_rollback::{
+    FortressError, Frame, P2PSession, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder,
+    SpectatorSession,
+};
+use std::time::Duration;
+
+/// Host tick interval in microseconds (25 Hz, slower than the configured fps).
+const HOST_TICK_US: u64 = 40_000;
+/// Largest deviation of one host tick from [`HOST_TICK_US`].
+const JITTER_US: u64 = 8_000;
+/// Spectator render interval in microseconds (60 Hz).
+const RENDER_US: u64 = 16_667;
+/// Interval between progress probes in microseconds.
+const PROBE_US: u64 = 2_000;
+/// Virtual time the match runs for, in microseconds.
+const DURATION_US: u64 = 10_000_000;
+
+fn sessions(
+    clock: &TestClock,
+) -> Result<(P2PSession<StubConfig>, SpectatorSession<StubConfig>), FortressError> {
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_fps(30)?
+        .with_protocol_config(protocol_config.clone())
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(2))?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_fps(30)?
+        .with_protocol_config(protocol_config)
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let sync_result = synchronize_spectator_deterministic(&mut spectator, &mut host, clock);
+    assert_spectator_synchronized(&spectator, &host, &sync_result);
+    Ok((host, spectator))
+}
+
+/// A deterministic host tick interval within [`JITTER_US`] of
+/// [`HOST_TICK_US`].
+fn jittered_tick(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    HOST_TICK_US - JITTER_US + *state % (2 * JITTER_US + 1)
+}
+
+#[test]
+fn fraction_is_monotonic_within_a_frame_and_resets_at_boundaries() {
+    let clock = TestClock::new();
+    let (mut host, mut spectator) = sessions(&clock).unwrap();
+    assert_eq!(spectator.frame_progress(), None);
+
+    let mut host_stub = GameStub::new();
+    let mut spec_stub = GameStub::new();
+    let mut rng = 0x2545_f491_4f6c_dd1d_u64;
+    let mut now_us = 0;
+    let mut next_host_us = 0;
+    let mut next_render_us = 0;
+    let mut next_probe_us = 0;
+    let mut last: Option<(Frame, f64)> = None;
+    let mut frames_seen = 0;
+
+    while now_us < DURATION_US {
+        let next_us = next_host_us.min(next_render_us).min(next_probe_us);
+        clock.advance(Duration::from_micros(next_us - now_us));
+        now_us = next_us;
+
+        if now_us == next_host_us {
+            for handle in 0..2 {
+                host.add_local_input(PlayerHandle::new(handle), StubInput { inp: handle as u32 })
+                    .unwrap();
+            }
+            host_stub.handle_requests(host.advance_frame().unwrap());
+            host.poll_remote_clients();
+            next_host_us += jittered_tick(&mut rng);
+        }
+
+        let mut rendered = false;
+        if now_us == next_render_us {
+            match spectator.advance_frame() {
+                Ok(requests) => {
+                    rendered = !requests.is_empty();
+                    spec_stub.handle_requests(requests);
+                },
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => panic!("spectator advance failed: {err}"),
+            }
+            next_render_us += RENDER_US;
+        }
+        if now_us == next_probe_us {
+            next_probe_us += PROBE_US;
+        }
+
+        let Some(progress) = spectator.frame_progress() else {
+            continue;
+        };
+        assert!((0.0..=1.0).contains(&progress.fraction), "{progress:?}");
+        assert_eq!(progress.frame, spectator.current_frame());
+        match last {
+            Some((frame, fraction)) if frame == progress.frame => {
+                assert!(!rendered);
+                assert!(
+                    progress.fraction >= fraction,
+                    "fraction went back within {frame}: {fraction} -> {}",
+                    progress.fraction
+                );
+            },
+            _ => {
+                assert!(rendered, "frames only change when the spectator advances");
+                assert_eq!(progress.fraction, 0.0, "{progress:?}");
+                assert!(progress.next_frame_at > clock.now());
+                frames_seen += 1;
+            },
+        }
+        last = Some((progress.frame, progress.fraction));
+    }
+
+    assert!(frames_seen > 200, "only {frames_seen} frames simulated");
+    let interval_ms = spectator
+        .frame_progress()
+        .unwrap()
+        .frame_interval
+        .as_secs_f64()
+        * 1000.0;
+    assert!(
+        (interval_ms - 40.0).abs() < 3.0,
+        "smoothed interval {interval_ms}ms should follow the 25 Hz host"
+    );
+}
@@ -0,0 +1,193 @@
+//! Keyframe input compression for the host-to-spectator stream
+//! ([`SpectatorConfig::keyframe_interval`]) end-to-end tests.
+//!
+//! An 8-player host feeds one spectator over in-memory sockets under virtual
+//! time. The spectator only polls every few host frames, so each `Input`
+//! message batches several unacknowledged frames, as it would over a real
+//! round trip. The tests check the observable contract: mostly idle inputs
+//! cost far fewer spectator-bound input bytes with the mode negotiated, the
+//! spectator reaches the same state as the host either way, and the mode stays
+//! off unless both sides opt in.
+//!
+//! The wire format and the handshake negotiation are asserted at the unit
+//! level in `network::compression` and `network::protocol`.
+
+// Allow test-specific patterns that are appropriate for test code
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    assert_spectator_synchronized, calculate_hash, create_channel_pair,
+    synchronize_spectator_deterministic, TestClock, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    FortressError, PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, SpectatorConfig,
+    SpectatorSession,
+};
+use std::collections::BTreeMap;
+
+/// Players on the host.
+const PLAYERS: usize = 8;
+/// Frames the host simulates per run.
+const FRAMES: u32 = 240;
+/// Host frames between spectator polls (the simulated round trip).
+const SPECTATOR_POLL_EVERY: u32 = 10;
+/// Keyframe interval used when the mode is enabled.
+const KEYFRAME_INTERVAL: usize = 60;
+/// Extra host frames that let the spectator catch up past [`FRAMES`].
+const SETTLE_FRAMES: u32 = 30;
+
+/// Outcome of one host/spectator run.
+struct RunOutcome {
+    /// Encoded input payload bytes the host sent to the spectator.
+    input_bytes_sent: u64,
+    /// Host state after frame [`FRAMES`].
+    host: StateStub,
+    /// Spectator state after frame [`FRAMES`].
+    spectator: StateStub,
+}
+
+/// Mostly idle inputs: every player holds its input and changes it once every
+/// 40 frames, staggered so a change lands somewhere every 5 frames.
+fn idle_input(player: usize, frame: u32) -> u32 {
+    (frame + 5 * player as u32) / 40
+}
+
+/// Runs an 8-player host with one spectator for [`FRAMES`] frames, with the
+/// given keyframe interval on each side.
+fn run(host_interval: usize, spectator_interval: usize) -> Result<RunOutcome, FortressError> {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let spectator_config = |keyframe_interval| SpectatorConfig {
+        buffer_size: 120,
+        max_frames_behind: 60,
+        keyframe_interval,
+        ..SpectatorConfig::default()
+    };
+
+    let mut builder = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config.clone())
+        .with_spectator_config(spectator_config(host_interval));
+    for player in 0..PLAYERS {
+        builder = builder.add_player(PlayerType::Local, PlayerHandle::new(player))?;
+    }
+    let spectator_handle = PlayerHandle::new(PLAYERS);
+    let mut host_sess = builder
+        .add_player(PlayerType::Spectator(spec_addr), spectator_handle)?
+        .start_p2p_session(host_socket)?;
+
+    let mut spec_sess = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config)
+        .with_spectator_config(spectator_config(spectator_interval))
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+
+    let sync_result = synchronize_spectator_deterministic(&mut spec_sess, &mut host_sess, &clock);
+    assert_spectator_synchronized(&spec_sess, &host_sess, &sync_result);
+
+    let mut input_bytes_sent = 0;
+    let baseline = host_sess
+        .peer_metrics(spectator_handle)?
+        .input_bytes_post_compression;
+    let mut host_stub = GameStub::new();
+    let mut spec_stub = GameStub::new();
+    let mut host_states = BTreeMap::new();
+    let mut spec_states = BTreeMap::new();
+
+    let mut poll_spectator = |spec_sess: &mut SpectatorSession<StubConfig>, target| {
+        spec_sess.poll_remote_clients();
+        while spec_sess.current_frame() < target {
+            match spec_sess.advance_frame() {
+                Ok(requests) if !requests.is_empty() => {
+                    spec_stub.handle_requests_recording(requests, &mut spec_states);
+                },
+                _ => break,
+            }
+        }
+    };
+
+    // The frames after `FRAMES` only let the spectator catch up.
+    for frame in 0..FRAMES + SETTLE_FRAMES {
+        if frame == FRAMES {
+            input_bytes_sent = host_sess
+                .peer_metrics(spectator_handle)?
+                .input_bytes_post_compression
+                - baseline;
+        }
+        for player in 0..PLAYERS {
+            host_sess.add_local_input(
+                PlayerHandle::new(player),
+                StubInput {
+                    inp: idle_input(player, frame),
+                },
+            )?;
+        }
+        host_stub.handle_requests_recording(host_sess.advance_frame()?, &mut host_states);
+        host_sess.poll_remote_clients();
+        if frame % SPECTATOR_POLL_EVERY == 0 {
+            poll_spectator(&mut spec_sess, host_sess.current_frame());
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    let frame = i32::try_from(FRAMES).unwrap();
+    Ok(RunOutcome {
+        input_bytes_sent,
+        host: host_states[&frame],
+        spectator: *spec_states
+            .get(&frame)
+            .expect("the spectator should have caught up past FRAMES"),
+    })
+}
+
+/// Mostly idle inputs cost far fewer spectator-bound bytes with keyframes, and
+/// the spectator ends in exactly the host's state, as it does without them.
+#[test]
+fn idle_inputs_send_fewer_spectator_bytes_with_identical_state() -> Result<(), FortressError> {
+    let plain = run(0, 0)?;
+    let keyframes = run(KEYFRAME_INTERVAL, KEYFRAME_INTERVAL)?;
+
+    assert!(
+        keyframes.input_bytes_sent * 2 < plain.input_bytes_sent,
+        "keyframes should at least halve spectator input bytes: \
+         {} with keyframes vs {} plain",
+        keyframes.input_bytes_sent,
+        plain.input_bytes_sent
+    );
+    assert_eq!(
+        calculate_hash(&keyframes.spectator),
+        calculate_hash(&keyframes.host)
+    );
+    assert_eq!(
+        calculate_hash(&plain.spectator),
+        calculate_hash(&plain.host)
+    );
+    assert_eq!(keyframes.host, plain.host);
+    Ok(())
+}
+
+/// The mode needs both sides: a spectator that did not ask, or a host that
+/// does not offer it, keeps the current format byte for byte.
+#[test]
+fn keyframes_stay_off_unless_both_sides_opt_in() -> Result<(), FortressError> {
+    let plain = run(0, 0)?;
+    for (host_interval, spectator_interval) in [(KEYFRAME_INTERVAL, 0), (0, KEYFRAME_INTERVAL)] {
+        let outcome = run(host_interval, spectator_interval)?;
+        assert_eq!(
+            outcome.input_bytes_sent, plain.input_bytes_sent,
+            "host {host_interval} / spectator {spectator_interval}"
+        );
+        assert_eq!(outcome.spectator, plain.spectator);
+    }
+    Ok(())
+}
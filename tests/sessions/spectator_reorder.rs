@@ -0,0 +1,171 @@
+//! Spectator input assembly under reordered, duplicated and dropped host
+//! packets.
+//!
+//! The host's link to the spectator runs through a seeded [`ChaosSocket`]
+//! that reorders, duplicates and drops packets in both directions. Whatever
+//! arrives out of order is buffered until the frames before it arrive, so
+//! the spectator plays only contiguous confirmed frames. Every frame it
+//! simulates must hold the host's state for that frame, and it must either
+//! catch up with the host or report the gap with
+//! [`FortressEvent::SpectatorInputGap`]; it never plays past a missing frame.
+//!
+//! [`ChaosSocket`]: fortress_rollback::ChaosSocket
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use crate::common::{
+    assert_spectator_synchronized, create_chaos_channel_pair, synchronize_spectator_deterministic,
+    TestClock,
+};
+use fortress_rollback::{
+    ChaosConfig, FortressError, FortressEvent, Frame, PlayerHandle, PlayerType, ProtocolConfig,
+    SessionBuilder, SpectatorConfig,
+};
+
+const PLAYERS: usize = 2;
+/// One frame at 60 FPS.
+const STEP: Duration = Duration::from_millis(16);
+/// Frames the host plays.
+const FRAMES: usize = 240;
+/// Steps the spectator gets to catch up once the host stops.
+const DRAIN_STEPS: usize = 300;
+
+/// What the spectator saw over one run.
+struct Run {
+    host_states: BTreeMap<i32, StateStub>,
+    spectator_states: BTreeMap<i32, StateStub>,
+    spectator_frame: Frame,
+    gaps: Vec<Frame>,
+}
+
+fn play(chaos: ChaosConfig, input_gap_timeout: Duration) -> Result<Run, FortressError> {
+    let clock = TestClock::new();
+    let (host_socket, spec_socket, host_addr, spec_addr) =
+        create_chaos_channel_pair(chaos.clone(), chaos, &clock);
+    let protocol_config = ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    };
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config.clone())
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(2))?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(PLAYERS)?
+        .with_protocol_config(protocol_config)
+        .with_spectator_config(SpectatorConfig {
+            input_gap_timeout: Some(input_gap_timeout),
+            ..SpectatorConfig::default()
+        })
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let sync_result = synchronize_spectator_deterministic(&mut spectator, &mut host, &clock);
+    assert_spectator_synchronized(&spectator, &host, &sync_result);
+
+    let (mut host_stub, mut spec_stub) = (GameStub::new(), GameStub::new());
+    let mut host_states = BTreeMap::new();
+    let mut spectator_states = BTreeMap::new();
+    let mut gaps = Vec::new();
+    for step in 0..FRAMES + DRAIN_STEPS {
+        if step < FRAMES {
+            for player in 0..PLAYERS {
+                let inp = (step * 7 + player * 3) as u32 % 5;
+                host.add_local_input(PlayerHandle::new(player), StubInput { inp })?;
+            }
+            host_stub.handle_requests_recording(host.advance_frame()?, &mut host_states);
+        }
+        host.poll_remote_clients();
+        for _ in host.events() {}
+        spectator.poll_remote_clients();
+        for event in spectator.events() {
+            if let FortressEvent::SpectatorInputGap { first_missing } = event {
+                gaps.push(first_missing);
+            }
+        }
+        while spectator.current_frame() < host.current_frame() {
+            match spectator.advance_frame() {
+                Ok(requests) if !requests.is_empty() => {
+                    spec_stub.handle_requests_recording(requests, &mut spectator_states);
+                },
+                Ok(_) | Err(FortressError::PredictionThreshold) => break,
+                Err(err) => return Err(err),
+            }
+        }
+        clock.advance(STEP);
+    }
+
+    Ok(Run {
+        host_states,
+        spectator_states,
+        spectator_frame: spectator.current_frame(),
+        gaps,
+    })
+}
+
+/// Every frame the spectator simulated matches the host, and the spectator
+/// either reached the frame it reaches on a clean link or reported why it
+/// could not.
+fn assert_parity_or_gap(run: &Run) -> Result<(), FortressError> {
+    let frontier = play(ChaosConfig::passthrough(), Duration::from_secs(1))?.spectator_frame;
+    assert!(
+        !run.spectator_states.is_empty(),
+        "the spectator never advanced"
+    );
+    for (frame, state) in &run.spectator_states {
+        assert_eq!(
+            Some(state),
+            run.host_states.get(frame),
+            "spectator diverged from the host at frame {frame}"
+        );
+    }
+    assert!(
+        run.spectator_frame == frontier || !run.gaps.is_empty(),
+        "spectator stalled at frame {} (clean link: {frontier}) without a SpectatorInputGap",
+        run.spectator_frame,
+    );
+    Ok(())
+}
+
+#[test]
+fn spectator_recovers_checksum_parity_under_reordering_and_loss() -> Result<(), FortressError> {
+    let chaos = ChaosConfig::builder()
+        .reorder_buffer_size(6)
+        .reorder_rate(0.3)
+        .duplication_rate(0.05)
+        .packet_loss_rate(0.1)
+        .seed(683)
+        .build();
+    let run = play(chaos, Duration::from_secs(1))?;
+    // The host resends every unacknowledged frame, so nothing stays missing.
+    assert!(run.gaps.is_empty(), "unexpected gaps: {:?}", run.gaps);
+    assert_parity_or_gap(&run)?;
+    Ok(())
+}
+
+#[test]
+fn spectator_never_plays_past_a_gap_under_heavy_loss() -> Result<(), FortressError> {
+    let chaos = ChaosConfig::builder()
+        .reorder_buffer_size(8)
+        .reorder_rate(0.5)
+        .duplication_rate(0.1)
+        .packet_loss_rate(0.3)
+        .seed(6830)
+        .build();
+    // A timeout shorter than the host's retry interval reports the gaps the
+    // retransmission has not closed yet.
+    let run = play(chaos, Duration::from_millis(1))?;
+    assert_parity_or_gap(&run)
+}
@@ -0,0 +1,203 @@
+//! Save-state slots.
+//!
+//! A training-mode flow: export a confirmed state, keep playing, restore the
+//! slot and replay the same inputs. Covers:
+//! - A `SyncTestSession` reaching the same state at frame 250 after restoring
+//!   the slot saved at frame 100, with the slot passed through serde.
+//! - A `P2PSession` whose players are all local doing the same.
+//! - The input delay's frames after a restore holding the default input.
+//! - A session with a remote player refusing to restore.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::create_channel_mesh;
+use crate::common::stubs::{GameStub, StateStub, StubConfig, StubInput};
+use fortress_rollback::hash::fnv1a_hash;
+use fortress_rollback::{
+    FortressError, FortressRequest, Frame, InputStatus, InvalidRequestKind, PlayerHandle,
+    PlayerType, RequestVec, SessionBuilder, StateSlot,
+};
+
+const SAVED_AT: i32 = 100;
+const PLAYED_TO: i32 = 250;
+
+/// The input `handle` gives for `frame`, varied so the state depends on it.
+fn input(frame: i32, handle: usize) -> StubInput {
+    StubInput {
+        inp: ((frame * 7) as u32 + handle as u32 * 3) % 5,
+    }
+}
+
+/// Fulfills `requests`, checking a restore's load comes first.
+fn fulfill(game: &mut GameStub, requests: RequestVec<StubConfig>, restored: Option<Frame>) {
+    if let Some(frame) = restored {
+        assert!(
+            matches!(requests.first(), Some(FortressRequest::LoadGameState { frame: f, .. }) if *f == frame),
+            "the restored state is loaded first"
+        );
+    }
+    game.handle_requests(requests);
+}
+
+#[test]
+fn synctest_restores_a_slot_and_replays_to_the_same_state() -> Result<(), FortressError> {
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_check_distance(2)
+        .start_synctest_session()?;
+    let mut game = GameStub::new();
+    assert_eq!(session.export_state_slot(), None);
+
+    let mut slot = None;
+    let mut restored = None;
+    let mut checksums = Vec::new();
+    for pass in 0..2 {
+        while session.current_frame().as_i32() < PLAYED_TO {
+            let frame = session.current_frame().as_i32();
+            for handle in 0..2 {
+                session.add_local_input(PlayerHandle::new(handle), input(frame, handle))?;
+            }
+            fulfill(&mut game, session.advance_frame()?, restored.take());
+            if pass == 0 && session.confirmed_frame() == Frame::new(SAVED_AT) {
+                slot = session.export_state_slot();
+            }
+        }
+        assert_eq!(game.gs.frame, PLAYED_TO);
+        checksums.push(fnv1a_hash(&game.gs));
+
+        if pass == 0 {
+            let slot = slot.take().unwrap();
+            assert_eq!(slot.frame(), Frame::new(SAVED_AT));
+            assert_eq!(slot.state().frame, SAVED_AT);
+            assert_eq!(slot.checksum(), Some(u128::from(fnv1a_hash(slot.state()))));
+            let json = serde_json::to_string(&slot).unwrap();
+            let slot: StateSlot<StateStub> = serde_json::from_str(&json).unwrap();
+            session.propose_state_restore(slot)?;
+            assert_eq!(session.current_frame(), Frame::new(SAVED_AT));
+            restored = Some(Frame::new(SAVED_AT));
+        }
+    }
+    assert_eq!(checksums[0], checksums[1]);
+    Ok(())
+}
+
+#[test]
+fn local_p2p_session_restores_a_slot_and_replays_to_the_same_state() -> Result<(), FortressError> {
+    let (sockets, _) = create_channel_mesh(2);
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(sockets.into_iter().next().unwrap())?;
+    let mut game = GameStub::new();
+
+    let mut slot = None;
+    let mut restored = None;
+    let mut states = Vec::new();
+    for pass in 0..2 {
+        while game.gs.frame < PLAYED_TO {
+            let frame = session.current_frame().as_i32();
+            for handle in 0..2 {
+                session.add_local_input(PlayerHandle::new(handle), input(frame, handle))?;
+            }
+            fulfill(&mut game, session.advance_frame()?, restored.take());
+            if slot.is_none() && session.finalized_frame() >= Frame::new(SAVED_AT) {
+                slot = session.export_state_slot();
+            }
+        }
+        states.push(game.gs);
+
+        if pass == 0 {
+            let slot = slot.clone().unwrap();
+            assert!(slot.frame() >= Frame::new(SAVED_AT));
+            restored = Some(slot.frame());
+            session.propose_state_restore(slot)?;
+        }
+    }
+    assert_eq!(states[0], states[1]);
+    Ok(())
+}
+
+#[test]
+fn input_delay_frames_after_a_restore_hold_the_default_input() -> Result<(), FortressError> {
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_check_distance(2)
+        .with_input_delay(2)?
+        .start_synctest_session()?;
+    let mut game = GameStub::new();
+    let mut slot = None;
+    while slot.is_none() {
+        let frame = session.current_frame().as_i32();
+        for handle in 0..2 {
+            session.add_local_input(PlayerHandle::new(handle), StubInput { inp: 3 })?;
+        }
+        game.handle_requests(session.advance_frame()?);
+        if frame > 20 {
+            slot = session.export_state_slot();
+        }
+    }
+    let slot = slot.unwrap();
+    let restored_at = slot.frame().as_i32();
+    session.propose_state_restore(slot)?;
+
+    for offset in 0..3 {
+        for handle in 0..2 {
+            session.add_local_input(PlayerHandle::new(handle), StubInput { inp: 3 })?;
+        }
+        let requests = session.advance_frame()?;
+        let inputs: Vec<_> = requests
+            .iter()
+            .filter_map(|request| match request {
+                FortressRequest::AdvanceFrame { inputs, .. } => Some(inputs.clone()),
+                _ => None,
+            })
+            .collect();
+        // the newest advance simulates the new frame
+        let newest = inputs.last().unwrap();
+        let expected = if offset < 2 { 0 } else { 3 };
+        for &(input, status) in newest.iter() {
+            assert_eq!(input.inp, expected, "frame {}", restored_at + offset);
+            assert_eq!(status, InputStatus::Confirmed);
+        }
+        game.handle_requests(requests);
+    }
+    assert_eq!(game.gs.frame, restored_at + 3);
+    Ok(())
+}
+
+#[test]
+fn a_session_with_a_remote_player_refuses_to_restore() -> Result<(), FortressError> {
+    let (sockets, addrs) = create_channel_mesh(2);
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(addrs[1]), PlayerHandle::new(1))?
+        .start_p2p_session(sockets.into_iter().next().unwrap())?;
+    let slot = SessionBuilder::<StubConfig>::new()
+        .with_check_distance(2)
+        .start_synctest_session()
+        .map(|mut synctest| {
+            let mut game = GameStub::new();
+            for frame in 0..10 {
+                for handle in 0..2 {
+                    synctest
+                        .add_local_input(PlayerHandle::new(handle), input(frame, handle))
+                        .unwrap();
+                }
+                game.handle_requests(synctest.advance_frame().unwrap());
+            }
+            synctest.export_state_slot().unwrap()
+        })?;
+
+    assert_eq!(
+        session.propose_state_restore(slot),
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::StateRestoreRequiresLocalSession
+        })
+    );
+    assert_eq!(session.current_frame(), Frame::new(0));
+    Ok(())
+}
@@ -0,0 +1,211 @@
+//! Integration tests for `P2PSession::stats_publisher`.
+//!
+//! Covers:
+//! - A render thread reading snapshots while two peers play over a lossy,
+//!   jittery `ChaosSocket` loopback, seeing every snapshot intact and
+//!   versions only moving forward.
+//! - Snapshots matching the session's own accessors once published.
+//! - The publish cadence, the first snapshot before synchronization, and
+//!   rejecting an interval of 0.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, create_chaos_channel_pair, synchronize_sessions_deterministic, SyncConfig,
+    TestClock, POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    ChaosConfig, FortressError, Frame, InvalidRequestKind, NonBlockingSocket, P2PSession,
+    PlayerHandle, PlayerType, ProtocolConfig, SessionBuilder, StatsSnapshot,
+};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn session(
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+fn step(sessions: &mut [P2PSession<StubConfig>; 2], stubs: &mut [GameStub; 2], clock: &TestClock) {
+    for (handle, (session, stub)) in sessions.iter_mut().zip(stubs.iter_mut()).enumerate() {
+        session.poll_remote_clients();
+        let inp = session.current_frame().as_i32() as u32 % 5 + handle as u32;
+        session
+            .add_local_input(PlayerHandle::new(handle), StubInput { inp })
+            .unwrap();
+        match session.advance_frame() {
+            Ok(requests) => stub.handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => panic!("advance failed: {err}"),
+        }
+    }
+    clock.advance(POLL_INTERVAL_DETERMINISTIC);
+}
+
+/// Fields every snapshot must agree on, whatever moment it was taken at.
+fn assert_consistent(snapshot: &StatsSnapshot) {
+    assert!(
+        snapshot.confirmed_frame <= snapshot.current_frame,
+        "{snapshot:?}"
+    );
+    for (handle, stats) in &snapshot.network_stats {
+        assert_eq!(*handle, PlayerHandle::new(1), "{snapshot:?}");
+        #[allow(deprecated)]
+        {
+            assert_eq!(stats.local_frames_behind, stats.local_frame_advantage);
+        }
+    }
+}
+
+#[test]
+fn render_thread_reads_intact_snapshots_during_a_chaotic_match() {
+    let clock = TestClock::new();
+    let chaos = |seed| {
+        ChaosConfig::builder()
+            .latency_ms(40)
+            .jitter_ms(20)
+            .packet_loss_rate(0.05)
+            .seed(seed)
+            .build()
+    };
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(7), chaos(8), &clock);
+    let mut sessions = [
+        session(s1, 0, a2, &clock).unwrap(),
+        session(s2, 1, a1, &clock).unwrap(),
+    ];
+    let [sess1, sess2] = &mut sessions;
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+
+    let subscription = sessions[0].stats_publisher(2).unwrap();
+    let done = Arc::new(AtomicBool::new(false));
+    let reader = {
+        let subscription = subscription.clone();
+        let done = Arc::clone(&done);
+        std::thread::spawn(move || {
+            let mut snapshot = StatsSnapshot::default();
+            let mut reads = 0_u64;
+            let mut last = StatsSnapshot::default();
+            while !done.load(Ordering::Acquire) {
+                subscription.latest_into(&mut snapshot);
+                assert!(snapshot.version >= last.version, "versions never go back");
+                if snapshot.version == last.version {
+                    assert_eq!(snapshot, last, "one version, one snapshot");
+                }
+                assert!(snapshot.current_frame >= last.current_frame);
+                assert!(snapshot.rollback_count >= last.rollback_count);
+                assert_consistent(&snapshot);
+                last.clone_from(&snapshot);
+                reads += 1;
+            }
+            (reads, last)
+        })
+    };
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    for _ in 0..600 {
+        step(&mut sessions, &mut stubs, &clock);
+    }
+    done.store(true, Ordering::Release);
+    let (reads, last) = reader.join().unwrap();
+
+    assert!(reads > 0);
+    assert!(last.version > 1, "the session kept publishing");
+    assert!(
+        sessions[0].metrics().rollback_count > 0,
+        "the chaotic link caused rollbacks"
+    );
+
+    // With an interval of 1 the next advance publishes the session's state
+    // exactly as its accessors report it afterwards.
+    let version = subscription.version();
+    sessions[0].stats_publisher(1).unwrap();
+    step(&mut sessions, &mut stubs, &clock);
+    let snapshot = subscription.latest();
+    assert_eq!(snapshot.version, version + 1);
+    let session = &sessions[0];
+    assert_eq!(snapshot.current_frame, session.current_frame());
+    assert_eq!(snapshot.confirmed_frame, session.confirmed_frame());
+    assert_eq!(snapshot.frames_ahead, session.frames_ahead());
+    assert_eq!(snapshot.rollback_count, session.metrics().rollback_count);
+    // The step advanced the clock after publishing, aging the stats since.
+    let mut stats = session.network_stats(PlayerHandle::new(1)).unwrap();
+    stats.stats_age_ms = snapshot.network_stats[0].1.stats_age_ms;
+    assert_eq!(snapshot.network_stats, vec![(PlayerHandle::new(1), stats)]);
+}
+
+#[test]
+fn snapshots_follow_the_interval_from_the_first_call() {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let mut sessions = [
+        session(s1, 0, a2, &clock).unwrap(),
+        session(s2, 1, a1, &clock).unwrap(),
+    ];
+
+    // Published on creation; the peer is not synchronized yet, so its stats
+    // are left out.
+    let subscription = sessions[0].stats_publisher(3).unwrap();
+    let first = subscription.latest();
+    assert_eq!(first.version, 1);
+    assert_eq!(first.current_frame, Frame::new(0));
+    assert!(first.network_stats.is_empty());
+
+    let [sess1, sess2] = &mut sessions;
+    synchronize_sessions_deterministic(sess1, sess2, &clock, &SyncConfig::default())
+        .expect("sessions should synchronize");
+    // Endpoints report stats once they have measured for a second.
+    clock.advance(Duration::from_secs(1));
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    let mut versions: Vec<u64> = (0..9)
+        .map(|_| {
+            step(&mut sessions, &mut stubs, &clock);
+            subscription.version()
+        })
+        .collect();
+    // Every third advance publishes; skip to the first one seen.
+    let start = versions
+        .windows(2)
+        .position(|pair| pair[0] != pair[1])
+        .unwrap()
+        + 1;
+    let base = versions[start];
+    versions.drain(..start);
+    assert_eq!(
+        versions[..6],
+        [0, 0, 0, 1, 1, 1].map(|offset| base + offset)
+    );
+    assert_eq!(subscription.latest().network_stats.len(), 1);
+
+    assert!(matches!(
+        sessions[0].stats_publisher(0),
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::ConfigValueOutOfRange { .. }
+        })
+    ));
+}
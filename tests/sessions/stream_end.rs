@@ -0,0 +1,295 @@
+//! Integration tests for the host's end-of-stream notice to spectators
+//! (`P2PSession::end_spectator_stream`, `FortressEvent::StreamEnded`).
+//!
+//! Covers:
+//! - A host ending the stream cleanly: the spectator plays up to the final
+//!   frame, emits `StreamEnded` once, and does not report the host leaving.
+//! - A host that vanishes without ending the stream: the spectator reports
+//!   `Disconnected` and never `StreamEnded`.
+//! - The stream ending on its own once every remote player disconnected.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use std::time::Duration;
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{
+    create_channel_pair, create_channel_triple, synchronize_spectator_deterministic, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    DisconnectBehavior, FortressError, FortressEvent, Frame, P2PSession, PlayerHandle, PlayerType,
+    ProtocolConfig, SessionBuilder, SessionState, SpectatorSession,
+};
+
+/// Frames the host plays before the stream ends.
+const FRAMES: i32 = 30;
+/// Upper bound on steps any loop may take.
+const MAX_STEPS: usize = 400;
+/// Short enough that a vanished host is noticed within `MAX_STEPS` polls.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+fn protocol_config(clock: &TestClock) -> ProtocolConfig {
+    ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    }
+}
+
+fn input_for(frame: Frame) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32) % 7,
+    }
+}
+
+/// A one-player host streaming to one spectator, both running.
+fn start_host_and_spectator(
+    clock: &TestClock,
+) -> Result<(P2PSession<StubConfig>, SpectatorSession<StubConfig>), FortressError> {
+    let (host_socket, spec_socket, host_addr, spec_addr) = create_channel_pair();
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config(clock))
+        .with_disconnect_timeout(DISCONNECT_TIMEOUT)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(1))?
+        .start_p2p_session(host_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_num_players(1)?
+        .with_protocol_config(protocol_config(clock))
+        .with_disconnect_timeout(DISCONNECT_TIMEOUT)
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    let result = synchronize_spectator_deterministic(&mut spectator, &mut host, clock);
+    assert!(result.success, "spectator should synchronize");
+    Ok((host, spectator))
+}
+
+/// Advances the host `frames` times, letting the spectator follow along.
+fn play(
+    host: &mut P2PSession<StubConfig>,
+    host_stub: &mut GameStub,
+    spectator: &mut SpectatorSession<StubConfig>,
+    spec_stub: &mut GameStub,
+    clock: &TestClock,
+    frames: i32,
+) -> Result<(), FortressError> {
+    for _ in 0..frames {
+        host.poll_remote_clients();
+        host.add_local_input(PlayerHandle::new(0), input_for(host.current_frame()))?;
+        host_stub.handle_requests(host.advance_frame()?);
+        spectator_step(spectator, spec_stub)?;
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    Ok(())
+}
+
+/// One spectator frame; waiting on the host is not an error here.
+fn spectator_step(
+    spectator: &mut SpectatorSession<StubConfig>,
+    stub: &mut GameStub,
+) -> Result<(), FortressError> {
+    spectator.poll_remote_clients();
+    match spectator.advance_frame() {
+        Ok(requests) => {
+            stub.handle_requests(requests);
+            Ok(())
+        },
+        Err(FortressError::PredictionThreshold) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+fn ended_frames(events: &[FortressEvent<StubConfig>]) -> Vec<Frame> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            FortressEvent::StreamEnded { final_frame } => Some(*final_frame),
+            _ => None,
+        })
+        .collect()
+}
+
+fn disconnects(events: &[FortressEvent<StubConfig>]) -> usize {
+    events
+        .iter()
+        .filter(|event| matches!(event, FortressEvent::Disconnected { .. }))
+        .count()
+}
+
+#[test]
+fn clean_host_shutdown_ends_the_stream_at_the_final_frame() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut host, mut spectator) = start_host_and_spectator(&clock)?;
+    let mut host_stub = GameStub::new();
+    let mut spec_stub = GameStub::new();
+    play(
+        &mut host,
+        &mut host_stub,
+        &mut spectator,
+        &mut spec_stub,
+        &clock,
+        FRAMES,
+    )?;
+
+    let final_frame = host.end_spectator_stream()?;
+    assert!(final_frame >= Frame::new(FRAMES - 2), "{final_frame:?}");
+    assert_eq!(host.end_spectator_stream()?, final_frame, "idempotent");
+    // The host keeps playing; nothing past the final frame is forwarded.
+    play(
+        &mut host,
+        &mut host_stub,
+        &mut spectator,
+        &mut spec_stub,
+        &clock,
+        5,
+    )?;
+
+    let mut events = Vec::new();
+    for _ in 0..MAX_STEPS {
+        spectator_step(&mut spectator, &mut spec_stub)?;
+        events.extend(spectator.events());
+        if spectator.stream_ended().is_some() {
+            break;
+        }
+        host.poll_remote_clients();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    assert_eq!(spectator.stream_ended(), Some(final_frame));
+    assert_eq!(spectator.current_frame(), final_frame);
+    assert_eq!(spec_stub.gs.frame, final_frame.as_i32() + 1);
+    // Nothing left to wait for: no prediction-threshold error.
+    assert!(spectator.advance_frame()?.is_empty());
+
+    // The host going away afterwards is expected, not a disconnect.
+    drop(host);
+    for _ in 0..MAX_STEPS {
+        spectator_step(&mut spectator, &mut spec_stub)?;
+        events.extend(spectator.events());
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    assert_eq!(ended_frames(&events), vec![final_frame]);
+    assert_eq!(disconnects(&events), 0, "{events:?}");
+    assert_eq!(spectator.stream_ended(), Some(final_frame));
+    Ok(())
+}
+
+#[test]
+fn killed_host_is_reported_as_disconnected() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (mut host, mut spectator) = start_host_and_spectator(&clock)?;
+    let mut host_stub = GameStub::new();
+    let mut spec_stub = GameStub::new();
+    play(
+        &mut host,
+        &mut host_stub,
+        &mut spectator,
+        &mut spec_stub,
+        &clock,
+        FRAMES,
+    )?;
+
+    drop(host);
+    let mut events = Vec::new();
+    for _ in 0..MAX_STEPS {
+        spectator_step(&mut spectator, &mut spec_stub)?;
+        events.extend(spectator.events());
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    assert_eq!(disconnects(&events), 1, "{events:?}");
+    assert!(ended_frames(&events).is_empty(), "{events:?}");
+    assert_eq!(spectator.stream_ended(), None);
+    Ok(())
+}
+
+#[test]
+fn stream_ends_once_every_remote_player_disconnected() -> Result<(), FortressError> {
+    let clock = TestClock::new();
+    let (host_socket, peer_socket, spec_socket, host_addr, peer_addr, spec_addr) =
+        create_channel_triple();
+    let mut host = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .with_disconnect_behavior(DisconnectBehavior::ContinueWithout)
+        .with_disconnect_timeout(DISCONNECT_TIMEOUT)
+        .add_player(PlayerType::Local, PlayerHandle::new(0))?
+        .add_player(PlayerType::Remote(peer_addr), PlayerHandle::new(1))?
+        .add_player(PlayerType::Spectator(spec_addr), PlayerHandle::new(2))?
+        .start_p2p_session(host_socket)?;
+    let mut peer = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .add_player(PlayerType::Remote(host_addr), PlayerHandle::new(0))?
+        .add_player(PlayerType::Local, PlayerHandle::new(1))?
+        .start_p2p_session(peer_socket)?;
+    let mut spectator = SessionBuilder::<StubConfig>::new()
+        .with_protocol_config(protocol_config(&clock))
+        .start_spectator_session(host_addr, spec_socket)
+        .expect("spectator session should start");
+    for _ in 0..MAX_STEPS {
+        host.poll_remote_clients();
+        peer.poll_remote_clients();
+        spectator.poll_remote_clients();
+        if [
+            host.current_state(),
+            peer.current_state(),
+            spectator.current_state(),
+        ]
+        .iter()
+        .all(|state| *state == SessionState::Running)
+        {
+            break;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    assert_eq!(host.current_state(), SessionState::Running);
+    assert_eq!(spectator.current_state(), SessionState::Running);
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    for _ in 0..10 {
+        for (handle, (session, stub)) in [&mut host, &mut peer]
+            .into_iter()
+            .zip(&mut stubs)
+            .enumerate()
+        {
+            session.poll_remote_clients();
+            let frame = session.current_frame();
+            session.add_local_input(PlayerHandle::new(handle), input_for(frame))?;
+            match session.advance_frame() {
+                Ok(requests) => stub.handle_requests(requests),
+                Err(FortressError::PredictionThreshold) => {},
+                Err(err) => return Err(err),
+            }
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    // The peer vanishes; the host drops it after the disconnect timeout.
+    drop(peer);
+
+    let mut spec_stub = GameStub::new();
+    let mut events = Vec::new();
+    for _ in 0..MAX_STEPS {
+        host.poll_remote_clients();
+        host.add_local_input(PlayerHandle::new(0), input_for(host.current_frame()))?;
+        match host.advance_frame() {
+            Ok(requests) => stubs[0].handle_requests(requests),
+            Err(FortressError::PredictionThreshold) => {},
+            Err(err) => return Err(err),
+        }
+        spectator_step(&mut spectator, &mut spec_stub)?;
+        events.extend(spectator.events());
+        if spectator.stream_ended().is_some() {
+            break;
+        }
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    let final_frame = spectator.stream_ended().expect("the stream should end");
+    assert!(final_frame >= Frame::new(0), "{final_frame:?}");
+    assert_eq!(spectator.current_frame(), final_frame);
+    assert_eq!(ended_frames(&events), vec![final_frame]);
+    assert_eq!(host.end_spectator_stream()?, final_frame);
+    Ok(())
+}
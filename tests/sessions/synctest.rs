@@ -8,8 +8,13 @@
     clippy::indexing_slicing
 )]
 
+use std::collections::BTreeMap;
+
 use crate::common::stubs::{GameStub, RandomChecksumGameStub, StubConfig, StubInput};
-use fortress_rollback::{FortressError, FortressRequest, PlayerHandle, SessionBuilder};
+use fortress_rollback::hash::fnv1a_hash;
+use fortress_rollback::{
+    FortressError, FortressRequest, Frame, InvalidFrameReason, PlayerHandle, SessionBuilder,
+};
 
 #[test]
 fn test_create_session() {
@@ -219,3 +224,95 @@ fn test_rollback_with_varying_input_delay() -> Result<(), FortressError> {
 
     Ok(())
 }
+
+/// Stepping back loads an earlier state, and the frames after it resimulate
+/// with the inputs of the first pass.
+#[test]
+fn test_step_back_resimulates_the_recorded_inputs() -> Result<(), FortressError> {
+    let mut stub = GameStub::new();
+    let mut sess = SessionBuilder::new()
+        .with_max_prediction_window(16)
+        .with_check_distance(2)
+        .start_synctest_session()?;
+
+    let mut first_pass = BTreeMap::new();
+    for i in 0..50 {
+        sess.add_local_input(PlayerHandle::new(0), StubInput { inp: i / 3 })?;
+        sess.add_local_input(PlayerHandle::new(1), StubInput { inp: i / 5 })?;
+        stub.handle_requests_recording(sess.advance_frame()?, &mut first_pass);
+    }
+
+    let requests = sess.step_back(10)?;
+    assert_eq!(requests.len(), 1);
+    assert!(matches!(
+        requests[0],
+        FortressRequest::LoadGameState { frame, .. } if frame == Frame::new(40)
+    ));
+    stub.handle_requests(requests);
+    assert_eq!(sess.current_frame(), Frame::new(40));
+    assert_eq!(stub.gs, first_pass[&40]);
+
+    let mut second_pass = BTreeMap::new();
+    for _ in 0..10 {
+        // Ignored: the frames already have recorded inputs.
+        sess.add_local_input(PlayerHandle::new(0), StubInput { inp: 1 })?;
+        stub.handle_requests_recording(sess.advance_frame()?, &mut second_pass);
+    }
+    assert_eq!(sess.current_frame(), Frame::new(50));
+    for frame in 41..=50 {
+        assert_eq!(
+            fnv1a_hash(&second_pass[&frame]),
+            fnv1a_hash(&first_pass[&frame]),
+            "checksum of frame {frame}"
+        );
+    }
+
+    // Past the frame it stepped back from, the session takes new inputs again.
+    assert!(matches!(
+        sess.advance_frame(),
+        Err(FortressError::InvalidRequestStructured { .. })
+    ));
+    sess.add_local_input(PlayerHandle::new(0), StubInput { inp: 0 })?;
+    sess.add_local_input(PlayerHandle::new(1), StubInput { inp: 0 })?;
+    stub.handle_requests(sess.advance_frame()?);
+    assert_eq!(stub.gs.frame, 51);
+
+    Ok(())
+}
+
+/// Stepping back further than the saved window names the oldest frame the
+/// session can return to.
+#[test]
+fn test_step_back_past_the_saved_window_fails() -> Result<(), FortressError> {
+    let mut stub = GameStub::new();
+    let mut sess = SessionBuilder::new()
+        .with_check_distance(2)
+        .start_synctest_session()?;
+    for i in 0..20 {
+        sess.add_local_input(PlayerHandle::new(0), StubInput { inp: i })?;
+        sess.add_local_input(PlayerHandle::new(1), StubInput { inp: i })?;
+        stub.handle_requests(sess.advance_frame()?);
+    }
+
+    // The default prediction window of 8 keeps frames 12 to 19.
+    let oldest_frame = Frame::new(12);
+    for frames in [9, 20, u32::MAX] {
+        match sess.step_back(frames) {
+            Err(FortressError::InvalidFrameStructured { reason, .. }) => {
+                assert_eq!(
+                    reason,
+                    InvalidFrameReason::BeforeOldestSavedFrame { oldest_frame }
+                );
+            },
+            other => panic!("stepping back {frames} frames returned {other:?}"),
+        }
+    }
+    assert_eq!(sess.current_frame(), Frame::new(20));
+
+    stub.handle_requests(sess.step_back(8)?);
+    assert_eq!(sess.current_frame(), oldest_frame);
+    assert_eq!(stub.gs.frame, 12);
+    assert!(sess.step_back(0)?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! Integration tests for perturbed sync-test resimulation.
+//!
+//! Covers:
+//! - `SessionBuilder::with_perturbed_resimulation` rejecting a state diff
+//!   codec.
+//! - A game that folds its entities in `HashMap` iteration order failing with
+//!   `SyncTestMismatchKind::OrderDependenceSuspected`, and the same game
+//!   passing once it uses a `BTreeMap`.
+//! - A deterministic game passing with the same rollback statistics as
+//!   without perturbation.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use fortress_rollback::hash::fnv1a_hash;
+use fortress_rollback::{
+    Config, FortressError, FortressRequest, Frame, InvalidRequestKind, PlayerHandle, RequestVec,
+    SessionBuilder, StateDiffCodec, SyncTestMismatchKind, SyncTestSession, SyncTestSummary,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+
+const ENTITIES: u32 = 16;
+const FRAMES: usize = 1_000;
+
+fn script(frame: Frame, handle: PlayerHandle) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(31) ^ handle.as_usize() as u32,
+    }
+}
+
+/// Which map the game collects its entities into each frame.
+#[derive(Debug, Clone, Copy)]
+enum Index {
+    Hash,
+    BTree,
+}
+
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+struct World {
+    frame: i32,
+    positions: Vec<u32>,
+    /// Folded over the entities in map iteration order, so it depends on
+    /// that order.
+    digest: u32,
+}
+
+struct WorldConfig;
+
+impl Config for WorldConfig {
+    type Input = StubInput;
+    type State = World;
+    type Address = SocketAddr;
+}
+
+struct MapGame {
+    index: Index,
+    world: World,
+}
+
+impl MapGame {
+    fn new(index: Index) -> Self {
+        Self {
+            index,
+            world: World {
+                frame: 0,
+                positions: (0..ENTITIES).collect(),
+                digest: 0,
+            },
+        }
+    }
+
+    fn handle_requests(&mut self, requests: RequestVec<WorldConfig>) {
+        for request in requests {
+            match request {
+                FortressRequest::SaveGameState { cell, frame } => {
+                    assert_eq!(self.world.frame, frame.as_i32());
+                    let checksum = u128::from(fnv1a_hash(&self.world));
+                    cell.save(frame, Some(self.world.clone()), Some(checksum));
+                },
+                FortressRequest::LoadGameState { cell, .. } => {
+                    self.world = cell.load().unwrap();
+                },
+                FortressRequest::AdvanceFrame { inputs, .. } => {
+                    for (input, _) in inputs.iter() {
+                        for (id, position) in self.world.positions.iter_mut().enumerate() {
+                            *position = position.wrapping_add(input.inp ^ id as u32);
+                        }
+                    }
+                    let entities = self.world.positions.iter().copied().enumerate();
+                    let fold = |digest: u32, (id, position): (usize, u32)| {
+                        digest.wrapping_mul(31).wrapping_add(position ^ id as u32)
+                    };
+                    self.world.digest = match self.index {
+                        Index::Hash => entities
+                            .collect::<HashMap<_, _>>()
+                            .into_iter()
+                            .fold(0, fold),
+                        Index::BTree => entities
+                            .collect::<BTreeMap<_, _>>()
+                            .into_iter()
+                            .fold(0, fold),
+                    };
+                    self.world.frame += 1;
+                },
+            }
+        }
+    }
+}
+
+fn run_map_game(index: Index) -> SyncTestSummary {
+    let mut session: SyncTestSession<WorldConfig> = SessionBuilder::new()
+        .with_num_players(2)
+        .unwrap()
+        .with_check_distance(4)
+        .with_perturbed_resimulation(true)
+        .start_synctest_session()
+        .unwrap();
+    assert!(session.perturbed_resimulation());
+    let mut game = MapGame::new(index);
+    session
+        .run_script(script, FRAMES, |requests| game.handle_requests(requests))
+        .unwrap()
+}
+
+/// Never used: the session is rejected before it saves anything.
+struct UnusedCodec;
+
+impl StateDiffCodec<World> for UnusedCodec {
+    fn diff(&self, _prev: &World, _curr: &World) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn apply(&self, base: &World, _diff: &[u8]) -> World {
+        base.clone()
+    }
+}
+
+#[test]
+fn perturbed_resimulation_rejects_a_state_diff_codec() {
+    let result = SessionBuilder::<WorldConfig>::new()
+        .with_perturbed_resimulation(true)
+        .with_state_diff_codec(Box::new(UnusedCodec))
+        .start_synctest_session();
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::NotSupported { .. }
+        })
+    ));
+}
+
+#[test]
+fn hash_map_iteration_order_is_reported_as_order_dependence() {
+    let summary = run_map_game(Index::Hash);
+
+    let mismatch = summary
+        .mismatch
+        .expect("HashMap iteration order should change the digest");
+    assert_eq!(
+        mismatch.kind,
+        SyncTestMismatchKind::OrderDependenceSuspected
+    );
+    assert!(!mismatch.mismatched_frames.is_empty());
+    assert_eq!(mismatch.seed, None);
+}
+
+#[test]
+fn btree_map_iteration_order_passes_perturbed_resimulation() {
+    let summary = run_map_game(Index::BTree);
+
+    assert_eq!(summary.mismatch, None);
+    assert_eq!(summary.frames, FRAMES);
+}
+
+#[test]
+fn perturbation_keeps_the_rollback_statistics() {
+    let run = |perturbed| {
+        let mut session: SyncTestSession<StubConfig> = SessionBuilder::new()
+            .with_randomized_rollbacks(0x5EED, 7)
+            .with_perturbed_resimulation(perturbed)
+            .start_synctest_session()
+            .unwrap();
+        let mut stub = GameStub::new();
+        let summary = session
+            .run_script(script, FRAMES, |requests| stub.handle_requests(requests))
+            .unwrap();
+        (summary, stub.gs)
+    };
+
+    let (plain, plain_state) = run(false);
+    let (perturbed, perturbed_state) = run(true);
+    assert_eq!(perturbed.mismatch, None);
+    assert_eq!(perturbed, plain);
+    assert_eq!(perturbed_state, plain_state);
+}
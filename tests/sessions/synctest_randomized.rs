@@ -0,0 +1,178 @@
+//! Integration tests for randomized sync-test rollbacks.
+//!
+//! Covers:
+//! - `SessionBuilder::with_randomized_rollbacks` rejecting a zero depth and a
+//!   depth that reaches the prediction window.
+//! - A deterministic game passing 10k frames of randomized rollbacks driven by
+//!   `SyncTestSession::run_script`.
+//! - A game that only diverges on deep resimulation passing fixed
+//!   check-distance rollbacks, failing randomized ones, and the reported seed
+//!   reproducing the same mismatch.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use fortress_rollback::{
+    FortressError, FortressRequest, Frame, InvalidRequestKind, PlayerHandle, RequestVec,
+    SessionBuilder, SyncTestSession, SyncTestSummary,
+};
+
+const SEED: u64 = 0x5EED;
+const MAX_DEPTH: usize = 7;
+
+fn script(frame: Frame, handle: PlayerHandle) -> StubInput {
+    StubInput {
+        inp: (frame.as_i32() as u32).wrapping_mul(31) ^ handle.as_usize() as u32,
+    }
+}
+
+fn randomized(seed: u64) -> SyncTestSession<StubConfig> {
+    SessionBuilder::new()
+        .with_randomized_rollbacks(seed, MAX_DEPTH)
+        .start_synctest_session()
+        .unwrap()
+}
+
+/// A game that drifts on the fifth frame it simulates after a load, so only
+/// rollbacks at least that deep expose it.
+struct DeepResimulationGame {
+    stub: GameStub,
+    advances_since_load: Option<usize>,
+}
+
+impl DeepResimulationGame {
+    fn new() -> Self {
+        Self {
+            stub: GameStub::new(),
+            advances_since_load: None,
+        }
+    }
+
+    fn handle_requests(&mut self, requests: RequestVec<StubConfig>) {
+        for request in requests {
+            let is_load = matches!(request, FortressRequest::LoadGameState { .. });
+            let is_advance = matches!(request, FortressRequest::AdvanceFrame { .. });
+            let mut single = RequestVec::new();
+            single.push(request);
+            self.stub.handle_requests(single);
+            if is_load {
+                self.advances_since_load = Some(0);
+            } else if is_advance {
+                if let Some(advances) = &mut self.advances_since_load {
+                    *advances += 1;
+                    if *advances == 5 {
+                        self.stub.gs.state += 1000;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn run_deep_resimulation_game(
+    session: &mut SyncTestSession<StubConfig>,
+    frames: usize,
+) -> SyncTestSummary {
+    let mut game = DeepResimulationGame::new();
+    session
+        .run_script(script, frames, |requests| game.handle_requests(requests))
+        .unwrap()
+}
+
+#[test]
+fn randomized_rollbacks_reject_a_zero_depth() {
+    let result = SessionBuilder::<StubConfig>::new()
+        .with_randomized_rollbacks(SEED, 0)
+        .start_synctest_session();
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::ConfigValueOutOfRange {
+                field: "max_depth",
+                ..
+            }
+        })
+    ));
+}
+
+#[test]
+fn randomized_rollbacks_reject_a_depth_reaching_the_prediction_window() {
+    let result = SessionBuilder::<StubConfig>::new()
+        .with_max_prediction_window(8)
+        .with_randomized_rollbacks(SEED, 8)
+        .start_synctest_session();
+    assert!(matches!(
+        result,
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::CheckDistanceTooLarge {
+                check_dist: 8,
+                max_prediction: 8,
+            }
+        })
+    ));
+}
+
+#[test]
+fn deterministic_game_passes_ten_thousand_randomized_frames() {
+    let mut session = randomized(SEED);
+    assert_eq!(session.rollback_seed(), Some(SEED));
+    assert_eq!(session.check_distance(), MAX_DEPTH);
+
+    let mut stub = GameStub::new();
+    let summary = session
+        .run_script(script, 10_000, |requests| stub.handle_requests(requests))
+        .unwrap();
+
+    assert_eq!(summary.mismatch, None);
+    assert_eq!(summary.frames, 10_000);
+    assert_eq!(stub.gs.frame, 10_000);
+    // Every frame past the first `MAX_DEPTH + 1` rolls back.
+    assert_eq!(summary.rollbacks, 10_000 - MAX_DEPTH - 1);
+    assert_eq!(summary.deepest_rollback, MAX_DEPTH);
+    assert!(summary.resimulated_frames > summary.rollbacks);
+    assert!(summary.resimulated_frames < summary.rollbacks * MAX_DEPTH);
+}
+
+#[test]
+fn same_seed_repeats_the_rollback_schedule() {
+    let mut first = GameStub::new();
+    let mut second = GameStub::new();
+    let a = randomized(SEED)
+        .run_script(script, 500, |requests| first.handle_requests(requests))
+        .unwrap();
+    let b = randomized(SEED)
+        .run_script(script, 500, |requests| second.handle_requests(requests))
+        .unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn deep_resimulation_drift_passes_fixed_rollbacks() {
+    let mut session = SessionBuilder::<StubConfig>::new()
+        .with_check_distance(2)
+        .start_synctest_session()
+        .unwrap();
+    assert_eq!(session.rollback_seed(), None);
+    let summary = run_deep_resimulation_game(&mut session, 1_000);
+    assert_eq!(summary.mismatch, None);
+    assert_eq!(summary.deepest_rollback, 2);
+}
+
+#[test]
+fn deep_resimulation_drift_fails_randomized_rollbacks_reproducibly() {
+    let summary = run_deep_resimulation_game(&mut randomized(SEED), 10_000);
+    let mismatch = summary.mismatch.expect("the drift is detected");
+    assert_eq!(mismatch.seed, Some(SEED));
+    assert!(!mismatch.mismatched_frames.is_empty());
+    assert!(summary.frames < 10_000);
+
+    let replayed = run_deep_resimulation_game(&mut randomized(mismatch.seed.unwrap()), 10_000);
+    assert_eq!(replayed.frames, summary.frames);
+    assert_eq!(replayed.mismatch, Some(mismatch));
+}
@@ -0,0 +1,172 @@
+//! Integration tests for `SessionBuilder::with_trace_recorder` and
+//! `P2PSession::export_trace_json`.
+//!
+//! Covers:
+//! - A scripted match with exactly one misprediction exporting a JSON trace
+//!   that parses, holds the synchronization events, advances, polls and the
+//!   one rollback, with durations and arguments.
+//! - The ring keeping only the most recent records and counting the rest.
+//! - Sessions without a recorder exporting an empty trace, and rejecting a
+//!   capacity of 0.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+use crate::common::stubs::{GameStub, StubConfig, StubInput};
+use crate::common::{create_channel_pair, ChannelSocket, TestClock, POLL_INTERVAL_DETERMINISTIC};
+use fortress_rollback::{
+    FortressError, InvalidRequestKind, P2PSession, PlayerHandle, PlayerType, ProtocolConfig,
+    SessionBuilder, SessionState,
+};
+use serde_json::Value;
+use std::net::SocketAddr;
+
+fn session(
+    socket: ChannelSocket,
+    local: usize,
+    remote_addr: SocketAddr,
+    capacity: Option<usize>,
+    clock: &TestClock,
+) -> Result<P2PSession<StubConfig>, FortressError> {
+    let mut builder = SessionBuilder::<StubConfig>::new().with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    });
+    if let Some(capacity) = capacity {
+        builder = builder.with_trace_recorder(capacity)?;
+    }
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+/// Plays `frames` frames after synchronizing; the peer's input changes once,
+/// at `change_at`, which the traced host only learns after predicting it.
+fn play(capacity: usize, frames: usize, change_at: usize) -> P2PSession<StubConfig> {
+    let clock = TestClock::new();
+    let (s1, s2, a1, a2) = create_channel_pair();
+    let mut host = session(s1, 0, a2, Some(capacity), &clock).unwrap();
+    let mut peer = session(s2, 1, a1, None, &clock).unwrap();
+    while host.current_state() != SessionState::Running
+        || peer.current_state() != SessionState::Running
+    {
+        host.poll_remote_clients();
+        peer.poll_remote_clients();
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+
+    let mut stubs = [GameStub::new(), GameStub::new()];
+    for frame in 0..frames {
+        // The host advances first, predicting the peer's input for the frame.
+        host.add_local_input(PlayerHandle::new(0), StubInput { inp: 0 })
+            .unwrap();
+        stubs[0].handle_requests(host.advance_frame().unwrap());
+        let inp = u32::from(frame >= change_at);
+        peer.add_local_input(PlayerHandle::new(1), StubInput { inp })
+            .unwrap();
+        stubs[1].handle_requests(peer.advance_frame().unwrap());
+        clock.advance(POLL_INTERVAL_DETERMINISTIC);
+    }
+    host.poll_remote_clients();
+    host
+}
+
+fn trace_events(trace: &Value) -> &Vec<Value> {
+    trace["traceEvents"].as_array().unwrap()
+}
+
+fn named<'a>(trace: &'a Value, name: &str) -> Vec<&'a Value> {
+    trace_events(trace)
+        .iter()
+        .filter(|event| event["name"] == name)
+        .collect()
+}
+
+#[test]
+fn scripted_match_with_one_rollback_exports_a_chrome_trace() {
+    let host = play(4096, 30, 10);
+    assert_eq!(host.metrics().rollback_count, 1);
+    let trace: Value = serde_json::from_str(&host.export_trace_json()).unwrap();
+    assert_eq!(trace["otherData"]["dropped_events"], 0);
+
+    for event in trace_events(&trace) {
+        assert!(event["ts"].is_u64(), "{event}");
+        assert_eq!(event["pid"], 1);
+        assert_eq!(event["tid"], 1);
+        match event["ph"].as_str().unwrap() {
+            "X" => assert!(event["dur"].is_u64(), "{event}"),
+            "i" => assert_eq!(event["s"], "t"),
+            phase => panic!("unexpected phase {phase}"),
+        }
+    }
+    assert!(!named(&trace, "Synchronizing").is_empty());
+    assert_eq!(named(&trace, "Synchronized").len(), 1);
+
+    let advances = named(&trace, "advance_frame");
+    assert_eq!(advances.len(), 30);
+    assert_eq!(advances[29]["args"]["frame"], 30);
+    let polls = named(&trace, "poll");
+    assert!(polls.len() > advances.len(), "every advance polls");
+    assert!(polls
+        .iter()
+        .any(|poll| poll["args"]["received"].as_u64() > Some(0)));
+
+    let rollbacks = named(&trace, "rollback");
+    assert_eq!(rollbacks.len(), 1);
+    let rollback = rollbacks[0];
+    assert_eq!(rollback["args"]["first_incorrect"], 10);
+    assert_eq!(rollback["args"]["depth"], 1);
+    // The rollback ran inside the advance after the peer's input changed.
+    let advance = advances[11];
+    let (start, end) = (
+        advance["ts"].as_u64().unwrap(),
+        advance["ts"].as_u64().unwrap() + advance["dur"].as_u64().unwrap(),
+    );
+    let at = rollback["ts"].as_u64().unwrap();
+    assert!((start..=end).contains(&at), "{rollback} not in {advance}");
+}
+
+#[test]
+fn ring_keeps_the_most_recent_records() {
+    let capacity = 16;
+    let host = play(capacity, 40, 100);
+    let trace: Value = serde_json::from_str(&host.export_trace_json()).unwrap();
+    let events = trace_events(&trace);
+    assert_eq!(events.len(), capacity);
+    assert!(trace["otherData"]["dropped_events"].as_u64().unwrap() > 0);
+    // The oldest records were dropped: only the last few frames remain.
+    assert!(named(&trace, "Synchronized").is_empty());
+    let frames: Vec<u64> = named(&trace, "advance_frame")
+        .iter()
+        .map(|advance| advance["args"]["frame"].as_u64().unwrap())
+        .collect();
+    assert_eq!(frames.last(), Some(&40));
+    assert!(frames.windows(2).all(|pair| pair[0] + 1 == pair[1]));
+}
+
+#[test]
+fn sessions_without_a_recorder_export_an_empty_trace() {
+    let clock = TestClock::new();
+    let (s1, _s2, _a1, a2) = create_channel_pair();
+    let session = session(s1, 0, a2, None, &clock).unwrap();
+    let trace: Value = serde_json::from_str(&session.export_trace_json()).unwrap();
+    assert!(trace_events(&trace).is_empty());
+
+    assert!(matches!(
+        SessionBuilder::<StubConfig>::new().with_trace_recorder(0),
+        Err(FortressError::InvalidRequestStructured {
+            kind: InvalidRequestKind::ConfigValueOutOfRange { .. }
+        })
+    ));
+}
@@ -3210,7 +3210,9 @@ fn h_asym_biases_throughput_without_wait_recommendations() {
             .iter()
             .map(|endpoint| endpoint.remote_frame_advantage)
             .collect::<Vec<_>>(),
-        vec![-1, 1],
+        // The half-RTT transit estimate rounds to the nearest frame, which
+        // here is one frame above the truncated value, shifting both gauges.
+        vec![0, 2],
         "the latest wire gauges stay below the three-frame recommendation dead band"
     );
 }
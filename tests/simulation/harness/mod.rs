@@ -1419,7 +1419,7 @@ fn handle_requests(&mut self, requests: RequestVec<I::SessionConfig>) {
                     self.load(&cell);
                 },
                 FortressRequest::SaveGameState { cell, frame } => self.save(&cell, frame),
-                FortressRequest::AdvanceFrame { inputs } => self.advance(&inputs),
+                FortressRequest::AdvanceFrame { inputs, .. } => self.advance(&inputs),
             }
         }
     }
@@ -1909,7 +1909,7 @@ fn record_spectator_requests<I: SimInput>(
 ) {
     let mut frame = start_frame;
     for request in requests {
-        if let FortressRequest::AdvanceFrame { inputs } = request {
+        if let FortressRequest::AdvanceFrame { inputs, .. } = request {
             let mut values: Vec<(InputFingerprint, InputStatus)> = inputs
                 .iter()
                 .map(|(input, status)| (input.fingerprint(), *status))
@@ -2011,7 +2011,9 @@ fn peer_event_key<I: SimInput>(event: &FortressEvent<I::SessionConfig>) -> Optio
         | FortressEvent::NetworkResumed { addr }
         | FortressEvent::DesyncDetected { addr, .. }
         | FortressEvent::SyncTimeout { addr, .. }
-        | FortressEvent::IncompatibleSession { addr, .. } => PeerEventPayload::Addr(*addr),
+        | FortressEvent::IncompatibleSession { addr, .. }
+        | FortressEvent::RemoteAdvanceStalled { addr }
+        | FortressEvent::RemoteAdvanceResumed { addr } => PeerEventPayload::Addr(*addr),
         FortressEvent::PeerDropped { handle, addr } => PeerEventPayload::PlayerAddr {
             handle: *handle,
             addr: *addr,
@@ -2025,7 +2027,21 @@ fn peer_event_key<I: SimInput>(event: &FortressEvent<I::SessionConfig>) -> Optio
         FortressEvent::WaitRecommendation { .. }
         | FortressEvent::ReplayDesync { .. }
         | FortressEvent::SpectatorDivergence { .. }
-        | FortressEvent::InputDelayRecommendation { .. } => return None,
+        | FortressEvent::InputDelayRecommendation { .. }
+        | FortressEvent::MatchPaused { .. }
+        | FortressEvent::MatchResumed { .. }
+        | FortressEvent::MatchPauseCancelled { .. }
+        | FortressEvent::SessionFrameLimitReached { .. }
+        | FortressEvent::StreamEnded { .. }
+        | FortressEvent::FrameTag { .. }
+        | FortressEvent::ReliableMessage { .. }
+        | FortressEvent::RejoinStateMismatch { .. }
+        | FortressEvent::LocalAdvanceStalled { .. }
+        | FortressEvent::LocalAdvanceResumed { .. }
+        | FortressEvent::SpectatorInputGap { .. }
+        | FortressEvent::TransportError { .. }
+        | FortressEvent::AuthoritativeDesyncVerdict { .. }
+        | FortressEvent::RollbackTooDeep { .. } => return None,
     };
     Some(PeerEventKey { kind, payload })
 }
@@ -4123,7 +4139,10 @@ fn replacement_generation_prunes_before_loading_snapshot_requests() {
             cell,
             frame: Frame::new(5),
         });
-        requests.push(FortressRequest::AdvanceFrame { inputs });
+        requests.push(FortressRequest::AdvanceFrame {
+            inputs,
+            previous_inputs: InputVec::new(),
+        });
 
         let loaded = game.handle_replacement_handoff_requests(requests, 5);
 
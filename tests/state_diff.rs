@@ -0,0 +1,312 @@
+//! Tests for [`SessionBuilder::with_state_diff_codec`] with a large game state.
+//!
+//! The state is a 512 KiB `Vec` of which each frame changes a few elements.
+//! A sync test at every rollback depth checks that loads rebuild the saved
+//! state exactly. Two P2P peers then play the same inputs with and without
+//! the codec while a byte-tracking global allocator records the peak of live
+//! heap bytes on the test's own thread; it lives in its own test binary so
+//! the allocator cannot disturb the other integration tests.
+
+// In tests: panic/unwrap/expect/etc. are appropriate.
+#![allow(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing
+)]
+
+#[path = "common/mod.rs"]
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::net::SocketAddr;
+
+use common::{
+    create_chaos_channel_pair, synchronize_sessions_deterministic, SyncConfig, TestClock,
+    POLL_INTERVAL_DETERMINISTIC,
+};
+use fortress_rollback::{
+    ChaosConfig, Config, DesyncDetection, FortressError, FortressRequest, NonBlockingSocket,
+    P2PSession, PlayerHandle, PlayerType, ProtocolConfig, RequestVec, SessionBuilder,
+    StateDiffCodec,
+};
+
+thread_local! {
+    static LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+    static PEAK_BYTES: Cell<i64> = const { Cell::new(0) };
+}
+
+/// Tracks the live and peak heap bytes of each thread.
+struct TrackingAllocator;
+
+impl TrackingAllocator {
+    fn record(delta: i64) {
+        // `try_with`: the thread-locals are gone while a thread shuts down.
+        let _ = LIVE_BYTES.try_with(|live| {
+            let now = live.get() + delta;
+            live.set(now);
+            let _ = PEAK_BYTES.try_with(|peak| peak.set(peak.get().max(now)));
+        });
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to the system allocator.
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size() as i64);
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size() as i64);
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size as i64 - layout.size() as i64);
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Self::record(-(layout.size() as i64));
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+/// Runs `f`, returning its result and how far this thread's live heap bytes
+/// peaked above where they started.
+fn peak_heap_growth<R>(f: impl FnOnce() -> R) -> (R, i64) {
+    let start = LIVE_BYTES.with(Cell::get);
+    PEAK_BYTES.with(|peak| peak.set(start));
+    let result = f();
+    (result, PEAK_BYTES.with(Cell::get) - start)
+}
+
+/// Elements in the game state: 512 KiB of `u64`s.
+const ELEMENTS: usize = 64 * 1024;
+const MAX_PREDICTION: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "hot-join", derive(serde::Serialize, serde::Deserialize))]
+struct BigState {
+    frame: i32,
+    elements: Vec<u64>,
+}
+
+impl BigState {
+    fn new() -> Self {
+        Self {
+            frame: 0,
+            elements: vec![0; ELEMENTS],
+        }
+    }
+
+    /// Changes one element per player input.
+    fn advance(&mut self, inputs: impl IntoIterator<Item = u8>) {
+        self.frame += 1;
+        for (player, input) in inputs.into_iter().enumerate() {
+            let index = (self.frame as usize * 7919 + player * 104_729) % ELEMENTS;
+            self.elements[index] = self.elements[index]
+                .wrapping_mul(31)
+                .wrapping_add(u64::from(input) + 1);
+        }
+    }
+
+    fn checksum(&self) -> u128 {
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64 ^ self.frame as u64;
+        for element in &self.elements {
+            hash = (hash ^ element).wrapping_mul(0x0100_0000_01b3);
+        }
+        u128::from(hash)
+    }
+}
+
+struct BigConfig;
+
+impl Config for BigConfig {
+    type Input = u8;
+    type State = BigState;
+    type Address = SocketAddr;
+}
+
+/// Records each changed element as its index and new value.
+struct ElementDiff;
+
+impl StateDiffCodec<BigState> for ElementDiff {
+    fn diff(&self, prev: &BigState, curr: &BigState) -> Vec<u8> {
+        let mut out = curr.frame.to_le_bytes().to_vec();
+        for (index, (a, b)) in prev.elements.iter().zip(&curr.elements).enumerate() {
+            if a != b {
+                out.extend_from_slice(&(index as u32).to_le_bytes());
+                out.extend_from_slice(&b.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    fn apply(&self, base: &BigState, diff: &[u8]) -> BigState {
+        let (frame, changes) = diff.split_at(4);
+        let mut state = BigState {
+            frame: i32::from_le_bytes(frame.try_into().unwrap()),
+            elements: base.elements.clone(),
+        };
+        for change in changes.chunks_exact(12) {
+            let index = u32::from_le_bytes(change[..4].try_into().unwrap());
+            state.elements[index as usize] = u64::from_le_bytes(change[4..].try_into().unwrap());
+        }
+        state
+    }
+}
+
+fn handle_requests(state: &mut BigState, requests: RequestVec<BigConfig>) {
+    for request in requests {
+        match request {
+            FortressRequest::SaveGameState { cell, frame } => {
+                assert_eq!(state.frame, frame.as_i32());
+                cell.save(frame, Some(state.clone()), Some(state.checksum()));
+            },
+            FortressRequest::LoadGameState { cell, frame } => {
+                *state = cell.load().expect("every loaded frame has a state");
+                assert_eq!(state.frame, frame.as_i32());
+            },
+            FortressRequest::AdvanceFrame { inputs, .. } => {
+                state.advance(inputs.iter().map(|(input, _)| *input));
+            },
+        }
+    }
+}
+
+fn builder(diff_saves: bool) -> SessionBuilder<BigConfig> {
+    let builder = SessionBuilder::<BigConfig>::new()
+        .with_max_prediction_window(MAX_PREDICTION)
+        .with_desync_detection_mode(DesyncDetection::On { interval: 10 });
+    if diff_saves {
+        builder.with_state_diff_codec(Box::new(ElementDiff))
+    } else {
+        builder
+    }
+}
+
+fn synctest_final_state(
+    check_distance: usize,
+    diff_saves: bool,
+) -> Result<BigState, FortressError> {
+    let mut session = builder(diff_saves)
+        .with_num_players(2)?
+        .with_check_distance(check_distance)
+        .start_synctest_session()?;
+    let mut state = BigState::new();
+    for frame in 0..60_u32 {
+        for player in 0..2 {
+            let input = (frame / (3 + player) + player) as u8;
+            session.add_local_input(PlayerHandle::new(player as usize), input)?;
+        }
+        // A rebuilt state that differs from the saved one fails the sync
+        // test's checksum comparison here.
+        handle_requests(&mut state, session.advance_frame()?);
+    }
+    Ok(state)
+}
+
+#[test]
+fn synctest_rebuilds_saved_states_at_every_rollback_depth() -> Result<(), FortressError> {
+    let expected = synctest_final_state(1, false)?;
+    for check_distance in 1..MAX_PREDICTION {
+        assert_eq!(
+            synctest_final_state(check_distance, true)?,
+            expected,
+            "rolling back {check_distance} frames"
+        );
+    }
+    Ok(())
+}
+
+fn peer(
+    socket: impl NonBlockingSocket<SocketAddr> + 'static,
+    local: usize,
+    remote_addr: SocketAddr,
+    clock: &TestClock,
+    diff_saves: bool,
+) -> Result<P2PSession<BigConfig>, FortressError> {
+    let mut builder = builder(diff_saves).with_protocol_config(ProtocolConfig {
+        clock: Some(clock.as_protocol_clock()),
+        ..ProtocolConfig::default()
+    });
+    for handle in 0..2 {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(remote_addr)
+        };
+        builder = builder.add_player(player, PlayerHandle::new(handle))?;
+    }
+    builder.start_p2p_session(socket)
+}
+
+/// Plays two peers whose inputs change at different rates over links with
+/// some latency, so predictions miss and both roll back a few frames.
+/// Returns both final states and the peak heap growth while playing.
+fn play(diff_saves: bool) -> Result<([BigState; 2], i64), FortressError> {
+    let clock = TestClock::new();
+    let chaos = |seed| ChaosConfig::builder().latency_ms(20).seed(seed).build();
+    let (s1, s2, a1, a2) = create_chaos_channel_pair(chaos(1), chaos(2), &clock);
+    let mut sessions = [
+        peer(s1, 0, a2, &clock, diff_saves)?,
+        peer(s2, 1, a1, &clock, diff_saves)?,
+    ];
+    let [first, second] = &mut sessions;
+    synchronize_sessions_deterministic(first, second, &clock, &SyncConfig::default())?;
+
+    let mut states = [BigState::new(), BigState::new()];
+    let (result, growth) = peak_heap_growth(|| -> Result<(), FortressError> {
+        for frame in 0..300_u32 {
+            for (handle, (session, state)) in sessions.iter_mut().zip(&mut states).enumerate() {
+                session.poll_remote_clients();
+                for _ in session.events() {}
+                let input = (frame / (3 + 4 * handle as u32)) as u8;
+                session.add_local_input(PlayerHandle::new(handle), input)?;
+                handle_requests(state, session.advance_frame()?);
+            }
+            clock.advance(POLL_INTERVAL_DETERMINISTIC);
+        }
+        Ok(())
+    });
+    result?;
+    for session in &sessions {
+        assert!(
+            session.last_rollback_cause().is_some(),
+            "the run never rolled back"
+        );
+        assert_eq!(session.metrics().checksums_mismatched, 0);
+    }
+    Ok((states, growth))
+}
+
+#[test]
+fn diff_saves_lower_the_heap_high_water_and_keep_the_simulation() -> Result<(), FortressError> {
+    let (full_states, full_peak) = play(false)?;
+    let (diff_states, diff_peak) = play(true)?;
+
+    assert_eq!(diff_states, full_states);
+    let state_bytes = (ELEMENTS * std::mem::size_of::<u64>()) as i64;
+    // Every frame in the prediction window is saved in full by each peer.
+    assert!(
+        full_peak >= 2 * MAX_PREDICTION as i64 * state_bytes,
+        "full saves peaked at {full_peak} bytes"
+    );
+    // Diff saves keep a keyframe, the newest state and a rebuilt load per
+    // peer, however deep the window.
+    assert!(
+        diff_peak * 3 < full_peak,
+        "diff saves peaked at {diff_peak} bytes vs {full_peak} bytes for full saves"
+    );
+    Ok(())
+}
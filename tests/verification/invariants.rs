@@ -38,7 +38,7 @@
 
 use fortress_rollback::__internal::{InputQueue, PlayerInput, SavedStates, SyncLayer};
 use fortress_rollback::telemetry::{InvariantChecker, InvariantViolation};
-use fortress_rollback::{Config, Frame};
+use fortress_rollback::{Config, Frame, InputStatus};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 
@@ -592,6 +592,60 @@ fn test_first_incorrect_frame_tracking() {
         );
     }
 
+    /// Verify a confirmation that arrives after a misprediction is detected,
+    /// but before the rollback runs, does not discard the inputs the rollback
+    /// replays. Frames are delay-adjusted: with a delay of 2, the input given
+    /// for frame `f` lands at frame `f + 2`.
+    #[test]
+    fn test_discard_keeps_inputs_of_pending_rollback() {
+        let mut queue = InputQueue::<TestConfig>::with_queue_length(0, 64).expect("queue");
+        queue.set_frame_delay(2).expect("valid delay");
+        let value = |frame: i32| if frame == 7 { 99 } else { frame as u8 };
+
+        // Inputs for frames 0-4 land at 2-6; frames 7-12 are predicted.
+        for i in 0..5 {
+            queue.add_input(PlayerInput::new(
+                Frame::new(i),
+                TestInput {
+                    value: value(i + 2),
+                },
+            ));
+        }
+        for i in 7..=12 {
+            let _ = queue.input(Frame::new(i));
+        }
+
+        // The inputs for frames 5-9 land at 7-11 and mispredict frame 7.
+        for i in 5..10 {
+            queue.add_input(PlayerInput::new(
+                Frame::new(i),
+                TestInput {
+                    value: value(i + 2),
+                },
+            ));
+        }
+        assert_eq!(queue.first_incorrect_frame(), Frame::new(7));
+
+        // A newly confirmed frame past the rollback target arrives first.
+        queue.discard_confirmed_frames(Frame::new(10));
+        assert!(
+            queue.check_invariants().is_ok(),
+            "{:?}",
+            queue.check_invariants()
+        );
+
+        // The rollback then replays frames 7-11 from confirmed inputs.
+        queue.reset_prediction();
+        for frame in 7..=11 {
+            let (input, status) = queue
+                .input(Frame::new(frame))
+                .unwrap_or_else(|| panic!("input for frame {frame} was discarded"));
+            assert_eq!(status, InputStatus::Confirmed, "frame {frame}");
+            assert_eq!(input.value, value(frame), "frame {frame}");
+        }
+        assert!(queue.check_invariants().is_ok());
+    }
+
     /// Verify reset_prediction clears prediction state properly.
     #[test]
     fn test_reset_prediction_clears_state() {
@@ -163,7 +163,7 @@ fn run_synctest_session(
                 FortressRequest::LoadGameState { cell, .. } => {
                     state = cell.load().expect("Failed to load state");
                 },
-                FortressRequest::AdvanceFrame { inputs } => {
+                FortressRequest::AdvanceFrame { inputs, .. } => {
                     state.apply_inputs(&inputs);
                 },
             }
@@ -367,7 +367,7 @@ fn test_metamorphic_input_delay_invariance() {
                     FortressRequest::LoadGameState { cell, .. } => {
                         state = cell.load().expect("Failed to load state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         state.apply_inputs(&inputs);
                     },
                 }
@@ -404,7 +404,7 @@ fn test_metamorphic_input_delay_invariance() {
                     FortressRequest::LoadGameState { cell, .. } => {
                         state = cell.load().expect("Failed to load state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         state.apply_inputs(&inputs);
                     },
                 }
@@ -462,7 +462,7 @@ fn test_metamorphic_prediction_window_invariance() {
                     FortressRequest::LoadGameState { cell, .. } => {
                         state = cell.load().expect("Failed to load state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         state.apply_inputs(&inputs);
                     },
                 }
@@ -499,7 +499,7 @@ fn test_metamorphic_prediction_window_invariance() {
                     FortressRequest::LoadGameState { cell, .. } => {
                         state = cell.load().expect("Failed to load state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         state.apply_inputs(&inputs);
                     },
                 }
@@ -908,7 +908,7 @@ fn run_checksum_determinism_test(
                     FortressRequest::LoadGameState { cell, .. } => {
                         state = cell.load().expect("Failed to load state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         state.apply_inputs(&inputs);
                     },
                 }
@@ -991,7 +991,7 @@ fn test_synctest_no_desync_with_correct_checksums() {
                     FortressRequest::LoadGameState { cell, .. } => {
                         state = cell.load().expect("Failed to load state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         state.apply_inputs(&inputs);
                     },
                 }
@@ -1144,7 +1144,7 @@ fn run_checksum_determinism_test(
                     FortressRequest::LoadGameState { cell, .. } => {
                         state = cell.load().expect("Failed to load state");
                     },
-                    FortressRequest::AdvanceFrame { inputs } => {
+                    FortressRequest::AdvanceFrame { inputs, .. } => {
                         state.apply_inputs(&inputs);
                     },
                 }
@@ -318,10 +318,11 @@ fn prop_runtime_delay_increase_gap_fills_and_preserves_sequence(
         prop_assert!(queue.check_invariants().is_ok());
     }
 
-    /// Runtime delay decrease: decreasing delay after inputs exist is rejected
-    /// and leaves observable queue state unchanged.
+    /// Runtime delay decrease: decreasing delay after inputs exist drops the
+    /// newest queued inputs the smaller delay overlaps, keeps the older ones,
+    /// and lets the next input take the first dropped frame.
     #[test]
-    fn prop_runtime_delay_decrease_is_rejected_without_state_change(
+    fn prop_runtime_delay_decrease_drops_newest_inputs(
         initial_count in 2usize..24,
         current_delay in 1usize..8,
         decrease_by in 1usize..8,
@@ -329,6 +330,7 @@ fn prop_runtime_delay_decrease_is_rejected_without_state_change(
     ) {
         let requested_delay = current_delay.saturating_sub(decrease_by);
         prop_assume!(requested_delay < current_delay);
+        let delta = (current_delay - requested_delay) as i32;
 
         let mut queue = InputQueue::<TestConfig>::with_queue_length(0, 96).expect("queue");
         queue.set_frame_delay(current_delay).expect("initial delay should be valid");
@@ -340,28 +342,32 @@ fn prop_runtime_delay_decrease_is_rejected_without_state_change(
         }
 
         let last_frame = Frame::new(initial_count as i32 - 1 + current_delay as i32);
-        let last_confirmed_before = queue
-            .confirmed_input(last_frame)
-            .expect("last delayed frame should be confirmed");
+        let kept_frame = Frame::new(last_frame.as_i32() - delta);
+        let kept_before = queue
+            .confirmed_input(kept_frame)
+            .expect("kept frame should be confirmed");
 
-        let err = queue
+        queue
             .set_frame_delay(requested_delay)
-            .expect_err("mid-session delay decrease should be rejected");
-        prop_assert!(
-            matches!(
-                err,
-                fortress_rollback::FortressError::InvalidRequestStructured {
-                    kind: fortress_rollback::InvalidRequestKind::InputDelayDecreaseUnsupported { .. }
-                }
-            ),
-            "expected InputDelayDecreaseUnsupported, got {err:?}"
+            .expect("mid-session delay decrease should be accepted");
+        prop_assert_eq!(queue.frame_delay(), requested_delay);
+        prop_assert_eq!(
+            queue.confirmed_input(kept_frame).expect("kept frame stays confirmed"),
+            kept_before
         );
+        prop_assert!(queue.confirmed_input(last_frame).is_err());
+        prop_assert!(queue.check_invariants().is_ok());
 
-        prop_assert_eq!(queue.frame_delay(), current_delay);
-        let last_confirmed_after = queue
-            .confirmed_input(last_frame)
-            .expect("last delayed frame should remain confirmed");
-        prop_assert_eq!(last_confirmed_after, last_confirmed_before);
+        let value = base_value.wrapping_add(100);
+        let added = queue.add_input(PlayerInput::new(
+            Frame::new(initial_count as i32),
+            TestInput { value },
+        ));
+        prop_assert_eq!(added, Frame::new(kept_frame.as_i32() + 1));
+        prop_assert_eq!(
+            queue.confirmed_input(added).expect("new input is queued").input.value,
+            value
+        );
         prop_assert!(queue.check_invariants().is_ok());
     }
 
@@ -562,8 +568,8 @@ fn prop_input_queue_delay_increase_gap_fill_sequence(
         prop_assert!(queue.check_invariants().is_ok());
     }
 
-    /// Runtime delay decreases after inputs exist are rejected and leave the
-    /// confirmed input stream unchanged.
+    /// Runtime delay decreases that would drop an input already read are
+    /// rejected and leave the confirmed input stream unchanged.
     #[test]
     fn prop_input_queue_delay_decrease_rejected_without_mutation(
         initial_delay in 1usize..6,
@@ -582,6 +588,8 @@ fn prop_input_queue_delay_decrease_rejected_without_mutation(
             ));
             prop_assert_eq!(accepted, Frame::new((frame + initial_delay) as i32));
         }
+        let newest = Frame::new((num_inputs - 1 + initial_delay) as i32);
+        prop_assert!(queue.input(newest).is_some());
 
         let confirmed_before: Vec<_> = (0..num_inputs)
             .map(|frame| {
@@ -1041,11 +1041,12 @@ fn z3_proof_frame_delay_increase_monotone_gap_fill() {
     });
 }
 
-/// Z3 Proof: Mid-session delay decreases are rejected without state mutation
+/// Z3 Proof: Rejected mid-session delay decreases leave the state unchanged
 ///
-/// Models the `InputDelayDecreaseUnsupported` branch: when inputs exist and
-/// `requested_delay < current_delay`, the operation is rejected and preserves
-/// delay and queue progress.
+/// Models the `InputDelayDecreaseUnsupported` branch: when inputs exist,
+/// `requested_delay < current_delay` and an input the decrease would drop was
+/// already read, the operation is rejected and preserves delay and queue
+/// progress.
 #[test]
 fn z3_proof_frame_delay_decrease_rejected_preserves_state() {
     let cfg = Config::new();